@@ -14,6 +14,7 @@ use crate::snapshot::{
     FileSnapshot, GhCommentSummary, GhReviewSummary, GhStatusCache, GhUser, GithubStatusSnapshot,
     LoadingState, MetaCache, PendingAiReplies, PrInfo, WatchStatusState,
 };
+use er_engine::ai::store::Sidecar;
 use er_engine::ai::CommentType;
 #[cfg(test)]
 use er_engine::app::CardAiInvocation;
@@ -2201,56 +2202,37 @@ pub fn remove_finding_thread(
     Ok(snap_from(&app, &state))
 }
 
-fn write_json_atomic<T: serde::Serialize>(path: &str, value: &T) -> Result<(), String> {
-    let tmp = format!("{path}.tmp");
-    let body = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
-    std::fs::write(&tmp, body).map_err(|e| e.to_string())?;
-    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
-}
-
 fn mark_thread_resolved_in_files(
     id: &str,
-    q_path: &str,
-    notes_path: &str,
-    gc_path: &str,
+    er_dir: &str,
+    comments_dir: &str,
 ) -> Result<bool, String> {
-    use er_engine::ai::{ErGitHubComments, ErNotes, ErQuestions};
-    if let Ok(text) = std::fs::read_to_string(q_path) {
-        if let Ok(mut qs) = serde_json::from_str::<ErQuestions>(&text) {
-            if let Some(q) = qs.questions.iter_mut().find(|q| q.id == id) {
-                if !q.resolved {
-                    q.resolved = true;
-                    write_json_atomic(q_path, &qs)?;
-                }
-                return Ok(true);
-            }
-        }
-    }
-
-    if let Ok(text) = std::fs::read_to_string(notes_path) {
-        if let Ok(mut ns) = serde_json::from_str::<ErNotes>(&text) {
-            if let Some(n) = ns.notes.iter_mut().find(|n| n.id == id) {
-                if !n.resolved {
-                    n.resolved = true;
-                    write_json_atomic(notes_path, &ns)?;
-                }
-                return Ok(true);
-            }
-        }
-    }
-
-    if let Ok(text) = std::fs::read_to_string(gc_path) {
-        if let Ok(mut gc) = serde_json::from_str::<ErGitHubComments>(&text) {
-            if let Some(c) = gc.comments.iter_mut().find(|c| c.id == id) {
-                if !c.resolved {
-                    c.resolved = true;
-                    write_json_atomic(gc_path, &gc)?;
-                }
-                return Ok(true);
-            }
-        }
-    }
-    Ok(false)
+    use er_engine::ai::{store, ErGitHubComments, ErNotes, ErQuestions};
+    let found =
+        |r: anyhow::Result<Option<bool>>| r.map(|f| f == Some(true)).map_err(|e| e.to_string());
+    let resolved = found(store::modify(er_dir, |qs: &mut ErQuestions| {
+        Ok(qs
+            .questions
+            .iter_mut()
+            .find(|q| q.id == id)
+            .map(|q| q.resolved = true)
+            .is_some())
+    }))? || found(store::modify(er_dir, |ns: &mut ErNotes| {
+        Ok(ns
+            .notes
+            .iter_mut()
+            .find(|n| n.id == id)
+            .map(|n| n.resolved = true)
+            .is_some())
+    }))? || found(store::modify(comments_dir, |gc: &mut ErGitHubComments| {
+        Ok(gc
+            .comments
+            .iter_mut()
+            .find(|c| c.id == id)
+            .map(|c| c.resolved = true)
+            .is_some())
+    }))?;
+    Ok(resolved)
 }
 
 #[tauri::command]
@@ -2258,10 +2240,7 @@ pub fn resolve_thread(id: String, state: State<AppState>) -> Result<AppSnapshot,
     let mut app = state.app.lock().map_err(|e| e.to_string())?;
 
     let tab = app.tab();
-    let q_path = format!("{}/questions.json", tab.er_dir());
-    let notes_path = format!("{}/notes.json", tab.er_dir());
-    let gc_path = tab.github_comments_path();
-    let changed = mark_thread_resolved_in_files(&id, &q_path, &notes_path, &gc_path)?;
+    let changed = mark_thread_resolved_in_files(&id, &tab.er_dir(), &tab.github_comments_dir())?;
     if !changed {
         return Err(format!("Thread not found or already resolved: {id}"));
     }
@@ -2523,7 +2502,7 @@ pub fn submit_github_review(
         pr_number,
         is_remote,
         repo_root,
-        comments_path,
+        comments_dir,
         file_anchors,
         old_file_anchors,
     ) = {
@@ -2531,7 +2510,7 @@ pub fn submit_github_review(
         let tab = app.tab();
         let is_remote = tab.is_remote();
         let repo_root = tab.repo_root.clone();
-        let comments_path = tab.github_comments_path();
+        let comments_dir = tab.github_comments_dir();
         let mut file_anchors: std::collections::HashMap<String, Vec<(usize, usize)>> =
             std::collections::HashMap::new();
         let mut old_file_anchors: std::collections::HashMap<String, Vec<(usize, usize)>> =
@@ -2589,22 +2568,16 @@ pub fn submit_github_review(
             pr_number,
             is_remote,
             repo_root,
-            comments_path,
+            comments_dir,
             file_anchors,
             old_file_anchors,
         )
     };
 
     // Collect pending line comments into the batch format.
-    let gc: ErGitHubComments = std::fs::read_to_string(&comments_path)
-        .ok()
-        .and_then(|s| serde_json::from_str::<ErGitHubComments>(&s).ok())
-        .unwrap_or(ErGitHubComments {
-            version: 1,
-            diff_hash: String::new(),
-            github: None,
-            comments: vec![],
-        });
+    let gc: ErGitHubComments = er_engine::ai::store::load(&comments_dir)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(|| ErGitHubComments::empty(""));
 
     // Reject early if any unsynced local comment has no line anchor — those can
     // never be part of a GitHub review batch and would silently get marked synced
@@ -2768,7 +2741,6 @@ pub fn submit_github_review(
         Err(e) => return Err(gh_review_submit_err(e)),
     }
 
-    let mut gc_to_write = gc;
     if decision_only_fallback {
         let skipped = batch.len();
         let mut app = state.app.lock().map_err(|e| e.to_string())?;
@@ -2776,17 +2748,17 @@ pub fn submit_github_review(
             "{event} submitted, but {skipped} inline comment(s) could not be bundled (stale vs PR head). Refresh the diff, then push them individually."
         ));
     } else if !submitted_ids.is_empty() {
+        // Marked on the file as it is now, keeping comments added meanwhile
         let submitted: std::collections::HashSet<String> = submitted_ids.into_iter().collect();
-        let mut touched = false;
-        for c in &mut gc_to_write.comments {
-            if submitted.contains(&c.id) {
-                c.synced = true;
-                touched = true;
+        er_engine::ai::store::modify(&comments_dir, |gc: &mut ErGitHubComments| {
+            for c in &mut gc.comments {
+                if submitted.contains(&c.id) {
+                    c.synced = true;
+                }
             }
-        }
-        if touched {
-            write_json_atomic(&comments_path, &gc_to_write)?;
-        }
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
     }
 
     let mut app = state.app.lock().map_err(|e| e.to_string())?;
@@ -3485,13 +3457,10 @@ fn eligible_github_comment_count(tab: &er_engine::app::TabState) -> usize {
     if let Some(gc) = tab.ai.github_comments.as_ref() {
         return er_engine::ai::count_eligible_github_comments(gc);
     }
-    let path = std::path::Path::new(&tab.er_dir()).join("github-comments.json");
-    if let Ok(content) = std::fs::read_to_string(&path) {
-        if let Ok(gc) = serde_json::from_str::<er_engine::ai::ErGitHubComments>(&content) {
-            return er_engine::ai::count_eligible_github_comments(&gc);
-        }
-    }
-    0
+    er_engine::ai::store::load::<er_engine::ai::ErGitHubComments>(&tab.er_dir())
+        .ok()
+        .flatten()
+        .map_or(0, |gc| er_engine::ai::count_eligible_github_comments(&gc))
 }
 
 #[tauri::command]
@@ -4609,6 +4578,7 @@ fn do_open_remote_pr(
         }
     }
     let pr_ref = er_engine::github::PrRef {
+        host: er_engine::github::host(),
         owner: owner.to_string(),
         repo: repo.to_string(),
         number,
@@ -4691,7 +4661,7 @@ fn fetch_single_pr_for_remote(remote: &str, pr_number: u64) -> Result<PrInfo, St
         latest_reviews: Vec<RawReview>,
     }
 
-    let out = std::process::Command::new("gh")
+    let out = er_engine::github::gh_command()
         .args([
             "pr",
             "view",
@@ -4977,7 +4947,7 @@ fn kick_background_branch_refresh(
 ) {
     std::thread::spawn(move || {
         // Fetch the base branch from origin so the local diff is up-to-date.
-        let base_strip = base_branch.strip_prefix("origin/").unwrap_or(&base_branch);
+        let base_strip = er_engine::github::strip_remote_prefix(&repo_root, &base_branch);
         match er_engine::github::fetch_base_branch_ref(&repo_root, base_strip) {
             Ok(base_ref) => {
                 let mut refreshed_active_tab = false;
@@ -5267,17 +5237,19 @@ fn run_gh_pr_view_for_open(repo_root: &str, pr_number: u64) -> Result<PrOpenMeta
         head_ref_oid: String,
         #[serde(default, rename = "updatedAt")]
         updated_at: String,
+        #[serde(default, rename = "createdAt")]
+        created_at: String,
         #[serde(default)]
         reviews: Vec<RawReview>,
     }
 
-    let out = std::process::Command::new("gh")
+    let out = er_engine::github::gh_command()
         .args([
             "pr",
             "view",
             &pr_number.to_string(),
             "--json",
-            "number,title,body,state,author,url,baseRefName,headRefName,headRefOid,updatedAt,reviews,commits",
+            "number,title,body,state,author,url,baseRefName,headRefName,headRefOid,updatedAt,createdAt,reviews,commits",
         ])
         .current_dir(repo_root)
         .output()
@@ -5323,6 +5295,9 @@ fn run_gh_pr_view_for_open(repo_root: &str, pr_number: u64) -> Result<PrOpenMeta
             head_branch: raw.head_ref_name,
             checks: Vec::new(),
             reviewers,
+            created_at: raw.created_at,
+            head_oid: String::new(),
+            labels: Vec::new(),
         },
         pr_commits,
     })
@@ -5359,6 +5334,9 @@ fn pr_overview_from_hint(
         head_branch: hint.head_ref.clone(),
         checks: Vec::new(),
         reviewers: Vec::new(),
+        created_at: String::new(),
+        head_oid: String::new(),
+        labels: Vec::new(),
     }
 }
 
@@ -6780,6 +6758,9 @@ pub fn dismiss_finding(finding_id: String, state: State<AppState>) -> Result<App
         save_finding_promotions(&er_dir, &promotions)
             .map_err(|e| format!("Failed to update finding promotions: {e}"))?;
     }
+    app.publish_event(er_engine::events::Event::FindingDismissed {
+        finding_id: finding_id.clone(),
+    });
 
     app.tab_mut().reload_ai_state();
 
@@ -7641,6 +7622,7 @@ fn poll_impl(state: &AppState) -> Result<PollResponse, String> {
         eprintln!("[bg] poll: pre poll_background_tasks snapshots={pre}");
     }
     app.poll_background_tasks();
+    app.poll_comment_push();
     let post = app.background_task_snapshots().len();
     if debug_bg || (er_engine::app::debug_bg_enabled() && post > 0) {
         eprintln!("[bg] poll: post poll_background_tasks snapshots={post}");
@@ -8609,6 +8591,7 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".to_string(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 in_reply_to: None,
                 author: "You".to_string(),
                 promoted_to: None,
@@ -8618,13 +8601,8 @@ mod tests {
         std::fs::write(&q_path, serde_json::to_string_pretty(&questions).unwrap()).unwrap();
         std::fs::write(&gc_path, r#"{"version":1,"diff_hash":"x","comments":[]}"#).unwrap();
 
-        let changed = mark_thread_resolved_in_files(
-            "q-1",
-            &q_path.to_string_lossy(),
-            &tmp.path().join("notes.json").to_string_lossy(),
-            &gc_path.to_string_lossy(),
-        )
-        .unwrap();
+        let dir = tmp.path().to_string_lossy();
+        let changed = mark_thread_resolved_in_files("q-1", &dir, &dir).unwrap();
         assert!(changed);
         let updated: ErQuestions =
             serde_json::from_str(&std::fs::read_to_string(&q_path).unwrap()).unwrap();
@@ -8664,19 +8642,17 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".to_string(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 finding_ref: None,
                 side: "RIGHT".to_string(),
+                edit_base: None,
+                remote_body: None,
             }],
         };
         std::fs::write(&gc_path, serde_json::to_string_pretty(&comments).unwrap()).unwrap();
 
-        let changed = mark_thread_resolved_in_files(
-            "c-1",
-            &q_path.to_string_lossy(),
-            &tmp.path().join("notes.json").to_string_lossy(),
-            &gc_path.to_string_lossy(),
-        )
-        .unwrap();
+        let dir = tmp.path().to_string_lossy();
+        let changed = mark_thread_resolved_in_files("c-1", &dir, &dir).unwrap();
         assert!(changed);
         let updated: ErGitHubComments =
             serde_json::from_str(&std::fs::read_to_string(&gc_path).unwrap()).unwrap();
@@ -8900,8 +8876,11 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".to_string(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             finding_ref: None,
             side: "RIGHT".to_string(),
+            edit_base: None,
+            remote_body: None,
         }
     }
 
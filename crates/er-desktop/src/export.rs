@@ -465,6 +465,7 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".into(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             in_reply_to: None,
             author: "you".into(),
             promoted_to: None,
@@ -496,8 +497,11 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".into(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             finding_ref: None,
             side: "RIGHT".into(),
+            edit_base: None,
+            remote_body: None,
         }
     }
 
@@ -766,7 +766,7 @@ fn main() {
     {
         let gh_user_bg = Arc::clone(&gh_user);
         std::thread::spawn(move || {
-            if let Ok(out) = std::process::Command::new("gh")
+            if let Ok(out) = er_engine::github::gh_command()
                 .args(["api", "user", "--jq", ".login"])
                 .output()
             {
@@ -1067,7 +1067,7 @@ fn main() {
                 }
 
                 // Phase 2: no lock — ask gh for the PR's latest head oid.
-                let out = std::process::Command::new("gh")
+                let out = er_engine::github::gh_command()
                     .args([
                         "pr",
                         "view",
@@ -1404,6 +1404,8 @@ fn main() {
                         got_event = true;
                         while let Ok(WatchEvent::FilesChanged(_)) = rx.try_recv() {}
                     }
+                    // AI dirs aren't watched here; the poll reloads AI data
+                    Ok(WatchEvent::AiFilesChanged(_)) => {}
                     Err(mpsc::RecvTimeoutError::Timeout)
                     | Err(mpsc::RecvTimeoutError::Disconnected) => {}
                 }
@@ -344,7 +344,7 @@ fn current_branch(root_path: &str) -> Option<String> {
 }
 
 fn query_remote(root_path: &str) -> Option<String> {
-    let out = std::process::Command::new("gh")
+    let out = er_engine::github::gh_command()
         .args([
             "repo",
             "view",
@@ -1740,11 +1740,15 @@ fn build_snapshot_inner(
     let input_mode = match &app.input_mode {
         InputMode::Normal => "normal",
         InputMode::Search => "search",
+        InputMode::Find => "find",
         InputMode::Comment => "comment",
         InputMode::Filter => "filter",
         InputMode::Commit => "commit",
         InputMode::Confirm(_) => "confirm",
         InputMode::RemoteUrl => "remoteurl",
+        InputMode::SkipReason => "skipreason",
+        InputMode::QuickDiffRef => "quickdiffref",
+        InputMode::Assignee => "assignee",
     };
 
     let (reviewed_count, total_count) = tab.active_reviewed_count();
@@ -3724,8 +3728,11 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".to_string(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             finding_ref: None,
             side: "RIGHT".to_string(),
+            edit_base: None,
+            remote_body: None,
         }
     }
 
@@ -3851,6 +3858,9 @@ mod tests {
             head_branch: "DEV-3884/data-table-sorting".to_string(),
             checks: Vec::new(),
             reviewers: Vec::new(),
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
 
         let pr = build_pr_snapshot(&tab).expect("pr snapshot");
@@ -239,6 +239,7 @@ fn rebuild_remote_pr(d: &TabDescriptor, lazy: bool) -> Result<er_engine::app::Ta
     let repo = d.pr_repo.clone().context("remote_pr missing repo")?;
     let number = d.pr_number.context("remote_pr missing number")?;
     let pr_ref = er_engine::github::PrRef {
+        host: er_engine::github::host(),
         owner,
         repo,
         number,
@@ -0,0 +1,111 @@
+//! J/K navigation on a large review: the hint order rebuilt and re-sorted on
+//! every press (as before the hint index) against the kept `HintIndex`.
+//!
+//! ```sh
+//! cargo bench -p er-engine --bench hint_navigation
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use er_engine::ai::{AiState, ErQuestions, ErReview};
+use serde_json::json;
+
+const FILES: usize = 400;
+const QUESTIONS: usize = 4000;
+const FINDINGS: usize = 3000;
+
+fn large_review() -> AiState {
+    let questions: Vec<_> = (0..QUESTIONS)
+        .map(|i| {
+            json!({
+                "id": format!("q-{}", i),
+                "file": format!("src/module_{:03}/file.rs", (i * 7) % FILES),
+                "hunk_index": i % 12,
+                "line_start": (i * 13) % 900,
+                "text": "Why is this needed?",
+                "in_reply_to": (i % 5 == 4).then(|| format!("q-{}", i - 1)),
+            })
+        })
+        .collect();
+    let mut files = serde_json::Map::new();
+    for i in 0..FINDINGS {
+        let file = format!("src/module_{:03}/file.rs", (i * 11) % FILES);
+        let entry = files
+            .entry(file)
+            .or_insert_with(|| json!({ "risk": "medium", "findings": [] }));
+        entry["findings"].as_array_mut().unwrap().push(json!({
+            "id": format!("f-{}", i),
+            "severity": "low",
+            "title": "Unchecked result",
+            "hunk_index": i % 9,
+            "line_start": (i * 17) % 900,
+        }));
+    }
+    let mut state = AiState::default();
+    state.questions = Some(
+        serde_json::from_value::<ErQuestions>(json!({
+            "version": 1,
+            "diff_hash": "bench",
+            "questions": questions,
+        }))
+        .unwrap(),
+    );
+    state.review = Some(
+        serde_json::from_value::<ErReview>(json!({
+            "version": 1,
+            "diff_hash": "bench",
+            "files": files,
+        }))
+        .unwrap(),
+    );
+    state
+}
+
+/// Average time of `press` over `presses` presses.
+fn bench(name: &str, presses: usize, mut press: impl FnMut(usize)) -> Duration {
+    press(0);
+    let started = Instant::now();
+    for i in 0..presses {
+        press(i);
+    }
+    let per_press = started.elapsed() / presses as u32;
+    println!("{:<28} {:>12?} per press", name, per_press);
+    per_press
+}
+
+fn main() {
+    let state = large_review();
+    let hints = state.hint_index().hints.len();
+    println!(
+        "{} hints, {} line-anchored findings in {} files\n",
+        hints,
+        state.hint_index().findings.len(),
+        FILES
+    );
+    let ids: Vec<String> = state
+        .hint_index()
+        .hints
+        .iter()
+        .map(|h| h.3.clone())
+        .collect();
+
+    // The order collected and sorted on every J, then a linear search for
+    // the focused hint
+    let rebuilt = bench("rebuilt on every press", 200, |i| {
+        state.rebuild_comment_index();
+        let all = state.all_hints_ordered();
+        let id = &ids[(i * 37) % ids.len()];
+        let pos = all.iter().position(|h| &h.3 == id);
+        black_box(pos.map(|p| all[(p + 1) % all.len()].clone()));
+    });
+    let indexed = bench("kept hint index", 20_000, |i| {
+        let index = state.hint_index();
+        let pos = index.hint_position(&ids[(i * 37) % ids.len()]);
+        black_box(pos.map(|p| index.hints[(p + 1) % index.hints.len()].clone()));
+    });
+    println!(
+        "\n{:.0}× faster per press",
+        rebuilt.as_secs_f64() / indexed.as_secs_f64().max(1e-9)
+    );
+}
@@ -0,0 +1,125 @@
+//! What a finding keeps when an agent rewrites `review.json` from scratch.
+//! Validation marks findings resolved or dropped and agents reply to them in
+//! place; a regenerated review usually starts those fields over. A finding
+//! that comes back with the same id in the same file keeps them, unless the
+//! new copy sets them itself.
+
+use std::collections::HashMap;
+
+use super::{AiResponse, AiState, Confidence};
+
+/// One finding's lifecycle fields.
+#[derive(Debug, Clone)]
+struct Lifecycle {
+    resolved: bool,
+    resolved_note: String,
+    resolved_at: String,
+    dropped: bool,
+    responses: Vec<AiResponse>,
+}
+
+/// Lifecycles of the loaded findings with something to keep, by
+/// `(file, id)`.
+#[derive(Debug, Default)]
+pub struct FindingLifecycles(HashMap<(String, String), Lifecycle>);
+
+impl FindingLifecycles {
+    pub fn of(ai: &AiState) -> Self {
+        let mut kept = HashMap::new();
+        for (path, fr) in ai.review.iter().flat_map(|r| r.files.iter()) {
+            for f in &fr.findings {
+                let dropped = f.confidence == Confidence::Dropped;
+                if f.resolved || dropped || !f.responses.is_empty() {
+                    kept.insert(
+                        (path.clone(), f.id.clone()),
+                        Lifecycle {
+                            resolved: f.resolved,
+                            resolved_note: f.resolved_note.clone(),
+                            resolved_at: f.resolved_at.clone(),
+                            dropped,
+                            responses: f.responses.clone(),
+                        },
+                    );
+                }
+            }
+        }
+        FindingLifecycles(kept)
+    }
+
+    /// Give the findings in `ai` back what their regeneration dropped.
+    /// Returns how many got something back.
+    pub fn restore(&self, ai: &mut AiState) -> usize {
+        if self.0.is_empty() {
+            return 0;
+        }
+        let mut restored = 0;
+        for (path, fr) in ai.review.iter_mut().flat_map(|r| r.files.iter_mut()) {
+            for f in &mut fr.findings {
+                let Some(old) = self.0.get(&(path.clone(), f.id.clone())) else {
+                    continue;
+                };
+                let mut changed = false;
+                if old.resolved && !f.resolved && f.resolved_at.is_empty() {
+                    f.resolved = true;
+                    f.resolved_note = old.resolved_note.clone();
+                    f.resolved_at = old.resolved_at.clone();
+                    changed = true;
+                }
+                if old.dropped && f.confidence == Confidence::default() {
+                    f.confidence = Confidence::Dropped;
+                    changed = true;
+                }
+                if !old.responses.is_empty() && f.responses.is_empty() {
+                    f.responses = old.responses.clone();
+                    changed = true;
+                }
+                restored += usize::from(changed);
+            }
+        }
+        restored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ErReview;
+
+    fn review(findings: serde_json::Value) -> AiState {
+        let review: ErReview = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "diff_hash": "h",
+            "files": { "src/a.rs": { "risk": "medium", "findings": findings } },
+        }))
+        .unwrap();
+        let mut ai = AiState::default();
+        ai.review = Some(review);
+        ai
+    }
+
+    #[test]
+    fn regenerated_findings_keep_resolution_dismissal_and_replies() {
+        let before = review(serde_json::json!([
+            { "id": "f-1", "severity": "high", "title": "Leak", "resolved": true,
+              "resolved_note": "closed on drop", "resolved_at": "2026-01-02T00:00:00Z" },
+            { "id": "f-2", "severity": "low", "title": "Naming", "confidence": "dropped",
+              "responses": [{ "id": "r-1", "text": "Not an issue" }] },
+            { "id": "f-3", "severity": "low", "title": "Typo" },
+        ]));
+        let lifecycles = FindingLifecycles::of(&before);
+
+        let mut after = review(serde_json::json!([
+            { "id": "f-1", "severity": "high", "title": "Leak (reworded)" },
+            { "id": "f-2", "severity": "low", "title": "Naming", "confidence": "confirmed" },
+            { "id": "f-3", "severity": "low", "title": "Typo" },
+        ]));
+        assert_eq!(lifecycles.restore(&mut after), 2);
+        let findings = &after.review.as_ref().unwrap().files["src/a.rs"].findings;
+        assert!(findings[0].resolved);
+        assert_eq!(findings[0].resolved_note, "closed on drop");
+        // The new copy's own confidence wins; the replies come back
+        assert_eq!(findings[1].confidence, Confidence::Confirmed);
+        assert_eq!(findings[1].responses.len(), 1);
+        assert!(!findings[2].resolved);
+    }
+}
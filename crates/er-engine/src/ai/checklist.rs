@@ -0,0 +1,260 @@
+//! Editing the review checklist and instantiating it from templates.
+//!
+//! The checklist lives in `.er/checklist.json` per branch. Besides the items
+//! an AI review writes, the reviewer can add, edit, remove and reorder items
+//! and tie them to files. Templates are team-standard checklists kept as
+//! JSON in the repo (`.er-checklists/<name>.json`, committed) or in managed
+//! storage (`<storage_root>/checklists/<name>.json`, personal):
+//!
+//! ```json
+//! { "items": [{ "text": "Migrations are reversible", "category": "db",
+//!               "related_files": ["migrations/"] }] }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChecklistItem, ErChecklist};
+
+/// Repo-relative directory holding shared checklist templates.
+pub const CHECKLIST_TEMPLATE_DIR: &str = ".er-checklists";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChecklistTemplate {
+    /// File stem; not stored in the file
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub items: Vec<ChecklistTemplateItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistTemplateItem {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_files: Vec<String>,
+}
+
+/// Template directories, repo first.
+pub fn checklist_template_dirs(repo_root: &str) -> Vec<PathBuf> {
+    vec![
+        Path::new(repo_root).join(CHECKLIST_TEMPLATE_DIR),
+        crate::storage::storage_root().join("checklists"),
+    ]
+}
+
+/// Every readable template, sorted by name. A repo template shadows a
+/// personal one of the same name.
+pub fn load_checklist_templates(repo_root: &str) -> Vec<ChecklistTemplate> {
+    let mut templates: Vec<ChecklistTemplate> = Vec::new();
+    for dir in checklist_template_dirs(repo_root) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            if templates.iter().any(|t| t.name == name) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            match serde_json::from_str::<ChecklistTemplate>(&content) {
+                Ok(mut template) => {
+                    template.name = name;
+                    templates.push(template);
+                }
+                Err(e) => crate::debug_log::warn(
+                    "checklist",
+                    format!("skipping template {}: {}", path.display(), e),
+                ),
+            }
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+fn new_item_id(checklist: &ErChecklist) -> String {
+    let mut n = checklist.items.len() + 1;
+    loop {
+        let id = format!("user-{}", n);
+        if !checklist.items.iter().any(|i| i.id == id) {
+            return id;
+        }
+        n += 1;
+    }
+}
+
+impl ErChecklist {
+    /// Empty checklist for a diff.
+    pub fn new(diff_hash: &str) -> Self {
+        ErChecklist {
+            version: 1,
+            diff_hash: diff_hash.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Insert an unchecked item at `at` (clamped). Returns its index.
+    pub fn insert_item(&mut self, at: usize, text: &str) -> usize {
+        let at = at.min(self.items.len());
+        let item = ChecklistItem {
+            id: new_item_id(self),
+            text: text.to_string(),
+            category: String::new(),
+            checked: false,
+            related_findings: Vec::new(),
+            related_files: Vec::new(),
+        };
+        self.items.insert(at, item);
+        at
+    }
+
+    /// Replace the text of item `index`. False when there is no such item.
+    pub fn set_item_text(&mut self, index: usize, text: &str) -> bool {
+        match self.items.get_mut(index) {
+            Some(item) => {
+                item.text = text.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_item(&mut self, index: usize) -> Option<ChecklistItem> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    /// Swap item `index` with its neighbour. Returns the item's new index.
+    pub fn move_item(&mut self, index: usize, down: bool) -> usize {
+        if index >= self.items.len() {
+            return index;
+        }
+        let target = if down {
+            (index + 1).min(self.items.len() - 1)
+        } else {
+            index.saturating_sub(1)
+        };
+        self.items.swap(index, target);
+        target
+    }
+
+    /// Tie `path` to item `index`, or untie it when already there. Returns
+    /// whether it is now associated; `None` when there is no such item.
+    pub fn toggle_related_file(&mut self, index: usize, path: &str) -> Option<bool> {
+        let item = self.items.get_mut(index)?;
+        if let Some(pos) = item.related_files.iter().position(|f| f == path) {
+            item.related_files.remove(pos);
+            Some(false)
+        } else {
+            item.related_files.push(path.to_string());
+            Some(true)
+        }
+    }
+
+    /// Append `template`'s items, unchecked, skipping texts already present.
+    /// Returns how many were added.
+    pub fn apply_template(&mut self, template: &ChecklistTemplate) -> usize {
+        let mut added = 0;
+        for t in &template.items {
+            if self.items.iter().any(|i| i.text == t.text) {
+                continue;
+            }
+            let at = self.insert_item(self.items.len(), &t.text);
+            let item = &mut self.items[at];
+            item.category = t.category.clone();
+            item.related_files = t.related_files.clone();
+            added += 1;
+        }
+        added
+    }
+
+    /// The items as a template, without check state or finding links.
+    pub fn to_template(&self, name: &str) -> ChecklistTemplate {
+        ChecklistTemplate {
+            name: name.to_string(),
+            items: self
+                .items
+                .iter()
+                .map(|i| ChecklistTemplateItem {
+                    text: i.text.clone(),
+                    category: i.category.clone(),
+                    related_files: i.related_files.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_operations_keep_ids_unique_and_indices_in_range() {
+        let mut checklist = ErChecklist::new("h");
+        assert_eq!(checklist.insert_item(5, "tests added"), 0);
+        assert_eq!(checklist.insert_item(0, "docs updated"), 0);
+        assert_ne!(checklist.items[0].id, checklist.items[1].id);
+
+        assert!(checklist.set_item_text(1, "tests cover the edge case"));
+        assert!(!checklist.set_item_text(9, "nope"));
+        assert_eq!(checklist.move_item(0, true), 1);
+        assert_eq!(checklist.items[1].text, "docs updated");
+        assert_eq!(checklist.move_item(0, false), 0);
+
+        assert_eq!(checklist.toggle_related_file(0, "src/a.rs"), Some(true));
+        assert_eq!(checklist.toggle_related_file(0, "src/a.rs"), Some(false));
+        assert_eq!(checklist.toggle_related_file(7, "src/a.rs"), None);
+
+        assert_eq!(
+            checklist.remove_item(0).map(|i| i.text),
+            Some("tests cover the edge case".into())
+        );
+        assert!(checklist.remove_item(3).is_none());
+        let id = checklist.items[0].id.clone();
+        checklist.insert_item(1, "new");
+        assert_ne!(checklist.items[1].id, id);
+    }
+
+    #[test]
+    fn templates_load_from_repo_and_apply_without_duplicates() {
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path().join(CHECKLIST_TEMPLATE_DIR);
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("backend.json"),
+            r#"{"items":[{"text":"Migrations reversible","category":"db","related_files":["migrations/"]},{"text":"Logs have no PII"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let templates = load_checklist_templates(&repo.path().to_string_lossy());
+        let backend = templates.iter().find(|t| t.name == "backend").unwrap();
+        assert_eq!(backend.items.len(), 2);
+        assert!(templates.iter().all(|t| t.name != "broken"));
+
+        let mut checklist = ErChecklist::new("h");
+        checklist.insert_item(0, "Logs have no PII");
+        assert_eq!(checklist.apply_template(backend), 1);
+        assert_eq!(checklist.items[1].category, "db");
+        assert_eq!(checklist.items[1].related_files, vec!["migrations/"]);
+        assert!(!checklist.items[1].checked);
+
+        let round: ChecklistTemplate =
+            serde_json::from_str(&serde_json::to_string(&checklist.to_template("x")).unwrap())
+                .unwrap();
+        assert_eq!(round.items.len(), 2);
+    }
+}
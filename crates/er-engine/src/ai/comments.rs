@@ -88,6 +88,19 @@ impl<'a> CommentRef<'a> {
         }
     }
 
+    /// The teammate who wrote this, for questions and notes pulled from the
+    /// shared review ref.
+    pub fn teammate(&self) -> Option<&str> {
+        match self {
+            CommentRef::Question(q) | CommentRef::Note(q)
+                if q.id.starts_with(SHARED_QUESTION_PREFIX) =>
+            {
+                Some(self.author())
+            }
+            _ => None,
+        }
+    }
+
     pub fn timestamp(&self) -> &str {
         match self {
             CommentRef::Question(q) | CommentRef::Note(q) => &q.timestamp,
@@ -105,6 +118,16 @@ impl<'a> CommentRef<'a> {
         }
     }
 
+    /// Edited both here and on GitHub since the last sync.
+    pub fn in_sync_conflict(&self) -> bool {
+        matches!(self, CommentRef::GitHubComment(c) if c.in_sync_conflict())
+    }
+
+    /// Edited here, not on GitHub yet.
+    pub fn has_unpushed_edit(&self) -> bool {
+        matches!(self, CommentRef::GitHubComment(c) if c.has_unpushed_edit())
+    }
+
     pub fn is_resolved(&self) -> bool {
         match self {
             CommentRef::Question(q) | CommentRef::Note(q) => q.resolved,
@@ -154,6 +177,15 @@ impl<'a> CommentRef<'a> {
         }
     }
 
+    /// The anchored line's text when the comment was placed
+    pub fn line_content(&self) -> &str {
+        match self {
+            CommentRef::Question(q) | CommentRef::Note(q) => &q.line_content,
+            CommentRef::GitHubComment(c) => &c.line_content,
+            CommentRef::Legacy(c) => &c.line_content,
+        }
+    }
+
     pub fn line_end(&self) -> Option<usize> {
         match self {
             CommentRef::Question(q) | CommentRef::Note(q) => q.line_end,
@@ -179,6 +211,15 @@ impl<'a> CommentRef<'a> {
         }
     }
 
+    /// Confidence of the last relocation, when it moved
+    pub fn anchor_confidence(&self) -> Option<f32> {
+        match self {
+            CommentRef::Question(q) | CommentRef::Note(q) => q.anchor_confidence,
+            CommentRef::GitHubComment(c) => c.anchor_confidence,
+            CommentRef::Legacy(_) => None,
+        }
+    }
+
     /// Whether this comment can be replied to (top-level comments/questions, not replies themselves)
     pub fn can_reply(&self) -> bool {
         match self {
@@ -192,7 +233,8 @@ impl<'a> CommentRef<'a> {
     #[allow(dead_code)]
     pub fn can_delete(&self) -> bool {
         match self {
-            CommentRef::Question(_) | CommentRef::Note(_) => true,
+            CommentRef::Question(q) => !q.id.starts_with(SHARED_QUESTION_PREFIX),
+            CommentRef::Note(_) => true,
             // Local comments are stored with author "You" — ownership is inferred from
             // that display name, not from the GitHub identity.
             CommentRef::GitHubComment(c) => c.source != "github" || c.author == "You",
@@ -267,6 +309,9 @@ pub struct ReviewQuestion {
     /// Diff hash when this comment was last relocated
     #[serde(default)]
     pub relocated_at_hash: String,
+    /// How sure the last relocation was, 0.0–1.0 (None = not relocated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_confidence: Option<f32>,
     /// ID of the question this is a reply to (None = top-level question)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -361,6 +406,9 @@ pub struct GitHubReviewComment {
     /// Diff hash when this comment was last relocated
     #[serde(default)]
     pub relocated_at_hash: String,
+    /// How sure the last relocation was, 0.0–1.0 (None = not relocated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_confidence: Option<f32>,
     /// Optional reference to an AI finding this comment responds to
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -368,6 +416,14 @@ pub struct GitHubReviewComment {
     /// "LEFT" for old-side (deleted lines) or "RIGHT" for new-side/unified (default)
     #[serde(default = "default_review_side")]
     pub side: String,
+    /// Body as GitHub last had it, while an edit made here hasn't reached
+    /// GitHub yet — the base a sync merges against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_base: Option<String>,
+    /// GitHub's body when both sides edited since `edit_base`; `comment`
+    /// keeps ours until the conflict is resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_body: Option<String>,
 }
 
 fn default_source() -> String {
@@ -382,6 +438,15 @@ fn default_anchor_status() -> String {
     "original".to_string()
 }
 
+/// Id prefix of teammates' questions loaded from the shared review ref
+/// (`er_dir/shared/<reviewer>/questions.json`); they are read-only here.
+pub const SHARED_QUESTION_PREFIX: &str = "shared-";
+
+/// `shared-<reviewer>-<id>`
+pub fn shared_question_id(reviewer: &str, id: &str) -> String {
+    format!("{}{}-{}", SHARED_QUESTION_PREFIX, reviewer, id)
+}
+
 fn default_author() -> String {
     "You".to_string()
 }
@@ -435,13 +500,12 @@ fn default_ui_version() -> u32 {
     1
 }
 
-/// Load annotations from `<comments_dir>/ui-annotations.json`. Missing file → empty.
+/// Load annotations from `<comments_dir>/ui-annotations.json`. Missing or
+/// invalid file → empty.
 pub fn load_ui_annotations(comments_dir: &str) -> Vec<UiAnnotation> {
-    let path = format!("{comments_dir}/ui-annotations.json");
-    let Ok(content) = std::fs::read_to_string(&path) else {
-        return Vec::new();
-    };
-    serde_json::from_str::<ErUiAnnotations>(&content)
+    super::store::load::<ErUiAnnotations>(comments_dir)
+        .ok()
+        .flatten()
         .map(|f| f.annotations)
         .unwrap_or_default()
 }
@@ -451,16 +515,11 @@ pub fn save_ui_annotations(
     comments_dir: &str,
     annotations: &[UiAnnotation],
 ) -> std::io::Result<()> {
-    std::fs::create_dir_all(comments_dir)?;
-    let path = format!("{comments_dir}/ui-annotations.json");
-    let tmp = format!("{path}.tmp");
     let file = ErUiAnnotations {
         version: 1,
         annotations: annotations.to_vec(),
     };
-    let json = serde_json::to_string_pretty(&file).map_err(std::io::Error::other)?;
-    std::fs::write(&tmp, json)?;
-    std::fs::rename(&tmp, &path)
+    super::store::save(comments_dir, &file).map_err(std::io::Error::other)
 }
 
 // ── Legacy .er-feedback.json (for migration) ──
@@ -506,6 +565,35 @@ pub struct FeedbackComment {
     pub synced: bool,
 }
 
+impl GitHubReviewComment {
+    /// About a whole file: a path but no hunk anchor. Pushed with
+    /// `subject_type=file`, which reviews can't batch.
+    pub fn is_file_level(&self) -> bool {
+        !self.file.is_empty() && self.hunk_index.is_none()
+    }
+
+    pub fn in_sync_conflict(&self) -> bool {
+        self.remote_body.is_some()
+    }
+
+    pub fn has_unpushed_edit(&self) -> bool {
+        self.edit_base.is_some() && self.remote_body.is_none()
+    }
+
+    /// Replace the body locally. A comment already on GitHub remembers the
+    /// body GitHub has, so the next sync can merge instead of overwriting.
+    pub fn edit_body(&mut self, text: &str) {
+        if self.comment == text {
+            return;
+        }
+        // General PR comments live in the issues API, which sync doesn't merge
+        if self.github_id.is_some() && !self.file.is_empty() && self.edit_base.is_none() {
+            self.edit_base = Some(self.comment.clone());
+        }
+        self.comment = text.to_string();
+    }
+}
+
 /// Top-level GitHub comment eligible for batch validate / re-anchor.
 pub fn github_comment_eligible_for_batch_validate(c: &GitHubReviewComment) -> bool {
     !c.resolved && !c.outdated && c.in_reply_to.is_none() && c.line_start.is_some()
@@ -541,6 +629,7 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".into(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             in_reply_to: None,
             author: "You".into(),
             promoted_to: None,
@@ -572,8 +661,11 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".into(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             finding_ref: None,
             side: "RIGHT".into(),
+            edit_base: None,
+            remote_body: None,
         }
     }
 
@@ -0,0 +1,179 @@
+//! What changed between two loads of a tab's AI data, for the notification
+//! after a reload: "2 findings added · 1 comment resolved" instead of a
+//! generic "AI data refreshed".
+
+use std::collections::{HashMap, HashSet};
+
+use super::{AiState, RiskLevel};
+
+/// Findings and comments by id, with whether each is resolved, plus the
+/// the High findings' ids and the other sidecars' presence — enough to tell
+/// two loads apart.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AiSnapshot {
+    findings: HashMap<String, bool>,
+    high: HashSet<String>,
+    comments: HashMap<String, bool>,
+    summary: Option<String>,
+    agent_summaries: usize,
+    sidecars: [bool; 4],
+}
+
+impl AiSnapshot {
+    pub fn of(ai: &AiState) -> Self {
+        let mut comments = HashMap::new();
+        if let Some(qs) = &ai.questions {
+            comments.extend(qs.questions.iter().map(|q| (q.id.clone(), q.resolved)));
+        }
+        if let Some(ns) = &ai.notes {
+            comments.extend(ns.notes.iter().map(|n| (n.id.clone(), n.resolved)));
+        }
+        if let Some(gc) = &ai.github_comments {
+            comments.extend(gc.comments.iter().map(|c| (c.id.clone(), c.resolved)));
+        }
+        let all_findings = || {
+            ai.review
+                .iter()
+                .flat_map(|r| r.files.values())
+                .flat_map(|fr| fr.findings.iter())
+        };
+        AiSnapshot {
+            findings: all_findings().map(|f| (f.id.clone(), f.resolved)).collect(),
+            high: all_findings()
+                .filter(|f| f.severity == RiskLevel::High)
+                .map(|f| f.id.clone())
+                .collect(),
+            comments,
+            summary: ai.summary.clone(),
+            agent_summaries: ai.agent_summaries.len(),
+            sidecars: [
+                ai.order.is_some(),
+                ai.tour.is_some(),
+                ai.checklist.is_some(),
+                ai.triage.is_some(),
+            ],
+        }
+    }
+}
+
+/// Counted changes between two [`AiSnapshot`]s.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AiDelta {
+    pub findings_added: usize,
+    pub findings_removed: usize,
+    pub findings_resolved: usize,
+    /// Ids of High findings that weren't High before, sorted
+    pub high_findings_added: Vec<String>,
+    /// Questions, notes and GitHub comments
+    pub comments_added: usize,
+    pub comments_removed: usize,
+    pub comments_resolved: usize,
+    /// Summary, order, tour, checklist or triage
+    pub other_changed: bool,
+    /// Reviewed marks re-read from disk
+    pub reviewed_changed: bool,
+}
+
+/// `(added, removed, resolved)` from `before` to `after`.
+fn count(before: &HashMap<String, bool>, after: &HashMap<String, bool>) -> (usize, usize, usize) {
+    let added = after.keys().filter(|id| !before.contains_key(*id)).count();
+    let removed = before.keys().filter(|id| !after.contains_key(*id)).count();
+    let resolved = after
+        .iter()
+        .filter(|(id, resolved)| **resolved && before.get(*id) == Some(&false))
+        .count();
+    (added, removed, resolved)
+}
+
+fn plural(n: usize, word: &str, what: &str) -> String {
+    format!("{} {}{} {}", n, word, if n == 1 { "" } else { "s" }, what)
+}
+
+impl AiDelta {
+    pub fn between(before: &AiSnapshot, after: &AiSnapshot) -> Self {
+        let (findings_added, findings_removed, findings_resolved) =
+            count(&before.findings, &after.findings);
+        let (comments_added, comments_removed, comments_resolved) =
+            count(&before.comments, &after.comments);
+        let mut high_findings_added: Vec<String> =
+            after.high.difference(&before.high).cloned().collect();
+        high_findings_added.sort();
+        AiDelta {
+            findings_added,
+            findings_removed,
+            findings_resolved,
+            high_findings_added,
+            comments_added,
+            comments_removed,
+            comments_resolved,
+            other_changed: before.summary != after.summary
+                || before.agent_summaries != after.agent_summaries
+                || before.sidecars != after.sidecars,
+            reviewed_changed: false,
+        }
+    }
+
+    /// The notification for this change, `None` when nothing did.
+    pub fn describe(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            (self.findings_added, "finding", "added"),
+            (self.findings_resolved, "finding", "resolved"),
+            (self.findings_removed, "finding", "removed"),
+            (self.comments_added, "comment", "added"),
+            (self.comments_resolved, "comment", "resolved"),
+            (self.comments_removed, "comment", "removed"),
+        ]
+        .into_iter()
+        .filter(|(n, _, _)| *n > 0)
+        .map(|(n, word, what)| plural(n, word, what))
+        .collect();
+        if !parts.is_empty() {
+            return Some(parts.join(" · "));
+        }
+        if self.other_changed {
+            return Some("AI data refreshed".into());
+        }
+        self.reviewed_changed
+            .then(|| "Reviewed marks updated".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(entries: &[(&str, bool)]) -> HashMap<String, bool> {
+        entries.iter().map(|(id, r)| (id.to_string(), *r)).collect()
+    }
+
+    #[test]
+    fn counts_added_removed_and_resolved() {
+        let before = AiSnapshot {
+            findings: ids(&[("f-1", false), ("f-2", false)]),
+            comments: ids(&[("q-1", false), ("c-1", true)]),
+            ..Default::default()
+        };
+        let after = AiSnapshot {
+            findings: ids(&[("f-1", false), ("f-3", false), ("f-4", false)]),
+            high: ["f-1".to_string(), "f-4".to_string()].into(),
+            comments: ids(&[("q-1", true), ("c-1", true)]),
+            ..Default::default()
+        };
+        let delta = AiDelta::between(&before, &after);
+        assert_eq!(delta.high_findings_added, vec!["f-1", "f-4"]);
+        assert_eq!(
+            delta.describe().as_deref(),
+            Some("2 findings added · 1 finding removed · 1 comment resolved")
+        );
+
+        assert_eq!(AiDelta::between(&after, &after).describe(), None);
+        let summary = AiSnapshot {
+            summary: Some("New summary".into()),
+            ..after.clone()
+        };
+        assert_eq!(
+            AiDelta::between(&after, &summary).describe().as_deref(),
+            Some("AI data refreshed")
+        );
+    }
+}
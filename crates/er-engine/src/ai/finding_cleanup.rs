@@ -307,6 +307,7 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".into(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 in_reply_to: None,
                 author: "You".into(),
                 promoted_to: None,
@@ -340,8 +341,11 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".into(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 finding_ref: Some("f-1".into()),
                 side: "RIGHT".into(),
+                edit_base: None,
+                remote_body: None,
             }],
         });
         assert_eq!(
@@ -0,0 +1,517 @@
+//! Agent hand-off: compile the open work from a review into one task list.
+//!
+//! Unresolved findings, local notes ("fix later") and open questions are
+//! flattened into `handoff.json` (structured, for tooling) and `handoff.md`
+//! (a prompt-ready checklist). Files the reviewer already signed off on are
+//! listed as "don't touch" so the agent keeps its changes scoped; files
+//! skipped from review are listed with the reviewer's reason, and TODO
+//! markers the diff adds are listed so they don't ship by accident. Config,
+//! env var and feature-flag changes come first: they need a deploy step.
+
+use super::{is_suppression_note, AiState, Finding, ReviewQuestion, RiskLevel};
+use crate::git::{OpsChange, OpsChangeKind, TodoMarker, TodoScanner};
+use crate::identity::{Identity, LOCAL_AUTHOR};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const HANDOFF_JSON: &str = "handoff.json";
+pub const HANDOFF_MD: &str = "handoff.md";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handoff {
+    pub version: u32,
+    pub generated_at: String,
+    pub branch: String,
+    pub base: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub pr_number: Option<u64>,
+    /// Who wrote the review (see [`crate::identity`]).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub reviewer: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<HandoffTask>,
+    #[serde(default)]
+    pub questions: Vec<HandoffTask>,
+    /// Reviewed files — the agent should leave these alone.
+    #[serde(default)]
+    pub do_not_touch: Vec<String>,
+    /// Files deliberately not reviewed.
+    #[serde(default)]
+    pub skipped: Vec<SkippedFile>,
+    /// TODO/FIXME/HACK markers on added lines.
+    #[serde(default)]
+    pub todos: Vec<HandoffTask>,
+    /// Config keys, env vars and feature flags the diff changes.
+    #[serde(default)]
+    pub operational: Vec<HandoffTask>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// One actionable item, from a finding or a note.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffTask {
+    pub id: String,
+    /// "finding" | "note" | "question" | "todo" | "operational"
+    pub source: String,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub line_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub line_end: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub severity: Option<RiskLevel>,
+    pub title: String,
+    #[serde(default)]
+    pub detail: String,
+    #[serde(default)]
+    pub suggestion: String,
+    /// Code outside the diff — fix only if cheap, else leave for a follow-up.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl Handoff {
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty() && self.questions.is_empty()
+    }
+
+    /// Build from the loaded AI state. `reviewed` are the reviewer's signed-off
+    /// paths; order of the output is deterministic (severity, file, line).
+    pub fn build(
+        ai: &AiState,
+        reviewed: &[String],
+        branch: &str,
+        base: &str,
+        pr_number: Option<u64>,
+    ) -> Self {
+        let mut findings: Vec<(&String, &Finding)> = ai
+            .review
+            .as_ref()
+            .map(|r| {
+                r.files
+                    .iter()
+                    .flat_map(|(path, fr)| fr.findings.iter().map(move |f| (path, f)))
+                    .filter(|(_, f)| f.is_active())
+                    .collect()
+            })
+            .unwrap_or_default();
+        findings.sort_by(|(pa, a), (pb, b)| {
+            severity_rank(a.severity)
+                .cmp(&severity_rank(b.severity))
+                .then_with(|| pa.cmp(pb))
+                .then_with(|| a.line_start.cmp(&b.line_start))
+        });
+
+        let mut tasks: Vec<HandoffTask> = findings
+            .into_iter()
+            .map(|(path, f)| HandoffTask {
+                id: f.id.clone(),
+                source: "finding".into(),
+                file: path.clone(),
+                line_start: f.line_start,
+                line_end: f.line_end,
+                severity: Some(f.severity),
+                title: f.title.clone(),
+                detail: f.description.clone(),
+                suggestion: f.suggestion.clone(),
+                optional: f.outside_diff,
+            })
+            .collect();
+
+        if let Some(notes) = &ai.notes {
+            tasks.extend(open_threads(&notes.notes, "note"));
+        }
+        let questions = ai
+            .questions
+            .as_ref()
+            .map(|q| open_threads(&q.questions, "question"))
+            .unwrap_or_default();
+
+        let mut do_not_touch = reviewed.to_vec();
+        do_not_touch.sort();
+
+        Handoff {
+            version: 1,
+            generated_at: crate::sync::chrono_now(),
+            branch: branch.to_string(),
+            base: base.to_string(),
+            pr_number,
+            reviewer: None,
+            tasks,
+            questions,
+            do_not_touch,
+            skipped: Vec::new(),
+            todos: Vec::new(),
+            operational: Vec::new(),
+        }
+    }
+
+    /// Drop the findings `er:ignore` notes suppress, as `(path, id)`.
+    pub fn without_findings(mut self, suppressed: &HashSet<(String, String)>) -> Self {
+        self.tasks.retain(|t| {
+            t.source != "finding" || !suppressed.contains(&(t.file.clone(), t.id.clone()))
+        });
+        self
+    }
+
+    /// List the TODO markers the diff adds, noting those missing a ticket.
+    pub fn with_todos(mut self, todos: &[TodoMarker], scanner: &TodoScanner) -> Self {
+        self.todos = todos
+            .iter()
+            .map(|todo| HandoffTask {
+                id: format!("todo-{}-{}", todo.path, todo.line.unwrap_or(0)),
+                source: "todo".into(),
+                file: todo.path.clone(),
+                line_start: todo.line,
+                line_end: None,
+                severity: None,
+                title: format!("{}: {}", todo.marker, todo.text),
+                detail: if todo.needs_ticket(scanner) {
+                    "No ticket reference yet".into()
+                } else {
+                    String::new()
+                },
+                suggestion: String::new(),
+                optional: false,
+            })
+            .collect();
+        self
+    }
+
+    /// List the config keys, env vars and feature flags the diff changes.
+    pub fn with_operational(mut self, changes: &[OpsChange]) -> Self {
+        self.operational = changes
+            .iter()
+            .map(|change| HandoffTask {
+                id: format!("ops-{}-{}", change.path, change.name),
+                source: "operational".into(),
+                file: change.path.clone(),
+                line_start: change.line,
+                line_end: None,
+                severity: None,
+                title: format!(
+                    "{} {} `{}`",
+                    match change.change {
+                        OpsChangeKind::Added => "New",
+                        OpsChangeKind::Removed => "Removed",
+                        OpsChangeKind::Changed => "Changed",
+                    },
+                    match change.kind {
+                        crate::git::OpsKind::Config => "config key",
+                        crate::git::OpsKind::EnvVar => "env var",
+                        crate::git::OpsKind::FeatureFlag => "feature flag",
+                    },
+                    change.name
+                ),
+                detail: change.text.clone(),
+                suggestion: String::new(),
+                optional: false,
+            })
+            .collect();
+        self
+    }
+
+    /// List the files skipped from review (`path → reason`), by path.
+    pub fn with_skipped(mut self, skipped: &HashMap<String, String>) -> Self {
+        self.skipped = skipped
+            .iter()
+            .map(|(path, reason)| SkippedFile {
+                path: path.clone(),
+                reason: reason.clone(),
+            })
+            .collect();
+        self.skipped.sort_by(|a, b| a.path.cmp(&b.path));
+        self
+    }
+
+    /// Name the reviewer and replace the local `You` on thread replies.
+    pub fn attributed(mut self, identity: &Identity) -> Self {
+        let local = format!("{}: ", LOCAL_AUTHOR);
+        let named = format!("{}: ", identity.name);
+        for task in self.tasks.iter_mut().chain(self.questions.iter_mut()) {
+            if task.source == "finding" {
+                continue;
+            }
+            task.detail = task
+                .detail
+                .lines()
+                .map(|line| match line.strip_prefix(&local) {
+                    Some(rest) => format!("{}{}", named, rest),
+                    None => line.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        if identity.name != LOCAL_AUTHOR {
+            self.reviewer = Some(identity.name.clone());
+        }
+        self
+    }
+
+    /// Markdown task list meant to be pasted straight into a coding agent.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let target = match self.pr_number {
+            Some(n) => format!("PR #{} ({} → {})", n, self.branch, self.base),
+            None => format!("{} (vs {})", self.branch, self.base),
+        };
+        out.push_str(&format!("# Review hand-off: {}\n\n", target));
+        if let Some(reviewer) = &self.reviewer {
+            out.push_str(&format!("Reviewed by {}.\n\n", reviewer));
+        }
+        out.push_str(
+            "Fix the tasks below. Keep changes minimal and scoped to each task; \
+             do not refactor unrelated code.\n",
+        );
+
+        if !self.operational.is_empty() {
+            out.push_str(
+                "\n## Operational changes (config, env vars, feature flags)\n\n\
+                 Each needs a matching change wherever this is deployed.\n\n",
+            );
+            for task in &self.operational {
+                push_task(&mut out, task);
+            }
+        }
+
+        let (overall, tasks): (Vec<_>, Vec<_>) = self.tasks.iter().partition(|t| t.file.is_empty());
+        if !overall.is_empty() {
+            out.push_str("\n## Overall\n\n");
+            for task in overall {
+                push_task(&mut out, task);
+            }
+        }
+        let (optional, required): (Vec<_>, Vec<_>) = tasks.into_iter().partition(|t| t.optional);
+        if !required.is_empty() {
+            out.push_str("\n## Fix these\n\n");
+            for task in required {
+                push_task(&mut out, task);
+            }
+        }
+        if !optional.is_empty() {
+            out.push_str("\n## Optional (outside this diff)\n\n");
+            for task in optional {
+                push_task(&mut out, task);
+            }
+        }
+        if !self.questions.is_empty() {
+            out.push_str("\n## Open questions (answer, don't guess)\n\n");
+            for task in &self.questions {
+                push_task(&mut out, task);
+            }
+        }
+        if !self.todos.is_empty() {
+            out.push_str("\n## New TODOs in this diff\n\n");
+            for task in &self.todos {
+                push_task(&mut out, task);
+            }
+        }
+        if !self.do_not_touch.is_empty() {
+            out.push_str("\n## Don't touch (already reviewed)\n\n");
+            for path in &self.do_not_touch {
+                out.push_str(&format!("- `{}`\n", path));
+            }
+        }
+        if !self.skipped.is_empty() {
+            out.push_str("\n## Skipped (not reviewed)\n\n");
+            for file in &self.skipped {
+                out.push_str(&format!("- `{}` — {}\n", file.path, file.reason));
+            }
+        }
+        if self.is_empty() {
+            out.push_str("\nNothing open — all findings, notes and questions are resolved.\n");
+        }
+        out
+    }
+}
+
+/// Unresolved top-level threads, with replies folded into the detail.
+fn open_threads(threads: &[ReviewQuestion], source: &str) -> Vec<HandoffTask> {
+    threads
+        .iter()
+        .filter(|q| q.in_reply_to.is_none() && !q.resolved && !is_suppression_note(q))
+        .map(|q| {
+            let replies: Vec<String> = threads
+                .iter()
+                .filter(|r| r.in_reply_to.as_deref() == Some(q.id.as_str()))
+                .map(|r| format!("{}: {}", r.author, r.text))
+                .collect();
+            HandoffTask {
+                id: q.id.clone(),
+                source: source.to_string(),
+                file: q.file.clone(),
+                line_start: q.line_start,
+                line_end: q.line_end,
+                severity: None,
+                title: q.text.clone(),
+                detail: replies.join("\n"),
+                suggestion: String::new(),
+                optional: false,
+            }
+        })
+        .collect()
+}
+
+fn severity_rank(level: RiskLevel) -> u8 {
+    match level {
+        RiskLevel::High => 0,
+        RiskLevel::Medium => 1,
+        RiskLevel::Low => 2,
+        RiskLevel::Info => 3,
+    }
+}
+
+fn push_task(out: &mut String, task: &HandoffTask) {
+    let location = match (task.line_start, task.line_end) {
+        (Some(s), Some(e)) if e > s => format!("{}:{}-{}", task.file, s, e),
+        (Some(s), _) => format!("{}:{}", task.file, s),
+        _ => task.file.clone(),
+    };
+    let tag = match task.severity {
+        Some(level) => format!("[{}] ", severity_label(level)),
+        None if task.source == "note" => "[note] ".to_string(),
+        None => String::new(),
+    };
+    if location.is_empty() {
+        out.push_str(&format!("- [ ] {}{}\n", tag, task.title));
+    } else {
+        out.push_str(&format!("- [ ] {}{} — `{}`\n", tag, task.title, location));
+    }
+    for line in task.detail.lines().filter(|l| !l.trim().is_empty()) {
+        out.push_str(&format!("  {}\n", line));
+    }
+    if !task.suggestion.is_empty() {
+        out.push_str(&format!("  Suggestion: {}\n", task.suggestion));
+    }
+}
+
+fn severity_label(level: RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::High => "high",
+        RiskLevel::Medium => "medium",
+        RiskLevel::Low => "low",
+        RiskLevel::Info => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(id: &str, severity: RiskLevel, line: usize) -> Finding {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "severity": severity,
+            "title": format!("title {id}"),
+            "line_start": line,
+        }))
+        .unwrap()
+    }
+
+    fn state() -> AiState {
+        let review: super::super::ErReview = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "diff_hash": "h",
+            "created_at": "",
+            "base_branch": "main",
+            "head_branch": "feature",
+            "files": {
+                "b.rs": { "risk": "low", "risk_reason": "", "summary": "", "findings": [] },
+                "a.rs": { "risk": "low", "risk_reason": "", "summary": "", "findings": [] },
+            },
+        }))
+        .unwrap();
+        let mut ai = AiState::default();
+        ai.review = Some(review);
+        let files = &mut ai.review.as_mut().unwrap().files;
+        files.get_mut("b.rs").unwrap().findings = vec![
+            finding("f-low", RiskLevel::Low, 1),
+            finding("f-high", RiskLevel::High, 9),
+        ];
+        let mut resolved = finding("f-done", RiskLevel::High, 2);
+        resolved.resolved = true;
+        files.get_mut("a.rs").unwrap().findings = vec![resolved];
+        ai
+    }
+
+    #[test]
+    fn build_orders_active_findings_by_severity() {
+        let h = Handoff::build(&state(), &["z.rs".into()], "feature", "main", None);
+        let ids: Vec<_> = h.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["f-high", "f-low"]);
+        assert_eq!(h.do_not_touch, vec!["z.rs"]);
+    }
+
+    #[test]
+    fn markdown_lists_tasks_and_scope() {
+        let skipped = HashMap::from([("vendor/x.js".to_string(), "vendored".to_string())]);
+        let todo = TodoMarker {
+            path: "b.rs".into(),
+            line: Some(4),
+            marker: "TODO".into(),
+            text: "drop the retry".into(),
+            ticket: None,
+        };
+        let h = Handoff::build(&state(), &["z.rs".into()], "feature", "main", Some(7))
+            .with_skipped(&skipped)
+            .with_todos(&[todo], &TodoScanner::default())
+            .with_operational(&[OpsChange {
+                kind: crate::git::OpsKind::EnvVar,
+                change: OpsChangeKind::Added,
+                name: "DATABASE_URL".into(),
+                path: "src/db.rs".into(),
+                line: Some(3),
+                text: "let url = env::var(\"DATABASE_URL\")?;".into(),
+            }]);
+        let md = h.to_markdown();
+        assert!(md.starts_with("# Review hand-off: PR #7"));
+        assert!(md.contains("- [ ] [high] title f-high — `b.rs:9`"));
+        assert!(md.contains("## Don't touch (already reviewed)\n\n- `z.rs`"));
+        assert!(md.contains("## Skipped (not reviewed)\n\n- `vendor/x.js` — vendored"));
+        assert!(md.contains("## New TODOs in this diff\n\n- [ ] TODO: drop the retry — `b.rs:4`"));
+        assert!(!md.contains("f-done"));
+        // Operational changes come before the tasks
+        let ops = md
+            .find("## Operational changes (config, env vars, feature flags)")
+            .unwrap();
+        assert!(ops < md.find("## Fix these").unwrap());
+        assert!(md.contains("- [ ] New env var `DATABASE_URL` — `src/db.rs:3`\n"));
+    }
+
+    #[test]
+    fn attributed_names_reviewer_and_local_replies() {
+        let mut ai = state();
+        ai.notes = Some(
+            serde_json::from_value(serde_json::json!({
+                "version": 1,
+                "diff_hash": "h",
+                "notes": [
+                    { "id": "n-1", "file": "a.rs", "hunk_index": 0, "text": "fix later" },
+                    { "id": "n-2", "file": "a.rs", "hunk_index": 0, "text": "agreed",
+                      "in_reply_to": "n-1", "author": "You" },
+                    { "id": "n-3", "file": "a.rs", "hunk_index": 0, "text": "on it",
+                      "in_reply_to": "n-1", "author": "bob" },
+                ],
+            }))
+            .unwrap(),
+        );
+        let identity = Identity {
+            name: "Ada".into(),
+            email: None,
+        };
+        let h = Handoff::build(&ai, &[], "feature", "main", None).attributed(&identity);
+        let note = h.tasks.iter().find(|t| t.id == "n-1").unwrap();
+        assert_eq!(note.detail, "Ada: agreed\nbob: on it");
+        assert!(h.to_markdown().contains("Reviewed by Ada."));
+    }
+}
@@ -1,9 +1,12 @@
-use super::comments::{ErFeedback, ErGitHubComments, ErNotes, ErQuestions};
+use super::comments::{
+    shared_question_id, ErFeedback, ErGitHubComments, ErNotes, ErQuestions, ReviewQuestion,
+};
 use super::experts::{
     expert_by_id, load_expert_reviews, merge_experts_into_review, synthesize_review_from_experts,
 };
 use super::professor::{load_professor_review, merge_professor_into_review};
 use super::review::*;
+use super::store::{self, Sidecar};
 use super::triage::load_triage_review;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -13,7 +16,7 @@ use std::path::Path;
 const MAX_SIDECAR_BYTES: u64 = 10_000_000;
 
 /// Read a sidecar file with a size limit to prevent memory spikes from large/adversarial files.
-fn read_sidecar(path: &Path) -> std::io::Result<String> {
+pub(super) fn read_sidecar(path: &Path) -> std::io::Result<String> {
     let metadata = std::fs::metadata(path)?;
     if metadata.len() > MAX_SIDECAR_BYTES {
         return Err(std::io::Error::new(
@@ -28,6 +31,52 @@ fn read_sidecar(path: &Path) -> std::io::Result<String> {
     std::fs::read_to_string(path)
 }
 
+/// `T` from `er_dir` for display; a broken file is logged and shown as absent.
+fn load_logged<T: Sidecar>(er_dir: &str) -> Option<T> {
+    let value = store::load(er_dir).unwrap_or_else(|e| {
+        crate::debug_log::warn("ai load", format!("{:#}", e));
+        None
+    });
+    // Keep the review index in step with edits made outside er
+    #[cfg(feature = "sqlite")]
+    if let Some(value) = &value {
+        crate::review_db::record(er_dir, value);
+    }
+    value
+}
+
+/// Teammates' questions pulled from the shared review ref into
+/// `er_dir/shared/<reviewer>/questions.json`, with ids namespaced per
+/// reviewer and "You" replaced by the reviewer's name.
+fn load_shared_questions(er_dir: &str) -> Vec<ReviewQuestion> {
+    let Ok(entries) = std::fs::read_dir(Path::new(er_dir).join("shared")) else {
+        return Vec::new();
+    };
+    let mut reviewers: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    reviewers.sort();
+    let mut out = Vec::new();
+    for dir in reviewers {
+        let Some(reviewer) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let Ok(content) = read_sidecar(&dir.join("questions.json")) else {
+            continue;
+        };
+        let Ok(questions) = serde_json::from_str::<ErQuestions>(&content) else {
+            continue;
+        };
+        out.extend(questions.questions.into_iter().map(|mut q| {
+            q.id = shared_question_id(&reviewer, &q.id);
+            q.in_reply_to = q.in_reply_to.map(|id| shared_question_id(&reviewer, &id));
+            if q.author.is_empty() || q.author == "You" {
+                q.author = reviewer.clone();
+            }
+            q
+        }));
+    }
+    out
+}
+
 /// Compute SHA-256 hash of raw diff output (for staleness detection).
 /// Used for .er-review.json compatibility where the hash is persisted.
 pub fn compute_diff_hash(raw_diff: &str) -> String {
@@ -152,6 +201,7 @@ pub fn artifacts_branch_mismatch(er_dir: &Path, scope: &str) -> bool {
 /// and other review artifacts from the same directory. This avoids showing a prior
 /// branch's review after mistaken migration into the wrong managed folder.
 pub fn load_ai_state(er_dir: &str, current_diff_hash: &str, branch_scope: Option<&str>) -> AiState {
+    let _profile = crate::profile::scope("ai load");
     let mut state = AiState::default();
     let er_path = Path::new(er_dir);
 
@@ -166,6 +216,8 @@ pub fn load_ai_state(er_dir: &str, current_diff_hash: &str, branch_scope: Option
     if let Ok(content) = read_sidecar(&review_path) {
         // A sidecar that fails to deserialize is treated the same as an absent file.
         if let Ok(review) = serde_json::from_str::<ErReview>(&content) {
+            #[cfg(feature = "sqlite")]
+            crate::review_db::record_review(er_dir, &review);
             state.is_stale = review.diff_hash != current_diff_hash;
             state.review = Some(review);
         }
@@ -196,39 +248,33 @@ pub fn load_ai_state(er_dir: &str, current_diff_hash: &str, branch_scope: Option
     }
 
     // Load .er/checklist.json
-    let checklist_path = Path::new(er_dir).join("checklist.json");
-    if let Ok(content) = read_sidecar(&checklist_path) {
-        if let Ok(checklist) = serde_json::from_str::<ErChecklist>(&content) {
-            if !state.is_stale && checklist.diff_hash != current_diff_hash {
-                state.is_stale = true;
-            }
-            state.checklist = Some(checklist);
+    if let Some(checklist) = load_logged::<ErChecklist>(er_dir) {
+        if !state.is_stale && checklist.diff_hash != current_diff_hash {
+            state.is_stale = true;
         }
+        state.checklist = Some(checklist);
     }
 
     // Load .er/questions.json (personal review questions)
-    let questions_path = Path::new(er_dir).join("questions.json");
-    if let Ok(content) = read_sidecar(&questions_path) {
-        if let Ok(questions) = serde_json::from_str::<ErQuestions>(&content) {
-            state.questions = Some(questions);
-        }
+    state.questions = load_logged::<ErQuestions>(er_dir);
+    let shared = load_shared_questions(er_dir);
+    if !shared.is_empty() {
+        state
+            .questions
+            .get_or_insert_with(|| ErQuestions {
+                version: 1,
+                diff_hash: current_diff_hash.to_string(),
+                questions: Vec::new(),
+            })
+            .questions
+            .extend(shared);
     }
 
     // Load .er/notes.json (local actionable notes)
-    let notes_path = Path::new(er_dir).join("notes.json");
-    if let Ok(content) = read_sidecar(&notes_path) {
-        if let Ok(notes) = serde_json::from_str::<ErNotes>(&content) {
-            state.notes = Some(notes);
-        }
-    }
+    state.notes = load_logged::<ErNotes>(er_dir);
 
     // Load .er/github-comments.json (GitHub PR comments)
-    let gh_comments_path = Path::new(er_dir).join("github-comments.json");
-    if let Ok(content) = read_sidecar(&gh_comments_path) {
-        if let Ok(gh_comments) = serde_json::from_str::<ErGitHubComments>(&content) {
-            state.github_comments = Some(gh_comments);
-        }
-    }
+    state.github_comments = load_logged::<ErGitHubComments>(er_dir);
 
     // Merge specialized expert sidecars into review (load-time only).
     let experts = load_expert_reviews(er_dir);
@@ -377,6 +423,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shared_questions_are_namespaced_and_attributed() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared/alice");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(
+            shared.join("questions.json"),
+            r#"{"version":1,"diff_hash":"h","questions":[
+                {"id":"q-1","file":"a.rs","hunk_index":0,"line_start":3,"text":"why?"},
+                {"id":"q-2","file":"a.rs","hunk_index":0,"line_start":3,"text":"ok","in_reply_to":"q-1","author":"Claude"}
+            ]}"#,
+        )
+        .unwrap();
+        let state = load_ai_state(dir.path().to_str().unwrap(), "h", None);
+        let qs = state.questions.unwrap().questions;
+        assert_eq!(qs[0].id, "shared-alice-q-1");
+        assert_eq!(qs[0].author, "alice");
+        assert_eq!(qs[1].in_reply_to.as_deref(), Some("shared-alice-q-1"));
+        assert_eq!(qs[1].author, "Claude");
+        assert_eq!(
+            crate::ai::CommentRef::Question(&qs[0]).teammate(),
+            Some("alice")
+        );
+    }
+
     #[test]
     fn load_ai_state_ignores_summary_only_wrong_branch() {
         let dir = tempfile::tempdir().unwrap();
@@ -1,22 +1,33 @@
+pub mod carry_over;
+pub mod checklist;
 pub mod comments;
+pub mod delta;
 pub mod experts;
 pub mod finding_cleanup;
 pub mod finding_responses;
+pub mod handoff;
 mod loader;
 pub mod professor;
 pub mod prompts;
 mod relocate;
 mod review;
 pub mod scoped_merge;
+pub mod store;
+pub mod suppressions;
 pub mod triage;
 
+pub use carry_over::*;
+pub use checklist::*;
 pub use comments::*;
+pub use delta::*;
 pub use experts::*;
 pub use finding_cleanup::*;
 pub use finding_responses::*;
+pub use handoff::*;
 pub use loader::*;
 pub use professor::*;
 pub use relocate::*;
 pub use review::*;
 pub use scoped_merge::*;
+pub use suppressions::*;
 pub use triage::*;
@@ -763,6 +763,62 @@ pub fn append_file_scope_if_present(mut prompt: String, output_dir: &str) -> Str
     prompt
 }
 
+/// Request for "explain this hunk" — a short read, not a review.
+pub const HUNK_EXPLAIN_PROMPT: &str =
+    "Explain in 2-4 plain sentences what this hunk changes and what it is for. \
+Describe, don't review: no findings, suggestions or headings.";
+
+/// System context for "explain this hunk": the hunk plus the lines just
+/// around it in the new version of the file.
+pub fn hunk_explain_context(path: &str, hunk: &str, before: &str, after: &str) -> String {
+    let mut out = format!("## Hunk in `{}`\n\n```diff\n{}", path, hunk);
+    if !hunk.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str("```\n");
+    for (label, lines) in [("Just above", before), ("Just below", after)] {
+        if lines.trim().is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {}\n\n```\n{}", label, lines));
+        if !lines.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str("```\n");
+    }
+    out
+}
+
+/// Repo-configured review hints (`[review_hints]`) for the changed files they
+/// match; empty when none apply.
+pub fn review_hints_appendix(hints: &[(String, Vec<String>)]) -> String {
+    if hints.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from(
+        "\n\n## Team review hints\n\nThis repo asks reviewers to check the following in these files. Treat each as a prompt to look, not a finding by itself:\n",
+    );
+    for (path, items) in hints {
+        out.push_str(&format!("\n- `{}`", path));
+        for item in items {
+            out.push_str(&format!("\n  - {}", item));
+        }
+    }
+    out
+}
+
+/// The active review template's instructions (`[[review_templates]] prompt`).
+pub fn review_template_appendix(name: &str, instructions: &str) -> String {
+    if instructions.trim().is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n\n## Change type: {}\n\nThis repo reviews this kind of change with these extra instructions:\n\n{}",
+        name,
+        instructions.trim()
+    )
+}
+
 fn triage_lens_instructions() -> String {
     r#"## Triage lens: breadth over depth
 
@@ -1899,4 +1955,13 @@ mod tests {
         assert!(prompt.contains("general|expert|arena|professor|skip"));
         assert!(prompt.contains("triage.json"));
     }
+
+    #[test]
+    fn review_hints_appendix_lists_hints_under_their_files() {
+        assert!(review_hints_appendix(&[]).is_empty());
+        let appendix =
+            review_hints_appendix(&[("db/users.sql".into(), vec!["Check index usage".into()])]);
+        assert!(appendix.contains("## Team review hints"));
+        assert!(appendix.contains("- `db/users.sql`\n  - Check index usage"));
+    }
 }
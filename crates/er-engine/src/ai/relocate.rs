@@ -1,8 +1,7 @@
-use crate::git::{DiffFile, LineType};
+use crate::git::{DiffFile, FileStatus, LineType};
 
 /// Anchor data extracted from a comment for relocation matching
 pub struct CommentAnchor {
-    #[allow(dead_code)]
     pub file: String,
     pub hunk_index: Option<usize>,
     pub line_start: Option<usize>,
@@ -13,6 +12,7 @@ pub struct CommentAnchor {
     pub hunk_header: String,
 }
 
+#[derive(Debug)]
 pub enum RelocationResult {
     /// Line found at same position with same content
     Unchanged,
@@ -20,11 +20,26 @@ pub enum RelocationResult {
     Relocated {
         new_hunk_index: usize,
         new_line_start: usize,
+        /// How sure the match is, 0.0–1.0; below [`LOW_CONFIDENCE`] the UI
+        /// asks the reviewer to check it
+        confidence: f32,
     },
     /// Line was deleted or cannot be found
     Lost,
 }
 
+/// Relocations scored below this are shown as uncertain.
+pub const LOW_CONFIDENCE: f32 = 0.75;
+
+/// Minimum fuzzy score for a line to take the comment at all.
+const FUZZY_MIN: f32 = 0.6;
+
+/// A fuzzy match further than this from the original line needs a score of
+/// at least [`FUZZY_FAR_MIN`]: repetitive files (JSON, YAML, protobuf) are
+/// full of lines that look alike.
+const FUZZY_FAR_LINES: usize = 50;
+const FUZZY_FAR_MIN: f32 = 0.8;
+
 /// Try to relocate a comment to its new position in the updated diff.
 pub fn relocate_comment(anchor: &CommentAnchor, diff_file: &DiffFile) -> RelocationResult {
     // Hunk-level comments: match by hunk header
@@ -42,7 +57,7 @@ pub fn relocate_comment(anchor: &CommentAnchor, diff_file: &DiffFile) -> Relocat
         return result;
     }
 
-    // Pass 3: fuzzy context match (line was edited)
+    // Pass 3: similarity over the line and its context (line was edited)
     if let Some(result) = pass3_fuzzy(anchor, diff_file) {
         return result;
     }
@@ -54,10 +69,10 @@ fn pass1_exact(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<Relocatio
     let target_line = anchor.line_start?;
 
     // Collect ALL matching lines — only act if there's exactly one (or we find the exact position)
-    let mut matches: Vec<(usize, usize)> = Vec::new(); // (hunk_idx, new_num)
+    let mut matches: Vec<(usize, usize, usize)> = Vec::new(); // (hunk_idx, line_idx, new_num)
 
     for (hunk_idx, hunk) in diff_file.hunks.iter().enumerate() {
-        for dl in &hunk.lines {
+        for (line_idx, dl) in hunk.lines.iter().enumerate() {
             if dl.line_type == LineType::Delete {
                 continue;
             }
@@ -69,16 +84,18 @@ fn pass1_exact(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<Relocatio
                     // Exact position match — always Unchanged regardless of duplicates
                     return Some(RelocationResult::Unchanged);
                 }
-                matches.push((hunk_idx, new_num));
+                matches.push((hunk_idx, line_idx, new_num));
             }
         }
     }
 
     // Only return Relocated from pass1 if the content is unique
-    if matches.len() == 1 {
+    if let [(hunk_idx, line_idx, new_num)] = matches[..] {
+        let context = context_similarity(anchor, &diff_file.hunks[hunk_idx].lines, line_idx);
         Some(RelocationResult::Relocated {
-            new_hunk_index: matches[0].0,
-            new_line_start: matches[0].1,
+            new_hunk_index: hunk_idx,
+            new_line_start: new_num,
+            confidence: context.map_or(0.9, |c| 0.6 + 0.4 * c),
         })
     } else {
         // Multiple or zero matches — let pass2 score them with context
@@ -89,7 +106,7 @@ fn pass1_exact(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<Relocatio
 fn pass2_scored(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<RelocationResult> {
     let target_line = anchor.line_start?;
     let mut best_score = 2i32; // minimum score to consider
-    let mut best: Option<(usize, usize)> = None;
+    let mut best: Option<(usize, usize, usize)> = None;
 
     for (hunk_idx, hunk) in diff_file.hunks.iter().enumerate() {
         for (line_idx, dl) in hunk.lines.iter().enumerate() {
@@ -141,83 +158,229 @@ fn pass2_scored(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<Relocati
 
             if score > best_score {
                 best_score = score;
-                best = Some((hunk_idx, new_num));
+                best = Some((hunk_idx, line_idx, new_num));
             }
         }
     }
 
-    best.map(|(hunk_idx, new_line_start)| RelocationResult::Relocated {
-        new_hunk_index: hunk_idx,
-        new_line_start,
+    best.map(|(hunk_idx, line_idx, new_line_start)| {
+        let context = context_similarity(anchor, &diff_file.hunks[hunk_idx].lines, line_idx);
+        RelocationResult::Relocated {
+            new_hunk_index: hunk_idx,
+            new_line_start,
+            confidence: context.map_or(0.8, |c| 0.5 + 0.5 * c),
+        }
     })
 }
 
+/// The line's content was edited (or is gone): score every line by how
+/// similar it and the lines around it are to the anchor's, tolerating
+/// whitespace changes and small edits, and take the best one that scores
+/// [`FUZZY_MIN`]. The score is the confidence.
 fn pass3_fuzzy(anchor: &CommentAnchor, diff_file: &DiffFile) -> Option<RelocationResult> {
-    // Only useful when we have context
-    let total_context = anchor.context_before.len() + anchor.context_after.len();
-    if total_context == 0 {
+    // Without context, a changed line has nothing to be recognised by
+    if anchor.context_before.is_empty() && anchor.context_after.is_empty() {
         return None;
     }
-
-    // Require at least 2/3 of available context lines to match
-    // For 2 context lines: need > 1 (i.e. both)
-    // For 3 context lines: need > 1 (i.e. >= 2)
-    // For 6 context lines: need > 3 (i.e. >= 4)
-    let min_required = (total_context * 2).div_ceil(3).max(1);
-    let mut best_score = min_required.saturating_sub(1); // > this to qualify
-    let mut best: Option<(usize, usize)> = None;
+    let target_line = anchor.line_start?;
+    let mut best: Option<(f32, usize, usize, usize)> = None; // (score, distance, hunk, line)
 
     for (hunk_idx, hunk) in diff_file.hunks.iter().enumerate() {
         for (line_idx, dl) in hunk.lines.iter().enumerate() {
             if dl.line_type == LineType::Delete {
                 continue;
             }
-            let new_num = match dl.new_num {
-                Some(n) => n,
-                None => continue,
+            let Some(new_num) = dl.new_num else {
+                continue;
+            };
+            let Some(context) = context_similarity(anchor, &hunk.lines, line_idx) else {
+                continue;
             };
+            let content = similarity(&dl.content, &anchor.line_content);
+            let score = 0.4 * content + 0.6 * context;
+            if score < FUZZY_MIN {
+                continue;
+            }
+            let distance = new_num.abs_diff(target_line);
+            // Ties go to the line nearest the original position
+            let better = best.is_none_or(|(best_score, best_distance, ..)| {
+                score > best_score + f32::EPSILON
+                    || ((score - best_score).abs() <= f32::EPSILON && distance < best_distance)
+            });
+            if better {
+                best = Some((score, distance, hunk_idx, new_num));
+            }
+        }
+    }
 
-            let mut ctx_matches = 0usize;
+    let (score, distance, hunk_idx, new_line_start) = best?;
+    if distance > FUZZY_FAR_LINES && score < FUZZY_FAR_MIN {
+        return Some(RelocationResult::Lost);
+    }
+    Some(RelocationResult::Relocated {
+        new_hunk_index: hunk_idx,
+        new_line_start,
+        confidence: score,
+    })
+}
 
-            for (offset, ctx) in anchor.context_before.iter().rev().enumerate() {
-                if line_idx > offset && hunk.lines[line_idx - offset - 1].content == *ctx {
-                    ctx_matches += 1;
-                }
-            }
+/// How well the lines around `lines[line_idx]` match the anchor's context,
+/// 0.0–1.0, each context line compared at its own offset. `None` when the
+/// anchor recorded no context.
+fn context_similarity(
+    anchor: &CommentAnchor,
+    lines: &[crate::git::DiffLine],
+    line_idx: usize,
+) -> Option<f32> {
+    let total = anchor.context_before.len() + anchor.context_after.len();
+    if total == 0 {
+        return None;
+    }
+    let mut sum = 0.0;
+    for (offset, ctx) in anchor.context_before.iter().rev().enumerate() {
+        if let Some(line) = (line_idx > offset).then(|| &lines[line_idx - offset - 1]) {
+            sum += similarity(&line.content, ctx);
+        }
+    }
+    for (offset, ctx) in anchor.context_after.iter().enumerate() {
+        if let Some(line) = lines.get(line_idx + offset + 1) {
+            sum += similarity(&line.content, ctx);
+        }
+    }
+    Some(sum / total as f32)
+}
 
-            for (offset, ctx) in anchor.context_after.iter().enumerate() {
-                let after_idx = line_idx + offset + 1;
-                if after_idx < hunk.lines.len() && hunk.lines[after_idx].content == *ctx {
-                    ctx_matches += 1;
-                }
-            }
+/// Longest line prefix compared, in chars; the rest rarely changes a match.
+const SIMILARITY_MAX_CHARS: usize = 200;
 
-            if ctx_matches > best_score {
-                best_score = ctx_matches;
-                best = Some((hunk_idx, new_num));
-            }
+/// Similarity of two lines, 0.0–1.0: one minus their edit distance over
+/// the longer length, after trimming and collapsing whitespace runs.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let normalize = |s: &str| -> Vec<char> {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(SIMILARITY_MAX_CHARS)
+            .collect()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / longest as f32
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
         }
+        std::mem::swap(&mut previous, &mut current);
     }
+    previous[b.len()]
+}
 
-    if let Some((best_hunk_idx, best_line)) = best {
-        // Proximity guard: a match that only barely meets the threshold is likely a false
-        // positive in files with repetitive structure (JSON, YAML, protobuf). If the best
-        // candidate is more than 50 lines away and only matched the minimum required context
-        // lines, treat it as Lost rather than silently attaching to the wrong location.
-        if let Some(original_line) = anchor.line_start {
-            let distance = (best_line as isize - original_line as isize).unsigned_abs();
-            if distance > 50 && best_score <= min_required {
-                return Some(RelocationResult::Lost);
-            }
+/// Where a lost comment's line turned up in another file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossFileMatch {
+    pub file: String,
+    pub hunk_index: usize,
+    pub line_start: usize,
+    pub confidence: f32,
+}
+
+/// Scores below this never move a comment to another file: it is offered,
+/// not applied, but a wrong offer still costs the reviewer a decision.
+pub const CROSS_FILE_MIN: f32 = 0.85;
+
+/// Look for a line-level comment's code in the other files of the diff,
+/// after [`relocate_comment`] lost it in its own. A copy of the anchor's
+/// file is searched like the file itself; elsewhere only added lines count
+/// (moved code arrives as additions), and the line must be near-identical
+/// with its context largely intact. `None` when nothing scores
+/// [`CROSS_FILE_MIN`].
+pub fn relocate_across_files(anchor: &CommentAnchor, files: &[DiffFile]) -> Option<CrossFileMatch> {
+    anchor.line_start?;
+    if anchor.line_content.trim().is_empty() {
+        return None;
+    }
+    // Edit distance is at least the length difference: lines too much
+    // longer or shorter can't reach 0.8 and skip the comparison
+    let wanted = anchor.line_content.trim().len();
+    let mut best: Option<CrossFileMatch> = None;
+    let mut consider = |found: CrossFileMatch| {
+        if found.confidence >= CROSS_FILE_MIN
+            && best
+                .as_ref()
+                .is_none_or(|b| found.confidence > b.confidence)
+        {
+            best = Some(found);
         }
+    };
 
-        Some(RelocationResult::Relocated {
-            new_hunk_index: best_hunk_idx,
-            new_line_start: best_line,
-        })
-    } else {
-        None
+    for file in files {
+        if file.path == anchor.file {
+            continue;
+        }
+        if let FileStatus::Copied(ref from) | FileStatus::Renamed(ref from) = file.status {
+            if *from == anchor.file {
+                if let RelocationResult::Relocated {
+                    new_hunk_index,
+                    new_line_start,
+                    confidence,
+                } = relocate_comment(anchor, file)
+                {
+                    consider(CrossFileMatch {
+                        file: file.path.clone(),
+                        hunk_index: new_hunk_index,
+                        line_start: new_line_start,
+                        confidence,
+                    });
+                }
+                continue;
+            }
+        }
+        for (hunk_idx, hunk) in file.hunks.iter().enumerate() {
+            for (line_idx, dl) in hunk.lines.iter().enumerate() {
+                if dl.line_type != LineType::Add {
+                    continue;
+                }
+                let Some(new_num) = dl.new_num else {
+                    continue;
+                };
+                let len = dl.content.trim().len();
+                if len.abs_diff(wanted) * 5 > len.max(wanted) {
+                    continue;
+                }
+                let content = similarity(&dl.content, &anchor.line_content);
+                if content < 0.8 {
+                    continue;
+                }
+                let confidence = match context_similarity(anchor, &hunk.lines, line_idx) {
+                    Some(context) => 0.5 * content + 0.5 * context,
+                    // Without context only an exact, distinctive line is enough,
+                    // and even then it is never sure
+                    None if content == 1.0 && anchor.line_content.trim().len() >= 20 => {
+                        CROSS_FILE_MIN
+                    }
+                    None => continue,
+                };
+                consider(CrossFileMatch {
+                    file: file.path.clone(),
+                    hunk_index: hunk_idx,
+                    line_start: new_num,
+                    confidence,
+                });
+            }
+        }
     }
+    best
 }
 
 fn relocate_hunk_level(anchor: &CommentAnchor, diff_file: &DiffFile) -> RelocationResult {
@@ -237,6 +400,7 @@ fn relocate_hunk_level(anchor: &CommentAnchor, diff_file: &DiffFile) -> Relocati
                 return RelocationResult::Relocated {
                     new_hunk_index: hunk_idx,
                     new_line_start: hunk.new_start,
+                    confidence: 1.0,
                 };
             }
         }
@@ -248,9 +412,11 @@ fn relocate_hunk_level(anchor: &CommentAnchor, diff_file: &DiffFile) -> Relocati
         let anchor_ctx = extract_hunk_context(&anchor.hunk_header);
         let hunk_ctx = extract_hunk_context(&hunk.header);
         if !anchor_ctx.is_empty() && anchor_ctx == hunk_ctx {
+            // Same enclosing function, but its lines changed
             return RelocationResult::Relocated {
                 new_hunk_index: hunk_idx,
                 new_line_start: hunk.new_start,
+                confidence: 0.7,
             };
         }
     }
@@ -267,6 +433,206 @@ fn extract_hunk_context(header: &str) -> &str {
     }
 }
 
+/// Where a finding's anchor lands in the current diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindingTarget {
+    pub hunk_index: usize,
+    /// Index into the hunk's lines; `None` for a hunk-level finding
+    pub line_index: Option<usize>,
+    /// The line is no longer in the hunk the review recorded
+    pub moved: bool,
+}
+
+/// Locate a finding in the current diff. Findings carry no line content, so
+/// unlike [`relocate_comment`] this matches on the new-side line number: the
+/// recorded hunk first, then any hunk that now holds the line. `None` when
+/// the line (or the recorded hunk, for hunk-level findings) is gone.
+pub fn locate_finding(
+    hunk_index: Option<usize>,
+    line_start: Option<usize>,
+    diff_file: &DiffFile,
+) -> Option<FindingTarget> {
+    let position = |hunk: &crate::git::DiffHunk, line: usize| {
+        hunk.lines.iter().position(|l| l.new_num == Some(line))
+    };
+    let Some(line) = line_start else {
+        let hunk_index = hunk_index.filter(|&h| h < diff_file.hunks.len())?;
+        return Some(FindingTarget {
+            hunk_index,
+            line_index: None,
+            moved: false,
+        });
+    };
+    if let Some(h) = hunk_index {
+        if let Some(line_index) = diff_file.hunks.get(h).and_then(|hunk| position(hunk, line)) {
+            return Some(FindingTarget {
+                hunk_index: h,
+                line_index: Some(line_index),
+                moved: false,
+            });
+        }
+    }
+    diff_file
+        .hunks
+        .iter()
+        .enumerate()
+        .find_map(|(h, hunk)| position(hunk, line).map(|idx| (h, idx)))
+        .map(|(h, line_index)| FindingTarget {
+            hunk_index: h,
+            line_index: Some(line_index),
+            moved: hunk_index.is_some_and(|recorded| recorded != h),
+        })
+}
+
+/// Where a local comment lands on the PR's own diff, in the side's line
+/// numbers GitHub expects (`line`, plus `start_line` for a range).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrAnchor {
+    pub start: usize,
+    pub end: usize,
+    /// The comment had to be re-anchored to reach the PR's diff
+    pub moved: bool,
+}
+
+impl PrAnchor {
+    /// `start_line` for a multi-line comment, `None` for a single line.
+    pub fn start_line(&self) -> Option<usize> {
+        (self.end > self.start).then_some(self.start)
+    }
+}
+
+/// Line number of `line` on the comment's side (old for LEFT, new otherwise).
+fn side_num(line: &crate::git::DiffLine, left: bool) -> Option<usize> {
+    if left {
+        line.old_num.filter(|_| line.line_type != LineType::Add)
+    } else {
+        line.new_num.filter(|_| line.line_type != LineType::Delete)
+    }
+}
+
+/// Map a local comment onto the PR's diff (`gh pr diff`), so pushing posts
+/// it where the reviewer put it even when the local diff has drifted from
+/// the PR head. Right-side lines go through [`relocate_comment`]; left-side
+/// (deleted) lines match on content at their old-side number. A range keeps
+/// its length and must still fit in one hunk, as GitHub requires. Errors
+/// say why the comment can't be placed.
+pub fn anchor_on_pr_diff(
+    comment: &super::GitHubReviewComment,
+    pr_files: &[DiffFile],
+) -> anyhow::Result<PrAnchor> {
+    let path = &comment.file;
+    let Some(diff_file) = pr_files.iter().find(|f| f.path == *path) else {
+        anyhow::bail!("{} is not part of the PR's diff", path);
+    };
+    let left = comment.side == "LEFT";
+
+    let Some(line_start) = comment.line_start else {
+        // Hunk-level: the hunk's first line on the comment's side
+        let anchor = CommentAnchor {
+            file: path.clone(),
+            hunk_index: comment.hunk_index,
+            line_start: None,
+            line_content: String::new(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            old_line_start: None,
+            hunk_header: comment.hunk_header.clone(),
+        };
+        let (hunk_index, moved) = match relocate_comment(&anchor, diff_file) {
+            RelocationResult::Unchanged => (comment.hunk_index.unwrap_or(0), false),
+            RelocationResult::Relocated { new_hunk_index, .. } => (new_hunk_index, true),
+            RelocationResult::Lost => anyhow::bail!(
+                "the hunk this comment is on ({}) is not in the PR's diff for {}",
+                comment.hunk_header.trim(),
+                path
+            ),
+        };
+        let line = diff_file
+            .hunks
+            .get(hunk_index)
+            .and_then(|hunk| hunk.lines.iter().find_map(|l| side_num(l, left)))
+            .ok_or_else(|| anyhow::anyhow!("hunk {} of {} has no lines", hunk_index, path))?;
+        return Ok(PrAnchor {
+            start: line,
+            end: line,
+            moved,
+        });
+    };
+
+    let start = if left {
+        comment.old_line_start.unwrap_or(line_start)
+    } else {
+        line_start
+    };
+    let at_start = |l: &crate::git::DiffLine| {
+        side_num(l, left) == Some(start)
+            && (comment.line_content.is_empty() || l.content == comment.line_content)
+    };
+    let mapped = if diff_file.hunks.iter().any(|h| h.lines.iter().any(at_start)) {
+        Some(start)
+    } else if comment.line_content.is_empty() {
+        None
+    } else if left {
+        // Deleted lines only move with the base; take a unique content match
+        let mut matches = diff_file
+            .hunks
+            .iter()
+            .flat_map(|h| h.lines.iter())
+            .filter(|l| l.content == comment.line_content)
+            .filter_map(|l| side_num(l, true));
+        match (matches.next(), matches.next()) {
+            (Some(n), None) => Some(n),
+            _ => None,
+        }
+    } else {
+        let anchor = CommentAnchor {
+            file: path.clone(),
+            hunk_index: comment.hunk_index,
+            line_start: Some(start),
+            line_content: comment.line_content.clone(),
+            context_before: comment.context_before.clone(),
+            context_after: comment.context_after.clone(),
+            old_line_start: comment.old_line_start,
+            hunk_header: comment.hunk_header.clone(),
+        };
+        match relocate_comment(&anchor, diff_file) {
+            RelocationResult::Unchanged => Some(start),
+            RelocationResult::Relocated { new_line_start, .. } => Some(new_line_start),
+            RelocationResult::Lost => None,
+        }
+    };
+    let Some(new_start) = mapped else {
+        anyhow::bail!(
+            "{}:{} is not in the PR's diff — the local diff has drifted from the PR head",
+            path,
+            start
+        );
+    };
+
+    let length = comment
+        .line_end
+        .unwrap_or(line_start)
+        .saturating_sub(line_start);
+    let new_end = new_start + length;
+    let fits = diff_file.hunks.iter().any(|h| {
+        let has = |n: usize| h.lines.iter().any(|l| side_num(l, left) == Some(n));
+        has(new_start) && has(new_end)
+    });
+    if !fits {
+        anyhow::bail!(
+            "{}:{}-{} no longer fits in one hunk of the PR's diff",
+            path,
+            new_start,
+            new_end
+        );
+    }
+    Ok(PrAnchor {
+        start: new_start,
+        end: new_end,
+        moved: new_start != start,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -572,4 +938,280 @@ mod tests {
             "expected Lost for distant weak match but got Relocated"
         );
     }
+
+    #[test]
+    fn locate_finding_follows_the_line_to_another_hunk() {
+        let file = make_file(vec![
+            make_hunk(
+                "@@ -1,2 +1,2 @@",
+                vec![ctx_line("a", 1, 1), add_line("b", 2)],
+            ),
+            make_hunk(
+                "@@ -9,2 +10,2 @@",
+                vec![ctx_line("c", 9, 10), add_line("d", 11)],
+            ),
+        ]);
+        let exact = locate_finding(Some(1), Some(11), &file).unwrap();
+        assert_eq!(
+            (exact.hunk_index, exact.line_index, exact.moved),
+            (1, Some(1), false)
+        );
+
+        let moved = locate_finding(Some(0), Some(10), &file).unwrap();
+        assert_eq!(
+            (moved.hunk_index, moved.line_index, moved.moved),
+            (1, Some(0), true)
+        );
+
+        assert!(locate_finding(Some(0), Some(40), &file).is_none());
+        assert_eq!(
+            locate_finding(Some(1), None, &file).unwrap().line_index,
+            None
+        );
+        assert!(locate_finding(Some(5), None, &file).is_none());
+    }
+
+    fn pr_comment(json: serde_json::Value) -> crate::ai::GitHubReviewComment {
+        let mut base = serde_json::json!({
+            "id": "c-1", "file": "test.rs", "hunk_index": 0,
+            "line_start": null, "line_end": null, "comment": "x",
+        });
+        base.as_object_mut()
+            .unwrap()
+            .extend(json.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    #[test]
+    fn pr_anchor_follows_drifted_lines_and_refuses_unmappable_ones() {
+        // The PR head has two extra lines above the commented code
+        let pr = vec![make_file(vec![make_hunk(
+            "@@ -1,4 +1,6 @@",
+            vec![
+                add_line("use a;", 1),
+                add_line("use b;", 2),
+                ctx_line("fn main() {", 1, 3),
+                del_line("    old();", 2),
+                add_line("    new();", 4),
+                add_line("    more();", 5),
+                ctx_line("}", 3, 6),
+            ],
+        )])];
+
+        let range = pr_comment(serde_json::json!({
+            "line_start": 2, "line_end": 3, "line_content": "    new();",
+            "context_before": ["fn main() {"],
+        }));
+        let mapped = anchor_on_pr_diff(&range, &pr).unwrap();
+        assert_eq!((mapped.start, mapped.end, mapped.moved), (4, 5, true));
+        assert_eq!(mapped.start_line(), Some(4));
+
+        let deleted = pr_comment(serde_json::json!({
+            "line_start": 2, "old_line_start": 2, "line_content": "    old();", "side": "LEFT",
+        }));
+        let mapped = anchor_on_pr_diff(&deleted, &pr).unwrap();
+        assert_eq!(
+            (mapped.start, mapped.end, mapped.start_line()),
+            (2, 2, None)
+        );
+
+        let hunk_level = pr_comment(serde_json::json!({"hunk_header": "@@ -1,4 +1,6 @@"}));
+        assert_eq!(anchor_on_pr_diff(&hunk_level, &pr).unwrap().start, 1);
+
+        let gone = pr_comment(serde_json::json!({"line_start": 9, "line_content": "gone();"}));
+        let err = anchor_on_pr_diff(&gone, &pr).unwrap_err().to_string();
+        assert!(err.contains("test.rs:9 is not in the PR's diff"), "{err}");
+
+        let overflow = pr_comment(serde_json::json!({
+            "line_start": 5, "line_end": 9, "line_content": "    more();",
+        }));
+        assert!(anchor_on_pr_diff(&overflow, &pr).is_err());
+
+        let other = pr_comment(serde_json::json!({"file": "b.rs", "line_start": 1}));
+        assert!(anchor_on_pr_diff(&other, &pr)
+            .unwrap_err()
+            .to_string()
+            .contains("not part of the PR's diff"));
+    }
+
+    #[test]
+    fn similarity_ignores_whitespace_and_scales_with_edits() {
+        assert_eq!(similarity("  let x = 1;", "let   x = 1;\t"), 1.0);
+        assert!(similarity("let total = a + b;", "let total = a + c;") > 0.9);
+        assert!(similarity("let total = a + b;", "return None;") < 0.3);
+        assert_eq!(similarity("", "   "), 1.0);
+    }
+
+    #[test]
+    fn confidence_reflects_how_the_line_was_found() {
+        let lines = |first: &str| {
+            vec![
+                ctx_line(first, 1, 1),
+                ctx_line("fn foo() {", 2, 2),
+                ctx_line("    let x = 1;", 3, 3),
+                ctx_line("}", 4, 4),
+            ]
+        };
+        // Moved down a line, context intact: sure
+        let file = make_file(vec![make_hunk("@@ -1,4 +1,4 @@", lines("// added"))]);
+        let a = anchor(Some(2), "    let x = 1;", vec!["fn foo() {"], vec!["}"]);
+        match relocate_comment(&a, &file) {
+            RelocationResult::Relocated { confidence, .. } => assert!(confidence >= 0.99),
+            other => panic!("expected Relocated, got {:?}", other),
+        }
+
+        // Moved and its context rewritten: found, but flagged for a check
+        let file = make_file(vec![make_hunk(
+            "@@ -1,4 +1,4 @@",
+            vec![
+                ctx_line("// added", 1, 1),
+                ctx_line("pub(crate) fn renamed(arg: u32) -> u32 {", 2, 2),
+                ctx_line("    let x = 1;", 3, 3),
+                ctx_line("    x + arg", 4, 4),
+            ],
+        )]);
+        match relocate_comment(&a, &file) {
+            RelocationResult::Relocated {
+                new_line_start,
+                confidence,
+                ..
+            } => {
+                assert_eq!(new_line_start, 3);
+                assert!(confidence < LOW_CONFIDENCE, "confidence {}", confidence);
+            }
+            other => panic!("expected Relocated, got {:?}", other),
+        }
+    }
+
+    /// Deterministic xorshift, so the synthetic cases below are the same on
+    /// every run and a failure names a reproducible seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    /// A plausible source line; neighbours share words, as real code does.
+    fn code_line(rng: &mut Rng) -> String {
+        const WORDS: [&str; 12] = [
+            "value", "count", "config", "path", "result", "items", "index", "buffer", "name",
+            "total", "state", "entry",
+        ];
+        let a = WORDS[rng.below(WORDS.len())];
+        let b = WORDS[rng.below(WORDS.len())];
+        let indent = "    ".repeat(1 + rng.below(3));
+        match rng.below(4) {
+            0 => format!("{}let {}_{} = {}.len();", indent, a, rng.below(100), b),
+            1 => format!("{}if {} > {} {{", indent, a, rng.below(1000)),
+            2 => format!("{}{}.push({}_{});", indent, a, b, rng.below(100)),
+            _ => format!("{}return {}({});", indent, a, b),
+        }
+    }
+
+    fn file_of(lines: &[String]) -> DiffFile {
+        let lines = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| ctx_line(l, i + 1, i + 1))
+            .collect();
+        make_file(vec![make_hunk("@@ -1,1 +1,1 @@", lines)])
+    }
+
+    /// An anchor on `lines[at]` (0-based) with three lines of context.
+    fn anchor_at(lines: &[String], at: usize) -> CommentAnchor {
+        let own = |range: std::ops::Range<usize>| lines[range].to_vec();
+        CommentAnchor {
+            file: "test.rs".to_string(),
+            hunk_index: Some(0),
+            line_start: Some(at + 1),
+            line_content: lines[at].clone(),
+            context_before: own(at.saturating_sub(3)..at),
+            context_after: own(at + 1..(at + 4).min(lines.len())),
+            old_line_start: None,
+            hunk_header: String::new(),
+        }
+    }
+
+    /// Synthetic code movements: lines inserted or deleted above the
+    /// anchor, the file reindented, and the anchored line itself edited a
+    /// little. The comment must land on the line it was written on.
+    #[test]
+    fn relocation_follows_synthetic_code_movements() {
+        for seed in 1..=200u64 {
+            let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1);
+            let original: Vec<String> = (0..60).map(|_| code_line(&mut rng)).collect();
+            let at = 10 + rng.below(40);
+            let anchor = anchor_at(&original, at);
+
+            let mut moved = original.clone();
+            let mut expected = at;
+            let kind = rng.below(4);
+            match kind {
+                0 => {
+                    let n = 1 + rng.below(20);
+                    let pos = rng.below(at);
+                    for _ in 0..n {
+                        moved.insert(pos, code_line(&mut rng));
+                    }
+                    expected += n;
+                }
+                1 => {
+                    // Delete above the context, which must survive
+                    let n = 1 + rng.below(at - 4);
+                    let pos = rng.below(at - 3 - n + 1);
+                    moved.drain(pos..pos + n);
+                    expected -= n;
+                }
+                2 => {
+                    for line in &mut moved {
+                        *line = format!("\t{}", line.trim_start());
+                    }
+                }
+                _ => {
+                    moved[at].push_str(" // checked");
+                    let n = rng.below(5);
+                    for _ in 0..n {
+                        moved.insert(0, code_line(&mut rng));
+                    }
+                    expected += n;
+                }
+            }
+
+            match relocate_comment(&anchor, &file_of(&moved)) {
+                RelocationResult::Unchanged => {
+                    assert_eq!(expected, at, "seed {} kind {}: unmoved line", seed, kind)
+                }
+                RelocationResult::Relocated {
+                    new_line_start,
+                    confidence,
+                    ..
+                } => {
+                    assert_eq!(
+                        new_line_start,
+                        expected + 1,
+                        "seed {} kind {}: wrong line",
+                        seed,
+                        kind
+                    );
+                    assert!(
+                        confidence >= LOW_CONFIDENCE,
+                        "seed {} kind {}: confidence {}",
+                        seed,
+                        kind,
+                        confidence
+                    );
+                }
+                RelocationResult::Lost => panic!("seed {} kind {}: lost", seed, kind),
+            }
+        }
+    }
 }
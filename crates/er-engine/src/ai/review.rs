@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 use std::collections::{HashMap, HashSet};
 
 // ── Inline layer visibility ──
@@ -11,6 +11,8 @@ pub struct InlineLayers {
     pub show_github_comments: bool,
     pub show_ai_findings: bool,
     pub hide_resolved: bool,
+    /// Tint added lines by the age of the code around them
+    pub show_code_age: bool,
 }
 
 impl Default for InlineLayers {
@@ -20,6 +22,7 @@ impl Default for InlineLayers {
             show_github_comments: true,
             show_ai_findings: true,
             hide_resolved: false,
+            show_code_age: false,
         }
     }
 }
@@ -31,6 +34,12 @@ pub enum PanelContent {
     AiSummary,
     FileDetail,
     SymbolRefs,
+    /// Config, env var and feature-flag changes
+    Operational,
+    /// Changed image assets
+    Assets,
+    /// Recent commits and PRs touching the selected file
+    RelatedChanges,
     AgentLog,
 }
 
@@ -203,6 +212,17 @@ pub struct Finding {
     pub promoted_to: Option<String>,
 }
 
+impl ErFileReview {
+    /// Severity spread of the findings still demanding attention.
+    pub fn severity_counts(&self) -> SeverityCounts {
+        let mut counts = SeverityCounts::default();
+        for f in self.findings.iter().filter(|f| f.is_active()) {
+            counts.add(&f.severity);
+        }
+        counts
+    }
+}
+
 impl Finding {
     /// Whether this finding still demands the reviewer's attention. False
     /// when the user has fixed it (`resolved`) or the AI dismissed it as a
@@ -429,7 +449,10 @@ pub struct ChecklistItem {
 }
 
 // Comment types, question/GitHub comment data, and legacy feedback are in comments.rs.
-use super::comments::{CommentRef, ErFeedback, ErGitHubComments, ErNotes, ErQuestions, HintType};
+use super::comments::{
+    CommentRef, CommentType, ErFeedback, ErGitHubComments, ErNotes, ErQuestions, HintType,
+    ReviewQuestion,
+};
 
 // ── AiReview navigation ──
 
@@ -438,10 +461,164 @@ use super::comments::{CommentRef, ErFeedback, ErGitHubComments, ErNotes, ErQuest
 pub enum ReviewFocus {
     /// Left column: file risk overview
     Files,
+    /// Findings grouped by category
+    Categories,
     /// Right column: checklist items
     Checklist,
 }
 
+/// Order of the file list in the AI summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewSort {
+    /// File risk, high first
+    #[default]
+    Risk,
+    /// Most unresolved high-severity findings first
+    UnresolvedHigh,
+}
+
+impl ReviewSort {
+    pub fn next(self) -> Self {
+        match self {
+            ReviewSort::Risk => ReviewSort::UnresolvedHigh,
+            ReviewSort::UnresolvedHigh => ReviewSort::Risk,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReviewSort::Risk => "risk",
+            ReviewSort::UnresolvedHigh => "open high",
+        }
+    }
+}
+
+/// Active findings by severity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeverityCounts {
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub info: usize,
+}
+
+impl SeverityCounts {
+    pub fn add(&mut self, severity: &RiskLevel) {
+        match severity {
+            RiskLevel::High => self.high += 1,
+            RiskLevel::Medium => self.medium += 1,
+            RiskLevel::Low => self.low += 1,
+            RiskLevel::Info => self.info += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.high + self.medium + self.low + self.info
+    }
+}
+
+/// Category sections in the AI summary, in display order. Findings whose
+/// category is none of these (expert ids, "professor", …) land in "other".
+pub const FINDING_CATEGORIES: &[&str] = &[
+    "correctness",
+    "security",
+    "performance",
+    "style",
+    "duplicate",
+];
+
+/// The section a finding's free-form category is grouped under.
+pub fn category_group(category: &str) -> &'static str {
+    let category = category.trim().to_lowercase();
+    match category.as_str() {
+        "correctness" | "bug" | "logic" | "error-handling" | "migration" => "correctness",
+        "security" | "auth" | "crypto" | "infrastructure" => "security",
+        "performance" | "perf" => "performance",
+        "style" | "naming" | "readability" | "maintainability" | "complexity" => "style",
+        "duplicate" | "duplication" => "duplicate",
+        _ => "other",
+    }
+}
+
+/// Swap the findings of a built-in check (duplicates, complexity) in
+/// `review` for `findings`, as `(path, finding)`; a review is made up for
+/// them when there is none.
+pub fn merge_builtin_findings(
+    review: &mut Option<ErReview>,
+    category: &str,
+    findings: &[(String, Finding)],
+    diff_hash: &str,
+) {
+    if let Some(review) = review.as_mut() {
+        for fr in review.files.values_mut() {
+            fr.findings.retain(|f| f.category != category);
+        }
+        // Entries that only held these
+        review.files.retain(|_, fr| {
+            !(fr.findings.is_empty()
+                && fr.risk == RiskLevel::Info
+                && fr.summary.is_empty()
+                && fr.risk_reason.is_empty())
+        });
+    }
+    if findings.is_empty() {
+        return;
+    }
+    let review = review.get_or_insert_with(|| ErReview {
+        version: 1,
+        diff_hash: diff_hash.to_string(),
+        created_at: String::new(),
+        base_branch: String::new(),
+        head_branch: String::new(),
+        files: Default::default(),
+        file_hashes: Default::default(),
+    });
+    for (path, finding) in findings {
+        review
+            .files
+            .entry(path.clone())
+            .or_insert_with(|| ErFileReview {
+                risk: RiskLevel::Info,
+                risk_reason: String::new(),
+                summary: String::new(),
+                findings: Vec::new(),
+            })
+            .findings
+            .push(finding.clone());
+    }
+}
+
+/// One line of the category section: a collapsible header or a finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryRow {
+    Header {
+        category: &'static str,
+        counts: SeverityCounts,
+        collapsed: bool,
+    },
+    Finding {
+        path: String,
+        finding_id: String,
+    },
+}
+
+/// A finding's claim on one diff line, drawn as a gutter bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMarker {
+    pub severity: RiskLevel,
+    /// False for resolved or dismissed findings, which are drawn dimmed
+    pub active: bool,
+}
+
+pub(crate) fn risk_rank(r: &RiskLevel) -> u8 {
+    match r {
+        RiskLevel::High => 0,
+        RiskLevel::Medium => 1,
+        RiskLevel::Low => 2,
+        RiskLevel::Info => 3,
+    }
+}
+
 // ── Comment index for O(1) lookups ──
 
 /// Identifies which source collection a comment came from
@@ -465,6 +642,44 @@ struct CommentIndexData {
     /// file -> (question_count, github_comment_count, note_count)
     /// Only top-level GitHub comments counted; all questions and notes counted
     file_comment_counts: HashMap<String, (usize, usize, usize)>,
+    /// file -> unresolved top-level comments whose anchor went stale
+    file_stale_counts: HashMap<String, usize>,
+}
+
+// ── Hint index for J/K navigation ──
+
+/// A hint in J/K order: (file, hunk_index, line_start, id, hint_type).
+pub type OrderedHint = (String, Option<usize>, Option<usize>, String, HintType);
+
+/// A line-anchored finding in finding order: (file, hunk_index, line_start, id).
+pub type OrderedFinding = (String, Option<usize>, Option<usize>, String);
+
+/// Hints and findings in navigation order with each id's position, so J/K
+/// steps through a sorted list instead of collecting and sorting every
+/// comment and finding on each press. Built lazily, rebuilt when the
+/// counts it was built from change or the comment index is invalidated.
+#[derive(Debug, Default)]
+pub struct HintIndex {
+    /// Comments, questions, notes and findings; replies right after their parent
+    pub hints: Vec<OrderedHint>,
+    /// Findings anchored to a hunk or line
+    pub findings: Vec<OrderedFinding>,
+    hint_pos: HashMap<String, usize>,
+    finding_pos: HashMap<String, usize>,
+    /// Question, note, GitHub comment and finding counts; `None` = unbuilt
+    source: Option<[usize; 4]>,
+}
+
+impl HintIndex {
+    /// Position of `id` in `hints`.
+    pub fn hint_position(&self, id: &str) -> Option<usize> {
+        self.hint_pos.get(id).copied()
+    }
+
+    /// Position of `id` in `findings`.
+    pub fn finding_position(&self, id: &str) -> Option<usize> {
+        self.finding_pos.get(id).copied()
+    }
 }
 
 // ── Aggregate AI state for a tab ──
@@ -501,6 +716,8 @@ pub struct AiState {
     /// Lazily-built comment index for O(1) lookups.
     /// `None` means unbuilt; rebuilt on first query after invalidation.
     comment_index: RefCell<Option<CommentIndexData>>,
+    /// Lazily-built J/K order (see `HintIndex`).
+    hint_index: RefCell<HintIndex>,
 }
 
 impl Default for AiState {
@@ -521,15 +738,166 @@ impl Default for AiState {
             tour_stale: false,
             stale_files: HashSet::new(),
             comment_index: RefCell::new(None),
+            hint_index: RefCell::new(HintIndex::default()),
         }
     }
 }
 
 impl AiState {
-    /// Invalidate the comment index (forces rebuild on next query).
+    /// Invalidate the comment and hint indexes (forces rebuild on next query).
     /// Call this after mutating `questions` or `github_comments` in-place.
     pub fn rebuild_comment_index(&self) {
         *self.comment_index.borrow_mut() = None;
+        self.hint_index.borrow_mut().source = None;
+    }
+
+    /// Replace the review's findings of a built-in `category` (see
+    /// `merge_builtin_findings`), keeping the hint index in step.
+    pub fn merge_builtin_findings(
+        &mut self,
+        category: &str,
+        findings: &[(String, Finding)],
+        diff_hash: &str,
+    ) {
+        merge_builtin_findings(&mut self.review, category, findings, diff_hash);
+        self.rebuild_comment_index();
+    }
+
+    fn hint_source(&self) -> [usize; 4] {
+        [
+            self.questions.as_ref().map_or(0, |q| q.questions.len()),
+            self.notes.as_ref().map_or(0, |n| n.notes.len()),
+            self.github_comments
+                .as_ref()
+                .map_or(0, |c| c.comments.len()),
+            self.review.as_ref().map_or(0, |r| {
+                r.files.values().map(|f| f.findings.len()).sum::<usize>()
+            }),
+        ]
+    }
+
+    /// Hints and findings in navigation order, built on first use.
+    pub fn hint_index(&self) -> Ref<'_, HintIndex> {
+        let source = self.hint_source();
+        if self.hint_index.borrow().source != Some(source) {
+            *self.hint_index.borrow_mut() = self.build_hint_index(source);
+        }
+        self.hint_index.borrow()
+    }
+
+    fn build_hint_index(&self, source: [usize; 4]) -> HintIndex {
+        // (file, hunk_index, line_start, is_reply, position, id, hint_type)
+        // is_reply=0 for parents, 1 for replies — ensures parents sort before their replies
+        // position preserves insertion order within each (is_reply) group for stable output
+        type ExtendedHint = (
+            String,
+            Option<usize>,
+            Option<usize>,
+            u8,
+            usize,
+            String,
+            HintType,
+        );
+        let mut extended: Vec<ExtendedHint> = Vec::new();
+        let mut findings: Vec<OrderedFinding> = Vec::new();
+        // Questions (both top-level and replies)
+        if let Some(qs) = &self.questions {
+            for (i, q) in qs.questions.iter().enumerate() {
+                let is_reply = if q.in_reply_to.is_none() { 0 } else { 1 };
+                extended.push((
+                    q.file.clone(),
+                    q.hunk_index,
+                    q.line_start,
+                    is_reply,
+                    i,
+                    q.id.clone(),
+                    HintType::Question,
+                ));
+            }
+        }
+        // Notes (both top-level and replies)
+        if let Some(ns) = &self.notes {
+            for (i, n) in ns.notes.iter().enumerate() {
+                let is_reply = if n.in_reply_to.is_none() { 0 } else { 1 };
+                extended.push((
+                    n.file.clone(),
+                    n.hunk_index,
+                    n.line_start,
+                    is_reply,
+                    i,
+                    n.id.clone(),
+                    HintType::Note,
+                ));
+            }
+        }
+        // GitHub comments (both top-level and replies)
+        if let Some(gc) = &self.github_comments {
+            for (i, c) in gc.comments.iter().enumerate() {
+                let is_reply = if c.in_reply_to.is_none() { 0 } else { 1 };
+                extended.push((
+                    c.file.clone(),
+                    c.hunk_index,
+                    c.line_start,
+                    is_reply,
+                    i,
+                    c.id.clone(),
+                    HintType::GitHubComment,
+                ));
+            }
+        }
+        // Findings (never have replies)
+        if let Some(review) = &self.review {
+            for (file_path, file_review) in &review.files {
+                for (i, finding) in file_review.findings.iter().enumerate() {
+                    extended.push((
+                        file_path.clone(),
+                        finding.hunk_index,
+                        finding.line_start,
+                        0,
+                        i,
+                        finding.id.clone(),
+                        HintType::Finding,
+                    ));
+                    // File-level findings show in the panel only
+                    if finding.hunk_index.is_some() || finding.line_start.is_some() {
+                        findings.push((
+                            file_path.clone(),
+                            finding.hunk_index,
+                            finding.line_start,
+                            finding.id.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        extended.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.cmp(&b.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+                .then(a.4.cmp(&b.4))
+        });
+        findings.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+        let hints: Vec<OrderedHint> = extended
+            .into_iter()
+            .map(|(file, hunk, line, _, _, id, hint_type)| (file, hunk, line, id, hint_type))
+            .collect();
+        // First occurrence wins, like a linear search
+        let mut hint_pos = HashMap::new();
+        for (i, h) in hints.iter().enumerate() {
+            hint_pos.entry(h.3.clone()).or_insert(i);
+        }
+        let mut finding_pos = HashMap::new();
+        for (i, f) in findings.iter().enumerate() {
+            finding_pos.entry(f.3.clone()).or_insert(i);
+        }
+        HintIndex {
+            hints,
+            findings,
+            hint_pos,
+            finding_pos,
+            source: Some(source),
+        }
     }
 
     /// Build the comment index from current data if not already built.
@@ -540,6 +908,7 @@ impl AiState {
         let mut hunk_index: HunkIndexMap = HashMap::new();
         let mut line_index: HashMap<(String, usize), Vec<(CommentSource, usize)>> = HashMap::new();
         let mut file_comment_counts: HashMap<String, (usize, usize, usize)> = HashMap::new();
+        let mut file_stale_counts: HashMap<String, usize> = HashMap::new();
 
         if let Some(qs) = &self.questions {
             for (i, q) in qs.questions.iter().enumerate() {
@@ -564,6 +933,8 @@ impl AiState {
                         .entry(q.file.clone())
                         .or_insert((0, 0, 0));
                     counts.0 += 1;
+                } else if q.in_reply_to.is_none() && !q.resolved {
+                    *file_stale_counts.entry(q.file.clone()).or_default() += 1;
                 }
             }
         }
@@ -589,6 +960,8 @@ impl AiState {
                         .entry(n.file.clone())
                         .or_insert((0, 0, 0));
                     counts.2 += 1;
+                } else if n.in_reply_to.is_none() && !n.resolved {
+                    *file_stale_counts.entry(n.file.clone()).or_default() += 1;
                 }
             }
         }
@@ -621,6 +994,12 @@ impl AiState {
                         .entry(c.file.clone())
                         .or_insert((0, 0, 0));
                     counts.1 += 1;
+                } else if c.in_reply_to.is_none()
+                    && c.finding_ref.is_none()
+                    && !c.resolved
+                    && c.stale
+                {
+                    *file_stale_counts.entry(c.file.clone()).or_default() += 1;
                 }
             }
         }
@@ -629,6 +1008,7 @@ impl AiState {
             hunk_index,
             line_index,
             file_comment_counts,
+            file_stale_counts,
         });
     }
 
@@ -741,6 +1121,29 @@ impl AiState {
         }
     }
 
+    /// Gutter markers for new-side `line`: every line-anchored finding whose
+    /// range covers it, active ones first, then by severity.
+    pub fn line_markers(&self, path: &str, line: usize) -> Vec<LineMarker> {
+        let Some(fr) = self.file_review(path) else {
+            return Vec::new();
+        };
+        let mut markers: Vec<LineMarker> = fr
+            .findings
+            .iter()
+            .filter(|f| {
+                f.line_start.is_some_and(|start| {
+                    (start..=f.line_end.unwrap_or(start).max(start)).contains(&line)
+                })
+            })
+            .map(|f| LineMarker {
+                severity: f.severity,
+                active: f.is_active(),
+            })
+            .collect();
+        markers.sort_by_key(|m| (!m.active, risk_rank(&m.severity)));
+        markers
+    }
+
     /// Get hunk-level findings (no line_start) for non-branch diff modes.
     /// Uses hunk_index when available; falls back to returning findings without
     /// any line anchor that haven't been matched to a specific hunk.
@@ -1102,6 +1505,57 @@ impl AiState {
         result
     }
 
+    /// Review-wide questions and notes (no file), oldest first. Top-level
+    /// only.
+    pub fn review_notes(&self) -> Vec<CommentRef<'_>> {
+        let review_wide = |q: &&ReviewQuestion| q.file.is_empty() && q.in_reply_to.is_none();
+        let mut result: Vec<CommentRef<'_>> = Vec::new();
+        if let Some(ns) = &self.notes {
+            result.extend(ns.notes.iter().filter(review_wide).map(CommentRef::Note));
+        }
+        if let Some(qs) = &self.questions {
+            result.extend(
+                qs.questions
+                    .iter()
+                    .filter(review_wide)
+                    .map(CommentRef::Question),
+            );
+        }
+        result.sort_by(|a, b| a.timestamp().cmp(b.timestamp()));
+        result
+    }
+
+    /// Body for the next review submission: open review-wide notes as overall
+    /// remarks and questions for the author, skipping ones already sent.
+    /// Returns the body and the ids it used (empty when there is nothing).
+    pub fn review_body(&self) -> (String, Vec<String>) {
+        let pending = |c: &CommentRef<'_>| match c {
+            CommentRef::Question(q) | CommentRef::Note(q) => !q.resolved && q.promoted_to.is_none(),
+            _ => false,
+        };
+        let notes: Vec<CommentRef<'_>> = self.review_notes().into_iter().filter(pending).collect();
+        let mut body = String::new();
+        for (ctype, heading) in [
+            (CommentType::Note, "Overall"),
+            (CommentType::Question, "Questions for the author"),
+        ] {
+            let items: Vec<&CommentRef<'_>> =
+                notes.iter().filter(|c| c.comment_type() == ctype).collect();
+            if items.is_empty() {
+                continue;
+            }
+            if !body.is_empty() {
+                body.push('\n');
+            }
+            body.push_str(&format!("**{}**\n\n", heading));
+            for item in items {
+                body.push_str(&format!("- {}\n", item.text().trim().replace('\n', "\n  ")));
+            }
+        }
+        let ids = notes.iter().map(|c| c.id().to_string()).collect();
+        (body, ids)
+    }
+
     /// Replies to a specific comment (questions or GitHub comments)
     pub fn replies_to(&self, comment_id: &str) -> Vec<CommentRef<'_>> {
         let mut result = Vec::new();
@@ -1211,6 +1665,17 @@ impl AiState {
         0
     }
 
+    /// Unresolved questions, notes and GitHub comments on `path` whose anchor
+    /// lines changed (not counted in the per-kind badges).
+    pub fn file_stale_comment_count(&self, path: &str) -> usize {
+        self.ensure_index();
+        let index = self.comment_index.borrow();
+        index
+            .as_ref()
+            .and_then(|i| i.file_stale_counts.get(path).copied())
+            .unwrap_or(0)
+    }
+
     /// Whether a file has any GitHub comments (top-level, not replies)
     #[allow(dead_code)]
     pub fn file_has_github_comments(&self, path: &str) -> bool {
@@ -1296,120 +1761,15 @@ impl AiState {
 
     /// All findings across all files, ordered by file path then hunk index then line_start.
     /// Returns (file, hunk_index, line_start, finding_id) tuples for navigation.
-    pub fn all_findings_ordered(&self) -> Vec<(String, Option<usize>, Option<usize>, String)> {
-        let mut result = Vec::new();
-        if let Some(review) = &self.review {
-            for (file_path, file_review) in &review.files {
-                for finding in &file_review.findings {
-                    // Skip file-level findings — they show in the panel only
-                    if finding.hunk_index.is_none() && finding.line_start.is_none() {
-                        continue;
-                    }
-                    result.push((
-                        file_path.clone(),
-                        finding.hunk_index,
-                        finding.line_start,
-                        finding.id.clone(),
-                    ));
-                }
-            }
-        }
-        result.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
-        result
+    pub fn all_findings_ordered(&self) -> Vec<OrderedFinding> {
+        self.hint_index().findings.clone()
     }
 
     /// All navigable hints (comments + questions + findings) merged and sorted by file + line.
     /// Returns (file, hunk_index, line_start, id, hint_type) tuples.
     /// Replies are included and sorted immediately after their parent.
-    #[allow(clippy::type_complexity)]
-    pub fn all_hints_ordered(
-        &self,
-    ) -> Vec<(String, Option<usize>, Option<usize>, String, HintType)> {
-        // Extended tuple: (file, hunk_index, line_start, is_reply, position, id, hint_type)
-        // is_reply=0 for parents, 1 for replies — ensures parents sort before their replies
-        // position preserves insertion order within each (is_reply) group for stable output
-        type ExtendedHint = (
-            String,
-            Option<usize>,
-            Option<usize>,
-            u8,
-            usize,
-            String,
-            HintType,
-        );
-        let mut extended: Vec<ExtendedHint> = Vec::new();
-        // Questions (both top-level and replies)
-        if let Some(qs) = &self.questions {
-            for (i, q) in qs.questions.iter().enumerate() {
-                let is_reply = if q.in_reply_to.is_none() { 0 } else { 1 };
-                extended.push((
-                    q.file.clone(),
-                    q.hunk_index,
-                    q.line_start,
-                    is_reply,
-                    i,
-                    q.id.clone(),
-                    HintType::Question,
-                ));
-            }
-        }
-        // Notes (both top-level and replies)
-        if let Some(ns) = &self.notes {
-            for (i, n) in ns.notes.iter().enumerate() {
-                let is_reply = if n.in_reply_to.is_none() { 0 } else { 1 };
-                extended.push((
-                    n.file.clone(),
-                    n.hunk_index,
-                    n.line_start,
-                    is_reply,
-                    i,
-                    n.id.clone(),
-                    HintType::Note,
-                ));
-            }
-        }
-        // GitHub comments (both top-level and replies)
-        if let Some(gc) = &self.github_comments {
-            for (i, c) in gc.comments.iter().enumerate() {
-                let is_reply = if c.in_reply_to.is_none() { 0 } else { 1 };
-                extended.push((
-                    c.file.clone(),
-                    c.hunk_index,
-                    c.line_start,
-                    is_reply,
-                    i,
-                    c.id.clone(),
-                    HintType::GitHubComment,
-                ));
-            }
-        }
-        // Findings (never have replies)
-        if let Some(review) = &self.review {
-            for (file_path, file_review) in &review.files {
-                for (i, finding) in file_review.findings.iter().enumerate() {
-                    extended.push((
-                        file_path.clone(),
-                        finding.hunk_index,
-                        finding.line_start,
-                        0,
-                        i,
-                        finding.id.clone(),
-                        HintType::Finding,
-                    ));
-                }
-            }
-        }
-        extended.sort_by(|a, b| {
-            a.0.cmp(&b.0)
-                .then(a.1.cmp(&b.1))
-                .then(a.2.cmp(&b.2))
-                .then(a.3.cmp(&b.3))
-                .then(a.4.cmp(&b.4))
-        });
-        extended
-            .into_iter()
-            .map(|(file, hunk, line, _, _, id, hint_type)| (file, hunk, line, id, hint_type))
-            .collect()
+    pub fn all_hints_ordered(&self) -> Vec<OrderedHint> {
+        self.hint_index().hints.clone()
     }
 
     /// Find a comment by ID across all comment types
@@ -1484,22 +1844,89 @@ impl AiState {
         self.checklist.as_ref().map(|c| c.items.len()).unwrap_or(0)
     }
 
-    /// Get the file path at the given cursor index in the risk list (sorted high→low)
-    pub fn review_file_at(&self, index: usize) -> Option<String> {
-        let review = self.review.as_ref()?;
+    /// Files of the risk list in display order
+    pub fn review_file_entries(&self, sort: ReviewSort) -> Vec<(&String, &ErFileReview)> {
+        let Some(review) = self.review.as_ref() else {
+            return Vec::new();
+        };
         let mut entries: Vec<(&String, &ErFileReview)> = review.files.iter().collect();
         entries.sort_by(|a, b| {
-            let risk_ord = |r: &RiskLevel| match r {
-                RiskLevel::High => 0,
-                RiskLevel::Medium => 1,
-                RiskLevel::Low => 2,
-                RiskLevel::Info => 3,
+            let by_risk = risk_rank(&a.1.risk).cmp(&risk_rank(&b.1.risk));
+            let ordered = match sort {
+                ReviewSort::Risk => by_risk,
+                ReviewSort::UnresolvedHigh => {
+                    let (ca, cb) = (a.1.severity_counts(), b.1.severity_counts());
+                    cb.high
+                        .cmp(&ca.high)
+                        .then_with(|| cb.medium.cmp(&ca.medium))
+                        .then(by_risk)
+                }
             };
-            risk_ord(&a.1.risk)
-                .cmp(&risk_ord(&b.1.risk))
-                .then_with(|| a.0.cmp(b.0))
+            ordered.then_with(|| a.0.cmp(b.0))
         });
-        entries.get(index).map(|(path, _)| (*path).clone())
+        entries
+    }
+
+    /// Get the file path at the given cursor index in the risk list (sorted high→low)
+    pub fn review_file_at(&self, index: usize) -> Option<String> {
+        self.review_file_at_sorted(index, ReviewSort::Risk)
+    }
+
+    pub fn review_file_at_sorted(&self, index: usize, sort: ReviewSort) -> Option<String> {
+        self.review_file_entries(sort)
+            .get(index)
+            .map(|(path, _)| (*path).clone())
+    }
+
+    /// Active findings grouped by [`category_group`], most severe first
+    /// within each group. Groups named in `collapsed` show only their header;
+    /// empty groups are left out.
+    pub fn category_rows(&self, collapsed: &HashSet<String>) -> Vec<CategoryRow> {
+        let Some(review) = self.review.as_ref() else {
+            return Vec::new();
+        };
+        let mut groups: Vec<(&'static str, Vec<(&String, &Finding)>)> = FINDING_CATEGORIES
+            .iter()
+            .chain(std::iter::once(&"other"))
+            .map(|c| (*c, Vec::new()))
+            .collect();
+        for (path, fr) in &review.files {
+            for f in fr.findings.iter().filter(|f| f.is_active()) {
+                let group = category_group(&f.category);
+                if let Some((_, members)) = groups.iter_mut().find(|(c, _)| *c == group) {
+                    members.push((path, f));
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        for (category, mut members) in groups {
+            if members.is_empty() {
+                continue;
+            }
+            members.sort_by(|a, b| {
+                risk_rank(&a.1.severity)
+                    .cmp(&risk_rank(&b.1.severity))
+                    .then_with(|| a.0.cmp(b.0))
+                    .then_with(|| a.1.line_start.cmp(&b.1.line_start))
+            });
+            let mut counts = SeverityCounts::default();
+            for (_, f) in &members {
+                counts.add(&f.severity);
+            }
+            let is_collapsed = collapsed.contains(category);
+            rows.push(CategoryRow::Header {
+                category,
+                counts,
+                collapsed: is_collapsed,
+            });
+            if !is_collapsed {
+                rows.extend(members.into_iter().map(|(path, f)| CategoryRow::Finding {
+                    path: path.clone(),
+                    finding_id: f.id.clone(),
+                }));
+            }
+        }
+        rows
     }
 
     /// Toggle checklist item at the given cursor index
@@ -2037,6 +2464,37 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn line_markers_cover_ranges_with_active_findings_first() {
+        let mut state = AiState::default();
+        let mut fixed = make_finding_with_lines("1", Some(0), Some(8), Some(12), RiskLevel::High);
+        fixed.resolved = true;
+        state.review = Some(make_review_with_files(vec![(
+            "a.rs",
+            RiskLevel::High,
+            vec![
+                fixed,
+                make_finding_with_lines("2", Some(0), Some(10), None, RiskLevel::Low),
+                make_finding_with_lines("3", Some(0), Some(9), Some(11), RiskLevel::Medium),
+            ],
+        )]));
+        let at_10: Vec<(RiskLevel, bool)> = state
+            .line_markers("a.rs", 10)
+            .into_iter()
+            .map(|m| (m.severity, m.active))
+            .collect();
+        assert_eq!(
+            at_10,
+            vec![
+                (RiskLevel::Medium, true),
+                (RiskLevel::Low, true),
+                (RiskLevel::High, false)
+            ]
+        );
+        assert_eq!(state.line_markers("a.rs", 12).len(), 1);
+        assert!(state.line_markers("a.rs", 13).is_empty());
+    }
+
     // ── AiState::comments_for_hunk ──
 
     #[test]
@@ -2272,6 +2730,91 @@ mod tests {
         assert_eq!(state.review_file_at(0), None);
     }
 
+    #[test]
+    fn unresolved_high_sort_ranks_files_by_open_high_findings() {
+        let mut state = AiState::default();
+        let mut fixed = make_finding("f3", None, RiskLevel::High);
+        fixed.resolved = true;
+        state.review = Some(make_review_with_files(vec![
+            (
+                "risky.rs",
+                RiskLevel::High,
+                vec![make_finding("f1", None, RiskLevel::Low), fixed],
+            ),
+            (
+                "quiet.rs",
+                RiskLevel::Low,
+                vec![
+                    make_finding("f2", None, RiskLevel::High),
+                    make_finding("f4", None, RiskLevel::High),
+                ],
+            ),
+        ]));
+        assert_eq!(state.review_file_at(0), Some("risky.rs".to_string()));
+        assert_eq!(
+            state.review_file_at_sorted(0, ReviewSort::UnresolvedHigh),
+            Some("quiet.rs".to_string())
+        );
+        let counts = state.file_review("risky.rs").unwrap().severity_counts();
+        assert_eq!((counts.high, counts.low, counts.total()), (0, 1, 1));
+    }
+
+    #[test]
+    fn category_rows_group_active_findings_and_fold() {
+        let mut state = AiState::default();
+        let with_category = |id: &str, category: &str, severity: RiskLevel| {
+            let mut f = make_finding(id, None, severity);
+            f.category = category.to_string();
+            f
+        };
+        let mut dropped = with_category("f5", "security", RiskLevel::High);
+        dropped.confidence = Confidence::Dropped;
+        state.review = Some(make_review_with_files(vec![(
+            "a.rs",
+            RiskLevel::High,
+            vec![
+                with_category("f1", "Security", RiskLevel::Low),
+                with_category("f2", "security", RiskLevel::High),
+                with_category("f3", "perf", RiskLevel::Medium),
+                with_category("f4", "professor", RiskLevel::Info),
+                dropped,
+            ],
+        )]));
+
+        let rows = state.category_rows(&HashSet::new());
+        let headers: Vec<&str> = rows
+            .iter()
+            .filter_map(|r| match r {
+                CategoryRow::Header { category, .. } => Some(*category),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(headers, vec!["security", "performance", "other"]);
+        assert_eq!(
+            rows[1],
+            CategoryRow::Finding {
+                path: "a.rs".into(),
+                finding_id: "f2".into()
+            }
+        );
+        match &rows[0] {
+            CategoryRow::Header { counts, .. } => assert_eq!((counts.high, counts.low), (1, 1)),
+            other => panic!("expected header, got {:?}", other),
+        }
+
+        let collapsed: HashSet<String> = ["security".to_string()].into();
+        let rows = state.category_rows(&collapsed);
+        assert_eq!(rows.len(), 5);
+        assert!(matches!(
+            rows[0],
+            CategoryRow::Header {
+                collapsed: true,
+                ..
+            }
+        ));
+        assert!(matches!(rows[1], CategoryRow::Header { .. }));
+    }
+
     // ── AiState::toggle_checklist_item ──
 
     #[test]
@@ -2419,6 +2962,7 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".to_string(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             in_reply_to: None,
             author: "You".to_string(),
             promoted_to: None,
@@ -2455,11 +2999,48 @@ mod tests {
             hunk_header: String::new(),
             anchor_status: "original".to_string(),
             relocated_at_hash: String::new(),
+            anchor_confidence: None,
             finding_ref: None,
             side: "RIGHT".to_string(),
+            edit_base: None,
+            remote_body: None,
         }
     }
 
+    // ── AiState::review_notes / review_body ──
+
+    #[test]
+    fn review_body_lists_open_review_wide_notes_and_questions() {
+        let mut state = AiState::default();
+        let mut overall = make_question("n1", "", None);
+        overall.text = "Looks good overall".to_string();
+        let mut sent = make_question("n2", "", None);
+        sent.promoted_to = Some("review".to_string());
+        state.notes = Some(ErNotes {
+            version: 1,
+            diff_hash: "test".to_string(),
+            notes: vec![overall, sent, make_question("n3", "a.rs", Some(0))],
+        });
+        let mut ask = make_question("q1", "", None);
+        ask.text = "Why a new crate?".to_string();
+        state.questions = Some(ErQuestions {
+            version: 1,
+            diff_hash: "test".to_string(),
+            questions: vec![ask],
+        });
+
+        let notes = state.review_notes();
+        let ids: Vec<&str> = notes.iter().map(|c| c.id()).collect();
+        assert_eq!(ids, vec!["n1", "n2", "q1"]);
+        let (body, used) = state.review_body();
+        assert_eq!(
+            body,
+            "**Overall**\n\n- Looks good overall\n\n**Questions for the author**\n\n- Why a new crate?\n"
+        );
+        assert_eq!(used, vec!["n1", "q1"]);
+        assert!(AiState::default().review_body().0.is_empty());
+    }
+
     // ── AiState::all_comments_ordered ──
 
     #[test]
@@ -2635,6 +3216,24 @@ mod tests {
         assert_eq!(state.file_github_comment_count("a.rs"), 1);
     }
 
+    #[test]
+    fn stale_comments_move_from_their_badge_to_the_stale_count() {
+        let mut state = AiState::default();
+        let mut stale = make_question("q2", "a.rs", Some(0));
+        stale.stale = true;
+        let mut done = make_question("q3", "a.rs", Some(0));
+        done.stale = true;
+        done.resolved = true;
+        state.questions = Some(ErQuestions {
+            version: 1,
+            diff_hash: "test".to_string(),
+            questions: vec![make_question("q1", "a.rs", Some(0)), stale, done],
+        });
+        assert_eq!(state.file_question_count("a.rs"), 1);
+        assert_eq!(state.file_stale_comment_count("a.rs"), 1);
+        assert_eq!(state.file_stale_comment_count("b.rs"), 0);
+    }
+
     #[test]
     fn file_github_comment_count_correct_per_file() {
         let mut state = AiState::default();
@@ -2781,6 +3380,41 @@ mod tests {
         assert_eq!(ids[3], "c-reply1");
     }
 
+    #[test]
+    fn hint_index_is_kept_until_the_data_changes() {
+        let mut state = AiState::default();
+        state.questions = Some(ErQuestions {
+            version: 1,
+            diff_hash: "test".to_string(),
+            questions: vec![
+                make_question("q-b", "b.rs", Some(0)),
+                make_question("q-a", "a.rs", Some(1)),
+            ],
+        });
+        assert_eq!(state.hint_index().hint_position("q-a"), Some(0));
+        assert_eq!(state.hint_index().hint_position("q-b"), Some(1));
+
+        // Moved in place: kept until the index is invalidated
+        state.questions.as_mut().unwrap().questions[1].file = "c.rs".to_string();
+        assert_eq!(state.hint_index().hint_position("q-a"), Some(0));
+        state.rebuild_comment_index();
+        assert_eq!(state.hint_index().hint_position("q-a"), Some(1));
+
+        // A new finding changes the counts, so it's picked up without one
+        state.merge_builtin_findings(
+            "duplicate",
+            &[(
+                "a.rs".to_string(),
+                make_finding_with_lines("f-1", Some(0), Some(3), Some(3), RiskLevel::Low),
+            )],
+            "test",
+        );
+        let index = state.hint_index();
+        assert_eq!(index.hints[0].3, "f-1");
+        assert_eq!(index.finding_position("f-1"), Some(0));
+        assert_eq!(index.findings.len(), 1);
+    }
+
     // ── Confidence + Finding backwards compatibility ──
 
     #[test]
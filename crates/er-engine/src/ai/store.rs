@@ -0,0 +1,299 @@
+//! Typed persistence for the sidecars `er` writes itself: questions, notes,
+//! GitHub comments, the checklist and UI annotations.
+//!
+//! Reads go through the loader's size limit and check the schema version, so
+//! a file written by a newer `er` is reported instead of being rewritten in
+//! the old format. Writes go through [`crate::file_lock`]: the file's lock is
+//! held while it is re-read and changed, and it is replaced atomically.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+use super::comments::{
+    ErGitHubComments, ErNotes, ErQuestions, ErUiAnnotations, GitHubReviewComment, ReviewQuestion,
+};
+use super::loader::read_sidecar;
+use super::review::ErChecklist;
+use crate::file_lock::{self, FileLock, Updated};
+
+/// A sidecar file with a known name and schema.
+pub trait Sidecar: Serialize + DeserializeOwned {
+    /// File name inside the sidecar directory.
+    const FILE: &'static str;
+
+    /// Newest schema version this build reads and writes.
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32;
+
+    /// A sidecar with nothing in it yet, for the diff `diff_hash`.
+    fn empty(diff_hash: &str) -> Self;
+
+    /// The comments in it, for the review index (`None`: not a comment file).
+    fn indexed_comments(&self) -> Option<Vec<IndexedComment>> {
+        None
+    }
+}
+
+/// A comment as the review index ([`crate::review_db`]) keeps it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedComment {
+    pub id: String,
+    pub file: String,
+    pub line: Option<usize>,
+    pub author: String,
+    pub text: String,
+    pub resolved: bool,
+    pub timestamp: String,
+}
+
+impl From<&ReviewQuestion> for IndexedComment {
+    fn from(q: &ReviewQuestion) -> Self {
+        IndexedComment {
+            id: q.id.clone(),
+            file: q.file.clone(),
+            line: q.line_start,
+            author: q.author.clone(),
+            text: q.text.clone(),
+            resolved: q.resolved,
+            timestamp: q.timestamp.clone(),
+        }
+    }
+}
+
+impl From<&GitHubReviewComment> for IndexedComment {
+    fn from(c: &GitHubReviewComment) -> Self {
+        IndexedComment {
+            id: c.id.clone(),
+            file: c.file.clone(),
+            line: c.line_start,
+            author: c.author.clone(),
+            text: c.comment.clone(),
+            resolved: c.resolved,
+            timestamp: c.timestamp.clone(),
+        }
+    }
+}
+
+impl Sidecar for ErQuestions {
+    const FILE: &'static str = "questions.json";
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn empty(diff_hash: &str) -> Self {
+        ErQuestions {
+            version: Self::VERSION,
+            diff_hash: diff_hash.to_string(),
+            questions: Vec::new(),
+        }
+    }
+
+    fn indexed_comments(&self) -> Option<Vec<IndexedComment>> {
+        Some(self.questions.iter().map(IndexedComment::from).collect())
+    }
+}
+
+impl Sidecar for ErNotes {
+    const FILE: &'static str = "notes.json";
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn empty(diff_hash: &str) -> Self {
+        ErNotes {
+            version: Self::VERSION,
+            diff_hash: diff_hash.to_string(),
+            notes: Vec::new(),
+        }
+    }
+
+    fn indexed_comments(&self) -> Option<Vec<IndexedComment>> {
+        Some(self.notes.iter().map(IndexedComment::from).collect())
+    }
+}
+
+impl Sidecar for ErGitHubComments {
+    const FILE: &'static str = "github-comments.json";
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn empty(diff_hash: &str) -> Self {
+        ErGitHubComments {
+            version: Self::VERSION,
+            diff_hash: diff_hash.to_string(),
+            github: None,
+            comments: Vec::new(),
+        }
+    }
+
+    fn indexed_comments(&self) -> Option<Vec<IndexedComment>> {
+        Some(self.comments.iter().map(IndexedComment::from).collect())
+    }
+}
+
+impl Sidecar for ErChecklist {
+    const FILE: &'static str = "checklist.json";
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn empty(diff_hash: &str) -> Self {
+        ErChecklist {
+            version: Self::VERSION,
+            diff_hash: diff_hash.to_string(),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl Sidecar for ErUiAnnotations {
+    const FILE: &'static str = "ui-annotations.json";
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn empty(_diff_hash: &str) -> Self {
+        ErUiAnnotations {
+            version: Self::VERSION,
+            annotations: Vec::new(),
+        }
+    }
+}
+
+/// Where `T` lives in `dir`.
+pub fn path<T: Sidecar>(dir: &str) -> String {
+    format!("{}/{}", dir, T::FILE)
+}
+
+/// Parse and validate `T` from the contents of its file.
+pub fn parse<T: Sidecar>(content: &str) -> Result<T> {
+    let value: T =
+        serde_json::from_str(content).with_context(|| format!("Failed to parse {}", T::FILE))?;
+    check_version(&value)?;
+    Ok(value)
+}
+
+fn check_version<T: Sidecar>(value: &T) -> Result<()> {
+    if value.version() > T::VERSION {
+        anyhow::bail!(
+            "{} is version {} (this er reads up to {}) — update er",
+            T::FILE,
+            value.version(),
+            T::VERSION
+        );
+    }
+    Ok(())
+}
+
+/// `T` from `dir`, `None` when there's no file yet. A file that can't be
+/// read or parsed, or is from a newer er, is an error naming it.
+pub fn load<T: Sidecar>(dir: &str) -> Result<Option<T>> {
+    let path = path::<T>(dir);
+    match read_sidecar(Path::new(&path)) {
+        Ok(content) => parse(&content).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path)),
+    }
+}
+
+/// Replace `T` in `dir` with `value`.
+pub fn save<T: Sidecar>(dir: &str, value: &T) -> Result<()> {
+    let _lock = lock::<T>(dir)?;
+    write(dir, value)
+}
+
+/// Hold `T`'s lock in `dir` across a longer load → change → [`write`], for
+/// callers that can't make the change in a [`modify`] closure.
+pub fn lock<T: Sidecar>(dir: &str) -> Result<FileLock> {
+    file_lock::lock(&path::<T>(dir))
+}
+
+/// Write `value` as `T` in `dir`; the caller holds its [`lock`].
+pub fn write<T: Sidecar>(dir: &str, value: &T) -> Result<()> {
+    file_lock::write_atomic(&path::<T>(dir), &serde_json::to_string_pretty(value)?)?;
+    #[cfg(feature = "sqlite")]
+    crate::review_db::record(dir, value);
+    Ok(())
+}
+
+/// Apply `change` to `T` in `dir` as it is on disk now, starting from
+/// `T::empty(diff_hash)` when there's no file or it's invalid JSON. Meant for
+/// additions: `change` may run more than once (see
+/// [`file_lock::update_json`]).
+pub fn update<T, F>(dir: &str, diff_hash: &str, change: F) -> Result<Updated>
+where
+    T: Sidecar,
+    F: FnMut(&mut T),
+{
+    let path = path::<T>(dir);
+    // Never replace a newer schema with an empty old one
+    if let Some(existing) = read_sidecar(Path::new(&path))
+        .ok()
+        .and_then(|content| serde_json::from_str::<T>(&content).ok())
+    {
+        check_version(&existing)?;
+    }
+    let updated = file_lock::update_json(&path, || T::empty(diff_hash), change)?;
+    #[cfg(feature = "sqlite")]
+    if let Ok(Some(value)) = load::<T>(dir) {
+        crate::review_db::record(dir, &value);
+    }
+    Ok(updated)
+}
+
+/// Edit `T` in `dir` in place under its lock. `Ok(None)` when there's no
+/// file; an error from `change` leaves the file untouched.
+pub fn modify<T, R, F>(dir: &str, change: F) -> Result<Option<R>>
+where
+    T: Sidecar,
+    F: FnOnce(&mut T) -> Result<R>,
+{
+    let _lock = lock::<T>(dir)?;
+    let Some(mut value) = load::<T>(dir)? else {
+        return Ok(None);
+    };
+    let result = change(&mut value)?;
+    write(dir, &value)?;
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_rejects_newer_schema() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dir = dir.path().to_string_lossy().into_owned();
+        assert!(load::<ErNotes>(&dir).unwrap().is_none());
+        assert_eq!(modify::<ErNotes, _, _>(&dir, |_| Ok(())).unwrap(), None);
+
+        let updated = update::<ErNotes, _>(&dir, "h1", |n| n.diff_hash = "h2".into()).unwrap();
+        assert_eq!(updated, Updated::Merged);
+        let renamed = modify::<ErNotes, _, _>(&dir, |n| Ok(n.diff_hash.clone())).unwrap();
+        assert_eq!(renamed.as_deref(), Some("h2"));
+
+        std::fs::write(
+            path::<ErNotes>(&dir),
+            r#"{"version": 2, "diff_hash": "h", "notes": []}"#,
+        )
+        .unwrap();
+        let err = load::<ErNotes>(&dir).unwrap_err().to_string();
+        assert!(err.contains("notes.json is version 2"), "{err}");
+        assert!(update::<ErNotes, _>(&dir, "h", |_| {}).is_err());
+
+        std::fs::write(path::<ErNotes>(&dir), "{ nope").unwrap();
+        assert!(load::<ErNotes>(&dir).is_err());
+        let updated = update::<ErNotes, _>(&dir, "h", |_| {}).unwrap();
+        assert_eq!(updated, Updated::StartedFresh);
+    }
+}
@@ -0,0 +1,232 @@
+//! Suppressing findings with a local note: a note whose text starts with
+//! `er:ignore` hides the findings on its hunk (the whole file for a
+//! file-level note), or only those in the listed categories with
+//! `er:ignore finding-category=style,naming`. Suppressed findings render
+//! collapsed and dimmed and don't hold back readiness or go into the
+//! hand-off.
+//!
+//! The marker lives in `notes.json`, not `review.json`, so it's kept and
+//! applied again when the AI rewrites the review; resolving or deleting the
+//! note lifts it.
+
+use std::collections::HashSet;
+
+use super::{AiState, Confidence, Finding, ReviewQuestion, RiskLevel};
+use crate::git::{DiffFile, DiffHunk};
+
+/// What a note starts with to suppress findings.
+pub const SUPPRESS_MARKER: &str = "er:ignore";
+
+/// A parsed `er:ignore` note.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Suppression {
+    /// Lowercase categories it applies to; empty for every finding
+    pub categories: Vec<String>,
+}
+
+impl Suppression {
+    /// The marker at the start of a note's text, if it has one.
+    pub fn parse(text: &str) -> Option<Self> {
+        let rest = text.trim_start().strip_prefix(SUPPRESS_MARKER)?;
+        if rest.starts_with(|c: char| !c.is_whitespace()) {
+            return None;
+        }
+        let mut categories = Vec::new();
+        for word in rest.split_whitespace() {
+            let Some(list) = word
+                .strip_prefix("finding-category=")
+                .or_else(|| word.strip_prefix("category="))
+            else {
+                continue;
+            };
+            categories.extend(
+                list.split(',')
+                    .map(|c| c.trim().to_lowercase())
+                    .filter(|c| !c.is_empty()),
+            );
+        }
+        Some(Suppression { categories })
+    }
+
+    pub fn covers(&self, category: &str) -> bool {
+        self.categories.is_empty()
+            || self
+                .categories
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(category))
+    }
+}
+
+/// Whether `note` is a suppression marker rather than a note to hand off.
+pub fn is_suppression_note(note: &ReviewQuestion) -> bool {
+    Suppression::parse(&note.text).is_some()
+}
+
+/// The hunk `finding` sits in: its own index, else the hunk whose new side
+/// holds its first line.
+fn finding_hunk(finding: &Finding, hunks: &[DiffHunk]) -> Option<usize> {
+    finding.hunk_index.or_else(|| {
+        let line = finding.line_start?;
+        hunks
+            .iter()
+            .position(|h| (h.new_start..h.new_start + h.new_count.max(1)).contains(&line))
+    })
+}
+
+impl AiState {
+    /// The open `er:ignore` note suppressing `finding` in `path`, whose
+    /// hunks are `hunks`.
+    pub fn suppressed_by(
+        &self,
+        path: &str,
+        finding: &Finding,
+        hunks: &[DiffHunk],
+    ) -> Option<&ReviewQuestion> {
+        let notes = self.notes.as_ref()?;
+        let mut hunk = None;
+        notes.notes.iter().find(|note| {
+            if note.file != path || note.resolved {
+                return false;
+            }
+            let Some(suppression) = Suppression::parse(&note.text) else {
+                return false;
+            };
+            if !suppression.covers(&finding.category) {
+                return false;
+            }
+            match note.hunk_index {
+                None => true,
+                Some(h) => *hunk.get_or_insert_with(|| finding_hunk(finding, hunks)) == Some(h),
+            }
+        })
+    }
+
+    pub fn is_suppressed(&self, path: &str, finding: &Finding, hunks: &[DiffHunk]) -> bool {
+        self.suppressed_by(path, finding, hunks).is_some()
+    }
+
+    /// `(path, id)` of every active finding an `er:ignore` note suppresses.
+    pub fn suppressed_findings(&self, files: &[DiffFile]) -> HashSet<(String, String)> {
+        let (Some(review), Some(_)) = (&self.review, &self.notes) else {
+            return HashSet::new();
+        };
+        let mut suppressed = HashSet::new();
+        for (path, fr) in &review.files {
+            let hunks = files
+                .iter()
+                .find(|f| &f.path == path)
+                .map(|f| f.hunks.as_slice())
+                .unwrap_or_default();
+            for f in fr.findings.iter().filter(|f| f.is_active()) {
+                if self.is_suppressed(path, f, hunks) {
+                    suppressed.insert((path.clone(), f.id.clone()));
+                }
+            }
+        }
+        suppressed
+    }
+
+    /// Confirmed high-severity findings still open and not suppressed: the
+    /// ones that keep a review from reading as ready.
+    pub fn blocking_findings(&self, files: &[DiffFile]) -> usize {
+        let Some(review) = &self.review else {
+            return 0;
+        };
+        let suppressed = self.suppressed_findings(files);
+        review
+            .files
+            .iter()
+            .flat_map(|(path, fr)| fr.findings.iter().map(move |f| (path, f)))
+            .filter(|(path, f)| {
+                f.is_active()
+                    && f.severity == RiskLevel::High
+                    && f.confidence == Confidence::Confirmed
+                    && !suppressed.contains(&((*path).clone(), f.id.clone()))
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{ErNotes, ErReview};
+    use crate::git;
+
+    const DIFF: &str = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                        @@ -1,2 +1,3 @@\n a\n+b\n c\n\
+                        @@ -20,2 +21,3 @@\n x\n+y\n z\n";
+
+    fn state(notes: serde_json::Value) -> AiState {
+        let finding = |id: &str, category: &str, line: usize| {
+            serde_json::json!({
+                "id": id, "severity": "high", "category": category, "title": id,
+                "line_start": line, "confidence": "confirmed",
+            })
+        };
+        let review: ErReview = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "diff_hash": "h",
+            "files": { "src/a.rs": { "risk": "high", "findings": [
+                finding("style-1", "style", 2),
+                finding("bug-1", "correctness", 2),
+                finding("style-2", "Style", 22),
+            ] } },
+        }))
+        .unwrap();
+        let notes: ErNotes = serde_json::from_value(serde_json::json!({
+            "version": 1, "diff_hash": "h", "notes": notes,
+        }))
+        .unwrap();
+        let mut ai = AiState::default();
+        ai.review = Some(review);
+        ai.notes = Some(notes);
+        ai
+    }
+
+    fn note(hunk: Option<usize>, text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "n-1", "file": "src/a.rs", "hunk_index": hunk,
+            "line_start": null, "text": text,
+        })
+    }
+
+    #[test]
+    fn markers_parse_with_and_without_categories() {
+        assert_eq!(
+            Suppression::parse("er:ignore finding-category=Style,naming  fixture code"),
+            Some(Suppression {
+                categories: vec!["style".into(), "naming".into()]
+            })
+        );
+        assert_eq!(
+            Suppression::parse("  er:ignore"),
+            Some(Suppression::default())
+        );
+        assert_eq!(Suppression::parse("er:ignored"), None);
+        assert_eq!(Suppression::parse("please er:ignore this"), None);
+    }
+
+    #[test]
+    fn a_hunk_note_suppresses_its_categories_there_only() {
+        let files = git::parse_diff(DIFF);
+        let ai = state(serde_json::json!([note(
+            Some(0),
+            "er:ignore finding-category=style"
+        )]));
+        let suppressed = ai.suppressed_findings(&files);
+        let ids: Vec<&str> = suppressed.iter().map(|(_, id)| id.as_str()).collect();
+        assert_eq!(ids, vec!["style-1"]);
+        assert_eq!(ai.blocking_findings(&files), 2);
+
+        // A file-level note without categories takes the whole file
+        let ai = state(serde_json::json!([note(None, "er:ignore")]));
+        assert_eq!(ai.suppressed_findings(&files).len(), 3);
+        assert_eq!(ai.blocking_findings(&files), 0);
+
+        // Resolving the note lifts it
+        let mut ai = ai;
+        ai.notes.as_mut().unwrap().notes[0].resolved = true;
+        assert_eq!(ai.blocking_findings(&files), 3);
+    }
+}
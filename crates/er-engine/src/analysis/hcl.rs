@@ -0,0 +1,349 @@
+//! The HCL of Terraform files, read down to its top-level blocks and their
+//! attributes. Nested blocks flatten into dotted keys (`ingress.from_port`,
+//! the second `ingress` being `ingress#2`), and each value is kept as its
+//! expression text with the whitespace collapsed — enough to tell what
+//! changed without evaluating anything.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// A top-level block, `resource "aws_s3_bucket" "logs" { … }`, or a
+/// top-level attribute (as in `.tfvars`), with an empty kind.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Block {
+    pub kind: String,
+    pub labels: Vec<String>,
+    /// Flattened key → expression
+    pub attrs: BTreeMap<String, String>,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, what: &str) -> String {
+        format!("line {}: {}", self.line, what)
+    }
+
+    /// Spaces and comments, and newlines too when `newlines`.
+    fn skip_trivia(&mut self, newlines: bool) {
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' if !newlines => return,
+                ' ' | '\t' | '\r' | '\n' => {
+                    self.bump();
+                }
+                '#' => self.skip_line(),
+                '/' if self.peek_at(1) == Some('/') => self.skip_line(),
+                '/' if self.peek_at(1) == Some('*') => {
+                    self.pos += 2;
+                    while self.peek().is_some()
+                        && !(self.peek() == Some('*') && self.peek_at(1) == Some('/'))
+                    {
+                        self.bump();
+                    }
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let mut out = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+        {
+            out.push(c);
+            self.bump();
+        }
+        out
+    }
+
+    /// A quoted string from its opening `"`, with `${…}` read through.
+    fn string(&mut self) -> Result<String, String> {
+        let start = self.line;
+        self.bump();
+        let mut out = String::new();
+        let mut interpolation = 0usize;
+        loop {
+            let Some(c) = self.bump() else {
+                return Err(format!("line {}: unclosed string", start));
+            };
+            match c {
+                '\\' => {
+                    out.push(c);
+                    if let Some(next) = self.bump() {
+                        out.push(next);
+                    }
+                    continue;
+                }
+                '"' if interpolation == 0 => return Ok(out),
+                '{' if out.ends_with(['$', '%']) => interpolation += 1,
+                '}' if interpolation > 0 => interpolation -= 1,
+                '\n' if interpolation == 0 => {
+                    return Err(format!("line {}: unclosed string", start));
+                }
+                _ => {}
+            }
+            out.push(c);
+        }
+    }
+
+    /// `<<EOF` / `<<-EOF` up to its closing marker.
+    fn heredoc(&mut self) -> Result<String, String> {
+        let start = self.line;
+        self.pos += 2;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        let marker = self.ident();
+        if marker.is_empty() {
+            return Err(self.error("expected a heredoc marker"));
+        }
+        self.skip_line();
+        let mut lines = Vec::new();
+        loop {
+            if self.bump().is_none() {
+                return Err(format!("line {}: unclosed heredoc {}", start, marker));
+            }
+            let from = self.pos;
+            self.skip_line();
+            let line: String = self.chars[from..self.pos].iter().collect();
+            if line.trim() == marker {
+                return Ok(lines.join("\n"));
+            }
+            lines.push(line);
+        }
+    }
+
+    /// An attribute's expression, to the end of its line at bracket depth 0.
+    fn expr(&mut self) -> Result<String, String> {
+        let mut out = String::new();
+        let mut depth = 0usize;
+        loop {
+            if depth > 0 {
+                self.skip_trivia(true);
+            } else {
+                self.skip_trivia(false);
+            }
+            let Some(c) = self.peek() else {
+                break;
+            };
+            match c {
+                '\n' => break,
+                '}' if depth == 0 => break,
+                '"' => {
+                    let s = self.string()?;
+                    out.push('"');
+                    out.push_str(&s);
+                    out.push('"');
+                }
+                '<' if self.peek_at(1) == Some('<') => {
+                    let body = self.heredoc()?;
+                    out.push_str(&body);
+                }
+                '(' | '[' | '{' => {
+                    depth += 1;
+                    out.push(c);
+                    self.bump();
+                }
+                ')' | ']' | '}' => {
+                    depth = depth.saturating_sub(1);
+                    out.push(c);
+                    self.bump();
+                }
+                _ => {
+                    out.push(c);
+                    self.bump();
+                }
+            }
+            if self.peek().is_some_and(char::is_whitespace) && !out.ends_with(' ') {
+                out.push(' ');
+            }
+        }
+        Ok(out.trim().to_string())
+    }
+
+    /// A block's labels, up to and including its `{`.
+    fn labels(&mut self) -> Result<Vec<String>, String> {
+        let mut labels = Vec::new();
+        loop {
+            self.skip_trivia(false);
+            match self.peek() {
+                Some('{') => {
+                    self.bump();
+                    return Ok(labels);
+                }
+                Some('"') => labels.push(self.string()?),
+                Some(c) if c.is_alphanumeric() || c == '_' => labels.push(self.ident()),
+                Some(c) => return Err(self.error(&format!("unexpected `{}`", c))),
+                None => return Err(self.error("expected `{`")),
+            }
+        }
+    }
+
+    /// A block body after its `{`, through its `}`.
+    fn body(&mut self, prefix: &str, attrs: &mut BTreeMap<String, String>) -> Result<(), String> {
+        let start = self.line;
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        loop {
+            self.skip_trivia(true);
+            let Some(c) = self.peek() else {
+                return Err(format!("line {}: unclosed block", start));
+            };
+            if c == '}' {
+                self.bump();
+                return Ok(());
+            }
+            let name = self.ident();
+            if name.is_empty() {
+                return Err(self.error(&format!("unexpected `{}`", c)));
+            }
+            self.skip_trivia(false);
+            if self.peek() == Some('=') && self.peek_at(1) != Some('=') {
+                self.bump();
+                let value = self.expr()?;
+                attrs.insert(format!("{}{}", prefix, name), value);
+                continue;
+            }
+            let labels = self.labels()?;
+            let mut key = std::iter::once(name)
+                .chain(labels)
+                .collect::<Vec<_>>()
+                .join(".");
+            let n = seen.entry(key.clone()).or_default();
+            *n += 1;
+            if *n > 1 {
+                key = format!("{}#{}", key, n);
+            }
+            self.body(&format!("{}{}.", prefix, key), attrs)?;
+        }
+    }
+}
+
+/// Parse `source` into its top-level blocks.
+pub fn parse(source: &str) -> Result<Vec<Block>, String> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    let mut blocks = Vec::new();
+    loop {
+        parser.skip_trivia(true);
+        let Some(c) = parser.peek() else {
+            return Ok(blocks);
+        };
+        let kind = parser.ident();
+        if kind.is_empty() {
+            return Err(parser.error(&format!("unexpected `{}`", c)));
+        }
+        parser.skip_trivia(false);
+        if parser.peek() == Some('=') {
+            parser.bump();
+            let value = parser.expr()?;
+            blocks.push(Block {
+                kind: String::new(),
+                labels: vec![kind],
+                attrs: BTreeMap::from([("value".to_string(), value)]),
+            });
+            continue;
+        }
+        let labels = parser.labels()?;
+        let mut attrs = BTreeMap::new();
+        parser.body("", &mut attrs)?;
+        blocks.push(Block {
+            kind,
+            labels,
+            attrs,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_flatten_into_dotted_attributes() {
+        let source = r#"
+# Public web tier
+resource "aws_security_group" "web" {
+  name = "web-${var.env}"   // interpolated
+  ingress {
+    from_port   = 443
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+  ingress {
+    from_port   = 22
+    cidr_blocks = [
+      "10.0.0.0/8",
+    ]
+  }
+  lifecycle { prevent_destroy = true }
+  policy = <<EOF
+{"Action": "*"}
+EOF
+}
+
+module "vpc" {
+  source = "./vpc"
+}
+region = "eu-west-1"
+"#;
+        let blocks = parse(source).unwrap();
+        assert_eq!(blocks.len(), 3);
+        let sg = &blocks[0];
+        assert_eq!(sg.kind, "resource");
+        assert_eq!(sg.labels, vec!["aws_security_group", "web"]);
+        let attrs: Vec<(&str, &str)> = sg
+            .attrs
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            attrs,
+            vec![
+                ("ingress#2.cidr_blocks", "[ \"10.0.0.0/8\", ]"),
+                ("ingress#2.from_port", "22"),
+                ("ingress.cidr_blocks", "[\"0.0.0.0/0\"]"),
+                ("ingress.from_port", "443"),
+                ("lifecycle.prevent_destroy", "true"),
+                ("name", "\"web-${var.env}\""),
+                ("policy", "{\"Action\": \"*\"}"),
+            ]
+        );
+        assert_eq!(blocks[1].labels, vec!["vpc"]);
+        assert_eq!(blocks[2].kind, "");
+        assert_eq!(blocks[2].attrs["value"], "\"eu-west-1\"");
+
+        assert!(parse("resource \"a\" \"b\" {\n  x = 1\n").is_err());
+    }
+}
@@ -0,0 +1,612 @@
+//! What a change to Terraform or Kubernetes files does to the
+//! infrastructure: which resources are created, modified or destroyed, and
+//! which of the fields it sets are dangerous — deletion policies turned
+//! off, ingress open to the internet, wildcard permissions.
+//!
+//! Both sides are read into resources of flattened attributes ([`hcl`]
+//! for `.tf`, [`yaml`] documents for manifests) and compared by address
+//! (`aws_s3_bucket.logs`, `Deployment prod/web`). A field is checked when
+//! it is new or its value changed, against rules keyed by the end of its
+//! dotted path.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use super::{hcl, yaml};
+
+/// The kind of infrastructure a file describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfraKind {
+    Terraform,
+    Kubernetes,
+}
+
+impl InfraKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InfraKind::Terraform => "Terraform",
+            InfraKind::Kubernetes => "Kubernetes",
+        }
+    }
+}
+
+/// What happens to a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Destroy,
+    Create,
+    Update,
+}
+
+impl Action {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Action::Create => "+",
+            Action::Destroy => "-",
+            Action::Update => "~",
+        }
+    }
+}
+
+/// One resource the change touches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceChange {
+    pub action: Action,
+    pub address: String,
+    /// Fields added, removed or changed, for updates
+    pub fields: Vec<String>,
+}
+
+/// A dangerous field value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Danger {
+    pub address: String,
+    /// Its flattened path, e.g. `ingress.cidr_blocks`
+    pub field: String,
+    /// The new value; the old one when the field was removed
+    pub value: String,
+    pub reason: &'static str,
+    /// Exposure or permissions, as opposed to losing data
+    pub security: bool,
+}
+
+/// The resource-level diff of an IaC file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InfraSummary {
+    /// Destroyed first, then created, then updated
+    pub changes: Vec<ResourceChange>,
+    pub dangers: Vec<Danger>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Test {
+    True,
+    /// A protection set to `false`; removing it counts too
+    False,
+    Any,
+    Wildcard,
+    OneOf(&'static [&'static str]),
+    Contains(&'static [&'static str]),
+    /// A policy document granting a wildcard action
+    WildcardPolicy,
+}
+
+/// `(field, test, reason, security)`. A field matches when the attribute
+/// path, with indexes dropped, is it or ends in `.field`.
+const RULES: &[(&str, Test, &str, bool)] = &[
+    // Deletion
+    (
+        "lifecycle.prevent_destroy",
+        Test::False,
+        "prevent_destroy is off",
+        false,
+    ),
+    (
+        "deletion_protection",
+        Test::False,
+        "deletion protection is off",
+        false,
+    ),
+    (
+        "force_destroy",
+        Test::True,
+        "destroying it deletes everything in it",
+        false,
+    ),
+    (
+        "skip_final_snapshot",
+        Test::True,
+        "no final snapshot when it is destroyed",
+        false,
+    ),
+    (
+        "deletion_policy",
+        Test::OneOf(&["Delete", "DELETE"]),
+        "deleted along with the resource",
+        false,
+    ),
+    (
+        "persistentVolumeReclaimPolicy",
+        Test::OneOf(&["Delete"]),
+        "the volume is deleted with its claim",
+        false,
+    ),
+    (
+        "reclaimPolicy",
+        Test::OneOf(&["Delete"]),
+        "volumes are deleted with their claims",
+        false,
+    ),
+    // Public exposure
+    (
+        "cidr_blocks",
+        Test::Contains(&["0.0.0.0/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "ipv6_cidr_blocks",
+        Test::Contains(&["::/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "cidr_ipv4",
+        Test::Contains(&["0.0.0.0/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "source_ranges",
+        Test::Contains(&["0.0.0.0/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "loadBalancerSourceRanges",
+        Test::Contains(&["0.0.0.0/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "ipBlock.cidr",
+        Test::Contains(&["0.0.0.0/0"]),
+        "open to the whole internet",
+        true,
+    ),
+    (
+        "publicly_accessible",
+        Test::True,
+        "publicly accessible",
+        true,
+    ),
+    (
+        "associate_public_ip_address",
+        Test::True,
+        "gets a public IP",
+        true,
+    ),
+    (
+        "acl",
+        Test::Contains(&["public-read"]),
+        "public bucket ACL",
+        true,
+    ),
+    (
+        "block_public_acls",
+        Test::False,
+        "public access block is off",
+        true,
+    ),
+    (
+        "block_public_policy",
+        Test::False,
+        "public access block is off",
+        true,
+    ),
+    (
+        "restrict_public_buckets",
+        Test::False,
+        "public access block is off",
+        true,
+    ),
+    (
+        "spec.type",
+        Test::OneOf(&["LoadBalancer", "NodePort"]),
+        "exposed outside the cluster",
+        true,
+    ),
+    ("hostNetwork", Test::True, "shares the node's network", true),
+    ("hostPID", Test::True, "sees the node's processes", true),
+    ("privileged", Test::True, "privileged container", true),
+    (
+        "allowPrivilegeEscalation",
+        Test::True,
+        "can escalate privileges",
+        true,
+    ),
+    ("runAsUser", Test::OneOf(&["0"]), "runs as root", true),
+    (
+        "hostPath.path",
+        Test::Any,
+        "mounts a path of the node",
+        true,
+    ),
+    // Wide permissions
+    ("actions", Test::Wildcard, "wildcard IAM action", true),
+    ("Action", Test::Wildcard, "wildcard IAM action", true),
+    (
+        "principals.identifiers",
+        Test::Wildcard,
+        "anyone can assume it",
+        true,
+    ),
+    ("Principal", Test::Wildcard, "anyone can assume it", true),
+    ("policy", Test::WildcardPolicy, "wildcard IAM action", true),
+    (
+        "assume_role_policy",
+        Test::WildcardPolicy,
+        "wildcard IAM action",
+        true,
+    ),
+    (
+        "policy_arn",
+        Test::Contains(&["AdministratorAccess"]),
+        "full admin access",
+        true,
+    ),
+    (
+        "role",
+        Test::OneOf(&["roles/owner", "roles/editor"]),
+        "project-wide role",
+        true,
+    ),
+    ("rules.verbs", Test::Wildcard, "every verb allowed", true),
+    (
+        "rules.resources",
+        Test::Wildcard,
+        "every resource allowed",
+        true,
+    ),
+    (
+        "roleRef.name",
+        Test::OneOf(&["cluster-admin"]),
+        "bound to cluster-admin",
+        true,
+    ),
+];
+
+/// Kubernetes fields that change on every apply.
+const K8S_NOISE: &[&str] = &[
+    "status",
+    "metadata.annotations.kubectl.kubernetes.io/last-applied-configuration",
+    "metadata.resourceVersion",
+    "metadata.uid",
+    "metadata.generation",
+    "metadata.creationTimestamp",
+    "metadata.managedFields",
+];
+
+fn passes(test: Test, value: &str) -> bool {
+    let bare = value.trim().trim_matches(['"', '\'']);
+    match test {
+        Test::True => bare == "true",
+        Test::False => bare == "false",
+        Test::Any => true,
+        Test::Wildcard => bare == "*" || value.contains("\"*\"") || value.contains(":*\""),
+        Test::OneOf(values) => values.contains(&bare),
+        Test::Contains(parts) => parts.iter().any(|p| value.contains(p)),
+        Test::WildcardPolicy => {
+            static RE: OnceLock<regex::Regex> = OnceLock::new();
+            RE.get_or_init(|| {
+                regex::Regex::new(r#"(?i)"?actions?"?\s*[:=]\s*\[?\s*"(?:[a-z0-9-]+:)?\*""#)
+                    .expect("policy pattern")
+            })
+            .is_match(value)
+        }
+    }
+}
+
+/// `field` without its `[n]` indexes and `#n` repeats.
+fn bare_path(field: &str) -> String {
+    field
+        .split('.')
+        .map(|part| {
+            let part = part.split('#').next().unwrap_or(part);
+            part.split('[').next().unwrap_or(part)
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The danger of `field` going from `old` to `new`, if any.
+fn danger(address: &str, field: &str, old: Option<&str>, new: Option<&str>) -> Option<Danger> {
+    if old == new {
+        return None;
+    }
+    let path = bare_path(field);
+    let (_, test, reason, security) = RULES
+        .iter()
+        .find(|(rule, ..)| path == *rule || path.ends_with(&format!(".{}", rule)))?;
+    let value = match new {
+        Some(new) if passes(*test, new) => new,
+        // A protection that was on and is now gone
+        None if matches!(test, Test::False) && old.is_some_and(|o| passes(Test::True, o)) => old?,
+        _ => return None,
+    };
+    Some(Danger {
+        address: address.to_string(),
+        field: field.to_string(),
+        value: value.to_string(),
+        reason,
+        security: *security,
+    })
+}
+
+/// The kind of `path`, if it is an IaC file. `text` is a side of it, for
+/// manifests only recognisable by their `apiVersion` and `kind`.
+pub fn infra_kind(path: &str, text: &str) -> Option<InfraKind> {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+    if name.ends_with(".tf") || name.ends_with(".tfvars") {
+        return Some(InfraKind::Terraform);
+    }
+    if !(name.ends_with(".yaml") || name.ends_with(".yml")) {
+        return None;
+    }
+    // Helm templates aren't YAML until rendered
+    if text.contains("{{") {
+        return None;
+    }
+    let top = |key: &str| text.lines().any(|l| l.starts_with(key));
+    (top("apiVersion:") && top("kind:")).then_some(InfraKind::Kubernetes)
+}
+
+type Resources = Vec<(String, BTreeMap<String, String>)>;
+
+fn terraform_resources(text: &str) -> Result<Resources, String> {
+    Ok(hcl::parse(text)?
+        .into_iter()
+        .filter_map(|block| {
+            let address = match (block.kind.as_str(), block.labels.as_slice()) {
+                ("resource", [ty, name]) => format!("{}.{}", ty, name),
+                ("data", [ty, name]) => format!("data.{}.{}", ty, name),
+                ("module", [name]) => format!("module.{}", name),
+                ("", [name]) => format!("var.{}", name),
+                _ => return None,
+            };
+            Some((address, block.attrs))
+        })
+        .collect())
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, String>) {
+    if K8S_NOISE.contains(&prefix) {
+        return;
+    }
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&join(key), value, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{}[{}]", prefix, i), item, out);
+            }
+        }
+        Value::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+fn kubernetes_resources(text: &str) -> Result<Resources, String> {
+    let mut resources = Vec::new();
+    let mut add = |doc: &Value| {
+        let Some(kind) = doc.get("kind").and_then(Value::as_str) else {
+            return;
+        };
+        let meta = doc.get("metadata");
+        let name = meta
+            .and_then(|m| m.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or("?");
+        let address = match meta
+            .and_then(|m| m.get("namespace"))
+            .and_then(Value::as_str)
+        {
+            Some(ns) => format!("{} {}/{}", kind, ns, name),
+            None => format!("{} {}", kind, name),
+        };
+        let mut attrs = BTreeMap::new();
+        flatten("", doc, &mut attrs);
+        resources.push((address, attrs));
+    };
+    for doc in yaml::parse_documents(text)? {
+        match doc.get("items").and_then(Value::as_array) {
+            Some(items) if doc.get("kind").and_then(Value::as_str) == Some("List") => {
+                items.iter().for_each(&mut add)
+            }
+            _ => add(&doc),
+        }
+    }
+    Ok(resources)
+}
+
+/// The resource-level diff from `old` to `new`. An empty side stands for
+/// an added or deleted file. Errors name the side that didn't parse.
+pub fn summarize(kind: InfraKind, old: &str, new: &str) -> Result<InfraSummary, String> {
+    let read = |text: &str| match kind {
+        InfraKind::Terraform => terraform_resources(text),
+        InfraKind::Kubernetes => kubernetes_resources(text),
+    };
+    let old = read(old).map_err(|e| format!("old side: {}", e))?;
+    let new = read(new).map_err(|e| format!("new side: {}", e))?;
+    let mut summary = InfraSummary::default();
+    for (address, _) in &old {
+        if !new.iter().any(|(a, _)| a == address) {
+            summary.changes.push(ResourceChange {
+                action: Action::Destroy,
+                address: address.clone(),
+                fields: Vec::new(),
+            });
+        }
+    }
+    for (address, attrs) in &new {
+        let before = old.iter().find(|(a, _)| a == address).map(|(_, b)| b);
+        let empty = BTreeMap::new();
+        let was = before.unwrap_or(&empty);
+        let mut fields: Vec<String> = attrs
+            .keys()
+            .chain(was.keys())
+            .filter(|k| attrs.get(*k) != was.get(*k))
+            .cloned()
+            .collect();
+        fields.sort();
+        fields.dedup();
+        for field in &fields {
+            let old = was.get(field).map(String::as_str);
+            let new = attrs.get(field).map(String::as_str);
+            summary.dangers.extend(danger(address, field, old, new));
+        }
+        match before {
+            None => summary.changes.push(ResourceChange {
+                action: Action::Create,
+                address: address.clone(),
+                fields: Vec::new(),
+            }),
+            Some(_) if !fields.is_empty() => summary.changes.push(ResourceChange {
+                action: Action::Update,
+                address: address.clone(),
+                fields,
+            }),
+            Some(_) => {}
+        }
+    }
+    summary.changes.sort_by_key(|c| c.action);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terraform_resources_are_diffed_and_dangers_flagged() {
+        let old = r#"
+resource "aws_s3_bucket" "logs" {
+  bucket = "logs"
+  lifecycle { prevent_destroy = true }
+}
+resource "aws_security_group" "web" {
+  ingress { cidr_blocks = ["10.0.0.0/8"] }
+}
+resource "aws_instance" "old" { ami = "x" }
+"#;
+        let new = r#"
+resource "aws_s3_bucket" "logs" {
+  bucket        = "logs"
+  force_destroy = true
+}
+resource "aws_security_group" "web" {
+  ingress { cidr_blocks = ["0.0.0.0/0"] }
+}
+resource "aws_iam_policy" "all" {
+  policy = jsonencode({ Statement = [{ Action = "*", Effect = "Allow" }] })
+}
+"#;
+        let summary = summarize(InfraKind::Terraform, old, new).unwrap();
+        let changes: Vec<(Action, &str)> = summary
+            .changes
+            .iter()
+            .map(|c| (c.action, c.address.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (Action::Destroy, "aws_instance.old"),
+                (Action::Create, "aws_iam_policy.all"),
+                (Action::Update, "aws_s3_bucket.logs"),
+                (Action::Update, "aws_security_group.web"),
+            ]
+        );
+        assert_eq!(
+            summary.changes[2].fields,
+            vec!["force_destroy", "lifecycle.prevent_destroy"]
+        );
+        let dangers: Vec<(&str, &str, bool)> = summary
+            .dangers
+            .iter()
+            .map(|d| (d.field.as_str(), d.reason, d.security))
+            .collect();
+        assert_eq!(
+            dangers,
+            vec![
+                (
+                    "force_destroy",
+                    "destroying it deletes everything in it",
+                    false
+                ),
+                ("lifecycle.prevent_destroy", "prevent_destroy is off", false),
+                ("ingress.cidr_blocks", "open to the whole internet", true),
+                ("policy", "wildcard IAM action", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn kubernetes_manifests_are_diffed_per_document() {
+        let old = "apiVersion: v1\nkind: Service\nmetadata:\n  name: web\n  namespace: prod\nspec:\n  type: ClusterIP\n";
+        let new = "apiVersion: v1\nkind: Service\nmetadata:\n  name: web\n  namespace: prod\nspec:\n  type: LoadBalancer\n\
+                   ---\napiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web\nspec:\n  template:\n    spec:\n\
+                   \x20     containers:\n        - name: app\n          securityContext:\n            privileged: true\n";
+        assert_eq!(
+            infra_kind("deploy/web.yaml", new),
+            Some(InfraKind::Kubernetes)
+        );
+        assert_eq!(
+            infra_kind("chart/templates/web.yaml", "{{ .Values }}"),
+            None
+        );
+        let summary = summarize(InfraKind::Kubernetes, old, new).unwrap();
+        let changes: Vec<(Action, &str)> = summary
+            .changes
+            .iter()
+            .map(|c| (c.action, c.address.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (Action::Create, "Deployment web"),
+                (Action::Update, "Service prod/web"),
+            ]
+        );
+        let dangers: Vec<(&str, &str)> = summary
+            .dangers
+            .iter()
+            .map(|d| (d.field.as_str(), d.reason))
+            .collect();
+        assert_eq!(
+            dangers,
+            vec![
+                ("spec.type", "exposed outside the cluster"),
+                (
+                    "spec.template.spec.containers[0].securityContext.privileged",
+                    "privileged container"
+                ),
+            ]
+        );
+    }
+}
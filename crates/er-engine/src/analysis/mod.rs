@@ -0,0 +1,187 @@
+//! Semantic diffs of API contracts: what a change to a `.proto` file or an
+//! OpenAPI / Swagger spec does to the API, rather than which lines moved.
+//!
+//! Both sides are parsed by purpose-built readers ([`proto`], and
+//! [`openapi`] over the YAML subset in [`yaml`]) into a small model of
+//! messages, fields, endpoints and schemas; the two models are compared
+//! and each difference classified as breaking or not, following the usual
+//! wire- and client-compatibility rules.
+//!
+//! [`infra`] does the same for Terraform and Kubernetes files, resource by
+//! resource, over [`hcl`] and the YAML reader.
+//!
+//! [`notebook`] and [`tabular`] are views rather than verdicts: Jupyter
+//! notebooks cell by cell, CSV / TSV changes as aligned columns.
+
+pub mod hcl;
+pub mod infra;
+pub mod notebook;
+pub mod openapi;
+pub mod proto;
+pub mod tabular;
+pub mod yaml;
+
+/// The kind of contract a file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractKind {
+    Proto,
+    OpenApi,
+}
+
+impl ContractKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContractKind::Proto => "protobuf",
+            ContractKind::OpenApi => "OpenAPI",
+        }
+    }
+}
+
+/// Whether an element was added, removed or changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+        }
+    }
+}
+
+/// One difference between the two sides of a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractChange {
+    pub kind: ChangeKind,
+    /// What changed, e.g. `message User field 3 (email)` or
+    /// `GET /users/{id}`
+    pub subject: String,
+    /// How, e.g. `string → bytes`; empty for plain additions and removals
+    pub detail: String,
+    /// Existing clients or stored data can break
+    pub breaking: bool,
+}
+
+impl ContractChange {
+    fn new(kind: ChangeKind, subject: String, detail: String, breaking: bool) -> Self {
+        ContractChange {
+            kind,
+            subject,
+            detail,
+            breaking,
+        }
+    }
+}
+
+/// The contract `path` holds, if any. `text` is its new side (or old, for
+/// a deleted file), for specs only recognisable by their top-level key.
+pub fn contract_kind(path: &str, text: &str) -> Option<ContractKind> {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+    if name.ends_with(".proto") {
+        return Some(ContractKind::Proto);
+    }
+    let spec_ext = [".yaml", ".yml", ".json"]
+        .iter()
+        .any(|ext| name.ends_with(ext));
+    if !spec_ext {
+        return None;
+    }
+    if name.contains("openapi") || name.contains("swagger") {
+        return Some(ContractKind::OpenApi);
+    }
+    // `openapi: 3.0.0` / `"swagger": "2.0"` near the top
+    text.lines()
+        .take(20)
+        .map(|l| l.trim_start_matches(['{', ' ']).trim_start_matches('"'))
+        .any(|l| l.starts_with("openapi") || l.starts_with("swagger"))
+        .then_some(ContractKind::OpenApi)
+}
+
+/// Index pairs of a longest common subsequence of `a` and `b`, in order.
+/// Past `MAX_LCS_CELLS` comparisons nothing is matched.
+pub(crate) fn lcs<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    const MAX_LCS_CELLS: usize = 4_000_000;
+    if a.is_empty() || b.is_empty() || a.len().saturating_mul(b.len()) > MAX_LCS_CELLS {
+        return Vec::new();
+    }
+    // lengths[i][j]: LCS of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut pairs = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// The semantic diff from `old` to `new`, breaking changes first. An empty
+/// side stands for an added or deleted file. Errors name the side that
+/// didn't parse.
+pub fn contract_diff(
+    kind: ContractKind,
+    old: &str,
+    new: &str,
+) -> Result<Vec<ContractChange>, String> {
+    let mut changes = match kind {
+        ContractKind::Proto => {
+            let old = proto::parse(old).map_err(|e| format!("old side: {}", e))?;
+            let new = proto::parse(new).map_err(|e| format!("new side: {}", e))?;
+            proto::diff(&old, &new)
+        }
+        ContractKind::OpenApi => {
+            let old = openapi::parse(old).map_err(|e| format!("old side: {}", e))?;
+            let new = openapi::parse(new).map_err(|e| format!("new side: {}", e))?;
+            openapi::diff(&old, &new)
+        }
+    };
+    // Stable: each side keeps its model order
+    changes.sort_by_key(|c| !c.breaking);
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specs_are_recognised_by_name_or_top_level_key() {
+        assert_eq!(
+            contract_kind("api/user.proto", ""),
+            Some(ContractKind::Proto)
+        );
+        assert_eq!(
+            contract_kind("docs/openapi.yaml", ""),
+            Some(ContractKind::OpenApi)
+        );
+        assert_eq!(
+            contract_kind("api/v1.json", "{\n  \"swagger\": \"2.0\",\n"),
+            Some(ContractKind::OpenApi)
+        );
+        assert_eq!(contract_kind("config/app.yaml", "port: 80\n"), None);
+        assert_eq!(contract_kind("src/main.rs", "openapi"), None);
+    }
+}
@@ -0,0 +1,288 @@
+//! Jupyter notebooks compared cell by cell. Execution counts and output
+//! blobs are what make a notebook's JSON diff unreadable, so cells are
+//! compared on their source alone; outputs are reduced to a line each
+//! (`stream · 3 lines`, `image/png`) and only said to have changed.
+
+use serde_json::Value;
+
+use super::lcs;
+use crate::git::LineType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Code,
+    Markdown,
+    Raw,
+}
+
+impl CellKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CellKind::Code => "code",
+            CellKind::Markdown => "markdown",
+            CellKind::Raw => "raw",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub kind: CellKind,
+    pub source: String,
+    /// One line per output
+    pub outputs: Vec<String>,
+    /// The outputs' JSON without execution counts, to tell when they changed
+    output_data: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellChange {
+    Unchanged,
+    Added,
+    Removed,
+    Modified,
+}
+
+impl CellChange {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CellChange::Unchanged => " ",
+            CellChange::Added => "+",
+            CellChange::Removed => "-",
+            CellChange::Modified => "~",
+        }
+    }
+}
+
+/// One cell of the notebook diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellDiff {
+    pub change: CellChange,
+    pub kind: CellKind,
+    /// 1-based position on the new side, the old one for removed cells
+    pub number: usize,
+    /// The source, lines marked added / deleted within a modified cell
+    pub lines: Vec<(LineType, String)>,
+    /// The new outputs, the old ones for removed cells
+    pub outputs: Vec<String>,
+    pub outputs_changed: bool,
+}
+
+/// `source`, a string or a list of line strings.
+fn text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+fn output_summary(output: &Value) -> String {
+    let kind = output
+        .get("output_type")
+        .and_then(Value::as_str)
+        .unwrap_or("output");
+    match kind {
+        "stream" => {
+            let name = output.get("name").and_then(Value::as_str).unwrap_or(kind);
+            let lines = text(output.get("text")).lines().count();
+            let plural = if lines == 1 { "" } else { "s" };
+            format!("{} · {} line{}", name, lines, plural)
+        }
+        "error" => {
+            let field = |key: &str| output.get(key).and_then(Value::as_str).unwrap_or("");
+            format!("error · {}: {}", field("ename"), field("evalue"))
+        }
+        _ => {
+            let Some(Value::Object(data)) = output.get("data") else {
+                return kind.to_string();
+            };
+            // The plain-text rendering when that's all there is
+            match (data.len(), data.get("text/plain")) {
+                (1, Some(plain)) => {
+                    let plain = text(Some(plain));
+                    let first = plain.lines().next().unwrap_or_default();
+                    format!("text/plain · {}", first)
+                }
+                _ => data
+                    .keys()
+                    .filter(|k| *k != "text/plain")
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+        }
+    }
+}
+
+/// The cells of a notebook; empty text is an empty notebook.
+pub fn parse(source: &str) -> Result<Vec<Cell>, String> {
+    if source.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let root: Value = serde_json::from_str(source).map_err(|e| e.to_string())?;
+    let Some(Value::Array(cells)) = root.get("cells") else {
+        return Err("no `cells` list".into());
+    };
+    Ok(cells
+        .iter()
+        .map(|cell| {
+            let mut outputs = cell.get("outputs").cloned().unwrap_or(Value::Null);
+            for output in outputs.as_array_mut().into_iter().flatten() {
+                if let Some(output) = output.as_object_mut() {
+                    output.remove("execution_count");
+                }
+            }
+            Cell {
+                kind: match cell.get("cell_type").and_then(Value::as_str) {
+                    Some("markdown") => CellKind::Markdown,
+                    Some("raw") => CellKind::Raw,
+                    _ => CellKind::Code,
+                },
+                source: text(cell.get("source")),
+                outputs: outputs
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(output_summary)
+                    .collect(),
+                output_data: outputs.to_string(),
+            }
+        })
+        .collect())
+}
+
+/// `old` to `new` line by line.
+fn line_diff(old: &str, new: &str) -> Vec<(LineType, String)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (oi, nj) in lcs(&old, &new).into_iter().chain([(old.len(), new.len())]) {
+        out.extend(old[i..oi].iter().map(|l| (LineType::Delete, l.to_string())));
+        out.extend(new[j..nj].iter().map(|l| (LineType::Add, l.to_string())));
+        if oi < old.len() {
+            out.push((LineType::Context, old[oi].to_string()));
+        }
+        (i, j) = (oi + 1, nj + 1);
+    }
+    out
+}
+
+fn whole(cell: &Cell, change: CellChange, number: usize) -> CellDiff {
+    let line_type = match change {
+        CellChange::Added => LineType::Add,
+        CellChange::Removed => LineType::Delete,
+        _ => LineType::Context,
+    };
+    CellDiff {
+        change,
+        kind: cell.kind,
+        number,
+        lines: cell
+            .source
+            .lines()
+            .map(|l| (line_type, l.to_string()))
+            .collect(),
+        outputs: cell.outputs.clone(),
+        outputs_changed: false,
+    }
+}
+
+/// The cells from `old` to `new`: unchanged ones matched on kind and
+/// source, and between them removed and added cells of the same kind
+/// paired up as modified.
+pub fn diff(old: &[Cell], new: &[Cell]) -> Vec<CellDiff> {
+    let key = |c: &Cell| (c.kind, c.source.clone());
+    let old_keys: Vec<_> = old.iter().map(key).collect();
+    let new_keys: Vec<_> = new.iter().map(key).collect();
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (oi, nj) in lcs(&old_keys, &new_keys)
+        .into_iter()
+        .chain([(old.len(), new.len())])
+    {
+        let (mut removed, mut added) = (i..oi, j..nj);
+        while let (Some(o), Some(n)) = (old.get(removed.start), new.get(added.start)) {
+            if removed.is_empty() || added.is_empty() || o.kind != n.kind {
+                break;
+            }
+            out.push(CellDiff {
+                change: CellChange::Modified,
+                kind: n.kind,
+                number: added.start + 1,
+                lines: line_diff(&o.source, &n.source),
+                outputs: n.outputs.clone(),
+                outputs_changed: o.output_data != n.output_data,
+            });
+            removed.start += 1;
+            added.start += 1;
+        }
+        out.extend(removed.map(|r| whole(&old[r], CellChange::Removed, r + 1)));
+        out.extend(added.map(|a| whole(&new[a], CellChange::Added, a + 1)));
+        if let (Some(o), Some(n)) = (old.get(oi), new.get(nj)) {
+            let mut cell = whole(n, CellChange::Unchanged, nj + 1);
+            cell.outputs_changed = o.output_data != n.output_data;
+            out.push(cell);
+        }
+        (i, j) = (oi + 1, nj + 1);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notebook(cells: &str) -> String {
+        format!(
+            r#"{{"nbformat": 4, "metadata": {{}}, "cells": [{}]}}"#,
+            cells
+        )
+    }
+
+    #[test]
+    fn cells_are_compared_on_source_without_the_noise() {
+        let old = notebook(
+            r##"{"cell_type": "markdown", "source": ["# Load\n"]},
+               {"cell_type": "code", "execution_count": 3, "source": ["import pandas as pd\n", "df = pd.read_csv('a.csv')"],
+                "outputs": [{"output_type": "stream", "name": "stdout", "text": ["ok\n"]}]},
+               {"cell_type": "code", "execution_count": 4, "source": "df.plot()",
+                "outputs": [{"output_type": "display_data", "data": {"image/png": "iVBOR…", "text/plain": "<Figure>"}}]}"##,
+        );
+        let new = notebook(
+            r##"{"cell_type": "markdown", "source": ["# Load\n"]},
+               {"cell_type": "code", "execution_count": 9, "source": ["import pandas as pd\n", "df = pd.read_csv('b.csv')"],
+                "outputs": [{"output_type": "stream", "name": "stdout", "text": ["ok\n"]}]},
+               {"cell_type": "code", "execution_count": 10, "source": "df.plot()",
+                "outputs": [{"output_type": "display_data", "data": {"image/png": "AAAA…", "text/plain": "<Figure>"}}]},
+               {"cell_type": "markdown", "source": "Done"}"##,
+        );
+        let cells = diff(&parse(&old).unwrap(), &parse(&new).unwrap());
+        let summary: Vec<(CellChange, usize, bool)> = cells
+            .iter()
+            .map(|c| (c.change, c.number, c.outputs_changed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (CellChange::Unchanged, 1, false),
+                (CellChange::Modified, 2, false),
+                // A new image is only an output change
+                (CellChange::Unchanged, 3, true),
+                (CellChange::Added, 4, false),
+            ]
+        );
+        assert_eq!(
+            cells[1].lines,
+            vec![
+                (LineType::Context, "import pandas as pd".to_string()),
+                (LineType::Delete, "df = pd.read_csv('a.csv')".to_string()),
+                (LineType::Add, "df = pd.read_csv('b.csv')".to_string()),
+            ]
+        );
+        assert_eq!(cells[1].outputs, vec!["stdout · 1 line"]);
+        assert_eq!(cells[2].outputs, vec!["image/png"]);
+        assert!(parse("{\"cells\": 3}").is_err());
+    }
+}
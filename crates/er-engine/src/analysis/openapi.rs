@@ -0,0 +1,468 @@
+//! OpenAPI 3 and Swagger 2 specs, JSON or YAML, read down to what clients
+//! depend on: each operation's parameters, request body and response codes,
+//! and each schema's properties.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use super::{yaml, ChangeKind, ContractChange};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "patch", "options", "head", "trace",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub required: bool,
+    /// The schema type, `?` when not given
+    pub ty: String,
+}
+
+/// One operation, `GET /users/{id}`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Endpoint {
+    /// Keyed by `(in, name)`, e.g. `("query", "page")`
+    pub params: BTreeMap<(String, String), Param>,
+    /// `None` without a request body
+    pub body_required: Option<bool>,
+    pub responses: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Schema {
+    /// Property name → type
+    pub properties: BTreeMap<String, String>,
+    pub required: BTreeSet<String>,
+}
+
+/// The parts of a spec a contract diff compares.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Spec {
+    /// Keyed by `METHOD /path`
+    pub endpoints: BTreeMap<String, Endpoint>,
+    /// `components.schemas` (v3) or `definitions` (v2)
+    pub schemas: BTreeMap<String, Schema>,
+}
+
+/// The type of a schema node: `$ref`s by their last segment, arrays as
+/// `array<T>`, a format appended as `string(uuid)`.
+fn type_of(schema: &Value) -> String {
+    if let Some(r) = schema.get("$ref").and_then(Value::as_str) {
+        return r.rsplit('/').next().unwrap_or(r).to_string();
+    }
+    let ty = match schema.get("type") {
+        Some(Value::String(s)) => s.clone(),
+        // 3.1: `type: [string, "null"]`
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("|"),
+        _ => {
+            for key in ["oneOf", "anyOf", "allOf"] {
+                if let Some(Value::Array(items)) = schema.get(key) {
+                    let parts: Vec<String> = items.iter().map(type_of).collect();
+                    return format!("{}<{}>", key, parts.join(", "));
+                }
+            }
+            return "?".into();
+        }
+    };
+    if ty == "array" {
+        let items = schema.get("items").map_or_else(|| "?".into(), type_of);
+        return format!("array<{}>", items);
+    }
+    match schema.get("format").and_then(Value::as_str) {
+        Some(format) => format!("{}({})", ty, format),
+        None => ty,
+    }
+}
+
+/// `node`, or what its local `$ref` points at.
+fn resolve<'a>(root: &'a Value, node: &'a Value) -> &'a Value {
+    match node.get("$ref").and_then(Value::as_str) {
+        Some(r) if r.starts_with("#/") => r[2..]
+            .split('/')
+            .try_fold(root, |at, key| {
+                at.get(key.replace("~1", "/").replace("~0", "~"))
+            })
+            .unwrap_or(node),
+        _ => node,
+    }
+}
+
+fn params(root: &Value, list: Option<&Value>, into: &mut BTreeMap<(String, String), Param>) {
+    for p in list.and_then(Value::as_array).into_iter().flatten() {
+        let p = resolve(root, p);
+        let (Some(name), Some(location)) = (
+            p.get("name").and_then(Value::as_str),
+            p.get("in").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        // v2 puts the type on the parameter, v3 in its schema
+        let ty = match p.get("schema") {
+            Some(schema) => type_of(schema),
+            None => type_of(p),
+        };
+        let required = p.get("required").and_then(Value::as_bool).unwrap_or(false);
+        if location == "body" {
+            continue;
+        }
+        into.insert(
+            (location.to_string(), name.to_string()),
+            Param { required, ty },
+        );
+    }
+}
+
+fn endpoint(root: &Value, shared: Option<&Value>, op: &Value) -> Endpoint {
+    let mut endpoint = Endpoint::default();
+    params(root, shared, &mut endpoint.params);
+    params(root, op.get("parameters"), &mut endpoint.params);
+    endpoint.body_required = match op.get("requestBody") {
+        Some(body) => Some(
+            resolve(root, body)
+                .get("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        ),
+        None => op
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|p| resolve(root, p))
+            .find(|p| p.get("in").and_then(Value::as_str) == Some("body"))
+            .map(|p| p.get("required").and_then(Value::as_bool).unwrap_or(false)),
+    };
+    if let Some(Value::Object(responses)) = op.get("responses") {
+        endpoint.responses = responses.keys().cloned().collect();
+    }
+    endpoint
+}
+
+fn schema(node: &Value) -> Schema {
+    let mut schema = Schema::default();
+    if let Some(Value::Object(props)) = node.get("properties") {
+        for (name, prop) in props {
+            schema.properties.insert(name.clone(), type_of(prop));
+        }
+    }
+    if let Some(Value::Array(required)) = node.get("required") {
+        schema.required = required
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+    }
+    schema
+}
+
+/// Parse a spec; JSON when it starts with `{`, YAML otherwise. Empty text
+/// is an empty spec.
+pub fn parse(text: &str) -> Result<Spec, String> {
+    let trimmed = text.trim_start();
+    if trimmed.is_empty() {
+        return Ok(Spec::default());
+    }
+    let root: Value = if trimmed.starts_with('{') {
+        serde_json::from_str(text).map_err(|e| e.to_string())?
+    } else {
+        yaml::parse(text)?
+    };
+    let mut spec = Spec::default();
+    if let Some(Value::Object(paths)) = root.get("paths") {
+        for (path, item) in paths {
+            let item = resolve(&root, item);
+            let shared = item.get("parameters");
+            for method in METHODS {
+                if let Some(op) = item.get(*method) {
+                    spec.endpoints.insert(
+                        format!("{} {}", method.to_ascii_uppercase(), path),
+                        endpoint(&root, shared, op),
+                    );
+                }
+            }
+        }
+    }
+    let schemas = root
+        .pointer("/components/schemas")
+        .or_else(|| root.get("definitions"));
+    if let Some(Value::Object(schemas)) = schemas {
+        for (name, node) in schemas {
+            spec.schemas.insert(name.clone(), schema(node));
+        }
+    }
+    Ok(spec)
+}
+
+fn diff_endpoint(name: &str, old: &Endpoint, new: &Endpoint, out: &mut Vec<ContractChange>) {
+    for ((location, param), o) in &old.params {
+        let subject = format!("{} {} param {}", name, location, param);
+        match new.params.get(&(location.clone(), param.clone())) {
+            None => out.push(ContractChange::new(
+                ChangeKind::Removed,
+                subject,
+                String::new(),
+                // A path parameter going away changes the route
+                location == "path",
+            )),
+            Some(n) => {
+                if n.ty != o.ty {
+                    out.push(ContractChange::new(
+                        ChangeKind::Changed,
+                        subject.clone(),
+                        format!("{} → {}", o.ty, n.ty),
+                        true,
+                    ));
+                }
+                if n.required != o.required {
+                    let detail = if n.required {
+                        "now required"
+                    } else {
+                        "now optional"
+                    };
+                    out.push(ContractChange::new(
+                        ChangeKind::Changed,
+                        subject,
+                        detail.into(),
+                        n.required,
+                    ));
+                }
+            }
+        }
+    }
+    for ((location, param), n) in &new.params {
+        if !old.params.contains_key(&(location.clone(), param.clone())) {
+            let detail = if n.required { "required" } else { "optional" };
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("{} {} param {}", name, location, param),
+                format!("{}, {}", n.ty, detail),
+                n.required,
+            ));
+        }
+    }
+    let body = format!("{} request body", name);
+    match (old.body_required, new.body_required) {
+        (None, Some(required)) => out.push(ContractChange::new(
+            ChangeKind::Added,
+            body,
+            if required { "required" } else { "optional" }.into(),
+            required,
+        )),
+        (Some(_), None) => out.push(ContractChange::new(
+            ChangeKind::Removed,
+            body,
+            String::new(),
+            true,
+        )),
+        (Some(false), Some(true)) => out.push(ContractChange::new(
+            ChangeKind::Changed,
+            body,
+            "now required".into(),
+            true,
+        )),
+        _ => {}
+    }
+    for code in old.responses.difference(&new.responses) {
+        out.push(ContractChange::new(
+            ChangeKind::Removed,
+            format!("{} response {}", name, code),
+            String::new(),
+            // Clients handle the success they were promised
+            code.starts_with('2'),
+        ));
+    }
+    for code in new.responses.difference(&old.responses) {
+        out.push(ContractChange::new(
+            ChangeKind::Added,
+            format!("{} response {}", name, code),
+            String::new(),
+            false,
+        ));
+    }
+}
+
+fn diff_schema(name: &str, old: &Schema, new: &Schema, out: &mut Vec<ContractChange>) {
+    for (prop, ty) in &old.properties {
+        let subject = format!("{}.{}", name, prop);
+        match new.properties.get(prop) {
+            None => out.push(ContractChange::new(
+                ChangeKind::Removed,
+                subject,
+                ty.clone(),
+                true,
+            )),
+            Some(n) if n != ty => out.push(ContractChange::new(
+                ChangeKind::Changed,
+                subject,
+                format!("{} → {}", ty, n),
+                true,
+            )),
+            Some(_) => {
+                if new.required.contains(prop) && !old.required.contains(prop) {
+                    out.push(ContractChange::new(
+                        ChangeKind::Changed,
+                        subject,
+                        "now required".into(),
+                        true,
+                    ));
+                }
+            }
+        }
+    }
+    for (prop, ty) in &new.properties {
+        if !old.properties.contains_key(prop) {
+            let required = new.required.contains(prop);
+            let detail = if required {
+                format!("{}, required", ty)
+            } else {
+                ty.clone()
+            };
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("{}.{}", name, prop),
+                detail,
+                required,
+            ));
+        }
+    }
+}
+
+/// The changes from `old` to `new`: endpoints, then schemas.
+pub fn diff(old: &Spec, new: &Spec) -> Vec<ContractChange> {
+    let mut out = Vec::new();
+    for (name, o) in &old.endpoints {
+        match new.endpoints.get(name) {
+            Some(n) => diff_endpoint(name, o, n, &mut out),
+            None => out.push(ContractChange::new(
+                ChangeKind::Removed,
+                name.clone(),
+                String::new(),
+                true,
+            )),
+        }
+    }
+    for name in new.endpoints.keys() {
+        if !old.endpoints.contains_key(name) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                name.clone(),
+                String::new(),
+                false,
+            ));
+        }
+    }
+    for (name, o) in &old.schemas {
+        match new.schemas.get(name) {
+            Some(n) => diff_schema(name, o, n, &mut out),
+            None => out.push(ContractChange::new(
+                ChangeKind::Removed,
+                format!("schema {}", name),
+                String::new(),
+                true,
+            )),
+        }
+    }
+    for (name, n) in &new.schemas {
+        if !old.schemas.contains_key(name) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("schema {}", name),
+                format!("{} propert(ies)", n.properties.len()),
+                false,
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+openapi: 3.0.0
+paths:
+  /users:
+    get:
+      parameters:
+        - { name: page, in: query, schema: { type: integer } }
+      responses:
+        '200': { description: ok }
+    delete:
+      responses:
+        '204': { description: gone }
+  /users/{id}:
+    parameters:
+      - $ref: '#/components/parameters/Id'
+    get:
+      responses:
+        '200': { description: ok }
+        '404': { description: missing }
+components:
+  parameters:
+    Id: { name: id, in: path, required: true, schema: { type: string } }
+  schemas:
+    User:
+      required: [id]
+      properties:
+        id: { type: string }
+        email: { type: string }
+        age: { type: integer }
+"#;
+
+    #[test]
+    fn diff_classifies_breaking_changes() {
+        let new = r#"{
+  "openapi": "3.0.0",
+  "paths": {
+    "/users": {
+      "get": {
+        "parameters": [
+          {"name": "page", "in": "query", "schema": {"type": "string"}},
+          {"name": "org", "in": "query", "required": true, "schema": {"type": "string"}}
+        ],
+        "responses": {"200": {"description": "ok"}, "429": {"description": "slow down"}}
+      },
+      "post": {"requestBody": {"required": true}, "responses": {"201": {}}}
+    },
+    "/users/{id}": {
+      "get": {
+        "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}],
+        "responses": {"404": {"description": "missing"}}
+      }
+    }
+  },
+  "components": {"schemas": {"User": {
+    "required": ["id", "email"],
+    "properties": {"id": {"type": "string"}, "email": {"type": "string"},
+                   "tags": {"type": "array", "items": {"type": "string"}}}
+  }}}
+}"#;
+        let changes = diff(&parse(OLD).unwrap(), &parse(new).unwrap());
+        let summary: Vec<(ChangeKind, &str, bool)> = changes
+            .iter()
+            .map(|c| (c.kind, c.subject.as_str(), c.breaking))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Removed, "DELETE /users", true),
+                (ChangeKind::Changed, "GET /users query param page", true),
+                (ChangeKind::Added, "GET /users query param org", true),
+                (ChangeKind::Added, "GET /users response 429", false),
+                (ChangeKind::Removed, "GET /users/{id} response 200", true),
+                (ChangeKind::Added, "POST /users", false),
+                (ChangeKind::Removed, "User.age", true),
+                (ChangeKind::Changed, "User.email", true),
+                (ChangeKind::Added, "User.tags", false),
+            ]
+        );
+        assert_eq!(changes[1].detail, "integer → string");
+        assert_eq!(changes[8].detail, "array<string>");
+    }
+}
@@ -0,0 +1,729 @@
+//! A reader for `.proto` files (proto2, proto3 and editions) down to what
+//! the contract is made of — messages and their numbered fields, enums,
+//! services and their RPCs — and the diff of two of them.
+//!
+//! Fields and enum values are matched by number, as on the wire: a
+//! renumbered field is a removal plus an addition, a renamed one a change.
+//! Removing a field whose number the new side reserves is not breaking.
+
+use super::{ChangeKind, ContractChange};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoFile {
+    pub package: String,
+    /// Nested ones flattened, named `Outer.Inner`
+    pub messages: Vec<Message>,
+    pub enums: Vec<Enum>,
+    pub services: Vec<Service>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Message {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub reserved: Vec<(u64, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub number: u64,
+    pub name: String,
+    /// `map<string, int32>` for maps
+    pub ty: String,
+    /// `repeated`, `optional`, `required` or empty
+    pub label: String,
+    pub oneof: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Enum {
+    pub name: String,
+    pub values: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Service {
+    pub name: String,
+    pub rpcs: Vec<Rpc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rpc {
+    pub name: String,
+    pub input: String,
+    pub output: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+/// A token and the line it starts on.
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    line: usize,
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line) = (0, 1);
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            let end = i.min(chars.len());
+            tokens.push(Token {
+                text: chars[start..end].iter().collect(),
+                line,
+            });
+        } else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || c == '+' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '-' | '+'))
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                text: chars[start..i].iter().collect(),
+                line,
+            });
+        } else {
+            tokens.push(Token {
+                text: c.to_string(),
+                line,
+            });
+            i += 1;
+        }
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    file: ProtoFile,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.text.as_str())
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| "unexpected end of file".to_string())?;
+        self.pos += 1;
+        Ok(token.text.clone())
+    }
+
+    fn expect(&mut self, want: &str) -> Result<(), String> {
+        let line = self.tokens.get(self.pos).map_or(0, |t| t.line);
+        match self.next()? {
+            got if got == want => Ok(()),
+            got => Err(format!(
+                "expected `{}`, found `{}` on line {}",
+                want, got, line
+            )),
+        }
+    }
+
+    /// Skip to just past the next `;` at this nesting, or past a `{ … }`
+    /// block, whichever ends the statement.
+    fn skip_statement(&mut self) -> Result<(), String> {
+        let mut depth = 0usize;
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "{" | "[" | "(" => depth += 1,
+                "}" | "]" | ")" => {
+                    depth = depth.saturating_sub(1);
+                    // `option (x) = { … };` ends at its `;`, a block at its `}`
+                    if token == "}" && depth == 0 && self.peek() != Some(";") {
+                        return Ok(());
+                    }
+                }
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    fn skip_brackets(&mut self) -> Result<(), String> {
+        if self.peek() != Some("[") {
+            return Ok(());
+        }
+        let mut depth = 0usize;
+        loop {
+            match self.next()?.as_str() {
+                "[" => depth += 1,
+                "]" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn top_level(&mut self) -> Result<(), String> {
+        while let Some(token) = self.peek() {
+            match token {
+                "package" => {
+                    self.next()?;
+                    self.file.package = self.next()?;
+                    self.expect(";")?;
+                }
+                "message" => {
+                    self.next()?;
+                    let name = self.next()?;
+                    self.message(name)?;
+                }
+                "enum" => {
+                    self.next()?;
+                    let name = self.next()?;
+                    self.enumeration(name)?;
+                }
+                "service" => {
+                    self.next()?;
+                    self.service()?;
+                }
+                ";" => {
+                    self.next()?;
+                }
+                // syntax, edition, import, option, extend
+                _ => self.skip_statement()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn message(&mut self, name: String) -> Result<(), String> {
+        self.expect("{")?;
+        let mut message = Message {
+            name: name.clone(),
+            ..Default::default()
+        };
+        let mut oneof: Option<String> = None;
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" if oneof.is_some() => oneof = None,
+                "}" => break,
+                ";" => {}
+                "message" => {
+                    let inner = self.next()?;
+                    self.message(format!("{}.{}", name, inner))?;
+                }
+                "enum" => {
+                    let inner = self.next()?;
+                    self.enumeration(format!("{}.{}", name, inner))?;
+                }
+                "oneof" => {
+                    oneof = Some(self.next()?);
+                    self.expect("{")?;
+                }
+                "reserved" => loop {
+                    let item = self.next()?;
+                    match item.as_str() {
+                        ";" => break,
+                        "," => {}
+                        _ => {
+                            let Ok(from) = item.parse::<u64>() else {
+                                continue; // reserved names
+                            };
+                            let mut to = from;
+                            if self.peek() == Some("to") {
+                                self.next()?;
+                                let end = self.next()?;
+                                to = if end == "max" {
+                                    u64::MAX
+                                } else {
+                                    end.parse().unwrap_or(from)
+                                };
+                            }
+                            message.reserved.push((from, to));
+                        }
+                    }
+                },
+                "option" | "extensions" | "extend" => {
+                    self.pos -= 1;
+                    self.skip_statement()?;
+                }
+                "map" => {
+                    self.expect("<")?;
+                    let key = self.next()?;
+                    self.expect(",")?;
+                    let value = self.next()?;
+                    self.expect(">")?;
+                    let ty = format!("map<{}, {}>", key, value);
+                    message
+                        .fields
+                        .push(self.field(String::new(), ty, oneof.clone())?);
+                }
+                "repeated" | "optional" | "required" => {
+                    let ty = self.next()?;
+                    if ty == "group" {
+                        self.skip_statement()?;
+                        continue;
+                    }
+                    message.fields.push(self.field(token, ty, oneof.clone())?);
+                }
+                "group" => self.skip_statement()?,
+                _ => {
+                    message
+                        .fields
+                        .push(self.field(String::new(), token, oneof.clone())?);
+                }
+            }
+        }
+        self.file.messages.push(message);
+        Ok(())
+    }
+
+    /// `name = N [options];`, the label and type already read.
+    fn field(&mut self, label: String, ty: String, oneof: Option<String>) -> Result<Field, String> {
+        let name = self.next()?;
+        self.expect("=")?;
+        let line = self.tokens.get(self.pos).map_or(0, |t| t.line);
+        let number = self
+            .next()?
+            .parse::<u64>()
+            .map_err(|_| format!("bad field number for `{}` on line {}", name, line))?;
+        self.skip_brackets()?;
+        self.expect(";")?;
+        Ok(Field {
+            number,
+            name,
+            ty,
+            label,
+            oneof,
+        })
+    }
+
+    fn enumeration(&mut self, name: String) -> Result<(), String> {
+        self.expect("{")?;
+        let mut e = Enum {
+            name,
+            values: Vec::new(),
+        };
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => break,
+                ";" => {}
+                "option" | "reserved" => {
+                    self.pos -= 1;
+                    self.skip_statement()?;
+                }
+                _ => {
+                    self.expect("=")?;
+                    let number = self.next()?;
+                    self.skip_brackets()?;
+                    self.expect(";")?;
+                    e.values.push((token, number.parse().unwrap_or_default()));
+                }
+            }
+        }
+        self.file.enums.push(e);
+        Ok(())
+    }
+
+    fn service(&mut self) -> Result<(), String> {
+        let mut service = Service {
+            name: self.next()?,
+            rpcs: Vec::new(),
+        };
+        self.expect("{")?;
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                "}" => break,
+                ";" => {}
+                "rpc" => {
+                    let name = self.next()?;
+                    let (input, client_streaming) = self.rpc_type()?;
+                    self.expect("returns")?;
+                    let (output, server_streaming) = self.rpc_type()?;
+                    match self.peek() {
+                        Some("{") => {
+                            self.skip_statement()?;
+                        }
+                        _ => self.expect(";")?,
+                    }
+                    service.rpcs.push(Rpc {
+                        name,
+                        input,
+                        output,
+                        client_streaming,
+                        server_streaming,
+                    });
+                }
+                _ => {
+                    self.pos -= 1;
+                    self.skip_statement()?;
+                }
+            }
+        }
+        self.file.services.push(service);
+        Ok(())
+    }
+
+    /// `(stream Type)`
+    fn rpc_type(&mut self) -> Result<(String, bool), String> {
+        self.expect("(")?;
+        let mut ty = self.next()?;
+        let stream = ty == "stream" && self.peek() != Some(")");
+        if stream {
+            ty = self.next()?;
+        }
+        self.expect(")")?;
+        Ok((ty, stream))
+    }
+}
+
+/// Parse `source`; an empty one is an empty file.
+pub fn parse(source: &str) -> Result<ProtoFile, String> {
+    let mut parser = Parser {
+        tokens: tokenize(source),
+        pos: 0,
+        file: ProtoFile::default(),
+    };
+    parser.top_level()?;
+    Ok(parser.file)
+}
+
+fn field_label(f: &Field) -> String {
+    let label = if f.label.is_empty() {
+        String::new()
+    } else {
+        format!("{} ", f.label)
+    };
+    match &f.oneof {
+        Some(oneof) => format!("{}{} (oneof {})", label, f.ty, oneof),
+        None => format!("{}{}", label, f.ty),
+    }
+}
+
+fn diff_fields(old: &Message, new: &Message, out: &mut Vec<ContractChange>) {
+    let subject = |f: &Field| format!("{}.{} ({})", new.name, f.name, f.number);
+    for f in &old.fields {
+        match new.fields.iter().find(|n| n.number == f.number) {
+            None => {
+                let reserved = new
+                    .reserved
+                    .iter()
+                    .any(|&(from, to)| (from..=to).contains(&f.number));
+                out.push(ContractChange::new(
+                    ChangeKind::Removed,
+                    format!("{}.{} ({})", old.name, f.name, f.number),
+                    if reserved {
+                        "number reserved".into()
+                    } else {
+                        "number not reserved; it can be reused".into()
+                    },
+                    !reserved,
+                ));
+            }
+            Some(n) => {
+                let mut how = Vec::new();
+                if n.name != f.name {
+                    how.push(format!("renamed from {}", f.name));
+                }
+                if n.ty != f.ty || n.label != f.label || n.oneof != f.oneof {
+                    how.push(format!("{} → {}", field_label(f), field_label(n)));
+                }
+                if !how.is_empty() {
+                    out.push(ContractChange::new(
+                        ChangeKind::Changed,
+                        subject(n),
+                        how.join(", "),
+                        true,
+                    ));
+                }
+            }
+        }
+    }
+    for n in &new.fields {
+        if !old.fields.iter().any(|f| f.number == n.number) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                subject(n),
+                field_label(n),
+                n.label == "required",
+            ));
+        }
+    }
+}
+
+/// What changed from `old` to `new`.
+pub fn diff(old: &ProtoFile, new: &ProtoFile) -> Vec<ContractChange> {
+    let mut out = Vec::new();
+    if old.package != new.package && !old.package.is_empty() {
+        out.push(ContractChange::new(
+            ChangeKind::Changed,
+            "package".into(),
+            format!("{} → {}", old.package, new.package),
+            true,
+        ));
+    }
+    for m in &old.messages {
+        match new.messages.iter().find(|n| n.name == m.name) {
+            Some(n) => diff_fields(m, n, &mut out),
+            None => out.push(ContractChange::new(
+                ChangeKind::Removed,
+                format!("message {}", m.name),
+                String::new(),
+                true,
+            )),
+        }
+    }
+    for n in &new.messages {
+        if !old.messages.iter().any(|m| m.name == n.name) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("message {}", n.name),
+                format!("{} field(s)", n.fields.len()),
+                false,
+            ));
+        }
+    }
+    for e in &old.enums {
+        let Some(n) = new.enums.iter().find(|n| n.name == e.name) else {
+            out.push(ContractChange::new(
+                ChangeKind::Removed,
+                format!("enum {}", e.name),
+                String::new(),
+                true,
+            ));
+            continue;
+        };
+        for (name, number) in &e.values {
+            match n.values.iter().find(|(_, v)| v == number) {
+                None => out.push(ContractChange::new(
+                    ChangeKind::Removed,
+                    format!("{}.{} ({})", e.name, name, number),
+                    String::new(),
+                    true,
+                )),
+                Some((new_name, _)) if new_name != name => out.push(ContractChange::new(
+                    ChangeKind::Changed,
+                    format!("{}.{} ({})", e.name, new_name, number),
+                    format!("renamed from {}", name),
+                    true,
+                )),
+                Some(_) => {}
+            }
+        }
+        for (name, number) in &n.values {
+            if !e.values.iter().any(|(_, v)| v == number) {
+                out.push(ContractChange::new(
+                    ChangeKind::Added,
+                    format!("{}.{} ({})", n.name, name, number),
+                    String::new(),
+                    false,
+                ));
+            }
+        }
+    }
+    for n in &new.enums {
+        if !old.enums.iter().any(|e| e.name == n.name) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("enum {}", n.name),
+                String::new(),
+                false,
+            ));
+        }
+    }
+    let signature = |r: &Rpc| {
+        let stream = |s: bool| if s { "stream " } else { "" };
+        format!(
+            "({}{}) → ({}{})",
+            stream(r.client_streaming),
+            r.input,
+            stream(r.server_streaming),
+            r.output
+        )
+    };
+    for s in &old.services {
+        let Some(n) = new.services.iter().find(|n| n.name == s.name) else {
+            out.push(ContractChange::new(
+                ChangeKind::Removed,
+                format!("service {}", s.name),
+                String::new(),
+                true,
+            ));
+            continue;
+        };
+        for r in &s.rpcs {
+            match n.rpcs.iter().find(|nr| nr.name == r.name) {
+                None => out.push(ContractChange::new(
+                    ChangeKind::Removed,
+                    format!("rpc {}.{}", s.name, r.name),
+                    String::new(),
+                    true,
+                )),
+                Some(nr) if signature(nr) != signature(r) => out.push(ContractChange::new(
+                    ChangeKind::Changed,
+                    format!("rpc {}.{}", s.name, r.name),
+                    format!("was {}, now {}", signature(r), signature(nr)),
+                    true,
+                )),
+                Some(_) => {}
+            }
+        }
+        for nr in &n.rpcs {
+            if !s.rpcs.iter().any(|r| r.name == nr.name) {
+                out.push(ContractChange::new(
+                    ChangeKind::Added,
+                    format!("rpc {}.{}", n.name, nr.name),
+                    signature(nr),
+                    false,
+                ));
+            }
+        }
+    }
+    for n in &new.services {
+        if !old.services.iter().any(|s| s.name == n.name) {
+            out.push(ContractChange::new(
+                ChangeKind::Added,
+                format!("service {}", n.name),
+                format!("{} rpc(s)", n.rpcs.len()),
+                false,
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+syntax = "proto3";
+package shop.v1;
+import "google/protobuf/timestamp.proto";
+option go_package = "x/y";
+
+// A user
+message User {
+  string id = 1;
+  string email = 2 [deprecated = true];
+  int32 age = 3;
+  repeated string tags = 4;
+  message Address { string city = 1; }
+  oneof contact { string phone = 5; string fax = 6; }
+  map<string, int64> counters = 7;
+}
+
+enum Status { STATUS_UNSPECIFIED = 0; ACTIVE = 1; ARCHIVED = 2; }
+
+service Users {
+  rpc GetUser (GetUserRequest) returns (User);
+  rpc Watch (WatchRequest) returns (stream User) { option (google.api.http) = { get: "/v1/watch" }; }
+  rpc Delete (DeleteRequest) returns (Empty);
+}
+"#;
+
+    const NEW: &str = r#"
+syntax = "proto3";
+package shop.v1;
+
+message User {
+  reserved 3;
+  string id = 1;
+  string mail = 2;
+  string tags = 4;
+  message Address { string city = 1; string zip = 2; }
+  oneof contact { string phone = 5; }
+  map<string, int64> counters = 7;
+  string nickname = 8;
+}
+
+enum Status { STATUS_UNSPECIFIED = 0; ACTIVE = 1; }
+
+service Users {
+  rpc GetUser (GetUserRequest) returns (User);
+  rpc Watch (WatchRequest) returns (User);
+  rpc Create (CreateRequest) returns (User);
+}
+"#;
+
+    #[test]
+    fn parses_messages_enums_and_services() {
+        let file = parse(OLD).unwrap();
+        assert_eq!(file.package, "shop.v1");
+        let names: Vec<&str> = file.messages.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["User.Address", "User"]);
+        let user = &file.messages[1];
+        assert_eq!(user.fields.len(), 7);
+        assert_eq!(user.fields[3].label, "repeated");
+        assert_eq!(user.fields[4].oneof.as_deref(), Some("contact"));
+        assert_eq!(user.fields[6].ty, "map<string, int64>");
+        assert_eq!(file.enums[0].values.len(), 3);
+        assert!(file.services[0].rpcs[1].server_streaming);
+        assert_eq!(file.services[0].rpcs.len(), 3);
+        assert!(parse("message X { string a = ; }").is_err());
+    }
+
+    #[test]
+    fn diff_classifies_breaking_changes() {
+        let changes = diff(&parse(OLD).unwrap(), &parse(NEW).unwrap());
+        let summary: Vec<(ChangeKind, &str, bool)> = changes
+            .iter()
+            .map(|c| (c.kind, c.subject.as_str(), c.breaking))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Added, "User.Address.zip (2)", false),
+                (ChangeKind::Changed, "User.mail (2)", true),
+                (ChangeKind::Removed, "User.age (3)", false),
+                (ChangeKind::Changed, "User.tags (4)", true),
+                (ChangeKind::Removed, "User.fax (6)", true),
+                (ChangeKind::Added, "User.nickname (8)", false),
+                (ChangeKind::Removed, "Status.ARCHIVED (2)", true),
+                (ChangeKind::Changed, "rpc Users.Watch", true),
+                (ChangeKind::Removed, "rpc Users.Delete", true),
+                (ChangeKind::Added, "rpc Users.Create", false),
+            ]
+        );
+        assert_eq!(changes[1].detail, "renamed from email");
+        assert_eq!(changes[3].detail, "repeated string → string");
+        assert_eq!(
+            changes[7].detail,
+            "was (WatchRequest) → (stream User), now (WatchRequest) → (User)"
+        );
+    }
+}
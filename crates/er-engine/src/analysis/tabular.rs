@@ -0,0 +1,171 @@
+//! CSV and TSV hunks laid out as a table. A one-field edit in a wide row is
+//! invisible in a line diff; split into columns, a deleted row and the
+//! added row replacing it are paired up and only the differing cells
+//! marked.
+
+use crate::git::{DiffHunk, LineType};
+
+/// Widest a column is laid out, in characters.
+pub const MAX_WIDTH: usize = 40;
+
+/// The field delimiter of `path`, when it's a delimited data file.
+pub fn delimiter(path: &str) -> Option<char> {
+    let lower = path.to_ascii_lowercase();
+    if lower.ends_with(".csv") {
+        Some(',')
+    } else if lower.ends_with(".tsv") || lower.ends_with(".tab") {
+        Some('\t')
+    } else {
+        None
+    }
+}
+
+/// The fields of `line`, quotes respected and removed.
+pub fn split_row(line: &str, delim: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted || field.is_empty() => quoted = !quoted,
+            c if c == delim && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// One diff line, split into cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRow {
+    pub line_type: LineType,
+    pub old_num: Option<usize>,
+    pub new_num: Option<usize>,
+    pub cells: Vec<String>,
+    /// Per cell, whether it differs from the row it replaces; all false
+    /// for context rows and rows with nothing to pair with
+    pub changed: Vec<bool>,
+}
+
+/// The hunks of a delimited file as rows.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    /// The file's first line, when given
+    pub header: Option<Vec<String>>,
+    pub rows: Vec<TableRow>,
+    /// Per column, capped at [`MAX_WIDTH`]
+    pub widths: Vec<usize>,
+    /// The row each hunk starts at
+    pub hunk_starts: Vec<usize>,
+}
+
+/// Mark the cells of each delete row against the add row at the same
+/// position in the add run right after it.
+fn pair(rows: &mut [TableRow]) {
+    let mut i = 0;
+    while i < rows.len() {
+        let deletes = rows[i..]
+            .iter()
+            .take_while(|r| r.line_type == LineType::Delete)
+            .count();
+        let adds = rows[i + deletes..]
+            .iter()
+            .take_while(|r| r.line_type == LineType::Add)
+            .count();
+        for k in 0..deletes.min(adds) {
+            let (old, new) = (&rows[i + k].cells, &rows[i + deletes + k].cells);
+            let changed: Vec<bool> = (0..old.len().max(new.len()))
+                .map(|c| old.get(c) != new.get(c))
+                .collect();
+            let (old_len, new_len) = (old.len(), new.len());
+            rows[i + k].changed = changed[..old_len].to_vec();
+            rows[i + deletes + k].changed = changed[..new_len].to_vec();
+        }
+        i += (deletes + adds).max(1);
+    }
+}
+
+/// Lay `hunks` out as a table. `header` is the file's first line; a
+/// context copy of it in the hunks is left out.
+pub fn table(hunks: &[DiffHunk], header: Option<&str>, delim: char) -> Table {
+    let mut out = Table {
+        header: header.map(|h| split_row(h, delim)),
+        ..Table::default()
+    };
+    for hunk in hunks {
+        let start = out.rows.len();
+        out.hunk_starts.push(start);
+        for line in &hunk.lines {
+            let is_header =
+                header.is_some() && line.line_type == LineType::Context && line.new_num == Some(1);
+            if matches!(line.line_type, LineType::Fold(_)) || is_header {
+                continue;
+            }
+            let cells = split_row(&line.content, delim);
+            out.rows.push(TableRow {
+                line_type: line.line_type,
+                old_num: line.old_num,
+                new_num: line.new_num,
+                changed: vec![false; cells.len()],
+                cells,
+            });
+        }
+        pair(&mut out.rows[start..]);
+    }
+    let rows = out.header.iter().chain(out.rows.iter().map(|r| &r.cells));
+    for cells in rows {
+        if out.widths.len() < cells.len() {
+            out.widths.resize(cells.len(), 0);
+        }
+        for (width, cell) in out.widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count().min(MAX_WIDTH));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn replaced_rows_mark_only_the_cells_that_differ() {
+        assert_eq!(
+            split_row("1,\"Smith, J\",\"say \"\"hi\"\"\"", ','),
+            vec!["1", "Smith, J", "say \"hi\""]
+        );
+        assert_eq!(delimiter("data/Users.TSV"), Some('\t'));
+        assert_eq!(delimiter("data/users.json"), None);
+
+        let diff = "diff --git a/users.csv b/users.csv\n--- a/users.csv\n+++ b/users.csv\n\
+                    @@ -1,4 +1,4 @@\n id,name,city\n 1,Ann,Oslo\n-2,Bob,Bergen\n-3,Cy,Tromsø\n\
+                    +2,Bob,Trondheim\n+3,Cy,Tromsø,extra\n";
+        let files = parse_diff(diff);
+        let table = table(&files[0].hunks, Some("id,name,city"), ',');
+        assert_eq!(table.header.as_deref().unwrap(), ["id", "name", "city"]);
+        let rows: Vec<(LineType, Vec<bool>)> = table
+            .rows
+            .iter()
+            .map(|r| (r.line_type, r.changed.clone()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (LineType::Context, vec![false; 3]),
+                (LineType::Delete, vec![false, false, true]),
+                (LineType::Delete, vec![false, false, false]),
+                (LineType::Add, vec![false, false, true]),
+                (LineType::Add, vec![false, false, false, true]),
+            ]
+        );
+        assert_eq!(table.widths, vec![2, 4, 9, 5]);
+        assert_eq!(table.hunk_starts, vec![0]);
+    }
+}
@@ -0,0 +1,441 @@
+//! The subset of YAML API specs are written in, read into a
+//! [`serde_json::Value`]: block mappings and sequences by indentation,
+//! plain and quoted scalars, flow `[…]` / `{…}` collections, `|` / `>`
+//! block scalars and comments. Anchors and tags are dropped and aliases
+//! read as null — specs rarely lean on them, and a contract diff doesn't
+//! need them resolved.
+
+use serde_json::{Map, Value};
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+/// `text` up to a ` #` comment that isn't inside quotes.
+fn strip_comment(text: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev.is_whitespace() => return &text[..i],
+            None => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+fn lines(source: &str) -> Vec<Line> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, raw)| {
+            let text = strip_comment(raw).trim_end();
+            let trimmed = text.trim_start();
+            if trimmed.is_empty()
+                || trimmed == "---"
+                || trimmed == "..."
+                || trimmed.starts_with('%')
+            {
+                return None;
+            }
+            Some(Line {
+                number: i + 1,
+                indent: text.len() - trimmed.len(),
+                text: trimmed.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Split `key: value` at the first `:` followed by a space or the end,
+/// outside quotes and brackets.
+fn split_key(text: &str) -> Option<(String, &str)> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0i32;
+    let bytes = text.as_bytes();
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' if i == 0 => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                ':' if depth == 0 && (i + 1 == bytes.len() || bytes[i + 1] == b' ') => {
+                    let key = scalar(text[..i].trim());
+                    let key = match key {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    return Some((key, text[i + 1..].trim()));
+                }
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Anchors (`&a`) and tags (`!!str`) before a value.
+fn strip_properties(mut text: &str) -> &str {
+    while text.starts_with('&') || text.starts_with('!') {
+        text = text
+            .split_once(' ')
+            .map_or("", |(_, rest)| rest.trim_start());
+    }
+    text
+}
+
+/// A plain or quoted scalar.
+fn scalar(text: &str) -> Value {
+    let text = strip_properties(text);
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        let inner = &text[1..text.len() - 1];
+        return serde_json::from_str(text)
+            .unwrap_or_else(|_| Value::String(inner.replace("\\\"", "\"")));
+    }
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        return Value::String(text[1..text.len() - 1].replace("''", "'"));
+    }
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ if text.starts_with('*') => Value::Null,
+        _ => {
+            if let Ok(n) = text.parse::<i64>() {
+                Value::from(n)
+            } else if let Some(n) = text
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .and_then(serde_json::Number::from_f64)
+            {
+                Value::Number(n)
+            } else {
+                Value::String(text.to_string())
+            }
+        }
+    }
+}
+
+/// A flow collection or a scalar, from `chars[*pos..]`.
+fn flow(chars: &[char], pos: &mut usize) -> Value {
+    let skip_ws = |pos: &mut usize| {
+        while *pos < chars.len() && chars[*pos].is_whitespace() {
+            *pos += 1;
+        }
+    };
+    skip_ws(pos);
+    match chars.get(*pos) {
+        Some('[') => {
+            *pos += 1;
+            let mut items = Vec::new();
+            loop {
+                skip_ws(pos);
+                match chars.get(*pos) {
+                    None => break,
+                    Some(']') => {
+                        *pos += 1;
+                        break;
+                    }
+                    Some(',') => *pos += 1,
+                    _ => items.push(flow(chars, pos)),
+                }
+            }
+            Value::Array(items)
+        }
+        Some('{') => {
+            *pos += 1;
+            let mut map = Map::new();
+            loop {
+                skip_ws(pos);
+                match chars.get(*pos) {
+                    None => break,
+                    Some('}') => {
+                        *pos += 1;
+                        break;
+                    }
+                    Some(',') => *pos += 1,
+                    _ => {
+                        let key = match flow_scalar(chars, pos, true) {
+                            Value::String(s) => s,
+                            other => other.to_string(),
+                        };
+                        skip_ws(pos);
+                        let value = if chars.get(*pos) == Some(&':') {
+                            *pos += 1;
+                            flow(chars, pos)
+                        } else {
+                            Value::Null
+                        };
+                        map.insert(key, value);
+                    }
+                }
+            }
+            Value::Object(map)
+        }
+        _ => flow_scalar(chars, pos, false),
+    }
+}
+
+/// A scalar inside a flow collection: up to `,`, `]`, `}` (or `:` for a
+/// key).
+fn flow_scalar(chars: &[char], pos: &mut usize, key: bool) -> Value {
+    let start = *pos;
+    if let Some(&q) = chars.get(*pos).filter(|c| **c == '"' || **c == '\'') {
+        *pos += 1;
+        while *pos < chars.len() && chars[*pos] != q {
+            if chars[*pos] == '\\' && q == '"' {
+                *pos += 1;
+            }
+            *pos += 1;
+        }
+        *pos = (*pos + 1).min(chars.len());
+    } else {
+        while *pos < chars.len() {
+            let c = chars[*pos];
+            let ends_key = key
+                && c == ':'
+                && chars
+                    .get(*pos + 1)
+                    .is_none_or(|n| n.is_whitespace() || matches!(n, ',' | '}'));
+            if matches!(c, ',' | ']' | '}') || ends_key {
+                break;
+            }
+            *pos += 1;
+        }
+    }
+    let text: String = chars[start..*pos].iter().collect();
+    scalar(text.trim())
+}
+
+fn inline(text: &str) -> Value {
+    if text.starts_with('[') || text.starts_with('{') {
+        let chars: Vec<char> = text.chars().collect();
+        flow(&chars, &mut 0)
+    } else {
+        scalar(text)
+    }
+}
+
+struct Parser {
+    lines: Vec<Line>,
+    pos: usize,
+}
+
+impl Parser {
+    /// The node whose lines start at `self.pos`, all indented `indent`.
+    fn block(&mut self, indent: usize) -> Result<Value, String> {
+        let Some(line) = self.lines.get(self.pos) else {
+            return Ok(Value::Null);
+        };
+        if is_item(&line.text) {
+            self.sequence(indent)
+        } else if split_key(&line.text).is_some() {
+            self.mapping(indent)
+        } else {
+            // A multi-line plain scalar
+            let mut parts = Vec::new();
+            while let Some(line) = self.lines.get(self.pos).filter(|l| l.indent >= indent) {
+                parts.push(line.text.clone());
+                self.pos += 1;
+            }
+            Ok(inline(&parts.join(" ")))
+        }
+    }
+
+    /// The value after `key:` on the line before `self.pos`.
+    fn value(&mut self, text: &str, indent: usize) -> Result<Value, String> {
+        let text = strip_properties(text);
+        if text.starts_with('|') || text.starts_with('>') {
+            let mut parts = Vec::new();
+            while let Some(line) = self.lines.get(self.pos).filter(|l| l.indent > indent) {
+                parts.push(line.text.clone());
+                self.pos += 1;
+            }
+            let sep = if text.starts_with('|') { "\n" } else { " " };
+            return Ok(Value::String(parts.join(sep)));
+        }
+        if !text.is_empty() {
+            // A flow collection may go on over the next lines
+            let mut joined = text.to_string();
+            let open = |s: &str| {
+                s.matches(['[', '{']).count() as i64 - s.matches([']', '}']).count() as i64
+            };
+            while open(&joined) > 0 {
+                let Some(line) = self.lines.get(self.pos) else {
+                    break;
+                };
+                joined.push(' ');
+                joined.push_str(&line.text);
+                self.pos += 1;
+            }
+            return Ok(inline(&joined));
+        }
+        match self.lines.get(self.pos) {
+            Some(next) if next.indent > indent => {
+                let child = next.indent;
+                self.block(child)
+            }
+            // `key:` then `- item` at the same indent
+            Some(next) if next.indent == indent && is_item(&next.text) => self.sequence(indent),
+            _ => Ok(Value::Null),
+        }
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value, String> {
+        let mut map = Map::new();
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent < indent || (line.indent == indent && is_item(&line.text)) {
+                break;
+            }
+            if line.indent > indent {
+                return Err(format!("line {}: unexpected indentation", line.number));
+            }
+            let number = line.number;
+            let text = line.text.clone();
+            let Some((key, rest)) = split_key(&text) else {
+                return Err(format!("line {}: expected `key: value`", number));
+            };
+            self.pos += 1;
+            let value = self.value(rest, indent)?;
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value, String> {
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent != indent || !is_item(&line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                self.pos += 1;
+                let item = match self.lines.get(self.pos) {
+                    Some(next) if next.indent > indent => {
+                        let child = next.indent;
+                        self.block(child)?
+                    }
+                    _ => Value::Null,
+                };
+                items.push(item);
+                continue;
+            }
+            // `- key: value` opens a mapping indented past the dash
+            let offset = line.text.len() - rest.len();
+            if is_item(&rest) || (split_key(&rest).is_some() && !rest.starts_with(['[', '{'])) {
+                let line = &mut self.lines[self.pos];
+                line.indent += offset;
+                line.text = rest;
+                let child = line.indent;
+                items.push(self.block(child)?);
+            } else {
+                self.pos += 1;
+                items.push(self.value(&rest, indent)?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+}
+
+/// Parse `source` as a single document (`---` lines are skipped; see
+/// [`parse_documents`] for a stream of them).
+pub fn parse(source: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        lines: lines(source),
+        pos: 0,
+    };
+    let indent = parser.lines.first().map_or(0, |l| l.indent);
+    let value = parser.block(indent)?;
+    match parser.lines.get(parser.pos) {
+        Some(line) => Err(format!("line {}: unexpected indentation", line.number)),
+        None => Ok(value),
+    }
+}
+
+/// Parse every `---`-separated document of `source`, skipping empty ones.
+pub fn parse_documents(source: &str) -> Result<Vec<Value>, String> {
+    let mut documents = Vec::new();
+    let mut chunk = String::new();
+    for line in source.lines() {
+        if line.trim_end() == "---" || line.starts_with("--- ") {
+            documents.push(parse(&chunk)?);
+            // Blank lines keep the line numbers of errors right
+            chunk = "\n".repeat(chunk.lines().count() + 1);
+            continue;
+        }
+        chunk.push_str(line);
+        chunk.push('\n');
+    }
+    documents.push(parse(&chunk)?);
+    documents.retain(|d| !d.is_null());
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_the_yaml_specs_are_written_in() {
+        let source = r#"
+openapi: 3.0.3   # version
+info:
+  title: "Shop: API"
+  description: |
+    Multi-line
+    text
+paths:
+  /users/{id}:
+    get:
+      tags: [users, "admin"]
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema: { type: string, format: uuid }
+        - $ref: '#/components/parameters/Page'
+      responses:
+        '200':
+          description: ok
+servers:
+- url: https://x
+"#;
+        let value = parse(source).unwrap();
+        assert_eq!(value["openapi"], json!("3.0.3"));
+        assert_eq!(value["info"]["title"], json!("Shop: API"));
+        assert_eq!(value["info"]["description"], json!("Multi-line\ntext"));
+        let get = &value["paths"]["/users/{id}"]["get"];
+        assert_eq!(get["tags"], json!(["users", "admin"]));
+        assert_eq!(
+            get["parameters"][0],
+            json!({"name": "id", "in": "path", "required": true,
+                   "schema": {"type": "string", "format": "uuid"}})
+        );
+        assert_eq!(
+            get["parameters"][1]["$ref"],
+            json!("#/components/parameters/Page")
+        );
+        assert_eq!(get["responses"]["200"]["description"], json!("ok"));
+        assert_eq!(value["servers"][0]["url"], json!("https://x"));
+
+        assert!(parse("a: 1\n    b: 2\n").is_err());
+
+        let documents = parse_documents("# head\n---\nkind: A\n---\n---\nkind: B\n").unwrap();
+        assert_eq!(documents, vec![json!({"kind": "A"}), json!({"kind": "B"})]);
+    }
+}
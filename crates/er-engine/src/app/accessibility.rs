@@ -0,0 +1,258 @@
+//! Screen-reader output.
+//!
+//! With `[accessibility] screen_reader` set, every change of focus (file,
+//! hunk, line, focused comment or finding, open menu, input prompt) and
+//! every new notification is written as one plain-text line to stderr or a
+//! file / named pipe, so a screen reader or `tail -f` can follow the TUI
+//! without scraping the screen.
+
+use super::{App, InputMode, OverlayData};
+use crate::git::LineType;
+use anyhow::{Context, Result};
+use std::io::Write;
+
+/// One-line description of what has focus right now.
+pub fn focus_description(app: &App) -> String {
+    if let Some(ref overlay) = app.overlay {
+        return overlay_description(overlay);
+    }
+    match app.input_mode {
+        InputMode::Search => return "search: type a query, Enter to apply".into(),
+        InputMode::Find => return "find in file: type a query, Enter to keep it".into(),
+        InputMode::Comment => return "comment: type the comment, Enter to submit".into(),
+        InputMode::Filter => return "filter: type a filter, Enter to apply".into(),
+        InputMode::Commit => return "commit message: type it, Enter to commit".into(),
+        InputMode::RemoteUrl => return "open: type a GitHub PR URL or a patch file path".into(),
+        InputMode::SkipReason => return "skip file: type why it needs no review".into(),
+        InputMode::QuickDiffRef => return "quick diff: type a tag, branch or commit".into(),
+        InputMode::Assignee => return "assign files: type the reviewer's name".into(),
+        InputMode::Confirm(_) => return "confirm: y to proceed, n to cancel".into(),
+        InputMode::Normal => {}
+    }
+
+    let tab = app.tab();
+    let files = tab.active_diff_files();
+    let Some(file) = files.get(tab.active_selected_file_index()) else {
+        return format!("{} mode: no files", tab.mode.git_mode());
+    };
+    let mut out = format!(
+        "{} mode, file {} of {}: {} {} +{} -{}",
+        tab.mode.git_mode(),
+        tab.active_selected_file_index() + 1,
+        files.len(),
+        file.status.symbol(),
+        file.path,
+        file.adds,
+        file.dels
+    );
+    if tab.reviewed.contains_key(&file.path) {
+        out.push_str(", reviewed");
+    }
+
+    if let Some(comment) = tab
+        .focused_comment_id
+        .as_deref()
+        .and_then(|id| tab.ai.find_comment(id))
+    {
+        out.push_str(&format!(
+            "; comment by {}: {}",
+            comment.author(),
+            first_line(comment.text())
+        ));
+        return out;
+    }
+    if let Some(finding) = tab.focused_finding_id.as_deref().and_then(|id| {
+        tab.ai
+            .file_review(&file.path)
+            .and_then(|fr| fr.findings.iter().find(|f| f.id == id))
+    }) {
+        out.push_str(&format!(
+            "; finding {:?}: {}",
+            finding.severity, finding.title
+        ));
+        return out;
+    }
+
+    let hunk_idx = tab.active_current_hunk();
+    let Some(hunk) = file.hunks.get(hunk_idx) else {
+        return out;
+    };
+    out.push_str(&format!(
+        "; hunk {} of {} at line {}",
+        hunk_idx + 1,
+        file.hunks.len(),
+        hunk.new_start
+    ));
+    if let Some(line) = tab.active_current_line().and_then(|i| hunk.lines.get(i)) {
+        let (kind, num) = match line.line_type {
+            LineType::Add => ("added", line.new_num),
+            LineType::Delete => ("removed", line.old_num),
+            LineType::Context => ("unchanged", line.new_num),
+            LineType::Fold(n) => {
+                out.push_str(&format!("; {} folded lines", n));
+                return out;
+            }
+        };
+        out.push_str(&format!(
+            "; line {} {}: {}",
+            num.unwrap_or(0),
+            kind,
+            line.content.trim_end()
+        ));
+    }
+    out
+}
+
+fn overlay_description(overlay: &OverlayData) -> String {
+    match overlay {
+        OverlayData::ModalHub {
+            kind,
+            title,
+            items,
+            selected,
+        } => {
+            let name = title.as_deref().unwrap_or(kind.title());
+            match items.get(*selected) {
+                Some(item) if item.description.is_empty() => {
+                    format!("{} menu: {}", name, item.label)
+                }
+                Some(item) => format!("{} menu: {}, {}", name, item.label, item.description),
+                None => format!("{} menu", name),
+            }
+        }
+        OverlayData::NotificationLog { entries, selected } => match entries.get(*selected) {
+            Some(e) => format!("notifications: {} {}", e.level.label(), e.message),
+            None => "notifications: empty".into(),
+        },
+        OverlayData::CommandError { title, failure, .. } => {
+            format!("error: {}: {}", title, failure)
+        }
+        OverlayData::Onboarding { .. } => "getting started guide: Esc to close".into(),
+        _ => "dialog open: Esc to close".into(),
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
+}
+
+/// Writes focus changes and new notifications, one line each.
+pub struct Announcer<W: Write = Box<dyn Write + Send>> {
+    out: W,
+    last_focus: String,
+    last_notice: Option<(String, u16)>,
+}
+
+impl Announcer {
+    /// Open the configured target: `stderr`/`-`, or a file / named pipe
+    /// (appended to). `None` when no target is configured.
+    pub fn open(target: &str) -> Result<Option<Self>> {
+        let target = target.trim();
+        if target.is_empty() {
+            return Ok(None);
+        }
+        let out: Box<dyn Write + Send> = if target == "stderr" || target == "-" {
+            Box::new(std::io::stderr())
+        } else {
+            Box::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(target)
+                    .with_context(|| format!("Failed to open screen reader output {}", target))?,
+            )
+        };
+        Ok(Some(Announcer::new(out)))
+    }
+}
+
+impl<W: Write> Announcer<W> {
+    pub fn new(out: W) -> Self {
+        Announcer {
+            out,
+            last_focus: String::new(),
+            last_notice: None,
+        }
+    }
+
+    /// Emit whatever changed since the last call. Write errors are ignored;
+    /// a closed pipe must never take the TUI down.
+    pub fn observe(&mut self, app: &App) {
+        let notice = app
+            .watch_message
+            .as_ref()
+            .map(|m| (m.clone(), app.watch_message_ticks));
+        if let Some((ref msg, ticks)) = notice {
+            let fresh = match self.last_notice {
+                Some((ref prev, prev_ticks)) => prev != msg || ticks < prev_ticks,
+                None => true,
+            };
+            if fresh {
+                let _ = writeln!(self.out, "{}: {}", app.watch_message_level.label(), msg);
+            }
+        }
+        self.last_notice = notice;
+
+        let focus = focus_description(app);
+        if focus != self.last_focus {
+            let _ = writeln!(self.out, "{}", focus);
+            self.last_focus = focus;
+        }
+        let _ = self.out.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffFile, DiffHunk, DiffLine, FileStatus};
+
+    fn app() -> App {
+        let file = DiffFile {
+            path: "src/lib.rs".into(),
+            status: FileStatus::Modified,
+            hunks: vec![DiffHunk {
+                header: "@@ -1,1 +1,2 @@".into(),
+                old_start: 1,
+                old_count: 1,
+                new_start: 1,
+                new_count: 2,
+                lines: vec![DiffLine {
+                    line_type: LineType::Add,
+                    content: "fn added() {}".into(),
+                    old_num: None,
+                    new_num: Some(2),
+                }],
+            }],
+            adds: 1,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        };
+        App::new_for_test(vec![file])
+    }
+
+    #[test]
+    fn describes_file_hunk_and_line() {
+        let mut app = app();
+        let desc = focus_description(&app);
+        assert!(desc.contains("file 1 of 1: ~ src/lib.rs +1 -0"), "{desc}");
+        assert!(desc.contains("hunk 1 of 1"), "{desc}");
+        app.tab_mut().current_line = Some(0);
+        assert!(focus_description(&app).ends_with("line 2 added: fn added() {}"));
+    }
+
+    #[test]
+    fn announces_only_changes() {
+        let mut app = app();
+        let mut announcer = Announcer::new(Vec::new());
+        announcer.observe(&app);
+        announcer.observe(&app);
+        app.notify("✓ Copied");
+        announcer.observe(&app);
+        let text = String::from_utf8(announcer.out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2, "{text}");
+        assert_eq!(lines[1], "ok: ✓ Copied");
+    }
+}
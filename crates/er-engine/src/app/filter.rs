@@ -1,6 +1,10 @@
 use crate::ai::{ErReview, RiskLevel};
-use crate::git::{DiffFile, FileStatus};
+use crate::git::{DiffFile, FileStatus, Package, TestPairing};
 use glob::{MatchOptions, Pattern};
+use std::collections::{HashMap, HashSet};
+
+/// Lowercased authors of each file's added lines (see `author_scope`).
+pub type FileAuthors = HashMap<String, HashSet<String>>;
 
 // ── Types ──
 
@@ -45,6 +49,37 @@ pub enum FilterRule {
         include: bool,
         levels: Vec<RiskLevel>,
     },
+    /// `untested:true` — a source changed without any of its tests
+    /// (see [`crate::git::pair_tests`]); `untested:false` is the opposite.
+    Untested {
+        include: bool,
+        untested: bool,
+    },
+    /// `author:alice` — files (and, in the Branch diff, hunks) whose added
+    /// lines `git blame` attributes to an author whose name contains `alice`.
+    Author {
+        include: bool,
+        /// Stored lowercased
+        needle: String,
+    },
+    /// `package:api-server` — files owned by that monorepo package (see
+    /// [`crate::git::detect_packages`]), matched by name or root directory.
+    /// Matches nothing until [`resolve_package_rules`] finds the package.
+    Package {
+        include: bool,
+        /// Stored lowercased
+        name: String,
+        /// Root of the package, once resolved
+        root: Option<String>,
+        /// Roots of packages nested inside it, whose files it doesn't own
+        nested: Vec<String>,
+    },
+    /// `script:name` — files a Lua filter keeps (see [`crate::scripting`]).
+    /// Matches nothing when the filter isn't loaded.
+    Script {
+        include: bool,
+        name: String,
+    },
 }
 
 pub struct FilterPreset {
@@ -75,6 +110,14 @@ pub const FILTER_PRESETS: &[FilterPreset] = &[
     },
 ];
 
+/// The expression of the preset called `name`, for `er --filter review`.
+pub fn preset_expr(name: &str) -> Option<&'static str> {
+    FILTER_PRESETS
+        .iter()
+        .find(|p| p.name == name)
+        .map(|p| p.expr)
+}
+
 impl FilterRule {
     fn is_include(&self) -> bool {
         match self {
@@ -83,10 +126,66 @@ impl FilterRule {
             FilterRule::Status { include, .. } => *include,
             FilterRule::Size { include, .. } => *include,
             FilterRule::Risk { include, .. } => *include,
+            FilterRule::Untested { include, .. } => *include,
+            FilterRule::Author { include, .. } => *include,
+            FilterRule::Package { include, .. } => *include,
+            FilterRule::Script { include, .. } => *include,
         }
     }
 }
 
+/// Needles of the include `author:` rules.
+pub fn included_authors(rules: &[FilterRule]) -> Vec<&str> {
+    rules
+        .iter()
+        .filter_map(|r| match r {
+            FilterRule::Author {
+                include: true,
+                needle,
+            } => Some(needle.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether any `author:` rule is present.
+pub fn has_author_rules(rules: &[FilterRule]) -> bool {
+    rules.iter().any(|r| matches!(r, FilterRule::Author { .. }))
+}
+
+/// Point the `package:` rules at `packages`; a name that isn't one of
+/// them leaves its rule unresolved.
+pub fn resolve_package_rules(rules: &mut [FilterRule], packages: &[Package]) {
+    for rule in rules {
+        let FilterRule::Package {
+            name, root, nested, ..
+        } = rule
+        else {
+            continue;
+        };
+        let found = packages
+            .iter()
+            .find(|p| p.name.to_lowercase() == *name || p.root.to_lowercase() == *name);
+        *root = found.map(|p| p.root.clone());
+        *nested = found
+            .map(|pkg| {
+                packages
+                    .iter()
+                    .filter(|p| p.root != pkg.root && pkg.contains(&p.root))
+                    .map(|p| p.root.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+}
+
+/// Whether one of `authors` (lowercased) matches one of `needles`.
+pub fn authors_match(authors: &HashSet<String>, needles: &[&str]) -> bool {
+    authors
+        .iter()
+        .any(|author| needles.iter().any(|needle| author.contains(needle)))
+}
+
 // ── Parser ──
 
 /// Parse a comma-separated filter expression into a list of rules.
@@ -124,6 +223,47 @@ pub fn parse_filter_expr(expr: &str) -> Vec<FilterRule> {
             continue;
         }
 
+        // Try untested:true / untested:false
+        if let Some(rule) = try_parse_untested(include, body) {
+            rules.push(rule);
+            continue;
+        }
+
+        // Try author:name
+        if let Some(needle) = body.strip_prefix("author:").map(str::trim) {
+            if !needle.is_empty() {
+                rules.push(FilterRule::Author {
+                    include,
+                    needle: needle.to_lowercase(),
+                });
+            }
+            continue;
+        }
+
+        // Try package:name
+        if let Some(name) = body.strip_prefix("package:").map(str::trim) {
+            if !name.is_empty() {
+                rules.push(FilterRule::Package {
+                    include,
+                    name: name.trim_end_matches('/').to_lowercase(),
+                    root: None,
+                    nested: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        // Try script:name
+        if let Some(name) = body.strip_prefix("script:").map(str::trim) {
+            if !name.is_empty() {
+                rules.push(FilterRule::Script {
+                    include,
+                    name: name.to_string(),
+                });
+            }
+            continue;
+        }
+
         // Try status keywords
         if let Some(rule) = try_parse_status(include, body) {
             rules.push(rule);
@@ -168,6 +308,15 @@ fn try_parse_risk(include: bool, body: &str) -> Option<FilterRule> {
     Some(FilterRule::Risk { include, levels })
 }
 
+fn try_parse_untested(include: bool, body: &str) -> Option<FilterRule> {
+    let untested = match body.strip_prefix("untested:")?.trim() {
+        "true" | "yes" => true,
+        "false" | "no" => false,
+        _ => return None,
+    };
+    Some(FilterRule::Untested { include, untested })
+}
+
 fn try_parse_size(include: bool, body: &str) -> Option<FilterRule> {
     if let Some(num_str) = body.strip_prefix('>') {
         if let Ok(n) = num_str.trim().parse::<usize>() {
@@ -242,11 +391,15 @@ pub fn apply_filter(rules: &[FilterRule], file: &DiffFile) -> bool {
     !excluded
 }
 
-/// Apply filter rules to a file with optional review data for risk filtering.
+/// Apply filter rules to a file with optional review data for risk filtering,
+/// the diff's [`TestPairing`] for `untested:` rules and [`FileAuthors`] for
+/// `author:` rules.
 pub fn apply_filter_with_review(
     rules: &[FilterRule],
     file: &DiffFile,
     review: Option<&ErReview>,
+    pairing: Option<&TestPairing>,
+    authors: Option<&FileAuthors>,
 ) -> bool {
     if rules.is_empty() {
         return true;
@@ -257,7 +410,7 @@ pub fn apply_filter_with_review(
     let included = if has_includes {
         rules
             .iter()
-            .any(|r| r.is_include() && matches_rule_with_review(r, file, review))
+            .any(|r| r.is_include() && matches_rule_with_review(r, file, review, pairing, authors))
     } else {
         true
     };
@@ -268,13 +421,30 @@ pub fn apply_filter_with_review(
 
     let excluded = rules
         .iter()
-        .any(|r| !r.is_include() && matches_rule_with_review(r, file, review));
+        .any(|r| !r.is_include() && matches_rule_with_review(r, file, review, pairing, authors));
 
     !excluded
 }
 
-fn matches_rule_with_review(rule: &FilterRule, file: &DiffFile, review: Option<&ErReview>) -> bool {
+fn matches_rule_with_review(
+    rule: &FilterRule,
+    file: &DiffFile,
+    review: Option<&ErReview>,
+    pairing: Option<&TestPairing>,
+    authors: Option<&FileAuthors>,
+) -> bool {
     match rule {
+        FilterRule::Untested { untested, .. } => match pairing {
+            Some(pairing) => pairing.tests_changed(&file.path) == Some(!*untested),
+            None => true,
+        },
+        FilterRule::Author { include, needle } => match authors {
+            Some(authors) => authors
+                .get(&file.path)
+                .is_some_and(|a| authors_match(a, &[needle.as_str()])),
+            // No blame outside the Branch diff: the rule is a no-op there
+            None => *include,
+        },
         FilterRule::Risk { levels, .. } => {
             if let Some(review) = review {
                 if let Some(fr) = review.files.get(&file.path) {
@@ -306,6 +476,25 @@ fn matches_rule(rule: &FilterRule, file: &DiffFile) -> bool {
             let _ = levels;
             true
         }
+        // Likewise without a test pairing or blame
+        FilterRule::Untested { .. } | FilterRule::Author { .. } => true,
+        FilterRule::Package { root, nested, .. } => {
+            let under = |dir: &str| {
+                dir.is_empty()
+                    || file
+                        .path
+                        .strip_prefix(dir)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            };
+            root.as_deref().is_some_and(under) && !nested.iter().any(|n| under(n))
+        }
+        #[cfg(feature = "scripting")]
+        FilterRule::Script { name, .. } => crate::scripting::filter_matches(
+            name,
+            &crate::scripting::ScriptFile::from_diff(file, false),
+        ),
+        #[cfg(not(feature = "scripting"))]
+        FilterRule::Script { .. } => false,
     }
 }
 
@@ -700,6 +889,105 @@ mod tests {
         assert!(matches!(&rules[0], FilterRule::Risk { include: false, .. }));
     }
 
+    #[test]
+    fn test_untested_filter_keeps_sources_without_test_changes() {
+        let files = [
+            make_file("src/api.ts", FileStatus::Modified, 3, 1),
+            make_file("src/api.test.ts", FileStatus::Modified, 5, 0),
+            make_file("src/db.ts", FileStatus::Modified, 2, 2),
+            make_file("README.md", FileStatus::Modified, 1, 0),
+        ];
+        let patterns: Vec<String> = crate::git::DEFAULT_TEST_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let pairing = crate::git::pair_tests(files.iter().map(|f| f.path.as_str()), &patterns);
+        let visible = |expr: &str| -> Vec<&str> {
+            let rules = parse_filter_expr(expr);
+            files
+                .iter()
+                .filter(|f| apply_filter_with_review(&rules, f, None, Some(&pairing), None))
+                .map(|f| f.path.as_str())
+                .collect()
+        };
+        assert_eq!(visible("untested:true"), ["src/db.ts"]);
+        assert_eq!(visible("untested:false"), ["src/api.ts"]);
+        assert_eq!(
+            visible("-untested:true"),
+            ["src/api.ts", "src/api.test.ts", "README.md"]
+        );
+    }
+
+    #[test]
+    fn author_rules_match_files_blamed_to_the_author() {
+        let files = [
+            make_file("src/a.rs", FileStatus::Modified, 3, 1),
+            make_file("src/b.rs", FileStatus::Modified, 2, 0),
+        ];
+        let authors: FileAuthors = [
+            (
+                "src/a.rs".to_string(),
+                HashSet::from(["alice smith".to_string()]),
+            ),
+            ("src/b.rs".to_string(), HashSet::from(["bob".to_string()])),
+        ]
+        .into();
+        let visible = |expr: &str| -> Vec<&str> {
+            let rules = parse_filter_expr(expr);
+            files
+                .iter()
+                .filter(|f| apply_filter_with_review(&rules, f, None, None, Some(&authors)))
+                .map(|f| f.path.as_str())
+                .collect()
+        };
+        assert_eq!(visible("author:Alice"), ["src/a.rs"]);
+        assert_eq!(visible("-author:alice"), ["src/b.rs"]);
+        assert_eq!(
+            included_authors(&parse_filter_expr("author:Bob, -author:x, *.rs")),
+            ["bob"]
+        );
+    }
+
+    #[test]
+    fn package_rules_match_files_owned_by_the_package() {
+        use crate::git::PackageKind;
+        let files = [
+            make_file("services/api/src/main.rs", FileStatus::Modified, 3, 1),
+            make_file(
+                "services/api/plugins/auth/lib.rs",
+                FileStatus::Modified,
+                1,
+                0,
+            ),
+            make_file("web/app.ts", FileStatus::Modified, 2, 0),
+        ];
+        let package = |name: &str, root: &str| Package {
+            name: name.into(),
+            root: root.into(),
+            kind: PackageKind::Cargo,
+        };
+        let packages = [
+            package("api-server", "services/api"),
+            package("auth", "services/api/plugins/auth"),
+        ];
+        let visible = |expr: &str| -> Vec<&str> {
+            let mut rules = parse_filter_expr(expr);
+            resolve_package_rules(&mut rules, &packages);
+            files
+                .iter()
+                .filter(|f| apply_filter_with_review(&rules, f, None, None, None))
+                .map(|f| f.path.as_str())
+                .collect()
+        };
+        assert_eq!(visible("package:API-Server"), ["services/api/src/main.rs"]);
+        assert_eq!(
+            visible("package:services/api/plugins/auth/"),
+            ["services/api/plugins/auth/lib.rs"]
+        );
+        assert_eq!(visible("-package:api-server").len(), 2);
+        assert!(visible("package:nope").is_empty());
+    }
+
     #[test]
     fn test_parse_risk_med_alias() {
         // "med" should be an alias for Medium
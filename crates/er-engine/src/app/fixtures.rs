@@ -0,0 +1,204 @@
+//! Fixture app states for tests outside the engine: the TUI's snapshot tests,
+//! or a downstream front end checking its rendering. Nothing here runs git
+//! or touches the disk; every app starts from [`App::new_for_test`].
+//!
+//! ```ignore
+//! let mut app = fixtures::app(fixtures::big_diff(40, 30));
+//! fixtures::open_panel(&mut app, PanelContent::FileDetail);
+//! ```
+
+use crate::ai::PanelContent;
+use crate::git::{CommitInfo, DiffFile, DiffHunk, DiffLine, FileStatus, LineType};
+
+use super::state::{DiffCache, HistoryState};
+use super::{App, DiffMode, HubAction, HubItem, HubKind, OverlayData};
+
+/// A modified file with one hunk of `changes` changed lines: each one a
+/// deletion followed by its replacement, between two context lines.
+pub fn modified_file(path: &str, changes: usize) -> DiffFile {
+    let mut lines = vec![context(1, "fn fixture() {")];
+    for i in 0..changes {
+        lines.push(DiffLine {
+            line_type: LineType::Delete,
+            content: format!("    let value_{} = {};", i, i),
+            old_num: Some(i + 2),
+            new_num: None,
+        });
+        lines.push(DiffLine {
+            line_type: LineType::Add,
+            content: format!("    let value_{} = {} * 2;", i, i),
+            old_num: None,
+            new_num: Some(i + 2),
+        });
+    }
+    lines.push(context(changes + 2, "}"));
+    file(path, FileStatus::Modified, lines)
+}
+
+/// A new file of `len` lines.
+pub fn added_file(path: &str, len: usize) -> DiffFile {
+    let lines = (1..=len)
+        .map(|n| DiffLine {
+            line_type: LineType::Add,
+            content: format!("// line {}", n),
+            old_num: None,
+            new_num: Some(n),
+        })
+        .collect();
+    file(path, FileStatus::Added, lines)
+}
+
+/// A three-file diff: a modified source file, a new test and a new doc.
+pub fn small_diff() -> Vec<DiffFile> {
+    vec![
+        modified_file("src/lib.rs", 3),
+        added_file("tests/lib.rs", 4),
+        added_file("docs/notes.md", 2),
+    ]
+}
+
+/// `files` modified files of `changes` changed lines each, spread over a
+/// few directories.
+pub fn big_diff(files: usize, changes: usize) -> Vec<DiffFile> {
+    const DIRS: [&str; 4] = ["src/app", "src/git", "src/ui", "tests"];
+    (0..files)
+        .map(|i| {
+            modified_file(
+                &format!("{}/file_{:02}.rs", DIRS[i % DIRS.len()], i),
+                changes,
+            )
+        })
+        .collect()
+}
+
+/// An app with one tab showing `files` as a branch diff.
+pub fn app(files: Vec<DiffFile>) -> App {
+    App::new_for_test(files)
+}
+
+/// A commit for [`history`], numbered `n`.
+pub fn commit(n: usize, subject: &str) -> CommitInfo {
+    CommitInfo {
+        hash: format!("{:040x}", n + 1),
+        short_hash: format!("{:07x}", n + 1),
+        subject: subject.to_string(),
+        author: "Fixture Author".to_string(),
+        date: "2024-01-01".to_string(),
+        relative_date: format!("{} hours ago", n + 2),
+        file_count: 1,
+        adds: 1,
+        dels: 1,
+        is_merge: false,
+    }
+}
+
+/// Switch the active tab to History mode over `commits`, with the first one
+/// selected and showing `files`.
+pub fn history(app: &mut App, commits: Vec<CommitInfo>, files: Vec<DiffFile>) {
+    let tab = app.tab_mut();
+    tab.mode = DiffMode::History;
+    tab.history = Some(HistoryState {
+        commits,
+        selected_commit: 0,
+        commit_files: files,
+        selected_file: 0,
+        current_hunk: 0,
+        current_line: None,
+        diff_scroll: 0,
+        h_scroll: 0,
+        all_loaded: true,
+        diff_cache: DiffCache::new(5),
+        messages: Default::default(),
+        net_zero: Default::default(),
+        fold_net_zero: false,
+        search: Default::default(),
+        prs: Default::default(),
+    });
+}
+
+/// Open the side panel on `content`.
+pub fn open_panel(app: &mut App, content: PanelContent) {
+    app.tab_mut().panel = Some(content);
+}
+
+/// Open a hub of `kind` listing `labels`, the first one selected.
+pub fn open_hub(app: &mut App, kind: HubKind, labels: &[&str]) {
+    let items = labels
+        .iter()
+        .map(|label| HubItem {
+            label: label.to_string(),
+            hint: "".into(),
+            description: format!("Fixture item {}", label),
+            action: HubAction::Noop,
+            is_header: false,
+            enabled: true,
+        })
+        .collect();
+    app.overlay = Some(OverlayData::ModalHub {
+        kind,
+        title: None,
+        items,
+        selected: 0,
+    });
+}
+
+fn context(n: usize, content: &str) -> DiffLine {
+    DiffLine {
+        line_type: LineType::Context,
+        content: content.to_string(),
+        old_num: Some(n),
+        new_num: Some(n),
+    }
+}
+
+fn file(path: &str, status: FileStatus, lines: Vec<DiffLine>) -> DiffFile {
+    let adds = lines
+        .iter()
+        .filter(|l| l.line_type == LineType::Add)
+        .count();
+    let dels = lines
+        .iter()
+        .filter(|l| l.line_type == LineType::Delete)
+        .count();
+    let old_count = lines.len() - adds;
+    let new_count = lines.len() - dels;
+    DiffFile {
+        path: path.to_string(),
+        status,
+        hunks: vec![DiffHunk {
+            header: format!(
+                "@@ -{},{} +1,{} @@",
+                usize::from(old_count > 0),
+                old_count,
+                new_count
+            ),
+            old_start: usize::from(old_count > 0),
+            old_count,
+            new_start: 1,
+            new_count,
+            lines,
+        }],
+        adds,
+        dels,
+        compacted: false,
+        raw_hunk_count: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_diffs_count_their_lines() {
+        let f = modified_file("a.rs", 3);
+        assert_eq!((f.adds, f.dels), (3, 3));
+        assert_eq!(f.hunks[0].header, "@@ -1,5 +1,5 @@");
+        assert_eq!(added_file("b.rs", 4).hunks[0].header, "@@ -0,0 +1,4 @@");
+
+        let mut app = app(big_diff(9, 2));
+        assert_eq!(app.tab().files.len(), 9);
+        history(&mut app, vec![commit(0, "Initial")], small_diff());
+        assert_eq!(app.tab().mode, DiffMode::History);
+    }
+}
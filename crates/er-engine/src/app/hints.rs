@@ -0,0 +1,258 @@
+//! Contextual key hints for the bottom bar.
+//!
+//! Every context-dependent action is declared once in [`ACTION_REGISTRY`]
+//! with the state it applies to and a weight. The bar asks for the few
+//! highest-weighted actions whose predicate matches, so the hints follow the
+//! cursor (a focused comment surfaces reply/edit/delete, Staged mode surfaces
+//! commit/unstage) instead of every mode hand-building its own list.
+
+use super::{App, DiffMode};
+use crate::config::HintConfig;
+
+/// Which `[hints]` toggle gates an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintGroup {
+    Navigation,
+    Comments,
+    Staging,
+    /// Always eligible (review flow, drafts, view toggles)
+    Core,
+}
+
+impl HintGroup {
+    fn enabled(self, config: &HintConfig) -> bool {
+        match self {
+            HintGroup::Navigation => config.navigation,
+            HintGroup::Comments => config.comments,
+            HintGroup::Staging => config.staging,
+            HintGroup::Core => true,
+        }
+    }
+}
+
+pub struct ActionHint {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub group: HintGroup,
+    /// Higher wins when more actions apply than fit.
+    pub weight: u8,
+    pub applies: fn(&App) -> bool,
+}
+
+/// How many contextual hints the bar shows by default.
+pub const CONTEXT_HINT_LIMIT: usize = 5;
+
+fn focused_comment(app: &App) -> Option<crate::ai::CommentRef<'_>> {
+    let tab = app.tab();
+    tab.focused_comment_id
+        .as_deref()
+        .and_then(|id| tab.ai.find_comment(id))
+}
+
+fn current_file_reviewed(app: &App) -> Option<bool> {
+    let tab = app.tab();
+    tab.files
+        .get(tab.selected_file)
+        .map(|f| tab.reviewed.contains_key(&f.path))
+}
+
+fn can_comment(app: &App) -> bool {
+    !matches!(app.tab().mode, DiffMode::Staged | DiffMode::Conflicts) && !app.tab().files.is_empty()
+}
+
+pub const ACTION_REGISTRY: &[ActionHint] = &[
+    ActionHint {
+        key: "Tab",
+        label: "resume draft",
+        group: HintGroup::Core,
+        weight: 100,
+        applies: |app| app.has_comment_draft(),
+    },
+    ActionHint {
+        key: "r",
+        label: "reply",
+        group: HintGroup::Comments,
+        weight: 95,
+        applies: |app| {
+            focused_comment(app).is_some_and(|c| c.can_reply())
+                || (app.tab().focused_comment_id.is_none()
+                    && app.tab().focused_finding_id.is_some())
+        },
+    },
+    ActionHint {
+        key: "e",
+        label: "edit",
+        group: HintGroup::Comments,
+        weight: 94,
+        applies: |app| {
+            focused_comment(app).is_some_and(|c| c.author() == "You" && c.in_reply_to().is_none())
+        },
+    },
+    ActionHint {
+        key: "x",
+        label: "delete",
+        group: HintGroup::Comments,
+        weight: 93,
+        applies: |app| focused_comment(app).is_some_and(|c| c.can_delete()),
+    },
+    ActionHint {
+        key: "c",
+        label: "commit",
+        group: HintGroup::Staging,
+        weight: 90,
+        applies: |app| app.tab().mode == DiffMode::Staged,
+    },
+    ActionHint {
+        key: "s",
+        label: "unstage",
+        group: HintGroup::Staging,
+        weight: 89,
+        applies: |app| app.tab().mode == DiffMode::Staged,
+    },
+    ActionHint {
+        key: "s",
+        label: "mark resolved",
+        group: HintGroup::Staging,
+        weight: 89,
+        applies: |app| app.tab().mode == DiffMode::Conflicts,
+    },
+    ActionHint {
+        key: "I",
+        label: "full diff",
+        group: HintGroup::Core,
+        weight: 85,
+        applies: |app| app.tab().in_checkpoint_view() && !app.tab().in_quick_diff(),
+    },
+    ActionHint {
+        key: "Esc",
+        label: "full diff",
+        group: HintGroup::Core,
+        weight: 85,
+        applies: |app| app.tab().in_quick_diff(),
+    },
+    ActionHint {
+        key: "^p",
+        label: "push",
+        group: HintGroup::Core,
+        weight: 80,
+        applies: |app| app.tab().has_unpushed_pr_commits(),
+    },
+    ActionHint {
+        key: "␣",
+        label: "review",
+        group: HintGroup::Core,
+        weight: 70,
+        applies: |app| current_file_reviewed(app) == Some(false),
+    },
+    ActionHint {
+        key: "U",
+        label: "next unreviewed",
+        group: HintGroup::Navigation,
+        weight: 65,
+        applies: |app| current_file_reviewed(app) == Some(true),
+    },
+    ActionHint {
+        key: "s",
+        label: "stage",
+        group: HintGroup::Staging,
+        weight: 60,
+        applies: |app| app.tab().mode == DiffMode::Unstaged,
+    },
+    ActionHint {
+        key: "J/K",
+        label: "comments",
+        group: HintGroup::Navigation,
+        weight: 50,
+        applies: |app| {
+            let tab = app.tab();
+            tab.files.get(tab.selected_file).is_some_and(|f| {
+                tab.ai.file_question_count(&f.path) > 0
+                    || tab.ai.file_note_count(&f.path) > 0
+                    || tab.ai.file_github_comment_count(&f.path) > 0
+            })
+        },
+    },
+    ActionHint {
+        key: "^j/^k",
+        label: "findings",
+        group: HintGroup::Navigation,
+        weight: 45,
+        applies: |app| app.tab().ai.has_data(),
+    },
+    ActionHint {
+        key: "q",
+        label: "question",
+        group: HintGroup::Comments,
+        weight: 30,
+        applies: can_comment,
+    },
+    ActionHint {
+        key: "c",
+        label: "comment",
+        group: HintGroup::Comments,
+        weight: 29,
+        applies: can_comment,
+    },
+];
+
+impl App {
+    /// Up to `limit` registry actions that apply right now, most relevant
+    /// first. A key appears at most once.
+    pub fn contextual_hints(&self, limit: usize) -> Vec<&'static ActionHint> {
+        let mut matching: Vec<&'static ActionHint> = ACTION_REGISTRY
+            .iter()
+            .filter(|a| a.group.enabled(&self.config.hints) && (a.applies)(self))
+            .collect();
+        matching.sort_by_key(|a| std::cmp::Reverse(a.weight));
+        let mut out: Vec<&'static ActionHint> = Vec::new();
+        for action in matching {
+            if out.len() >= limit {
+                break;
+            }
+            if !out.iter().any(|a| a.key == action.key) {
+                out.push(action);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    fn app_in(mode: DiffMode) -> App {
+        let mut app = App::new_for_test(vec![modified_file("a.rs", 1)]);
+        app.tab_mut().mode = mode;
+        app
+    }
+
+    fn keys(app: &App) -> Vec<(&'static str, &'static str)> {
+        app.contextual_hints(CONTEXT_HINT_LIMIT)
+            .iter()
+            .map(|a| (a.key, a.label))
+            .collect()
+    }
+
+    #[test]
+    fn staged_mode_leads_with_commit_and_unstage() {
+        let app = app_in(DiffMode::Staged);
+        let hints = keys(&app);
+        assert_eq!(hints[0], ("c", "commit"));
+        assert_eq!(hints[1], ("s", "unstage"));
+        assert!(!hints.iter().any(|h| h.1 == "question"));
+    }
+
+    #[test]
+    fn disabled_groups_are_skipped_and_keys_unique() {
+        let mut app = app_in(DiffMode::Unstaged);
+        assert!(keys(&app).contains(&("s", "stage")));
+        app.config.hints.staging = false;
+        let hints = keys(&app);
+        assert!(!hints.iter().any(|h| h.0 == "s"));
+        let mut seen: Vec<_> = hints.iter().map(|h| h.0).collect();
+        seen.dedup();
+        assert_eq!(seen.len(), hints.len());
+    }
+}
@@ -0,0 +1,254 @@
+//! Width breakpoints for the main area.
+//!
+//! Below `[layout] narrow_below` the diff gets the whole width: the file tree
+//! becomes a drawer drawn over it and the side panel stacks underneath. At
+//! `wide_above` and up an always-on third column can be configured. In
+//! between the classic tree + diff (+ panel) columns are kept, with the tree
+//! scaled to the terminal inside `tree_min..=tree_max`, and panels docked
+//! right or under the diff per `panel_dock`/`panel_docks` — up to two of
+//! them, with one pinned. Zoom (`M`) gives the focused pane the whole area
+//! regardless of width.
+
+use super::{App, TabState};
+use crate::ai::PanelContent;
+use crate::config::LayoutConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    Narrow,
+    Normal,
+    Wide,
+}
+
+pub fn layout_mode(width: u16, config: &LayoutConfig) -> LayoutMode {
+    if width < config.narrow_below {
+        LayoutMode::Narrow
+    } else if width >= config.wide_above {
+        LayoutMode::Wide
+    } else {
+        LayoutMode::Normal
+    }
+}
+
+/// File tree width for a `width`-column area: the `<`/`>` override when set,
+/// otherwise `tree_percent` of the width within the configured clamps.
+pub fn tree_width(tab: &TabState, width: u16, config: &LayoutConfig) -> u16 {
+    if let Some(w) = tab.file_tree_width {
+        return w;
+    }
+    let min = config.tree_min.min(config.tree_max);
+    let scaled = u32::from(width) * u32::from(config.tree_percent) / 100;
+    (scaled.min(u32::from(u16::MAX)) as u16).clamp(min, config.tree_max)
+}
+
+/// The panel a config name stands for: `comments`/`file`, `summary`/`ai`,
+/// `pr`, `refs`/`symbols`, `ops`, `assets`, `related` or `log`.
+pub fn panel_named(name: &str) -> Option<PanelContent> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "comments" | "file" => Some(PanelContent::FileDetail),
+        "summary" | "ai" => Some(PanelContent::AiSummary),
+        "pr" => Some(PanelContent::PrOverview),
+        "refs" | "symbols" => Some(PanelContent::SymbolRefs),
+        "ops" | "operational" => Some(PanelContent::Operational),
+        "assets" | "images" => Some(PanelContent::Assets),
+        "related" | "history" => Some(PanelContent::RelatedChanges),
+        "log" => Some(PanelContent::AgentLog),
+        _ => None,
+    }
+}
+
+/// The config name of a panel, the first of [`panel_named`]'s.
+pub fn panel_name(content: PanelContent) -> &'static str {
+    match content {
+        PanelContent::FileDetail => "comments",
+        PanelContent::AiSummary => "summary",
+        PanelContent::PrOverview => "pr",
+        PanelContent::SymbolRefs => "refs",
+        PanelContent::Operational => "ops",
+        PanelContent::Assets => "assets",
+        PanelContent::RelatedChanges => "related",
+        PanelContent::AgentLog => "log",
+    }
+}
+
+/// Panel shown as the always-on third column, from `wide_column`.
+pub fn wide_column_content(config: &LayoutConfig) -> Option<PanelContent> {
+    panel_named(&config.wide_column)
+}
+
+/// Where a panel is drawn on normal and wide terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dock {
+    /// A column right of the diff
+    Right,
+    /// Under the diff, in its column
+    Bottom,
+}
+
+/// The dock for `content`: its `panel_docks` entry, else `panel_dock`.
+pub fn panel_dock(config: &LayoutConfig, content: PanelContent) -> Dock {
+    let dock = config
+        .panel_docks
+        .iter()
+        .find(|(name, _)| panel_named(name) == Some(content))
+        .map_or(config.panel_dock.as_str(), |(_, dock)| dock.as_str());
+    if dock.trim().eq_ignore_ascii_case("bottom") {
+        Dock::Bottom
+    } else {
+        Dock::Right
+    }
+}
+
+/// How the main area is split for the current width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MainLayout {
+    pub mode: LayoutMode,
+    /// File tree width, 0 when hidden. On narrow layouts this is the drawer,
+    /// drawn over the diff.
+    pub tree: u16,
+    /// Panels in the right column, top to bottom.
+    pub right: Vec<PanelContent>,
+    /// Width of the right column.
+    pub panel_width: u16,
+    /// Panels under the diff, left to right. Narrow layouts stack every
+    /// panel here.
+    pub bottom: Vec<PanelContent>,
+    /// False when the panel is zoomed and covers the diff.
+    pub diff: bool,
+}
+
+pub fn main_layout(app: &App, width: u16) -> MainLayout {
+    let tab = app.tab();
+    let config = &app.config.layout;
+    let mode = layout_mode(width, config);
+    let tree = tree_width(tab, width, config);
+    let mut layout = MainLayout {
+        mode,
+        tree,
+        right: Vec::new(),
+        panel_width: tab.panel_width,
+        bottom: Vec::new(),
+        diff: true,
+    };
+
+    if app.zoomed {
+        layout.tree = 0;
+        if let Some(content) = tab.panel.filter(|_| tab.panel_focus) {
+            layout.right.push(content);
+            layout.panel_width = width;
+            layout.diff = false;
+        }
+        return layout;
+    }
+
+    if mode == LayoutMode::Narrow {
+        layout.tree = if app.file_tree_toggled {
+            tree.max(width * 3 / 5).min(width)
+        } else {
+            0
+        };
+        layout.bottom = tab.open_panels();
+        return layout;
+    }
+
+    layout.tree = if app.file_tree_toggled { 0 } else { tree };
+    let mut open = tab.open_panels();
+    if open.is_empty() && mode == LayoutMode::Wide {
+        open.extend(wide_column_content(config));
+    }
+    let (right, bottom) = open
+        .into_iter()
+        .partition(|&p| panel_dock(config, p) == Dock::Right);
+    layout.bottom = bottom;
+    if width >= layout.tree + tab.panel_width + 20 {
+        layout.right = right;
+    }
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakpoints_pick_mode() {
+        let config = LayoutConfig::default();
+        assert_eq!(layout_mode(79, &config), LayoutMode::Narrow);
+        assert_eq!(layout_mode(80, &config), LayoutMode::Normal);
+        assert_eq!(layout_mode(200, &config), LayoutMode::Wide);
+    }
+
+    #[test]
+    fn tree_scales_within_clamps_until_resized() {
+        let config = LayoutConfig::default();
+        let mut tab = TabState::new_for_test(vec![]);
+        assert_eq!(tree_width(&tab, 160, &config), 32);
+        assert_eq!(tree_width(&tab, 60, &config), config.tree_min);
+        assert_eq!(tree_width(&tab, 400, &config), config.tree_max);
+        tab.file_tree_width = Some(30);
+        assert_eq!(tree_width(&tab, 400, &config), 30);
+    }
+
+    #[test]
+    fn narrow_uses_drawer_and_wide_adds_column() {
+        let mut app = App::new_for_test(vec![]);
+        let narrow = main_layout(&app, 70);
+        assert_eq!(narrow.tree, 0);
+        assert!(narrow.right.is_empty() && narrow.bottom.is_empty());
+        app.file_tree_toggled = true;
+        assert_eq!(main_layout(&app, 70).tree, 42);
+        assert_eq!(main_layout(&app, 120).tree, 0);
+
+        app.file_tree_toggled = false;
+        app.config.layout.wide_column = "comments".into();
+        assert!(main_layout(&app, 160).right.is_empty());
+        let wide = main_layout(&app, 220);
+        assert_eq!(
+            (wide.right, wide.panel_width),
+            (vec![PanelContent::FileDetail], 40)
+        );
+    }
+
+    #[test]
+    fn zoom_gives_focused_pane_the_whole_area() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().panel = Some(PanelContent::AiSummary);
+        app.zoomed = true;
+        let diff = main_layout(&app, 120);
+        assert_eq!((diff.tree, diff.diff), (0, true));
+        assert!(diff.right.is_empty());
+        app.tab_mut().panel_focus = true;
+        let panel = main_layout(&app, 120);
+        assert_eq!(
+            (panel.right, panel.panel_width),
+            (vec![PanelContent::AiSummary], 120)
+        );
+        assert!(!panel.diff);
+    }
+
+    #[test]
+    fn panels_go_to_their_configured_dock() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().panel = Some(PanelContent::AgentLog);
+        app.tab_mut().second_panel = Some(PanelContent::FileDetail);
+        let both = main_layout(&app, 160);
+        assert_eq!(
+            both.right,
+            vec![PanelContent::FileDetail, PanelContent::AgentLog]
+        );
+        assert!(both.bottom.is_empty());
+
+        app.config
+            .layout
+            .panel_docks
+            .insert("log".into(), "bottom".into());
+        let split = main_layout(&app, 160);
+        assert_eq!(split.right, vec![PanelContent::FileDetail]);
+        assert_eq!(split.bottom, vec![PanelContent::AgentLog]);
+
+        // Everything stacks under the diff on narrow terminals
+        assert_eq!(main_layout(&app, 70).bottom.len(), 2);
+        app.config.layout.panel_dock = "bottom".into();
+        assert!(main_layout(&app, 160).right.is_empty());
+    }
+}
@@ -1,18 +1,40 @@
+pub mod accessibility;
 pub mod card_ai_context;
 pub mod card_ai_spawn;
 pub mod filter;
+pub mod fixtures;
+pub mod hints;
+pub mod layout;
 mod state;
 
 pub use card_ai_context::{build_card_ai_system_context, CardAiContextParams};
 pub use card_ai_spawn::{plan_card_ai_invocation, run_card_ai_subprocess, CardAiInvocation};
 
 pub use crate::git::Worktree;
+pub use state::assets::{AssetChange, AssetSide};
 pub use state::background::{
     debug_bg_enabled, BackgroundTask, BackgroundTaskSnapshot, BackgroundTaskTarget,
 };
+pub use state::checklist::ChecklistEdit;
 pub use state::chrono_now;
+pub use state::code_age::CodeAge;
+pub use state::commit_groups::{author_initials, commit_age, date_group, DateGroup};
+pub use state::commit_search::CommitField;
+pub use state::compaction_policy::{CompactionPolicy, CompactionReason};
+pub use state::confirm::ConfirmPrompt;
+pub use state::contracts::ContractDiff;
+pub use state::data_views::DataView;
+pub use state::file_sort::FileSort;
 pub use state::github_sync::{fetch_comment_sync_data, CommentSyncContext, CommentSyncResult};
+pub use state::infrastructure::InfraDiff;
+pub use state::marks::{is_mark_name, Mark};
+pub use state::motions::Motion;
+pub use state::no_repo::{is_patch_file, NoRepo};
+pub use state::notifications::{NotificationEntry, NotificationLevel, NotificationLog};
+pub use state::onboarding::ONBOARDING_SECTIONS;
+pub use state::related_changes::RelatedChanges;
 pub use state::remote_diff_sync::{fetch_remote_diff_data, RemoteDiffContext, RemoteDiffResult};
+pub use state::size_guard::enable as enable_size_guard;
 pub use state::{
     cleanup_question_answers, cleanup_questions_and_notes, cleanup_review_artifacts,
     cleanup_reviews, cleanup_triage, AgentLogEntry, AgentLogSource, AiActionKind, App,
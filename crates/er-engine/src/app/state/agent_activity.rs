@@ -0,0 +1,230 @@
+//! Attribute working-tree changes to open findings and comments.
+//!
+//! When the file watcher fires (typically an agent applying fixes), the added
+//! lines of the diff before and after the refresh are compared. Open findings
+//! and comments whose anchor overlaps a changed line are marked "addressed —
+//! needs re-check" so the reviewer can revisit exactly what was touched. The
+//! marks are per-tab and cleared when the file is marked reviewed again.
+
+use super::TabState;
+use crate::ai::CommentRef;
+use crate::git::{DiffFile, LineType};
+use std::collections::{HashMap, HashSet};
+
+/// Lines around an anchor that still count as "touching" it.
+const ANCHOR_SLACK: usize = 2;
+
+/// Added lines per file as `(new_num, content)`, captured before a refresh.
+pub type AddedLines = HashMap<String, Vec<(usize, String)>>;
+
+fn added_lines(file: &DiffFile) -> Vec<(usize, String)> {
+    file.hunks
+        .iter()
+        .flat_map(|h| h.lines.iter())
+        .filter(|l| l.line_type == LineType::Add)
+        .filter_map(|l| Some((l.new_num?, l.content.clone())))
+        .collect()
+}
+
+/// New-side line numbers that changed between `before` and `after`: added
+/// lines with new content, plus the old positions of added lines that went
+/// away (an approximation — later lines may have shifted).
+fn touched_lines(before: Option<&[(usize, String)]>, after: &DiffFile) -> Vec<usize> {
+    let now = added_lines(after);
+    let Some(before) = before else {
+        return now.into_iter().map(|(n, _)| n).collect();
+    };
+    let before_content: HashSet<&str> = before.iter().map(|(_, c)| c.as_str()).collect();
+    let now_content: HashSet<&str> = now.iter().map(|(_, c)| c.as_str()).collect();
+    let mut touched: Vec<usize> = now
+        .iter()
+        .filter(|(_, c)| !before_content.contains(c.as_str()))
+        .map(|(n, _)| *n)
+        .collect();
+    touched.extend(
+        before
+            .iter()
+            .filter(|(_, c)| !now_content.contains(c.as_str()))
+            .map(|(n, _)| *n),
+    );
+    touched.sort_unstable();
+    touched.dedup();
+    touched
+}
+
+/// Whether any touched line falls within the anchor. Hunk-only anchors use the
+/// hunk's new-side range; file-level anchors match any change in the file.
+fn anchor_touched(
+    file: &DiffFile,
+    touched: &[usize],
+    hunk_index: Option<usize>,
+    line_start: Option<usize>,
+    line_end: Option<usize>,
+) -> bool {
+    let range = match (line_start, hunk_index) {
+        (Some(s), _) => Some((s, line_end.unwrap_or(s).max(s))),
+        (None, Some(h)) => file
+            .hunks
+            .get(h)
+            .map(|h| (h.new_start, h.new_start + h.new_count.saturating_sub(1))),
+        (None, None) => None,
+    };
+    match range {
+        Some((s, e)) => {
+            let lo = s.saturating_sub(ANCHOR_SLACK);
+            let hi = e + ANCHOR_SLACK;
+            touched.iter().any(|&n| n >= lo && n <= hi)
+        }
+        None => !touched.is_empty(),
+    }
+}
+
+impl TabState {
+    /// Snapshot taken before a watch-triggered refresh.
+    pub fn added_lines_snapshot(&self) -> AddedLines {
+        self.files
+            .iter()
+            .map(|f| (f.path.clone(), added_lines(f)))
+            .collect()
+    }
+
+    /// Mark open findings/comments touched since `before`. Returns how many
+    /// were newly marked.
+    pub fn mark_addressed_since(&mut self, before: &AddedLines) -> usize {
+        let mut marked = Vec::new();
+        for file in &self.files {
+            if file.hunks.is_empty() {
+                continue;
+            }
+            let touched = touched_lines(before.get(&file.path).map(Vec::as_slice), file);
+            if touched.is_empty() {
+                continue;
+            }
+
+            if let Some(fr) = self
+                .ai
+                .review
+                .as_ref()
+                .and_then(|r| r.files.get(&file.path))
+            {
+                for finding in fr.findings.iter().filter(|f| f.is_active()) {
+                    if anchor_touched(
+                        file,
+                        &touched,
+                        finding.hunk_index,
+                        finding.line_start,
+                        finding.line_end,
+                    ) {
+                        marked.push((finding.id.clone(), file.path.clone()));
+                    }
+                }
+            }
+
+            for comment in self.open_comments_for(&file.path) {
+                if anchor_touched(
+                    file,
+                    &touched,
+                    comment.hunk_index(),
+                    comment.line_start(),
+                    comment.line_end(),
+                ) {
+                    marked.push((comment.id().to_string(), file.path.clone()));
+                }
+            }
+        }
+
+        let mut count = 0;
+        for (id, path) in marked {
+            if self.addressed.insert(id, path).is_none() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Unresolved top-level questions, notes and GitHub comments on `path`.
+    fn open_comments_for(&self, path: &str) -> Vec<CommentRef<'_>> {
+        let mut out = Vec::new();
+        if let Some(qs) = &self.ai.questions {
+            out.extend(qs.questions.iter().map(CommentRef::Question));
+        }
+        if let Some(ns) = &self.ai.notes {
+            out.extend(ns.notes.iter().map(CommentRef::Note));
+        }
+        if let Some(gc) = &self.ai.github_comments {
+            out.extend(gc.comments.iter().map(CommentRef::GitHubComment));
+        }
+        out.retain(|c| c.file() == path && c.in_reply_to().is_none() && !c.is_resolved());
+        out
+    }
+
+    /// Finding or comment was touched by a working-tree change since the
+    /// reviewer last signed off on its file.
+    pub fn is_addressed(&self, id: &str) -> bool {
+        self.addressed.contains_key(id)
+    }
+
+    pub fn addressed_count_for_file(&self, path: &str) -> usize {
+        self.addressed
+            .values()
+            .filter(|p| p.as_str() == path)
+            .count()
+    }
+
+    /// Drop re-check marks for a file (the reviewer has looked again).
+    pub fn clear_addressed_for_file(&mut self, path: &str) {
+        self.addressed.retain(|_, p| p != path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffHunk, DiffLine, FileStatus};
+
+    fn file(adds: &[(usize, &str)]) -> DiffFile {
+        DiffFile {
+            path: "src/a.rs".into(),
+            status: FileStatus::Modified,
+            hunks: vec![DiffHunk {
+                header: "@@ -1,1 +1,10 @@".into(),
+                old_start: 1,
+                old_count: 1,
+                new_start: 1,
+                new_count: 10,
+                lines: adds
+                    .iter()
+                    .map(|(n, c)| DiffLine {
+                        line_type: LineType::Add,
+                        content: c.to_string(),
+                        old_num: None,
+                        new_num: Some(*n),
+                    })
+                    .collect(),
+            }],
+            adds: adds.len(),
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        }
+    }
+
+    #[test]
+    fn touched_lines_reports_new_and_removed_content() {
+        let before = vec![(2, "a".to_string()), (5, "old".to_string())];
+        let after = file(&[(2, "a"), (8, "new")]);
+        assert_eq!(touched_lines(Some(&before), &after), vec![5, 8]);
+        assert_eq!(touched_lines(None, &after), vec![2, 8]);
+    }
+
+    #[test]
+    fn anchor_touched_uses_line_range_with_slack() {
+        let f = file(&[]);
+        assert!(anchor_touched(&f, &[7], None, Some(4), Some(5)));
+        assert!(!anchor_touched(&f, &[9], None, Some(4), Some(5)));
+        // Hunk-level anchor covers the hunk's new range (1..=10)
+        assert!(anchor_touched(&f, &[9], Some(0), None, None));
+        // File-level anchor matches any change
+        assert!(anchor_touched(&f, &[40], None, None, None));
+    }
+}
@@ -0,0 +1,163 @@
+//! Porting a hunk to another worktree (Git hub → Apply hunk to worktree):
+//! the worktree picker lists the repo's other checkouts and Enter runs
+//! `git apply` there, falling back to a three-way merge that leaves conflict
+//! markers. The current tab stays where it is.
+
+use anyhow::Result;
+
+use super::{App, DiffMode, OverlayData, TabState};
+use crate::git::{self, DiffFile, FileStatus, PatchApply};
+
+/// The hunk the worktree picker will apply.
+#[derive(Debug, Clone)]
+pub struct PendingHunkPatch {
+    pub path: String,
+    pub patch: String,
+}
+
+impl TabState {
+    /// The revision the diff's old side is read from, `""` being the index.
+    /// `None` for remote tabs.
+    pub(super) fn old_side_rev(&self) -> Option<String> {
+        if self.is_remote() {
+            return None;
+        }
+        Some(match self.mode {
+            // `:path` is the index copy
+            DiffMode::Unstaged => String::new(),
+            DiffMode::Staged => "HEAD".to_string(),
+            DiffMode::History => {
+                let history = self.history.as_ref()?;
+                format!("{}^", history.commits.get(history.selected_commit)?.hash)
+            }
+            _ => {
+                let out = std::process::Command::new("git")
+                    .args(["merge-base", &self.base_branch, self.commit_head_ref()])
+                    .current_dir(self.commit_log_root())
+                    .output()
+                    .ok()?;
+                String::from_utf8_lossy(&out.stdout).trim().to_string()
+            }
+        })
+    }
+
+    /// Blob the old side of `file` comes from, for the three-way fallback.
+    /// `None` for new files and remote tabs.
+    fn preimage_blob(&self, file: &DiffFile) -> Option<String> {
+        if file.status == FileStatus::Added {
+            return None;
+        }
+        let old_path = match &file.status {
+            FileStatus::Renamed(old) | FileStatus::Copied(old) => old.as_str(),
+            _ => file.path.as_str(),
+        };
+        let rev = self.old_side_rev()?;
+        crate::github::rev_parse_oid(self.commit_log_root(), &format!("{}:{}", rev, old_path))
+    }
+}
+
+impl App {
+    /// Pick a worktree to apply the current hunk to.
+    pub fn start_apply_hunk(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("applying hunks") {
+            return Ok(());
+        }
+        let tab = self.tab();
+        let Some(file) = tab.selected_diff_file() else {
+            self.notify("No file selected");
+            return Ok(());
+        };
+        let Some(hunk) = file.hunks.get(tab.current_hunk) else {
+            self.notify("No hunk selected");
+            return Ok(());
+        };
+        let patch = match git::file_patch(file, &[hunk], false, tab.preimage_blob(file).as_deref())
+        {
+            Ok(patch) => patch,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return Ok(());
+            }
+        };
+        let path = file.path.clone();
+        let here = std::fs::canonicalize(tab.commit_log_root()).ok();
+        let worktrees: Vec<_> = git::list_worktrees(&tab.repo_root)?
+            .into_iter()
+            .filter(|wt| std::fs::canonicalize(&wt.path).ok() != here)
+            .collect();
+        if worktrees.is_empty() {
+            self.notify("No other worktree to apply to — add one with git worktree add");
+            return Ok(());
+        }
+        self.overlay = Some(OverlayData::WorktreePicker {
+            worktrees,
+            selected: 0,
+            apply: Some(PendingHunkPatch { path, patch }),
+        });
+        Ok(())
+    }
+
+    /// Enter in the picker: apply the pending hunk to `worktree`.
+    pub(super) fn apply_hunk_to(&mut self, pending: &PendingHunkPatch, worktree: &git::Worktree) {
+        let target = if worktree.branch.is_empty() {
+            worktree.path.as_str()
+        } else {
+            worktree.branch.as_str()
+        };
+        match git::apply_patch(&worktree.path, &pending.patch) {
+            Ok(PatchApply::Clean) => {
+                self.notify(&format!("Applied {} hunk to {}", pending.path, target))
+            }
+            Ok(PatchApply::Conflicts(paths)) => self.notify_warn(&format!(
+                "⚠ Applied to {} with conflicts in {} — resolve them there",
+                target,
+                paths.join(", ")
+            )),
+            Err(e) => self.notify_warn(&format!("⚠ Hunk doesn't apply to {}: {:#}", target, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::DiffHunk;
+
+    #[test]
+    fn preimage_is_the_index_blob_for_unstaged_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("f"), "a\n").unwrap();
+        git(&["add", "f"]);
+
+        let mut tab = TabState::new_for_test(vec![]);
+        tab.repo_root = root.clone();
+        tab.mode = DiffMode::Unstaged;
+        let file = DiffFile {
+            path: "f".into(),
+            status: FileStatus::Modified,
+            hunks: Vec::<DiffHunk>::new(),
+            adds: 1,
+            dels: 1,
+            compacted: false,
+            raw_hunk_count: 1,
+        };
+        let blob = String::from_utf8(git(&["rev-parse", ":f"]).stdout).unwrap();
+        assert_eq!(tab.preimage_blob(&file).as_deref(), Some(blob.trim()));
+
+        let added = DiffFile {
+            status: FileStatus::Added,
+            ..file
+        };
+        assert_eq!(tab.preimage_blob(&added), None);
+    }
+}
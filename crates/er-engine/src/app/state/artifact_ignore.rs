@@ -0,0 +1,147 @@
+//! Keeping er's files out of commits. In repo-local mode (`ER_REPO_LOCAL=1`)
+//! the review artifacts are written to `.er/` in the working tree; the first
+//! time that directory shows up, er checks that git ignores it (and legacy
+//! `.er-*.json` sidecars and handoff bundles) and offers once per session to
+//! add what's missing to `.git/info/exclude` or `.gitignore` (`[storage]
+//! ignore_artifacts`). `location = "repo"` commits them on purpose, so it
+//! isn't asked.
+//!
+//! Separately, when er's files are among the changed files under review,
+//! a warning says so — they're about to be committed.
+
+use std::path::Path;
+
+use super::{App, ConfirmAction, InputMode, TabState};
+use crate::git;
+use crate::paths::ErRoot;
+use crate::storage::{self, StorageLocation};
+
+impl TabState {
+    pub(super) fn refresh_artifacts_in_diff(&mut self) {
+        self.artifacts_in_diff = self
+            .files
+            .iter()
+            .filter(|f| git::is_artifact_path(&f.path))
+            .map(|f| f.path.clone())
+            .collect();
+    }
+
+    /// er has written its artifacts into the working tree.
+    fn writes_artifacts_to_working_tree(&self) -> bool {
+        matches!(self.er_root, ErRoot::RepoLocal(ref root) if !root.is_empty())
+            && storage::location() != StorageLocation::Repo
+            && Path::new(&self.er_dir()).is_dir()
+    }
+}
+
+impl App {
+    /// Warn about er's files in the diff, and offer to gitignore the
+    /// artifacts after the first write. True when something was shown.
+    pub fn poll_artifact_ignore(&mut self) -> bool {
+        if self.input_mode != InputMode::Normal || self.overlay.is_some() || self.tab().is_remote()
+        {
+            return false;
+        }
+        let tab = self.tab();
+        if !tab.artifacts_in_diff.is_empty() && tab.artifacts_in_diff != tab.artifacts_warned {
+            let files = tab.artifacts_in_diff.clone();
+            self.tab_mut().artifacts_warned = files.clone();
+            self.notify_warn(&format!(
+                "⚠ Review files in the diff, they'd be committed: {}",
+                files.join(", ")
+            ));
+            return true;
+        }
+        if tab.artifact_ignore_checked
+            || self
+                .config
+                .storage
+                .ignore_artifacts
+                .eq_ignore_ascii_case("off")
+            || !tab.writes_artifacts_to_working_tree()
+        {
+            return false;
+        }
+        let repo_root = tab.repo_root.clone();
+        self.tab_mut().artifact_ignore_checked = true;
+        let patterns = git::unignored_artifact_patterns(&repo_root);
+        if patterns.is_empty() {
+            return false;
+        }
+        let file = match git::ignore_file_for(&repo_root, &self.config.storage.ignore_artifacts) {
+            Ok(file) => file,
+            Err(e) => {
+                crate::debug_log::warn("git", format!("finding the ignore file: {:#}", e));
+                return false;
+            }
+        };
+        self.confirm(ConfirmAction::IgnoreArtifacts {
+            file,
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        });
+        true
+    }
+
+    /// Append the patterns the prompt offered.
+    pub fn ignore_artifacts(&mut self, file: &Path, patterns: &[String]) {
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        match git::append_ignore_patterns(file, &patterns) {
+            Ok(()) => self.notify(&format!(
+                "Added {} to {}",
+                patterns.join(" "),
+                file.display()
+            )),
+            Err(e) => self.notify_warn(&format!("{:#}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::OutputLogged;
+
+    #[test]
+    fn artifacts_in_the_diff_warn_and_the_first_write_offers_to_ignore() {
+        // `[storage] location` is process-wide
+        let _guard = storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&root)
+            .logged_output()
+            .unwrap();
+        let mut app = App::new_for_test(git::parse_diff(
+            "diff --git a/.er/review.json b/.er/review.json\n--- a/.er/review.json\n\
+             +++ b/.er/review.json\n@@ -1 +1 @@\n-{}\n+{ }\n",
+        ));
+        app.tab_mut().repo_root = root.clone();
+        app.tab_mut().er_root = ErRoot::RepoLocal(root.clone());
+        app.tab_mut().refresh_artifacts_in_diff();
+        assert!(app.poll_artifact_ignore());
+        assert!(app
+            .watch_message
+            .as_deref()
+            .is_some_and(|m| m.contains(".er/review.json")));
+
+        // Nothing written to the working tree yet
+        assert!(!app.poll_artifact_ignore());
+        std::fs::create_dir(dir.path().join(".er")).unwrap();
+        assert!(app.poll_artifact_ignore());
+        let InputMode::Confirm(ConfirmAction::IgnoreArtifacts { file, patterns }) =
+            app.input_mode.clone()
+        else {
+            panic!("expected the ignore prompt, got {:?}", app.input_mode);
+        };
+        assert_eq!(patterns.len(), git::ARTIFACT_PATTERNS.len());
+        app.input_mode = InputMode::Normal;
+        app.ignore_artifacts(&file, &patterns);
+        assert!(git::unignored_artifact_patterns(&root).is_empty());
+
+        // Asked once per session
+        assert!(!app.poll_artifact_ignore());
+    }
+}
@@ -0,0 +1,315 @@
+//! The asset gallery: the image files a diff adds, changes or deletes (see
+//! [`git::is_image`]), with their pixel size and byte size on both sides
+//! and a small preview, in the Assets panel. Design-heavy PRs touch dozens
+//! of them; `a` in the panel marks them all reviewed at once.
+//!
+//! Whenever the diff changes, both sides of each image are read on a
+//! worker thread, the old one from git and the new one from wherever the
+//! diff's new side lives.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use anyhow::Result;
+
+use super::complexity::NewSide;
+use super::{App, DiffMode, TabState};
+use crate::ai::PanelContent;
+use crate::git::{self, FileStatus, ImagePreview};
+
+/// Images read per scan.
+const MAX_ASSETS: usize = 200;
+/// Images given a preview, in diff order.
+const MAX_PREVIEWS: usize = 60;
+/// Preview size in pixels; a terminal cell shows two rows of them.
+pub const PREVIEW_WIDTH: usize = 32;
+pub const PREVIEW_HEIGHT: usize = 24;
+/// From this many changed images on, their arrival is announced.
+const ANNOUNCE_AT: usize = 5;
+
+/// One side of an image asset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetSide {
+    pub bytes: u64,
+    /// `(width, height)` in pixels, when the header says
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl AssetSide {
+    fn read(data: &[u8]) -> Self {
+        AssetSide {
+            bytes: data.len() as u64,
+            dimensions: git::image_dimensions(data),
+        }
+    }
+}
+
+/// A changed image asset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetChange {
+    pub path: String,
+    pub status: FileStatus,
+    /// `None` for added files, or when the side couldn't be read
+    pub old: Option<AssetSide>,
+    pub new: Option<AssetSide>,
+    /// Of the new side, the old one for deleted files
+    pub preview: Option<ImagePreview>,
+}
+
+impl AssetChange {
+    /// New size minus old size in bytes.
+    pub fn size_delta(&self) -> i64 {
+        let bytes = |side: &Option<AssetSide>| side.as_ref().map_or(0, |s| s.bytes as i64);
+        bytes(&self.new) - bytes(&self.old)
+    }
+
+    /// Whether the pixel size changed; false when a side isn't known.
+    pub fn resized(&self) -> bool {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                old.dimensions.is_some()
+                    && new.dimensions.is_some()
+                    && old.dimensions != new.dimensions
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The changed image assets of a tab's diff.
+#[derive(Debug, Default)]
+pub struct AssetScan {
+    /// The diff the results are for
+    pub diff_hash: String,
+    /// In diff order
+    pub assets: Vec<AssetChange>,
+    /// The count last announced, so an unchanged set isn't announced on
+    /// every refresh
+    announced: usize,
+    rx: Option<Receiver<Vec<AssetChange>>>,
+}
+
+/// A file to read: `(path, old path, status)`.
+type AssetFile = (String, String, FileStatus);
+
+/// Read both sides of `files`: the old one at `old_rev` in `repo_root`.
+fn read_assets(
+    files: Vec<AssetFile>,
+    repo_root: &str,
+    old_rev: &str,
+    new_side: &NewSide,
+) -> Vec<AssetChange> {
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(i, (path, old_path, status))| {
+            let old = (status != FileStatus::Added)
+                .then(|| {
+                    git::git_show_bytes(repo_root, old_rev, &old_path)
+                        .ok()
+                        .flatten()
+                })
+                .flatten();
+            let new = (status != FileStatus::Deleted)
+                .then(|| new_side.read_bytes(&path))
+                .flatten();
+            let preview = new
+                .as_deref()
+                .or(old.as_deref())
+                .filter(|_| i < MAX_PREVIEWS)
+                .and_then(|data| git::image_preview(data, PREVIEW_WIDTH, PREVIEW_HEIGHT));
+            AssetChange {
+                path,
+                status,
+                old: old.as_deref().map(AssetSide::read),
+                new: new.as_deref().map(AssetSide::read),
+                preview,
+            }
+        })
+        .collect()
+}
+
+impl TabState {
+    /// Drop the Assets panel once there's nothing in it.
+    fn close_empty_asset_panel(&mut self) {
+        if !self.assets.assets.is_empty() {
+            return;
+        }
+        if self.panel == Some(PanelContent::Assets) {
+            self.panel = None;
+        }
+        if self.second_panel == Some(PanelContent::Assets) {
+            self.second_panel = None;
+        }
+    }
+
+    /// Changed assets, and how many of them are reviewed.
+    pub fn asset_counts(&self) -> (usize, usize) {
+        let reviewed = self
+            .assets
+            .assets
+            .iter()
+            .filter(|a| !self.needs_review(&a.path))
+            .count();
+        (self.assets.assets.len(), reviewed)
+    }
+}
+
+impl App {
+    /// Read the active tab's changed images when its diff changed. True
+    /// when the results arrived.
+    pub fn poll_assets(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.assets.rx {
+            return match rx.try_recv() {
+                Ok(_) if tab.assets.diff_hash != tab.diff_hash => {
+                    tab.assets.rx = None;
+                    false
+                }
+                Ok(assets) => {
+                    tab.assets.rx = None;
+                    tab.assets.assets = assets;
+                    tab.close_empty_asset_panel();
+                    let count = tab.assets.assets.len();
+                    let announce = count >= ANNOUNCE_AT
+                        && count != tab.assets.announced
+                        && tab.panel != Some(PanelContent::Assets);
+                    tab.assets.announced = count;
+                    if announce {
+                        self.notify(&format!(
+                            "{} images changed — the Assets panel has them side by side",
+                            count
+                        ));
+                    }
+                    true
+                }
+                Err(TryRecvError::Empty) => false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.assets.rx = None;
+                    false
+                }
+            };
+        }
+        if matches!(tab.mode, DiffMode::History | DiffMode::Tour)
+            || tab.assets.diff_hash == tab.diff_hash
+        {
+            return false;
+        }
+        tab.assets.diff_hash = tab.diff_hash.clone();
+        let had_results = !tab.assets.assets.is_empty();
+        tab.assets.assets.clear();
+        tab.close_empty_asset_panel();
+        let files: Vec<AssetFile> = tab
+            .files
+            .iter()
+            .filter(|f| git::is_image(&f.path))
+            .take(MAX_ASSETS)
+            .map(|f| {
+                let old_path = match &f.status {
+                    FileStatus::Renamed(old) | FileStatus::Copied(old) => old.clone(),
+                    _ => f.path.clone(),
+                };
+                (f.path.clone(), old_path, f.status.clone())
+            })
+            .collect();
+        if files.is_empty() {
+            return had_results;
+        }
+        let (Some(new_side), Some(old_rev)) = (tab.new_side(), tab.old_side_rev()) else {
+            return had_results;
+        };
+        let repo_root = tab.commit_log_root().to_string();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(read_assets(files, &repo_root, &old_rev, &new_side));
+        });
+        tab.assets.rx = Some(rx);
+        had_results
+    }
+
+    /// Open the Assets panel, or say there's nothing in it.
+    pub fn open_assets(&mut self) {
+        if self.tab().assets.assets.is_empty() {
+            self.notify("No image assets in this diff");
+            return;
+        }
+        let tab = self.tab_mut();
+        if tab.second_panel != Some(PanelContent::Assets) {
+            tab.panel = Some(PanelContent::Assets);
+            tab.panel_scroll = 0;
+        }
+    }
+
+    /// Mark every changed image reviewed (`a` in the Assets panel).
+    pub fn mark_assets_reviewed(&mut self) -> Result<()> {
+        let tab = self.tab_mut();
+        let pending: Vec<String> = tab
+            .assets
+            .assets
+            .iter()
+            .filter(|a| tab.needs_review(&a.path))
+            .map(|a| a.path.clone())
+            .collect();
+        if pending.is_empty() {
+            self.notify("All image assets are already reviewed");
+            return Ok(());
+        }
+        for path in &pending {
+            let hash = tab
+                .current_per_file_hashes
+                .get(path)
+                .cloned()
+                .unwrap_or_default();
+            tab.reviewed.insert(path.clone(), hash);
+            tab.clear_addressed_for_file(path);
+        }
+        tab.reviewed_revision += 1;
+        tab.save_reviewed_files()?;
+        for path in &pending {
+            self.push_viewed_toggle(path, true);
+            self.publish_event(crate::events::Event::FileReviewed {
+                file: path.clone(),
+                reviewed: true,
+            });
+        }
+        self.notify(&format!("Reviewed {} image asset(s)", pending.len()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_panel_follows_the_scan() {
+        let diff =
+            "diff --git a/assets/logo.png b/assets/logo.png\nindex 1111111..2222222 100644\n\
+                    Binary files a/assets/logo.png and b/assets/logo.png differ\n\
+                    diff --git a/src/app.ts b/src/app.ts\n--- a/src/app.ts\n+++ b/src/app.ts\n\
+                    @@ -1 +1 @@\n-a\n+b\n";
+        let mut tab = TabState::new_for_test(git::parse_diff(diff));
+        tab.assets.assets = vec![AssetChange {
+            path: "assets/logo.png".into(),
+            status: FileStatus::Modified,
+            old: Some(AssetSide {
+                bytes: 2048,
+                dimensions: Some((64, 64)),
+            }),
+            new: Some(AssetSide {
+                bytes: 1024,
+                dimensions: Some((128, 128)),
+            }),
+            preview: None,
+        }];
+        assert_eq!(tab.assets.assets[0].size_delta(), -1024);
+        assert!(tab.assets.assets[0].resized());
+        assert_eq!(tab.asset_counts(), (1, 0));
+        tab.panel = Some(PanelContent::SymbolRefs);
+        tab.toggle_panel();
+        assert_eq!(tab.panel, Some(PanelContent::Assets));
+
+        tab.assets.assets.clear();
+        tab.close_empty_asset_panel();
+        assert_eq!(tab.panel, None);
+    }
+}
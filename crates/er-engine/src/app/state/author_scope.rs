@@ -0,0 +1,244 @@
+//! Splitting a multi-author branch: per-author stats (Git hub → Authors)
+//! and the `author:` filter, which narrows the Branch diff to the hunks
+//! whose added lines `git blame` gives to that author.
+//!
+//! Blame runs once per diff load while an `author:` rule is active; hunks
+//! without a matching author are dropped from `files` (lazy files when they
+//! are parsed), so clearing the rule reloads the diff.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use super::{App, DiffMode, HubAction, HubItem, HubKind, OverlayData, TabState};
+use crate::app::filter::{self, FileAuthors};
+use crate::git::{self, DiffFile, DiffHunk, LineType};
+
+/// Blame of the lines the Branch diff adds: path → new line → author.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorScope {
+    lines: HashMap<String, HashMap<usize, String>>,
+    /// Lowercased authors per file, for the file filter
+    pub files: FileAuthors,
+}
+
+impl AuthorScope {
+    /// Lowercased authors of `hunk`'s added lines.
+    fn hunk_authors(&self, path: &str, hunk: &DiffHunk) -> HashSet<String> {
+        let Some(lines) = self.lines.get(path) else {
+            return HashSet::new();
+        };
+        hunk.lines
+            .iter()
+            .filter(|l| l.line_type == LineType::Add)
+            .filter_map(|l| lines.get(&l.new_num?))
+            .map(|author| author.to_lowercase())
+            .collect()
+    }
+
+    /// Keep only the hunks an include needle matches.
+    fn prune(&self, file: &mut DiffFile, needles: &[&str]) {
+        if needles.is_empty() {
+            return;
+        }
+        let path = file.path.clone();
+        file.hunks
+            .retain(|h| filter::authors_match(&self.hunk_authors(&path, h), needles));
+    }
+}
+
+impl TabState {
+    fn author_blame_rev(&self) -> &str {
+        self.pr_head_ref.as_deref().unwrap_or("HEAD")
+    }
+
+    /// Hunks of file `index`, parsing a lazy stub from the raw diff.
+    pub(super) fn hunks_for_blame(&self, index: usize) -> Vec<DiffHunk> {
+        let file = &self.files[index];
+        if !file.hunks.is_empty() || !self.lazy_mode {
+            return file.hunks.clone();
+        }
+        match (
+            &self.raw_diff,
+            self.file_headers.iter().find(|h| h.path == file.path),
+        ) {
+            (Some(raw), Some(header)) => git::parse_file_at_offset(raw, header).hunks,
+            _ => Vec::new(),
+        }
+    }
+
+    /// Blame the diff's added lines when an `author:` rule is active in the
+    /// Branch diff, then narrow the parsed files to the matching hunks.
+    pub(super) fn refresh_author_scope(&mut self) {
+        self.author_scope = None;
+        if self.mode != DiffMode::Branch
+            || self.is_remote()
+            || !filter::has_author_rules(&self.filter_rules)
+        {
+            return;
+        }
+        let mut scope = AuthorScope::default();
+        for index in 0..self.files.len() {
+            let added: HashSet<usize> = self
+                .hunks_for_blame(index)
+                .iter()
+                .flat_map(|h| h.lines.iter())
+                .filter(|l| l.line_type == LineType::Add)
+                .filter_map(|l| l.new_num)
+                .collect();
+            if added.is_empty() {
+                continue;
+            }
+            let path = self.files[index].path.clone();
+            let blame =
+                match git::blame_line_authors(&self.repo_root, self.author_blame_rev(), &path) {
+                    Ok(blame) => blame,
+                    Err(e) => {
+                        crate::debug_log::warn("git", format!("blame {}: {:#}", path, e));
+                        continue;
+                    }
+                };
+            let lines: HashMap<usize, String> = blame
+                .into_iter()
+                .filter(|(n, _)| added.contains(n))
+                .collect();
+            scope.files.insert(
+                path.clone(),
+                lines.values().map(|a| a.to_lowercase()).collect(),
+            );
+            scope.lines.insert(path, lines);
+        }
+        let needles = filter::included_authors(&self.filter_rules);
+        for file in &mut self.files {
+            scope.prune(file, &needles);
+        }
+        self.author_scope = Some(scope);
+    }
+
+    /// Narrow a just-parsed lazy file to the active `author:` hunks.
+    pub(super) fn prune_to_author_scope(&mut self, index: usize) {
+        let Some(scope) = &self.author_scope else {
+            return;
+        };
+        let needles = filter::included_authors(&self.filter_rules);
+        if let Some(file) = self.files.get_mut(index) {
+            scope.prune(file, &needles);
+        }
+    }
+}
+
+impl App {
+    /// Git hub → Authors: who wrote how much of the branch; picking one
+    /// filters the diff to their hunks.
+    pub fn open_branch_authors(&mut self) {
+        if let Err(e) = self.show_branch_authors() {
+            self.notify(&format!("Authors: {:#}", e));
+        }
+    }
+
+    fn show_branch_authors(&mut self) -> Result<()> {
+        let tab = self.tab();
+        if tab.is_remote() {
+            anyhow::bail!("Authors need a local checkout of the branch");
+        }
+        let stats = git::branch_author_stats(
+            tab.commit_log_root(),
+            &tab.base_branch,
+            tab.commit_head_ref(),
+        )?;
+        if stats.is_empty() {
+            self.notify("No commits on this branch yet");
+            return Ok(());
+        }
+        let items = stats
+            .iter()
+            .map(|s| HubItem {
+                label: s.name.clone(),
+                hint: "".into(),
+                description: format!(
+                    "{} commit{} · {} file{} · +{} −{}",
+                    s.commits,
+                    if s.commits == 1 { "" } else { "s" },
+                    s.files,
+                    if s.files == 1 { "" } else { "s" },
+                    s.adds,
+                    s.dels
+                ),
+                action: HubAction::FilterByAuthor(s.name.clone()),
+                is_header: false,
+                enabled: true,
+            })
+            .collect();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Authors,
+            title: Some(format!("AUTHORS · {}", stats.len())),
+            selected: 0,
+            items,
+        });
+        Ok(())
+    }
+
+    pub fn filter_by_author(&mut self, name: &str) {
+        if self.tab().mode != DiffMode::Branch {
+            self.tab_mut().set_mode(DiffMode::Branch);
+        }
+        self.tab_mut()
+            .apply_filter_expr(&format!("author:{}", name));
+        let files = self.tab().visible_files().len();
+        self.notify(&format!(
+            "Showing {}'s hunks: {} file{}",
+            name,
+            files,
+            if files == 1 { "" } else { "s" }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::DiffLine;
+
+    fn hunk(new_lines: &[usize]) -> DiffHunk {
+        DiffHunk {
+            header: "@@".into(),
+            old_start: 1,
+            old_count: 0,
+            new_start: new_lines[0],
+            new_count: new_lines.len(),
+            lines: new_lines
+                .iter()
+                .map(|&n| DiffLine {
+                    line_type: LineType::Add,
+                    content: String::new(),
+                    old_num: None,
+                    new_num: Some(n),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn pruning_keeps_hunks_with_lines_by_the_author() {
+        let scope = AuthorScope {
+            lines: [(
+                "a.rs".to_string(),
+                [(1, "Alice".to_string()), (9, "Bob".to_string())].into(),
+            )]
+            .into(),
+            files: FileAuthors::new(),
+        };
+        let mut file = DiffFile {
+            path: "a.rs".into(),
+            status: git::FileStatus::Modified,
+            hunks: vec![hunk(&[1, 2]), hunk(&[9])],
+            adds: 3,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 2,
+        };
+        scope.prune(&mut file, &["alice"]);
+        assert_eq!(file.hunks.len(), 1);
+        assert_eq!(file.hunks[0].new_start, 1);
+    }
+}
@@ -0,0 +1,186 @@
+//! The branch hygiene audit (Git hub → Branch audit) and the approval gate
+//! built on it (`[audit] gate_approve`).
+
+use anyhow::Result;
+
+use super::pr_head::PrHeadSync;
+use super::{App, ConfirmAction, HubAction, HubItem, HubKind, OverlayData};
+use crate::git::{self, BranchAudit};
+
+/// Offenders listed per check before "+N more".
+const OFFENDERS_SHOWN: usize = 3;
+
+impl App {
+    /// Audit the active tab's branch against its base.
+    pub fn run_branch_audit(&self) -> Result<BranchAudit> {
+        let tab = self.tab();
+        if tab.is_remote() {
+            anyhow::bail!("needs a local checkout of the branch");
+        }
+        git::audit_branch(
+            tab.commit_log_root(),
+            &tab.base_branch,
+            tab.commit_head_ref(),
+            &self.config.audit,
+        )
+    }
+
+    pub fn open_branch_audit(&mut self) {
+        match self.run_branch_audit() {
+            Ok(audit) => self.show_branch_audit(&audit),
+            Err(e) => self.notify(&format!("Branch audit: {:#}", e)),
+        }
+    }
+
+    /// The audit as a checklist, failing checks first.
+    fn show_branch_audit(&mut self, audit: &BranchAudit) {
+        let mut checks: Vec<_> = audit.checks.iter().collect();
+        checks.sort_by_key(|c| c.passed());
+        let items = checks
+            .into_iter()
+            .map(|check| {
+                let mut description = check
+                    .offenders
+                    .iter()
+                    .take(OFFENDERS_SHOWN)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if check.offenders.len() > OFFENDERS_SHOWN {
+                    description.push_str(&format!(
+                        ", +{} more",
+                        check.offenders.len() - OFFENDERS_SHOWN
+                    ));
+                }
+                HubItem {
+                    label: format!(
+                        "{} {}",
+                        if check.passed() { "✓" } else { "✗" },
+                        check.label()
+                    ),
+                    hint: "".into(),
+                    description,
+                    action: HubAction::Noop,
+                    is_header: false,
+                    enabled: !check.passed(),
+                }
+            })
+            .collect();
+        let failed = audit.failed();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Audit,
+            title: (failed > 0).then(|| format!("BRANCH AUDIT · {} failing", failed)),
+            selected: 0,
+            items,
+        });
+    }
+
+    /// Ask to approve the PR, with the audit's verdict alongside. Under
+    /// `[audit] gate_approve` a failing audit is shown instead of the prompt.
+    pub fn request_approve_pr(&mut self) {
+        if self.blocked_by_read_only("approving") {
+            return;
+        }
+        let audit = match self.run_branch_audit() {
+            Ok(audit) => Some(audit),
+            Err(e) => {
+                crate::debug_log::warn("git", format!("branch audit: {:#}", e));
+                None
+            }
+        };
+        let failed = audit.as_ref().map_or(0, BranchAudit::failed);
+        if let (true, Some(audit)) = (self.config.audit.gate_approve && failed > 0, &audit) {
+            self.show_branch_audit(audit);
+            self.notify(&format!(
+                "Approval blocked: {} audit check{} failing",
+                failed,
+                if failed == 1 { "" } else { "s" }
+            ));
+            return;
+        }
+        let mut prompt = ConfirmAction::ApprovePR.prompt();
+        if let Some(pr) = self.tab().pr_number {
+            prompt = prompt.detail(format!("PR #{}", pr));
+        }
+        if failed > 0 {
+            prompt = prompt.detail(format!(
+                "⚠ Branch audit: {} check{} failing",
+                failed,
+                if failed == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(differs) = self.tab().pr_head_sync().and_then(PrHeadSync::label) {
+            prompt = prompt.detail(format!("⚠ Local HEAD vs GitHub: {}", differs));
+        }
+        self.ask_confirm(prompt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::InputMode;
+    use crate::command::OutputLogged;
+
+    fn repo_with_wip_commit() -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@t.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git(&["init", "-b", "main"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["commit", "-m", "base", "--no-gpg-sign"]);
+        git(&["checkout", "-b", "feature"]);
+        std::fs::write(root.join("a.txt"), "two\n").unwrap();
+        git(&["commit", "-am", "WIP: try a thing", "--no-gpg-sign"]);
+
+        let mut app = App::new_for_test(vec![]);
+        let tab = app.tab_mut();
+        tab.repo_root = root.to_str().unwrap().to_string();
+        tab.base_branch = "main".into();
+        tab.current_branch = "feature".into();
+        (dir, app)
+    }
+
+    #[test]
+    fn audit_lists_failing_checks_first() {
+        let (_dir, mut app) = repo_with_wip_commit();
+        app.open_branch_audit();
+        let Some(OverlayData::ModalHub { kind, items, .. }) = &app.overlay else {
+            panic!("audit not open");
+        };
+        assert_eq!(*kind, HubKind::Audit);
+        assert_eq!(items[0].label, "✗ WIP commits (1)");
+        assert!(items[0].description.ends_with("WIP: try a thing"));
+        assert!(items[1..].iter().all(|i| i.label.starts_with('✓')));
+    }
+
+    #[test]
+    fn gated_approval_shows_the_audit_instead() {
+        let (_dir, mut app) = repo_with_wip_commit();
+        app.request_approve_pr();
+        assert_eq!(app.input_mode, InputMode::Confirm(ConfirmAction::ApprovePR));
+
+        app.cancel_confirm();
+        app.config.audit.gate_approve = true;
+        app.request_approve_pr();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(matches!(
+            app.overlay,
+            Some(OverlayData::ModalHub {
+                kind: HubKind::Audit,
+                ..
+            })
+        ));
+    }
+}
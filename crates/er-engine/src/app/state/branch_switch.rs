@@ -0,0 +1,248 @@
+//! Switching the working tree's branch (Git hub → *Switch branch…*).
+//!
+//! The picker lists local branches with how far they are ahead of and
+//! behind their upstream, then remote branches nobody has checked out yet
+//! (switching to one makes a local branch tracking it). Uncommitted changes
+//! to tracked files would either travel to the other branch or make the
+//! switch fail, so er asks first: `y` stashes them and switches, `n` stays.
+//! The stash isn't popped on the new branch — it's the old branch's work.
+//!
+//! After the switch the tab reloads like any branch change under it: the
+//! old branch's reviewed marks and session are saved, the new branch's
+//! storage and session are loaded, and the diff is redone against the same
+//! base.
+
+use super::{App, ConfirmAction, HubAction, HubItem, HubKind, OverlayData};
+use crate::git::{self, BranchInfo};
+
+/// Picker entries: local branches, then remote ones.
+fn branch_items(branches: &[BranchInfo]) -> Vec<HubItem> {
+    let header = |label: &str| HubItem {
+        label: format!("── {} ──", label),
+        hint: "".into(),
+        description: "".into(),
+        action: HubAction::Noop,
+        is_header: true,
+        enabled: true,
+    };
+    let item = |b: &BranchInfo| HubItem {
+        label: if b.current {
+            format!("{} (current)", b.name)
+        } else {
+            b.name.clone()
+        },
+        hint: match (b.ahead, b.behind) {
+            (0, 0) => "".into(),
+            (a, 0) => format!("↑{}", a),
+            (0, b) => format!("↓{}", b),
+            (a, b) => format!("↑{} ↓{}", a, b),
+        },
+        description: format!("{} · {} · {}", b.hash, b.age, b.subject),
+        action: HubAction::SwitchBranch {
+            name: b.name.clone(),
+            remote: b.remote,
+        },
+        is_header: false,
+        enabled: !b.current,
+    };
+    let mut items = Vec::new();
+    for (remote, label) in [(false, "Local"), (true, "Remote")] {
+        let mut group = branches.iter().filter(|b| b.remote == remote).peekable();
+        if group.peek().is_some() {
+            items.push(header(label));
+            items.extend(group.map(item));
+        }
+    }
+    items
+}
+
+impl App {
+    /// Git hub → Switch branch…: pick a branch for the working tree.
+    pub fn open_branch_picker(&mut self) {
+        if self.blocked_by_read_only("switching branches") {
+            return;
+        }
+        let tab = self.tab();
+        if tab.is_remote() || tab.is_local_branch_view() {
+            self.notify_warn("Switching branches works in the working-tree tab");
+            return;
+        }
+        let branches = match git::git_branches(&tab.repo_root) {
+            Ok(branches) => branches,
+            Err(e) => {
+                self.report_command_error("Listing branches failed", &e);
+                return;
+            }
+        };
+        if branches.iter().all(|b| b.current) {
+            self.notify("No other branches");
+            return;
+        }
+        let items = branch_items(&branches);
+        let selected = items
+            .iter()
+            .position(|i| !i.is_header && i.enabled)
+            .unwrap_or(0);
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Branches,
+            title: None,
+            items,
+            selected,
+        });
+    }
+
+    /// Switch to `name`, asking to stash first when tracked files have
+    /// uncommitted changes.
+    pub fn request_switch_branch(&mut self, name: &str, remote: bool) {
+        if self.blocked_by_read_only("switching branches") {
+            return;
+        }
+        let repo_root = self.tab().repo_root.clone();
+        if git::is_merge_in_progress(&repo_root) {
+            self.notify_warn("Finish or abort the merge before switching branches");
+            return;
+        }
+        match git::uncommitted_changes(&repo_root) {
+            Ok(0) => self.switch_branch(name, remote, false),
+            Ok(changes) => self.confirm(ConfirmAction::SwitchBranch {
+                name: name.to_string(),
+                remote,
+                changes,
+            }),
+            Err(e) => self.report_command_error("Checking the working tree failed", &e),
+        }
+    }
+
+    /// Check out `name` in the working tree, stashing first when `stash`,
+    /// and reload the tab for it. A failed switch puts the stash back.
+    pub fn switch_branch(&mut self, name: &str, remote: bool, stash: bool) {
+        if self.blocked_by_read_only("switching branches") {
+            return;
+        }
+        let repo_root = self.tab().repo_root.clone();
+        let from = self.tab().current_branch.clone();
+        self.tab().save_session();
+        if stash {
+            let message = format!("er: {} before switching to {}", from, name);
+            if let Err(e) = git::git_stash_push(&repo_root, &message) {
+                self.report_command_error("Stashing the changes failed", &e);
+                return;
+            }
+        }
+        if let Err(e) = git::git_switch(&repo_root, name, remote) {
+            if stash {
+                if let Err(pop) = git::git_stash_pop(&repo_root) {
+                    crate::debug_log::warn("git", format!("restoring the stash: {:#}", pop));
+                }
+            }
+            self.report_command_error(&format!("Switching to {} failed", name), &e);
+            return;
+        }
+
+        let tab = self.tab_mut();
+        tab.checkpoint_view = None;
+        // The PR found at launch was the old branch's
+        if tab.remote_repo.is_none() {
+            tab.pr_number = None;
+            tab.pr_data = None;
+        }
+        if let Err(e) = tab.refresh_diff() {
+            self.report_command_error("Refreshing the diff failed", &e);
+            return;
+        }
+        self.tab_mut().restore_session();
+        let branch = self.tab().current_branch.clone();
+        self.notify(&if stash {
+            format!(
+                "Switched to {} — changes stashed, git stash pop on {} to get them back",
+                branch, from
+            )
+        } else {
+            format!("Switched to {}", branch)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::InputMode;
+    use crate::command::OutputLogged;
+
+    #[test]
+    fn dirty_switch_asks_then_stashes_and_reloads_the_branch() {
+        let _guard = crate::storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let storage = tempfile::tempdir().unwrap();
+        std::env::set_var("ER_STORAGE_ROOT", storage.path());
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@t.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git(&["init", "-b", "main"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "first", "--no-gpg-sign"]);
+        git(&["branch", "feature"]);
+        std::fs::write(root.join("a.txt"), "edited\n").unwrap();
+
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().repo_root = root.to_str().unwrap().to_string();
+        app.tab_mut().current_branch = "main".into();
+
+        app.open_branch_picker();
+        let Some(OverlayData::ModalHub {
+            items, selected, ..
+        }) = &app.overlay
+        else {
+            panic!("expected the branch picker");
+        };
+        assert_eq!(
+            items[*selected].action,
+            HubAction::SwitchBranch {
+                name: "feature".into(),
+                remote: false,
+            }
+        );
+        assert!(items
+            .iter()
+            .any(|i| i.label == "main (current)" && !i.enabled));
+        app.overlay = None;
+
+        app.request_switch_branch("feature", false);
+        assert!(matches!(
+            app.input_mode,
+            InputMode::Confirm(ConfirmAction::SwitchBranch { changes: 1, .. })
+        ));
+        app.input_mode = InputMode::Normal;
+        app.switch_branch("feature", false, true);
+        assert_eq!(app.tab().current_branch, "feature");
+        assert_eq!(
+            git::uncommitted_changes(app.tab().repo_root.as_str()).unwrap(),
+            0
+        );
+        let stashes = git(&["stash", "list"]);
+        assert!(String::from_utf8_lossy(&stashes.stdout).contains("main before switching"));
+
+        // A branch that isn't there leaves the working tree as it was
+        app.switch_branch("nope", false, false);
+        assert_eq!(app.tab().current_branch, "feature");
+
+        // --read-only never moves HEAD
+        app.read_only = true;
+        app.switch_branch("main", false, false);
+        assert_eq!(app.tab().current_branch, "feature");
+
+        std::env::remove_var("ER_STORAGE_ROOT");
+    }
+}
@@ -0,0 +1,252 @@
+//! Settings → *Report a bug…*: package a sanitized report (see
+//! [`crate::bug_report`]) into `<storage root>/reports/` and copy either
+//! its path or the `gh issue create` command that files it. Reports can
+//! carry a snapshot of the view — the mode, counts, selection and what's
+//! open, never file names or code.
+//!
+//! `er doctor`'s checks run `git` and `gh`, so the report is put together
+//! on a worker thread and picked up by [`App::poll_bug_report`].
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData};
+use crate::bug_report::{self, ReportInput};
+use crate::{config_overrides, debug_log, doctor};
+
+/// A report being written, and whether to copy the gh command for it.
+#[derive(Debug, Default)]
+pub struct BugReport {
+    rx: Option<Receiver<std::io::Result<PathBuf>>>,
+    gh: bool,
+}
+
+/// The variant name of a `Debug` value, without its fields.
+fn variant_name(value: &impl std::fmt::Debug) -> String {
+    let text = format!("{:?}", value);
+    text.split([' ', '(', '{'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+impl App {
+    /// Settings → Report a bug…: where the report goes, with or without
+    /// the view state.
+    pub fn open_bug_report_hub(&mut self) {
+        let header = |label: &str| HubItem {
+            label: format!("── {} ──", label),
+            hint: "".into(),
+            description: "".into(),
+            action: HubAction::Noop,
+            is_header: true,
+            enabled: true,
+        };
+        let item = |label: &str, description: &str, snapshot: bool, gh: bool| HubItem {
+            label: label.into(),
+            hint: "".into(),
+            description: description.into(),
+            action: HubAction::BugReport { snapshot, gh },
+            is_header: false,
+            enabled: true,
+        };
+        let mut items = Vec::new();
+        for (snapshot, label) in [(true, "With view state"), (false, "Without")] {
+            items.push(header(label));
+            items.push(item(
+                "Save to a file",
+                "Version, OS, terminal, changed config (secrets stripped), last log lines — copies the path",
+                snapshot,
+                false,
+            ));
+            items.push(item(
+                "Copy gh issue create",
+                "Saves the same file and copies the command that files it as an issue",
+                snapshot,
+                true,
+            ));
+        }
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::BugReport,
+            title: None,
+            items,
+            selected: 1,
+        });
+    }
+
+    /// The view state for a report: where er is and what's open, without
+    /// file names or code.
+    pub fn view_snapshot(&self) -> String {
+        let tab = self.tab();
+        let kind = if tab.is_repoless() {
+            if tab.files.is_empty() {
+                "no repository"
+            } else {
+                "patch"
+            }
+        } else if tab.is_remote() {
+            "remote PR"
+        } else {
+            "local"
+        };
+        let layers = &tab.layers;
+        [
+            format!("tab: {} ({} of {})", kind, self.active_tab + 1, self.tabs.len()),
+            format!("mode: {:?}", tab.mode),
+            format!(
+                "files: {} ({} reviewed), selected {}, hunk {}",
+                tab.files.len(),
+                tab.reviewed.len(),
+                tab.selected_file,
+                tab.current_hunk
+            ),
+            format!(
+                "layers: questions {}, github comments {}, ai findings {}, hide resolved {}, code age {}",
+                layers.show_questions,
+                layers.show_github_comments,
+                layers.show_ai_findings,
+                layers.hide_resolved,
+                layers.show_code_age
+            ),
+            format!("panel: {:?}", tab.panel),
+            format!("input: {}", variant_name(&self.input_mode)),
+            format!(
+                "overlay: {}",
+                match &self.overlay {
+                    Some(OverlayData::ModalHub { kind, .. }) => format!("ModalHub {:?}", kind),
+                    Some(overlay) => variant_name(overlay),
+                    None => "none".into(),
+                }
+            ),
+            format!("filter: {}", !tab.filter_input.is_empty()),
+            format!("terminal width: {}", self.last_terminal_width),
+        ]
+        .join("\n")
+    }
+
+    /// Write the report on a worker thread; [`Self::poll_bug_report`]
+    /// copies the result.
+    pub fn start_bug_report(&mut self, snapshot: bool, gh: bool) {
+        if self.bug_report.rx.is_some() {
+            self.notify("Already writing a bug report");
+            return;
+        }
+        let tab = self.tab();
+        let repo_root = (!tab.is_remote() && !tab.is_repoless()).then(|| tab.repo_root.clone());
+        let snapshot = snapshot.then(|| self.view_snapshot());
+        let log = debug_log::recent();
+        let home = dirs::home_dir().map(|h| h.to_string_lossy().to_string());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let checks = doctor::run(repo_root.as_deref());
+            let config = config_overrides::effective(repo_root.as_deref());
+            let env = |name: &str| std::env::var(name).ok();
+            let text = bug_report::report(&ReportInput {
+                checks: &checks,
+                config: &config,
+                log: &log,
+                snapshot: snapshot.as_deref(),
+                home: home.as_deref(),
+                env: &env,
+            });
+            let _ = tx.send(bug_report::write_report(crate::outbox::unix_now(), &text));
+        });
+        self.bug_report = BugReport { rx: Some(rx), gh };
+        self.notify("Writing a bug report…");
+    }
+
+    /// Copy the written report's path or gh command. True once it's done.
+    pub fn poll_bug_report(&mut self) -> bool {
+        let Some(rx) = &self.bug_report.rx else {
+            return false;
+        };
+        let written = match rx.try_recv() {
+            Ok(written) => written,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                self.bug_report.rx = None;
+                self.notify_error("Writing the bug report failed: the worker stopped");
+                return true;
+            }
+        };
+        self.bug_report.rx = None;
+        let path = match written {
+            Ok(path) => path,
+            Err(e) => {
+                self.notify_error(&format!("Writing the bug report failed: {}", e));
+                return true;
+            }
+        };
+        let (text, msg) = if self.bug_report.gh {
+            (
+                bug_report::gh_issue_command(&path),
+                "Copied gh issue create — read the report before filing it".to_string(),
+            )
+        } else {
+            let path = path.display().to_string();
+            let msg = format!("Bug report saved: {}", path);
+            (path, msg)
+        };
+        match self.copy_to_clipboard(&text) {
+            Ok(via) => self.notify_copied(&msg, via),
+            Err(_) => self.notify(&format!("Bug report saved: {}", path.display())),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::InputMode;
+    use crate::git;
+
+    #[test]
+    fn hub_offers_both_targets_and_the_snapshot_leaves_out_paths() {
+        let files = git::parse_diff(
+            "diff --git a/secret/plan.rs b/secret/plan.rs\n--- a/secret/plan.rs\n+++ b/secret/plan.rs\n\
+             @@ -1 +1 @@\n-old\n+new\n",
+        );
+        let mut app = App::new_for_test(files);
+        app.open_bug_report_hub();
+        let Some(OverlayData::ModalHub {
+            kind: HubKind::BugReport,
+            items,
+            selected,
+            ..
+        }) = &app.overlay
+        else {
+            panic!("expected the bug report hub");
+        };
+        assert_eq!(
+            items[*selected].action,
+            HubAction::BugReport {
+                snapshot: true,
+                gh: false
+            }
+        );
+        assert!(items.iter().any(|i| i.action
+            == HubAction::BugReport {
+                snapshot: false,
+                gh: true
+            }));
+
+        app.input_mode = InputMode::Search;
+        let snapshot = app.view_snapshot();
+        assert!(snapshot.contains("tab: local (1 of 1)"));
+        assert!(snapshot.contains("files: 1 (0 reviewed), selected 0, hunk 0"));
+        assert!(snapshot.contains("input: Search"));
+        assert!(snapshot.contains("overlay: ModalHub BugReport"));
+        assert!(!snapshot.contains("plan.rs"));
+
+        // A worker that died doesn't leave the next report blocked
+        let (tx, rx) = mpsc::channel();
+        drop(tx);
+        app.bug_report = BugReport {
+            rx: Some(rx),
+            gh: false,
+        };
+        assert!(app.poll_bug_report());
+        assert!(app.bug_report.rx.is_none());
+    }
+}
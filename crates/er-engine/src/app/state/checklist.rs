@@ -0,0 +1,330 @@
+//! Editing the review checklist from the AI summary panel.
+//!
+//! With the checklist column focused: `a` adds an item below the cursor and
+//! `e` edits it, both in the comment box; `x` deletes (after a confirm),
+//! `J`/`K` move it, `f` ties it to the selected file and `t` opens the
+//! templates hub (see [`crate::ai::checklist`]). Every change is written
+//! straight back to `.er/checklist.json`.
+
+use anyhow::Result;
+use tui_textarea::TextArea;
+
+use super::{App, ConfirmAction, HubAction, HubItem, HubKind, InputMode, OverlayData};
+use crate::ai::{self, ErChecklist, ReviewFocus};
+
+/// What the comment box is editing when it holds a checklist item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecklistEdit {
+    /// New item, inserted at this index
+    Add {
+        at: usize,
+    },
+    Edit {
+        index: usize,
+    },
+}
+
+impl App {
+    fn checklist_cursor(&self) -> Option<usize> {
+        let tab = self.tab();
+        (tab.review_focus == ReviewFocus::Checklist).then_some(tab.review_cursor)
+    }
+
+    /// Write the checklist back to `.er/checklist.json`.
+    pub(super) fn save_checklist(&mut self) -> Result<()> {
+        let tab = self.tab();
+        let Some(checklist) = tab.ai.checklist.as_ref() else {
+            return Ok(());
+        };
+        crate::ai::store::save(&tab.er_dir(), checklist)
+    }
+
+    pub(super) fn checklist_mut(&mut self) -> &mut ErChecklist {
+        let tab = self.tab_mut();
+        let diff_hash = tab.branch_diff_hash.clone();
+        tab.ai
+            .checklist
+            .get_or_insert_with(|| ErChecklist::new(&diff_hash))
+    }
+
+    fn open_checklist_editor(&mut self, edit: ChecklistEdit, text: &str) {
+        let tab = self.tab_mut();
+        tab.stash_comment_draft();
+        tab.comment_textarea = TextArea::new(text.lines().map(str::to_string).collect());
+        tab.comment_textarea
+            .move_cursor(tui_textarea::CursorMove::Bottom);
+        tab.comment_textarea
+            .move_cursor(tui_textarea::CursorMove::End);
+        tab.checklist_edit = Some(edit);
+        self.input_mode = InputMode::Comment;
+    }
+
+    /// Compose a new item below the cursor (or first, on an empty list).
+    pub fn start_checklist_add(&mut self) {
+        let at = match (self.checklist_cursor(), self.tab().review_checklist_count()) {
+            (Some(cursor), n) if n > 0 => cursor + 1,
+            _ => 0,
+        };
+        self.open_checklist_editor(ChecklistEdit::Add { at }, "");
+    }
+
+    /// Edit the text of the item at the cursor.
+    pub fn start_checklist_edit(&mut self) {
+        let Some(index) = self.checklist_cursor() else {
+            return;
+        };
+        let Some(text) = self
+            .tab()
+            .ai
+            .checklist
+            .as_ref()
+            .and_then(|c| c.items.get(index))
+            .map(|i| i.text.clone())
+        else {
+            return;
+        };
+        self.open_checklist_editor(ChecklistEdit::Edit { index }, &text);
+    }
+
+    /// Finish an add or edit from the comment box.
+    pub(super) fn submit_checklist_text(&mut self, edit: ChecklistEdit, text: &str) -> Result<()> {
+        let tab = self.tab_mut();
+        tab.checklist_edit = None;
+        tab.comment_textarea = TextArea::default();
+        self.input_mode = InputMode::Normal;
+        let cursor = match edit {
+            ChecklistEdit::Add { at } => self.checklist_mut().insert_item(at, text),
+            ChecklistEdit::Edit { index } => {
+                self.checklist_mut().set_item_text(index, text);
+                index
+            }
+        };
+        self.save_checklist()?;
+        let tab = self.tab_mut();
+        tab.review_focus = ReviewFocus::Checklist;
+        tab.review_cursor = cursor;
+        let label = match edit {
+            ChecklistEdit::Add { .. } => "Checklist item added",
+            ChecklistEdit::Edit { .. } => "Checklist item updated",
+        };
+        self.notify(label);
+        Ok(())
+    }
+
+    /// Ask before deleting the item at the cursor.
+    pub fn request_delete_checklist_item(&mut self) {
+        let Some(index) = self.checklist_cursor() else {
+            return;
+        };
+        let Some(text) = self
+            .tab()
+            .ai
+            .checklist
+            .as_ref()
+            .and_then(|c| c.items.get(index))
+            .map(|i| i.text.clone())
+        else {
+            return;
+        };
+        self.ask_confirm(
+            ConfirmAction::DeleteChecklistItem { index }
+                .prompt()
+                .detail(text),
+        );
+    }
+
+    pub fn delete_checklist_item(&mut self, index: usize) -> Result<()> {
+        let Some(removed) = self
+            .tab_mut()
+            .ai
+            .checklist
+            .as_mut()
+            .and_then(|c| c.remove_item(index))
+        else {
+            return Ok(());
+        };
+        self.save_checklist()?;
+        let count = self.tab().review_checklist_count();
+        let tab = self.tab_mut();
+        tab.review_cursor = tab.review_cursor.min(count.saturating_sub(1));
+        self.notify(&format!("Removed: {}", removed.text));
+        Ok(())
+    }
+
+    /// Move the item at the cursor one place down (or up); the cursor follows.
+    pub fn move_checklist_item(&mut self, down: bool) -> Result<()> {
+        let Some(index) = self.checklist_cursor() else {
+            return Ok(());
+        };
+        let Some(checklist) = self.tab_mut().ai.checklist.as_mut() else {
+            return Ok(());
+        };
+        let moved = checklist.move_item(index, down);
+        if moved == index {
+            return Ok(());
+        }
+        self.tab_mut().review_cursor = moved;
+        self.save_checklist()
+    }
+
+    /// Tie the selected diff file to the item at the cursor, or untie it.
+    pub fn toggle_checklist_file(&mut self) -> Result<()> {
+        let Some(index) = self.checklist_cursor() else {
+            return Ok(());
+        };
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            self.notify("No file selected");
+            return Ok(());
+        };
+        let linked = self
+            .tab_mut()
+            .ai
+            .checklist
+            .as_mut()
+            .and_then(|c| c.toggle_related_file(index, &path));
+        let Some(linked) = linked else {
+            return Ok(());
+        };
+        self.save_checklist()?;
+        if linked {
+            self.notify(&format!("Linked {}", path));
+        } else {
+            self.notify(&format!("Unlinked {}", path));
+        }
+        Ok(())
+    }
+
+    /// Hub of checklist templates, plus saving the current checklist as one.
+    pub fn open_checklist_hub(&mut self) {
+        let repo_root = self.tab().repo_root.clone();
+        let templates = ai::load_checklist_templates(&repo_root);
+        let has_items = self.tab().review_checklist_count() > 0;
+        let mut items: Vec<HubItem> = templates
+            .iter()
+            .map(|t| HubItem {
+                label: t.name.clone(),
+                hint: String::new(),
+                description: format!("Add {} item(s) to this branch's checklist", t.items.len()),
+                action: HubAction::ApplyChecklistTemplate(t.name.clone()),
+                is_header: false,
+                enabled: !t.items.is_empty(),
+            })
+            .collect();
+        if items.is_empty() {
+            items.push(HubItem {
+                label: "No templates".into(),
+                hint: String::new(),
+                description: format!("Add JSON files to {}/", ai::CHECKLIST_TEMPLATE_DIR),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            });
+        }
+        items.push(HubItem {
+            label: "Save as template".into(),
+            hint: String::new(),
+            description: if has_items {
+                format!(
+                    "Write this checklist to {}/default.json",
+                    ai::CHECKLIST_TEMPLATE_DIR
+                )
+            } else {
+                "checklist is empty".into()
+            },
+            action: HubAction::SaveChecklistTemplate,
+            is_header: false,
+            enabled: has_items,
+        });
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Checklist,
+            title: None,
+            items,
+            selected: 0,
+        });
+    }
+
+    pub fn apply_checklist_template(&mut self, name: &str) -> Result<()> {
+        let repo_root = self.tab().repo_root.clone();
+        let Some(template) = ai::load_checklist_templates(&repo_root)
+            .into_iter()
+            .find(|t| t.name == name)
+        else {
+            self.notify(&format!("Template not found: {}", name));
+            return Ok(());
+        };
+        let added = self.checklist_mut().apply_template(&template);
+        self.save_checklist()?;
+        self.tab_mut().review_focus = ReviewFocus::Checklist;
+        self.notify(&format!("{}: {} item(s) added", name, added));
+        Ok(())
+    }
+
+    /// Write the current checklist to `.er-checklists/default.json` in the repo.
+    pub fn save_checklist_template(&mut self) -> Result<()> {
+        let tab = self.tab();
+        let Some(checklist) = tab.ai.checklist.as_ref() else {
+            return Ok(());
+        };
+        let template = checklist.to_template("default");
+        let dir = std::path::Path::new(&tab.repo_root).join(ai::CHECKLIST_TEMPLATE_DIR);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("default.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&template)?)?;
+        self.notify(&format!(
+            "Saved {} item(s) to {}/default.json",
+            template.items.len(),
+            ai::CHECKLIST_TEMPLATE_DIR
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::ErRoot;
+
+    #[test]
+    fn add_edit_move_and_delete_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().er_root = ErRoot::RepoLocal(root.clone());
+        app.tab_mut().repo_root = root;
+        app.tab_mut().review_focus = ReviewFocus::Checklist;
+
+        for text in ["first", "second"] {
+            app.start_checklist_add();
+            assert_eq!(app.input_mode, InputMode::Comment);
+            app.tab_mut().comment_textarea = TextArea::new(vec![text.to_string()]);
+            app.submit_comment().unwrap();
+        }
+        assert_eq!(app.tab().review_cursor, 1);
+
+        app.start_checklist_edit();
+        assert_eq!(app.tab().comment_text(), "second");
+        app.tab_mut().comment_textarea = TextArea::new(vec!["second, edited".to_string()]);
+        app.submit_comment().unwrap();
+
+        app.move_checklist_item(false).unwrap();
+        assert_eq!(app.tab().review_cursor, 0);
+        app.delete_checklist_item(1).unwrap();
+
+        app.tab_mut().reload_ai_state();
+        let items = &app.tab().ai.checklist.as_ref().unwrap().items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "second, edited");
+    }
+
+    #[test]
+    fn cancel_drops_checklist_edit_without_a_draft() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().review_focus = ReviewFocus::Checklist;
+        app.start_checklist_add();
+        app.tab_mut().comment_textarea = TextArea::new(vec!["half typed".to_string()]);
+        app.cancel_comment();
+        assert!(app.tab().checklist_edit.is_none());
+        assert!(app.tab().comment_drafts.is_empty());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+}
@@ -0,0 +1,176 @@
+//! Checklist gates (`[checklist_gates]`): path pattern → checklist items
+//! that must be ticked before a matching file can be marked reviewed. Every
+//! gate a changed file falls under gets an item in the review checklist,
+//! merged in after each reload like the migration rollback items; Space on a
+//! gated file refuses until its items are ticked and says which ones.
+//!
+//! An item the checklist already has under the same text (from a template,
+//! say) counts as the gate's, so it isn't asked for twice.
+
+use std::collections::BTreeMap;
+
+use super::{App, TabState};
+use crate::ai::{ChecklistItem, ErChecklist};
+use crate::config::{self, review_hints_for, ErConfig};
+
+/// The category the gate items are listed under.
+pub const GATE_CATEGORY: &str = "gate";
+
+/// Id prefix of the gate items, followed by the item's text.
+const GATE_ITEM: &str = "gate:";
+
+/// The global gates with the repo's `.er-config.toml` ones added.
+pub(super) fn resolve_gates(repo_root: &str, global: &ErConfig) -> BTreeMap<String, Vec<String>> {
+    let mut gates = global.checklist_gates.clone();
+    for (pattern, items) in config::load_repo_checklist_gates(repo_root) {
+        let merged = gates.entry(pattern).or_default();
+        for item in items {
+            if !merged.contains(&item) {
+                merged.push(item);
+            }
+        }
+    }
+    gates
+}
+
+fn find_item<'a>(checklist: &'a ErChecklist, text: &str) -> Option<&'a ChecklistItem> {
+    checklist
+        .items
+        .iter()
+        .find(|item| item.text.trim().eq_ignore_ascii_case(text.trim()))
+}
+
+impl TabState {
+    /// Gate item texts that apply to `path`, in pattern order.
+    pub fn checklist_gates_for(&self, path: &str) -> Vec<&str> {
+        let mut items: Vec<&str> = Vec::new();
+        for item in review_hints_for(&self.checklist_gates, path) {
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    /// Gate items for `path` not ticked yet.
+    pub fn unmet_checklist_gates(&self, path: &str) -> Vec<String> {
+        self.checklist_gates_for(path)
+            .into_iter()
+            .filter(|text| {
+                !self
+                    .ai
+                    .checklist
+                    .as_ref()
+                    .and_then(|c| find_item(c, text))
+                    .is_some_and(|item| item.checked)
+            })
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Put an item for every gate the diff's files fall under into a freshly
+    /// loaded checklist, and drop unticked ones no file needs any more.
+    pub(super) fn merge_checklist_gates(&mut self) {
+        let mut wanted: Vec<(String, Vec<String>)> = Vec::new();
+        for file in &self.files {
+            for text in self.checklist_gates_for(&file.path) {
+                match wanted.iter_mut().find(|(t, _)| t == text) {
+                    Some((_, paths)) => paths.push(file.path.clone()),
+                    None => wanted.push((text.to_string(), vec![file.path.clone()])),
+                }
+            }
+        }
+        if wanted.is_empty() && self.ai.checklist.is_none() {
+            return;
+        }
+        let diff_hash = self.branch_diff_hash.clone();
+        let checklist = self
+            .ai
+            .checklist
+            .get_or_insert_with(|| ErChecklist::new(&diff_hash));
+        checklist
+            .items
+            .retain(|item| match item.id.strip_prefix(GATE_ITEM) {
+                Some(text) => item.checked || wanted.iter().any(|(t, _)| t == text),
+                None => true,
+            });
+        for (text, paths) in wanted {
+            if find_item(checklist, &text).is_some() {
+                continue;
+            }
+            checklist.items.push(ChecklistItem {
+                id: format!("{}{}", GATE_ITEM, text),
+                text,
+                category: GATE_CATEGORY.to_string(),
+                checked: false,
+                related_findings: Vec::new(),
+                related_files: paths,
+            });
+        }
+        if checklist.items.is_empty() {
+            self.ai.checklist = None;
+        }
+    }
+}
+
+impl App {
+    /// Refuse to mark `path` reviewed while its gate items aren't ticked,
+    /// saying which. True when it was refused.
+    pub(super) fn held_back_by_checklist(&mut self, path: &str) -> bool {
+        let unmet = self.tab().unmet_checklist_gates(path);
+        if unmet.is_empty() {
+            return false;
+        }
+        self.notify(&format!(
+            "Can't mark {} reviewed — tick \u{201c}{}\u{201d} in the checklist first",
+            path,
+            unmet.join("\u{201d}, \u{201c}")
+        ));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git;
+
+    const DIFF: &str = "diff --git a/payments/ledger.rs b/payments/ledger.rs\n\
+                        --- a/payments/ledger.rs\n+++ b/payments/ledger.rs\n\
+                        @@ -1 +1,2 @@\n a\n+b\n\
+                        diff --git a/src/main.rs b/src/main.rs\n\
+                        --- a/src/main.rs\n+++ b/src/main.rs\n\
+                        @@ -1 +1,2 @@\n a\n+b\n";
+
+    #[test]
+    fn a_gated_file_is_only_marked_reviewed_once_its_items_are_ticked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(git::parse_diff(DIFF));
+        let tab = app.tab_mut();
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        tab.checklist_gates
+            .insert("payments/**".into(), vec!["Reconciliation tested".into()]);
+        tab.merge_checklist_gates();
+        let items = &tab.ai.checklist.as_ref().unwrap().items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].related_files, ["payments/ledger.rs"]);
+
+        app.toggle_reviewed().unwrap();
+        assert!(app.tab().reviewed.is_empty());
+        assert!(app
+            .watch_message
+            .as_deref()
+            .is_some_and(|m| m.contains("Reconciliation tested")));
+
+        app.tab_mut().ai.toggle_checklist_item(0);
+        app.toggle_reviewed().unwrap();
+        assert!(app.tab().reviewed.contains_key("payments/ledger.rs"));
+
+        // Ungated files were never held back; merging again keeps the tick
+        app.tab_mut().selected_file = 1;
+        app.toggle_reviewed().unwrap();
+        assert_eq!(app.tab().reviewed.len(), 2);
+        app.tab_mut().merge_checklist_gates();
+        assert!(app.tab().ai.checklist.as_ref().unwrap().items[0].checked);
+    }
+}
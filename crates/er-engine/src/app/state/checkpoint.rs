@@ -0,0 +1,302 @@
+//! Review checkpoints: "what changed since I last looked".
+//!
+//! A checkpoint pins the reviewed side of the branch diff as a commit
+//! (`git stash create` for a live working tree, the head ref otherwise) under
+//! `refs/er/checkpoints/…` and records it in `checkpoint.json`. The checkpoint
+//! view then swaps the Branch diff for `git diff <checkpoint> <head>` limited
+//! to the branch's files, so after the author pushes fixes only the fixes are
+//! shown. Reviewed marks, per-file hashes and AI staleness keep following the
+//! full branch diff while the view is on.
+//!
+//! The same view compares local `HEAD` with the PR head on GitHub
+//! (`pr_head.rs`), with the PR head in place of the checkpoint, and one file
+//! with any ref (`quick_diff.rs`).
+
+use super::{App, DiffMode, TabState};
+use crate::git;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+/// Above this many paths the interdiff is not path-limited (argv size).
+const MAX_PATHSPEC: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCheckpoint {
+    pub version: u32,
+    pub oid: String,
+    pub created_at: String,
+    /// Branch / PR label the checkpoint was taken on.
+    #[serde(default)]
+    pub label: String,
+    /// Files in the branch diff at checkpoint time.
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// Active checkpoint view, resolved when toggled on.
+#[derive(Debug, Clone)]
+pub struct CheckpointView {
+    pub oid: String,
+    pub created_at: String,
+    /// `oid` is the PR head on GitHub, not a checkpoint
+    pub pr_head: bool,
+    /// `oid` is this ref, typed for a quick diff of one file
+    pub quick_ref: Option<String>,
+    root: String,
+    head: Option<String>,
+    paths: Vec<String>,
+}
+
+impl CheckpointView {
+    /// Everything local `HEAD` has that `pr_head_oid` doesn't, and back.
+    pub(super) fn against_pr_head(root: String, pr_head_oid: String) -> Self {
+        CheckpointView {
+            oid: pr_head_oid,
+            created_at: String::new(),
+            pr_head: true,
+            quick_ref: None,
+            root,
+            head: Some("HEAD".to_string()),
+            paths: Vec::new(),
+        }
+    }
+
+    /// `path` at `oid` (resolved from `ref_name`) against the head.
+    pub(super) fn against_ref(
+        root: String,
+        head: Option<String>,
+        oid: String,
+        ref_name: String,
+        path: String,
+    ) -> Self {
+        CheckpointView {
+            oid,
+            created_at: String::new(),
+            pr_head: false,
+            quick_ref: Some(ref_name),
+            root,
+            head,
+            paths: vec![path],
+        }
+    }
+
+    /// Raw interdiff for this view (what `refresh_checkpoint_diff` parses).
+    pub(super) fn raw_diff(&self) -> Result<String> {
+        git::git_diff_since(&self.root, &self.oid, self.head.as_deref(), &self.paths)
+    }
+
+    /// `git diff` arguments reproducing the interdiff, and the root to run in.
+    pub(super) fn diff_args(&self) -> (&str, Vec<String>) {
+        let mut args = vec![
+            "diff".to_string(),
+            format!("--unified={}", git::DEFAULT_CONTEXT_LINES),
+            self.oid.clone(),
+        ];
+        args.extend(self.head.clone());
+        if !self.paths.is_empty() {
+            args.push("--".to_string());
+            args.extend(self.paths.iter().cloned());
+        }
+        (&self.root, args)
+    }
+}
+
+impl TabState {
+    /// Git root and head ref the branch diff's new side comes from. `None`
+    /// head means the working tree.
+    pub(super) fn checkpoint_target(&self) -> Result<(String, Option<String>)> {
+        if self.is_remote() {
+            anyhow::bail!("Checkpoints need a local clone (remote PR tab)");
+        }
+        if let Some(ref branch) = self.local_branch_view {
+            if let Some(ref root) = self.local_branch_checkout_root {
+                return Ok((root.clone(), None));
+            }
+            let head = self.pr_head_ref.clone().unwrap_or_else(|| branch.clone());
+            return Ok((self.repo_root.clone(), Some(head)));
+        }
+        let head = self
+            .pr_head_ref
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        Ok((self.repo_root.clone(), Some(head)))
+    }
+
+    fn checkpoint_label(&self) -> String {
+        match (self.pr_number, &self.local_branch_view) {
+            (Some(n), _) => format!("pr-{}", n),
+            (None, Some(branch)) => branch.clone(),
+            (None, None) => self.current_branch.clone(),
+        }
+    }
+
+    fn checkpoint_path(&self) -> String {
+        format!("{}/{}", self.er_dir(), CHECKPOINT_FILE)
+    }
+
+    /// Branch-diff paths (from the last full refresh, not the checkpoint view).
+    fn branch_diff_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.current_per_file_hashes.keys().cloned().collect();
+        if paths.is_empty() {
+            paths = self.files.iter().map(|f| f.path.clone()).collect();
+        }
+        paths.sort();
+        paths
+    }
+
+    pub fn load_review_checkpoint(&self) -> Option<ReviewCheckpoint> {
+        let content = std::fs::read_to_string(self.checkpoint_path()).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Snapshot the current review state as the new checkpoint.
+    pub fn set_review_checkpoint(&mut self) -> Result<ReviewCheckpoint> {
+        let (root, head) = self.checkpoint_target()?;
+        let label = self.checkpoint_label();
+        let pin_ref = format!(
+            "refs/er/checkpoints/{}",
+            crate::storage::slug_branch(&label)
+        );
+        let oid = git::git_snapshot_commit(&root, head.as_deref(), &pin_ref)?;
+        let checkpoint = ReviewCheckpoint {
+            version: 1,
+            oid,
+            created_at: crate::sync::chrono_now(),
+            label,
+            files: self.branch_diff_paths(),
+        };
+        std::fs::create_dir_all(self.er_dir()).context("Failed to create .er directory")?;
+        let json = serde_json::to_string_pretty(&checkpoint)?;
+        let path = self.checkpoint_path();
+        let tmp = format!("{}.tmp", path);
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path).context("Failed to write checkpoint.json")?;
+        // A checkpoint view on the old snapshot would now be misleading.
+        if self.checkpoint_view.is_some() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+        }
+        Ok(checkpoint)
+    }
+
+    /// Toggle the "since checkpoint" view. Returns whether it is now on.
+    pub fn toggle_checkpoint_view(&mut self) -> Result<bool> {
+        if self.checkpoint_view.take().is_some() {
+            self.refresh_diff()?;
+            return Ok(false);
+        }
+        let checkpoint = self
+            .load_review_checkpoint()
+            .context("No review checkpoint yet — set one from the git hub")?;
+        let (root, head) = self.checkpoint_target()?;
+        let mut paths = self.branch_diff_paths();
+        paths.extend(checkpoint.files.iter().cloned());
+        paths.sort();
+        paths.dedup();
+        if paths.len() > MAX_PATHSPEC {
+            paths.clear();
+        }
+        if self.mode != DiffMode::Branch {
+            self.set_mode(DiffMode::Branch);
+        }
+        self.checkpoint_view = Some(CheckpointView {
+            oid: checkpoint.oid,
+            created_at: checkpoint.created_at,
+            pr_head: false,
+            quick_ref: None,
+            root,
+            head,
+            paths,
+        });
+        if let Err(e) = self.refresh_diff_quick() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Whether the Branch diff currently shows changes since the checkpoint
+    /// (or against the PR head).
+    pub fn in_checkpoint_view(&self) -> bool {
+        self.mode == DiffMode::Branch && self.checkpoint_view.is_some()
+    }
+
+    /// Whether the Branch diff currently shows local `HEAD` vs the PR head.
+    pub fn in_pr_head_view(&self) -> bool {
+        self.in_checkpoint_view() && self.checkpoint_view.as_ref().is_some_and(|v| v.pr_head)
+    }
+
+    /// Replace the file list with the interdiff. Leaves branch hashes, reviewed
+    /// marks and AI state alone — those stay keyed to the full branch diff.
+    pub(super) fn refresh_checkpoint_diff(&mut self, view: &CheckpointView) -> Result<()> {
+        let raw = view.raw_diff()?;
+        let prev_path = self.files.get(self.selected_file).map(|f| f.path.clone());
+
+        self.files = git::parse_diff(&raw);
+        self.file_headers.clear();
+        self.raw_diff = None;
+        self.lazy_mode = false;
+        git::compact_files(&mut self.files, &self.compaction_config);
+        self.diff_hash = format!("{:016x}", crate::ai::compute_diff_hash_fast(&raw));
+
+        self.selected_file = prev_path
+            .and_then(|p| self.files.iter().position(|f| f.path == p))
+            .unwrap_or(0);
+        self.clamp_hunk();
+        self.ensure_file_parsed();
+        self.rebuild_hunk_offsets();
+        self.mtime_cache.clear();
+        self.update_mem_budget();
+        Ok(())
+    }
+}
+
+impl App {
+    pub fn set_review_checkpoint(&mut self) {
+        match self.tab_mut().set_review_checkpoint() {
+            Ok(cp) => {
+                let short: String = cp.oid.chars().take(7).collect();
+                self.notify(&format!(
+                    "Checkpoint set at {} ({} files) — I shows changes since",
+                    short,
+                    cp.files.len()
+                ));
+            }
+            Err(e) => self.notify(&format!("Checkpoint failed: {}", e)),
+        }
+    }
+
+    pub fn toggle_checkpoint_view(&mut self) {
+        if self.tab().in_pr_head_view() {
+            self.toggle_pr_head_view();
+            return;
+        }
+        match self.tab_mut().toggle_checkpoint_view() {
+            Ok(true) => {
+                let tab = self.tab();
+                let since = tab
+                    .checkpoint_view
+                    .as_ref()
+                    .map(|v| {
+                        v.created_at
+                            .replace('T', " ")
+                            .trim_end_matches('Z')
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                let count = tab.files.len();
+                self.notify(&format!(
+                    "Since checkpoint ({}): {} file{} changed",
+                    since,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+            Ok(false) => self.notify("Checkpoint view off — full branch diff"),
+            Err(e) => self.notify(&format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Settings → *Clean up review data*: the in-app `er clean`. The plan comes
+//! from [`crate::artifact_gc`] for the active tab's repo, with every open
+//! tab's artifact dir kept, and is shown in a confirmation before anything
+//! is removed.
+
+use std::path::PathBuf;
+
+use super::{App, ConfirmAction};
+use crate::artifact_gc::{self, CleanTarget};
+
+impl App {
+    /// Plan the cleanup and ask before running it.
+    pub fn start_clean_review_data(&mut self) {
+        if self.tab().is_remote() {
+            self.notify_warn("Cleaning up review data needs a local clone");
+            return;
+        }
+        let keep: Vec<PathBuf> = self
+            .tabs
+            .iter()
+            .map(|t| PathBuf::from(t.er_dir()))
+            .collect();
+        let targets = artifact_gc::plan(&self.tab().repo_root, &keep);
+        if targets.is_empty() {
+            self.notify("No stale review data");
+            return;
+        }
+        self.confirm(ConfirmAction::CleanReviewData { targets });
+    }
+
+    pub fn clean_review_data(&mut self, targets: &[CleanTarget]) {
+        let report = artifact_gc::execute(targets);
+        if let Some((path, e)) = report.failed.first() {
+            self.notify_warn(&format!(
+                "Couldn't remove {} ({} of {} failed): {}",
+                path.display(),
+                report.failed.len(),
+                targets.len(),
+                e
+            ));
+            return;
+        }
+        self.notify(&format!(
+            "Removed {} stale item{} — freed {}",
+            report.removed.len(),
+            if report.removed.len() == 1 { "" } else { "s" },
+            crate::git::format_size(report.bytes)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::InputMode;
+
+    #[test]
+    fn the_open_tabs_bucket_survives_and_the_rest_asks_first() {
+        let _guard = crate::storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let storage = tempfile::tempdir().unwrap();
+        std::env::set_var("ER_STORAGE_ROOT", storage.path());
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path().to_string_lossy().to_string();
+        std::process::Command::new("git")
+            .args(["init", "-q", "-b", "main"])
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+        let branches = crate::storage::repo_dir(&crate::storage::slug_repo(&root)).join("branches");
+        // A branch that was never created in this clone, open in the tab
+        let open = branches.join("spike/view-buckets/branch");
+        std::fs::create_dir_all(&open).unwrap();
+        std::fs::write(open.join("review.json"), "{}").unwrap();
+        let gone = branches.join("feat-gone/view-buckets/branch");
+        std::fs::create_dir_all(&gone).unwrap();
+        std::fs::write(gone.join("review.json"), "{}").unwrap();
+
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().repo_root = root;
+        app.tab_mut().er_root = crate::paths::ErRoot::Managed {
+            agent_dir: open.display().to_string(),
+            session_dir: open.display().to_string(),
+        };
+        app.start_clean_review_data();
+        let InputMode::Confirm(ConfirmAction::CleanReviewData { targets }) = app.input_mode.clone()
+        else {
+            panic!("expected a confirmation, got {:?}", app.input_mode);
+        };
+        assert_eq!(targets.len(), 1);
+        assert!(targets[0].path.ends_with("feat-gone"));
+
+        app.clean_review_data(&targets);
+        std::env::remove_var("ER_STORAGE_ROOT");
+        assert!(!branches.join("feat-gone").exists());
+        assert!(open.join("review.json").exists());
+    }
+}
@@ -0,0 +1,268 @@
+//! The code age layer (Alt+g): the gutter of each added line is tinted by
+//! how old the code around it is, so an edit inside code nobody touched for
+//! five years stands out from one in a file written last week. The ages
+//! come from `git blame` of the old side: an added line takes the age of
+//! the nearest context or deleted line in its hunk, and a hunk with none of
+//! those (a new file) is new code. The top bar shows the legend while the
+//! layer is on.
+//!
+//! Blame runs on a worker thread for the selected file in the Branch,
+//! Unstaged and Staged views, and is kept per old-side revision and path
+//! for the session; the tints are worked out from it again when the diff
+//! reloads.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{App, DiffMode, TabState};
+use crate::git::{self, DiffHunk, FileStatus, LineType};
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// Old-side revision and path a blame is for.
+pub type BlameKey = (String, String);
+/// Author time (Unix seconds) by old line number.
+pub type BlameTimes = HashMap<usize, i64>;
+
+/// How old the code around an added line is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeAge {
+    /// Nothing around it: a new file
+    New,
+    Month,
+    Year,
+    FiveYears,
+    Older,
+}
+
+impl CodeAge {
+    /// Youngest first, as the legend lists them.
+    pub const ALL: [CodeAge; 5] = [
+        CodeAge::New,
+        CodeAge::Month,
+        CodeAge::Year,
+        CodeAge::FiveYears,
+        CodeAge::Older,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CodeAge::New => "new",
+            CodeAge::Month => "<1mo",
+            CodeAge::Year => "<1y",
+            CodeAge::FiveYears => "<5y",
+            CodeAge::Older => "5y+",
+        }
+    }
+
+    fn of(age_secs: i64) -> Self {
+        match age_secs / DAY {
+            d if d < 30 => CodeAge::Month,
+            d if d < 365 => CodeAge::Year,
+            d if d < 5 * 365 => CodeAge::FiveYears,
+            _ => CodeAge::Older,
+        }
+    }
+}
+
+/// The age of each added line of `hunks` by new line number, from `times`
+/// (old line → author time) as of `now`. Lines whose hunk has old lines
+/// but no blame for them are left out.
+pub fn added_line_ages(
+    hunks: &[DiffHunk],
+    times: &BlameTimes,
+    now: i64,
+) -> HashMap<usize, CodeAge> {
+    let mut ages = HashMap::new();
+    for hunk in hunks {
+        let has_old = hunk.lines.iter().any(|l| {
+            matches!(l.line_type, LineType::Context | LineType::Delete) && l.old_num.is_some()
+        });
+        let time_at = |i: usize| times.get(&hunk.lines[i].old_num?).copied();
+        // Nearest blamed old line above each line, then below
+        let mut above = vec![None; hunk.lines.len()];
+        let mut last = None;
+        for (i, slot) in above.iter_mut().enumerate() {
+            if let Some(t) = time_at(i) {
+                last = Some((i, t));
+            }
+            *slot = last;
+        }
+        let mut next = None;
+        for i in (0..hunk.lines.len()).rev() {
+            if let Some(t) = time_at(i) {
+                next = Some((i, t));
+            }
+            let line = &hunk.lines[i];
+            let (LineType::Add, Some(new_num)) = (line.line_type, line.new_num) else {
+                continue;
+            };
+            let nearest = match (above[i], next) {
+                (Some((a, ta)), Some((b, tb))) => Some(if i - a <= b - i { ta } else { tb }),
+                (one, other) => one.or(other).map(|(_, t)| t),
+            };
+            match nearest {
+                Some(t) => {
+                    ages.insert(new_num, CodeAge::of(now - t));
+                }
+                None if !has_old => {
+                    ages.insert(new_num, CodeAge::New);
+                }
+                None => {}
+            }
+        }
+    }
+    ages
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+impl TabState {
+    /// Whether the layer is on and the view is one it tints.
+    fn code_age_shown(&self) -> bool {
+        self.layers.show_code_age
+            && !self.is_remote()
+            && !self.is_repoless()
+            && matches!(
+                self.mode,
+                DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged
+            )
+    }
+
+    /// The age tint for an added line, while the layer is on.
+    pub fn code_age(&self, path: &str, new_num: usize) -> Option<CodeAge> {
+        if !self.code_age_shown() {
+            return None;
+        }
+        self.code_ages.get(path)?.get(&new_num).copied()
+    }
+
+    pub fn toggle_code_age(&mut self) {
+        self.layers.show_code_age = !self.layers.show_code_age;
+    }
+
+    /// The old side's commit, for blame and its cache key.
+    fn code_age_rev(&self) -> Option<String> {
+        let rev = self.old_side_rev()?;
+        // The Unstaged view's old side is the index; HEAD is close enough
+        if rev.is_empty() || rev == "HEAD" {
+            return crate::github::rev_parse_oid(self.commit_log_root(), "HEAD");
+        }
+        Some(rev)
+    }
+}
+
+impl App {
+    /// Alt+g.
+    pub fn toggle_code_age(&mut self) {
+        self.tab_mut().toggle_code_age();
+        self.notify(if self.tab().layers.show_code_age {
+            "Code age: ON"
+        } else {
+            "Code age: OFF"
+        });
+    }
+
+    /// Blame the selected file's old side for the code age layer, and work
+    /// out its tints once blamed. True when tints changed.
+    pub fn poll_code_age(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.code_age_rx {
+            match rx.try_recv() {
+                Ok((key, times)) => {
+                    tab.code_age_rx = None;
+                    tab.code_age_blame.insert(key, times);
+                }
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.code_age_rx = None;
+                    return false;
+                }
+            }
+        }
+        if !tab.code_age_shown() {
+            return false;
+        }
+        let index = tab.selected_file;
+        let Some(file) = tab.selected_diff_file() else {
+            return false;
+        };
+        if tab.code_ages.contains_key(&file.path) {
+            return false;
+        }
+        let path = file.path.clone();
+        let old_path = match &file.status {
+            FileStatus::Added => None,
+            FileStatus::Renamed(old) | FileStatus::Copied(old) => Some(old.clone()),
+            _ => Some(path.clone()),
+        };
+        let hunks = tab.hunks_for_blame(index);
+        let Some(old_path) = old_path else {
+            let ages = added_line_ages(&hunks, &HashMap::new(), now_secs());
+            tab.code_ages.insert(path, ages);
+            return true;
+        };
+        let Some(rev) = tab.code_age_rev() else {
+            tab.code_ages.insert(path, HashMap::new());
+            return false;
+        };
+        let key = (rev, old_path);
+        if let Some(times) = tab.code_age_blame.get(&key) {
+            let ages = added_line_ages(&hunks, times, now_secs());
+            tab.code_ages.insert(path, ages);
+            return true;
+        }
+        let root = tab.commit_log_root().to_string();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let times = git::blame_line_times(&root, &key.0, &key.1).unwrap_or_else(|e| {
+                crate::debug_log::warn("git", format!("blame {}: {:#}", key.1, e));
+                HashMap::new()
+            });
+            let _ = tx.send((key, times));
+        });
+        tab.code_age_rx = Some(rx);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn added_lines_take_the_age_of_the_nearest_old_line() {
+        let files = git::parse_diff(
+            "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,4 +1,6 @@\n\
+             \x20old\n+added next to old\n-gone\n+replaces recent\n\x20recent\n+\n\x20\n\
+             diff --git a/n.rs b/n.rs\nnew file mode 100644\n--- /dev/null\n+++ b/n.rs\n\
+             @@ -0,0 +1 @@\n+fresh\n",
+        );
+        let now = 10_000 * DAY;
+        let times = HashMap::from([(1, now - 1000 * DAY), (2, now - 100 * DAY), (3, now - DAY)]);
+        let ages = added_line_ages(&files[0].hunks, &times, now);
+        assert_eq!(ages[&2], CodeAge::FiveYears);
+        assert_eq!(ages[&3], CodeAge::Year);
+        // Line 4 of the old side has no blame, so line 5 looks up to "recent"
+        assert_eq!(ages[&5], CodeAge::Month);
+        assert_eq!(
+            added_line_ages(&files[1].hunks, &HashMap::new(), now),
+            HashMap::from([(1, CodeAge::New)])
+        );
+        // Blame failed: no tint rather than a wrong one
+        assert!(added_line_ages(&files[0].hunks, &HashMap::new(), now).is_empty());
+
+        let mut tab = TabState::new_for_test(files);
+        tab.code_ages.insert("a.rs".into(), ages);
+        assert_eq!(tab.code_age("a.rs", 2), None);
+        tab.toggle_code_age();
+        assert_eq!(tab.code_age("a.rs", 2), Some(CodeAge::FiveYears));
+        assert_eq!(CodeAge::of(6 * 365 * DAY).label(), "5y+");
+    }
+}
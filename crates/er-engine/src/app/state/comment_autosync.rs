@@ -0,0 +1,184 @@
+//! Pulling PR comments in the background (`[github] comment_sync_minutes`).
+//!
+//! Every interval the active tab's PR comments are fetched off the main
+//! thread with the same snapshot → fetch → apply cycle the desktop uses (see
+//! [`super::github_sync`]). Comments that weren't there before are announced
+//! by author — "2 new comments from alice" — and their files get a `★N`
+//! badge in the file tree until the file is next selected.
+
+use std::collections::HashSet;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::{Duration, Instant};
+
+use super::App;
+use crate::sync::{fetch_comment_sync_data, local_pr_target, CommentSyncResult};
+
+/// `2 new comments from alice, bob` — authors in order of first appearance.
+pub fn new_comments_note(authors: &[String]) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for author in authors {
+        if !names.contains(&author.as_str()) {
+            names.push(author);
+        }
+    }
+    format!(
+        "{} new comment{} from {}",
+        authors.len(),
+        if authors.len() == 1 { "" } else { "s" },
+        names.join(", ")
+    )
+}
+
+impl App {
+    /// Start a background pull once the interval has passed, and apply one
+    /// that has finished. True when something visible changed.
+    pub fn poll_comment_autosync(&mut self) -> bool {
+        if let Some(rx) = &self.tab().comment_sync_rx {
+            let received = rx.try_recv();
+            if matches!(received, Err(TryRecvError::Empty)) {
+                return false;
+            }
+            self.tab_mut().comment_sync_rx = None;
+            return match received {
+                Ok(Ok(result)) => self.apply_comment_autosync(result),
+                Ok(Err(e)) => {
+                    crate::debug_log::warn("sync", format!("background comment sync: {:#}", e));
+                    false
+                }
+                Err(_) => false,
+            };
+        }
+        let minutes = self.config.github.comment_sync_minutes;
+        let interval = Duration::from_secs(u64::from(minutes) * 60);
+        if minutes == 0 || self.tab().last_comment_sync.elapsed() < interval {
+            return false;
+        }
+        self.tab_mut().last_comment_sync = Instant::now();
+        self.spawn_comment_autosync();
+        false
+    }
+
+    fn spawn_comment_autosync(&mut self) {
+        let tab = self.tab();
+        // Remote tabs know their PR; local ones ask gh on the worker thread
+        let known = if tab.is_remote() {
+            let slug = tab.remote_repo.as_deref().and_then(|s| s.split_once('/'));
+            match (slug, tab.pr_number) {
+                (Some((owner, repo)), Some(n)) => Some((owner.to_string(), repo.to_string(), n)),
+                _ => return,
+            }
+        } else {
+            None
+        };
+        let explicit_pr_number = tab.pr_number;
+        let mut ctx = self.snapshot_for_comment_sync(String::new(), String::new(), 0);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = known
+                .map(Ok)
+                .unwrap_or_else(|| local_pr_target(&ctx.repo_root, explicit_pr_number))
+                .and_then(|(owner, repo_name, pr_number)| {
+                    ctx.owner = owner;
+                    ctx.repo_name = repo_name;
+                    ctx.pr_number = pr_number;
+                    fetch_comment_sync_data(&ctx)
+                });
+            let _ = tx.send(result);
+        });
+        self.tab_mut().comment_sync_rx = Some(rx);
+    }
+
+    /// Apply a background pull, announcing comments that weren't here before.
+    fn apply_comment_autosync(&mut self, result: CommentSyncResult) -> bool {
+        let Some(idx) = self.comment_sync_tab(&result) else {
+            return false;
+        };
+        // A tab that never synced has nothing to compare against
+        let known: Option<HashSet<u64>> = self.tabs[idx]
+            .ai
+            .github_comments
+            .as_ref()
+            .map(|gc| gc.comments.iter().filter_map(|c| c.github_id).collect());
+        let fresh: Vec<(String, String)> = match &known {
+            Some(known) => result
+                .gc
+                .comments
+                .iter()
+                .filter(|c| c.source == "github")
+                .filter(|c| c.github_id.is_some_and(|id| !known.contains(&id)))
+                .map(|c| (c.file.clone(), c.author.clone()))
+                .collect(),
+            None => Vec::new(),
+        };
+        let conflicts = result.conflicts;
+        self.apply_comment_sync_to_tab(idx, result);
+
+        let tab = &mut self.tabs[idx];
+        for (file, _) in &fresh {
+            if !file.is_empty() {
+                *tab.new_comment_files.entry(file.clone()).or_default() += 1;
+            }
+        }
+        if idx != self.active_tab || (fresh.is_empty() && conflicts == 0) {
+            return !fresh.is_empty();
+        }
+        let authors: Vec<String> = fresh.into_iter().map(|(_, author)| author).collect();
+        let note = if authors.is_empty() {
+            format!("GitHub sync: {} comment(s) in conflict", conflicts)
+        } else {
+            format!(
+                "{}{}",
+                new_comments_note(&authors),
+                crate::sync::conflict_note(conflicts)
+            )
+        };
+        self.notify(&note);
+        true
+    }
+
+    /// The selected file's comments have been seen; drop its `★` badge.
+    pub(super) fn see_selected_file_comments(&mut self) -> bool {
+        let tab = self.tab_mut();
+        let Some(path) = tab.selected_diff_file().map(|f| f.path.clone()) else {
+            return false;
+        };
+        tab.new_comment_files.remove(&path).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn note_counts_comments_and_names_each_author_once() {
+        assert_eq!(
+            new_comments_note(&["alice".into()]),
+            "1 new comment from alice"
+        );
+        assert_eq!(
+            new_comments_note(&["alice".into(), "bob".into(), "alice".into()]),
+            "3 new comments from alice, bob"
+        );
+    }
+
+    #[test]
+    fn selecting_a_file_clears_its_new_comment_badge() {
+        let mut app = App::new_for_test(vec![modified_file("src/a.rs", 1)]);
+        app.tab_mut().new_comment_files.insert("src/a.rs".into(), 2);
+        app.tab_mut().new_comment_files.insert("src/b.rs".into(), 1);
+        assert!(app.tick());
+        assert!(!app.tab().new_comment_files.contains_key("src/a.rs"));
+        assert!(app.tab().new_comment_files.contains_key("src/b.rs"));
+        assert!(!app.see_selected_file_comments());
+    }
+
+    #[test]
+    fn autosync_stays_off_until_an_interval_is_set() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().last_comment_sync = Instant::now() - Duration::from_secs(3600);
+        assert!(!app.poll_comment_autosync());
+        assert!(app.tab().comment_sync_rx.is_none());
+    }
+}
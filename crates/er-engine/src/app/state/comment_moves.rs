@@ -0,0 +1,351 @@
+//! Comments that followed their code to another file.
+//!
+//! When relocation loses a comment in its own file, the other files of the
+//! diff are searched for its line ([`ai::relocate_across_files`]). A match is
+//! never applied on its own: it waits here until the reviewer is idle, then
+//! a prompt offers "comment followed code to b.rs:123". `y` moves the
+//! comment, and its replies, there; `n` leaves it lost where it was, and the
+//! same move is not offered again. Comments already posted to GitHub stay
+//! put, since GitHub can't move them.
+
+use anyhow::Result;
+
+use super::{App, ConfirmAction, InputMode, LineAnchor, TabState};
+use crate::ai;
+use crate::git::DiffFile;
+
+/// A lost comment and where its code went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentMove {
+    pub comment_id: String,
+    /// Where the comment was, `a.rs:40`
+    pub from: String,
+    pub to: ai::CrossFileMatch,
+    /// Already asked about
+    pub offered: bool,
+}
+
+impl CommentMove {
+    /// The move for a comment lost at `anchor`, if its code is in another
+    /// of `files`.
+    pub(super) fn find(
+        comment_id: &str,
+        anchor: &ai::CommentAnchor,
+        files: &[DiffFile],
+    ) -> Option<Self> {
+        let to = ai::relocate_across_files(anchor, files)?;
+        Some(CommentMove {
+            comment_id: comment_id.to_string(),
+            from: format!("{}:{}", anchor.file, anchor.line_start.unwrap_or(0)),
+            to,
+            offered: false,
+        })
+    }
+
+    /// `b.rs:123`
+    pub fn target(&self) -> String {
+        format!("{}:{}", self.to.file, self.to.line_start)
+    }
+}
+
+impl TabState {
+    /// Replace the pending moves of the comments in `found`, keeping
+    /// whether the same move was already offered.
+    pub(super) fn merge_comment_moves(&mut self, found: Vec<CommentMove>) {
+        for mut m in found {
+            if let Some(old) = self
+                .comment_moves
+                .iter()
+                .position(|o| o.comment_id == m.comment_id)
+            {
+                let old = self.comment_moves.remove(old);
+                m.offered = old.offered && old.to.file == m.to.file;
+            }
+            self.comment_moves.push(m);
+        }
+        // Drop moves for comments found again in their own file, or deleted
+        let moves = std::mem::take(&mut self.comment_moves);
+        self.comment_moves = moves
+            .into_iter()
+            .filter(|m| self.comment_anchor_status(&m.comment_id) == Some("lost"))
+            .collect();
+    }
+
+    fn comment_anchor_status(&self, id: &str) -> Option<&str> {
+        let questions = self.ai.questions.iter().flat_map(|qs| &qs.questions);
+        let notes = self.ai.notes.iter().flat_map(|ns| &ns.notes);
+        if let Some(q) = questions.chain(notes).find(|q| q.id == id) {
+            return Some(&q.anchor_status);
+        }
+        self.ai
+            .github_comments
+            .iter()
+            .flat_map(|gc| &gc.comments)
+            .find(|c| c.id == id)
+            .map(|c| c.anchor_status.as_str())
+    }
+
+    /// Move comment `comment_id` (and its replies) to its pending target,
+    /// re-capturing the anchor there. Returns the target, or `None` when
+    /// there is no pending move for it.
+    pub fn accept_comment_move(&mut self, comment_id: &str) -> Result<Option<String>> {
+        let Some(pos) = self
+            .comment_moves
+            .iter()
+            .position(|m| m.comment_id == comment_id)
+        else {
+            return Ok(None);
+        };
+        let m = self.comment_moves.remove(pos);
+        let to = &m.to;
+        let Some(hunk) = self
+            .files
+            .iter()
+            .find(|f| f.path == to.file)
+            .and_then(|f| f.hunks.get(to.hunk_index))
+        else {
+            return Ok(None);
+        };
+        if !hunk.lines.iter().any(|l| l.new_num == Some(to.line_start)) {
+            return Ok(None);
+        }
+        let anchor = LineAnchor::capture(hunk, Some(to.line_start), None);
+        let diff_hash = self.diff_hash.clone();
+
+        // ReviewQuestion and GitHubReviewComment share these field names
+        macro_rules! move_comment {
+            ($c:expr) => {{
+                let length = $c
+                    .line_end
+                    .zip($c.line_start)
+                    .map(|(end, start)| end.saturating_sub(start));
+                $c.file = to.file.clone();
+                $c.hunk_index = Some(to.hunk_index);
+                $c.line_start = Some(to.line_start);
+                $c.line_end = length.map(|len| to.line_start + len);
+                $c.line_content = anchor.line_content.clone();
+                $c.context_before = anchor.context_before.clone();
+                $c.context_after = anchor.context_after.clone();
+                $c.old_line_start = anchor.old_line_start;
+                $c.hunk_header = anchor.hunk_header.clone();
+                $c.anchor_status = "relocated".to_string();
+                $c.anchor_confidence = Some(to.confidence);
+                $c.relocated_at_hash = diff_hash.clone();
+                $c.stale = false;
+            }};
+        }
+
+        let er_dir = self.er_dir();
+        if let Some(ref mut qs) = self.ai.questions {
+            if qs.questions.iter().any(|q| q.id == comment_id) {
+                for q in &mut qs.questions {
+                    if q.id == comment_id {
+                        move_comment!(q);
+                    } else if q.in_reply_to.as_deref() == Some(comment_id) {
+                        q.file = to.file.clone();
+                    }
+                }
+                ai::store::save(&er_dir, qs)?;
+            }
+        }
+        if let Some(ref mut ns) = self.ai.notes {
+            if ns.notes.iter().any(|n| n.id == comment_id) {
+                for n in &mut ns.notes {
+                    if n.id == comment_id {
+                        move_comment!(n);
+                    } else if n.in_reply_to.as_deref() == Some(comment_id) {
+                        n.file = to.file.clone();
+                    }
+                }
+                ai::store::save(&er_dir, ns)?;
+            }
+        }
+        let comments_dir = self.github_comments_dir();
+        if let Some(ref mut gc) = self.ai.github_comments {
+            if gc.comments.iter().any(|c| c.id == comment_id) {
+                for c in &mut gc.comments {
+                    if c.id == comment_id {
+                        move_comment!(c);
+                    } else if c.in_reply_to.as_deref() == Some(comment_id) {
+                        c.file = to.file.clone();
+                    }
+                }
+                ai::store::save(&comments_dir, gc)?;
+            }
+        }
+        self.ai.rebuild_comment_index();
+        Ok(Some(m.target()))
+    }
+}
+
+impl App {
+    /// Offer the next pending comment move, when nothing else is open.
+    /// Returns whether a prompt was shown.
+    pub fn poll_comment_moves(&mut self) -> bool {
+        if self.input_mode != InputMode::Normal || self.overlay.is_some() {
+            return false;
+        }
+        let tab = self.tab_mut();
+        let Some(m) = tab.comment_moves.iter_mut().find(|m| !m.offered) else {
+            return false;
+        };
+        m.offered = true;
+        let m = m.clone();
+        let preview = self
+            .tab()
+            .ai
+            .find_comment(&m.comment_id)
+            .map(|c| c.text().lines().next().unwrap_or("").to_string())
+            .unwrap_or_default();
+        let mut prompt = ConfirmAction::MoveComment {
+            comment_id: m.comment_id.clone(),
+        }
+        .prompt()
+        .detail(format!("Comment followed code to {}", m.target()))
+        .detail(format!(
+            "From {} ({:.0}% match)",
+            m.from,
+            m.to.confidence * 100.0
+        ));
+        if !preview.is_empty() {
+            prompt = prompt.detail(format!("“{}”", preview));
+        }
+        self.ask_confirm(prompt);
+        true
+    }
+
+    /// `y` on the move prompt.
+    pub fn accept_comment_move(&mut self, comment_id: &str) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        match self.tab_mut().accept_comment_move(comment_id)? {
+            Some(target) => self.notify(&format!("Comment moved to {}", target)),
+            None => self.notify_warn("That code has moved again — the comment stays where it was"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffHunk, DiffLine, FileStatus, LineType};
+
+    fn added(path: &str, lines: &[&str]) -> DiffFile {
+        let lines: Vec<DiffLine> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| DiffLine {
+                line_type: LineType::Add,
+                content: l.to_string(),
+                old_num: None,
+                new_num: Some(i + 1),
+            })
+            .collect();
+        DiffFile {
+            path: path.to_string(),
+            status: FileStatus::Added,
+            hunks: vec![DiffHunk {
+                header: format!("@@ -0,0 +1,{} @@", lines.len()),
+                old_start: 0,
+                old_count: 0,
+                new_start: 1,
+                new_count: lines.len(),
+                lines,
+            }],
+            adds: 0,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        }
+    }
+
+    fn lost_note(id: &str) -> ai::ReviewQuestion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "file": "src/a.rs",
+            "hunk_index": 0,
+            "line_start": 5,
+            "line_content": "    let total = parse_entries(&config)?;",
+            "text": "Why propagate here?",
+            "context_before": ["fn load(config: &Config) -> Result<u32> {"],
+            "context_after": ["    Ok(total)"],
+            "anchor_status": "lost",
+            "stale": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn lost_comment_is_offered_and_moved_with_its_code() {
+        let mut app = App::new_for_test(vec![added(
+            "src/b.rs",
+            &[
+                "use crate::Config;",
+                "",
+                "fn load(config: &Config) -> Result<u32> {",
+                "    let total = parse_entries(&config)?;",
+                "    Ok(total)",
+                "}",
+            ],
+        )]);
+        let note = lost_note("n-1");
+        let anchor = ai::CommentAnchor {
+            file: note.file.clone(),
+            hunk_index: note.hunk_index,
+            line_start: note.line_start,
+            line_content: note.line_content.clone(),
+            context_before: note.context_before.clone(),
+            context_after: note.context_after.clone(),
+            old_line_start: None,
+            hunk_header: String::new(),
+        };
+        app.tab_mut().ai.notes = Some(ai::ErNotes {
+            version: 1,
+            diff_hash: String::new(),
+            notes: vec![note],
+        });
+        let found = CommentMove::find("n-1", &anchor, &app.tab().files).unwrap();
+        assert_eq!(found.target(), "src/b.rs:4");
+        assert_eq!(found.from, "src/a.rs:5");
+        app.tab_mut().merge_comment_moves(vec![found.clone()]);
+
+        assert!(app.poll_comment_moves());
+        let prompt = app.current_confirm().unwrap();
+        assert!(prompt.details[0].contains("src/b.rs:4"));
+        // Asked once: declining leaves it, and a re-found move isn't re-offered
+        app.cancel_confirm();
+        app.tab_mut().merge_comment_moves(vec![found]);
+        assert!(!app.poll_comment_moves());
+
+        let dir = tempfile::tempdir().unwrap();
+        let tab = app.tab_mut();
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        assert_eq!(
+            tab.accept_comment_move("n-1").unwrap().as_deref(),
+            Some("src/b.rs:4")
+        );
+        let note = &tab.ai.notes.as_ref().unwrap().notes[0];
+        assert_eq!((note.file.as_str(), note.line_start), ("src/b.rs", Some(4)));
+        assert_eq!(note.anchor_status, "relocated");
+        assert!(!note.stale);
+        assert!(tab.comment_moves.is_empty());
+    }
+
+    #[test]
+    fn unrelated_or_faint_matches_are_not_offered() {
+        let files = vec![added("src/b.rs", &["fn other() {", "    todo!()", "}"])];
+        let note = lost_note("n-1");
+        let anchor = ai::CommentAnchor {
+            file: note.file,
+            hunk_index: note.hunk_index,
+            line_start: note.line_start,
+            line_content: note.line_content,
+            context_before: note.context_before,
+            context_after: note.context_after,
+            old_line_start: None,
+            hunk_header: String::new(),
+        };
+        assert!(CommentMove::find("n-1", &anchor, &files).is_none());
+    }
+}
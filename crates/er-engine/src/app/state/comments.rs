@@ -1,4 +1,5 @@
 use super::*;
+use crate::clipboard::ClipboardBackend;
 
 impl App {
     // ── Comment System ──
@@ -12,6 +13,7 @@ impl App {
             Some(f) => f.path.clone(),
             None => return,
         };
+        tab.stash_comment_draft();
         tab.comment_textarea = TextArea::default();
         tab.comment_file = file_path;
         tab.comment_hunk = tab.current_hunk;
@@ -23,12 +25,36 @@ impl App {
         tab.comment_reply_to = None;
         tab.comment_finding_ref = None;
         tab.comment_type = comment_type;
+        tab.comment_file_level = false;
+        self.input_mode = InputMode::Comment;
+    }
+
+    /// Enter comment mode for the selected file as a whole — no hunk or line
+    /// anchor. Pushed to GitHub as a file-level review comment.
+    pub fn start_file_comment(&mut self, comment_type: CommentType) {
+        let tab = self.tab_mut();
+        let file_path = match tab.selected_diff_file() {
+            Some(f) => f.path.clone(),
+            None => return,
+        };
+        tab.stash_comment_draft();
+        tab.comment_textarea = TextArea::default();
+        tab.comment_file = file_path;
+        tab.comment_hunk = 0;
+        tab.comment_line_num = None;
+        tab.comment_line_end = None;
+        tab.comment_reply_to = None;
+        tab.comment_finding_ref = None;
+        tab.comment_type = comment_type;
+        tab.comment_edit_id = None;
+        tab.comment_file_level = true;
         self.input_mode = InputMode::Comment;
     }
 
     /// Start typing a general PR comment (not attached to any file/line)
     pub fn start_general_comment(&mut self) {
         let tab = self.tab_mut();
+        tab.stash_comment_draft();
         tab.comment_textarea = TextArea::default();
         tab.comment_file = String::new();
         tab.comment_hunk = 0;
@@ -38,17 +64,46 @@ impl App {
         tab.comment_finding_ref = None;
         tab.comment_type = CommentType::GitHubComment;
         tab.comment_edit_id = None;
+        tab.comment_file_level = false;
+        self.input_mode = InputMode::Comment;
+    }
+
+    /// Start typing a review-wide note or question for the author: no file,
+    /// no anchor, sent in the body of the next review submission
+    pub fn start_review_comment(&mut self, comment_type: CommentType) {
+        let tab = self.tab_mut();
+        tab.stash_comment_draft();
+        tab.comment_textarea = TextArea::default();
+        tab.comment_file = String::new();
+        tab.comment_hunk = 0;
+        tab.comment_line_num = None;
+        tab.comment_line_end = None;
+        tab.comment_reply_to = None;
+        tab.comment_finding_ref = None;
+        tab.comment_type = comment_type;
+        tab.comment_edit_id = None;
+        tab.comment_file_level = true;
         self.input_mode = InputMode::Comment;
     }
 
+    /// Record review-wide notes as sent in a review body so the next
+    /// submission doesn't repeat them.
+    pub fn mark_review_notes_sent(&mut self, ids: &[String]) -> Result<()> {
+        self.tab_mut().mark_review_notes_sent(ids)
+    }
+
     /// Start editing an existing comment — opens comment input pre-filled with its text
     pub fn start_edit_comment(&mut self, comment_id: &str) {
         let tab = self.tab();
         // Find the comment text and type
-        let (text, comment_type) = if comment_id.starts_with("q-") {
+        let (text, comment_type, file_level) = if comment_id.starts_with("q-") {
             if let Some(qs) = &tab.ai.questions {
                 if let Some(q) = qs.questions.iter().find(|q| q.id == comment_id) {
-                    (q.text.clone(), CommentType::Question)
+                    (
+                        q.text.clone(),
+                        CommentType::Question,
+                        q.hunk_index.is_none(),
+                    )
                 } else {
                     return;
                 }
@@ -58,7 +113,7 @@ impl App {
         } else if comment_id.starts_with("n-") {
             if let Some(ns) = &tab.ai.notes {
                 if let Some(n) = ns.notes.iter().find(|n| n.id == comment_id) {
-                    (n.text.clone(), CommentType::Note)
+                    (n.text.clone(), CommentType::Note, n.hunk_index.is_none())
                 } else {
                     return;
                 }
@@ -67,7 +122,11 @@ impl App {
             }
         } else if let Some(gc) = &tab.ai.github_comments {
             if let Some(c) = gc.comments.iter().find(|c| c.id == comment_id) {
-                (c.comment.clone(), CommentType::GitHubComment)
+                (
+                    c.comment.clone(),
+                    CommentType::GitHubComment,
+                    c.hunk_index.is_none() && !c.file.is_empty(),
+                )
             } else {
                 return;
             }
@@ -80,6 +139,7 @@ impl App {
             Some(f) => f.path.clone(),
             None => return,
         };
+        tab.stash_comment_draft();
         tab.comment_textarea = TextArea::new(vec![text]);
         tab.comment_file = file_path;
         tab.comment_hunk = tab.current_hunk;
@@ -87,6 +147,7 @@ impl App {
         tab.comment_reply_to = None;
         tab.comment_type = comment_type;
         tab.comment_edit_id = Some(comment_id.to_string());
+        tab.comment_file_level = file_level;
         self.input_mode = InputMode::Comment;
     }
 
@@ -140,6 +201,7 @@ impl App {
         };
 
         let tab = self.tab_mut();
+        tab.stash_comment_draft();
         tab.comment_textarea = TextArea::default();
         tab.comment_file = file;
         tab.comment_hunk = hunk_index;
@@ -148,6 +210,7 @@ impl App {
         tab.comment_finding_ref = None;
         tab.comment_type = comment_type;
         tab.comment_edit_id = None;
+        tab.comment_file_level = false;
         self.input_mode = InputMode::Comment;
     }
 
@@ -185,6 +248,7 @@ impl App {
         };
 
         let tab = self.tab_mut();
+        tab.stash_comment_draft();
         tab.comment_textarea = TextArea::default();
         tab.comment_file = file;
         tab.comment_hunk = hunk_index;
@@ -193,6 +257,7 @@ impl App {
         tab.comment_finding_ref = Some(finding_id.to_string());
         tab.comment_type = CommentType::GitHubComment;
         tab.comment_edit_id = None;
+        tab.comment_file_level = false;
         self.input_mode = InputMode::Comment;
     }
 
@@ -205,6 +270,10 @@ impl App {
             return Ok(());
         }
 
+        if let Some(edit) = tab.checklist_edit {
+            return self.submit_checklist_text(edit, &text);
+        }
+
         // If editing an existing comment, update it in-place
         if let Some(edit_id) = tab.comment_edit_id.clone() {
             return self.update_comment(edit_id, text);
@@ -247,34 +316,7 @@ impl App {
             }
         }
 
-        let anchor = self.get_line_anchor(hunk_index, comment_line_num);
-
-        // Load or create questions.json
-        let questions_path = format!("{}/questions.json", er_dir);
-        let mut questions: ai::ErQuestions = match std::fs::read_to_string(&questions_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(qs) => qs,
-                Err(_) => {
-                    self.notify("Warning: .er/questions.json is invalid JSON — starting fresh");
-                    ai::ErQuestions {
-                        version: 1,
-                        diff_hash: diff_hash.clone(),
-                        questions: Vec::new(),
-                    }
-                }
-            },
-            Err(_) => ai::ErQuestions {
-                version: 1,
-                diff_hash: diff_hash.clone(),
-                questions: Vec::new(),
-            },
-        };
-
-        // If diff hash changed, update it but preserve existing questions
-        // (the relocation system handles comment drift)
-        if questions.diff_hash != diff_hash {
-            questions.diff_hash = diff_hash;
-        }
+        let (hunk_index, anchor) = self.comment_target(hunk_index, comment_line_num);
 
         let seq = COMMENT_SEQ.fetch_add(1, Ordering::Relaxed);
         let id = format!(
@@ -293,11 +335,11 @@ impl App {
             .comment_author_override
             .take()
             .unwrap_or_else(|| "You".to_string());
-        questions.questions.push(ai::ReviewQuestion {
+        let entry = ai::ReviewQuestion {
             id,
             timestamp: chrono_now(),
             file: file_path,
-            hunk_index: Some(hunk_index),
+            hunk_index,
             line_start: anchor.line_start,
             line_end: Self::normalize_line_end(anchor.line_start, comment_line_end),
             line_content: anchor.line_content,
@@ -310,18 +352,31 @@ impl App {
             hunk_header: anchor.hunk_header,
             anchor_status: "original".to_string(),
             relocated_at_hash: self.tab().diff_hash.clone(),
+            anchor_confidence: None,
             in_reply_to: reply_to,
             author,
             promoted_to: None,
             finding_ref,
-        });
+        };
 
-        // Write atomically
-        std::fs::create_dir_all(&er_dir)?;
-        let json = serde_json::to_string_pretty(&questions)?;
-        let tmp_path = format!("{}.tmp", questions_path);
-        std::fs::write(&tmp_path, json)?;
-        std::fs::rename(&tmp_path, &questions_path)?;
+        // Re-read under the lock so a concurrent writer's entries survive
+        let updated = ai::store::update(&er_dir, &diff_hash, |questions: &mut ai::ErQuestions| {
+            // If diff hash changed, update it but preserve existing questions
+            // (the relocation system handles comment drift)
+            questions.diff_hash = diff_hash.clone();
+            questions.questions.push(entry.clone());
+        })?;
+        if updated == crate::file_lock::Updated::StartedFresh {
+            self.notify_warn("Warning: .er/questions.json was invalid JSON — started fresh");
+        }
+        self.publish_comment_added(
+            "question",
+            &entry.id,
+            &entry.file,
+            entry.line_start,
+            &text,
+            &entry.in_reply_to,
+        );
 
         self.tab_mut().comment_textarea = TextArea::default();
         self.input_mode = InputMode::Normal;
@@ -358,32 +413,7 @@ impl App {
             }
         }
 
-        let anchor = self.get_line_anchor(hunk_index, comment_line_num);
-
-        // Load or create notes.json
-        let notes_path = format!("{}/notes.json", er_dir);
-        let mut notes: ai::ErNotes = match std::fs::read_to_string(&notes_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(ns) => ns,
-                Err(_) => {
-                    self.notify("Warning: .er/notes.json is invalid JSON — starting fresh");
-                    ai::ErNotes {
-                        version: 1,
-                        diff_hash: diff_hash.clone(),
-                        notes: Vec::new(),
-                    }
-                }
-            },
-            Err(_) => ai::ErNotes {
-                version: 1,
-                diff_hash: diff_hash.clone(),
-                notes: Vec::new(),
-            },
-        };
-
-        if notes.diff_hash != diff_hash {
-            notes.diff_hash = diff_hash;
-        }
+        let (hunk_index, anchor) = self.comment_target(hunk_index, comment_line_num);
 
         let seq = COMMENT_SEQ.fetch_add(1, Ordering::Relaxed);
         let id = format!(
@@ -402,11 +432,11 @@ impl App {
             .comment_author_override
             .take()
             .unwrap_or_else(|| "You".to_string());
-        notes.notes.push(ai::ReviewQuestion {
+        let entry = ai::ReviewQuestion {
             id,
             timestamp: chrono_now(),
             file: file_path,
-            hunk_index: Some(hunk_index),
+            hunk_index,
             line_start: anchor.line_start,
             line_end: Self::normalize_line_end(anchor.line_start, comment_line_end),
             line_content: anchor.line_content,
@@ -419,18 +449,29 @@ impl App {
             hunk_header: anchor.hunk_header,
             anchor_status: "original".to_string(),
             relocated_at_hash: self.tab().diff_hash.clone(),
+            anchor_confidence: None,
             in_reply_to: reply_to,
             author,
             promoted_to: None,
             finding_ref,
-        });
+        };
 
-        // Write atomically
-        std::fs::create_dir_all(&er_dir)?;
-        let json = serde_json::to_string_pretty(&notes)?;
-        let tmp_path = format!("{}.tmp", notes_path);
-        std::fs::write(&tmp_path, json)?;
-        std::fs::rename(&tmp_path, &notes_path)?;
+        // Re-read under the lock so a concurrent writer's entries survive
+        let updated = ai::store::update(&er_dir, &diff_hash, |notes: &mut ai::ErNotes| {
+            notes.diff_hash = diff_hash.clone();
+            notes.notes.push(entry.clone());
+        })?;
+        if updated == crate::file_lock::Updated::StartedFresh {
+            self.notify_warn("Warning: .er/notes.json was invalid JSON — started fresh");
+        }
+        self.publish_comment_added(
+            "note",
+            &entry.id,
+            &entry.file,
+            entry.line_start,
+            &text,
+            &entry.in_reply_to,
+        );
 
         self.tab_mut().comment_textarea = TextArea::default();
         self.input_mode = InputMode::Normal;
@@ -451,38 +492,7 @@ impl App {
         let comment_line_num = tab.comment_line_num;
         let comment_line_end = tab.comment_line_end;
 
-        let anchor = self.get_line_anchor(hunk_index, comment_line_num);
-
-        // Load or create github-comments.json (uses cache dir in remote mode)
-        let comments_path = self.tab().github_comments_path();
-        let mut gh_comments: ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(gc) => gc,
-                Err(_) => {
-                    self.notify(
-                        "Warning: .er/github-comments.json is invalid JSON — starting fresh",
-                    );
-                    ai::ErGitHubComments {
-                        version: 1,
-                        diff_hash: diff_hash.clone(),
-                        github: None,
-                        comments: Vec::new(),
-                    }
-                }
-            },
-            Err(_) => ai::ErGitHubComments {
-                version: 1,
-                diff_hash: diff_hash.clone(),
-                github: None,
-                comments: Vec::new(),
-            },
-        };
-
-        // If diff hash changed, update it but preserve existing comments
-        // (the relocation system handles comment drift)
-        if gh_comments.diff_hash != diff_hash {
-            gh_comments.diff_hash = diff_hash;
-        }
+        let (hunk_index, anchor) = self.comment_target(hunk_index, comment_line_num);
 
         let seq = COMMENT_SEQ.fetch_add(1, Ordering::Relaxed);
         let id = format!(
@@ -505,11 +515,11 @@ impl App {
             .comment_side
             .take()
             .unwrap_or_else(|| "RIGHT".to_string());
-        gh_comments.comments.push(ai::GitHubReviewComment {
+        let entry = ai::GitHubReviewComment {
             id,
             timestamp: chrono_now(),
             file: file_path,
-            hunk_index: Some(hunk_index),
+            hunk_index,
             line_start: anchor.line_start,
             line_end: Self::normalize_line_end(anchor.line_start, comment_line_end),
             line_content: anchor.line_content,
@@ -528,17 +538,36 @@ impl App {
             hunk_header: anchor.hunk_header,
             anchor_status: "original".to_string(),
             relocated_at_hash: self.tab().diff_hash.clone(),
+            anchor_confidence: None,
             finding_ref,
             side,
-        });
+            edit_base: None,
+            remote_body: None,
+        };
 
-        // Write atomically (github-comments.json is PR-scoped — shared PR bucket)
-        let comments_dir = self.tab().github_comments_dir();
-        std::fs::create_dir_all(&comments_dir)?;
-        let json = serde_json::to_string_pretty(&gh_comments)?;
-        let tmp_path = format!("{}.tmp", comments_path);
-        std::fs::write(&tmp_path, json)?;
-        std::fs::rename(&tmp_path, &comments_path)?;
+        // github-comments.json is PR-scoped (shared PR bucket; cache dir in
+        // remote mode). Re-read under the lock so concurrent writers' comments survive.
+        let updated = ai::store::update(
+            &self.tab().github_comments_dir(),
+            &diff_hash,
+            |gh_comments: &mut ai::ErGitHubComments| {
+                // If diff hash changed, update it but preserve existing comments
+                // (the relocation system handles comment drift)
+                gh_comments.diff_hash = diff_hash.clone();
+                gh_comments.comments.push(entry.clone());
+            },
+        )?;
+        if updated == crate::file_lock::Updated::StartedFresh {
+            self.notify_warn("Warning: .er/github-comments.json was invalid JSON — started fresh");
+        }
+        self.publish_comment_added(
+            "github",
+            &entry.id,
+            &entry.file,
+            entry.line_start,
+            &text,
+            &entry.in_reply_to,
+        );
 
         self.tab_mut().comment_textarea = TextArea::default();
         self.input_mode = InputMode::Normal;
@@ -554,6 +583,23 @@ impl App {
         Ok(())
     }
 
+    /// Hunk index and anchor for the comment being submitted; neither for a
+    /// file-level comment.
+    fn comment_target(
+        &self,
+        hunk_index: usize,
+        comment_line_num: Option<usize>,
+    ) -> (Option<usize>, LineAnchor) {
+        if self.tab().comment_file_level {
+            (None, LineAnchor::default())
+        } else {
+            (
+                Some(hunk_index),
+                self.get_line_anchor(hunk_index, comment_line_num),
+            )
+        }
+    }
+
     /// Richer anchor data captured when placing a comment
     pub(crate) fn get_line_anchor(
         &self,
@@ -574,81 +620,10 @@ impl App {
             tab.files.iter().find(|f| f.path == tab.comment_file)
         };
 
-        if let Some(df) = diff_file {
-            if let Some(hunk) = df.hunks.get(hunk_index) {
-                if let Some(ln) = comment_line_num {
-                    // Find the target line index within the hunk
-                    let target_idx = match tab.comment_side.as_deref() {
-                        Some("LEFT") => hunk
-                            .lines
-                            .iter()
-                            .position(|l| l.old_num == Some(ln))
-                            .or_else(|| hunk.lines.iter().position(|l| l.new_num == Some(ln))),
-                        Some("RIGHT") => hunk
-                            .lines
-                            .iter()
-                            .position(|l| l.new_num == Some(ln))
-                            .or_else(|| hunk.lines.iter().position(|l| l.old_num == Some(ln))),
-                        _ => hunk
-                            .lines
-                            .iter()
-                            .position(|l| l.new_num == Some(ln))
-                            .or_else(|| hunk.lines.iter().position(|l| l.old_num == Some(ln))),
-                    };
-                    let (line_content, old_line_start) = if let Some(idx) = target_idx {
-                        let dl = &hunk.lines[idx];
-                        (dl.content.clone(), dl.old_num)
-                    } else {
-                        (String::new(), None)
-                    };
-
-                    // Collect up to 3 content lines before the target (same hunk)
-                    let context_before = if let Some(idx) = target_idx {
-                        let start = idx.saturating_sub(3);
-                        hunk.lines[start..idx]
-                            .iter()
-                            .map(|l| l.content.clone())
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
-
-                    // Collect up to 3 content lines after the target (same hunk)
-                    let context_after = if let Some(idx) = target_idx {
-                        let end = (idx + 4).min(hunk.lines.len());
-                        hunk.lines[(idx + 1)..end]
-                            .iter()
-                            .map(|l| l.content.clone())
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
-
-                    LineAnchor {
-                        line_start: Some(ln),
-                        line_content,
-                        context_before,
-                        context_after,
-                        old_line_start,
-                        hunk_header: hunk.header.clone(),
-                    }
-                } else {
-                    // Hunk-level comment
-                    LineAnchor {
-                        line_start: None,
-                        line_content: hunk.header.clone(),
-                        context_before: Vec::new(),
-                        context_after: Vec::new(),
-                        old_line_start: None,
-                        hunk_header: hunk.header.clone(),
-                    }
-                }
-            } else {
-                LineAnchor::default()
-            }
-        } else {
-            LineAnchor::default()
-        }
+        diff_file
+            .and_then(|df| df.hunks.get(hunk_index))
+            .map(|hunk| LineAnchor::capture(hunk, comment_line_num, tab.comment_side.as_deref()))
+            .unwrap_or_default()
     }
 
     /// Inclusive end line for a multi-line anchor; `None` when single-line or invalid.
@@ -742,6 +717,7 @@ impl App {
             tab.comment_finding_ref = finding_ref;
             tab.comment_type = comment_type;
             tab.comment_edit_id = None;
+            tab.comment_file_level = false;
             tab.comment_textarea = TextArea::new(vec![text]);
             tab.comment_author_override = author;
         }
@@ -761,83 +737,80 @@ impl App {
         let repo_root = self.tab().repo_root.clone();
 
         if comment_id.starts_with("q-") {
-            let path = format!("{}/questions.json", er_dir);
-            let content =
-                std::fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
-            let mut qs: ai::ErQuestions =
-                serde_json::from_str(&content).context("Failed to parse questions.json")?;
-            let q = qs
-                .questions
-                .iter_mut()
-                .find(|q| q.id == comment_id)
-                .context("Question not found")?;
-            if q.author == "ai" {
-                anyhow::bail!("Cannot edit AI-generated text");
-            }
-            q.text = new_text.to_string();
-            let json = serde_json::to_string_pretty(&qs)?;
-            let tmp = format!("{path}.tmp");
-            std::fs::write(&tmp, json)?;
-            std::fs::rename(&tmp, &path)?;
+            ai::store::modify(&er_dir, |qs: &mut ai::ErQuestions| {
+                let q = qs
+                    .questions
+                    .iter_mut()
+                    .find(|q| q.id == comment_id)
+                    .context("Question not found")?;
+                if q.author == "ai" {
+                    anyhow::bail!("Cannot edit AI-generated text");
+                }
+                q.text = new_text.to_string();
+                Ok(())
+            })?
+            .context("No questions.json found")?;
         } else if comment_id.starts_with("n-") {
-            let path = format!("{}/notes.json", er_dir);
-            let content =
-                std::fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
-            let mut ns: ai::ErNotes =
-                serde_json::from_str(&content).context("Failed to parse notes.json")?;
-            let n = ns
-                .notes
-                .iter_mut()
-                .find(|n| n.id == comment_id)
-                .context("Note not found")?;
-            if n.author == "ai" {
-                anyhow::bail!("Cannot edit AI-generated text");
-            }
-            n.text = new_text.to_string();
-            let json = serde_json::to_string_pretty(&ns)?;
-            let tmp = format!("{path}.tmp");
-            std::fs::write(&tmp, json)?;
-            std::fs::rename(&tmp, &path)?;
+            ai::store::modify(&er_dir, |ns: &mut ai::ErNotes| {
+                let n = ns
+                    .notes
+                    .iter_mut()
+                    .find(|n| n.id == comment_id)
+                    .context("Note not found")?;
+                if n.author == "ai" {
+                    anyhow::bail!("Cannot edit AI-generated text");
+                }
+                n.text = new_text.to_string();
+                Ok(())
+            })?
+            .context("No notes.json found")?;
         } else {
-            let path = self.tab().github_comments_path();
-            let content =
-                std::fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
-            let mut gc: ai::ErGitHubComments =
-                serde_json::from_str(&content).context("Failed to parse github-comments.json")?;
-            let (github_id, gh_meta, author) = {
-                let c = gc
+            let comments_dir = self.tab().github_comments_dir();
+            ai::store::modify(&comments_dir, |gc: &mut ai::ErGitHubComments| {
+                let (github_id, author) = gc
                     .comments
                     .iter()
                     .find(|c| c.id == comment_id)
+                    .map(|c| (c.github_id, c.author.clone()))
                     .context("Comment not found")?;
-                (c.github_id, gc.github.clone(), c.author.clone())
-            };
-            if author == "ai" {
-                anyhow::bail!("Cannot edit AI-generated text");
-            }
-            if let (Some(gh_id), Some(gh)) = (github_id, gh_meta.as_ref()) {
-                crate::github::gh_pr_update_review_comment(
-                    &gh.owner, &gh.repo, gh_id, new_text, &repo_root,
-                )?;
-            }
-            if let Some(c) = gc.comments.iter_mut().find(|c| c.id == comment_id) {
-                c.comment = new_text.to_string();
-            }
-            let json = serde_json::to_string_pretty(&gc)?;
-            let tmp = format!("{path}.tmp");
-            std::fs::write(&tmp, json)?;
-            std::fs::rename(&tmp, &path)?;
+                if author == "ai" {
+                    anyhow::bail!("Cannot edit AI-generated text");
+                }
+                let updated = if let (Some(gh_id), Some(gh)) = (github_id, gc.github.as_ref()) {
+                    crate::github::gh_pr_update_review_comment(
+                        &gh.owner, &gh.repo, gh_id, new_text, &repo_root,
+                    )?;
+                    true
+                } else {
+                    false
+                };
+                if let Some(c) = gc.comments.iter_mut().find(|c| c.id == comment_id) {
+                    if updated {
+                        c.comment = new_text.to_string();
+                        c.edit_base = None;
+                        c.remote_body = None;
+                    } else {
+                        c.edit_body(new_text);
+                    }
+                }
+                Ok(())
+            })?
+            .context("No github-comments.json found")?;
         }
 
         self.tab_mut().reload_ai_state();
         Ok(())
     }
 
-    /// Cancel comment input
+    /// Cancel comment input. A new comment's text is kept in the drafts list.
     pub fn cancel_comment(&mut self) {
+        let stashed = self.tab_mut().stash_comment_draft();
         self.tab_mut().comment_textarea = TextArea::default();
         self.tab_mut().comment_edit_id = None;
         self.input_mode = InputMode::Normal;
+        if stashed {
+            self.notify("Draft saved — D to reopen");
+        }
     }
 
     /// Check if there is a non-empty comment draft that is paused (not actively being edited)
@@ -864,8 +837,9 @@ impl App {
         let tab = self.tab();
         tab.comment_reply_to.is_none()
             && tab.comment_edit_id.is_none()
+            && tab.checklist_edit.is_none()
             && tab.comment_finding_ref.is_none()
-            && !tab.comment_file.is_empty()
+            && (!tab.comment_file.is_empty() || tab.comment_file_level)
     }
 
     /// Flip question ↔ GitHub comment for a new file-anchored draft.
@@ -874,6 +848,14 @@ impl App {
             return;
         }
         let tab = self.tab_mut();
+        // Review notes stay local until the review is pushed: note ↔ question
+        if tab.comment_file.is_empty() {
+            tab.comment_type = match tab.comment_type {
+                CommentType::Note => CommentType::Question,
+                _ => CommentType::Note,
+            };
+            return;
+        }
         // Cycle through all three local draft kinds: question → note → comment.
         tab.comment_type = match tab.comment_type {
             CommentType::Question => CommentType::Note,
@@ -889,78 +871,63 @@ impl App {
         let hunk_index = tab.comment_hunk;
         let comment_line_num = tab.comment_line_num;
 
-        let anchor = self.get_line_anchor(hunk_index, comment_line_num);
+        let (hunk_index, anchor) = self.comment_target(hunk_index, comment_line_num);
         let diff_hash = self.tab().diff_hash.clone();
 
         if comment_id.starts_with("q-") {
-            let path = format!("{}/questions.json", er_dir);
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut qs) = serde_json::from_str::<ai::ErQuestions>(&content) {
-                    if let Some(q) = qs.questions.iter_mut().find(|q| q.id == comment_id) {
-                        q.text = new_text.clone();
-                        q.line_start = anchor.line_start;
-                        q.line_content = anchor.line_content.clone();
-                        q.context_before = anchor.context_before.clone();
-                        q.context_after = anchor.context_after.clone();
-                        q.old_line_start = anchor.old_line_start;
-                        q.hunk_header = anchor.hunk_header.clone();
-                        q.hunk_index = Some(hunk_index);
-                        q.anchor_status = "original".to_string();
-                        q.relocated_at_hash = diff_hash;
-                        q.stale = false;
-                    }
-                    let json = serde_json::to_string_pretty(&qs)?;
-                    let tmp = format!("{}.tmp", path);
-                    std::fs::write(&tmp, json)?;
-                    std::fs::rename(&tmp, &path)?;
+            ai::store::modify(&er_dir, |qs: &mut ai::ErQuestions| {
+                if let Some(q) = qs.questions.iter_mut().find(|q| q.id == comment_id) {
+                    q.text = new_text.clone();
+                    q.line_start = anchor.line_start;
+                    q.line_content = anchor.line_content.clone();
+                    q.context_before = anchor.context_before.clone();
+                    q.context_after = anchor.context_after.clone();
+                    q.old_line_start = anchor.old_line_start;
+                    q.hunk_header = anchor.hunk_header.clone();
+                    q.hunk_index = hunk_index;
+                    q.anchor_status = "original".to_string();
+                    q.relocated_at_hash = diff_hash;
+                    q.stale = false;
                 }
-            }
+                Ok(())
+            })?;
         } else if comment_id.starts_with("n-") {
-            let path = format!("{}/notes.json", er_dir);
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut ns) = serde_json::from_str::<ai::ErNotes>(&content) {
-                    if let Some(n) = ns.notes.iter_mut().find(|n| n.id == comment_id) {
-                        n.text = new_text.clone();
-                        n.line_start = anchor.line_start;
-                        n.line_content = anchor.line_content.clone();
-                        n.context_before = anchor.context_before.clone();
-                        n.context_after = anchor.context_after.clone();
-                        n.old_line_start = anchor.old_line_start;
-                        n.hunk_header = anchor.hunk_header.clone();
-                        n.hunk_index = Some(hunk_index);
-                        n.anchor_status = "original".to_string();
-                        n.relocated_at_hash = diff_hash;
-                        n.stale = false;
-                    }
-                    let json = serde_json::to_string_pretty(&ns)?;
-                    let tmp = format!("{}.tmp", path);
-                    std::fs::write(&tmp, json)?;
-                    std::fs::rename(&tmp, &path)?;
+            ai::store::modify(&er_dir, |ns: &mut ai::ErNotes| {
+                if let Some(n) = ns.notes.iter_mut().find(|n| n.id == comment_id) {
+                    n.text = new_text.clone();
+                    n.line_start = anchor.line_start;
+                    n.line_content = anchor.line_content.clone();
+                    n.context_before = anchor.context_before.clone();
+                    n.context_after = anchor.context_after.clone();
+                    n.old_line_start = anchor.old_line_start;
+                    n.hunk_header = anchor.hunk_header.clone();
+                    n.hunk_index = hunk_index;
+                    n.anchor_status = "original".to_string();
+                    n.relocated_at_hash = diff_hash;
+                    n.stale = false;
                 }
-            }
+                Ok(())
+            })?;
         } else {
-            let path = self.tab().github_comments_path();
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut gc) = serde_json::from_str::<ai::ErGitHubComments>(&content) {
+            ai::store::modify(
+                &self.tab().github_comments_dir(),
+                |gc: &mut ai::ErGitHubComments| {
                     if let Some(c) = gc.comments.iter_mut().find(|c| c.id == comment_id) {
-                        c.comment = new_text.clone();
+                        c.edit_body(&new_text);
                         c.line_start = anchor.line_start;
                         c.line_content = anchor.line_content.clone();
                         c.context_before = anchor.context_before.clone();
                         c.context_after = anchor.context_after.clone();
                         c.old_line_start = anchor.old_line_start;
                         c.hunk_header = anchor.hunk_header.clone();
-                        c.hunk_index = Some(hunk_index);
+                        c.hunk_index = hunk_index;
                         c.anchor_status = "original".to_string();
                         c.relocated_at_hash = diff_hash;
                         c.stale = false;
                     }
-                    let json = serde_json::to_string_pretty(&gc)?;
-                    let tmp = format!("{}.tmp", path);
-                    std::fs::write(&tmp, json)?;
-                    std::fs::rename(&tmp, &path)?;
-                }
-            }
+                    Ok(())
+                },
+            )?;
         }
 
         self.tab_mut().comment_textarea = TextArea::default();
@@ -1164,14 +1131,14 @@ impl App {
             Some(f) => f.path.clone(),
             None => return,
         };
-        let (hunk_index, line_start) = {
+        let (hunk_index, line_start, line_end) = {
             let tab = self.tab();
             let fr = match tab.ai.file_review(&path) {
                 Some(fr) => fr,
                 None => return,
             };
             match fr.findings.iter().find(|f| f.id == fid) {
-                Some(f) => (f.hunk_index, f.line_start),
+                Some(f) => (f.hunk_index, f.line_start, f.line_end),
                 None => return,
             }
         };
@@ -1223,6 +1190,9 @@ impl App {
                 Some(hunk.lines.len().saturating_sub(1))
             };
         }
+        if let Some(start) = line_start {
+            tab.flash_lines(&path, start, line_end.unwrap_or(start));
+        }
         tab.panel_focus = false;
         tab.scroll_to_current_hunk();
     }
@@ -1233,70 +1203,45 @@ impl App {
         let tab = self.tab_mut();
         let file_paths: std::collections::HashSet<&str> =
             tab.files.iter().map(|f| f.path.as_str()).collect();
-        let all: Vec<_> = tab
-            .ai
-            .all_findings_ordered()
-            .into_iter()
-            .filter(|(file, _, _, _)| file_paths.contains(file.as_str()))
-            .collect();
-
-        if all.is_empty() {
-            return;
-        }
-
-        // Find current position by exact ID match first, then fallback to file position
-        let current_pos = tab
-            .focused_finding_id
-            .as_ref()
-            .and_then(|fid| all.iter().position(|(_, _, _, id)| id == fid))
-            .filter(|&pos| {
+        let target = {
+            let index = tab.ai.hint_index();
+            let all = &index.findings;
+            let shown =
+                |(file, _, _, _): &crate::ai::OrderedFinding| file_paths.contains(file.as_str());
+            let current_file = tab.files.get(tab.selected_file).map(|f| f.path.as_str());
+
+            // Find current position by exact ID match first, then fallback to file position
+            let current_pos = tab
+                .focused_finding_id
+                .as_ref()
+                .and_then(|fid| index.finding_position(fid))
                 // Ignore stale focused ID if user moved to a different file
-                tab.files
-                    .get(tab.selected_file)
-                    .is_some_and(|f| f.path == all[pos].0)
-            })
-            .or_else(|| {
-                let current_file = tab.files.get(tab.selected_file).map(|f| f.path.as_str());
-                let current_hunk = tab.current_hunk;
-                current_file.and_then(|cf| {
-                    if forward {
-                        // Find first finding at or after current position
-                        all.iter().position(|(f, hi, _, _)| {
-                            f.as_str() > cf || (f == cf && hi.unwrap_or(0) >= current_hunk)
-                        })
-                    } else {
-                        // Find last finding at or before current position
-                        all.iter().rposition(|(f, hi, _, _)| {
-                            f.as_str() < cf || (f == cf && hi.unwrap_or(0) <= current_hunk)
-                        })
-                    }
-                })
-            });
-
-        let next_idx = match current_pos {
-            Some(pos) => {
-                if forward {
-                    if pos + 1 < all.len() {
-                        pos + 1
-                    } else {
-                        0
-                    }
-                } else if pos > 0 {
-                    pos - 1
-                } else {
-                    all.len() - 1
-                }
-            }
-            None => {
-                if forward {
-                    0
-                } else {
-                    all.len() - 1
-                }
-            }
+                .filter(|&pos| current_file == Some(all[pos].0.as_str()))
+                .or_else(|| {
+                    let current_hunk = tab.current_hunk;
+                    current_file.and_then(|cf| {
+                        if forward {
+                            // Find first finding at or after current position
+                            all.iter().position(|e @ (f, hi, _, _)| {
+                                shown(e)
+                                    && (f.as_str() > cf
+                                        || (f == cf && hi.unwrap_or(0) >= current_hunk))
+                            })
+                        } else {
+                            // Find last finding at or before current position
+                            all.iter().rposition(|e @ (f, hi, _, _)| {
+                                shown(e)
+                                    && (f.as_str() < cf
+                                        || (f == cf && hi.unwrap_or(0) <= current_hunk))
+                            })
+                        }
+                    })
+                });
+            step_shown(all, current_pos, forward, shown).map(|i| all[i].clone())
+        };
+        let Some((ref file, hunk_index, line_start, ref finding_id)) = target else {
+            return;
         };
-
-        let (ref file, hunk_index, line_start, ref finding_id) = all[next_idx];
 
         tab.focused_finding_id = Some(finding_id.clone());
         tab.focused_comment_id = None;
@@ -1356,59 +1301,36 @@ impl App {
         use crate::ai::HintType;
 
         let tab = self.tab_mut();
-        let all: Vec<_> = tab
-            .ai
-            .all_hints_ordered()
-            .into_iter()
-            .filter(|(_, _, _, _, ht)| *ht != HintType::Finding)
-            .collect();
-
-        if all.is_empty() {
+        let target = {
+            let index = tab.ai.hint_index();
+            let all = &index.hints;
+            let shown = |(_, _, _, _, ht): &crate::ai::OrderedHint| *ht != HintType::Finding;
+
+            // Find current position by matching the currently focused ID
+            let current_id = tab
+                .focused_comment_id
+                .as_ref()
+                .or(tab.focused_finding_id.as_ref());
+            let current_pos = current_id
+                .and_then(|fid| index.hint_position(fid))
+                .filter(|&pos| shown(&all[pos]))
+                .or_else(|| {
+                    let current_file = tab.files.get(tab.selected_file).map(|f| &f.path);
+                    current_file.and_then(|cf| {
+                        if forward {
+                            all.iter().position(|e| shown(e) && e.0 == *cf)
+                        } else {
+                            all.iter().rposition(|e| shown(e) && e.0 == *cf)
+                        }
+                    })
+                });
+            step_shown(all, current_pos, forward, shown).map(|i| all[i].clone())
+        };
+        let Some((ref file, hunk_index, _, ref id, hint_type)) = target else {
             return;
-        }
-
-        // Find current position by matching the currently focused ID
-        let current_id = tab
-            .focused_comment_id
-            .as_ref()
-            .or(tab.focused_finding_id.as_ref());
-        let current_pos = current_id
-            .and_then(|fid| all.iter().position(|(_, _, _, id, _)| id == fid))
-            .or_else(|| {
-                let current_file = tab.files.get(tab.selected_file).map(|f| &f.path);
-                current_file.and_then(|cf| {
-                    if forward {
-                        all.iter().position(|(f, _, _, _, _)| f == cf)
-                    } else {
-                        all.iter().rposition(|(f, _, _, _, _)| f == cf)
-                    }
-                })
-            });
-
-        let next_idx = match current_pos {
-            Some(pos) => {
-                if forward {
-                    if pos + 1 < all.len() {
-                        pos + 1
-                    } else {
-                        0
-                    }
-                } else if pos > 0 {
-                    pos - 1
-                } else {
-                    all.len() - 1
-                }
-            }
-            None => {
-                if forward {
-                    0
-                } else {
-                    all.len() - 1
-                }
-            }
         };
 
-        let (ref file, hunk_index, _, ref id, hint_type) = all[next_idx];
+        tab.record_jump();
 
         // Set the appropriate focus ID based on hint type
         match hint_type {
@@ -1454,78 +1376,61 @@ impl App {
         // Determine which file this comment lives in (by id prefix)
         if comment_id.starts_with("q-") {
             // Delete from questions.json
-            let path = format!("{}/questions.json", er_dir);
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut qs) = serde_json::from_str::<ai::ErQuestions>(&content) {
-                    qs.questions.retain(|q| {
-                        q.id != comment_id && q.in_reply_to.as_deref() != Some(comment_id)
-                    });
-                    let json = serde_json::to_string_pretty(&qs)?;
-                    let tmp_path = format!("{}.tmp", path);
-                    std::fs::write(&tmp_path, &json)?;
-                    std::fs::rename(&tmp_path, &path)?;
-                }
-            }
+            ai::store::modify(&er_dir, |qs: &mut ai::ErQuestions| {
+                qs.questions
+                    .retain(|q| q.id != comment_id && q.in_reply_to.as_deref() != Some(comment_id));
+                Ok(())
+            })?;
         } else if comment_id.starts_with("n-") {
             // Delete from notes.json (cascade replies)
-            let path = format!("{}/notes.json", er_dir);
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut ns) = serde_json::from_str::<ai::ErNotes>(&content) {
-                    ns.notes.retain(|n| {
-                        n.id != comment_id && n.in_reply_to.as_deref() != Some(comment_id)
-                    });
-                    let json = serde_json::to_string_pretty(&ns)?;
-                    let tmp_path = format!("{}.tmp", path);
-                    std::fs::write(&tmp_path, &json)?;
-                    std::fs::rename(&tmp_path, &path)?;
-                }
-            }
+            ai::store::modify(&er_dir, |ns: &mut ai::ErNotes| {
+                ns.notes
+                    .retain(|n| n.id != comment_id && n.in_reply_to.as_deref() != Some(comment_id));
+                Ok(())
+            })?;
         } else {
             // Delete from github-comments.json (uses cache dir in remote mode)
-            let path = self.tab().github_comments_path();
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(mut gc) = serde_json::from_str::<ai::ErGitHubComments>(&content) {
-                    // Check if the comment has a github_id for API deletion
-                    let github_id = gc
-                        .comments
+            let comments_dir = self.tab().github_comments_dir();
+            let on_github =
+                ai::store::load::<ai::ErGitHubComments>(&comments_dir)?.is_some_and(|gc| {
+                    gc.comments
                         .iter()
-                        .find(|c| c.id == comment_id)
-                        .and_then(|c| c.github_id);
+                        .any(|c| c.id == comment_id && c.github_id.is_some())
+                });
+            if on_github && self.blocked_by_read_only("deleting GitHub comments") {
+                self.input_mode = InputMode::Normal;
+                return Ok(());
+            }
+            ai::store::modify(&comments_dir, |gc: &mut ai::ErGitHubComments| {
+                // Check if the comment has a github_id for API deletion
+                let github_id = gc
+                    .comments
+                    .iter()
+                    .find(|c| c.id == comment_id)
+                    .and_then(|c| c.github_id);
+                let reply_github_ids: Vec<u64> = gc
+                    .comments
+                    .iter()
+                    .filter(|c| c.in_reply_to.as_deref() == Some(comment_id))
+                    .filter_map(|c| c.github_id)
+                    .collect();
 
-                    let reply_github_ids: Vec<u64> = gc
-                        .comments
-                        .iter()
-                        .filter(|c| {
-                            c.in_reply_to.as_deref() == Some(comment_id) && c.github_id.is_some()
-                        })
-                        .filter_map(|c| c.github_id)
-                        .collect();
-
-                    // Delete from GitHub if applicable
-                    if let Some(gh_id) = github_id {
-                        if let Some(ref gh) = gc.github {
-                            let _ = crate::github::gh_pr_delete_comment(
-                                &gh.owner, &gh.repo, gh_id, &repo_root,
-                            );
-                            for reply_id in &reply_github_ids {
-                                let _ = crate::github::gh_pr_delete_comment(
-                                    &gh.owner, &gh.repo, *reply_id, &repo_root,
-                                );
-                            }
-                        }
+                // Delete from GitHub if applicable
+                if let (Some(gh_id), Some(gh)) = (github_id, gc.github.as_ref()) {
+                    let _ =
+                        crate::github::gh_pr_delete_comment(&gh.owner, &gh.repo, gh_id, &repo_root);
+                    for reply_id in &reply_github_ids {
+                        let _ = crate::github::gh_pr_delete_comment(
+                            &gh.owner, &gh.repo, *reply_id, &repo_root,
+                        );
                     }
-
-                    // Remove comment and cascade replies
-                    gc.comments.retain(|c| {
-                        c.id != comment_id && c.in_reply_to.as_deref() != Some(comment_id)
-                    });
-
-                    let json = serde_json::to_string_pretty(&gc)?;
-                    let tmp_path = format!("{}.tmp", path);
-                    std::fs::write(&tmp_path, &json)?;
-                    std::fs::rename(&tmp_path, &path)?;
                 }
-            }
+
+                // Remove comment and cascade replies
+                gc.comments
+                    .retain(|c| c.id != comment_id && c.in_reply_to.as_deref() != Some(comment_id));
+                Ok(())
+            })?;
         }
 
         self.input_mode = InputMode::Normal;
@@ -1537,6 +1442,7 @@ impl App {
     /// Cancel the confirm dialog
     pub fn cancel_confirm(&mut self) {
         self.input_mode = InputMode::Normal;
+        self.confirm_prompt = None;
         self.clear_ai_selection_override();
     }
 
@@ -1546,6 +1452,9 @@ impl App {
 
     /// Start commit input (only in Staged mode)
     pub fn start_commit(&mut self) {
+        if self.blocked_by_read_only("committing") {
+            return;
+        }
         self.tab_mut().commit_input.clear();
         self.input_mode = InputMode::Commit;
     }
@@ -1554,22 +1463,46 @@ impl App {
     pub fn submit_commit(&mut self) -> Result<()> {
         let message = self.tab().commit_input.trim().to_string();
         if message.is_empty() {
+            self.tab_mut().split_pending = None;
             self.input_mode = InputMode::Normal;
             return Ok(());
         }
+        if self.tab().split_pending.is_some() {
+            self.submit_split_bucket(message);
+            return Ok(());
+        }
+        let lint = self.commit_input_lint();
         let repo_root = self.tab().repo_root.clone();
-        git::git_commit(&repo_root, &message)?;
+        if let Err(e) = git::git_commit(&repo_root, &message) {
+            // Keep the typed message so the commit can be retried after a fix
+            self.input_mode = InputMode::Normal;
+            self.report_command_error("Commit failed", &e);
+            return Ok(());
+        }
         self.tab_mut().commit_input.clear();
         self.input_mode = InputMode::Normal;
         self.tab_mut().committed_unpushed = true;
+        self.tab_mut().staged_in_app = false;
         let _ = self.tab_mut().refresh_diff();
-        self.notify("Committed! Ctrl+P to push");
+        match lint.first() {
+            Some(issue) => self.notify(&format!(
+                "Committed — message: {}{}",
+                issue.message,
+                if lint.len() > 1 {
+                    format!(" (+{} more)", lint.len() - 1)
+                } else {
+                    String::new()
+                }
+            )),
+            None => self.notify("Committed! Ctrl+P to push"),
+        }
         Ok(())
     }
 
     /// Cancel commit input
     pub fn cancel_commit(&mut self) {
         self.tab_mut().commit_input.clear();
+        self.tab_mut().split_pending = None;
         self.input_mode = InputMode::Normal;
     }
 
@@ -1577,10 +1510,43 @@ impl App {
 
     /// Jump from AiSummary panel to the selected file in FileDetail mode
     pub fn review_jump_to_file(&mut self) {
+        let on_header = {
+            let tab = self.tab();
+            tab.review_focus == ReviewFocus::Categories
+                && matches!(
+                    tab.review_category_rows().get(tab.review_cursor),
+                    Some(ai::CategoryRow::Header { .. })
+                )
+        };
+        if on_header {
+            self.tab_mut().review_toggle_category();
+            return;
+        }
+        let finding = {
+            let tab = self.tab();
+            match tab.review_category_rows().get(tab.review_cursor) {
+                Some(ai::CategoryRow::Finding { path, finding_id })
+                    if tab.review_focus == ReviewFocus::Categories =>
+                {
+                    Some((path.clone(), finding_id.clone()))
+                }
+                _ => None,
+            }
+        };
+        if let Some((path, finding_id)) = finding {
+            self.jump_to_finding(&path, &finding_id);
+            return;
+        }
         let file_path = {
             let tab = self.tab();
             match tab.review_focus {
-                ReviewFocus::Files => tab.ai.review_file_at(tab.review_cursor),
+                ReviewFocus::Files => tab.review_file_at_cursor(),
+                ReviewFocus::Categories => {
+                    match tab.review_category_rows().get(tab.review_cursor) {
+                        Some(ai::CategoryRow::Finding { path, .. }) => Some(path.clone()),
+                        _ => None,
+                    }
+                }
                 ReviewFocus::Checklist => tab.ai.checklist_file_at(tab.review_cursor),
             }
         };
@@ -1602,6 +1568,7 @@ impl App {
                     .map(|f| (f.hunk_index.unwrap(), f.id.clone()));
 
                 let tab = self.tab_mut();
+                tab.record_jump();
                 tab.selected_file = idx;
                 tab.current_hunk = first_finding.as_ref().map(|(hi, _)| *hi).unwrap_or(0);
                 tab.focused_finding_id = first_finding.map(|(_, id)| id);
@@ -1623,6 +1590,67 @@ impl App {
         }
     }
 
+    /// Open `path` at the finding's exact hunk and line and flash its line
+    /// range. The anchor is re-resolved against the current diff, so a line
+    /// that moved to another hunk is still found.
+    pub fn jump_to_finding(&mut self, path: &str, finding_id: &str) {
+        let Some(idx) = self.tab().files.iter().position(|f| f.path == path) else {
+            self.notify(&format!("File not in diff: {}", path));
+            return;
+        };
+        let Some((hunk_index, line_start, line_end)) = self
+            .tab()
+            .ai
+            .file_review(path)
+            .and_then(|fr| fr.findings.iter().find(|f| f.id == finding_id))
+            .map(|f| (f.hunk_index, f.line_start, f.line_end))
+        else {
+            return;
+        };
+
+        let tab = self.tab_mut();
+        tab.record_jump();
+        tab.selected_file = idx;
+        tab.focused_finding_id = Some(finding_id.to_string());
+        tab.focused_comment_id = None;
+        tab.selected_watched = None;
+        tab.selection_anchor = None;
+        tab.diff_scroll = 0;
+        tab.h_scroll = 0;
+        tab.ensure_file_parsed();
+        let target = ai::locate_finding(hunk_index, line_start, &tab.files[idx]);
+        tab.current_hunk = target.map_or(hunk_index.unwrap_or(0), |t| t.hunk_index);
+        tab.current_line = target.and_then(|t| t.line_index);
+        tab.rebuild_hunk_offsets();
+        tab.scroll_to_current_hunk();
+        if tab.panel.is_none() {
+            tab.panel = Some(PanelContent::FileDetail);
+        }
+        tab.panel_focus = false;
+
+        match (target, line_start) {
+            (Some(t), Some(start)) => {
+                tab.flash_lines(path, start, line_end.unwrap_or(start));
+                if t.moved {
+                    self.notify(&format!(
+                        "Jumped to {}:{} (anchor moved hunks)",
+                        path, start
+                    ));
+                } else {
+                    self.notify(&format!("Jumped to {}:{}", path, start));
+                }
+            }
+            (Some(_), None) => self.notify(&format!("Jumped to: {}", path)),
+            (None, line) => {
+                let ln = line.map(|l| l.to_string()).unwrap_or_else(|| "?".into());
+                self.notify(&format!(
+                    "Line {} is outside the diff — open in editor to view",
+                    ln
+                ));
+            }
+        }
+    }
+
     /// Toggle the checklist item at cursor and persist to .er/checklist.json
     pub fn review_toggle_checklist(&mut self) -> Result<()> {
         let tab = self.tab_mut();
@@ -1633,16 +1661,10 @@ impl App {
         let cursor = tab.review_cursor;
         tab.ai.toggle_checklist_item(cursor);
 
-        // Persist atomically via temp file + rename
-        if let Some(ref checklist) = tab.ai.checklist {
-            let checklist_path = format!("{}/checklist.json", tab.er_dir());
-            let tmp_path = format!("{}.tmp", checklist_path);
-            let json = serde_json::to_string_pretty(checklist)?;
-            std::fs::write(&tmp_path, json)?;
-            std::fs::rename(&tmp_path, &checklist_path)?;
-        }
+        self.save_checklist()?;
 
-        let checked = tab
+        let checked = self
+            .tab()
             .ai
             .checklist
             .as_ref()
@@ -1670,8 +1692,8 @@ impl App {
         }
         let content = std::fs::read_to_string(&path).context("Failed to read review.json")?;
         let bytes = content.len();
-        Self::copy_to_clipboard(&content)?;
-        self.notify(&format!("Copied review.json ({} bytes)", bytes));
+        let via = self.copy_to_clipboard(&content)?;
+        self.notify_copied(&format!("Copied review.json ({} bytes)", bytes), via);
         Ok(())
     }
 
@@ -1685,8 +1707,8 @@ impl App {
         }
         let content = std::fs::read_to_string(&path).context("Failed to read questions.json")?;
         let bytes = content.len();
-        Self::copy_to_clipboard(&content)?;
-        self.notify(&format!("Copied questions.json ({} bytes)", bytes));
+        let via = self.copy_to_clipboard(&content)?;
+        self.notify_copied(&format!("Copied questions.json ({} bytes)", bytes), via);
         Ok(())
     }
 
@@ -1704,8 +1726,8 @@ impl App {
         }
 
         let text = self.tab().files[si].hunks[hi].to_text();
-        Self::copy_to_clipboard(&text)?;
-        self.notify("Hunk copied to clipboard");
+        let via = self.copy_to_clipboard(&text)?;
+        self.notify_copied("Hunk copied to clipboard", via);
         Ok(())
     }
 
@@ -1719,8 +1741,8 @@ impl App {
                 text.push('\n');
             }
             let count = file.hunks.len();
-            Self::copy_to_clipboard(&text)?;
-            self.notify(&format!("Copied full file diff ({} hunks)", count));
+            let via = self.copy_to_clipboard(&text)?;
+            self.notify_copied(&format!("Copied full file diff ({} hunks)", count), via);
         } else {
             self.notify("No file selected");
         }
@@ -1732,8 +1754,8 @@ impl App {
         let tab = self.tab();
         if let Some(file) = tab.selected_diff_file() {
             let path = file.path.clone();
-            Self::copy_to_clipboard(&path)?;
-            self.notify(&format!("Copied: {}", path));
+            let via = self.copy_to_clipboard(&path)?;
+            self.notify_copied(&format!("Copied: {}", path), via);
         } else {
             self.notify("No file selected");
         }
@@ -1748,8 +1770,8 @@ impl App {
                 if let Some(hunk) = file.hunks.get(tab.current_hunk) {
                     if let Some(line) = hunk.lines.get(line_idx) {
                         let content = line.content.clone();
-                        Self::copy_to_clipboard(&content)?;
-                        self.notify("Line copied to clipboard");
+                        let via = self.copy_to_clipboard(&content)?;
+                        self.notify_copied("Line copied to clipboard", via);
                         return Ok(());
                     }
                 }
@@ -1864,57 +1886,155 @@ impl App {
         } else {
             "hunk"
         };
-        Self::copy_to_clipboard(&text)?;
-        self.notify(&format!("Copied {} ({} lines)", scope, line_count));
+        let via = self.copy_to_clipboard(&text)?;
+        self.notify_copied(&format!("Copied {} ({} lines)", scope, line_count), via);
         Ok(())
     }
 
-    fn copy_to_clipboard(text: &str) -> Result<()> {
-        let (cmd, args): (&str, Vec<&str>) = if cfg!(target_os = "macos") {
-            ("pbcopy", vec![])
-        } else if cfg!(target_os = "windows") {
-            ("clip", vec![])
-        } else {
-            // Linux — try xclip, fall back to xsel
-            if std::process::Command::new("which")
-                .arg("xclip")
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false)
-            {
-                ("xclip", vec!["-selection", "clipboard"])
-            } else {
-                ("xsel", vec!["--clipboard", "--input"])
-            }
-        };
+    /// Write `handoff.json` + `handoff.md` for the whole review and copy the
+    /// markdown task list to the clipboard (whole-review `copy_context`).
+    pub fn export_handoff(&mut self) -> Result<()> {
+        let tab = self.tab();
+        let reviewed: Vec<String> = tab.reviewed.keys().cloned().collect();
+        let handoff = ai::Handoff::build(
+            &tab.ai,
+            &reviewed,
+            &tab.current_branch,
+            &tab.base_branch,
+            tab.pr_number,
+        )
+        .without_findings(&tab.ai.suppressed_findings(&tab.files))
+        .with_skipped(&tab.skipped)
+        .with_todos(&tab.todos, &tab.todo_scanner)
+        .with_operational(&tab.operational)
+        .attributed(&self.identity());
+        let er_dir = tab.er_dir();
+        std::fs::create_dir_all(&er_dir).context("Failed to create .er directory")?;
+        let json = serde_json::to_string_pretty(&handoff)?;
+        std::fs::write(format!("{}/{}", er_dir, ai::HANDOFF_JSON), json)
+            .context("Failed to write handoff.json")?;
+        let markdown = handoff.to_markdown();
+        std::fs::write(format!("{}/{}", er_dir, ai::HANDOFF_MD), &markdown)
+            .context("Failed to write handoff.md")?;
+
+        let count = handoff.tasks.len() + handoff.questions.len();
+        match self.copy_to_clipboard(&markdown) {
+            Ok(via) => self.notify_copied(
+                &format!(
+                    "Hand-off copied ({} item{}) — saved to .er/{}",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    ai::HANDOFF_MD
+                ),
+                via,
+            ),
+            Err(_) => self.notify(&format!(
+                "Hand-off saved to {}/{} (clipboard unavailable)",
+                er_dir,
+                ai::HANDOFF_MD
+            )),
+        }
+        Ok(())
+    }
 
-        let mut child = std::process::Command::new(cmd)
-            .args(&args)
-            .stdin(std::process::Stdio::piped())
-            .spawn()
-            .context("Failed to open clipboard command")?;
+    /// Copy via the `[clipboard]` backends; returns the one that worked.
+    pub(super) fn copy_to_clipboard(&self, text: &str) -> Result<ClipboardBackend> {
+        crate::clipboard::copy(text, &self.config.clipboard)
+    }
 
-        if let Some(ref mut stdin) = child.stdin {
-            stdin.write_all(text.as_bytes())?;
+    /// Success toast for a copy, naming the backend when it isn't a native
+    /// tool (OSC 52 depends on terminal support, so say so).
+    pub(super) fn notify_copied(&mut self, msg: &str, via: ClipboardBackend) {
+        match via {
+            ClipboardBackend::Osc52 => self.notify(&format!("{} · via {}", msg, via.name())),
+            _ => self.notify(msg),
         }
-
-        child.wait().context("Clipboard command failed")?;
-        Ok(())
     }
 
     // ── Notifications ──
 
+    /// Toast with a level inferred from the wording (see `NotificationLevel::infer`).
     pub fn notify(&mut self, msg: &str) {
-        self.watch_message = Some(msg.to_string());
-        self.watch_message_ticks = 0;
-        self.watch_message_max_ticks = 20; // ~2s
+        self.notify_level(NotificationLevel::infer(msg), msg);
     }
 
-    /// Like notify but persists for ~5 seconds — for important results.
+    /// Like notify but persists for at least ~5 seconds — for important results.
     pub fn notify_long(&mut self, msg: &str) {
-        self.watch_message = Some(msg.to_string());
-        self.watch_message_ticks = 0;
-        self.watch_message_max_ticks = 50; // ~5s
+        self.notify(msg);
+        self.watch_message_max_ticks = self.watch_message_max_ticks.max(50);
+    }
+
+    /// Post a review milestone to the configured webhook (no-op when unset).
+    pub fn send_webhook(&self, event: crate::webhook::WebhookEvent, message: &str) {
+        let tab = self.tab();
+        let repo = tab.remote_repo.clone().unwrap_or_else(|| {
+            std::path::Path::new(&tab.repo_root)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let ctx = crate::webhook::WebhookContext {
+            repo,
+            branch: tab.current_branch.clone(),
+            pr_number: tab.pr_number,
+        };
+        crate::webhook::send(&self.config.notifications, event, &ctx, message);
+    }
+
+    /// Publish `event` for the active tab (see [`crate::events`]).
+    pub fn publish_event(&self, event: crate::events::Event) {
+        self.tab().publish_event(event);
+    }
+
+    fn publish_comment_added(
+        &self,
+        kind: &str,
+        id: &str,
+        file: &str,
+        line: Option<usize>,
+        text: &str,
+        reply_to: &Option<String>,
+    ) {
+        self.publish_event(crate::events::Event::CommentAdded {
+            kind: kind.to_string(),
+            id: id.to_string(),
+            file: file.to_string(),
+            line,
+            text: text.to_string(),
+            reply_to: reply_to.clone(),
+        });
+    }
+
+    /// Post a `high_finding` notification for the `added` High findings
+    /// (an AI reload's [`crate::ai::AiDelta::high_findings_added`]).
+    pub fn send_new_high_findings_webhook(&self, added: &[String]) {
+        if added.is_empty() {
+            return;
+        }
+        let Some(review) = self.tab().ai.review.as_ref() else {
+            return;
+        };
+        let mut new_titles: Vec<String> = review
+            .files
+            .iter()
+            .flat_map(|(path, fr)| {
+                fr.findings
+                    .iter()
+                    .filter(|f| added.contains(&f.id))
+                    .map(move |f| format!("{}: {}", path, f.title))
+            })
+            .collect();
+        if new_titles.is_empty() {
+            return;
+        }
+        new_titles.sort();
+        let message = format!(
+            "{} new high-severity finding{}: {}",
+            new_titles.len(),
+            if new_titles.len() == 1 { "" } else { "s" },
+            new_titles.join("; ")
+        );
+        self.send_webhook(crate::webhook::WebhookEvent::HighFinding, &message);
     }
 
     // ── Background Commands ──
@@ -2115,7 +2235,9 @@ impl App {
 
     /// Drain all pending agent log entries from the channel into `agent_log`.
     /// Called each tick. Auto-scrolls the AgentLog panel when new entries arrive.
-    pub fn drain_agent_log(&mut self) {
+    /// Returns whether anything arrived (the TUI redraws only then).
+    pub fn drain_agent_log(&mut self) -> bool {
+        let mut any = false;
         for (i, tab) in self.tabs.iter_mut().enumerate() {
             let mut received = false;
             while let Ok(entry) = tab.log_rx.try_recv() {
@@ -2125,6 +2247,7 @@ impl App {
                     tab.agent_log.pop_front();
                 }
             }
+            any |= received;
             if received && i == self.active_tab && tab.agent_log_auto_scroll {
                 if let Some(panel) = tab.panel {
                     if panel == crate::ai::PanelContent::AgentLog {
@@ -2134,10 +2257,12 @@ impl App {
                 }
             }
         }
+        any
     }
 
     /// Poll all running commands for completion (called from event loop).
-    pub fn check_commands(&mut self) {
+    /// Returns whether any command finished.
+    pub fn check_commands(&mut self) -> bool {
         // Collect completions per tab to avoid borrow conflicts with notify_long
         let mut notifications: Vec<String> = Vec::new();
 
@@ -2204,9 +2329,11 @@ impl App {
         }
 
         // Apply notifications after the tab iteration loop (avoids borrow conflict)
+        let finished = !notifications.is_empty();
         for msg in notifications {
             self.notify_long(&msg);
         }
+        finished
     }
     /// Spawn the configured agent command with a pre-built prompt.
     ///
@@ -2498,14 +2625,29 @@ impl App {
         Ok(())
     }
 
-    pub fn tick(&mut self) {
+    /// Advance the toast timer; returns true when a toast was just cleared.
+    pub fn tick(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if tab
+            .line_flash
+            .as_ref()
+            .is_some_and(|f| Instant::now() >= f.until)
+        {
+            tab.line_flash = None;
+            return true;
+        }
         if self.watch_message.is_some() {
             self.watch_message_ticks += 1;
             if self.watch_message_ticks > self.watch_message_max_ticks {
                 self.watch_message = None;
                 self.watch_message_ticks = 0;
+                return true;
             }
         }
+        if self.see_selected_file_comments() {
+            return true;
+        }
+        self.retry_outbox_if_due()
     }
 
     /// Spawn an app-level background general review (`kind` = `review`).
@@ -3327,6 +3469,64 @@ impl App {
     }
 }
 
+impl TabState {
+    /// Record review-wide notes as sent in a review body so the next
+    /// submission doesn't repeat them.
+    pub(super) fn mark_review_notes_sent(&mut self, ids: &[String]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let er_dir = self.er_dir();
+        let questions_path = format!("{}/questions.json", er_dir);
+        if let Ok(content) = std::fs::read_to_string(&questions_path) {
+            if let Ok(mut qs) = serde_json::from_str::<ai::ErQuestions>(&content) {
+                mark_sent(&mut qs.questions, ids);
+                std::fs::write(&questions_path, serde_json::to_string_pretty(&qs)?)?;
+            }
+        }
+        let notes_path = format!("{}/notes.json", er_dir);
+        if let Ok(content) = std::fs::read_to_string(&notes_path) {
+            if let Ok(mut ns) = serde_json::from_str::<ai::ErNotes>(&content) {
+                mark_sent(&mut ns.notes, ids);
+                std::fs::write(&notes_path, serde_json::to_string_pretty(&ns)?)?;
+            }
+        }
+        self.reload_ai_state();
+        Ok(())
+    }
+}
+
+fn mark_sent(items: &mut [ai::ReviewQuestion], ids: &[String]) {
+    for item in items.iter_mut().filter(|q| ids.contains(&q.id)) {
+        item.promoted_to = Some("review".to_string());
+    }
+}
+
+/// The entry `shown` after (or before) `from`, wrapping around; the first
+/// (or last) one shown without a position.
+fn step_shown<T>(
+    entries: &[T],
+    from: Option<usize>,
+    forward: bool,
+    shown: impl Fn(&T) -> bool,
+) -> Option<usize> {
+    let n = entries.len();
+    let start = match from {
+        Some(pos) => pos,
+        None if forward => n.checked_sub(1)?,
+        None => 0,
+    };
+    (1..=n)
+        .map(|k| {
+            if forward {
+                (start + k) % n
+            } else {
+                (start + n - k) % n
+            }
+        })
+        .find(|&i| shown(&entries[i]))
+}
+
 #[cfg(test)]
 mod background_queue_tests {
     use crate::app::{App, BackgroundTaskTarget};
@@ -0,0 +1,152 @@
+//! Date headers, ages and author initials for the History commit list.
+//!
+//! Commits are grouped by the calendar day they were made on, in the
+//! author's timezone from `%aI`: Today, Yesterday, This week (up to six
+//! days back), Last week, and then by month. Ages are worked out from the
+//! clock at render time, unlike git's `%ar`, which is frozen at load.
+
+use crate::review_queue::parse_github_updated_at;
+
+/// A date header in the commit list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateGroup {
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    /// Older commits, by `(year, month)`
+    Month(i64, u32),
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl DateGroup {
+    pub fn label(&self) -> String {
+        match self {
+            DateGroup::Today => "Today".into(),
+            DateGroup::Yesterday => "Yesterday".into(),
+            DateGroup::ThisWeek => "This week".into(),
+            DateGroup::LastWeek => "Last week".into(),
+            DateGroup::Month(year, month) => {
+                format!("{} {}", MONTHS[(*month as usize).clamp(1, 12) - 1], year)
+            }
+        }
+    }
+}
+
+/// `(utc, offset)` seconds of an ISO 8601 date with a `+02:00` / `Z` offset.
+fn parse_date(date: &str) -> Option<(i64, i64)> {
+    let wall = parse_github_updated_at(date)?;
+    let (_, time) = date.trim().split_once('T')?;
+    let offset = match time.rfind(['+', '-']) {
+        Some(at) => {
+            let sign = if time[at..].starts_with('-') { -1 } else { 1 };
+            let (h, m) = time[at + 1..]
+                .split_once(':')
+                .unwrap_or((&time[at + 1..], "0"));
+            sign * (h.parse::<i64>().ok()? * 3600 + m.parse::<i64>().ok()? * 60)
+        }
+        None => 0,
+    };
+    Some((wall - offset, offset))
+}
+
+/// The header `date` goes under at `now` (Unix seconds). `None` when the
+/// date doesn't parse.
+pub fn date_group(date: &str, now: i64) -> Option<DateGroup> {
+    let (utc, offset) = parse_date(date)?;
+    let day = (utc + offset).div_euclid(86_400);
+    let today = (now + offset).div_euclid(86_400);
+    Some(match today - day {
+        i64::MIN..=0 => DateGroup::Today,
+        1 => DateGroup::Yesterday,
+        2..=6 => DateGroup::ThisWeek,
+        7..=13 => DateGroup::LastWeek,
+        _ => {
+            let mut parts = date.split('-');
+            let year = parts.next()?.parse().ok()?;
+            let month = parts.next()?.parse().ok()?;
+            DateGroup::Month(year, month)
+        }
+    })
+}
+
+/// Compact age of `date` at `now`: `5m`, `3h`, `6d`, `3w`, `4mo`, `2y`.
+pub fn commit_age(date: &str, now: i64) -> Option<String> {
+    let (utc, _) = parse_date(date)?;
+    let secs = (now - utc).max(0);
+    let days = secs / 86_400;
+    Some(match secs {
+        s if s < 3_600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3_600),
+        _ if days < 14 => format!("{}d", days),
+        _ if days < 60 => format!("{}w", days / 7),
+        _ if days < 730 => format!("{}mo", days / 30),
+        _ => format!("{}y", days / 365),
+    })
+}
+
+/// Two-letter badge for an author: first and last initials of a full
+/// name, the first two letters of a single one.
+pub fn author_initials(author: &str) -> String {
+    let words: Vec<&str> = author
+        .split(|c: char| c.is_whitespace() || c == '.' || c == '_' || c == '-')
+        .filter(|w| w.chars().next().is_some_and(char::is_alphanumeric))
+        .collect();
+    let initials: String = match words.as_slice() {
+        [] => "?".into(),
+        [only] => only.chars().take(2).collect(),
+        [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    initials.to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-06-15T12:00:00Z
+    const NOW: i64 = 1_718_452_800;
+
+    #[test]
+    fn commits_group_by_calendar_day_in_their_timezone() {
+        let group = |date| date_group(date, NOW).unwrap();
+        assert_eq!(group("2024-06-15T08:00:00+00:00"), DateGroup::Today);
+        // The day is the author's: late on the 14th in UTC is yesterday,
+        // 22:30 on the 15th in Tokyo is today
+        assert_eq!(group("2024-06-14T23:30:00Z"), DateGroup::Yesterday);
+        assert_eq!(group("2024-06-15T22:30:00+09:00"), DateGroup::Today);
+        assert_eq!(group("2024-06-10T10:00:00-07:00"), DateGroup::ThisWeek);
+        assert_eq!(group("2024-06-03T10:00:00+02:00"), DateGroup::LastWeek);
+        assert_eq!(group("2024-05-20T10:00:00+02:00").label(), "May 2024");
+        assert_eq!(date_group("yesterday", NOW), None);
+    }
+
+    #[test]
+    fn ages_and_initials() {
+        assert_eq!(commit_age("2024-06-15T11:15:00Z", NOW).unwrap(), "45m");
+        assert_eq!(commit_age("2024-06-15T14:00:00+02:00", NOW).unwrap(), "0m");
+        assert_eq!(commit_age("2024-06-12T12:00:00Z", NOW).unwrap(), "3d");
+        assert_eq!(commit_age("2024-05-01T12:00:00Z", NOW).unwrap(), "6w");
+        assert_eq!(commit_age("2021-01-01T12:00:00Z", NOW).unwrap(), "3y");
+
+        assert_eq!(author_initials("Alice van der Berg"), "AB");
+        assert_eq!(author_initials("bob"), "BO");
+        assert_eq!(author_initials("dependabot[bot]"), "DE");
+        assert_eq!(author_initials("j.doe"), "JD");
+        assert_eq!(author_initials(""), "?");
+    }
+}
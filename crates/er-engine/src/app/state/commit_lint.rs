@@ -0,0 +1,94 @@
+//! Commit message lint (`[commit_lint]`) for History mode and in-app commits.
+
+use super::App;
+use crate::git::{lint_commit_message, CommitInfo, CommitLintIssue};
+
+impl App {
+    /// Rules `commit` breaks, checked against its full message when History
+    /// mode has it and its subject otherwise.
+    pub fn commit_lint_issues(&self, commit: &CommitInfo) -> Vec<CommitLintIssue> {
+        if commit.is_merge {
+            return Vec::new();
+        }
+        let message = self
+            .tab()
+            .history
+            .as_ref()
+            .and_then(|h| h.messages.get(&commit.hash))
+            .map_or(commit.subject.as_str(), String::as_str);
+        lint_commit_message(message, &self.config.commit_lint)
+    }
+
+    /// Loaded History commits whose message breaks a rule.
+    pub fn history_lint_count(&self) -> usize {
+        self.tab().history.as_ref().map_or(0, |h| {
+            h.commits
+                .iter()
+                .filter(|c| !self.commit_lint_issues(c).is_empty())
+                .count()
+        })
+    }
+
+    /// Rules the commit message being typed breaks.
+    pub fn commit_input_lint(&self) -> Vec<CommitLintIssue> {
+        let message = self.tab().commit_input.trim();
+        if message.is_empty() {
+            return Vec::new();
+        }
+        lint_commit_message(message, &self.config.commit_lint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{DiffCache, HistoryState};
+    use std::collections::HashMap;
+
+    fn commit(hash: &str, subject: &str) -> CommitInfo {
+        CommitInfo {
+            hash: hash.into(),
+            short_hash: hash.into(),
+            subject: subject.into(),
+            author: "dev".into(),
+            date: String::new(),
+            relative_date: String::new(),
+            file_count: 0,
+            adds: 0,
+            dels: 0,
+            is_merge: false,
+        }
+    }
+
+    #[test]
+    fn history_commits_are_linted_with_their_full_message() {
+        let mut app = App::new_for_test(vec![]);
+        app.config.commit_lint.require_issue = true;
+        let messages: HashMap<String, String> =
+            [("a".to_string(), "Fix login\n\nFixes #4".to_string())].into();
+        app.tab_mut().history = Some(HistoryState {
+            commits: vec![commit("a", "Fix login"), commit("b", "Fixed logout")],
+            selected_commit: 0,
+            commit_files: Vec::new(),
+            selected_file: 0,
+            current_hunk: 0,
+            current_line: None,
+            diff_scroll: 0,
+            h_scroll: 0,
+            all_loaded: true,
+            diff_cache: DiffCache::new(5),
+            messages,
+            net_zero: Default::default(),
+            fold_net_zero: false,
+            search: Default::default(),
+            prs: Default::default(),
+        });
+        let commits = app.tab().history.as_ref().unwrap().commits.clone();
+        assert!(app.commit_lint_issues(&commits[0]).is_empty());
+        assert_eq!(app.commit_lint_issues(&commits[1]).len(), 2);
+        assert_eq!(app.history_lint_count(), 1);
+
+        app.tab_mut().commit_input = "Adding things".into();
+        assert_eq!(app.commit_input_lint().len(), 2);
+    }
+}
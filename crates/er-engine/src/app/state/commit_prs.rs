@@ -0,0 +1,243 @@
+//! The PR that merged each History commit, and Enter to review it.
+//!
+//! Merge and squash subjects name their PR (`Merge pull request #12 from
+//! …`, `Fix login (#12)`), as do `PR:` / `Pull-Request:` / `PR-URL:`
+//! trailers. Commits without one are looked up with `gh api
+//! commits/{sha}/pulls` on a worker thread, once each; only merged PRs
+//! count, so commits of the branch's own open PR stay unannotated. Enter
+//! on a commit opens its PR in a new tab, like `er --pr`.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, HistoryState, TabState};
+use crate::git::CommitInfo;
+
+/// A finished lookup: a commit's hash and its merged PR.
+type Lookup = (String, Option<u64>);
+
+/// PRs looked up through `gh`.
+#[derive(Default)]
+pub struct CommitPrState {
+    /// By full hash; `None` when no merged PR contains the commit
+    pub looked_up: HashMap<String, Option<u64>>,
+    /// Lookups as they finish; `None` when `gh` failed and the rest of
+    /// the pass was dropped
+    rx: Option<Receiver<Option<Lookup>>>,
+    /// `gh` failed (not installed, offline, not a GitHub remote): no more
+    /// passes for this history
+    unavailable: bool,
+}
+
+/// `#12` from a merge or squash subject.
+pub fn pr_from_subject(subject: &str) -> Option<u64> {
+    if let Some(rest) = subject.strip_prefix("Merge pull request #") {
+        return rest
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok();
+    }
+    let inner = subject.trim_end().strip_suffix(')')?;
+    let (_, number) = inner.rsplit_once("(#")?;
+    number.parse().ok()
+}
+
+/// The PR a `PR:`, `Pull-Request:` or `PR-URL:` trailer names, as `#12`
+/// or a `…/pull/12` link.
+pub fn pr_from_trailers(message: &str) -> Option<u64> {
+    message.lines().rev().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim().to_ascii_lowercase();
+        if !matches!(key.as_str(), "pr" | "pull-request" | "pr-url") {
+            return None;
+        }
+        let value = value.trim();
+        let number = match value.rsplit_once("/pull/") {
+            Some((_, n)) => n.trim_end_matches('/'),
+            None => value.strip_prefix('#')?,
+        };
+        number.parse().ok()
+    })
+}
+
+impl HistoryState {
+    /// The PR that merged `commit`, when known.
+    pub fn commit_pr(&self, commit: &CommitInfo) -> Option<u64> {
+        pr_from_subject(&commit.subject)
+            .or_else(|| {
+                self.messages
+                    .get(&commit.hash)
+                    .and_then(|m| pr_from_trailers(m))
+            })
+            .or_else(|| self.prs.looked_up.get(&commit.hash).copied().flatten())
+    }
+
+    /// Loaded commits that name no PR and weren't looked up yet.
+    fn unresolved_commits(&self) -> Vec<String> {
+        self.commits
+            .iter()
+            .filter(|c| {
+                !self.prs.looked_up.contains_key(&c.hash)
+                    && pr_from_subject(&c.subject).is_none()
+                    && self
+                        .messages
+                        .get(&c.hash)
+                        .and_then(|m| pr_from_trailers(m))
+                        .is_none()
+            })
+            .map(|c| c.hash.clone())
+            .collect()
+    }
+}
+
+impl TabState {
+    /// The selected History commit's PR.
+    pub fn selected_commit_pr(&self) -> Option<u64> {
+        let history = self.history.as_ref()?;
+        history.commit_pr(history.commits.get(history.selected_commit)?)
+    }
+}
+
+impl App {
+    /// Look up the PRs of loaded History commits in the background and
+    /// store what arrived. True when the commit list needs a redraw.
+    pub fn poll_commit_prs(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if tab.mode != DiffMode::History || tab.is_remote() {
+            return false;
+        }
+        let repo_root = tab.repo_root.clone();
+        let Some(history) = tab.history.as_mut() else {
+            return false;
+        };
+        let prs = &mut history.prs;
+        if let Some(rx) = &prs.rx {
+            let mut changed = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(Some((hash, pr))) => {
+                        changed |= pr.is_some();
+                        prs.looked_up.insert(hash, pr);
+                    }
+                    Ok(None) => prs.unavailable = true,
+                    Err(TryRecvError::Empty) => return changed,
+                    Err(TryRecvError::Disconnected) => {
+                        prs.rx = None;
+                        return changed;
+                    }
+                }
+            }
+        }
+        if prs.unavailable {
+            return false;
+        }
+        let hashes = history.unresolved_commits();
+        if hashes.is_empty() {
+            return false;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (owner, repo) = match crate::github::get_repo_info(&repo_root) {
+                Ok(slug) => slug,
+                Err(e) => {
+                    crate::debug_log::warn("github", format!("commit PRs: {:#}", e));
+                    let _ = tx.send(None);
+                    return;
+                }
+            };
+            for hash in hashes {
+                match crate::github::gh_commit_pr(&owner, &repo, &hash) {
+                    Ok(pr) => {
+                        if tx.send(Some((hash, pr))).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        crate::debug_log::warn("github", format!("commit PRs: {:#}", e));
+                        let _ = tx.send(None);
+                        return;
+                    }
+                }
+            }
+        });
+        // Commits loaded meanwhile get their own pass after this one
+        history.prs.rx = Some(rx);
+        false
+    }
+
+    /// Enter in History: review the selected commit's PR in a new tab, or
+    /// switch to the tab already reviewing it.
+    pub fn open_commit_pr(&mut self) {
+        let Some(number) = self.tab().selected_commit_pr() else {
+            self.notify("No PR known for this commit");
+            return;
+        };
+        if self.tab().is_remote() {
+            self.notify(&format!("Commit was merged in PR #{}", number));
+            return;
+        }
+        let repo_root = self.tab().repo_root.clone();
+        if let Some(idx) = self
+            .tabs
+            .iter()
+            .position(|t| t.repo_root == repo_root && t.pr_number == Some(number))
+        {
+            self.active_tab = idx;
+            self.notify(&format!("Switched to tab: {}", self.tab().tab_name()));
+            return;
+        }
+        match TabState::new_pr_head_review(repo_root, number) {
+            Ok(tab) => {
+                self.open_tab(tab);
+            }
+            Err(e) => self.report_command_error(&format!("Opening PR #{} failed", number), &e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subjects_and_trailers_name_the_pr() {
+        assert_eq!(
+            pr_from_subject("Merge pull request #123 from alice/login"),
+            Some(123)
+        );
+        assert_eq!(pr_from_subject("Fix login redirect (#45)"), Some(45));
+        assert_eq!(pr_from_subject("Fix (#45) later"), None);
+        assert_eq!(pr_from_subject("Bump to 1.2 (final)"), None);
+
+        let message =
+            "Add retries\n\nBody text.\n\nPR-URL: https://github.com/o/r/pull/88\nSigned-off-by: A";
+        assert_eq!(pr_from_trailers(message), Some(88));
+        assert_eq!(pr_from_trailers("Add\n\nPull-Request: #9"), Some(9));
+        assert_eq!(pr_from_trailers("Add\n\nSee: #9"), None);
+    }
+
+    #[test]
+    fn enter_needs_a_known_pr() {
+        let mut app = App::new_for_test(vec![]);
+        let commits = vec![
+            crate::app::fixtures::commit(0, "Merge pull request #7 from dev/x"),
+            crate::app::fixtures::commit(1, "Tidy up"),
+        ];
+        crate::app::fixtures::history(&mut app, commits, vec![]);
+        assert_eq!(app.tab().selected_commit_pr(), Some(7));
+        let history = app.tab_mut().history.as_mut().unwrap();
+        assert_eq!(history.unresolved_commits().len(), 1);
+        history.selected_commit = 1;
+        let hash = history.commits[1].hash.clone();
+        history.prs.looked_up.insert(hash, Some(31));
+        assert_eq!(app.tab().selected_commit_pr(), Some(31));
+        assert!(app
+            .tab()
+            .history
+            .as_ref()
+            .unwrap()
+            .unresolved_commits()
+            .is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+//! The `/` search in History mode.
+//!
+//! Every word of the query has to match. A plain word matches the subject,
+//! hash or author; `author:`, `hash:`, `subject:` and `path:` narrow it to
+//! one field, and `before:` / `after:` take a date (`2024`, `2024-06`,
+//! `2024-06-15`; `after:` includes the date itself). A `/…/` value is a
+//! regex, quotes keep spaces in one word, and matching ignores case. A
+//! word with an unknown field, like `fix:`, is plain text.
+//!
+//! Only 50 commits load up front, so while a search is active the rest of
+//! the branch streams in on a worker thread, a page at a time. `path:`
+//! needs the files of every commit, read once by another worker.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use regex::{Regex, RegexBuilder};
+
+use super::{App, DiffMode, TabState};
+use crate::git::{self, CommitInfo};
+
+/// Commits the search loader reads per page.
+const SEARCH_PAGE: usize = 200;
+
+/// A field a search word can be narrowed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitField {
+    /// Subject, hash or author
+    Any,
+    Author,
+    Hash,
+    Subject,
+    Path,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Match(CommitField, Regex),
+    Before(String),
+    After(String),
+}
+
+/// A parsed History search.
+#[derive(Debug, Clone, Default)]
+pub struct CommitQuery {
+    terms: Vec<Term>,
+    /// Why a word was left out (a bad regex or date)
+    pub error: Option<String>,
+}
+
+/// Commits loaded and files read in the background for the search.
+#[derive(Default)]
+pub struct CommitSearchState {
+    pages_rx: Option<Receiver<CommitPage>>,
+    paths_rx: Option<Receiver<HashMap<String, Vec<String>>>>,
+    /// Files each commit touched, once read
+    pub commit_paths: Option<HashMap<String, Vec<String>>>,
+}
+
+impl CommitSearchState {
+    /// Whether the rest of the history is streaming in.
+    pub fn streaming(&self) -> bool {
+        self.pages_rx.is_some()
+    }
+
+    /// Whether commits or files are still coming in.
+    pub fn loading(&self) -> bool {
+        self.pages_rx.is_some() || self.paths_rx.is_some()
+    }
+}
+
+struct CommitPage {
+    commits: Vec<CommitInfo>,
+    messages: HashMap<String, String>,
+    last: bool,
+}
+
+/// Split on whitespace, keeping `"quoted words"` together.
+fn words(query: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn is_date(value: &str) -> bool {
+    let shape = value.len() == 4 || value.len() == 7 || value.len() == 10;
+    shape
+        && value.char_indices().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+impl CommitQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = CommitQuery::default();
+        for word in words(query) {
+            let (field, value) = match word.split_once(':') {
+                Some((name, value)) if !value.is_empty() => match name {
+                    "author" => (Some(CommitField::Author), value),
+                    "hash" => (Some(CommitField::Hash), value),
+                    "subject" => (Some(CommitField::Subject), value),
+                    "path" => (Some(CommitField::Path), value),
+                    "before" | "after" => {
+                        if !is_date(value) {
+                            parsed.error = Some(format!("{}: takes a date like 2024-06-15", name));
+                        } else if name == "before" {
+                            parsed.terms.push(Term::Before(value.to_string()));
+                        } else {
+                            parsed.terms.push(Term::After(value.to_string()));
+                        }
+                        continue;
+                    }
+                    _ => (None, word.as_str()),
+                },
+                _ => (None, word.as_str()),
+            };
+            let field = field.unwrap_or(CommitField::Any);
+            let pattern = match value.strip_prefix('/').and_then(|v| v.strip_suffix('/')) {
+                Some(re) if !re.is_empty() => re.to_string(),
+                _ => regex::escape(value),
+            };
+            match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                Ok(re) => parsed.terms.push(Term::Match(field, re)),
+                Err(e) => {
+                    parsed.error = Some(
+                        e.to_string()
+                            .lines()
+                            .last()
+                            .unwrap_or("invalid regex")
+                            .trim()
+                            .to_string(),
+                    )
+                }
+            }
+        }
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether a `path:` word needs the files of each commit.
+    pub fn needs_paths(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|t| matches!(t, Term::Match(CommitField::Path, _)))
+    }
+
+    /// Whether `commit` matches every word. `path:` matches nothing until
+    /// `paths` is read.
+    pub fn matches(
+        &self,
+        commit: &CommitInfo,
+        paths: Option<&HashMap<String, Vec<String>>>,
+    ) -> bool {
+        self.terms.iter().all(|term| match term {
+            Term::Match(field, re) => match field {
+                CommitField::Any => {
+                    re.is_match(&commit.subject)
+                        || re.is_match(&commit.hash)
+                        || re.is_match(&commit.author)
+                }
+                CommitField::Author => re.is_match(&commit.author),
+                CommitField::Hash => re.is_match(&commit.hash),
+                CommitField::Subject => re.is_match(&commit.subject),
+                CommitField::Path => paths
+                    .and_then(|p| p.get(&commit.hash))
+                    .is_some_and(|files| files.iter().any(|f| re.is_match(f))),
+            },
+            Term::Before(date) => commit.date.as_str() < date.as_str(),
+            Term::After(date) => {
+                commit.date.get(..date.len()).unwrap_or(&commit.date) >= date.as_str()
+            }
+        })
+    }
+
+    /// Byte ranges in `text`, the commit's `field`, to highlight: the
+    /// non-empty matches of the words for that field or for any.
+    pub fn highlights(&self, field: CommitField, text: &str) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = self
+            .terms
+            .iter()
+            .filter_map(|t| match t {
+                Term::Match(f, re) if *f == field || *f == CommitField::Any => Some(re),
+                _ => None,
+            })
+            .flat_map(|re| {
+                re.find_iter(text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.range())
+            })
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        // Merge overlaps so each byte is marked once
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        merged
+    }
+}
+
+impl TabState {
+    /// The History search, when one is typed.
+    pub fn commit_query(&self) -> Option<CommitQuery> {
+        if self.mode != DiffMode::History || self.search_query.is_empty() {
+            return None;
+        }
+        Some(CommitQuery::parse(&self.search_query))
+    }
+}
+
+impl App {
+    /// Stream in the rest of the history and the commit files while a
+    /// History search needs them, and store what arrived. True when the
+    /// commit list needs a redraw.
+    pub fn poll_commit_search(&mut self) -> bool {
+        let query = self.tab().commit_query().filter(|q| !q.is_empty());
+        let log_root = self.tab().commit_log_root().to_string();
+        let head_ref = self.tab().commit_head_ref().to_string();
+        let tab = self.tab_mut();
+        let base = tab.base_branch.clone();
+        let Some(history) = tab.history.as_mut() else {
+            return false;
+        };
+        let mut changed = false;
+
+        if let Some(rx) = &history.search.paths_rx {
+            match rx.try_recv() {
+                Err(TryRecvError::Empty) => {}
+                Ok(paths) => {
+                    history.search.commit_paths = Some(paths);
+                    history.search.paths_rx = None;
+                    changed = true;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    history.search.commit_paths = Some(HashMap::new());
+                    history.search.paths_rx = None;
+                }
+            }
+        }
+
+        let Some(query) = query else {
+            // Dropping the receiver stops the loader at its next page
+            history.search.pages_rx = None;
+            return changed;
+        };
+
+        if let Some(rx) = history.search.pages_rx.take() {
+            let mut done = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(page) => {
+                        done |= page.last;
+                        history.messages.extend(page.messages);
+                        history.commits.extend(page.commits);
+                        changed = true;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        done = true;
+                        break;
+                    }
+                }
+            }
+            if done {
+                history.all_loaded = true;
+            } else {
+                history.search.pages_rx = Some(rx);
+            }
+        } else if !history.all_loaded {
+            let skip = history.commits.len();
+            let (tx, rx) = mpsc::channel();
+            let (base, head_ref, log_root) = (base.clone(), head_ref.clone(), log_root.clone());
+            std::thread::spawn(move || {
+                let mut skip = skip;
+                loop {
+                    let commits =
+                        git::git_log_range(&base, &head_ref, &log_root, SEARCH_PAGE, skip)
+                            .unwrap_or_default();
+                    let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+                    let messages = git::git_commit_messages(&log_root, &hashes).unwrap_or_default();
+                    let last = commits.len() < SEARCH_PAGE;
+                    skip += commits.len();
+                    let page = CommitPage {
+                        commits,
+                        messages,
+                        last,
+                    };
+                    if tx.send(page).is_err() || last {
+                        break;
+                    }
+                }
+            });
+            history.search.pages_rx = Some(rx);
+        }
+
+        let search = &mut history.search;
+        if query.needs_paths() && search.commit_paths.is_none() && search.paths_rx.is_none() {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let paths = git::git_log_paths(&base, &head_ref, &log_root);
+                match paths {
+                    Ok(paths) => {
+                        let _ = tx.send(paths);
+                    }
+                    Err(e) => crate::debug_log::warn("git", format!("commit paths: {:#}", e)),
+                }
+            });
+            search.paths_rx = Some(rx);
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, subject: &str, author: &str, date: &str) -> CommitInfo {
+        CommitInfo {
+            hash: hash.into(),
+            short_hash: hash[..4].into(),
+            subject: subject.into(),
+            author: author.into(),
+            date: date.into(),
+            relative_date: String::new(),
+            file_count: 1,
+            adds: 1,
+            dels: 0,
+            is_merge: false,
+        }
+    }
+
+    #[test]
+    fn fielded_words_and_regexes_all_have_to_match() {
+        let a = commit(
+            "aaaa1111",
+            "Fix login redirect",
+            "Alice",
+            "2024-05-30T10:00:00+02:00",
+        );
+        let b = commit(
+            "bbbb2222",
+            "fix: logout",
+            "Bob",
+            "2024-06-02T10:00:00+02:00",
+        );
+
+        let q = CommitQuery::parse("fix author:ali");
+        assert!(q.matches(&a, None) && !q.matches(&b, None));
+        let q = CommitQuery::parse("before:2024-06");
+        assert!(q.matches(&a, None) && !q.matches(&b, None));
+        let q = CommitQuery::parse("after:2024-06-02");
+        assert!(!q.matches(&a, None) && q.matches(&b, None));
+        // An unknown field is plain text
+        assert!(CommitQuery::parse("fix:").matches(&b, None));
+        assert!(!CommitQuery::parse("fix:").matches(&a, None));
+        let q = CommitQuery::parse("subject:/^fix\\b.*redirect$/");
+        assert!(q.matches(&a, None) && !q.matches(&b, None));
+        assert!(CommitQuery::parse("\"login redirect\"").matches(&a, None));
+        assert!(CommitQuery::parse("hash:bbbb").matches(&b, None));
+
+        let q = CommitQuery::parse("subject:/(/ before:june");
+        assert!(q.error.is_some());
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn path_words_wait_for_the_commit_files() {
+        let mut app = App::new_for_test(vec![]);
+        let commits = vec![
+            commit("aaaa1111", "Add endpoint", "Alice", ""),
+            commit("bbbb2222", "Docs", "Bob", ""),
+        ];
+        crate::app::fixtures::history(&mut app, commits, vec![]);
+        app.tab_mut().search_query = "path:src/api".into();
+        assert!(app.tab().commit_query().unwrap().needs_paths());
+        assert!(app.tab().visible_commits().is_empty());
+
+        app.tab_mut().history.as_mut().unwrap().search.commit_paths = Some(
+            [
+                ("aaaa1111".to_string(), vec!["src/api/users.rs".to_string()]),
+                ("bbbb2222".to_string(), vec!["README.md".to_string()]),
+            ]
+            .into(),
+        );
+        let shown: Vec<usize> = app
+            .tab()
+            .visible_commits()
+            .iter()
+            .map(|(i, _)| *i)
+            .collect();
+        assert_eq!(shown, [0]);
+        app.tab_mut().search_query = "path:/\\.md$/ docs".into();
+        let shown: Vec<usize> = app
+            .tab()
+            .visible_commits()
+            .iter()
+            .map(|(i, _)| *i)
+            .collect();
+        assert_eq!(shown, [1]);
+    }
+
+    #[test]
+    fn highlights_merge_overlapping_matches() {
+        let q = CommitQuery::parse("log login author:log");
+        assert_eq!(q.highlights(CommitField::Subject, "Fix login"), vec![4..9]);
+        assert_eq!(q.highlights(CommitField::Author, "Logan"), vec![0..3]);
+    }
+}
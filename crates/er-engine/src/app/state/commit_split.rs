@@ -0,0 +1,421 @@
+//! Commit splitting: turn a messy working tree into a series of clean
+//! commits. `B` assigns the current hunk (or its whole file) to a named
+//! commit bucket — new buckets take their message from the commit editor —
+//! and running the plan stages and commits each bucket in order.
+//!
+//! Works from the Unstaged diff (with nothing staged yet) or the Staged one.
+//! In Staged mode the index is emptied first and whatever no bucket claims
+//! is staged again afterwards. Hunks are staged as patches with
+//! `git apply --cached`, so the working tree is never touched.
+
+use anyhow::Result;
+
+use super::{App, DiffMode, HubAction, HubItem, HubKind, InputMode, OverlayData, TabState};
+use crate::git::{self, DiffFile, DiffHunk};
+
+/// A file, or one of its hunks (by header), claimed by a bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitItem {
+    pub path: String,
+    /// `None` = the whole file
+    pub hunk: Option<String>,
+}
+
+impl SplitItem {
+    pub fn label(&self) -> String {
+        match &self.hunk {
+            Some(header) => format!("{} {}", self.path, header),
+            None => self.path.clone(),
+        }
+    }
+
+    /// Whether both claim some of the same lines.
+    fn overlaps(&self, other: &SplitItem) -> bool {
+        self.path == other.path
+            && (self.hunk.is_none() || other.hunk.is_none() || self.hunk == other.hunk)
+    }
+}
+
+/// One planned commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitBucket {
+    pub message: String,
+    pub items: Vec<SplitItem>,
+}
+
+impl TabState {
+    /// The bucket holding `path` (or one of its hunks), 1-based, for the tree.
+    pub fn commit_bucket_of(&self, path: &str) -> Option<usize> {
+        self.commit_plan
+            .iter()
+            .position(|b| b.items.iter().any(|i| i.path == path))
+            .map(|idx| idx + 1)
+    }
+
+    /// The current hunk, or `None` when the selection has no hunks.
+    fn split_hunk_item(&self) -> Option<SplitItem> {
+        let file = self.files.get(self.selected_file)?;
+        let hunk = file.hunks.get(self.current_hunk)?;
+        Some(SplitItem {
+            path: file.path.clone(),
+            hunk: Some(hunk.header.clone()),
+        })
+    }
+
+    /// Move `item` into bucket `idx`, dropping whatever claimed the same lines.
+    pub fn assign_to_bucket(&mut self, idx: usize, item: SplitItem) {
+        for bucket in &mut self.commit_plan {
+            bucket.items.retain(|i| !i.overlaps(&item));
+        }
+        if let Some(bucket) = self.commit_plan.get_mut(idx) {
+            bucket.items.push(item);
+        }
+        self.commit_plan.retain(|b| !b.items.is_empty());
+    }
+}
+
+/// The hunks of `file` an item stands for, `None` when its hunk is gone.
+fn item_hunks<'a>(file: &'a DiffFile, item: &SplitItem) -> Option<Vec<&'a DiffHunk>> {
+    match &item.hunk {
+        None => Some(file.hunks.iter().collect()),
+        Some(header) => file
+            .hunks
+            .iter()
+            .find(|h| &h.header == header)
+            .map(|h| vec![h]),
+    }
+}
+
+impl App {
+    fn split_available(&mut self) -> bool {
+        if self.blocked_by_read_only("committing") {
+            return false;
+        }
+        let tab = self.tab();
+        if tab.is_remote() || !matches!(tab.mode, DiffMode::Unstaged | DiffMode::Staged) {
+            self.notify("Commit splitting works in Unstaged or Staged mode");
+            return false;
+        }
+        if tab.mode == DiffMode::Staged
+            && tab.committed_unpushed
+            && !git::has_staged_changes(&tab.repo_root)
+        {
+            self.notify("Nothing staged — the Staged view shows the last commit");
+            return false;
+        }
+        true
+    }
+
+    /// `B` / Git hub → Commit plan: pick the bucket for the current hunk or
+    /// its file, or run the plan.
+    pub fn open_commit_plan(&mut self) {
+        if !self.split_available() {
+            return;
+        }
+        let tab = self.tab();
+        let header = |label: String| HubItem {
+            label,
+            hint: "".into(),
+            description: "".into(),
+            action: HubAction::Noop,
+            is_header: true,
+            enabled: false,
+        };
+        let bucket_rows = |item: &SplitItem| {
+            let mut rows: Vec<HubItem> = tab
+                .commit_plan
+                .iter()
+                .enumerate()
+                .map(|(idx, bucket)| HubItem {
+                    label: format!("{}. {}", idx + 1, bucket.message),
+                    hint: "".into(),
+                    description: format!(
+                        "{} item{}",
+                        bucket.items.len(),
+                        if bucket.items.len() == 1 { "" } else { "s" }
+                    ),
+                    action: HubAction::AssignToCommit {
+                        bucket: Some(idx),
+                        path: item.path.clone(),
+                        hunk: item.hunk.clone(),
+                    },
+                    is_header: false,
+                    enabled: true,
+                })
+                .collect();
+            rows.push(HubItem {
+                label: "New commit…".into(),
+                hint: "".into(),
+                description: "Type its message in the commit editor".into(),
+                action: HubAction::AssignToCommit {
+                    bucket: None,
+                    path: item.path.clone(),
+                    hunk: item.hunk.clone(),
+                },
+                is_header: false,
+                enabled: true,
+            });
+            rows
+        };
+
+        let mut items = Vec::new();
+        if let Some(hunk) = tab.split_hunk_item() {
+            let file = SplitItem {
+                path: hunk.path.clone(),
+                hunk: None,
+            };
+            items.push(header(format!(
+                "── This hunk ({}/{}) ──",
+                tab.current_hunk + 1,
+                tab.files[tab.selected_file].hunks.len()
+            )));
+            items.extend(bucket_rows(&hunk));
+            items.push(header(format!("── Whole file: {} ──", file.path)));
+            items.extend(bucket_rows(&file));
+        } else if let Some(file) = tab.files.get(tab.selected_file) {
+            let file = SplitItem {
+                path: file.path.clone(),
+                hunk: None,
+            };
+            items.push(header(format!("── Whole file: {} ──", file.path)));
+            items.extend(bucket_rows(&file));
+        }
+
+        let planned: usize = tab.commit_plan.iter().map(|b| b.items.len()).sum();
+        if planned > 0 {
+            items.push(header(format!(
+                "── Plan: {} commit{} ──",
+                tab.commit_plan.len(),
+                if tab.commit_plan.len() == 1 { "" } else { "s" }
+            )));
+            for (idx, bucket) in tab.commit_plan.iter().enumerate() {
+                items.push(HubItem {
+                    label: format!("{}. {}", idx + 1, bucket.message),
+                    hint: "".into(),
+                    description: bucket
+                        .items
+                        .iter()
+                        .map(SplitItem::label)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    action: HubAction::Noop,
+                    is_header: false,
+                    enabled: false,
+                });
+            }
+            items.push(HubItem {
+                label: "Run plan".into(),
+                hint: "".into(),
+                description: "Stage and commit each bucket in order".into(),
+                action: HubAction::RunCommitPlan,
+                is_header: false,
+                enabled: true,
+            });
+            items.push(HubItem {
+                label: "Clear plan".into(),
+                hint: "".into(),
+                description: "Forget every bucket — nothing is staged or committed".into(),
+                action: HubAction::ClearCommitPlan,
+                is_header: false,
+                enabled: true,
+            });
+        }
+        let selected = items.iter().position(|i| i.enabled).unwrap_or(0);
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::CommitPlan,
+            title: None,
+            items,
+            selected,
+        });
+    }
+
+    /// Put an item in bucket `bucket`, or ask for a new bucket's message.
+    pub fn assign_to_commit(&mut self, bucket: Option<usize>, path: String, hunk: Option<String>) {
+        let item = SplitItem { path, hunk };
+        match bucket {
+            Some(idx) => {
+                let label = item.label();
+                self.tab_mut().assign_to_bucket(idx, item);
+                self.notify(&format!("{} → commit {}", label, idx + 1));
+            }
+            None => {
+                self.tab_mut().split_pending = Some(item);
+                self.start_commit();
+            }
+        }
+    }
+
+    /// Commit editor submit while an item waits for a new bucket.
+    pub(super) fn submit_split_bucket(&mut self, message: String) {
+        let tab = self.tab_mut();
+        let Some(item) = tab.split_pending.take() else {
+            return;
+        };
+        tab.commit_plan.push(CommitBucket {
+            message,
+            items: Vec::new(),
+        });
+        let idx = tab.commit_plan.len() - 1;
+        let label = item.label();
+        tab.assign_to_bucket(idx, item);
+        tab.commit_input.clear();
+        self.input_mode = InputMode::Normal;
+        self.notify(&format!("{} → commit {}", label, idx + 1));
+    }
+
+    pub fn clear_commit_plan(&mut self) {
+        self.tab_mut().commit_plan.clear();
+        self.notify("Commit plan cleared");
+    }
+
+    /// Stage and commit each bucket in order. Stops at the first failure,
+    /// keeping the buckets not yet committed.
+    pub fn run_commit_plan(&mut self) -> Result<()> {
+        if !self.split_available() {
+            return Ok(());
+        }
+        let tab = self.tab();
+        let repo_root = tab.repo_root.clone();
+        let staged_mode = tab.mode == DiffMode::Staged;
+        if !staged_mode && git::has_staged_changes(&repo_root) {
+            self.notify("Something is already staged — plan from Staged mode, or unstage it first");
+            return Ok(());
+        }
+        let files = tab.files.clone();
+        let plan = tab.commit_plan.clone();
+        let find = |path: &str| files.iter().find(|f| f.path == path);
+
+        // Staged changes no bucket claims, staged again at the end
+        let leftovers: Vec<String> = if staged_mode {
+            files
+                .iter()
+                .filter_map(|file| {
+                    let free: Vec<&DiffHunk> = file
+                        .hunks
+                        .iter()
+                        .filter(|h| {
+                            let item = SplitItem {
+                                path: file.path.clone(),
+                                hunk: Some(h.header.clone()),
+                            };
+                            !plan
+                                .iter()
+                                .flat_map(|b| &b.items)
+                                .any(|i| i.overlaps(&item))
+                        })
+                        .collect();
+                    (!free.is_empty()).then(|| git::file_patch(file, &free, false, None).ok())?
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if staged_mode {
+            if let Err(e) = git::git_unstage_all(&repo_root) {
+                self.report_command_error("Unstaging before the plan failed", &e);
+                return Ok(());
+            }
+        }
+
+        let mut committed = 0;
+        let mut failure = None;
+        for bucket in &plan {
+            let staged: Result<()> = bucket.items.iter().try_for_each(|item| {
+                let file = find(&item.path)
+                    .ok_or_else(|| anyhow::anyhow!("{} is no longer in the diff", item.path))?;
+                if item.hunk.is_none() && !staged_mode {
+                    return git::git_stage_file(&repo_root, &item.path);
+                }
+                let hunks = item_hunks(file, item).ok_or_else(|| {
+                    anyhow::anyhow!("{} changed since it was planned", item.label())
+                })?;
+                git::stage_patch(&repo_root, &git::file_patch(file, &hunks, false, None)?)
+            });
+            match staged.and_then(|()| git::git_commit(&repo_root, &bucket.message)) {
+                Ok(()) => committed += 1,
+                Err(e) => {
+                    failure = Some((bucket.message.clone(), e));
+                    break;
+                }
+            }
+        }
+        if failure.is_some() {
+            // Don't fold a half-staged bucket into whatever gets committed next
+            let _ = git::git_unstage_all(&repo_root);
+        }
+        let restaged = leftovers
+            .iter()
+            .filter(|patch| git::stage_patch(&repo_root, patch).is_ok())
+            .count();
+
+        let tab = self.tab_mut();
+        tab.commit_plan.drain(..committed);
+        if committed > 0 {
+            tab.committed_unpushed = true;
+            tab.staged_in_app = restaged > 0;
+        }
+        tab.refresh_diff()?;
+        match failure {
+            Some((message, e)) => self.report_command_error(
+                &format!(
+                    "Committed {}/{} — \"{}\" failed",
+                    committed,
+                    plan.len(),
+                    message
+                ),
+                &e,
+            ),
+            None if restaged < leftovers.len() => self.notify(&format!(
+                "Committed {} — {} unplanned change(s) could not be staged again",
+                committed,
+                leftovers.len() - restaged
+            )),
+            None => self.notify(&format!(
+                "Committed {} commit{}! Ctrl+P to push",
+                committed,
+                if committed == 1 { "" } else { "s" }
+            )),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &str, hunk: Option<&str>) -> SplitItem {
+        SplitItem {
+            path: path.into(),
+            hunk: hunk.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn assigning_moves_items_between_buckets() {
+        let mut tab = TabState::new_for_test(Vec::new());
+        tab.commit_plan = vec![
+            CommitBucket {
+                message: "feat: parser".into(),
+                items: vec![item("a.rs", Some("@@ -1 +1 @@")), item("b.rs", None)],
+            },
+            CommitBucket {
+                message: "docs".into(),
+                items: Vec::new(),
+            },
+        ];
+        tab.assign_to_bucket(1, item("a.rs", Some("@@ -9 +9 @@")));
+        assert_eq!(tab.commit_plan[0].items.len(), 2);
+        assert_eq!(tab.commit_bucket_of("a.rs"), Some(1));
+
+        // The whole file takes over both of its hunks
+        tab.assign_to_bucket(1, item("a.rs", None));
+        assert_eq!(tab.commit_plan[0].items, [item("b.rs", None)]);
+        assert_eq!(tab.commit_plan[1].items, [item("a.rs", None)]);
+
+        // A bucket emptied by a move is dropped
+        tab.assign_to_bucket(1, item("b.rs", None));
+        assert_eq!(tab.commit_plan.len(), 1);
+        assert_eq!(tab.commit_plan[0].message, "docs");
+    }
+}
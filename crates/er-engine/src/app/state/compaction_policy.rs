@@ -0,0 +1,297 @@
+//! Why a file is compacted — too big to load, learned, a matched pattern or
+//! over the line limit — shown on its summary, and the Compaction hub
+//! (Alt+c on a compacted file) to change that with one key: always expand
+//! the file, stop compacting the pattern it matched, or raise `max_lines`
+//! past it. Each choice goes into the repo's `.er-config.toml`
+//! `[large_files]`, which then takes precedence over the global one.
+
+use anyhow::Result;
+
+use super::compaction_profile::limit_above;
+use super::{App, HubAction, HubItem, HubKind, OverlayData, TabState};
+use crate::config::{self, ErConfig, LargeFilesConfig};
+use crate::git;
+
+/// The repo's own `[large_files]` when its `.er-config.toml` has one, else
+/// the global one.
+pub(super) fn resolve_large_files(repo_root: &str, global: &ErConfig) -> LargeFilesConfig {
+    config::load_repo_large_files(repo_root).unwrap_or_else(|| global.large_files.clone())
+}
+
+/// Why a file starts compacted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompactionReason {
+    /// Working-tree size in bytes, over `max_file_kb`
+    TooBig(u64),
+    /// Never scrolled in this repo (see `compaction_profile`)
+    Learned,
+    /// The compaction pattern it matched
+    Pattern(String),
+    /// Over `max_lines` changed lines
+    OverLimit(usize),
+}
+
+impl CompactionReason {
+    pub fn label(&self) -> String {
+        match self {
+            CompactionReason::TooBig(size) => {
+                format!("{} file, over the size limit", git::format_size(*size))
+            }
+            CompactionReason::Learned => "learned — never scrolled in this repo".into(),
+            CompactionReason::Pattern(pattern) => format!("matched pattern {}", pattern),
+            CompactionReason::OverLimit(limit) => format!("exceeds {} lines", limit),
+        }
+    }
+}
+
+/// A change to the repo's `[large_files]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompactionPolicy {
+    /// Add the path to `always_expand`
+    AlwaysExpand(String),
+    /// Add the pattern to `never_compact`
+    NeverCompact(String),
+    /// Set `max_lines`
+    RaiseLimit(usize),
+}
+
+impl TabState {
+    /// Why `path` is compacted, `None` when it isn't.
+    pub fn compaction_reason(&self, path: &str) -> Option<CompactionReason> {
+        if !self.files.iter().any(|f| f.path == path && f.compacted) {
+            return None;
+        }
+        if let Some(size) = self.large_file_size(path) {
+            return Some(CompactionReason::TooBig(size));
+        }
+        let config = &self.compaction_config;
+        if config.learned_compacted.iter().any(|p| p == path) {
+            return Some(CompactionReason::Learned);
+        }
+        if let Some(pattern) = config
+            .patterns
+            .iter()
+            .find(|p| git::compact_files_match(p, path))
+        {
+            return Some(CompactionReason::Pattern(pattern.clone()));
+        }
+        Some(CompactionReason::OverLimit(config.max_lines_before_compact))
+    }
+
+    /// Whether the selected file would start compacted under the current
+    /// policy.
+    fn selected_stays_compacted(&self) -> bool {
+        let Some(file) = self.selected_diff_file() else {
+            return false;
+        };
+        let config = &self.compaction_config;
+        if self.large_files.contains_key(&file.path) {
+            return !config.always_expand.contains(&file.path);
+        }
+        config.should_compact(&file.path, file.adds + file.dels)
+    }
+}
+
+/// The hub's items for `path`, compacted for `reason`: each change that
+/// would let it load, the ones that don't apply greyed out.
+fn policy_items(path: &str, reason: &CompactionReason, lines: usize) -> Vec<HubItem> {
+    let item = |label: String, hint: &str, description: String, policy, enabled| HubItem {
+        label,
+        hint: hint.into(),
+        description,
+        action: HubAction::SetCompactionPolicy(policy),
+        is_header: false,
+        enabled,
+    };
+    let pattern = match reason {
+        CompactionReason::Pattern(p) => p.clone(),
+        _ => String::new(),
+    };
+    let (limit, raised) = match reason {
+        CompactionReason::OverLimit(limit) => (*limit, limit_above(lines).max(limit + 500)),
+        _ => (0, 0),
+    };
+    vec![
+        item(
+            format!("Always expand {}", path),
+            "a",
+            "Add it to [large_files] always_expand".into(),
+            CompactionPolicy::AlwaysExpand(path.to_string()),
+            true,
+        ),
+        item(
+            if pattern.is_empty() {
+                "Never compact its pattern".into()
+            } else {
+                format!("Never compact {}", pattern)
+            },
+            "n",
+            "Add the pattern to [large_files] never_compact".into(),
+            CompactionPolicy::NeverCompact(pattern.clone()),
+            !pattern.is_empty(),
+        ),
+        item(
+            if raised == 0 {
+                "Raise the line limit".into()
+            } else {
+                format!("Raise max_lines to {}", raised)
+            },
+            "r",
+            if raised == 0 {
+                "Only for files over [large_files] max_lines".into()
+            } else {
+                format!("From {}; the file has {} changed lines", limit, lines)
+            },
+            CompactionPolicy::RaiseLimit(raised),
+            raised > 0,
+        ),
+    ]
+}
+
+impl App {
+    /// Alt+c on a compacted file: why it's compacted, and what to change.
+    pub fn open_compaction_hub(&mut self) {
+        let tab = self.tab();
+        let Some(file) = tab.selected_diff_file() else {
+            return;
+        };
+        let Some(reason) = tab.compaction_reason(&file.path) else {
+            self.notify("Not compacted — Alt+c changes the policy of a compacted file");
+            return;
+        };
+        let items = policy_items(&file.path, &reason, file.adds + file.dels);
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Compaction,
+            title: Some(format!("COMPACTED · {}", reason.label())),
+            items,
+            selected: 0,
+        });
+    }
+
+    /// `a` / `n` / `r` in the Compaction hub: the item with that hint.
+    pub fn compaction_hub_key(&self, key: char) -> Option<CompactionPolicy> {
+        let Some(OverlayData::ModalHub {
+            kind: HubKind::Compaction,
+            items,
+            ..
+        }) = &self.overlay
+        else {
+            return None;
+        };
+        items
+            .iter()
+            .find(|i| i.enabled && i.hint == key.to_string())
+            .and_then(|i| match &i.action {
+                HubAction::SetCompactionPolicy(policy) => Some(policy.clone()),
+                _ => None,
+            })
+    }
+
+    /// Write `policy` to the repo's `.er-config.toml` and compact by it,
+    /// loading the selected file when it no longer starts compacted.
+    pub fn set_compaction_policy(&mut self, policy: CompactionPolicy) -> Result<()> {
+        if self.tab().is_remote() {
+            self.notify_warn("Compaction policy is saved in a local clone's .er-config.toml");
+            return Ok(());
+        }
+        let repo_root = self.tab().repo_root.clone();
+        let mut large = resolve_large_files(&repo_root, &self.config);
+        let note = match &policy {
+            CompactionPolicy::AlwaysExpand(path) => {
+                if !large.always_expand.contains(path) {
+                    large.always_expand.push(path.clone());
+                }
+                format!("{} always expands", path)
+            }
+            CompactionPolicy::NeverCompact(pattern) => {
+                if !large.never_compact.contains(pattern) {
+                    large.never_compact.push(pattern.clone());
+                }
+                format!("{} is no longer compacted", pattern)
+            }
+            CompactionPolicy::RaiseLimit(max_lines) => {
+                large.max_lines = *max_lines;
+                format!("Files compact from {} changed lines", max_lines)
+            }
+        };
+        config::save_repo_section(&repo_root, "large_files", &large)?;
+        let tab = self.tab_mut();
+        tab.compaction_config = large.compaction();
+        tab.compaction_learning.enabled = large.learn;
+        tab.load_compaction_profile();
+        let expand = tab.selected_diff_file().is_some_and(|f| f.compacted)
+            && !tab.selected_stays_compacted();
+        if expand {
+            tab.toggle_compacted()?;
+        }
+        self.notify(&format!("{} — saved to .er-config.toml", note));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reasons_and_policies_per_compacted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        std::fs::write(
+            dir.path().join(".er-config.toml"),
+            "# team settings\n[todos]\nticket_pattern = \"JIRA-\"\n",
+        )
+        .unwrap();
+        let diff = "diff --git a/tools/deps.lock b/tools/deps.lock\n--- a/tools/deps.lock\n+++ b/tools/deps.lock\n\
+                    @@ -1 +1 @@\n-a\n+b\n\
+                    diff --git a/data/seed.sql b/data/seed.sql\n--- a/data/seed.sql\n+++ b/data/seed.sql\n\
+                    @@ -1 +1,2 @@\n-a\n+b\n+c\n\
+                    diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+                    @@ -1 +1 @@\n-a\n+b\n";
+        let mut app = App::new_for_test(git::parse_diff(diff));
+        let tab = app.tab_mut();
+        tab.repo_root = root.clone();
+        tab.compaction_config.max_lines_before_compact = 2;
+        git::compact_files(&mut tab.files, &tab.compaction_config);
+        tab.selected_file = 2;
+
+        assert_eq!(
+            tab.compaction_reason("tools/deps.lock"),
+            Some(CompactionReason::Pattern("*.lock".into()))
+        );
+        assert_eq!(
+            tab.compaction_reason("data/seed.sql").map(|r| r.label()),
+            Some("exceeds 2 lines".into())
+        );
+
+        let items = policy_items(
+            "tools/deps.lock",
+            &CompactionReason::Pattern("*.lock".into()),
+            2,
+        );
+        assert!(items[1].enabled && !items[2].enabled);
+        let items = policy_items("data/seed.sql", &CompactionReason::OverLimit(2000), 2600);
+        assert_eq!(
+            items[2].action,
+            HubAction::SetCompactionPolicy(CompactionPolicy::RaiseLimit(3500))
+        );
+
+        app.set_compaction_policy(CompactionPolicy::NeverCompact("*.lock".into()))
+            .unwrap();
+        let saved = std::fs::read_to_string(dir.path().join(".er-config.toml")).unwrap();
+        assert!(saved.starts_with(
+            "# team settings\n[todos]\nticket_pattern = \"JIRA-\"\n\n[large_files]\n"
+        ));
+        let large = config::load_repo_large_files(&root).unwrap();
+        assert_eq!(large.never_compact, ["*.lock"]);
+        assert!(!app
+            .tab()
+            .compaction_config
+            .should_compact("tools/deps.lock", 2));
+
+        app.set_compaction_policy(CompactionPolicy::RaiseLimit(4000))
+            .unwrap();
+        let large = config::load_repo_large_files(&root).unwrap();
+        assert_eq!((large.max_lines, large.never_compact.len()), (4000, 1));
+    }
+}
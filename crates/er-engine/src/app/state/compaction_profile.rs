@@ -0,0 +1,416 @@
+//! Compaction learned per repo (`[large_files] learn`): a file that starts
+//! compacted and gets expanded visit after visit stops being compacted, and
+//! a big file that's loaded but left without a scroll, visit after visit,
+//! starts compacted. When enough learned files point the same way, the
+//! profile suggests the config change that covers them all — a higher
+//! `max_lines`, or a `*.ext` pattern.
+//!
+//! The profile lives in repo-level storage (`compaction-profile.json`), so
+//! it carries across branches and sessions.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{App, DiffMode, TabState};
+use crate::git::{self, CompactionConfig};
+
+const PROFILE_FILE: &str = "compaction-profile.json";
+
+/// Expansions, or unscrolled visits, before a file's compaction flips.
+const LEARN_AT: u32 = 3;
+/// Loaded files with fewer changed lines aren't worth compacting.
+const MIN_LINES: usize = 300;
+/// A shorter visit is passing through on the way to another file.
+const MIN_VISIT: Duration = Duration::from_secs(3);
+/// Learned files that share a suggestion before it's made.
+const SUGGEST_AT: usize = 2;
+
+/// A `max_lines` that fits `lines` with a quarter of headroom, in steps
+/// of 500.
+pub(super) fn limit_above(lines: usize) -> usize {
+    (lines + lines / 4).div_ceil(500) * 500
+}
+
+/// How a file was used across visits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileUse {
+    /// Times it was expanded after starting compacted
+    #[serde(default)]
+    pub expanded: u32,
+    /// Visits in a row it was loaded and left without a scroll
+    #[serde(default)]
+    pub unscrolled: u32,
+    /// Changed lines when last seen
+    #[serde(default)]
+    pub lines: usize,
+    /// Compacted for its line count rather than a pattern
+    #[serde(default)]
+    pub over_limit: bool,
+}
+
+impl FileUse {
+    pub fn keeps_expanded(&self) -> bool {
+        self.expanded >= LEARN_AT
+    }
+
+    pub fn compacts(&self) -> bool {
+        self.unscrolled >= LEARN_AT
+    }
+}
+
+/// What a repo's reviewers do with compacted and big files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompactionProfile {
+    #[serde(default)]
+    pub files: BTreeMap<String, FileUse>,
+    /// Suggestions already made, so each is made once
+    #[serde(default)]
+    pub suggested: Vec<String>,
+}
+
+impl CompactionProfile {
+    /// `path` was expanded from compacted with `lines` changed lines. True
+    /// when that makes it stay expanded from now on.
+    fn record_expand(&mut self, path: &str, lines: usize, over_limit: bool) -> bool {
+        let file = self.files.entry(path.to_string()).or_default();
+        let was = file.keeps_expanded();
+        file.expanded += 1;
+        file.unscrolled = 0;
+        file.lines = lines;
+        file.over_limit = over_limit;
+        !was && file.keeps_expanded()
+    }
+
+    /// A visit to loaded `path` ended. True when that makes it start
+    /// compacted from now on.
+    fn record_visit(&mut self, path: &str, lines: usize, scrolled: bool) -> bool {
+        if scrolled {
+            // Read files only matter when they were on their way to compacting
+            if let Some(file) = self.files.get_mut(path) {
+                file.unscrolled = 0;
+                file.lines = lines;
+            }
+            return false;
+        }
+        let file = self.files.entry(path.to_string()).or_default();
+        let was = file.compacts();
+        file.unscrolled += 1;
+        file.lines = lines;
+        if file.compacts() {
+            file.expanded = 0;
+        }
+        !was && file.compacts()
+    }
+
+    /// Set the learned paths of `config`.
+    pub fn apply(&self, config: &mut CompactionConfig) {
+        let paths = |keep: fn(&FileUse) -> bool| -> Vec<String> {
+            self.files
+                .iter()
+                .filter(|(_, f)| keep(f))
+                .map(|(p, _)| p.clone())
+                .collect()
+        };
+        config.learned_expanded = paths(FileUse::keeps_expanded);
+        config.learned_compacted = paths(FileUse::compacts);
+    }
+
+    /// Config changes covering several learned files, as `(key, message)`:
+    /// a `max_lines` above the files over it that keep getting expanded,
+    /// and a pattern for never-scrolled files sharing an extension.
+    pub fn suggestions(&self, config: &CompactionConfig) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        let over: Vec<usize> = self
+            .files
+            .values()
+            .filter(|f| f.keeps_expanded() && f.over_limit)
+            .map(|f| f.lines)
+            .collect();
+        let biggest = over.iter().copied().max().unwrap_or(0);
+        if over.len() >= SUGGEST_AT && biggest > config.max_lines_before_compact {
+            let max_lines = limit_above(biggest);
+            out.push((
+                format!("max_lines={}", max_lines),
+                format!(
+                    "{} files over the {}-line limit keep getting expanded here — try [large_files] max_lines = {}",
+                    over.len(),
+                    config.max_lines_before_compact,
+                    max_lines
+                ),
+            ));
+        }
+        let mut by_ext: BTreeMap<String, usize> = BTreeMap::new();
+        for path in self
+            .files
+            .iter()
+            .filter(|(_, f)| f.compacts())
+            .map(|(p, _)| p)
+        {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            if let Some((stem, ext)) = name.rsplit_once('.') {
+                if !stem.is_empty() {
+                    *by_ext.entry(format!("*.{}", ext)).or_default() += 1;
+                }
+            }
+        }
+        for (pattern, count) in by_ext {
+            if count >= SUGGEST_AT && !config.patterns.contains(&pattern) {
+                out.push((
+                    format!("pattern={}", pattern),
+                    format!(
+                        "{} {} files are never scrolled here — they'd read better compacted as a pattern",
+                        count, pattern
+                    ),
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// A visit to a loaded file big enough to learn from.
+#[derive(Debug)]
+struct Visit {
+    path: String,
+    lines: usize,
+    since: Instant,
+    scrolled: bool,
+}
+
+/// A tab's side of the profile.
+#[derive(Debug, Default)]
+pub struct CompactionLearning {
+    /// Off with `[large_files] learn = false`, and for remote tabs
+    pub enabled: bool,
+    pub profile: CompactionProfile,
+    visit: Option<Visit>,
+    /// What was learned since the last poll, to tell
+    notes: Vec<String>,
+}
+
+impl CompactionLearning {
+    pub fn new(enabled: bool) -> Self {
+        CompactionLearning {
+            enabled,
+            ..Default::default()
+        }
+    }
+}
+
+impl TabState {
+    /// Load the repo's profile and compact by it.
+    pub(super) fn load_compaction_profile(&mut self) {
+        self.compaction_learning.profile = if self.compaction_learning.enabled && !self.is_remote()
+        {
+            std::fs::read_to_string(self.repo_level_path(PROFILE_FILE))
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default()
+        } else {
+            CompactionProfile::default()
+        };
+        self.compaction_learning
+            .profile
+            .apply(&mut self.compaction_config);
+    }
+
+    fn save_compaction_profile(&mut self) {
+        let path = self.repo_level_path(PROFILE_FILE);
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&self.compaction_learning.profile) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    fn learning(&self) -> bool {
+        self.compaction_learning.enabled && !self.is_remote() && self.mode != DiffMode::History
+    }
+
+    /// Apply what was learned, save it and queue what to tell.
+    fn learned(&mut self, note: Option<String>) {
+        let learning = &mut self.compaction_learning;
+        learning.notes.extend(note);
+        learning.profile.apply(&mut self.compaction_config);
+        for (key, message) in learning.profile.suggestions(&self.compaction_config) {
+            if !learning.profile.suggested.contains(&key) {
+                learning.profile.suggested.push(key);
+                learning.notes.push(message);
+            }
+        }
+        self.save_compaction_profile();
+    }
+
+    /// The selected file was just expanded from compacted. Files compacted
+    /// for their byte size don't count: that limit is about memory.
+    pub(super) fn learn_from_expand(&mut self, path: &str) {
+        if !self.learning() || self.large_files.contains_key(path) {
+            return;
+        }
+        let Some(file) = self.files.iter().find(|f| f.path == path) else {
+            return;
+        };
+        let lines = file.adds + file.dels;
+        let config = &self.compaction_config;
+        let over_limit = !config.learned_compacted.iter().any(|p| p == path)
+            && !config
+                .patterns
+                .iter()
+                .any(|p| git::compact_files_match(p, path));
+        let learned = self
+            .compaction_learning
+            .profile
+            .record_expand(path, lines, over_limit);
+        self.learned(learned.then(|| {
+            format!(
+                "{} keeps getting expanded — it won't start compacted in this repo anymore",
+                path
+            )
+        }));
+    }
+
+    /// Follow visits to big loaded files: whether the diff moved off the
+    /// top before the selection left, for visits long enough to be reads.
+    fn watch_compaction_visit(&mut self, now: Instant) {
+        let current = self
+            .selected_diff_file()
+            .filter(|f| !f.compacted && f.adds + f.dels >= MIN_LINES)
+            .map(|f| (f.path.clone(), f.adds + f.dels));
+        let moved = self.diff_scroll > 0 || self.current_hunk > 0 || self.current_line.is_some();
+        if let Some(visit) = &mut self.compaction_learning.visit {
+            if current
+                .as_ref()
+                .is_some_and(|(path, _)| *path == visit.path)
+            {
+                visit.scrolled |= moved;
+                return;
+            }
+        }
+        if let Some(visit) = self.compaction_learning.visit.take() {
+            if now.duration_since(visit.since) >= MIN_VISIT {
+                let learned = self.compaction_learning.profile.record_visit(
+                    &visit.path,
+                    visit.lines,
+                    visit.scrolled,
+                );
+                self.learned(learned.then(|| {
+                    format!(
+                        "{} is never scrolled — it starts compacted in this repo from now on",
+                        visit.path
+                    )
+                }));
+            }
+        }
+        self.compaction_learning.visit = current.map(|(path, lines)| Visit {
+            path,
+            lines,
+            since: now,
+            scrolled: false,
+        });
+    }
+}
+
+impl App {
+    /// Follow the active tab's visits and tell what the profile learned.
+    /// True when there was something to tell.
+    pub fn poll_compaction_learning(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if !tab.learning() {
+            return false;
+        }
+        tab.watch_compaction_visit(Instant::now());
+        let notes = std::mem::take(&mut tab.compaction_learning.notes);
+        for note in &notes {
+            self.notify(note);
+        }
+        !notes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(path: &str, lines: usize) -> String {
+        let adds: String = (0..lines).map(|i| format!("+line {}\n", i)).collect();
+        format!(
+            "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -0,0 +1,{} @@\n{}",
+            lines,
+            adds,
+            p = path
+        )
+    }
+
+    #[test]
+    fn expanded_files_stay_expanded_and_unread_ones_compact() {
+        let dir = tempfile::tempdir().unwrap();
+        let raw = diff("gen/api.ts", 400) + &diff("Cargo.lock", 10);
+        let mut tab = TabState::new_for_test(git::parse_diff(&raw));
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        tab.compaction_learning.enabled = true;
+
+        for _ in 0..LEARN_AT {
+            tab.learn_from_expand("Cargo.lock");
+        }
+        assert!(!tab.compaction_config.should_compact("Cargo.lock", 10));
+        assert_eq!(tab.compaction_learning.notes.len(), 1);
+
+        // A glance doesn't count, a read resets, three long unscrolled visits compact
+        let start = Instant::now();
+        let mut at = start;
+        let mut visit = |tab: &mut TabState, secs: u64, scrolled: bool| {
+            tab.selected_file = 0;
+            tab.current_hunk = 0;
+            tab.watch_compaction_visit(at);
+            if scrolled {
+                tab.current_hunk = 1;
+                tab.watch_compaction_visit(at);
+            }
+            at += Duration::from_secs(secs);
+            tab.selected_file = 1;
+            tab.watch_compaction_visit(at);
+        };
+        visit(&mut tab, 1, false);
+        visit(&mut tab, 5, false);
+        visit(&mut tab, 5, true);
+        assert!(!tab.compaction_learning.profile.files["gen/api.ts"].compacts());
+        for _ in 0..LEARN_AT {
+            visit(&mut tab, 5, false);
+        }
+        assert!(tab.compaction_config.should_compact("gen/api.ts", 400));
+
+        // It's saved for the next session in this repo
+        let mut next = TabState::new_for_test(Vec::new());
+        next.er_root = tab.er_root.clone();
+        next.compaction_learning.enabled = true;
+        next.load_compaction_profile();
+        assert_eq!(next.compaction_config.learned_expanded, ["Cargo.lock"]);
+        assert_eq!(next.compaction_config.learned_compacted, ["gen/api.ts"]);
+    }
+
+    #[test]
+    fn suggests_config_covering_several_learned_files() {
+        let mut profile = CompactionProfile::default();
+        let config = CompactionConfig::default();
+        for _ in 0..LEARN_AT {
+            profile.record_expand("data/a.json", 2500, true);
+            profile.record_expand("data/b.json", 3100, true);
+            profile.record_visit("gen/a.pb.ts", 900, false);
+            profile.record_visit("gen/b.pb.ts", 900, false);
+        }
+        let keys: Vec<String> = profile
+            .suggestions(&config)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, ["max_lines=4000", "pattern=*.ts"]);
+
+        // One file isn't a trend
+        profile.record_visit("gen/b.pb.ts", 900, true);
+        profile.files.remove("data/b.json");
+        assert!(profile.suggestions(&config).is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+//! Size and complexity budgets for the functions a diff touches (see
+//! [`crate::git::function_metrics`]).
+//!
+//! Whenever the diff changes, each changed file is read at the diff's new
+//! side on a worker thread and its functions measured; the ones with an
+//! added line in them are kept for the File Detail panel's summary, and
+//! each one over a `[complexity]` budget becomes a finding in the
+//! "complexity" category, anchored on its first added line. Like the
+//! duplicate findings, these are merged into the AI review after every
+//! reload.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, TabState};
+use crate::ai::{Confidence, Finding, RiskLevel};
+use crate::config::ComplexityConfig;
+use crate::git::{self, FunctionMetrics, LineType};
+
+/// The category the findings are listed under.
+pub const COMPLEXITY_CATEGORY: &str = "complexity";
+
+/// Files read per scan.
+const MAX_FILES: usize = 300;
+
+/// Changed functions, by path, and the findings for the ones over budget.
+type ScanResult = (
+    HashMap<String, Vec<FunctionMetrics>>,
+    Vec<(String, Finding)>,
+);
+
+/// The function metrics of a tab's diff.
+#[derive(Debug, Default)]
+pub struct ComplexityScan {
+    /// The diff the metrics are for
+    pub diff_hash: String,
+    /// Functions with an added line, by path
+    pub functions: HashMap<String, Vec<FunctionMetrics>>,
+    /// `(path, finding)`
+    pub findings: Vec<(String, Finding)>,
+    rx: Option<Receiver<ScanResult>>,
+}
+
+/// Where a diff's new side is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum NewSide {
+    WorkTree(String),
+    /// `rev` in `repo_root`, `""` being the index
+    Rev {
+        repo_root: String,
+        rev: String,
+    },
+}
+
+impl NewSide {
+    pub(super) fn read(&self, path: &str) -> Option<String> {
+        match self {
+            NewSide::WorkTree(root) => {
+                std::fs::read_to_string(std::path::Path::new(root).join(path)).ok()
+            }
+            NewSide::Rev { repo_root, rev } => git::git_show_file(repo_root, rev, path)
+                .unwrap_or_else(|e| {
+                    crate::debug_log::warn("git", format!("complexity of {}: {:#}", path, e));
+                    None
+                }),
+        }
+    }
+
+    /// The file's bytes, binary or not.
+    pub(super) fn read_bytes(&self, path: &str) -> Option<Vec<u8>> {
+        match self {
+            NewSide::WorkTree(root) => std::fs::read(std::path::Path::new(root).join(path)).ok(),
+            NewSide::Rev { repo_root, rev } => git::git_show_bytes(repo_root, rev, path)
+                .unwrap_or_else(|e| {
+                    crate::debug_log::warn("git", format!("reading {}: {:#}", path, e));
+                    None
+                }),
+        }
+    }
+}
+
+/// A changed function's added lines: `(new-side line, hunk)`.
+type Added = Vec<(usize, usize)>;
+
+/// The finding for `f` when it's over budget: anchored on its first line
+/// in `added`.
+fn budget_finding(
+    path: &str,
+    f: &FunctionMetrics,
+    added: &[(usize, usize)],
+    budget: &ComplexityConfig,
+) -> Option<(String, Finding)> {
+    let over = budget.over(f);
+    if !over.contains(&true) {
+        return None;
+    }
+    let &(anchor, hunk) = added
+        .iter()
+        .find(|(line, _)| (f.line_start..=f.line_end).contains(line))?;
+    let values = [f.length(), f.nesting, f.complexity];
+    let limits = [budget.max_lines, budget.max_nesting, budget.max_complexity];
+    let labels = ["lines", "nesting", "complexity"];
+    let parts: Vec<String> = (0..3)
+        .filter(|&i| over[i])
+        .map(|i| match i {
+            0 => format!("{} lines", values[i]),
+            _ => format!("{} {}", labels[i], values[i]),
+        })
+        .collect();
+    let doubled = (0..3).any(|i| over[i] && values[i] >= 2 * limits[i].max(1));
+    let finding = Finding {
+        id: format!("complexity-{}:{}:{}", path, f.name, f.line_start),
+        severity: if doubled {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        },
+        category: COMPLEXITY_CATEGORY.to_string(),
+        title: format!("{} is over budget: {}", f.name, parts.join(", ")),
+        description: format!(
+            "`{}` (lines {}–{}) has {} lines, nesting depth {} and cyclomatic complexity {}; \
+             the budgets are {}, {} and {} ([complexity] in the config).",
+            f.name,
+            f.line_start,
+            f.line_end,
+            f.length(),
+            f.nesting,
+            f.complexity,
+            budget.max_lines,
+            budget.max_nesting,
+            budget.max_complexity
+        ),
+        hunk_index: Some(hunk),
+        line_start: Some(anchor),
+        line_end: None,
+        suggestion: if over[1] || over[2] {
+            "Split out the branches, or return early to flatten the nesting".to_string()
+        } else {
+            "Split it into smaller functions".to_string()
+        },
+        related_files: vec![],
+        outside_diff: false,
+        confidence: Confidence::Informational,
+        verification_plan: String::new(),
+        evidence: vec![],
+        responses: vec![],
+        resolved: false,
+        resolved_note: String::new(),
+        resolved_at: String::new(),
+        promoted_to: None,
+    };
+    Some((path.to_string(), finding))
+}
+
+/// Measure the functions of `text` and keep the ones `added` touches.
+fn changed_functions(path: &str, text: &str, added: &[(usize, usize)]) -> Vec<FunctionMetrics> {
+    git::function_metrics(path, text)
+        .into_iter()
+        .filter(|f| {
+            added
+                .iter()
+                .any(|(line, _)| (f.line_start..=f.line_end).contains(line))
+        })
+        .collect()
+}
+
+impl TabState {
+    /// Put this diff's complexity findings back into a freshly loaded review.
+    pub(super) fn merge_complexity_findings(&mut self) {
+        if self.complexity.diff_hash != self.diff_hash {
+            return;
+        }
+        self.ai.merge_builtin_findings(
+            COMPLEXITY_CATEGORY,
+            &self.complexity.findings,
+            &self.branch_diff_hash,
+        );
+    }
+
+    /// The changed functions of `path`, for the File Detail panel.
+    pub fn changed_function_metrics(&self, path: &str) -> Option<&[FunctionMetrics]> {
+        self.complexity.functions.get(path).map(Vec::as_slice)
+    }
+
+    pub(super) fn new_side(&self) -> Option<NewSide> {
+        if self.is_remote() {
+            return None;
+        }
+        let rev = match self.mode {
+            DiffMode::Staged => Some(String::new()),
+            _ => self.new_side_rev(),
+        };
+        Some(match rev {
+            Some(rev) => NewSide::Rev {
+                repo_root: self.repo_root.clone(),
+                rev,
+            },
+            None => NewSide::WorkTree(
+                self.local_branch_checkout_root
+                    .clone()
+                    .unwrap_or_else(|| self.repo_root.clone()),
+            ),
+        })
+    }
+}
+
+impl App {
+    /// Measure the active tab's changed functions when its diff changed,
+    /// and merge the findings once done. True when they arrived.
+    pub fn poll_complexity(&mut self) -> bool {
+        let budget = self.config.complexity.clone();
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.complexity.rx {
+            return match rx.try_recv() {
+                Ok(_) if tab.complexity.diff_hash != tab.diff_hash => {
+                    tab.complexity.rx = None;
+                    false
+                }
+                Ok((functions, findings)) => {
+                    tab.complexity.rx = None;
+                    tab.complexity.functions = functions;
+                    tab.complexity.findings = findings;
+                    tab.ai.merge_builtin_findings(
+                        COMPLEXITY_CATEGORY,
+                        &tab.complexity.findings,
+                        &tab.branch_diff_hash,
+                    );
+                    true
+                }
+                Err(TryRecvError::Empty) => false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.complexity.rx = None;
+                    false
+                }
+            };
+        }
+        if !budget.enabled
+            || matches!(tab.mode, DiffMode::History | DiffMode::Tour)
+            || tab.complexity.diff_hash == tab.diff_hash
+        {
+            return false;
+        }
+        tab.complexity.diff_hash = tab.diff_hash.clone();
+        let had_results =
+            !tab.complexity.functions.is_empty() || !tab.complexity.findings.is_empty();
+        tab.complexity.functions.clear();
+        tab.complexity.findings.clear();
+        if had_results {
+            tab.ai
+                .merge_builtin_findings(COMPLEXITY_CATEGORY, &[], &tab.branch_diff_hash);
+        }
+        let Some(side) = tab.new_side() else {
+            return had_results;
+        };
+        let parsed = tab.parsed_stubs();
+        let files: Vec<(String, Added)> = tab
+            .all_hunks(&parsed)
+            .into_iter()
+            .map(|(path, hunks)| {
+                let added: Added = hunks
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(hi, h)| {
+                        h.lines
+                            .iter()
+                            .filter(|l| l.line_type == LineType::Add)
+                            .filter_map(move |l| Some((l.new_num?, hi)))
+                    })
+                    .collect();
+                (path.to_string(), added)
+            })
+            .filter(|(path, added)| !added.is_empty() && git::is_measured(path))
+            .take(MAX_FILES)
+            .collect();
+        if files.is_empty() {
+            return had_results;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut functions = HashMap::new();
+            let mut findings = Vec::new();
+            for (path, added) in files {
+                let Some(text) = side.read(&path) else {
+                    continue;
+                };
+                let changed = changed_functions(&path, &text, &added);
+                if changed.is_empty() {
+                    continue;
+                }
+                findings.extend(
+                    changed
+                        .iter()
+                        .filter_map(|f| budget_finding(&path, f, &added, &budget)),
+                );
+                functions.insert(path, changed);
+            }
+            let _ = tx.send((functions, findings));
+        });
+        tab.complexity.rx = Some(rx);
+        had_results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(length: usize, nesting: usize, complexity: usize) -> FunctionMetrics {
+        FunctionMetrics {
+            name: "handle".into(),
+            line_start: 10,
+            line_end: 9 + length,
+            nesting,
+            complexity,
+        }
+    }
+
+    #[test]
+    fn over_budget_functions_become_findings_on_their_added_lines() {
+        let budget = ComplexityConfig::default();
+        let added = vec![(3, 0), (42, 1), (50, 1)];
+        assert!(budget_finding("a.rs", &metrics(20, 2, 5), &added, &budget).is_none());
+
+        let (path, finding) = budget_finding("a.rs", &metrics(90, 5, 5), &added, &budget).unwrap();
+        assert_eq!(path, "a.rs");
+        assert_eq!(finding.title, "handle is over budget: 90 lines, nesting 5");
+        assert_eq!(
+            (finding.hunk_index, finding.line_start),
+            (Some(1), Some(42))
+        );
+        assert_eq!(finding.severity, RiskLevel::Low);
+        assert_eq!(
+            budget_finding("a.rs", &metrics(40, 2, 30), &added, &budget)
+                .unwrap()
+                .1
+                .severity,
+            RiskLevel::Medium
+        );
+        // No added line inside it: nothing to anchor on
+        assert!(budget_finding("a.rs", &metrics(40, 2, 30), &[(3, 0)], &budget).is_none());
+    }
+
+    #[test]
+    fn only_functions_with_added_lines_are_kept() {
+        let text = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        let changed = changed_functions("x.rs", text, &[(6, 0)]);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].name, "b");
+    }
+}
@@ -0,0 +1,279 @@
+//! Hot-reload of the global `config.toml` and each tab's `.er-config.toml`.
+//! Their mtimes are checked about once a second. A changed file is
+//! re-applied without a restart and the notification names the sections
+//! that changed. Feature flags, display and theme are read from `App::config`
+//! on every draw. Compaction, watched paths and the other per-tab settings
+//! are re-resolved through `TabState::reload_config`. A global file that
+//! doesn't parse is reported and the running settings are kept.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{App, OverlayData};
+use crate::config::{self, ErConfig};
+
+/// How often the config files' mtimes are checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The config files as last applied.
+#[derive(Debug, Default)]
+pub struct ConfigWatch {
+    last_check: Option<Instant>,
+    /// `None` until the first check; `Some(None)` while there's no file
+    global: Option<Option<SystemTime>>,
+    /// Repo root → the `.er-config.toml` mtime and top-level tables
+    repos: HashMap<String, (Option<SystemTime>, toml::Table)>,
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn repo_config_path(repo_root: &str) -> PathBuf {
+    Path::new(repo_root).join(".er-config.toml")
+}
+
+/// The repo file's top-level tables; empty when there's no file.
+fn read_repo_tables(path: &Path) -> Result<toml::Table, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => content
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string()),
+        Err(_) => Ok(toml::Table::new()),
+    }
+}
+
+/// Top-level keys whose values differ between `old` and `new`, sorted.
+fn changed_keys(old: &toml::Table, new: &toml::Table) -> Vec<String> {
+    let mut keys: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|k| old.get(*k) != new.get(*k))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Sections of the global config that differ, as `[section]` names.
+fn changed_sections(old: &ErConfig, new: &ErConfig) -> Vec<String> {
+    let table = |c: &ErConfig| match toml::Table::try_from(c) {
+        Ok(table) => table,
+        Err(_) => toml::Table::new(),
+    };
+    changed_keys(&table(old), &table(new))
+}
+
+/// Give `new` the models discovered from the providers' CLIs at runtime;
+/// they never reach the file.
+fn keep_discovered_models(old: &ErConfig, new: &mut ErConfig) {
+    for (id, provider) in new.ai_hub.providers.iter_mut() {
+        let Some(old_provider) = old.ai_hub.providers.get(id) else {
+            continue;
+        };
+        for model in old_provider.models.iter().filter(|m| m.discovered) {
+            if !provider.models.iter().any(|m| m.id == model.id) {
+                provider.models.push(model.clone());
+            }
+        }
+    }
+}
+
+impl ConfigWatch {
+    /// Whether the repo's file changed since the last look; the first look
+    /// at a repo only records it.
+    fn repo_changed(&mut self, repo_root: &str) -> Option<Result<Vec<String>, String>> {
+        let path = repo_config_path(repo_root);
+        let now = mtime(&path);
+        let Some((seen, tables)) = self.repos.get_mut(repo_root) else {
+            let tables = read_repo_tables(&path).unwrap_or_default();
+            self.repos.insert(repo_root.to_string(), (now, tables));
+            return None;
+        };
+        if *seen == now {
+            return None;
+        }
+        *seen = now;
+        Some(read_repo_tables(&path).map(|new| {
+            let changed = changed_keys(tables, &new);
+            *tables = new;
+            changed
+        }))
+    }
+}
+
+impl App {
+    /// Apply config files that changed on disk. Returns whether anything
+    /// was reloaded. Waits while the Settings overlay is open; its own
+    /// save is what the running settings already are.
+    pub fn poll_config_reload(&mut self) -> bool {
+        let watch = &mut self.config_watch;
+        if watch
+            .last_check
+            .is_some_and(|t| t.elapsed() < CHECK_INTERVAL)
+        {
+            return false;
+        }
+        watch.last_check = Some(Instant::now());
+        if matches!(self.overlay, Some(OverlayData::ConfigHub { .. })) {
+            return false;
+        }
+
+        let mut changed: Vec<String> = Vec::new();
+        let global_now = mtime(&config::managed_config_path());
+        match watch.global {
+            None => watch.global = Some(global_now),
+            Some(seen) if seen != global_now => {
+                watch.global = Some(global_now);
+                match config::reload_global_config() {
+                    Ok(mut new) => {
+                        keep_discovered_models(&self.config, &mut new);
+                        let sections = changed_sections(&self.config, &new);
+                        let ai_hub = sections.iter().any(|s| s == "ai_hub");
+                        self.config = new;
+                        if ai_hub {
+                            self.sync_ai_selection_from_defaults();
+                        }
+                        changed.extend(sections);
+                    }
+                    Err(e) => {
+                        self.notify_warn(&format!("config.toml not reloaded: {}", e));
+                        return false;
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+
+        let mut reloaded = !changed.is_empty();
+        for i in 0..self.tabs.len() {
+            let repo_root = self.tabs[i].repo_root.clone();
+            let mut tab_changed = changed.clone();
+            if !self.tabs[i].is_remote() {
+                match self.config_watch.repo_changed(&repo_root) {
+                    Some(Ok(sections)) => {
+                        for section in sections {
+                            let name = format!(".er-config.toml {}", section);
+                            if !changed.contains(&name) {
+                                changed.push(name);
+                            }
+                            tab_changed.push(section);
+                        }
+                    }
+                    Some(Err(e)) => {
+                        self.notify_warn(&format!(".er-config.toml not reloaded: {}", e));
+                    }
+                    None => {}
+                }
+            }
+            if tab_changed.is_empty() {
+                continue;
+            }
+            reloaded = true;
+            let tab = &mut self.tabs[i];
+            tab.reload_config(&self.config);
+            tab.refresh_watched_files();
+            let large_files = tab_changed.iter().any(|s| s == "large_files");
+            if large_files && !tab.is_remote() {
+                if let Err(e) = tab.refresh_diff() {
+                    self.notify_warn(&format!("Config reloaded, diff refresh failed: {}", e));
+                    return true;
+                }
+            }
+        }
+        if reloaded {
+            self.notify(&format!("Config reloaded — {}", changed.join(", ")));
+        }
+        reloaded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::DiffMode;
+
+    #[test]
+    fn repo_config_change_recompacts_and_names_the_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("lib.rs"), "a\nb\nc\n").unwrap();
+        git(&["add", "lib.rs"]);
+        git(&["commit", "-q", "-m", "init"]);
+        std::fs::write(dir.path().join("lib.rs"), "a\nB\nC\n").unwrap();
+        let repo_file = dir.path().join(".er-config.toml");
+        std::fs::write(&repo_file, "[todos]\nticket_pattern = \"JIRA-\"\n").unwrap();
+
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().repo_root = root.clone();
+        app.tab_mut().mode = DiffMode::Unstaged;
+        // The first look only records what's there
+        assert!(!app.poll_config_reload());
+        assert!(app.config_watch.repos.contains_key(&root));
+
+        std::fs::write(
+            &repo_file,
+            "[todos]\nticket_pattern = \"JIRA-\"\n\n[large_files]\nmax_lines = 2\n",
+        )
+        .unwrap();
+        let changed = |app: &mut App| {
+            app.config_watch.repos.get_mut(&root).unwrap().0 = None;
+            app.config_watch.last_check = None;
+        };
+        changed(&mut app);
+        assert!(app.poll_config_reload());
+        assert_eq!(app.tab().compaction_config.max_lines_before_compact, 2);
+        let lib = app.tab().files.iter().find(|f| f.path == "lib.rs");
+        assert!(lib.is_some_and(|f| f.compacted));
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Config reloaded — .er-config.toml large_files")
+        );
+
+        // Nothing changed since: quiet
+        app.config_watch.last_check = None;
+        assert!(!app.poll_config_reload());
+
+        // A half-saved file keeps the running settings
+        std::fs::write(&repo_file, "[large_files\n").unwrap();
+        changed(&mut app);
+        assert!(!app.poll_config_reload());
+        assert_eq!(app.tab().compaction_config.max_lines_before_compact, 2);
+    }
+
+    #[test]
+    fn global_sections_diff_and_discovered_models_survive() {
+        let old = ErConfig::default();
+        let mut new = ErConfig::default();
+        new.display.theme = "light".into();
+        new.large_files.max_lines = 10;
+        assert_eq!(changed_sections(&old, &new), ["display", "large_files"]);
+        assert!(changed_sections(&new, &new.clone()).is_empty());
+
+        let mut old = ErConfig::default();
+        config::supplement_ai_hub(&mut old.ai_hub);
+        let mut new = old.clone();
+        let (id, provider) = old.ai_hub.providers.iter_mut().next().unwrap();
+        let id = id.clone();
+        let mut model = provider.models.first().cloned().unwrap();
+        model.id = "runtime-model".into();
+        model.discovered = true;
+        provider.models.push(model);
+        keep_discovered_models(&old, &mut new);
+        assert!(new.ai_hub.providers[&id]
+            .models
+            .iter()
+            .any(|m| m.id == "runtime-model"));
+    }
+}
@@ -0,0 +1,265 @@
+//! y/n confirmations.
+//!
+//! Every destructive or outward-facing action goes through one prompt: a
+//! [`ConfirmPrompt`] carrying the [`ConfirmAction`] to run, a title, optional
+//! detail lines and the keys on offer. [`App::ask_confirm`] shows it; the TUI
+//! draws it as a popup (red for `danger`) and the confirm key handler runs the
+//! action. [`ConfirmAction::prompt`] gives each action its default wording,
+//! which call sites extend with context:
+//!
+//! ```ignore
+//! app.ask_confirm(ConfirmAction::Push.prompt().detail("feature → origin"));
+//! ```
+
+use super::{App, ConfirmAction, InputMode};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmPrompt {
+    pub action: ConfirmAction,
+    pub title: String,
+    pub details: Vec<String>,
+    /// Destructive or hard to undo — drawn in red
+    pub danger: bool,
+    /// Keys on offer besides Esc, in display order
+    pub keys: Vec<(char, String)>,
+}
+
+impl ConfirmPrompt {
+    /// `title` with `y` / `n` keys.
+    pub fn new(action: ConfirmAction, title: impl Into<String>) -> Self {
+        ConfirmPrompt {
+            action,
+            title: title.into(),
+            details: Vec::new(),
+            danger: false,
+            keys: vec![('y', "yes".into()), ('n', "no".into())],
+        }
+    }
+
+    pub fn detail(mut self, line: impl Into<String>) -> Self {
+        self.details.push(line.into());
+        self
+    }
+
+    pub fn details<I, S>(mut self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.details.extend(lines.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn danger(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+
+    /// Relabel the `y` key, e.g. "delete".
+    pub fn yes(mut self, label: impl Into<String>) -> Self {
+        if let Some(key) = self.keys.iter_mut().find(|(k, _)| *k == 'y') {
+            key.1 = label.into();
+        }
+        self
+    }
+
+    /// Replace the offered keys.
+    pub fn keys(mut self, keys: &[(char, &str)]) -> Self {
+        self.keys = keys.iter().map(|(k, l)| (*k, l.to_string())).collect();
+        self
+    }
+
+    /// `y=delete n=no Esc=cancel`
+    pub fn key_hint(&self) -> String {
+        let mut parts: Vec<String> = self
+            .keys
+            .iter()
+            .map(|(k, label)| format!("{}={}", k, label))
+            .collect();
+        parts.push("Esc=cancel".into());
+        parts.join(" ")
+    }
+}
+
+impl ConfirmAction {
+    /// Default prompt for this action.
+    pub fn prompt(&self) -> ConfirmPrompt {
+        let action = self.clone();
+        match self {
+            ConfirmAction::DeleteComment { .. } => ConfirmPrompt::new(action, "Delete comment?")
+                .yes("delete")
+                .danger(),
+            ConfirmAction::DeleteWatchedFile { path } => {
+                ConfirmPrompt::new(action, format!("Delete {}?", path))
+                    .detail("The file is removed from disk")
+                    .yes("delete")
+                    .danger()
+            }
+            ConfirmAction::Push => ConfirmPrompt::new(action, "Push branch to remote?").yes("push"),
+            ConfirmAction::CleanupQuestions { count } => ConfirmPrompt::new(
+                action,
+                format!("Clear {} item(s) (questions & notes)?", count),
+            )
+            .yes("clear")
+            .danger(),
+            ConfirmAction::CleanupReviews { count } => {
+                ConfirmPrompt::new(action, format!("Clear {} review file(s)?", count))
+                    .yes("clear")
+                    .danger()
+            }
+            ConfirmAction::DiscardDrafts { count } => {
+                ConfirmPrompt::new(action, format!("Discard {} saved draft(s)?", count))
+                    .yes("discard")
+                    .danger()
+            }
+            ConfirmAction::Quit { left_behind } => ConfirmPrompt::new(action, "Quit and leave:")
+                .details(left_behind.iter().cloned())
+                .keys(&[('y', "quit"), ('n', "stay")])
+                .danger(),
+            ConfirmAction::RunAgentReview { .. } => {
+                ConfirmPrompt::new(action, "Clear previous review before running?")
+                    .keys(&[('y', "clear"), ('k', "keep")])
+            }
+            ConfirmAction::RunAgentQuestions { .. } => {
+                ConfirmPrompt::new(action, "Clear previous AI answers before running?")
+                    .keys(&[('y', "clear"), ('k', "keep")])
+            }
+            ConfirmAction::ApprovePR => {
+                ConfirmPrompt::new(action, "Approve this PR on GitHub?").yes("approve")
+            }
+            ConfirmAction::PushComments => ConfirmPrompt::new(action, "Push comments as:")
+                .keys(&[('r', "review"), ('i', "individual")]),
+            ConfirmAction::DeleteChecklistItem { .. } => {
+                ConfirmPrompt::new(action, "Delete checklist item?")
+                    .yes("delete")
+                    .danger()
+            }
+            ConfirmAction::PushDespiteChecks { failed } => ConfirmPrompt::new(
+                action,
+                format!("{} pre-push check(s) failed — push anyway?", failed.len()),
+            )
+            .details(failed.iter().map(|name| format!("✗ {}", name)))
+            .yes("push anyway")
+            .danger(),
+            ConfirmAction::HideNoise { files, .. } => ConfirmPrompt::new(
+                action,
+                format!(
+                    "Hide {} generated file{}?",
+                    files,
+                    if *files == 1 { "" } else { "s" }
+                ),
+            )
+            .keys(&[('y', "hide"), ('n', "keep")]),
+            ConfirmAction::MoveComment { .. } => {
+                ConfirmPrompt::new(action, "Comment followed its code — move it?")
+                    .keys(&[('y', "move"), ('n', "leave")])
+            }
+            ConfirmAction::DiscardLines { path, lines, .. } => ConfirmPrompt::new(
+                action,
+                format!(
+                    "Discard {} selected line{} in {}?",
+                    lines,
+                    if *lines == 1 { "" } else { "s" },
+                    path
+                ),
+            )
+            .detail("The working tree change is lost; the index is left alone")
+            .yes("discard")
+            .danger(),
+            ConfirmAction::IgnoreArtifacts { file, patterns } => ConfirmPrompt::new(
+                action,
+                format!(
+                    "Review files aren't ignored by git — add them to {}?",
+                    if file.ends_with("info/exclude") {
+                        ".git/info/exclude"
+                    } else {
+                        ".gitignore"
+                    }
+                ),
+            )
+            .details(patterns.iter().cloned())
+            .keys(&[('y', "add"), ('n', "not now")]),
+            ConfirmAction::CleanReviewData { targets } => ConfirmPrompt::new(
+                action,
+                format!(
+                    "Remove {} stale review item{}?",
+                    targets.len(),
+                    if targets.len() == 1 { "" } else { "s" }
+                ),
+            )
+            .details(crate::artifact_gc::summarize(targets))
+            .yes("remove")
+            .danger(),
+            ConfirmAction::SwitchBranch { name, changes, .. } => ConfirmPrompt::new(
+                action,
+                format!(
+                    "{} uncommitted change{} — stash and switch to {}?",
+                    changes,
+                    if *changes == 1 { "" } else { "s" },
+                    name
+                ),
+            )
+            .detail("git stash pop on this branch brings them back")
+            .keys(&[('y', "stash & switch"), ('n', "stay")]),
+        }
+    }
+}
+
+impl App {
+    /// Show `prompt` and wait for the confirm keys.
+    pub fn ask_confirm(&mut self, prompt: ConfirmPrompt) {
+        self.input_mode = InputMode::Confirm(prompt.action.clone());
+        self.confirm_prompt = Some(prompt);
+    }
+
+    /// Confirm `action` with its default prompt.
+    pub fn confirm(&mut self, action: ConfirmAction) {
+        self.ask_confirm(action.prompt());
+    }
+
+    /// The prompt being shown, `None` outside confirm mode. Falls back to the
+    /// default wording when the mode was set without [`App::ask_confirm`].
+    pub fn current_confirm(&self) -> Option<ConfirmPrompt> {
+        let InputMode::Confirm(action) = &self.input_mode else {
+            return None;
+        };
+        match &self.confirm_prompt {
+            Some(prompt) if prompt.action == *action => Some(prompt.clone()),
+            _ => Some(action.prompt()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_adds_details_and_relabels_yes() {
+        let prompt = ConfirmPrompt::new(ConfirmAction::Push, "Push?")
+            .detail("feature → origin")
+            .details(["2 commits"])
+            .yes("push");
+        assert_eq!(prompt.details, vec!["feature → origin", "2 commits"]);
+        assert!(!prompt.danger);
+        assert_eq!(prompt.key_hint(), "y=push n=no Esc=cancel");
+    }
+
+    #[test]
+    fn ask_confirm_keeps_prompt_until_mode_changes() {
+        let mut app = App::new_for_test(vec![]);
+        assert!(app.current_confirm().is_none());
+        app.ask_confirm(ConfirmAction::ApprovePR.prompt().detail("PR #42"));
+        assert_eq!(app.input_mode, InputMode::Confirm(ConfirmAction::ApprovePR));
+        assert_eq!(app.current_confirm().unwrap().details, vec!["PR #42"]);
+
+        // Set directly: default wording, not the stale details
+        app.input_mode = InputMode::Confirm(ConfirmAction::CleanupReviews { count: 3 });
+        let prompt = app.current_confirm().unwrap();
+        assert!(prompt.danger && prompt.details.is_empty());
+        assert_eq!(prompt.title, "Clear 3 review file(s)?");
+
+        app.cancel_confirm();
+        assert!(app.current_confirm().is_none());
+    }
+}
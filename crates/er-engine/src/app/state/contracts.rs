@@ -0,0 +1,163 @@
+//! Semantic diffs of the API contracts a diff changes (see
+//! [`crate::analysis`]).
+//!
+//! Whenever the diff changes, each changed `.proto` or OpenAPI file is
+//! read at the diff's new side on a worker thread, its old side rebuilt
+//! from that and the hunks, and the two compared. The File Detail panel
+//! shows the result above the hunks.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, TabState};
+use crate::analysis::{self, ContractChange, ContractKind};
+use crate::git::{self, DiffHunk};
+
+/// Files compared per scan.
+const MAX_FILES: usize = 50;
+
+/// One changed contract file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractDiff {
+    pub kind: ContractKind,
+    /// Breaking changes first
+    pub changes: Vec<ContractChange>,
+    /// Why a side couldn't be read, when it couldn't
+    pub error: Option<String>,
+}
+
+impl ContractDiff {
+    pub fn breaking(&self) -> usize {
+        self.changes.iter().filter(|c| c.breaking).count()
+    }
+}
+
+/// The contract diffs of a tab's diff.
+#[derive(Debug, Default)]
+pub struct ContractScan {
+    /// The diff the results are for
+    pub diff_hash: String,
+    /// By path
+    pub files: HashMap<String, ContractDiff>,
+    rx: Option<Receiver<HashMap<String, ContractDiff>>>,
+}
+
+/// The contract diff of `path`, whose new side is `new` (empty when
+/// deleted); `None` when it isn't a contract.
+fn contract_for(path: &str, new: &str, hunks: &[DiffHunk]) -> Option<ContractDiff> {
+    let old = git::old_side(new, hunks);
+    let sample = if new.is_empty() { &old } else { new };
+    let kind = analysis::contract_kind(path, sample)?;
+    Some(match analysis::contract_diff(kind, &old, new) {
+        Ok(changes) => ContractDiff {
+            kind,
+            changes,
+            error: None,
+        },
+        Err(e) => ContractDiff {
+            kind,
+            changes: Vec::new(),
+            error: Some(e),
+        },
+    })
+}
+
+impl TabState {
+    /// The contract diff of `path`, for the File Detail panel.
+    pub fn contract_changes(&self, path: &str) -> Option<&ContractDiff> {
+        if self.contracts.diff_hash != self.diff_hash {
+            return None;
+        }
+        self.contracts.files.get(path)
+    }
+}
+
+impl App {
+    /// Compare the active tab's changed contracts when its diff changed.
+    /// True when the results arrived.
+    pub fn poll_contracts(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.contracts.rx {
+            return match rx.try_recv() {
+                Ok(_) if tab.contracts.diff_hash != tab.diff_hash => {
+                    tab.contracts.rx = None;
+                    false
+                }
+                Ok(files) => {
+                    tab.contracts.rx = None;
+                    tab.contracts.files = files;
+                    true
+                }
+                Err(TryRecvError::Empty) => false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.contracts.rx = None;
+                    false
+                }
+            };
+        }
+        if matches!(tab.mode, DiffMode::History | DiffMode::Tour)
+            || tab.contracts.diff_hash == tab.diff_hash
+        {
+            return false;
+        }
+        tab.contracts.diff_hash = tab.diff_hash.clone();
+        let had_results = !tab.contracts.files.is_empty();
+        tab.contracts.files.clear();
+        let Some(side) = tab.new_side() else {
+            return had_results;
+        };
+        let parsed = tab.parsed_stubs();
+        let files: Vec<(String, Vec<DiffHunk>)> = tab
+            .all_hunks(&parsed)
+            .into_iter()
+            // The name alone can't rule out a spec found by its top-level key
+            .filter(|(path, hunks)| {
+                !hunks.is_empty()
+                    && [".proto", ".yaml", ".yml", ".json"]
+                        .iter()
+                        .any(|ext| path.to_ascii_lowercase().ends_with(ext))
+            })
+            .take(MAX_FILES)
+            .map(|(path, hunks)| (path.to_string(), hunks.to_vec()))
+            .collect();
+        if files.is_empty() {
+            return had_results;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let found = files
+                .into_iter()
+                .filter_map(|(path, hunks)| {
+                    let new = side.read(&path).unwrap_or_default();
+                    Some((path.clone(), contract_for(&path, &new, &hunks)?))
+                })
+                .collect();
+            let _ = tx.send(found);
+        });
+        tab.contracts.rx = Some(rx);
+        had_results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::ChangeKind;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn the_old_side_is_rebuilt_from_the_hunks() {
+        let new =
+            "syntax = \"proto3\";\n\nmessage User {\n  string id = 1;\n  bytes email = 2;\n}\n";
+        let diff = "diff --git a/api/user.proto b/api/user.proto\n--- a/api/user.proto\n+++ b/api/user.proto\n\
+                    @@ -3,4 +3,4 @@\n message User {\n   string id = 1;\n-  string email = 2;\n+  bytes email = 2;\n }\n";
+        let files = parse_diff(diff);
+        let found = contract_for("api/user.proto", new, &files[0].hunks).unwrap();
+        assert_eq!(found.kind, ContractKind::Proto);
+        assert_eq!(found.breaking(), 1);
+        assert_eq!(found.changes[0].kind, ChangeKind::Changed);
+        assert_eq!(found.changes[0].detail, "string → bytes");
+
+        assert!(contract_for("config/app.yaml", "port: 80\n", &files[0].hunks).is_none());
+    }
+}
@@ -0,0 +1,429 @@
+//! Whole-view copy actions: the complete diff, a GitHub permalink to the
+//! cursor line, and the `git diff` / `gh pr diff` command behind the view.
+//! Plus patches for moving fixes between branches: the current hunk (or its
+//! reverse) for `git apply`, and the files in view saved to a `.patch` file.
+
+use super::{App, DiffMode, TabState};
+use crate::command::shell_line;
+use crate::git;
+use anyhow::Result;
+
+/// Largest diff the copy hub puts on the clipboard. Past this, terminals and
+/// OSC 52 start truncating, so the diff command is the better thing to share.
+pub const COPY_DIFF_LIMIT_BYTES: usize = 2 * 1024 * 1024;
+
+impl TabState {
+    /// Whether this view's diff comes from `gh pr diff` (mirrors
+    /// `fetch_pr_diff_for_review`).
+    fn view_uses_pr_diff(&self) -> bool {
+        self.pr_number.is_some()
+            && !(self.local_branch_checkout_root.is_some()
+                && matches!(
+                    self.mode,
+                    DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged
+                ))
+            && (self.remote_repo.is_some() || !self.repo_root.is_empty())
+    }
+
+    /// Shell command that prints the diff this tab shows, so it can be pasted
+    /// into a terminal or a bug report. `None` for views with no diff.
+    pub fn view_diff_command(&self) -> Option<String> {
+        let unified = format!("--unified={}", git::DEFAULT_CONTEXT_LINES);
+        let git_in = |root: &str, args: Vec<String>| {
+            let mut full = Vec::new();
+            if root != self.repo_root {
+                full.push("-C".to_string());
+                full.push(root.to_string());
+            }
+            full.extend(args);
+            shell_line("git", &full)
+        };
+        let diff = |extra: &[&str]| {
+            let mut args = vec!["diff".to_string()];
+            args.extend(extra.iter().map(|a| a.to_string()));
+            args.push(unified.clone());
+            args
+        };
+
+        match self.mode {
+            DiffMode::Hidden => return None,
+            DiffMode::History => {
+                let history = self.history.as_ref()?;
+                let commit = history.commits.get(history.selected_commit)?;
+                let range = format!("{0}^..{0}", commit.hash);
+                return Some(git_in(&self.repo_root, diff(&[&range])));
+            }
+            DiffMode::Conflicts => return Some(git_in(&self.repo_root, diff(&["HEAD"]))),
+            _ => {}
+        }
+
+        if self.in_checkpoint_view() {
+            let (root, args) = self.checkpoint_view.as_ref()?.diff_args();
+            return Some(git_in(root, args));
+        }
+
+        if self.view_uses_pr_diff() {
+            let number = self.pr_number?.to_string();
+            let mut args = vec!["pr", "diff", number.as_str()];
+            if let Some(ref slug) = self.remote_repo {
+                args.push("-R");
+                args.push(slug);
+            }
+            return Some(shell_line("gh", &args));
+        }
+        if self.is_remote() {
+            return None;
+        }
+
+        let scope = self.mode.fetch_scope();
+        if let Some(ref branch) = self.local_branch_view {
+            if let Some(ref root) = self.local_branch_checkout_root {
+                let args = match scope {
+                    "unstaged" => diff(&[]),
+                    "staged" => diff(&["--staged"]),
+                    _ => diff(&["--merge-base", &self.base_branch]),
+                };
+                return Some(git_in(root, args));
+            }
+            let head = self.pr_head_ref.as_deref().unwrap_or(branch);
+            let range = format!("{}...{}", self.base_branch, head);
+            return Some(git_in(&self.repo_root, diff(&[&range])));
+        }
+
+        let args = match scope {
+            "unstaged" => diff(&[]),
+            "staged" => diff(&["--staged"]),
+            _ => {
+                let head = self.pr_head_ref.as_deref().unwrap_or("HEAD");
+                diff(&[&format!("{}...{}", self.base_branch, head)])
+            }
+        };
+        Some(git_in(&self.repo_root, args))
+    }
+
+    /// Raw diff text for the whole view, fetched the same way as the view.
+    fn view_raw_diff(&self) -> Result<String> {
+        match self.mode {
+            DiffMode::Hidden => Ok(String::new()),
+            DiffMode::History => match self
+                .history
+                .as_ref()
+                .and_then(|h| h.commits.get(h.selected_commit))
+            {
+                Some(commit) => git::git_diff_commit(&commit.hash, &self.repo_root),
+                None => Ok(String::new()),
+            },
+            DiffMode::Conflicts => git::git_diff_conflicts(&self.repo_root),
+            _ => match self.checkpoint_view.as_ref() {
+                Some(view) if self.in_checkpoint_view() => view.raw_diff(),
+                _ => self.fetch_tab_raw_diff(self.mode.fetch_scope()),
+            },
+        }
+    }
+
+    /// Head-side line range under the cursor (selection, line, or hunk
+    /// start) in the selected file; `None` without a hunk.
+    pub(super) fn cursor_head_lines(&self) -> Result<Option<(usize, usize)>> {
+        let Some(file) = self.selected_diff_file() else {
+            return Ok(None);
+        };
+        let (hunk_idx, line_idx, range) = match (&self.history, self.mode) {
+            (Some(h), DiffMode::History) => (h.current_hunk, h.current_line, None),
+            _ => (self.current_hunk, self.current_line, self.selected_range()),
+        };
+        let Some(hunk) = file.hunks.get(hunk_idx) else {
+            return Ok(None);
+        };
+        let nums: Vec<usize> = match (range, line_idx) {
+            (Some(range), _) => hunk
+                .lines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| range.contains(i))
+                .filter_map(|(_, l)| l.new_num)
+                .collect(),
+            (None, Some(i)) => hunk
+                .lines
+                .get(i)
+                .and_then(|l| l.new_num)
+                .into_iter()
+                .collect(),
+            (None, None) => vec![hunk.new_start],
+        };
+        if nums.is_empty() {
+            anyhow::bail!("Deleted lines have no line at head to link to");
+        }
+        Ok(Some((nums[0], nums[nums.len() - 1])))
+    }
+
+    /// `(owner, repo, sha)` a permalink for this view should point at.
+    pub(super) fn permalink_target(&self) -> Result<(String, String, String)> {
+        let (owner, repo) = match self.remote_repo.as_deref() {
+            Some(slug) => slug
+                .split_once('/')
+                .map(|(o, r)| (o.to_string(), r.to_string())),
+            None => crate::github::parse_origin_remote(&self.repo_root),
+        }
+        .ok_or_else(|| anyhow::anyhow!("No GitHub remote (origin) to link to"))?;
+
+        let sha = if self.mode == DiffMode::History {
+            self.history
+                .as_ref()
+                .and_then(|h| h.commits.get(h.selected_commit))
+                .map(|c| c.hash.clone())
+        } else if self.is_remote() {
+            self.last_diff_head_oid.clone()
+        } else {
+            let root = self
+                .local_branch_checkout_root
+                .as_deref()
+                .unwrap_or(&self.repo_root);
+            let head = match (&self.pr_head_ref, &self.local_branch_view) {
+                (Some(head), _) => head.as_str(),
+                (None, Some(branch)) if self.local_branch_checkout_root.is_none() => {
+                    branch.as_str()
+                }
+                _ => "HEAD",
+            };
+            crate::github::rev_parse_oid(root, head)
+        }
+        .ok_or_else(|| anyhow::anyhow!("Could not resolve the commit to link to"))?;
+        Ok((owner, repo, sha))
+    }
+}
+
+impl App {
+    /// Copy the whole view's diff (every file), refusing past
+    /// [`COPY_DIFF_LIMIT_BYTES`].
+    pub fn copy_view_diff(&mut self) -> Result<()> {
+        let label = match self.tab().pr_number {
+            Some(_) if self.tab().view_uses_pr_diff() => "PR diff",
+            _ => "diff",
+        };
+        let raw = self.tab().view_raw_diff()?;
+        if raw.trim().is_empty() {
+            self.notify("Nothing to copy — the diff is empty");
+            return Ok(());
+        }
+        if raw.len() > COPY_DIFF_LIMIT_BYTES {
+            self.notify_warn(&format!(
+                "⚠ Whole {} is {} KB (limit {} KB) — copy the diff command instead",
+                label,
+                raw.len() / 1024,
+                COPY_DIFF_LIMIT_BYTES / 1024
+            ));
+            return Ok(());
+        }
+        let files = raw.lines().filter(|l| l.starts_with("diff --git ")).count();
+        let kb = raw.len().div_ceil(1024);
+        let via = self.copy_to_clipboard(&raw)?;
+        self.notify_copied(
+            &format!(
+                "Copied whole {} ({} file{}, {} KB)",
+                label,
+                files,
+                if files == 1 { "" } else { "s" },
+                kb
+            ),
+            via,
+        );
+        Ok(())
+    }
+
+    /// Copy the current hunk as a patch `git apply` takes; `reverse` copies
+    /// the patch that undoes it.
+    pub fn copy_hunk_patch(&mut self, reverse: bool) -> Result<()> {
+        let tab = self.tab();
+        let Some(file) = tab.selected_diff_file() else {
+            self.notify("No file selected");
+            return Ok(());
+        };
+        let Some(hunk) = file.hunks.get(tab.current_hunk) else {
+            self.notify("No hunk selected");
+            return Ok(());
+        };
+        let patch = match git::file_patch(file, &[hunk], reverse, None) {
+            Ok(patch) => patch,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return Ok(());
+            }
+        };
+        let via = self.copy_to_clipboard(&patch)?;
+        let message = if reverse {
+            "Reverse patch copied — git apply undoes the hunk"
+        } else {
+            "Hunk patch copied — ready for git apply"
+        };
+        self.notify_copied(message, via);
+        Ok(())
+    }
+
+    /// Save the diff of every file in view (filters apply) to
+    /// `<er dir>/patches/<branch>-<time>.patch`.
+    pub fn save_view_patch(&mut self) -> Result<()> {
+        let indices: Vec<usize> = self.tab().visible_files().iter().map(|(i, _)| *i).collect();
+        for &i in &indices {
+            self.tab_mut().ensure_file_parsed_at(i);
+        }
+        let tab = self.tab();
+        let files = tab.active_diff_files();
+        let (mut patch, mut saved, mut left_out) = (String::new(), 0, 0);
+        for file in indices.iter().filter_map(|&i| files.get(i)) {
+            let hunks: Vec<&git::DiffHunk> = file.hunks.iter().collect();
+            match git::file_patch(file, &hunks, false, None) {
+                Ok(text) if !hunks.is_empty() => {
+                    patch.push_str(&text);
+                    saved += 1;
+                }
+                // Binary, compacted, or folded: nothing applyable in memory
+                _ => left_out += 1,
+            }
+        }
+        if patch.is_empty() {
+            self.notify("Nothing to save — no file in view has a text diff");
+            return Ok(());
+        }
+        let dir = tab.er_root.patches_dir();
+        std::fs::create_dir_all(&dir)?;
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let branch = match tab.current_branch.replace('/', "-") {
+            b if b.is_empty() => "diff".to_string(),
+            b => b,
+        };
+        let path = format!("{}/{}-{}.patch", dir, branch, secs);
+        std::fs::write(&path, patch)?;
+        let mut message = format!(
+            "Saved {} file{} to {}",
+            saved,
+            if saved == 1 { "" } else { "s" },
+            path
+        );
+        if left_out > 0 {
+            message.push_str(&format!(" ({} without a text diff left out)", left_out));
+        }
+        self.notify(&message);
+        Ok(())
+    }
+
+    /// Copy a GitHub permalink to the cursor line (or selection, or hunk).
+    pub fn copy_permalink(&mut self) -> Result<()> {
+        let tab = self.tab();
+        let Some(file) = tab.selected_diff_file() else {
+            self.notify("No file selected");
+            return Ok(());
+        };
+        let lines = match tab.cursor_head_lines() {
+            Ok(lines) => lines,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return Ok(());
+            }
+        };
+        let path = file.path.clone();
+        let working_tree = matches!(tab.mode, DiffMode::Unstaged | DiffMode::Staged);
+        let (owner, repo, sha) = match tab.permalink_target() {
+            Ok(target) => target,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return Ok(());
+            }
+        };
+        let url = crate::github::blob_permalink(&owner, &repo, &sha, &path, lines);
+        let via = self.copy_to_clipboard(&url)?;
+        if working_tree {
+            self.notify_copied(
+                "⚠ Permalink copied — line numbers are from the working tree, not the commit",
+                via,
+            );
+        } else {
+            self.notify_copied(&format!("Copied: {}", url), via);
+        }
+        Ok(())
+    }
+
+    /// Copy the `git diff` (or `gh pr diff`) command that reproduces the view.
+    pub fn copy_diff_command(&mut self) -> Result<()> {
+        let Some(cmd) = self.tab().view_diff_command() else {
+            self.notify("No diff in this view");
+            return Ok(());
+        };
+        let via = self.copy_to_clipboard(&cmd)?;
+        self.notify_copied(&format!("Copied: {}", cmd), via);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_command_follows_mode() {
+        let mut tab = TabState::new_for_test(vec![]);
+        assert_eq!(
+            tab.view_diff_command().unwrap(),
+            "git diff main...HEAD --unified=10"
+        );
+        tab.mode = DiffMode::Staged;
+        assert_eq!(
+            tab.view_diff_command().unwrap(),
+            "git diff --staged --unified=10"
+        );
+        tab.mode = DiffMode::Hidden;
+        assert!(tab.view_diff_command().is_none());
+    }
+
+    #[test]
+    fn diff_command_for_pr_tabs_uses_gh() {
+        let mut tab = TabState::new_for_test(vec![]);
+        tab.pr_number = Some(42);
+        tab.remote_repo = Some("acme/app".into());
+        assert_eq!(
+            tab.view_diff_command().unwrap(),
+            "gh pr diff 42 -R acme/app"
+        );
+    }
+
+    #[test]
+    fn copy_toast_names_only_osc52() {
+        use crate::clipboard::ClipboardBackend;
+        let mut app = App::new_for_test(vec![]);
+        app.notify_copied("Copied: a.rs", ClipboardBackend::Pbcopy);
+        assert_eq!(app.watch_message.as_deref(), Some("Copied: a.rs"));
+        app.notify_copied("Copied: a.rs", ClipboardBackend::Osc52);
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Copied: a.rs · via osc52")
+        );
+    }
+
+    #[test]
+    fn saved_patch_holds_the_files_in_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,1 +1,1 @@\n-x\n+y\n\
+                    diff --git a/b.md b/b.md\n--- a/b.md\n+++ b/b.md\n@@ -1,0 +1,1 @@\n+z\n";
+        let mut app = App::new_for_test(git::parse_diff(diff));
+        let tab = app.tab_mut();
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        tab.current_branch = "feat/x".into();
+        tab.apply_filter_expr("*.rs");
+
+        app.save_view_patch().unwrap();
+        let saved: Vec<_> = std::fs::read_dir(app.tab().er_root.patches_dir())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(saved.len(), 1);
+        assert!(saved[0]
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("feat-x-"));
+        let patch = std::fs::read_to_string(&saved[0]).unwrap();
+        assert!(patch.starts_with("diff --git a/a.rs b/a.rs\n"));
+        assert!(!patch.contains("b.md"));
+    }
+}
@@ -0,0 +1,111 @@
+//! Your own shell commands (`[commands.custom]`), listed in the Verify hub
+//! and run like the built-in ones: in the background from the repo root, with
+//! output streamed to the agent log panel.
+//!
+//! On top of `{base}`, `{branch}`, `{repo}` and `{output}` they can use the
+//! cursor: `{file}` (selected path), `{line}` (head-side line under the
+//! cursor, or the hunk start) and `{hunk_patch}` (path to a file holding the
+//! current hunk as a patch). Every value is shell-quoted.
+
+use anyhow::Result;
+
+use super::App;
+use crate::ai::prompts::sanitize_for_shell;
+use crate::git;
+
+/// Prefix keeping custom names apart from built-ins in the agent log.
+const NAME_PREFIX: &str = "custom/";
+
+impl App {
+    /// Fill the cursor placeholders in `template`; `{base}`, `{branch}`,
+    /// `{repo}` and `{output}` are left for [`App::spawn_command`].
+    fn expand_custom_command(&self, template: &str) -> Result<String> {
+        let tab = self.tab();
+        let file = tab.selected_diff_file();
+        let needs_file = ["{file}", "{line}", "{hunk_patch}"]
+            .iter()
+            .any(|p| template.contains(p));
+        if needs_file && file.is_none() {
+            anyhow::bail!("needs a selected file");
+        }
+        let mut cmd = template.to_string();
+        if let Some(file) = file {
+            cmd = cmd.replace("{file}", &sanitize_for_shell(&file.path));
+            let line = tab
+                .cursor_head_lines()
+                .ok()
+                .flatten()
+                .map(|(start, _)| start.to_string())
+                .unwrap_or_default();
+            cmd = cmd.replace("{line}", &sanitize_for_shell(&line));
+            if cmd.contains("{hunk_patch}") {
+                let Some(hunk) = file.hunks.get(tab.current_hunk) else {
+                    anyhow::bail!("needs a hunk under the cursor");
+                };
+                let patch = git::file_patch(file, &[hunk], false, None)?;
+                let dir = tab.er_dir();
+                std::fs::create_dir_all(&dir)?;
+                let path = format!("{}/hunk.patch", dir);
+                std::fs::write(&path, patch)?;
+                cmd = cmd.replace("{hunk_patch}", &sanitize_for_shell(&path));
+            }
+        }
+        Ok(cmd)
+    }
+
+    /// Run `[commands.custom]` entry `name` and show its output.
+    pub fn run_custom_command(&mut self, name: &str) -> Result<()> {
+        let Some(template) = self.config.commands.custom.get(name).cloned() else {
+            self.notify(&format!("{}: not configured", name));
+            return Ok(());
+        };
+        let cmd = match self.expand_custom_command(&template) {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                self.notify(&format!("{}: {:#}", name, e));
+                return Ok(());
+            }
+        };
+        self.spawn_command(&format!("{}{}", NAME_PREFIX, name), &cmd)?;
+        let tab = self.tab_mut();
+        tab.panel = Some(crate::ai::PanelContent::AgentLog);
+        tab.agent_log_auto_scroll = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_placeholders_are_quoted_and_the_patch_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let diff = "diff --git a/src/it's.rs b/src/it's.rs\n--- a/src/it's.rs\n+++ b/src/it's.rs\n\
+                    @@ -4,1 +4,1 @@\n-x\n+y\n";
+        let mut app = App::new_for_test(git::parse_diff(diff));
+        app.tab_mut().er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+
+        let cmd = app
+            .expand_custom_command("run {file}:{line} --patch {hunk_patch} {base}")
+            .unwrap();
+        let patch_path = format!("{}/.er/hunk.patch", dir.path().display());
+        assert_eq!(
+            cmd,
+            format!(
+                "run 'src/it'\\''s.rs':'4' --patch '{}' {{base}}",
+                patch_path
+            )
+        );
+        assert!(std::fs::read_to_string(patch_path)
+            .unwrap()
+            .contains("+y\n"));
+
+        let empty = App::new_for_test(vec![]);
+        assert!(empty.expand_custom_command("cargo test {file}").is_err());
+        assert_eq!(
+            empty.expand_custom_command("make {branch}").unwrap(),
+            "make {branch}"
+        );
+    }
+}
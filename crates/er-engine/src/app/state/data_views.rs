@@ -0,0 +1,180 @@
+//! Notebook and table views of data files (Alt+v), in place of the line
+//! diff: `.ipynb` files cell by cell (see [`crate::analysis::notebook`]),
+//! CSV / TSV hunks as aligned columns ([`crate::analysis::tabular`]).
+//!
+//! Views are off until asked for, per file. Only the selected file's view
+//! is built, when it's toggled on or the diff changes under it.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{App, DiffMode, TabState};
+use crate::analysis::notebook::{self, CellDiff};
+use crate::analysis::tabular::{self, Table};
+use crate::git::{self, DiffHunk, LineType};
+
+/// A file's data view.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataView {
+    /// The cells, or why a side didn't parse
+    Notebook(Result<Vec<CellDiff>, String>),
+    Table(Table),
+}
+
+/// The data views of a tab.
+#[derive(Debug, Default)]
+pub struct DataViews {
+    /// Paths shown as a data view
+    pub on: HashSet<String>,
+    /// The diff the built views are for
+    diff_hash: String,
+    built: HashMap<String, DataView>,
+}
+
+/// Whether `path` has a data view.
+pub fn has_data_view(path: &str) -> bool {
+    path.to_ascii_lowercase().ends_with(".ipynb") || tabular::delimiter(path).is_some()
+}
+
+/// The view of `path` from its new side `new` (`None` when it can't be
+/// read) and its `hunks`.
+fn build(path: &str, new: Option<&str>, hunks: &[DiffHunk]) -> Option<DataView> {
+    if path.to_ascii_lowercase().ends_with(".ipynb") {
+        let Some(new) = new else {
+            return Some(DataView::Notebook(Err("the new side can't be read".into())));
+        };
+        let old = git::old_side(new, hunks);
+        let cells = match (notebook::parse(&old), notebook::parse(new)) {
+            (Ok(old), Ok(new)) => Ok(notebook::diff(&old, &new)),
+            (Err(e), _) => Err(format!("old side: {}", e)),
+            (_, Err(e)) => Err(format!("new side: {}", e)),
+        };
+        return Some(DataView::Notebook(cells));
+    }
+    let delim = tabular::delimiter(path)?;
+    // The first line from the hunks when they show it, else from the file
+    let first = hunks
+        .first()
+        .and_then(|h| {
+            h.lines
+                .iter()
+                .find(|l| l.new_num == Some(1) && l.line_type != LineType::Delete)
+        })
+        .map(|l| l.content.clone())
+        .or_else(|| new.and_then(|n| n.lines().next().map(str::to_string)));
+    Some(DataView::Table(tabular::table(
+        hunks,
+        first.as_deref(),
+        delim,
+    )))
+}
+
+impl TabState {
+    /// The data view of `path` when it's on and built.
+    pub fn data_view(&self, path: &str) -> Option<&DataView> {
+        if !self.data_views.on.contains(path) || self.data_views.diff_hash != self.diff_hash {
+            return None;
+        }
+        self.data_views.built.get(path)
+    }
+
+    /// Build the selected file's view when it's on and missing.
+    fn build_data_view(&mut self) -> bool {
+        if self.data_views.diff_hash != self.diff_hash {
+            self.data_views.diff_hash = self.diff_hash.clone();
+            self.data_views.built.clear();
+        }
+        let Some(file) = self.selected_diff_file() else {
+            return false;
+        };
+        let path = file.path.clone();
+        if !self.data_views.on.contains(&path) || self.data_views.built.contains_key(&path) {
+            return false;
+        }
+        // Tables only need the file for a header the hunks don't show
+        let needs_file = path.to_ascii_lowercase().ends_with(".ipynb")
+            || file.hunks.first().is_none_or(|h| h.new_start > 1);
+        let new = if file.status == git::FileStatus::Deleted {
+            Some(String::new())
+        } else if needs_file {
+            self.new_side().and_then(|side| side.read(&path))
+        } else {
+            None
+        };
+        let hunks = file.hunks.clone();
+        match build(&path, new.as_deref(), &hunks) {
+            Some(view) => {
+                self.data_views.built.insert(path, view);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl App {
+    /// Alt+v: show the selected notebook or CSV / TSV file as a data view,
+    /// or go back to its line diff.
+    pub fn toggle_data_view(&mut self) {
+        if matches!(self.tab().mode, DiffMode::History | DiffMode::Tour) {
+            return;
+        }
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        if !has_data_view(&path) {
+            self.notify("Data views are for .ipynb, .csv and .tsv files");
+            return;
+        }
+        let tab = self.tab_mut();
+        if tab.data_views.on.remove(&path) {
+            self.notify("Line diff");
+            return;
+        }
+        tab.data_views.on.insert(path.clone());
+        tab.build_data_view();
+        self.notify(if path.to_ascii_lowercase().ends_with(".ipynb") {
+            "Notebook view — Alt+v for the line diff"
+        } else {
+            "Table view — Alt+v for the line diff"
+        });
+    }
+
+    /// Build the selected file's data view when the diff or selection
+    /// moved under it. True when one was built.
+    pub fn poll_data_views(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if tab.data_views.on.is_empty() || matches!(tab.mode, DiffMode::History | DiffMode::Tour) {
+            return false;
+        }
+        tab.build_data_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn the_header_comes_from_the_file_when_the_hunks_skip_it() {
+        let diff = "diff --git a/users.csv b/users.csv\n--- a/users.csv\n+++ b/users.csv\n\
+                    @@ -8,2 +8,2 @@\n 7,Gus,Oslo\n-8,Hal,Bergen\n+8,Hal,Bodø\n";
+        let files = parse_diff(diff);
+        let Some(DataView::Table(table)) = build(
+            "users.csv",
+            Some("id,name,city\n1,Ann,Oslo\n"),
+            &files[0].hunks,
+        ) else {
+            panic!("expected a table");
+        };
+        assert_eq!(table.header.as_deref().unwrap(), ["id", "name", "city"]);
+        assert_eq!(table.rows.len(), 3);
+
+        let Some(DataView::Notebook(cells)) = build("nb.ipynb", None, &files[0].hunks) else {
+            panic!("expected a notebook");
+        };
+        assert!(cells.is_err());
+        assert!(build("users.json", None, &files[0].hunks).is_none());
+        assert!(has_data_view("Data/Users.TSV"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Comment drafts that outlive the input box.
+//!
+//! The comment being typed is autosaved to `session.json` (debounced in the
+//! event loop) and comes back paused on the next launch — even when the diff
+//! changed, unlike the rest of the session. A draft that gets cancelled or
+//! displaced by starting another comment is stashed in the drafts list
+//! (`D`) instead of being thrown away.
+
+use super::{App, CommentType, HubAction, HubItem, HubKind, InputMode, OverlayData, TabState};
+use serde::{Deserialize, Serialize};
+use tui_textarea::TextArea;
+
+/// Oldest drafts are dropped past this.
+pub const MAX_DRAFTS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentDraft {
+    pub text: String,
+    /// Target file; empty for a general PR comment.
+    #[serde(default)]
+    pub file: String,
+    #[serde(default)]
+    pub hunk: usize,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub line_end: Option<usize>,
+    /// "question", "note" or "github"
+    #[serde(default)]
+    pub comment_type: String,
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(default)]
+    pub finding_ref: Option<String>,
+    /// About the whole file rather than a hunk or line.
+    #[serde(default)]
+    pub file_level: bool,
+    #[serde(default)]
+    pub saved_at: String,
+}
+
+pub(super) fn comment_type_key(ty: CommentType) -> &'static str {
+    match ty {
+        CommentType::Question => "question",
+        CommentType::Note => "note",
+        CommentType::GitHubComment => "github",
+    }
+}
+
+pub(super) fn comment_type_from_key(key: &str) -> CommentType {
+    match key {
+        "question" => CommentType::Question,
+        "note" => CommentType::Note,
+        _ => CommentType::GitHubComment,
+    }
+}
+
+impl CommentDraft {
+    /// `src/lib.rs:42`, `src/lib.rs` or `PR comment`.
+    pub fn location(&self) -> String {
+        match (self.file.is_empty(), self.line) {
+            (true, _) => "PR comment".to_string(),
+            (false, _) if self.file_level => format!("{} (file)", self.file),
+            (false, Some(line)) => format!("{}:{}", self.file, line),
+            (false, None) => self.file.clone(),
+        }
+    }
+
+    pub fn kind_label(&self) -> &'static str {
+        if self.reply_to.is_some() || self.finding_ref.is_some() {
+            return "reply";
+        }
+        match comment_type_from_key(&self.comment_type) {
+            CommentType::Question => "question",
+            CommentType::Note => "note",
+            CommentType::GitHubComment => "comment",
+        }
+    }
+}
+
+impl TabState {
+    /// The comment box as a draft, `None` when it's empty or editing an
+    /// existing comment (that text is already saved).
+    pub fn current_comment_draft(&self) -> Option<CommentDraft> {
+        let text = self.comment_text();
+        if text.is_empty() || self.comment_edit_id.is_some() || self.checklist_edit.is_some() {
+            return None;
+        }
+        Some(CommentDraft {
+            text,
+            file: self.comment_file.clone(),
+            hunk: self.comment_hunk,
+            line: self.comment_line_num,
+            line_end: self.comment_line_end,
+            comment_type: comment_type_key(self.comment_type).to_string(),
+            reply_to: self.comment_reply_to.clone(),
+            finding_ref: self.comment_finding_ref.clone(),
+            file_level: self.comment_file_level,
+            saved_at: super::chrono_now(),
+        })
+    }
+
+    /// Move the comment box's text into the drafts list (newest first) and
+    /// clear the box. Returns whether anything was stashed. A checklist item
+    /// being edited is dropped, not stashed.
+    pub fn stash_comment_draft(&mut self) -> bool {
+        if self.checklist_edit.take().is_some() {
+            self.comment_textarea = TextArea::default();
+            return false;
+        }
+        let Some(draft) = self.current_comment_draft() else {
+            return false;
+        };
+        self.comment_drafts
+            .retain(|d| !(d.text == draft.text && d.file == draft.file && d.line == draft.line));
+        self.comment_drafts.insert(0, draft);
+        self.comment_drafts.truncate(MAX_DRAFTS);
+        self.comment_textarea = TextArea::default();
+        true
+    }
+
+    /// Put `draft` back into the comment box.
+    pub fn load_comment_draft(&mut self, draft: &CommentDraft) {
+        self.comment_textarea = TextArea::new(draft.text.lines().map(str::to_string).collect());
+        self.comment_file = draft.file.clone();
+        self.comment_hunk = draft.hunk;
+        self.comment_line_num = draft.line;
+        self.comment_line_end = draft.line_end;
+        self.comment_type = comment_type_from_key(&draft.comment_type);
+        self.comment_reply_to = draft.reply_to.clone();
+        self.comment_finding_ref = draft.finding_ref.clone();
+        self.comment_file_level = draft.file_level;
+        self.comment_edit_id = None;
+    }
+}
+
+impl App {
+    /// Startup toast for drafts brought back from the last session.
+    pub fn notify_restored_drafts(&mut self) {
+        if self.has_comment_draft() {
+            let location = self
+                .tab()
+                .current_comment_draft()
+                .map(|d| d.location())
+                .unwrap_or_default();
+            self.notify_long(&format!(
+                "Unsent comment on {} restored — Tab to resume, D for drafts",
+                location
+            ));
+            return;
+        }
+        let count = self.tab().comment_drafts.len();
+        if count > 0 {
+            self.notify(&format!(
+                "{} saved comment draft{} — D to review",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    /// Open the drafts list (newest first).
+    pub fn open_drafts_hub(&mut self) {
+        let tab = self.tab();
+        if tab.comment_drafts.is_empty() {
+            self.notify("No saved comment drafts");
+            return;
+        }
+        let mut items: Vec<HubItem> = tab
+            .comment_drafts
+            .iter()
+            .enumerate()
+            .map(|(idx, draft)| HubItem {
+                label: format!("{} · {}", draft.location(), draft.kind_label()),
+                hint: String::new(),
+                description: draft.text.lines().next().unwrap_or("").to_string(),
+                action: HubAction::ResumeDraft(idx),
+                is_header: false,
+                enabled: true,
+            })
+            .collect();
+        items.push(HubItem {
+            label: "Discard all drafts".into(),
+            hint: String::new(),
+            description: format!("Delete {} saved draft(s)", tab.comment_drafts.len()),
+            action: HubAction::DiscardDrafts,
+            is_header: false,
+            enabled: true,
+        });
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Drafts,
+            title: None,
+            items,
+            selected: 0,
+        });
+    }
+
+    /// Reopen draft `idx` for editing at its file, stashing whatever is in
+    /// the comment box first.
+    pub fn resume_saved_draft(&mut self, idx: usize) {
+        let tab = self.tab_mut();
+        if idx >= tab.comment_drafts.len() {
+            return;
+        }
+        let draft = tab.comment_drafts.remove(idx);
+        tab.stash_comment_draft();
+        if !draft.file.is_empty() {
+            if let Some(pos) = tab.files.iter().position(|f| f.path == draft.file) {
+                tab.selected_file = pos;
+                tab.current_hunk = draft.hunk.min(tab.files[pos].hunks.len().saturating_sub(1));
+                tab.current_line = None;
+            }
+        }
+        tab.load_comment_draft(&draft);
+        self.input_mode = InputMode::Comment;
+    }
+
+    pub fn discard_drafts(&mut self) {
+        let count = std::mem::take(&mut self.tab_mut().comment_drafts).len();
+        self.notify(&format!(
+            "Discarded {} draft{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stash_keeps_newest_first_and_skips_edits() {
+        let mut tab = TabState::new_for_test(vec![]);
+        tab.comment_file = "src/a.rs".into();
+        tab.comment_line_num = Some(3);
+        tab.comment_textarea = TextArea::new(vec!["first".into(), "second line".into()]);
+        assert!(tab.stash_comment_draft());
+        assert!(tab.comment_text().is_empty());
+        tab.comment_textarea = TextArea::new(vec!["other".into()]);
+        tab.stash_comment_draft();
+        assert_eq!(tab.comment_drafts[0].text, "other");
+        assert_eq!(tab.comment_drafts[1].text, "first\nsecond line");
+        assert_eq!(tab.comment_drafts[1].location(), "src/a.rs:3");
+
+        tab.comment_textarea = TextArea::new(vec!["edited".into()]);
+        tab.comment_edit_id = Some("q-1".into());
+        assert!(!tab.stash_comment_draft());
+    }
+
+    #[test]
+    fn resume_restores_multiline_text_and_stashes_current() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().comment_textarea = TextArea::new(vec!["older".into()]);
+        app.tab_mut().stash_comment_draft();
+        app.tab_mut().comment_textarea = TextArea::new(vec!["paused".into()]);
+        app.resume_saved_draft(0);
+        assert_eq!(app.input_mode, InputMode::Comment);
+        assert_eq!(app.tab().comment_text(), "older");
+        assert_eq!(app.tab().comment_drafts.len(), 1);
+        assert_eq!(app.tab().comment_drafts[0].text, "paused");
+    }
+}
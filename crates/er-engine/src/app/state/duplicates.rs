@@ -0,0 +1,275 @@
+//! Near-duplicate code as review findings (see [`crate::git::find_duplicates`]).
+//!
+//! Whenever the diff changes, its added blocks are fingerprinted on a
+//! worker thread and compared with one another and, for diffs whose new
+//! side is the working tree, with the repo's tracked files. Each block with
+//! a copy becomes a finding in the "duplicate" category, with the copy as
+//! its evidence; Alt+m on a focused duplicate finding jumps to the copy.
+//! The findings are kept apart from the AI review on disk and merged into
+//! it again after every reload.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, TabState};
+use crate::ai::{Confidence, EvidenceItem, Finding, RiskLevel};
+use crate::git::{self, AddedBlock, Duplicate};
+
+/// The category the findings are listed under.
+pub const DUPLICATE_CATEGORY: &str = "duplicate";
+
+/// The duplicate findings of a tab's diff.
+#[derive(Debug, Default)]
+pub struct DuplicateScan {
+    /// The diff the findings are for
+    pub diff_hash: String,
+    /// `(path, finding)`
+    pub findings: Vec<(String, Finding)>,
+    rx: Option<Receiver<Vec<(String, Finding)>>>,
+}
+
+/// The finding for `dup`, a copy of one of `blocks`.
+fn duplicate_finding(blocks: &[AddedBlock], dup: &Duplicate) -> (String, Finding) {
+    let block = &blocks[dup.block];
+    let copy = &dup.copy;
+    let place = format!("{}:{}–{}", copy.path, copy.line_start, copy.line_end);
+    let mut description = format!(
+        "Lines {}–{} share {:.0}% of their code with {}",
+        block.first_line(),
+        block.last_line(),
+        dup.overlap * 100.0,
+        place
+    );
+    if copy.added {
+        description.push_str(", which this diff adds too");
+    }
+    description.push('.');
+    if dup.more > 0 {
+        description.push_str(&format!(
+            " {} more place{} look{} alike.",
+            dup.more,
+            if dup.more == 1 { "" } else { "s" },
+            if dup.more == 1 { "s" } else { "" }
+        ));
+    }
+    let finding = Finding {
+        id: format!("dup-{}:{}", block.path, block.first_line()),
+        severity: RiskLevel::Low,
+        category: DUPLICATE_CATEGORY.to_string(),
+        title: format!("Near-duplicate of {}", place),
+        description,
+        hunk_index: Some(block.hunk),
+        line_start: Some(block.first_line()),
+        line_end: Some(block.last_line()),
+        suggestion: if copy.added {
+            "Keep one copy and call it from both places".to_string()
+        } else {
+            "Reuse the existing code, or extract what both need".to_string()
+        },
+        related_files: if copy.path == block.path {
+            vec![]
+        } else {
+            vec![copy.path.clone()]
+        },
+        outside_diff: false,
+        confidence: Confidence::Informational,
+        verification_plan: String::new(),
+        evidence: vec![EvidenceItem {
+            file: copy.path.clone(),
+            line_start: Some(copy.line_start),
+            line_end: Some(copy.line_end),
+            note: "The copy".to_string(),
+        }],
+        responses: vec![],
+        resolved: false,
+        resolved_note: String::new(),
+        resolved_at: String::new(),
+        promoted_to: None,
+    };
+    (block.path.clone(), finding)
+}
+
+impl TabState {
+    /// Put this diff's duplicate findings back into a freshly loaded review.
+    pub(super) fn merge_duplicate_findings(&mut self) {
+        if self.duplicates.diff_hash != self.diff_hash {
+            return;
+        }
+        self.ai.merge_builtin_findings(
+            DUPLICATE_CATEGORY,
+            &self.duplicates.findings,
+            &self.branch_diff_hash,
+        );
+    }
+
+    /// Where the repo copies are looked for: the working tree the diff's
+    /// new side is, if it is one.
+    pub(super) fn duplicate_scan_root(&self) -> Option<String> {
+        if self.is_remote() || self.new_side_rev().is_some() {
+            return None;
+        }
+        Some(
+            self.local_branch_checkout_root
+                .clone()
+                .unwrap_or_else(|| self.repo_root.clone()),
+        )
+    }
+
+    /// The copy of the focused duplicate finding, as `(path, first line)`.
+    pub fn focused_duplicate_copy(&self) -> Option<(String, usize)> {
+        let id = self.focused_finding_id.as_ref()?;
+        let finding = self
+            .ai
+            .review
+            .as_ref()?
+            .files
+            .values()
+            .flat_map(|fr| &fr.findings)
+            .find(|f| &f.id == id && f.category == DUPLICATE_CATEGORY)?;
+        let copy = finding.evidence.first()?;
+        Some((copy.file.clone(), copy.line_start?))
+    }
+}
+
+impl App {
+    /// Scan the active tab's diff for duplicates when it changed, and merge
+    /// the findings once the scan is done. True when they arrived.
+    pub fn poll_duplicates(&mut self) -> bool {
+        let settings = self.config.duplicates.clone();
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.duplicates.rx {
+            return match rx.try_recv() {
+                // A scan of a diff since replaced is dropped; the next poll
+                // starts one of the current diff
+                Ok(_) if tab.duplicates.diff_hash != tab.diff_hash => {
+                    tab.duplicates.rx = None;
+                    false
+                }
+                Ok(findings) => {
+                    tab.duplicates.rx = None;
+                    tab.duplicates.findings = findings;
+                    tab.ai.merge_builtin_findings(
+                        DUPLICATE_CATEGORY,
+                        &tab.duplicates.findings,
+                        &tab.branch_diff_hash,
+                    );
+                    !tab.duplicates.findings.is_empty()
+                }
+                Err(TryRecvError::Empty) => false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.duplicates.rx = None;
+                    false
+                }
+            };
+        }
+        if !settings.enabled
+            || matches!(tab.mode, DiffMode::History | DiffMode::Tour)
+            || tab.duplicates.diff_hash == tab.diff_hash
+        {
+            return false;
+        }
+        tab.duplicates.diff_hash = tab.diff_hash.clone();
+        let had_findings = !tab.duplicates.findings.is_empty();
+        tab.duplicates.findings.clear();
+        if had_findings {
+            tab.ai
+                .merge_builtin_findings(DUPLICATE_CATEGORY, &[], &tab.branch_diff_hash);
+        }
+        let parsed = tab.parsed_stubs();
+        let blocks = git::added_blocks(&tab.all_hunks(&parsed), settings.min_lines);
+        if blocks.is_empty() {
+            return had_findings;
+        }
+        let root = tab.duplicate_scan_root().filter(|_| settings.repo);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let findings = git::find_duplicates(&blocks, root.as_deref())
+                .iter()
+                .map(|dup| duplicate_finding(&blocks, dup))
+                .collect();
+            let _ = tx.send(findings);
+        });
+        tab.duplicates.rx = Some(rx);
+        had_findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::merge_builtin_findings;
+    use crate::git::{parse_diff, CopyLocation};
+
+    fn block(path: &str, lines: std::ops::Range<usize>) -> AddedBlock {
+        AddedBlock {
+            path: path.to_string(),
+            hunk: 0,
+            lines: lines.map(|n| (n, format!("line {}", n))).collect(),
+        }
+    }
+
+    #[test]
+    fn findings_link_to_the_copy_and_replace_the_last_scan() {
+        let blocks = vec![block("src/a.rs", 10..20)];
+        let dup = Duplicate {
+            block: 0,
+            copy: CopyLocation {
+                path: "src/old.rs".into(),
+                line_start: 40,
+                line_end: 49,
+                added: false,
+            },
+            overlap: 0.8,
+            more: 1,
+        };
+        let (path, finding) = duplicate_finding(&blocks, &dup);
+        assert_eq!(path, "src/a.rs");
+        assert_eq!(finding.title, "Near-duplicate of src/old.rs:40–49");
+        assert_eq!(
+            finding.description,
+            "Lines 10–19 share 80% of their code with src/old.rs:40–49. 1 more place looks alike."
+        );
+        assert_eq!((finding.line_start, finding.line_end), (Some(10), Some(19)));
+        assert_eq!(finding.related_files, vec!["src/old.rs".to_string()]);
+        assert_eq!(finding.evidence[0].line_start, Some(40));
+
+        let mut review = None;
+        merge_builtin_findings(&mut review, DUPLICATE_CATEGORY, &[(path, finding)], "h");
+        assert_eq!(review.as_ref().unwrap().files["src/a.rs"].findings.len(), 1);
+        // A rescan that finds nothing drops the made-up entry again
+        merge_builtin_findings(&mut review, DUPLICATE_CATEGORY, &[], "h");
+        assert!(review.unwrap().files.is_empty());
+    }
+
+    #[test]
+    fn alt_m_on_a_duplicate_finding_jumps_to_the_copy() {
+        let body: Vec<String> = (0..8)
+            .map(|i| format!("+    total += items[{}].price * items[{}].quantity;", i, i))
+            .collect();
+        let file = |p: &str| {
+            format!(
+                "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -1,1 +1,9 @@\n use x;\n{}\n",
+                body.join("\n")
+            )
+        };
+        let mut app = App::new_for_test(parse_diff(&format!("{}{}", file("a.rs"), file("b.rs"))));
+        app.tab_mut().diff_hash = "h".into();
+        let tab = app.tab();
+        let parsed = tab.parsed_stubs();
+        let blocks = git::added_blocks(&tab.all_hunks(&parsed), 6);
+        let findings: Vec<(String, Finding)> = git::find_duplicates(&blocks, None)
+            .iter()
+            .map(|d| duplicate_finding(&blocks, d))
+            .collect();
+        assert_eq!(findings.len(), 2);
+        let tab = app.tab_mut();
+        tab.duplicates.diff_hash = "h".into();
+        tab.duplicates.findings = findings;
+        tab.merge_duplicate_findings();
+        tab.focused_finding_id = Some("dup-a.rs:2".into());
+        assert_eq!(tab.focused_duplicate_copy(), Some(("b.rs".to_string(), 2)));
+
+        app.find_moved_code();
+        assert_eq!(app.tab().selected_file, 1);
+        assert_eq!(app.tab().current_line, Some(1));
+    }
+}
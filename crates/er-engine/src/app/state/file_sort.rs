@@ -0,0 +1,287 @@
+//! File tree order (`m` cycles it): the diff's own order, recently edited,
+//! name, change size, AI risk, findings, directory, unreviewed first,
+//! grouped by monorepo package, or the repo's guided review order. The last
+//! two split the tree into sections with their own reviewed counts.
+
+use std::cmp::Ordering;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::TabState;
+use crate::ai::risk_rank;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileSort {
+    /// As `git diff` lists them
+    #[default]
+    Diff,
+    /// Newest mtime first
+    Recent,
+    /// File name, ignoring the directory
+    Name,
+    /// Most lines added + removed first
+    Size,
+    /// AI file risk, high first; unreviewed-by-AI files last
+    Risk,
+    /// Most unresolved findings first
+    Findings,
+    /// Directory, then file name
+    Directory,
+    /// Files not marked reviewed first
+    Unreviewed,
+    /// Grouped by monorepo package, then path; files outside one last
+    Package,
+    /// `[review_order]` phases in order, then the files no phase matches
+    Guided,
+}
+
+impl FileSort {
+    pub fn next(self) -> Self {
+        match self {
+            FileSort::Diff => FileSort::Recent,
+            FileSort::Recent => FileSort::Name,
+            FileSort::Name => FileSort::Size,
+            FileSort::Size => FileSort::Risk,
+            FileSort::Risk => FileSort::Findings,
+            FileSort::Findings => FileSort::Directory,
+            FileSort::Directory => FileSort::Unreviewed,
+            FileSort::Unreviewed => FileSort::Package,
+            FileSort::Package => FileSort::Guided,
+            FileSort::Guided => FileSort::Diff,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FileSort::Diff => "diff order",
+            FileSort::Recent => "recent first",
+            FileSort::Name => "name",
+            FileSort::Size => "change size",
+            FileSort::Risk => "risk",
+            FileSort::Findings => "findings",
+            FileSort::Directory => "directory",
+            FileSort::Unreviewed => "unreviewed first",
+            FileSort::Package => "package",
+            FileSort::Guided => "guided order",
+        }
+    }
+}
+
+/// `(directory, file name)` of a repo path.
+fn split_path(path: &str) -> (&str, &str) {
+    path.rsplit_once('/').unwrap_or(("", path))
+}
+
+/// `items` rearranged so position `n` holds the old `items[order[n]]`.
+/// Reviewed progress of one section of a grouped tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupProgress {
+    pub name: String,
+    pub reviewed: usize,
+    /// Changed files, not counting skipped ones
+    pub total: usize,
+}
+
+fn reorder<T>(items: Vec<T>, order: &[usize]) -> Vec<T> {
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    order.iter().filter_map(|&i| slots[i].take()).collect()
+}
+
+impl TabState {
+    /// Reorder `files` by `file_sort`, keeping the selected file selected.
+    /// Stable, so ties keep the diff order; lazy `file_headers` move with
+    /// their files.
+    pub(super) fn sort_files(&mut self) {
+        if self.file_sort == FileSort::Diff || self.files.len() < 2 {
+            return;
+        }
+        let files = &self.files;
+        let mut order: Vec<usize> = (0..files.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (fa, fb) = (&files[a], &files[b]);
+            match self.file_sort {
+                FileSort::Diff => Ordering::Equal,
+                FileSort::Recent => {
+                    let mtime = |p: &str| {
+                        self.mtime_cache
+                            .get(p)
+                            .copied()
+                            .unwrap_or(SystemTime::UNIX_EPOCH)
+                    };
+                    mtime(&fb.path).cmp(&mtime(&fa.path))
+                }
+                FileSort::Name => {
+                    let name = |p: &str| split_path(p).1.to_lowercase();
+                    name(&fa.path).cmp(&name(&fb.path))
+                }
+                FileSort::Size => (fb.adds + fb.dels).cmp(&(fa.adds + fa.dels)),
+                FileSort::Risk => {
+                    let rank = |p: &str| {
+                        self.ai
+                            .file_review(p)
+                            .map_or(u8::MAX, |r| risk_rank(&r.risk))
+                    };
+                    rank(&fa.path).cmp(&rank(&fb.path))
+                }
+                FileSort::Findings => {
+                    let count = |p: &str| self.ai.file_active_findings(p).len();
+                    count(&fb.path).cmp(&count(&fa.path))
+                }
+                FileSort::Directory => split_path(&fa.path).cmp(&split_path(&fb.path)),
+                FileSort::Unreviewed => {
+                    let done = |p: &str| !self.needs_review(p);
+                    done(&fa.path).cmp(&done(&fb.path))
+                }
+                FileSort::Package => {
+                    let key = |p: &str| {
+                        self.package_of(p)
+                            .map_or((true, ""), |pkg| (false, pkg.root.as_str()))
+                    };
+                    key(&fa.path)
+                        .cmp(&key(&fb.path))
+                        .then_with(|| fa.path.cmp(&fb.path))
+                }
+                FileSort::Guided => {
+                    let phase = |p: &str| self.review_phase_index(p).unwrap_or(usize::MAX);
+                    phase(&fa.path).cmp(&phase(&fb.path))
+                }
+            }
+        });
+
+        let selected = self.files.get(self.selected_file).map(|f| f.path.clone());
+        if self.file_headers.len() == self.files.len() {
+            self.file_headers = reorder(std::mem::take(&mut self.file_headers), &order);
+        }
+        self.files = reorder(std::mem::take(&mut self.files), &order);
+        if let Some(selected) = selected {
+            if let Some(idx) = self.files.iter().position(|f| f.path == selected) {
+                self.selected_file = idx;
+            }
+        }
+    }
+
+    /// Whether the current order splits the tree into sections.
+    pub fn groups_tree(&self) -> bool {
+        match self.file_sort {
+            FileSort::Package => !self.packages.is_empty(),
+            FileSort::Guided => !self.review_phases.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Section of the grouped tree `path` falls in; `None` for the files
+    /// left over at the end.
+    pub fn tree_group(&self, path: &str) -> Option<&str> {
+        match self.file_sort {
+            FileSort::Package => self.package_of(path).map(|p| p.name.as_str()),
+            FileSort::Guided => self.review_phase(path).map(|p| p.name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Reviewed progress of each tree section, in tree order.
+    pub fn tree_group_progress(&self) -> Vec<GroupProgress> {
+        self.group_progress(|path| self.tree_group(path))
+    }
+
+    /// Reviewed progress per group in the order each group's first file
+    /// appears; files `group_of` puts in no group aren't counted.
+    pub(super) fn group_progress<'a>(
+        &'a self,
+        group_of: impl Fn(&str) -> Option<&'a str>,
+    ) -> Vec<GroupProgress> {
+        let mut progress: Vec<GroupProgress> = Vec::new();
+        for file in self.active_diff_files() {
+            let Some(name) = group_of(&file.path) else {
+                continue;
+            };
+            let idx = match progress.iter().position(|p| p.name == name) {
+                Some(idx) => idx,
+                None => {
+                    progress.push(GroupProgress {
+                        name: name.to_string(),
+                        reviewed: 0,
+                        total: 0,
+                    });
+                    progress.len() - 1
+                }
+            };
+            if self.is_skipped(&file.path) {
+                continue;
+            }
+            progress[idx].total += 1;
+            if self.reviewed.contains_key(&file.path) {
+                progress[idx].reviewed += 1;
+            }
+        }
+        progress
+    }
+
+    /// Switch to the next sort mode and reload the tree in that order.
+    /// The guided order is skipped when the repo doesn't define one.
+    pub fn cycle_file_sort(&mut self) -> FileSort {
+        self.file_sort = self.file_sort.next();
+        if self.file_sort == FileSort::Guided && self.review_phases.is_empty() {
+            self.file_sort = self.file_sort.next();
+        }
+        if self.file_sort == FileSort::Diff {
+            // Back to git's order: only a reload has it
+            let _ = self.refresh_diff();
+        } else {
+            self.sort_files();
+            self.rebuild_hunk_offsets();
+        }
+        self.file_sort
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::added_file;
+    use crate::app::state::App;
+
+    #[test]
+    fn each_mode_orders_the_tree_and_keeps_the_selection() {
+        let mut app = App::new_for_test(vec![]);
+        let tab = app.tab_mut();
+        tab.files = vec![
+            added_file("src/b.rs", 1),
+            added_file("a/z.rs", 9),
+            added_file("src/a.rs", 3),
+        ];
+        tab.selected_file = 1;
+        tab.reviewed.insert("a/z.rs".into(), String::new());
+        let paths =
+            |tab: &TabState| -> Vec<String> { tab.files.iter().map(|f| f.path.clone()).collect() };
+
+        tab.file_sort = FileSort::Size;
+        tab.sort_files();
+        assert_eq!(paths(tab), ["a/z.rs", "src/a.rs", "src/b.rs"]);
+        assert_eq!(tab.selected_file, 0);
+
+        tab.file_sort = FileSort::Name;
+        tab.sort_files();
+        assert_eq!(paths(tab), ["src/a.rs", "src/b.rs", "a/z.rs"]);
+
+        tab.file_sort = FileSort::Directory;
+        tab.sort_files();
+        assert_eq!(paths(tab), ["a/z.rs", "src/a.rs", "src/b.rs"]);
+
+        tab.file_sort = FileSort::Unreviewed;
+        tab.sort_files();
+        assert_eq!(paths(tab), ["src/a.rs", "src/b.rs", "a/z.rs"]);
+        assert_eq!(tab.selected_file, 2);
+
+        tab.packages = vec![crate::git::Package {
+            name: "app".into(),
+            root: "a".into(),
+            kind: crate::git::PackageKind::Cargo,
+        }];
+        tab.file_sort = FileSort::Package;
+        tab.sort_files();
+        assert_eq!(paths(tab), ["a/z.rs", "src/a.rs", "src/b.rs"]);
+    }
+}
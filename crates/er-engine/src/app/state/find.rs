@@ -0,0 +1,350 @@
+//! Find in the current file (Ctrl+f), apart from the `/` file-path search.
+//!
+//! Typing jumps to the first match after where the find started; Alt+r
+//! toggles regex and Alt+c case sensitivity while typing. Enter keeps the
+//! query: every match in the diff is highlighted, n/N step between them
+//! (wrapping at the ends) instead of between hunks, and the status bar
+//! shows `3/12`. Esc clears it. The query follows the selection, so the
+//! same search runs on whichever file is open.
+
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use super::{App, InputMode, TabState};
+use crate::git::LineType;
+
+/// The find query and how to match it.
+#[derive(Debug, Clone, Default)]
+pub struct FileFind {
+    pub query: String,
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Why the regex doesn't compile
+    pub error: Option<String>,
+    matcher: Option<Regex>,
+    /// Cursor (hunk, line) when the find started; typing searches from
+    /// here and Esc goes back
+    origin: (usize, Option<usize>),
+}
+
+/// A match in the selected file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindMatch {
+    pub hunk: usize,
+    pub line: usize,
+    /// Byte range in the line's content
+    pub range: Range<usize>,
+}
+
+impl FileFind {
+    fn rebuild(&mut self) {
+        self.error = None;
+        self.matcher = None;
+        if self.query.is_empty() {
+            return;
+        }
+        let pattern = if self.regex {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        match RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+        {
+            Ok(re) => self.matcher = Some(re),
+            Err(e) => {
+                self.error = Some(
+                    e.to_string()
+                        .lines()
+                        .last()
+                        .unwrap_or("invalid regex")
+                        .trim()
+                        .to_string(),
+                )
+            }
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.rebuild();
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.rebuild();
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.rebuild();
+    }
+
+    pub fn toggle_case(&mut self) {
+        self.case_sensitive = !self.case_sensitive;
+        self.rebuild();
+    }
+
+    /// Byte ranges of the non-empty matches in `text`.
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match &self.matcher {
+            Some(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| m.range())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// `regex · case` flags for the prompt.
+    pub fn flags(&self) -> String {
+        let mut flags = Vec::new();
+        if self.regex {
+            flags.push("regex");
+        }
+        if self.case_sensitive {
+            flags.push("case");
+        }
+        flags.join(" · ")
+    }
+}
+
+impl TabState {
+    /// The active find, once it has something to match.
+    pub fn active_find(&self) -> Option<&FileFind> {
+        self.find.as_ref().filter(|f| f.matcher.is_some())
+    }
+
+    /// Every match in the selected file, in diff order.
+    pub fn find_matches(&self) -> Vec<FindMatch> {
+        let (Some(find), Some(file)) = (self.active_find(), self.selected_diff_file()) else {
+            return Vec::new();
+        };
+        let mut matches = Vec::new();
+        for (hi, hunk) in file.hunks.iter().enumerate() {
+            for (li, line) in hunk.lines.iter().enumerate() {
+                if matches!(line.line_type, LineType::Fold(_)) {
+                    continue;
+                }
+                matches.extend(
+                    find.ranges(&line.content)
+                        .into_iter()
+                        .map(|range| FindMatch {
+                            hunk: hi,
+                            line: li,
+                            range,
+                        }),
+                );
+            }
+        }
+        matches
+    }
+
+    /// `(n, total)` for the status bar: the match on the cursor line (or
+    /// the last one before it), 0 when the cursor is before the first.
+    pub fn find_position(&self) -> Option<(usize, usize)> {
+        self.active_find()?;
+        let matches = self.find_matches();
+        let here = (self.current_hunk, self.current_line);
+        let n = matches
+            .iter()
+            .filter(|m| (m.hunk, Some(m.line)) <= here)
+            .count();
+        Some((n, matches.len()))
+    }
+
+    /// Move to the next (or previous) line with a match after `from` (or
+    /// at it, with `at_from`), wrapping around. Returns whether it wrapped,
+    /// `None` with no match.
+    fn find_from(
+        &mut self,
+        from: (usize, Option<usize>),
+        forward: bool,
+        at_from: bool,
+    ) -> Option<bool> {
+        let mut lines: Vec<(usize, usize)> = self
+            .find_matches()
+            .iter()
+            .map(|m| (m.hunk, m.line))
+            .collect();
+        lines.dedup();
+        let after =
+            |&&(h, l): &&(usize, usize)| (h, Some(l)) > from || at_from && (h, Some(l)) == from;
+        let before = |&&(h, l): &&(usize, usize)| (h, Some(l)) < from;
+        let (target, wrapped) = if forward {
+            match lines.iter().find(after) {
+                Some(t) => (*t, false),
+                None => (*lines.first()?, true),
+            }
+        } else {
+            match lines.iter().rev().find(before) {
+                Some(t) => (*t, false),
+                None => (*lines.last()?, true),
+            }
+        };
+        self.focused_comment_id = None;
+        self.focused_finding_id = None;
+        self.selection_anchor = None;
+        self.current_hunk = target.0;
+        self.current_line = Some(target.1);
+        self.scroll_to_current_hunk();
+        Some(wrapped)
+    }
+}
+
+impl App {
+    /// Ctrl+f: open the find prompt, with the last query.
+    pub fn start_find(&mut self) {
+        let tab = self.tab_mut();
+        let origin = (tab.current_hunk, tab.current_line);
+        let mut find = tab.find.take().unwrap_or_default();
+        find.origin = origin;
+        tab.find = Some(find);
+        self.input_mode = InputMode::Find;
+    }
+
+    /// A key typed into the find prompt changed the query: jump to the
+    /// first match from where the find started.
+    pub fn find_query_changed(&mut self) {
+        let tab = self.tab_mut();
+        let Some(origin) = tab.find.as_ref().map(|f| f.origin) else {
+            return;
+        };
+        if tab.find_from(origin, true, true).is_none() {
+            tab.current_hunk = origin.0;
+            tab.current_line = origin.1;
+            tab.scroll_to_current_hunk();
+        }
+    }
+
+    /// Enter in the find prompt.
+    pub fn confirm_find(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let tab = self.tab_mut();
+        if tab.find.as_ref().is_some_and(|f| f.query.is_empty()) {
+            tab.find = None;
+            return;
+        }
+        match tab.find_position() {
+            Some((_, 0)) => self.notify_warn("No matches in this file"),
+            Some((_, total)) => self.notify(&format!(
+                "{} match{} — n/N to step, Esc clears",
+                total,
+                if total == 1 { "" } else { "es" }
+            )),
+            None => {}
+        }
+    }
+
+    /// Esc in the find prompt: back to where it started, and no find.
+    pub fn cancel_find(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let tab = self.tab_mut();
+        if let Some(find) = tab.find.take() {
+            tab.current_hunk = find.origin.0;
+            tab.current_line = find.origin.1;
+            tab.scroll_to_current_hunk();
+        }
+    }
+
+    /// Esc after the find was confirmed.
+    pub fn clear_find(&mut self) {
+        if self.tab_mut().find.take().is_some() {
+            self.notify("Find cleared");
+        }
+    }
+
+    /// n / N while a find is active.
+    pub fn find_next(&mut self, forward: bool) {
+        let tab = self.tab_mut();
+        let here = (tab.current_hunk, tab.current_line);
+        match tab.find_from(here, forward, false) {
+            Some(true) => self.notify(if forward {
+                "Find wrapped to the top"
+            } else {
+                "Find wrapped to the bottom"
+            }),
+            Some(false) => {}
+            None => self.notify_warn("No matches in this file"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    const DIFF: &str = "diff --git a/f.rs b/f.rs\n--- a/f.rs\n+++ b/f.rs\n\
+                        @@ -1,3 +1,3 @@\n let Total = 1;\n-let x = total;\n+let y = total + total;\n\
+                        @@ -20,2 +20,2 @@\n fn f() {}\n-fn total() {}\n+fn sum() {}\n";
+
+    fn app_finding(query: &str) -> App {
+        let mut app = App::new_for_test(parse_diff(DIFF));
+        app.start_find();
+        for c in query.chars() {
+            app.tab_mut().find.as_mut().unwrap().push(c);
+        }
+        app.find_query_changed();
+        app
+    }
+
+    #[test]
+    fn matches_are_case_insensitive_until_toggled() {
+        let mut app = app_finding("total");
+        assert_eq!(app.tab().find_matches().len(), 5);
+        // Typing jumped to the first match
+        assert_eq!(
+            (app.tab().current_hunk, app.tab().current_line),
+            (0, Some(0))
+        );
+
+        app.tab_mut().find.as_mut().unwrap().toggle_case();
+        let matches = app.tab().find_matches();
+        assert_eq!(matches.len(), 4);
+        assert_eq!(matches[1].range, 8..13);
+
+        let find = app.tab_mut().find.as_mut().unwrap();
+        find.toggle_regex();
+        find.pop();
+        find.push('(');
+        assert!(find.error.is_some());
+        assert!(app.tab().find_matches().is_empty());
+    }
+
+    #[test]
+    fn n_steps_between_matching_lines_and_wraps() {
+        let mut app = app_finding("total");
+        app.confirm_find();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.tab().find_position(), Some((1, 5)));
+
+        app.find_next(true);
+        assert_eq!(app.tab().current_line, Some(1));
+        // Two matches on one line: one step
+        app.find_next(true);
+        assert_eq!(
+            (app.tab().current_hunk, app.tab().current_line),
+            (0, Some(2))
+        );
+        assert_eq!(app.tab().find_position(), Some((4, 5)));
+        app.find_next(true);
+        assert_eq!(
+            (app.tab().current_hunk, app.tab().current_line),
+            (1, Some(1))
+        );
+        app.find_next(true);
+        assert_eq!(
+            (app.tab().current_hunk, app.tab().current_line),
+            (0, Some(0))
+        );
+        app.find_next(false);
+        assert_eq!(app.tab().current_hunk, 1);
+
+        app.clear_find();
+        assert!(app.tab().find.is_none());
+    }
+}
@@ -0,0 +1,99 @@
+//! Focus mode (`V`): marking a file reviewed moves straight on to the next
+//! unreviewed one, so a full pass is Space, Space, Space.
+//!
+//! The queue is the visible file list (filters apply). In the diff's own
+//! order with an AI review loaded it goes by file risk, high first;
+//! otherwise it follows whatever order `m` picked for the tree.
+
+use super::file_sort::FileSort;
+use super::{App, TabState};
+use crate::ai::risk_rank;
+
+impl TabState {
+    /// Visible file indices in the order focus mode walks them.
+    fn focus_queue(&self) -> Vec<usize> {
+        let mut queue: Vec<(usize, u8)> = self
+            .visible_files()
+            .into_iter()
+            .map(|(i, f)| {
+                let rank = self
+                    .ai
+                    .file_review(&f.path)
+                    .map_or(u8::MAX, |r| risk_rank(&r.risk));
+                (i, rank)
+            })
+            .collect();
+        if self.file_sort == FileSort::Diff && self.ai.review.is_some() {
+            queue.sort_by_key(|&(_, rank)| rank);
+        }
+        queue.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// The unreviewed file after `from` in the focus queue (wrapping).
+    fn next_in_focus_queue(&self, from: usize) -> Option<usize> {
+        let queue = self.focus_queue();
+        let pos = queue.iter().position(|&i| i == from).unwrap_or(0);
+        (1..=queue.len())
+            .map(|offset| queue[(pos + offset) % queue.len()])
+            .find(|&i| {
+                self.files
+                    .get(i)
+                    .is_some_and(|f| self.needs_review(&f.path))
+            })
+    }
+}
+
+impl App {
+    pub fn toggle_focus_mode(&mut self) {
+        let tab = self.tab_mut();
+        tab.focus_mode = !tab.focus_mode;
+        if tab.focus_mode {
+            self.notify("Focus mode: Space marks reviewed and moves to the next unreviewed file");
+        } else {
+            self.notify("Focus mode off");
+        }
+    }
+
+    /// After marking file `from` reviewed in focus mode: select the next
+    /// unreviewed file. Returns its path, `None` when everything visible is
+    /// reviewed.
+    pub(super) fn focus_advance(&mut self, from: usize) -> Option<String> {
+        let tab = self.tab_mut();
+        let target = tab.next_in_focus_queue(from)?;
+        tab.selected_file = target;
+        tab.current_hunk = 0;
+        tab.current_line = None;
+        tab.diff_scroll = 0;
+        tab.h_scroll = 0;
+        tab.ensure_file_parsed();
+        tab.rebuild_hunk_offsets();
+        Some(tab.files[target].path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn marking_reviewed_in_focus_mode_jumps_to_the_next_unreviewed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(vec![]);
+        let tab = app.tab_mut();
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        tab.files = vec![
+            modified_file("a.rs", 1),
+            modified_file("b.rs", 1),
+            modified_file("c.rs", 1),
+        ];
+        tab.reviewed.insert("b.rs".into(), String::new());
+        tab.focus_mode = true;
+
+        app.toggle_reviewed().unwrap();
+        assert_eq!(app.tab().selected_file, 2);
+        app.toggle_reviewed().unwrap();
+        assert_eq!(app.tab().selected_file, 2);
+        assert_eq!(app.tab().reviewed.len(), 3);
+    }
+}
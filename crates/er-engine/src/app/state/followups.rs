@@ -0,0 +1,465 @@
+//! Follow-ups: snooze a file, comment or finding ("revisit tomorrow") with
+//! Alt+z. They're kept across repos in `followups.json` under the storage
+//! root, announced when er starts or when one comes due, and listed in the
+//! review dashboard, where Enter jumps back to one and clears it.
+//!
+//! Adding one publishes `follow_up_added` and coming due publishes
+//! `follow_up_due`, so a `[hooks]` command can put them in a calendar or
+//! raise a desktop notification.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData};
+use crate::events::{self, Event, EventContext};
+use crate::review_queue::format_age;
+
+/// How often the store is re-read for items coming due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The snooze choices offered by Alt+z.
+pub const SNOOZE_CHOICES: [(&str, u64); 5] = [
+    ("In 1 hour", 3600),
+    ("In 4 hours", 4 * 3600),
+    ("Tomorrow", 24 * 3600),
+    ("In 3 days", 3 * 24 * 3600),
+    ("Next week", 7 * 24 * 3600),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowUpKind {
+    File,
+    Comment,
+    Finding,
+}
+
+impl FollowUpKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            FollowUpKind::File => "file",
+            FollowUpKind::Comment => "comment",
+            FollowUpKind::Finding => "finding",
+        }
+    }
+}
+
+/// A snoozed file, comment or finding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FollowUp {
+    pub id: String,
+    pub kind: FollowUpKind,
+    /// The comment's or finding's id; empty for a file
+    #[serde(default)]
+    pub target_id: String,
+    pub repo_root: String,
+    pub branch: String,
+    #[serde(default)]
+    pub pr: Option<u64>,
+    pub file: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// The comment's first line, the finding's title, or the path
+    pub label: String,
+    /// Unix seconds
+    pub created: u64,
+    /// Unix seconds
+    pub due: u64,
+    /// `follow_up_due` has been published
+    #[serde(default)]
+    pub notified: bool,
+}
+
+impl FollowUp {
+    fn context(&self) -> EventContext {
+        EventContext {
+            repo_root: self.repo_root.clone(),
+            branch: self.branch.clone(),
+            pr: self.pr,
+        }
+    }
+
+    /// `due in 3h` / `due 2d ago`.
+    pub fn due_label(&self, now: u64) -> String {
+        if self.due > now {
+            format!("due in {}", format_age(self.due - now))
+        } else {
+            format!("due {} ago", format_age(now - self.due))
+        }
+    }
+}
+
+/// The follow-ups as last read, and when to look again.
+#[derive(Debug, Default)]
+pub struct FollowUps {
+    /// Due first
+    pub items: Vec<FollowUp>,
+    last_check: Option<Instant>,
+    /// Store location; `None` is `followups.json` under the storage root
+    pub path: Option<PathBuf>,
+}
+
+fn default_path() -> PathBuf {
+    crate::storage::storage_root().join("followups.json")
+}
+
+fn load(path: &PathBuf) -> Vec<FollowUp> {
+    let mut items: Vec<FollowUp> = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    items.sort_by_key(|f| f.due);
+    items
+}
+
+fn save(path: &PathBuf, items: &[FollowUp]) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(items)?)?;
+    Ok(())
+}
+
+impl App {
+    fn followups_path(&self) -> PathBuf {
+        self.followups.path.clone().unwrap_or_else(default_path)
+    }
+
+    /// Re-read the store, change it with `f` and write it back, so
+    /// follow-ups added by another er in the meantime survive.
+    fn update_followups(&mut self, f: impl FnOnce(&mut Vec<FollowUp>)) {
+        let path = self.followups_path();
+        let mut items = load(&path);
+        f(&mut items);
+        items.sort_by_key(|f| f.due);
+        if let Err(e) = save(&path, &items) {
+            self.notify_error(&format!("Could not save follow-ups: {:#}", e));
+        }
+        self.followups.items = items;
+    }
+
+    /// What Alt+z would snooze: the focused comment or finding, else the
+    /// selected file at the cursor line. `(kind, target id, file, line, label)`.
+    fn snooze_target(&self) -> Option<(FollowUpKind, String, String, Option<usize>, String)> {
+        let tab = self.tab();
+        if let Some(id) = &tab.focused_comment_id {
+            if let Some(comment) = tab.ai.find_comment(id) {
+                let label = comment
+                    .text()
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                return Some((
+                    FollowUpKind::Comment,
+                    id.clone(),
+                    comment.file().to_string(),
+                    comment.line_start(),
+                    label,
+                ));
+            }
+        }
+        if let Some(id) = &tab.focused_finding_id {
+            let found = tab
+                .ai
+                .all_findings_ordered()
+                .into_iter()
+                .find(|f| &f.3 == id);
+            if let Some((file, _, line, _)) = found {
+                let title = tab
+                    .ai
+                    .file_review(&file)
+                    .and_then(|fr| fr.findings.iter().find(|f| &f.id == id))
+                    .map(|f| f.title.clone())
+                    .unwrap_or_default();
+                return Some((FollowUpKind::Finding, id.clone(), file, line, title));
+            }
+        }
+        let file = tab.selected_diff_file()?;
+        Some((
+            FollowUpKind::File,
+            String::new(),
+            file.path.clone(),
+            tab.current_line_number(),
+            file.path.clone(),
+        ))
+    }
+
+    /// Alt+z: pick when to come back to the focused comment, finding or file.
+    pub fn open_snooze_hub(&mut self) {
+        let Some((kind, target_id, file, _, label)) = self.snooze_target() else {
+            self.notify("Nothing to snooze");
+            return;
+        };
+        let mut items: Vec<HubItem> = SNOOZE_CHOICES
+            .iter()
+            .map(|(choice, secs)| HubItem {
+                label: (*choice).into(),
+                hint: format_age(*secs),
+                description: String::new(),
+                action: HubAction::Snooze(*secs),
+                is_header: false,
+                enabled: true,
+            })
+            .collect();
+        let existing = self
+            .followups
+            .items
+            .iter()
+            .find(|f| f.kind == kind && f.target_id == target_id && f.file == file);
+        if let Some(existing) = existing {
+            items.push(HubItem {
+                label: "Clear follow-up".into(),
+                hint: existing.due_label(crate::outbox::unix_now()),
+                description: String::new(),
+                action: HubAction::ClearFollowUp(existing.id.clone()),
+                is_header: false,
+                enabled: true,
+            });
+        }
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Snooze,
+            title: Some(format!("SNOOZE {} · {}", kind.name(), label)),
+            selected: 0,
+            items,
+        });
+    }
+
+    /// Snooze the Alt+z target for `secs`; replaces its earlier follow-up.
+    pub fn snooze(&mut self, secs: u64) {
+        let Some((kind, target_id, file, line, label)) = self.snooze_target() else {
+            return;
+        };
+        let context = self.tab().event_context();
+        let now = crate::outbox::unix_now();
+        let follow_up = FollowUp {
+            id: format!("fu-{}-{}", now, std::process::id()),
+            kind,
+            target_id,
+            repo_root: context.repo_root.clone(),
+            branch: context.branch.clone(),
+            pr: context.pr,
+            file,
+            line,
+            label,
+            created: now,
+            due: now + secs,
+            notified: false,
+        };
+        let event = Event::FollowUpAdded {
+            kind: kind.name().into(),
+            file: follow_up.file.clone(),
+            line: follow_up.line,
+            label: follow_up.label.clone(),
+            due: follow_up.due,
+        };
+        let message = format!("Snoozed {} — {}", follow_up.file, follow_up.due_label(now));
+        self.update_followups(|items| {
+            items.retain(|f| {
+                !(f.kind == follow_up.kind
+                    && f.target_id == follow_up.target_id
+                    && f.file == follow_up.file
+                    && f.repo_root == follow_up.repo_root
+                    && f.branch == follow_up.branch)
+            });
+            items.push(follow_up);
+        });
+        events::publish(&context, event);
+        self.notify(&message);
+    }
+
+    pub fn clear_follow_up(&mut self, id: &str) {
+        self.update_followups(|items| items.retain(|f| f.id != id));
+        self.notify("Follow-up cleared");
+    }
+
+    /// `d` in the review dashboard: clear the selected follow-up.
+    pub fn clear_selected_follow_up(&mut self) {
+        let Some(OverlayData::ModalHub {
+            kind: HubKind::Reviews,
+            items,
+            selected,
+            ..
+        }) = &self.overlay
+        else {
+            return;
+        };
+        let Some(HubAction::OpenFollowUp(id)) = items.get(*selected).map(|i| i.action.clone())
+        else {
+            return;
+        };
+        self.clear_follow_up(&id);
+        self.open_reviews_dashboard();
+    }
+
+    /// Jump to a follow-up in the tab reviewing its branch, and clear it.
+    pub fn open_follow_up(&mut self, id: &str) {
+        let Some(follow_up) = self.followups.items.iter().find(|f| f.id == id).cloned() else {
+            return;
+        };
+        let context = follow_up.context();
+        let Some(idx) = self.tabs.iter().position(|t| t.event_context() == context) else {
+            self.notify(&format!(
+                "Open {} on {} to revisit {}",
+                follow_up.repo_root, follow_up.branch, follow_up.file
+            ));
+            return;
+        };
+        self.active_tab = idx;
+        match follow_up.kind {
+            FollowUpKind::Finding => self.jump_to_finding(&follow_up.file, &follow_up.target_id),
+            FollowUpKind::Comment => {
+                self.focus_file_line(&follow_up.file, follow_up.line);
+                self.tab_mut().focused_comment_id = Some(follow_up.target_id.clone());
+            }
+            FollowUpKind::File => self.focus_file_line(&follow_up.file, follow_up.line),
+        }
+        self.update_followups(|items| items.retain(|f| f.id != follow_up.id));
+    }
+
+    /// Dashboard rows for the follow-ups, due first, under a header.
+    pub(super) fn follow_up_items(&self) -> Vec<HubItem> {
+        if self.followups.items.is_empty() {
+            return Vec::new();
+        }
+        let now = crate::outbox::unix_now();
+        let mut items = vec![HubItem {
+            label: "Follow-ups".into(),
+            hint: String::new(),
+            description: String::new(),
+            action: HubAction::Noop,
+            is_header: true,
+            enabled: false,
+        }];
+        items.extend(self.followups.items.iter().map(|f| {
+            let repo = std::path::Path::new(&f.repo_root)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            HubItem {
+                label: match f.kind {
+                    FollowUpKind::File => f.file.clone(),
+                    _ => format!("{} · {}", f.file, f.label),
+                },
+                hint: f.due_label(now),
+                description: format!("{} {} · {}", repo, f.branch, f.kind.name()),
+                action: HubAction::OpenFollowUp(f.id.clone()),
+                is_header: false,
+                enabled: true,
+            }
+        }));
+        items
+    }
+
+    /// Re-read the follow-ups every so often: on the first look say how
+    /// many are due, afterwards say which just came due. Each coming due
+    /// publishes `follow_up_due` once.
+    pub fn poll_followups(&mut self) -> bool {
+        let first = self.followups.last_check.is_none();
+        if self
+            .followups
+            .last_check
+            .is_some_and(|t| t.elapsed() < CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.followups.last_check = Some(Instant::now());
+        let path = self.followups_path();
+        let items = load(&path);
+        let now = crate::outbox::unix_now();
+        let newly_due: Vec<FollowUp> = items
+            .iter()
+            .filter(|f| f.due <= now && !f.notified)
+            .cloned()
+            .collect();
+        let due = items.iter().filter(|f| f.due <= now).count();
+        let changed = items != self.followups.items;
+        self.followups.items = items;
+        if !newly_due.is_empty() {
+            for f in &newly_due {
+                events::publish(
+                    &f.context(),
+                    Event::FollowUpDue {
+                        kind: f.kind.name().into(),
+                        file: f.file.clone(),
+                        line: f.line,
+                        label: f.label.clone(),
+                        due: f.due,
+                    },
+                );
+            }
+            self.update_followups(|items| {
+                for f in items.iter_mut() {
+                    if newly_due.iter().any(|d| d.id == f.id) {
+                        f.notified = true;
+                    }
+                }
+            });
+        }
+        if first && due > 0 {
+            self.notify(&format!(
+                "{} follow-up{} due — Git hub → Review dashboard",
+                due,
+                if due == 1 { "" } else { "s" }
+            ));
+            return true;
+        }
+        if let [one] = newly_due.as_slice() {
+            self.notify(&format!("Follow-up due: {}", one.label));
+        } else if newly_due.len() > 1 {
+            self.notify(&format!("{} follow-ups due", newly_due.len()));
+        }
+        changed || !newly_due.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn snoozed_file_comes_due_once_and_opens_from_the_dashboard() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(vec![modified_file("a.rs", 1), modified_file("b.rs", 1)]);
+        app.followups.path = Some(dir.path().join("followups.json"));
+        app.tab_mut().selected_file = 1;
+
+        app.snooze(3600);
+        assert_eq!(app.followups.items.len(), 1);
+        assert_eq!(app.followups.items[0].file, "b.rs");
+        // Snoozing again replaces it
+        app.snooze(7200);
+        assert_eq!(app.followups.items.len(), 1);
+
+        // Make it due and look again
+        let path = app.followups_path();
+        let mut items = load(&path);
+        items[0].due = 1;
+        save(&path, &items).unwrap();
+        assert!(app.poll_followups());
+        assert!(app.followups.items[0].notified);
+        assert!(app
+            .watch_message
+            .as_deref()
+            .is_some_and(|m| m.contains("1 follow-up due")));
+
+        app.open_reviews_dashboard();
+        let Some(OverlayData::ModalHub { items, .. }) = &app.overlay else {
+            panic!("dashboard not open");
+        };
+        let id = app.followups.items[0].id.clone();
+        assert_eq!(
+            items.last().unwrap().action,
+            HubAction::OpenFollowUp(id.clone())
+        );
+
+        app.tab_mut().selected_file = 0;
+        app.open_follow_up(&id);
+        assert_eq!(app.tab().selected_file, 1);
+        assert!(app.followups.items.is_empty());
+        assert!(load(&path).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Open on GitHub (Open hub): the file at the cursor line, pinned to the
+//! head commit, or the commit selected in History. The URL comes from the
+//! detected owner/repo and opens with the system browser; without a GitHub
+//! `origin` (or a browser opener) the page is handed to `gh browse`, which
+//! resolves the repo itself.
+
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use super::{App, DiffMode};
+
+/// A page on the forge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ForgePage {
+    File {
+        path: String,
+        lines: Option<(usize, usize)>,
+    },
+    Commit,
+}
+
+/// Arguments for `gh browse` showing `page` at `sha`.
+fn gh_browse_args(page: &ForgePage, sha: Option<&str>) -> Vec<String> {
+    let mut args = vec!["browse".to_string()];
+    match page {
+        ForgePage::File { path, lines } => {
+            args.push(match lines {
+                Some((start, end)) if end > start => format!("{}:{}-{}", path, start, end),
+                Some((start, _)) => format!("{}:{}", path, start),
+                None => path.clone(),
+            });
+            args.push(match sha {
+                Some(sha) => format!("--commit={}", sha),
+                None => "--commit".to_string(),
+            });
+        }
+        ForgePage::Commit => args.extend(sha.map(str::to_string)),
+    }
+    args
+}
+
+/// Spawn without waiting on the UI thread; a reaper thread collects it.
+fn spawn_detached(cmd: &mut Command) -> Result<()> {
+    let mut child = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", cmd.get_program().to_string_lossy()))?;
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Open `url` with the platform's default browser.
+fn open_url(url: &str) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+    spawn_detached(cmd.arg(url))
+}
+
+impl App {
+    /// Open the selected file at the cursor line (or selection) on GitHub.
+    pub fn open_line_on_forge(&mut self) {
+        let tab = self.tab();
+        let Some(path) = tab.selected_diff_file().map(|f| f.path.clone()) else {
+            self.notify("No file selected");
+            return;
+        };
+        let lines = match tab.cursor_head_lines() {
+            Ok(lines) => lines,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return;
+            }
+        };
+        let working_tree = matches!(tab.mode, DiffMode::Unstaged | DiffMode::Staged);
+        self.open_on_forge(ForgePage::File { path, lines });
+        if working_tree {
+            self.notify_warn("⚠ Opened at the last commit — working tree lines may differ");
+        }
+    }
+
+    /// Open the commit selected in History on GitHub.
+    pub fn open_commit_on_forge(&mut self) {
+        if self.tab().mode != DiffMode::History {
+            self.notify("Select a commit in History mode first");
+            return;
+        }
+        self.open_on_forge(ForgePage::Commit);
+    }
+
+    fn open_on_forge(&mut self, page: ForgePage) {
+        let tab = self.tab();
+        let (url, sha) = match tab.permalink_target() {
+            Ok((owner, repo, sha)) => {
+                let url = match &page {
+                    ForgePage::File { path, lines } => {
+                        crate::github::blob_permalink(&owner, &repo, &sha, path, *lines)
+                    }
+                    ForgePage::Commit => {
+                        crate::github::web_url(&format!("{}/{}/commit/{}", owner, repo, sha))
+                    }
+                };
+                (Some(url), Some(sha))
+            }
+            Err(_) => {
+                let history_commit = tab
+                    .history
+                    .as_ref()
+                    .and_then(|h| h.commits.get(h.selected_commit))
+                    .map(|c| c.hash.clone());
+                (None, history_commit.filter(|_| page == ForgePage::Commit))
+            }
+        };
+        if let Some(url) = url {
+            if open_url(&url).is_ok() {
+                self.notify(&format!("Opening {}", url));
+                return;
+            }
+        }
+
+        let mut args = gh_browse_args(&page, sha.as_deref());
+        if let Some(ref slug) = tab.remote_repo {
+            args.push("-R".to_string());
+            args.push(slug.clone());
+        }
+        let mut cmd = crate::github::gh_command();
+        cmd.args(&args).current_dir(tab.commit_log_root());
+        match spawn_detached(&mut cmd) {
+            Ok(()) => self.notify("Opening in browser via gh browse..."),
+            Err(e) => self.notify(&format!("Couldn't open a browser: {:#}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gh_browse_fallback_pins_the_commit() {
+        let page = ForgePage::File {
+            path: "src/a.rs".into(),
+            lines: Some((3, 7)),
+        };
+        assert_eq!(
+            gh_browse_args(&page, Some("abc123")),
+            ["browse", "src/a.rs:3-7", "--commit=abc123"]
+        );
+        let page = ForgePage::File {
+            path: "src/a.rs".into(),
+            lines: None,
+        };
+        assert_eq!(
+            gh_browse_args(&page, None),
+            ["browse", "src/a.rs", "--commit"]
+        );
+        assert_eq!(
+            gh_browse_args(&ForgePage::Commit, Some("abc123")),
+            ["browse", "abc123"]
+        );
+    }
+}
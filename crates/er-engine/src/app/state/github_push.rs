@@ -0,0 +1,1067 @@
+//! Pushing local comments to GitHub.
+//!
+//! Both pushes — individually (`i`) and as one review (`r`) — send edits to
+//! comments already on GitHub first, place line comments on the PR's own
+//! diff, and ride out GitHub's rate limits with
+//! [`github::with_rate_limit_backoff`]. The individual push still batches
+//! plain line comments into a single review once there are
+//! [`REVIEW_BATCH_MIN`] of them, so a long review costs one call instead of
+//! one per comment. If GitHub is still refusing after the last backoff, the
+//! rest goes to the [`Outbox`] rather than counting as failed, and
+//! [`App::tick`] pushes again once the cooldown has passed.
+//!
+//! Those backoffs sleep, so pushes run on a worker thread. It reports each
+//! wait — "rate limited, retrying in 8s (3/12 pushed)" — and the result to
+//! [`App::poll_comment_push`], which writes the GitHub ids back.
+
+use std::cell::Cell;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+use anyhow::Result;
+
+use super::App;
+use crate::ai::{self, store::Sidecar, GitHubReviewComment};
+use crate::git::DiffFile;
+use crate::github::{self, is_rate_limited, ReviewBatchEntry};
+use crate::outbox::{self, Outbox};
+use crate::sync::local_pr_target;
+use crate::webhook::WebhookEvent;
+
+/// Fewest plain line comments the individual push sends as one review.
+pub const REVIEW_BATCH_MIN: usize = 3;
+
+/// The PR a push goes to.
+pub(super) struct PushTarget {
+    pub owner: String,
+    pub repo_name: String,
+    pub pr_number: u64,
+    pub is_remote: bool,
+    pub repo_root: String,
+    /// Seconds spent waiting out rate limits so far
+    waited: Cell<u64>,
+    /// Comments pushed so far, of `total`
+    pushed: Cell<u32>,
+    total: u32,
+    /// Where a worker push reports its rate-limit waits
+    progress: Option<Sender<PushUpdate>>,
+}
+
+impl PushTarget {
+    fn root(&self) -> Option<&str> {
+        (!self.is_remote).then_some(self.repo_root.as_str())
+    }
+
+    /// Retry `call` through GitHub's rate limits, logging each pause.
+    fn backoff<T>(&self, call: impl FnMut() -> Result<T>) -> Result<T> {
+        github::with_rate_limit_backoff(github::RATE_LIMIT_BACKOFF, call, |wait| {
+            self.waited.set(self.waited.get() + wait.as_secs());
+            crate::debug_log::info(
+                "github",
+                format!("rate limited; retrying in {}s", wait.as_secs()),
+            );
+            if let Some(tx) = &self.progress {
+                let _ = tx.send(PushUpdate::Waiting {
+                    secs: wait.as_secs(),
+                    pushed: self.pushed.get(),
+                    total: self.total,
+                });
+            }
+        })
+    }
+
+    fn count(&self, pushed: u32) {
+        self.pushed.set(self.pushed.get() + pushed);
+    }
+
+    pub(super) fn pr_files(&self) -> Result<Vec<DiffFile>> {
+        self.backoff(|| {
+            github::gh_pr_diff_files(&self.owner, &self.repo_name, self.pr_number, self.root())
+        })
+    }
+
+    /// Post a thread root: general comments go to the conversation,
+    /// file-level ones to the file, line comments to their place in
+    /// `pr_files`. Returns the GitHub id.
+    pub(super) fn push_root(
+        &self,
+        comment: &GitHubReviewComment,
+        pr_files: &[DiffFile],
+    ) -> Result<u64> {
+        if comment.file.is_empty() {
+            return self.backoff(|| {
+                if self.is_remote {
+                    github::gh_pr_general_comment_remote(
+                        &self.owner,
+                        &self.repo_name,
+                        self.pr_number,
+                        &comment.comment,
+                    )
+                } else {
+                    github::gh_pr_general_comment(
+                        &self.owner,
+                        &self.repo_name,
+                        self.pr_number,
+                        &comment.comment,
+                        &self.repo_root,
+                    )
+                }
+            });
+        }
+        if comment.is_file_level() {
+            return self.backoff(|| {
+                github::gh_pr_file_comment(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    &comment.file,
+                    &comment.comment,
+                    self.root(),
+                )
+            });
+        }
+        let anchor = ai::anchor_on_pr_diff(comment, pr_files)?;
+        let side = comment.side.as_str();
+        self.backoff(|| {
+            if self.is_remote {
+                github::gh_pr_push_comment_remote(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    &comment.file,
+                    anchor.start,
+                    Some(anchor.end),
+                    &comment.comment,
+                    side,
+                )
+            } else {
+                github::gh_pr_push_comment(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    &comment.file,
+                    anchor.start,
+                    Some(anchor.end),
+                    &comment.comment,
+                    side,
+                    &self.repo_root,
+                )
+            }
+        })
+    }
+
+    pub(super) fn push_reply(&self, parent_github_id: u64, body: &str) -> Result<u64> {
+        self.backoff(|| {
+            if self.is_remote {
+                github::gh_pr_reply_comment_remote(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    parent_github_id,
+                    body,
+                )
+            } else {
+                github::gh_pr_reply_comment(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    parent_github_id,
+                    body,
+                    &self.repo_root,
+                )
+            }
+        })
+    }
+
+    /// Submit a review; returns its id.
+    fn submit_review(&self, batch: &[ReviewBatchEntry], body: &str) -> Result<u64> {
+        self.backoff(|| {
+            if self.is_remote {
+                github::gh_pr_submit_review_remote(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    batch,
+                    "COMMENT",
+                    body,
+                )
+            } else {
+                github::gh_pr_submit_review(
+                    &self.owner,
+                    &self.repo_name,
+                    self.pr_number,
+                    batch,
+                    &self.repo_root,
+                    "COMMENT",
+                    body,
+                )
+            }
+        })
+    }
+
+    /// The comments review `review_id` created; none when GitHub won't say,
+    /// which leaves their replies waiting.
+    fn review_comment_ids(&self, review_id: u64) -> Vec<github::ReviewCommentId> {
+        self.backoff(|| {
+            github::gh_pr_review_comment_ids(
+                &self.owner,
+                &self.repo_name,
+                self.pr_number,
+                review_id,
+                self.root(),
+            )
+        })
+        .unwrap_or_else(|e| {
+            crate::debug_log::warn("github", format!("{:#}", e));
+            Vec::new()
+        })
+    }
+
+    /// ` (waited 10s on GitHub's rate limit)`, or nothing.
+    fn waited_note(&self) -> String {
+        match self.waited.get() {
+            0 => String::new(),
+            secs => format!(" (waited {}s on GitHub's rate limit)", secs),
+        }
+    }
+}
+
+/// What stopped a push. The count of what got through is kept on its
+/// [`PushTarget`].
+#[derive(Default)]
+struct PushTally {
+    /// Comments that failed for a reason other than the rate limit
+    failed_ids: Vec<String>,
+    /// First failure, shown in full when the push summary reports failures
+    first_error: Option<anyhow::Error>,
+    /// GitHub kept refusing after the last backoff; the rest waits
+    rate_limited: bool,
+    /// Review-wide notes that went up in a review body
+    sent_note_ids: Vec<String>,
+    /// Comments that went up, with their GitHub id when known
+    sent: Vec<(String, Option<u64>)>,
+    /// Edits that went up, with the body GitHub now has
+    edited: Vec<(String, String)>,
+}
+
+impl PushTally {
+    fn fail(&mut self, id: &str, e: anyhow::Error) {
+        if is_rate_limited(&e) {
+            self.rate_limited = true;
+        } else {
+            self.failed_ids.push(id.to_string());
+            self.first_error.get_or_insert(e);
+        }
+    }
+
+    /// Mark comment `id` pushed in `gc`, and remember it for the write-back.
+    fn mark_pushed(&mut self, gc: &mut ai::ErGitHubComments, id: &str, github_id: Option<u64>) {
+        if let Some(c) = gc.comments.iter_mut().find(|c| c.id == id) {
+            if github_id.is_some() {
+                c.github_id = github_id;
+            }
+            c.synced = true;
+        }
+        self.sent.push((id.to_string(), github_id));
+    }
+
+    /// Apply what this push sent to the comments as they are on disk now,
+    /// leaving everything else — edits, resolves, deletes made while it
+    /// ran — alone. An edit made again meanwhile stays unpushed.
+    fn apply_to(&self, on_disk: &mut ai::ErGitHubComments) {
+        for c in &mut on_disk.comments {
+            if let Some((_, github_id)) = self.sent.iter().find(|(id, _)| *id == c.id) {
+                if github_id.is_some() {
+                    c.github_id = *github_id;
+                }
+                c.synced = true;
+            }
+            if self
+                .edited
+                .iter()
+                .any(|(id, body)| *id == c.id && *body == c.comment)
+            {
+                c.edit_base = None;
+            }
+        }
+    }
+}
+
+/// What a worker push sends [`App::poll_comment_push`].
+enum PushUpdate {
+    /// GitHub rate-limited a call; the worker retries after `secs`
+    Waiting {
+        secs: u64,
+        pushed: u32,
+        total: u32,
+    },
+    Done(Box<PushDone>),
+}
+
+/// A finished push. `tally` is an error when the PR diff to place
+/// comments on couldn't be fetched, and nothing was written.
+struct PushDone {
+    label: &'static str,
+    comments_dir: String,
+    gc: ai::ErGitHubComments,
+    tally: Result<PushTally>,
+    target: PushTarget,
+}
+
+/// The push running on a worker, if any.
+#[derive(Debug, Default)]
+pub struct CommentPush {
+    rx: Option<Receiver<PushUpdate>>,
+}
+
+/// Review entry for a line comment placed on `pr_files`.
+fn batch_entry(comment: &GitHubReviewComment, pr_files: &[DiffFile]) -> Result<ReviewBatchEntry> {
+    let anchor = ai::anchor_on_pr_diff(comment, pr_files)?;
+    Ok(ReviewBatchEntry {
+        file: comment.file.clone(),
+        line: anchor.end,
+        start_line: anchor.start_line(),
+        body: comment.comment.clone(),
+        side: comment.side.clone(),
+    })
+}
+
+fn is_line_comment(c: &GitHubReviewComment) -> bool {
+    !c.file.is_empty() && !c.is_file_level()
+}
+
+/// Send edits to comments already on GitHub.
+fn push_edits(target: &PushTarget, gc: &mut ai::ErGitHubComments, tally: &mut PushTally) {
+    let editing: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| c.has_unpushed_edit())
+        .map(|c| c.id.clone())
+        .collect();
+    let (edits, edit_errors) = crate::sync::push_comment_edits(
+        &mut gc.comments,
+        &target.owner,
+        &target.repo_name,
+        &target.repo_root,
+    );
+    target.count(edits);
+    for e in edit_errors {
+        tally.fail("", e);
+    }
+    tally.edited.extend(
+        gc.comments
+            .iter()
+            .filter(|c| editing.contains(&c.id) && c.edit_base.is_none())
+            .map(|c| (c.id.clone(), c.comment.clone())),
+    );
+}
+
+/// The GitHub id of the review comment `entry` became, taken out of
+/// `created` so a twin comment gets the next one.
+fn take_review_comment(
+    created: &mut Vec<github::ReviewCommentId>,
+    entry: &ReviewBatchEntry,
+) -> Option<u64> {
+    let i = created
+        .iter()
+        .position(|c| c.path == entry.file && c.body == entry.body)?;
+    Some(created.remove(i).id)
+}
+
+/// Local comments still to go up, minus the ones that failed for good.
+fn unpushed_ids(gc: &ai::ErGitHubComments, failed: &[String]) -> Vec<String> {
+    gc.comments
+        .iter()
+        .filter(|c| c.source == "local" && (!c.synced || c.has_unpushed_edit()))
+        .filter(|c| !failed.contains(&c.id))
+        .map(|c| c.id.clone())
+        .collect()
+}
+
+/// Send `gc`'s unpushed comments one by one, plain line comments as one
+/// review once there are enough of them. Fails when the PR diff to place
+/// line comments on can't be fetched.
+fn push_individually(target: &PushTarget, gc: &mut ai::ErGitHubComments) -> Result<PushTally> {
+    let mut tally = PushTally::default();
+
+    // Edits to comments already on GitHub
+    push_edits(target, gc, &mut tally);
+
+    // Push parents first
+    let root_ids: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_none())
+        .map(|c| c.id.clone())
+        .collect();
+
+    // Line comments are re-anchored onto the PR's own diff before posting
+    let needs_pr_diff = gc
+        .comments
+        .iter()
+        .any(|c| root_ids.contains(&c.id) && is_line_comment(c));
+    let mut pr_files = Vec::new();
+    if needs_pr_diff && !tally.rate_limited {
+        match target.pr_files() {
+            Ok(files) => pr_files = files,
+            Err(e) if is_rate_limited(&e) => tally.rate_limited = true,
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Plain line comments go up as one review, their ids looked up after.
+    // Threads with replies waiting stay out so the replies don't hang on
+    // that lookup.
+    let batchable: Vec<&GitHubReviewComment> = gc
+        .comments
+        .iter()
+        .filter(|c| root_ids.contains(&c.id) && is_line_comment(c))
+        .filter(|c| {
+            !gc.comments
+                .iter()
+                .any(|r| !r.synced && r.in_reply_to.as_deref() == Some(c.id.as_str()))
+        })
+        .collect();
+    if !tally.rate_limited && batchable.len() >= REVIEW_BATCH_MIN {
+        // Comments that don't place are left to the loop below to report
+        let (ids, batch): (Vec<String>, Vec<ReviewBatchEntry>) = batchable
+            .iter()
+            .filter_map(|c| Some((c.id.clone(), batch_entry(c, &pr_files).ok()?)))
+            .unzip();
+        match target.submit_review(&batch, "") {
+            Ok(review_id) => {
+                let mut created = target.review_comment_ids(review_id);
+                for (id, entry) in ids.iter().zip(&batch) {
+                    let github_id = take_review_comment(&mut created, entry);
+                    tally.mark_pushed(gc, id, github_id);
+                }
+                target.count(ids.len() as u32);
+            }
+            Err(e) if is_rate_limited(&e) => tally.rate_limited = true,
+            Err(e) => crate::debug_log::warn(
+                "github",
+                format!("review batch failed, pushing one by one: {}", e),
+            ),
+        }
+    }
+
+    for cid in &root_ids {
+        if tally.rate_limited {
+            break;
+        }
+        let Some(comment) = gc.comments.iter().find(|c| c.id == *cid && !c.synced) else {
+            continue;
+        };
+        match target.push_root(comment, &pr_files) {
+            Ok(github_id) => {
+                tally.mark_pushed(gc, cid, Some(github_id));
+                target.count(1);
+            }
+            Err(e) => tally.fail(cid, e),
+        }
+    }
+
+    // Then push replies
+    let reply_ids: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_some())
+        .map(|c| c.id.clone())
+        .collect();
+
+    for cid in &reply_ids {
+        if tally.rate_limited {
+            break;
+        }
+        let Some(comment) = gc.comments.iter().find(|c| c.id == *cid) else {
+            continue;
+        };
+        let parent_gh_id = comment
+            .in_reply_to
+            .as_ref()
+            .and_then(|rt| gc.comments.iter().find(|c| c.id == *rt))
+            .and_then(|c| c.github_id);
+
+        if let Some(parent_gh_id) = parent_gh_id {
+            match target.push_reply(parent_gh_id, &comment.comment) {
+                Ok(github_id) => {
+                    tally.mark_pushed(gc, cid, Some(github_id));
+                    target.count(1);
+                }
+                Err(e) => tally.fail(cid, e),
+            }
+        } else {
+            tally.failed_ids.push(cid.clone());
+        }
+    }
+    Ok(tally)
+}
+
+/// Send `gc`'s unpushed comments as one review with `review_body`; see
+/// [`App::push_comments_as_review`].
+fn push_review(
+    target: &PushTarget,
+    gc: &mut ai::ErGitHubComments,
+    review_body: &str,
+    review_note_ids: &[String],
+) -> Result<PushTally> {
+    let mut tally = PushTally::default();
+
+    // Edits to comments already on GitHub go up one by one
+    push_edits(target, gc, &mut tally);
+
+    let root_ids: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_none())
+        .map(|c| c.id.clone())
+        .collect();
+    let line_comment_ids: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| root_ids.contains(&c.id) && is_line_comment(c))
+        .map(|c| c.id.clone())
+        .collect();
+
+    // Line comments are re-anchored onto the PR's own diff; ones that no
+    // longer fit stay local and are reported instead of landing elsewhere
+    let mut batched_ids: Vec<String> = Vec::new();
+    let mut batch: Vec<ReviewBatchEntry> = Vec::new();
+    if !line_comment_ids.is_empty() && !tally.rate_limited {
+        let pr_files = match target.pr_files() {
+            Ok(files) => files,
+            Err(e) if is_rate_limited(&e) => {
+                tally.rate_limited = true;
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+        for cid in &line_comment_ids {
+            if tally.rate_limited {
+                break;
+            }
+            let Some(c) = gc.comments.iter().find(|c| c.id == *cid) else {
+                continue;
+            };
+            match batch_entry(c, &pr_files) {
+                Ok(entry) => {
+                    batch.push(entry);
+                    batched_ids.push(cid.clone());
+                }
+                Err(e) => tally.fail(cid, e),
+            }
+        }
+    }
+
+    // Submit line comments as a single review batch, with the review notes
+    if !tally.rate_limited && (!batched_ids.is_empty() || !review_body.is_empty()) {
+        match target.submit_review(&batch, review_body) {
+            Ok(review_id) => {
+                // The review doesn't return its comments' ids; replies need them
+                let mut created = if batch.is_empty() {
+                    Vec::new()
+                } else {
+                    target.review_comment_ids(review_id)
+                };
+                for (cid, entry) in batched_ids.iter().zip(&batch) {
+                    let github_id = take_review_comment(&mut created, entry);
+                    tally.mark_pushed(gc, cid, github_id);
+                }
+                target.count((batched_ids.len() + review_note_ids.len()) as u32);
+                tally.sent_note_ids = review_note_ids.to_vec();
+            }
+            Err(e) if is_rate_limited(&e) => tally.rate_limited = true,
+            Err(e) => {
+                tally.failed_ids.extend(batched_ids.iter().cloned());
+                tally
+                    .first_error
+                    .get_or_insert(e.context("Review submit failed"));
+            }
+        }
+    }
+
+    // General and file-level comments go one by one — reviews can't carry them
+    for cid in &root_ids {
+        if tally.rate_limited {
+            break;
+        }
+        let Some(comment) = gc.comments.iter().find(|c| c.id == *cid) else {
+            continue;
+        };
+        if is_line_comment(comment) {
+            continue;
+        }
+        match target.push_root(comment, &[]) {
+            Ok(github_id) => {
+                tally.mark_pushed(gc, cid, Some(github_id));
+                target.count(1);
+            }
+            Err(e) => tally.fail(cid, e),
+        }
+    }
+
+    // Push replies individually (review API doesn't support threading)
+    let reply_ids: Vec<String> = gc
+        .comments
+        .iter()
+        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_some())
+        .map(|c| c.id.clone())
+        .collect();
+
+    for cid in &reply_ids {
+        if tally.rate_limited {
+            break;
+        }
+        let Some(comment) = gc.comments.iter().find(|c| c.id == *cid) else {
+            continue;
+        };
+        let parent_gh_id = comment
+            .in_reply_to
+            .as_ref()
+            .and_then(|rt| gc.comments.iter().find(|c| c.id == *rt))
+            .and_then(|c| c.github_id);
+
+        if let Some(parent_gh_id) = parent_gh_id {
+            match target.push_reply(parent_gh_id, &comment.comment) {
+                Ok(github_id) => {
+                    tally.mark_pushed(gc, cid, Some(github_id));
+                    target.count(1);
+                }
+                Err(e) => tally.fail(cid, e),
+            }
+        } else {
+            tally.failed_ids.push(cid.clone());
+        }
+    }
+    Ok(tally)
+}
+
+impl App {
+    /// Owner, repo and PR for pushing from the active tab.
+    pub(super) fn push_target(&self, pr_number_hint: Option<u64>) -> Result<PushTarget> {
+        let tab = self.tab();
+        let repo_root = tab.repo_root.clone();
+        let explicit_pr_number = tab.pr_number.or(pr_number_hint);
+        let is_remote = tab.is_remote();
+        let (owner, repo_name, pr_number) = if is_remote {
+            if let (Some(slug), Some(n)) = (&tab.remote_repo, explicit_pr_number) {
+                let parts: Vec<&str> = slug.split('/').collect();
+                if parts.len() == 2 {
+                    (parts[0].to_string(), parts[1].to_string(), n)
+                } else {
+                    anyhow::bail!("Invalid remote repo slug");
+                }
+            } else {
+                anyhow::bail!("No PR info for remote mode");
+            }
+        } else {
+            local_pr_target(&repo_root, explicit_pr_number)
+                .map_err(|_| anyhow::anyhow!("No PR found for current branch"))?
+        };
+        Ok(PushTarget {
+            owner,
+            repo_name,
+            pr_number,
+            is_remote,
+            repo_root,
+            waited: Cell::new(0),
+            pushed: Cell::new(0),
+            total: 0,
+            progress: None,
+        })
+    }
+
+    /// Record what `tally`'s push sent in `comments_dir` and reload tab
+    /// `idx`, if it's still open.
+    fn write_pushed_comments(
+        &mut self,
+        idx: Option<usize>,
+        tally: &PushTally,
+        comments_dir: &str,
+    ) -> Result<()> {
+        // Merged into the file as it is now: the push ran unlocked
+        ai::store::modify(comments_dir, |on_disk: &mut ai::ErGitHubComments| {
+            tally.apply_to(on_disk);
+            Ok(())
+        })?;
+        let Some(tab) = idx.and_then(|i| self.tabs.get_mut(i)) else {
+            return Ok(());
+        };
+        if tab.is_remote() {
+            tab.reload_remote_comments();
+        } else {
+            tab.reload_ai_state();
+        }
+        Ok(())
+    }
+
+    /// Park `ids` in the outbox until the rate limit cools down.
+    fn queue_after_rate_limit(
+        &mut self,
+        idx: Option<usize>,
+        comments_dir: &str,
+        ids: Vec<String>,
+    ) -> usize {
+        let outbox = Outbox::queue(ids, outbox::unix_now());
+        if let Err(e) = outbox.save(comments_dir) {
+            crate::debug_log::warn("github", format!("could not save the outbox: {}", e));
+        }
+        let queued = outbox.comment_ids.len();
+        if let Some(tab) = idx.and_then(|i| self.tabs.get_mut(i)) {
+            tab.outbox = Some(outbox);
+        }
+        queued
+    }
+
+    fn report_push(
+        &mut self,
+        label: &str,
+        tally: PushTally,
+        queued: Option<usize>,
+        target: &PushTarget,
+    ) {
+        let (pushed, failed) = (target.pushed.get(), tally.failed_ids.len());
+        if let Some(queued) = queued {
+            self.notify_long(&format!(
+                "GitHub rate limit: pushed {}, {} queued — retrying in {}s",
+                pushed,
+                queued,
+                outbox::RATE_LIMIT_COOLDOWN_SECS
+            ));
+        } else if failed > 0 {
+            let summary = format!("{} {} ({} failed)", label, pushed, failed);
+            match tally.first_error {
+                Some(e) => self.report_command_error(&summary, &e),
+                None => self.notify(&summary),
+            }
+        } else {
+            self.notify(&format!("{} {}{}", label, pushed, target.waited_note()));
+        }
+        if pushed > 0 {
+            self.send_webhook(
+                WebhookEvent::CommentsPushed,
+                &format!("Pushed {} review comments", pushed),
+            );
+            self.publish_event(crate::events::Event::CommentsPushed {
+                count: pushed as usize,
+            });
+        }
+    }
+
+    /// The target and comments for a push from the active tab, with the
+    /// outbox it covers cleared. None when there's nothing to push or a
+    /// push is already running. A review with a body goes up even without
+    /// comments.
+    fn prepare_push(
+        &mut self,
+        review_body: &str,
+    ) -> Result<Option<(PushTarget, ai::ErGitHubComments, String)>> {
+        if self.blocked_by_read_only("pushing comments") {
+            return Ok(None);
+        }
+        if self.comment_push.rx.is_some() {
+            self.notify("Already pushing comments");
+            return Ok(None);
+        }
+        let target = match self.push_target(None) {
+            Ok(target) => target,
+            Err(e) => {
+                self.notify(&e.to_string());
+                return Ok(None);
+            }
+        };
+
+        // Not locked across the push; written back with the GitHub ids after
+        let comments_dir = self.tab().github_comments_dir();
+        let gc = match ai::store::load::<ai::ErGitHubComments>(&comments_dir)? {
+            Some(gc) => gc,
+            None if !review_body.is_empty() => {
+                ai::ErGitHubComments::empty(&self.tab().branch_diff_hash)
+            }
+            None => return Ok(None),
+        };
+        // This push covers whatever was queued
+        Outbox::clear(&comments_dir);
+        self.tab_mut().outbox = None;
+        Ok(Some((target, gc, comments_dir)))
+    }
+
+    /// Run `push` on a worker; [`Self::poll_comment_push`] picks up its
+    /// waits and result.
+    fn spawn_push(
+        &mut self,
+        label: &'static str,
+        (mut target, mut gc, comments_dir): (PushTarget, ai::ErGitHubComments, String),
+        total: usize,
+        push: impl FnOnce(&PushTarget, &mut ai::ErGitHubComments) -> Result<PushTally> + Send + 'static,
+    ) {
+        let (tx, rx) = mpsc::channel();
+        target.total = total as u32;
+        target.progress = Some(tx.clone());
+        std::thread::spawn(move || {
+            let tally = push(&target, &mut gc);
+            target.progress = None;
+            let _ = tx.send(PushUpdate::Done(Box::new(PushDone {
+                label,
+                comments_dir,
+                gc,
+                tally,
+                target,
+            })));
+        });
+        self.comment_push.rx = Some(rx);
+        self.notify("Pushing comments to GitHub…");
+    }
+
+    /// Push all unpushed local comments to GitHub
+    pub fn push_all_comments_to_github(&mut self) -> Result<()> {
+        let Some(push) = self.prepare_push("")? else {
+            return Ok(());
+        };
+        let total = unpushed_ids(&push.1, &[]).len();
+        self.spawn_push("Pushed", push, total, push_individually);
+        Ok(())
+    }
+
+    /// Push unpushed comments as one GitHub review: line comments in the
+    /// review itself, with the open review-wide notes and questions as its
+    /// body. General comments, file-level comments and replies can't ride
+    /// in a review and go up one by one.
+    pub fn push_comments_as_review(&mut self) -> Result<()> {
+        // Review-wide notes and questions go in the review body
+        let (review_body, review_note_ids) = self.tab().ai.review_body();
+        let Some(push) = self.prepare_push(&review_body)? else {
+            return Ok(());
+        };
+        let total = unpushed_ids(&push.1, &[]).len() + review_note_ids.len();
+        self.spawn_push("Review: pushed", push, total, move |target, gc| {
+            push_review(target, gc, &review_body, &review_note_ids)
+        });
+        Ok(())
+    }
+
+    /// Show a running push's rate-limit waits, and write back what it
+    /// pushed once it's done. True when there was news.
+    pub fn poll_comment_push(&mut self) -> bool {
+        let Some(rx) = &self.comment_push.rx else {
+            return false;
+        };
+        let done = match rx.try_recv() {
+            Ok(PushUpdate::Waiting {
+                secs,
+                pushed,
+                total,
+            }) => {
+                self.notify(&format!(
+                    "GitHub rate limited, retrying in {}s ({}/{} pushed)",
+                    secs, pushed, total
+                ));
+                return true;
+            }
+            Ok(PushUpdate::Done(done)) => done,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                self.comment_push.rx = None;
+                self.notify_error("Pushing comments failed: the worker stopped");
+                return true;
+            }
+        };
+        self.comment_push.rx = None;
+        if let Err(e) = self.finish_push(*done) {
+            self.report_command_error("Saving pushed comments failed", &e);
+        }
+        true
+    }
+
+    fn finish_push(&mut self, done: PushDone) -> Result<()> {
+        let PushDone {
+            label,
+            comments_dir,
+            gc,
+            tally,
+            target,
+        } = done;
+        let mut tally = match tally {
+            Ok(tally) => tally,
+            Err(e) => {
+                self.report_command_error("Could not fetch the PR diff to place comments", &e);
+                return Ok(());
+            }
+        };
+        // The tab may have moved or closed while the push ran
+        let idx = self
+            .tabs
+            .iter()
+            .position(|t| t.github_comments_dir() == comments_dir);
+        if let Some(tab) = idx.map(|i| &mut self.tabs[i]) {
+            if let Err(e) = tab.mark_review_notes_sent(&tally.sent_note_ids) {
+                tally.first_error.get_or_insert(e);
+            }
+        }
+        let queued = tally
+            .rate_limited
+            .then(|| unpushed_ids(&gc, &tally.failed_ids));
+        self.write_pushed_comments(idx, &tally, &comments_dir)?;
+        let queued = queued.map(|ids| self.queue_after_rate_limit(idx, &comments_dir, ids));
+        self.report_push(label, tally, queued, &target);
+        Ok(())
+    }
+
+    /// Push the outbox once its cooldown has passed. True when it did.
+    pub(super) fn retry_outbox_if_due(&mut self) -> bool {
+        let due = self
+            .tab()
+            .outbox
+            .as_ref()
+            .is_some_and(|o| o.due(outbox::unix_now()));
+        if !due
+            || self.read_only
+            || self.input_mode != super::InputMode::Normal
+            || self.overlay.is_some()
+            || self.comment_push.rx.is_some()
+        {
+            return false;
+        }
+        Outbox::clear(&self.tab().github_comments_dir());
+        self.tab_mut().outbox = None;
+        if let Err(e) = self.push_all_comments_to_github() {
+            self.report_command_error("Retrying queued comments failed", &e);
+        } else if self.comment_push.rx.is_some() {
+            self.notify("Retrying comments queued after GitHub's rate limit");
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(json: &str) -> GitHubReviewComment {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn queue_skips_pushed_and_failed_comments() {
+        let gc = ai::ErGitHubComments {
+            version: 1,
+            diff_hash: "h".into(),
+            github: None,
+            comments: vec![
+                comment(
+                    r#"{"id":"a","file":"","comment":"x","author":"You","source":"local","synced":false}"#,
+                ),
+                comment(
+                    r#"{"id":"b","file":"","comment":"x","author":"You","source":"local","synced":true}"#,
+                ),
+                comment(
+                    r#"{"id":"c","file":"","comment":"x","author":"You","source":"local","synced":false}"#,
+                ),
+                comment(
+                    r#"{"id":"d","file":"","comment":"x","author":"octocat","source":"github","synced":true}"#,
+                ),
+            ],
+        };
+        assert_eq!(unpushed_ids(&gc, &["c".to_string()]), vec!["a"]);
+    }
+
+    #[test]
+    fn write_back_keeps_changes_made_while_the_push_ran() {
+        let mut on_disk = ai::ErGitHubComments {
+            version: 1,
+            diff_hash: "h".into(),
+            github: None,
+            comments: vec![
+                // Resolved while its push ran
+                comment(
+                    r#"{"id":"a","file":"","comment":"x","author":"You","source":"local","synced":false,"resolved":true}"#,
+                ),
+                // Edited again after the edit that went up
+                comment(
+                    r#"{"id":"b","file":"","comment":"newer","author":"You","source":"local","synced":true,"github_id":2,"edit_base":"old"}"#,
+                ),
+                comment(
+                    r#"{"id":"c","file":"","comment":"sent","author":"You","source":"local","synced":true,"github_id":3,"edit_base":"old"}"#,
+                ),
+            ],
+        };
+        let tally = PushTally {
+            // "d" was deleted while its push ran
+            sent: vec![("a".into(), Some(10)), ("d".into(), Some(11))],
+            edited: vec![("b".into(), "new".into()), ("c".into(), "sent".into())],
+            ..Default::default()
+        };
+        tally.apply_to(&mut on_disk);
+        let ids: Vec<&str> = on_disk.comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        let a = &on_disk.comments[0];
+        assert!(a.synced && a.resolved);
+        assert_eq!(a.github_id, Some(10));
+        assert!(on_disk.comments[1].has_unpushed_edit());
+        assert!(!on_disk.comments[2].has_unpushed_edit());
+    }
+
+    #[test]
+    fn review_comments_get_their_ids_in_order() {
+        let entry = |file: &str, body: &str| ReviewBatchEntry {
+            file: file.into(),
+            line: 1,
+            start_line: None,
+            body: body.into(),
+            side: "RIGHT".into(),
+        };
+        let created = |id: u64, path: &str, body: &str| github::ReviewCommentId {
+            id,
+            path: path.into(),
+            body: body.into(),
+        };
+        let mut ids = vec![
+            created(5, "a.rs", "Nit"),
+            created(6, "b.rs", "Nit"),
+            created(7, "a.rs", "Nit"),
+        ];
+        assert_eq!(
+            take_review_comment(&mut ids, &entry("a.rs", "Nit")),
+            Some(5)
+        );
+        assert_eq!(
+            take_review_comment(&mut ids, &entry("a.rs", "Nit")),
+            Some(7)
+        );
+        assert_eq!(take_review_comment(&mut ids, &entry("a.rs", "Nit")), None);
+        assert_eq!(
+            take_review_comment(&mut ids, &entry("b.rs", "Nit")),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn poll_reports_rate_limit_waits_and_a_stopped_worker() {
+        let mut app = App::new_for_test(vec![]);
+        let (tx, rx) = mpsc::channel();
+        app.comment_push.rx = Some(rx);
+
+        app.push_all_comments_to_github().unwrap();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Already pushing comments")
+        );
+
+        tx.send(PushUpdate::Waiting {
+            secs: 8,
+            pushed: 3,
+            total: 12,
+        })
+        .unwrap();
+        assert!(app.poll_comment_push());
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("GitHub rate limited, retrying in 8s (3/12 pushed)")
+        );
+        assert!(!app.poll_comment_push());
+
+        // A worker that died doesn't block the next push
+        drop(tx);
+        assert!(app.poll_comment_push());
+        assert!(app.comment_push.rx.is_none());
+    }
+}
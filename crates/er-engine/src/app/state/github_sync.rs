@@ -1,6 +1,6 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use crate::ai;
+use crate::ai::{self, store::Sidecar};
 use crate::github;
 
 // The pure sync core (no App dependency) lives in `crate::sync`; re-exported
@@ -31,27 +31,35 @@ impl App {
             pr_number,
             is_remote: tab.is_remote(),
             repo_root: tab.repo_root.clone(),
-            comments_path: tab.github_comments_path(),
+            comments_dir: tab.github_comments_dir(),
             diff_hash: tab.branch_diff_hash.clone(),
             anchor_hash: tab.diff_hash.clone(),
             files: tab.files.clone(),
             pr_number_for_overview: tab.pr_number,
+            sync_viewed: self.config.features.github_viewed_sync,
         }
     }
 
-    /// Apply pre-fetched comment sync results. Finds the correct tab by identity
-    /// (safe against the user switching/closing tabs during network I/O).
-    pub fn apply_comment_sync_result(&mut self, result: CommentSyncResult) {
+    /// Tab a sync result belongs to, found by identity (safe against the
+    /// user switching or closing tabs during network I/O).
+    pub(super) fn comment_sync_tab(&self, result: &CommentSyncResult) -> Option<usize> {
         let (target_root, target_pr, target_is_remote) = &result.tab_key;
-        let tab_idx = self.tabs.iter().position(|t| {
+        self.tabs.iter().position(|t| {
             &t.repo_root == target_root
                 && t.pr_number == *target_pr
                 && t.is_remote() == *target_is_remote
-        });
-        let idx = match tab_idx {
-            Some(i) => i,
-            None => return, // tab was closed or switched — file was written; next activate picks it up
-        };
+        })
+    }
+
+    /// Reload tab `idx` from a sync result already written to disk.
+    pub(super) fn apply_comment_sync_to_tab(&mut self, idx: usize, result: CommentSyncResult) {
+        crate::debug_log::info(
+            "sync",
+            format!(
+                "{}: {} from GitHub, {} local kept",
+                result.comments_path, result.github_count, result.local_count
+            ),
+        );
         if result.is_remote {
             self.tabs[idx].reload_remote_comments();
         } else {
@@ -60,12 +68,30 @@ impl App {
         if let Some(pr_data) = result.pr_data {
             self.tabs[idx].pr_data = Some(pr_data);
         }
+        if let Some(viewed) = result.viewed {
+            let merge = self.apply_viewed_files(idx, viewed);
+            self.spawn_viewed_pushes(idx, &merge);
+        }
+    }
+
+    /// Apply pre-fetched comment sync results to the tab they were fetched for.
+    pub fn apply_comment_sync_result(&mut self, result: CommentSyncResult) {
+        // Tab was closed or switched — the file was written; next activate picks it up
+        let Some(idx) = self.comment_sync_tab(&result) else {
+            return;
+        };
+        let (github_count, local_count, conflicts) =
+            (result.github_count, result.local_count, result.conflicts);
+        self.apply_comment_sync_to_tab(idx, result);
         // Only notify if this is the currently active tab.
         if idx == self.active_tab {
             self.notify(&format!(
-                "GitHub sync: {} from GitHub, {} local kept, PR status refreshed",
-                result.github_count, result.local_count
+                "GitHub sync: {} from GitHub, {} local kept{}, PR status refreshed",
+                github_count,
+                local_count,
+                crate::sync::conflict_note(conflicts)
             ));
+            self.surface_comment_conflicts(conflicts);
         }
     }
 }
@@ -129,25 +155,10 @@ impl App {
 
         // Load existing github-comments.json (PR-scoped: shared PR bucket for PR tabs)
         let comments_dir = self.tab().github_comments_dir();
-        let _ = std::fs::create_dir_all(&comments_dir);
-        let comments_path = self.tab().github_comments_path();
+        let _lock = ai::store::lock::<ai::ErGitHubComments>(&comments_dir)?;
         let diff_hash = tab.branch_diff_hash.clone();
-        let mut gc: ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-            Ok(content) => {
-                serde_json::from_str(&content).unwrap_or_else(|_| ai::ErGitHubComments {
-                    version: 1,
-                    diff_hash: diff_hash.clone(),
-                    github: None,
-                    comments: Vec::new(),
-                })
-            }
-            Err(_) => ai::ErGitHubComments {
-                version: 1,
-                diff_hash: diff_hash.clone(),
-                github: None,
-                comments: Vec::new(),
-            },
-        };
+        let mut gc = ai::store::load(&comments_dir)?
+            .unwrap_or_else(|| ai::ErGitHubComments::empty(&diff_hash));
 
         gc.github = Some(ai::GitHubSyncState {
             pr_number: Some(pr_number),
@@ -156,12 +167,14 @@ impl App {
             last_synced: chrono_now(),
         });
 
-        // Keep only truly local unpushed comments
-        let local_unpushed: Vec<_> = gc
-            .comments
-            .into_iter()
+        // Unpushed local comments and edits GitHub hasn't seen survive the merge
+
+        let local = std::mem::take(&mut gc.comments);
+
+        let local_count = local
+            .iter()
             .filter(|c| c.source == "local" && !c.synced)
-            .collect();
+            .count();
 
         // Build fresh GitHub entries from API response
         let tab_files = self.tab().files.clone();
@@ -226,23 +239,19 @@ impl App {
                 hunk_header: anchor_hunk_header,
                 anchor_status: "original".to_string(),
                 relocated_at_hash: diff_hash_for_anchor.clone(),
+                anchor_confidence: None,
                 finding_ref: None,
                 side: gh.side.clone().unwrap_or_else(|| "RIGHT".to_string()),
+                edit_base: None,
+                remote_body: None,
             });
         }
 
         let github_count = github_entries.len();
-        let local_count = local_unpushed.len();
-        gc.comments = local_unpushed;
-        gc.comments.extend(github_entries);
+        let (comments, conflicts) = crate::sync::merge_github_comments(local, github_entries);
+        gc.comments = comments;
 
-        if let Some(dir) = std::path::Path::new(&comments_path).parent() {
-            std::fs::create_dir_all(dir)?;
-        }
-        let json = serde_json::to_string_pretty(&gc)?;
-        let tmp_path = format!("{}.tmp", comments_path);
-        std::fs::write(&tmp_path, &json)?;
-        std::fs::rename(&tmp_path, &comments_path)?;
+        ai::store::write(&comments_dir, &gc)?;
 
         if is_remote {
             self.tab_mut().reload_remote_comments();
@@ -264,211 +273,18 @@ impl App {
             self.tab_mut().pr_data = Some(pr_data);
         }
 
+        let viewed = self
+            .sync_viewed_files(&owner, &repo_name, pr_number)
+            .map(|s| format!(", {}", s))
+            .unwrap_or_default();
         self.notify(&format!(
-            "GitHub sync: {} from GitHub, {} local kept, PR status refreshed",
-            github_count, local_count
+            "GitHub sync: {} from GitHub, {} local kept{}{}, PR status refreshed",
+            github_count,
+            local_count,
+            crate::sync::conflict_note(conflicts),
+            viewed
         ));
-        Ok(())
-    }
-
-    /// Push all unpushed local comments to GitHub
-    pub fn push_all_comments_to_github(&mut self) -> Result<()> {
-        let tab = self.tab();
-        let repo_root = tab.repo_root.clone();
-        let explicit_pr_number = tab.pr_number;
-        let is_remote = tab.is_remote();
-        let remote_repo = tab.remote_repo.clone();
-
-        let (owner, repo_name, pr_number) = if is_remote {
-            if let (Some(ref slug), Some(n)) = (&remote_repo, explicit_pr_number) {
-                let parts: Vec<&str> = slug.split('/').collect();
-                if parts.len() == 2 {
-                    (parts[0].to_string(), parts[1].to_string(), n)
-                } else {
-                    self.notify("Invalid remote repo slug");
-                    return Ok(());
-                }
-            } else {
-                self.notify("No PR info for remote mode");
-                return Ok(());
-            }
-        } else {
-            match local_pr_target(&repo_root, explicit_pr_number) {
-                Ok(info) => info,
-                Err(_) => {
-                    self.notify("No PR found for current branch");
-                    return Ok(());
-                }
-            }
-        };
-
-        let comments_path = self.tab().github_comments_path();
-        let mut gc: ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(gc) => gc,
-                Err(_) => return Ok(()),
-            },
-            Err(_) => return Ok(()),
-        };
-
-        let mut pushed = 0u32;
-        let mut failed = 0u32;
-
-        // Push parents first
-        let comment_ids: Vec<String> = gc
-            .comments
-            .iter()
-            .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_none())
-            .map(|c| c.id.clone())
-            .collect();
-
-        for cid in &comment_ids {
-            let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-            if let Some(comment) = comment {
-                // General comments (empty file) route to the issues API
-                if comment.file.is_empty() {
-                    match if is_remote {
-                        github::gh_pr_general_comment_remote(
-                            &owner,
-                            &repo_name,
-                            pr_number,
-                            &comment.comment,
-                        )
-                    } else {
-                        github::gh_pr_general_comment(
-                            &owner,
-                            &repo_name,
-                            pr_number,
-                            &comment.comment,
-                            &repo_root,
-                        )
-                    } {
-                        Ok(github_id) => {
-                            if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                                c.github_id = Some(github_id);
-                                c.synced = true;
-                            }
-                            pushed += 1;
-                        }
-                        Err(_) => {
-                            failed += 1;
-                        }
-                    }
-                    continue;
-                }
-
-                let path = &comment.file;
-                // Hunk-level comments have no line_start; the line-level push API requires
-                // a line, so they get anchored to line 1 on GitHub.
-                let start = comment.line_start.unwrap_or(1);
-                let end = comment.line_end.unwrap_or(start);
-                let side = comment.side.as_str();
-                match if is_remote {
-                    github::gh_pr_push_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        path,
-                        start,
-                        Some(end),
-                        &comment.comment,
-                        side,
-                    )
-                } else {
-                    github::gh_pr_push_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        path,
-                        start,
-                        Some(end),
-                        &comment.comment,
-                        side,
-                        &repo_root,
-                    )
-                } {
-                    Ok(github_id) => {
-                        if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                            c.github_id = Some(github_id);
-                            c.synced = true;
-                        }
-                        pushed += 1;
-                    }
-                    Err(_) => {
-                        failed += 1;
-                    }
-                }
-            }
-        }
-
-        // Then push replies
-        let reply_ids: Vec<String> = gc
-            .comments
-            .iter()
-            .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_some())
-            .map(|c| c.id.clone())
-            .collect();
-
-        for cid in &reply_ids {
-            let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-            if let Some(comment) = comment {
-                let parent_gh_id = comment
-                    .in_reply_to
-                    .as_ref()
-                    .and_then(|rt| gc.comments.iter().find(|c| c.id == *rt))
-                    .and_then(|c| c.github_id);
-
-                if let Some(parent_gh_id) = parent_gh_id {
-                    match if is_remote {
-                        github::gh_pr_reply_comment_remote(
-                            &owner,
-                            &repo_name,
-                            pr_number,
-                            parent_gh_id,
-                            &comment.comment,
-                        )
-                    } else {
-                        github::gh_pr_reply_comment(
-                            &owner,
-                            &repo_name,
-                            pr_number,
-                            parent_gh_id,
-                            &comment.comment,
-                            &repo_root,
-                        )
-                    } {
-                        Ok(github_id) => {
-                            if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                                c.github_id = Some(github_id);
-                                c.synced = true;
-                            }
-                            pushed += 1;
-                        }
-                        Err(_) => {
-                            failed += 1;
-                        }
-                    }
-                } else {
-                    failed += 1;
-                }
-            }
-        }
-
-        let json = serde_json::to_string_pretty(&gc)?;
-        let tmp_path = format!("{}.tmp", comments_path);
-        std::fs::write(&tmp_path, &json)?;
-        std::fs::rename(&tmp_path, &comments_path)?;
-        if is_remote {
-            self.tab_mut().reload_remote_comments();
-        } else {
-            self.tab_mut().reload_ai_state();
-        }
-
-        if failed > 0 {
-            self.notify(&format!("Pushed {} comments ({} failed)", pushed, failed));
-        } else {
-            self.notify(&format!("Pushed {} comments", pushed));
-        }
+        self.surface_comment_conflicts(conflicts);
         Ok(())
     }
 
@@ -478,36 +294,14 @@ impl App {
         thread_id: &str,
         pr_number_hint: Option<u64>,
     ) -> Result<()> {
-        let tab = self.tab();
-        let repo_root = tab.repo_root.clone();
-        let explicit_pr_number = tab.pr_number.or(pr_number_hint);
-        let is_remote = tab.is_remote();
-        let remote_repo = tab.remote_repo.clone();
-
-        let (owner, repo_name, pr_number) = if is_remote {
-            if let (Some(ref slug), Some(n)) = (&remote_repo, explicit_pr_number) {
-                let parts: Vec<&str> = slug.split('/').collect();
-                if parts.len() == 2 {
-                    (parts[0].to_string(), parts[1].to_string(), n)
-                } else {
-                    anyhow::bail!("Invalid remote repo slug");
-                }
-            } else {
-                anyhow::bail!("No PR info for remote mode");
-            }
-        } else {
-            local_pr_target(&repo_root, explicit_pr_number)
-                .map_err(|_| anyhow::anyhow!("No PR found for current branch"))?
-        };
-
-        let comments_path = self.tab().github_comments_path();
-        let mut gc: ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(gc) => gc,
-                Err(e) => anyhow::bail!("Failed to parse github-comments.json: {e}"),
-            },
-            Err(_) => anyhow::bail!("No github-comments.json found"),
-        };
+        if self.blocked_by_read_only("pushing comments") {
+            return Ok(());
+        }
+        let target = self.push_target(pr_number_hint)?;
+        let comments_dir = self.tab().github_comments_dir();
+        let _lock = ai::store::lock::<ai::ErGitHubComments>(&comments_dir)?;
+        let mut gc: ai::ErGitHubComments =
+            ai::store::load(&comments_dir)?.context("No github-comments.json found")?;
 
         let parent_idx = gc
             .comments
@@ -525,61 +319,19 @@ impl App {
             anyhow::bail!("Use Push only this on the thread root, not a reply");
         }
 
-        let push_parent = |comment: &ai::GitHubReviewComment| -> Result<u64> {
-            if comment.file.is_empty() {
-                if is_remote {
-                    github::gh_pr_general_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.comment,
-                    )
-                } else {
-                    github::gh_pr_general_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.comment,
-                        &repo_root,
-                    )
-                }
-            } else {
-                let start = comment.line_start.ok_or_else(|| {
-                    anyhow::anyhow!(
-                        "Comment has no line anchor; add it on a diff line before pushing"
-                    )
-                })?;
-                let end = comment.line_end.unwrap_or(start);
-                let side = comment.side.as_str();
-                if is_remote {
-                    github::gh_pr_push_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.file,
-                        start,
-                        Some(end),
-                        &comment.comment,
-                        side,
-                    )
-                } else {
-                    github::gh_pr_push_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.file,
-                        start,
-                        Some(end),
-                        &comment.comment,
-                        side,
-                        &repo_root,
-                    )
-                }
+        let pr_files = if parent.file.is_empty() || parent.is_file_level() {
+            Vec::new()
+        } else {
+            if parent.line_start.is_none() && parent.hunk_header.is_empty() {
+                anyhow::bail!("Comment has no line anchor; add it on a diff line before pushing");
             }
+            target
+                .pr_files()
+                .map_err(|e| anyhow::anyhow!("Could not fetch the PR diff: {e}"))?
         };
-
-        let github_id =
-            push_parent(parent).map_err(|e| anyhow::anyhow!("Failed to push comment: {e}"))?;
+        let github_id = target
+            .push_root(parent, &pr_files)
+            .map_err(|e| anyhow::anyhow!("Failed to push comment: {e}"))?;
         gc.comments[parent_idx].github_id = Some(github_id);
         gc.comments[parent_idx].synced = true;
 
@@ -597,24 +349,7 @@ impl App {
             let Some(comment) = gc.comments.iter().find(|c| c.id == rid).cloned() else {
                 continue;
             };
-            match if is_remote {
-                github::gh_pr_reply_comment_remote(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    github_id,
-                    &comment.comment,
-                )
-            } else {
-                github::gh_pr_reply_comment(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    github_id,
-                    &comment.comment,
-                    &repo_root,
-                )
-            } {
+            match target.push_reply(github_id, &comment.comment) {
                 Ok(reply_gh_id) => {
                     if let Some(c) = gc.comments.iter_mut().find(|c| c.id == rid) {
                         c.github_id = Some(reply_gh_id);
@@ -625,11 +360,8 @@ impl App {
             }
         }
 
-        let json = serde_json::to_string_pretty(&gc)?;
-        let tmp_path = format!("{}.tmp", comments_path);
-        std::fs::write(&tmp_path, &json)?;
-        std::fs::rename(&tmp_path, &comments_path)?;
-        if is_remote {
+        ai::store::write(&comments_dir, &gc)?;
+        if target.is_remote {
             self.tab_mut().reload_remote_comments();
         } else {
             self.tab_mut().reload_ai_state();
@@ -656,36 +388,12 @@ impl App {
             anyhow::bail!("Finding validation replies cannot be pushed individually");
         }
 
-        let tab = self.tab();
-        let repo_root = tab.repo_root.clone();
-        let explicit_pr_number = tab.pr_number.or(pr_number_hint);
-        let is_remote = tab.is_remote();
-        let remote_repo = tab.remote_repo.clone();
+        let target = self.push_target(pr_number_hint)?;
 
-        let (owner, repo_name, pr_number) = if is_remote {
-            if let (Some(ref slug), Some(n)) = (&remote_repo, explicit_pr_number) {
-                let parts: Vec<&str> = slug.split('/').collect();
-                if parts.len() == 2 {
-                    (parts[0].to_string(), parts[1].to_string(), n)
-                } else {
-                    anyhow::bail!("Invalid remote repo slug");
-                }
-            } else {
-                anyhow::bail!("No PR info for remote mode");
-            }
-        } else {
-            local_pr_target(&repo_root, explicit_pr_number)
-                .map_err(|_| anyhow::anyhow!("No PR found for current branch"))?
-        };
-
-        let comments_path = self.tab().github_comments_path();
-        let mut gc: ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(gc) => gc,
-                Err(e) => anyhow::bail!("Failed to parse github-comments.json: {e}"),
-            },
-            Err(_) => anyhow::bail!("No github-comments.json found"),
-        };
+        let comments_dir = self.tab().github_comments_dir();
+        let _lock = ai::store::lock::<ai::ErGitHubComments>(&comments_dir)?;
+        let mut gc: ai::ErGitHubComments =
+            ai::store::load(&comments_dir)?.context("No github-comments.json found")?;
 
         let reply = gc
             .comments
@@ -715,36 +423,17 @@ impl App {
             .ok_or_else(|| anyhow::anyhow!("Parent comment has no GitHub id"))?;
 
         let reply_body = reply.comment.clone();
-        let github_id = if is_remote {
-            github::gh_pr_reply_comment_remote(
-                &owner,
-                &repo_name,
-                pr_number,
-                parent_github_id,
-                &reply_body,
-            )
-        } else {
-            github::gh_pr_reply_comment(
-                &owner,
-                &repo_name,
-                pr_number,
-                parent_github_id,
-                &reply_body,
-                &repo_root,
-            )
-        }
-        .map_err(|e| anyhow::anyhow!("Failed to push reply: {e}"))?;
+        let github_id = target
+            .push_reply(parent_github_id, &reply_body)
+            .map_err(|e| anyhow::anyhow!("Failed to push reply: {e}"))?;
 
         if let Some(c) = gc.comments.iter_mut().find(|c| c.id == reply_id) {
             c.github_id = Some(github_id);
             c.synced = true;
         }
 
-        let json = serde_json::to_string_pretty(&gc)?;
-        let tmp_path = format!("{}.tmp", comments_path);
-        std::fs::write(&tmp_path, &json)?;
-        std::fs::rename(&tmp_path, &comments_path)?;
-        if is_remote {
+        ai::store::write(&comments_dir, &gc)?;
+        if target.is_remote {
             self.tab_mut().reload_remote_comments();
         } else {
             self.tab_mut().reload_ai_state();
@@ -753,3 +442,177 @@ impl App {
         Ok(())
     }
 }
+
+// ── Comments edited on both sides ─────────────────────────────────────────────
+
+impl App {
+    pub fn comment_conflict_count(&self) -> usize {
+        self.tab().ai.github_comments.as_ref().map_or(0, |gc| {
+            gc.comments.iter().filter(|c| c.in_sync_conflict()).count()
+        })
+    }
+
+    /// After a sync: open the conflict list when there is one to settle and
+    /// nothing else is on screen.
+    pub fn surface_comment_conflicts(&mut self, conflicts: usize) {
+        if conflicts > 0 && self.overlay.is_none() {
+            self.open_comment_conflicts_hub();
+        }
+    }
+
+    /// Each conflicted comment with both versions to pick from.
+    pub fn open_comment_conflicts_hub(&mut self) {
+        let conflicted: Vec<ai::GitHubReviewComment> = self
+            .tab()
+            .ai
+            .github_comments
+            .as_ref()
+            .map(|gc| {
+                gc.comments
+                    .iter()
+                    .filter(|c| c.in_sync_conflict())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        if conflicted.is_empty() {
+            self.notify("No comment conflicts");
+            return;
+        }
+        let first_line = |s: &str| s.lines().next().unwrap_or("").to_string();
+        let mut items = Vec::new();
+        for c in &conflicted {
+            let location = match c.line_start {
+                Some(line) => format!("{}:{}", c.file, line),
+                None => c.file.clone(),
+            };
+            items.push(super::HubItem {
+                label: location,
+                hint: String::new(),
+                description: String::new(),
+                action: super::HubAction::Noop,
+                is_header: true,
+                enabled: false,
+            });
+            for (keep_mine, label, body) in [
+                (true, "Keep mine", c.comment.as_str()),
+                (
+                    false,
+                    "Take GitHub's",
+                    c.remote_body.as_deref().unwrap_or(""),
+                ),
+            ] {
+                items.push(super::HubItem {
+                    label: label.into(),
+                    hint: String::new(),
+                    description: first_line(body),
+                    action: super::HubAction::ResolveCommentConflict {
+                        id: c.id.clone(),
+                        keep_mine,
+                    },
+                    is_header: false,
+                    enabled: true,
+                });
+            }
+        }
+        self.overlay = Some(super::OverlayData::ModalHub {
+            kind: super::HubKind::CommentConflicts,
+            title: None,
+            selected: 1,
+            items,
+        });
+    }
+
+    /// Settle a conflict. Keeping ours leaves it as an edit for the next
+    /// push; taking GitHub's drops ours.
+    pub fn resolve_comment_conflict(&mut self, id: &str, keep_mine: bool) -> Result<()> {
+        let comments_dir = self.tab().github_comments_dir();
+        let _lock = ai::store::lock::<ai::ErGitHubComments>(&comments_dir)?;
+        let mut gc: ai::ErGitHubComments =
+            ai::store::load(&comments_dir)?.context("No github-comments.json found")?;
+        let Some(c) = gc.comments.iter_mut().find(|c| c.id == id) else {
+            self.notify("Comment not found");
+            return Ok(());
+        };
+        let Some(theirs) = c.remote_body.take() else {
+            return Ok(());
+        };
+        if keep_mine {
+            c.edit_base = Some(theirs);
+        } else {
+            c.comment = theirs;
+            c.edit_base = None;
+        }
+        ai::store::write(&comments_dir, &gc)?;
+        if self.tab().is_remote() {
+            self.tab_mut().reload_remote_comments();
+        } else {
+            self.tab_mut().reload_ai_state();
+        }
+
+        let remaining = self.comment_conflict_count();
+        if remaining > 0 {
+            self.open_comment_conflicts_hub();
+        }
+        let outcome = if keep_mine {
+            "Kept your version — push comments to send it"
+        } else {
+            "Took GitHub's version"
+        };
+        match remaining {
+            0 => self.notify(outcome),
+            n => self.notify(&format!("{} · {} conflict(s) left", outcome, n)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::ErRoot;
+
+    #[test]
+    fn conflicts_open_a_hub_and_resolve_either_way() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().er_root = ErRoot::RepoLocal(root.clone());
+        app.tab_mut().repo_root = root;
+        let comment = |id: &str| {
+            serde_json::json!({
+                "id": id, "file": "a.rs", "hunk_index": 0, "line_start": 3, "line_end": null,
+                "comment": "ours", "github_id": 7, "source": "github", "synced": true,
+                "edit_base": "original", "remote_body": "theirs",
+            })
+        };
+        let gc = serde_json::json!({
+            "version": 1, "diff_hash": "h",
+            "comments": [comment("gh-1"), comment("gh-2")],
+        });
+        let path = app.tab().github_comments_path();
+        std::fs::create_dir_all(std::path::Path::new(&path).parent().unwrap()).unwrap();
+        std::fs::write(&path, gc.to_string()).unwrap();
+        app.tab_mut().reload_ai_state();
+        assert_eq!(app.comment_conflict_count(), 2);
+
+        app.surface_comment_conflicts(2);
+        assert!(matches!(
+            app.overlay,
+            Some(super::super::OverlayData::ModalHub {
+                kind: super::super::HubKind::CommentConflicts,
+                ..
+            })
+        ));
+
+        app.resolve_comment_conflict("gh-1", true).unwrap();
+        app.resolve_comment_conflict("gh-2", false).unwrap();
+        let comments = &app.tab().ai.github_comments.as_ref().unwrap().comments;
+        assert_eq!(comments[0].comment, "ours");
+        assert!(comments[0].has_unpushed_edit());
+        assert_eq!(comments[0].edit_base.as_deref(), Some("theirs"));
+        assert_eq!(comments[1].comment, "theirs");
+        assert!(comments[1].edit_base.is_none() && !comments[1].in_sync_conflict());
+        assert_eq!(app.comment_conflict_count(), 0);
+    }
+}
@@ -0,0 +1,196 @@
+//! "Explain this hunk" — a few sentences from the AI provider about the
+//! current hunk, shown in the File Detail panel.
+//!
+//! Only the hunk and a handful of lines around it are sent, through the same
+//! one-shot invocation the desktop's Ask AI uses. Replies are kept per hunk
+//! hash, so asking again about an unchanged hunk (in any file position, after
+//! any refresh) costs nothing.
+
+use std::sync::mpsc::{self, TryRecvError};
+
+use crate::ai::prompts::{hunk_explain_context, HUNK_EXPLAIN_PROMPT};
+use crate::ai::PanelContent;
+use crate::app::{plan_card_ai_invocation, run_card_ai_subprocess};
+use crate::git::DiffHunk;
+
+use super::{App, TabState};
+
+/// Lines of the file shown above and below the hunk.
+const CONTEXT_LINES: usize = 8;
+
+/// What `run_card_ai_subprocess` answers with when the provider failed.
+const FAILED_REPLY_PREFIX: &str = "Pending — invoke via CLI";
+
+/// Cache key for a hunk: its path and content, not its position.
+pub fn hunk_explain_key(path: &str, hunk: &DiffHunk) -> String {
+    crate::ai::compute_diff_hash(&format!("{}\n{}", path, hunk.to_text()))
+}
+
+/// The `CONTEXT_LINES` lines before and after `hunk` in `content`.
+fn surrounding_lines(content: &str, hunk: &DiffHunk) -> (String, String) {
+    let lines: Vec<&str> = content.lines().collect();
+    let first = hunk.new_start.saturating_sub(1).min(lines.len());
+    let last = (first + hunk.new_count).min(lines.len());
+    let before = lines[first.saturating_sub(CONTEXT_LINES)..first].join("\n");
+    let after = lines[last..(last + CONTEXT_LINES).min(lines.len())].join("\n");
+    (before, after)
+}
+
+impl TabState {
+    fn current_hunk_key(&self) -> Option<String> {
+        let file = self.selected_diff_file()?;
+        let hunk = file.hunks.get(self.current_hunk)?;
+        Some(hunk_explain_key(&file.path, hunk))
+    }
+
+    /// Cached explanation of the current hunk.
+    pub fn current_hunk_explanation(&self) -> Option<&str> {
+        let key = self.current_hunk_key()?;
+        self.hunk_explanations.get(&key).map(String::as_str)
+    }
+
+    /// An explanation of the current hunk is on its way.
+    pub fn explaining_current_hunk(&self) -> bool {
+        match &self.hunk_explain_rx {
+            Some((key, _)) => self.current_hunk_key().as_ref() == Some(key),
+            None => false,
+        }
+    }
+}
+
+impl App {
+    /// Explain the current hunk in the File Detail panel, asking the AI
+    /// provider only when this hunk hasn't been explained before.
+    pub fn explain_current_hunk(&mut self) {
+        let tab = self.tab();
+        let Some(file) = tab.selected_diff_file() else {
+            self.notify("No file selected");
+            return;
+        };
+        let Some(hunk) = file.hunks.get(tab.current_hunk) else {
+            self.notify("No hunk to explain");
+            return;
+        };
+        let key = hunk_explain_key(&file.path, hunk);
+        if tab.hunk_explanations.contains_key(&key) || tab.explaining_current_hunk() {
+            self.tab_mut().panel = Some(PanelContent::FileDetail);
+            return;
+        }
+        if tab.hunk_explain_rx.is_some() {
+            self.notify("Already explaining another hunk");
+            return;
+        }
+
+        let (before, after) = if tab.is_remote() {
+            (String::new(), String::new())
+        } else {
+            std::fs::read_to_string(std::path::Path::new(&tab.repo_root).join(&file.path))
+                .map(|content| surrounding_lines(&content, hunk))
+                .unwrap_or_default()
+        };
+        let system = hunk_explain_context(&file.path, &hunk.to_text(), &before, &after);
+
+        self.sync_ai_selection();
+        let invocation = plan_card_ai_invocation(
+            &self.config,
+            self.current_ai_provider.as_deref(),
+            self.current_ai_model.as_deref(),
+            self.current_ai_effort.as_deref(),
+            self.tab().repo_root.clone(),
+        );
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let reply = run_card_ai_subprocess(&invocation, &system, HUNK_EXPLAIN_PROMPT, None);
+            let _ = tx.send(reply);
+        });
+        let tab = self.tab_mut();
+        tab.hunk_explain_rx = Some((key, rx));
+        tab.panel = Some(PanelContent::FileDetail);
+    }
+
+    /// Store a finished explanation. True when the panel needs a redraw.
+    pub fn poll_hunk_explanation(&mut self) -> bool {
+        let tab = self.tab_mut();
+        let Some((_, rx)) = &tab.hunk_explain_rx else {
+            return false;
+        };
+        let reply = match rx.try_recv() {
+            Err(TryRecvError::Empty) => return false,
+            Ok(reply) => Some(reply),
+            Err(TryRecvError::Disconnected) => None,
+        };
+        let Some((key, _)) = tab.hunk_explain_rx.take() else {
+            return false;
+        };
+        match reply {
+            Some(reply) if !reply.starts_with(FAILED_REPLY_PREFIX) => {
+                tab.hunk_explanations.insert(key, reply);
+            }
+            Some(reply) => {
+                crate::debug_log::warn("ai", format!("explain hunk: {}", reply));
+                self.notify("Couldn't explain the hunk — see the debug log");
+            }
+            None => {}
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffFile, DiffLine, FileStatus, LineType};
+
+    fn hunk(new_start: usize, content: &str) -> DiffHunk {
+        DiffHunk {
+            header: format!("@@ -{0},1 +{0},1 @@", new_start),
+            old_start: new_start,
+            old_count: 1,
+            new_start,
+            new_count: 1,
+            lines: vec![DiffLine {
+                line_type: LineType::Add,
+                content: content.into(),
+                old_num: None,
+                new_num: Some(new_start),
+            }],
+        }
+    }
+
+    #[test]
+    fn context_is_the_lines_around_the_hunk() {
+        let content: String = (1..=30).map(|n| format!("line {}\n", n)).collect();
+        let (before, after) = surrounding_lines(&content, &hunk(12, "line 12"));
+        assert!(before.starts_with("line 4\n") && before.ends_with("line 11"));
+        assert!(after.starts_with("line 13\n") && after.ends_with("line 20"));
+        let (before, after) = surrounding_lines("only\n", &hunk(1, "only"));
+        assert!(before.is_empty() && after.is_empty());
+    }
+
+    #[test]
+    fn explained_hunks_are_answered_from_the_cache() {
+        let mut app = App::new_for_test(vec![DiffFile {
+            path: "src/a.rs".into(),
+            status: FileStatus::Modified,
+            hunks: vec![hunk(4, "let x = 1;"), hunk(9, "let y = 2;")],
+            adds: 2,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 2,
+        }]);
+        let first = hunk_explain_key("src/a.rs", &hunk(4, "let x = 1;"));
+        assert_ne!(first, hunk_explain_key("src/b.rs", &hunk(4, "let x = 1;")));
+        app.tab_mut()
+            .hunk_explanations
+            .insert(first, "Sets x.".into());
+
+        app.explain_current_hunk();
+        assert!(app.tab().hunk_explain_rx.is_none());
+        assert_eq!(app.tab().panel, Some(PanelContent::FileDetail));
+        assert_eq!(app.tab().current_hunk_explanation(), Some("Sets x."));
+
+        app.tab_mut().current_hunk = 1;
+        assert_eq!(app.tab().current_hunk_explanation(), None);
+        assert!(!app.poll_hunk_explanation());
+    }
+}
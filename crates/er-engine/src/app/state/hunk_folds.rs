@@ -0,0 +1,140 @@
+//! Folding single hunks to their `@@` line (Enter on a file that isn't
+//! compacted), for the 400-line fixture hunk next to the logic worth reading.
+//!
+//! Folds are saved in the session file and come back even when the diff
+//! changed, like marks. A fold is keyed by the hunk's old-side range and
+//! new-side length rather than its index or new-side start, so it stays put
+//! when hunks above it grow and lets go once the hunk itself changes.
+
+use super::{App, TabState};
+use crate::git::DiffHunk;
+
+/// What a fold remembers of its hunk.
+pub fn fold_key(hunk: &DiffHunk) -> String {
+    format!("-{},{} +{}", hunk.old_start, hunk.old_count, hunk.new_count)
+}
+
+impl TabState {
+    pub fn is_hunk_folded(&self, path: &str, hunk: &DiffHunk) -> bool {
+        self.folded_hunks
+            .get(path)
+            .is_some_and(|keys| keys.contains(&fold_key(hunk)))
+    }
+
+    /// Whether each hunk of the selected file is folded.
+    pub fn selected_file_folds(&self) -> Vec<bool> {
+        match self.selected_diff_file() {
+            Some(file) if self.folded_hunks.contains_key(&file.path) => file
+                .hunks
+                .iter()
+                .map(|h| self.is_hunk_folded(&file.path, h))
+                .collect(),
+            Some(file) => vec![false; file.hunks.len()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether hunk `idx` of the selected file is folded.
+    pub fn hunk_folded_at(&self, idx: usize) -> bool {
+        self.selected_diff_file()
+            .and_then(|f| f.hunks.get(idx).map(|h| self.is_hunk_folded(&f.path, h)))
+            .unwrap_or(false)
+    }
+
+    /// Fold or unfold hunk `idx` of the selected file. Returns whether it's
+    /// folded now, `None` when there's no such hunk.
+    pub fn set_hunk_folded(&mut self, idx: usize, folded: bool) -> Option<bool> {
+        let file = self.selected_diff_file()?;
+        let key = fold_key(file.hunks.get(idx)?);
+        let path = file.path.clone();
+        if folded {
+            self.folded_hunks.entry(path).or_default().insert(key);
+        } else if let Some(keys) = self.folded_hunks.get_mut(&path) {
+            keys.remove(&key);
+            if keys.is_empty() {
+                self.folded_hunks.remove(&path);
+            }
+        }
+        if folded && idx == self.current_hunk {
+            self.current_line = None;
+            self.selection_anchor = None;
+        }
+        self.rebuild_hunk_offsets();
+        Some(folded)
+    }
+
+    /// Fold the current hunk, or unfold it.
+    pub fn toggle_hunk_fold(&mut self) -> Option<bool> {
+        let folded = !self.hunk_folded_at(self.current_hunk);
+        let folded = self.set_hunk_folded(self.current_hunk, folded)?;
+        self.scroll_to_current_hunk();
+        Some(folded)
+    }
+}
+
+impl App {
+    /// Enter on a file that isn't compacted.
+    pub fn toggle_hunk_fold(&mut self) {
+        let lines = self
+            .tab()
+            .selected_diff_file()
+            .and_then(|f| f.hunks.get(self.tab().current_hunk))
+            .map(|h| h.lines.len());
+        match self.tab_mut().toggle_hunk_fold() {
+            Some(true) => self.notify(&format!(
+                "Hunk folded ({} lines) — Enter unfolds",
+                lines.unwrap_or_default()
+            )),
+            Some(false) => self.notify("Hunk unfolded"),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+                        @@ -1,2 +1,3 @@\n a\n+b\n c\n\
+                        @@ -20,2 +21,4 @@\n x\n+fixture 1\n+fixture 2\n y\n\
+                        @@ -40,1 +43,2 @@\n p\n+q\n";
+
+    #[test]
+    fn a_folded_hunk_is_skipped_by_line_moves_and_survives_shifts_above() {
+        let mut tab = TabState::new_for_test(git::parse_diff(DIFF));
+        tab.current_hunk = 1;
+        assert_eq!(tab.toggle_hunk_fold(), Some(true));
+        assert_eq!(tab.selected_file_folds(), vec![false, true, false]);
+        assert_eq!(tab.current_hunk_line_count(), 0);
+
+        // Down from the end of the first hunk stops on the fold's header,
+        // then goes on past it
+        tab.current_hunk = 0;
+        tab.current_line = Some(2);
+        tab.next_line();
+        assert_eq!((tab.current_hunk, tab.current_line), (1, None));
+        tab.next_line();
+        assert_eq!((tab.current_hunk, tab.current_line), (2, Some(0)));
+        tab.prev_line();
+        tab.prev_line();
+        assert_eq!((tab.current_hunk, tab.current_line), (0, Some(2)));
+
+        // The hunk above grows: the fold stays with its hunk
+        let shifted = DIFF
+            .replace(
+                "@@ -1,2 +1,3 @@\n a\n+b\n c\n",
+                "@@ -1,2 +1,4 @@\n a\n+b\n+b2\n c\n",
+            )
+            .replace("@@ -20,2 +21,4 @@", "@@ -20,2 +22,4 @@");
+        let folds = tab.folded_hunks.clone();
+        let mut tab = TabState::new_for_test(git::parse_diff(&shifted));
+        tab.folded_hunks = folds;
+        assert_eq!(tab.selected_file_folds(), vec![false, true, false]);
+
+        tab.current_hunk = 1;
+        assert_eq!(tab.toggle_hunk_fold(), Some(false));
+        assert!(tab.folded_hunks.is_empty());
+    }
+}
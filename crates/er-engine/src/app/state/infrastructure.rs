@@ -0,0 +1,260 @@
+//! Resource-level summaries of the Terraform and Kubernetes files a diff
+//! changes (see [`crate::analysis::infra`]).
+//!
+//! Read like the contract diffs: each changed `.tf`, `.tfvars` or manifest
+//! is read at the diff's new side on a worker thread and its old side
+//! rebuilt from the hunks. The File Detail panel lists the resources
+//! created, modified and destroyed, and each dangerous field becomes a
+//! finding in the "infrastructure" category, anchored on the first added
+//! line naming it. `[commands] plan` runs a real plan from the Verify hub.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, TabState};
+use crate::ai::{Confidence, Finding, RiskLevel};
+use crate::analysis::infra::{self, Danger, InfraKind, InfraSummary};
+use crate::git::{self, DiffHunk, LineType};
+
+/// The category the findings are listed under.
+pub const INFRA_CATEGORY: &str = "infrastructure";
+
+/// Files summarized per scan.
+const MAX_FILES: usize = 100;
+
+/// One changed IaC file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfraDiff {
+    pub kind: InfraKind,
+    pub summary: InfraSummary,
+    /// Why a side couldn't be read, when it couldn't
+    pub error: Option<String>,
+}
+
+/// Summaries by path, and the findings for their dangers.
+type ScanResult = (HashMap<String, InfraDiff>, Vec<(String, Finding)>);
+
+/// The IaC summaries of a tab's diff.
+#[derive(Debug, Default)]
+pub struct InfraScan {
+    /// The diff the results are for
+    pub diff_hash: String,
+    /// By path
+    pub files: HashMap<String, InfraDiff>,
+    /// `(path, finding)`
+    pub findings: Vec<(String, Finding)>,
+    rx: Option<Receiver<ScanResult>>,
+}
+
+/// The summary of `path`, whose new side is `new` (empty when deleted);
+/// `None` when it isn't an IaC file.
+fn infra_for(path: &str, new: &str, hunks: &[DiffHunk]) -> Option<InfraDiff> {
+    let old = git::old_side(new, hunks);
+    let sample = if new.is_empty() { &old } else { new };
+    let kind = infra::infra_kind(path, sample)?;
+    Some(match infra::summarize(kind, &old, new) {
+        Ok(summary) => InfraDiff {
+            kind,
+            summary,
+            error: None,
+        },
+        Err(e) => InfraDiff {
+            kind,
+            summary: InfraSummary::default(),
+            error: Some(e),
+        },
+    })
+}
+
+/// The finding for `danger`: on the first added line naming its field,
+/// file-level when none does.
+fn danger_finding(path: &str, danger: &Danger, hunks: &[DiffHunk]) -> (String, Finding) {
+    let key = danger
+        .field
+        .rsplit('.')
+        .next()
+        .unwrap_or(&danger.field)
+        .split(['[', '#'])
+        .next()
+        .unwrap_or_default();
+    let anchor = hunks.iter().enumerate().find_map(|(hi, h)| {
+        h.lines
+            .iter()
+            .filter(|l| l.line_type == LineType::Add && l.content.contains(key))
+            .find_map(|l| Some((hi, l.new_num?)))
+    });
+    let finding = Finding {
+        id: format!(
+            "infrastructure-{}:{}:{}",
+            path, danger.address, danger.field
+        ),
+        severity: if danger.security {
+            RiskLevel::High
+        } else {
+            RiskLevel::Medium
+        },
+        category: INFRA_CATEGORY.to_string(),
+        title: format!("{}: {}", danger.address, danger.reason),
+        description: format!(
+            "`{}` is `{}` on {}.",
+            danger.field, danger.value, danger.address
+        ),
+        hunk_index: anchor.map(|(hi, _)| hi),
+        line_start: anchor.map(|(_, line)| line),
+        line_end: None,
+        suggestion: if danger.security {
+            "Narrow it to what needs it, or note why it has to be this open".to_string()
+        } else {
+            "Keep the protection on until the data is backed up or no longer needed".to_string()
+        },
+        related_files: vec![],
+        outside_diff: false,
+        confidence: Confidence::Informational,
+        verification_plan: String::new(),
+        evidence: vec![],
+        responses: vec![],
+        resolved: false,
+        resolved_note: String::new(),
+        resolved_at: String::new(),
+        promoted_to: None,
+    };
+    (path.to_string(), finding)
+}
+
+impl TabState {
+    /// Put this diff's infrastructure findings back into a freshly loaded
+    /// review.
+    pub(super) fn merge_infra_findings(&mut self) {
+        if self.infrastructure.diff_hash != self.diff_hash {
+            return;
+        }
+        self.ai.merge_builtin_findings(
+            INFRA_CATEGORY,
+            &self.infrastructure.findings,
+            &self.branch_diff_hash,
+        );
+    }
+
+    /// The IaC summary of `path`, for the File Detail panel.
+    pub fn infra_changes(&self, path: &str) -> Option<&InfraDiff> {
+        if self.infrastructure.diff_hash != self.diff_hash {
+            return None;
+        }
+        self.infrastructure.files.get(path)
+    }
+}
+
+impl App {
+    /// Summarize the active tab's changed IaC files when its diff changed,
+    /// and merge the findings once done. True when they arrived.
+    pub fn poll_infrastructure(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.infrastructure.rx {
+            return match rx.try_recv() {
+                Ok(_) if tab.infrastructure.diff_hash != tab.diff_hash => {
+                    tab.infrastructure.rx = None;
+                    false
+                }
+                Ok((files, findings)) => {
+                    tab.infrastructure.rx = None;
+                    tab.infrastructure.files = files;
+                    tab.infrastructure.findings = findings;
+                    tab.ai.merge_builtin_findings(
+                        INFRA_CATEGORY,
+                        &tab.infrastructure.findings,
+                        &tab.branch_diff_hash,
+                    );
+                    true
+                }
+                Err(TryRecvError::Empty) => false,
+                Err(TryRecvError::Disconnected) => {
+                    tab.infrastructure.rx = None;
+                    false
+                }
+            };
+        }
+        if matches!(tab.mode, DiffMode::History | DiffMode::Tour)
+            || tab.infrastructure.diff_hash == tab.diff_hash
+        {
+            return false;
+        }
+        tab.infrastructure.diff_hash = tab.diff_hash.clone();
+        let had_results =
+            !tab.infrastructure.files.is_empty() || !tab.infrastructure.findings.is_empty();
+        tab.infrastructure.files.clear();
+        if !tab.infrastructure.findings.is_empty() {
+            tab.infrastructure.findings.clear();
+            tab.ai
+                .merge_builtin_findings(INFRA_CATEGORY, &[], &tab.branch_diff_hash);
+        }
+        let Some(side) = tab.new_side() else {
+            return had_results;
+        };
+        let parsed = tab.parsed_stubs();
+        let files: Vec<(String, Vec<DiffHunk>)> = tab
+            .all_hunks(&parsed)
+            .into_iter()
+            .filter(|(path, hunks)| {
+                !hunks.is_empty()
+                    && [".tf", ".tfvars", ".yaml", ".yml"]
+                        .iter()
+                        .any(|ext| path.to_ascii_lowercase().ends_with(ext))
+            })
+            .take(MAX_FILES)
+            .map(|(path, hunks)| (path.to_string(), hunks.to_vec()))
+            .collect();
+        if files.is_empty() {
+            return had_results;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut found = HashMap::new();
+            let mut findings = Vec::new();
+            for (path, hunks) in files {
+                let new = side.read(&path).unwrap_or_default();
+                let Some(diff) = infra_for(&path, &new, &hunks) else {
+                    continue;
+                };
+                findings.extend(
+                    diff.summary
+                        .dangers
+                        .iter()
+                        .map(|d| danger_finding(&path, d, &hunks)),
+                );
+                found.insert(path, diff);
+            }
+            let _ = tx.send((found, findings));
+        });
+        tab.infrastructure.rx = Some(rx);
+        had_results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn dangers_become_findings_on_the_line_naming_them() {
+        let new = "resource \"aws_db_instance\" \"main\" {\n  engine = \"postgres\"\n  skip_final_snapshot = true\n}\n";
+        let diff = "diff --git a/infra/db.tf b/infra/db.tf\n--- a/infra/db.tf\n+++ b/infra/db.tf\n\
+                    @@ -1,3 +1,4 @@\n resource \"aws_db_instance\" \"main\" {\n   engine = \"postgres\"\n\
+                    +  skip_final_snapshot = true\n }\n";
+        let files = parse_diff(diff);
+        let found = infra_for("infra/db.tf", new, &files[0].hunks).unwrap();
+        assert_eq!(found.kind, InfraKind::Terraform);
+        assert_eq!(found.summary.changes.len(), 1);
+        assert_eq!(found.summary.dangers.len(), 1);
+
+        let (path, finding) =
+            danger_finding("infra/db.tf", &found.summary.dangers[0], &files[0].hunks);
+        assert_eq!(path, "infra/db.tf");
+        assert_eq!(
+            finding.title,
+            "aws_db_instance.main: no final snapshot when it is destroyed"
+        );
+        assert_eq!(finding.severity, RiskLevel::Medium);
+        assert_eq!((finding.hunk_index, finding.line_start), (Some(0), Some(3)));
+    }
+}
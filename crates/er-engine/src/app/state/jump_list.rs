@@ -0,0 +1,204 @@
+//! Jump list: back/forward through the places a jump left (Ctrl+o /
+//! Ctrl+i, like vim).
+//!
+//! Jumps — J/K to a comment, a finding or file from the AI panel, a search,
+//! a TODO or test-pair jump, anything through `focus_file_line` — record
+//! where the cursor was first. Moving with j/k or n/N doesn't, so the list
+//! holds the places you chased a reference from. Entries are indices into
+//! the diff and are clamped when it changed since.
+
+use super::TabState;
+
+/// Most locations kept; the oldest go first.
+const JUMP_LIST_MAX: usize = 100;
+
+/// A cursor position in the diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpLocation {
+    pub path: String,
+    pub hunk: usize,
+    /// Line index in the hunk, `None` on the hunk header
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JumpList {
+    entries: Vec<JumpLocation>,
+    /// Where back/forward stand; `entries.len()` when at the newest place
+    pos: usize,
+}
+
+impl JumpList {
+    /// Record `from` as the place a jump left. Forward history is dropped,
+    /// and an older entry for the same place moves to the end.
+    pub fn push(&mut self, from: JumpLocation) {
+        self.entries.truncate(self.pos);
+        self.entries.retain(|e| *e != from);
+        self.entries.push(from);
+        if self.entries.len() > JUMP_LIST_MAX {
+            self.entries.remove(0);
+        }
+        self.pos = self.entries.len();
+    }
+
+    /// The place before `current`, skipping entries that are `current`.
+    pub fn back(&mut self, current: &JumpLocation) -> Option<JumpLocation> {
+        let pushed = self.pos == self.entries.len();
+        if pushed {
+            // Remember the newest place so forward can return to it
+            self.entries.push(current.clone());
+        }
+        let start = self.pos;
+        while self.pos > 0 {
+            self.pos -= 1;
+            if self.entries[self.pos] != *current {
+                return Some(self.entries[self.pos].clone());
+            }
+        }
+        self.pos = start;
+        if pushed {
+            self.entries.pop();
+        }
+        None
+    }
+
+    /// The place after `current`, when back was used.
+    pub fn forward(&mut self, current: &JumpLocation) -> Option<JumpLocation> {
+        let start = self.pos;
+        while self.pos + 1 < self.entries.len() {
+            self.pos += 1;
+            if self.entries[self.pos] != *current {
+                return Some(self.entries[self.pos].clone());
+            }
+        }
+        self.pos = start;
+        None
+    }
+}
+
+impl TabState {
+    /// The cursor position, `None` with no file selected.
+    fn jump_location(&self) -> Option<JumpLocation> {
+        let file = self.selected_diff_file()?;
+        Some(JumpLocation {
+            path: file.path.clone(),
+            hunk: self.current_hunk,
+            line: self.current_line,
+        })
+    }
+
+    /// Call before a jump: remember where the cursor is.
+    pub fn record_jump(&mut self) {
+        if let Some(here) = self.jump_location() {
+            self.jump_list.push(here);
+        }
+    }
+
+    /// Ctrl+o: back to where the last jump came from. False when there is
+    /// nowhere to go.
+    pub fn jump_back(&mut self) -> bool {
+        let Some(here) = self.jump_location() else {
+            return false;
+        };
+        match self.jump_list.back(&here) {
+            Some(to) => self.go_to_jump(&to),
+            None => false,
+        }
+    }
+
+    /// Ctrl+i: forward again after [`TabState::jump_back`].
+    pub fn jump_forward(&mut self) -> bool {
+        let Some(here) = self.jump_location() else {
+            return false;
+        };
+        match self.jump_list.forward(&here) {
+            Some(to) => self.go_to_jump(&to),
+            None => false,
+        }
+    }
+
+    /// Put the cursor at `to` without recording a jump. False when its file
+    /// left the diff.
+    fn go_to_jump(&mut self, to: &JumpLocation) -> bool {
+        let Some(idx) = self.files.iter().position(|f| f.path == to.path) else {
+            return false;
+        };
+        self.focused_comment_id = None;
+        self.focused_finding_id = None;
+        self.selected_watched = None;
+        self.selection_anchor = None;
+        self.selected_file = idx;
+        self.h_scroll = 0;
+        self.ensure_file_parsed();
+        self.rebuild_hunk_offsets();
+        let hunks = &self.files[idx].hunks;
+        self.current_hunk = to.hunk.min(hunks.len().saturating_sub(1));
+        self.current_line = hunks
+            .get(self.current_hunk)
+            .and_then(|h| to.line.map(|l| l.min(h.lines.len().saturating_sub(1))));
+        self.scroll_to_current_hunk();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(path: &str, hunk: usize) -> JumpLocation {
+        JumpLocation {
+            path: path.into(),
+            hunk,
+            line: None,
+        }
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_recorded_jumps() {
+        let mut list = JumpList::default();
+        list.push(at("a.rs", 0));
+        list.push(at("b.rs", 2));
+        // Now at c.rs after the second jump
+        let c = at("c.rs", 1);
+        assert_eq!(list.back(&c), Some(at("b.rs", 2)));
+        assert_eq!(list.back(&at("b.rs", 2)), Some(at("a.rs", 0)));
+        assert_eq!(list.back(&at("a.rs", 0)), None);
+        assert_eq!(list.forward(&at("a.rs", 0)), Some(at("b.rs", 2)));
+        assert_eq!(list.forward(&at("b.rs", 2)), Some(c.clone()));
+        assert_eq!(list.forward(&c), None);
+
+        // A new jump from the middle drops the forward history
+        list.back(&c);
+        list.push(at("b.rs", 2));
+        assert_eq!(list.forward(&at("d.rs", 0)), None);
+        assert_eq!(list.back(&at("d.rs", 0)), Some(at("b.rs", 2)));
+    }
+
+    #[test]
+    fn revisiting_a_place_keeps_one_entry() {
+        let mut list = JumpList::default();
+        list.push(at("a.rs", 0));
+        list.push(at("b.rs", 0));
+        list.push(at("a.rs", 0));
+        let here = at("c.rs", 0);
+        assert_eq!(list.back(&here), Some(at("a.rs", 0)));
+        assert_eq!(list.back(&at("a.rs", 0)), Some(at("b.rs", 0)));
+        assert_eq!(list.back(&at("b.rs", 0)), None);
+    }
+
+    #[test]
+    fn focus_jumps_can_be_undone() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-x\n+y\n\
+                    diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n";
+        let mut tab = TabState::new_for_test(crate::git::parse_diff(diff));
+        tab.current_line = Some(1);
+        assert!(tab.focus_file_line("b.rs", Some(1)));
+        assert_eq!(tab.selected_file, 1);
+
+        assert!(tab.jump_back());
+        assert_eq!((tab.selected_file, tab.current_line), (0, Some(1)));
+        assert!(!tab.jump_back());
+        assert!(tab.jump_forward());
+        assert_eq!(tab.selected_file, 1);
+    }
+}
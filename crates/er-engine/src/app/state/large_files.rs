@@ -0,0 +1,63 @@
+//! Files too large to load eagerly (`[large_files] max_file_kb`): after a
+//! refresh they start compacted, so the diff view shows their size and Enter
+//! loads them anyway. Expanding one keeps it expanded across refreshes.
+
+use super::{DiffMode, TabState};
+use crate::git;
+
+impl TabState {
+    /// Compact working-tree files over the size limit, remembering their size.
+    pub(super) fn compact_large_files(&mut self) {
+        self.large_files.clear();
+        if self.is_remote() || self.mode == DiffMode::History {
+            return;
+        }
+        let max_bytes = self.compaction_config.max_file_bytes;
+        for (path, size) in git::oversized_files(&self.repo_root, &self.files, max_bytes) {
+            if self.user_expanded.contains(&path)
+                || self.compaction_config.always_expand.contains(&path)
+            {
+                continue;
+            }
+            if let Some(file) = self.files.iter_mut().find(|f| f.path == path) {
+                file.compacted = true;
+                file.raw_hunk_count = file.hunks.len();
+                file.hunks = Vec::new();
+            }
+            self.large_files.insert(path, size);
+        }
+    }
+
+    /// Size of `path` when it was compacted for being too large.
+    pub fn large_file_size(&self, path: &str) -> Option<u64> {
+        self.large_files.get(path).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_files_start_compacted_unless_expanded() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("big.json"), vec![b'x'; 8192]).unwrap();
+        let diff = "diff --git a/big.json b/big.json\n--- a/big.json\n+++ b/big.json\n\
+                    @@ -1 +1 @@\n-a\n+b\n";
+        let mut tab = TabState::new_for_test(git::parse_diff(diff));
+        tab.repo_root = dir.path().display().to_string();
+        tab.compaction_config.max_file_bytes = 4096;
+
+        tab.compact_large_files();
+        assert!(tab.files[0].compacted);
+        assert_eq!(tab.files[0].raw_hunk_count, 1);
+        assert_eq!(tab.large_file_size("big.json"), Some(8192));
+
+        let mut tab = TabState::new_for_test(git::parse_diff(diff));
+        tab.repo_root = dir.path().display().to_string();
+        tab.compaction_config.max_file_bytes = 4096;
+        tab.user_expanded.insert("big.json".into());
+        tab.compact_large_files();
+        assert!(!tab.files[0].compacted);
+    }
+}
@@ -0,0 +1,209 @@
+//! Staging, unstaging and discarding the lines selected in the diff.
+//!
+//! A shift-selection narrows `s` from the whole file to those lines. In
+//! split view the focused pane decides which of them count: selecting on
+//! the New side stages exactly the additions there, selecting on the Old
+//! side and discarding restores exactly those deletions. The patch is cut
+//! from the current hunk by [`git::select_lines`] and applied with
+//! `git apply`, to the index or to the working tree.
+
+use anyhow::Result;
+
+use super::{App, ConfirmAction, DiffMode, InputMode, SplitSide};
+use crate::git::{self, LineType};
+
+/// A patch for the selected lines of one file.
+struct LinePatch {
+    path: String,
+    patch: String,
+    lines: usize,
+}
+
+impl App {
+    /// The lines `Add`/`Delete` the selection takes: the focused pane's in
+    /// split view, both in unified.
+    fn selection_side(&self) -> Option<LineType> {
+        if !self.split_diff_active(&self.config) {
+            return None;
+        }
+        Some(match self.tab().split_focus {
+            SplitSide::Old => LineType::Delete,
+            SplitSide::New => LineType::Add,
+        })
+    }
+
+    /// The patch for the selection in the current hunk; `reverse` builds the
+    /// one that applies to the new side. `Ok(None)` without a selection or
+    /// when it has no changed lines of the focused side.
+    fn selection_patch(&self, reverse: bool) -> Result<Option<LinePatch>> {
+        let side = self.selection_side();
+        let tab = self.tab();
+        let (Some(range), Some(file)) = (tab.selected_range(), tab.selected_diff_file()) else {
+            return Ok(None);
+        };
+        let Some(hunk) = file.hunks.get(tab.current_hunk) else {
+            return Ok(None);
+        };
+        let Some(part) = git::select_lines(hunk, range.clone(), side, reverse) else {
+            return Ok(None);
+        };
+        let lines = hunk
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(i, l)| {
+                range.contains(i)
+                    && matches!(l.line_type, LineType::Add | LineType::Delete)
+                    && side.is_none_or(|s| s == l.line_type)
+            })
+            .count();
+        Ok(Some(LinePatch {
+            path: file.path.clone(),
+            patch: git::file_patch(file, &[&part], reverse, None)?,
+            lines,
+        }))
+    }
+
+    fn no_lines_selected(&mut self) {
+        let side = match self.selection_side() {
+            Some(LineType::Add) => " on the new side",
+            Some(_) => " on the old side",
+            None => "",
+        };
+        self.notify_warn(&format!("No changed lines selected{}", side));
+    }
+
+    /// `s` with a line selection: stage the selected lines in the unstaged
+    /// view, unstage them in the staged view.
+    pub fn toggle_stage_lines(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("staging") {
+            return Ok(());
+        }
+        let mode = self.tab().mode;
+        let (reverse, done) = match mode {
+            DiffMode::Unstaged => (false, "Staged"),
+            DiffMode::Staged => (true, "Unstaged"),
+            _ => {
+                self.notify("Line staging works in the unstaged and staged views");
+                return Ok(());
+            }
+        };
+        let lp = match self.selection_patch(reverse) {
+            Ok(Some(lp)) => lp,
+            Ok(None) => {
+                self.no_lines_selected();
+                return Ok(());
+            }
+            Err(e) => {
+                self.notify_warn(&e.to_string());
+                return Ok(());
+            }
+        };
+        let repo_root = self.tab().repo_root.clone();
+        if let Err(e) = git::stage_patch(&repo_root, &lp.patch) {
+            self.report_command_error(&format!("Staging lines of {} failed", lp.path), &e);
+            return Ok(());
+        }
+        self.notify(&format!(
+            "{} {} line{} of {}",
+            done,
+            lp.lines,
+            if lp.lines == 1 { "" } else { "s" },
+            lp.path
+        ));
+        let tab = self.tab_mut();
+        if mode == DiffMode::Unstaged {
+            tab.staged_in_app = true;
+        }
+        tab.selection_anchor = None;
+        tab.refresh_diff()
+    }
+
+    /// `x` with a line selection in the unstaged view: ask before restoring
+    /// the selected lines in the working tree.
+    pub fn request_discard_lines(&mut self) {
+        if self.blocked_by_read_only("discarding") {
+            return;
+        }
+        if self.tab().mode != DiffMode::Unstaged {
+            self.notify("Discard lines in the unstaged view");
+            return;
+        }
+        match self.selection_patch(true) {
+            Ok(Some(lp)) => self.confirm(ConfirmAction::DiscardLines {
+                path: lp.path,
+                lines: lp.lines,
+                patch: lp.patch,
+            }),
+            Ok(None) => self.no_lines_selected(),
+            Err(e) => self.notify_warn(&e.to_string()),
+        }
+    }
+
+    /// `y` on the discard prompt.
+    pub fn discard_lines(&mut self, path: &str, patch: &str) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let repo_root = self.tab().repo_root.clone();
+        if let Err(e) = git::apply_worktree_patch(&repo_root, patch) {
+            self.report_command_error(&format!("Discarding lines of {} failed", path), &e);
+            return Ok(());
+        }
+        self.notify(&format!("Discarded lines of {}", path));
+        let tab = self.tab_mut();
+        tab.selection_anchor = None;
+        tab.refresh_diff()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    fn app_with_selection(anchor: usize, line: usize) -> App {
+        let diff =
+            "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n+C\n d\n";
+        let mut app = App::new_for_test(parse_diff(diff));
+        let tab = app.tab_mut();
+        tab.mode = DiffMode::Unstaged;
+        tab.selection_anchor = Some(anchor);
+        tab.current_line = Some(line);
+        app
+    }
+
+    #[test]
+    fn split_focus_picks_the_side_of_the_selection() {
+        let mut app = app_with_selection(1, 4);
+        app.config.display.split_diff = true;
+        app.tab_mut().split_focus = SplitSide::New;
+        let lp = app.selection_patch(false).unwrap().unwrap();
+        assert_eq!(lp.lines, 2);
+        assert!(lp.patch.contains(" b\n c\n+B\n+C\n"));
+
+        app.tab_mut().split_focus = SplitSide::Old;
+        let lp = app.selection_patch(true).unwrap().unwrap();
+        assert_eq!(lp.lines, 2);
+        assert!(lp.patch.contains("+b\n+c\n B\n C\n"));
+
+        // Only additions selected: nothing for the Old pane
+        app.tab_mut().selection_anchor = Some(3);
+        assert!(app.selection_patch(true).unwrap().is_none());
+    }
+
+    #[test]
+    fn discard_asks_first_and_only_in_the_unstaged_view() {
+        let mut app = app_with_selection(2, 3);
+        app.request_discard_lines();
+        match app.input_mode {
+            InputMode::Confirm(ConfirmAction::DiscardLines { lines, .. }) => {
+                assert_eq!(lines, 2)
+            }
+            ref other => panic!("expected the discard prompt, got {:?}", other),
+        }
+
+        let mut app = app_with_selection(2, 3);
+        app.tab_mut().mode = DiffMode::Staged;
+        app.request_discard_lines();
+        assert!(!matches!(app.input_mode, InputMode::Confirm(_)));
+    }
+}
@@ -0,0 +1,382 @@
+//! Lost comments: questions, notes and comments whose line relocation could
+//! no longer find (`anchor_status == "lost"`), listed in one hub so they get
+//! dealt with instead of rotting in the JSON. From the list:
+//!
+//! - Enter jumps to the comment's file and starts re-anchoring: move the
+//!   cursor to the line it belongs on and press Enter to bind it there
+//!   (Esc cancels).
+//! - `d` deletes it, after the usual prompt.
+//! - `f` keeps it on the file as a whole, with no line to lose.
+//!
+//! Comments already posted to GitHub can't be re-anchored from here, since
+//! GitHub doesn't move them; teammates' shared questions are read-only.
+
+use anyhow::Result;
+
+use super::{App, ConfirmAction, HubAction, HubItem, HubKind, LineAnchor, OverlayData, TabState};
+use crate::ai::{self, CommentRef, CommentType};
+
+/// What to do with a lost comment's anchor.
+enum Fix {
+    /// Bind to line `anchor` of hunk `hunk_index` in `file`
+    Rebind {
+        file: String,
+        hunk_index: usize,
+        anchor: LineAnchor,
+    },
+    FileLevel,
+}
+
+impl TabState {
+    /// Top-level lost comments, questions first, in stored order.
+    pub fn lost_comments(&self) -> Vec<CommentRef<'_>> {
+        let mut lost = Vec::new();
+        if let Some(qs) = &self.ai.questions {
+            lost.extend(
+                qs.questions
+                    .iter()
+                    .filter(|q| q.anchor_status == "lost" && q.in_reply_to.is_none())
+                    .map(CommentRef::Question),
+            );
+        }
+        if let Some(ns) = &self.ai.notes {
+            lost.extend(
+                ns.notes
+                    .iter()
+                    .filter(|n| n.anchor_status == "lost" && n.in_reply_to.is_none())
+                    .map(CommentRef::Note),
+            );
+        }
+        if let Some(gc) = &self.ai.github_comments {
+            lost.extend(
+                gc.comments
+                    .iter()
+                    .filter(|c| c.anchor_status == "lost" && c.in_reply_to.is_none())
+                    .map(CommentRef::GitHubComment),
+            );
+        }
+        lost
+    }
+}
+
+impl App {
+    /// Open the lost comments list.
+    pub fn open_lost_comments_hub(&mut self) {
+        let tab = self.tab();
+        let lost = tab.lost_comments();
+        if lost.is_empty() {
+            self.notify("No lost comments");
+            return;
+        }
+        let items = lost
+            .iter()
+            .map(|c| {
+                let kind = match c.comment_type() {
+                    CommentType::Question => "question",
+                    CommentType::Note => "note",
+                    CommentType::GitHubComment => "comment",
+                };
+                let at = match c.line_start() {
+                    Some(line) => format!("{}:{}", c.file(), line),
+                    None => c.file().to_string(),
+                };
+                let snippet = c.line_content().trim();
+                HubItem {
+                    label: format!("{} · {}", at, c.text().lines().next().unwrap_or("")),
+                    hint: kind.into(),
+                    description: if snippet.is_empty() {
+                        "was on a hunk that's gone".into()
+                    } else {
+                        format!("was on: {}", snippet)
+                    },
+                    action: HubAction::ReanchorComment(c.id().to_string()),
+                    is_header: false,
+                    enabled: true,
+                }
+            })
+            .collect();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::LostComments,
+            title: Some(format!(
+                "LOST COMMENTS ({}) · ⏎ re-anchor · d delete · f file-level",
+                lost.len()
+            )),
+            items,
+            selected: 0,
+        });
+    }
+
+    /// Id of the comment selected in the lost comments hub.
+    pub fn selected_lost_comment(&self) -> Option<String> {
+        match &self.overlay {
+            Some(OverlayData::ModalHub {
+                kind: HubKind::LostComments,
+                items,
+                selected,
+                ..
+            }) => match items.get(*selected).map(|i| &i.action) {
+                Some(HubAction::ReanchorComment(id)) => Some(id.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Jump to comment `id`'s file and wait for the line to bind it to.
+    pub fn start_reanchor(&mut self, id: &str) {
+        let Some(file) = self.fixable_comment(id) else {
+            return;
+        };
+        self.overlay = None;
+        let tab = self.tab_mut();
+        if let Some(pos) = tab.files.iter().position(|f| f.path == file) {
+            tab.selected_file = pos;
+            tab.current_hunk = 0;
+            tab.current_line = None;
+        }
+        tab.reanchoring = Some(id.to_string());
+        self.notify_long("Move to the line this comment belongs on and press Enter — Esc cancels");
+    }
+
+    /// Bind the comment being re-anchored to the cursor line.
+    pub fn finish_reanchor(&mut self) -> Result<()> {
+        let Some(id) = self.tab_mut().reanchoring.take() else {
+            return Ok(());
+        };
+        let tab = self.tab();
+        let Some(df) = tab.selected_diff_file() else {
+            self.notify_warn("No file selected — the comment stays lost");
+            return Ok(());
+        };
+        let Some(hunk) = df.hunks.get(tab.current_hunk) else {
+            self.notify_warn("No hunk here — the comment stays lost");
+            return Ok(());
+        };
+        let line = tab.current_line_number();
+        let fix = Fix::Rebind {
+            file: df.path.clone(),
+            hunk_index: tab.current_hunk,
+            anchor: LineAnchor::capture(hunk, line, None),
+        };
+        let at = match line {
+            Some(line) => format!("{}:{}", df.path, line),
+            None => format!("the hunk in {}", df.path),
+        };
+        if self.fix_lost_comment(&id, fix)? {
+            self.notify(&format!("Comment re-anchored to {}", at));
+        }
+        Ok(())
+    }
+
+    pub fn cancel_reanchor(&mut self) {
+        if self.tab_mut().reanchoring.take().is_some() {
+            self.notify("Re-anchoring cancelled");
+        }
+    }
+
+    /// `d` in the lost comments hub.
+    pub fn request_delete_lost_comment(&mut self) {
+        let Some(id) = self.selected_lost_comment() else {
+            return;
+        };
+        let deletable = self
+            .tab()
+            .ai
+            .find_comment(&id)
+            .is_some_and(|c| c.can_delete());
+        if !deletable {
+            self.notify_warn("Only your own comments can be deleted");
+            return;
+        }
+        self.overlay = None;
+        self.confirm(ConfirmAction::DeleteComment { comment_id: id });
+    }
+
+    /// `f` in the lost comments hub.
+    pub fn lost_comment_to_file_level(&mut self) -> Result<()> {
+        let Some(id) = self.selected_lost_comment() else {
+            return Ok(());
+        };
+        if self.fixable_comment(&id).is_none() {
+            return Ok(());
+        }
+        if self.fix_lost_comment(&id, Fix::FileLevel)? {
+            self.notify("Comment kept on the file as a whole");
+            if self.tab().lost_comments().is_empty() {
+                self.overlay = None;
+            } else {
+                self.open_lost_comments_hub();
+            }
+        }
+        Ok(())
+    }
+
+    /// The file of comment `id` when its anchor may be changed here; says
+    /// why not otherwise.
+    fn fixable_comment(&mut self, id: &str) -> Option<String> {
+        let tab = self.tab();
+        let comment = tab.ai.find_comment(id)?;
+        let reason = match comment {
+            CommentRef::GitHubComment(c) if c.github_id.is_some() => {
+                Some("It's on GitHub already, which can't move it")
+            }
+            CommentRef::Legacy(_) => Some("Legacy comments can't be re-anchored"),
+            _ if comment.teammate().is_some() => Some("Teammates' questions are read-only"),
+            _ => None,
+        };
+        let file = comment.file().to_string();
+        match reason {
+            Some(reason) => {
+                self.notify_warn(reason);
+                None
+            }
+            None => Some(file),
+        }
+    }
+
+    /// Apply `fix` to comment `id` in its store, re-read under the lock.
+    /// Replies follow to a new file. Returns whether the comment was found.
+    fn fix_lost_comment(&mut self, id: &str, fix: Fix) -> Result<bool> {
+        let diff_hash = self.tab().diff_hash.clone();
+        let mut found = false;
+
+        // ReviewQuestion and GitHubReviewComment share these field names
+        macro_rules! apply {
+            ($list:expr) => {{
+                for c in $list.iter_mut() {
+                    if c.id == id {
+                        found = true;
+                        match &fix {
+                            Fix::Rebind {
+                                file,
+                                hunk_index,
+                                anchor,
+                            } => {
+                                c.file = file.clone();
+                                c.hunk_index = Some(*hunk_index);
+                                c.line_start = anchor.line_start;
+                                c.line_content = anchor.line_content.clone();
+                                c.context_before = anchor.context_before.clone();
+                                c.context_after = anchor.context_after.clone();
+                                c.old_line_start = anchor.old_line_start;
+                                c.hunk_header = anchor.hunk_header.clone();
+                            }
+                            Fix::FileLevel => {
+                                c.hunk_index = None;
+                                c.line_start = None;
+                                c.line_content = String::new();
+                                c.context_before = Vec::new();
+                                c.context_after = Vec::new();
+                                c.old_line_start = None;
+                                c.hunk_header = String::new();
+                            }
+                        }
+                        c.line_end = None;
+                        c.anchor_status = "original".to_string();
+                        c.anchor_confidence = None;
+                        c.relocated_at_hash = diff_hash.clone();
+                        c.stale = false;
+                    } else if c.in_reply_to.as_deref() == Some(id) {
+                        if let Fix::Rebind { file, .. } = &fix {
+                            c.file = file.clone();
+                        }
+                    }
+                }
+                Ok(())
+            }};
+        }
+
+        let er_dir = self.tab().er_dir();
+        if id.starts_with("q-") {
+            ai::store::modify(&er_dir, |qs: &mut ai::ErQuestions| apply!(qs.questions))?;
+        } else if id.starts_with("n-") {
+            ai::store::modify(&er_dir, |ns: &mut ai::ErNotes| apply!(ns.notes))?;
+        } else {
+            let dir = self.tab().github_comments_dir();
+            ai::store::modify(&dir, |gc: &mut ai::ErGitHubComments| apply!(gc.comments))?;
+        }
+        if self.tab().is_remote() {
+            self.tab_mut().reload_remote_comments();
+        } else {
+            self.tab_mut().reload_ai_state();
+        }
+        if !found {
+            self.notify_warn("That comment is gone");
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures;
+    use crate::paths::ErRoot;
+
+    fn lost_question(id: &str, file: &str) -> ai::ReviewQuestion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "file": file,
+            "hunk_index": 3,
+            "line_start": 40,
+            "line_content": "    let removed = true;",
+            "text": "Is this still needed?",
+            "anchor_status": "lost",
+            "stale": true,
+        }))
+        .unwrap()
+    }
+
+    fn app_with_lost(dir: &std::path::Path) -> App {
+        let mut app = fixtures::app(fixtures::small_diff());
+        app.tab_mut().er_root = ErRoot::RepoLocal(dir.display().to_string());
+        let qs = ai::ErQuestions {
+            version: 1,
+            diff_hash: String::new(),
+            questions: vec![
+                lost_question("q-1", "src/lib.rs"),
+                lost_question("q-2", "src/gone.rs"),
+            ],
+        };
+        ai::store::save(&app.tab().er_dir(), &qs).unwrap();
+        app.tab_mut().reload_ai_state();
+        app
+    }
+
+    #[test]
+    fn hub_lists_lost_comments_and_rebinds_to_the_cursor_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with_lost(dir.path());
+        app.open_lost_comments_hub();
+        assert_eq!(app.selected_lost_comment().as_deref(), Some("q-1"));
+
+        app.start_reanchor("q-1");
+        assert!(app.overlay.is_none());
+        assert_eq!(app.tab().reanchoring.as_deref(), Some("q-1"));
+        app.tab_mut().current_line = Some(2);
+        let line = app.tab().current_line_number();
+        app.finish_reanchor().unwrap();
+
+        let tab = app.tab();
+        let q = tab.ai.find_comment("q-1").unwrap();
+        assert_eq!((q.file(), q.line_start()), ("src/lib.rs", line));
+        assert_eq!(q.anchor_status(), "original");
+        assert!(!q.is_stale());
+        assert_eq!(tab.lost_comments().len(), 1);
+    }
+
+    #[test]
+    fn file_level_conversion_drops_the_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with_lost(dir.path());
+        app.open_lost_comments_hub();
+        app.lost_comment_to_file_level().unwrap();
+
+        let q = app.tab().ai.find_comment("q-1").unwrap();
+        assert_eq!((q.hunk_index(), q.line_start()), (None, None));
+        assert_eq!(q.anchor_status(), "original");
+        // The other one is still listed
+        assert_eq!(app.selected_lost_comment().as_deref(), Some("q-2"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Named marks: `m` + a letter remembers the cursor line, `'` + the letter
+//! jumps back to it (a jump, so Ctrl+o returns). `` ` `` lists them, Enter
+//! jumps and `d` deletes.
+//!
+//! Marks are saved in the session file and come back even when the diff
+//! changed, like drafts. They hold the file, line number and text of the
+//! line rather than indices into the diff, so a mark follows its line when
+//! hunks above it grow or shrink; when the text is gone it lands on the
+//! nearest hunk of the file.
+
+use serde::{Deserialize, Serialize};
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData, TabState};
+
+/// A marked line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mark {
+    pub path: String,
+    /// New-side line number (old-side for a deleted line), `None` for a
+    /// hunk header
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// Hunk index, for header marks and as the fallback
+    #[serde(default)]
+    pub hunk: usize,
+    /// Text of the marked line
+    #[serde(default)]
+    pub content: String,
+}
+
+impl Mark {
+    /// `src/lib.rs:42`
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.path, line),
+            None => format!("{} (hunk {})", self.path, self.hunk + 1),
+        }
+    }
+}
+
+/// Letters a mark can be named by.
+pub fn is_mark_name(c: char) -> bool {
+    c.is_ascii_lowercase()
+}
+
+impl TabState {
+    /// Mark the cursor line as `name`, replacing an older mark of that name.
+    /// `None` with no file selected.
+    pub fn set_mark(&mut self, name: char) -> Option<Mark> {
+        let file = self.selected_diff_file()?;
+        let hunk = file.hunks.get(self.current_hunk);
+        let line = hunk.and_then(|h| self.current_line.and_then(|l| h.lines.get(l)));
+        let mark = Mark {
+            path: file.path.clone(),
+            line: line.and_then(|l| l.new_num.or(l.old_num)),
+            hunk: self.current_hunk,
+            content: line.map(|l| l.content.clone()).unwrap_or_default(),
+        };
+        self.marks.insert(name, mark.clone());
+        Some(mark)
+    }
+
+    /// Jump to mark `name`. False when there is no such mark or its file
+    /// left the diff.
+    pub fn jump_to_mark(&mut self, name: char) -> bool {
+        let Some(mark) = self.marks.get(&name).cloned() else {
+            return false;
+        };
+        let Some(idx) = self.files.iter().position(|f| f.path == mark.path) else {
+            return false;
+        };
+        self.record_jump();
+        self.focused_comment_id = None;
+        self.focused_finding_id = None;
+        self.selected_watched = None;
+        self.selection_anchor = None;
+        self.selected_file = idx;
+        self.h_scroll = 0;
+        self.ensure_file_parsed();
+        self.rebuild_hunk_offsets();
+        let (hunk, line) = mark_position(&self.files[idx].hunks, &mark);
+        self.current_hunk = hunk;
+        self.current_line = line;
+        self.scroll_to_current_hunk();
+        true
+    }
+}
+
+/// Where `mark` is in `hunks` now: its line by text, the nearest such line
+/// when the text repeats, else the saved line number, else its hunk.
+fn mark_position(hunks: &[crate::git::DiffHunk], mark: &Mark) -> (usize, Option<usize>) {
+    let number = |l: &crate::git::DiffLine| l.new_num.or(l.old_num);
+    let lines = || {
+        hunks
+            .iter()
+            .enumerate()
+            .flat_map(|(hi, h)| h.lines.iter().enumerate().map(move |(li, l)| (hi, li, l)))
+    };
+    if let Some(target) = mark.line {
+        let distance =
+            |l: &crate::git::DiffLine| number(l).map_or(usize::MAX, |n| n.abs_diff(target));
+        let by_text = lines()
+            .filter(|(_, _, l)| !mark.content.is_empty() && l.content == mark.content)
+            .min_by_key(|(_, _, l)| distance(l));
+        let by_number = || lines().find(|(_, _, l)| number(l) == Some(target));
+        if let Some((hi, li, _)) = by_text.or_else(by_number) {
+            return (hi, Some(li));
+        }
+    }
+    (mark.hunk.min(hunks.len().saturating_sub(1)), None)
+}
+
+impl App {
+    /// `m` + letter.
+    pub fn set_mark(&mut self, name: char) {
+        match self.tab_mut().set_mark(name) {
+            Some(mark) => self.notify(&format!("Mark '{}' set at {}", name, mark.location())),
+            None => self.notify_warn("No file selected to mark"),
+        }
+    }
+
+    /// `'` + letter.
+    pub fn jump_to_mark(&mut self, name: char) {
+        let Some(mark) = self.tab().marks.get(&name) else {
+            self.notify_warn(&format!("No mark '{}'", name));
+            return;
+        };
+        let path = mark.path.clone();
+        if !self.tab_mut().jump_to_mark(name) {
+            self.notify_warn(&format!("Mark '{}': {} is not in this diff", name, path));
+        }
+    }
+
+    /// Open the marks list (`` ` ``).
+    pub fn open_marks_hub(&mut self) {
+        self.open_marks_hub_at(0);
+    }
+
+    fn open_marks_hub_at(&mut self, selected: usize) {
+        let marks = &self.tab().marks;
+        if marks.is_empty() {
+            self.overlay = None;
+            self.notify("No marks — m + a letter sets one");
+            return;
+        }
+        let in_diff = |m: &Mark| self.tab().files.iter().any(|f| f.path == m.path);
+        let items: Vec<HubItem> = marks
+            .iter()
+            .map(|(&name, mark)| HubItem {
+                label: format!("{}  {}", name, mark.location()),
+                hint: if in_diff(mark) { "" } else { "not in diff" }.into(),
+                description: mark.content.trim().to_string(),
+                action: HubAction::JumpToMark(name),
+                is_header: false,
+                enabled: in_diff(mark),
+            })
+            .collect();
+        let count = items.len();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Marks,
+            title: Some(format!("MARKS ({}) · ⏎ jump · d delete", count)),
+            items,
+            selected: selected.min(count - 1),
+        });
+    }
+
+    /// `d` in the marks hub.
+    pub fn delete_selected_mark(&mut self) {
+        let Some(OverlayData::ModalHub {
+            kind: HubKind::Marks,
+            items,
+            selected,
+            ..
+        }) = &self.overlay
+        else {
+            return;
+        };
+        let selected = *selected;
+        let Some(HubAction::JumpToMark(name)) = items.get(selected).map(|i| i.action.clone())
+        else {
+            return;
+        };
+        self.tab_mut().marks.remove(&name);
+        self.notify(&format!("Mark '{}' deleted", name));
+        self.open_marks_hub_at(selected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    const DIFF: &str = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n\
+                        @@ -1,3 +1,3 @@\n fn a() {\n-    old();\n+    new();\n }\n\
+                        diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n";
+
+    #[test]
+    fn marks_jump_back_and_follow_their_line() {
+        let mut tab = TabState::new_for_test(parse_diff(DIFF));
+        tab.current_line = Some(2);
+        let mark = tab.set_mark('a').unwrap();
+        assert_eq!(mark.location(), "a.rs:2");
+        assert_eq!(mark.content, "    new();");
+
+        tab.selected_file = 1;
+        tab.current_line = None;
+        assert!(tab.jump_to_mark('a'));
+        assert_eq!((tab.selected_file, tab.current_line), (0, Some(2)));
+        assert!(tab.jump_back());
+        assert_eq!(tab.selected_file, 1);
+        assert!(!tab.jump_to_mark('b'));
+
+        // Two lines added above: the mark finds its text again
+        let moved = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n\
+                     @@ -1,3 +1,5 @@\n fn a() {\n+    one();\n+    two();\n-    old();\n+    new();\n }\n";
+        let hunks = &parse_diff(moved)[0].hunks;
+        assert_eq!(mark_position(hunks, &mark), (0, Some(4)));
+    }
+
+    #[test]
+    fn marks_hub_lists_and_deletes() {
+        let mut app = App::new_for_test(parse_diff(DIFF));
+        app.open_marks_hub();
+        assert!(app.overlay.is_none());
+
+        app.tab_mut().current_line = Some(1);
+        app.set_mark('b');
+        app.tab_mut().selected_file = 1;
+        app.set_mark('a');
+        app.open_marks_hub();
+        let Some(OverlayData::ModalHub { items, .. }) = &app.overlay else {
+            panic!("expected the marks hub");
+        };
+        assert_eq!(items.len(), 2);
+        assert!(items[0].label.starts_with("a  b.rs"));
+        assert_eq!(items[1].description, "old();");
+
+        app.delete_selected_mark();
+        assert_eq!(app.tab().marks.keys().collect::<Vec<_>>(), vec![&'b']);
+        app.delete_selected_mark();
+        assert!(app.overlay.is_none());
+    }
+}
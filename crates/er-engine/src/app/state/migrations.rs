@@ -0,0 +1,230 @@
+//! Database migration review (see [`git::check_migrations`]). Each
+//! migration file in the diff gets a finding in the "migration" category
+//! per destructive statement on an added line (DROP, TRUNCATE, a NOT NULL
+//! without a default, …) and one when it has no down migration, shown in
+//! the gutter like any finding. It also gets a checklist item asking for
+//! the rollback to be verified; until those are ticked the review doesn't
+//! read as ready. Like the duplicate findings, both are merged into the
+//! loaded review again after every reload.
+
+use super::TabState;
+use crate::ai::{ChecklistItem, Confidence, ErChecklist, Finding, RiskLevel};
+use crate::config::{self, ErConfig};
+use crate::git::{self, DownMigration, MigrationCheck};
+
+/// The category the findings are listed under.
+pub const MIGRATION_CATEGORY: &str = "migration";
+
+/// Id prefix of the rollback checklist items, followed by the path.
+const ROLLBACK_ITEM: &str = "migration-rollback:";
+
+/// The repo's own `[migrations]` when its `.er-config.toml` has one, else
+/// the global one; no paths when disabled.
+pub(super) fn resolve_paths(repo_root: &str, global: &ErConfig) -> Vec<String> {
+    let migrations =
+        config::load_repo_migrations(repo_root).unwrap_or_else(|| global.migrations.clone());
+    if migrations.enabled {
+        migrations.paths
+    } else {
+        Vec::new()
+    }
+}
+
+fn finding(id: String, severity: RiskLevel) -> Finding {
+    Finding {
+        id,
+        severity,
+        category: MIGRATION_CATEGORY.to_string(),
+        title: String::new(),
+        description: String::new(),
+        hunk_index: None,
+        line_start: None,
+        line_end: None,
+        suggestion: String::new(),
+        related_files: vec![],
+        outside_diff: false,
+        confidence: Confidence::Informational,
+        verification_plan: String::new(),
+        evidence: vec![],
+        responses: vec![],
+        resolved: false,
+        resolved_note: String::new(),
+        resolved_at: String::new(),
+        promoted_to: None,
+    }
+}
+
+/// The findings for `checks`, as `(path, finding)`.
+fn migration_findings(checks: &[MigrationCheck]) -> Vec<(String, Finding)> {
+    let mut findings = Vec::new();
+    for check in checks {
+        for d in &check.destructive {
+            let severity = if d.data_loss {
+                RiskLevel::High
+            } else {
+                RiskLevel::Medium
+            };
+            let mut f = finding(format!("migration-{}:{}", check.path, d.line), severity);
+            f.title = format!("Destructive migration: {}", d.statement);
+            f.description = if d.data_loss {
+                format!(
+                    "`{}` deletes data that a rollback can't bring back.",
+                    d.text
+                )
+            } else {
+                format!(
+                    "`{}` can fail on existing rows or lock the table, and code still running \
+                     against the old schema may break.",
+                    d.text
+                )
+            };
+            f.suggestion = if d.data_loss {
+                "Back up or copy the data first, and deploy the code that stops using it before this"
+                    .to_string()
+            } else {
+                "Split it: add the new shape, backfill, then tighten in a later migration"
+                    .to_string()
+            };
+            f.hunk_index = Some(d.hunk);
+            f.line_start = Some(d.line);
+            findings.push((check.path.clone(), f));
+        }
+        if check.down == DownMigration::Missing {
+            let mut f = finding(format!("migration-down-{}", check.path), RiskLevel::Medium);
+            f.title = "No down migration".to_string();
+            f.description = "There is no down section in the file and no `.down` / `U…__` \
+                             sibling next to it, so this can't be rolled back."
+                .to_string();
+            f.suggestion =
+                "Add the down migration, or note why this one is irreversible".to_string();
+            findings.push((check.path.clone(), f));
+        }
+    }
+    findings
+}
+
+/// Make `checklist` hold one rollback item per migration in `checks`;
+/// unticked items of migrations no longer in the diff are dropped.
+fn sync_rollback_items(checklist: &mut ErChecklist, checks: &[MigrationCheck]) {
+    let wanted: Vec<&MigrationCheck> = checks
+        .iter()
+        .filter(|c| c.down != DownMigration::IsDown)
+        .collect();
+    checklist
+        .items
+        .retain(|item| match item.id.strip_prefix(ROLLBACK_ITEM) {
+            Some(path) => item.checked || wanted.iter().any(|c| c.path == path),
+            None => true,
+        });
+    for check in wanted {
+        let id = format!("{}{}", ROLLBACK_ITEM, check.path);
+        if checklist.items.iter().any(|item| item.id == id) {
+            continue;
+        }
+        checklist.items.push(ChecklistItem {
+            id,
+            text: format!(
+                "Rollback verified: {} goes down and up again cleanly",
+                check.path
+            ),
+            category: MIGRATION_CATEGORY.to_string(),
+            checked: false,
+            related_findings: Vec::new(),
+            related_files: vec![check.path.clone()],
+        });
+    }
+}
+
+impl TabState {
+    pub(super) fn refresh_migrations(&mut self) {
+        let root = self.duplicate_scan_root().map(std::path::PathBuf::from);
+        let read = |path: &str| std::fs::read_to_string(root.as_ref()?.join(path)).ok();
+        let exists = |path: &str| root.as_ref().is_some_and(|r| r.join(path).is_file());
+        self.migrations = git::check_migrations(&self.files, &self.migration_paths, &read, &exists);
+        self.merge_migration_findings();
+    }
+
+    /// Put the migration findings and rollback items back into a freshly
+    /// loaded review and checklist.
+    pub(super) fn merge_migration_findings(&mut self) {
+        self.ai.merge_builtin_findings(
+            MIGRATION_CATEGORY,
+            &migration_findings(&self.migrations),
+            &self.branch_diff_hash,
+        );
+        if self.migrations.is_empty() && self.ai.checklist.is_none() {
+            return;
+        }
+        let diff_hash = self.branch_diff_hash.clone();
+        let checklist = self
+            .ai
+            .checklist
+            .get_or_insert_with(|| ErChecklist::new(&diff_hash));
+        sync_rollback_items(checklist, &self.migrations);
+        if checklist.items.is_empty() {
+            self.ai.checklist = None;
+        }
+    }
+
+    /// The migration check of `path`, for the File Detail panel.
+    pub fn migration_check(&self, path: &str) -> Option<&MigrationCheck> {
+        self.migrations.iter().find(|c| c.path == path)
+    }
+
+    /// Rollback checklist items not ticked yet.
+    pub fn pending_rollbacks(&self) -> usize {
+        self.ai.checklist.as_ref().map_or(0, |checklist| {
+            checklist
+                .items
+                .iter()
+                .filter(|item| item.id.starts_with(ROLLBACK_ITEM) && !item.checked)
+                .count()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/migrations/5_drop.up.sql b/migrations/5_drop.up.sql\n\
+                        new file mode 100644\n--- /dev/null\n+++ b/migrations/5_drop.up.sql\n\
+                        @@ -0,0 +1,2 @@\n+ALTER TABLE users DROP COLUMN legacy_id;\n+SELECT 1;\n";
+
+    #[test]
+    fn migrations_get_findings_and_a_rollback_item_that_holds_back_readiness() {
+        let mut tab = TabState::new_for_test(git::parse_diff(DIFF));
+        tab.reviewed
+            .insert("migrations/5_drop.up.sql".into(), String::new());
+        tab.refresh_migrations();
+
+        let findings = &tab.ai.review.as_ref().unwrap().files["migrations/5_drop.up.sql"].findings;
+        let titles: Vec<&str> = findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Destructive migration: DROP COLUMN", "No down migration"]
+        );
+        assert_eq!(findings[0].severity, RiskLevel::High);
+        assert_eq!(findings[0].line_start, Some(1));
+        assert_eq!(tab.pending_rollbacks(), 1);
+        assert!(!tab.review_ready());
+
+        tab.ai.toggle_checklist_item(0);
+        assert!(tab.review_ready());
+        // Refreshing keeps the ticked item rather than adding another
+        tab.refresh_migrations();
+        assert_eq!(tab.ai.checklist.as_ref().unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn unticked_items_go_when_the_migration_leaves_the_diff() {
+        let mut tab = TabState::new_for_test(git::parse_diff(DIFF));
+        tab.refresh_migrations();
+        assert_eq!(tab.pending_rollbacks(), 1);
+
+        tab.files.clear();
+        tab.refresh_migrations();
+        assert_eq!(tab.pending_rollbacks(), 0);
+        assert!(tab.ai.review.as_ref().is_none_or(|r| r.files.is_empty()));
+    }
+}
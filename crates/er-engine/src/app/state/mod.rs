@@ -1,21 +1,102 @@
+pub(super) mod agent_activity;
+pub mod apply_hunk;
 pub mod arena;
+pub mod artifact_ignore;
+pub mod assets;
+pub mod author_scope;
 pub mod background;
+pub mod branch_audit;
+pub mod branch_switch;
+pub mod bug_report;
+pub mod checklist;
+pub mod checklist_gates;
+pub mod checkpoint;
+pub mod clean_review_data;
+pub mod code_age;
+pub mod comment_autosync;
+pub mod comment_moves;
 pub(super) mod comments;
+pub mod commit_groups;
+pub mod commit_lint;
+pub mod commit_prs;
+pub mod commit_search;
+pub mod commit_split;
+pub mod compaction_policy;
+pub mod compaction_profile;
+pub mod complexity;
+pub mod config_reload;
+pub mod confirm;
+pub mod contracts;
+pub mod copy;
+pub mod custom_commands;
+pub mod data_views;
+pub mod drafts;
+pub mod duplicates;
+pub mod file_sort;
+pub mod find;
+pub mod focus;
+pub mod followups;
+pub mod forge_browse;
+pub mod github_push;
 pub mod github_sync;
+pub mod hunk_explain;
+pub mod hunk_folds;
+pub mod infrastructure;
+pub mod jump_list;
+pub mod large_files;
+pub mod line_staging;
+pub mod lost_comments;
+pub mod marks;
+pub mod migrations;
+pub mod motions;
+pub mod moved_code;
+pub mod multi_select;
 pub(super) mod navigation;
+pub mod no_repo;
+pub mod noise;
+pub mod notifications;
+pub mod onboarding;
+pub mod operational;
+pub mod ownership;
+pub mod packages;
+pub mod pr_head;
+pub mod pre_push;
+pub mod quick_diff;
+pub mod quit;
+pub mod read_only;
+pub mod related_changes;
+pub mod releases;
 pub mod remote_diff_sync;
+pub mod review_hints;
+pub mod review_order;
+pub mod review_pace;
+pub mod review_sla;
+pub mod review_templates;
+pub mod scripts;
+pub mod session_handoff;
+pub mod size_guard;
+pub mod skip_list;
+pub mod spelling;
+pub mod team_review;
+pub mod test_pairing;
+pub mod todos;
+pub mod update_check;
+pub mod usage;
+pub mod view_prefs;
+pub mod viewed_sync;
 
 use crate::ai::{self, AiState, CommentType, InlineLayers, PanelContent, ReviewFocus};
-use crate::config::{self, ErConfig, WatchedConfig};
+use crate::config::{self, ErConfig, LayoutConfig, WatchedConfig};
 use crate::git::{
     self, CommitInfo, CompactionConfig, DiffFile, DiffFileHeader, WatchedFile, Worktree,
 };
 use crate::github::PrOverviewData;
 use crate::paths::ErRoot;
 use anyhow::{Context, Result};
+use comment_moves::CommentMove;
+use notifications::{NotificationEntry, NotificationLevel, NotificationLog};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::io::Write;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 #[allow(unused_imports)]
@@ -93,6 +174,49 @@ pub(crate) struct LineAnchor {
     hunk_header: String,
 }
 
+impl LineAnchor {
+    /// Anchor on line `line` of `hunk` (`side` "LEFT" looks up old-side
+    /// numbers first), or on the hunk itself when `line` is `None`.
+    fn capture(hunk: &git::DiffHunk, line: Option<usize>, side: Option<&str>) -> Self {
+        let Some(ln) = line else {
+            // Hunk-level comment
+            return LineAnchor {
+                line_start: None,
+                line_content: hunk.header.clone(),
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                old_line_start: None,
+                hunk_header: hunk.header.clone(),
+            };
+        };
+        // Find the target line index within the hunk
+        let by_old = || hunk.lines.iter().position(|l| l.old_num == Some(ln));
+        let by_new = || hunk.lines.iter().position(|l| l.new_num == Some(ln));
+        let target_idx = match side {
+            Some("LEFT") => by_old().or_else(by_new),
+            _ => by_new().or_else(by_old),
+        };
+        let Some(idx) = target_idx else {
+            return LineAnchor {
+                line_start: Some(ln),
+                hunk_header: hunk.header.clone(),
+                ..Default::default()
+            };
+        };
+        let dl = &hunk.lines[idx];
+        // Up to 3 content lines either side of the target (same hunk)
+        let contents = |lines: &[git::DiffLine]| lines.iter().map(|l| l.content.clone()).collect();
+        LineAnchor {
+            line_start: Some(ln),
+            line_content: dl.content.clone(),
+            context_before: contents(&hunk.lines[idx.saturating_sub(3)..idx]),
+            context_after: contents(&hunk.lines[idx + 1..(idx + 4).min(hunk.lines.len())]),
+            old_line_start: dl.old_num,
+            hunk_header: hunk.header.clone(),
+        }
+    }
+}
+
 // ── Enums ──
 
 /// Which set of changes we're viewing
@@ -184,6 +308,24 @@ pub struct HistoryState {
     pub all_loaded: bool,
     /// LRU cache of recently viewed commit diffs
     pub diff_cache: DiffCache,
+    /// Full commit messages by hash, for `[commit_lint]` (subjects only
+    /// when git doesn't have the commit)
+    pub messages: HashMap<String, String>,
+    /// Commits a later commit on the branch undoes (`↺`), by full hash
+    pub net_zero: HashSet<String>,
+    /// Hide `net_zero` commits from the list (`O`)
+    pub fold_net_zero: bool,
+    /// Background loading for the History search
+    pub search: commit_search::CommitSearchState,
+    /// PRs looked up for commits that don't name theirs
+    pub prs: commit_prs::CommitPrState,
+}
+
+impl HistoryState {
+    /// Whether `commit` is folded out of the commit list.
+    pub fn is_folded(&self, commit: &CommitInfo) -> bool {
+        self.fold_net_zero && self.net_zero.contains(&commit.hash)
+    }
 }
 
 /// Simple LRU cache for parsed commit diffs
@@ -293,11 +435,18 @@ impl TourState {
 pub enum InputMode {
     Normal,
     Search,
+    /// Typing the find-in-file query (Ctrl+f)
+    Find,
     Comment,
     Confirm(ConfirmAction),
     Filter,
     Commit,
     RemoteUrl,
+    SkipReason,
+    /// Naming the reviewer the selected files go to
+    Assignee,
+    /// Typing the ref to diff the selected file against
+    QuickDiffRef,
 }
 
 /// Actions that require user confirmation (y/n)
@@ -317,6 +466,14 @@ pub enum ConfirmAction {
     CleanupReviews {
         count: usize,
     },
+    /// Drop every saved comment draft
+    DiscardDrafts {
+        count: usize,
+    },
+    /// Quit although `left_behind` (unsent draft, unpushed comments, …) would be lost
+    Quit {
+        left_behind: Vec<String>,
+    },
     /// Confirm clearing previous review before running AI review
     RunAgentReview {
         clear_previous: bool,
@@ -329,6 +486,43 @@ pub enum ConfirmAction {
     ApprovePR,
     /// Choose how to push comments: as review or individually
     PushComments,
+    DeleteChecklistItem {
+        index: usize,
+    },
+    /// Push although these pre-push checks failed
+    PushDespiteChecks {
+        failed: Vec<String>,
+    },
+    /// Apply the suggested filter hiding `files` noise files
+    HideNoise {
+        files: usize,
+        expr: String,
+    },
+    /// Move a lost comment to where its code went in another file
+    MoveComment {
+        comment_id: String,
+    },
+    /// Restore the `lines` selected lines of `path` with a reverse patch
+    DiscardLines {
+        path: String,
+        lines: usize,
+        patch: String,
+    },
+    /// Append ignore patterns for er's artifacts to `file`
+    IgnoreArtifacts {
+        file: std::path::PathBuf,
+        patterns: Vec<String>,
+    },
+    /// Remove the repo's stale review data (`er clean`)
+    CleanReviewData {
+        targets: Vec<crate::artifact_gc::CleanTarget>,
+    },
+    /// Stash the uncommitted changes and switch the working tree's branch
+    SwitchBranch {
+        name: String,
+        remote: bool,
+        changes: usize,
+    },
 }
 
 /// Which pane has focus in split diff view
@@ -362,6 +556,8 @@ pub enum OverlayData {
     WorktreePicker {
         worktrees: Vec<Worktree>,
         selected: usize,
+        /// Set when picking where to apply a hunk rather than a tab to open
+        apply: Option<apply_hunk::PendingHunkPatch>,
     },
     DirectoryBrowser {
         current_path: String,
@@ -380,6 +576,20 @@ pub enum OverlayData {
         items: Vec<HubItem>,
         selected: usize,
     },
+    NotificationLog {
+        /// Snapshot of the log, newest first
+        entries: Vec<NotificationEntry>,
+        selected: usize,
+    },
+    /// First-run guide (modes, review keys, AI file conventions)
+    Onboarding { scroll: u16 },
+    /// Full details of a failed `git`/`gh` invocation
+    CommandError {
+        /// What was being attempted, e.g. "Commit failed"
+        title: String,
+        failure: Box<crate::command::CommandFailure>,
+        scroll: u16,
+    },
     ConfigHub {
         tab: config::SettingsScope,
         items: Vec<config::ConfigItem>,
@@ -415,6 +625,28 @@ pub enum HubKind {
     Help,
     Open,
     Copy,
+    Drafts,
+    Checklist,
+    CommentConflicts,
+    Reviews,
+    Audit,
+    Authors,
+    Packages,
+    Sessions,
+    Todos,
+    Spelling,
+    CommitPlan,
+    /// A Lua panel's lines
+    Script,
+    LostComments,
+    Marks,
+    Releases,
+    Branches,
+    Compaction,
+    SizeGuard,
+    Snooze,
+    Selection,
+    BugReport,
 }
 
 impl HubKind {
@@ -430,6 +662,27 @@ impl HubKind {
             HubKind::Help => "HELP",
             HubKind::Open => "OPEN",
             HubKind::Copy => "COPY",
+            HubKind::Drafts => "DRAFTS",
+            HubKind::Checklist => "CHECKLIST",
+            HubKind::CommentConflicts => "COMMENT CONFLICTS",
+            HubKind::Reviews => "OPEN REVIEWS",
+            HubKind::Audit => "BRANCH AUDIT",
+            HubKind::Authors => "BRANCH AUTHORS",
+            HubKind::Packages => "PACKAGES",
+            HubKind::Sessions => "IMPORT SESSION",
+            HubKind::Todos => "TODOS",
+            HubKind::Spelling => "SPELLING",
+            HubKind::CommitPlan => "COMMIT PLAN",
+            HubKind::Script => "SCRIPT",
+            HubKind::LostComments => "LOST COMMENTS",
+            HubKind::Marks => "MARKS",
+            HubKind::Releases => "TAG RANGES",
+            HubKind::Branches => "SWITCH BRANCH",
+            HubKind::Compaction => "COMPACTION",
+            HubKind::SizeGuard => "HUGE DIFF",
+            HubKind::Snooze => "SNOOZE",
+            HubKind::Selection => "SELECTION",
+            HubKind::BugReport => "REPORT A BUG",
         }
     }
 }
@@ -460,10 +713,18 @@ pub enum HubAction {
     PullGitHubComments,
     PushCommentsToGitHub,
     RefreshDiff,
+    SetReviewCheckpoint,
+    ToggleCheckpointView,
+    TogglePrHeadView,
+    StartQuickDiff,
+    ToggleCodeAge,
+    OpenBranchPicker,
     StageFile,
     StageAll,
     // AI hub actions
     CopyContext,
+    ExplainHunk,
+    ExportHandoff,
     CopyReviewJson,
     CopyQuestionsJson,
     ToggleAiFindings,
@@ -474,6 +735,39 @@ pub enum HubAction {
     CleanupReviews,
     /// Run a named command from [commands] config (e.g. "summary", "test", "lint")
     RunCommand(String),
+    /// `[commands.custom]` entry by name
+    RunCustomCommand(String),
+    /// Lua action by name (see `scripts`)
+    RunScriptAction(String),
+    /// Lua panel by name
+    OpenScriptPanel(String),
+    /// Comments relocation lost (see `lost_comments`)
+    OpenLostComments,
+    /// Start re-anchoring lost comment by id
+    ReanchorComment(String),
+    /// Jump to a named mark (see `marks`)
+    JumpToMark(char),
+    /// Change the repo's compaction policy (see `compaction_policy`)
+    SetCompactionPolicy(compaction_policy::CompactionPolicy),
+    /// Leave a directory out of a huge diff, or put it back (see `size_guard`)
+    ToggleGuardDir(String),
+    /// Load the huge diff without the directories left out
+    LoadGuardedDiff,
+    /// Open a tag range tab (see `releases`)
+    OpenReleaseRange {
+        from: String,
+        to: String,
+    },
+    /// Check out a branch in the working tree (see `branch_switch`)
+    SwitchBranch {
+        name: String,
+        remote: bool,
+    },
+    /// Write a bug report, copying its path or a `gh issue create` for it
+    BugReport {
+        snapshot: bool,
+        gh: bool,
+    },
     /// Update the AI provider/model selection without running an action
     ConfigureAiSelection,
     /// Start an AI action through the provider/model selection flow
@@ -513,16 +807,108 @@ pub enum HubAction {
     ApprovePR,
     /// Post a general comment on the PR (not attached to a file/line)
     CommentOnPR,
+    /// Note on the selected file as a whole (no hunk or line)
+    CommentOnFile,
+    /// Review-wide note or question, sent in the next review's body
+    ReviewNote,
+    /// Push own review state to `refs/er/reviews/<branch>`
+    PublishSharedReview,
+    /// Fetch teammates' review state from `refs/er/reviews/<branch>`
+    PullSharedReview,
     // Open hub actions
     OpenDirectory,
     OpenWorktree,
     OpenRemoteUrl,
     OpenPrInBrowser,
+    OpenLineOnForge,
+    OpenCommitOnForge,
+    OpenInSplit,
     // Copy hub actions
     CopyFullFile,
     CopyFilePath,
     CopyHunk,
     CopyLine,
+    /// Current hunk with file headers (`reverse`: the patch undoing it)
+    CopyHunkPatch {
+        reverse: bool,
+    },
+    CopyViewDiff,
+    SaveViewPatch,
+    CopyPermalink,
+    CopyDiffCommand,
+    // Drafts hub actions
+    /// Reopen the saved draft at this index
+    ResumeDraft(usize),
+    DiscardDrafts,
+    // Checklist hub actions
+    /// Add a named template's items to the checklist
+    ApplyChecklistTemplate(String),
+    SaveChecklistTemplate,
+    // Comment conflict hub actions
+    /// Settle a comment edited both here and on GitHub
+    ResolveCommentConflict {
+        id: String,
+        keep_mine: bool,
+    },
+    OpenCommentConflicts,
+    // Review dashboard actions
+    OpenReviewsDashboard,
+    /// Snooze the focused comment, finding or file for this many seconds
+    /// (see `followups`)
+    Snooze(u64),
+    /// Drop a follow-up by id
+    ClearFollowUp(String),
+    /// Jump to a follow-up by id and clear it
+    OpenFollowUp(String),
+    // Selection hub actions (see `multi_select`)
+    /// Mark the selected files reviewed, or unmark them
+    ReviewSelection,
+    /// Stage, unstage or resolve the selected files
+    StageSelection,
+    /// Add the selected files to the filter
+    FilterSelection {
+        include: bool,
+    },
+    /// Compact the selected files, or expand them
+    CompactSelection,
+    /// Ask who the selected files are assigned to
+    AssignSelection,
+    ClearSelection,
+    OpenBranchAudit,
+    OpenBranchAuthors,
+    ApplyHunkToWorktree,
+    /// Filter the Branch diff to one author's hunks (`author:<name>`)
+    FilterByAuthor(String),
+    OpenPackages,
+    /// Filter the tree to one monorepo package's files (`package:<name>`)
+    FilterByPackage(String),
+    ExportSession,
+    OpenImportSession,
+    /// Merge the session bundle at this path
+    ImportSession(String),
+    OpenTodos,
+    /// Open the Ops panel
+    OpenOperational,
+    /// Open the Assets panel
+    OpenAssets,
+    OpenSpelling,
+    /// Jump the diff to a file (and line)
+    FocusFileLine {
+        path: String,
+        line: Option<usize>,
+    },
+    OpenCommitPlan,
+    /// Put a file (or one hunk) in a planned commit; `None` = a new one
+    AssignToCommit {
+        bucket: Option<usize>,
+        path: String,
+        hunk: Option<String>,
+    },
+    RunCommitPlan,
+    ClearCommitPlan,
+    SwitchTab(usize),
+    // Help hub actions
+    ShowOnboarding,
     // Help — no dispatch, just informational
     /// Select a package in the verify flow, then show that package's commands
     SelectVerifyPackage {
@@ -537,6 +923,18 @@ pub enum HubAction {
 
 // ── Per-Tab State ──
 
+/// How long a jumped-to finding's lines stay highlighted.
+pub const LINE_FLASH_DURATION: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// New-side line range briefly highlighted in the diff after a jump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineFlash {
+    pub path: String,
+    pub start: usize,
+    pub end: usize,
+    pub until: Instant,
+}
+
 /// State for a single repo tab
 pub struct TabState {
     pub mode: DiffMode,
@@ -592,8 +990,57 @@ pub struct TabState {
     /// Whether keyboard focus is on the panel (vs diff view)
     pub panel_focus: bool,
 
-    /// Width of the file tree panel in columns (resizable with </>)
-    pub file_tree_width: u16,
+    /// A second panel pinned beside `panel` (Alt+p). Tab moves focus
+    /// between the two by swapping them, so the focused one is always
+    /// `panel`; they're drawn in a fixed order either way.
+    pub second_panel: Option<PanelContent>,
+    pub second_panel_scroll: u16,
+
+    /// Places jumps left, for Ctrl+o / Ctrl+i
+    pub jump_list: jump_list::JumpList,
+
+    /// Named marks (`m` + letter), saved in the session
+    pub marks: BTreeMap<char, marks::Mark>,
+
+    /// Folded hunks: path → fold keys (see `hunk_folds`), saved in the session
+    pub folded_hunks: BTreeMap<String, BTreeSet<String>>,
+
+    /// View preferences as last loaded or saved (see `view_prefs`)
+    pub view_prefs: Option<view_prefs::ViewPrefs>,
+
+    /// Find in the current file (Ctrl+f); `None` when not searching
+    pub find: Option<find::FileFind>,
+
+    /// The tags this tab diffs (`t`); `local_branch_view` holds the newer one
+    pub release: Option<releases::ReleaseRange>,
+
+    /// Near-duplicate findings of this diff, merged into the AI review
+    pub duplicates: duplicates::DuplicateScan,
+
+    /// Changed functions' size and complexity, and the over-budget findings
+    pub complexity: complexity::ComplexityScan,
+
+    /// Semantic diffs of the changed .proto and OpenAPI files
+    pub contracts: contracts::ContractScan,
+
+    /// Notebook and table views of data files, toggled per file
+    pub data_views: data_views::DataViews,
+
+    /// The changed image assets, for the Assets panel
+    pub assets: assets::AssetScan,
+
+    /// Resource-level summaries of the changed Terraform and Kubernetes files
+    pub infrastructure: infrastructure::InfraScan,
+
+    /// Which compacted files get expanded, learned per repo
+    pub compaction_learning: compaction_profile::CompactionLearning,
+
+    /// Huge diffs ask which directories to leave out before parsing
+    pub size_guard: size_guard::SizeGuard,
+
+    /// File tree width in columns once resized with </>; `None` follows
+    /// `[layout] tree_percent`
+    pub file_tree_width: Option<u16>,
 
     /// Width of the side panel in columns (resizable with {/})
     pub panel_width: u16,
@@ -604,6 +1051,73 @@ pub struct TabState {
     /// ID of the finding currently highlighted by [/] jumping
     pub focused_finding_id: Option<String>,
 
+    /// Lines highlighted after jumping to a finding, until they expire
+    pub line_flash: Option<LineFlash>,
+
+    /// Findings shown in full beneath their lines (`i` on the focused one)
+    pub expanded_findings: HashSet<String>,
+
+    /// Comment pushes queued after a GitHub rate limit (`outbox.json`)
+    pub outbox: Option<crate::outbox::Outbox>,
+
+    /// Background comment pull in flight (`[github] comment_sync_minutes`)
+    pub comment_sync_rx: Option<std::sync::mpsc::Receiver<Result<crate::sync::CommentSyncResult>>>,
+
+    /// When the last background comment pull started
+    pub last_comment_sync: Instant,
+
+    /// Files with GitHub comments that arrived since they were last selected
+    pub new_comment_files: HashMap<String, usize>,
+
+    /// `[review_hints]` from this repo's `.er-config.toml`
+    pub repo_review_hints: BTreeMap<String, Vec<String>>,
+
+    /// `[review_order]` phases for the guided file order (see [`review_order`])
+    pub review_phases: Vec<config::ReviewPhase>,
+    /// `[[review_templates]]` to pick from (see [`review_templates`])
+    pub review_templates: Vec<config::ReviewTemplate>,
+    /// The template applied to this tab, once one matched
+    pub review_template: Option<config::ReviewTemplate>,
+
+    /// Where tests live relative to their sources (`[tests] patterns`)
+    pub test_patterns: Vec<String>,
+
+    /// Authors of files shown in the File Detail panel, by path
+    pub file_ownership: HashMap<String, git::FileOwnership>,
+
+    /// Ownership fetch in flight for one path
+    pub ownership_rx: Option<std::sync::mpsc::Receiver<(String, Result<git::FileOwnership>)>>,
+
+    /// Recent commits and PRs touching files shown in the Related panel,
+    /// by path (see [`related_changes`])
+    pub related_changes: HashMap<String, related_changes::RelatedChanges>,
+
+    /// Related-changes fetch in flight for one path
+    pub related_changes_rx:
+        Option<std::sync::mpsc::Receiver<(String, related_changes::RelatedChanges)>>,
+
+    /// Code age tints of added lines, by path then new line (see
+    /// [`code_age`]); cleared when the diff reloads
+    pub code_ages: HashMap<String, HashMap<usize, code_age::CodeAge>>,
+
+    /// Old-side blame times by (revision, path), kept for the session
+    pub code_age_blame: HashMap<code_age::BlameKey, code_age::BlameTimes>,
+
+    /// Blame in flight for the code age layer
+    pub code_age_rx: Option<std::sync::mpsc::Receiver<(code_age::BlameKey, code_age::BlameTimes)>>,
+
+    /// AI explanations of hunks, by hunk hash (see [`hunk_explain`])
+    pub hunk_explanations: HashMap<String, String>,
+
+    /// Explanation in flight, with the hash of the hunk it is for
+    pub hunk_explain_rx: Option<(String, std::sync::mpsc::Receiver<String>)>,
+
+    /// Pre-push checks in flight (see [`pre_push`])
+    pub pre_push_rx: Option<std::sync::mpsc::Receiver<pre_push::PrePushReport>>,
+
+    /// Local `HEAD` vs the PR head, with the PR head oid it was computed for
+    pub pr_head_sync: Option<(String, pr_head::PrHeadSync)>,
+
     /// File paths the user explicitly expanded (survive diff refreshes)
     pub user_expanded: HashSet<String>,
 
@@ -613,6 +1127,12 @@ pub struct TabState {
     /// Cursor position within the focused panel section
     pub review_cursor: usize,
 
+    /// Order of the AiSummary file list (`s` cycles)
+    pub review_sort: ai::ReviewSort,
+
+    /// Category sections folded in the AiSummary view
+    pub collapsed_categories: HashSet<String>,
+
     /// Search/filter input
     pub search_query: String,
 
@@ -620,6 +1140,18 @@ pub struct TabState {
     /// Empty-string hash is a sentinel for entries loaded from the old single-column format
     /// (backwards compat) — those entries are never auto-unmarked.
     pub reviewed: HashMap<String, String>,
+    /// `reviewed` as this tab last read or wrote the file; a save applies
+    /// only what changed since, so marks other writers made meanwhile stay
+    pub reviewed_on_disk: HashMap<String, String>,
+
+    /// Files skipped from review: path → reason. Out of the reviewed count.
+    pub skipped: HashMap<String, String>,
+
+    /// Files assigned to a reviewer: path → name (see `multi_select`).
+    pub assigned: HashMap<String, String>,
+
+    /// Files picked for a grouped action (Alt+s, Alt+j / Alt+k).
+    pub multi_selected: HashSet<String>,
 
     /// Per-file diff hashes for the current refresh (volatile, not persisted).
     /// Used to detect when a reviewed file's diff has changed since it was marked.
@@ -628,8 +1160,62 @@ pub struct TabState {
     /// Only show unreviewed files in the file tree
     pub show_unreviewed_only: bool,
 
-    /// Sort files by mtime (newest first) — works in any diff mode
-    pub sort_by_mtime: bool,
+    /// Marking a file reviewed moves on to the next unreviewed one
+    pub focus_mode: bool,
+
+    /// File tree order — works in any diff mode
+    pub file_sort: file_sort::FileSort,
+
+    /// Encoding of files that aren't UTF-8, for the file header
+    pub file_encodings: HashMap<String, String>,
+
+    /// Files compacted for their size (see [`large_files`]), with the size in bytes
+    pub large_files: HashMap<String, u64>,
+
+    /// Monorepo workspace members (see [`packages`]); empty outside one
+    pub packages: Vec<git::Package>,
+
+    /// How TODO markers are found (see [`todos`])
+    pub todo_scanner: git::TodoScanner,
+    /// TODO/FIXME/HACK markers on added lines, in diff order
+    pub todos: Vec<git::TodoMarker>,
+
+    /// How operational changes are found (see [`operational`]); none when
+    /// `[operational]` is disabled
+    pub ops_scanner: Option<git::OpsScanner>,
+    /// Config keys, env vars and feature flags the diff changes
+    pub operational: Vec<git::OpsChange>,
+
+    /// Paths that are migrations (see [`migrations`]); empty when
+    /// `[migrations]` is disabled
+    pub migration_paths: Vec<String>,
+    /// The diff's migration files, checked
+    pub migrations: Vec<git::MigrationCheck>,
+    /// Path pattern → checklist items to tick before marking a matching file
+    /// reviewed, global and repo merged (see [`checklist_gates`])
+    pub checklist_gates: BTreeMap<String, Vec<String>>,
+
+    /// Planned commits for the splitting assistant, in commit order
+    pub commit_plan: Vec<commit_split::CommitBucket>,
+    /// Item waiting for the commit editor to name its new bucket
+    pub split_pending: Option<commit_split::SplitItem>,
+
+    /// Low-value files dominating the diff (see [`noise`])
+    pub noise_suggestion: Option<noise::NoiseSuggestion>,
+    /// Filter expression last offered, so each suggestion is asked only once
+    pub noise_offered: Option<String>,
+
+    /// er's own files among the changed ones (see [`artifact_ignore`])
+    pub artifacts_in_diff: Vec<String>,
+    /// The `artifacts_in_diff` last warned about
+    pub artifacts_warned: Vec<String>,
+    /// Whether the working tree's artifacts were checked against git's ignores
+    pub artifact_ignore_checked: bool,
+
+    /// Lost comments whose code moved to another file (see [`comment_moves`])
+    pub comment_moves: Vec<CommentMove>,
+    /// Lost comment waiting for the cursor line to bind it to
+    pub reanchoring: Option<String>,
 
     /// Cached mtime per file path, populated on refresh (avoids per-frame fs::metadata calls)
     pub mtime_cache: HashMap<String, std::time::SystemTime>,
@@ -651,6 +1237,17 @@ pub struct TabState {
     /// Timestamp of last .er-* file check (to avoid re-reading every tick)
     pub last_ai_check: Option<std::time::SystemTime>,
 
+    /// mtime of the `reviewed` file when last read, so marks written by other
+    /// processes (e.g. the MCP server) show up live.
+    pub reviewed_mtime: Option<std::time::SystemTime>,
+
+    /// Finding/comment id → file for items whose anchor lines changed on disk
+    /// since the file was last marked reviewed ("addressed — needs re-check").
+    pub addressed: HashMap<String, String>,
+
+    /// "Since checkpoint" interdiff view (Branch mode only); `None` = full diff.
+    pub checkpoint_view: Option<checkpoint::CheckpointView>,
+
     // ── Filter state ──
     /// Active filter expression (user-visible string)
     pub filter_expr: String,
@@ -668,6 +1265,9 @@ pub struct TabState {
     /// Multi-line text area for the comment being typed
     pub comment_textarea: TextArea<'static>,
 
+    /// Cancelled or displaced comment drafts, newest first (see `drafts`)
+    pub comment_drafts: Vec<drafts::CommentDraft>,
+
     /// File path the comment targets
     pub comment_file: String,
 
@@ -701,6 +1301,13 @@ pub struct TabState {
     /// Consumed by submit_github_comment. Defaults to "RIGHT".
     pub comment_side: Option<String>,
 
+    /// The comment being typed is about the whole file: stored with no hunk
+    /// or line anchor and shown above the file's first hunk.
+    pub comment_file_level: bool,
+
+    /// Set while the comment box is editing a checklist item instead
+    pub checklist_edit: Option<checklist::ChecklistEdit>,
+
     /// History mode state (only populated when mode == History)
     pub history: Option<HistoryState>,
 
@@ -744,6 +1351,10 @@ pub struct TabState {
     /// PR number when opened via --pr or PR URL. Used for comment sync and PR data fetching.
     pub pr_number: Option<u64>,
 
+    /// Opened via --pr or PR URL: the diff is the fetched PR head, and the
+    /// working tree (whatever is checked out) isn't part of the review.
+    pub pr_head_only: bool,
+
     // ── Commit input state ──
     /// Text buffer for the commit message being typed
     pub commit_input: String,
@@ -789,10 +1400,24 @@ pub struct TabState {
     /// triggering a full hunk rebuild on the frontend.
     pub reviewed_revision: u64,
 
+    /// Blame of the Branch diff's added lines while an `author:` rule is active
+    pub author_scope: Option<author_scope::AuthorScope>,
+
+    /// This session's review pace, for the top bar's ETA
+    pub review_pace: review_pace::ReviewPace,
+
     /// True after a commit in Staged mode — causes diff view to show HEAD~1..HEAD until next
     /// new staged change or the user pushes.
     pub committed_unpushed: bool,
 
+    /// Something was staged from inside er and not committed yet; quitting
+    /// asks first (see `quit`).
+    pub staged_in_app: bool,
+
+    /// GraphQL ID of this tab's PR, learned on sync; lets Space toggles
+    /// reach GitHub's "viewed" checkboxes (see `viewed_sync`).
+    pub github_pr_node_id: Option<String>,
+
     /// Per-file context line overrides (path -> context lines count).
     /// Default context is 10 (git's --unified=10). Cleared on diff refresh.
     pub context_overrides: HashMap<String, usize>,
@@ -805,6 +1430,14 @@ pub struct TabState {
     /// Diff source is `git diff <base>...<branch>` run from `repo_root`. No git mutation.
     pub local_branch_view: Option<String>,
 
+    /// Set when no git repository is behind this tab: the start tab of a
+    /// launch outside one, or a patch file (see [`no_repo`])
+    pub no_repo: Option<no_repo::NoRepo>,
+
+    /// Diff hash "review completed" last went out for, so unmarking and
+    /// re-marking the last file doesn't announce it again
+    pub review_completed_for: Option<String>,
+
     /// When Some and `local_branch_view` is also Some, the branch is checked out
     /// at this path (project root or linked worktree) and refreshes use
     /// `git_diff_checkout_against_base` against that working tree so live edits
@@ -962,10 +1595,14 @@ pub struct SessionState {
     #[serde(default)]
     pub show_unreviewed_only: bool,
 
-    /// Whether sorting by mtime
+    /// Whether sorting by mtime (sessions from before `file_sort`)
     #[serde(default)]
     pub sort_by_mtime: bool,
 
+    /// File tree sort mode
+    #[serde(default)]
+    pub file_sort: file_sort::FileSort,
+
     /// In-progress comment draft text (empty if none)
     #[serde(default)]
     pub comment_draft: String,
@@ -982,9 +1619,26 @@ pub struct SessionState {
     #[serde(default)]
     pub comment_draft_line: Option<usize>,
 
-    /// Comment draft type ("question" or "github")
+    /// Comment draft type ("question", "note" or "github")
     #[serde(default)]
     pub comment_draft_type: String,
+
+    /// Full state of the comment being typed (reply target, range); the
+    /// `comment_draft*` fields above are kept for older readers
+    #[serde(default)]
+    pub active_draft: Option<drafts::CommentDraft>,
+
+    /// Cancelled or displaced drafts, newest first
+    #[serde(default)]
+    pub drafts: Vec<drafts::CommentDraft>,
+
+    /// Named marks
+    #[serde(default)]
+    pub marks: BTreeMap<char, marks::Mark>,
+
+    /// Folded hunks
+    #[serde(default)]
+    pub folded_hunks: BTreeMap<String, BTreeSet<String>>,
 }
 
 impl SessionState {
@@ -1015,13 +1669,16 @@ pub struct HunkOffsets {
 }
 
 impl HunkOffsets {
-    pub fn build(hunks: &[git::DiffHunk]) -> Self {
+    /// `folded[i]`: hunk i shows only its header (see `hunk_folds`)
+    pub fn build(hunks: &[git::DiffHunk], folded: &[bool]) -> Self {
         let mut offsets = Vec::with_capacity(hunks.len());
         let mut cursor: usize = 2; // file header lines
-        for hunk in hunks {
+        for (i, hunk) in hunks.iter().enumerate() {
             offsets.push(cursor);
             cursor += 1; // hunk header
-            cursor += hunk.lines.len();
+            if !folded.get(i).copied().unwrap_or(false) {
+                cursor += hunk.lines.len();
+            }
             cursor += 1; // blank line between hunks
         }
         Self { offsets }
@@ -1078,6 +1735,30 @@ impl TabState {
         Ok(tab)
     }
 
+    /// Review a PR from its fetched head without checking it out: fetches
+    /// `pull/<n>/head` from the forge remote to `refs/er/pr/<n>/head` and diffs
+    /// the base against it. Works the same for PRs from forks, whose head
+    /// branch lives on a remote this clone may not have.
+    pub fn new_pr_head_review(repo_root: String, pr_number: u64) -> Result<Self> {
+        let head_ref = crate::github::fetch_pr_head(pr_number, &repo_root)?;
+        let base = crate::github::gh_pr_base_branch(pr_number, &repo_root)?;
+        let base = crate::github::ensure_base_ref_available(&repo_root, &base)?;
+        let head = crate::github::gh_pr_head(pr_number, &repo_root)
+            .map(|head| head.label())
+            .ok()
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| format!("pr/{}", pr_number));
+
+        let mut tab = TabState::new_with_base(repo_root, base)?;
+        tab.last_diff_head_oid = crate::github::rev_parse_oid(&tab.repo_root, &head_ref);
+        tab.pr_head_ref = Some(head_ref);
+        tab.pr_number = Some(pr_number);
+        tab.pr_head_only = true;
+        tab.current_branch = head;
+        tab.refresh_diff()?;
+        Ok(tab)
+    }
+
     /// Create a TabState for a read-only local PR review. Fetches the PR head to
     /// `refs/er/pr/<number>/head` without running `gh pr checkout` or touching the
     /// working tree. Diffs `<resolved_base>...refs/er/pr/<number>/head`.
@@ -1186,6 +1867,7 @@ impl TabState {
     pub fn new_remote(pr_ref: &crate::github::PrRef) -> Result<Self> {
         let repo_slug = format!("{}/{}", pr_ref.owner, pr_ref.repo);
         let (agent_log_tx, agent_log_rx) = std::sync::mpsc::channel();
+        crate::github::set_host(&pr_ref.host);
 
         // Get metadata (base/head branch names)
         let (base_branch, head_branch) =
@@ -1214,13 +1896,7 @@ impl TabState {
             // Apply compaction to stubs in lazy mode
             for (file, header) in files.iter_mut().zip(headers.iter()) {
                 let total_lines = header.adds + header.dels;
-                let should_compact = compaction_config.enabled
-                    && (compaction_config
-                        .patterns
-                        .iter()
-                        .any(|p| crate::git::compact_files_match(p, &file.path))
-                        || total_lines > compaction_config.max_lines_before_compact);
-                if should_compact {
+                if compaction_config.should_compact(&file.path, total_lines) {
                     file.compacted = true;
                     file.raw_hunk_count = header.hunk_count;
                 }
@@ -1255,10 +1931,47 @@ impl TabState {
             panel: None,
             panel_scroll: 0,
             panel_focus: false,
-            file_tree_width: 32,
+            second_panel: None,
+            second_panel_scroll: 0,
+            jump_list: jump_list::JumpList::default(),
+            marks: BTreeMap::new(),
+            folded_hunks: BTreeMap::new(),
+            view_prefs: None,
+            find: None,
+            release: None,
+            duplicates: Default::default(),
+            complexity: Default::default(),
+            contracts: Default::default(),
+            data_views: Default::default(),
+            assets: Default::default(),
+            infrastructure: Default::default(),
+            compaction_learning: Default::default(),
+            size_guard: Default::default(),
+            file_tree_width: None,
             panel_width: 40,
             focused_comment_id: None,
             focused_finding_id: None,
+            line_flash: None,
+            expanded_findings: HashSet::new(),
+            outbox: None,
+            comment_sync_rx: None,
+            last_comment_sync: Instant::now(),
+            new_comment_files: HashMap::new(),
+            repo_review_hints: BTreeMap::new(),
+            review_phases: Vec::new(),
+            review_templates: Vec::new(),
+            review_template: None,
+            file_ownership: HashMap::new(),
+            ownership_rx: None,
+            related_changes: HashMap::new(),
+            related_changes_rx: None,
+            code_ages: HashMap::new(),
+            code_age_blame: HashMap::new(),
+            code_age_rx: None,
+            pr_head_sync: None,
+            hunk_explanations: HashMap::new(),
+            hunk_explain_rx: None,
+            pre_push_rx: None,
             user_expanded: HashSet::new(),
             review_focus: ReviewFocus::Files,
             review_cursor: 0,
@@ -1268,16 +1981,44 @@ impl TabState {
             filter_input: String::new(),
             filter_history: Vec::new(),
             reviewed: HashMap::new(),
+            skipped: HashMap::new(),
+            assigned: HashMap::new(),
+            multi_selected: HashSet::new(),
             current_per_file_hashes: HashMap::new(),
             show_unreviewed_only: false,
-            sort_by_mtime: false,
+            file_sort: file_sort::FileSort::Diff,
+            focus_mode: false,
             mtime_cache: HashMap::new(),
+            large_files: HashMap::new(),
+            packages: Vec::new(),
+            todo_scanner: git::TodoScanner::default(),
+            todos: Vec::new(),
+            ops_scanner: Some(git::OpsScanner::default()),
+            operational: Vec::new(),
+            migration_paths: config::MigrationsConfig::default().paths,
+            checklist_gates: BTreeMap::new(),
+            migrations: Vec::new(),
+            commit_plan: Vec::new(),
+            split_pending: None,
+            noise_suggestion: None,
+            noise_offered: None,
+            artifacts_in_diff: Vec::new(),
+            artifacts_warned: Vec::new(),
+            artifact_ignore_checked: false,
+            comment_moves: Vec::new(),
+            reanchoring: None,
+            file_encodings: HashMap::new(),
             search_query_lower: String::new(),
             ai: AiState::default(),
             diff_hash: diff_hash.clone(),
             branch_diff_hash: diff_hash,
             last_ai_check: None,
+            reviewed_mtime: None,
+            reviewed_on_disk: HashMap::new(),
+            addressed: HashMap::new(),
+            checkpoint_view: None,
             comment_textarea: TextArea::default(),
+            comment_drafts: Vec::new(),
             comment_file: String::new(),
             comment_hunk: 0,
             comment_reply_to: None,
@@ -1288,14 +2029,20 @@ impl TabState {
             comment_finding_ref: None,
             comment_author_override: None,
             comment_side: None,
+            comment_file_level: false,
+            review_sort: ai::ReviewSort::Risk,
+            collapsed_categories: HashSet::new(),
+            checklist_edit: None,
             pr_data: None,
             pr_commits,
             pr_head_ref: None,
+            pr_head_only: false,
             pr_number: Some(pr_ref.number),
             history: None,
             tour: None,
             tour_is_pr: false,
             watched_config: er_config.watched.clone(),
+            test_patterns: er_config.tests.resolved_patterns(),
             watched_files: Vec::new(),
             selected_watched: None,
             show_watched: false,
@@ -1312,11 +2059,17 @@ impl TabState {
             symbol_refs: None,
             pending_unmark_count: 0,
             reviewed_revision: 0,
+            author_scope: None,
+            review_pace: review_pace::ReviewPace::default(),
             committed_unpushed: false,
+            staged_in_app: false,
+            github_pr_node_id: None,
             context_overrides: HashMap::new(),
             remote_repo: Some(repo_slug),
             local_branch_view: None,
             local_branch_checkout_root: None,
+            no_repo: None,
+            review_completed_for: None,
             command_rx: std::collections::HashMap::new(),
             command_status: std::collections::HashMap::new(),
             log_tx: agent_log_tx,
@@ -1350,6 +2103,7 @@ impl TabState {
     pub fn new_remote_stub(pr_ref: &crate::github::PrRef) -> Result<Self> {
         let repo_slug = format!("{}/{}", pr_ref.owner, pr_ref.repo);
         let (agent_log_tx, agent_log_rx) = std::sync::mpsc::channel();
+        crate::github::set_host(&pr_ref.host);
         let er_config = crate::config::ErConfig::default();
         let repo_root_remote = std::env::current_dir()
             .map(|p| p.to_string_lossy().to_string())
@@ -1375,10 +2129,47 @@ impl TabState {
             panel: None,
             panel_scroll: 0,
             panel_focus: false,
-            file_tree_width: 32,
+            second_panel: None,
+            second_panel_scroll: 0,
+            jump_list: jump_list::JumpList::default(),
+            marks: BTreeMap::new(),
+            folded_hunks: BTreeMap::new(),
+            view_prefs: None,
+            find: None,
+            release: None,
+            duplicates: Default::default(),
+            complexity: Default::default(),
+            contracts: Default::default(),
+            data_views: Default::default(),
+            assets: Default::default(),
+            infrastructure: Default::default(),
+            compaction_learning: Default::default(),
+            size_guard: Default::default(),
+            file_tree_width: None,
             panel_width: 40,
             focused_comment_id: None,
             focused_finding_id: None,
+            line_flash: None,
+            expanded_findings: HashSet::new(),
+            outbox: None,
+            comment_sync_rx: None,
+            last_comment_sync: Instant::now(),
+            new_comment_files: HashMap::new(),
+            repo_review_hints: BTreeMap::new(),
+            review_phases: Vec::new(),
+            review_templates: Vec::new(),
+            review_template: None,
+            file_ownership: HashMap::new(),
+            ownership_rx: None,
+            related_changes: HashMap::new(),
+            related_changes_rx: None,
+            code_ages: HashMap::new(),
+            code_age_blame: HashMap::new(),
+            code_age_rx: None,
+            pr_head_sync: None,
+            hunk_explanations: HashMap::new(),
+            hunk_explain_rx: None,
+            pre_push_rx: None,
             user_expanded: HashSet::new(),
             review_focus: ReviewFocus::Files,
             review_cursor: 0,
@@ -1388,16 +2179,44 @@ impl TabState {
             filter_input: String::new(),
             filter_history: Vec::new(),
             reviewed: HashMap::new(),
+            skipped: HashMap::new(),
+            assigned: HashMap::new(),
+            multi_selected: HashSet::new(),
             current_per_file_hashes: HashMap::new(),
             show_unreviewed_only: false,
-            sort_by_mtime: false,
+            file_sort: file_sort::FileSort::Diff,
+            focus_mode: false,
             mtime_cache: HashMap::new(),
+            large_files: HashMap::new(),
+            packages: Vec::new(),
+            todo_scanner: git::TodoScanner::default(),
+            todos: Vec::new(),
+            ops_scanner: Some(git::OpsScanner::default()),
+            operational: Vec::new(),
+            migration_paths: config::MigrationsConfig::default().paths,
+            checklist_gates: BTreeMap::new(),
+            migrations: Vec::new(),
+            commit_plan: Vec::new(),
+            split_pending: None,
+            noise_suggestion: None,
+            noise_offered: None,
+            artifacts_in_diff: Vec::new(),
+            artifacts_warned: Vec::new(),
+            artifact_ignore_checked: false,
+            comment_moves: Vec::new(),
+            reanchoring: None,
+            file_encodings: HashMap::new(),
             search_query_lower: String::new(),
             ai: AiState::default(),
             diff_hash: String::new(),
             branch_diff_hash: String::new(),
             last_ai_check: None,
+            reviewed_mtime: None,
+            reviewed_on_disk: HashMap::new(),
+            addressed: HashMap::new(),
+            checkpoint_view: None,
             comment_textarea: TextArea::default(),
+            comment_drafts: Vec::new(),
             comment_file: String::new(),
             comment_hunk: 0,
             comment_reply_to: None,
@@ -1408,14 +2227,20 @@ impl TabState {
             comment_finding_ref: None,
             comment_author_override: None,
             comment_side: None,
+            comment_file_level: false,
+            review_sort: ai::ReviewSort::Risk,
+            collapsed_categories: HashSet::new(),
+            checklist_edit: None,
             pr_data: None,
             pr_commits: Vec::new(),
             pr_head_ref: None,
+            pr_head_only: false,
             pr_number: Some(pr_ref.number),
             history: None,
             tour: None,
             tour_is_pr: false,
             watched_config: er_config.watched.clone(),
+            test_patterns: er_config.tests.resolved_patterns(),
             watched_files: Vec::new(),
             selected_watched: None,
             show_watched: false,
@@ -1432,11 +2257,17 @@ impl TabState {
             symbol_refs: None,
             pending_unmark_count: 0,
             reviewed_revision: 0,
+            author_scope: None,
+            review_pace: review_pace::ReviewPace::default(),
             committed_unpushed: false,
+            staged_in_app: false,
+            github_pr_node_id: None,
             context_overrides: HashMap::new(),
             remote_repo: Some(repo_slug),
             local_branch_view: None,
             local_branch_checkout_root: None,
+            no_repo: None,
+            review_completed_for: None,
             command_rx: std::collections::HashMap::new(),
             command_status: std::collections::HashMap::new(),
             log_tx: agent_log_tx,
@@ -1472,6 +2303,31 @@ impl TabState {
         let merge_active = git::is_merge_in_progress(&repo_root);
         eprintln!("pr_open phase=cfg_merge ms={}", t_cfg.elapsed().as_millis()); // TEMP diagnostic
         let er_root = ErRoot::RepoLocal(repo_root.clone());
+        let repo_review_hints = config::load_repo_review_hints(&repo_root);
+        let review_phases = review_order::resolve_phases(&repo_root, &er_config);
+        let review_templates = review_templates::resolve_templates(&repo_root, &er_config);
+        let todo_scanner = todos::resolve_scanner(&repo_root, &er_config);
+        let large_files_config = compaction_policy::resolve_large_files(&repo_root, &er_config);
+        let ops_scanner = operational::resolve_scanner(&repo_root, &er_config);
+        let migration_paths = migrations::resolve_paths(&repo_root, &er_config);
+        let checklist_gates = checklist_gates::resolve_gates(&repo_root, &er_config);
+        // GitHub Enterprise: the repo's [github] host, the global one, or origin's
+        let github_host = config::load_repo_github_host(&repo_root)
+            .unwrap_or_else(|| er_config.github.host.clone());
+        crate::github::set_host(&crate::github::resolve_host(&repo_root, &github_host));
+        crate::github::set_remote(&er_config.github.remote);
+        crate::storage::configure_for_repo(&repo_root, &er_config.storage);
+        crate::events::set_hooks(&er_config.hooks);
+        let sqlite_backend = er_config.storage.backend.eq_ignore_ascii_case("sqlite");
+        #[cfg(feature = "sqlite")]
+        crate::review_db::set_enabled(sqlite_backend);
+        #[cfg(not(feature = "sqlite"))]
+        if sqlite_backend {
+            crate::debug_log::warn(
+                "storage",
+                "[storage] backend = \"sqlite\" needs er built with the sqlite feature",
+            );
+        }
 
         let mut tab = TabState {
             mode: DiffMode::Branch,
@@ -1493,10 +2349,49 @@ impl TabState {
             panel: None,
             panel_scroll: 0,
             panel_focus: false,
-            file_tree_width: 32,
+            second_panel: None,
+            second_panel_scroll: 0,
+            jump_list: jump_list::JumpList::default(),
+            marks: BTreeMap::new(),
+            folded_hunks: BTreeMap::new(),
+            view_prefs: None,
+            find: None,
+            release: None,
+            duplicates: Default::default(),
+            complexity: Default::default(),
+            contracts: Default::default(),
+            data_views: Default::default(),
+            assets: Default::default(),
+            infrastructure: Default::default(),
+            compaction_learning: compaction_profile::CompactionLearning::new(
+                large_files_config.learn,
+            ),
+            size_guard: size_guard::SizeGuard::new(large_files_config.guard_lines),
+            file_tree_width: None,
             panel_width: 40,
             focused_comment_id: None,
             focused_finding_id: None,
+            line_flash: None,
+            expanded_findings: HashSet::new(),
+            outbox: None,
+            comment_sync_rx: None,
+            last_comment_sync: Instant::now(),
+            new_comment_files: HashMap::new(),
+            repo_review_hints,
+            review_phases,
+            review_templates,
+            review_template: None,
+            file_ownership: HashMap::new(),
+            ownership_rx: None,
+            related_changes: HashMap::new(),
+            related_changes_rx: None,
+            code_ages: HashMap::new(),
+            code_age_blame: HashMap::new(),
+            code_age_rx: None,
+            pr_head_sync: None,
+            hunk_explanations: HashMap::new(),
+            hunk_explain_rx: None,
+            pre_push_rx: None,
             user_expanded: HashSet::new(),
             review_focus: ReviewFocus::Files,
             review_cursor: 0,
@@ -1506,16 +2401,44 @@ impl TabState {
             filter_input: String::new(),
             filter_history: Vec::new(),
             reviewed: HashMap::new(),
+            skipped: HashMap::new(),
+            assigned: HashMap::new(),
+            multi_selected: HashSet::new(),
             current_per_file_hashes: HashMap::new(),
             show_unreviewed_only: false,
-            sort_by_mtime: false,
+            file_sort: file_sort::FileSort::Diff,
+            focus_mode: false,
             mtime_cache: HashMap::new(),
+            large_files: HashMap::new(),
+            packages: Vec::new(),
+            todo_scanner,
+            todos: Vec::new(),
+            ops_scanner,
+            operational: Vec::new(),
+            migration_paths,
+            checklist_gates,
+            migrations: Vec::new(),
+            commit_plan: Vec::new(),
+            split_pending: None,
+            noise_suggestion: None,
+            noise_offered: None,
+            artifacts_in_diff: Vec::new(),
+            artifacts_warned: Vec::new(),
+            artifact_ignore_checked: false,
+            comment_moves: Vec::new(),
+            reanchoring: None,
+            file_encodings: HashMap::new(),
             search_query_lower: String::new(),
             ai: AiState::default(),
             diff_hash: String::new(),
             branch_diff_hash: String::new(),
             last_ai_check: None,
+            reviewed_mtime: None,
+            reviewed_on_disk: HashMap::new(),
+            addressed: HashMap::new(),
+            checkpoint_view: None,
             comment_textarea: TextArea::default(),
+            comment_drafts: Vec::new(),
             comment_file: String::new(),
             comment_hunk: 0,
             comment_reply_to: None,
@@ -1526,14 +2449,20 @@ impl TabState {
             comment_finding_ref: None,
             comment_author_override: None,
             comment_side: None,
+            comment_file_level: false,
+            review_sort: ai::ReviewSort::Risk,
+            collapsed_categories: HashSet::new(),
+            checklist_edit: None,
             pr_data: None,
             pr_commits: Vec::new(),
             pr_head_ref: None,
+            pr_head_only: false,
             pr_number: None,
             history: None,
             tour: None,
             tour_is_pr: false,
             watched_config,
+            test_patterns: er_config.tests.resolved_patterns(),
             watched_files: Vec::new(),
             selected_watched: None,
             show_watched: has_watched,
@@ -1541,7 +2470,7 @@ impl TabState {
             commit_input: String::new(),
             merge_active,
             unresolved_count: 0,
-            compaction_config: CompactionConfig::default(),
+            compaction_config: large_files_config.compaction(),
             hunk_offsets: None,
             mem_budget: MemoryBudget::default(),
             lazy_mode: false,
@@ -1550,11 +2479,17 @@ impl TabState {
             symbol_refs: None,
             pending_unmark_count: 0,
             reviewed_revision: 0,
+            author_scope: None,
+            review_pace: review_pace::ReviewPace::default(),
             committed_unpushed: false,
+            staged_in_app: false,
+            github_pr_node_id: None,
             context_overrides: HashMap::new(),
             remote_repo: None,
             local_branch_view: None,
             local_branch_checkout_root: None,
+            no_repo: None,
+            review_completed_for: None,
             command_rx: std::collections::HashMap::new(),
             command_status: std::collections::HashMap::new(),
             log_tx: agent_log_tx,
@@ -1572,6 +2507,7 @@ impl TabState {
             pr_refs_fetched: false,
         };
 
+        tab.packages = git::detect_packages(&tab.repo_root);
         let t_fs = Instant::now(); // TEMP diagnostic
         tab.finish_storage_setup();
         eprintln!(
@@ -1619,10 +2555,47 @@ impl TabState {
             panel: None,
             panel_scroll: 0,
             panel_focus: false,
-            file_tree_width: 32,
+            second_panel: None,
+            second_panel_scroll: 0,
+            jump_list: jump_list::JumpList::default(),
+            marks: BTreeMap::new(),
+            folded_hunks: BTreeMap::new(),
+            view_prefs: None,
+            find: None,
+            release: None,
+            duplicates: Default::default(),
+            complexity: Default::default(),
+            contracts: Default::default(),
+            data_views: Default::default(),
+            assets: Default::default(),
+            infrastructure: Default::default(),
+            compaction_learning: Default::default(),
+            size_guard: Default::default(),
+            file_tree_width: None,
             panel_width: 40,
             focused_comment_id: None,
             focused_finding_id: None,
+            line_flash: None,
+            expanded_findings: HashSet::new(),
+            outbox: None,
+            comment_sync_rx: None,
+            last_comment_sync: Instant::now(),
+            new_comment_files: HashMap::new(),
+            repo_review_hints: BTreeMap::new(),
+            review_phases: Vec::new(),
+            review_templates: Vec::new(),
+            review_template: None,
+            file_ownership: HashMap::new(),
+            ownership_rx: None,
+            related_changes: HashMap::new(),
+            related_changes_rx: None,
+            code_ages: HashMap::new(),
+            code_age_blame: HashMap::new(),
+            code_age_rx: None,
+            pr_head_sync: None,
+            hunk_explanations: HashMap::new(),
+            hunk_explain_rx: None,
+            pre_push_rx: None,
             user_expanded: HashSet::new(),
             review_focus: ReviewFocus::Files,
             review_cursor: 0,
@@ -1632,16 +2605,44 @@ impl TabState {
             filter_input: String::new(),
             filter_history: Vec::new(),
             reviewed: HashMap::new(),
+            skipped: HashMap::new(),
+            assigned: HashMap::new(),
+            multi_selected: HashSet::new(),
             current_per_file_hashes: HashMap::new(),
             show_unreviewed_only: false,
-            sort_by_mtime: false,
+            file_sort: file_sort::FileSort::Diff,
+            focus_mode: false,
             mtime_cache: HashMap::new(),
+            large_files: HashMap::new(),
+            packages: Vec::new(),
+            todo_scanner: git::TodoScanner::default(),
+            todos: Vec::new(),
+            ops_scanner: Some(git::OpsScanner::default()),
+            operational: Vec::new(),
+            migration_paths: config::MigrationsConfig::default().paths,
+            checklist_gates: BTreeMap::new(),
+            migrations: Vec::new(),
+            commit_plan: Vec::new(),
+            split_pending: None,
+            noise_suggestion: None,
+            noise_offered: None,
+            artifacts_in_diff: Vec::new(),
+            artifacts_warned: Vec::new(),
+            artifact_ignore_checked: false,
+            comment_moves: Vec::new(),
+            reanchoring: None,
+            file_encodings: HashMap::new(),
             search_query_lower: String::new(),
             ai: AiState::default(),
             diff_hash: String::new(),
             branch_diff_hash: String::new(),
             last_ai_check: None,
+            reviewed_mtime: None,
+            reviewed_on_disk: HashMap::new(),
+            addressed: HashMap::new(),
+            checkpoint_view: None,
             comment_textarea: TextArea::default(),
+            comment_drafts: Vec::new(),
             comment_file: String::new(),
             comment_hunk: 0,
             comment_reply_to: None,
@@ -1652,14 +2653,20 @@ impl TabState {
             comment_finding_ref: None,
             comment_author_override: None,
             comment_side: None,
+            comment_file_level: false,
+            review_sort: ai::ReviewSort::Risk,
+            collapsed_categories: HashSet::new(),
+            checklist_edit: None,
             pr_data: None,
             pr_commits: Vec::new(),
             pr_head_ref: None,
+            pr_head_only: false,
             pr_number: None,
             history: None,
             tour: None,
             tour_is_pr: false,
             watched_config: WatchedConfig::default(),
+            test_patterns: config::TestsConfig::default().resolved_patterns(),
             watched_files: Vec::new(),
             selected_watched: None,
             show_watched: false,
@@ -1676,11 +2683,17 @@ impl TabState {
             symbol_refs: None,
             pending_unmark_count: 0,
             reviewed_revision: 0,
+            author_scope: None,
+            review_pace: review_pace::ReviewPace::default(),
             committed_unpushed: false,
+            staged_in_app: false,
+            github_pr_node_id: None,
             context_overrides: HashMap::new(),
             remote_repo: None,
             local_branch_view: None,
             local_branch_checkout_root: None,
+            no_repo: None,
+            review_completed_for: None,
             command_rx: std::collections::HashMap::new(),
             command_status: std::collections::HashMap::new(),
             log_tx: agent_log_tx,
@@ -1700,8 +2713,9 @@ impl TabState {
     }
 
     /// Resize the file tree panel by `delta` columns, enforcing min/max and diff minimum.
-    pub fn resize_file_tree(&mut self, delta: i16, terminal_width: u16) {
-        let new_width = (self.file_tree_width as i16 + delta).clamp(16, 60) as u16;
+    pub fn resize_file_tree(&mut self, delta: i16, terminal_width: u16, layout: &LayoutConfig) {
+        let current = crate::app::layout::tree_width(self, terminal_width, layout);
+        let new_width = (current as i16 + delta).clamp(16, 60) as u16;
         let panel_w = if self.panel.is_some() {
             self.panel_width
         } else {
@@ -1709,14 +2723,15 @@ impl TabState {
         };
         let diff_remaining = terminal_width.saturating_sub(new_width + panel_w);
         if diff_remaining >= 20 {
-            self.file_tree_width = new_width;
+            self.file_tree_width = Some(new_width);
         }
     }
 
     /// Resize the side panel by `delta` columns, enforcing min/max and diff minimum.
-    pub fn resize_panel(&mut self, delta: i16, terminal_width: u16) {
+    pub fn resize_panel(&mut self, delta: i16, terminal_width: u16, layout: &LayoutConfig) {
         let new_width = (self.panel_width as i16 + delta).clamp(24, 80) as u16;
-        let diff_remaining = terminal_width.saturating_sub(self.file_tree_width + new_width);
+        let tree = crate::app::layout::tree_width(self, terminal_width, layout);
+        let diff_remaining = terminal_width.saturating_sub(tree + new_width);
         if diff_remaining >= 20 {
             self.panel_width = new_width;
         }
@@ -1725,11 +2740,18 @@ impl TabState {
     /// Short name for display in tab bar.
     ///
     /// Priority:
-    /// 1. Local-branch view → that branch name.
-    /// 2. Remote PR → `repo#N` (or `repo` if no PR number).
-    /// 3. Working tab → `current_branch` if non-empty.
-    /// 4. Fallback → repo directory basename.
+    /// 1. Tag range → `from…to`.
+    /// 2. Local-branch view → that branch name.
+    /// 3. Remote PR → `repo#N` (or `repo` if no PR number).
+    /// 4. Working tab → `current_branch` if non-empty.
+    /// 5. Fallback → repo directory basename.
     pub fn tab_name(&self) -> String {
+        if let Some(name) = self.no_repo_name() {
+            return name;
+        }
+        if let Some(ref range) = self.release {
+            return range.label();
+        }
         if let Some(ref branch) = self.local_branch_view {
             return branch.clone();
         }
@@ -1826,6 +2848,10 @@ impl TabState {
         if self.is_remote() {
             return vec![DiffMode::PrDiff];
         }
+        // A patch has one diff, and the start tab none
+        if self.is_repoless() {
+            return vec![DiffMode::Branch];
+        }
         // PR tabs without a checked-out head branch have no working tree to
         // review either — their Branch mode would just re-show `gh pr diff`
         // (the PR parity diff), which is what PR Diff already shows. So PR
@@ -1957,7 +2983,25 @@ impl TabState {
     /// Point this tab at managed storage (shared by TUI and Desktop).
     /// Routes to a per-bucket directory so review notes are isolated per view.
     pub fn apply_managed_root(&mut self) {
+        // Set once, when the patch was opened
+        if self.is_repoless() {
+            return;
+        }
         if crate::storage::use_repo_local_storage() {
+            // Switching to `[storage] location = "repo"` brings the working
+            // tree's branch review along
+            if crate::storage::location() == crate::storage::StorageLocation::Repo
+                && self.remote_repo.is_none()
+                && self.local_branch_view.is_none()
+                && !self.current_branch.is_empty()
+            {
+                let branch_slug = crate::storage::slug_branch(&self.current_branch);
+                if let Err(e) =
+                    crate::storage::migrate_into_repo_local(&self.repo_root, &branch_slug)
+                {
+                    crate::debug_log::warn("storage", format!("migrate into .er: {}", e));
+                }
+            }
             self.er_root = ErRoot::RepoLocal(self.repo_root.clone());
             return;
         }
@@ -1989,8 +3033,10 @@ impl TabState {
 
         // Local view buckets (branch/unstaged/staged/history)
         let branch = self
-            .local_branch_view
-            .clone()
+            .release
+            .as_ref()
+            .map(|r| r.label())
+            .or_else(|| self.local_branch_view.clone())
             .unwrap_or_else(|| self.current_branch.clone());
         if branch.is_empty() || self.repo_root.is_empty() {
             return;
@@ -2009,7 +3055,10 @@ impl TabState {
     /// Apply managed storage, migrate legacy paths, and load reviewed markers.
     pub fn finish_storage_setup(&mut self) {
         self.apply_managed_root();
-        self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+        self.load_reviewed_files();
+        self.load_skipped();
+        self.load_assigned();
+        self.load_compaction_profile();
     }
 
     /// Re-resolve managed storage for this tab's branch/PR and reload sidecars.
@@ -2020,7 +3069,9 @@ impl TabState {
     /// views must sync again once the viewed branch is known.
     pub fn sync_managed_storage(&mut self) {
         self.apply_managed_root();
-        self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+        self.load_reviewed_files();
+        self.load_skipped();
+        self.load_assigned();
         if !self.active_diff_files().is_empty() {
             self.prune_reviewed_not_in_diff();
         }
@@ -2135,7 +3186,7 @@ impl TabState {
             let base = self.base_branch.clone();
             let base_ref = crate::github::fetch_base_branch_ref(
                 &self.repo_root,
-                base.trim_start_matches("origin/"),
+                crate::github::strip_remote_prefix(&self.repo_root, &base),
             )?;
             // Record the oid the diff is computed against so the desktop
             // freshness check has a baseline on the normal open path (not just
@@ -2148,7 +3199,9 @@ impl TabState {
 
         self.mode = DiffMode::PrDiff;
         self.apply_managed_root();
-        self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+        self.load_reviewed_files();
+        self.load_skipped();
+        self.load_assigned();
         self.reload_ai_state();
 
         if skip_refresh_if_loaded && !self.files.is_empty() {
@@ -2185,13 +3238,31 @@ impl TabState {
 
         self.mode = DiffMode::PrDiff;
         self.apply_managed_root();
-        self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+        self.load_reviewed_files();
+        self.load_skipped();
+        self.load_assigned();
         self.reload_ai_state();
         Ok(())
     }
 
     // ── Diff ──
 
+    /// Where this tab's events happened (see [`crate::events`]).
+    pub fn event_context(&self) -> crate::events::EventContext {
+        crate::events::EventContext {
+            repo_root: self.repo_root.clone(),
+            branch: self
+                .local_branch_view
+                .clone()
+                .unwrap_or_else(|| self.current_branch.clone()),
+            pr: self.pr_number,
+        }
+    }
+
+    pub fn publish_event(&self, event: crate::events::Event) {
+        crate::events::publish(&self.event_context(), event);
+    }
+
     /// Re-run git diff and update the file list
     pub fn refresh_diff(&mut self) -> Result<()> {
         self.refresh_diff_impl(true, true)
@@ -2238,7 +3309,7 @@ impl TabState {
             // Best-effort: if the fetch fails (e.g. offline), keep the existing
             // base ref and still recompute the diff rather than erroring out.
             let base = self.base_branch.clone();
-            let base_short = base.strip_prefix("origin/").unwrap_or(&base);
+            let base_short = crate::github::strip_remote_prefix(&self.repo_root, &base);
             let t = Instant::now();
             match crate::github::fetch_base_branch_ref(&self.repo_root, base_short) {
                 Ok(resolved_base) => self.base_branch = resolved_base,
@@ -2285,11 +3356,9 @@ impl TabState {
             .clone()
             .ok_or_else(|| anyhow::anyhow!("No local branch view for this tab"))?;
 
-        let base_short = self
-            .base_branch
-            .strip_prefix("origin/")
-            .unwrap_or(&self.base_branch);
-        let base_candidates = [format!("origin/{base_short}"), base_short.to_string()];
+        let base_short = crate::github::strip_remote_prefix(&self.repo_root, &self.base_branch);
+        let remote = crate::github::forge_remote(&self.repo_root);
+        let base_candidates = [format!("{remote}/{base_short}"), base_short.to_string()];
         let resolved_base = base_candidates
             .into_iter()
             .find(|candidate| crate::github::ref_exists_locally(&self.repo_root, candidate))
@@ -2521,9 +3590,37 @@ impl TabState {
     }
 
     fn refresh_diff_impl(&mut self, recompute_branch_hash: bool, auto_unmark: bool) -> Result<()> {
+        self.refresh_diff_inner(recompute_branch_hash, auto_unmark)?;
+        let (adds, dels) = self
+            .files
+            .iter()
+            .fold((0, 0), |(a, d), f| (a + f.adds, d + f.dels));
+        self.publish_event(crate::events::Event::DiffRefreshed {
+            files: self.files.len(),
+            adds,
+            dels,
+            diff_hash: self.diff_hash.clone(),
+        });
+        Ok(())
+    }
+
+    fn refresh_diff_inner(&mut self, recompute_branch_hash: bool, auto_unmark: bool) -> Result<()> {
+        let _profile = crate::profile::scope("refresh diff");
         let t_total = Instant::now();
 
+        if self.is_repoless() {
+            return self.refresh_no_repo();
+        }
         self.sync_storage_if_checkout_branch_changed()?;
+        // HEAD may have moved; `App::poll_pr_head_sync` recomputes
+        self.pr_head_sync = None;
+
+        // Checkpoint view swaps the branch diff for the interdiff
+        if self.mode == DiffMode::Branch {
+            if let Some(view) = self.checkpoint_view.clone() {
+                return self.refresh_checkpoint_diff(&view);
+            }
+        }
 
         // History mode doesn't use git_diff_raw — skip normal diff refresh
         if self.mode == DiffMode::History {
@@ -2717,6 +3814,10 @@ impl TabState {
         } else {
             self.fetch_tab_raw_diff(self.mode.fetch_scope())?
         };
+        // A huge diff waits for the Huge Diff hub (see `size_guard`)
+        let Some(raw) = self.guard_diff_size(raw) else {
+            return Ok(());
+        };
 
         // Decide parsing strategy based on diff size.
         // Use byte-length heuristic (O(1)) instead of counting newlines (O(n)).
@@ -2768,16 +3869,27 @@ impl TabState {
             }
         }
 
+        // Before compaction empties the oversized files' hunks
+        self.refresh_todos();
+        self.refresh_operational();
+        self.refresh_migrations();
+        self.merge_checklist_gates();
+        self.refresh_noise_suggestion();
+        self.refresh_artifacts_in_diff();
+        self.code_ages.clear();
+        self.compact_large_files();
+        self.refresh_file_encodings();
+        self.refresh_packages_if_manifests_changed();
+
         // Clear per-file context overrides — diff content has changed
         self.context_overrides.clear();
 
-        if self.sort_by_mtime {
-            self.sort_files_by_mtime();
-        }
-
         // Refresh mtime cache once per diff load (avoids per-frame fs::metadata calls)
         self.refresh_mtime_cache();
 
+        self.refresh_author_scope();
+        self.sort_files();
+
         // Update memory budget
         self.update_mem_budget();
 
@@ -3013,6 +4125,9 @@ impl TabState {
         let er_dir = self.er_dir();
         let branch_scope = self.storage_branch_scope().map(str::to_string);
         let prev_tour_stale = self.ai.tour_stale;
+        let cursor_file = (self.review_focus == ReviewFocus::Files)
+            .then(|| self.review_file_at_cursor())
+            .flatten();
         self.ai = ai::load_ai_state(&er_dir, &self.branch_diff_hash, branch_scope.as_deref());
 
         // GitHub PR comments are PR-scoped: stored in the shared PR bucket and shown
@@ -3031,18 +4146,12 @@ impl TabState {
                     // er_dir then). The PR bucket is now authoritative, so migrate any
                     // such local comments into it instead of discarding them.
                     let orphaned = self.ai.github_comments.take();
-                    let gh_path = std::path::Path::new(&gh_dir).join("github-comments.json");
-                    let mut pr_comments = std::fs::read_to_string(&gh_path)
+                    let mut pr_comments = ai::store::load::<ai::ErGitHubComments>(&gh_dir)
                         .ok()
-                        .and_then(|c| serde_json::from_str::<ai::ErGitHubComments>(&c).ok());
+                        .flatten();
                     if Self::migrate_orphaned_github_comments(orphaned, &mut pr_comments) {
                         if let Some(ref gc) = pr_comments {
-                            let _ = std::fs::create_dir_all(&gh_dir);
-                            if let Ok(json) = serde_json::to_string_pretty(gc) {
-                                let tmp = format!("{}.tmp", gh_path.to_string_lossy());
-                                let _ = std::fs::write(&tmp, json)
-                                    .and_then(|_| std::fs::rename(&tmp, &gh_path));
-                            }
+                            let _ = ai::store::save(&gh_dir, gc);
                         }
                         // Drop the orphan file so the migration runs only once.
                         let orphan_path =
@@ -3075,34 +4184,76 @@ impl TabState {
                 prev_tour_stale,
             ),
         };
-        // Finding IDs may change after review reload — clear stale reference
-        self.focused_finding_id = None;
+        // Keep UI references to what the reload kept, by id; drop the rest
+        self.drop_dangling_ai_refs();
+        if let Some(path) = cursor_file {
+            if let Some(idx) = self
+                .ai
+                .review_file_entries(self.review_sort)
+                .iter()
+                .position(|(p, _)| **p == path)
+            {
+                self.review_cursor = idx;
+            }
+        }
         // Preserve per-file staleness across .er-* file reloads (recomputed in refresh_diff)
         if self.ai.is_stale {
             self.ai.stale_files = prev_stale_files;
         }
         // Clamp cursor to valid range after reload (item count may have decreased)
-        let item_count = match self.review_focus {
-            ReviewFocus::Files => self.ai.review_file_count(),
-            ReviewFocus::Checklist => self.ai.review_checklist_count(),
-        };
+        let item_count = self.review_item_count();
         // review_cursor stays 0 for an empty list; consumers must still guard
         // item_count == 0 before indexing.
         let max_cursor = if item_count == 0 { 0 } else { item_count - 1 };
         self.review_cursor = self.review_cursor.min(max_cursor);
         self.last_ai_check = ai::latest_er_mtime(&er_dir);
+        self.outbox = crate::outbox::Outbox::load(&self.github_comments_dir());
+        self.merge_duplicate_findings();
+        self.merge_complexity_findings();
+        self.merge_infra_findings();
+        self.merge_migration_findings();
+        self.merge_checklist_gates();
+    }
+
+    /// Clear focus, expansion and "addressed" entries whose finding or
+    /// comment is gone after a reload.
+    fn drop_dangling_ai_refs(&mut self) {
+        let finding_ids: HashSet<String> = self
+            .ai
+            .review
+            .iter()
+            .flat_map(|r| r.files.values())
+            .flat_map(|fr| fr.findings.iter())
+            .map(|f| f.id.clone())
+            .collect();
+        if self
+            .focused_finding_id
+            .as_ref()
+            .is_some_and(|id| !finding_ids.contains(id))
+        {
+            self.focused_finding_id = None;
+        }
+        if self
+            .focused_comment_id
+            .as_ref()
+            .is_some_and(|id| self.ai.find_comment(id).is_none())
+        {
+            self.focused_comment_id = None;
+        }
+        self.expanded_findings.retain(|id| finding_ids.contains(id));
+        let ai = &self.ai;
+        self.addressed
+            .retain(|id, _| finding_ids.contains(id) || ai.find_comment(id).is_some());
     }
 
     /// Reload github comments from cache in remote mode.
     /// Unlike reload_ai_state() which reads from .er/, this reads from the remote cache dir.
     pub fn reload_remote_comments(&mut self) {
-        let path = self.github_comments_path();
-        if let Ok(content) = std::fs::read_to_string(&path) {
-            if let Ok(gc) = serde_json::from_str::<ai::ErGitHubComments>(&content) {
-                self.ai.github_comments = Some(gc);
-                self.ai.rebuild_comment_index();
-            }
+        if let Ok(Some(gc)) = ai::store::load::<ai::ErGitHubComments>(&self.github_comments_dir()) {
+            self.ai.github_comments = Some(gc);
+            self.ai.rebuild_comment_index();
         }
+        self.outbox = crate::outbox::Outbox::load(&self.github_comments_dir());
     }
 
     /// Relocate all comments to their new positions after a diff change.
@@ -3141,6 +4292,9 @@ impl TabState {
             Some(idx)
         };
 
+        // Lost comments whose code turned up in another file, to offer moving
+        let mut moves = Vec::new();
+
         // Process questions
         let questions_changed = if let Some(ref mut qs) = self.ai.questions {
             let mut changed = false;
@@ -3153,24 +4307,24 @@ impl TabState {
                     q.relocated_at_hash = current_hash.clone();
                     continue;
                 }
-                let result = if let Some(idx) = find_file(&q.file) {
-                    let anchor = ai::CommentAnchor {
-                        file: q.file.clone(),
-                        hunk_index: q.hunk_index,
-                        line_start: q.line_start,
-                        line_content: q.line_content.clone(),
-                        context_before: q.context_before.clone(),
-                        context_after: q.context_after.clone(),
-                        old_line_start: q.old_line_start,
-                        hunk_header: q.hunk_header.clone(),
-                    };
-                    ai::relocate_comment(&anchor, &self.files[idx])
-                } else {
-                    ai::RelocationResult::Lost
+                let anchor = ai::CommentAnchor {
+                    file: q.file.clone(),
+                    hunk_index: q.hunk_index,
+                    line_start: q.line_start,
+                    line_content: q.line_content.clone(),
+                    context_before: q.context_before.clone(),
+                    context_after: q.context_after.clone(),
+                    old_line_start: q.old_line_start,
+                    hunk_header: q.hunk_header.clone(),
+                };
+                let result = match find_file(&q.file) {
+                    Some(idx) => ai::relocate_comment(&anchor, &self.files[idx]),
+                    None => ai::RelocationResult::Lost,
                 };
                 match result {
                     ai::RelocationResult::Unchanged => {
                         q.anchor_status = "original".to_string();
+                        q.anchor_confidence = None;
                         q.relocated_at_hash = current_hash.clone();
                         q.stale = false;
                         changed = true;
@@ -3178,19 +4332,25 @@ impl TabState {
                     ai::RelocationResult::Relocated {
                         new_hunk_index,
                         new_line_start,
+                        confidence,
                     } => {
                         q.hunk_index = Some(new_hunk_index);
                         q.line_start = Some(new_line_start);
                         q.anchor_status = "relocated".to_string();
+                        q.anchor_confidence = Some(confidence);
                         q.relocated_at_hash = current_hash.clone();
                         q.stale = false;
                         changed = true;
                     }
                     ai::RelocationResult::Lost => {
                         q.anchor_status = "lost".to_string();
+                        q.anchor_confidence = None;
                         q.stale = true;
                         q.relocated_at_hash = current_hash.clone();
                         changed = true;
+                        if q.in_reply_to.is_none() {
+                            moves.extend(CommentMove::find(&q.id, &anchor, &self.files));
+                        }
                     }
                 }
             }
@@ -3210,24 +4370,24 @@ impl TabState {
                     n.relocated_at_hash = current_hash.clone();
                     continue;
                 }
-                let result = if let Some(idx) = find_file(&n.file) {
-                    let anchor = ai::CommentAnchor {
-                        file: n.file.clone(),
-                        hunk_index: n.hunk_index,
-                        line_start: n.line_start,
-                        line_content: n.line_content.clone(),
-                        context_before: n.context_before.clone(),
-                        context_after: n.context_after.clone(),
-                        old_line_start: n.old_line_start,
-                        hunk_header: n.hunk_header.clone(),
-                    };
-                    ai::relocate_comment(&anchor, &self.files[idx])
-                } else {
-                    ai::RelocationResult::Lost
+                let anchor = ai::CommentAnchor {
+                    file: n.file.clone(),
+                    hunk_index: n.hunk_index,
+                    line_start: n.line_start,
+                    line_content: n.line_content.clone(),
+                    context_before: n.context_before.clone(),
+                    context_after: n.context_after.clone(),
+                    old_line_start: n.old_line_start,
+                    hunk_header: n.hunk_header.clone(),
+                };
+                let result = match find_file(&n.file) {
+                    Some(idx) => ai::relocate_comment(&anchor, &self.files[idx]),
+                    None => ai::RelocationResult::Lost,
                 };
                 match result {
                     ai::RelocationResult::Unchanged => {
                         n.anchor_status = "original".to_string();
+                        n.anchor_confidence = None;
                         n.relocated_at_hash = current_hash.clone();
                         n.stale = false;
                         changed = true;
@@ -3235,19 +4395,25 @@ impl TabState {
                     ai::RelocationResult::Relocated {
                         new_hunk_index,
                         new_line_start,
+                        confidence,
                     } => {
                         n.hunk_index = Some(new_hunk_index);
                         n.line_start = Some(new_line_start);
                         n.anchor_status = "relocated".to_string();
+                        n.anchor_confidence = Some(confidence);
                         n.relocated_at_hash = current_hash.clone();
                         n.stale = false;
                         changed = true;
                     }
                     ai::RelocationResult::Lost => {
                         n.anchor_status = "lost".to_string();
+                        n.anchor_confidence = None;
                         n.stale = true;
                         n.relocated_at_hash = current_hash.clone();
                         changed = true;
+                        if n.in_reply_to.is_none() {
+                            moves.extend(CommentMove::find(&n.id, &anchor, &self.files));
+                        }
                     }
                 }
             }
@@ -3271,24 +4437,24 @@ impl TabState {
                     c.relocated_at_hash = current_hash.clone();
                     continue;
                 }
-                let result = if let Some(idx) = find_file(&c.file) {
-                    let anchor = ai::CommentAnchor {
-                        file: c.file.clone(),
-                        hunk_index: c.hunk_index,
-                        line_start: c.line_start,
-                        line_content: c.line_content.clone(),
-                        context_before: c.context_before.clone(),
-                        context_after: c.context_after.clone(),
-                        old_line_start: c.old_line_start,
-                        hunk_header: c.hunk_header.clone(),
-                    };
-                    ai::relocate_comment(&anchor, &self.files[idx])
-                } else {
-                    ai::RelocationResult::Lost
+                let anchor = ai::CommentAnchor {
+                    file: c.file.clone(),
+                    hunk_index: c.hunk_index,
+                    line_start: c.line_start,
+                    line_content: c.line_content.clone(),
+                    context_before: c.context_before.clone(),
+                    context_after: c.context_after.clone(),
+                    old_line_start: c.old_line_start,
+                    hunk_header: c.hunk_header.clone(),
+                };
+                let result = match find_file(&c.file) {
+                    Some(idx) => ai::relocate_comment(&anchor, &self.files[idx]),
+                    None => ai::RelocationResult::Lost,
                 };
                 match result {
                     ai::RelocationResult::Unchanged => {
                         c.anchor_status = "original".to_string();
+                        c.anchor_confidence = None;
                         c.relocated_at_hash = current_hash.clone();
                         c.stale = false;
                         changed = true;
@@ -3296,19 +4462,25 @@ impl TabState {
                     ai::RelocationResult::Relocated {
                         new_hunk_index,
                         new_line_start,
+                        confidence,
                     } => {
                         c.hunk_index = Some(new_hunk_index);
                         c.line_start = Some(new_line_start);
                         c.anchor_status = "relocated".to_string();
+                        c.anchor_confidence = Some(confidence);
                         c.relocated_at_hash = current_hash.clone();
                         c.stale = false;
                         changed = true;
                     }
                     ai::RelocationResult::Lost => {
                         c.anchor_status = "lost".to_string();
+                        c.anchor_confidence = None;
                         c.stale = true;
                         c.relocated_at_hash = current_hash.clone();
                         changed = true;
+                        if c.github_id.is_none() {
+                            moves.extend(CommentMove::find(&c.id, &anchor, &self.files));
+                        }
                     }
                 }
             }
@@ -3317,34 +4489,23 @@ impl TabState {
             false
         };
 
+        self.merge_comment_moves(moves);
+
         // Write back to disk if anything changed. Write failures are ignored — the
         // next refresh re-relocates the same comments from scratch.
         if questions_changed {
             if let Some(ref qs) = self.ai.questions {
-                let path = format!("{}/questions.json", self.er_dir());
-                if let Ok(json) = serde_json::to_string_pretty(qs) {
-                    let tmp = format!("{}.tmp", path);
-                    let _ = std::fs::write(&tmp, json).and_then(|_| std::fs::rename(&tmp, &path));
-                }
+                let _ = ai::store::save(&self.er_dir(), qs);
             }
         }
         if notes_changed {
             if let Some(ref ns) = self.ai.notes {
-                let path = format!("{}/notes.json", self.er_dir());
-                if let Ok(json) = serde_json::to_string_pretty(ns) {
-                    let tmp = format!("{}.tmp", path);
-                    let _ = std::fs::write(&tmp, json).and_then(|_| std::fs::rename(&tmp, &path));
-                }
+                let _ = ai::store::save(&self.er_dir(), ns);
             }
         }
         if comments_changed {
             if let Some(ref gc) = self.ai.github_comments {
-                let path = self.github_comments_path();
-                if let Ok(json) = serde_json::to_string_pretty(gc) {
-                    let _ = std::fs::create_dir_all(self.github_comments_dir());
-                    let tmp = format!("{}.tmp", path);
-                    let _ = std::fs::write(&tmp, json).and_then(|_| std::fs::rename(&tmp, &path));
-                }
+                let _ = ai::store::save(&self.github_comments_dir(), gc);
             }
         }
 
@@ -3379,6 +4540,12 @@ impl TabState {
 
     /// Check if .er-* files have been updated since last load (called on tick)
     pub fn check_ai_files_changed(&mut self) -> bool {
+        self.check_ai_files_delta().is_some()
+    }
+
+    /// Reload the AI data when .er-* files were updated since the last
+    /// load, and say what changed; `None` when nothing was reloaded.
+    pub fn check_ai_files_delta(&mut self) -> Option<ai::AiDelta> {
         let latest_mtime = ai::latest_er_mtime(&self.er_dir());
 
         let should_reload = match latest_mtime {
@@ -3390,11 +4557,54 @@ impl TabState {
             None => self.last_ai_check.is_some(),
         };
 
-        if should_reload {
+        let reviewed_changed = self.check_reviewed_file_changed();
+
+        let mut delta = if should_reload {
+            let before = ai::AiSnapshot::of(&self.ai);
+            // Same sidecars rewritten: findings keep their lifecycle by id
+            let lifecycles = ai::FindingLifecycles::of(&self.ai);
             self.reload_ai_state();
-            return true;
+            if lifecycles.restore(&mut self.ai) > 0 {
+                self.ai.rebuild_comment_index();
+            }
+            ai::AiDelta::between(&before, &ai::AiSnapshot::of(&self.ai))
+        } else if reviewed_changed {
+            ai::AiDelta::default()
+        } else {
+            return None;
+        };
+        delta.reviewed_changed = reviewed_changed;
+        Some(delta)
+    }
+
+    /// Directories holding the files [`Self::check_ai_files_delta`] reads,
+    /// for a file watcher to follow.
+    pub fn ai_watch_dirs(&self) -> Vec<String> {
+        let mut dirs = vec![self.er_dir()];
+        if let Some(parent) = std::path::Path::new(&self.er_root.reviewed_path()).parent() {
+            let parent = parent.to_string_lossy().into_owned();
+            if !dirs.contains(&parent) {
+                dirs.push(parent);
+            }
+        }
+        dirs
+    }
+
+    /// Re-read reviewed markers when the `reviewed` file changed on disk.
+    fn check_reviewed_file_changed(&mut self) -> bool {
+        let path = self.er_root.reviewed_path();
+        let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if mtime == self.reviewed_mtime {
+            return false;
         }
-        false
+        self.reviewed_mtime = mtime;
+        let reviewed = Self::load_reviewed_files_from_path(&path);
+        self.reviewed_on_disk = reviewed.clone();
+        if reviewed == self.reviewed {
+            return false;
+        }
+        self.reviewed = reviewed;
+        true
     }
 
     // ── Layer toggles ──
@@ -3417,11 +4627,14 @@ impl TabState {
 
     /// Forward cycle order for the side panel. `FileDetail` and `AgentLog` are
     /// always available; the others are skipped when their data is absent.
-    const PANEL_CYCLE: [PanelContent; 5] = [
+    const PANEL_CYCLE: [PanelContent; 8] = [
         PanelContent::FileDetail,
         PanelContent::AiSummary,
         PanelContent::PrOverview,
         PanelContent::SymbolRefs,
+        PanelContent::Operational,
+        PanelContent::Assets,
+        PanelContent::RelatedChanges,
         PanelContent::AgentLog,
     ];
 
@@ -3429,46 +4642,128 @@ impl TabState {
         match panel {
             PanelContent::FileDetail | PanelContent::AgentLog => true,
             PanelContent::AiSummary => self.layers.show_ai_findings && self.ai.has_data(),
-            PanelContent::PrOverview => self.pr_data.is_some(),
+            PanelContent::PrOverview => self.pr_data.is_some() || self.release_notes().is_some(),
             PanelContent::SymbolRefs => self.symbol_refs.is_some(),
+            PanelContent::Operational => !self.operational.is_empty(),
+            PanelContent::Assets => !self.assets.assets.is_empty(),
+            PanelContent::RelatedChanges => {
+                !self.is_remote() && self.selected_diff_file().is_some()
+            }
         }
     }
 
-    /// Cycle panel: None → FileDetail → AiSummary (if AI data) → PrOverview (if PR live) → SymbolRefs (if symbols) → AgentLog → None
+    /// Cycle panel: None → FileDetail → AiSummary (if AI data) → PrOverview (if PR live) → SymbolRefs (if symbols) → Operational (if ops changes) → Assets (if images changed) → RelatedChanges (if a local file is selected) → AgentLog → None
     pub fn toggle_panel(&mut self) {
         self.cycle_panel(true);
     }
 
-    /// Cycle panel in reverse: None → AgentLog → SymbolRefs → PrOverview → AiSummary → FileDetail → None
+    /// Cycle panel in reverse: None → AgentLog → RelatedChanges → Assets → Operational → SymbolRefs → PrOverview → AiSummary → FileDetail → None
     pub fn toggle_panel_reverse(&mut self) {
         self.cycle_panel(false);
     }
 
     /// Move to the next available panel in the cycle direction, closing the
-    /// panel after the last one.
+    /// panel after the last one. The pinned second panel is skipped, and
+    /// takes over when the first one closes.
     fn cycle_panel(&mut self, forward: bool) {
         let cycle = &Self::PANEL_CYCLE;
         let pos = self.panel.and_then(|p| cycle.iter().position(|&c| c == p));
+        let shown = |p: PanelContent| self.panel_available(p) && self.second_panel != Some(p);
         self.panel = match (forward, pos) {
-            (true, None) => cycle.iter().copied().find(|&p| self.panel_available(p)),
-            (true, Some(i)) => cycle[i + 1..]
-                .iter()
-                .copied()
-                .find(|&p| self.panel_available(p)),
-            (false, None) => cycle
-                .iter()
-                .rev()
-                .copied()
-                .find(|&p| self.panel_available(p)),
-            (false, Some(i)) => cycle[..i]
-                .iter()
-                .rev()
-                .copied()
-                .find(|&p| self.panel_available(p)),
+            (true, None) => cycle.iter().copied().find(|&p| shown(p)),
+            (true, Some(i)) => cycle[i + 1..].iter().copied().find(|&p| shown(p)),
+            (false, None) => cycle.iter().rev().copied().find(|&p| shown(p)),
+            (false, Some(i)) => cycle[..i].iter().rev().copied().find(|&p| shown(p)),
+        };
+        self.panel_scroll = 0;
+        if self.panel.is_none() {
+            if let Some(second) = self.second_panel.take() {
+                self.panel = Some(second);
+                self.panel_scroll = std::mem::take(&mut self.second_panel_scroll);
+            } else {
+                self.panel_focus = false;
+            }
+        }
+    }
+
+    /// Alt+p: pin the open panel and open the next one beside it, or close
+    /// the pinned one when two are open.
+    pub fn toggle_pin_panel(&mut self) {
+        if self.second_panel.take().is_some() {
+            self.second_panel_scroll = 0;
+            return;
+        }
+        let Some(current) = self.panel else {
+            return;
         };
+        self.second_panel = Some(current);
+        self.second_panel_scroll = self.panel_scroll;
+        self.panel = None;
+        // Continue the cycle from the pinned panel
+        let cycle = &Self::PANEL_CYCLE;
+        let start = cycle.iter().position(|&c| c == current).unwrap_or(0);
+        self.panel = cycle
+            .iter()
+            .cycle()
+            .skip(start + 1)
+            .take(cycle.len() - 1)
+            .copied()
+            .find(|&p| self.panel_available(p));
         self.panel_scroll = 0;
         if self.panel.is_none() {
+            self.panel = self.second_panel.take();
+            self.panel_scroll = self.second_panel_scroll;
+        }
+    }
+
+    /// The open panels in drawing order (the cycle order), at most two.
+    pub fn open_panels(&self) -> Vec<PanelContent> {
+        let rank = |p: PanelContent| Self::PANEL_CYCLE.iter().position(|&c| c == p);
+        let mut open: Vec<PanelContent> = self.panel.into_iter().collect();
+        if let Some(second) = self.second_panel.filter(|&s| Some(s) != self.panel) {
+            open.push(second);
+        }
+        open.sort_by_key(|&p| rank(p));
+        open
+    }
+
+    /// Tab with a panel open: diff → panels in drawing order → diff.
+    pub fn cycle_panel_focus(&mut self) {
+        if self.panel.is_none() {
+            return;
+        }
+        let Some(second) = self.second_panel.filter(|&s| Some(s) != self.panel) else {
+            self.panel_focus = !self.panel_focus;
+            return;
+        };
+        let first = self.open_panels()[0];
+        let focus_second = if !self.panel_focus {
+            // From the diff to whichever panel is drawn first
+            Some(second) == Some(first)
+        } else if self.panel == Some(first) {
+            true
+        } else {
             self.panel_focus = false;
+            return;
+        };
+        if focus_second {
+            self.second_panel = self.panel.replace(second);
+            std::mem::swap(&mut self.panel_scroll, &mut self.second_panel_scroll);
+        }
+        self.panel_focus = true;
+    }
+
+    /// Whether `content` is the panel with keyboard focus.
+    pub fn panel_has_focus(&self, content: PanelContent) -> bool {
+        self.panel_focus && self.panel == Some(content)
+    }
+
+    /// Scroll offset of panel `content`, first or pinned.
+    pub fn scroll_of_panel(&self, content: PanelContent) -> u16 {
+        if self.second_panel == Some(content) && self.panel != Some(content) {
+            self.second_panel_scroll
+        } else {
+            self.panel_scroll
         }
     }
 
@@ -3484,11 +4779,92 @@ impl TabState {
         self.ai.review_checklist_count()
     }
 
-    fn review_item_count(&self) -> usize {
-        match self.review_focus {
-            ReviewFocus::Files => self.review_file_count(),
-            ReviewFocus::Checklist => self.review_checklist_count(),
-        }
+    /// Highlight new-side lines `start..=end` of `path` for [`LINE_FLASH_DURATION`].
+    pub fn flash_lines(&mut self, path: &str, start: usize, end: usize) {
+        self.line_flash = Some(LineFlash {
+            path: path.to_string(),
+            start,
+            end: end.max(start),
+            until: Instant::now() + LINE_FLASH_DURATION,
+        });
+    }
+
+    /// Whether new-side line `line` of `path` is in an unexpired flash.
+    pub fn is_line_flashed(&self, path: &str, line: usize) -> bool {
+        self.line_flash.as_ref().is_some_and(|f| {
+            f.path == path && (f.start..=f.end).contains(&line) && Instant::now() < f.until
+        })
+    }
+
+    /// Expand the focused finding's full text inline, or collapse it again.
+    /// Returns whether it is now expanded; `None` with no focused finding.
+    pub fn toggle_finding_details(&mut self) -> Option<bool> {
+        let id = self.focused_finding_id.clone()?;
+        if self.expanded_findings.remove(&id) {
+            Some(false)
+        } else {
+            self.expanded_findings.insert(id);
+            Some(true)
+        }
+    }
+
+    /// Rows of the category section, honouring folded groups
+    pub fn review_category_rows(&self) -> Vec<ai::CategoryRow> {
+        self.ai.category_rows(&self.collapsed_categories)
+    }
+
+    /// File path at the cursor of the file list, in the current sort
+    pub fn review_file_at_cursor(&self) -> Option<String> {
+        self.ai
+            .review_file_at_sorted(self.review_cursor, self.review_sort)
+    }
+
+    fn review_item_count(&self) -> usize {
+        match self.review_focus {
+            ReviewFocus::Files => self.review_file_count(),
+            ReviewFocus::Categories => self.review_category_rows().len(),
+            ReviewFocus::Checklist => self.review_checklist_count(),
+        }
+    }
+
+    /// Cycle the file list order, keeping the cursor on the same file.
+    pub fn review_cycle_sort(&mut self) {
+        let current = (self.review_focus == ReviewFocus::Files)
+            .then(|| self.review_file_at_cursor())
+            .flatten();
+        self.review_sort = self.review_sort.next();
+        if let Some(path) = current {
+            if let Some(idx) = self
+                .ai
+                .review_file_entries(self.review_sort)
+                .iter()
+                .position(|(p, _)| **p == path)
+            {
+                self.review_cursor = idx;
+            }
+        }
+    }
+
+    /// Fold or unfold the category under the cursor (a header, or the
+    /// group of the finding on the cursor). The cursor lands on the header.
+    pub fn review_toggle_category(&mut self) {
+        if self.review_focus != ReviewFocus::Categories {
+            return;
+        }
+        let rows = self.review_category_rows();
+        let Some(header_idx) = rows[..rows.len().min(self.review_cursor + 1)]
+            .iter()
+            .rposition(|r| matches!(r, ai::CategoryRow::Header { .. }))
+        else {
+            return;
+        };
+        let ai::CategoryRow::Header { category, .. } = &rows[header_idx] else {
+            return;
+        };
+        if !self.collapsed_categories.remove(*category) {
+            self.collapsed_categories.insert(category.to_string());
+        }
+        self.review_cursor = header_idx;
     }
 
     pub fn review_next(&mut self) {
@@ -3504,18 +4880,21 @@ impl TabState {
         }
     }
 
-    pub fn review_toggle_focus(&mut self) {
-        self.review_focus = match self.review_focus {
-            ReviewFocus::Files => ReviewFocus::Checklist,
-            ReviewFocus::Checklist => ReviewFocus::Files,
+    /// Move focus to the next section (files → categories → checklist), or
+    /// the previous one when `back`.
+    pub fn review_cycle_focus(&mut self, back: bool) {
+        self.review_focus = match (self.review_focus, back) {
+            (ReviewFocus::Files, false) | (ReviewFocus::Checklist, true) => ReviewFocus::Categories,
+            (ReviewFocus::Categories, false) | (ReviewFocus::Files, true) => ReviewFocus::Checklist,
+            (ReviewFocus::Checklist, false) | (ReviewFocus::Categories, true) => ReviewFocus::Files,
         };
         self.review_cursor = 0;
     }
 
     /// Estimate the panel_scroll value needed to show the start of each AiSummary section.
-    /// Returns (files_section_line, checklist_section_line).
+    /// Returns (files_section_line, categories_section_line, checklist_section_line).
     /// Must mirror the line-building logic in render_ai_summary() in ui/panel.rs.
-    pub fn ai_summary_section_offsets(&self) -> (u16, u16) {
+    pub fn ai_summary_section_offsets(&self) -> (u16, u16, u16) {
         let mut line: u16 = 0;
 
         // Title bar + separator (added by render_panel before content)
@@ -3558,9 +4937,18 @@ impl TabState {
 
         line += 1; // blank
 
+        let categories_start = line;
+
+        // "Findings by Category" header + blank, rows (or "No open findings"), blank
+        if self.ai.review.is_some() {
+            line += 2;
+            line += self.review_category_rows().len().max(1) as u16;
+            line += 1;
+        }
+
         let checklist_start = line;
 
-        (files_start, checklist_start)
+        (files_start, categories_start, checklist_start)
     }
 
     /// Diff files for the active view: branch/unstaged/staged use `files`; History uses
@@ -3593,8 +4981,15 @@ impl TabState {
         // Phase 1: Apply filter rules
         if !self.filter_rules.is_empty() {
             let review = self.ai.review.as_ref();
+            let pairing = self.filter_pairing();
             visible.retain(|(_, f)| {
-                super::filter::apply_filter_with_review(&self.filter_rules, f, review)
+                super::filter::apply_filter_with_review(
+                    &self.filter_rules,
+                    f,
+                    review,
+                    pairing.as_ref(),
+                    self.author_scope.as_ref().map(|s| &s.files),
+                )
             });
         }
 
@@ -3605,7 +5000,7 @@ impl TabState {
 
         // Phase 3: Apply unreviewed-only toggle
         if self.show_unreviewed_only {
-            visible.retain(|(_, f)| !self.reviewed.contains_key(&f.path));
+            visible.retain(|(_, f)| self.needs_review(&f.path));
         }
 
         visible
@@ -3873,7 +5268,9 @@ impl TabState {
             // Must run before refresh_diff_mode_switch() and before the selection restore.
             if self.review_bucket() != prev_bucket {
                 self.apply_managed_root();
-                self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+                self.load_reviewed_files();
+                self.load_skipped();
+                self.load_assigned();
                 self.reload_ai_state();
             }
 
@@ -3902,6 +5299,11 @@ impl TabState {
                     if let Some(c) = commits.first() {
                         cache.insert(c.hash.clone(), first_diff.clone());
                     }
+                    let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+                    let messages = git::git_commit_messages(&log_root, &hashes).unwrap_or_default();
+                    let net_zero =
+                        git::net_zero_commits(&log_root, &self.base_branch, self.commit_head_ref())
+                            .unwrap_or_default();
 
                     self.history = Some(HistoryState {
                         commits,
@@ -3914,6 +5316,11 @@ impl TabState {
                         h_scroll: 0,
                         all_loaded: is_pr_review_tab,
                         diff_cache: cache,
+                        messages,
+                        net_zero,
+                        fold_net_zero: false,
+                        search: Default::default(),
+                        prs: Default::default(),
                     });
                 }
             } else if mode == DiffMode::Tour {
@@ -4010,11 +5417,23 @@ impl TabState {
         }
     }
 
-    /// Reload config from .er-config.toml
-    #[allow(dead_code)]
-    pub fn reload_config(&mut self) {
-        let er_config = config::load_global_config();
-        self.watched_config = er_config.watched;
+    /// Re-resolve the tab's settings from `er_config` and the repo's
+    /// `.er-config.toml`
+    pub fn reload_config(&mut self, er_config: &ErConfig) {
+        self.test_patterns = er_config.tests.resolved_patterns();
+        let large_files_config = compaction_policy::resolve_large_files(&self.repo_root, er_config);
+        self.compaction_config = large_files_config.compaction();
+        self.compaction_learning.enabled = large_files_config.learn;
+        self.load_compaction_profile();
+        self.size_guard.guard_lines = large_files_config.guard_lines;
+        self.review_phases = review_order::resolve_phases(&self.repo_root, er_config);
+        self.review_templates = review_templates::resolve_templates(&self.repo_root, er_config);
+        self.todo_scanner = todos::resolve_scanner(&self.repo_root, er_config);
+        self.ops_scanner = operational::resolve_scanner(&self.repo_root, er_config);
+        self.migration_paths = migrations::resolve_paths(&self.repo_root, er_config);
+        self.checklist_gates = checklist_gates::resolve_gates(&self.repo_root, er_config);
+        self.watched_config = er_config.watched.clone();
+        self.repo_review_hints = config::load_repo_review_hints(&self.repo_root);
         let has_paths = !self.watched_config.paths.is_empty();
         if !has_paths {
             self.show_watched = false;
@@ -4033,26 +5452,6 @@ impl TabState {
         Ok(())
     }
 
-    /// Sort files by filesystem mtime (newest first)
-    fn sort_files_by_mtime(&mut self) {
-        use std::fs;
-        use std::time::SystemTime;
-
-        // In lazy mode this breaks the index correspondence between self.files and
-        // self.file_headers that ensure_file_parsed() relies on.
-        let repo_root = self.repo_root.clone();
-        self.files.sort_by(|a, b| {
-            let mtime_a = fs::metadata(format!("{}/{}", repo_root, a.path))
-                .and_then(|m| m.modified())
-                .unwrap_or(SystemTime::UNIX_EPOCH);
-            let mtime_b = fs::metadata(format!("{}/{}", repo_root, b.path))
-                .and_then(|m| m.modified())
-                .unwrap_or(SystemTime::UNIX_EPOCH);
-            // Newest first (reverse chronological)
-            mtime_b.cmp(&mtime_a)
-        });
-    }
-
     /// Populate `mtime_cache` with one `fs::metadata` call per diff file.
     /// Called after the diff is loaded so rendering never touches the filesystem directly.
     pub fn refresh_mtime_cache(&mut self) {
@@ -4067,6 +5466,32 @@ impl TabState {
         }
     }
 
+    /// Label files that aren't UTF-8. Only files whose diff has non-ASCII
+    /// text are sniffed; `.gitattributes` encodings apply to every file.
+    pub fn refresh_file_encodings(&mut self) {
+        self.file_encodings.clear();
+        if self.is_remote() || self.mode == DiffMode::History {
+            return;
+        }
+        let present: Vec<&DiffFile> = self
+            .files
+            .iter()
+            .filter(|f| f.status != git::FileStatus::Deleted)
+            .collect();
+        let paths: Vec<&str> = present.iter().map(|f| f.path.as_str()).collect();
+        let sniff: Vec<&str> = present
+            .iter()
+            .filter(|f| {
+                f.hunks
+                    .iter()
+                    .flat_map(|h| &h.lines)
+                    .any(|l| !l.content.is_ascii())
+            })
+            .map(|f| f.path.as_str())
+            .collect();
+        self.file_encodings = git::file_encodings(&self.repo_root, &paths, &sniff);
+    }
+
     fn clamp_hunk(&mut self) {
         let total = self.total_hunks();
         if total == 0 {
@@ -4086,8 +5511,17 @@ impl TabState {
             self.clear_filter();
             return;
         }
+        let had_authors = super::filter::has_author_rules(&self.filter_rules);
         self.filter_expr = expr.clone();
         self.filter_rules = super::filter::parse_filter_expr(&self.filter_expr);
+        super::filter::resolve_package_rules(&mut self.filter_rules, &self.packages);
+        if had_authors
+            || super::filter::has_author_rules(&self.filter_rules)
+            || self.shows_pruned_dir()
+        {
+            // Author scope and the size guard prune at load time
+            let _ = self.refresh_diff();
+        }
 
         // Add to history (remove duplicate if exists, push to front)
         self.filter_history.retain(|h| h != &expr);
@@ -4102,7 +5536,11 @@ impl TabState {
     /// Clear the active filter
     pub fn clear_filter(&mut self) {
         self.filter_expr.clear();
+        let had_authors = super::filter::has_author_rules(&self.filter_rules);
         self.filter_rules.clear();
+        if had_authors || self.shows_pruned_dir() {
+            let _ = self.refresh_diff();
+        }
         self.snap_to_visible();
     }
 
@@ -4111,7 +5549,7 @@ impl TabState {
     /// Count of reviewed files vs total in the active diff (branch / history commit).
     pub fn active_reviewed_count(&self) -> (usize, usize) {
         let files = self.active_diff_files();
-        let total = files.len();
+        let total = files.iter().filter(|f| !self.is_skipped(&f.path)).count();
         let reviewed = files
             .iter()
             .filter(|f| self.reviewed.contains_key(&f.path))
@@ -4125,6 +5563,20 @@ impl TabState {
         self.active_reviewed_count()
     }
 
+    /// Whether every file is now reviewed and that hasn't been announced
+    /// for this diff yet. Marks it announced.
+    pub fn take_review_completed(&mut self) -> bool {
+        let (reviewed, total) = self.reviewed_count();
+        if total == 0 || reviewed < total {
+            return false;
+        }
+        if self.review_completed_for.as_deref() == Some(self.diff_hash.as_str()) {
+            return false;
+        }
+        self.review_completed_for = Some(self.diff_hash.clone());
+        true
+    }
+
     /// Count of reviewed files vs total among filtered files only.
     /// Returns None if no filter is active.
     pub fn filtered_reviewed_count(&self) -> Option<(usize, usize)> {
@@ -4133,8 +5585,16 @@ impl TabState {
         }
         let (mut total, mut reviewed) = (0, 0);
         let review = self.ai.review.as_ref();
+        let pairing = self.filter_pairing();
         for f in &self.files {
-            if super::filter::apply_filter_with_review(&self.filter_rules, f, review) {
+            if super::filter::apply_filter_with_review(
+                &self.filter_rules,
+                f,
+                review,
+                pairing.as_ref(),
+                self.author_scope.as_ref().map(|s| &s.files),
+            ) && !self.is_skipped(&f.path)
+            {
                 total += 1;
                 if self.reviewed.contains_key(&f.path) {
                     reviewed += 1;
@@ -4144,6 +5604,11 @@ impl TabState {
         Some((reviewed, total))
     }
 
+    fn load_reviewed_files(&mut self) {
+        self.reviewed = Self::load_reviewed_files_from_path(&self.er_root.reviewed_path());
+        self.reviewed_on_disk = self.reviewed.clone();
+    }
+
     fn load_reviewed_files_from_path(path: &str) -> HashMap<String, String> {
         match std::fs::read_to_string(path) {
             Ok(content) => content
@@ -4165,7 +5630,10 @@ impl TabState {
         }
     }
 
-    pub fn save_reviewed_files(&self) -> Result<()> {
+    /// Write what changed in `reviewed` since the file was last read or
+    /// written, re-reading it under the MCP session writer's lock so marks
+    /// made there meanwhile survive — and are picked up here.
+    pub fn save_reviewed_files(&mut self) -> Result<()> {
         // Remote tabs that belong to the Pr bucket (PrDiff mode or --remote) are
         // allowed to persist — they write to the shared prs/pr-N dir.
         // Only skip when remote AND NOT the Pr bucket (shouldn't happen today, but
@@ -4173,22 +5641,37 @@ impl TabState {
         if self.is_remote() && self.review_bucket() != ReviewBucket::Pr {
             return Ok(());
         }
+        if self.reviewed == self.reviewed_on_disk {
+            return Ok(());
+        }
         let path = self.er_root.reviewed_path();
-        if self.reviewed.is_empty() {
+        let _lock = crate::file_lock::lock(&path)?;
+        let mut entries = Self::load_reviewed_files_from_path(&path);
+        for (p, h) in &self.reviewed {
+            if self.reviewed_on_disk.get(p) != Some(h) {
+                entries.insert(p.clone(), h.clone());
+            }
+        }
+        for p in self.reviewed_on_disk.keys() {
+            if !self.reviewed.contains_key(p) {
+                entries.remove(p);
+            }
+        }
+        if entries.is_empty() {
             // Remove file if no reviewed files
             let _ = std::fs::remove_file(&path);
-            return Ok(());
+        } else {
+            let mut sorted: Vec<(&String, &String)> = entries.iter().collect();
+            sorted.sort_by_key(|(p, _)| p.as_str());
+            let content = sorted
+                .iter()
+                .map(|(p, h)| format!("{}\t{}", p, h))
+                .collect::<Vec<_>>()
+                .join("\n");
+            crate::file_lock::write_atomic(&path, &format!("{}\n", content))?;
         }
-        let er_dir = self.er_root.er_dir();
-        std::fs::create_dir_all(&er_dir)?;
-        let mut entries: Vec<(&String, &String)> = self.reviewed.iter().collect();
-        entries.sort_by_key(|(p, _)| p.as_str());
-        let content = entries
-            .iter()
-            .map(|(p, h)| format!("{}\t{}", p, h))
-            .collect::<Vec<_>>()
-            .join("\n");
-        std::fs::write(&path, format!("{}\n", content))?;
+        self.reviewed = entries.clone();
+        self.reviewed_on_disk = entries;
         Ok(())
     }
 
@@ -4267,22 +5750,24 @@ impl TabState {
             filter_expr: self.filter_expr.clone(),
             filter_history: self.filter_history.clone(),
             show_unreviewed_only: self.show_unreviewed_only,
-            sort_by_mtime: self.sort_by_mtime,
+            sort_by_mtime: self.file_sort == file_sort::FileSort::Recent,
+            file_sort: self.file_sort,
             comment_draft: self.comment_text(),
             comment_draft_file: self.comment_file.clone(),
             comment_draft_hunk: self.comment_hunk,
             comment_draft_line: self.comment_line_num,
-            comment_draft_type: match self.comment_type {
-                CommentType::Question => "question".to_string(),
-                CommentType::Note => "note".to_string(),
-                CommentType::GitHubComment => "github".to_string(),
-            },
+            comment_draft_type: drafts::comment_type_key(self.comment_type).to_string(),
+            active_draft: self.current_comment_draft(),
+            drafts: self.comment_drafts.clone(),
+            marks: self.marks.clone(),
+            folded_hunks: self.folded_hunks.clone(),
         }
     }
 
     /// Restore session state if the diff hash matches. Returns true if restored.
     pub fn restore_session(&mut self) -> bool {
-        if self.is_remote() {
+        let _profile = crate::profile::scope("restore session");
+        if self.is_remote() || self.is_repoless() {
             return false;
         }
         let session = match SessionState::load(&self.er_root.session_path()) {
@@ -4290,6 +5775,13 @@ impl TabState {
             None => return false,
         };
 
+        // Drafts come back even when the diff changed — they are the user's
+        // words, not view state
+        self.restore_drafts(&session);
+        self.marks = session.marks.clone();
+        self.folded_hunks = session.folded_hunks.clone();
+        self.rebuild_hunk_offsets();
+
         // Only restore if the diff hasn't changed
         if session.diff_hash != self.branch_diff_hash {
             return false;
@@ -4308,6 +5800,14 @@ impl TabState {
             self.set_mode(mode);
         }
 
+        // Restore the file order before the selection that indexes into it
+        self.file_sort = match session.file_sort {
+            file_sort::FileSort::Diff if session.sort_by_mtime => file_sort::FileSort::Recent,
+            sort => sort,
+        };
+        self.sort_files();
+        self.rebuild_hunk_offsets();
+
         // Restore navigation (clamped to current file count)
         let file_count = self.files.len();
         if file_count == 0 {
@@ -4334,27 +5834,42 @@ impl TabState {
 
         // Restore view preferences
         self.show_unreviewed_only = session.show_unreviewed_only;
-        self.sort_by_mtime = session.sort_by_mtime;
-
-        // Restore comment draft if non-empty
-        if !session.comment_draft.is_empty() {
-            self.comment_textarea = TextArea::new(vec![session.comment_draft]);
-            self.comment_file = session.comment_draft_file;
-            self.comment_hunk = session.comment_draft_hunk;
-            self.comment_line_num = session.comment_draft_line;
-            self.comment_type = match session.comment_draft_type.as_str() {
-                "question" => CommentType::Question,
-                "note" => CommentType::Note,
-                _ => CommentType::GitHubComment,
-            };
-        }
 
         true
     }
 
+    /// Bring back the paused comment (when its file is still in the diff;
+    /// otherwise it joins the drafts list) and the drafts list.
+    fn restore_drafts(&mut self, session: &SessionState) {
+        self.comment_drafts = session.drafts.clone();
+        let active = session.active_draft.clone().or_else(|| {
+            (!session.comment_draft.is_empty()).then(|| drafts::CommentDraft {
+                text: session.comment_draft.clone(),
+                file: session.comment_draft_file.clone(),
+                hunk: session.comment_draft_hunk,
+                line: session.comment_draft_line,
+                line_end: None,
+                comment_type: session.comment_draft_type.clone(),
+                reply_to: None,
+                finding_ref: None,
+                file_level: false,
+                saved_at: String::new(),
+            })
+        });
+        let Some(draft) = active else {
+            return;
+        };
+        if draft.file.is_empty() || self.files.iter().any(|f| f.path == draft.file) {
+            self.load_comment_draft(&draft);
+        } else if !self.comment_drafts.contains(&draft) {
+            self.comment_drafts.insert(0, draft);
+            self.comment_drafts.truncate(drafts::MAX_DRAFTS);
+        }
+    }
+
     /// Save current session state to .er/session.json.
     pub fn save_session(&self) {
-        if self.is_remote() {
+        if self.is_remote() || self.is_repoless() {
             return;
         }
         let session = self.capture_session();
@@ -4420,6 +5935,10 @@ pub struct App {
     /// Should the app quit?
     pub should_quit: bool,
 
+    /// `er --read-only`: nothing that changes the repo, the working tree or
+    /// GitHub comments may run (see `read_only`)
+    pub read_only: bool,
+
     /// Active overlay popup (None = no overlay)
     pub overlay: Option<OverlayData>,
 
@@ -4435,12 +5954,27 @@ pub struct App {
     /// How many ticks the current notification should persist (default 20 ≈ 2s)
     pub watch_message_max_ticks: u16,
 
-    /// Counter for throttling AI file polling (check every 10 ticks ≈ 1s)
+    /// Severity of the current notification (drives its color)
+    pub watch_message_level: NotificationLevel,
+
+    /// Recent notifications with full text, for the notifications pane
+    pub notification_log: NotificationLog,
+
+    /// Event-loop iteration counter (wraps)
     pub ai_poll_counter: u16,
 
     /// Input buffer for remote URL input mode
     pub remote_url_input: String,
 
+    /// Input buffer for the skip-reason prompt
+    pub skip_reason_input: String,
+
+    /// Input buffer for the assignee prompt
+    pub assignee_input: String,
+
+    /// Input buffer for the quick-diff ref prompt
+    pub quick_diff_input: String,
+
     /// Application configuration (loaded from .er-config.toml)
     pub config: ErConfig,
 
@@ -4463,6 +5997,17 @@ pub struct App {
     /// Last known terminal width (updated each tick for resize calculations)
     pub last_terminal_width: u16,
 
+    /// Wording for the confirm currently asked (see `confirm::ConfirmPrompt`)
+    pub confirm_prompt: Option<confirm::ConfirmPrompt>,
+
+    /// `\` flips the file tree from its default: opens it as a drawer on
+    /// narrow layouts, hides it on wider ones
+    pub file_tree_toggled: bool,
+
+    /// Focused pane (diff, or the panel when it has focus) fills the main
+    /// area; toggled with `M`
+    pub zoomed: bool,
+
     /// Which panels are currently visible in the desktop UI
     pub panels_visible: PanelsVisible,
 
@@ -4495,6 +6040,38 @@ pub struct App {
 
     /// TUI: provider id waiting for the event loop to spawn discovery.
     pub pending_model_discovery: Option<String>,
+
+    /// A prefix key waiting for the key it goes with: `m` (set a mark) or
+    /// `'` (jump to one)
+    pub key_prefix: Option<char>,
+
+    /// Count typed before a motion (`Alt+5 n`)
+    pub pending_count: Option<usize>,
+
+    /// The last motion and its count, for `.`
+    pub last_motion: Option<(motions::Motion, usize)>,
+
+    /// Config files' mtimes and contents as last applied, to hot-reload
+    /// them when they change
+    pub config_watch: config_reload::ConfigWatch,
+
+    /// Background check for a newer er (`[updates] check`)
+    pub update_check: update_check::UpdateCheck,
+
+    /// This session's local usage stats, written on quit (`er stats`)
+    pub usage: usage::UsageSession,
+
+    /// Snoozed files, comments and findings (Alt+z)
+    pub followups: followups::FollowUps,
+
+    /// The bug report being written (Settings → Report a bug…)
+    pub bug_report: bug_report::BugReport,
+
+    /// Viewed-checkbox pushes reporting back (see `viewed_sync`)
+    pub viewed_pushes: viewed_sync::ViewedPushes,
+
+    /// The comment push running on a worker (see `github_push`)
+    pub comment_push: github_push::CommentPush,
 }
 
 impl App {
@@ -4590,24 +6167,18 @@ impl App {
                     crate::github::verify_remote_matches(&repo_root, &pr_ref)?;
 
                     // Fetch PR head to a local ref without touching the working tree
-                    let head_ref = crate::github::fetch_pr_head(pr_ref.number, &repo_root)?;
-                    let base = crate::github::gh_pr_base_branch(pr_ref.number, &repo_root)?;
-                    let base = crate::github::ensure_base_ref_available(&repo_root, &base)?;
-                    let head_branch =
-                        crate::github::gh_pr_head_branch_name(pr_ref.number, &repo_root)
-                            .unwrap_or_else(|_| format!("pr/{}", pr_ref.number));
-
-                    let mut tab = TabState::new_with_base(repo_root, base)?;
-                    tab.pr_head_ref = Some(head_ref);
-                    tab.pr_number = Some(pr_ref.number);
-                    tab.current_branch = head_branch;
-                    tab.refresh_diff()?;
+                    let tab = TabState::new_pr_head_review(repo_root, pr_ref.number)?;
+                    crate::github::set_host(&pr_ref.host);
                     tabs.push(tab);
                 } else {
                     // Local path
                     let canonical = std::fs::canonicalize(path)
                         .with_context(|| format!("Path not found: {}", path))?;
                     let dir = canonical.to_string_lossy().to_string();
+                    if canonical.is_file() {
+                        tabs.push(TabState::new_patch(&dir)?);
+                        continue;
+                    }
                     let repo_root = git::get_repo_root_in(&dir)
                         .with_context(|| format!("Not a git repository: {}", path))?;
                     tabs.push(TabState::new(repo_root)?);
@@ -4627,20 +6198,36 @@ impl App {
             active_tab: 0,
             input_mode: InputMode::Normal,
             should_quit: false,
+            read_only: false,
             overlay: None,
             watching: false,
             watch_message: None,
             watch_message_ticks: 0,
             watch_message_max_ticks: 20,
+            watch_message_level: NotificationLevel::Info,
+            notification_log: NotificationLog::default(),
             ai_poll_counter: 0,
             remote_url_input: String::new(),
+            skip_reason_input: String::new(),
+            assignee_input: String::new(),
+            quick_diff_input: String::new(),
             config: er_config,
             current_ai_provider,
             current_ai_model,
             current_ai_effort,
             pending_ai_selection_override: None,
             pending_hub_action: None,
+            config_watch: config_reload::ConfigWatch::default(),
+            update_check: update_check::UpdateCheck::default(),
+            usage: usage::UsageSession::default(),
+            followups: followups::FollowUps::default(),
+            bug_report: bug_report::BugReport::default(),
+            viewed_pushes: viewed_sync::ViewedPushes::default(),
+            comment_push: github_push::CommentPush::default(),
             last_terminal_width: 0,
+            confirm_prompt: None,
+            file_tree_toggled: false,
+            zoomed: false,
             panels_visible: PanelsVisible::default(),
             background_tasks: std::collections::HashMap::new(),
             recent_background_tasks: Vec::new(),
@@ -4649,6 +6236,9 @@ impl App {
             active_arena_runs: std::collections::HashMap::new(),
             model_discovery_inflight: std::collections::HashSet::new(),
             pending_model_discovery: None,
+            key_prefix: None,
+            pending_count: None,
+            last_motion: None,
         };
         app.drain_storage_notices();
         app.overlay_cached_discovered_models();
@@ -4673,20 +6263,36 @@ impl App {
             active_tab: 0,
             input_mode: InputMode::Normal,
             should_quit: false,
+            read_only: false,
             overlay: None,
             watching: false,
             watch_message: None,
             watch_message_ticks: 0,
             watch_message_max_ticks: 20,
+            watch_message_level: NotificationLevel::Info,
+            notification_log: NotificationLog::default(),
             ai_poll_counter: 0,
             remote_url_input: String::new(),
+            skip_reason_input: String::new(),
+            assignee_input: String::new(),
+            quick_diff_input: String::new(),
             config: er_config,
             current_ai_provider,
             current_ai_model,
             current_ai_effort,
             pending_ai_selection_override: None,
             pending_hub_action: None,
+            config_watch: config_reload::ConfigWatch::default(),
+            update_check: update_check::UpdateCheck::default(),
+            usage: usage::UsageSession::default(),
+            followups: followups::FollowUps::default(),
+            bug_report: bug_report::BugReport::default(),
+            viewed_pushes: viewed_sync::ViewedPushes::default(),
+            comment_push: github_push::CommentPush::default(),
             last_terminal_width: 0,
+            confirm_prompt: None,
+            file_tree_toggled: false,
+            zoomed: false,
             panels_visible: PanelsVisible::default(),
             background_tasks: std::collections::HashMap::new(),
             recent_background_tasks: Vec::new(),
@@ -4695,6 +6301,9 @@ impl App {
             active_arena_runs: std::collections::HashMap::new(),
             model_discovery_inflight: std::collections::HashSet::new(),
             pending_model_discovery: None,
+            key_prefix: None,
+            pending_count: None,
+            last_motion: None,
         };
         app.overlay_cached_discovered_models();
         Ok(app)
@@ -4713,20 +6322,36 @@ impl App {
             active_tab: 0,
             input_mode: InputMode::Normal,
             should_quit: false,
+            read_only: false,
             overlay: None,
             watching: false,
             watch_message: None,
             watch_message_ticks: 0,
             watch_message_max_ticks: 20,
+            watch_message_level: NotificationLevel::Info,
+            notification_log: NotificationLog::default(),
             ai_poll_counter: 0,
             remote_url_input: String::new(),
+            skip_reason_input: String::new(),
+            assignee_input: String::new(),
+            quick_diff_input: String::new(),
             config: er_config,
             current_ai_provider,
             current_ai_model,
             current_ai_effort,
             pending_ai_selection_override: None,
             pending_hub_action: None,
+            config_watch: config_reload::ConfigWatch::default(),
+            update_check: update_check::UpdateCheck::default(),
+            usage: usage::UsageSession::default(),
+            followups: followups::FollowUps::default(),
+            bug_report: bug_report::BugReport::default(),
+            viewed_pushes: viewed_sync::ViewedPushes::default(),
+            comment_push: github_push::CommentPush::default(),
             last_terminal_width: 0,
+            confirm_prompt: None,
+            file_tree_toggled: false,
+            zoomed: false,
             panels_visible: PanelsVisible::default(),
             background_tasks: std::collections::HashMap::new(),
             recent_background_tasks: Vec::new(),
@@ -4735,6 +6360,9 @@ impl App {
             active_arena_runs: std::collections::HashMap::new(),
             model_discovery_inflight: std::collections::HashSet::new(),
             pending_model_discovery: None,
+            key_prefix: None,
+            pending_count: None,
+            last_motion: None,
         };
         app.overlay_cached_discovered_models();
         app
@@ -4748,20 +6376,36 @@ impl App {
             active_tab: 0,
             input_mode: InputMode::Normal,
             should_quit: false,
+            read_only: false,
             overlay: None,
             watching: false,
             watch_message: None,
             watch_message_ticks: 0,
             watch_message_max_ticks: 20,
+            watch_message_level: NotificationLevel::Info,
+            notification_log: NotificationLog::default(),
             ai_poll_counter: 0,
             remote_url_input: String::new(),
+            skip_reason_input: String::new(),
+            assignee_input: String::new(),
+            quick_diff_input: String::new(),
             config: ErConfig::default(),
             current_ai_provider: None,
             current_ai_model: None,
             current_ai_effort: None,
             pending_ai_selection_override: None,
             pending_hub_action: None,
+            config_watch: config_reload::ConfigWatch::default(),
+            update_check: update_check::UpdateCheck::default(),
+            usage: usage::UsageSession::default(),
+            followups: followups::FollowUps::default(),
+            bug_report: bug_report::BugReport::default(),
+            viewed_pushes: viewed_sync::ViewedPushes::default(),
+            comment_push: github_push::CommentPush::default(),
             last_terminal_width: 0,
+            confirm_prompt: None,
+            file_tree_toggled: false,
+            zoomed: false,
             panels_visible: PanelsVisible::default(),
             background_tasks: std::collections::HashMap::new(),
             recent_background_tasks: Vec::new(),
@@ -4770,6 +6414,9 @@ impl App {
             active_arena_runs: std::collections::HashMap::new(),
             model_discovery_inflight: std::collections::HashSet::new(),
             pending_model_discovery: None,
+            key_prefix: None,
+            pending_count: None,
+            last_motion: None,
         }
     }
 
@@ -5044,6 +6691,7 @@ impl App {
         let name = tab.tab_name();
         self.tabs.push(tab);
         self.active_tab = self.tabs.len() - 1;
+        self.close_start_tab();
         self.notify(&format!("Opened remote: {}", name));
         Ok(())
     }
@@ -5074,16 +6722,16 @@ impl App {
     }
 
     /// Returns true when split diff rendering should be active.
-    /// Requires the config flag and no open panel.
+    /// Requires the config flag and no open panel in the right column.
     pub fn split_diff_active(&self, config: &ErConfig) -> bool {
+        use crate::app::layout::Dock;
         if !config.display.split_diff {
             return false;
         }
         let tab = self.tab();
-        if tab.panel.is_some() {
-            return false;
-        }
-        true
+        !tab.open_panels()
+            .into_iter()
+            .any(|p| crate::app::layout::panel_dock(&config.layout, p) == Dock::Right)
     }
 
     // ── Tab Management ──
@@ -5106,6 +6754,7 @@ impl App {
         self.tabs.push(tab);
         self.active_tab = self.tabs.len() - 1;
         self.overlay = None;
+        self.close_start_tab();
         self.notify(&format!("Opened: {}", name));
         Ok(())
     }
@@ -5246,6 +6895,7 @@ impl App {
         self.overlay = Some(OverlayData::WorktreePicker {
             worktrees,
             selected: 0,
+            apply: None,
         });
         Ok(())
     }
@@ -5261,13 +6911,18 @@ impl App {
         });
     }
 
-    /// Open the directory browser overlay (starts from parent of repo root)
+    /// Open the directory browser overlay (starts from parent of repo root,
+    /// or from where a tab without a repository is)
     pub fn open_directory_browser(&mut self) {
         let repo_root = self.tab().repo_root.clone();
-        let start_path = std::path::Path::new(&repo_root)
-            .parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|| "/".to_string());
+        let start_path = if self.tab().is_repoless() {
+            repo_root
+        } else {
+            std::path::Path::new(&repo_root)
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| "/".to_string())
+        };
 
         let entries = Self::read_directory(&start_path);
         self.overlay = Some(OverlayData::DirectoryBrowser {
@@ -5289,7 +6944,7 @@ impl App {
                 description: "Push current branch to origin".into(),
                 action: HubAction::PushToRemote,
                 is_header: false,
-                enabled: in_staged,
+                enabled: in_staged || self.tab().has_unpushed_pr_commits(),
             },
             HubItem {
                 label: "Stage current file".into(),
@@ -5307,6 +6962,18 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Commit plan".into(),
+                hint: "B".into(),
+                description: match self.tab().commit_plan.len() {
+                    0 => "Split the changes into several commits, hunk by hunk".into(),
+                    n => format!("{} planned commit{}", n, if n == 1 { "" } else { "s" }),
+                },
+                action: HubAction::OpenCommitPlan,
+                is_header: false,
+                enabled: matches!(self.tab().mode, DiffMode::Unstaged | DiffMode::Staged)
+                    && !self.tab().is_remote(),
+            },
             HubItem {
                 label: "Refresh diff".into(),
                 hint: "R".into(),
@@ -5315,6 +6982,84 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Set review checkpoint".into(),
+                hint: "".into(),
+                description: "Snapshot what you've reviewed so far".into(),
+                action: HubAction::SetReviewCheckpoint,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
+            HubItem {
+                label: if self.tab().in_checkpoint_view() {
+                    "Show full branch diff".into()
+                } else {
+                    "Changes since checkpoint".into()
+                },
+                hint: "I".into(),
+                description: match self.tab().load_review_checkpoint() {
+                    Some(cp) => format!("Interdiff vs checkpoint from {}", cp.created_at),
+                    None => "No checkpoint yet".into(),
+                },
+                action: HubAction::ToggleCheckpointView,
+                is_header: false,
+                enabled: self.tab().checkpoint_view.is_some()
+                    || self.tab().load_review_checkpoint().is_some(),
+            },
+            HubItem {
+                label: if self.tab().in_pr_head_view() {
+                    "Show full branch diff".into()
+                } else {
+                    "Local vs PR head".into()
+                },
+                hint: "".into(),
+                description: match self.tab().pr_head_sync() {
+                    Some(sync) => sync
+                        .label()
+                        .unwrap_or_else(|| "Local HEAD matches GitHub".into()),
+                    None => "Needs a checkout of the PR's branch".into(),
+                },
+                action: HubAction::TogglePrHeadView,
+                is_header: false,
+                enabled: self.tab().pr_head_sync().is_some(),
+            },
+            HubItem {
+                label: if self.tab().in_quick_diff() {
+                    "Show full diff".into()
+                } else {
+                    "Diff file against ref…".into()
+                },
+                hint: "".into(),
+                description: match self.tab().quick_diff_ref() {
+                    Some(r) => format!("Showing the selected file vs {}", r),
+                    None => "The selected file vs a tag, branch or commit".into(),
+                },
+                action: HubAction::StartQuickDiff,
+                is_header: false,
+                enabled: !self.tab().is_remote() && self.tab().selected_diff_file().is_some(),
+            },
+            HubItem {
+                label: "Code age layer".into(),
+                hint: "Alt+g".into(),
+                description: if self.tab().layers.show_code_age {
+                    "On — added lines tinted by the age of the code around them".into()
+                } else {
+                    "Tint added lines by the age of the code around them".into()
+                },
+                action: HubAction::ToggleCodeAge,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Switch branch…".into(),
+                hint: "".into(),
+                description: format!("Check out another branch (on {})", self.tab().current_branch),
+                action: HubAction::OpenBranchPicker,
+                is_header: false,
+                enabled: !self.read_only
+                    && !self.tab().is_remote()
+                    && !self.tab().is_local_branch_view(),
+            },
             HubItem {
                 label: "Pull GitHub comments".into(),
                 hint: "".into(),
@@ -5323,10 +7068,37 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Resolve comment conflicts".into(),
+                hint: "".into(),
+                description: match self.comment_conflict_count() {
+                    0 => "No comments edited on both sides".into(),
+                    n => format!("{} comment(s) edited here and on GitHub", n),
+                },
+                action: HubAction::OpenCommentConflicts,
+                is_header: false,
+                enabled: self.comment_conflict_count() > 0,
+            },
+            HubItem {
+                label: "Review dashboard".into(),
+                hint: "".into(),
+                description: "Open tabs' PRs, oldest first, and follow-ups".into(),
+                action: HubAction::OpenReviewsDashboard,
+                is_header: false,
+                enabled: self.tabs.iter().any(|t| t.pr_data.is_some())
+                    || !self.followups.items.is_empty(),
+            },
             HubItem {
                 label: "Push comments to GitHub".into(),
                 hint: "".into(),
-                description: "Publish local comments to the PR".into(),
+                description: match &self.tab().outbox {
+                    Some(outbox) => format!(
+                        "{} queued after a rate limit — retrying in {}s",
+                        outbox.comment_ids.len(),
+                        outbox.seconds_left(crate::outbox::unix_now())
+                    ),
+                    None => "Publish local comments to the PR".into(),
+                },
                 action: HubAction::PushCommentsToGitHub,
                 is_header: false,
                 enabled: true,
@@ -5339,6 +7111,149 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Comment on file".into(),
+                hint: "".into(),
+                description: "Note on the whole file, shown above its first hunk (Ctrl+t: question / PR comment)".into(),
+                action: HubAction::CommentOnFile,
+                is_header: false,
+                enabled: true,
+            },
+            HubItem {
+                label: "Review note".into(),
+                hint: "".into(),
+                description: "Overall impression or question for the author — goes in the review body".into(),
+                action: HubAction::ReviewNote,
+                is_header: false,
+                enabled: true,
+            },
+            HubItem {
+                label: "Publish shared review".into(),
+                hint: "".into(),
+                description: if self.config.features.shared_review {
+                    "Push your questions & progress to refs/er/reviews/<branch>".into()
+                } else {
+                    "Off — [features] shared_review = true".into()
+                },
+                action: HubAction::PublishSharedReview,
+                is_header: false,
+                enabled: self.config.features.shared_review && !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Pull shared review".into(),
+                hint: "".into(),
+                description: "Show teammates' questions & progress from the shared ref".into(),
+                action: HubAction::PullSharedReview,
+                is_header: false,
+                enabled: self.config.features.shared_review && !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Branch audit".into(),
+                hint: "".into(),
+                description: "Merge, fixup!/WIP and unsigned commits, large binaries".into(),
+                action: HubAction::OpenBranchAudit,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Apply hunk to worktree".into(),
+                hint: "".into(),
+                description: "git apply the current hunk in another checkout, e.g. a hotfix branch".into(),
+                action: HubAction::ApplyHunkToWorktree,
+                is_header: false,
+                enabled: !self.tab().files.is_empty(),
+            },
+            HubItem {
+                label: "Authors on this branch".into(),
+                hint: "".into(),
+                description: "Commits and lines per author; pick one to filter to their hunks".into(),
+                action: HubAction::OpenBranchAuthors,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Packages".into(),
+                hint: "".into(),
+                description: "Touched monorepo packages and the unchanged ones that depend on them"
+                    .into(),
+                action: HubAction::OpenPackages,
+                is_header: false,
+                enabled: !self.tab().packages.is_empty(),
+            },
+            HubItem {
+                label: "TODOs".into(),
+                hint: "".into(),
+                description: match self.tab().untracked_todos() {
+                    0 => format!("{} TODO/FIXME/HACK on added lines", self.tab().todos.len()),
+                    n => format!("{} new, {} without a ticket", self.tab().todos.len(), n),
+                },
+                action: HubAction::OpenTodos,
+                is_header: false,
+                enabled: !self.tab().todos.is_empty(),
+            },
+            HubItem {
+                label: "Ops changes".into(),
+                hint: "".into(),
+                description: format!(
+                    "{} config key(s), env var(s) and feature flag(s) this diff changes",
+                    self.tab().operational.len()
+                ),
+                action: HubAction::OpenOperational,
+                is_header: false,
+                enabled: !self.tab().operational.is_empty(),
+            },
+            HubItem {
+                label: "Asset gallery".into(),
+                hint: "".into(),
+                description: format!(
+                    "{} changed image(s) with sizes and previews; a marks them reviewed",
+                    self.tab().assets.assets.len()
+                ),
+                action: HubAction::OpenAssets,
+                is_header: false,
+                enabled: !self.tab().assets.assets.is_empty(),
+            },
+            HubItem {
+                label: "Lost comments".into(),
+                hint: "".into(),
+                description: format!(
+                    "{} comment(s) whose line is gone — re-anchor, delete or keep file-level",
+                    self.tab().lost_comments().len()
+                ),
+                action: HubAction::OpenLostComments,
+                is_header: false,
+                enabled: !self.tab().lost_comments().is_empty(),
+            },
+            HubItem {
+                label: "Spelling".into(),
+                hint: "".into(),
+                description: if self.config.spell.enabled {
+                    "Typos in added doc comments and markdown (hunspell / aspell)".into()
+                } else {
+                    "Off — [spell] enabled = true".into()
+                },
+                action: HubAction::OpenSpelling,
+                is_header: false,
+                enabled: self.config.spell.enabled && !self.tab().files.is_empty(),
+            },
+            HubItem {
+                label: "Export session".into(),
+                hint: "".into(),
+                description: "One file with your reviewed files, questions, comments & filter"
+                    .into(),
+                action: HubAction::ExportSession,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
+            HubItem {
+                label: "Import session".into(),
+                hint: "".into(),
+                description: "Merge another reviewer's exported session to take over from them"
+                    .into(),
+                action: HubAction::OpenImportSession,
+                is_header: false,
+                enabled: !self.tab().is_remote(),
+            },
             HubItem {
                 label: "Approve PR".into(),
                 hint: "".into(),
@@ -5425,6 +7340,14 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Explain this hunk".into(),
+                hint: "H".into(),
+                description: format!("A few sentences on the current hunk via {selection_label}"),
+                action: HubAction::ExplainHunk,
+                is_header: false,
+                enabled: self.tab().selected_diff_file().is_some(),
+            },
             HubItem {
                 label: "Specialized review".into(),
                 hint: "".into(),
@@ -5506,6 +7429,16 @@ impl App {
                 is_header: false,
                 enabled: true,
             },
+            HubItem {
+                label: "Agent hand-off".into(),
+                hint: "Y".into(),
+                description:
+                    "Open findings, notes and questions as an agent task list (saved + copied)"
+                        .into(),
+                action: HubAction::ExportHandoff,
+                is_header: false,
+                enabled: true,
+            },
             HubItem {
                 label: "Copy review.json".into(),
                 hint: "".into(),
@@ -5683,6 +7616,36 @@ impl App {
             is_header: false,
             enabled: cmds.security.is_some(),
         });
+        items.push(HubItem {
+            label: "Infrastructure plan".into(),
+            hint: "".into(),
+            description: cmds.plan.as_deref().unwrap_or(not_configured).to_string(),
+            action: HubAction::RunCommand("plan".into()),
+            is_header: false,
+            enabled: cmds.plan.is_some(),
+        });
+
+        if !cmds.custom.is_empty() {
+            items.push(HubItem {
+                label: "── Custom ──".into(),
+                hint: "".into(),
+                description: "".into(),
+                action: HubAction::Noop,
+                is_header: true,
+                enabled: false,
+            });
+            for (name, cmd) in &cmds.custom {
+                items.push(HubItem {
+                    label: name.clone(),
+                    hint: "".into(),
+                    description: cmd.clone(),
+                    action: HubAction::RunCustomCommand(name.clone()),
+                    is_header: false,
+                    enabled: true,
+                });
+            }
+        }
+        items.extend(self.script_hub_items());
 
         // Pre-select the first enabled item, falling back to the first non-header row so
         // the cursor never lands on a section header when nothing is enabled.
@@ -5781,10 +7744,12 @@ impl App {
         });
     }
 
-    /// Open the Copy modal hub — copy file, path, hunk, or line to clipboard
+    /// Open the Copy modal hub — copy file, path, hunk, line, whole diff,
+    /// permalink or diff command to clipboard
     pub fn open_copy_hub(&mut self) {
         let has_file = !self.tab().files.is_empty();
         let has_line = has_file && self.tab().current_line.is_some();
+        let is_pr = self.tab().pr_number.is_some();
         let items = vec![
             HubItem {
                 label: "Full file diff".into(),
@@ -5810,6 +7775,22 @@ impl App {
                 is_header: false,
                 enabled: has_file,
             },
+            HubItem {
+                label: "Hunk as patch".into(),
+                hint: "".into(),
+                description: "With file headers, for git apply on another branch".into(),
+                action: HubAction::CopyHunkPatch { reverse: false },
+                is_header: false,
+                enabled: has_file,
+            },
+            HubItem {
+                label: "Reverse hunk patch".into(),
+                hint: "".into(),
+                description: "git apply it to undo the hunk".into(),
+                action: HubAction::CopyHunkPatch { reverse: true },
+                is_header: false,
+                enabled: has_file,
+            },
             HubItem {
                 label: "Line".into(),
                 hint: "".into(),
@@ -5818,6 +7799,46 @@ impl App {
                 is_header: false,
                 enabled: has_line,
             },
+            HubItem {
+                label: if is_pr { "Whole PR diff" } else { "Whole diff" }.into(),
+                hint: "".into(),
+                description: "Every file in this view (up to 2 MB)".into(),
+                action: HubAction::CopyViewDiff,
+                is_header: false,
+                enabled: has_file,
+            },
+            HubItem {
+                label: "Save as .patch file".into(),
+                hint: "".into(),
+                description: "Files in view (filters apply) to the er patches folder".into(),
+                action: HubAction::SaveViewPatch,
+                is_header: false,
+                enabled: has_file,
+            },
+            HubItem {
+                label: "GitHub permalink".into(),
+                hint: "".into(),
+                description: "Link to the current line at this commit".into(),
+                action: HubAction::CopyPermalink,
+                is_header: false,
+                enabled: has_file,
+            },
+            HubItem {
+                label: "Diff command".into(),
+                hint: "".into(),
+                description: "git diff / gh pr diff command reproducing this view".into(),
+                action: HubAction::CopyDiffCommand,
+                is_header: false,
+                enabled: has_file,
+            },
+            HubItem {
+                label: "Agent hand-off".into(),
+                hint: "Y".into(),
+                description: "Whole-review task list for a coding agent".into(),
+                action: HubAction::ExportHandoff,
+                is_header: false,
+                enabled: true,
+            },
         ];
         self.overlay = Some(OverlayData::ModalHub {
             kind: HubKind::Copy,
@@ -5830,6 +7851,14 @@ impl App {
     /// Open the Help modal hub (keybind reference)
     pub fn open_help_hub(&mut self) {
         let items = vec![
+            HubItem {
+                label: "Getting started".into(),
+                hint: "".into(),
+                description: "Modes, review keys and AI file conventions (er --onboarding)".into(),
+                action: HubAction::ShowOnboarding,
+                is_header: false,
+                enabled: true,
+            },
             // ── Navigation ──
             HubItem {
                 label: "── Navigation ──".into(),
@@ -5895,6 +7924,14 @@ impl App {
                 is_header: false,
                 enabled: false,
             },
+            HubItem {
+                label: "Alt+5 n / .".into(),
+                hint: "".into(),
+                description: "Count before a motion / repeat the last one".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
             HubItem {
                 label: "Home".into(),
                 hint: "".into(),
@@ -5911,6 +7948,14 @@ impl App {
                 is_header: false,
                 enabled: false,
             },
+            HubItem {
+                label: "t".into(),
+                hint: "".into(),
+                description: "Diff since a tag, or between two releases".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
             // ── Review ──
             HubItem {
                 label: "── Review ──".into(),
@@ -5944,34 +7989,157 @@ impl App {
                 is_header: false,
                 enabled: false,
             },
+            HubItem {
+                label: "S".into(),
+                hint: "".into(),
+                description: "Skip file from review (with a reason)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "V".into(),
+                hint: "".into(),
+                description: "Focus mode: Space advances to next unreviewed".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "T".into(),
+                hint: "".into(),
+                description: "Jump between source and its test".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+m".into(),
+                hint: "".into(),
+                description: "Where did this deleted code go? / Jump to a duplicate's copy".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+v".into(),
+                hint: "".into(),
+                description: "Notebook / table view of an .ipynb, .csv or .tsv file".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+z".into(),
+                hint: "".into(),
+                description: "Snooze the focused comment, finding or file — listed in the review dashboard when due".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+g".into(),
+                hint: "".into(),
+                description: "Code age layer: tint added lines by how old the code around them is"
+                    .into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+s".into(),
+                hint: "".into(),
+                description: "Add the file to the multi-selection (Alt+j / Alt+k extend it)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+a".into(),
+                hint: "".into(),
+                description: "Act on the selected files: review, stage, filter, compact, assign"
+                    .into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Alt+c".into(),
+                hint: "".into(),
+                description: "Why a file is compacted; always expand it, or change the policy"
+                    .into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "H".into(),
+                hint: "".into(),
+                description: "Explain the current hunk (AI)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "O".into(),
+                hint: "".into(),
+                description: "Fold commits undone later (History)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
             HubItem {
                 label: "q".into(),
                 hint: "".into(),
-                description: "Add review question (Ctrl+t → note)".into(),
+                description: "Add review question (Ctrl+t → note)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "c".into(),
+                hint: "".into(),
+                description: "Add GitHub comment".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "s".into(),
+                hint: "".into(),
+                description: "Stage / unstage file (with a line selection: those lines)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "y".into(),
+                hint: "".into(),
+                description: "Yank hunk to clipboard".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
             },
             HubItem {
-                label: "c".into(),
+                label: "I".into(),
                 hint: "".into(),
-                description: "Add GitHub comment".into(),
+                description: "Toggle changes since review checkpoint (set in git hub)".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
             },
             HubItem {
-                label: "s".into(),
+                label: "L".into(),
                 hint: "".into(),
-                description: "Stage / unstage file".into(),
+                description: "Notification log (full text of recent messages)".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
             },
             HubItem {
-                label: "y".into(),
+                label: "Y".into(),
                 hint: "".into(),
-                description: "Yank hunk to clipboard".into(),
+                description: "Copy agent hand-off (open findings + notes)".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
@@ -6009,6 +8177,14 @@ impl App {
                 is_header: false,
                 enabled: false,
             },
+            HubItem {
+                label: "i".into(),
+                hint: "".into(),
+                description: "Expand / collapse focused finding".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
             HubItem {
                 label: "x".into(),
                 hint: "".into(),
@@ -6025,6 +8201,14 @@ impl App {
                 is_header: false,
                 enabled: false,
             },
+            HubItem {
+                label: "E".into(),
+                hint: "".into(),
+                description: "Open in tmux / WezTerm / kitty split".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
             HubItem {
                 label: "Ctrl+t".into(),
                 hint: "".into(),
@@ -6078,7 +8262,15 @@ impl App {
             HubItem {
                 label: "/ / f".into(),
                 hint: "".into(),
-                description: "Search / filter files".into(),
+                description: "Search / filter files (History: author: path: before: /re/)".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "Ctrl+f".into(),
+                hint: "".into(),
+                description: "Find in the current file (n/N between matches)".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
@@ -6102,15 +8294,23 @@ impl App {
             HubItem {
                 label: "Enter".into(),
                 hint: "".into(),
-                description: "Expand / collapse compacted file".into(),
+                description: "Expand compacted file / fold hunk".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            },
+            HubItem {
+                label: "mm".into(),
+                hint: "".into(),
+                description: "Cycle file sort".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
             },
             HubItem {
-                label: "m".into(),
+                label: "m / ' + a–z".into(),
                 hint: "".into(),
-                description: "Toggle mtime sort".into(),
+                description: "Set a mark / jump to it (` lists marks)".into(),
                 action: HubAction::Noop,
                 is_header: false,
                 enabled: false,
@@ -6376,15 +8576,65 @@ impl App {
                 enabled: has_worktrees,
             },
             HubItem {
-                label: "Open remote PR".into(),
+                label: "Open PR URL or patch".into(),
                 hint: "".into(),
-                description: "Review a GitHub PR by URL".into(),
+                description: "Review a GitHub PR by URL, or a patch file".into(),
                 action: HubAction::OpenRemoteUrl,
                 is_header: false,
                 enabled: true,
             },
         ];
 
+        let split = crate::multiplexer::Multiplexer::detect();
+        let can_split =
+            split.is_some() && !self.tab().is_remote() && self.tab().selected_diff_file().is_some();
+        items.push(HubItem {
+            label: "── Current file ──".into(),
+            hint: "".into(),
+            description: "".into(),
+            action: HubAction::Noop,
+            is_header: true,
+            enabled: false,
+        });
+        items.push(HubItem {
+            label: "Open in split".into(),
+            hint: "E".into(),
+            description: match split {
+                Some(mux) => format!("Editor pane beside er ({})", mux.label()),
+                None => "Not inside tmux, WezTerm or kitty".into(),
+            },
+            action: HubAction::OpenInSplit,
+            is_header: false,
+            enabled: can_split,
+        });
+        items.push(HubItem {
+            label: "Open on GitHub".into(),
+            hint: "".into(),
+            description: "File at the cursor line, pinned to the head commit".into(),
+            action: HubAction::OpenLineOnForge,
+            is_header: false,
+            enabled: self.tab().selected_diff_file().is_some(),
+        });
+
+        if self.tab().mode == DiffMode::History {
+            items.push(HubItem {
+                label: "── Current commit ──".into(),
+                hint: "".into(),
+                description: "".into(),
+                action: HubAction::Noop,
+                is_header: true,
+                enabled: false,
+            });
+            items.push(HubItem {
+                label: "Open commit on GitHub".into(),
+                hint: "".into(),
+                description: "The commit selected in History".into(),
+                action: HubAction::OpenCommitOnForge,
+                is_header: false,
+                enabled: true,
+            });
+        }
+
         if has_pr {
             items.push(HubItem {
                 label: "── Current PR ──".into(),
@@ -6416,6 +8666,48 @@ impl App {
         });
     }
 
+    /// Open the current file in an editor pane beside the TUI, notifying when
+    /// no supported multiplexer is detected.
+    pub fn open_in_split(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("editing files") {
+            return Ok(());
+        }
+        if self.tab().is_remote() {
+            self.notify("Editor not available in remote mode");
+            return Ok(());
+        }
+        match self.tab().open_in_split(&self.config.split) {
+            Ok(Some(mux)) => self.notify(&format!("Opened in {} split", mux.label())),
+            Ok(None) => self.notify("Not inside tmux, WezTerm or kitty — use e"),
+            Err(e) => self.notify(&format!("{}", e)),
+        }
+        Ok(())
+    }
+
+    /// Apply a cursor position pushed by an editor (`er --focus file:line`).
+    /// Switches to the tab for `repo_root` when one is open.
+    pub fn apply_focus_request(&mut self, repo_root: &str, req: &crate::focus_ipc::FocusRequest) {
+        if let Some(idx) = self
+            .tabs
+            .iter()
+            .position(|t| !t.is_remote() && t.repo_root == repo_root)
+        {
+            if idx != self.active_tab {
+                self.select_tab(idx);
+            }
+        }
+        let label = match req.line {
+            Some(line) => format!("{}:{}", req.path, line),
+            None => req.path.clone(),
+        };
+        if self.tab_mut().focus_file_line(&req.path, req.line) {
+            self.tab_mut().panel_focus = false;
+            self.notify(&format!("Focused {}", label));
+        } else {
+            self.notify(&format!("File not in diff: {}", req.path));
+        }
+    }
+
     pub fn open_config_hub(&mut self) {
         let tab = config::SettingsScope::General;
         let items = config::config_hub_items_for_scope(&self.config, tab);
@@ -6556,6 +8848,9 @@ impl App {
                     "copy_questions_json" => {
                         let _ = self.copy_questions_json();
                     }
+                    "reset_view_prefs" => self.reset_view_prefs(),
+                    "clean_review_data" => self.start_clean_review_data(),
+                    "bug_report" => self.open_bug_report_hub(),
                     _ => {}
                 }
             }
@@ -6746,6 +9041,7 @@ impl App {
             Some(OverlayData::WorktreePicker {
                 worktrees,
                 selected,
+                ..
             }) => {
                 if *selected + 1 < worktrees.len() {
                     *selected += 1;
@@ -6769,6 +9065,15 @@ impl App {
                     *selected += 1;
                 }
             }
+            Some(OverlayData::NotificationLog { entries, selected }) => {
+                if *selected + 1 < entries.len() {
+                    *selected += 1;
+                }
+            }
+            Some(OverlayData::CommandError { scroll, .. })
+            | Some(OverlayData::Onboarding { scroll }) => {
+                *scroll = scroll.saturating_add(1);
+            }
             Some(OverlayData::ModalHub {
                 items, selected, ..
             }) => {
@@ -6814,11 +9119,16 @@ impl App {
         match &mut self.overlay {
             Some(OverlayData::WorktreePicker { selected, .. })
             | Some(OverlayData::DirectoryBrowser { selected, .. })
-            | Some(OverlayData::FilterHistory { selected, .. }) => {
+            | Some(OverlayData::FilterHistory { selected, .. })
+            | Some(OverlayData::NotificationLog { selected, .. }) => {
                 if *selected > 0 {
                     *selected -= 1;
                 }
             }
+            Some(OverlayData::CommandError { scroll, .. })
+            | Some(OverlayData::Onboarding { scroll }) => {
+                *scroll = scroll.saturating_sub(1);
+            }
             Some(OverlayData::ModalHub {
                 items, selected, ..
             }) => {
@@ -6868,12 +9178,15 @@ impl App {
             OverlayData::WorktreePicker {
                 worktrees,
                 selected,
-            } => {
-                if let Some(wt) = worktrees.get(selected) {
+                apply,
+            } => match (worktrees.get(selected), apply) {
+                (Some(wt), Some(pending)) => self.apply_hunk_to(&pending, wt),
+                (Some(wt), None) => {
                     let path = wt.path.clone();
                     self.open_in_new_tab(path)?;
                 }
-            }
+                (None, _) => {}
+            },
             OverlayData::FilterHistory {
                 history,
                 selected,
@@ -6910,8 +9223,10 @@ impl App {
                                 selected: 0,
                             });
                         }
+                    } else if no_repo::is_patch_file(&entry.name) {
+                        self.open_patch(&full_path)?;
                     }
-                    // Non-directory entries are ignored
+                    // Other files are ignored
                 } else {
                     // Restore overlay if nothing was selected
                     self.overlay = Some(OverlayData::DirectoryBrowser {
@@ -6936,6 +9251,16 @@ impl App {
                     }
                 }
             }
+            OverlayData::NotificationLog { .. } => {
+                // Keep the pane open; Enter copies the selected entry
+                self.overlay = Some(overlay);
+                self.copy_selected_notification();
+            }
+            OverlayData::CommandError { .. } => {
+                self.overlay = Some(overlay);
+                self.copy_command_error();
+            }
+            OverlayData::Onboarding { .. } => self.dismiss_onboarding(),
             OverlayData::ConfigHub { .. } => {
                 // ConfigHub enter is handled directly in handle_overlay_input
             }
@@ -6966,6 +9291,8 @@ impl App {
     pub fn overlay_close(&mut self) {
         if matches!(self.overlay, Some(OverlayData::ConfigHub { .. })) {
             self.config_hub_cancel();
+        } else if matches!(self.overlay, Some(OverlayData::Onboarding { .. })) {
+            self.dismiss_onboarding();
         } else if matches!(
             self.overlay,
             Some(OverlayData::ModalHub {
@@ -6975,6 +9302,18 @@ impl App {
         ) {
             // Navigate back to the package picker instead of closing entirely.
             self.open_verify_hub();
+        } else if matches!(
+            self.overlay,
+            Some(OverlayData::ModalHub {
+                kind: HubKind::SizeGuard,
+                ..
+            })
+        ) {
+            // The diff still has to load
+            self.overlay = None;
+            if let Err(e) = self.load_guarded_diff(false) {
+                self.notify_error(&format!("Load failed: {}", e));
+            }
         } else {
             self.overlay = None;
         }
@@ -7017,6 +9356,9 @@ impl App {
 
     /// Stage or unstage the current file (toggle based on mode)
     pub fn toggle_stage_file(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("staging") {
+            return Ok(());
+        }
         let si = self.tab().selected_file;
         if si >= self.tab().files.len() {
             return Ok(());
@@ -7025,23 +9367,24 @@ impl App {
         let mode = self.tab().mode;
         let repo_root = self.tab().repo_root.clone();
 
-        match mode {
+        let (result, done) = match mode {
             DiffMode::Branch | DiffMode::Unstaged => {
-                git::git_stage_file(&repo_root, &file_path)?;
-                self.notify(&format!("Staged: {}", file_path));
-            }
-            DiffMode::Staged => {
-                git::git_unstage_file(&repo_root, &file_path)?;
-                self.notify(&format!("Unstaged: {}", file_path));
-            }
-            DiffMode::Conflicts => {
-                git::git_stage_file(&repo_root, &file_path)?;
-                self.notify(&format!("Resolved: {}", file_path));
+                (git::git_stage_file(&repo_root, &file_path), "Staged")
             }
+            DiffMode::Staged => (git::git_unstage_file(&repo_root, &file_path), "Unstaged"),
+            DiffMode::Conflicts => (git::git_stage_file(&repo_root, &file_path), "Resolved"),
             DiffMode::History | DiffMode::Hidden | DiffMode::PrDiff | DiffMode::Tour => {
                 self.notify("Staging not available in this mode");
                 return Ok(());
             }
+        };
+        if let Err(e) = result {
+            self.report_command_error(&format!("Staging {} failed", file_path), &e);
+            return Ok(());
+        }
+        self.notify(&format!("{}: {}", done, file_path));
+        if done == "Staged" {
+            self.tab_mut().staged_in_app = true;
         }
 
         if mode == DiffMode::Conflicts {
@@ -7055,9 +9398,16 @@ impl App {
     /// Stage all files
     #[allow(dead_code)]
     pub fn stage_all(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("staging") {
+            return Ok(());
+        }
         let repo_root = self.tab().repo_root.clone();
-        git::git_stage_all(&repo_root)?;
+        if let Err(e) = git::git_stage_all(&repo_root) {
+            self.report_command_error("Staging failed", &e);
+            return Ok(());
+        }
         self.notify("Staged all files");
+        self.tab_mut().staged_in_app = true;
         self.tab_mut().refresh_diff()?;
         Ok(())
     }
@@ -7071,6 +9421,9 @@ impl App {
             return Ok(());
         }
         let path = self.tab().files[si].path.clone();
+        if !self.tab().reviewed.contains_key(&path) && self.held_back_by_checklist(&path) {
+            return Ok(());
+        }
 
         // Capture current position in visible list before toggling — needed for
         // advancing selection when show_unreviewed_only is active.
@@ -7096,13 +9449,24 @@ impl App {
                 .cloned()
                 .unwrap_or_default();
             tab.reviewed.insert(path.clone(), hash);
+            tab.clear_addressed_for_file(&path);
+            tab.record_review_pace(&path);
+            tab.unskip_file(&path)?;
         }
         tab.reviewed_revision += 1;
         tab.save_reviewed_files()?;
+        self.push_viewed_toggle(&path, !was_reviewed);
+        self.publish_event(crate::events::Event::FileReviewed {
+            file: path.clone(),
+            reviewed: !was_reviewed,
+        });
 
-        // When marking a file reviewed while show_unreviewed_only is active, advance
-        // to the next unreviewed file so the user doesn't land on a now-hidden entry.
-        if !was_reviewed && self.tab().show_unreviewed_only {
+        let mut advanced_to = None;
+        if !was_reviewed && self.tab().focus_mode {
+            advanced_to = self.focus_advance(si);
+        } else if !was_reviewed && self.tab().show_unreviewed_only {
+            // Advance to the next unreviewed file so the user doesn't land on a
+            // now-hidden entry.
             let visible_after: Vec<usize> = self
                 .tab()
                 .visible_files()
@@ -7127,7 +9491,21 @@ impl App {
         if was_reviewed {
             self.notify(&format!("Unreviewed: {}", path));
         } else {
-            self.notify(&format!("Reviewed: {}", path));
+            match advanced_to {
+                Some(next) => self.notify(&format!("Reviewed: {} → {}", path, next)),
+                None if self.tab().focus_mode => {
+                    self.notify(&format!("Reviewed: {} — all visible files reviewed", path))
+                }
+                None => self.notify(&format!("Reviewed: {}", path)),
+            }
+            if self.tab_mut().take_review_completed() {
+                let (_, total) = self.tab().reviewed_count();
+                self.send_webhook(
+                    crate::webhook::WebhookEvent::ReviewCompleted,
+                    &format!("Review completed: all {} files reviewed", total),
+                );
+                self.publish_event(crate::events::Event::ReviewCompleted { files: total });
+            }
         }
         Ok(())
     }
@@ -7161,7 +9539,7 @@ impl App {
                 .unwrap_or(0);
             let targets: Vec<(usize, bool)> = visible
                 .iter()
-                .map(|(i, f)| (*i, tab.reviewed.contains_key(&f.path)))
+                .map(|(i, f)| (*i, !tab.needs_review(&f.path)))
                 .collect();
             (pos, targets)
         };
@@ -7438,10 +9816,47 @@ mod tests {
             panel: None,
             panel_scroll: 0,
             panel_focus: false,
-            file_tree_width: 32,
+            second_panel: None,
+            second_panel_scroll: 0,
+            jump_list: jump_list::JumpList::default(),
+            marks: BTreeMap::new(),
+            folded_hunks: BTreeMap::new(),
+            view_prefs: None,
+            find: None,
+            release: None,
+            duplicates: Default::default(),
+            complexity: Default::default(),
+            contracts: Default::default(),
+            data_views: Default::default(),
+            assets: Default::default(),
+            infrastructure: Default::default(),
+            compaction_learning: Default::default(),
+            size_guard: Default::default(),
+            file_tree_width: None,
             panel_width: 40,
             focused_comment_id: None,
             focused_finding_id: None,
+            line_flash: None,
+            expanded_findings: HashSet::new(),
+            outbox: None,
+            comment_sync_rx: None,
+            last_comment_sync: Instant::now(),
+            new_comment_files: HashMap::new(),
+            repo_review_hints: BTreeMap::new(),
+            review_phases: Vec::new(),
+            review_templates: Vec::new(),
+            review_template: None,
+            file_ownership: HashMap::new(),
+            ownership_rx: None,
+            related_changes: HashMap::new(),
+            related_changes_rx: None,
+            code_ages: HashMap::new(),
+            code_age_blame: HashMap::new(),
+            code_age_rx: None,
+            pr_head_sync: None,
+            hunk_explanations: HashMap::new(),
+            hunk_explain_rx: None,
+            pre_push_rx: None,
             user_expanded: HashSet::new(),
             review_focus: ReviewFocus::Files,
             review_cursor: 0,
@@ -7451,16 +9866,44 @@ mod tests {
             filter_input: String::new(),
             filter_history: Vec::new(),
             reviewed: HashMap::new(),
+            skipped: HashMap::new(),
+            assigned: HashMap::new(),
+            multi_selected: HashSet::new(),
             current_per_file_hashes: HashMap::new(),
             show_unreviewed_only: false,
-            sort_by_mtime: false,
+            file_sort: file_sort::FileSort::Diff,
+            focus_mode: false,
             mtime_cache: HashMap::new(),
+            large_files: HashMap::new(),
+            packages: Vec::new(),
+            todo_scanner: git::TodoScanner::default(),
+            todos: Vec::new(),
+            ops_scanner: Some(git::OpsScanner::default()),
+            operational: Vec::new(),
+            migration_paths: config::MigrationsConfig::default().paths,
+            checklist_gates: BTreeMap::new(),
+            migrations: Vec::new(),
+            commit_plan: Vec::new(),
+            split_pending: None,
+            noise_suggestion: None,
+            noise_offered: None,
+            artifacts_in_diff: Vec::new(),
+            artifacts_warned: Vec::new(),
+            artifact_ignore_checked: false,
+            comment_moves: Vec::new(),
+            reanchoring: None,
+            file_encodings: HashMap::new(),
             search_query_lower: String::new(),
             ai: AiState::default(),
             diff_hash: String::new(),
             branch_diff_hash: String::new(),
             last_ai_check: None,
+            reviewed_mtime: None,
+            reviewed_on_disk: HashMap::new(),
+            addressed: HashMap::new(),
+            checkpoint_view: None,
             comment_textarea: TextArea::default(),
+            comment_drafts: Vec::new(),
             comment_file: String::new(),
             comment_hunk: 0,
             comment_reply_to: None,
@@ -7471,14 +9914,20 @@ mod tests {
             comment_finding_ref: None,
             comment_author_override: None,
             comment_side: None,
+            comment_file_level: false,
+            review_sort: ai::ReviewSort::Risk,
+            collapsed_categories: HashSet::new(),
+            checklist_edit: None,
             pr_data: None,
             pr_commits: Vec::new(),
             pr_head_ref: None,
+            pr_head_only: false,
             pr_number: None,
             history: None,
             tour: None,
             tour_is_pr: false,
             watched_config: WatchedConfig::default(),
+            test_patterns: config::TestsConfig::default().resolved_patterns(),
             watched_files: Vec::new(),
             selected_watched: None,
             show_watched: false,
@@ -7495,11 +9944,17 @@ mod tests {
             symbol_refs: None,
             pending_unmark_count: 0,
             reviewed_revision: 0,
+            author_scope: None,
+            review_pace: review_pace::ReviewPace::default(),
             committed_unpushed: false,
+            staged_in_app: false,
+            github_pr_node_id: None,
             context_overrides: HashMap::new(),
             remote_repo: None,
             local_branch_view: None,
             local_branch_checkout_root: None,
+            no_repo: None,
+            review_completed_for: None,
             command_rx: std::collections::HashMap::new(),
             command_status: std::collections::HashMap::new(),
             log_tx: agent_log_tx,
@@ -7550,6 +10005,68 @@ mod tests {
         }
     }
 
+    // ── focus_file_line ──
+
+    #[test]
+    fn focus_file_line_selects_exact_line() {
+        let hunk = make_hunk(vec![
+            make_line(LineType::Context, "a", Some(1)),
+            make_line(LineType::Add, "b", Some(2)),
+            make_line(LineType::Context, "c", Some(3)),
+        ]);
+        let files = vec![
+            make_file("src/main.rs", vec![], 1, 0),
+            make_file("src/lib.rs", vec![hunk], 1, 0),
+        ];
+        let mut tab = make_test_tab(files);
+        assert!(tab.focus_file_line("src/lib.rs", Some(2)));
+        assert_eq!(tab.selected_file, 1);
+        assert_eq!(tab.current_hunk, 0);
+        assert_eq!(tab.current_line, Some(1));
+    }
+
+    #[test]
+    fn jump_to_finding_lands_on_the_moved_line_and_flashes_it() {
+        let first = make_hunk(vec![make_line(LineType::Context, "a", Some(1))]);
+        let second = make_hunk(vec![
+            make_line(LineType::Context, "b", Some(20)),
+            make_line(LineType::Add, "c", Some(21)),
+        ]);
+        let mut app = App::new_for_test(vec![make_file("src/lib.rs", vec![first, second], 1, 0)]);
+        app.tab_mut().ai.review = Some(
+            serde_json::from_str(
+                r#"{"version":1,"diff_hash":"h","files":{"src/lib.rs":{"risk":"high","findings":[
+                    {"id":"f1","severity":"high","category":"security","title":"t","hunk_index":0,"line_start":21,"line_end":22}
+                ]}}}"#,
+            )
+            .unwrap(),
+        );
+        app.tab_mut().review_focus = ReviewFocus::Categories;
+        app.tab_mut().review_cursor = 1;
+        app.review_jump_to_file();
+
+        let tab = app.tab();
+        assert_eq!((tab.current_hunk, tab.current_line), (1, Some(1)));
+        assert_eq!(tab.focused_finding_id.as_deref(), Some("f1"));
+        assert!(tab.is_line_flashed("src/lib.rs", 22));
+        assert!(!tab.is_line_flashed("src/lib.rs", 20));
+        assert!(!tab.is_line_flashed("src/main.rs", 21));
+    }
+
+    #[test]
+    fn focus_file_line_falls_back_to_nearest_hunk() {
+        let mut far = make_hunk(vec![make_line(LineType::Add, "x", Some(100))]);
+        far.new_start = 100;
+        far.new_count = 1;
+        let near = make_hunk(vec![make_line(LineType::Add, "y", Some(1))]);
+        let files = vec![make_file("a.rs", vec![near, far], 2, 0)];
+        let mut tab = make_test_tab(files);
+        assert!(tab.focus_file_line("a.rs", Some(90)));
+        assert_eq!(tab.current_hunk, 1);
+        assert_eq!(tab.current_line, None);
+        assert!(!tab.focus_file_line("missing.rs", Some(1)));
+    }
+
     // ── truncate ──
 
     #[test]
@@ -7716,6 +10233,28 @@ mod tests {
         assert_eq!(tab.reviewed_count(), (2, 2));
     }
 
+    #[test]
+    fn review_completed_is_announced_once_per_diff() {
+        let files = vec![
+            make_file("src/main.rs", vec![], 1, 0),
+            make_file("src/lib.rs", vec![], 1, 0),
+        ];
+        let mut tab = make_test_tab(files);
+        tab.diff_hash = "one".into();
+        tab.reviewed
+            .insert("src/main.rs".to_string(), String::new());
+        assert!(!tab.take_review_completed());
+        tab.reviewed.insert("src/lib.rs".to_string(), String::new());
+        assert!(tab.take_review_completed());
+        // Unmarking and re-marking the last file stays quiet
+        tab.reviewed.remove("src/lib.rs");
+        tab.reviewed.insert("src/lib.rs".to_string(), String::new());
+        assert!(!tab.take_review_completed());
+        // A new diff is a new review
+        tab.diff_hash = "two".into();
+        assert!(tab.take_review_completed());
+    }
+
     #[test]
     fn active_reviewed_count_ignores_orphan_reviewed_paths() {
         let files = vec![
@@ -8338,6 +10877,34 @@ mod tests {
         assert_eq!(tab.panel, Some(crate::ai::PanelContent::AgentLog));
     }
 
+    #[test]
+    fn pinned_panel_stays_while_the_other_cycles_and_tab_visits_both() {
+        use crate::ai::PanelContent::{AgentLog, FileDetail};
+        let mut tab = make_test_tab(vec![]);
+        tab.panel = Some(FileDetail);
+        tab.toggle_pin_panel();
+        assert_eq!(
+            (tab.panel, tab.second_panel),
+            (Some(AgentLog), Some(FileDetail))
+        );
+        assert_eq!(tab.open_panels(), vec![FileDetail, AgentLog]);
+
+        // diff → FileDetail (drawn first) → AgentLog → diff
+        tab.cycle_panel_focus();
+        assert!(tab.panel_has_focus(FileDetail));
+        tab.cycle_panel_focus();
+        assert!(tab.panel_has_focus(AgentLog));
+        tab.cycle_panel_focus();
+        assert!(!tab.panel_focus);
+
+        // Cycling skips the pinned panel; closing the other leaves it open
+        tab.toggle_panel();
+        assert_eq!((tab.panel, tab.second_panel), (Some(FileDetail), None));
+        tab.toggle_pin_panel();
+        tab.toggle_pin_panel();
+        assert_eq!((tab.panel, tab.second_panel), (Some(AgentLog), None));
+    }
+
     #[test]
     fn toggle_panel_skips_ai_goes_to_pr_when_pr_available() {
         let mut tab = make_test_tab(vec![]);
@@ -8352,6 +10919,9 @@ mod tests {
             head_branch: "feat".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
         tab.panel = Some(crate::ai::PanelContent::FileDetail);
         tab.toggle_panel();
@@ -8384,6 +10954,9 @@ mod tests {
             head_branch: "feat".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
         tab.panel = Some(crate::ai::PanelContent::AiSummary);
         tab.toggle_panel();
@@ -8468,6 +11041,9 @@ mod tests {
             head_branch: "feature".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
         assert_eq!(tab.panel, None);
         tab.toggle_panel(); // None → FileDetail
@@ -8528,6 +11104,9 @@ mod tests {
             head_branch: "feature".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
         assert_eq!(tab.panel, None);
         tab.toggle_panel_reverse(); // None → AgentLog (always last in forward cycle)
@@ -8567,6 +11146,9 @@ mod tests {
             head_branch: "feature".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         });
         // From PrOverview, no AI present: should go to FileDetail (skip AiSummary)
         tab.panel = Some(crate::ai::PanelContent::PrOverview);
@@ -8784,20 +11366,36 @@ mod tests {
             active_tab: 0,
             input_mode: InputMode::Normal,
             should_quit: false,
+            read_only: false,
             overlay: None,
             watching: false,
             watch_message: None,
             watch_message_ticks: 0,
             watch_message_max_ticks: 20,
+            watch_message_level: NotificationLevel::Info,
+            notification_log: NotificationLog::default(),
             ai_poll_counter: 0,
             remote_url_input: String::new(),
+            skip_reason_input: String::new(),
+            assignee_input: String::new(),
+            quick_diff_input: String::new(),
             config: ErConfig::default(),
             current_ai_provider: None,
             current_ai_model: None,
             current_ai_effort: None,
             pending_ai_selection_override: None,
             pending_hub_action: None,
+            config_watch: config_reload::ConfigWatch::default(),
+            update_check: update_check::UpdateCheck::default(),
+            usage: usage::UsageSession::default(),
+            followups: followups::FollowUps::default(),
+            bug_report: bug_report::BugReport::default(),
+            viewed_pushes: viewed_sync::ViewedPushes::default(),
+            comment_push: github_push::CommentPush::default(),
             last_terminal_width: 0,
+            confirm_prompt: None,
+            file_tree_toggled: false,
+            zoomed: false,
             panels_visible: PanelsVisible::default(),
             background_tasks: std::collections::HashMap::new(),
             recent_background_tasks: Vec::new(),
@@ -8806,6 +11404,9 @@ mod tests {
             active_arena_runs: std::collections::HashMap::new(),
             model_discovery_inflight: std::collections::HashSet::new(),
             pending_model_discovery: None,
+            key_prefix: None,
+            pending_count: None,
+            last_motion: None,
         }
     }
 
@@ -9271,6 +11872,68 @@ mod tests {
         assert!(app.tab().ai.has_notes());
     }
 
+    #[test]
+    fn file_note_has_no_anchor_and_lists_unanchored() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_string_lossy().into_owned();
+        let files = vec![make_file(
+            "src/main.rs",
+            vec![make_hunk(vec![make_line(LineType::Add, "x", Some(1))])],
+            1,
+            0,
+        )];
+        let mut tab = make_test_tab(files);
+        tab.er_root = ErRoot::RepoLocal(root.clone());
+        tab.repo_root = root.clone();
+        let mut app = make_test_app(tab);
+
+        app.start_file_comment(CommentType::Note);
+        assert_eq!(
+            app.tab().current_comment_draft().map(|d| d.file_level),
+            None
+        );
+        app.tab_mut().comment_textarea = TextArea::new(vec!["needs a design doc".to_string()]);
+        assert!(app.tab().current_comment_draft().unwrap().file_level);
+        app.submit_comment().unwrap();
+
+        let note = &app.tab().ai.notes.as_ref().unwrap().notes[0];
+        assert_eq!(note.hunk_index, None);
+        assert_eq!(note.line_start, None);
+        let unanchored = app.tab().ai.comments_for_file_unanchored("src/main.rs");
+        assert_eq!(unanchored.len(), 1);
+
+        // The next ordinary comment is anchored again
+        app.start_comment(CommentType::Note);
+        assert!(!app.tab().comment_file_level);
+    }
+
+    #[test]
+    fn review_note_goes_in_body_once() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path().to_string_lossy().into_owned();
+        let mut tab = make_test_tab(vec![]);
+        tab.er_root = ErRoot::RepoLocal(root.clone());
+        tab.repo_root = root.clone();
+        let mut app = make_test_app(tab);
+
+        app.start_review_comment(CommentType::Note);
+        app.toggle_comment_type();
+        assert_eq!(app.tab().comment_type, CommentType::Question);
+        app.tab_mut().comment_textarea =
+            TextArea::new(vec!["why not reuse the cache?".to_string()]);
+        app.submit_comment().unwrap();
+
+        let notes = app.tab().ai.review_notes();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].file(), "");
+        let (body, ids) = app.tab().ai.review_body();
+        assert!(body.contains("- why not reuse the cache?"));
+
+        app.mark_review_notes_sent(&ids).unwrap();
+        assert!(app.tab().ai.review_body().0.is_empty());
+        assert_eq!(app.tab().ai.review_notes().len(), 1);
+    }
+
     #[test]
     fn submit_comment_empty_text_returns_to_normal() {
         let files = vec![make_file(
@@ -9327,6 +11990,7 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".to_string(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 in_reply_to: None,
                 author: "You".to_string(),
                 promoted_to: None,
@@ -9601,6 +12265,94 @@ mod tests {
             .contains(&crate::storage::slug_branch("main")));
     }
 
+    #[test]
+    fn check_ai_files_changed_picks_up_external_reviewed_marks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut tab = make_test_tab(vec![]);
+        tab.er_root = ErRoot::RepoLocal(tmp.path().to_string_lossy().into_owned());
+        let path = tab.er_root.reviewed_path();
+        std::fs::create_dir_all(std::path::Path::new(&path).parent().unwrap()).unwrap();
+
+        std::fs::write(&path, "src/a.rs\th1\n").unwrap();
+        assert!(tab.check_ai_files_changed());
+        assert_eq!(tab.reviewed.get("src/a.rs").map(String::as_str), Some("h1"));
+
+        // Unchanged file: no reload reported
+        assert!(!tab.check_ai_files_changed());
+    }
+
+    #[test]
+    fn check_ai_files_delta_counts_new_comments() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut tab = make_test_tab(vec![]);
+        tab.er_root = ErRoot::RepoLocal(tmp.path().to_string_lossy().into_owned());
+        assert_eq!(tab.check_ai_files_delta(), None);
+
+        std::fs::create_dir_all(tab.er_dir()).unwrap();
+        let question = |id: &str| {
+            serde_json::json!({
+                "id": id, "file": "src/a.rs", "hunk_index": 0, "line_start": 1, "text": "Why?"
+            })
+        };
+        let questions = serde_json::json!({
+            "version": 1,
+            "diff_hash": tab.branch_diff_hash,
+            "questions": [question("q-1"), question("q-2")],
+        });
+        std::fs::write(
+            format!("{}/questions.json", tab.er_dir()),
+            questions.to_string(),
+        )
+        .unwrap();
+        let delta = tab.check_ai_files_delta().unwrap();
+        assert_eq!(delta.comments_added, 2);
+        assert_eq!(delta.describe().as_deref(), Some("2 comments added"));
+        assert_eq!(tab.ai_watch_dirs(), [tab.er_dir()]);
+    }
+
+    #[test]
+    fn regenerated_review_keeps_focus_and_lifecycle_by_id() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut tab = make_test_tab(vec![]);
+        tab.er_root = ErRoot::RepoLocal(tmp.path().to_string_lossy().into_owned());
+        std::fs::create_dir_all(tab.er_dir()).unwrap();
+        let write_review = |tab: &TabState, findings: serde_json::Value| {
+            let review = serde_json::json!({
+                "version": 1,
+                "diff_hash": tab.branch_diff_hash,
+                "files": { "src/a.rs": { "risk": "high", "findings": findings } },
+            });
+            std::fs::write(format!("{}/review.json", tab.er_dir()), review.to_string()).unwrap();
+        };
+        write_review(
+            &tab,
+            serde_json::json!([
+                { "id": "f-1", "severity": "high", "title": "Leak", "hunk_index": 0,
+                  "resolved": true, "resolved_at": "2026-01-02T00:00:00Z" },
+                { "id": "f-2", "severity": "low", "title": "Naming", "hunk_index": 0 },
+            ]),
+        );
+        tab.check_ai_files_delta().unwrap();
+        tab.focused_finding_id = Some("f-1".into());
+        tab.expanded_findings = ["f-1".to_string(), "f-2".to_string()].into();
+
+        // The agent rewrites the review: f-1 again without its resolution, f-2 gone
+        tab.last_ai_check = None;
+        write_review(
+            &tab,
+            serde_json::json!([
+                { "id": "f-1", "severity": "high", "title": "Leak", "hunk_index": 0 },
+                { "id": "f-3", "severity": "low", "title": "Unused", "hunk_index": 0 },
+            ]),
+        );
+        let delta = tab.check_ai_files_delta().unwrap();
+        assert_eq!((delta.findings_added, delta.findings_removed), (1, 1));
+        assert_eq!(tab.focused_finding_id.as_deref(), Some("f-1"));
+        assert_eq!(tab.expanded_findings, ["f-1".to_string()].into());
+        let findings = &tab.ai.review.as_ref().unwrap().files["src/a.rs"].findings;
+        assert!(findings.iter().find(|f| f.id == "f-1").unwrap().resolved);
+    }
+
     #[test]
     fn apply_checkout_branch_storage_change_reloads_reviewed() {
         let _guard = crate::storage::STORAGE_TEST_ENV_LOCK
@@ -10287,6 +13039,11 @@ mod tests {
             h_scroll: 0,
             all_loaded: true,
             diff_cache: DiffCache::new(5),
+            messages: HashMap::new(),
+            net_zero: HashSet::new(),
+            fold_net_zero: false,
+            search: Default::default(),
+            prs: Default::default(),
         });
 
         assert_eq!(tab.files.len(), 1);
@@ -11468,6 +14225,43 @@ mod tests {
         );
     }
 
+    /// A save applies only this tab's own toggles, so marks the MCP session
+    /// wrote meanwhile survive, and unmarking the last file removes it.
+    #[test]
+    fn save_reviewed_keeps_marks_written_meanwhile() {
+        let _guard = crate::storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::env::set_var("ER_STORAGE_ROOT", tmp.path());
+
+        let mut tab = TabState::new_for_test(vec![]);
+        tab.repo_root = "/home/user/my-project".to_string();
+        tab.current_branch = "main".to_string();
+        tab.mode = DiffMode::Branch;
+        tab.apply_managed_root();
+        tab.reviewed.insert("a.rs".to_string(), "ha".to_string());
+        tab.save_reviewed_files().unwrap();
+
+        // The MCP session marks b.rs behind the tab's back
+        let path = tab.er_root.reviewed_path();
+        std::fs::write(&path, "a.rs\tha\nb.rs\thb\n").unwrap();
+
+        tab.reviewed.insert("c.rs".to_string(), "hc".to_string());
+        tab.reviewed.remove("a.rs");
+        tab.save_reviewed_files().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "b.rs\thb\nc.rs\thc\n"
+        );
+        assert!(tab.reviewed.contains_key("b.rs"));
+
+        tab.reviewed.clear();
+        tab.save_reviewed_files().unwrap();
+        assert!(!std::path::Path::new(&path).exists());
+        std::env::remove_var("ER_STORAGE_ROOT");
+    }
+
     /// Remote (--remote) reviewed entries must persist to disk even after the guard
     /// `is_remote() && review_bucket() != Pr` was lifted.
     ///
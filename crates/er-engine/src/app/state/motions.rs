@@ -0,0 +1,131 @@
+//! Counted and repeatable motions.
+//!
+//! A count before a motion runs it that many times: `Alt+5 n` skips five
+//! hunks, `Alt+1 0 ↓` moves ten lines. Plain digits switch mode tabs, so a
+//! count starts with Alt and any digits typed after it extend it. `.` runs
+//! the last motion again with its count. A motion stops early once it no
+//! longer moves, at the last hunk or file.
+
+use super::App;
+
+/// Largest count taken; more digits are ignored.
+const MAX_COUNT: usize = 9999;
+
+/// A navigation key that takes a count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    NextHunk,
+    PrevHunk,
+    NextLine,
+    PrevLine,
+    NextFile,
+    PrevFile,
+    /// J/K: comments, questions and findings across files
+    NextHint,
+    PrevHint,
+    NextFinding,
+    PrevFinding,
+}
+
+impl App {
+    /// A digit of the count being typed.
+    pub fn push_count_digit(&mut self, digit: char) {
+        let Some(d) = digit.to_digit(10) else {
+            return;
+        };
+        let count = self.pending_count.unwrap_or(0) * 10 + d as usize;
+        // A leading 0 is no count
+        self.pending_count = (count > 0).then_some(count.min(MAX_COUNT));
+    }
+
+    /// Run `motion` `count` times and remember it for `.`.
+    pub fn run_motion(&mut self, motion: Motion, count: usize) {
+        self.last_motion = Some((motion, count));
+        for _ in 0..count.max(1) {
+            let before = self.motion_position();
+            self.step(motion);
+            if self.motion_position() == before {
+                break;
+            }
+        }
+    }
+
+    /// `.`: the last motion again.
+    pub fn repeat_motion(&mut self) {
+        match self.last_motion {
+            Some((motion, count)) => self.run_motion(motion, count),
+            None => self.notify("Nothing to repeat yet"),
+        }
+    }
+
+    fn step(&mut self, motion: Motion) {
+        match motion {
+            Motion::NextHunk => self.tab_mut().next_hunk(),
+            Motion::PrevHunk => self.tab_mut().prev_hunk(),
+            Motion::NextLine => self.tab_mut().next_line(),
+            Motion::PrevLine => self.tab_mut().prev_line(),
+            Motion::NextFile | Motion::PrevFile => {
+                if motion == Motion::NextFile {
+                    self.tab_mut().next_file();
+                } else {
+                    self.tab_mut().prev_file();
+                }
+                let threshold = self.config.display.auto_context_threshold;
+                self.tab_mut().maybe_auto_expand_context(threshold);
+            }
+            Motion::NextHint => self.next_hint(),
+            Motion::PrevHint => self.prev_hint(),
+            Motion::NextFinding => self.next_finding(),
+            Motion::PrevFinding => self.prev_finding(),
+        }
+    }
+
+    /// Everything a motion can move.
+    fn motion_position(&self) -> (usize, usize, Option<usize>, Option<String>, Option<String>) {
+        let tab = self.tab();
+        (
+            tab.selected_file,
+            tab.current_hunk,
+            tab.current_line,
+            tab.focused_comment_id.clone(),
+            tab.focused_finding_id.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    fn hunks(n: usize) -> App {
+        let mut diff = String::from("diff --git a/f b/f\n--- a/f\n+++ b/f\n");
+        for i in 0..n {
+            let line = i * 10 + 1;
+            diff.push_str(&format!("@@ -{line} +{line} @@\n-a{i}\n+b{i}\n"));
+        }
+        App::new_for_test(parse_diff(&diff))
+    }
+
+    #[test]
+    fn counts_run_a_motion_several_times() {
+        let mut app = hunks(12);
+        app.push_count_digit('1');
+        app.push_count_digit('0');
+        assert_eq!(app.pending_count, Some(10));
+        let count = app.pending_count.take().unwrap();
+        app.run_motion(Motion::NextHunk, count);
+        assert_eq!(app.tab().current_hunk, 10);
+
+        // `.` repeats with the same count; a count past the end stops there
+        app.run_motion(Motion::PrevHunk, 3);
+        assert_eq!(app.tab().current_hunk, 7);
+        app.repeat_motion();
+        assert_eq!(app.tab().current_hunk, 4);
+        app.run_motion(Motion::NextHunk, 50);
+        assert_eq!(app.tab().current_hunk, 11);
+
+        app.push_count_digit('0');
+        assert_eq!(app.pending_count, None);
+    }
+}
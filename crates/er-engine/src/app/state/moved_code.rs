@@ -0,0 +1,310 @@
+//! "Where did this code go?" (Alt+m on a deleted line).
+//!
+//! The run of deleted lines under the cursor is looked for among the added
+//! lines of the whole diff, its own file included, since moved code arrives
+//! as additions. Windows of added lines start where a block line turns up
+//! again (exactly, or near-identical for the block's longest line) and
+//! score the mean [`similarity`] of their lines to the block's, whitespace
+//! ignored; the best one at [`MOVED_MIN`] or more wins and the cursor jumps
+//! there (Ctrl+o comes back). Otherwise the block's most distinctive line
+//! is grepped for in the repo at the diff's new side, which finds the copy
+//! that was kept when a duplicate was deleted.
+
+use super::{App, DiffMode, TabState};
+use crate::ai::similarity;
+use crate::git::{self, DiffFile, DiffHunk, LineType};
+
+/// Windows scoring less are a different block.
+pub const MOVED_MIN: f32 = 0.6;
+
+/// Longest block searched; a bigger deletion is matched by its start.
+const MAX_BLOCK_LINES: usize = 50;
+
+/// Repo matches listed.
+const REPO_LIMIT: usize = 3;
+
+/// Where a deleted block turned up among the added lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovedMatch {
+    pub path: String,
+    /// New-side number of the window's first line
+    pub line: usize,
+    /// Mean line similarity, 0.0–1.0
+    pub score: f32,
+}
+
+/// An added, non-blank line of a file.
+struct Added<'a> {
+    new_num: usize,
+    text: &'a str,
+}
+
+fn added_lines(hunks: &[DiffHunk]) -> Vec<Added<'_>> {
+    hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| l.line_type == LineType::Add && !l.content.trim().is_empty())
+        .filter_map(|l| {
+            Some(Added {
+                new_num: l.new_num?,
+                text: l.content.trim(),
+            })
+        })
+        .collect()
+}
+
+/// The best window of `added` for `block`, as (first index, score).
+fn best_window(block: &[&str], added: &[Added]) -> Option<(usize, f32)> {
+    let longest = block
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, l)| l.len())
+        .map(|(i, _)| i)?;
+    let mut starts: Vec<usize> = Vec::new();
+    for (p, a) in added.iter().enumerate() {
+        for (i, line) in block.iter().enumerate() {
+            let near = || {
+                i == longest
+                    && a.text.len().abs_diff(line.len()) * 5 <= a.text.len().max(line.len())
+                    && similarity(a.text, line) >= 0.8
+            };
+            if p >= i && (a.text == *line || near()) {
+                starts.push(p - i);
+            }
+        }
+    }
+    starts.sort_unstable();
+    starts.dedup();
+    starts
+        .into_iter()
+        .map(|start| {
+            let total: f32 = block
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    added
+                        .get(start + i)
+                        .map_or(0.0, |a| similarity(a.text, line))
+                })
+                .sum();
+            (start, total / block.len() as f32)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Where `block` went in `files`: the best window at [`MOVED_MIN`].
+pub fn find_moved(block: &[&str], files: &[(&str, &[DiffHunk])]) -> Option<MovedMatch> {
+    files
+        .iter()
+        .filter_map(|(path, hunks)| {
+            let added = added_lines(hunks);
+            let (start, score) = best_window(block, &added)?;
+            Some(MovedMatch {
+                path: path.to_string(),
+                line: added[start].new_num,
+                score,
+            })
+        })
+        .filter(|m| m.score >= MOVED_MIN)
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+impl TabState {
+    /// The trimmed, non-blank lines of the run of deletions around the
+    /// cursor. `None` when the cursor isn't on a deleted line.
+    pub fn deleted_block(&self) -> Option<Vec<String>> {
+        let lines = &self
+            .selected_diff_file()?
+            .hunks
+            .get(self.current_hunk)?
+            .lines;
+        let at = self.current_line?;
+        let deleted = |i: &usize| lines[*i].line_type == LineType::Delete;
+        if !deleted(&at) {
+            return None;
+        }
+        let start = (0..at).rev().take_while(deleted).last().unwrap_or(at);
+        let block: Vec<String> = (start..lines.len())
+            .take_while(deleted)
+            .map(|i| lines[i].content.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .take(MAX_BLOCK_LINES)
+            .collect();
+        (!block.is_empty()).then_some(block)
+    }
+
+    /// The lazy stubs among `files`, parsed for a search over the whole
+    /// diff and dropped after it; `None` for files already parsed.
+    pub(super) fn parsed_stubs(&self) -> Vec<Option<DiffFile>> {
+        self.files
+            .iter()
+            .map(|f| {
+                let stub = self.lazy_mode && f.hunks.is_empty() && !f.compacted;
+                let raw = self.raw_diff.as_ref().filter(|_| stub)?;
+                let header = self.file_headers.iter().find(|h| h.path == f.path)?;
+                Some(git::parse_file_at_offset(raw, header))
+            })
+            .collect()
+    }
+
+    /// Every file's hunks, `parsed` (from [`Self::parsed_stubs`]) standing
+    /// in for the stubs.
+    pub(super) fn all_hunks<'a>(
+        &'a self,
+        parsed: &'a [Option<DiffFile>],
+    ) -> Vec<(&'a str, &'a [DiffHunk])> {
+        self.files
+            .iter()
+            .zip(parsed)
+            .map(|(f, p)| (f.path.as_str(), p.as_ref().unwrap_or(f).hunks.as_slice()))
+            .collect()
+    }
+
+    /// The revision the repo search reads: `None` for the working tree,
+    /// the viewed branch or PR head otherwise.
+    pub(super) fn new_side_rev(&self) -> Option<String> {
+        if self.local_branch_checkout_root.is_some() {
+            return None;
+        }
+        self.pr_head_ref
+            .clone()
+            .or_else(|| self.local_branch_view.clone())
+    }
+}
+
+impl App {
+    /// Alt+m on a deleted line: jump to where its block went, or say where
+    /// in the repo it still is. On a focused duplicate finding: jump to
+    /// the copy.
+    pub fn find_moved_code(&mut self) {
+        if matches!(self.tab().mode, DiffMode::History | DiffMode::Tour) {
+            return;
+        }
+        let Some(block) = self.tab().deleted_block() else {
+            if let Some((path, line)) = self.tab().focused_duplicate_copy() {
+                if self.tab_mut().focus_file_line(&path, Some(line)) {
+                    self.notify("Jumped to the copy — Ctrl+o back");
+                } else {
+                    self.notify(&format!(
+                        "The copy is at {}:{}, outside the diff",
+                        path, line
+                    ));
+                }
+                return;
+            }
+            self.notify("Put the cursor on a deleted line to find where it went");
+            return;
+        };
+        let block: Vec<&str> = block.iter().map(String::as_str).collect();
+        let tab = self.tab();
+        let parsed = tab.parsed_stubs();
+        let files = tab.all_hunks(&parsed);
+        if let Some(found) = find_moved(&block, &files) {
+            let here = tab.selected_diff_file().map(|f| f.path.clone());
+            let what = if found.score >= 0.95 {
+                "Moved"
+            } else {
+                "Similar code"
+            };
+            let place = if here.as_deref() == Some(found.path.as_str()) {
+                format!("line {}", found.line)
+            } else {
+                format!("{}:{}", found.path, found.line)
+            };
+            let message = format!(
+                "{} at {} ({:.0}% alike) — Ctrl+o back",
+                what,
+                place,
+                found.score * 100.0
+            );
+            self.tab_mut()
+                .focus_file_line(&found.path, Some(found.line));
+            self.notify(&message);
+            return;
+        }
+
+        if tab.is_remote() {
+            self.notify("Not in the diff: deleted, not moved");
+            return;
+        }
+        let needle = block.iter().max_by_key(|l| l.len()).copied().unwrap_or("");
+        let rev = tab.new_side_rev();
+        match git::git_grep_fixed(&tab.repo_root, rev.as_deref(), needle, REPO_LIMIT + 1) {
+            Ok(found) if found.is_empty() => {
+                self.notify("Not in the diff or the repo: deleted, not moved")
+            }
+            Ok(found) => {
+                let places: Vec<String> = found
+                    .iter()
+                    .take(REPO_LIMIT)
+                    .map(|(path, line)| format!("{}:{}", path, line))
+                    .collect();
+                let more = if found.len() > REPO_LIMIT {
+                    " and more"
+                } else {
+                    ""
+                };
+                self.notify(&format!(
+                    "Not in the diff; still in the repo at {}{}",
+                    places.join(", "),
+                    more
+                ));
+            }
+            Err(e) => self.report_command_error("Searching the repo failed", &e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    const DIFF: &str = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n\
+                        @@ -1,6 +1,2 @@\n fn keep() {}\n-fn moved(x: u32) -> u32 {\n-    x * 2\n-}\n-\n-fn gone() { panic!() }\n fn end() {}\n\
+                        diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n\
+                        @@ -1,1 +1,5 @@\n use std::fmt;\n+\n+pub fn moved(x: u32) -> u32 {\n+        x * 2\n+}\n";
+
+    #[test]
+    fn deleted_block_spans_the_run_of_deletions() {
+        let mut tab = TabState::new_for_test(parse_diff(DIFF));
+        tab.current_line = Some(0);
+        assert_eq!(tab.deleted_block(), None);
+        tab.current_line = Some(3);
+        assert_eq!(
+            tab.deleted_block().unwrap(),
+            vec![
+                "fn moved(x: u32) -> u32 {",
+                "x * 2",
+                "}",
+                "fn gone() { panic!() }"
+            ]
+        );
+    }
+
+    #[test]
+    fn moved_blocks_are_found_in_other_files() {
+        let files = parse_diff(DIFF);
+        let hunks: Vec<(&str, &[DiffHunk])> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.hunks.as_slice()))
+            .collect();
+        // Re-indented and made pub: still the best window, and alike enough
+        let found = find_moved(&["fn moved(x: u32) -> u32 {", "x * 2", "}"], &hunks).unwrap();
+        assert_eq!((found.path.as_str(), found.line), ("b.rs", 3));
+        assert!(found.score > 0.9 && found.score < 1.0, "{}", found.score);
+
+        assert_eq!(find_moved(&["fn gone() { panic!() }"], &hunks), None);
+    }
+
+    #[test]
+    fn alt_m_jumps_to_the_moved_code() {
+        let mut app = App::new_for_test(parse_diff(DIFF));
+        app.tab_mut().current_line = Some(1);
+        app.find_moved_code();
+        assert_eq!(app.tab().selected_file, 1);
+        assert_eq!(app.tab().current_line, Some(2));
+        assert!(app.tab_mut().jump_back());
+        assert_eq!(app.tab().selected_file, 0);
+    }
+}
@@ -0,0 +1,493 @@
+//! Grouped actions on several files. `Alt+s` adds the selected file to the
+//! multi-selection (again to drop it) and `Alt+j` / `Alt+k` extend it while
+//! moving; `Alt+a` opens the Selection hub — mark reviewed, stage, filter to
+//! or hide, compact, assign to a reviewer. While files are selected, Space
+//! and `s` act on all of them and Esc clears the selection.
+//!
+//! Assignments are kept in their own sidecar next to `skipped`
+//! (`path\treviewer` lines) and shown in the file tree as `@reviewer`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::motions::Motion;
+use super::{App, DiffMode, HubAction, HubItem, HubKind, InputMode, OverlayData, TabState};
+use crate::git;
+
+/// `path\treviewer` lines; lines without a reviewer are dropped.
+fn parse_assigned(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|l| l.split_once('\t'))
+        .filter(|(_, reviewer)| !reviewer.trim().is_empty())
+        .map(|(path, reviewer)| (path.to_string(), reviewer.trim().to_string()))
+        .collect()
+}
+
+impl TabState {
+    /// Multi-selected files in file-list order, leaving out any the diff no
+    /// longer has.
+    pub fn selection_paths(&self) -> Vec<String> {
+        if self.multi_selected.is_empty() {
+            return Vec::new();
+        }
+        self.files
+            .iter()
+            .filter(|f| self.multi_selected.contains(&f.path))
+            .map(|f| f.path.clone())
+            .collect()
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.files
+            .iter()
+            .any(|f| self.multi_selected.contains(&f.path))
+    }
+
+    pub(super) fn load_assigned(&mut self) {
+        self.assigned = std::fs::read_to_string(self.er_root.assigned_path())
+            .map(|content| parse_assigned(&content))
+            .unwrap_or_default();
+    }
+
+    fn save_assigned(&self) -> Result<()> {
+        if self.is_remote() && self.review_bucket() != super::ReviewBucket::Pr {
+            return Ok(());
+        }
+        let path = self.er_root.assigned_path();
+        if self.assigned.is_empty() {
+            let _ = std::fs::remove_file(&path);
+            return Ok(());
+        }
+        if let Some(dir) = std::path::Path::new(&path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut entries: Vec<(&String, &String)> = self.assigned.iter().collect();
+        entries.sort_by_key(|(p, _)| p.as_str());
+        let content = entries
+            .iter()
+            .map(|(p, reviewer)| format!("{}\t{}", p, reviewer))
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /// Assign `paths` to `reviewer`; an empty name drops their assignment.
+    pub fn assign_files(&mut self, paths: &[String], reviewer: &str) -> Result<()> {
+        let reviewer = reviewer.trim().trim_start_matches('@').replace('\t', " ");
+        for path in paths {
+            if reviewer.is_empty() {
+                self.assigned.remove(path);
+            } else {
+                self.assigned.insert(path.clone(), reviewer.clone());
+            }
+        }
+        self.save_assigned()
+    }
+}
+
+impl App {
+    fn multi_select_available(&mut self) -> bool {
+        if matches!(self.tab().mode, DiffMode::History | DiffMode::Tour) {
+            self.notify("Multi-selection works on the diff's file list");
+            return false;
+        }
+        self.tab().selected_diff_file().is_some()
+    }
+
+    fn notify_selection(&mut self) {
+        let n = self.tab().selection_paths().len();
+        if n == 0 {
+            self.notify("Selection cleared");
+        } else {
+            self.notify(&format!("{} file(s) selected — Alt+a for actions", n));
+        }
+    }
+
+    /// `Alt+s`: add the selected file to the multi-selection, or drop it.
+    pub fn toggle_file_selection(&mut self) {
+        if !self.multi_select_available() {
+            return;
+        }
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        let tab = self.tab_mut();
+        if !tab.multi_selected.remove(&path) {
+            tab.multi_selected.insert(path);
+        }
+        self.notify_selection();
+    }
+
+    /// `Alt+j` / `Alt+k`: select the current file and the one `motion`
+    /// moves to.
+    pub fn extend_file_selection(&mut self, motion: Motion) {
+        if !self.multi_select_available() {
+            return;
+        }
+        for step in 0..2 {
+            if step == 1 {
+                self.run_motion(motion, 1);
+            }
+            if let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) {
+                self.tab_mut().multi_selected.insert(path);
+            }
+        }
+        self.notify_selection();
+    }
+
+    /// Esc: drop the multi-selection. False when nothing was selected.
+    pub fn clear_file_selection(&mut self) -> bool {
+        let had = self.tab().has_selection();
+        self.tab_mut().multi_selected.clear();
+        had
+    }
+
+    /// `Alt+a`: what can be done with the selected files.
+    pub fn open_selection_hub(&mut self) {
+        let tab = self.tab();
+        let paths = tab.selection_paths();
+        if paths.is_empty() {
+            self.notify("No files selected — Alt+s selects a file, Alt+j / Alt+k extend");
+            return;
+        }
+        let all_reviewed = paths.iter().all(|p| tab.reviewed.contains_key(p));
+        let all_compacted = tab
+            .files
+            .iter()
+            .filter(|f| tab.multi_selected.contains(&f.path))
+            .all(|f| f.compacted);
+        let stage = match tab.mode {
+            DiffMode::Staged => Some("Unstage"),
+            DiffMode::Conflicts => Some("Mark resolved"),
+            DiffMode::Branch | DiffMode::Unstaged if !tab.is_remote() => Some("Stage"),
+            _ => None,
+        };
+        let item = |label: &str, hint: &str, description: String, action, enabled| HubItem {
+            label: label.into(),
+            hint: hint.into(),
+            description,
+            action,
+            is_header: false,
+            enabled,
+        };
+        let n = paths.len();
+        let items = vec![
+            item(
+                if all_reviewed {
+                    "Unmark reviewed"
+                } else {
+                    "Mark reviewed"
+                },
+                "Space",
+                format!("All {} selected files", n),
+                HubAction::ReviewSelection,
+                true,
+            ),
+            item(
+                stage.unwrap_or("Stage"),
+                "s",
+                if stage.is_some() {
+                    format!("All {} selected files", n)
+                } else {
+                    "Not available in this mode".into()
+                },
+                HubAction::StageSelection,
+                stage.is_some(),
+            ),
+            item(
+                "Filter to selection",
+                "",
+                "Add the files to the filter as includes".into(),
+                HubAction::FilterSelection { include: true },
+                true,
+            ),
+            item(
+                "Hide selection",
+                "",
+                "Add the files to the filter as excludes".into(),
+                HubAction::FilterSelection { include: false },
+                true,
+            ),
+            item(
+                if all_compacted { "Expand" } else { "Compact" },
+                "",
+                if all_compacted {
+                    "Load the compacted files' hunks".into()
+                } else {
+                    "Collapse the files to their header line".into()
+                },
+                HubAction::CompactSelection,
+                true,
+            ),
+            item(
+                "Assign to reviewer…",
+                "",
+                "Shown as @name in the file tree; an empty name unassigns".into(),
+                HubAction::AssignSelection,
+                true,
+            ),
+            item(
+                "Clear selection",
+                "Esc",
+                String::new(),
+                HubAction::ClearSelection,
+                true,
+            ),
+        ];
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Selection,
+            title: Some(format!("SELECTION · {} files", n)),
+            selected: 0,
+            items,
+        });
+    }
+
+    /// Mark every selected file reviewed, or unmark them all when they
+    /// already are.
+    pub fn review_selection(&mut self) -> Result<()> {
+        let paths = self.tab().selection_paths();
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let unmark = paths.iter().all(|p| self.tab().reviewed.contains_key(p));
+        let gated: Vec<String> = paths
+            .iter()
+            .filter(|p| !unmark && !self.tab().unmet_checklist_gates(p).is_empty())
+            .cloned()
+            .collect();
+        let tab = self.tab_mut();
+        let mut changed = Vec::new();
+        for path in &paths {
+            if unmark {
+                tab.reviewed.remove(path);
+            } else if !tab.reviewed.contains_key(path) && !gated.contains(path) {
+                let hash = tab
+                    .current_per_file_hashes
+                    .get(path)
+                    .cloned()
+                    .unwrap_or_default();
+                tab.reviewed.insert(path.clone(), hash);
+                tab.clear_addressed_for_file(path);
+                tab.unskip_file(path)?;
+            } else {
+                continue;
+            }
+            changed.push(path.clone());
+        }
+        tab.reviewed_revision += 1;
+        tab.save_reviewed_files()?;
+        for path in &changed {
+            self.push_viewed_toggle(path, !unmark);
+            self.publish_event(crate::events::Event::FileReviewed {
+                file: path.clone(),
+                reviewed: !unmark,
+            });
+        }
+        if gated.is_empty() {
+            self.notify(&format!(
+                "{} {} file(s)",
+                if unmark { "Unreviewed" } else { "Reviewed" },
+                changed.len()
+            ));
+        } else {
+            self.notify(&format!(
+                "Reviewed {} file(s) — {} held back until their checklist items are ticked",
+                changed.len(),
+                gated.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stage the selected files (unstage in Staged, resolve in Conflicts).
+    pub fn stage_selection(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("staging") {
+            return Ok(());
+        }
+        let paths = self.tab().selection_paths();
+        let mode = self.tab().mode;
+        let repo_root = self.tab().repo_root.clone();
+        let done = match mode {
+            DiffMode::Branch | DiffMode::Unstaged if !self.tab().is_remote() => "Staged",
+            DiffMode::Staged => "Unstaged",
+            DiffMode::Conflicts => "Resolved",
+            _ => {
+                self.notify("Staging not available in this mode");
+                return Ok(());
+            }
+        };
+        let mut count = 0;
+        for path in &paths {
+            let result = if mode == DiffMode::Staged {
+                git::git_unstage_file(&repo_root, path)
+            } else {
+                git::git_stage_file(&repo_root, path)
+            };
+            if let Err(e) = result {
+                self.report_command_error(&format!("Staging {} failed", path), &e);
+                break;
+            }
+            count += 1;
+        }
+        if count == 0 {
+            return Ok(());
+        }
+        self.notify(&format!("{}: {} file(s)", done, count));
+        if done == "Staged" {
+            self.tab_mut().staged_in_app = true;
+        }
+        if mode == DiffMode::Conflicts {
+            self.tab_mut().refresh_conflicts();
+        } else {
+            self.tab_mut().refresh_diff()?;
+        }
+        Ok(())
+    }
+
+    /// Add the selected paths to the filter, as includes or excludes.
+    pub fn filter_selection(&mut self, include: bool) {
+        let paths = self.tab().selection_paths();
+        if paths.is_empty() {
+            return;
+        }
+        let sign = if include { "+" } else { "-" };
+        let mut segments: Vec<String> = self
+            .tab()
+            .filter_expr
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        for path in &paths {
+            let segment = format!("{}{}", sign, path);
+            if !segments.contains(&segment) {
+                segments.push(segment);
+            }
+        }
+        let expr = segments.join(", ");
+        self.tab_mut().apply_filter_expr(&expr);
+        self.notify(&format!("Filter: {}", expr));
+    }
+
+    /// Compact the selected files, or expand them all when every one
+    /// already is compacted.
+    pub fn compact_selection(&mut self) -> Result<()> {
+        let tab = self.tab_mut();
+        let indices: Vec<usize> = tab
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| tab.multi_selected.contains(&f.path))
+            .map(|(i, _)| i)
+            .collect();
+        let expand = indices.iter().all(|&i| tab.files[i].compacted);
+        let selected = tab.selected_file;
+        let mut count = 0;
+        for &i in &indices {
+            if tab.files[i].compacted == expand {
+                tab.selected_file = i;
+                tab.toggle_compacted()?;
+                count += 1;
+            }
+        }
+        tab.selected_file = selected;
+        tab.ensure_file_parsed();
+        tab.rebuild_hunk_offsets();
+        self.notify(&format!(
+            "{} {} file(s)",
+            if expand { "Expanded" } else { "Compacted" },
+            count
+        ));
+        Ok(())
+    }
+
+    /// Ask who the selected files go to, starting from their shared
+    /// assignee if they have one.
+    pub fn start_assign_selection(&mut self) {
+        let tab = self.tab();
+        let paths = tab.selection_paths();
+        if paths.is_empty() {
+            return;
+        }
+        let first = tab.assigned.get(&paths[0]);
+        self.assignee_input = match first {
+            Some(reviewer) if paths.iter().all(|p| tab.assigned.get(p) == Some(reviewer)) => {
+                reviewer.clone()
+            }
+            _ => String::new(),
+        };
+        self.input_mode = InputMode::Assignee;
+    }
+
+    pub fn submit_assignee(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let reviewer = std::mem::take(&mut self.assignee_input);
+        let paths = self.tab().selection_paths();
+        self.tab_mut().assign_files(&paths, &reviewer)?;
+        let reviewer = reviewer.trim().trim_start_matches('@');
+        if reviewer.is_empty() {
+            self.notify(&format!("Unassigned {} file(s)", paths.len()));
+        } else {
+            self.notify(&format!(
+                "Assigned {} file(s) to @{}",
+                paths.len(),
+                reviewer
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn cancel_assignee(&mut self) {
+        self.assignee_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn extending_selects_both_files_and_actions_cover_the_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(vec![
+            modified_file("a.rs", 1),
+            modified_file("b.rs", 1),
+            modified_file("c.rs", 1),
+        ]);
+        app.tab_mut().er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+
+        app.extend_file_selection(Motion::NextFile);
+        assert_eq!(app.tab().selection_paths(), vec!["a.rs", "b.rs"]);
+        app.toggle_file_selection();
+        assert_eq!(app.tab().selection_paths(), vec!["a.rs"]);
+        app.extend_file_selection(Motion::NextFile);
+        assert_eq!(app.tab().selection_paths(), vec!["a.rs", "b.rs", "c.rs"]);
+
+        app.review_selection().unwrap();
+        assert_eq!(app.tab().reviewed.len(), 3);
+        app.review_selection().unwrap();
+        assert!(app.tab().reviewed.is_empty(), "all reviewed: unmarks");
+
+        app.filter_selection(false);
+        assert_eq!(app.tab().filter_expr, "-a.rs, -b.rs, -c.rs");
+
+        app.start_assign_selection();
+        assert_eq!(app.input_mode, InputMode::Assignee);
+        app.assignee_input = "@ana".into();
+        app.submit_assignee().unwrap();
+        assert_eq!(
+            app.tab().assigned.get("b.rs").map(String::as_str),
+            Some("ana")
+        );
+        app.tab_mut().load_assigned();
+        assert_eq!(app.tab().assigned.len(), 3, "kept in the sidecar");
+
+        assert!(app.clear_file_selection());
+        assert!(!app.clear_file_selection());
+    }
+}
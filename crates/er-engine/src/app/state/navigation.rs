@@ -210,6 +210,13 @@ impl TabState {
         self.selection_anchor = None;
         let total_lines = self.current_hunk_line_count();
         if total_lines == 0 {
+            // A folded hunk: on to the next one
+            if self.hunk_folded_at(self.current_hunk) && self.current_hunk + 1 < self.total_hunks()
+            {
+                self.current_hunk += 1;
+                self.current_line = (!self.hunk_folded_at(self.current_hunk)).then_some(0);
+                self.scroll_to_current_hunk();
+            }
             return;
         }
         match self.current_line {
@@ -237,7 +244,8 @@ impl TabState {
                         let total_hunks = self.total_hunks();
                         if self.current_hunk + 1 < total_hunks {
                             self.current_hunk += 1;
-                            self.current_line = Some(0);
+                            self.current_line =
+                                (!self.hunk_folded_at(self.current_hunk)).then_some(0);
                             self.scroll_to_current_hunk();
                         }
                     }
@@ -245,7 +253,7 @@ impl TabState {
                     let total_hunks = self.total_hunks();
                     if self.current_hunk + 1 < total_hunks {
                         self.current_hunk += 1;
-                        self.current_line = Some(0);
+                        self.current_line = (!self.hunk_folded_at(self.current_hunk)).then_some(0);
                         self.scroll_to_current_hunk();
                     }
                 }
@@ -257,6 +265,13 @@ impl TabState {
     pub fn prev_line(&mut self) {
         self.selection_anchor = None;
         match self.current_line {
+            // On a folded hunk: back to the last line of the one before
+            None if self.hunk_folded_at(self.current_hunk) => {
+                if self.current_hunk > 0 {
+                    self.current_line = Some(0);
+                    self.prev_line();
+                }
+            }
             None => {
                 // Enter line mode at the last non-Fold line of the current hunk
                 let count = self.current_hunk_line_count();
@@ -312,12 +327,15 @@ impl TabState {
         }
     }
 
-    /// Get the number of lines in the current hunk
+    /// Get the number of lines in the current hunk (none when it's folded)
     pub fn current_hunk_line_count(&self) -> usize {
-        self.selected_diff_file()
-            .and_then(|f| f.hunks.get(self.current_hunk))
-            .map(|h| h.lines.len())
-            .unwrap_or(0)
+        let Some(file) = self.selected_diff_file() else {
+            return 0;
+        };
+        match file.hunks.get(self.current_hunk) {
+            Some(h) if !self.is_hunk_folded(&file.path, h) => h.lines.len(),
+            _ => 0,
+        }
     }
 
     /// Get the line number for the currently selected diff line.
@@ -366,6 +384,60 @@ impl TabState {
         Some(anchor.min(current)..=anchor.max(current))
     }
 
+    /// Select `path` and put the cursor on new-side `line` (or the closest hunk
+    /// when the line is outside the diff). Returns false when the file is not
+    /// in the current diff.
+    pub fn focus_file_line(&mut self, path: &str, line: Option<usize>) -> bool {
+        let Some(idx) = self.files.iter().position(|f| f.path == path) else {
+            return false;
+        };
+        self.record_jump();
+        self.focused_comment_id = None;
+        self.focused_finding_id = None;
+        self.selected_watched = None;
+        self.selected_file = idx;
+        self.current_hunk = 0;
+        self.current_line = None;
+        self.selection_anchor = None;
+        self.diff_scroll = 0;
+        self.h_scroll = 0;
+        self.ensure_file_parsed();
+        self.rebuild_hunk_offsets();
+
+        if let (Some(target), Some(file)) = (line, self.files.get(idx)) {
+            let exact = file.hunks.iter().enumerate().find_map(|(hi, h)| {
+                h.lines
+                    .iter()
+                    .position(|l| l.new_num == Some(target))
+                    .map(|li| (hi, Some(li)))
+            });
+            let nearest = || {
+                file.hunks
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, h)| {
+                        let end = h.new_start + h.new_count.saturating_sub(1);
+                        if target < h.new_start {
+                            h.new_start - target
+                        } else {
+                            target.saturating_sub(end)
+                        }
+                    })
+                    .map(|(hi, _)| (hi, None))
+            };
+            if let Some((hi, li)) = exact.or_else(nearest) {
+                self.current_hunk = hi;
+                self.current_line = li;
+                // Landing on a line inside a fold opens it
+                if li.is_some() && self.hunk_folded_at(hi) {
+                    self.set_hunk_folded(hi, false);
+                }
+            }
+        }
+        self.scroll_to_current_hunk();
+        true
+    }
+
     pub fn scroll_to_current_hunk(&mut self) {
         // Use precomputed hunk offsets if available (O(1) lookup)
         if let Some(ref offsets) = self.hunk_offsets {
@@ -465,9 +537,10 @@ impl TabState {
 
     /// Rebuild hunk offsets for the currently selected file
     pub fn rebuild_hunk_offsets(&mut self) {
+        let folds = self.selected_file_folds();
         self.hunk_offsets = self
             .selected_diff_file()
-            .map(|f| HunkOffsets::build(&f.hunks));
+            .map(|f| HunkOffsets::build(&f.hunks, &folds));
     }
 
     /// Update memory budget counters
@@ -523,6 +596,7 @@ impl TabState {
                     file.adds = parsed.adds;
                     file.dels = parsed.dels;
                 }
+                self.prune_to_author_scope(index);
                 self.rebuild_hunk_offsets();
                 self.update_mem_budget();
                 return;
@@ -606,6 +680,7 @@ impl TabState {
                     head_ref_owned.as_deref(),
                 )?;
             }
+            self.learn_from_expand(&path);
             self.user_expanded.insert(path);
             self.rebuild_hunk_offsets();
             self.update_mem_budget();
@@ -827,6 +902,50 @@ impl TabState {
         Ok(())
     }
 
+    /// Open the current file at the current line in an editor pane beside the
+    /// TUI (tmux / WezTerm / kitty). Returns the multiplexer used, or `None`
+    /// when er is not running inside a supported one.
+    pub fn open_in_split(
+        &self,
+        config: &config::SplitConfig,
+    ) -> Result<Option<crate::multiplexer::Multiplexer>> {
+        let Some(mux) = crate::multiplexer::Multiplexer::detect() else {
+            return Ok(None);
+        };
+        let file = match self.selected_diff_file() {
+            Some(f) => f,
+            None => return Ok(Some(mux)),
+        };
+
+        let editor = std::env::var("EDITOR")
+            .ok()
+            .filter(|e| !e.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string());
+        let file_path = std::path::Path::new(&self.repo_root).join(&file.path);
+        let line_num = self
+            .current_line_number()
+            .or_else(|| file.hunks.get(self.current_hunk).map(|h| h.new_start))
+            .unwrap_or(1);
+
+        let Some(args) = crate::multiplexer::split_command_args(
+            mux.template(config),
+            &editor,
+            &file_path.to_string_lossy(),
+            line_num,
+            &self.repo_root,
+        ) else {
+            anyhow::bail!("No split command configured for {}", mux.label());
+        };
+        std::process::Command::new(&args[0])
+            .args(&args[1..])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to open {} split", mux.label()))?;
+        Ok(Some(mux))
+    }
+
     // ── History Mode Navigation ──
 
     /// Move to the next commit in history (older)
@@ -835,8 +954,10 @@ impl TabState {
             Some(h) => h,
             None => return,
         };
-        if history.selected_commit + 1 < history.commits.len() {
-            history.selected_commit += 1;
+        let next = (history.selected_commit + 1..history.commits.len())
+            .find(|&i| !history.is_folded(&history.commits[i]));
+        if let Some(next) = next {
+            history.selected_commit = next;
             self.history_load_selected_diff();
         }
     }
@@ -847,12 +968,44 @@ impl TabState {
             Some(h) => h,
             None => return,
         };
-        if history.selected_commit > 0 {
-            history.selected_commit -= 1;
+        let prev = (0..history.selected_commit)
+            .rev()
+            .find(|&i| !history.is_folded(&history.commits[i]));
+        if let Some(prev) = prev {
+            history.selected_commit = prev;
             self.history_load_selected_diff();
         }
     }
 
+    /// Fold (or unfold) the commits a later commit undoes. Returns how many
+    /// are now hidden; a folded selection moves to the nearest shown commit.
+    pub fn toggle_net_zero_fold(&mut self) -> usize {
+        let Some(history) = self.history.as_mut() else {
+            return 0;
+        };
+        history.fold_net_zero = !history.fold_net_zero;
+        let folded = history
+            .commits
+            .iter()
+            .filter(|c| history.is_folded(c))
+            .count();
+        let selected = history.selected_commit;
+        if history
+            .commits
+            .get(selected)
+            .is_some_and(|c| history.is_folded(c))
+        {
+            let nearest = (selected..history.commits.len())
+                .chain((0..selected).rev())
+                .find(|&i| !history.is_folded(&history.commits[i]));
+            if let Some(nearest) = nearest {
+                history.selected_commit = nearest;
+                self.history_load_selected_diff();
+            }
+        }
+        folded
+    }
+
     /// Load the diff for the currently selected commit
     pub fn history_load_selected_diff(&mut self) {
         let (hash, repo_root) = {
@@ -1105,7 +1258,8 @@ impl TabState {
             Some(h) => (h.commits.len(), h.all_loaded),
             None => return,
         };
-        if all_loaded {
+        // The search loader is already streaming the rest in
+        if all_loaded || self.history.as_ref().is_some_and(|h| h.search.streaming()) {
             return;
         }
         if self.pr_number.is_some() && self.local_branch_view.is_some() {
@@ -1129,31 +1283,32 @@ impl TabState {
         if new_commits.is_empty() {
             history.all_loaded = true;
         } else {
+            let hashes: Vec<&str> = new_commits.iter().map(|c| c.hash.as_str()).collect();
+            history
+                .messages
+                .extend(git::git_commit_messages(&log_root, &hashes).unwrap_or_default());
             history.commits.extend(new_commits);
         }
     }
 
-    /// Get visible commits (filtered by search query)
+    /// Get visible commits (filtered by search query and the net-zero fold)
     pub fn visible_commits(&self) -> Vec<(usize, &CommitInfo)> {
         let history = match self.history.as_ref() {
             Some(h) => h,
             None => return vec![],
         };
 
-        if self.search_query.is_empty() {
-            history.commits.iter().enumerate().collect()
-        } else {
-            let q = self.search_query.to_lowercase();
-            history
-                .commits
-                .iter()
-                .enumerate()
-                .filter(|(_, c)| {
-                    c.subject.to_lowercase().contains(&q)
-                        || c.short_hash.contains(&q)
-                        || c.author.to_lowercase().contains(&q)
-                })
-                .collect()
+        let shown = history
+            .commits
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !history.is_folded(c));
+        match self.commit_query().filter(|q| !q.is_empty()) {
+            None => shown.collect(),
+            Some(query) => {
+                let paths = history.search.commit_paths.as_ref();
+                shown.filter(|(_, c)| query.matches(c, paths)).collect()
+            }
         }
     }
 
@@ -1458,6 +1613,7 @@ impl TabState {
                 .get(&path)
                 .cloned()
                 .unwrap_or_default();
+            self.record_review_pace(&path);
             self.reviewed.insert(path, hash);
         }
         let _ = self.save_reviewed_files();
@@ -1517,6 +1673,60 @@ fn pick_context_for_size(total_lines: usize, hunks: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::pick_context_for_size;
+    use crate::app::state::{App, DiffCache, HistoryState};
+    use crate::git::CommitInfo;
+
+    fn commit(hash: &str) -> CommitInfo {
+        CommitInfo {
+            hash: hash.into(),
+            short_hash: hash.into(),
+            subject: format!("commit {}", hash),
+            author: "dev".into(),
+            date: String::new(),
+            relative_date: String::new(),
+            file_count: 1,
+            adds: 1,
+            dels: 0,
+            is_merge: false,
+        }
+    }
+
+    #[test]
+    fn folding_net_zero_commits_hides_and_skips_them() {
+        let mut app = App::new_for_test(vec![]);
+        let mut cache = DiffCache::new(5);
+        cache.insert("a".into(), Vec::new());
+        cache.insert("c".into(), Vec::new());
+        app.tab_mut().history = Some(HistoryState {
+            commits: vec![commit("a"), commit("b"), commit("c")],
+            selected_commit: 1,
+            commit_files: Vec::new(),
+            selected_file: 0,
+            current_hunk: 0,
+            current_line: None,
+            diff_scroll: 0,
+            h_scroll: 0,
+            all_loaded: true,
+            diff_cache: cache,
+            messages: Default::default(),
+            net_zero: ["b".to_string()].into(),
+            fold_net_zero: false,
+            search: Default::default(),
+            prs: Default::default(),
+        });
+        let tab = app.tab_mut();
+        assert_eq!(tab.visible_commits().len(), 3);
+
+        assert_eq!(tab.toggle_net_zero_fold(), 1);
+        let shown: Vec<usize> = tab.visible_commits().iter().map(|(i, _)| *i).collect();
+        assert_eq!(shown, [0, 2]);
+        assert_eq!(tab.history.as_ref().unwrap().selected_commit, 2);
+
+        tab.history_prev_commit();
+        assert_eq!(tab.history.as_ref().unwrap().selected_commit, 0);
+        assert_eq!(tab.toggle_net_zero_fold(), 0);
+        assert_eq!(tab.visible_commits().len(), 3);
+    }
 
     #[test]
     fn tiny_files_get_full_context() {
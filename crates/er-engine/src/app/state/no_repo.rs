@@ -0,0 +1,271 @@
+//! Starting outside a git repository. Instead of erroring out, `er` run
+//! anywhere else opens on an empty start tab with the folder browser up:
+//! Enter on a repository (`[git]`) opens it, Enter on a `.patch` / `.diff`
+//! file reads it, and `o` asks for a GitHub PR URL or a patch path. The
+//! start tab gives way to the first tab opened from it; until then `o`
+//! (the Open hub) gets back to the same choices.
+//!
+//! A patch tab shows a `git diff` or `git format-patch` file as a read-only
+//! Branch view. There's no repository behind it: refreshing reads the file
+//! again, and reviewed marks and comments are kept in managed storage under
+//! the patch's file name.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::{App, DiffMode, TabState};
+use crate::git;
+use crate::paths::ErRoot;
+use crate::storage;
+
+/// What a tab without a repository shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoRepo {
+    /// The placeholder a launch outside a repository starts on
+    Start,
+    /// A patch file, by absolute path
+    Patch(String),
+}
+
+/// File names the folder browser opens as patches.
+pub fn is_patch_file(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".patch") || name.ends_with(".diff")
+}
+
+/// `git format-patch` signs off with a `-- ` line and git's version, which
+/// would read as a deleted line and a blank one.
+fn strip_signature(raw: &str) -> &str {
+    match raw.rsplit_once("\n-- \n") {
+        Some((diff, sig))
+            if sig
+                .lines()
+                .all(|l| !l.starts_with(['+', '-', ' ', '@', '\\'])) =>
+        {
+            diff
+        }
+        _ => raw,
+    }
+}
+
+fn read_patch(path: &str) -> Result<(String, Vec<git::DiffFile>)> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("Reading {}", path))?;
+    let mut files = git::parse_diff(strip_signature(&raw));
+    if files.is_empty() {
+        anyhow::bail!("No git diff in {}", path);
+    }
+    git::compact_files(&mut files, &git::CompactionConfig::default());
+    Ok((raw, files))
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+impl TabState {
+    /// A tab that runs no git, browsing from `dir`.
+    fn repoless(dir: String, files: Vec<git::DiffFile>, no_repo: NoRepo) -> Self {
+        // The test constructor's defaults touch neither git nor storage
+        let mut tab = Self::new_for_test(files);
+        tab.base_branch.clear();
+        tab.current_branch.clear();
+        tab.er_root = ErRoot::RepoLocal(String::new());
+        tab.repo_root = dir;
+        tab.no_repo = Some(no_repo);
+        tab
+    }
+
+    /// The start tab for a launch from `dir`, outside any repository.
+    pub fn new_start(dir: String) -> Self {
+        Self::repoless(dir, Vec::new(), NoRepo::Start)
+    }
+
+    /// A read-only tab for the patch file at `path`.
+    pub fn new_patch(path: &str) -> Result<Self> {
+        let path = std::fs::canonicalize(path)
+            .with_context(|| format!("Path not found: {}", path))?
+            .to_string_lossy()
+            .to_string();
+        let (raw, files) = read_patch(&path)?;
+        let dir = Path::new(&path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| "/".to_string());
+        let mut tab = Self::repoless(dir, files, NoRepo::Patch(path.clone()));
+        tab.diff_hash = crate::ai::compute_diff_hash(&raw);
+        tab.branch_diff_hash = tab.diff_hash.clone();
+        tab.er_root =
+            storage::resolve_managed_root_from_slugs(&storage::slugify(&file_name(&path)), "patch");
+        tab.sync_managed_storage();
+        Ok(tab)
+    }
+
+    /// Whether this tab has no repository behind it (start or patch tab).
+    pub fn is_repoless(&self) -> bool {
+        self.no_repo.is_some()
+    }
+
+    /// Tab bar label for a tab without a repository.
+    pub(super) fn no_repo_name(&self) -> Option<String> {
+        match self.no_repo.as_ref()? {
+            NoRepo::Start => Some("no repository".to_string()),
+            NoRepo::Patch(path) => Some(file_name(path)),
+        }
+    }
+
+    /// Refresh for a tab without a repository: a patch is read again.
+    pub(super) fn refresh_no_repo(&mut self) -> Result<()> {
+        let Some(NoRepo::Patch(path)) = self.no_repo.clone() else {
+            return Ok(());
+        };
+        let (raw, files) = read_patch(&path)?;
+        self.files = files;
+        self.mode = DiffMode::Branch;
+        self.diff_hash = crate::ai::compute_diff_hash(&raw);
+        self.branch_diff_hash = self.diff_hash.clone();
+        self.selected_file = self.selected_file.min(self.files.len().saturating_sub(1));
+        self.current_hunk = 0;
+        self.current_line = None;
+        Ok(())
+    }
+}
+
+impl App {
+    /// Launched outside a repository: the start tab, with the folder
+    /// browser open on `dir`.
+    pub fn new_without_repo(dir: String) -> Self {
+        let mut app = Self::new_remote(TabState::new_start(dir), None);
+        app.open_directory_browser();
+        app
+    }
+
+    /// Open the patch file at `path` in a new tab (or switch to it).
+    pub fn open_patch(&mut self, path: &str) -> Result<()> {
+        let tab = TabState::new_patch(path)?;
+        if let Some(i) = self.tabs.iter().position(|t| t.no_repo == tab.no_repo) {
+            self.active_tab = i;
+            self.overlay = None;
+            self.notify(&format!("Switched to tab: {}", tab.tab_name()));
+            return Ok(());
+        }
+        let name = tab.tab_name();
+        self.tabs.push(tab);
+        self.active_tab = self.tabs.len() - 1;
+        self.overlay = None;
+        self.close_start_tab();
+        self.notify(&format!("Opened patch: {}", name));
+        Ok(())
+    }
+
+    /// Open what was typed at the Open prompt: a GitHub PR URL, a patch
+    /// file, or a directory inside a repository.
+    pub fn open_location(&mut self, input: &str) -> Result<()> {
+        let input = input.trim();
+        if crate::github::is_github_pr_url(input) {
+            return self.open_remote_url(input);
+        }
+        let path = match input.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest))
+                .unwrap_or_else(|| rest.into()),
+            None => input.into(),
+        };
+        if path.is_file() {
+            return self.open_patch(&path.to_string_lossy());
+        }
+        if path.is_dir() {
+            let repo_root = git::get_repo_root_in(&path.to_string_lossy())
+                .with_context(|| format!("Not a git repository: {}", input))?;
+            return self.open_in_new_tab(repo_root);
+        }
+        anyhow::bail!("Not a PR URL, patch file or directory: {}", input)
+    }
+
+    /// Drop the start tab once something else is open.
+    pub(super) fn close_start_tab(&mut self) {
+        let Some(i) = self
+            .tabs
+            .iter()
+            .position(|t| t.no_repo == Some(NoRepo::Start))
+        else {
+            return;
+        };
+        if self.tabs.len() < 2 {
+            return;
+        }
+        self.tabs.remove(i);
+        if self.active_tab > i {
+            self.active_tab -= 1;
+        }
+        self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{InputMode, OverlayData};
+
+    #[test]
+    fn start_tab_browses_and_gives_way_to_an_opened_patch() {
+        let _guard = storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let storage_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("ER_STORAGE_ROOT", storage_dir.path());
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::fs::write(
+            dir.path().join("fix.patch"),
+            "From abc Mon Sep 17 00:00:00 2001\nSubject: [PATCH] Fix\n\n\
+             diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-old\n+new\n-- \n2.43.0\n\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a diff\n").unwrap();
+
+        let mut app = App::new_without_repo(root.clone());
+        assert!(app.tab().is_repoless());
+        assert_eq!(app.tab().tab_name(), "no repository");
+        let Some(OverlayData::DirectoryBrowser {
+            current_path,
+            entries,
+            ..
+        }) = &app.overlay
+        else {
+            panic!("expected the folder browser");
+        };
+        assert_eq!(current_path, &root);
+        assert!(entries.iter().any(|e| e.name == "fix.patch"));
+        assert!(app.tab_mut().refresh_diff().is_ok());
+
+        assert!(app.open_location(&format!("{}/notes.txt", root)).is_err());
+        assert!(app.open_location(&root).is_err());
+        assert_eq!(app.tabs.len(), 1);
+
+        app.open_location(&format!("{}/fix.patch", root)).unwrap();
+        assert_eq!(app.tabs.len(), 1);
+        let tab = app.tab();
+        assert_eq!(tab.tab_name(), "fix.patch");
+        assert_eq!(tab.files[0].path, "a.rs");
+        assert_eq!(tab.files[0].hunks[0].lines.len(), 2);
+        assert_eq!(tab.visible_modes(&app.config), [DiffMode::Branch]);
+        assert!(app.overlay.is_none() && app.input_mode == InputMode::Normal);
+
+        // Opening it again switches to it, and a refresh reads the file again
+        std::fs::write(
+            dir.path().join("fix.patch"),
+            "diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n",
+        )
+        .unwrap();
+        app.open_patch(&format!("{}/fix.patch", root)).unwrap();
+        assert_eq!(app.tabs.len(), 1);
+        app.tab_mut().refresh_diff().unwrap();
+        assert_eq!(app.tab().files[0].path, "b.rs");
+
+        std::env::remove_var("ER_STORAGE_ROOT");
+    }
+}
@@ -0,0 +1,234 @@
+//! Noise filter suggestion: when most of a diff's changed lines come from a
+//! few kinds of low-value files — lockfiles, snapshots, generated protobufs —
+//! offer once to hide them with a filter (`[features] noise_filter_hint`).
+//!
+//! Kinds are the compaction patterns plus a few path heuristics, and files
+//! whose first added lines carry a "generated, do not edit" banner. Each is
+//! turned into an exclude segment of the filter syntax (`-*.snap`), so what
+//! the prompt counts is exactly what the filter hides.
+
+use std::collections::BTreeMap;
+
+use super::{App, ConfirmAction, InputMode, TabState};
+use crate::app::filter;
+use crate::git::{DiffFile, LineType};
+
+/// Path patterns for generated output besides the compaction ones.
+const NOISE_PATTERNS: &[&str] = &[
+    "*__snapshots__/*",
+    "*.snap.json",
+    "*.pb.*",
+    "*_pb2.py",
+    "*_pb2_grpc.py",
+    "*.min.*",
+    "*.map",
+    "*__generated__/*",
+    "*.designer.cs",
+    "*.g.cs",
+];
+
+/// Banners code generators put at the top of their output.
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "code generated",
+    "do not edit",
+    "auto-generated",
+    "autogenerated",
+];
+
+/// Added lines checked for a banner.
+const BANNER_LINES: usize = 5;
+
+/// At most this many patterns may make up the noise.
+const MAX_NOISE_PATTERNS: usize = 5;
+
+/// Noise must be more than this share of changed lines.
+const NOISE_SHARE: f64 = 0.5;
+
+/// Diffs smaller than this aren't worth a prompt.
+const MIN_CHANGED_LINES: usize = 200;
+
+/// Low-value files worth hiding, as a filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoiseSuggestion {
+    /// Filter patterns, biggest first
+    pub patterns: Vec<String>,
+    pub files: usize,
+    pub lines: usize,
+    pub total_lines: usize,
+}
+
+impl NoiseSuggestion {
+    /// `-p1, -p2` — the filter expression that hides the noise.
+    pub fn filter_expr(&self) -> String {
+        self.patterns
+            .iter()
+            .map(|p| format!("-{}", p))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn percent(&self) -> usize {
+        self.lines * 100 / self.total_lines.max(1)
+    }
+}
+
+fn changed_lines(file: &DiffFile) -> usize {
+    file.adds + file.dels
+}
+
+fn has_generated_banner(file: &DiffFile) -> bool {
+    file.hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| l.line_type == LineType::Add)
+        .take(BANNER_LINES)
+        .any(|l| {
+            let lower = l.content.to_ascii_lowercase();
+            GENERATED_MARKERS.iter().any(|m| lower.contains(m))
+        })
+}
+
+/// The suggestion for `files`, `None` when noise doesn't dominate the diff,
+/// needs too many patterns, or would hide every file.
+pub fn detect_noise(files: &[DiffFile], compaction_patterns: &[String]) -> Option<NoiseSuggestion> {
+    let total_lines: usize = files.iter().map(changed_lines).sum();
+    if total_lines < MIN_CHANGED_LINES {
+        return None;
+    }
+    // Each pattern as the filter segment `-pattern` would match it
+    let patterns: Vec<(&str, Vec<filter::FilterRule>)> = compaction_patterns
+        .iter()
+        .map(String::as_str)
+        .chain(NOISE_PATTERNS.iter().copied())
+        .map(|p| (p, filter::parse_filter_expr(&format!("-{}", p))))
+        .collect();
+
+    // Lines per pattern; each file counts once, for the first that hides it
+    let mut by_pattern: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut bannered: Vec<&DiffFile> = Vec::new();
+    for file in files {
+        match patterns
+            .iter()
+            .find(|(_, rules)| !filter::apply_filter(rules, file))
+        {
+            Some((pattern, _)) => {
+                let entry = by_pattern.entry(pattern.to_string()).or_default();
+                entry.0 += 1;
+                entry.1 += changed_lines(file);
+            }
+            None if has_generated_banner(file) => bannered.push(file),
+            None => {}
+        }
+    }
+    // Bannered files go in by directory when nothing else there changed
+    for file in bannered {
+        let pattern = match file.path.rsplit_once('/') {
+            Some((dir, _))
+                if files
+                    .iter()
+                    .filter(|f| f.path.starts_with(&format!("{}/", dir)))
+                    .all(has_generated_banner) =>
+            {
+                format!("{}/*", dir)
+            }
+            _ => file.path.clone(),
+        };
+        let entry = by_pattern.entry(pattern).or_default();
+        entry.0 += 1;
+        entry.1 += changed_lines(file);
+    }
+
+    let mut ranked: Vec<(String, usize, usize)> = by_pattern
+        .into_iter()
+        .map(|(pattern, (files, lines))| (pattern, files, lines))
+        .collect();
+    ranked.sort_by_key(|(_, _, lines)| std::cmp::Reverse(*lines));
+    let mut suggestion = NoiseSuggestion {
+        patterns: Vec::new(),
+        files: 0,
+        lines: 0,
+        total_lines,
+    };
+    for (pattern, count, lines) in ranked.into_iter().take(MAX_NOISE_PATTERNS) {
+        suggestion.patterns.push(pattern);
+        suggestion.files += count;
+        suggestion.lines += lines;
+        if suggestion.lines as f64 > total_lines as f64 * NOISE_SHARE {
+            return (suggestion.files < files.len()).then_some(suggestion);
+        }
+    }
+    None
+}
+
+impl TabState {
+    pub(super) fn refresh_noise_suggestion(&mut self) {
+        self.noise_suggestion = detect_noise(&self.files, &self.compaction_config.patterns);
+    }
+}
+
+impl App {
+    /// Offer the noise filter once per suggestion, when nothing else is open
+    /// and no filter is set. Returns whether a prompt was shown.
+    pub fn poll_noise_suggestion(&mut self) -> bool {
+        if !self.config.features.noise_filter_hint
+            || self.input_mode != InputMode::Normal
+            || self.overlay.is_some()
+        {
+            return false;
+        }
+        let tab = self.tab();
+        let Some(suggestion) = tab.noise_suggestion.clone() else {
+            return false;
+        };
+        let expr = suggestion.filter_expr();
+        if !tab.filter_expr.is_empty() || tab.noise_offered.as_deref() == Some(expr.as_str()) {
+            return false;
+        }
+        self.tab_mut().noise_offered = Some(expr.clone());
+        let prompt = ConfirmAction::HideNoise {
+            files: suggestion.files,
+            expr: expr.clone(),
+        }
+        .prompt()
+        .detail(format!(
+            "{}% of the changed lines ({} of {})",
+            suggestion.percent(),
+            suggestion.lines,
+            suggestion.total_lines
+        ))
+        .detail(format!("Filter: {}", expr));
+        self.ask_confirm(prompt);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::added_file;
+
+    #[test]
+    fn suggests_hiding_dominant_generated_files() {
+        let compaction = crate::git::CompactionConfig::default().patterns;
+        let mut files = vec![
+            added_file("src/lib.rs", 120),
+            added_file("Cargo.lock", 300),
+            added_file("ui/__snapshots__/a.snap", 40),
+            added_file("ui/__snapshots__/b.snap", 40),
+            added_file("api/user.pb.go", 10),
+        ];
+        let suggestion = detect_noise(&files, &compaction).unwrap();
+        assert_eq!(suggestion.patterns, ["*.lock"]);
+        assert_eq!((suggestion.files, suggestion.lines), (1, 300));
+        assert_eq!(suggestion.filter_expr(), "-*.lock");
+
+        // Mostly real code: no nagging
+        files[0].adds = 600;
+        assert_eq!(detect_noise(&files, &compaction), None);
+
+        // Nothing would be left to review
+        let only_noise = vec![added_file("Cargo.lock", 500)];
+        assert_eq!(detect_noise(&only_noise, &compaction), None);
+    }
+}
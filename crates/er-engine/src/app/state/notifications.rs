@@ -0,0 +1,313 @@
+//! Notification history and severity.
+//!
+//! Every `notify*` call lands in a capped log alongside the transient toast,
+//! so messages that vanished after a couple of seconds (or were truncated to
+//! the terminal width) can be read in full from the notifications pane. The
+//! level drives the toast color and how long it stays up.
+
+use super::{App, OverlayData};
+use crate::command::CommandFailure;
+use std::collections::VecDeque;
+
+/// How many notifications are kept in the log.
+pub const NOTIFICATION_LOG_CAP: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Best-effort level for plain `notify()` calls, from the wording most
+    /// call sites already use ("… failed: …", "⚠ …", "✓ …"). Failure words
+    /// only count as whole words not negated by a zero count, so "No errors"
+    /// and "0 failed" stay informational.
+    pub fn infer(msg: &str) -> Self {
+        let trimmed = msg.trim_start();
+        let lower = msg.to_lowercase();
+        if reports_failure(&lower) {
+            NotificationLevel::Error
+        } else if trimmed.starts_with('⚠') || lower.starts_with("warning") {
+            NotificationLevel::Warning
+        } else if trimmed.starts_with('✓') {
+            NotificationLevel::Success
+        } else {
+            NotificationLevel::Info
+        }
+    }
+
+    /// Ticks (≈100ms) the toast stays visible.
+    pub fn toast_ticks(self) -> u16 {
+        match self {
+            NotificationLevel::Info | NotificationLevel::Success => 20,
+            NotificationLevel::Warning => 40,
+            NotificationLevel::Error => 60,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "info",
+            NotificationLevel::Success => "ok",
+            NotificationLevel::Warning => "warn",
+            NotificationLevel::Error => "error",
+        }
+    }
+}
+
+/// Whether `lower` contains "failed" / "error…" as a word, not preceded by
+/// "no", "zero" or "0".
+fn reports_failure(lower: &str) -> bool {
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.iter().enumerate().any(|(i, word)| {
+        let failure = *word == "failed" || word.starts_with("error");
+        let negated = i > 0 && matches!(words[i - 1], "no" | "zero" | "0");
+        failure && !negated
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationEntry {
+    /// ISO 8601 UTC, from `chrono_now()`.
+    pub timestamp: String,
+    pub level: NotificationLevel,
+    /// Full text, never truncated.
+    pub message: String,
+}
+
+impl NotificationEntry {
+    /// `HH:MM:SS` part of the timestamp, for compact display.
+    pub fn time(&self) -> &str {
+        self.timestamp
+            .split_once('T')
+            .map(|(_, t)| t.trim_end_matches('Z'))
+            .unwrap_or(&self.timestamp)
+    }
+}
+
+/// Capped, oldest-first notification history.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLog {
+    entries: VecDeque<NotificationEntry>,
+}
+
+impl NotificationLog {
+    pub fn push(&mut self, level: NotificationLevel, message: &str) {
+        if self.entries.len() >= NOTIFICATION_LOG_CAP {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(NotificationEntry {
+            timestamp: crate::sync::chrono_now(),
+            level,
+            message: message.to_string(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Newest first — the order the pane shows them in.
+    pub fn newest_first(&self) -> Vec<NotificationEntry> {
+        self.entries.iter().rev().cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl App {
+    /// Show a toast and record it in the notification log.
+    pub fn notify_level(&mut self, level: NotificationLevel, msg: &str) {
+        let log_level = match level {
+            NotificationLevel::Error => crate::debug_log::Level::Error,
+            NotificationLevel::Warning => crate::debug_log::Level::Warn,
+            NotificationLevel::Info | NotificationLevel::Success => crate::debug_log::Level::Info,
+        };
+        crate::debug_log::log(log_level, "notify", msg);
+        self.notification_log.push(level, msg);
+        self.watch_message = Some(msg.to_string());
+        self.watch_message_level = level;
+        self.watch_message_ticks = 0;
+        self.watch_message_max_ticks = level.toast_ticks();
+    }
+
+    pub fn notify_error(&mut self, msg: &str) {
+        self.notify_level(NotificationLevel::Error, msg);
+    }
+
+    pub fn notify_warn(&mut self, msg: &str) {
+        self.notify_level(NotificationLevel::Warning, msg);
+    }
+
+    /// Warn once when a sidecar write had to wait for another er instance
+    /// (see [`crate::file_lock`]). Returns whether a warning was shown.
+    pub fn poll_lock_contention(&mut self) -> bool {
+        let Some(name) = crate::file_lock::take_contention() else {
+            return false;
+        };
+        self.notify_warn(&format!(
+            "Another er instance was writing {} — waited for it and kept both changes",
+            name
+        ));
+        true
+    }
+
+    /// Open the notifications pane (newest first).
+    pub fn open_notification_log(&mut self) {
+        if self.notification_log.is_empty() {
+            self.notify("No notifications yet");
+            return;
+        }
+        self.overlay = Some(OverlayData::NotificationLog {
+            entries: self.notification_log.newest_first(),
+            selected: 0,
+        });
+    }
+
+    /// Copy the selected notification's full text to the clipboard.
+    pub fn copy_selected_notification(&mut self) {
+        let text = match &self.overlay {
+            Some(OverlayData::NotificationLog { entries, selected }) => entries
+                .get(*selected)
+                .map(|e| format!("[{}] {}: {}", e.timestamp, e.level.label(), e.message)),
+            _ => None,
+        };
+        if let Some(text) = text {
+            match self.copy_to_clipboard(&text) {
+                Ok(via) => self.notify_copied("✓ Notification copied to clipboard", via),
+                Err(e) => self.notify(&format!("Clipboard failed: {}", e)),
+            }
+        }
+    }
+
+    /// Empty the log and close the pane.
+    pub fn clear_notification_log(&mut self) {
+        self.notification_log.clear();
+        self.overlay = None;
+    }
+
+    /// Surface a failed operation. When a `git`/`gh` invocation is behind the
+    /// error, open the error detail overlay (command, exit code, stderr);
+    /// otherwise fall back to an error toast. Either way the full text goes
+    /// to the notification log.
+    pub fn report_command_error(&mut self, title: &str, err: &anyhow::Error) {
+        let Some(failure) = CommandFailure::find(err) else {
+            self.notify_error(&format!("{}: {}", title, err));
+            return;
+        };
+        crate::debug_log::error("notify", format!("{}: {}", title, failure.report()));
+        self.notification_log.push(
+            NotificationLevel::Error,
+            &format!("{}: {}\n{}", title, failure, failure.report()),
+        );
+        self.overlay = Some(OverlayData::CommandError {
+            title: title.to_string(),
+            failure: Box::new(failure.clone()),
+            scroll: 0,
+        });
+    }
+
+    /// Copy the open error overlay's full report to the clipboard.
+    pub fn copy_command_error(&mut self) {
+        let text = match &self.overlay {
+            Some(OverlayData::CommandError { title, failure, .. }) => {
+                format!("{}\n\n{}", title, failure.report())
+            }
+            _ => return,
+        };
+        match self.copy_to_clipboard(&text) {
+            Ok(via) => self.notify_copied("✓ Error details copied to clipboard", via),
+            Err(e) => self.notify(&format!("Clipboard failed: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_level_from_wording() {
+        assert_eq!(
+            NotificationLevel::infer("Push failed: rejected"),
+            NotificationLevel::Error
+        );
+        assert_eq!(
+            NotificationLevel::infer("⚠ AI review is stale"),
+            NotificationLevel::Warning
+        );
+        assert_eq!(
+            NotificationLevel::infer("✓ Copied"),
+            NotificationLevel::Success
+        );
+        assert_eq!(
+            NotificationLevel::infer("Filter: +src/"),
+            NotificationLevel::Info
+        );
+    }
+
+    #[test]
+    fn infer_ignores_zero_counts_and_partial_words() {
+        for msg in [
+            "No errors found",
+            "Tests: 12 passed, 0 failed",
+            "Terrors.md",
+        ] {
+            assert_eq!(
+                NotificationLevel::infer(msg),
+                NotificationLevel::Info,
+                "{msg}"
+            );
+        }
+        assert_eq!(
+            NotificationLevel::infer("2 failed, 10 passed"),
+            NotificationLevel::Error
+        );
+        assert_eq!(
+            NotificationLevel::infer("Error: gh not found"),
+            NotificationLevel::Error
+        );
+    }
+
+    #[test]
+    fn errors_stay_up_longer() {
+        assert!(NotificationLevel::Error.toast_ticks() > NotificationLevel::Info.toast_ticks());
+    }
+
+    #[test]
+    fn log_is_capped_and_newest_first() {
+        let mut log = NotificationLog::default();
+        for i in 0..NOTIFICATION_LOG_CAP + 5 {
+            log.push(NotificationLevel::Info, &format!("msg {}", i));
+        }
+        assert_eq!(log.len(), NOTIFICATION_LOG_CAP);
+        let entries = log.newest_first();
+        assert_eq!(
+            entries[0].message,
+            format!("msg {}", NOTIFICATION_LOG_CAP + 4)
+        );
+        assert_eq!(entries.last().unwrap().message, "msg 5");
+    }
+
+    #[test]
+    fn entry_time_strips_date() {
+        let entry = NotificationEntry {
+            timestamp: "2026-01-02T03:04:05Z".into(),
+            level: NotificationLevel::Info,
+            message: String::new(),
+        };
+        assert_eq!(entry.time(), "03:04:05");
+    }
+}
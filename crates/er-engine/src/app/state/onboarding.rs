@@ -0,0 +1,160 @@
+//! First-run onboarding overlay.
+//!
+//! Shown once per repo when nothing has been reviewed there yet (no session,
+//! no review artifacts, no legacy `.er-*` files). Dismissing it drops an
+//! `onboarded` marker in the repo-level storage dir; `er --onboarding` or the
+//! help hub bring it back.
+
+use super::{App, OverlayData, TabState};
+use crate::paths::ErRoot;
+use std::path::PathBuf;
+
+pub const ONBOARDED_MARKER: &str = "onboarded";
+
+/// Onboarding content as `(section, [(key, explanation)])`. Kept here so the
+/// TUI and any future surface render the same text.
+pub const ONBOARDING_SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Diff modes",
+        &[
+            ("1", "Branch — everything on this branch vs the base"),
+            ("2", "Unstaged — working-tree changes not yet staged"),
+            ("3", "Staged — what the next commit contains (c commits)"),
+            ("4", "History — browse commits one at a time"),
+            (
+                "5+",
+                "PR Diff, Tour, Conflicts, Hidden — shown when they apply",
+            ),
+        ],
+    ),
+    (
+        "Reviewing",
+        &[
+            ("k / j", "Next / previous file, n / N for hunks"),
+            (
+                "Space",
+                "Mark the file reviewed (U jumps to the next unreviewed)",
+            ),
+            (
+                "q / c",
+                "Private question / GitHub comment on the current line",
+            ),
+            ("r / e / x", "Reply to, edit or delete the focused comment"),
+        ],
+    ),
+    (
+        "AI review files",
+        &[
+            (
+                "review.json",
+                "Findings per file — written by an agent, shown inline",
+            ),
+            (
+                "questions.json",
+                "Your questions and notes; agents answer them in place",
+            ),
+            (
+                "order.json",
+                "Suggested review order; summary.md for the overview",
+            ),
+            ("a", "AI hub — run a review, triage or answer questions"),
+        ],
+    ),
+    (
+        "More",
+        &[
+            ("?", "Help hub — every key; reopen this guide from there"),
+            (",", "Settings"),
+            ("L", "Notification log"),
+        ],
+    ),
+];
+
+impl TabState {
+    /// A repo-level file: next to the branch dirs in managed storage
+    /// (`repos/<repo>/<name>`), so switching branches keeps it.
+    pub(super) fn repo_level_path(&self, name: &str) -> PathBuf {
+        match &self.er_root {
+            ErRoot::RepoLocal(root) => PathBuf::from(root).join(".er").join(name),
+            ErRoot::Managed { .. } => crate::storage::storage_root()
+                .join("repos")
+                .join(crate::storage::slug_repo(&self.repo_root))
+                .join(name),
+        }
+    }
+
+    /// Repo-level marker that onboarding was dismissed, so switching
+    /// branches doesn't show it again.
+    fn onboarding_marker_path(&self) -> PathBuf {
+        self.repo_level_path(ONBOARDED_MARKER)
+    }
+
+    /// No trace of a previous review in this repo.
+    pub fn is_first_run(&self) -> bool {
+        if self.is_remote() || self.onboarding_marker_path().exists() {
+            return false;
+        }
+        if std::path::Path::new(&self.er_root.session_path()).exists()
+            || crate::storage::managed_dir_has_artifacts(std::path::Path::new(&self.er_dir()))
+        {
+            return false;
+        }
+        let has_legacy = std::fs::read_dir(&self.repo_root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| e.file_name().to_string_lossy().starts_with(".er-"))
+            })
+            .unwrap_or(false);
+        !has_legacy
+    }
+
+    fn mark_onboarded(&self) {
+        let path = self.onboarding_marker_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(&path, crate::sync::chrono_now());
+    }
+}
+
+impl App {
+    /// Open the onboarding overlay on first launch in a repo.
+    pub fn maybe_show_onboarding(&mut self) {
+        if self.overlay.is_none() && self.tab().is_first_run() {
+            self.open_onboarding();
+        }
+    }
+
+    pub fn open_onboarding(&mut self) {
+        self.overlay = Some(OverlayData::Onboarding { scroll: 0 });
+    }
+
+    /// Close the overlay and remember it was seen.
+    pub fn dismiss_onboarding(&mut self) {
+        self.overlay = None;
+        self.tab().mark_onboarded();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_run_until_dismissed_or_reviewed() {
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path().to_string_lossy().to_string();
+        let mut tab = TabState::new_for_test(vec![]);
+        tab.repo_root = root.clone();
+        tab.er_root = ErRoot::RepoLocal(root.clone());
+        assert!(tab.is_first_run());
+
+        std::fs::write(repo.path().join(".er-review.json"), "{}").unwrap();
+        assert!(!tab.is_first_run());
+        std::fs::remove_file(repo.path().join(".er-review.json")).unwrap();
+
+        tab.mark_onboarded();
+        assert!(!tab.is_first_run());
+    }
+}
@@ -0,0 +1,84 @@
+//! Operational changes (see [`git::scan_operational`]): the config keys,
+//! env vars and feature flags a diff adds, removes or edits. Listed in the
+//! Ops panel, counted in its tab, and put first in the hand-off export —
+//! these are what need a deploy note or a settings change.
+
+use super::{App, TabState};
+use crate::config::{self, ErConfig};
+use crate::git;
+
+/// The repo's own `[operational]` when its `.er-config.toml` has one, else
+/// the global one.
+pub(super) fn resolve_scanner(repo_root: &str, global: &ErConfig) -> Option<git::OpsScanner> {
+    config::load_repo_operational(repo_root)
+        .unwrap_or_else(|| global.operational.clone())
+        .scanner()
+}
+
+impl TabState {
+    pub(super) fn refresh_operational(&mut self) {
+        self.operational = match &self.ops_scanner {
+            Some(scanner) => git::scan_operational(&self.files, scanner),
+            None => Vec::new(),
+        };
+        if self.operational.is_empty() {
+            if self.panel == Some(crate::ai::PanelContent::Operational) {
+                self.panel = None;
+            }
+            if self.second_panel == Some(crate::ai::PanelContent::Operational) {
+                self.second_panel = None;
+            }
+        }
+    }
+
+    /// Counts of `(added, removed, changed)` operational changes.
+    pub fn operational_counts(&self) -> (usize, usize, usize) {
+        let count =
+            |kind: git::OpsChangeKind| self.operational.iter().filter(|c| c.change == kind).count();
+        (
+            count(git::OpsChangeKind::Added),
+            count(git::OpsChangeKind::Removed),
+            count(git::OpsChangeKind::Changed),
+        )
+    }
+}
+
+impl App {
+    /// Open the Ops panel, or say there's nothing in it.
+    pub fn open_operational(&mut self) {
+        if self.tab().operational.is_empty() {
+            self.notify("No config, env var or feature-flag changes in this diff");
+            return;
+        }
+        let tab = self.tab_mut();
+        if tab.second_panel != Some(crate::ai::PanelContent::Operational) {
+            tab.panel = Some(crate::ai::PanelContent::Operational);
+            tab.panel_scroll = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::PanelContent;
+
+    #[test]
+    fn ops_panel_joins_the_cycle_only_with_changes() {
+        let diff =
+            "diff --git a/.env.example b/.env.example\n--- a/.env.example\n+++ b/.env.example\n\
+                    @@ -1 +1,2 @@\n PORT=8080\n+STRIPE_KEY=\n";
+        let mut tab = TabState::new_for_test(git::parse_diff(diff));
+        tab.refresh_operational();
+        assert_eq!(tab.operational.len(), 1);
+        assert_eq!(tab.operational_counts(), (1, 0, 0));
+        tab.panel = Some(PanelContent::SymbolRefs);
+        tab.toggle_panel();
+        assert_eq!(tab.panel, Some(PanelContent::Operational));
+
+        tab.ops_scanner = None;
+        tab.refresh_operational();
+        assert!(tab.operational.is_empty());
+        assert_eq!(tab.panel, None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Ownership of the selected file for the File Detail panel.
+//!
+//! `git log -- path` can take a while on a long history, so each file's
+//! authors are read on a worker thread the first time the panel shows it and
+//! kept for the rest of the session.
+
+use std::sync::mpsc::{self, TryRecvError};
+
+use crate::ai::PanelContent;
+use crate::git;
+
+use super::App;
+
+impl App {
+    /// Fetch ownership for the file the File Detail panel is showing, and
+    /// store a finished fetch. True when the panel needs a redraw.
+    pub fn poll_file_ownership(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.ownership_rx {
+            return match rx.try_recv() {
+                Err(TryRecvError::Empty) => false,
+                Ok((path, result)) => {
+                    tab.ownership_rx = None;
+                    match result {
+                        Ok(ownership) => {
+                            tab.file_ownership.insert(path, ownership);
+                            true
+                        }
+                        Err(e) => {
+                            crate::debug_log::warn(
+                                "git",
+                                format!("ownership of {}: {:#}", path, e),
+                            );
+                            tab.file_ownership
+                                .insert(path, git::FileOwnership::default());
+                            false
+                        }
+                    }
+                }
+                Err(TryRecvError::Disconnected) => {
+                    tab.ownership_rx = None;
+                    false
+                }
+            };
+        }
+        if tab.panel != Some(PanelContent::FileDetail) || tab.is_remote() {
+            return false;
+        }
+        let Some(path) = tab.selected_diff_file().map(|f| f.path.clone()) else {
+            return false;
+        };
+        if tab.file_ownership.contains_key(&path) {
+            return false;
+        }
+        let repo_root = tab.repo_root.clone();
+        let rev = tab
+            .local_branch_view
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = git::file_ownership(&repo_root, &rev, &path);
+            let _ = tx.send((path, result));
+        });
+        tab.ownership_rx = Some(rx);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn ownership_is_only_fetched_for_the_file_detail_panel() {
+        let mut app = App::new_for_test(vec![modified_file("src/a.rs", 1)]);
+        assert!(!app.poll_file_ownership());
+        assert!(app.tab().ownership_rx.is_none());
+
+        app.tab_mut().panel = Some(PanelContent::FileDetail);
+        app.tab_mut()
+            .file_ownership
+            .insert("src/a.rs".into(), git::FileOwnership::default());
+        assert!(!app.poll_file_ownership());
+        assert!(app.tab().ownership_rx.is_none());
+    }
+}
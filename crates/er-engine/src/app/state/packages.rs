@@ -0,0 +1,166 @@
+//! Monorepo package awareness: the tab's workspace members (see
+//! [`git::detect_packages`]), which of them the diff touches, and how far the
+//! review of each has got. Feeds `package:` filters, the package sort and the
+//! top bar. The Packages hub adds the members that depend on a touched
+//! package without changing themselves — what else to smoke test.
+
+use super::file_sort::GroupProgress;
+use super::{App, HubAction, HubItem, HubKind, OverlayData, TabState};
+use crate::git;
+
+impl TabState {
+    /// The package owning `path`.
+    pub fn package_of(&self, path: &str) -> Option<&git::Package> {
+        git::package_for(&self.packages, path)
+    }
+
+    /// Touched packages in the order their first file appears in the tree,
+    /// with reviewed counts.
+    pub fn package_progress(&self) -> Vec<GroupProgress> {
+        self.group_progress(|path| self.package_of(path).map(|p| p.name.as_str()))
+    }
+
+    /// Detect the packages again when a diff touches a workspace manifest,
+    /// since members may have been added, renamed or removed.
+    pub(super) fn refresh_packages_if_manifests_changed(&mut self) {
+        if self.is_remote() {
+            return;
+        }
+        let touches_manifest = self.files.iter().any(|f| {
+            let name = f.path.rsplit('/').next().unwrap_or(&f.path);
+            git::PACKAGE_MANIFESTS.contains(&name)
+        });
+        if !touches_manifest {
+            return;
+        }
+        self.packages = git::detect_packages(&self.repo_root);
+        crate::app::filter::resolve_package_rules(&mut self.filter_rules, &self.packages);
+    }
+}
+
+impl App {
+    /// Git hub → Packages: reviewed progress of each touched package (pick
+    /// one to filter to it), then the unchanged packages downstream of them.
+    pub fn open_packages(&mut self) {
+        let tab = self.tab();
+        if tab.packages.is_empty() {
+            self.notify("No monorepo packages found");
+            return;
+        }
+        let progress = tab.package_progress();
+        let touched: Vec<&str> = progress.iter().map(|p| p.name.as_str()).collect();
+        let graph = git::package_dependencies(&tab.repo_root, &tab.packages);
+        let affected = git::affected_packages(&graph, &touched);
+
+        let header = |label: String| HubItem {
+            label,
+            hint: "".into(),
+            description: "".into(),
+            action: HubAction::Noop,
+            is_header: true,
+            enabled: false,
+        };
+        let mut items = vec![header(format!("── Changed ({}) ──", progress.len()))];
+        items.extend(progress.iter().map(|p| HubItem {
+            label: p.name.clone(),
+            hint: "".into(),
+            description: format!("{}/{} files reviewed", p.reviewed, p.total),
+            action: HubAction::FilterByPackage(p.name.clone()),
+            is_header: false,
+            enabled: true,
+        }));
+        items.push(header(format!(
+            "── Potentially affected, unchanged ({}) ──",
+            affected.len()
+        )));
+        if affected.is_empty() {
+            items.push(HubItem {
+                label: "Nothing else depends on the changed packages".into(),
+                hint: "".into(),
+                description: "".into(),
+                action: HubAction::Noop,
+                is_header: false,
+                enabled: false,
+            });
+        }
+        items.extend(affected.iter().map(|a| HubItem {
+            label: a.name.clone(),
+            hint: "".into(),
+            description: if a.depth == 1 {
+                format!("depends on {}", a.via)
+            } else {
+                format!("depends on {} ({} hops)", a.via, a.depth)
+            },
+            action: HubAction::Noop,
+            is_header: false,
+            enabled: true,
+        }));
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Packages,
+            title: Some(format!(
+                "PACKAGES · {} changed · {} affected",
+                progress.len(),
+                affected.len()
+            )),
+            selected: usize::from(!progress.is_empty()),
+            items,
+        });
+    }
+
+    pub fn filter_by_package(&mut self, name: &str) {
+        self.tab_mut()
+            .apply_filter_expr(&format!("package:{}", name));
+        let files = self.tab().visible_files().len();
+        self.notify(&format!(
+            "Showing {}: {} file{}",
+            name,
+            files,
+            if files == 1 { "" } else { "s" }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffFile, FileStatus, Package, PackageKind};
+
+    fn file(path: &str) -> DiffFile {
+        DiffFile {
+            path: path.into(),
+            status: FileStatus::Modified,
+            hunks: Vec::new(),
+            adds: 1,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        }
+    }
+
+    #[test]
+    fn progress_counts_reviewed_files_per_touched_package() {
+        let mut tab = TabState::new_for_test(vec![
+            file("web/app.ts"),
+            file("services/api/main.rs"),
+            file("web/lib.ts"),
+            file("README.md"),
+        ]);
+        tab.packages = ["services/api", "tools", "web"]
+            .iter()
+            .map(|root| Package {
+                name: root.rsplit('/').next().unwrap().to_string(),
+                root: root.to_string(),
+                kind: PackageKind::Node,
+            })
+            .collect();
+        tab.reviewed.insert("web/lib.ts".into(), String::new());
+
+        let progress = tab.package_progress();
+        let summary: Vec<(&str, usize, usize)> = progress
+            .iter()
+            .map(|p| (p.name.as_str(), p.reviewed, p.total))
+            .collect();
+        assert_eq!(summary, [("web", 1, 2), ("api", 0, 1)]);
+        assert!(tab.package_of("README.md").is_none());
+    }
+}
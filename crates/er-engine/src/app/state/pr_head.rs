@@ -0,0 +1,277 @@
+//! Local `HEAD` against the PR head on GitHub, so a review (or approval)
+//! isn't of commits GitHub doesn't have — or missing ones it does.
+//!
+//! The comparison is cached per PR head oid and dropped on every diff
+//! refresh; [`App::poll_pr_head_sync`] recomputes it from the main loop.
+
+use super::checkpoint::CheckpointView;
+use super::{App, DiffMode, TabState};
+use crate::git;
+use anyhow::{Context, Result};
+
+/// How local `HEAD` relates to the PR head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrHeadSync {
+    InSync,
+    /// `ahead` local commits GitHub doesn't have, `behind` PR commits not
+    /// pulled
+    Differs {
+        ahead: usize,
+        behind: usize,
+    },
+    /// The PR head commit isn't in the local clone yet
+    NotFetched,
+}
+
+impl PrHeadSync {
+    /// Short label for the top bar; `None` when in sync.
+    pub fn label(self) -> Option<String> {
+        match self {
+            PrHeadSync::InSync => None,
+            PrHeadSync::Differs { ahead, behind } => {
+                let mut parts = Vec::new();
+                if ahead > 0 {
+                    parts.push(format!("⇡{} unpushed", ahead));
+                }
+                if behind > 0 {
+                    parts.push(format!("⇣{} not pulled", behind));
+                }
+                if parts.is_empty() {
+                    parts.push("differs from PR head".to_string());
+                }
+                Some(parts.join(" "))
+            }
+            PrHeadSync::NotFetched => Some("PR head not fetched".to_string()),
+        }
+    }
+
+    /// Local commits a push would add to the PR.
+    pub fn unpushed(self) -> usize {
+        match self {
+            PrHeadSync::Differs { ahead, .. } => ahead,
+            _ => 0,
+        }
+    }
+}
+
+fn compare_with_pr_head(repo_root: &str, pr_head: &str) -> PrHeadSync {
+    if crate::github::rev_parse_oid(repo_root, "HEAD").as_deref() == Some(pr_head) {
+        return PrHeadSync::InSync;
+    }
+    if !crate::github::ref_exists_locally(repo_root, &format!("{}^{{commit}}", pr_head)) {
+        return PrHeadSync::NotFetched;
+    }
+    match git::git_ahead_behind(repo_root, "HEAD", pr_head) {
+        Ok((ahead, behind)) => PrHeadSync::Differs { ahead, behind },
+        Err(e) => {
+            crate::debug_log::warn("git", format!("HEAD vs PR head: {:#}", e));
+            PrHeadSync::NotFetched
+        }
+    }
+}
+
+impl TabState {
+    /// PR head to compare with: only for a local checkout of the PR's own
+    /// branch (not remote or PR review tabs) once the overview has loaded.
+    fn pr_head_oid(&self) -> Option<&str> {
+        if self.is_remote() || self.local_branch_view.is_some() || self.pr_head_only {
+            return None;
+        }
+        let pr = self.pr_data.as_ref()?;
+        (!pr.head_oid.is_empty() && pr.head_branch == self.current_branch)
+            .then_some(pr.head_oid.as_str())
+    }
+
+    /// The cached comparison, `None` when there's nothing to compare.
+    pub fn pr_head_sync(&self) -> Option<PrHeadSync> {
+        let oid = self.pr_head_oid()?;
+        match &self.pr_head_sync {
+            Some((cached, sync)) if cached == oid => Some(*sync),
+            _ => None,
+        }
+    }
+
+    /// Whether a push would add local commits to the PR.
+    pub fn has_unpushed_pr_commits(&self) -> bool {
+        self.pr_head_sync().is_some_and(|s| s.unpushed() > 0)
+    }
+
+    /// Recompute the comparison if the PR head moved or a refresh dropped
+    /// it. True when the result changed.
+    pub fn update_pr_head_sync(&mut self) -> bool {
+        let Some(oid) = self.pr_head_oid().map(str::to_string) else {
+            return self.pr_head_sync.take().is_some();
+        };
+        if self.pr_head_sync.as_ref().is_some_and(|(c, _)| *c == oid) {
+            return false;
+        }
+        let previous = self.pr_head_sync.take().map(|(_, s)| s);
+        let sync = compare_with_pr_head(&self.repo_root, &oid);
+        self.pr_head_sync = Some((oid, sync));
+        previous != Some(sync)
+    }
+
+    /// After a push from the PR's branch, GitHub has local `HEAD`; the next
+    /// overview sync confirms it.
+    pub fn note_pushed_to_pr(&mut self) {
+        if self.pr_head_oid().is_none() {
+            return;
+        }
+        if let Some(local) = crate::github::rev_parse_oid(&self.repo_root, "HEAD") {
+            if let Some(pr) = self.pr_data.as_mut() {
+                pr.head_oid = local;
+            }
+        }
+        self.pr_head_sync = None;
+    }
+
+    /// Toggle the Branch diff between the full branch and local `HEAD` vs
+    /// the PR head (fetching the PR head first if needed). Returns whether
+    /// the comparison is now on.
+    pub fn toggle_pr_head_view(&mut self) -> Result<bool> {
+        if self.in_pr_head_view() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+            return Ok(false);
+        }
+        let oid = self
+            .pr_head_oid()
+            .context("Not on a PR's branch (or the PR hasn't loaded yet)")?
+            .to_string();
+        self.update_pr_head_sync();
+        if self.pr_head_sync() == Some(PrHeadSync::NotFetched) {
+            let pr = self.pr_number.or(self.pr_data.as_ref().map(|p| p.number));
+            let pr = pr.context("PR number unknown")?;
+            crate::github::fetch_pr_head(pr, &self.repo_root)?;
+            self.pr_head_sync = None;
+            self.update_pr_head_sync();
+        }
+        if self.mode != DiffMode::Branch {
+            self.set_mode(DiffMode::Branch);
+        }
+        self.checkpoint_view = Some(CheckpointView::against_pr_head(self.repo_root.clone(), oid));
+        if let Err(e) = self.refresh_diff_quick() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+            return Err(e);
+        }
+        Ok(true)
+    }
+}
+
+impl App {
+    pub fn toggle_pr_head_view(&mut self) {
+        match self.tab_mut().toggle_pr_head_view() {
+            Ok(true) => {
+                self.tab_mut().update_pr_head_sync();
+                let tab = self.tab();
+                let status = tab
+                    .pr_head_sync()
+                    .and_then(PrHeadSync::label)
+                    .unwrap_or_else(|| "in sync".to_string());
+                let count = tab.files.len();
+                self.notify(&format!(
+                    "Local HEAD vs PR head ({}): {} file{} differ",
+                    status,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+            Ok(false) => self.notify("PR head view off — full branch diff"),
+            Err(e) => self.notify(&format!("{:#}", e)),
+        }
+    }
+
+    /// Keep the active tab's PR head comparison current. True when it
+    /// changed.
+    pub fn poll_pr_head_sync(&mut self) -> bool {
+        self.tab_mut().update_pr_head_sync()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::OutputLogged;
+
+    #[test]
+    fn labels_say_which_way_local_and_github_differ() {
+        assert_eq!(PrHeadSync::InSync.label(), None);
+        assert_eq!(
+            PrHeadSync::Differs {
+                ahead: 2,
+                behind: 1
+            }
+            .label()
+            .as_deref(),
+            Some("⇡2 unpushed ⇣1 not pulled")
+        );
+        assert_eq!(
+            PrHeadSync::Differs {
+                ahead: 0,
+                behind: 3
+            }
+            .unpushed(),
+            0
+        );
+    }
+
+    #[test]
+    fn local_commits_past_the_pr_head_count_as_unpushed() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@t.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git(&["init", "-b", "feature"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["commit", "-m", "first", "--no-gpg-sign"]);
+        let pushed = String::from_utf8_lossy(&git(&["rev-parse", "HEAD"]).stdout)
+            .trim()
+            .to_string();
+        std::fs::write(root.join("a.txt"), "two\n").unwrap();
+        git(&["commit", "-am", "second", "--no-gpg-sign"]);
+
+        let mut app = App::new_for_test(vec![]);
+        let tab = app.tab_mut();
+        tab.repo_root = root.to_str().unwrap().to_string();
+        tab.current_branch = "feature".into();
+        tab.pr_data = Some(crate::github::PrOverviewData {
+            number: 7,
+            title: String::new(),
+            body: String::new(),
+            state: "OPEN".into(),
+            author: String::new(),
+            url: String::new(),
+            base_branch: "main".into(),
+            head_branch: "feature".into(),
+            checks: Vec::new(),
+            reviewers: Vec::new(),
+            created_at: String::new(),
+            head_oid: pushed,
+            labels: Vec::new(),
+        });
+        assert!(tab.update_pr_head_sync());
+        assert_eq!(
+            tab.pr_head_sync(),
+            Some(PrHeadSync::Differs {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        assert!(!tab.update_pr_head_sync());
+
+        tab.note_pushed_to_pr();
+        tab.update_pr_head_sync();
+        assert_eq!(tab.pr_head_sync(), Some(PrHeadSync::InSync));
+    }
+}
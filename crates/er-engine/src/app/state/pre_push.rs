@@ -0,0 +1,250 @@
+//! Pre-push validation (`[push]`): before the push action runs `git push`,
+//! the configured checks — and, with `run_hook`, the repo's own pre-push
+//! hook — run in the background from the repo root with their output in the
+//! agent log panel. A clean run pushes straight away; any failure asks
+//! whether to push anyway.
+
+use std::sync::mpsc::{self, TryRecvError};
+
+use anyhow::Result;
+
+use super::{AgentLogEntry, AgentLogSource, App, ConfirmAction};
+use crate::ai::prompts::sanitize_for_shell;
+use crate::config::ErConfig;
+use crate::git;
+
+/// Name the hook's output is logged under.
+const HOOK_NAME: &str = "pre-push hook";
+
+/// Outcome of a pre-push run.
+#[derive(Debug, Clone, Default)]
+pub struct PrePushReport {
+    /// Checks that failed, in run order
+    pub failed: Vec<String>,
+    /// The repo's hook ran, so `git push` shouldn't run it again
+    pub hook_ran: bool,
+}
+
+/// Shell command for check `name`: a `[commands]` entry, a
+/// `[commands.custom]` entry, or else `name` itself.
+fn resolve_check(config: &ErConfig, name: &str) -> String {
+    config
+        .resolve_command(name)
+        .or_else(|| config.commands.custom.get(name).cloned())
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn log(tx: &mpsc::Sender<AgentLogEntry>, name: &str, source: AgentLogSource, text: String) {
+    let _ = tx.send(AgentLogEntry {
+        timestamp: std::time::Instant::now(),
+        command_name: name.to_string(),
+        source,
+        text,
+    });
+}
+
+/// Log `output` under `name`; true when it exited cleanly.
+fn log_output(tx: &mpsc::Sender<AgentLogEntry>, name: &str, output: &std::process::Output) -> bool {
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        log(tx, name, AgentLogSource::Stdout, line.to_string());
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        log(tx, name, AgentLogSource::Stderr, line.to_string());
+    }
+    let ok = output.status.success();
+    let status = if ok { "passed" } else { "failed" };
+    log(
+        tx,
+        name,
+        AgentLogSource::Status,
+        format!("{} {}", name, status),
+    );
+    ok
+}
+
+impl App {
+    /// The push action: run the pre-push checks first when any are
+    /// configured, otherwise push now.
+    pub fn start_push(&mut self) -> Result<()> {
+        if self.blocked_by_read_only("pushing") {
+            return Ok(());
+        }
+        let mut push = self.config.push.clone();
+        push.checks = self.required_push_checks();
+        if push.checks.is_empty() && !push.run_hook {
+            self.push_now(false);
+            return Ok(());
+        }
+        if self.tab().pre_push_rx.is_some() {
+            self.notify("Pre-push checks already running");
+            return Ok(());
+        }
+
+        let tab = self.tab();
+        let repo_root = tab.repo_root.clone();
+        let checks: Vec<(String, String)> = push
+            .checks
+            .iter()
+            .map(|name| {
+                let cmd = resolve_check(&self.config, name)
+                    .replace("{base}", &sanitize_for_shell(&tab.base_branch))
+                    .replace("{branch}", &sanitize_for_shell(&tab.current_branch))
+                    .replace("{repo}", &sanitize_for_shell(&repo_root));
+                (name.clone(), cmd)
+            })
+            .collect();
+        let run_hook = push.run_hook;
+        let log_tx = tab.log_tx.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut report = PrePushReport::default();
+            if run_hook {
+                log(
+                    &log_tx,
+                    HOOK_NAME,
+                    AgentLogSource::Status,
+                    format!("{} started", HOOK_NAME),
+                );
+                match git::run_pre_push_hook(&repo_root) {
+                    Ok(Some(output)) => {
+                        report.hook_ran = true;
+                        if !log_output(&log_tx, HOOK_NAME, &output) {
+                            report.failed.push(HOOK_NAME.to_string());
+                        }
+                    }
+                    Ok(None) => log(
+                        &log_tx,
+                        HOOK_NAME,
+                        AgentLogSource::Status,
+                        "no pre-push hook in this repo".to_string(),
+                    ),
+                    Err(e) => {
+                        log(
+                            &log_tx,
+                            HOOK_NAME,
+                            AgentLogSource::Stderr,
+                            format!("{:#}", e),
+                        );
+                        report.failed.push(HOOK_NAME.to_string());
+                    }
+                }
+            }
+            for (name, cmd) in checks {
+                log(
+                    &log_tx,
+                    &name,
+                    AgentLogSource::Status,
+                    format!("{} started", name),
+                );
+                let output = std::process::Command::new("sh")
+                    .args(["-c", &cmd])
+                    .current_dir(&repo_root)
+                    .output();
+                let ok = match output {
+                    Ok(output) => log_output(&log_tx, &name, &output),
+                    Err(e) => {
+                        log(&log_tx, &name, AgentLogSource::Stderr, e.to_string());
+                        false
+                    }
+                };
+                if !ok {
+                    report.failed.push(name);
+                }
+            }
+            let _ = tx.send(report);
+        });
+
+        let tab = self.tab_mut();
+        tab.pre_push_rx = Some(rx);
+        tab.panel = Some(crate::ai::PanelContent::AgentLog);
+        tab.agent_log_auto_scroll = true;
+        self.notify("Running pre-push checks...");
+        Ok(())
+    }
+
+    /// Push when the checks came back clean, otherwise ask whether to push
+    /// anyway. True when the run finished.
+    pub fn poll_pre_push(&mut self) -> bool {
+        let Some(rx) = &self.tab().pre_push_rx else {
+            return false;
+        };
+        let report = match rx.try_recv() {
+            Err(TryRecvError::Empty) => return false,
+            Ok(report) => report,
+            Err(TryRecvError::Disconnected) => PrePushReport {
+                failed: vec!["pre-push checks".to_string()],
+                hook_ran: false,
+            },
+        };
+        self.tab_mut().pre_push_rx = None;
+        if report.failed.is_empty() {
+            self.push_now(report.hook_ran);
+        } else {
+            self.confirm(ConfirmAction::PushDespiteChecks {
+                failed: report.failed,
+            });
+        }
+        true
+    }
+
+    /// `git push` now. `no_verify` skips the repo's pre-push hook, for when it
+    /// already ran or the user chose to push past it.
+    pub fn push_now(&mut self, no_verify: bool) {
+        if self.blocked_by_read_only("pushing") {
+            return;
+        }
+        let repo_root = self.tab().repo_root.clone();
+        match git::git_push(&repo_root, no_verify) {
+            Ok(_) => {
+                let tab = self.tab_mut();
+                tab.committed_unpushed = false;
+                tab.note_pushed_to_pr();
+                let _ = tab.refresh_diff();
+                self.notify("Pushed!");
+            }
+            Err(e) => self.report_command_error("Push failed", &e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_resolve_builtin_then_custom_then_raw() {
+        let mut config = ErConfig::default();
+        config.commands.lint = Some("cargo clippy".into());
+        config
+            .commands
+            .custom
+            .insert("fmt".into(), "cargo fmt --check".into());
+        assert_eq!(resolve_check(&config, "lint"), "cargo clippy");
+        assert_eq!(resolve_check(&config, "fmt"), "cargo fmt --check");
+        assert_eq!(resolve_check(&config, "make check"), "make check");
+    }
+
+    #[test]
+    fn failed_checks_ask_before_pushing() {
+        let mut app = App::new_for_test(vec![]);
+        let (tx, rx) = mpsc::channel();
+        app.tab_mut().pre_push_rx = Some(rx);
+        assert!(!app.poll_pre_push());
+
+        tx.send(PrePushReport {
+            failed: vec!["test".into()],
+            hook_ran: true,
+        })
+        .unwrap();
+        assert!(app.poll_pre_push());
+        assert!(app.tab().pre_push_rx.is_none());
+        let prompt = app.current_confirm().unwrap();
+        assert_eq!(
+            prompt.action,
+            ConfirmAction::PushDespiteChecks {
+                failed: vec!["test".into()]
+            }
+        );
+        assert!(prompt.danger);
+    }
+}
@@ -0,0 +1,190 @@
+//! Quick diff of the selected file against any ref (Git hub → *Diff file
+//! against ref…*): a tag, branch or commit typed at the prompt. For "this
+//! restores the 1.3 behaviour", diffing the file against `v1.3.0` shows
+//! whether it does.
+//!
+//! It's the checkpoint view with the ref in place of the checkpoint and the
+//! one file as the pathspec, so it swaps the Branch diff the same way and
+//! Esc (or `I`) goes back to the full one. The new side is what the tab
+//! diffs: the working tree of the tab's own checkout, the branch for a
+//! branch view.
+
+use anyhow::{Context, Result};
+
+use super::checkpoint::CheckpointView;
+use super::{App, DiffMode, InputMode, TabState};
+
+impl TabState {
+    /// The ref the quick diff compares with, while it's on.
+    pub fn quick_diff_ref(&self) -> Option<&str> {
+        if !self.in_checkpoint_view() {
+            return None;
+        }
+        self.checkpoint_view.as_ref()?.quick_ref.as_deref()
+    }
+
+    pub fn in_quick_diff(&self) -> bool {
+        self.quick_diff_ref().is_some()
+    }
+
+    /// Swap the diff for the selected file against `ref_name`. Returns the
+    /// number of changed lines; nothing is swapped when there are none.
+    pub fn open_quick_diff(&mut self, ref_name: &str) -> Result<usize> {
+        let ref_name = ref_name.trim();
+        if ref_name.is_empty() {
+            anyhow::bail!("No ref given");
+        }
+        if !matches!(
+            self.mode,
+            DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged
+        ) {
+            anyhow::bail!("Quick diff works from the Branch, Unstaged or Staged view");
+        }
+        let path = self
+            .selected_diff_file()
+            .map(|f| f.path.clone())
+            .context("No file selected")?;
+        let (root, head) = self.checkpoint_target()?;
+        // The tab's own checkout diffs the working tree, so edits count
+        let head = head.filter(|_| self.local_branch_view.is_some());
+        let oid = crate::github::rev_parse_oid(&root, &format!("{}^{{commit}}", ref_name))
+            .with_context(|| format!("Unknown ref: {}", ref_name))?;
+        let view = CheckpointView::against_ref(root, head, oid, ref_name.to_string(), path);
+        let raw = view.raw_diff()?;
+        if raw.trim().is_empty() {
+            return Ok(0);
+        }
+        let changed = crate::git::parse_diff(&raw)
+            .iter()
+            .map(|f| f.adds + f.dels)
+            .sum::<usize>();
+        if self.mode != DiffMode::Branch {
+            self.set_mode(DiffMode::Branch);
+        }
+        self.checkpoint_view = Some(view);
+        if let Err(e) = self.refresh_diff_quick() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+            return Err(e);
+        }
+        Ok(changed)
+    }
+
+    /// Back to the full diff, on the file that was quick-diffed.
+    pub fn close_quick_diff(&mut self) -> Result<()> {
+        if self.in_quick_diff() {
+            self.checkpoint_view = None;
+            self.refresh_diff()?;
+        }
+        Ok(())
+    }
+}
+
+impl App {
+    /// Ask which ref to diff the selected file against.
+    pub fn start_quick_diff(&mut self) {
+        if self.tab().is_remote() {
+            self.notify_warn("Quick diff needs a local clone");
+            return;
+        }
+        if self.tab().selected_diff_file().is_none() {
+            self.notify("Select a file to diff against a ref");
+            return;
+        }
+        self.quick_diff_input = self.tab().quick_diff_ref().unwrap_or_default().to_string();
+        self.input_mode = InputMode::QuickDiffRef;
+    }
+
+    pub fn submit_quick_diff(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let ref_name = std::mem::take(&mut self.quick_diff_input);
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        match self.tab_mut().open_quick_diff(&ref_name) {
+            Ok(0) => self.notify(&format!("{} is the same at {}", path, ref_name.trim())),
+            Ok(changed) => self.notify(&format!(
+                "{} vs {}: {} line{} differ — Esc for the full diff",
+                path,
+                ref_name.trim(),
+                changed,
+                if changed == 1 { "" } else { "s" }
+            )),
+            Err(e) => self.notify_warn(&format!("{:#}", e)),
+        }
+    }
+
+    pub fn cancel_quick_diff(&mut self) {
+        self.quick_diff_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Esc in the quick diff.
+    pub fn close_quick_diff(&mut self) {
+        match self.tab_mut().close_quick_diff() {
+            Ok(()) => self.notify("Full diff"),
+            Err(e) => self.report_command_error("Refreshing the diff failed", &e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::OutputLogged;
+
+    #[test]
+    fn a_file_is_diffed_against_a_tag_until_esc() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@t.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git(&["init", "-b", "main"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        std::fs::write(root.join("b.txt"), "b\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "first", "--no-gpg-sign"]);
+        git(&["tag", "v1.3"]);
+        std::fs::write(root.join("a.txt"), "two\n").unwrap();
+        git(&["commit", "-am", "second", "--no-gpg-sign"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        std::fs::write(root.join("b.txt"), "b2\n").unwrap();
+
+        let mut app = App::new_for_test(crate::git::parse_diff(
+            "diff --git a/a.txt b/a.txt\n--- a/a.txt\n+++ b/a.txt\n@@ -1 +1 @@\n-two\n+one\n\
+             diff --git a/b.txt b/b.txt\n--- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-b\n+b2\n",
+        ));
+        app.tab_mut().repo_root = root.to_str().unwrap().to_string();
+        app.tab_mut().mode = DiffMode::Branch;
+
+        // The working tree restored a.txt to what v1.3 had
+        app.start_quick_diff();
+        assert_eq!(app.input_mode, InputMode::QuickDiffRef);
+        app.quick_diff_input = "v1.3".into();
+        app.submit_quick_diff();
+        assert!(!app.tab().in_quick_diff());
+        assert!(app
+            .watch_message
+            .as_deref()
+            .is_some_and(|m| m.contains("same at v1.3")));
+
+        app.tab_mut().selected_file = 1;
+        assert_eq!(app.tab_mut().open_quick_diff("v1.3").unwrap(), 2);
+        assert_eq!(app.tab().quick_diff_ref(), Some("v1.3"));
+        let paths: Vec<&str> = app.tab().files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["b.txt"]);
+
+        assert!(app.tab_mut().open_quick_diff("v9").is_err());
+        app.tab_mut().close_quick_diff().unwrap();
+        assert!(!app.tab().in_quick_diff());
+    }
+}
@@ -0,0 +1,105 @@
+//! Ctrl+q with work still in flight.
+//!
+//! Quitting asks first when it would leave something behind: a comment
+//! being typed, local GitHub comments not pushed yet, a commit made here but
+//! not pushed, or files staged here but not committed. A second Ctrl+q
+//! quits anyway; `[features] confirm_quit = false` turns the prompt off.
+
+use super::{App, ConfirmAction, InputMode};
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+impl App {
+    /// What quitting now would leave behind, one short phrase each.
+    pub fn unsaved_work(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        let drafts = self
+            .tabs
+            .iter()
+            .filter(|t| t.current_comment_draft().is_some())
+            .count();
+        if drafts > 0 {
+            out.push(plural(drafts, "unsent comment", "unsent comments"));
+        }
+        let unpushed: usize = self
+            .tabs
+            .iter()
+            .filter_map(|t| t.ai.github_comments.as_ref())
+            .map(|gc| gc.comments.iter().filter(|c| !c.synced).count())
+            .sum();
+        if unpushed > 0 {
+            out.push(plural(
+                unpushed,
+                "unpushed GitHub comment",
+                "unpushed GitHub comments",
+            ));
+        }
+        if self.tabs.iter().any(|t| t.committed_unpushed) {
+            out.push("a commit not pushed".to_string());
+        }
+        if self
+            .tabs
+            .iter()
+            .any(|t| t.staged_in_app && crate::git::has_staged_changes(&t.repo_root))
+        {
+            out.push("staged changes not committed".to_string());
+        }
+        out
+    }
+
+    /// Ctrl+q: quit now, or ask when [`App::unsaved_work`] finds something.
+    /// Pressed again while asking, it quits regardless.
+    pub fn request_quit(&mut self) {
+        if matches!(
+            self.input_mode,
+            InputMode::Confirm(ConfirmAction::Quit { .. })
+        ) || !self.config.features.confirm_quit
+        {
+            self.should_quit = true;
+            return;
+        }
+        let left_behind = self.unsaved_work();
+        if left_behind.is_empty() {
+            self.should_quit = true;
+        } else {
+            self.confirm(ConfirmAction::Quit { left_behind });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_textarea::TextArea;
+
+    #[test]
+    fn quit_asks_with_a_draft_and_second_press_forces() {
+        let mut app = App::new_for_test(vec![]);
+        app.tab_mut().comment_textarea = TextArea::new(vec!["half a thought".into()]);
+        app.request_quit();
+        assert!(!app.should_quit);
+        assert_eq!(
+            app.input_mode,
+            InputMode::Confirm(ConfirmAction::Quit {
+                left_behind: vec!["1 unsent comment".into()]
+            })
+        );
+        app.request_quit();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn quit_is_immediate_when_disabled_or_clean() {
+        let mut app = App::new_for_test(vec![]);
+        app.request_quit();
+        assert!(app.should_quit);
+
+        let mut app = App::new_for_test(vec![]);
+        app.config.features.confirm_quit = false;
+        app.tab_mut().committed_unpushed = true;
+        app.request_quit();
+        assert!(app.should_quit);
+    }
+}
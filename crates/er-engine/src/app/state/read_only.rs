@@ -0,0 +1,68 @@
+//! Read-only sessions (`er --read-only`): for reviewing on a machine or in a
+//! checkout where nothing may change. Staging, committing, pushing, applying
+//! hunks, opening files in an editor and anything that writes to GitHub —
+//! approving, pushing or deleting comments, publishing the shared review,
+//! sending viewed ticks — are all refused; reviewing, commenting locally and
+//! AI features still work.
+
+use super::App;
+
+impl App {
+    /// `true` (after saying so) when the session is read-only and `action`
+    /// must not run. Callers return early on `true`.
+    pub fn blocked_by_read_only(&mut self, action: &str) -> bool {
+        if self.read_only {
+            self.notify(&format!("Read-only session — {} is disabled", action));
+        }
+        self.read_only
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::InputMode;
+
+    #[test]
+    fn read_only_session_refuses_mutating_actions() {
+        let mut app = App::new_for_test(vec![]);
+        assert!(!app.blocked_by_read_only("staging"));
+
+        app.read_only = true;
+        app.toggle_stage_file().unwrap();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — staging is disabled")
+        );
+        app.start_commit();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        app.start_push().unwrap();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — pushing is disabled")
+        );
+
+        app.request_approve_pr();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — approving is disabled")
+        );
+        app.push_all_comments_to_github().unwrap();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — pushing comments is disabled")
+        );
+        app.watch_message = None;
+        app.push_comments_as_review().unwrap();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — pushing comments is disabled")
+        );
+        app.publish_shared_review();
+        assert_eq!(
+            app.watch_message.as_deref(),
+            Some("Read-only session — publishing the shared review is disabled")
+        );
+    }
+}
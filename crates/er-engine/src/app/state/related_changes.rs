@@ -0,0 +1,160 @@
+//! The Related panel: recent commits to the selected file on the base
+//! branch and the open or merged PRs that changed it, so work in flight on
+//! the same file and fixes that landed last week show up before they turn
+//! into a conflict or a regression.
+//!
+//! Like ownership, each file's are fetched on a worker thread the first time
+//! the panel shows it (`git log` for the commits, `gh pr list` for the PRs)
+//! and kept for the rest of the session.
+
+use std::sync::mpsc::{self, TryRecvError};
+
+use super::{App, TabState};
+use crate::ai::PanelContent;
+use crate::git::{self, RecentCommit};
+use crate::github::{self, RelatedPr};
+
+/// Commits listed per file.
+const MAX_COMMITS: usize = 12;
+/// Most recent PRs searched for the file.
+const PR_SEARCH_LIMIT: usize = 50;
+
+/// What touched one file lately.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelatedChanges {
+    /// On the base branch, newest first
+    pub commits: Vec<RecentCommit>,
+    /// Open ones first, then merged, newest first; the tab's own PR left out
+    pub prs: Vec<RelatedPr>,
+    /// Why there are no PRs (no `gh`, not a GitHub remote, …)
+    pub forge_error: Option<String>,
+}
+
+impl RelatedChanges {
+    /// Open PRs besides this one that change the file.
+    pub fn open_prs(&self) -> usize {
+        self.prs.iter().filter(|p| p.state == "open").count()
+    }
+
+    /// Base-branch commits that landed after the branch forked.
+    pub fn commits_since_fork(&self) -> usize {
+        self.commits.iter().filter(|c| c.since_fork).count()
+    }
+}
+
+fn fetch(
+    repo_root: &str,
+    base: &str,
+    rev: &str,
+    path: &str,
+    own_pr: Option<u64>,
+) -> RelatedChanges {
+    let commits = if base.is_empty() {
+        Vec::new()
+    } else {
+        git::recent_commits(repo_root, base, rev, path, MAX_COMMITS).unwrap_or_else(|e| {
+            crate::debug_log::warn("git", format!("recent commits of {}: {:#}", path, e));
+            Vec::new()
+        })
+    };
+    let (mut prs, forge_error) = match github::gh_prs_touching(repo_root, path, PR_SEARCH_LIMIT) {
+        Ok(prs) => (prs, None),
+        Err(e) => {
+            crate::debug_log::warn("github", format!("PRs touching {}: {:#}", path, e));
+            (Vec::new(), Some(format!("{:#}", e)))
+        }
+    };
+    prs.retain(|p| Some(p.number) != own_pr);
+    prs.sort_by(|a, b| {
+        (a.state != "open")
+            .cmp(&(b.state != "open"))
+            .then_with(|| b.date.cmp(&a.date))
+    });
+    RelatedChanges {
+        commits,
+        prs,
+        forge_error,
+    }
+}
+
+impl TabState {
+    /// Whether the Related panel is one of the open panels.
+    fn related_panel_open(&self) -> bool {
+        self.panel == Some(PanelContent::RelatedChanges)
+            || self.second_panel == Some(PanelContent::RelatedChanges)
+    }
+
+    /// What touched the selected file lately, once fetched.
+    pub fn selected_related_changes(&self) -> Option<&RelatedChanges> {
+        self.related_changes.get(&self.selected_diff_file()?.path)
+    }
+}
+
+impl App {
+    /// Fetch related changes for the file the Related panel is showing,
+    /// and store a finished fetch. True when the panel needs a redraw.
+    pub fn poll_related_changes(&mut self) -> bool {
+        let tab = self.tab_mut();
+        if let Some(rx) = &tab.related_changes_rx {
+            return match rx.try_recv() {
+                Err(TryRecvError::Empty) => false,
+                Ok((path, related)) => {
+                    tab.related_changes_rx = None;
+                    tab.related_changes.insert(path, related);
+                    true
+                }
+                Err(TryRecvError::Disconnected) => {
+                    tab.related_changes_rx = None;
+                    false
+                }
+            };
+        }
+        if !tab.related_panel_open() || tab.is_remote() {
+            return false;
+        }
+        let Some(path) = tab.selected_diff_file().map(|f| f.path.clone()) else {
+            return false;
+        };
+        if tab.related_changes.contains_key(&path) {
+            return false;
+        }
+        let repo_root = tab.repo_root.clone();
+        let base = tab.base_branch.clone();
+        let rev = tab
+            .local_branch_view
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let own_pr = tab.pr_number;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let related = fetch(&repo_root, &base, &rev, &path, own_pr);
+            let _ = tx.send((path, related));
+        });
+        tab.related_changes_rx = Some(rx);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn related_changes_are_only_fetched_for_the_related_panel() {
+        let mut app = App::new_for_test(vec![modified_file("payments/ledger.rs", 1)]);
+        assert!(!app.poll_related_changes());
+        assert!(app.tab().related_changes_rx.is_none());
+
+        app.tab_mut().panel = Some(PanelContent::RelatedChanges);
+        app.tab_mut()
+            .related_changes
+            .insert("payments/ledger.rs".into(), RelatedChanges::default());
+        assert!(!app.poll_related_changes());
+        assert!(app.tab().related_changes_rx.is_none());
+        assert_eq!(
+            app.tab().selected_related_changes(),
+            Some(&RelatedChanges::default())
+        );
+    }
+}
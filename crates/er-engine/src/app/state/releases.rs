@@ -0,0 +1,249 @@
+//! Tag and release ranges (`t`).
+//!
+//! `t` lists the newest tags twice: each one up to HEAD (`v1.4.0 → HEAD`,
+//! what is unreleased) and each against the tag before it (`v1.3.0 →
+//! v1.4.0`, what a release shipped). The first of each is what is usually
+//! wanted, so `t` then Enter opens the unreleased changes. A range opens
+//! read-only in its own tab, like a branch view, with the forge's release
+//! notes for its newer tag (the older one for a range up to HEAD) in the
+//! PR overview panel slot, fetched in the background and opened when
+//! they arrive.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use super::{App, DiffMode, HubAction, HubItem, HubKind, OverlayData, TabState};
+use crate::ai::PanelContent;
+use crate::github::ReleaseNotes;
+
+/// Tags listed in the picker.
+const TAG_LIMIT: usize = 15;
+
+/// A tab diffing two tags, or a tag and HEAD.
+#[derive(Debug)]
+pub struct ReleaseRange {
+    pub from: String,
+    pub to: String,
+    /// From the forge; `None` until fetched, or when the tag has no release
+    pub notes: Option<ReleaseNotes>,
+    notes_rx: Option<Receiver<Option<ReleaseNotes>>>,
+}
+
+impl ReleaseRange {
+    /// `v1.3.0…v1.4.0`, the tab name and storage scope.
+    pub fn label(&self) -> String {
+        format!("{}…{}", self.from, self.to)
+    }
+
+    /// The tag whose release notes describe the range.
+    fn notes_tag(&self) -> &str {
+        if self.to == "HEAD" {
+            &self.from
+        } else {
+            &self.to
+        }
+    }
+}
+
+/// Picker entries for tags listed newest first: every tag up to HEAD,
+/// then every tag against the one before it.
+fn range_items(tags: &[crate::git::Tag]) -> Vec<HubItem> {
+    let header = |label: &str| HubItem {
+        label: format!("── {} ──", label),
+        hint: "".into(),
+        description: "".into(),
+        action: HubAction::Noop,
+        is_header: true,
+        enabled: true,
+    };
+    let item = |from: &str, to: &str, tag: &crate::git::Tag| HubItem {
+        label: format!("{} → {}", from, to),
+        hint: tag.date.get(..10).unwrap_or(&tag.date).to_string(),
+        description: tag.subject.clone(),
+        action: HubAction::OpenReleaseRange {
+            from: from.to_string(),
+            to: to.to_string(),
+        },
+        is_header: false,
+        enabled: true,
+    };
+    let mut items = vec![header("Unreleased")];
+    items.extend(tags.iter().map(|t| item(&t.name, "HEAD", t)));
+    if tags.len() > 1 {
+        items.push(header("Releases"));
+        items.extend(tags.windows(2).map(|w| item(&w[1].name, &w[0].name, &w[0])));
+    }
+    items
+}
+
+impl TabState {
+    /// A read-only tab diffing `from...to`.
+    pub fn new_release_range(repo_root: String, from: String, to: String) -> anyhow::Result<Self> {
+        let mut tab = TabState::new_with_base_unloaded(repo_root, from.clone())?;
+        tab.local_branch_view = Some(to.clone());
+        tab.release = Some(ReleaseRange {
+            from,
+            to,
+            notes: None,
+            notes_rx: None,
+        });
+        tab.mode = DiffMode::Branch;
+        tab.sync_managed_storage();
+        tab.refresh_diff()?;
+        Ok(tab)
+    }
+
+    /// The release notes the overview panel shows.
+    pub fn release_notes(&self) -> Option<&ReleaseNotes> {
+        self.release.as_ref()?.notes.as_ref()
+    }
+}
+
+impl App {
+    /// `t`: pick a tag range.
+    pub fn open_releases_hub(&mut self) {
+        if self.tab().is_remote() {
+            self.notify_warn("Tag ranges need a local clone");
+            return;
+        }
+        let tags = match crate::git::git_tags(&self.tab().repo_root, TAG_LIMIT) {
+            Ok(tags) => tags,
+            Err(e) => {
+                self.report_command_error("Listing tags failed", &e);
+                return;
+            }
+        };
+        if tags.is_empty() {
+            self.notify("No tags in this repository");
+            return;
+        }
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Releases,
+            title: None,
+            items: range_items(&tags),
+            selected: 1,
+        });
+    }
+
+    /// Open `from...to` in a new tab, or switch to the tab showing it.
+    pub fn open_release_range(&mut self, from: &str, to: &str) {
+        let repo_root = self.tab().repo_root.clone();
+        if let Some(idx) = self.tabs.iter().position(|t| {
+            t.repo_root == repo_root
+                && t.release
+                    .as_ref()
+                    .is_some_and(|r| r.from == from && r.to == to)
+        }) {
+            self.active_tab = idx;
+            self.notify(&format!("Switched to tab: {}", self.tab().tab_name()));
+            return;
+        }
+        match TabState::new_release_range(repo_root, from.to_string(), to.to_string()) {
+            Ok(mut tab) => {
+                if let Some(range) = tab.release.as_mut() {
+                    let (tx, rx) = mpsc::channel();
+                    let root = tab.repo_root.clone();
+                    let tag = range.notes_tag().to_string();
+                    std::thread::spawn(move || {
+                        let notes =
+                            crate::github::gh_release_notes(&root, &tag).unwrap_or_else(|e| {
+                                crate::debug_log::warn("github", format!("release notes: {:#}", e));
+                                None
+                            });
+                        let _ = tx.send(notes);
+                    });
+                    range.notes_rx = Some(rx);
+                }
+                self.open_tab(tab);
+            }
+            Err(e) => self.report_command_error(&format!("Opening {} → {} failed", from, to), &e),
+        }
+    }
+
+    /// Store the active tab's release notes once fetched, and show them
+    /// unless another panel is open. True when they arrived.
+    pub fn poll_release_notes(&mut self) -> bool {
+        let tab = self.tab_mut();
+        let Some(range) = tab.release.as_mut() else {
+            return false;
+        };
+        let Some(rx) = &range.notes_rx else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(notes) => {
+                range.notes_rx = None;
+                range.notes = notes;
+                if range.notes.is_none() {
+                    return false;
+                }
+                if tab.panel.is_none() {
+                    tab.panel = Some(PanelContent::PrOverview);
+                }
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                range.notes_rx = None;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::Tag;
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            name: name.to_string(),
+            date: "2024-06-01T10:00:00+02:00".to_string(),
+            subject: format!("Release {}", name),
+        }
+    }
+
+    #[test]
+    fn picker_opens_on_the_newest_tag_to_head() {
+        let items = range_items(&[tag("v1.4.0"), tag("v1.3.0"), tag("v1.2.0")]);
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "── Unreleased ──",
+                "v1.4.0 → HEAD",
+                "v1.3.0 → HEAD",
+                "v1.2.0 → HEAD",
+                "── Releases ──",
+                "v1.3.0 → v1.4.0",
+                "v1.2.0 → v1.3.0",
+            ]
+        );
+        // `t` + Enter: the first entry after the header
+        assert_eq!(
+            items[1].action,
+            HubAction::OpenReleaseRange {
+                from: "v1.4.0".into(),
+                to: "HEAD".into()
+            }
+        );
+        assert_eq!(items[5].hint, "2024-06-01");
+        assert_eq!(items[5].description, "Release v1.4.0");
+
+        // A single tag has no previous release
+        assert_eq!(range_items(&[tag("v0.1.0")]).len(), 2);
+    }
+
+    #[test]
+    fn notes_come_from_the_newer_tag() {
+        let range = |from: &str, to: &str| ReleaseRange {
+            from: from.into(),
+            to: to.into(),
+            notes: None,
+            notes_rx: None,
+        };
+        assert_eq!(range("v1.3.0", "v1.4.0").notes_tag(), "v1.4.0");
+        assert_eq!(range("v1.4.0", "HEAD").notes_tag(), "v1.4.0");
+        assert_eq!(range("v1.4.0", "HEAD").label(), "v1.4.0…HEAD");
+    }
+}
@@ -0,0 +1,67 @@
+//! Per-path review checklists (`[review_hints]`).
+//!
+//! Hints come from the global config and the repo's `.er-config.toml`. The
+//! file detail panel shows the selected file's, and AI review prompts get
+//! every changed file's as extra context.
+
+use super::App;
+use crate::config::review_hints_for;
+
+impl App {
+    /// Hints for `path`: global ones first, then the repo's, without repeats.
+    pub fn review_hints_for(&self, path: &str) -> Vec<String> {
+        let mut hints: Vec<String> = Vec::new();
+        let global = review_hints_for(&self.config.review_hints, path);
+        let repo = review_hints_for(&self.tab().repo_review_hints, path);
+        for hint in global.into_iter().chain(repo) {
+            if !hints.iter().any(|h| h == hint) {
+                hints.push(hint.to_string());
+            }
+        }
+        hints
+    }
+
+    /// Append the changed files' hints, and the review template's
+    /// instructions, to an AI prompt.
+    pub fn with_review_hints(&self, mut prompt: String) -> String {
+        let hints: Vec<(String, Vec<String>)> = self
+            .tab()
+            .files
+            .iter()
+            .map(|f| (f.path.clone(), self.review_hints_for(&f.path)))
+            .filter(|(_, hints)| !hints.is_empty())
+            .collect();
+        prompt.push_str(&crate::ai::prompts::review_hints_appendix(&hints));
+        self.with_review_template(prompt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn hints_merge_global_and_repo_and_reach_the_prompt() {
+        let mut app = App::new_for_test(vec![
+            modified_file("db/users.sql", 1),
+            modified_file("src/main.rs", 1),
+        ]);
+        app.config
+            .review_hints
+            .insert("*.sql".into(), vec!["Check index usage".into()]);
+        app.tab_mut().repo_review_hints.insert(
+            "db/**".into(),
+            vec!["Check index usage".into(), "Is it reversible?".into()],
+        );
+        assert_eq!(
+            app.review_hints_for("db/users.sql"),
+            ["Check index usage", "Is it reversible?"]
+        );
+        assert!(app.review_hints_for("src/main.rs").is_empty());
+
+        let prompt = app.with_review_hints("Review.".into());
+        assert!(prompt.contains("`db/users.sql`"));
+        assert!(!prompt.contains("src/main.rs"));
+    }
+}
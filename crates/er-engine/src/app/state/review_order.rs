@@ -0,0 +1,119 @@
+//! Guided review order (`[review_order]`): the repo names the phases a
+//! change reads best in — schema → domain → api → ui — and Ctrl+g sorts the
+//! tree into one section per phase. Focus mode follows the tree, so its
+//! queue walks the phases too.
+
+use super::file_sort::FileSort;
+use super::TabState;
+use crate::config::{self, ErConfig, ReviewPhase};
+
+/// The repo's own phases when its `.er-config.toml` has them, else the
+/// global ones.
+pub(super) fn resolve_phases(repo_root: &str, global: &ErConfig) -> Vec<ReviewPhase> {
+    let repo = config::load_repo_review_order(repo_root);
+    if repo.is_empty() {
+        global.review_order.phases.clone()
+    } else {
+        repo
+    }
+}
+
+impl TabState {
+    /// Position of the first phase matching `path`.
+    pub(super) fn review_phase_index(&self, path: &str) -> Option<usize> {
+        self.review_phases.iter().position(|phase| {
+            phase
+                .paths
+                .iter()
+                .any(|pattern| crate::git::compact_files_match(pattern, path))
+        })
+    }
+
+    pub fn review_phase(&self, path: &str) -> Option<&ReviewPhase> {
+        self.review_phase_index(path)
+            .map(|idx| &self.review_phases[idx])
+    }
+
+    /// Switch between the guided order and the diff's own. `None` when the
+    /// repo has no `[review_order]`.
+    pub fn toggle_guided_order(&mut self) -> Option<bool> {
+        if self.review_phases.is_empty() {
+            return None;
+        }
+        if self.file_sort == FileSort::Guided {
+            self.file_sort = FileSort::Diff;
+            let _ = self.refresh_diff();
+            Some(false)
+        } else {
+            self.file_sort = FileSort::Guided;
+            self.sort_files();
+            self.rebuild_hunk_offsets();
+            Some(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffFile, FileStatus};
+
+    fn file(path: &str) -> DiffFile {
+        DiffFile {
+            path: path.into(),
+            status: FileStatus::Modified,
+            hunks: Vec::new(),
+            adds: 1,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        }
+    }
+
+    #[test]
+    fn guided_order_sorts_files_into_repo_phases() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".er-config.toml"),
+            r#"
+            [[review_order.phases]]
+            name = "schema"
+            paths = ["*.sql", "migrations/**"]
+
+            [[review_order.phases]]
+            name = "api"
+            paths = ["src/api/**"]
+            "#,
+        )
+        .unwrap();
+        let mut tab = TabState::new_for_test(vec![
+            file("README.md"),
+            file("src/api/users.rs"),
+            file("migrations/002.sql"),
+        ]);
+        tab.review_phases = resolve_phases(&dir.path().to_string_lossy(), &ErConfig::default());
+        tab.reviewed
+            .insert("migrations/002.sql".into(), String::new());
+
+        assert_eq!(tab.toggle_guided_order(), Some(true));
+        let paths: Vec<&str> = tab.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["migrations/002.sql", "src/api/users.rs", "README.md"]
+        );
+        assert!(tab.groups_tree());
+        assert_eq!(tab.tree_group("README.md"), None);
+        let progress: Vec<(String, usize, usize)> = tab
+            .tree_group_progress()
+            .into_iter()
+            .map(|g| (g.name, g.reviewed, g.total))
+            .collect();
+        assert_eq!(
+            progress,
+            [("schema".to_string(), 1, 1), ("api".to_string(), 0, 1)]
+        );
+
+        tab.review_phases.clear();
+        assert_eq!(tab.toggle_guided_order(), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Reviewed progress weighted by changed lines, and an ETA from this
+//! session's pace (top bar).
+//!
+//! Pace is changed lines marked reviewed per minute of active time: the gap
+//! before each mark counts toward the time, capped at [`IDLE_CAP`] so a
+//! coffee break doesn't stretch the estimate.
+
+use std::time::{Duration, Instant};
+
+use super::TabState;
+use crate::git::DiffFile;
+
+/// Longest gap between marks counted as reviewing time.
+const IDLE_CAP: Duration = Duration::from_secs(10 * 60);
+
+/// Active time needed before an ETA means anything.
+const MIN_ACTIVE: Duration = Duration::from_secs(30);
+
+/// Line weight of a file: binary and mode-only changes still count as one.
+fn weight(file: &DiffFile) -> usize {
+    (file.adds + file.dels).max(1)
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewPace {
+    last_mark: Instant,
+    active: Duration,
+    lines: usize,
+}
+
+impl Default for ReviewPace {
+    fn default() -> Self {
+        Self::starting_at(Instant::now())
+    }
+}
+
+impl ReviewPace {
+    fn starting_at(now: Instant) -> Self {
+        Self {
+            last_mark: now,
+            active: Duration::ZERO,
+            lines: 0,
+        }
+    }
+
+    /// A file of `lines` changed lines was just marked reviewed.
+    fn record(&mut self, lines: usize, now: Instant) {
+        self.active += now.saturating_duration_since(self.last_mark).min(IDLE_CAP);
+        self.last_mark = now;
+        self.lines += lines;
+    }
+
+    /// Time to review `remaining` more lines at this session's pace.
+    fn eta(&self, remaining: usize) -> Option<Duration> {
+        if self.lines == 0 || self.active < MIN_ACTIVE {
+            return None;
+        }
+        Some(self.active.mul_f64(remaining as f64 / self.lines as f64))
+    }
+}
+
+/// Reviewed vs total changed lines in the active diff, with an ETA.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReviewProgress {
+    pub reviewed_lines: usize,
+    pub total_lines: usize,
+    pub eta: Option<Duration>,
+}
+
+impl ReviewProgress {
+    /// Reviewed share of the changed lines, 0.0–1.0.
+    pub fn fraction(&self) -> f64 {
+        if self.total_lines == 0 {
+            return 0.0;
+        }
+        self.reviewed_lines as f64 / self.total_lines as f64
+    }
+
+    /// `"~12m left"` (or `~1h 5m`, `<1m`), `None` until there's a pace or
+    /// once done.
+    pub fn eta_label(&self) -> Option<String> {
+        let eta = self.eta?;
+        if self.reviewed_lines >= self.total_lines {
+            return None;
+        }
+        let minutes = eta.as_secs().div_ceil(60);
+        Some(match minutes {
+            0 | 1 => "<1m left".to_string(),
+            m if m < 60 => format!("~{}m left", m),
+            m => format!("~{}h {}m left", m / 60, m % 60),
+        })
+    }
+}
+
+impl TabState {
+    pub fn review_progress(&self) -> ReviewProgress {
+        let (mut reviewed_lines, mut total_lines) = (0, 0);
+        for file in self.active_diff_files() {
+            if self.is_skipped(&file.path) {
+                continue;
+            }
+            total_lines += weight(file);
+            if self.reviewed.contains_key(&file.path) {
+                reviewed_lines += weight(file);
+            }
+        }
+        ReviewProgress {
+            reviewed_lines,
+            total_lines,
+            eta: self.review_pace.eta(total_lines - reviewed_lines),
+        }
+    }
+
+    /// Count `path`, just marked reviewed, toward the session's pace.
+    pub(super) fn record_review_pace(&mut self, path: &str) {
+        let lines = self
+            .active_diff_files()
+            .iter()
+            .find(|f| f.path == path)
+            .map_or(1, weight);
+        self.review_pace.record(lines, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eta_extrapolates_active_time_and_skips_idle_gaps() {
+        let start = Instant::now();
+        let mut pace = ReviewPace::starting_at(start);
+        assert_eq!(pace.eta(100), None);
+
+        // 100 lines in 2 minutes, then 50 more after an hour away
+        pace.record(100, start + Duration::from_secs(120));
+        pace.record(50, start + Duration::from_secs(120 + 3600));
+        assert_eq!(pace.active, Duration::from_secs(120) + IDLE_CAP);
+
+        let mut pace = ReviewPace::starting_at(start);
+        pace.record(100, start + Duration::from_secs(120));
+        let progress = ReviewProgress {
+            reviewed_lines: 100,
+            total_lines: 600,
+            eta: pace.eta(500),
+        };
+        assert_eq!(progress.eta, Some(Duration::from_secs(600)));
+        assert_eq!(progress.eta_label().as_deref(), Some("~10m left"));
+        assert!((progress.fraction() - 1.0 / 6.0).abs() < 1e-9);
+    }
+}
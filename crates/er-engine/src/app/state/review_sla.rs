@@ -0,0 +1,151 @@
+//! PR age against the review SLA (`[github] review_sla_hours`).
+//!
+//! The top bar colours the active PR's age, and the review dashboard lists
+//! every open tab's PR oldest first so the most overdue gets looked at next,
+//! then the follow-ups (see `followups`).
+//! With the SQLite review index on, each row also counts its unresolved
+//! comments.
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData};
+use crate::review_queue::{format_age, sla_status, SlaStatus};
+
+impl App {
+    /// SLA standing of tab `idx`'s PR; `None` when the SLA is off or the
+    /// tab has no PR loaded.
+    pub fn tab_sla(&self, idx: usize) -> Option<SlaStatus> {
+        let pr = self.tabs.get(idx)?.pr_data.as_ref()?;
+        sla_status(
+            &pr.created_at,
+            u64::from(self.config.github.review_sla_hours),
+            crate::outbox::unix_now() as i64,
+        )
+    }
+
+    /// Every tab with a PR, oldest first.
+    pub fn open_reviews_dashboard(&mut self) {
+        let now = crate::outbox::unix_now() as i64;
+        let mut rows: Vec<(Option<i64>, HubItem)> = Vec::new();
+        for (idx, tab) in self.tabs.iter().enumerate() {
+            let Some(pr) = &tab.pr_data else {
+                continue;
+            };
+            let opened = crate::review_queue::parse_github_updated_at(&pr.created_at);
+            let hint = match (self.tab_sla(idx), opened) {
+                (Some(sla), _) => sla.label(),
+                (None, Some(opened)) => {
+                    format!(
+                        "open {}",
+                        format_age(now.saturating_sub(opened).max(0) as u64)
+                    )
+                }
+                (None, None) => String::new(),
+            };
+            #[cfg(feature = "sqlite")]
+            let hint = match unresolved_in(tab) {
+                Some(n) if n > 0 && hint.is_empty() => format!("{} unresolved", n),
+                Some(n) if n > 0 => format!("{} · {} unresolved", hint, n),
+                _ => hint,
+            };
+            rows.push((
+                opened,
+                HubItem {
+                    label: format!("#{} {}", pr.number, pr.title),
+                    hint,
+                    description: tab.tab_name(),
+                    action: HubAction::SwitchTab(idx),
+                    is_header: false,
+                    enabled: true,
+                },
+            ));
+        }
+        let follow_ups = self.follow_up_items();
+        if rows.is_empty() && follow_ups.is_empty() {
+            self.notify("No tabs with an open PR, and no follow-ups");
+            return;
+        }
+        // Oldest first; PRs with no known age go last
+        rows.sort_by_key(|(opened, _)| opened.unwrap_or(i64::MAX));
+        let mut items: Vec<HubItem> = rows.into_iter().map(|(_, item)| item).collect();
+        let selected = if items.is_empty() { 1 } else { 0 };
+        let title =
+            (!follow_ups.is_empty()).then(|| "OPEN REVIEWS · d clear follow-up".to_string());
+        items.extend(follow_ups);
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Reviews,
+            title,
+            selected,
+            items,
+        });
+    }
+
+    pub fn switch_to_tab(&mut self, idx: usize) {
+        if idx < self.tabs.len() {
+            self.active_tab = idx;
+            let name = self.tab().tab_name();
+            self.notify(&format!("Tab: {}", name));
+        }
+    }
+}
+
+/// Unresolved comments in the tab's review, from the review index.
+#[cfg(feature = "sqlite")]
+fn unresolved_in(tab: &super::TabState) -> Option<usize> {
+    let er_dir = tab.er_dir();
+    let github_dir = tab.github_comments_dir();
+    let mut dirs = vec![er_dir.as_str()];
+    if github_dir != er_dir {
+        dirs.push(github_dir.as_str());
+    }
+    crate::review_db::with_db(|db| db.unresolved_count(&dirs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::PrOverviewData;
+
+    fn pr(number: u64, created_at: &str) -> PrOverviewData {
+        PrOverviewData {
+            number,
+            title: format!("PR {}", number),
+            body: String::new(),
+            state: "OPEN".into(),
+            author: "dev".into(),
+            url: String::new(),
+            base_branch: "main".into(),
+            head_branch: "feature".into(),
+            checks: Vec::new(),
+            reviewers: Vec::new(),
+            created_at: created_at.into(),
+            head_oid: String::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dashboard_lists_tabs_oldest_pr_first() {
+        let mut app = App::new_for_test(vec![]);
+        app.tabs.push(super::super::TabState::new_for_test(vec![]));
+        app.tabs.push(super::super::TabState::new_for_test(vec![]));
+        app.tabs[0].pr_data = Some(pr(3, ""));
+        app.tabs[1].pr_data = Some(pr(1, "2026-01-02T00:00:00Z"));
+        app.tabs[2].pr_data = Some(pr(2, "2026-01-01T00:00:00Z"));
+        app.open_reviews_dashboard();
+        let Some(OverlayData::ModalHub { kind, items, .. }) = &app.overlay else {
+            panic!("dashboard not open");
+        };
+        assert_eq!(*kind, HubKind::Reviews);
+        let order: Vec<&HubAction> = items.iter().map(|i| &i.action).collect();
+        assert_eq!(
+            order,
+            [
+                &HubAction::SwitchTab(2),
+                &HubAction::SwitchTab(1),
+                &HubAction::SwitchTab(0)
+            ]
+        );
+        assert!(app.tab_sla(1).is_none());
+        app.config.github.review_sla_hours = 24;
+        assert!(app.tab_sla(1).is_some());
+    }
+}
@@ -0,0 +1,182 @@
+//! Review templates per change type (`[[review_templates]]`): a migration,
+//! dependency bump or hotfix — told apart by branch name or PR label — brings
+//! its own filter, checklist template, required pre-push checks and AI
+//! instructions. The first matching template applies once per tab, as soon
+//! as the branch (or, for labels, the PR data) is known.
+
+use super::{App, TabState};
+use crate::ai;
+use crate::config::{self, ErConfig, ReviewTemplate};
+
+/// The repo's own templates when its `.er-config.toml` has them, else the
+/// global ones.
+pub(super) fn resolve_templates(repo_root: &str, global: &ErConfig) -> Vec<ReviewTemplate> {
+    let repo = config::load_repo_review_templates(repo_root);
+    if repo.is_empty() {
+        global.review_templates.clone()
+    } else {
+        repo
+    }
+}
+
+impl TabState {
+    /// The first template matching the PR's head branch (or the local one)
+    /// and labels.
+    pub fn matching_review_template(&self) -> Option<&ReviewTemplate> {
+        let pr = self.pr_data.as_ref();
+        let branch = pr
+            .map(|pr| pr.head_branch.as_str())
+            .filter(|b| !b.is_empty())
+            .unwrap_or(&self.current_branch);
+        let labels = pr.map(|pr| pr.labels.as_slice()).unwrap_or_default();
+        self.review_templates
+            .iter()
+            .find(|t| t.matches(branch, labels))
+    }
+}
+
+impl App {
+    /// Apply the matching template when it differs from the one applied.
+    /// Returns whether one was.
+    pub fn poll_review_template(&mut self) -> bool {
+        let tab = self.tab();
+        let Some(template) = tab.matching_review_template() else {
+            return false;
+        };
+        if tab
+            .review_template
+            .as_ref()
+            .is_some_and(|t| t.name == template.name)
+        {
+            return false;
+        }
+        let template = template.clone();
+        self.apply_review_template(template);
+        true
+    }
+
+    fn apply_review_template(&mut self, template: ReviewTemplate) {
+        let mut applied: Vec<String> = Vec::new();
+        if !template.filter.is_empty() && self.tab().filter_expr.is_empty() {
+            self.tab_mut().apply_filter_expr(&template.filter);
+            applied.push(format!("filter {}", template.filter));
+        }
+        let checklist_empty = self
+            .tab()
+            .ai
+            .checklist
+            .as_ref()
+            .is_none_or(|c| c.items.is_empty());
+        if !template.checklist.is_empty() && checklist_empty {
+            let found = ai::load_checklist_templates(&self.tab().repo_root)
+                .into_iter()
+                .find(|t| t.name == template.checklist);
+            match found {
+                Some(checklist) => {
+                    let added = self.checklist_mut().apply_template(&checklist);
+                    if self.save_checklist().is_ok() && added > 0 {
+                        applied.push(format!("{} checklist item(s)", added));
+                    }
+                }
+                None => applied.push(format!("checklist {} not found", template.checklist)),
+            }
+        }
+        if !template.checks.is_empty() {
+            applied.push(format!("{} required check(s)", template.checks.len()));
+        }
+        if !template.prompt.is_empty() {
+            applied.push("AI instructions".into());
+        }
+        let summary = if applied.is_empty() {
+            format!("Review template: {}", template.name)
+        } else {
+            format!(
+                "Review template: {} — {}",
+                template.name,
+                applied.join(", ")
+            )
+        };
+        self.tab_mut().review_template = Some(template);
+        self.notify(&summary);
+    }
+
+    /// `[push] checks`, then the active template's, without repeats.
+    pub fn required_push_checks(&self) -> Vec<String> {
+        let mut checks = self.config.push.checks.clone();
+        if let Some(template) = &self.tab().review_template {
+            for check in &template.checks {
+                if !checks.contains(check) {
+                    checks.push(check.clone());
+                }
+            }
+        }
+        checks
+    }
+
+    /// Append the active template's instructions to an AI prompt.
+    pub fn with_review_template(&self, mut prompt: String) -> String {
+        if let Some(template) = &self.tab().review_template {
+            prompt.push_str(&ai::prompts::review_template_appendix(
+                &template.name,
+                &template.prompt,
+            ));
+        }
+        prompt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_template_applies_once_with_its_checks() {
+        let mut app = App::new_for_test(Vec::new());
+        app.config.push.checks = vec!["test".into()];
+        let tab = app.tab_mut();
+        tab.current_branch = "deps/bump-serde".into();
+        tab.review_templates = vec![
+            ReviewTemplate {
+                name: "migration".into(),
+                labels: vec!["db".into()],
+                ..Default::default()
+            },
+            ReviewTemplate {
+                name: "deps".into(),
+                filter: "+Cargo.*".into(),
+                checks: vec!["test".into(), "audit".into()],
+                prompt: "Check changelogs for breaking changes.".into(),
+                ..Default::default()
+            },
+        ];
+        assert!(app.poll_review_template());
+        assert!(!app.poll_review_template());
+        assert_eq!(app.tab().filter_expr, "+Cargo.*");
+        assert_eq!(app.required_push_checks(), ["test", "audit"]);
+        assert!(app
+            .with_review_template("Review.".into())
+            .contains("Check changelogs"));
+
+        // The PR's labels can pick an earlier template once its data is in
+        app.tab_mut().pr_data = Some(crate::github::PrOverviewData {
+            number: 1,
+            title: String::new(),
+            body: String::new(),
+            state: "OPEN".into(),
+            author: String::new(),
+            url: String::new(),
+            base_branch: "main".into(),
+            head_branch: "deps/bump-serde".into(),
+            checks: vec![],
+            reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: vec!["DB".into()],
+        });
+        assert!(app.poll_review_template());
+        assert_eq!(
+            app.tab().review_template.as_ref().unwrap().name,
+            "migration"
+        );
+    }
+}
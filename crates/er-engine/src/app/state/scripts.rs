@@ -0,0 +1,191 @@
+//! Lua plugin actions and panels (see [`crate::scripting`]), listed in the
+//! Verify hub. Built without the `scripting` feature, the hub lists none.
+
+use anyhow::Result;
+
+use super::{App, HubItem};
+#[cfg(feature = "scripting")]
+use super::{HubAction, HubKind, OverlayData};
+
+impl App {
+    /// Verify hub rows for the loaded scripts' actions and panels.
+    pub(super) fn script_hub_items(&self) -> Vec<HubItem> {
+        #[cfg(feature = "scripting")]
+        {
+            let Some((actions, panels)) =
+                crate::scripting::with_host(|host| (host.actions(), host.panels()))
+            else {
+                return Vec::new();
+            };
+            if actions.is_empty() && panels.is_empty() {
+                return Vec::new();
+            }
+            let mut items = vec![HubItem {
+                label: "── Scripts ──".into(),
+                hint: "".into(),
+                description: "".into(),
+                action: HubAction::Noop,
+                is_header: true,
+                enabled: false,
+            }];
+            let rows = actions
+                .into_iter()
+                .map(|name| (name.clone(), HubAction::RunScriptAction(name), "action"))
+                .chain(
+                    panels
+                        .into_iter()
+                        .map(|name| (name.clone(), HubAction::OpenScriptPanel(name), "panel")),
+                );
+            for (label, action, kind) in rows {
+                items.push(HubItem {
+                    label,
+                    hint: "".into(),
+                    description: format!("Lua {}", kind),
+                    action,
+                    is_header: false,
+                    enabled: true,
+                });
+            }
+            items
+        }
+        #[cfg(not(feature = "scripting"))]
+        Vec::new()
+    }
+
+    /// Run script action `name` and apply what it returns.
+    pub fn run_script_action(&mut self, name: &str) -> Result<()> {
+        #[cfg(feature = "scripting")]
+        {
+            let state = self.script_state();
+            let result = crate::scripting::with_host(|host| host.run_action(name, &state))
+                .unwrap_or_else(|| Err(anyhow::anyhow!("the Lua runtime didn't start")));
+            match result {
+                Ok(result) => self.apply_script_result(result)?,
+                Err(e) => self.notify_error(&format!("Script failed: {:#}", e)),
+            }
+        }
+        #[cfg(not(feature = "scripting"))]
+        self.notify(&format!(
+            "{}: scripts need er built with the scripting feature",
+            name
+        ));
+        Ok(())
+    }
+
+    /// Show the lines of script panel `name`.
+    pub fn open_script_panel(&mut self, name: &str) {
+        #[cfg(feature = "scripting")]
+        {
+            let state = self.script_state();
+            let lines = crate::scripting::with_host(|host| host.panel_lines(name, &state))
+                .unwrap_or_else(|| Err(anyhow::anyhow!("the Lua runtime didn't start")));
+            let lines = match lines {
+                Ok(lines) => lines,
+                Err(e) => {
+                    self.notify_error(&format!("Script failed: {:#}", e));
+                    return;
+                }
+            };
+            let items = if lines.is_empty() {
+                vec![HubItem {
+                    label: "(nothing to show)".into(),
+                    hint: "".into(),
+                    description: "".into(),
+                    action: HubAction::Noop,
+                    is_header: true,
+                    enabled: false,
+                }]
+            } else {
+                lines
+                    .into_iter()
+                    .map(|line| HubItem {
+                        label: line,
+                        hint: "".into(),
+                        description: "".into(),
+                        action: HubAction::Noop,
+                        is_header: false,
+                        enabled: true,
+                    })
+                    .collect()
+            };
+            self.overlay = Some(OverlayData::ModalHub {
+                kind: HubKind::Script,
+                title: Some(name.to_uppercase()),
+                items,
+                selected: 0,
+            });
+        }
+        #[cfg(not(feature = "scripting"))]
+        self.notify(&format!(
+            "{}: scripts need er built with the scripting feature",
+            name
+        ));
+    }
+
+    #[cfg(feature = "scripting")]
+    fn script_state(&self) -> crate::scripting::ScriptState {
+        use crate::scripting::{ScriptFile, ScriptState, API_VERSION};
+        let tab = self.tab();
+        ScriptState {
+            api_version: API_VERSION,
+            repo_root: tab.repo_root.clone(),
+            branch: tab.event_context().branch,
+            base: tab.base_branch.clone(),
+            pr: tab.pr_number,
+            mode: tab.mode.git_mode().to_string(),
+            files: tab
+                .files
+                .iter()
+                .map(|f| ScriptFile::from_diff(f, tab.reviewed.contains_key(&f.path)))
+                .collect(),
+            selected: tab.selected_diff_file().map(|f| f.path.clone()),
+            line: tab
+                .cursor_head_lines()
+                .ok()
+                .flatten()
+                .map(|(start, _)| start),
+            filter: tab.filter_expr.clone(),
+        }
+    }
+
+    #[cfg(feature = "scripting")]
+    fn apply_script_result(&mut self, result: crate::scripting::ActionResult) -> Result<()> {
+        if let Some(expr) = result.filter {
+            self.tab_mut().apply_filter_expr(&expr);
+        }
+        if !result.reviewed.is_empty() {
+            let tab = self.tab_mut();
+            for path in &result.reviewed {
+                if tab.reviewed.contains_key(path) || !tab.files.iter().any(|f| &f.path == path) {
+                    continue;
+                }
+                let hash = tab
+                    .current_per_file_hashes
+                    .get(path)
+                    .cloned()
+                    .unwrap_or_default();
+                tab.reviewed.insert(path.clone(), hash);
+            }
+            tab.reviewed_revision += 1;
+            tab.save_reviewed_files()?;
+        }
+        if let Some(target) = result.select {
+            let (path, line) = match target.rsplit_once(':') {
+                Some((path, line)) if line.parse::<usize>().is_ok() => {
+                    (path.to_string(), line.parse().ok())
+                }
+                _ => (target, None),
+            };
+            if !self.tab_mut().focus_file_line(&path, line) {
+                self.notify_warn(&format!("{} is not in this diff", path));
+            }
+        }
+        if let Some(message) = result.notify {
+            self.notify(&message);
+        }
+        if let Some(panel) = result.panel {
+            self.open_script_panel(&panel);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+//! Handing a review over to another reviewer without a forge round-trip
+//! (see [`crate::session_bundle`]). Both actions live in the Git hub: export
+//! writes one bundle file to send along; import lists the bundles found in
+//! the repo root and `er_dir/handoff/` and merges the picked one.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData};
+use crate::ai::{store, ErGitHubComments, ErQuestions};
+use crate::session_bundle::{ImportSummary, SessionBundle, BUNDLE_SUFFIX};
+
+/// Bundles listed in the import hub.
+const MAX_LISTED_BUNDLES: usize = 20;
+
+impl App {
+    /// Git hub → Export session: write the reviewed set, questions, unsent
+    /// comments, checklist and filter to `er_dir/handoff/`.
+    pub fn export_session(&mut self) {
+        match self.write_session_bundle() {
+            Ok(path) => self.notify_long(&format!(
+                "Session exported to {} — send it to whoever takes over",
+                path.display()
+            )),
+            Err(e) => self.report_command_error("Exporting session failed", &e),
+        }
+    }
+
+    fn write_session_bundle(&self) -> Result<PathBuf> {
+        let identity = self.identity();
+        let tab = self.tab();
+        let er_dir = tab.er_dir();
+        let questions: Option<ErQuestions> = store::load(&er_dir).ok().flatten();
+        let comments: Option<ErGitHubComments> =
+            store::load(&tab.github_comments_dir()).ok().flatten();
+        let bundle = SessionBundle::new(
+            &identity.name,
+            &identity.slug(),
+            &tab.current_branch,
+            &tab.base_branch,
+            &tab.filter_expr,
+            &tab.reviewed,
+            questions.as_ref(),
+            comments.as_ref(),
+            tab.ai.checklist.as_ref(),
+        );
+        let dir = PathBuf::from(&er_dir).join("handoff");
+        std::fs::create_dir_all(&dir)?;
+        let branch = tab.current_branch.replace('/', "-");
+        let path = dir.join(format!("{}-{}{}", branch, identity.slug(), BUNDLE_SUFFIX));
+        std::fs::write(&path, serde_json::to_string_pretty(&bundle)?)?;
+        Ok(path)
+    }
+
+    /// Bundles in the repo root and `er_dir/handoff/`, newest first.
+    fn session_bundles(&self) -> Vec<PathBuf> {
+        let tab = self.tab();
+        let dirs = [
+            PathBuf::from(&tab.repo_root),
+            PathBuf::from(tab.er_dir()).join("handoff"),
+        ];
+        let mut bundles: Vec<(std::time::SystemTime, PathBuf)> = dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(BUNDLE_SUFFIX))
+            .map(|entry| {
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                (modified, entry.path())
+            })
+            .collect();
+        bundles.sort_by_key(|b| std::cmp::Reverse(b.0));
+        bundles
+            .into_iter()
+            .take(MAX_LISTED_BUNDLES)
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Git hub → Import session: pick a bundle to merge.
+    pub fn open_import_session(&mut self) {
+        let bundles = self.session_bundles();
+        if bundles.is_empty() {
+            self.notify(&format!(
+                "No *{} in the repo root or the handoff dir",
+                BUNDLE_SUFFIX
+            ));
+            return;
+        }
+        let me = self.identity().slug();
+        let items: Vec<HubItem> = bundles
+            .iter()
+            .map(|path| {
+                let bundle = std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| SessionBundle::parse(&content).ok());
+                let label = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let (description, enabled) = match bundle {
+                    Some(b) if b.reviewer_id == me => ("Your own export".to_string(), false),
+                    Some(b) => (
+                        format!(
+                            "{} · {} reviewed · {} questions · {} comments · {}",
+                            b.reviewer,
+                            b.reviewed.len(),
+                            b.questions.len(),
+                            b.comments.len(),
+                            b.branch
+                        ),
+                        true,
+                    ),
+                    None => ("Not a readable session bundle".to_string(), false),
+                };
+                HubItem {
+                    label,
+                    hint: "".into(),
+                    description,
+                    action: HubAction::ImportSession(path.to_string_lossy().into_owned()),
+                    is_header: false,
+                    enabled,
+                }
+            })
+            .collect();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Sessions,
+            title: None,
+            items,
+            selected: 0,
+        });
+    }
+
+    /// Merge the bundle at `path` into this tab's review state.
+    pub fn import_session(&mut self, path: &str) {
+        let bundle = match std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path))
+            .and_then(|content| SessionBundle::parse(&content))
+        {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                self.report_command_error("Importing session failed", &e);
+                return;
+            }
+        };
+        if bundle.reviewer_id == self.identity().slug() {
+            self.notify("That's your own session export");
+            return;
+        }
+        let branch_note = if bundle.branch.is_empty() || bundle.branch == self.tab().current_branch
+        {
+            String::new()
+        } else {
+            format!(" (exported on {})", bundle.branch)
+        };
+        match self.merge_session_bundle(&bundle) {
+            Ok(summary) => self.notify_long(&format!(
+                "Imported {}'s session{}: {}",
+                bundle.reviewer,
+                branch_note,
+                summary.label()
+            )),
+            Err(e) => self.report_command_error("Importing session failed", &e),
+        }
+    }
+
+    fn merge_session_bundle(&mut self, bundle: &SessionBundle) -> Result<ImportSummary> {
+        let me = self.identity().slug();
+        let mut summary = ImportSummary::default();
+        let tab = self.tab_mut();
+        let diff_hash = tab.branch_diff_hash.clone();
+        let er_dir = tab.er_dir();
+
+        summary.reviewed = bundle.merge_reviewed(&mut tab.reviewed);
+        if summary.reviewed > 0 {
+            tab.save_reviewed_files()?;
+        }
+
+        if !bundle.questions.is_empty() {
+            store::update(&er_dir, &diff_hash, |questions: &mut ErQuestions| {
+                summary.questions = bundle.merge_questions(questions, &me);
+            })?;
+        }
+        if !bundle.comments.is_empty() {
+            store::update(
+                &tab.github_comments_dir(),
+                &diff_hash,
+                |comments: &mut ErGitHubComments| {
+                    summary.comments = bundle.merge_comments(comments, &me);
+                },
+            )?;
+        }
+
+        if let Some(checklist) = tab.ai.checklist.as_mut() {
+            summary.checked = bundle.merge_checklist(checklist);
+        }
+        if summary.checked > 0 {
+            self.save_checklist()?;
+        }
+
+        let tab = self.tab_mut();
+        if tab.filter_expr.is_empty() && !bundle.filter.is_empty() {
+            tab.apply_filter_expr(&bundle.filter);
+            summary.filter_applied = true;
+        }
+        tab.reload_ai_state();
+        Ok(summary)
+    }
+}
@@ -0,0 +1,400 @@
+//! Startup guard for enormous diffs (`[large_files] guard_lines`): a vendored
+//! directory or a regenerated SDK can put a million changed lines in a diff.
+//! Before parsing one, the Huge Diff hub lists the top directories by changed
+//! lines; the ones picked are left out of the load and hidden with a seeded
+//! filter (`-vendor/*`). A filter that shows them again loads them again.
+//!
+//! Only front ends that can show the hub ask ([`enable`]); elsewhere the diff
+//! loads as it is.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+use super::{App, HubAction, HubItem, HubKind, InputMode, OverlayData, TabState};
+use crate::app::filter;
+use crate::git::{self, DiffFile, DiffFileHeader, FileStatus};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Smaller raw diffs aren't scanned.
+const MIN_BYTES: usize = 1 << 20;
+/// Directories listed in the hub.
+const MAX_DIRS: usize = 12;
+/// A directory with more than this share of the lines is listed by its
+/// subdirectories instead.
+const SPLIT_SHARE: f64 = 0.5;
+
+/// Ask about enormous diffs in this process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Changed lines and files under a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirStat {
+    pub dir: String,
+    pub files: usize,
+    pub lines: usize,
+}
+
+/// A diff's size, with its biggest directories first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSizeSummary {
+    pub dirs: Vec<DirStat>,
+    pub files: usize,
+    pub lines: usize,
+}
+
+/// A tab's guard.
+#[derive(Debug, Default)]
+pub struct SizeGuard {
+    /// Changed lines that make a diff ask first (0 = never)
+    pub guard_lines: usize,
+    /// The diff waiting for the hub's answer
+    pub pending: Option<DiffSizeSummary>,
+    /// Directories picked in the hub
+    pub excluded: BTreeSet<String>,
+    /// Left out of the load while the filter hides them
+    pub pruned: Vec<String>,
+    /// Asked once per tab
+    answered: bool,
+}
+
+impl SizeGuard {
+    pub fn new(guard_lines: usize) -> Self {
+        SizeGuard {
+            guard_lines,
+            ..Default::default()
+        }
+    }
+}
+
+fn under(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The first `depth` directories of `path`; `None` for a file above them.
+fn dir_at(path: &str, depth: usize) -> Option<String> {
+    let parts: Vec<&str> = path.split('/').collect();
+    (parts.len() > depth).then(|| parts[..depth].join("/"))
+}
+
+/// Group `headers` by top-level directory, a dominant one by its
+/// subdirectories. Files at the top of the repo aren't listed.
+pub fn summarize(headers: &[DiffFileHeader]) -> DiffSizeSummary {
+    let lines: usize = headers.iter().map(|h| h.adds + h.dels).sum();
+    let group = |depth: usize, within: Option<&str>| {
+        let mut dirs: BTreeMap<String, DirStat> = BTreeMap::new();
+        for h in headers {
+            if within.is_some_and(|w| !under(&h.path, w)) {
+                continue;
+            }
+            let dir = dir_at(&h.path, depth).or_else(|| within.map(str::to_string));
+            if let Some(dir) = dir {
+                let stat = dirs.entry(dir.clone()).or_insert(DirStat {
+                    dir,
+                    files: 0,
+                    lines: 0,
+                });
+                stat.files += 1;
+                stat.lines += h.adds + h.dels;
+            }
+        }
+        dirs
+    };
+    let mut dirs = Vec::new();
+    for (dir, stat) in group(1, None) {
+        if stat.lines as f64 > lines as f64 * SPLIT_SHARE {
+            dirs.extend(group(2, Some(&dir)).into_values());
+        } else {
+            dirs.push(stat);
+        }
+    }
+    dirs.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.dir.cmp(&b.dir)));
+    dirs.truncate(MAX_DIRS);
+    DiffSizeSummary {
+        dirs,
+        files: headers.len(),
+        lines,
+    }
+}
+
+/// `raw` without the sections of files under `dirs`.
+pub fn prune(raw: &str, headers: &[DiffFileHeader], dirs: &[String]) -> String {
+    headers
+        .iter()
+        .filter(|h| !dirs.iter().any(|d| under(&h.path, d)))
+        .filter_map(|h| raw.get(h.byte_offset..h.byte_offset + h.byte_length))
+        .collect()
+}
+
+/// The filter that hides `dirs`.
+pub fn filter_expr(dirs: &[String]) -> String {
+    dirs.iter()
+        .map(|d| format!("-{}/*", d))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl TabState {
+    /// Whether the filter hides `dir`.
+    fn filter_hides(&self, dir: &str) -> bool {
+        let probe = DiffFile {
+            path: format!("{}/-", dir),
+            status: FileStatus::Modified,
+            hunks: Vec::new(),
+            adds: 0,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 0,
+        };
+        !filter::apply_filter(&self.filter_rules, &probe)
+    }
+
+    /// Whether the filter shows a directory left out of the load, so the
+    /// diff has to be loaded again.
+    pub(super) fn shows_pruned_dir(&self) -> bool {
+        self.size_guard.pruned.iter().any(|d| !self.filter_hides(d))
+    }
+
+    /// The raw diff to parse: `raw` without the directories left out, or
+    /// `None` when it's big enough to ask about first.
+    pub(super) fn guard_diff_size(&mut self, raw: String) -> Option<String> {
+        let pruned: Vec<String> = self
+            .size_guard
+            .pruned
+            .iter()
+            .filter(|d| self.filter_hides(d))
+            .cloned()
+            .collect();
+        self.size_guard.pruned = pruned;
+        if !self.size_guard.pruned.is_empty() {
+            let headers = git::parse_diff_headers(&raw);
+            return Some(prune(&raw, &headers, &self.size_guard.pruned));
+        }
+        let guard = &self.size_guard;
+        if guard.answered
+            || guard.guard_lines == 0
+            || !ENABLED.load(Ordering::Relaxed)
+            || raw.len() < MIN_BYTES
+            || !self.filter_expr.is_empty()
+        {
+            return Some(raw);
+        }
+        let summary = summarize(&git::parse_diff_headers(&raw));
+        if summary.lines <= guard.guard_lines || summary.dirs.is_empty() {
+            return Some(raw);
+        }
+        self.size_guard.pending = Some(summary);
+        None
+    }
+}
+
+impl App {
+    /// Ask about the active tab's enormous diff. True when the hub opened.
+    pub fn poll_size_guard(&mut self) -> bool {
+        if self.tab().size_guard.pending.is_none()
+            || self.overlay.is_some()
+            || self.input_mode != InputMode::Normal
+        {
+            return false;
+        }
+        self.open_size_guard_hub(0);
+        true
+    }
+
+    fn open_size_guard_hub(&mut self, selected: usize) {
+        let guard = &self.tab().size_guard;
+        let Some(summary) = &guard.pending else {
+            return;
+        };
+        let mut items: Vec<HubItem> = summary
+            .dirs
+            .iter()
+            .map(|stat| {
+                let excluded = guard.excluded.contains(&stat.dir);
+                HubItem {
+                    label: format!("[{}] {}/", if excluded { "x" } else { " " }, stat.dir),
+                    hint: format!("{} lines", stat.lines),
+                    description: format!(
+                        "{} files · {}% of the diff",
+                        stat.files,
+                        stat.lines * 100 / summary.lines.max(1)
+                    ),
+                    action: HubAction::ToggleGuardDir(stat.dir.clone()),
+                    is_header: false,
+                    enabled: true,
+                }
+            })
+            .collect();
+        let left_out: usize = summary
+            .dirs
+            .iter()
+            .filter(|s| guard.excluded.contains(&s.dir))
+            .map(|s| s.lines)
+            .sum();
+        items.push(HubItem {
+            label: if guard.excluded.is_empty() {
+                "Load everything".into()
+            } else {
+                format!("Load without {} directories", guard.excluded.len())
+            },
+            hint: String::new(),
+            description: format!(
+                "{} of {} changed lines",
+                summary.lines - left_out,
+                summary.lines
+            ),
+            action: HubAction::LoadGuardedDiff,
+            is_header: false,
+            enabled: true,
+        });
+        let title = format!(
+            "HUGE DIFF · {} lines in {} files · ⏎/Space leave a directory out",
+            summary.lines, summary.files
+        );
+        let count = items.len();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::SizeGuard,
+            title: Some(title),
+            items,
+            selected: selected.min(count - 1),
+        });
+    }
+
+    /// Pick `dir` to leave out, or put it back.
+    pub fn toggle_guard_dir(&mut self, dir: &str) {
+        let selected = match &self.overlay {
+            Some(OverlayData::ModalHub { selected, .. }) => *selected,
+            _ => self
+                .tab()
+                .size_guard
+                .pending
+                .as_ref()
+                .and_then(|s| s.dirs.iter().position(|d| d.dir == dir))
+                .unwrap_or(0),
+        };
+        let excluded = &mut self.tab_mut().size_guard.excluded;
+        if !excluded.remove(dir) {
+            excluded.insert(dir.to_string());
+        }
+        self.open_size_guard_hub(selected);
+    }
+
+    /// Space in the Huge Diff hub.
+    pub fn toggle_selected_guard_dir(&mut self) {
+        let Some(OverlayData::ModalHub {
+            kind: HubKind::SizeGuard,
+            items,
+            selected,
+            ..
+        }) = &self.overlay
+        else {
+            return;
+        };
+        if let Some(HubAction::ToggleGuardDir(dir)) = items.get(*selected).map(|i| &i.action) {
+            let dir = dir.clone();
+            self.toggle_guard_dir(&dir);
+        }
+    }
+
+    /// Load the waiting diff, without the picked directories when
+    /// `exclude`, hiding them with a filter.
+    pub fn load_guarded_diff(&mut self, exclude: bool) -> Result<()> {
+        let tab = self.tab_mut();
+        let Some(summary) = tab.size_guard.pending.take() else {
+            return Ok(());
+        };
+        tab.size_guard.answered = true;
+        let excluded = std::mem::take(&mut tab.size_guard.excluded);
+        let dirs: Vec<String> = if exclude {
+            excluded.into_iter().collect()
+        } else {
+            Vec::new()
+        };
+        if !dirs.is_empty() {
+            tab.size_guard.pruned = dirs.clone();
+            tab.apply_filter_expr(&filter_expr(&dirs));
+        }
+        tab.refresh_diff()?;
+        if !dirs.is_empty() {
+            let lines: usize = summary
+                .dirs
+                .iter()
+                .filter(|s| dirs.contains(&s.dir))
+                .map(|s| s.lines)
+                .sum();
+            self.notify(&format!(
+                "Left out {} director{} ({} lines) — change the filter to load them",
+                dirs.len(),
+                if dirs.len() == 1 { "y" } else { "ies" },
+                lines
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(path: &str, lines: usize) -> String {
+        let adds: String = (0..lines).map(|i| format!("+{}\n", i)).collect();
+        format!(
+            "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -0,0 +1,{} @@\n{}",
+            lines,
+            adds,
+            p = path
+        )
+    }
+
+    #[test]
+    fn biggest_directories_first_and_left_out_while_hidden() {
+        let raw = [
+            section("vendor/github.com/a/lib.go", 60),
+            section("vendor/golang.org/x/net.go", 30),
+            section("src/main.go", 8),
+            section("docs/guide.md", 2),
+            section("go.mod", 1),
+        ]
+        .concat();
+        let headers = git::parse_diff_headers(&raw);
+        let summary = summarize(&headers);
+        assert_eq!((summary.files, summary.lines), (5, 101));
+        // vendor/ holds most of the lines, so it's listed by subdirectory
+        let dirs: Vec<&str> = summary.dirs.iter().map(|d| d.dir.as_str()).collect();
+        assert_eq!(
+            dirs,
+            ["vendor/github.com", "vendor/golang.org", "src", "docs"]
+        );
+
+        let vendor = vec!["vendor/github.com".to_string()];
+        assert_eq!(filter_expr(&vendor), "-vendor/github.com/*");
+        let kept = git::parse_diff(&prune(&raw, &headers, &vendor));
+        assert_eq!(kept.len(), 4);
+        assert!(kept
+            .iter()
+            .all(|f| !f.path.starts_with("vendor/github.com/")));
+
+        let mut tab = TabState::new_for_test(Vec::new());
+        tab.size_guard.pruned = vendor.clone();
+        tab.apply_filter_expr(&filter_expr(&vendor));
+        assert!(!tab.shows_pruned_dir());
+        let parsed = git::parse_diff(&tab.guard_diff_size(raw.clone()).unwrap());
+        assert_eq!(parsed.len(), 4);
+
+        // Once the filter shows them, the whole diff loads
+        tab.filter_expr.clear();
+        tab.filter_rules.clear();
+        assert!(tab.shows_pruned_dir());
+        assert_eq!(
+            tab.guard_diff_size(raw.clone()).as_deref(),
+            Some(raw.as_str())
+        );
+        assert!(tab.size_guard.pruned.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+//! Files deliberately left unreviewed (`S`): vendored code, snapshots,
+//! generated fixtures. A skip carries a reason, is kept in its own sidecar
+//! next to `reviewed`, drops the file out of the reviewed count and progress,
+//! and is listed in the hand-off export.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::{App, InputMode, TabState};
+
+/// Reason recorded when the prompt is left empty.
+const NO_REASON: &str = "no reason given";
+
+/// `path\treason` lines.
+fn parse_skipped(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| match l.split_once('\t') {
+            Some((path, reason)) => (path.to_string(), reason.to_string()),
+            None => (l.to_string(), NO_REASON.to_string()),
+        })
+        .collect()
+}
+
+impl TabState {
+    pub fn is_skipped(&self, path: &str) -> bool {
+        self.skipped.contains_key(path)
+    }
+
+    /// Neither reviewed nor skipped.
+    pub fn needs_review(&self, path: &str) -> bool {
+        !self.reviewed.contains_key(path) && !self.skipped.contains_key(path)
+    }
+
+    pub(super) fn load_skipped(&mut self) {
+        self.skipped = std::fs::read_to_string(self.er_root.skipped_path())
+            .map(|content| parse_skipped(&content))
+            .unwrap_or_default();
+    }
+
+    fn save_skipped(&self) -> Result<()> {
+        if self.is_remote() && self.review_bucket() != super::ReviewBucket::Pr {
+            return Ok(());
+        }
+        let path = self.er_root.skipped_path();
+        if self.skipped.is_empty() {
+            let _ = std::fs::remove_file(&path);
+            return Ok(());
+        }
+        if let Some(dir) = std::path::Path::new(&path).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut entries: Vec<(&String, &String)> = self.skipped.iter().collect();
+        entries.sort_by_key(|(p, _)| p.as_str());
+        let content = entries
+            .iter()
+            .map(|(p, reason)| format!("{}\t{}", p, reason))
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    /// Skip `path` with `reason`, dropping a reviewed mark it had.
+    pub fn skip_file(&mut self, path: &str, reason: &str) -> Result<()> {
+        let reason = reason.trim().replace('\t', " ");
+        let reason = if reason.is_empty() {
+            NO_REASON.to_string()
+        } else {
+            reason
+        };
+        self.skipped.insert(path.to_string(), reason);
+        if self.reviewed.remove(path).is_some() {
+            self.reviewed_revision += 1;
+            self.save_reviewed_files()?;
+        }
+        self.save_skipped()
+    }
+
+    /// Drop the skip mark; true if there was one.
+    pub fn unskip_file(&mut self, path: &str) -> Result<bool> {
+        if self.skipped.remove(path).is_none() {
+            return Ok(false);
+        }
+        self.save_skipped()?;
+        Ok(true)
+    }
+}
+
+impl App {
+    /// `S`: unskip the selected file, or ask why it's being skipped.
+    pub fn start_skip_file(&mut self) -> Result<()> {
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            return Ok(());
+        };
+        if self.tab_mut().unskip_file(&path)? {
+            self.notify(&format!("No longer skipped: {}", path));
+            return Ok(());
+        }
+        self.skip_reason_input.clear();
+        self.input_mode = InputMode::SkipReason;
+        Ok(())
+    }
+
+    pub fn submit_skip_reason(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        let reason = std::mem::take(&mut self.skip_reason_input);
+        let Some(path) = self.tab().selected_diff_file().map(|f| f.path.clone()) else {
+            return Ok(());
+        };
+        self.tab_mut().skip_file(&path, &reason)?;
+        let reason = self.tab().skipped[&path].clone();
+        self.notify(&format!("Skipped: {} ({})", path, reason));
+        Ok(())
+    }
+
+    pub fn cancel_skip_reason(&mut self) {
+        self.skip_reason_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn skipped_files_leave_the_count_and_survive_a_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(vec![]);
+        let tab = app.tab_mut();
+        tab.er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        tab.files = vec![
+            modified_file("src/lib.rs", 1),
+            modified_file("vendor/dep.rs", 1),
+        ];
+        tab.selected_file = 1;
+        tab.reviewed.insert("vendor/dep.rs".into(), String::new());
+
+        app.start_skip_file().unwrap();
+        assert_eq!(app.input_mode, InputMode::SkipReason);
+        app.skip_reason_input = "vendored\tcode".into();
+        app.submit_skip_reason().unwrap();
+
+        let tab = app.tab_mut();
+        assert!(!tab.reviewed.contains_key("vendor/dep.rs"));
+        assert_eq!(tab.reviewed_count(), (0, 1));
+        tab.skipped.clear();
+        tab.load_skipped();
+        assert_eq!(tab.skipped["vendor/dep.rs"], "vendored code");
+
+        app.start_skip_file().unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.tab().skipped.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+//! Spell checking in the review (see [`crate::spell`]): the Spelling hub
+//! lists typos on the diff's added doc comments and prose files, and the
+//! push prompt lists the ones in your own comments before they reach GitHub.
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData};
+use crate::config;
+use crate::spell::SpellChecker;
+
+/// Typos listed in a confirm prompt before it's cut off.
+const MAX_PROMPT_TYPOS: usize = 8;
+
+impl App {
+    /// The checker for the active repo, with its `[spell] words`.
+    pub fn spell_checker(&self) -> Option<SpellChecker> {
+        let repo_words = config::load_repo_spell_words(&self.tab().repo_root);
+        SpellChecker::detect(&self.config.spell, &repo_words)
+    }
+
+    /// Git hub → Spelling: typos on added prose lines; pick one to jump to it.
+    pub fn open_spelling(&mut self) {
+        if !self.config.spell.enabled {
+            self.notify("Spell checking is off — set [spell] enabled = true");
+            return;
+        }
+        let Some(checker) = self.spell_checker() else {
+            self.notify(
+                "No spell checker found — install hunspell or aspell, or set [spell] command",
+            );
+            return;
+        };
+        let typos = checker.diff_typos(&self.tab().files);
+        if typos.is_empty() {
+            self.notify(&format!(
+                "No typos in added doc comments or prose ({})",
+                checker.name()
+            ));
+            return;
+        }
+        let items: Vec<HubItem> = typos
+            .iter()
+            .map(|typo| HubItem {
+                label: typo.word.clone(),
+                hint: "".into(),
+                description: match typo.line {
+                    Some(line) => format!("{}:{}", typo.path, line),
+                    None => typo.path.clone(),
+                },
+                action: HubAction::FocusFileLine {
+                    path: typo.path.clone(),
+                    line: typo.line,
+                },
+                is_header: false,
+                enabled: true,
+            })
+            .collect();
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Spelling,
+            title: Some(format!(
+                "SPELLING · {} possible typo{} · add words to [spell] words",
+                items.len(),
+                if items.len() == 1 { "" } else { "s" }
+            )),
+            items,
+            selected: 0,
+        });
+    }
+
+    /// Possible typos in the comments and review notes about to be pushed,
+    /// as one prompt line. `None` when there are none or no checker is set up.
+    pub fn pending_comment_typos(&self) -> Option<String> {
+        let ai = &self.tab().ai;
+        let mut text: Vec<String> = ai
+            .github_comments
+            .as_ref()
+            .map(|gc| {
+                gc.comments
+                    .iter()
+                    .filter(|c| !c.synced && c.source != "github")
+                    .map(|c| c.comment.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (body, _) = ai.review_body();
+        text.push(body);
+        let text = text.join("\n");
+        if text.trim().is_empty() {
+            return None;
+        }
+        let typos = self.spell_checker()?.check(&text);
+        if typos.is_empty() {
+            return None;
+        }
+        let mut line = typos
+            .iter()
+            .take(MAX_PROMPT_TYPOS)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if typos.len() > MAX_PROMPT_TYPOS {
+            line.push_str(&format!(", +{}", typos.len() - MAX_PROMPT_TYPOS));
+        }
+        Some(format!("Possible typos: {}", line))
+    }
+}
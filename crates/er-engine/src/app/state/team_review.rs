@@ -0,0 +1,173 @@
+//! Publishing and pulling review state through `refs/er/reviews/<branch>`
+//! (see [`crate::shared_review`]).
+//!
+//! Opt-in with `[features] shared_review = true`; both actions live in the
+//! Git hub. Pulled teammates land in `er_dir/shared/<reviewer>/`, where the
+//! AI loader picks up their questions (read-only, attributed to them).
+
+use std::path::PathBuf;
+
+use super::App;
+use crate::identity::Identity;
+use crate::shared_review::{self, SharedReviewer, SHARED_FILES};
+
+/// Remote the shared ref is published to.
+pub const SHARED_REVIEW_REMOTE: &str = "origin";
+
+impl App {
+    /// The local reviewer, from `[identity]` or the active repo's git config.
+    pub fn identity(&self) -> Identity {
+        Identity::resolve(&self.config.identity, &self.tab().repo_root)
+    }
+
+    /// Directory-safe id for the local reviewer.
+    pub fn shared_reviewer_id(&self) -> String {
+        self.identity().slug()
+    }
+
+    fn shared_review_available(&mut self) -> bool {
+        if !self.config.features.shared_review {
+            self.notify("Shared review is off — set [features] shared_review = true");
+            return false;
+        }
+        if self.tab().is_remote() {
+            self.notify("Shared review needs a local clone");
+            return false;
+        }
+        true
+    }
+
+    /// Push this reviewer's questions, comments, checklist and reviewed set.
+    pub fn publish_shared_review(&mut self) {
+        if self.blocked_by_read_only("publishing the shared review")
+            || !self.shared_review_available()
+        {
+            return;
+        }
+        let identity = self.identity();
+        let reviewer = identity.slug();
+        let tab = self.tab();
+        let er_dir = PathBuf::from(tab.er_dir());
+        // Comment files go out with "You" replaced by our name, staged in a
+        // scratch directory so the local copies keep their ownership marker
+        let staging = std::env::temp_dir().join(format!("er-shared-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&staging);
+        let files: Vec<(&str, PathBuf)> = SHARED_FILES
+            .iter()
+            .map(|name| {
+                let path = match *name {
+                    "reviewed" => PathBuf::from(tab.er_root.reviewed_path()),
+                    "questions.json" | "github-comments.json" => {
+                        let source = er_dir.join(name);
+                        let staged = staging.join(name);
+                        let _ = std::fs::remove_file(&staged);
+                        if let Ok(contents) = std::fs::read_to_string(&source) {
+                            let _ = std::fs::write(&staged, identity.attribute_json(&contents));
+                        }
+                        staged
+                    }
+                    _ => er_dir.join(name),
+                };
+                (*name, path)
+            })
+            .collect();
+        let branch = tab.current_branch.clone();
+        let result = shared_review::publish(
+            &tab.repo_root,
+            SHARED_REVIEW_REMOTE,
+            &branch,
+            &reviewer,
+            &files,
+        );
+        let _ = std::fs::remove_dir_all(&staging);
+        match result {
+            Ok(true) => self.notify(&format!(
+                "Published review state as {} to {}",
+                reviewer,
+                shared_review::ref_name(&branch)
+            )),
+            Ok(false) => self.notify("Shared review already up to date"),
+            Err(e) => self.report_command_error("Publishing shared review failed", &e),
+        }
+    }
+
+    /// Fetch teammates' review state and show their questions.
+    pub fn pull_shared_review(&mut self) {
+        if !self.shared_review_available() {
+            return;
+        }
+        let tab = self.tab();
+        let (root, branch) = (tab.repo_root.clone(), tab.current_branch.clone());
+        let reviewers = match shared_review::pull(&root, SHARED_REVIEW_REMOTE, &branch) {
+            Ok(r) => r,
+            Err(e) => {
+                self.report_command_error("Pulling shared review failed", &e);
+                return;
+            }
+        };
+        let me = self.shared_reviewer_id();
+        let others: Vec<SharedReviewer> = reviewers.into_iter().filter(|r| r.id != me).collect();
+        let shared_dir = PathBuf::from(self.tab().er_dir()).join("shared");
+        let _ = std::fs::remove_dir_all(&shared_dir);
+        for reviewer in &others {
+            let dir = shared_dir.join(&reviewer.id);
+            if std::fs::create_dir_all(&dir).is_err() {
+                continue;
+            }
+            for (name, contents) in &reviewer.files {
+                let _ = std::fs::write(dir.join(name), contents);
+            }
+        }
+        self.tab_mut().reload_ai_state();
+
+        if others.is_empty() {
+            self.notify("No teammates have shared review state for this branch yet");
+            return;
+        }
+        let total = self.tab().files.len();
+        let summary: Vec<String> = others.iter().map(|r| shared_progress(r, total)).collect();
+        self.notify_long(&format!("Shared review: {}", summary.join("; ")));
+    }
+}
+
+/// `alice 2 questions · 5/12 reviewed`
+fn shared_progress(reviewer: &SharedReviewer, total_files: usize) -> String {
+    let questions = reviewer
+        .file("questions.json")
+        .and_then(|c| serde_json::from_str::<crate::ai::ErQuestions>(c).ok())
+        .map_or(0, |q| q.questions.len());
+    let reviewed = reviewer
+        .file("reviewed")
+        .map_or(0, |c| c.lines().filter(|l| !l.trim().is_empty()).count());
+    format!(
+        "{} {} question{} · {}/{} reviewed",
+        reviewer.id,
+        questions,
+        if questions == 1 { "" } else { "s" },
+        reviewed,
+        total_files
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_counts_questions_and_reviewed_lines() {
+        let reviewer = SharedReviewer {
+            id: "alice".into(),
+            files: vec![
+                (
+                    "questions.json".into(),
+                    r#"{"version":1,"diff_hash":"h","questions":[{"id":"q-1","file":"a.rs","hunk_index":0,"line_start":1,"text":"?"}]}"#.into(),
+                ),
+                ("reviewed".into(), "a.rs\th\nb.rs\th\n".into()),
+            ],
+        };
+        assert_eq!(
+            shared_progress(&reviewer, 4),
+            "alice 1 question · 2/4 reviewed"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Source ↔ test awareness: the file tree's tests-changed badge, the
+//! `untested:true` filter and jumping between a file and its tests.
+
+use super::{App, TabState};
+use crate::app::filter::FilterRule;
+use crate::git::{self, TestPairing};
+
+impl TabState {
+    /// Pair the diff's sources with the tests changed alongside them.
+    pub fn test_pairing(&self) -> TestPairing {
+        git::pair_tests(
+            self.active_diff_files().iter().map(|f| f.path.as_str()),
+            &self.test_patterns,
+        )
+    }
+
+    /// The pairing, only when an `untested:` filter needs it.
+    pub(super) fn filter_pairing(&self) -> Option<TestPairing> {
+        self.filter_rules
+            .iter()
+            .any(|r| matches!(r, FilterRule::Untested { .. }))
+            .then(|| self.test_pairing())
+    }
+}
+
+impl App {
+    /// Jump from a source file to its test, or from a test to its source.
+    /// Files in the diff win; otherwise say which file would be the pair.
+    pub fn jump_to_test_pair(&mut self) {
+        let tab = self.tab();
+        let Some(path) = tab.selected_diff_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        let is_test = git::classify_path(&path) == git::FileKind::Test;
+        let candidates = if is_test {
+            git::source_candidates(&path, &tab.test_patterns)
+        } else {
+            git::test_candidates(&path, &tab.test_patterns)
+        };
+        let in_diff = candidates
+            .iter()
+            .find(|c| tab.files.iter().any(|f| &f.path == *c))
+            .cloned();
+        let on_disk = || {
+            candidates
+                .iter()
+                .find(|c| std::path::Path::new(&tab.repo_root).join(c).is_file())
+                .cloned()
+        };
+        let what = if is_test { "source" } else { "test" };
+        match (in_diff, on_disk()) {
+            (Some(target), _) => {
+                self.tab_mut().focus_file_line(&target, None);
+                self.notify(&format!("Jumped to {}: {}", what, target));
+            }
+            (None, Some(existing)) => {
+                self.notify(&format!("{} is unchanged in this diff", existing));
+            }
+            (None, None) => self.notify(&format!("No {} found for {}", what, path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn jump_goes_between_a_source_and_its_test_and_back() {
+        let mut app = App::new_for_test(vec![
+            modified_file("pkg/server.go", 1),
+            modified_file("pkg/client.go", 1),
+            modified_file("pkg/server_test.go", 1),
+        ]);
+        app.jump_to_test_pair();
+        assert_eq!(app.tab().selected_file, 2);
+        app.jump_to_test_pair();
+        assert_eq!(app.tab().selected_file, 0);
+
+        app.tab_mut().selected_file = 1;
+        app.jump_to_test_pair();
+        assert_eq!(app.tab().selected_file, 1);
+        assert!(app.tab().test_pairing().untested.contains("pkg/client.go"));
+    }
+}
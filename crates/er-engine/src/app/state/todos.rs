@@ -0,0 +1,129 @@
+//! TODO/FIXME/HACK markers the diff adds (see [`git::scan_todos`]): listed
+//! in the TODOs hub with jump-to, counted in the top bar and exported with
+//! the hand-off. With `[todos] ticket_pattern`, the review only reads as
+//! ready once every new marker references a ticket.
+
+use super::{App, HubAction, HubItem, HubKind, OverlayData, TabState};
+use crate::config::{self, ErConfig};
+use crate::git;
+
+/// The repo's own `[todos]` when its `.er-config.toml` has one, else the
+/// global one.
+pub(super) fn resolve_scanner(repo_root: &str, global: &ErConfig) -> git::TodoScanner {
+    config::load_repo_todos(repo_root)
+        .unwrap_or_else(|| global.todos.clone())
+        .scanner()
+}
+
+impl TabState {
+    pub(super) fn refresh_todos(&mut self) {
+        self.todos = git::scan_todos(&self.files, &self.todo_scanner);
+    }
+
+    /// New markers missing the required ticket reference.
+    pub fn untracked_todos(&self) -> usize {
+        self.todos
+            .iter()
+            .filter(|t| t.needs_ticket(&self.todo_scanner))
+            .count()
+    }
+
+    /// Every file reviewed, no new TODO missing its ticket, every
+    /// migration's rollback verified and no confirmed high-severity finding
+    /// left open that an `er:ignore` note doesn't suppress.
+    pub fn review_ready(&self) -> bool {
+        let (reviewed, total) = self.reviewed_count();
+        total > 0
+            && reviewed == total
+            && self.untracked_todos() == 0
+            && self.pending_rollbacks() == 0
+            && self.ai.blocking_findings(&self.files) == 0
+    }
+}
+
+impl App {
+    /// Git hub → TODOs: every new marker; pick one to jump to it.
+    pub fn open_todos(&mut self) {
+        let tab = self.tab();
+        if tab.todos.is_empty() {
+            self.notify("No TODO/FIXME/HACK markers on added lines");
+            return;
+        }
+        let untracked = tab.untracked_todos();
+        let items: Vec<HubItem> = tab
+            .todos
+            .iter()
+            .map(|todo| {
+                let location = match todo.line {
+                    Some(line) => format!("{}:{}", todo.path, line),
+                    None => todo.path.clone(),
+                };
+                let description = if todo.needs_ticket(&tab.todo_scanner) {
+                    format!("{} · no ticket", location)
+                } else {
+                    location
+                };
+                HubItem {
+                    label: format!("{} {}", todo.marker, todo.text),
+                    hint: "".into(),
+                    description,
+                    action: HubAction::FocusFileLine {
+                        path: todo.path.clone(),
+                        line: todo.line,
+                    },
+                    is_header: false,
+                    enabled: true,
+                }
+            })
+            .collect();
+        let title = if untracked > 0 {
+            format!(
+                "TODOS · {} new · {} without a ticket",
+                items.len(),
+                untracked
+            )
+        } else {
+            format!("TODOS · {} new", items.len())
+        };
+        self.overlay = Some(OverlayData::ModalHub {
+            kind: HubKind::Todos,
+            title: Some(title),
+            items,
+            selected: 0,
+        });
+    }
+
+    /// Jump the diff to `path` at `line`.
+    pub fn focus_file_line(&mut self, path: &str, line: Option<usize>) {
+        if self.tab_mut().focus_file_line(path, line) {
+            self.tab_mut().panel_focus = false;
+        } else {
+            self.notify(&format!("File not in diff: {}", path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticket_pattern_holds_back_readiness() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n\
+                    @@ -1 +1,2 @@\n x\n+// TODO: handle retries\n";
+        let mut tab = TabState::new_for_test(git::parse_diff(diff));
+        tab.reviewed.insert("a.rs".into(), String::new());
+        tab.refresh_todos();
+        assert_eq!(tab.todos.len(), 1);
+        assert!(tab.review_ready());
+
+        tab.todo_scanner = config::TodosConfig {
+            ticket_pattern: r"#\d+".into(),
+            ..Default::default()
+        }
+        .scanner();
+        tab.refresh_todos();
+        assert_eq!(tab.untracked_todos(), 1);
+        assert!(!tab.review_ready());
+    }
+}
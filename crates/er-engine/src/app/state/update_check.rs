@@ -0,0 +1,52 @@
+//! The opt-in background check for a newer `er` (`[updates] check`). Runs
+//! once per session on a worker thread; a newer release shows as a
+//! status-bar hint and one notification pointing at `er update`.
+
+use std::sync::mpsc::{self, Receiver};
+
+use super::App;
+use crate::self_update;
+
+/// The check's channel while it runs, and what it found.
+#[derive(Debug, Default)]
+pub struct UpdateCheck {
+    rx: Option<Receiver<Option<String>>>,
+    /// Version of a newer release, for the status-bar hint
+    pub available: Option<String>,
+}
+
+impl App {
+    /// Start the check when `[updates] check` is on.
+    pub fn start_update_check(&mut self) {
+        if !self.config.updates.check || self.update_check.rx.is_some() {
+            return;
+        }
+        let interval = self.config.updates.interval_hours;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(self_update::check_for_update(interval));
+        });
+        self.update_check.rx = Some(rx);
+    }
+
+    /// Pick up the check's answer. Returns whether a newer release turned up.
+    pub fn poll_update_check(&mut self) -> bool {
+        let Some(rx) = &self.update_check.rx else {
+            return false;
+        };
+        let Ok(found) = rx.try_recv() else {
+            return false;
+        };
+        self.update_check.rx = None;
+        let Some(version) = found else {
+            return false;
+        };
+        self.notify(&format!(
+            "er {} is out (you have {}) — run er update",
+            version,
+            self_update::VERSION
+        ));
+        self.update_check.available = Some(version);
+        true
+    }
+}
@@ -0,0 +1,274 @@
+//! Collecting this session's usage stats (see `crate::usage`): active time
+//! per tab from key presses, features from the overlays, input modes and
+//! diff modes they open, comments from the event bus. Written when er
+//! exits, unless `[usage] record = false`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{App, DiffMode, HubKind, InputMode, OverlayData, TabState};
+use crate::events::{self, Event, EventContext, Subscription};
+use crate::usage::{self, UsageRecord};
+
+/// Longest gap between key presses counted as reviewing time (as for the
+/// review pace).
+const IDLE_CAP: Duration = Duration::from_secs(10 * 60);
+
+/// A tab's review: repo, branch, PR.
+type TabKey = (String, String, Option<u64>);
+
+fn tab_key(context: &EventContext) -> TabKey {
+    (
+        context.repo_root.clone(),
+        context.branch.clone(),
+        context.pr,
+    )
+}
+
+#[derive(Debug, Default)]
+struct TabUsage {
+    started: u64,
+    active: Duration,
+    features: BTreeMap<String, u32>,
+    /// Size when last seen, for tabs closed before the end
+    files: usize,
+    adds: usize,
+    dels: usize,
+    reviewed: usize,
+}
+
+/// This session's stats so far.
+#[derive(Default)]
+pub struct UsageSession {
+    tabs: HashMap<TabKey, TabUsage>,
+    last_key: Option<Instant>,
+    last_tab: Option<TabKey>,
+    last_ui: Option<String>,
+    last_mode: Option<DiffMode>,
+    /// Comments added, per tab, from the event bus
+    comments: Arc<Mutex<HashMap<TabKey, u32>>>,
+    subscription: Option<Subscription>,
+}
+
+impl std::fmt::Debug for UsageSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageSession")
+            .field("tabs", &self.tabs.len())
+            .finish()
+    }
+}
+
+/// `ExpertReview` → `expert_review`.
+fn snake(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn hub_name(kind: HubKind) -> String {
+    snake(&format!("{:?}", kind))
+}
+
+/// The overlay or typing mode on screen, as a feature name.
+fn ui_feature(overlay: Option<&OverlayData>, input: &InputMode) -> Option<String> {
+    if let Some(overlay) = overlay {
+        let name = match overlay {
+            OverlayData::ModalHub { kind, .. } => return Some(format!("hub.{}", hub_name(*kind))),
+            OverlayData::WorktreePicker { .. } => "worktree_picker",
+            OverlayData::DirectoryBrowser { .. } => "directory_browser",
+            OverlayData::FilterHistory { .. } => "filter_history",
+            OverlayData::NotificationLog { .. } => "notification_log",
+            OverlayData::Onboarding { .. } => "onboarding",
+            OverlayData::CommandError { .. } => "command_error",
+            OverlayData::ConfigHub { .. } => "config_hub",
+        };
+        return Some(format!("overlay.{}", name));
+    }
+    let name = match input {
+        InputMode::Normal | InputMode::Confirm(_) => return None,
+        InputMode::Search => "search",
+        InputMode::Find => "find",
+        InputMode::Comment => "comment",
+        InputMode::Filter => "filter",
+        InputMode::Commit => "commit",
+        InputMode::RemoteUrl => "remote_url",
+        InputMode::SkipReason => "skip_reason",
+        InputMode::QuickDiffRef => "quick_diff_ref",
+        InputMode::Assignee => "assignee",
+    };
+    Some(format!("input.{}", name))
+}
+
+/// Remember `tab`'s diff size and reviewed count.
+fn snapshot_size(tabs: &mut HashMap<TabKey, TabUsage>, tab: &TabState) {
+    if let Some(entry) = tabs.get_mut(&tab_key(&tab.event_context())) {
+        entry.files = tab.files.len();
+        entry.adds = tab.files.iter().map(|f| f.adds).sum();
+        entry.dels = tab.files.iter().map(|f| f.dels).sum();
+        entry.reviewed = tab.reviewed.len();
+    }
+}
+
+impl App {
+    /// Start counting comments from the event bus, when recording is on.
+    pub fn start_usage(&mut self) {
+        if !self.config.usage.record || self.usage.subscription.is_some() {
+            return;
+        }
+        let comments = Arc::clone(&self.usage.comments);
+        self.usage.subscription = Some(events::subscribe(move |envelope| {
+            if let Event::CommentAdded { .. } = envelope.event {
+                if let Ok(mut comments) = comments.lock() {
+                    *comments.entry(tab_key(envelope.context)).or_default() += 1;
+                }
+            }
+        }));
+    }
+
+    /// After each key: credit the time since the last one to the active
+    /// tab and count any feature the key opened.
+    pub fn note_usage(&mut self) {
+        if self.usage.subscription.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let key = tab_key(&self.tab().event_context());
+        let gap = self
+            .usage
+            .last_key
+            .map(|last| now.saturating_duration_since(last).min(IDLE_CAP))
+            .unwrap_or_default();
+        self.usage.last_key = Some(now);
+
+        let switched = self.usage.last_tab.as_ref() != Some(&key);
+        let ui = ui_feature(self.overlay.as_ref(), &self.input_mode);
+        let mode = self.tab().mode;
+        let mut opened = Vec::new();
+        if ui.is_some() && ui != self.usage.last_ui {
+            opened.extend(ui.clone());
+        }
+        if mode != DiffMode::Branch && (switched || Some(mode) != self.usage.last_mode) {
+            opened.push(format!("mode.{}", mode.git_mode()));
+        }
+        self.usage.last_ui = ui;
+        self.usage.last_mode = Some(mode);
+
+        let entry = self
+            .usage
+            .tabs
+            .entry(key.clone())
+            .or_insert_with(|| TabUsage {
+                started: crate::outbox::unix_now(),
+                ..Default::default()
+            });
+        // Time before the first key in a tab went to wherever it was before
+        if !switched {
+            entry.active += gap;
+        }
+        for feature in opened {
+            *entry.features.entry(feature).or_default() += 1;
+        }
+        if switched {
+            self.usage.last_tab = Some(key);
+            let idx = self.active_tab.min(self.tabs.len().saturating_sub(1));
+            snapshot_size(&mut self.usage.tabs, &self.tabs[idx]);
+        }
+    }
+
+    /// This session's records, one per tab that saw a key press.
+    pub fn usage_records(&mut self) -> Vec<UsageRecord> {
+        for tab in &self.tabs {
+            snapshot_size(&mut self.usage.tabs, tab);
+        }
+
+        let comments = self
+            .usage
+            .comments
+            .lock()
+            .map(|c| c.clone())
+            .unwrap_or_default();
+        let ended = crate::outbox::unix_now();
+        let mut records: Vec<UsageRecord> = self
+            .usage
+            .tabs
+            .iter()
+            .map(|((repo, branch, pr), tab)| UsageRecord {
+                started: tab.started,
+                ended,
+                repo: repo.clone(),
+                branch: branch.clone(),
+                pr: *pr,
+                active_secs: tab.active.as_secs(),
+                files: tab.files,
+                adds: tab.adds,
+                dels: tab.dels,
+                reviewed: tab.reviewed,
+                comments: comments
+                    .get(&(repo.clone(), branch.clone(), *pr))
+                    .copied()
+                    .unwrap_or_default(),
+                features: tab.features.clone(),
+            })
+            .collect();
+        records.sort_by_key(|r| r.started);
+        records
+    }
+
+    /// Write this session's records; called once, on quit.
+    pub fn finish_usage(&mut self) {
+        let Some(subscription) = self.usage.subscription.take() else {
+            return;
+        };
+        events::unsubscribe(subscription);
+        if let Err(e) = usage::append(&self.usage_records()) {
+            crate::debug_log::warn("usage", format!("{:#}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    #[test]
+    fn keys_credit_time_and_count_opened_features() {
+        let mut app = App::new_for_test(vec![modified_file("a.rs", 5), modified_file("b.rs", 3)]);
+        app.usage.subscription = Some(events::subscribe(|_| {}));
+
+        app.note_usage();
+        app.usage.last_key = Some(Instant::now() - Duration::from_secs(90));
+        app.input_mode = InputMode::Search;
+        app.note_usage();
+        // Still searching: not a second open
+        app.note_usage();
+        app.input_mode = InputMode::Normal;
+        app.tab_mut().mode = DiffMode::History;
+        app.note_usage();
+        app.tab_mut().reviewed.insert("a.rs".into(), String::new());
+
+        let records = app.usage_records();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert!(record.active_secs >= 90);
+        assert_eq!(record.files, 2);
+        assert_eq!(record.lines(), 16);
+        assert_eq!(record.reviewed, 1);
+        assert_eq!(record.features.get("input.search"), Some(&1));
+        assert_eq!(record.features.get("mode.history"), Some(&1));
+
+        if let Some(subscription) = app.usage.subscription.take() {
+            events::unsubscribe(subscription);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! View preferences kept per repo: the inline layers (comments, questions,
+//! AI findings, resolved threads, code age), the open panel, the file sort
+//! and split diff. They're saved with the session to `view-prefs.json` in
+//! the repo's storage directory (next to `branches/`, so every branch shares
+//! them) and put back on launch, before the session's own restore. Settings
+//! → *Reset view preferences* deletes the file and goes back to the
+//! defaults.
+//!
+//! A panel only comes back when it has something to show.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::file_sort::FileSort;
+use super::{App, TabState};
+use crate::ai::InlineLayers;
+use crate::app::layout;
+
+pub const VIEW_PREFS_FILE: &str = "view-prefs.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewPrefs {
+    pub show_questions: bool,
+    pub show_github_comments: bool,
+    pub show_ai_findings: bool,
+    pub hide_resolved: bool,
+    pub show_code_age: bool,
+    /// Config name of the open panel (`comments`, `summary`, …)
+    pub panel: Option<String>,
+    pub file_sort: FileSort,
+    pub split_diff: bool,
+}
+
+impl Default for ViewPrefs {
+    fn default() -> Self {
+        let layers = InlineLayers::default();
+        ViewPrefs {
+            show_questions: layers.show_questions,
+            show_github_comments: layers.show_github_comments,
+            show_ai_findings: layers.show_ai_findings,
+            hide_resolved: layers.hide_resolved,
+            show_code_age: layers.show_code_age,
+            panel: None,
+            file_sort: FileSort::default(),
+            split_diff: false,
+        }
+    }
+}
+
+impl ViewPrefs {
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// `view-prefs.json` for the repo at `repo_root`.
+pub fn view_prefs_path(repo_root: &str) -> PathBuf {
+    if crate::storage::use_repo_local_storage() {
+        return Path::new(repo_root).join(".er").join(VIEW_PREFS_FILE);
+    }
+    crate::storage::repo_dir(&crate::storage::slug_repo(repo_root)).join(VIEW_PREFS_FILE)
+}
+
+impl TabState {
+    /// This tab's preferences, with `split_diff` from the config.
+    pub fn capture_view_prefs(&self, split_diff: bool) -> ViewPrefs {
+        ViewPrefs {
+            show_questions: self.layers.show_questions,
+            show_github_comments: self.layers.show_github_comments,
+            show_ai_findings: self.layers.show_ai_findings,
+            hide_resolved: self.layers.hide_resolved,
+            show_code_age: self.layers.show_code_age,
+            panel: self.panel.map(|p| layout::panel_name(p).to_string()),
+            file_sort: self.file_sort,
+            split_diff,
+        }
+    }
+
+    /// Put the layers, sort and (when it has content) panel back.
+    pub fn apply_view_prefs(&mut self, prefs: &ViewPrefs) {
+        self.layers = InlineLayers {
+            show_questions: prefs.show_questions,
+            show_github_comments: prefs.show_github_comments,
+            show_ai_findings: prefs.show_ai_findings,
+            hide_resolved: prefs.hide_resolved,
+            show_code_age: prefs.show_code_age,
+        };
+        if self.file_sort != prefs.file_sort {
+            self.file_sort = prefs.file_sort;
+            self.sort_files();
+            self.rebuild_hunk_offsets();
+        }
+        self.panel = prefs
+            .panel
+            .as_deref()
+            .and_then(layout::panel_named)
+            .filter(|&p| self.panel_available(p));
+    }
+}
+
+impl App {
+    /// On launch: every local tab gets its repo's preferences, and split
+    /// diff follows the active tab's.
+    pub fn restore_view_prefs(&mut self) {
+        let active = self.active_tab;
+        for idx in 0..self.tabs.len() {
+            let tab = &mut self.tabs[idx];
+            if tab.is_remote() || tab.is_repoless() {
+                continue;
+            }
+            let Some(prefs) = ViewPrefs::load(&view_prefs_path(&tab.repo_root)) else {
+                continue;
+            };
+            tab.apply_view_prefs(&prefs);
+            tab.view_prefs = Some(prefs.clone());
+            if idx == active {
+                self.config.display.split_diff = prefs.split_diff;
+            }
+        }
+    }
+
+    /// Save the active tab's preferences when they changed since the last
+    /// save.
+    pub fn save_view_prefs(&mut self) {
+        let split = self.config.display.split_diff;
+        let tab = self.tab_mut();
+        if tab.is_remote() || tab.is_repoless() {
+            return;
+        }
+        let prefs = tab.capture_view_prefs(split);
+        if tab.view_prefs.as_ref() == Some(&prefs) {
+            return;
+        }
+        if let Err(e) = prefs.save(&view_prefs_path(&tab.repo_root)) {
+            crate::debug_log::warn("session", format!("saving view preferences: {:#}", e));
+            return;
+        }
+        tab.view_prefs = Some(prefs);
+    }
+
+    /// Settings → Reset view preferences.
+    pub fn reset_view_prefs(&mut self) {
+        let path = view_prefs_path(&self.tab().repo_root);
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                self.notify_warn(&format!("Couldn't remove {}: {}", path.display(), e));
+                return;
+            }
+        }
+        let defaults = ViewPrefs {
+            split_diff: crate::config::load_global_config().display.split_diff,
+            ..ViewPrefs::default()
+        };
+        let tab = self.tab_mut();
+        tab.apply_view_prefs(&defaults);
+        tab.view_prefs = Some(defaults.clone());
+        self.config.display.split_diff = defaults.split_diff;
+        self.notify("View preferences reset");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::PanelContent;
+    use crate::git;
+
+    const DIFF: &str = "diff --git a/src/b.rs b/src/b.rs\n--- a/src/b.rs\n+++ b/src/b.rs\n\
+                        @@ -1 +1,2 @@\n a\n+b\n\
+                        diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                        @@ -1 +1,2 @@\n a\n+b\n";
+
+    #[test]
+    fn preferences_round_trip_through_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(VIEW_PREFS_FILE);
+        let mut tab = TabState::new_for_test(git::parse_diff(DIFF));
+        tab.layers.show_ai_findings = false;
+        tab.layers.hide_resolved = true;
+        tab.panel = Some(PanelContent::FileDetail);
+        tab.file_sort = FileSort::Name;
+        let prefs = tab.capture_view_prefs(true);
+        prefs.save(&path).unwrap();
+        let loaded = ViewPrefs::load(&path).unwrap();
+        assert_eq!(loaded, prefs);
+        assert_eq!(loaded.panel.as_deref(), Some("comments"));
+
+        let mut fresh = TabState::new_for_test(git::parse_diff(DIFF));
+        fresh.apply_view_prefs(&loaded);
+        assert!(!fresh.layers.show_ai_findings);
+        assert!(fresh.layers.hide_resolved);
+        assert_eq!(fresh.panel, Some(PanelContent::FileDetail));
+        assert_eq!(fresh.files[0].path, "src/a.rs");
+
+        // A panel with nothing to show stays closed
+        let assets = ViewPrefs {
+            panel: Some("assets".into()),
+            ..ViewPrefs::default()
+        };
+        fresh.apply_view_prefs(&assets);
+        assert_eq!(fresh.panel, None);
+        assert_eq!(fresh.file_sort, FileSort::Diff);
+    }
+}
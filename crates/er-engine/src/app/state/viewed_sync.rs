@@ -0,0 +1,352 @@
+//! Reviewed files ↔ GitHub's per-file "viewed" checkboxes.
+//!
+//! Every comment sync also fetches the PR's viewed files and merges them
+//! three-way against what GitHub showed at the previous sync (kept in
+//! `github-viewed` next to `reviewed`): ticks and unticks made in the browser
+//! since then are applied here, and local changes GitHub doesn't have are
+//! pushed. Toggling a file with Space pushes straight away, in the
+//! background. Off unless `[features] github_viewed_sync = true`.
+//!
+//! A push only joins the baseline once GitHub took it
+//! ([`App::poll_viewed_pushes`]): recording a push that failed would make the
+//! next sync read GitHub's unchanged state as a browser tick or untick and
+//! undo the local change.
+
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::{App, TabState};
+use crate::github::{self, PrViewedFiles};
+
+/// Viewed states GitHub took, by the baseline file they belong to.
+#[derive(Debug)]
+pub struct ViewedPushed {
+    baseline_path: String,
+    /// `(path, viewed)` pushes that succeeded
+    pushed: Vec<(String, bool)>,
+    failed: usize,
+}
+
+/// The channel the push workers report on.
+#[derive(Debug)]
+pub struct ViewedPushes {
+    tx: Sender<ViewedPushed>,
+    rx: Receiver<ViewedPushed>,
+}
+
+impl Default for ViewedPushes {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        ViewedPushes { tx, rx }
+    }
+}
+
+/// What a viewed-state merge changes on each side.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ViewedMerge {
+    /// Ticked in the browser since the last sync — mark reviewed here
+    pub mark: Vec<String>,
+    /// Unticked in the browser since the last sync — unmark here
+    pub unmark: Vec<String>,
+    /// Reviewed here, not viewed on GitHub
+    pub push_viewed: Vec<String>,
+    /// Unmarked here, still viewed on GitHub
+    pub push_unviewed: Vec<String>,
+}
+
+/// Three-way merge over `files`: a GitHub change since `base` wins, otherwise
+/// the local state is pushed.
+pub fn merge_viewed(
+    base: &HashSet<String>,
+    local: &HashSet<String>,
+    remote: &HashSet<String>,
+    files: &[String],
+) -> ViewedMerge {
+    let mut merge = ViewedMerge::default();
+    for path in files {
+        let (b, l, r) = (
+            base.contains(path),
+            local.contains(path),
+            remote.contains(path),
+        );
+        if r != b {
+            if r && !l {
+                merge.mark.push(path.clone());
+            } else if !r && l {
+                merge.unmark.push(path.clone());
+            }
+        } else if l && !r {
+            merge.push_viewed.push(path.clone());
+        } else if !l && r {
+            merge.push_unviewed.push(path.clone());
+        }
+    }
+    merge
+}
+
+fn read_baseline(path: &str) -> HashSet<String> {
+    std::fs::read_to_string(path)
+        .map(|c| {
+            c.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_baseline(path: &str, viewed: &HashSet<String>) {
+    let mut paths: Vec<&String> = viewed.iter().collect();
+    paths.sort();
+    let content: String = paths.iter().map(|p| format!("{}\n", p)).collect();
+    if let Err(e) = crate::file_lock::write_atomic(path, &content) {
+        crate::debug_log::warn("sync", format!("{:#}", e));
+    }
+}
+
+impl TabState {
+    fn github_viewed_path(&self) -> String {
+        self.er_root.github_viewed_path()
+    }
+
+    /// Viewed files GitHub reported at the last sync, plus pushes it took
+    /// since.
+    pub fn github_viewed_baseline(&self) -> HashSet<String> {
+        read_baseline(&self.github_viewed_path())
+    }
+
+    /// `None` for remote tabs, which have no clone to run `gh` in.
+    fn gh_cwd(&self) -> Option<String> {
+        (!self.is_remote()).then(|| self.repo_root.clone())
+    }
+}
+
+impl App {
+    /// Fetch GitHub's viewed files for the active tab's PR and merge them
+    /// with the reviewed set. Returns a toast suffix when anything moved.
+    pub fn sync_viewed_files(&mut self, owner: &str, repo: &str, pr: u64) -> Option<String> {
+        if !self.config.features.github_viewed_sync {
+            return None;
+        }
+        let cwd = self.tab().gh_cwd();
+        match github::gh_pr_viewed_files(owner, repo, pr, cwd.as_deref()) {
+            Ok(remote) => {
+                let idx = self.active_tab;
+                let merge = self.apply_viewed_files(idx, remote);
+                let summary = viewed_summary(&merge);
+                self.spawn_viewed_pushes(idx, &merge);
+                summary
+            }
+            Err(e) => {
+                crate::debug_log::warn("sync", format!("viewed files: {:#}", e));
+                None
+            }
+        }
+    }
+
+    /// Merge `remote` into tab `idx`'s reviewed set and remember it as the new
+    /// baseline. The pushes in the result are left to the caller; they join
+    /// the baseline as GitHub takes them.
+    pub fn apply_viewed_files(&mut self, idx: usize, remote: PrViewedFiles) -> ViewedMerge {
+        let Some(tab) = self.tabs.get_mut(idx) else {
+            return ViewedMerge::default();
+        };
+        let files: Vec<String> = tab.files.iter().map(|f| f.path.clone()).collect();
+        let local: HashSet<String> = tab.reviewed.keys().cloned().collect();
+        let base = tab.github_viewed_baseline();
+        let merge = merge_viewed(&base, &local, &remote.viewed, &files);
+
+        for path in &merge.mark {
+            let hash = tab
+                .current_per_file_hashes
+                .get(path)
+                .cloned()
+                .unwrap_or_default();
+            tab.reviewed.insert(path.clone(), hash);
+        }
+        for path in &merge.unmark {
+            tab.reviewed.remove(path);
+        }
+        if !merge.mark.is_empty() || !merge.unmark.is_empty() {
+            tab.reviewed_revision += 1;
+            let _ = tab.save_reviewed_files();
+        }
+
+        write_baseline(&tab.github_viewed_path(), &remote.viewed);
+        tab.github_pr_node_id = Some(remote.pr_id);
+        merge
+    }
+
+    /// Send `merge`'s pushes for tab `idx` from a background thread.
+    pub fn spawn_viewed_pushes(&self, idx: usize, merge: &ViewedMerge) {
+        let Some(tab) = self.tabs.get(idx) else {
+            return;
+        };
+        let changes: Vec<(String, bool)> = merge
+            .push_viewed
+            .iter()
+            .map(|p| (p.clone(), true))
+            .chain(merge.push_unviewed.iter().map(|p| (p.clone(), false)))
+            .collect();
+        if let Some(pr_id) = tab.github_pr_node_id.clone() {
+            self.spawn_set_viewed(tab, pr_id, changes);
+        }
+    }
+
+    /// Mirror a Space toggle to GitHub, once a sync has told us the PR.
+    pub(super) fn push_viewed_toggle(&mut self, path: &str, viewed: bool) {
+        if !self.config.features.github_viewed_sync {
+            return;
+        }
+        let tab = self.tab();
+        let Some(pr_id) = tab.github_pr_node_id.clone() else {
+            return;
+        };
+        self.spawn_set_viewed(tab, pr_id, vec![(path.to_string(), viewed)]);
+    }
+
+    fn spawn_set_viewed(&self, tab: &TabState, pr_id: String, changes: Vec<(String, bool)>) {
+        // Read-only sessions still pull GitHub's ticks, quietly sending none
+        if changes.is_empty() || self.read_only {
+            return;
+        }
+        let cwd = tab.gh_cwd();
+        let baseline_path = tab.github_viewed_path();
+        let tx = self.viewed_pushes.tx.clone();
+        std::thread::spawn(move || {
+            let mut pushed = Vec::new();
+            let mut failed = 0;
+            for (path, viewed) in changes {
+                match github::gh_pr_set_viewed(&pr_id, &path, viewed, cwd.as_deref()) {
+                    Ok(()) => pushed.push((path, viewed)),
+                    Err(e) => {
+                        failed += 1;
+                        crate::debug_log::warn("sync", format!("{:#}", e));
+                    }
+                }
+            }
+            let _ = tx.send(ViewedPushed {
+                baseline_path,
+                pushed,
+                failed,
+            });
+        });
+    }
+
+    /// Record the viewed states GitHub took in their baselines. True when
+    /// a push failed and was reported.
+    pub fn poll_viewed_pushes(&mut self) -> bool {
+        let mut failed = 0;
+        while let Ok(done) = self.viewed_pushes.rx.try_recv() {
+            apply_pushed(&done);
+            failed += done.failed;
+        }
+        if failed == 0 {
+            return false;
+        }
+        self.notify_warn(&format!(
+            "GitHub didn't take the viewed state of {} file{} — the next sync retries",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        ));
+        true
+    }
+}
+
+/// Add the pushes GitHub took to their baseline.
+fn apply_pushed(done: &ViewedPushed) {
+    if done.pushed.is_empty() {
+        return;
+    }
+    let mut baseline = read_baseline(&done.baseline_path);
+    for (path, viewed) in &done.pushed {
+        if *viewed {
+            baseline.insert(path.clone());
+        } else {
+            baseline.remove(path);
+        }
+    }
+    write_baseline(&done.baseline_path, &baseline);
+}
+
+/// `2 viewed from GitHub, 1 pushed`
+fn viewed_summary(merge: &ViewedMerge) -> Option<String> {
+    let pulled = merge.mark.len() + merge.unmark.len();
+    let pushed = merge.push_viewed.len() + merge.push_unviewed.len();
+    if pulled + pushed == 0 {
+        return None;
+    }
+    Some(format!("{} viewed from GitHub, {} pushed", pulled, pushed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixtures::modified_file;
+
+    fn set(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn browser_changes_win_and_local_changes_push() {
+        let files: Vec<String> = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        // a: ticked in browser, b: unticked in browser, c: reviewed here,
+        // d: unmarked here, e: in sync
+        let base = set(&["b", "d", "e"]);
+        let local = set(&["b", "c", "e"]);
+        let remote = set(&["a", "d", "e"]);
+        let merge = merge_viewed(&base, &local, &remote, &files);
+        assert_eq!(merge.mark, vec!["a"]);
+        assert_eq!(merge.unmark, vec!["b"]);
+        assert_eq!(merge.push_viewed, vec!["c"]);
+        assert_eq!(merge.push_unviewed, vec!["d"]);
+        assert_eq!(
+            viewed_summary(&merge).as_deref(),
+            Some("2 viewed from GitHub, 2 pushed")
+        );
+    }
+
+    #[test]
+    fn apply_marks_reviewed_and_records_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(vec![modified_file("src/a.rs", 1)]);
+        app.tab_mut().er_root = crate::paths::ErRoot::RepoLocal(dir.path().display().to_string());
+        let remote = PrViewedFiles {
+            pr_id: "PR_1".into(),
+            viewed: set(&["src/a.rs"]),
+        };
+        let merge = app.apply_viewed_files(0, remote);
+        assert_eq!(merge.mark, vec!["src/a.rs"]);
+        assert!(app.tab().reviewed.contains_key("src/a.rs"));
+        assert_eq!(app.tab().github_viewed_baseline(), set(&["src/a.rs"]));
+        assert_eq!(app.tab().github_pr_node_id.as_deref(), Some("PR_1"));
+
+        // Only pushes GitHub took join the baseline
+        let baseline_path = app.tab().github_viewed_path();
+        let report = |pushed: Vec<(&str, bool)>, failed| ViewedPushed {
+            baseline_path: baseline_path.clone(),
+            pushed: pushed
+                .into_iter()
+                .map(|(p, v)| (p.to_string(), v))
+                .collect(),
+            failed,
+        };
+        app.viewed_pushes
+            .tx
+            .send(report(vec![("src/b.rs", true)], 1))
+            .unwrap();
+        assert!(app.poll_viewed_pushes());
+        assert_eq!(
+            app.tab().github_viewed_baseline(),
+            set(&["src/a.rs", "src/b.rs"])
+        );
+        app.viewed_pushes
+            .tx
+            .send(report(vec![("src/a.rs", false)], 0))
+            .unwrap();
+        assert!(!app.poll_viewed_pushes());
+        assert_eq!(app.tab().github_viewed_baseline(), set(&["src/b.rs"]));
+    }
+}
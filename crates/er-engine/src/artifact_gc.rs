@@ -0,0 +1,512 @@
+//! Clean up a repo's review artifacts (`er clean`, Settings → *Clean up
+//! review data*).
+//!
+//! Storage only ever grows: every branch reviewed gets a bucket under
+//! `repos/<slug>/branches/`, every PR one under `prs/`, and nothing removes
+//! them when the branch is deleted or the PR closes. The plan lists what can
+//! go, and [`execute`] removes it:
+//!
+//! - branch buckets whose branch (or, for a release tab, tags) no longer
+//!   exists locally or on a remote
+//! - PR buckets of PRs that aren't open, when `gh` can say which are
+//! - sidecars with nothing left in them, temp files a crashed write left
+//!   behind and `*.lock` files (see [`crate::file_lock`]) whose sidecar is
+//!   gone, in the buckets that stay
+//! - directories that end up empty
+//!
+//! Buckets an open tab is using are never touched. Entries inside the
+//! sidecars that stay aren't pruned: comments whose anchor was lost are
+//! still the reviewer's words (the Lost comments hub re-anchors or deletes
+//! them), and AI artifacts of an older diff are shown as stale and cleared
+//! with `Z`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::storage;
+
+/// Most open PRs asked for; a PR bucket is only pruned when the list is complete.
+const OPEN_PR_LIMIT: usize = 1000;
+
+/// Tags listed for matching release buckets.
+const TAG_LIMIT: usize = 10_000;
+
+/// A `*.tmp` file older than this isn't a write in progress.
+const STALE_TMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Comment sidecars and the list that makes them non-empty.
+const COMMENT_SIDECARS: &[(&str, &str)] = &[
+    ("questions.json", "questions"),
+    ("notes.json", "notes"),
+    ("github-comments.json", "comments"),
+];
+
+/// Why a path is in the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanKind {
+    /// A branch bucket whose branch is gone
+    DeletedBranch,
+    /// A PR bucket of a merged or closed PR
+    ClosedPr,
+    /// A comment sidecar with no comments in it
+    EmptySidecar,
+    /// A temp file from an interrupted write, or the lock of a sidecar
+    /// that's gone
+    Leftover,
+    /// A directory with nothing left in it
+    EmptyDir,
+}
+
+impl CleanKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DeletedBranch => "Deleted branch",
+            Self::ClosedPr => "Closed PR",
+            Self::EmptySidecar => "Empty sidecar",
+            Self::Leftover => "Leftover file",
+            Self::EmptyDir => "Empty directory",
+        }
+    }
+}
+
+/// One path the cleanup removes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanTarget {
+    pub kind: CleanKind,
+    pub path: PathBuf,
+    /// Bytes freed
+    pub bytes: u64,
+}
+
+impl CleanTarget {
+    pub fn description(&self) -> String {
+        format!("{} — {}", self.kind.label(), self.path.display())
+    }
+}
+
+/// Result of executing a cleanup plan.
+#[derive(Debug, Clone, Default)]
+pub struct CleanReport {
+    pub removed: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+    pub bytes: u64,
+}
+
+impl CleanReport {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for p in &self.removed {
+            lines.push(format!("removed  {}", p.display()));
+        }
+        for (p, err) in &self.failed {
+            lines.push(format!("failed   {}: {err}", p.display()));
+        }
+        lines
+    }
+}
+
+/// What the repo still has: branch slugs, tag slugs and open PR numbers.
+/// `None` when it couldn't be listed, so nothing of that kind is pruned.
+#[derive(Debug, Default)]
+pub struct LiveRefs {
+    branches: Option<HashSet<String>>,
+    tags: HashSet<String>,
+    open_prs: Option<HashSet<u64>>,
+}
+
+impl LiveRefs {
+    /// Ask git (and `gh`, for PRs) what exists.
+    pub fn load(repo_root: &str) -> Self {
+        let branches = crate::git::git_branch_names(repo_root).ok().map(|names| {
+            let mut slugs = HashSet::new();
+            for name in names {
+                // `origin/feat/x` was reviewed as `feat/x`
+                if let Some((_, rest)) = name.split_once('/') {
+                    slugs.insert(storage::slug_branch(rest));
+                }
+                slugs.insert(storage::slug_branch(&name));
+            }
+            slugs
+        });
+        let tags = crate::git::git_tags(repo_root, TAG_LIMIT)
+            .map(|tags| tags.iter().map(|t| storage::slug_branch(&t.name)).collect())
+            .unwrap_or_default();
+        let open_prs = crate::github::gh_open_prs(repo_root, OPEN_PR_LIMIT)
+            .ok()
+            .filter(|prs| prs.len() < OPEN_PR_LIMIT)
+            .map(|prs| prs.into_iter().map(|(number, _)| number).collect());
+        LiveRefs {
+            branches,
+            tags,
+            open_prs,
+        }
+    }
+
+    /// Whether the bucket `slug` still belongs to something: a branch, or a
+    /// release range between tags (`v1.3.0…v1.4.0` is stored as
+    /// `v1.3.0-v1.4.0`).
+    fn keeps_branch(&self, slug: &str) -> bool {
+        let Some(branches) = &self.branches else {
+            return true;
+        };
+        branches.contains(slug)
+            || self.tags.iter().any(|from| {
+                slug.strip_prefix(from.as_str())
+                    .and_then(|rest| rest.strip_prefix('-'))
+                    .is_some_and(|to| to == "HEAD" || self.tags.contains(to))
+            })
+    }
+
+    fn keeps_pr(&self, number: u64) -> bool {
+        self.open_prs
+            .as_ref()
+            .is_none_or(|open| open.contains(&number))
+    }
+}
+
+/// Where a repo's artifacts are.
+#[derive(Debug, Default)]
+struct CleanRoots {
+    /// `repos/<slug>/branches/`
+    branches: Option<PathBuf>,
+    /// `repos/<owner-repo>/prs/`
+    prs: Option<PathBuf>,
+    /// `.er/` in the working tree
+    repo_local: Option<PathBuf>,
+}
+
+/// What cleaning up the repo at `repo_root` would remove, leaving `keep`
+/// (the open tabs' artifact dirs) and everything above them alone.
+pub fn plan(repo_root: &str, keep: &[PathBuf]) -> Vec<CleanTarget> {
+    let repo_dir = storage::repo_dir(&storage::slug_repo(repo_root));
+    let roots = CleanRoots {
+        branches: Some(repo_dir.join("branches")),
+        prs: crate::github::canonical_owner_repo_slug(repo_root)
+            .map(|slug| storage::repo_dir(&slug).join("prs")),
+        repo_local: Some(Path::new(repo_root).join(".er")),
+    };
+    plan_in(&roots, &LiveRefs::load(repo_root), keep, SystemTime::now())
+}
+
+fn plan_in(
+    roots: &CleanRoots,
+    live: &LiveRefs,
+    keep: &[PathBuf],
+    now: SystemTime,
+) -> Vec<CleanTarget> {
+    let mut targets = Vec::new();
+    let mut scan = Scan {
+        keep,
+        now,
+        targets: &mut targets,
+    };
+    for (dir, name) in roots.branches.iter().flat_map(|d| entries(d)) {
+        if !dir.is_dir() {
+            continue;
+        }
+        if live.keeps_branch(&name) || scan.protects(&dir) {
+            scan.compact(&dir);
+        } else {
+            scan.push(CleanKind::DeletedBranch, dir);
+        }
+    }
+    for (dir, name) in roots.prs.iter().flat_map(|d| entries(d)) {
+        if !dir.is_dir() {
+            continue;
+        }
+        let number = name.strip_prefix("pr-").and_then(|n| n.parse().ok());
+        match number {
+            Some(n) if !live.keeps_pr(n) && !scan.protects(&dir) => {
+                scan.push(CleanKind::ClosedPr, dir)
+            }
+            _ => {
+                scan.compact(&dir);
+            }
+        }
+    }
+    if let Some(dir) = roots.repo_local.as_ref().filter(|d| d.is_dir()) {
+        // `.er/` itself stays: it may be gitignored or committed on purpose
+        scan.compact_inside(dir);
+    }
+    targets
+}
+
+struct Scan<'a> {
+    keep: &'a [PathBuf],
+    now: SystemTime,
+    targets: &'a mut Vec<CleanTarget>,
+}
+
+impl Scan<'_> {
+    /// `dir` is an open tab's artifact dir or one of its parents.
+    fn protects(&self, dir: &Path) -> bool {
+        self.keep.iter().any(|k| k.starts_with(dir))
+    }
+
+    fn push(&mut self, kind: CleanKind, path: PathBuf) {
+        let bytes = size_of(&path);
+        self.targets.push(CleanTarget { kind, path, bytes });
+    }
+
+    /// Plan the leftovers inside `dir`, and `dir` itself when nothing would
+    /// be left. True when it's planned.
+    fn compact(&mut self, dir: &Path) -> bool {
+        if self.compact_inside(dir) && !self.protects(dir) {
+            self.push(CleanKind::EmptyDir, dir.to_path_buf());
+            return true;
+        }
+        false
+    }
+
+    /// Children before parents, so each directory is empty by the time it's
+    /// removed. True when everything inside is planned.
+    fn compact_inside(&mut self, dir: &Path) -> bool {
+        let mut emptied = true;
+        for (path, name) in entries(dir) {
+            let gone = if path.is_dir() {
+                self.compact(&path)
+            } else if let Some(kind) = self.leftover_kind(&path, &name) {
+                self.push(kind, path);
+                true
+            } else {
+                false
+            };
+            emptied &= gone;
+        }
+        emptied
+    }
+
+    fn leftover_kind(&self, path: &Path, name: &str) -> Option<CleanKind> {
+        if let Some(sidecar) = name.strip_suffix(".lock") {
+            // Entries are sorted, so the sidecar was planned already if it goes
+            let sidecar = path.with_file_name(sidecar);
+            let orphaned = !sidecar.exists() || self.targets.iter().any(|t| t.path == sidecar);
+            return (orphaned && !lock_held(path)).then_some(CleanKind::Leftover);
+        }
+        if name.ends_with(".tmp") {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+            let age = self.now.duration_since(modified).unwrap_or_default();
+            return (age >= STALE_TMP_AGE).then_some(CleanKind::Leftover);
+        }
+        let (_, list) = COMMENT_SIDECARS.iter().find(|(file, _)| *file == name)?;
+        let content = std::fs::read_to_string(path).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let empty = value
+            .get(list)
+            .is_none_or(|v| v.as_array().is_some_and(|a| a.is_empty()));
+        // github-comments.json also remembers the last sync
+        let synced = value.get("github").is_some_and(|v| !v.is_null());
+        (empty && !synced).then_some(CleanKind::EmptySidecar)
+    }
+}
+
+/// Whether a writer holds the lock at `path` right now.
+fn lock_held(path: &Path) -> bool {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .is_ok_and(|file| matches!(file.try_lock(), Err(std::fs::TryLockError::WouldBlock)))
+}
+
+/// Entries of `dir` with their names, sorted so plans read the same each run.
+fn entries(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut out: Vec<(PathBuf, String)> = std::fs::read_dir(dir)
+        .map(|rd| {
+            rd.flatten()
+                .map(|e| (e.path(), e.file_name().to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    out.sort();
+    out
+}
+
+fn size_of(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    entries(path).iter().map(|(p, _)| size_of(p)).sum()
+}
+
+/// Bytes freed by the plan.
+pub fn total_bytes(targets: &[CleanTarget]) -> u64 {
+    targets.iter().map(|t| t.bytes).sum()
+}
+
+/// One line per kind: `3 deleted branch buckets (1.2 MB)`.
+pub fn summarize(targets: &[CleanTarget]) -> Vec<String> {
+    let kinds = [
+        (CleanKind::DeletedBranch, "deleted branch bucket"),
+        (CleanKind::ClosedPr, "closed PR bucket"),
+        (CleanKind::EmptySidecar, "empty sidecar"),
+        (CleanKind::Leftover, "leftover file"),
+        (CleanKind::EmptyDir, "empty directory"),
+    ];
+    kinds
+        .iter()
+        .filter_map(|(kind, noun)| {
+            let of_kind: Vec<&CleanTarget> = targets.iter().filter(|t| t.kind == *kind).collect();
+            if of_kind.is_empty() {
+                return None;
+            }
+            let bytes: u64 = of_kind.iter().map(|t| t.bytes).sum();
+            let noun = match (of_kind.len(), *kind) {
+                (1, _) => noun.to_string(),
+                (_, CleanKind::EmptyDir) => "empty directories".to_string(),
+                _ => format!("{}s", noun),
+            };
+            Some(format!(
+                "{} {} ({})",
+                of_kind.len(),
+                noun,
+                crate::git::format_size(bytes)
+            ))
+        })
+        .collect()
+}
+
+/// Remove the planned paths, in plan order.
+pub fn execute(targets: &[CleanTarget]) -> CleanReport {
+    let mut report = CleanReport::default();
+    for target in targets {
+        let result = match target.kind {
+            CleanKind::DeletedBranch | CleanKind::ClosedPr => std::fs::remove_dir_all(&target.path),
+            CleanKind::EmptyDir => std::fs::remove_dir(&target.path),
+            CleanKind::EmptySidecar | CleanKind::Leftover => std::fs::remove_file(&target.path),
+        };
+        match result {
+            Ok(()) => {
+                report.removed.push(target.path.clone());
+                report.bytes += target.bytes;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => report.failed.push((target.path.clone(), e.to_string())),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn gone_branches_and_closed_prs_are_pruned_and_the_rest_compacted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let branches = tmp.path().join("branches");
+        let prs = tmp.path().join("prs");
+        let bucket = |dir: &Path| {
+            let b = dir.join("view-buckets/branch");
+            fs::create_dir_all(&b).unwrap();
+            b
+        };
+        let main = bucket(&branches.join("main"));
+        fs::write(main.join("review.json"), "{}").unwrap();
+        fs::write(
+            main.join("questions.json"),
+            r#"{"version":1,"diff_hash":"x","questions":[]}"#,
+        )
+        .unwrap();
+        fs::write(main.join("order.json.tmp"), "{").unwrap();
+        fs::write(main.join("questions.json.lock"), "").unwrap();
+        fs::write(main.join("review.json.lock"), "").unwrap();
+        fs::write(main.join("notes.json.lock"), "").unwrap();
+        // A writer holding its lock keeps it
+        let _held = crate::file_lock::lock(&main.join("todos.json").to_string_lossy()).unwrap();
+        let unstaged = branches.join("main/view-buckets/unstaged");
+        fs::create_dir_all(&unstaged).unwrap();
+        let synced = bucket(&branches.join("feat-sync"));
+        fs::write(
+            synced.join("github-comments.json"),
+            r#"{"version":1,"diff_hash":"x","github":{"pr_number":3},"comments":[]}"#,
+        )
+        .unwrap();
+        fs::write(
+            bucket(&branches.join("feat-gone")).join("review.json"),
+            "{}",
+        )
+        .unwrap();
+        bucket(&branches.join("v1.3.0-v1.4.0"));
+        let open_elsewhere = bucket(&branches.join("spike"));
+        bucket(&prs.join("pr-7"));
+        fs::write(bucket(&prs.join("pr-8")).join("reviewed"), "a.rs").unwrap();
+
+        let live = LiveRefs {
+            branches: Some(["main", "feat-sync"].map(String::from).into()),
+            tags: ["v1.3.0", "v1.4.0"].map(String::from).into(),
+            open_prs: Some([8].into()),
+        };
+        let roots = CleanRoots {
+            branches: Some(branches.clone()),
+            prs: Some(prs.clone()),
+            repo_local: None,
+        };
+        let later = SystemTime::now() + STALE_TMP_AGE * 2;
+        let targets = plan_in(&roots, &live, &[open_elsewhere], later);
+        let planned: Vec<(CleanKind, &Path)> = targets
+            .iter()
+            .map(|t| (t.kind, t.path.strip_prefix(tmp.path()).unwrap()))
+            .collect();
+        assert_eq!(
+            planned,
+            [
+                (CleanKind::DeletedBranch, Path::new("branches/feat-gone")),
+                (
+                    CleanKind::Leftover,
+                    Path::new("branches/main/view-buckets/branch/notes.json.lock")
+                ),
+                (
+                    CleanKind::Leftover,
+                    Path::new("branches/main/view-buckets/branch/order.json.tmp")
+                ),
+                (
+                    CleanKind::EmptySidecar,
+                    Path::new("branches/main/view-buckets/branch/questions.json")
+                ),
+                (
+                    CleanKind::Leftover,
+                    Path::new("branches/main/view-buckets/branch/questions.json.lock")
+                ),
+                (
+                    CleanKind::EmptyDir,
+                    Path::new("branches/main/view-buckets/unstaged")
+                ),
+                (
+                    CleanKind::EmptyDir,
+                    Path::new("branches/v1.3.0-v1.4.0/view-buckets/branch")
+                ),
+                (
+                    CleanKind::EmptyDir,
+                    Path::new("branches/v1.3.0-v1.4.0/view-buckets")
+                ),
+                (CleanKind::EmptyDir, Path::new("branches/v1.3.0-v1.4.0")),
+                (CleanKind::ClosedPr, Path::new("prs/pr-7")),
+            ]
+        );
+        assert_eq!(summarize(&targets)[0], "1 deleted branch bucket (1 KB)");
+
+        let report = execute(&targets);
+        assert!(report.is_success(), "{:?}", report.failed);
+        assert!(!branches.join("feat-gone").exists());
+        assert!(!branches.join("v1.3.0-v1.4.0").exists());
+        assert!(main.join("review.json").exists());
+        assert!(main.join("review.json.lock").exists());
+        assert!(main.join("todos.json.lock").exists());
+        assert!(synced.join("github-comments.json").exists());
+        assert!(branches.join("spike").exists());
+        assert!(prs.join("pr-8/view-buckets/branch/reviewed").exists());
+
+        // Without a branch list nothing is taken for deleted
+        let unknown = LiveRefs::default();
+        assert!(unknown.keeps_branch("feat-gone") && unknown.keeps_pr(7));
+    }
+}
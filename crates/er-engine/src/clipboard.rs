@@ -0,0 +1,260 @@
+//! Clipboard backends.
+//!
+//! Native clipboard tools (`pbcopy`, `wl-copy`, `xclip`, `xsel`, `clip`) are
+//! tried in the order given by `[clipboard] backends`; `auto` expands to the
+//! platform's usual order. OSC 52 writes the text as a terminal escape
+//! sequence, so it reaches the local clipboard over SSH and through tmux —
+//! `auto` puts it first when running over SSH and last otherwise.
+
+use crate::config::ClipboardConfig;
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardBackend {
+    Pbcopy,
+    WlCopy,
+    Xclip,
+    Xsel,
+    Clip,
+    Osc52,
+}
+
+impl ClipboardBackend {
+    pub fn name(self) -> &'static str {
+        match self {
+            ClipboardBackend::Pbcopy => "pbcopy",
+            ClipboardBackend::WlCopy => "wl-copy",
+            ClipboardBackend::Xclip => "xclip",
+            ClipboardBackend::Xsel => "xsel",
+            ClipboardBackend::Clip => "clip",
+            ClipboardBackend::Osc52 => "osc52",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "pbcopy" => Some(ClipboardBackend::Pbcopy),
+            "wl-copy" | "wayland" => Some(ClipboardBackend::WlCopy),
+            "xclip" => Some(ClipboardBackend::Xclip),
+            "xsel" => Some(ClipboardBackend::Xsel),
+            "clip" => Some(ClipboardBackend::Clip),
+            "osc52" | "osc-52" => Some(ClipboardBackend::Osc52),
+            _ => None,
+        }
+    }
+
+    /// The program this backend runs, `None` for OSC 52.
+    pub fn program(self) -> Option<&'static str> {
+        self.command().map(|(program, _)| program)
+    }
+
+    fn command(self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            ClipboardBackend::Pbcopy => Some(("pbcopy", &[])),
+            ClipboardBackend::WlCopy => Some(("wl-copy", &[])),
+            ClipboardBackend::Xclip => Some(("xclip", &["-selection", "clipboard"])),
+            ClipboardBackend::Xsel => Some(("xsel", &["--clipboard", "--input"])),
+            ClipboardBackend::Clip => Some(("clip", &[])),
+            ClipboardBackend::Osc52 => None,
+        }
+    }
+
+    fn copy(self, text: &str) -> Result<()> {
+        let Some((program, args)) = self.command() else {
+            return osc52_copy(text);
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to run {}", program))?;
+        if let Some(ref mut stdin) = child.stdin {
+            stdin.write_all(text.as_bytes())?;
+        }
+        drop(child.stdin.take());
+        let status = child
+            .wait()
+            .with_context(|| format!("{} failed", program))?;
+        if !status.success() {
+            anyhow::bail!("{} exited with {}", program, status);
+        }
+        Ok(())
+    }
+}
+
+fn over_ssh() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+/// Platform default order for `auto`.
+fn auto_order(ssh: bool, wayland: bool) -> Vec<ClipboardBackend> {
+    let mut order = Vec::new();
+    if ssh {
+        order.push(ClipboardBackend::Osc52);
+    }
+    if cfg!(target_os = "macos") {
+        order.push(ClipboardBackend::Pbcopy);
+    } else if cfg!(target_os = "windows") {
+        order.push(ClipboardBackend::Clip);
+    } else {
+        if wayland {
+            order.push(ClipboardBackend::WlCopy);
+        }
+        order.push(ClipboardBackend::Xclip);
+        order.push(ClipboardBackend::Xsel);
+    }
+    order.push(ClipboardBackend::Osc52);
+    order
+}
+
+/// Backends to try, in order, with `auto` expanded and duplicates dropped.
+/// Unknown names are ignored; an empty list means `auto`.
+pub fn resolve_order(config: &ClipboardConfig) -> Vec<ClipboardBackend> {
+    resolve_order_with(
+        config,
+        over_ssh(),
+        std::env::var_os("WAYLAND_DISPLAY").is_some(),
+    )
+}
+
+fn resolve_order_with(config: &ClipboardConfig, ssh: bool, wayland: bool) -> Vec<ClipboardBackend> {
+    let mut order = Vec::new();
+    let names: Vec<&str> = if config.backends.is_empty() {
+        vec!["auto"]
+    } else {
+        config.backends.iter().map(String::as_str).collect()
+    };
+    for name in names {
+        let expanded = if name.trim().eq_ignore_ascii_case("auto") {
+            auto_order(ssh, wayland)
+        } else {
+            ClipboardBackend::parse(name).into_iter().collect()
+        };
+        for backend in expanded {
+            if !order.contains(&backend) {
+                order.push(backend);
+            }
+        }
+    }
+    order
+}
+
+/// Copy `text` with the first backend that works; returns which one did.
+pub fn copy(text: &str, config: &ClipboardConfig) -> Result<ClipboardBackend> {
+    let order = resolve_order(config);
+    let mut last_err = None;
+    for backend in &order {
+        match backend.copy(text) {
+            Ok(()) => return Ok(*backend),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    let tried: Vec<&str> = order.iter().map(|b| b.name()).collect();
+    match last_err {
+        Some(e) => Err(e.context(format!(
+            "No clipboard backend worked (tried {})",
+            tried.join(", ")
+        ))),
+        None => anyhow::bail!("No clipboard backend configured"),
+    }
+}
+
+/// OSC 52 "set clipboard" sequence, wrapped for tmux passthrough when needed.
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let payload = base64_encode(text.as_bytes());
+    if tmux {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", payload)
+    } else {
+        format!("\x1b]52;c;{}\x07", payload)
+    }
+}
+
+fn osc52_copy(text: &str) -> Result<()> {
+    let seq = osc52_sequence(text, std::env::var_os("TMUX").is_some());
+    // Prefer the controlling terminal so the sequence never lands in a pipe
+    match std::fs::OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => tty.write_all(seq.as_bytes())?,
+        Err(_) => {
+            let mut out = std::io::stdout();
+            out.write_all(seq.as_bytes())?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        out.push(TABLE[(n >> 18) as usize & 63] as char);
+        out.push(TABLE[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            TABLE[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            TABLE[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(backends: &[&str]) -> ClipboardConfig {
+        ClipboardConfig {
+            backends: backends.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn osc52_wraps_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert!(osc52_sequence("hi", true).starts_with("\x1bPtmux;\x1b\x1b]52;"));
+    }
+
+    #[test]
+    fn auto_prefers_osc52_over_ssh() {
+        let order = resolve_order_with(&config(&["auto"]), true, false);
+        assert_eq!(order.first(), Some(&ClipboardBackend::Osc52));
+        assert_eq!(
+            order
+                .iter()
+                .filter(|b| **b == ClipboardBackend::Osc52)
+                .count(),
+            1
+        );
+        let local = resolve_order_with(&config(&[]), false, false);
+        assert_eq!(local.last(), Some(&ClipboardBackend::Osc52));
+    }
+
+    #[test]
+    fn explicit_order_is_kept_and_unknown_names_skipped() {
+        let order = resolve_order_with(&config(&["osc52", "bogus", "xsel", "osc52"]), false, true);
+        assert_eq!(order, vec![ClipboardBackend::Osc52, ClipboardBackend::Xsel]);
+    }
+}
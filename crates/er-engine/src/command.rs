@@ -0,0 +1,228 @@
+//! Structured failures for external commands (`git`, `gh`, …).
+//!
+//! Most wrappers used to flatten a failed command into `"git add failed: …"`,
+//! which loses the exact invocation and exit code and gets truncated in a
+//! one-line notification. [`run`] keeps those details in a [`CommandFailure`]
+//! carried inside the `anyhow::Error`; callers that want to show them (the
+//! TUI error overlay) recover it with [`CommandFailure::find`]. The `Display`
+//! form keeps the old one-line wording so existing messages don't change.
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::process::{Command, Output};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandFailure {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    /// `None` when the process was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandFailure {
+    pub fn from_output(cmd: &Command, output: &Output) -> Self {
+        CommandFailure {
+            program: cmd.get_program().to_string_lossy().to_string(),
+            args: cmd
+                .get_args()
+                .map(|a| a.to_string_lossy().to_string())
+                .collect(),
+            cwd: cmd
+                .get_current_dir()
+                .map(|d| d.to_string_lossy().to_string()),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+    }
+
+    /// First `CommandFailure` in an error's context chain.
+    pub fn find(err: &anyhow::Error) -> Option<&CommandFailure> {
+        err.chain().find_map(|e| e.downcast_ref::<CommandFailure>())
+    }
+
+    /// Shell-like rendering of the invocation (args with spaces are quoted).
+    pub fn command_line(&self) -> String {
+        shell_line(&self.program, &self.args)
+    }
+
+    pub fn exit_label(&self) -> String {
+        match self.exit_code {
+            Some(code) => code.to_string(),
+            None => "killed by signal".to_string(),
+        }
+    }
+
+    /// Full plain-text report — what the error overlay copies to the clipboard.
+    pub fn report(&self) -> String {
+        let mut out = format!("$ {}\n", self.command_line());
+        if let Some(ref cwd) = self.cwd {
+            out.push_str(&format!("cwd: {}\n", cwd));
+        }
+        out.push_str(&format!("exit: {}\n", self.exit_label()));
+        if !self.stderr.trim().is_empty() {
+            out.push_str(&format!("\nstderr:\n{}\n", self.stderr.trim_end()));
+        }
+        if !self.stdout.trim().is_empty() {
+            out.push_str(&format!("\nstdout:\n{}\n", self.stdout.trim_end()));
+        }
+        out
+    }
+}
+
+impl fmt::Display for CommandFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sub = self.args.first().map(String::as_str).unwrap_or("");
+        let detail = self.stderr.trim();
+        if detail.is_empty() {
+            write!(
+                f,
+                "{} {} failed (exit {})",
+                self.program,
+                sub,
+                self.exit_label()
+            )
+        } else {
+            write!(f, "{} {} failed: {}", self.program, sub, detail)
+        }
+    }
+}
+
+impl std::error::Error for CommandFailure {}
+
+/// `program arg…` with args quoted the way a POSIX shell would need them
+/// (whitespace, quotes or `$`-style metacharacters get single quotes).
+pub fn shell_line<S: AsRef<str>>(program: &str, args: &[S]) -> String {
+    let mut out = program.to_string();
+    for arg in args {
+        let arg = arg.as_ref();
+        out.push(' ');
+        let plain = !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./=:@^~+,%".contains(c));
+        if plain {
+            out.push_str(arg);
+        } else {
+            out.push('\'');
+            out.push_str(&arg.replace('\'', "'\\''"));
+            out.push('\'');
+        }
+    }
+    out
+}
+
+/// `Command::output` that also records the invocation, exit and duration in
+/// the debug log (`ER_LOG=debug`, see [`crate::debug_log`]).
+pub trait OutputLogged {
+    fn logged_output(&mut self) -> std::io::Result<Output>;
+}
+
+impl OutputLogged for Command {
+    fn logged_output(&mut self) -> std::io::Result<Output> {
+        use crate::debug_log::{self, Level};
+        if !debug_log::enabled(Level::Debug) {
+            return self.output();
+        }
+        let started = std::time::Instant::now();
+        let result = self.output();
+        let args: Vec<String> = self
+            .get_args()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect();
+        let mut line = shell_line(&self.get_program().to_string_lossy(), &args);
+        if let Some(dir) = self.get_current_dir() {
+            line.push_str(&format!(" (in {})", dir.display()));
+        }
+        let ms = started.elapsed().as_millis();
+        match &result {
+            Ok(out) if out.status.success() => {
+                debug_log::debug("cmd", format!("{} → ok in {}ms", line, ms));
+            }
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                let exit = out
+                    .status
+                    .code()
+                    .map_or_else(|| "signal".to_string(), |c| c.to_string());
+                debug_log::debug(
+                    "cmd",
+                    format!(
+                        "{} → exit {} in {}ms: {}",
+                        line,
+                        exit,
+                        ms,
+                        stderr.lines().next().unwrap_or("").trim()
+                    ),
+                );
+            }
+            Err(e) => debug_log::debug("cmd", format!("{} → failed to start: {}", line, e)),
+        }
+        result
+    }
+}
+
+/// Run `cmd` to completion, turning a non-zero exit into a [`CommandFailure`].
+pub fn run(cmd: &mut Command) -> Result<Output> {
+    let output = cmd
+        .logged_output()
+        .with_context(|| format!("Failed to run {}", cmd.get_program().to_string_lossy()))?;
+    if !output.status.success() {
+        return Err(CommandFailure::from_output(cmd, &output).into());
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_captures_failure_details() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cmd = Command::new("git");
+        cmd.args(["rev-parse", "--verify", "no such ref"])
+            .current_dir(dir.path());
+        let err = run(&mut cmd).unwrap_err();
+        let failure = CommandFailure::find(&err).expect("command failure in chain");
+        assert_eq!(failure.program, "git");
+        assert_eq!(
+            failure.command_line(),
+            "git rev-parse --verify 'no such ref'"
+        );
+        assert!(failure.exit_code.is_some_and(|c| c != 0));
+        assert!(!failure.stderr.is_empty());
+        assert!(err.to_string().starts_with("git rev-parse failed"));
+        assert!(failure.report().contains("stderr:\n"));
+    }
+
+    #[test]
+    fn find_sees_through_context() {
+        let failure = CommandFailure {
+            program: "gh".into(),
+            args: vec!["api".into()],
+            cwd: None,
+            exit_code: Some(1),
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        let err = anyhow::Error::new(failure.clone()).context("Sync failed");
+        assert_eq!(CommandFailure::find(&err), Some(&failure));
+        assert_eq!(failure.to_string(), "gh api failed (exit 1)");
+    }
+
+    #[test]
+    fn shell_line_quotes_only_when_needed() {
+        assert_eq!(
+            shell_line("git", &["diff", "main...HEAD", "--", "a b.rs", "it's"]),
+            "git diff main...HEAD -- 'a b.rs' 'it'\\''s'"
+        );
+        assert_eq!(
+            shell_line("git", &["log", "$HOME", ""]),
+            "git log '$HOME' ''"
+        );
+    }
+}
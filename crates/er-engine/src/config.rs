@@ -37,6 +37,843 @@ pub struct ErConfig {
     pub ai_hub: AiHubConfig,
     #[serde(default)]
     pub packages: PackagesConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub split: SplitConfig,
+    #[serde(default)]
+    pub clipboard: ClipboardConfig,
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub identity: IdentityConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    /// [review_hints] section — path pattern → things to check in matching
+    /// files (`"*.sql" = ["Check index usage"]`). Patterns follow the
+    /// compaction globs: `*.ext`, an exact file name, or `dir/**`.
+    #[serde(default)]
+    pub review_hints: BTreeMap<String, Vec<String>>,
+    /// [checklist_gates] section — path pattern → checklist items that must
+    /// be ticked before a matching file can be marked reviewed
+    /// (`"payments/**" = ["Reconciliation tested"]`). Same patterns as
+    /// `review_hints`
+    #[serde(default)]
+    pub checklist_gates: BTreeMap<String, Vec<String>>,
+    /// [hooks] section — event name (`file_reviewed`, `comment_added`, …, or
+    /// `*`) → shell commands run with the event as JSON on stdin (see
+    /// `events`). Only read from the global config
+    #[serde(default)]
+    pub hooks: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub tests: TestsConfig,
+    #[serde(default)]
+    pub commit_lint: CommitLintConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub push: PushConfig,
+    #[serde(default)]
+    pub large_files: LargeFilesConfig,
+    #[serde(default)]
+    pub review_order: ReviewOrderConfig,
+    #[serde(default)]
+    pub todos: TodosConfig,
+    #[serde(default)]
+    pub spell: SpellConfig,
+    #[serde(default)]
+    pub duplicates: DuplicatesConfig,
+    #[serde(default)]
+    pub complexity: ComplexityConfig,
+    #[serde(default)]
+    pub operational: OperationalConfig,
+    #[serde(default)]
+    pub migrations: MigrationsConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// [[review_templates]] — defaults per change type (migration,
+    /// dependency bump, hotfix, …), picked by branch name or PR label
+    #[serde(default)]
+    pub review_templates: Vec<ReviewTemplate>,
+    #[serde(default)]
+    pub updates: UpdatesConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
+}
+
+/// [usage] section — local usage stats behind `er stats` (see `usage`).
+/// Nothing recorded here leaves the machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageConfig {
+    /// Append a record per reviewed tab when er exits
+    #[serde(default = "default_true")]
+    pub record: bool,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self { record: true }
+    }
+}
+
+/// [updates] section — the opt-in check for a newer `er` (see `self_update`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatesConfig {
+    /// Look for a newer release in the background and hint at it in the
+    /// status bar
+    #[serde(default)]
+    pub check: bool,
+    /// Hours between two checks
+    #[serde(default = "default_update_interval_hours")]
+    pub interval_hours: u64,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            check: false,
+            interval_hours: default_update_interval_hours(),
+        }
+    }
+}
+
+fn default_update_interval_hours() -> u64 {
+    24
+}
+
+/// [tests] section — where a source file's tests live.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestsConfig {
+    /// Path templates from a source file to its tests, using `{dir}`,
+    /// `{pkg}`, `{stem}` and `{ext}`. Empty = the built-in conventions
+    /// ([`crate::git::DEFAULT_TEST_PATTERNS`]).
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl TestsConfig {
+    /// The configured conventions, or the built-in ones.
+    pub fn resolved_patterns(&self) -> Vec<String> {
+        if self.patterns.is_empty() {
+            crate::git::DEFAULT_TEST_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect()
+        } else {
+            self.patterns.clone()
+        }
+    }
+}
+
+/// [commit_lint] section — checks on commit messages in History mode and on
+/// commits made in the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitLintConfig {
+    /// Longest allowed subject line, in characters (0 = no limit)
+    #[serde(default = "default_max_subject")]
+    pub max_subject: usize,
+    /// Flag subjects that open with "Added" / "Fixes" / "Adding" instead of "Add"
+    #[serde(default = "default_true")]
+    pub imperative: bool,
+    /// Require Conventional Commits subjects (`type(scope): subject`)
+    #[serde(default)]
+    pub conventional: bool,
+    /// Require an issue reference (`#123`, `ABC-123` or an issue URL)
+    #[serde(default)]
+    pub require_issue: bool,
+}
+
+impl Default for CommitLintConfig {
+    fn default() -> Self {
+        Self {
+            max_subject: default_max_subject(),
+            imperative: true,
+            conventional: false,
+            require_issue: false,
+        }
+    }
+}
+
+fn default_max_subject() -> usize {
+    72
+}
+
+/// [audit] section — the branch hygiene and compliance audit and whether
+/// it gates approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Refuse "Approve PR" while an audit check fails
+    #[serde(default)]
+    pub gate_approve: bool,
+    /// Flag commits without a signature
+    #[serde(default)]
+    pub require_signed: bool,
+    /// Binary files larger than this (KB) count as large (0 = don't check)
+    #[serde(default = "default_max_blob_kb")]
+    pub max_blob_kb: u64,
+    /// Flag commits without a `Signed-off-by:` trailer (DCO)
+    #[serde(default)]
+    pub require_signoff: bool,
+    /// Text new files must contain near the top, e.g.
+    /// `SPDX-License-Identifier: Apache-2.0` (empty = don't check)
+    #[serde(default)]
+    pub license_header: String,
+    /// Files the license header applies to (`*.rs`, `src/**`, …); empty =
+    /// source and test files
+    #[serde(default)]
+    pub license_files: Vec<String>,
+    /// File name conventions for new files: pattern → `snake_case`,
+    /// `kebab-case`, `camelCase`, `PascalCase` or `lowercase`
+    #[serde(default)]
+    pub naming: BTreeMap<String, String>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            gate_approve: false,
+            require_signed: false,
+            max_blob_kb: default_max_blob_kb(),
+            require_signoff: false,
+            license_header: String::new(),
+            license_files: Vec::new(),
+            naming: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_max_blob_kb() -> u64 {
+    1024
+}
+
+/// [push] section — validations run before the push action.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PushConfig {
+    /// Run before pushing, in order: `[commands]` names (`test`, `lint`, …),
+    /// `[commands.custom]` names, or shell commands
+    #[serde(default)]
+    pub checks: Vec<String>,
+    /// Run the repo's own pre-push hook first (the push then skips it)
+    #[serde(default)]
+    pub run_hook: bool,
+}
+
+/// [review_order] section — the order a change is best read in (schema →
+/// domain → api → ui), used by the guided file order. A repo's own
+/// `.er-config.toml` takes precedence over the global one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReviewOrderConfig {
+    #[serde(default)]
+    pub phases: Vec<ReviewPhase>,
+}
+
+/// One step of the guided order: files matching any of `paths` (`*.ext`,
+/// an exact file name, or `dir/**`). The first matching phase wins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewPhase {
+    pub name: String,
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// One `[[review_templates]]` entry: the defaults for a change type, applied
+/// when a PR's branch or labels match. A repo's own `.er-config.toml` list
+/// takes precedence over the global one; the first matching entry wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewTemplate {
+    pub name: String,
+    /// Branch globs (`migration/*`); with neither this nor `labels`, the
+    /// branch prefix `<name>/` or a `<name>` label matches
+    #[serde(default)]
+    pub branches: Vec<String>,
+    /// PR labels, case-insensitive
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Filter expression set when no filter is active
+    #[serde(default)]
+    pub filter: String,
+    /// Checklist template (`.er-checklists/<name>.json`) for an empty checklist
+    #[serde(default)]
+    pub checklist: String,
+    /// Added to `[push] checks` while the template applies
+    #[serde(default)]
+    pub checks: Vec<String>,
+    /// Extra instructions for AI reviews
+    #[serde(default)]
+    pub prompt: String,
+}
+
+impl ReviewTemplate {
+    /// Whether a PR on `branch` with `labels` is this change type.
+    pub fn matches(&self, branch: &str, labels: &[String]) -> bool {
+        let has_label = |wanted: &str| labels.iter().any(|l| l.eq_ignore_ascii_case(wanted));
+        if self.branches.is_empty() && self.labels.is_empty() {
+            return branch.starts_with(&format!("{}/", self.name)) || has_label(&self.name);
+        }
+        self.labels.iter().any(|l| has_label(l))
+            || self
+                .branches
+                .iter()
+                .any(|pattern| glob::Pattern::new(pattern).is_ok_and(|p| p.matches(branch)))
+    }
+}
+
+/// [todos] section — markers listed in the TODOs hub when an added line
+/// has them, and whether each needs a ticket reference. A repo's own
+/// `.er-config.toml` takes precedence over the global one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodosConfig {
+    #[serde(default = "default_todo_markers")]
+    pub markers: Vec<String>,
+    /// Regex a new marker's line must match, e.g. `[A-Z]+-\d+`; until every
+    /// one does the review isn't ready (empty = no requirement)
+    #[serde(default)]
+    pub ticket_pattern: String,
+}
+
+impl Default for TodosConfig {
+    fn default() -> Self {
+        Self {
+            markers: default_todo_markers(),
+            ticket_pattern: String::new(),
+        }
+    }
+}
+
+impl TodosConfig {
+    /// The scanner for these settings. An invalid pattern requires nothing.
+    pub fn scanner(&self) -> crate::git::TodoScanner {
+        crate::git::TodoScanner {
+            markers: self.markers.clone(),
+            ticket: (!self.ticket_pattern.is_empty())
+                .then(|| regex::Regex::new(&self.ticket_pattern).ok())
+                .flatten(),
+        }
+    }
+}
+
+fn default_todo_markers() -> Vec<String> {
+    crate::git::DEFAULT_TODO_MARKERS
+        .iter()
+        .map(|m| m.to_string())
+        .collect()
+}
+
+/// [spell] section — spell checking of added doc comments, markdown and
+/// your own comments before they're pushed (see [`crate::spell`]). `words`
+/// from the repo's `.er-config.toml` are accepted too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpellConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Checker reading text on stdin and printing one misspelled word per
+    /// line; empty tries `hunspell -l` then `aspell list`
+    #[serde(default)]
+    pub command: String,
+    /// Dictionary passed to the default checkers as `{lang}`
+    #[serde(default = "default_spell_language")]
+    pub language: String,
+    /// Project words that are never flagged
+    #[serde(default)]
+    pub words: Vec<String>,
+}
+
+impl Default for SpellConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            command: String::new(),
+            language: default_spell_language(),
+            words: Vec::new(),
+        }
+    }
+}
+
+fn default_spell_language() -> String {
+    "en_US".to_string()
+}
+
+/// [duplicates] section — near-duplicate added blocks, listed as findings
+/// in the "duplicate" category (see [`crate::git::find_duplicates`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatesConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Shortest run of added lines checked, blank lines not counted
+    #[serde(default = "default_duplicate_min_lines")]
+    pub min_lines: usize,
+    /// Also look for copies among the repo's tracked files (working-tree
+    /// diffs only)
+    #[serde(default = "default_true")]
+    pub repo: bool,
+}
+
+impl Default for DuplicatesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_lines: default_duplicate_min_lines(),
+            repo: true,
+        }
+    }
+}
+
+fn default_duplicate_min_lines() -> usize {
+    6
+}
+
+/// [complexity] section — budgets for the functions a diff adds or
+/// changes; one over any of them is listed as a finding in the
+/// "complexity" category (see [`crate::git::function_metrics`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Lines from the signature to the closing brace
+    #[serde(default = "default_complexity_max_lines")]
+    pub max_lines: usize,
+    /// Blocks nested inside the body
+    #[serde(default = "default_complexity_max_nesting")]
+    pub max_nesting: usize,
+    /// Cyclomatic complexity: one plus the branch points
+    #[serde(default = "default_complexity_max_complexity")]
+    pub max_complexity: usize,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_lines: default_complexity_max_lines(),
+            max_nesting: default_complexity_max_nesting(),
+            max_complexity: default_complexity_max_complexity(),
+        }
+    }
+}
+
+impl ComplexityConfig {
+    /// Which of length, nesting and complexity are over budget.
+    pub fn over(&self, f: &crate::git::FunctionMetrics) -> [bool; 3] {
+        [
+            f.length() > self.max_lines,
+            f.nesting > self.max_nesting,
+            f.complexity > self.max_complexity,
+        ]
+    }
+}
+
+/// [operational] section — config files, environment variables and
+/// feature flags the diff changes, listed in the Ops panel and at the top
+/// of the hand-off (see [`crate::git::scan_operational`]). A repo's own
+/// `.er-config.toml` takes precedence over the global one. Empty lists
+/// take the built-in patterns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationalConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Globs of config files, e.g. `*.env`, `config/**`, `values.yaml`
+    #[serde(default)]
+    pub config_paths: Vec<String>,
+    /// Regexes of env var reads; the first capture group is the name
+    #[serde(default)]
+    pub env_patterns: Vec<String>,
+    /// Regexes of feature-flag checks; the first capture group is the name
+    #[serde(default)]
+    pub flag_patterns: Vec<String>,
+}
+
+impl Default for OperationalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            config_paths: Vec::new(),
+            env_patterns: Vec::new(),
+            flag_patterns: Vec::new(),
+        }
+    }
+}
+
+impl OperationalConfig {
+    /// The scanner for these settings, or none when disabled.
+    pub fn scanner(&self) -> Option<crate::git::OpsScanner> {
+        self.enabled.then(|| {
+            crate::git::OpsScanner::new(&self.config_paths, &self.env_patterns, &self.flag_patterns)
+        })
+    }
+}
+
+/// [migrations] section — which files are database migrations, reviewed
+/// for destructive statements and a down migration, each with a rollback
+/// checklist item. A repo's own `.er-config.toml` takes precedence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationsConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// `dir/` (that directory anywhere in the path) or a compaction glob
+    #[serde(default = "default_migration_paths")]
+    pub paths: Vec<String>,
+}
+
+impl Default for MigrationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            paths: default_migration_paths(),
+        }
+    }
+}
+
+fn default_migration_paths() -> Vec<String> {
+    crate::git::DEFAULT_MIGRATION_PATHS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+fn default_complexity_max_lines() -> usize {
+    80
+}
+
+fn default_complexity_max_nesting() -> usize {
+    4
+}
+
+fn default_complexity_max_complexity() -> usize {
+    15
+}
+
+/// [large_files] section — when a file shows a size-only summary (Enter
+/// loads it anyway) instead of its diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFilesConfig {
+    /// Diffs with more changed lines than this start compacted
+    #[serde(default = "default_large_max_lines")]
+    pub max_lines: usize,
+    /// Working-tree files larger than this (KB) start compacted (0 = no limit)
+    #[serde(default = "default_large_max_file_kb")]
+    pub max_file_kb: u64,
+    /// Learn per repo which compacted files get expanded and which loaded
+    /// ones are never scrolled, and compact accordingly
+    #[serde(default = "default_true")]
+    pub learn: bool,
+    /// Diffs with more changed lines than this ask which directories to
+    /// leave out before loading (0 = never ask)
+    #[serde(default = "default_large_guard_lines")]
+    pub guard_lines: usize,
+    /// Paths that never start compacted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub always_expand: Vec<String>,
+    /// Built-in compaction patterns (`*.lock`, `dist/**`, …) turned off
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub never_compact: Vec<String>,
+}
+
+impl Default for LargeFilesConfig {
+    fn default() -> Self {
+        Self {
+            max_lines: default_large_max_lines(),
+            max_file_kb: default_large_max_file_kb(),
+            learn: true,
+            guard_lines: default_large_guard_lines(),
+            always_expand: Vec::new(),
+            never_compact: Vec::new(),
+        }
+    }
+}
+
+impl LargeFilesConfig {
+    /// Compaction settings with these limits.
+    pub fn compaction(&self) -> crate::git::CompactionConfig {
+        let mut config = crate::git::CompactionConfig {
+            max_lines_before_compact: self.max_lines,
+            max_file_bytes: self.max_file_kb.saturating_mul(1024),
+            always_expand: self.always_expand.clone(),
+            ..Default::default()
+        };
+        config.patterns.retain(|p| !self.never_compact.contains(p));
+        config
+    }
+}
+
+fn default_large_max_lines() -> usize {
+    2000
+}
+
+fn default_large_max_file_kb() -> u64 {
+    2048
+}
+
+fn default_large_guard_lines() -> usize {
+    100_000
+}
+
+/// [github] section — keeping up with the PR while the app is open.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitHubConfig {
+    /// Pull PR comments in the background every N minutes (0 = off)
+    #[serde(default)]
+    pub comment_sync_minutes: u8,
+    /// Hours a PR may stay open before its review is overdue (0 = off)
+    #[serde(default)]
+    pub review_sla_hours: u8,
+    /// GitHub Enterprise Server host (`github.example.com`). Empty = the
+    /// origin remote's host when `gh` is logged in to it, else github.com
+    #[serde(default)]
+    pub host: String,
+    /// Remote the PR lives on (`upstream` in a fork clone). Empty = a remote
+    /// named `upstream` when there is one, else `origin`
+    #[serde(default)]
+    pub remote: String,
+}
+
+/// [storage] section — where review artifacts live (see [`crate::storage`]).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// `data` (empty): the user data dir. `git`: `.git/er/` inside the
+    /// clone. `repo`: a `.er/` directory in the working tree, for teams that
+    /// commit their review artifacts
+    #[serde(default)]
+    pub location: String,
+    /// `sqlite`: also index comments and AI review runs in `review.db` for
+    /// cross-review queries (`er comments`). Needs er built with the `sqlite`
+    /// feature. Empty = JSON sidecars only
+    #[serde(default)]
+    pub backend: String,
+    /// Where to add ignore patterns when er's files in the working tree
+    /// aren't gitignored: `exclude` (empty) = `.git/info/exclude`,
+    /// `gitignore` = the repo's `.gitignore`, `off` = don't offer
+    #[serde(default)]
+    pub ignore_artifacts: String,
+}
+
+/// [identity] section — who local comments are attributed to in exports,
+/// shared review state and pushed comments. Unset fields fall back to
+/// `git config user.name` / `user.email`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdentityConfig {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// [layout] section — width breakpoints and column sizing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Below this many columns the diff gets the whole width and the file
+    /// tree becomes a drawer (toggled with `\`).
+    #[serde(default = "default_narrow_below")]
+    pub narrow_below: u16,
+    /// At or above this many columns `wide_column` is shown.
+    #[serde(default = "default_wide_above")]
+    pub wide_above: u16,
+    /// Always-on third column on wide terminals when no panel is open:
+    /// `off`, `comments` (file detail), `summary` (AI summary) or `pr`.
+    #[serde(default = "default_wide_column")]
+    pub wide_column: String,
+    /// File tree width as a percentage of the terminal, clamped to
+    /// `tree_min..=tree_max`. `<`/`>` override it for the session.
+    #[serde(default = "default_tree_percent")]
+    pub tree_percent: u8,
+    #[serde(default = "default_tree_min")]
+    pub tree_min: u16,
+    #[serde(default = "default_tree_max")]
+    pub tree_max: u16,
+    /// Where panels open: `right` (a column beside the diff) or `bottom`
+    /// (under it).
+    #[serde(default = "default_panel_dock")]
+    pub panel_dock: String,
+    /// `panel_dock` per panel, keyed `comments`, `summary`, `pr`, `refs`
+    /// or `log`.
+    #[serde(default)]
+    pub panel_docks: BTreeMap<String, String>,
+    /// Height of the bottom dock as a percentage of the main area. Narrow
+    /// terminals stack every panel there.
+    #[serde(default = "default_bottom_percent")]
+    pub bottom_percent: u8,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            narrow_below: default_narrow_below(),
+            wide_above: default_wide_above(),
+            wide_column: default_wide_column(),
+            tree_percent: default_tree_percent(),
+            tree_min: default_tree_min(),
+            tree_max: default_tree_max(),
+            panel_dock: default_panel_dock(),
+            panel_docks: BTreeMap::new(),
+            bottom_percent: default_bottom_percent(),
+        }
+    }
+}
+
+fn default_narrow_below() -> u16 {
+    80
+}
+
+fn default_wide_above() -> u16 {
+    200
+}
+
+fn default_wide_column() -> String {
+    "off".into()
+}
+
+fn default_tree_percent() -> u8 {
+    20
+}
+
+fn default_tree_min() -> u16 {
+    20
+}
+
+fn default_tree_max() -> u16 {
+    48
+}
+
+fn default_panel_dock() -> String {
+    "right".into()
+}
+
+fn default_bottom_percent() -> u8 {
+    40
+}
+
+/// [accessibility] section — screen-reader output, high contrast, no color
+/// and ASCII-only glyphs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessibilityConfig {
+    /// Swap the configured theme for its contrast-dark/contrast-light twin.
+    #[serde(default)]
+    pub high_contrast: bool,
+    /// Render without colors (also on when `NO_COLOR` is set and non-empty).
+    #[serde(default)]
+    pub no_color: bool,
+    /// Replace box-drawing and symbol glyphs with ASCII markers.
+    #[serde(default)]
+    pub ascii: bool,
+    /// Where to write a plain-text line each time focus changes: `stderr`,
+    /// or a file / named-pipe path. Empty disables it.
+    #[serde(default)]
+    pub screen_reader: String,
+}
+
+impl AccessibilityConfig {
+    pub fn no_color_active(&self) -> bool {
+        self.no_color || std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty())
+    }
+
+    /// Theme to apply for the configured `display.theme`.
+    pub fn theme<'a>(&self, configured: &'a str) -> &'a str {
+        if !self.high_contrast {
+            return configured;
+        }
+        match configured {
+            "paper" | "daylight" | "contrast-light" | "daybreak" | "tokyo-night-day" => {
+                "contrast-light"
+            }
+            _ => "contrast-dark",
+        }
+    }
+}
+
+/// [clipboard] section — backends to try, in order. Names: `pbcopy`,
+/// `wl-copy`, `xclip`, `xsel`, `clip`, `osc52`; `auto` expands to the
+/// platform default (OSC 52 first over SSH, last otherwise).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardConfig {
+    #[serde(default = "default_clipboard_backends")]
+    pub backends: Vec<String>,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            backends: default_clipboard_backends(),
+        }
+    }
+}
+
+fn default_clipboard_backends() -> Vec<String> {
+    vec!["auto".to_string()]
+}
+
+/// [split] section — "open in split" command templates per terminal
+/// multiplexer. Placeholders: `{editor}` ($EDITOR, default `vi`), `{file}`
+/// (absolute path), `{line}`, `{repo}` (repo root).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitConfig {
+    #[serde(default = "default_split_tmux")]
+    pub tmux: String,
+    #[serde(default = "default_split_wezterm")]
+    pub wezterm: String,
+    #[serde(default = "default_split_kitty")]
+    pub kitty: String,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            tmux: default_split_tmux(),
+            wezterm: default_split_wezterm(),
+            kitty: default_split_kitty(),
+        }
+    }
+}
+
+fn default_split_tmux() -> String {
+    "tmux split-window -h -c {repo} {editor} +{line} {file}".to_string()
+}
+
+fn default_split_wezterm() -> String {
+    "wezterm cli split-pane --right --cwd {repo} -- {editor} +{line} {file}".to_string()
+}
+
+fn default_split_kitty() -> String {
+    "kitty @ launch --location=vsplit --cwd {repo} {editor} +{line} {file}".to_string()
+}
+
+/// [notifications] section — outbound webhook for review milestones.
+/// The payload carries a Slack-compatible `text` field plus structured keys,
+/// so it works with Slack incoming webhooks and generic JSON receivers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Webhook URL (empty / unset disables notifications)
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Events to send: `review_completed`, `approved`, `high_finding`, `comments_pushed`
+    #[serde(default = "default_notification_events")]
+    pub events: Vec<String>,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            events: default_notification_events(),
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// Whether `event` should be delivered (a URL is set and the event is enabled).
+    pub fn wants(&self, event: &str) -> bool {
+        self.webhook_url
+            .as_deref()
+            .is_some_and(|u| !u.trim().is_empty())
+            && self.events.iter().any(|e| e == event)
+    }
+}
+
+fn default_notification_events() -> Vec<String> {
+    crate::webhook::WebhookEvent::ALL
+        .iter()
+        .map(|e| e.as_str().to_string())
+        .collect()
 }
 
 /// [commands] section — configurable shell commands for hub actions.
@@ -60,6 +897,13 @@ pub struct CommandsConfig {
     /// Security scan (Verify hub)
     #[serde(default)]
     pub security: Option<String>,
+    /// Infrastructure plan, e.g. `terraform plan -no-color` (Verify hub)
+    #[serde(default)]
+    pub plan: Option<String>,
+    /// [commands.custom] — name → your own command (Verify hub), which may
+    /// also use `{file}`, `{line}` and `{hunk_patch}` (see `custom_commands`)
+    #[serde(default)]
+    pub custom: BTreeMap<String, String>,
 }
 
 /// Per-package command overrides for mono-repo setups.
@@ -135,6 +979,20 @@ pub struct FeatureFlags {
     /// Discover models from provider CLIs (`models_command`) and merge into pickers.
     #[serde(default = "default_true")]
     pub model_discovery: bool,
+    /// Ask before Ctrl+q quits with an unsent comment, unpushed local
+    /// comments or uncommitted in-app staging.
+    #[serde(default = "default_true")]
+    pub confirm_quit: bool,
+    /// Keep reviewed files in step with the PR's "viewed" checkboxes on GitHub.
+    #[serde(default = "default_true")]
+    pub github_viewed_sync: bool,
+    /// Publish/pull review state through `refs/er/reviews/<branch>` on origin.
+    #[serde(default)]
+    pub shared_review: bool,
+    /// Offer to filter out lockfiles, snapshots and generated files when they
+    /// make up most of the diff.
+    #[serde(default = "default_true")]
+    pub noise_filter_hint: bool,
 }
 
 /// Claude-compatible effort levels passed as `--effort` when spawning agents.
@@ -346,6 +1204,10 @@ impl Default for FeatureFlags {
             view_tour: true,
             arena: true,
             model_discovery: true,
+            confirm_quit: true,
+            github_viewed_sync: false,
+            shared_review: false,
+            noise_filter_hint: true,
         }
     }
 }
@@ -1220,6 +2082,7 @@ impl ErConfig {
             "lint" => self.commands.lint.clone(),
             "typecheck" => self.commands.typecheck.clone(),
             "security" => self.commands.security.clone(),
+            "plan" => self.commands.plan.clone(),
             _ => None,
         }
     }
@@ -1379,6 +2242,186 @@ fn parse_config_from_path(path: &std::path::Path) -> Option<ErConfig> {
     }
 }
 
+/// Re-read the global config after it changed on disk. Unlike
+/// [`load_global_config`], a file that doesn't parse is an error (with the
+/// parser's message) rather than the defaults, so a half-saved edit never
+/// wipes the running settings.
+pub fn reload_global_config() -> std::result::Result<ErConfig, String> {
+    let path = managed_config_path();
+    if !path.exists() {
+        return Ok(load_global_config());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut config: ErConfig = toml::from_str(&content).map_err(|e| e.message().to_string())?;
+    supplement_ai_hub(&mut config.ai_hub);
+    crate::config_overrides::apply(&mut config, &crate::config_overrides::active())?;
+    Ok(config)
+}
+
+/// The `[name]` section of the repo's own `.er-config.toml` with the
+/// overrides under it applied (see `config_overrides`), when the file has one.
+fn load_repo_section(repo_root: &str, name: &str) -> Option<toml::Value> {
+    let path = std::path::Path::new(repo_root).join(".er-config.toml");
+    let mut section = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|mut table| table.remove(name))?;
+    crate::config_overrides::apply_to_section(name, &mut section);
+    Some(section)
+}
+
+/// `[review_hints]` from the repo's own `.er-config.toml`, so a team can keep
+/// its checklists next to the code. Empty when the file or section is missing.
+pub fn load_repo_review_hints(repo_root: &str) -> BTreeMap<String, Vec<String>> {
+    load_repo_section(repo_root, "review_hints")
+        .and_then(|hints| hints.try_into().ok())
+        .unwrap_or_default()
+}
+
+/// `[checklist_gates]` from the repo's own `.er-config.toml`. Empty when the
+/// file or section is missing.
+pub fn load_repo_checklist_gates(repo_root: &str) -> BTreeMap<String, Vec<String>> {
+    load_repo_section(repo_root, "checklist_gates")
+        .and_then(|gates| gates.try_into().ok())
+        .unwrap_or_default()
+}
+
+/// `[review_order]` phases from the repo's own `.er-config.toml`. Empty when
+/// the file or section is missing.
+pub fn load_repo_review_order(repo_root: &str) -> Vec<ReviewPhase> {
+    load_repo_section(repo_root, "review_order")
+        .and_then(|order| order.try_into::<ReviewOrderConfig>().ok())
+        .map(|order| order.phases)
+        .unwrap_or_default()
+}
+
+/// `[[review_templates]]` from the repo's own `.er-config.toml`. Empty when
+/// the file or section is missing.
+pub fn load_repo_review_templates(repo_root: &str) -> Vec<ReviewTemplate> {
+    load_repo_section(repo_root, "review_templates")
+        .and_then(|templates| templates.try_into::<Vec<ReviewTemplate>>().ok())
+        .unwrap_or_default()
+}
+
+fn load_repo_github(repo_root: &str) -> Option<GitHubConfig> {
+    load_repo_section(repo_root, "github").and_then(|github| github.try_into::<GitHubConfig>().ok())
+}
+
+/// `[github] host` from the repo's own `.er-config.toml`, when set.
+pub fn load_repo_github_host(repo_root: &str) -> Option<String> {
+    load_repo_github(repo_root)
+        .map(|github| github.host)
+        .filter(|host| !host.trim().is_empty())
+}
+
+/// `[github] remote` from the repo's own `.er-config.toml`, when set.
+pub fn load_repo_github_remote(repo_root: &str) -> Option<String> {
+    load_repo_github(repo_root)
+        .map(|github| github.remote)
+        .filter(|remote| !remote.trim().is_empty())
+}
+
+/// `[storage] location` from the repo's own `.er-config.toml`, when set.
+pub fn load_repo_storage_location(repo_root: &str) -> Option<String> {
+    load_repo_section(repo_root, "storage")
+        .and_then(|storage| storage.try_into::<StorageConfig>().ok())
+        .map(|storage| storage.location)
+        .filter(|location| !location.trim().is_empty())
+}
+
+/// `[todos]` from the repo's own `.er-config.toml`, when it has one.
+pub fn load_repo_todos(repo_root: &str) -> Option<TodosConfig> {
+    load_repo_section(repo_root, "todos").and_then(|todos| todos.try_into::<TodosConfig>().ok())
+}
+
+/// `[operational]` from the repo's own `.er-config.toml`, when it has one.
+pub fn load_repo_operational(repo_root: &str) -> Option<OperationalConfig> {
+    load_repo_section(repo_root, "operational")
+        .and_then(|ops| ops.try_into::<OperationalConfig>().ok())
+}
+
+/// `[migrations]` from the repo's own `.er-config.toml`, when it has one.
+pub fn load_repo_migrations(repo_root: &str) -> Option<MigrationsConfig> {
+    load_repo_section(repo_root, "migrations")
+        .and_then(|migrations| migrations.try_into::<MigrationsConfig>().ok())
+}
+
+/// `[large_files]` from the repo's own `.er-config.toml`, when it has one.
+pub fn load_repo_large_files(repo_root: &str) -> Option<LargeFilesConfig> {
+    load_repo_section(repo_root, "large_files")
+        .and_then(|large| large.try_into::<LargeFilesConfig>().ok())
+}
+
+/// Write `value` as the `[name]` section of the repo's `.er-config.toml`,
+/// creating the file when missing. Only that section is rewritten; the
+/// rest of the file, comments included, stays as it was.
+pub fn save_repo_section<T: Serialize>(repo_root: &str, name: &str, value: &T) -> Result<()> {
+    let path = std::path::Path::new(repo_root).join(".er-config.toml");
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut table = toml::Table::new();
+    table.insert(name.to_string(), toml::Value::try_from(value)?);
+    let section = toml::to_string_pretty(&table)?;
+
+    let header = format!("[{}]", name);
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|l| {
+        l.trim()
+            .strip_prefix(&header)
+            .is_some_and(|rest| rest.trim().is_empty() || rest.trim_start().starts_with('#'))
+    });
+    let mut out = String::new();
+    match start {
+        Some(start) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with('['))
+                .map_or(lines.len(), |i| start + 1 + i);
+            for line in &lines[..start] {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push_str(&section);
+            if end < lines.len() {
+                out.push('\n');
+                for line in &lines[end..] {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        None => {
+            out.push_str(&content);
+            if !content.is_empty() {
+                if !content.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            out.push_str(&section);
+        }
+    }
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+/// `[spell] words` from the repo's own `.er-config.toml` — the team's
+/// custom dictionary. Empty when the file or section is missing.
+pub fn load_repo_spell_words(repo_root: &str) -> Vec<String> {
+    load_repo_section(repo_root, "spell")
+        .and_then(|spell| spell.try_into::<SpellConfig>().ok())
+        .map(|spell| spell.words)
+        .unwrap_or_default()
+}
+
+/// Hints from `hints` whose pattern matches `path`, in pattern order.
+pub fn review_hints_for<'a>(hints: &'a BTreeMap<String, Vec<String>>, path: &str) -> Vec<&'a str> {
+    hints
+        .iter()
+        .filter(|(pattern, _)| crate::git::compact_files_match(pattern, path))
+        .flat_map(|(_, items)| items.iter().map(String::as_str))
+        .collect()
+}
+
 /// Load the global config from managed storage (with one-time legacy migration).
 pub fn load_global_config() -> ErConfig {
     let managed = managed_config_path();
@@ -1398,13 +2441,15 @@ pub fn load_global_config() -> ErConfig {
             .unwrap_or_default()
     };
     supplement_ai_hub(&mut config.ai_hub);
+    crate::config_overrides::apply_active(&mut config);
     config
 }
 
 /// Save config to managed storage (`<storage_root>/config.toml`).
 ///
 /// Strips runtime-only discovered models before serializing so discovery
-/// overlays never leak into the persisted file.
+/// overlays never leak into the persisted file, and keeps the file's own
+/// values under keys overridden from the environment or `--set`.
 pub fn save_config(config: &ErConfig) -> Result<()> {
     let root = crate::storage::storage_root();
     std::fs::create_dir_all(&root)?;
@@ -1418,7 +2463,18 @@ pub fn save_config(config: &ErConfig) -> Result<()> {
     for provider in to_save.ai_hub.providers.values_mut() {
         provider.models.retain(|m| !m.discovered);
     }
-    let content = toml::to_string_pretty(&to_save)?;
+    let overrides = crate::config_overrides::active();
+    let content = if overrides.is_empty() {
+        toml::to_string_pretty(&to_save)?
+    } else {
+        let file = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .unwrap_or_default();
+        let mut table = toml::Table::try_from(&to_save)?;
+        crate::config_overrides::restore_file_values(&mut table, &file, &overrides);
+        toml::to_string_pretty(&table)?
+    };
     std::fs::write(&tmp_path, content)?;
     std::fs::rename(&tmp_path, &path)?;
     Ok(())
@@ -1554,6 +2610,13 @@ fn general_config_hub_items(config: &ErConfig) -> Vec<ConfigItem> {
             get: |c| c.commands.security.clone().unwrap_or_default(),
             set: |c, v| c.commands.security = if v.is_empty() { None } else { Some(v) },
         },
+        ConfigItem::StringEdit {
+            label: "Plan".into(),
+            description: "Render an infrastructure plan".into(),
+            placeholder: "e.g. terraform plan -no-color".into(),
+            get: |c| c.commands.plan.clone().unwrap_or_default(),
+            set: |c, v| c.commands.plan = if v.is_empty() { None } else { Some(v) },
+        },
         ConfigItem::BoolToggle {
             label: "Push summary to PR body".into(),
             description: "Auto-push summary to GitHub PR".into(),
@@ -1621,6 +2684,29 @@ fn general_config_hub_items(config: &ErConfig) -> Vec<ConfigItem> {
             get: |c| c.features.model_discovery,
             set: |c, v| c.features.model_discovery = v,
         },
+        ConfigItem::SectionHeader("Notifications".into()),
+        ConfigItem::StringEdit {
+            label: "Webhook URL".into(),
+            description: "Post review milestones (Slack-compatible JSON)".into(),
+            placeholder: "e.g. https://hooks.slack.com/services/...".into(),
+            get: |c| c.notifications.webhook_url.clone().unwrap_or_default(),
+            set: |c, v| c.notifications.webhook_url = if v.is_empty() { None } else { Some(v) },
+        },
+        ConfigItem::SectionHeader("Updates".into()),
+        ConfigItem::BoolToggle {
+            label: "Check for updates".into(),
+            description: "Hint in the status bar when a newer er is out".into(),
+            get: |c| c.updates.check,
+            set: |c, v| c.updates.check = v,
+        },
+        ConfigItem::SectionHeader("Usage".into()),
+        ConfigItem::BoolToggle {
+            label: "Record usage stats".into(),
+            description: "Keep review time, sizes and features used on this machine for er stats"
+                .into(),
+            get: |c| c.usage.record,
+            set: |c, v| c.usage.record = v,
+        },
         ConfigItem::SectionHeader("Watched Paths".into()),
         ConfigItem::StringCycle {
             label: "Diff mode".into(),
@@ -1804,6 +2890,12 @@ fn terminal_config_hub_items(_config: &ErConfig) -> Vec<ConfigItem> {
             get: |c| c.display.split_diff,
             set: |c, v| c.display.split_diff = v,
         },
+        ConfigItem::Action {
+            label: "Reset view preferences".into(),
+            description: "Layers, panel, sort and split diff back to the defaults for this repo"
+                .into(),
+            action_id: "reset_view_prefs",
+        },
         ConfigItem::BoolToggle {
             label: "Auto-expand context".into(),
             description: "Pick unified context per file (small → more, big → less)".into(),
@@ -1818,6 +2910,37 @@ fn terminal_config_hub_items(_config: &ErConfig) -> Vec<ConfigItem> {
             get: |c| c.display.tab_width,
             set: |c, v| c.display.tab_width = v,
         },
+        ConfigItem::SectionHeader("Layout".into()),
+        ConfigItem::StringCycle {
+            label: "Wide third column".into(),
+            description: "Always-on column on wide terminals".into(),
+            options: &["off", "comments", "summary", "pr"],
+            get: |c| c.layout.wide_column.clone(),
+            set: |c, v| c.layout.wide_column = v,
+        },
+        ConfigItem::NumberEdit {
+            label: "File tree width %".into(),
+            description: "Share of the terminal the file tree takes".into(),
+            min: 5,
+            max: 60,
+            get: |c| c.layout.tree_percent,
+            set: |c, v| c.layout.tree_percent = v,
+        },
+        ConfigItem::StringCycle {
+            label: "Panel dock".into(),
+            description: "Open panels beside the diff or under it".into(),
+            options: &["right", "bottom"],
+            get: |c| c.layout.panel_dock.clone(),
+            set: |c, v| c.layout.panel_dock = v,
+        },
+        ConfigItem::NumberEdit {
+            label: "Bottom dock height %".into(),
+            description: "Share of the main area panels docked at the bottom take".into(),
+            min: 10,
+            max: 80,
+            get: |c| c.layout.bottom_percent,
+            set: |c, v| c.layout.bottom_percent = v,
+        },
         ConfigItem::SectionHeader("Key Hints".into()),
         ConfigItem::BoolToggle {
             label: "Navigation hints".into(),
@@ -1843,6 +2966,74 @@ fn terminal_config_hub_items(_config: &ErConfig) -> Vec<ConfigItem> {
             get: |c| c.hints.verbose,
             set: |c, v| c.hints.verbose = v,
         },
+        ConfigItem::SectionHeader("Safety".into()),
+        ConfigItem::BoolToggle {
+            label: "Confirm quit".into(),
+            description: "Ask before quitting with unsent or unpushed work".into(),
+            get: |c| c.features.confirm_quit,
+            set: |c, v| c.features.confirm_quit = v,
+        },
+        ConfigItem::SectionHeader("Collaboration".into()),
+        ConfigItem::BoolToggle {
+            label: "Sync viewed files".into(),
+            description: "Mirror reviewed files to the PR's viewed checkboxes".into(),
+            get: |c| c.features.github_viewed_sync,
+            set: |c, v| c.features.github_viewed_sync = v,
+        },
+        ConfigItem::NumberEdit {
+            label: "Auto-sync comments (min)".into(),
+            description: "Pull PR comments in the background (0 = off)".into(),
+            min: 0,
+            max: 60,
+            get: |c| c.github.comment_sync_minutes,
+            set: |c, v| c.github.comment_sync_minutes = v,
+        },
+        ConfigItem::NumberEdit {
+            label: "Review SLA (hours)".into(),
+            description: "Colour PR age against a review deadline (0 = off)".into(),
+            min: 0,
+            max: 240,
+            get: |c| c.github.review_sla_hours,
+            set: |c, v| c.github.review_sla_hours = v,
+        },
+        ConfigItem::BoolToggle {
+            label: "Shared review ref".into(),
+            description: "Share questions & progress via refs/er/reviews/<branch>".into(),
+            get: |c| c.features.shared_review,
+            set: |c, v| c.features.shared_review = v,
+        },
+        ConfigItem::SectionHeader("Accessibility".into()),
+        ConfigItem::BoolToggle {
+            label: "High contrast".into(),
+            description: "Use the contrast variant of the theme".into(),
+            get: |c| c.accessibility.high_contrast,
+            set: |c, v| c.accessibility.high_contrast = v,
+        },
+        ConfigItem::BoolToggle {
+            label: "No color".into(),
+            description: "Monochrome rendering (NO_COLOR also enables it)".into(),
+            get: |c| c.accessibility.no_color,
+            set: |c, v| c.accessibility.no_color = v,
+        },
+        ConfigItem::BoolToggle {
+            label: "ASCII glyphs".into(),
+            description: "Replace box-drawing and symbols with ASCII".into(),
+            get: |c| c.accessibility.ascii,
+            set: |c, v| c.accessibility.ascii = v,
+        },
+        ConfigItem::SectionHeader("Storage".into()),
+        ConfigItem::Action {
+            label: "Clean up review data".into(),
+            description: "Remove reviews of deleted branches and closed PRs, and empty files"
+                .into(),
+            action_id: "clean_review_data",
+        },
+        ConfigItem::SectionHeader("Feedback".into()),
+        ConfigItem::Action {
+            label: "Report a bug…".into(),
+            description: "Save a sanitized bug report, or copy a gh issue create for it".into(),
+            action_id: "bug_report",
+        },
         ConfigItem::SectionHeader("AI".into()),
         ConfigItem::Action {
             label: "Copy review.json".into(),
@@ -1984,6 +3175,10 @@ mod tests {
                 view_tour: true,
                 arena: false,
                 model_discovery: true,
+                confirm_quit: false,
+                github_viewed_sync: true,
+                shared_review: true,
+                noise_filter_hint: false,
             },
             display: DisplayConfig {
                 tab_width: 8,
@@ -3320,4 +4515,33 @@ mod tests {
         supplement_ai_hub(&mut hub);
         assert!(!hub.providers.contains_key("cursor"));
     }
+
+    #[test]
+    fn review_hints_load_from_the_repo_and_match_by_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".er-config.toml"),
+            r#"
+            [display]
+            tab_width = 2
+
+            [review_hints]
+            "*.sql" = ["Check index usage"]
+            Dockerfile = ["Pin the base image"]
+            "migrations/**" = ["Is it reversible?"]
+            "#,
+        )
+        .unwrap();
+        let hints = load_repo_review_hints(&dir.path().to_string_lossy());
+        assert_eq!(
+            review_hints_for(&hints, "migrations/001_users.sql"),
+            ["Check index usage", "Is it reversible?"]
+        );
+        assert_eq!(
+            review_hints_for(&hints, "deploy/Dockerfile"),
+            ["Pin the base image"]
+        );
+        assert!(review_hints_for(&hints, "src/main.rs").is_empty());
+        assert!(load_repo_review_hints("/nonexistent").is_empty());
+    }
 }
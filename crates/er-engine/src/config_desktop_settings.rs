@@ -204,6 +204,11 @@ pub fn apply_config_field(config: &mut ErConfig, key: &str, value: ConfigFieldVa
                 config.commands.security = if v.is_empty() { None } else { Some(v) };
             }
         }
+        "commands.plan" => {
+            if let ConfigFieldValue::String(v) = value {
+                config.commands.plan = if v.is_empty() { None } else { Some(v) };
+            }
+        }
         "summary.push_to_pr" => {
             if let ConfigFieldValue::Bool(v) = value {
                 config.summary.push_to_pr = v;
@@ -238,6 +243,16 @@ pub fn apply_config_field(config: &mut ErConfig, key: &str, value: ConfigFieldVa
                 config.ai_hub.max_concurrent_reviews = n;
             }
         }
+        "notifications.webhook_url" => {
+            if let ConfigFieldValue::String(v) = value {
+                let trimmed = v.trim();
+                config.notifications.webhook_url = if trimmed.is_empty() {
+                    None
+                } else {
+                    Some(trimmed.to_string())
+                };
+            }
+        }
         "watched.diff_mode" => {
             if let ConfigFieldValue::String(v) = value {
                 if v == "content" || v == "snapshot" {
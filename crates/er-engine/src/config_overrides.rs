@@ -0,0 +1,384 @@
+//! Config overrides on top of the files: `ER_SECTION__KEY` environment
+//! variables and `--set section.key=value` on the command line, the latter
+//! winning. They apply wherever config is read — the global config, the
+//! per-repo sections and hot reloads — and are never written back by a
+//! save. `er config show` prints the merged result with each value's source.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::config::{self, ErConfig};
+
+/// Environment variables that override a key start with this
+const ENV_PREFIX: &str = "ER_";
+/// …and separate the key's parts with this: `ER_LARGE_FILES__MAX_LINES`.
+/// A single `_` belongs to the key, so `ER_LOG` and friends never match.
+const ENV_SEPARATOR: &str = "__";
+
+static CLI_OVERRIDES: OnceLock<Vec<Override>> = OnceLock::new();
+
+/// Where an override came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideSource {
+    /// The environment variable's name
+    Env(String),
+    Cli,
+}
+
+/// One `key = value` set outside the config files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Override {
+    /// Dotted path, `display.theme`
+    pub key: String,
+    pub value: toml::Value,
+    pub source: OverrideSource,
+}
+
+impl Override {
+    /// `env ER_DISPLAY__THEME` / `--set`
+    pub fn source_label(&self) -> String {
+        match &self.source {
+            OverrideSource::Env(var) => format!("env {}", var),
+            OverrideSource::Cli => "--set".into(),
+        }
+    }
+}
+
+/// A value as TOML when it parses as one (`42`, `true`, `["a", "b"]`), else
+/// the plain string, so `display.theme=light` needs no quotes.
+pub fn parse_value(raw: &str) -> toml::Value {
+    format!("v = {}", raw)
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// `--set key=value`.
+pub fn parse_set(arg: &str) -> Result<Override, String> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("--set {}: expected key=value", arg))?;
+    let key = key.trim();
+    if key.is_empty() || key.split('.').any(str::is_empty) {
+        return Err(format!("--set {}: expected a key like display.theme", arg));
+    }
+    Ok(Override {
+        key: key.to_string(),
+        value: parse_value(value.trim()),
+        source: OverrideSource::Cli,
+    })
+}
+
+/// The overrides in `vars`, by key.
+pub fn from_env(vars: impl IntoIterator<Item = (String, String)>) -> Vec<Override> {
+    let mut overrides: Vec<Override> = vars
+        .into_iter()
+        .filter_map(|(var, value)| {
+            let rest = var.strip_prefix(ENV_PREFIX)?;
+            if !rest.contains(ENV_SEPARATOR) {
+                return None;
+            }
+            let parts: Vec<String> = rest
+                .split(ENV_SEPARATOR)
+                .map(str::to_ascii_lowercase)
+                .collect();
+            if parts.iter().any(String::is_empty) {
+                return None;
+            }
+            Some(Override {
+                key: parts.join("."),
+                value: parse_value(&value),
+                source: OverrideSource::Env(var),
+            })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.key.cmp(&b.key));
+    overrides
+}
+
+/// Check the environment's overrides and `sets` against the config and keep
+/// `sets` for the rest of the process. Call once at startup, before config
+/// is loaded.
+pub fn install(sets: &[String]) -> Result<(), String> {
+    let cli = sets
+        .iter()
+        .map(|s| parse_set(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut all = from_env(std::env::vars());
+    all.extend(cli.iter().cloned());
+    let mut probe = ErConfig::default();
+    config::supplement_ai_hub(&mut probe.ai_hub);
+    for o in &all {
+        apply_one(&mut probe, o).map_err(|e| format!("{}: {}", o.source_label(), e))?;
+    }
+    let _ = CLI_OVERRIDES.set(cli);
+    Ok(())
+}
+
+/// The overrides in effect: the environment's, then `--set`'s.
+pub fn active() -> Vec<Override> {
+    let mut all = from_env(std::env::vars());
+    if let Some(cli) = CLI_OVERRIDES.get() {
+        all.extend(cli.iter().cloned());
+    }
+    all
+}
+
+fn get_path<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let mut parts = key.split('.');
+    let mut value = table.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_table()?.get(part)?;
+    }
+    Some(value)
+}
+
+fn set_path(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let parts: Vec<&str> = key.split('.').collect();
+    let (last, parents) = parts.split_last().expect("keys are never empty");
+    let mut table = table;
+    for part in parents {
+        let entry = table
+            .entry(part.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().expect("just made a table");
+    }
+    table.insert(last.to_string(), value);
+}
+
+fn remove_path(table: &mut toml::Table, key: &str) {
+    let parts: Vec<&str> = key.split('.').collect();
+    let (last, parents) = parts.split_last().expect("keys are never empty");
+    let mut table = table;
+    for part in parents {
+        match table.get_mut(*part).and_then(toml::Value::as_table_mut) {
+            Some(t) => table = t,
+            None => return,
+        }
+    }
+    table.remove(*last);
+}
+
+fn apply_one(config: &mut ErConfig, o: &Override) -> Result<(), String> {
+    let mut table = toml::Table::try_from(&*config).map_err(|e| e.to_string())?;
+    let section = o.key.split('.').next().unwrap_or_default();
+    if !table.contains_key(section) {
+        return Err(format!("unknown config key {}", o.key));
+    }
+    set_path(&mut table, &o.key, o.value.clone());
+    let updated: ErConfig = toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| format!("{}: {}", o.key, e.message()))?;
+    // A field serde doesn't know is dropped on the way in
+    let back = toml::Table::try_from(&updated).map_err(|e| e.to_string())?;
+    if get_path(&back, &o.key).is_none() {
+        return Err(format!("unknown config key {}", o.key));
+    }
+    *config = updated;
+    Ok(())
+}
+
+/// Apply `overrides` to `config` in order, stopping at the first that
+/// doesn't fit it.
+pub fn apply(config: &mut ErConfig, overrides: &[Override]) -> Result<(), String> {
+    for o in overrides {
+        apply_one(config, o).map_err(|e| format!("{}: {}", o.source_label(), e))?;
+    }
+    Ok(())
+}
+
+/// [`apply`] the active overrides, logging the ones that don't fit. For
+/// the lenient loaders.
+pub fn apply_active(config: &mut ErConfig) {
+    for o in active() {
+        if let Err(e) = apply_one(config, &o) {
+            crate::debug_log::warn("config", format!("{}: {}", o.source_label(), e));
+        }
+    }
+}
+
+/// Apply the active overrides under `[name]` to that section as read from a
+/// repo's `.er-config.toml`.
+pub fn apply_to_section(name: &str, section: &mut toml::Value) {
+    let Some(table) = section.as_table_mut() else {
+        return;
+    };
+    for o in active() {
+        if let Some(rest) = o.key.strip_prefix(name).and_then(|k| k.strip_prefix('.')) {
+            set_path(table, rest, o.value.clone());
+        }
+    }
+}
+
+/// Put the file's own values back under the overridden keys of `table`, a
+/// config about to be saved, so an override never reaches the file.
+pub fn restore_file_values(table: &mut toml::Table, file: &toml::Table, overrides: &[Override]) {
+    for o in overrides {
+        match get_path(file, &o.key) {
+            Some(value) => set_path(table, &o.key, value.clone()),
+            None => remove_path(table, &o.key),
+        }
+    }
+}
+
+/// One key of the effective config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveValue {
+    pub key: String,
+    pub value: toml::Value,
+    /// `default`, the file it came from, `env ER_…` or `--set`
+    pub source: String,
+}
+
+fn read_table(path: &Path) -> toml::Table {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse().ok())
+        .unwrap_or_default()
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut Vec<(String, toml::Value)>) {
+    for (k, v) in table {
+        let key = if prefix.is_empty() {
+            k.clone()
+        } else {
+            format!("{}.{}", prefix, k)
+        };
+        match v {
+            toml::Value::Table(t) if !t.is_empty() => flatten(&key, t, out),
+            _ => out.push((key, v.clone())),
+        }
+    }
+}
+
+/// The config as `er` would run with it in `repo_root`: global file,
+/// the repo's sections (each replacing the global one) and the overrides,
+/// key by key with where each value came from.
+pub fn effective(repo_root: Option<&str>) -> Vec<EffectiveValue> {
+    let global_path = config::managed_config_path();
+    let global_file = read_table(&global_path);
+    let repo_path = repo_root.map(|r| Path::new(r).join(".er-config.toml"));
+    let repo_file = repo_path.as_deref().map(read_table).unwrap_or_default();
+
+    let loaded = config::load_global_config();
+    let mut table = toml::Table::try_from(&loaded).unwrap_or_default();
+    let defaults = toml::Table::try_from(ErConfig::default()).unwrap_or_default();
+    for (name, section) in &repo_file {
+        let Some(mut merged) = defaults.get(name).cloned() else {
+            continue;
+        };
+        if let (Some(base), Some(own)) = (merged.as_table_mut(), section.as_table()) {
+            let mut leaves = Vec::new();
+            flatten("", own, &mut leaves);
+            for (k, v) in leaves {
+                set_path(base, &k, v);
+            }
+        } else {
+            merged = section.clone();
+        }
+        apply_to_section(name, &mut merged);
+        table.insert(name.clone(), merged);
+    }
+
+    let overrides = active();
+    let mut leaves = Vec::new();
+    flatten("", &table, &mut leaves);
+    leaves
+        .into_iter()
+        .map(|(key, value)| {
+            let section = key.split('.').next().unwrap_or_default();
+            let source = if let Some(o) = overrides.iter().rev().find(|o| o.key == key) {
+                o.source_label()
+            } else if repo_file.contains_key(section) {
+                if get_path(&repo_file, &key).is_some() {
+                    repo_path
+                        .as_deref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default()
+                } else {
+                    "default".into()
+                }
+            } else if get_path(&global_file, &key).is_some() {
+                global_path.display().to_string()
+            } else {
+                "default".into()
+            };
+            EffectiveValue { key, value, source }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(key: &str, value: &str) -> Override {
+        parse_set(&format!("{}={}", key, value)).unwrap()
+    }
+
+    #[test]
+    fn env_names_and_set_args_become_dotted_keys() {
+        let env = from_env([
+            ("ER_LARGE_FILES__MAX_LINES".to_string(), "42".to_string()),
+            ("ER_DISPLAY__THEME".to_string(), "light".to_string()),
+            ("ER_LOG".to_string(), "debug".to_string()),
+            ("ER_CONFIG_PATH".to_string(), "/tmp/c.toml".to_string()),
+            ("PATH".to_string(), "/bin".to_string()),
+        ]);
+        let keys: Vec<&str> = env.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["display.theme", "large_files.max_lines"]);
+        assert_eq!(env[1].value, toml::Value::Integer(42));
+        assert_eq!(env[1].source_label(), "env ER_LARGE_FILES__MAX_LINES");
+
+        assert_eq!(set("features.view_history", "false").value, false.into());
+        assert_eq!(
+            set("watched.paths", r#"["a", "b"]"#).value,
+            toml::Value::Array(vec!["a".into(), "b".into()])
+        );
+        assert!(parse_set("display.theme").is_err());
+        assert!(parse_set("display..theme=x").is_err());
+    }
+
+    #[test]
+    fn overrides_apply_by_type_and_reject_unknown_keys() {
+        let mut config = ErConfig::default();
+        apply(
+            &mut config,
+            &[
+                set("display.theme", "light"),
+                set("large_files.max_lines", "42"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.display.theme, "light");
+        assert_eq!(config.large_files.max_lines, 42);
+
+        let err = apply(&mut config, &[set("display.nope", "1")]).unwrap_err();
+        assert_eq!(err, "--set: unknown config key display.nope");
+        assert!(apply(&mut config, &[set("nope.theme", "1")]).is_err());
+        assert!(apply(&mut config, &[set("large_files.max_lines", "many")]).is_err());
+        assert_eq!(config.large_files.max_lines, 42);
+    }
+
+    #[test]
+    fn saving_keeps_the_files_values_under_overridden_keys() {
+        let file: toml::Table = "[display]\ntheme = \"slate\"\n".parse().unwrap();
+        let mut config = ErConfig::default();
+        let overrides = [
+            set("display.theme", "light"),
+            set("large_files.max_lines", "42"),
+        ];
+        apply(&mut config, &overrides).unwrap();
+        let mut table = toml::Table::try_from(&config).unwrap();
+        restore_file_values(&mut table, &file, &overrides);
+        assert_eq!(
+            get_path(&table, "display.theme"),
+            Some(&toml::Value::from("slate"))
+        );
+        assert_eq!(get_path(&table, "large_files.max_lines"), None);
+    }
+}
@@ -148,6 +148,14 @@ fn general_desktop_fields(config: &ErConfig) -> Vec<ConfigHubFieldDto> {
             value: config.commands.security.clone().unwrap_or_default(),
             strict: true,
         },
+        ConfigHubFieldDto::Text {
+            key: "commands.plan".into(),
+            label: "Plan".into(),
+            description: "Render an infrastructure plan".into(),
+            placeholder: "e.g. terraform plan -no-color".into(),
+            value: config.commands.plan.clone().unwrap_or_default(),
+            strict: true,
+        },
         ConfigHubFieldDto::Bool {
             key: "summary.push_to_pr".into(),
             label: "Push summary to PR body".into(),
@@ -193,6 +201,17 @@ fn general_desktop_fields(config: &ErConfig) -> Vec<ConfigHubFieldDto> {
             description: "List models from provider CLIs (desktop + TUI)".into(),
             value: config.features.model_discovery,
         },
+        ConfigHubFieldDto::Section {
+            title: "Notifications".into(),
+        },
+        ConfigHubFieldDto::Text {
+            key: "notifications.webhook_url".into(),
+            label: "Webhook URL".into(),
+            description: "Post review milestones (Slack-compatible JSON)".into(),
+            placeholder: "e.g. https://hooks.slack.com/services/...".into(),
+            value: config.notifications.webhook_url.clone().unwrap_or_default(),
+            strict: true,
+        },
         ConfigHubFieldDto::Section {
             title: "Watched Paths".into(),
         },
@@ -0,0 +1,88 @@
+//! Crash reports written by the TUI's panic hook.
+//!
+//! The hook restores the terminal first, then writes
+//! `.er-crash-<timestamp>.txt` (panic message, location, backtrace and the
+//! last lines from [`crate::debug_log::recent`]) and prints where it went.
+
+use std::path::{Path, PathBuf};
+
+/// Where new issues are filed; printed alongside the report path.
+pub const ISSUES_URL: &str = "https://github.com/VilfredSikker/easy-review/issues";
+
+/// `.er-crash-20260102T030405Z.txt` for a Unix time in seconds.
+pub fn report_file_name(unix_secs: u64) -> String {
+    let stamp: String = crate::sync::format_iso8601(unix_secs)
+        .chars()
+        .filter(|c| *c != '-' && *c != ':')
+        .collect();
+    format!(".er-crash-{}.txt", stamp)
+}
+
+/// Plain-text crash report.
+pub fn report(message: &str, location: Option<&str>, backtrace: &str, recent: &[String]) -> String {
+    let mut out = format!(
+        "er {} crashed ({} {})\n\npanic: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        message
+    );
+    if let Some(location) = location {
+        out.push_str(&format!("at: {}\n", location));
+    }
+    out.push_str("\nlast actions:\n");
+    if recent.is_empty() {
+        out.push_str("  (none recorded)\n");
+    }
+    for line in recent {
+        out.push_str(&format!("  {}\n", line));
+    }
+    out.push_str(&format!("\nbacktrace:\n{}\n", backtrace.trim_end()));
+    out
+}
+
+/// Write `contents` into `dir`, falling back to the temp dir when `dir` is
+/// not writable. Returns the path written.
+pub fn write_report(dir: &Path, unix_secs: u64, contents: &str) -> std::io::Result<PathBuf> {
+    let name = report_file_name(unix_secs);
+    let path = dir.join(&name);
+    match std::fs::write(&path, contents) {
+        Ok(()) => Ok(path),
+        Err(_) => {
+            let fallback = std::env::temp_dir().join(name);
+            std::fs::write(&fallback, contents)?;
+            Ok(fallback)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_is_a_compact_utc_stamp() {
+        assert_eq!(
+            report_file_name(1_700_000_000),
+            ".er-crash-20231114T221320Z.txt"
+        );
+    }
+
+    #[test]
+    fn report_has_panic_actions_and_backtrace() {
+        let recent = vec!["INFO notify 3 files changed".to_string()];
+        let text = report(
+            "index out of bounds",
+            Some("src/ui.rs:10:5"),
+            "0: main",
+            &recent,
+        );
+        assert!(text.contains("panic: index out of bounds\nat: src/ui.rs:10:5"));
+        assert!(text.contains("last actions:\n  INFO notify 3 files changed\n"));
+        assert!(text.ends_with("backtrace:\n0: main\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_report(dir.path(), 0, &text).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), text);
+    }
+}
@@ -0,0 +1,275 @@
+//! Opt-in debug log file (`ER_LOG=debug`).
+//!
+//! The TUI owns the terminal, so nothing may go to stdout/stderr while it
+//! runs. Instead, when `ER_LOG` names a level (`error`, `warn`, `info` or
+//! `debug`; it can sit next to the desktop log groups, e.g.
+//! `ER_LOG=debug,arena`) lines are appended to
+//! `<storage root>/logs/er.log`, or `ER_LOG_FILE` when set. The file rotates
+//! at [`MAX_LOG_BYTES`] into `er.log.1` … `er.log.<KEEP_ROTATED>`.
+//!
+//! Targets in use: `cmd` (external commands), `watch` (file watcher),
+//! `sync` (GitHub sync), `notify` (every toast, so errors shown to the user
+//! are kept).
+//!
+//! The last [`RECENT_LINES`] info-or-worse lines (plus whatever the file is
+//! logging) are also kept in memory, log file or not, so a crash report can
+//! say what happened just before.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Rotate once the live file passes this size.
+pub const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the live one.
+pub const KEEP_ROTATED: usize = 3;
+/// Lines kept in memory for [`recent`].
+pub const RECENT_LINES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+}
+
+impl Level {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" | "warning" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" | "trace" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+/// 0 = off, otherwise the highest enabled `Level`.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+struct Sink {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl Sink {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Sink { path, file, size })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.size >= MAX_LOG_BYTES {
+            rotate(&self.path);
+            match OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&self.path)
+            {
+                Ok(file) => {
+                    self.file = file;
+                    self.size = 0;
+                }
+                // Keep appending to the old handle rather than losing lines
+                Err(_) => self.size = 0,
+            }
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.size += line.len() as u64 + 1;
+        }
+    }
+}
+
+/// Shift `er.log.N` up by one (dropping the oldest) and move the live file to `.1`.
+fn rotate(path: &Path) {
+    let numbered = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+    let _ = std::fs::remove_file(numbered(KEEP_ROTATED));
+    for n in (1..KEEP_ROTATED).rev() {
+        let _ = std::fs::rename(numbered(n), numbered(n + 1));
+    }
+    let _ = std::fs::rename(path, numbered(1));
+}
+
+/// Highest level named in an `ER_LOG` value (other entries are log groups).
+pub fn level_from_spec(spec: &str) -> Option<Level> {
+    spec.split(',').filter_map(Level::parse).max()
+}
+
+/// Default log path: `<storage root>/logs/er.log`.
+pub fn default_path() -> PathBuf {
+    crate::storage::storage_root().join("logs").join("er.log")
+}
+
+/// Read `ER_LOG` / `ER_LOG_FILE` and open the log. Returns the file path
+/// when logging is on; a file that can't be opened leaves it off.
+pub fn init_from_env() -> Option<PathBuf> {
+    let level = level_from_spec(&std::env::var("ER_LOG").ok()?)?;
+    let path = std::env::var("ER_LOG_FILE")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(default_path);
+    init(level, path)
+}
+
+/// Start logging at `level` to `path`.
+pub fn init(level: Level, path: PathBuf) -> Option<PathBuf> {
+    let sink = Sink::open(path.clone()).ok()?;
+    if let Ok(mut guard) = SINK.lock() {
+        *guard = Some(sink);
+    }
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+    log(
+        Level::Info,
+        "er",
+        format!(
+            "log started (er {}, level {})",
+            env!("CARGO_PKG_VERSION"),
+            level.label().to_ascii_lowercase()
+        ),
+    );
+    Some(path)
+}
+
+/// Whether a `level` line would be written — check before building an
+/// expensive message.
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn log(level: Level, target: &str, message: impl AsRef<str>) {
+    let to_file = enabled(level);
+    if !to_file && level > Level::Info {
+        return;
+    }
+    let line = format_line(now_millis(), level, target, message.as_ref());
+    if to_file {
+        if let Ok(mut guard) = SINK.lock() {
+            if let Some(sink) = guard.as_mut() {
+                sink.write_line(&line);
+            }
+        }
+    }
+    if let Ok(mut recent) = RECENT.lock() {
+        if recent.len() == RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back(line);
+    }
+}
+
+/// The most recent log lines, oldest first.
+pub fn recent() -> Vec<String> {
+    RECENT
+        .lock()
+        .map(|r| r.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub fn error(target: &str, message: impl AsRef<str>) {
+    log(Level::Error, target, message);
+}
+
+pub fn warn(target: &str, message: impl AsRef<str>) {
+    log(Level::Warn, target, message);
+}
+
+pub fn info(target: &str, message: impl AsRef<str>) {
+    log(Level::Info, target, message);
+}
+
+pub fn debug(target: &str, message: impl AsRef<str>) {
+    log(Level::Debug, target, message);
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// `2026-01-02T03:04:05.678Z DEBUG cmd    git status` — multi-line messages
+/// are folded onto one line so each entry stays greppable.
+fn format_line(millis: u64, level: Level, target: &str, message: &str) -> String {
+    let stamp = crate::sync::format_iso8601(millis / 1000);
+    let stamp = stamp.trim_end_matches('Z');
+    format!(
+        "{}.{:03}Z {:<5} {:<6} {}",
+        stamp,
+        millis % 1000,
+        level.label(),
+        target,
+        message.trim_end().replace('\n', " ⏎ ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_picks_highest_level_and_ignores_groups() {
+        assert_eq!(level_from_spec("debug"), Some(Level::Debug));
+        assert_eq!(level_from_spec("arena, warn"), Some(Level::Warn));
+        assert_eq!(level_from_spec("error,info"), Some(Level::Info));
+        assert_eq!(level_from_spec("arena,profile"), None);
+    }
+
+    #[test]
+    fn lines_are_stamped_and_single_line() {
+        let line = format_line(1_700_000_000_123, Level::Debug, "cmd", "git diff\nfatal\n");
+        assert_eq!(
+            line,
+            "2023-11-14T22:13:20.123Z DEBUG cmd    git diff ⏎ fatal"
+        );
+    }
+
+    #[test]
+    fn recent_keeps_info_lines_without_a_log_file() {
+        info("test", "recent-marker");
+        debug("test", "debug-marker");
+        let recent = recent();
+        assert!(recent.iter().any(|l| l.ends_with("recent-marker")));
+        assert!(enabled(Level::Debug) || !recent.iter().any(|l| l.ends_with("debug-marker")));
+    }
+
+    #[test]
+    fn rotation_shifts_numbered_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("er.log");
+        for n in 1..=KEEP_ROTATED {
+            std::fs::write(format!("{}.{}", path.display(), n), n.to_string()).unwrap();
+        }
+        std::fs::write(&path, "live").unwrap();
+        rotate(&path);
+        assert!(!path.exists());
+        let read = |n: usize| std::fs::read_to_string(format!("{}.{}", path.display(), n)).unwrap();
+        assert_eq!(read(1), "live");
+        assert_eq!(read(2), "1");
+        assert_eq!(read(KEEP_ROTATED), (KEEP_ROTATED - 1).to_string());
+    }
+}
@@ -0,0 +1,485 @@
+//! `er doctor`: check the environment er depends on — git, `gh`, a
+//! clipboard backend, the file-watch limit, the terminal's colours and
+//! OSC 52, and the config files — and say how to fix what isn't right.
+//!
+//! Every check is cheap and read-only; nothing here changes the machine.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::clipboard::{self, ClipboardBackend};
+use crate::command::OutputLogged;
+use crate::config::{self, ErConfig};
+use crate::config_overrides;
+
+/// Oldest git er is tested against.
+pub const MIN_GIT: (u64, u64) = (2, 20);
+
+/// How a check came out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// Works, with something missing or degraded
+    Warn,
+    /// Part of er won't work
+    Fail,
+}
+
+impl Status {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Status::Pass => "✓",
+            Status::Warn => "!",
+            Status::Fail => "✗",
+        }
+    }
+}
+
+/// One line of the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub detail: String,
+    /// What to do about a warning or failure
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check, for the repo at `repo_root` when there is one.
+pub fn run(repo_root: Option<&str>) -> Vec<Check> {
+    let env = |name: &str| std::env::var(name).ok();
+    let global = config::load_global_config();
+    let mut checks = vec![git_version(), git_identity(repo_root), gh()];
+    checks.push(clipboard_backend(&clipboard::resolve_order(
+        &global.clipboard,
+    )));
+    if let Some(root) = repo_root {
+        if let Some(check) = watch_limit(root) {
+            checks.push(check);
+        }
+    }
+    checks.push(truecolor(&env));
+    checks.push(osc52(&env, tmux_option("allow-passthrough").as_deref()));
+    checks.push(config_file("config.toml", &config::managed_config_path()));
+    if let Some(root) = repo_root {
+        checks.push(config_file(
+            ".er-config.toml",
+            &Path::new(root).join(".er-config.toml"),
+        ));
+    }
+    checks.push(overrides());
+    checks
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+fn stdout_of(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).logged_output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `(major, minor, patch)` from `git version 2.39.5 (Apple Git-154)`.
+fn parse_git_version(output: &str) -> Option<(u64, u64, u64)> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|p| p.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+fn git_version() -> Check {
+    let Some(output) = stdout_of("git", &["--version"]) else {
+        return Check::fail(
+            "git",
+            "not found on PATH",
+            "Install git (https://git-scm.com/downloads)",
+        );
+    };
+    match parse_git_version(&output) {
+        Some((major, minor, patch)) if (major, minor) >= MIN_GIT => {
+            Check::pass("git", format!("{}.{}.{}", major, minor, patch))
+        }
+        Some((major, minor, patch)) => Check::fail(
+            "git",
+            format!(
+                "{}.{}.{} is older than {}.{}",
+                major, minor, patch, MIN_GIT.0, MIN_GIT.1
+            ),
+            "Upgrade git — worktrees and some diff options need a newer one",
+        ),
+        None => Check::warn(
+            "git",
+            format!("unrecognised version {:?}", output),
+            "Make sure `git` on PATH is git itself, not a wrapper",
+        ),
+    }
+}
+
+fn git_identity(repo_root: Option<&str>) -> Check {
+    let get = |key: &str| {
+        let mut cmd = Command::new("git");
+        if let Some(root) = repo_root {
+            cmd.current_dir(root);
+        }
+        let output = cmd.args(["config", "--get", key]).logged_output().ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+    match (get("user.name"), get("user.email")) {
+        (Some(name), Some(email)) => Check::pass("git identity", format!("{} <{}>", name, email)),
+        _ => Check::warn(
+            "git identity",
+            "user.name or user.email isn't set, so committing from er fails",
+            "git config --global user.name \"Your Name\" && git config --global user.email you@example.com",
+        ),
+    }
+}
+
+fn gh() -> Check {
+    let Some(version) = stdout_of("gh", &["--version"]) else {
+        return Check::warn(
+            "gh",
+            "not found — PR review, GitHub comments and --pr need it",
+            "Install the GitHub CLI (https://cli.github.com), then run gh auth login",
+        );
+    };
+    let version = version
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("gh version ")
+        .to_string();
+    let authed = crate::github::gh_command()
+        .args(["auth", "status"])
+        .logged_output()
+        .is_ok_and(|o| o.status.success());
+    if authed {
+        Check::pass("gh", format!("{}, authenticated", version))
+    } else {
+        Check::warn(
+            "gh",
+            format!("{}, not authenticated", version),
+            "gh auth login",
+        )
+    }
+}
+
+fn clipboard_backend(order: &[ClipboardBackend]) -> Check {
+    let native = order
+        .iter()
+        .find(|b| b.program().is_some_and(on_path))
+        .map(|b| b.name());
+    match native {
+        Some(name) => Check::pass("clipboard", name),
+        None if order.contains(&ClipboardBackend::Osc52) => Check::warn(
+            "clipboard",
+            "no clipboard tool found, copying relies on OSC 52 (see below)",
+            if cfg!(target_os = "linux") {
+                "Install wl-clipboard (Wayland) or xclip"
+            } else {
+                "Add a native backend to [clipboard] backends"
+            },
+        ),
+        None => Check::fail(
+            "clipboard",
+            "none of the [clipboard] backends is available",
+            "Add \"auto\" or \"osc52\" to [clipboard] backends",
+        ),
+    }
+}
+
+/// Directories under `root`, counting no further than `cap`.
+fn count_dirs(root: &Path, cap: usize) -> usize {
+    let mut count = 0;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        count += 1;
+        if count >= cap {
+            break;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                stack.push(entry.path());
+            }
+        }
+    }
+    count
+}
+
+/// The recursive watch takes one inotify watch per directory, shared with
+/// every other watcher the user runs (editors, dev servers).
+fn watch_limit_check(limit: u64, dirs: usize) -> Check {
+    let detail = format!("{} directories to watch, limit {}", dirs, limit);
+    let fix = "echo fs.inotify.max_user_watches=524288 | sudo tee /etc/sysctl.d/60-er.conf && sudo sysctl --system";
+    if dirs as u64 >= limit {
+        Check::fail(
+            "file watching",
+            format!("{} — live refresh will stop", detail),
+            fix,
+        )
+    } else if dirs as u64 * 2 >= limit {
+        Check::warn(
+            "file watching",
+            format!("{} — little room for other watchers", detail),
+            fix,
+        )
+    } else {
+        Check::pass("file watching", detail)
+    }
+}
+
+fn watch_limit(repo_root: &str) -> Option<Check> {
+    let limit: u64 = std::fs::read_to_string("/proc/sys/fs/inotify/max_user_watches")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let cap = usize::try_from(limit.saturating_add(1)).unwrap_or(usize::MAX);
+    Some(watch_limit_check(
+        limit,
+        count_dirs(Path::new(repo_root), cap),
+    ))
+}
+
+fn truecolor(env: &dyn Fn(&str) -> Option<String>) -> Check {
+    let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return Check::pass("truecolor", format!("COLORTERM={}", colorterm));
+    }
+    let term = env("TERM").unwrap_or_default();
+    Check::warn(
+        "truecolor",
+        format!(
+            "COLORTERM isn't truecolor (TERM={}) — theme colours may look off",
+            term
+        ),
+        "Use a truecolor terminal, or export COLORTERM=truecolor if yours is one",
+    )
+}
+
+fn tmux_option(name: &str) -> Option<String> {
+    std::env::var_os("TMUX")?;
+    stdout_of("tmux", &["show-options", "-gv", name])
+}
+
+/// Terminals known to take OSC 52 writes, by `TERM_PROGRAM` or `TERM`.
+const OSC52_TERMINALS: &[&str] = &[
+    "iTerm.app",
+    "WezTerm",
+    "ghostty",
+    "kitty",
+    "alacritty",
+    "foot",
+    "rio",
+    "tabby",
+    "vscode",
+];
+
+/// `passthrough` is tmux's `allow-passthrough`, which the wrapped OSC 52
+/// sequence needs on tmux 3.3 and later.
+fn osc52(env: &dyn Fn(&str) -> Option<String>, passthrough: Option<&str>) -> Check {
+    if env("TMUX").is_some() && passthrough == Some("off") {
+        return Check::warn(
+            "OSC 52",
+            "tmux drops the clipboard sequence (allow-passthrough off)",
+            "Add `set -g allow-passthrough on` to ~/.tmux.conf",
+        );
+    }
+    let program = env("TERM_PROGRAM").unwrap_or_default();
+    let term = env("TERM").unwrap_or_default();
+    let known = OSC52_TERMINALS.iter().find(|t| {
+        program.eq_ignore_ascii_case(t) || term.to_ascii_lowercase().contains(&t.to_lowercase())
+    });
+    if let Some(name) = known {
+        Check::pass("OSC 52", format!("{} supports it", name))
+    } else if env("WT_SESSION").is_some() {
+        Check::pass("OSC 52", "Windows Terminal supports it")
+    } else if program == "Apple_Terminal" {
+        Check::warn(
+            "OSC 52",
+            "Terminal.app ignores it — copying over SSH won't reach your clipboard",
+            "Use iTerm2, WezTerm, kitty or Ghostty for copying over SSH",
+        )
+    } else {
+        let name = if program.is_empty() { term } else { program };
+        Check::warn(
+            "OSC 52",
+            format!("unknown whether {} supports it", name),
+            "Copy something with y over SSH to try it; set [clipboard] backends if it doesn't arrive",
+        )
+    }
+}
+
+/// Whether `content` parses as er config. Unknown sections are usually
+/// typos and are silently ignored at load, so they're called out.
+fn config_content(name: &'static str, content: &str) -> Check {
+    let table: toml::Table = match content.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            let e: toml::de::Error = e;
+            return Check::fail(
+                name,
+                e.message().to_string(),
+                format!("Fix the TOML syntax in {}", name),
+            );
+        }
+    };
+    if let Err(e) = toml::from_str::<ErConfig>(content) {
+        return Check::fail(
+            name,
+            e.message().to_string(),
+            "See docs/config-reference.md for each key's type",
+        );
+    }
+    let known = toml::Table::try_from(ErConfig::default()).unwrap_or_default();
+    let unknown: Vec<&str> = table
+        .keys()
+        .filter(|k| !known.contains_key(*k))
+        .map(String::as_str)
+        .collect();
+    if unknown.is_empty() {
+        Check::pass(name, format!("{} sections", table.len()))
+    } else {
+        Check::warn(
+            name,
+            format!("unknown sections ignored: {}", unknown.join(", ")),
+            "Check the section names against docs/config-reference.md",
+        )
+    }
+}
+
+fn config_file(name: &'static str, path: &Path) -> Check {
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let mut check = config_content(name, &content);
+            check.detail = format!("{} — {}", path.display(), check.detail);
+            check
+        }
+        Err(_) if !path.exists() => Check::pass(name, "not present, defaults apply"),
+        Err(e) => Check::fail(
+            name,
+            format!("{} — {}", path.display(), e),
+            "Make the file readable",
+        ),
+    }
+}
+
+fn overrides() -> Check {
+    let active = config_overrides::active();
+    if active.is_empty() {
+        return Check::pass("overrides", "no ER_* variables or --set");
+    }
+    match config_overrides::apply(&mut ErConfig::default(), &active) {
+        Ok(()) => Check::pass("overrides", format!("{} applied", active.len())),
+        Err(e) => Check::fail(
+            "overrides",
+            e,
+            "Fix or unset the ER_SECTION__KEY variable (er config show lists the rest)",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn versions_limits_and_terminals_grade_as_expected() {
+        assert_eq!(
+            parse_git_version("git version 2.39.5 (Apple Git-154)"),
+            Some((2, 39, 5))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45, 1))
+        );
+        assert_eq!(parse_git_version("hub version 2.14"), None);
+
+        assert_eq!(watch_limit_check(8192, 100).status, Status::Pass);
+        assert_eq!(watch_limit_check(8192, 5000).status, Status::Warn);
+        assert_eq!(watch_limit_check(8192, 8193).status, Status::Fail);
+
+        let truecolor_env = env_of(&[("COLORTERM", "truecolor")]);
+        assert_eq!(truecolor(&truecolor_env).status, Status::Pass);
+        let plain = env_of(&[("TERM", "xterm-256color")]);
+        assert_eq!(truecolor(&plain).status, Status::Warn);
+
+        let kitty = env_of(&[("TERM", "xterm-kitty")]);
+        assert_eq!(osc52(&kitty, None).status, Status::Pass);
+        let apple = env_of(&[("TERM_PROGRAM", "Apple_Terminal")]);
+        assert_eq!(osc52(&apple, None).status, Status::Warn);
+        let tmux = env_of(&[
+            ("TMUX", "/tmp/tmux-1000/default,1,0"),
+            ("TERM_PROGRAM", "WezTerm"),
+        ]);
+        let check = osc52(&tmux, Some("off"));
+        assert!(check.fix.unwrap().contains("allow-passthrough on"));
+        assert_eq!(osc52(&tmux, Some("on")).status, Status::Pass);
+    }
+
+    #[test]
+    fn config_content_flags_syntax_types_and_unknown_sections() {
+        let ok = config_content("config.toml", "[display]\ntab_width = 4\n");
+        assert_eq!(ok.status, Status::Pass);
+
+        let syntax = config_content("config.toml", "[display\n");
+        assert_eq!(syntax.status, Status::Fail);
+
+        let wrong_type = config_content("config.toml", "[display]\ntab_width = \"four\"\n");
+        assert_eq!(wrong_type.status, Status::Fail);
+
+        let typo = config_content(".er-config.toml", "[todo]\nenabled = true\n");
+        assert_eq!(typo.status, Status::Warn);
+        assert!(typo.detail.contains("todo"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Review events and the hooks that run on them.
+//!
+//! The app publishes an [`Event`] when something happens worth reacting to —
+//! a file marked reviewed, a comment added, the diff refreshed, a finding
+//! dismissed. In-process subscribers get every event ([`subscribe`]); the
+//! `[hooks]` config maps event names to shell commands that are run in the
+//! background from the repo root with the event as JSON on stdin, so
+//! integrations (metrics, custom notifications) don't need patches to er.
+//!
+//! Hooks come from the global config only: a cloned repo's `.er-config.toml`
+//! never gets to run commands.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+/// Hook key that matches every event.
+pub const ANY_EVENT: &str = "*";
+
+/// Something that happened in a review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A file was marked reviewed, or unmarked.
+    FileReviewed { file: String, reviewed: bool },
+    /// Every file in the diff is reviewed.
+    ReviewCompleted { files: usize },
+    /// A question, note or GitHub comment was added.
+    CommentAdded {
+        /// `question`, `note` or `github`
+        kind: String,
+        id: String,
+        file: String,
+        line: Option<usize>,
+        text: String,
+        reply_to: Option<String>,
+    },
+    /// Local comments were pushed to GitHub.
+    CommentsPushed { count: usize },
+    /// The diff was re-read.
+    DiffRefreshed {
+        files: usize,
+        adds: usize,
+        dels: usize,
+        diff_hash: String,
+    },
+    /// An AI finding was dismissed.
+    FindingDismissed { finding_id: String },
+    /// A file, comment or finding was snoozed until `due` (unix seconds).
+    FollowUpAdded {
+        /// `file`, `comment` or `finding`
+        kind: String,
+        file: String,
+        line: Option<usize>,
+        label: String,
+        due: u64,
+    },
+    /// A snoozed follow-up came due.
+    FollowUpDue {
+        kind: String,
+        file: String,
+        line: Option<usize>,
+        label: String,
+        due: u64,
+    },
+}
+
+impl Event {
+    /// Every event name, for docs and config validation.
+    pub const NAMES: [&'static str; 8] = [
+        "file_reviewed",
+        "review_completed",
+        "comment_added",
+        "comments_pushed",
+        "diff_refreshed",
+        "finding_dismissed",
+        "follow_up_added",
+        "follow_up_due",
+    ];
+
+    /// The `[hooks]` key / payload `event` value.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::FileReviewed { .. } => "file_reviewed",
+            Event::ReviewCompleted { .. } => "review_completed",
+            Event::CommentAdded { .. } => "comment_added",
+            Event::CommentsPushed { .. } => "comments_pushed",
+            Event::DiffRefreshed { .. } => "diff_refreshed",
+            Event::FindingDismissed { .. } => "finding_dismissed",
+            Event::FollowUpAdded { .. } => "follow_up_added",
+            Event::FollowUpDue { .. } => "follow_up_due",
+        }
+    }
+}
+
+/// Which review an event belongs to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EventContext {
+    pub repo_root: String,
+    pub branch: String,
+    pub pr: Option<u64>,
+}
+
+/// What subscribers and hooks receive: the event, where it happened and when.
+#[derive(Debug, Clone, Serialize)]
+pub struct Envelope<'a> {
+    #[serde(flatten)]
+    pub event: &'a Event,
+    #[serde(flatten)]
+    pub context: &'a EventContext,
+    /// Unix seconds
+    pub at: u64,
+}
+
+type Subscriber = Box<dyn Fn(&Envelope) + Send>;
+
+static SUBSCRIBERS: Mutex<Vec<(usize, Subscriber)>> = Mutex::new(Vec::new());
+
+static NEXT_ID: Mutex<usize> = Mutex::new(0);
+
+/// `[hooks]`: event name (or `*`) → commands.
+static HOOKS: Mutex<BTreeMap<String, Vec<String>>> = Mutex::new(BTreeMap::new());
+
+/// Handle for [`unsubscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subscription(usize);
+
+/// Call `f` for every event published from now on, on the publishing thread.
+pub fn subscribe(f: impl Fn(&Envelope) + Send + 'static) -> Subscription {
+    let id = NEXT_ID
+        .lock()
+        .map(|mut next| {
+            *next += 1;
+            *next
+        })
+        .unwrap_or_default();
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.push((id, Box::new(f)));
+    }
+    Subscription(id)
+}
+
+pub fn unsubscribe(subscription: Subscription) {
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.retain(|(id, _)| *id != subscription.0);
+    }
+}
+
+/// Replace the configured hooks (`[hooks]` from the global config).
+pub fn set_hooks(hooks: &BTreeMap<String, Vec<String>>) {
+    for name in hooks.keys() {
+        if name != ANY_EVENT && !Event::NAMES.contains(&name.as_str()) {
+            crate::debug_log::warn("hooks", format!("unknown event `{}` in [hooks]", name));
+        }
+    }
+    if let Ok(mut current) = HOOKS.lock() {
+        *current = hooks.clone();
+    }
+}
+
+/// Commands configured for `event`: its own, then the `*` ones.
+pub fn hooks_for(event: &str) -> Vec<String> {
+    let Ok(hooks) = HOOKS.lock() else {
+        return Vec::new();
+    };
+    [event, ANY_EVENT]
+        .iter()
+        .filter_map(|name| hooks.get(*name))
+        .flatten()
+        .filter(|command| !command.trim().is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Tell subscribers about `event` and start its hooks.
+pub fn publish(context: &EventContext, event: Event) {
+    let envelope = Envelope {
+        event: &event,
+        context,
+        at: crate::outbox::unix_now(),
+    };
+    if let Ok(subscribers) = SUBSCRIBERS.lock() {
+        for (_, subscriber) in subscribers.iter() {
+            subscriber(&envelope);
+        }
+    }
+    let commands = hooks_for(event.name());
+    if commands.is_empty() {
+        return;
+    }
+    let payload = match serde_json::to_string(&envelope) {
+        Ok(payload) => payload,
+        Err(e) => {
+            crate::debug_log::warn("hooks", format!("{}: {}", event.name(), e));
+            return;
+        }
+    };
+    let dir = context.repo_root.clone();
+    std::thread::spawn(move || {
+        for command in commands {
+            if let Err(e) = run_hook(&command, &dir, &payload) {
+                crate::debug_log::warn("hooks", format!("`{}`: {:#}", command, e));
+            }
+        }
+    });
+}
+
+/// Run one hook with `payload` on stdin; a non-zero exit is an error.
+fn run_hook(command: &str, dir: &str, payload: &str) -> anyhow::Result<()> {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    if !dir.is_empty() {
+        cmd.current_dir(dir);
+    }
+    let mut child = cmd.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that doesn't read stdin closes it early; that's fine
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex as StdMutex};
+
+    #[test]
+    fn subscribers_get_the_event_as_flat_json() {
+        let seen = Arc::new(StdMutex::new(Vec::new()));
+        let sub = {
+            let seen = seen.clone();
+            subscribe(move |envelope| {
+                if envelope.context.repo_root == "/tmp/events-test" {
+                    seen.lock()
+                        .unwrap()
+                        .push(serde_json::to_value(envelope).unwrap());
+                }
+            })
+        };
+        let context = EventContext {
+            repo_root: "/tmp/events-test".into(),
+            branch: "main".into(),
+            pr: Some(7),
+        };
+        publish(
+            &context,
+            Event::FileReviewed {
+                file: "src/lib.rs".into(),
+                reviewed: true,
+            },
+        );
+        unsubscribe(sub);
+        publish(&context, Event::CommentsPushed { count: 1 });
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0]["event"], "file_reviewed");
+        assert_eq!(seen[0]["file"], "src/lib.rs");
+        assert_eq!(seen[0]["pr"], 7);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hook_reads_payload_on_stdin() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = dir.path().join("out.json");
+        let payload = r#"{"event":"diff_refreshed"}"#;
+        run_hook(
+            &format!("cat > {}", out.display()),
+            &dir.path().to_string_lossy(),
+            payload,
+        )
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), payload);
+        assert!(run_hook("exit 3", "", payload).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Advisory locking for the JSON sidecars (`questions.json`, `notes.json`,
+//! `github-comments.json`, …), so two er instances — or er and an agent —
+//! don't lose each other's updates in a read-modify-write race.
+//!
+//! Writers take an exclusive lock on `<file>.lock`, re-read the file under it,
+//! apply their change to what's there now and write it back atomically. For
+//! writers that don't lock (scripts, older versions), the file is re-read just
+//! before the rename and the change is rebased onto it if it moved.
+//!
+//! When another instance holds the lock, the wait is remembered so the UI can
+//! say so ([`take_contention`]).
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Longest wait for another instance to let go of a lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Poll interval while waiting.
+const LOCK_POLL: Duration = Duration::from_millis(20);
+
+/// Times a change is rebased onto a file that moved under an unlocked writer.
+const MAX_ATTEMPTS: usize = 3;
+
+/// File name of the last lock another instance held.
+static CONTENDED: Mutex<Option<String>> = Mutex::new(None);
+
+/// An exclusive lock on `<path>.lock`, released on drop.
+pub struct FileLock {
+    _file: File,
+}
+
+/// Lock `path` for writing, waiting up to [`LOCK_TIMEOUT`] for another
+/// instance to finish.
+pub fn lock(path: &str) -> Result<FileLock> {
+    if let Some(dir) = Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let lock_path = format!("{}.lock", path);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path))?;
+    let started = Instant::now();
+    let mut waited = false;
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(FileLock { _file: file }),
+            Err(TryLockError::WouldBlock) => {
+                if !waited {
+                    note_contention(path);
+                    waited = true;
+                }
+                if started.elapsed() >= LOCK_TIMEOUT {
+                    anyhow::bail!(
+                        "Another er instance is writing {} — try again",
+                        file_name(path)
+                    );
+                }
+                std::thread::sleep(LOCK_POLL);
+            }
+            Err(TryLockError::Error(e)) => {
+                // Filesystems without locking: carry on unlocked
+                crate::debug_log::warn("lock", format!("{}: {}", lock_path, e));
+                return Ok(FileLock { _file: file });
+            }
+        }
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn note_contention(path: &str) {
+    crate::debug_log::warn("lock", format!("waiting for another writer of {}", path));
+    if let Ok(mut contended) = CONTENDED.lock() {
+        *contended = Some(file_name(path));
+    }
+}
+
+/// The file another instance was writing when we last had to wait, once.
+pub fn take_contention() -> Option<String> {
+    CONTENDED.lock().ok().and_then(|mut c| c.take())
+}
+
+/// Write `content` to `path` via a temp file and rename.
+pub fn write_atomic(path: &str, content: &str) -> Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = format!("{}.tmp", path);
+    std::fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path))
+}
+
+/// Whether [`update_json`] found the file unreadable and started from the
+/// default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Updated {
+    Merged,
+    StartedFresh,
+}
+
+/// Apply `change` to the JSON in `path` under its lock: the file is read
+/// fresh (or `default()` when missing or invalid), changed and written back
+/// atomically. `change` may run more than once if an unlocked writer moves
+/// the file meanwhile, so it should only describe the edit.
+pub fn update_json<T, D, F>(path: &str, default: D, mut change: F) -> Result<Updated>
+where
+    T: Serialize + DeserializeOwned,
+    D: Fn() -> T,
+    F: FnMut(&mut T),
+{
+    let _lock = lock(path)?;
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let before = std::fs::read_to_string(path).ok();
+        let (mut value, updated) = match before.as_deref().map(serde_json::from_str::<T>) {
+            Some(Ok(value)) => (value, Updated::Merged),
+            Some(Err(_)) => (default(), Updated::StartedFresh),
+            None => (default(), Updated::Merged),
+        };
+        change(&mut value);
+        let json = serde_json::to_string_pretty(&value)?;
+        // Someone wrote without the lock since we read: rebase onto theirs
+        if attempt < MAX_ATTEMPTS && std::fs::read_to_string(path).ok() != before {
+            continue;
+        }
+        write_atomic(path, &json)?;
+        return Ok(updated);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Serialize, serde::Deserialize)]
+    struct Items {
+        items: Vec<String>,
+    }
+
+    #[test]
+    fn concurrent_updates_are_all_kept() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir
+            .path()
+            .join("questions.json")
+            .to_string_lossy()
+            .into_owned();
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for j in 0..5 {
+                        update_json(&path, Items::default, |items: &mut Items| {
+                            items.items.push(format!("{}-{}", i, j))
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let items: Items = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(items.items.len(), 40);
+
+        std::fs::write(&path, "{ not json").unwrap();
+        let updated = update_json(&path, Items::default, |items: &mut Items| {
+            items.items.push("fresh".into())
+        })
+        .unwrap();
+        assert_eq!(updated, Updated::StartedFresh);
+    }
+
+    #[test]
+    fn held_lock_is_reported_as_contention() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("notes.json").to_string_lossy().into_owned();
+        let held = lock(&path).unwrap();
+        let waiter = {
+            let path = path.clone();
+            std::thread::spawn(move || lock(&path).map(drop))
+        };
+        std::thread::sleep(LOCK_POLL * 3);
+        drop(held);
+        waiter.join().unwrap().unwrap();
+        // Other tests may contend too; some file was reported
+        assert!(take_contention().is_some());
+    }
+}
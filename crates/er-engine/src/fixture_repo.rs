@@ -0,0 +1,204 @@
+//! Throwaway git repos for integration tests (built with the `test-support`
+//! feature).
+//!
+//! A [`FixtureRepo`] is a fresh repo in a temp dir, deleted on drop, with an
+//! identity set and global/system git config ignored so the developer's
+//! settings (signing, hooks, default branch) can't change what a test sees.
+//! Build the history you need step by step, or start from one of the ready
+//! scenarios — [`feature_branch`], [`conflicted`], [`history`]:
+//!
+//! ```ignore
+//! let repo = FixtureRepo::new()?;
+//! repo.write("src/lib.rs", "fn a() {}\n")?;
+//! repo.commit("base")?;
+//! repo.branch("feature")?;
+//! repo.rename("src/lib.rs", "src/core.rs")?;
+//! repo.write_generated("data/big.txt", 20_000)?;
+//! repo.commit("move and grow")?;
+//! let app = App::new_with_args(&[repo.root()])?;
+//! ```
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// A temporary git repo on `main`, with no commits yet.
+pub struct FixtureRepo {
+    dir: tempfile::TempDir,
+}
+
+impl FixtureRepo {
+    pub fn new() -> Result<Self> {
+        let repo = FixtureRepo {
+            dir: tempfile::Builder::new().prefix("er-fixture-").tempdir()?,
+        };
+        repo.git(&["init", "-q", "-b", "main"])?;
+        repo.git(&["config", "user.email", "fixture@example.com"])?;
+        repo.git(&["config", "user.name", "Fixture"])?;
+        repo.git(&["config", "commit.gpgsign", "false"])?;
+        Ok(repo)
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// The repo root as the `String` the engine's git functions take.
+    pub fn root(&self) -> String {
+        self.path().to_string_lossy().into_owned()
+    }
+
+    /// Run git in the repo; stdout on success, an error with stderr otherwise.
+    pub fn git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(self.path())
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_SYSTEM", "/dev/null")
+            .output()
+            .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Write `content` to `path` (relative to the root), creating directories.
+    pub fn write(&self, path: &str, content: &str) -> Result<()> {
+        let full = self.path().join(path);
+        if let Some(parent) = full.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full, content).with_context(|| format!("Failed to write {}", path))
+    }
+
+    /// Write a file of `lines` distinct generated lines.
+    pub fn write_generated(&self, path: &str, lines: usize) -> Result<()> {
+        self.write(path, &generated_lines(lines))
+    }
+
+    pub fn remove(&self, path: &str) -> Result<()> {
+        self.git(&["rm", "-q", path]).map(|_| ())
+    }
+
+    /// `git mv`, so the next commit records a rename.
+    pub fn rename(&self, from: &str, to: &str) -> Result<()> {
+        if let Some(parent) = self.path().join(to).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        self.git(&["mv", from, to]).map(|_| ())
+    }
+
+    /// Stage everything and commit; returns the new commit's hash.
+    pub fn commit(&self, message: &str) -> Result<String> {
+        self.git(&["add", "-A"])?;
+        self.git(&["commit", "-q", "--allow-empty", "-m", message])?;
+        self.head()
+    }
+
+    pub fn head(&self) -> Result<String> {
+        Ok(self.git(&["rev-parse", "HEAD"])?.trim().to_string())
+    }
+
+    /// Create branch `name` at HEAD and switch to it.
+    pub fn branch(&self, name: &str) -> Result<()> {
+        self.git(&["checkout", "-q", "-b", name]).map(|_| ())
+    }
+
+    pub fn checkout(&self, name: &str) -> Result<()> {
+        self.git(&["checkout", "-q", name]).map(|_| ())
+    }
+
+    /// Merge `branch` into the current one with a merge commit. `Ok(false)`
+    /// when it conflicts; the merge is left in progress for conflicts mode.
+    pub fn merge(&self, branch: &str) -> Result<bool> {
+        match self.git(&["merge", "-q", "--no-ff", "--no-edit", branch]) {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                if self
+                    .git(&["diff", "--name-only", "--diff-filter=U"])?
+                    .trim()
+                    .is_empty()
+                {
+                    Err(e)
+                } else {
+                    Ok(false)
+                }
+            }
+        }
+    }
+}
+
+/// `lines` lines that differ from each other, so diffs of them stay exact.
+pub fn generated_lines(lines: usize) -> String {
+    (1..=lines)
+        .map(|n| {
+            format!(
+                "line {:06} {:x}\n",
+                n,
+                n.wrapping_mul(2_654_435_761) % 65_521
+            )
+        })
+        .collect()
+}
+
+/// `main` with a few files, and the checked-out `feature` branch on top that
+/// modifies `src/lib.rs`, adds `src/new.rs`, deletes `README.md` and renames
+/// `src/old_name.rs` to `src/new_name.rs`.
+pub fn feature_branch() -> Result<FixtureRepo> {
+    let repo = FixtureRepo::new()?;
+    repo.write("README.md", "# Fixture\n")?;
+    repo.write("src/lib.rs", "pub fn one() -> u32 {\n    1\n}\n")?;
+    repo.write("src/old_name.rs", &generated_lines(20))?;
+    repo.commit("Initial commit")?;
+    repo.branch("feature")?;
+    repo.write(
+        "src/lib.rs",
+        "pub fn one() -> u32 {\n    1\n}\n\npub fn two() -> u32 {\n    2\n}\n",
+    )?;
+    repo.write("src/new.rs", "pub const NEW: bool = true;\n")?;
+    repo.remove("README.md")?;
+    repo.rename("src/old_name.rs", "src/new_name.rs")?;
+    repo.commit("Add two, move old_name")?;
+    Ok(repo)
+}
+
+/// `main` and `feature` both change line 2 of `conflict.txt`; `feature` is
+/// checked out with a merge of `main` in progress and that file unmerged.
+pub fn conflicted() -> Result<FixtureRepo> {
+    let repo = FixtureRepo::new()?;
+    repo.write("conflict.txt", "one\ntwo\nthree\n")?;
+    repo.commit("Initial commit")?;
+    repo.branch("feature")?;
+    repo.write("conflict.txt", "one\nfeature two\nthree\n")?;
+    repo.commit("Change two on feature")?;
+    repo.checkout("main")?;
+    repo.write("conflict.txt", "one\nmain two\nthree\n")?;
+    repo.commit("Change two on main")?;
+    repo.checkout("feature")?;
+    if repo.merge("main")? {
+        anyhow::bail!("the fixture merge was expected to conflict");
+    }
+    Ok(repo)
+}
+
+/// `commits` commits on the checked-out `feature` branch after `main`, each
+/// appending a line to `log.txt`.
+pub fn history(commits: usize) -> Result<FixtureRepo> {
+    let repo = FixtureRepo::new()?;
+    repo.write("log.txt", "start\n")?;
+    repo.commit("Initial commit")?;
+    repo.branch("feature")?;
+    let mut log = String::from("start\n");
+    for n in 1..=commits {
+        log.push_str(&format!("entry {}\n", n));
+        repo.write("log.txt", &log)?;
+        repo.commit(&format!("Commit {}", n))?;
+    }
+    Ok(repo)
+}
@@ -0,0 +1,200 @@
+//! Editor → er round-trip: `er --focus file:line` pushes a cursor position into
+//! a running er instance over a unix socket in the repo root (`.er.sock`).
+//!
+//! Protocol: one UTF-8 line per connection, `<path>[:<line>]\n`. Paths may be
+//! absolute or repo-relative. The listener is non-blocking and polled from the
+//! event loop, so editor plugins never wait on er.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Socket file name inside the repo root.
+pub const SOCKET_NAME: &str = ".er.sock";
+
+/// A position pushed by an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusRequest {
+    /// Repo-relative path (as it appears in the diff).
+    pub path: String,
+    pub line: Option<usize>,
+}
+
+pub fn socket_path(repo_root: &str) -> PathBuf {
+    Path::new(repo_root).join(SOCKET_NAME)
+}
+
+/// Parse `file`, `file:line` or `file:line:col` (col ignored). Absolute paths
+/// under `repo_root` are made repo-relative.
+pub fn parse_focus_target(target: &str, repo_root: &str) -> Option<FocusRequest> {
+    let target = target.trim();
+    if target.is_empty() {
+        return None;
+    }
+    let mut parts: Vec<&str> = target.rsplitn(3, ':').collect();
+    parts.reverse();
+    let (path, line) = match parts.as_slice() {
+        [p, l, c] if l.parse::<usize>().is_ok() && c.parse::<usize>().is_ok() => {
+            (p.to_string(), l.parse().ok())
+        }
+        [p, l, c] if c.parse::<usize>().is_ok() => (format!("{}:{}", p, l), c.parse().ok()),
+        [p, l] if l.parse::<usize>().is_ok() => (p.to_string(), l.parse().ok()),
+        _ => (target.to_string(), None),
+    };
+    let root = repo_root.trim_end_matches('/');
+    let path = match path.strip_prefix(root) {
+        Some(rest) if !root.is_empty() && rest.starts_with('/') => rest[1..].to_string(),
+        _ => path.strip_prefix("./").unwrap_or(&path).to_string(),
+    };
+    if path.is_empty() {
+        return None;
+    }
+    Some(FocusRequest { path, line })
+}
+
+/// Send a focus request to the er instance serving `repo_root`.
+#[cfg(unix)]
+pub fn send_focus(repo_root: &str, target: &str) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::net::UnixStream;
+
+    let path = socket_path(repo_root);
+    let mut stream = UnixStream::connect(&path)
+        .with_context(|| format!("No running er instance for {}", repo_root))?;
+    stream
+        .write_all(format!("{}\n", target.trim()).as_bytes())
+        .context("Failed to send focus request")?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn send_focus(_repo_root: &str, _target: &str) -> Result<()> {
+    anyhow::bail!("--focus requires unix domain sockets")
+}
+
+/// Non-blocking listener owned by the TUI event loop. Removes the socket file
+/// on drop.
+#[cfg(unix)]
+pub struct FocusListener {
+    listener: std::os::unix::net::UnixListener,
+    path: PathBuf,
+    repo_root: String,
+}
+
+#[cfg(unix)]
+impl FocusListener {
+    /// Bind `.er.sock` in `repo_root`. A leftover socket from a crashed
+    /// instance is replaced; a live one (another er in the same repo) is left
+    /// alone and binding fails.
+    pub fn bind(repo_root: &str) -> Result<Self> {
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        let path = socket_path(repo_root);
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                anyhow::bail!("Another er instance is listening on {}", path.display());
+            }
+            let _ = std::fs::remove_file(&path);
+        }
+        let listener = UnixListener::bind(&path)
+            .with_context(|| format!("Failed to bind {}", path.display()))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            path,
+            repo_root: repo_root.to_string(),
+        })
+    }
+
+    pub fn repo_root(&self) -> &str {
+        &self.repo_root
+    }
+
+    /// Drain pending connections. Call once per event-loop tick.
+    pub fn poll(&self) -> Vec<FocusRequest> {
+        use std::io::{BufRead, BufReader};
+
+        let mut out = Vec::new();
+        while let Ok((stream, _)) = self.listener.accept() {
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_read_timeout(Some(std::time::Duration::from_millis(100)));
+            let mut line = String::new();
+            if BufReader::new(stream).read_line(&mut line).is_ok() {
+                if let Some(req) = parse_focus_target(&line, &self.repo_root) {
+                    out.push(req);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FocusListener {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_file_and_line() {
+        assert_eq!(
+            parse_focus_target("src/main.rs:42", "/repo"),
+            Some(FocusRequest {
+                path: "src/main.rs".into(),
+                line: Some(42)
+            })
+        );
+        assert_eq!(
+            parse_focus_target("src/main.rs", "/repo").unwrap().line,
+            None
+        );
+    }
+
+    #[test]
+    fn parse_ignores_column_and_strips_repo_root() {
+        let req = parse_focus_target("/repo/src/lib.rs:10:5\n", "/repo").unwrap();
+        assert_eq!(req.path, "src/lib.rs");
+        assert_eq!(req.line, Some(10));
+
+        let req = parse_focus_target("./a.rs:3", "/repo/").unwrap();
+        assert_eq!(req.path, "a.rs");
+    }
+
+    #[test]
+    fn parse_rejects_empty() {
+        assert!(parse_focus_target("  ", "/repo").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn listener_round_trip_and_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        let listener = FocusListener::bind(&root).unwrap();
+        assert!(FocusListener::bind(&root).is_err());
+
+        send_focus(&root, "src/a.rs:7").unwrap();
+        let mut got = Vec::new();
+        for _ in 0..50 {
+            got.extend(listener.poll());
+            if !got.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            got,
+            vec![FocusRequest {
+                path: "src/a.rs".into(),
+                line: Some(7)
+            }]
+        );
+
+        drop(listener);
+        assert!(!socket_path(&root).exists());
+    }
+}
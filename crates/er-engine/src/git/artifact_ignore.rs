@@ -0,0 +1,141 @@
+//! Keeping review artifacts out of commits: which of er's working-tree files
+//! git doesn't ignore yet, and appending patterns for them to
+//! `.git/info/exclude` or `.gitignore` (`[storage] ignore_artifacts`).
+//!
+//! Only `.er/` (repo-local storage), legacy `.er-*.json` sidecars and
+//! handoff bundles count — `.er-config.toml` is meant to be committed.
+
+use crate::command::OutputLogged;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Ignore patterns for er's artifacts, each with a path it must match.
+pub const ARTIFACT_PATTERNS: &[(&str, &str)] = &[
+    (".er/", ".er/review.json"),
+    (".er-*.json", ".er-review.json"),
+    ("*.er-session.json", "main-ada.er-session.json"),
+];
+
+/// Written above the appended patterns.
+const IGNORE_HEADER: &str = "# Easy Review artifacts";
+
+/// Whether `path` (repo-relative) is one of er's artifacts rather than code.
+pub fn is_artifact_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    path.starts_with(".er/")
+        || path.contains("/.er/")
+        || (name.starts_with(".er-") && name.ends_with(".json"))
+        || name.ends_with(crate::session_bundle::BUNDLE_SUFFIX)
+}
+
+/// Artifact patterns git doesn't ignore in `repo_root` yet.
+pub fn unignored_artifact_patterns(repo_root: &str) -> Vec<&'static str> {
+    let samples: Vec<String> = ARTIFACT_PATTERNS
+        .iter()
+        .map(|(_, sample)| sample.to_string())
+        .collect();
+    let ignored = super::gitignored_paths(repo_root, &samples);
+    ARTIFACT_PATTERNS
+        .iter()
+        .filter(|(_, sample)| !ignored.contains(*sample))
+        .map(|(pattern, _)| *pattern)
+        .collect()
+}
+
+/// The clone's `info/exclude` (shared by its worktrees).
+fn exclude_file(repo_root: &str) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "info/exclude"])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git rev-parse failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        Path::new(repo_root).join(path)
+    })
+}
+
+/// Where `ignore_artifacts` puts the patterns: `.gitignore` for
+/// `"gitignore"`, `.git/info/exclude` otherwise.
+pub fn ignore_file_for(repo_root: &str, ignore_artifacts: &str) -> Result<PathBuf> {
+    if ignore_artifacts.eq_ignore_ascii_case("gitignore") {
+        Ok(Path::new(repo_root).join(".gitignore"))
+    } else {
+        exclude_file(repo_root)
+    }
+}
+
+/// Append `patterns` to `file` under a header, keeping what's there.
+pub fn append_ignore_patterns(file: &Path, patterns: &[&str]) -> Result<()> {
+    let existing = std::fs::read_to_string(file).unwrap_or_default();
+    let mut text = existing.clone();
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    if !existing.lines().any(|l| l.trim() == IGNORE_HEADER) {
+        text.push_str(IGNORE_HEADER);
+        text.push('\n');
+    }
+    for pattern in patterns {
+        if !existing.lines().any(|l| l.trim() == *pattern) {
+            text.push_str(pattern);
+            text.push('\n');
+        }
+    }
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(file, text).with_context(|| format!("Writing {}", file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifacts_are_told_from_code_and_config() {
+        assert!(is_artifact_path(".er/review.json"));
+        assert!(is_artifact_path("app/.er/questions.json"));
+        assert!(is_artifact_path(".er-questions.json"));
+        assert!(is_artifact_path("main-ada.er-session.json"));
+        assert!(!is_artifact_path(".er-config.toml"));
+        assert!(!is_artifact_path("src/er-review.json"));
+        assert!(!is_artifact_path("docs/.eruda.json"));
+    }
+
+    #[test]
+    fn missing_patterns_are_appended_to_the_exclude_file_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .logged_output()
+            .unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n.er/\n").unwrap();
+        let missing = unignored_artifact_patterns(root);
+        assert_eq!(missing, [".er-*.json", "*.er-session.json"]);
+
+        let exclude = ignore_file_for(root, "").unwrap();
+        assert!(exclude.ends_with(".git/info/exclude"));
+        append_ignore_patterns(&exclude, &missing).unwrap();
+        append_ignore_patterns(&exclude, &missing).unwrap();
+        let text = std::fs::read_to_string(&exclude).unwrap();
+        assert_eq!(text.matches(".er-*.json").count(), 1);
+        assert_eq!(text.matches(IGNORE_HEADER).count(), 1);
+        assert!(unignored_artifact_patterns(root).is_empty());
+        assert_eq!(
+            ignore_file_for(root, "gitignore").unwrap(),
+            dir.path().join(".gitignore")
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Who wrote what on a branch: per-author totals from `git log --numstat`,
+//! per-line authors from `git blame` on the new side (`author:` filter),
+//! and per-line dates on the old side (the code age layer).
+
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::command::OutputLogged;
+
+/// One author's share of `base..head`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorStat {
+    pub name: String,
+    pub commits: usize,
+    /// Distinct files their commits touched
+    pub files: usize,
+    pub adds: usize,
+    pub dels: usize,
+}
+
+/// Authors of the non-merge commits in `base..head`, most lines changed
+/// first.
+pub fn branch_author_stats(repo_root: &str, base: &str, head: &str) -> Result<Vec<AuthorStat>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--no-merges",
+            "--format=%x1e%aN",
+            "--numstat",
+            &format!("{}..{}", base, head),
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_author_numstat(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parse `git log --format=%x1e%aN --numstat`.
+fn parse_author_numstat(log: &str) -> Vec<AuthorStat> {
+    let mut stats: Vec<(AuthorStat, HashSet<String>)> = Vec::new();
+    for record in log.split('\x1e') {
+        let mut lines = record.lines();
+        let Some(name) = lines.next().map(str::trim).filter(|n| !n.is_empty()) else {
+            continue;
+        };
+        let idx = match stats.iter().position(|(s, _)| s.name == name) {
+            Some(idx) => idx,
+            None => {
+                stats.push((
+                    AuthorStat {
+                        name: name.to_string(),
+                        commits: 0,
+                        files: 0,
+                        adds: 0,
+                        dels: 0,
+                    },
+                    HashSet::new(),
+                ));
+                stats.len() - 1
+            }
+        };
+        let (stat, files) = &mut stats[idx];
+        stat.commits += 1;
+        for line in lines {
+            // <adds>\t<dels>\t<path>; binary files show "-"
+            let mut parts = line.splitn(3, '\t');
+            let (Some(adds), Some(dels), Some(path)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            stat.adds += adds.parse::<usize>().unwrap_or(0);
+            stat.dels += dels.parse::<usize>().unwrap_or(0);
+            files.insert(path.to_string());
+        }
+    }
+    let mut out: Vec<AuthorStat> = stats
+        .into_iter()
+        .map(|(mut stat, files)| {
+            stat.files = files.len();
+            stat
+        })
+        .collect();
+    out.sort_by_key(|s| std::cmp::Reverse(s.adds + s.dels));
+    out
+}
+
+/// Author of each line of `path` at `rev`, by new-side line number.
+pub fn blame_line_authors(
+    repo_root: &str,
+    rev: &str,
+    path: &str,
+) -> Result<HashMap<usize, String>> {
+    Ok(parse_blame_field(
+        &blame_porcelain(repo_root, rev, path)?,
+        "author ",
+    ))
+}
+
+/// When each line of `path` at `rev` was written (author time, Unix
+/// seconds), by line number.
+pub fn blame_line_times(repo_root: &str, rev: &str, path: &str) -> Result<HashMap<usize, i64>> {
+    Ok(
+        parse_blame_field(&blame_porcelain(repo_root, rev, path)?, "author-time ")
+            .into_iter()
+            .filter_map(|(n, t)| Some((n, t.parse().ok()?)))
+            .collect(),
+    )
+}
+
+fn blame_porcelain(repo_root: &str, rev: &str, path: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["blame", "--line-porcelain", rev, "--", path])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git blame")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git blame --line-porcelain` for one header field (`"author "`):
+/// each line's header is `<sha> <orig_line> <final_line> [<group>]`, then
+/// `author <name>`, `author-time <secs>`, …, then the content prefixed with
+/// a tab.
+fn parse_blame_field(output: &str, field: &str) -> HashMap<usize, String> {
+    let mut out = HashMap::new();
+    let mut line_no = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            line_no = None;
+            continue;
+        }
+        if line_no.is_none() {
+            let mut fields = line.split(' ');
+            let is_header = fields
+                .next()
+                .is_some_and(|sha| sha.len() >= 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()));
+            if is_header {
+                line_no = fields.nth(1).and_then(|n| n.parse::<usize>().ok());
+            }
+            continue;
+        }
+        if let (Some(n), Some(value)) = (line_no, line.strip_prefix(field)) {
+            out.insert(n, value.to_string());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numstat_and_blame_attribute_lines_to_authors() {
+        let log = "\x1ealice\n\n10\t2\tsrc/a.rs\n3\t0\tsrc/b.rs\n\
+                   \x1ebob\n\n1\t1\tsrc/a.rs\n\
+                   \x1ealice\n\n-\t-\tlogo.png\n5\t5\tsrc/a.rs\n";
+        let stats = parse_author_numstat(log);
+        assert_eq!(
+            stats[0],
+            AuthorStat {
+                name: "alice".into(),
+                commits: 2,
+                files: 3,
+                adds: 18,
+                dels: 7,
+            }
+        );
+        assert_eq!((stats[1].name.as_str(), stats[1].files), ("bob", 1));
+
+        let sha = "a".repeat(40);
+        let other = "b".repeat(40);
+        let blame = format!(
+            "{sha} 1 1 2\nauthor alice\nauthor-mail <a@x>\nauthor-time 1700000000\nsummary s\nfilename f\n\tfn a() {{\n\
+             {sha} 2 2\nauthor alice\nsummary s\nfilename f\n\t}}\n\
+             {other} 7 3 1\nauthor bob\nsummary author notes\nfilename f\n\tauthor x\n"
+        );
+        let authors = parse_blame_field(&blame, "author ");
+        assert_eq!(authors.len(), 3);
+        assert_eq!(authors[&2], "alice");
+        assert_eq!(authors[&3], "bob");
+        let times = parse_blame_field(&blame, "author-time ");
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[&1], "1700000000");
+    }
+}
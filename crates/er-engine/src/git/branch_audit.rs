@@ -0,0 +1,490 @@
+//! Branch hygiene before approval: merge commits, leftover `fixup!` /
+//! `squash!` commits, WIP commits, unsigned commits and large binaries —
+//! plus the compliance checks a foundation may require: `Signed-off-by`
+//! (DCO), license headers on new files and file naming conventions.
+
+use super::large_files::format_size;
+use crate::command::OutputLogged;
+use crate::config::AuditConfig;
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// What a check looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditKind {
+    MergeCommits,
+    FixupCommits,
+    WipCommits,
+    UnsignedCommits,
+    LargeBinaries,
+    MissingSignoff,
+    MissingLicenseHeader,
+    FileNaming,
+}
+
+impl AuditKind {
+    /// Wording when the check passes / fails.
+    pub fn labels(self) -> (&'static str, &'static str) {
+        match self {
+            AuditKind::MergeCommits => ("No merge commits", "Merge commits"),
+            AuditKind::FixupCommits => ("No fixup!/squash! commits", "Commits to autosquash"),
+            AuditKind::WipCommits => ("No WIP commits", "WIP commits"),
+            AuditKind::UnsignedCommits => ("All commits signed", "Unsigned commits"),
+            AuditKind::LargeBinaries => ("No large binaries", "Large binaries added"),
+            AuditKind::MissingSignoff => ("All commits signed off", "Missing Signed-off-by"),
+            AuditKind::MissingLicenseHeader => (
+                "New files carry the license header",
+                "Missing license header",
+            ),
+            AuditKind::FileNaming => ("File names follow conventions", "Misnamed files"),
+        }
+    }
+}
+
+/// One line of the audit; `offenders` are `short-hash subject`, `path` or
+/// `path (size)` entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditCheck {
+    pub kind: AuditKind,
+    pub offenders: Vec<String>,
+}
+
+impl AuditCheck {
+    pub fn passed(&self) -> bool {
+        self.offenders.is_empty()
+    }
+
+    pub fn label(&self) -> String {
+        let (ok, failed) = self.kind.labels();
+        if self.passed() {
+            ok.to_string()
+        } else {
+            format!("{} ({})", failed, self.offenders.len())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchAudit {
+    pub checks: Vec<AuditCheck>,
+}
+
+impl BranchAudit {
+    pub fn failed(&self) -> usize {
+        self.checks.iter().filter(|c| !c.passed()).count()
+    }
+}
+
+/// One commit of `base..head` as the audit sees it.
+struct AuditCommit {
+    short_hash: String,
+    subject: String,
+    parents: usize,
+    /// `%G?`: `N` = no signature
+    signature: String,
+    signed_off: bool,
+}
+
+/// Audit the commits and blobs `head` adds on top of `base`. The optional
+/// checks follow `config`: unsigned commits under `require_signed`, binaries
+/// over `max_blob_kb`, and the compliance checks when configured.
+pub fn audit_branch(
+    repo_root: &str,
+    base: &str,
+    head: &str,
+    config: &AuditConfig,
+) -> Result<BranchAudit> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--format=%h%x1f%G?%x1f%P%x1f%(trailers:key=Signed-off-by,valueonly,separator=%x2c)%x1f%s",
+            &format!("{}..{}", base, head),
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let commits = parse_audit_log(&String::from_utf8_lossy(&output.stdout));
+    let mut audit = audit_commits(&commits, config.require_signed, config.require_signoff);
+    let max_blob_bytes = config.max_blob_kb.saturating_mul(1024);
+    if max_blob_bytes > 0 {
+        audit.checks.push(AuditCheck {
+            kind: AuditKind::LargeBinaries,
+            offenders: large_binaries(repo_root, base, head, max_blob_bytes)?,
+        });
+    }
+    let header = config.license_header.trim();
+    if !header.is_empty() || !config.naming.is_empty() {
+        let (added, renamed) = new_paths(repo_root, base, head)?;
+        if !header.is_empty() {
+            let wanted: Vec<&String> = added
+                .iter()
+                .filter(|path| needs_license_header(&config.license_files, path))
+                .collect();
+            audit.checks.push(AuditCheck {
+                kind: AuditKind::MissingLicenseHeader,
+                offenders: missing_header(repo_root, head, &wanted, header)?,
+            });
+        }
+        if !config.naming.is_empty() {
+            audit.checks.push(AuditCheck {
+                kind: AuditKind::FileNaming,
+                offenders: added
+                    .iter()
+                    .chain(&renamed)
+                    .filter_map(|path| misnamed(&config.naming, path))
+                    .collect(),
+            });
+        }
+    }
+    Ok(audit)
+}
+
+fn parse_audit_log(output: &str) -> Vec<AuditCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\x1f');
+            let short_hash = fields.next()?.to_string();
+            let signature = fields.next()?.to_string();
+            let parents = fields.next()?.split_whitespace().count();
+            let signed_off = !fields.next()?.trim().is_empty();
+            let subject = fields.next().unwrap_or("").to_string();
+            Some(AuditCommit {
+                short_hash,
+                subject,
+                parents,
+                signature,
+                signed_off,
+            })
+        })
+        .collect()
+}
+
+fn audit_commits(
+    commits: &[AuditCommit],
+    require_signed: bool,
+    require_signoff: bool,
+) -> BranchAudit {
+    let offenders = |is_offender: &dyn Fn(&AuditCommit) -> bool| -> Vec<String> {
+        commits
+            .iter()
+            .filter(|c| is_offender(c))
+            .map(|c| format!("{} {}", c.short_hash, c.subject))
+            .collect()
+    };
+    let mut checks = vec![
+        AuditCheck {
+            kind: AuditKind::MergeCommits,
+            offenders: offenders(&|c| c.parents > 1),
+        },
+        AuditCheck {
+            kind: AuditKind::FixupCommits,
+            offenders: offenders(&|c| {
+                ["fixup! ", "squash! ", "amend! "]
+                    .iter()
+                    .any(|p| c.subject.starts_with(p))
+            }),
+        },
+        AuditCheck {
+            kind: AuditKind::WipCommits,
+            offenders: offenders(&|c| is_wip(&c.subject)),
+        },
+    ];
+    if require_signed {
+        checks.push(AuditCheck {
+            kind: AuditKind::UnsignedCommits,
+            offenders: offenders(&|c| c.parents <= 1 && c.signature == "N"),
+        });
+    }
+    if require_signoff {
+        checks.push(AuditCheck {
+            kind: AuditKind::MissingSignoff,
+            offenders: offenders(&|c| c.parents <= 1 && !c.signed_off),
+        });
+    }
+    BranchAudit { checks }
+}
+
+/// `WIP`, `wip: …`, `[WIP] …`, `WIP …`
+fn is_wip(subject: &str) -> bool {
+    let word = subject
+        .trim_start_matches(['[', '('])
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or("");
+    word.eq_ignore_ascii_case("wip")
+}
+
+/// Paths `head` adds on top of `base`, and the new names of renamed files.
+fn new_paths(repo_root: &str, base: &str, head: &str) -> Result<(Vec<String>, Vec<String>)> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--name-status",
+            "-M",
+            "--diff-filter=AR",
+            &format!("{}...{}", base, head),
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git diff")?;
+    Ok(parse_name_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `A\tpath` / `R100\told\tnew` lines into `(added, renamed)`.
+fn parse_name_status(output: &str) -> (Vec<String>, Vec<String>) {
+    let mut added = Vec::new();
+    let mut renamed = Vec::new();
+    for line in output.lines() {
+        let status = line.split('\t').next();
+        match (status, line.rsplit('\t').next()) {
+            (Some("A"), Some(path)) => added.push(path.to_string()),
+            (Some(status), Some(path)) if status.starts_with('R') => renamed.push(path.to_string()),
+            _ => {}
+        }
+    }
+    (added, renamed)
+}
+
+/// Lines from the top of a file searched for the license header.
+const LICENSE_HEADER_LINES: usize = 20;
+
+/// Whether `path` must carry the header: it matches one of `patterns`, or
+/// with none set, it's source or test code.
+fn needs_license_header(patterns: &[String], path: &str) -> bool {
+    if patterns.is_empty() {
+        return matches!(
+            super::classify_path(path),
+            super::FileKind::Production | super::FileKind::Test
+        );
+    }
+    patterns
+        .iter()
+        .any(|pattern| super::compact_files_match(pattern, path))
+}
+
+/// Whether `header` appears in the first lines of `content` (comment
+/// markers and all, so `// SPDX-…` and `# SPDX-…` both match).
+fn has_license_header(content: &str, header: &str) -> bool {
+    content
+        .lines()
+        .take(LICENSE_HEADER_LINES)
+        .any(|line| line.contains(header))
+}
+
+/// Text files among `paths` at `head` without `header`. Binary blobs are
+/// skipped.
+fn missing_header(
+    repo_root: &str,
+    head: &str,
+    paths: &[&String],
+    header: &str,
+) -> Result<Vec<String>> {
+    let mut missing = Vec::new();
+    for path in paths {
+        let output = Command::new("git")
+            .args(["show", &format!("{}:{}", head, path)])
+            .current_dir(repo_root)
+            .logged_output()
+            .context("Failed to run git show")?;
+        if !output.status.success() || output.stdout.contains(&0) {
+            continue;
+        }
+        if !has_license_header(&String::from_utf8_lossy(&output.stdout), header) {
+            missing.push(path.to_string());
+        }
+    }
+    Ok(missing)
+}
+
+/// Whether `stem` (file name up to the first `.`) follows `convention`.
+/// `None` for a convention this doesn't know.
+fn follows_convention(stem: &str, convention: &str) -> Option<bool> {
+    let alnum = stem.chars().all(|c| c.is_ascii_alphanumeric());
+    let first = stem.chars().next();
+    let ok = match convention {
+        "snake_case" => stem
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+        "kebab-case" => stem
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+        "camelCase" => alnum && first.is_some_and(|c| c.is_ascii_lowercase()),
+        "PascalCase" => alnum && first.is_some_and(|c| c.is_ascii_uppercase()),
+        "lowercase" => !stem.chars().any(|c| c.is_uppercase()),
+        _ => return None,
+    };
+    Some(ok)
+}
+
+/// `path (convention)` when `path` breaks a naming rule that applies to it.
+fn misnamed(rules: &std::collections::BTreeMap<String, String>, path: &str) -> Option<String> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    // Dotfiles (`.eslintrc.js`) keep their leading dot out of the stem
+    let stem = name
+        .trim_start_matches('.')
+        .split('.')
+        .next()
+        .unwrap_or(name);
+    rules
+        .iter()
+        .filter(|(pattern, _)| super::compact_files_match(pattern, path))
+        .find(|(_, convention)| follows_convention(stem, convention) == Some(false))
+        .map(|(_, convention)| format!("{} ({})", path, convention))
+}
+
+/// Binary files `head` adds or changes whose new blob is over `max_bytes`.
+fn large_binaries(repo_root: &str, base: &str, head: &str, max_bytes: u64) -> Result<Vec<String>> {
+    let range = format!("{}...{}", base, head);
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--raw",
+            "--numstat",
+            "--no-abbrev",
+            "--no-renames",
+            "--diff-filter=AM",
+            &range,
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git diff")?;
+    let (blobs, binaries) = parse_raw_numstat(&String::from_utf8_lossy(&output.stdout));
+    let candidates: Vec<(String, String)> = blobs
+        .into_iter()
+        .filter(|(_, path)| binaries.contains(path))
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch-check=%(objectsize)"])
+        .current_dir(repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run git cat-file")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for (blob, _) in &candidates {
+            writeln!(stdin, "{}", blob)?;
+        }
+    }
+    let output = child.wait_with_output()?;
+    let sizes = String::from_utf8_lossy(&output.stdout);
+    Ok(candidates
+        .iter()
+        .zip(sizes.lines())
+        .filter_map(|((_, path), size)| {
+            let size: u64 = size.trim().parse().ok()?;
+            (size > max_bytes).then(|| format!("{} ({})", path, format_size(size)))
+        })
+        .collect())
+}
+
+/// `(new blob, path)` from `--raw` lines and the binary paths from
+/// `--numstat` (`-\t-\tpath`).
+fn parse_raw_numstat(output: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let mut blobs = Vec::new();
+    let mut binaries = Vec::new();
+    for line in output.lines() {
+        if let Some(raw) = line.strip_prefix(':') {
+            // :old_mode new_mode old_sha new_sha status\tpath
+            let Some((meta, path)) = raw.split_once('\t') else {
+                continue;
+            };
+            if let Some(blob) = meta.split_whitespace().nth(3) {
+                blobs.push((blob.to_string(), path.to_string()));
+            }
+        } else if let Some(path) = line.strip_prefix("-\t-\t") {
+            binaries.push(path.to_string());
+        }
+    }
+    (blobs, binaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_checks_flag_merges_fixups_wip_and_unsigned() {
+        let log = "a1\x1fG\x1fp1\x1fA <a@a>\x1fAdd parser\n\
+                   b2\x1fN\x1fp1 p2\x1f\x1fMerge branch 'main'\n\
+                   c3\x1fN\x1fp1\x1fA <a@a>\x1ffixup! Add parser\n\
+                   d4\x1fG\x1fp1\x1f\x1f[WIP] lexer\n\
+                   e5\x1fG\x1fp1\x1fA <a@a>\x1fWipe caches\n";
+        let commits = parse_audit_log(log);
+        let audit = audit_commits(&commits, true, true);
+        let offenders: Vec<(AuditKind, Vec<&str>)> = audit
+            .checks
+            .iter()
+            .map(|c| (c.kind, c.offenders.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            offenders,
+            [
+                (AuditKind::MergeCommits, vec!["b2 Merge branch 'main'"]),
+                (AuditKind::FixupCommits, vec!["c3 fixup! Add parser"]),
+                (AuditKind::WipCommits, vec!["d4 [WIP] lexer"]),
+                (AuditKind::UnsignedCommits, vec!["c3 fixup! Add parser"]),
+                (AuditKind::MissingSignoff, vec!["d4 [WIP] lexer"]),
+            ]
+        );
+        assert_eq!(audit.failed(), 5);
+        assert_eq!(audit_commits(&commits, false, false).checks.len(), 3);
+    }
+
+    #[test]
+    fn only_binary_blobs_are_size_checked() {
+        let out = ":000000 100644 0000 abc A\tassets/logo.png\n\
+                   :100644 100644 1111 def M\tsrc/main.rs\n\
+                   -\t-\tassets/logo.png\n\
+                   3\t1\tsrc/main.rs\n";
+        let (blobs, binaries) = parse_raw_numstat(out);
+        assert_eq!(blobs.len(), 2);
+        assert_eq!(blobs[0], ("abc".to_string(), "assets/logo.png".to_string()));
+        assert_eq!(binaries, ["assets/logo.png"]);
+        assert_eq!(format_size(3 << 20), "3.0 MB");
+        assert_eq!(format_size(1500), "2 KB");
+    }
+
+    #[test]
+    fn compliance_checks_cover_headers_and_naming() {
+        let (added, renamed) = parse_name_status(
+            "A\tsrc/new_mod.rs\nR087\tsrc/old.rs\tsrc/NewName.rs\nA\tREADME.md\n",
+        );
+        assert_eq!(added, ["src/new_mod.rs", "README.md"]);
+        assert_eq!(renamed, ["src/NewName.rs"]);
+
+        assert!(needs_license_header(&[], "src/new_mod.rs"));
+        assert!(!needs_license_header(&[], "README.md"));
+        assert!(needs_license_header(&["*.md".into()], "README.md"));
+        let header = "SPDX-License-Identifier: Apache-2.0";
+        assert!(has_license_header(
+            "// SPDX-License-Identifier: Apache-2.0\nfn a() {}\n",
+            header
+        ));
+        assert!(!has_license_header("fn a() {}\n", header));
+
+        let rules = std::collections::BTreeMap::from([
+            ("*.rs".to_string(), "snake_case".to_string()),
+            ("components/**".to_string(), "PascalCase".to_string()),
+        ]);
+        assert_eq!(misnamed(&rules, "src/new_mod.rs"), None);
+        assert_eq!(
+            misnamed(&rules, "src/NewName.rs").as_deref(),
+            Some("src/NewName.rs (snake_case)")
+        );
+        assert_eq!(misnamed(&rules, "components/Button.test.tsx"), None);
+        assert!(misnamed(&rules, "components/button.tsx").is_some());
+        assert_eq!(follows_convention("x", "SCREAMING"), None);
+    }
+}
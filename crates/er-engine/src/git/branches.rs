@@ -0,0 +1,179 @@
+//! Branches for the branch picker: local and remote-tracking branches with
+//! their upstream ahead/behind and last commit, and the `git switch` /
+//! `git stash` calls switching the working tree needs.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::process::Command;
+
+/// A branch the working tree can switch to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchInfo {
+    /// Short name: `feature/x`, or `origin/feature/x` for a remote branch
+    pub name: String,
+    pub remote: bool,
+    /// Checked out in this working tree
+    pub current: bool,
+    /// Commits ahead of / behind its upstream (0 without one)
+    pub ahead: usize,
+    pub behind: usize,
+    /// Abbreviated hash of the last commit
+    pub hash: String,
+    /// When the last commit was made (`3 days ago`)
+    pub age: String,
+    pub subject: String,
+}
+
+impl BranchInfo {
+    /// The name of the local branch `git switch` makes for a remote one.
+    pub fn local_name(&self) -> &str {
+        if self.remote {
+            self.name
+                .split_once('/')
+                .map_or(&self.name, |(_, rest)| rest)
+        } else {
+            &self.name
+        }
+    }
+}
+
+const BRANCH_FORMAT: &str = "--format=%(HEAD)%1f%(refname)%1f%(refname:short)%1f\
+     %(upstream:track,nobracket)%1f%(objectname:short)%1f%(committerdate:relative)%1f\
+     %(subject)%1f%(symref)";
+
+/// Local branches, then remote ones without a local branch of the same
+/// name, each most recently committed first.
+pub fn git_branches(repo_root: &str) -> Result<Vec<BranchInfo>> {
+    let output = crate::command::run(
+        Command::new("git")
+            .args([
+                "for-each-ref",
+                "--sort=-committerdate",
+                BRANCH_FORMAT,
+                "refs/heads",
+                "refs/remotes",
+            ])
+            .current_dir(repo_root),
+    )?;
+    Ok(parse_branches(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git for-each-ref` with [`BRANCH_FORMAT`].
+fn parse_branches(output: &str) -> Vec<BranchInfo> {
+    let mut local = Vec::new();
+    let mut remote = Vec::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split('\x1f').collect();
+        let [head, refname, name, track, hash, age, subject, symref] = fields[..] else {
+            continue;
+        };
+        if !symref.is_empty() || name.is_empty() {
+            continue;
+        }
+        let (ahead, behind) = parse_track(track);
+        let branch = BranchInfo {
+            name: name.to_string(),
+            remote: refname.starts_with("refs/remotes/"),
+            current: head == "*",
+            ahead,
+            behind,
+            hash: hash.to_string(),
+            age: age.to_string(),
+            subject: subject.trim().to_string(),
+        };
+        if branch.remote {
+            remote.push(branch);
+        } else {
+            local.push(branch);
+        }
+    }
+    let local_names: HashSet<String> = local.iter().map(|b| b.name.clone()).collect();
+    remote.retain(|b| !local_names.contains(b.local_name()));
+    local.extend(remote);
+    local
+}
+
+/// `ahead 2, behind 1` → (2, 1); `gone` and empty → (0, 0).
+fn parse_track(track: &str) -> (usize, usize) {
+    let mut ahead = 0;
+    let mut behind = 0;
+    for part in track.split(',') {
+        match part.trim().split_once(' ') {
+            Some(("ahead", n)) => ahead = n.parse().unwrap_or(0),
+            Some(("behind", n)) => behind = n.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (ahead, behind)
+}
+
+/// Tracked files with uncommitted changes, staged or not. Untracked files
+/// don't count: `git switch` carries them over, or refuses on a clash.
+pub fn uncommitted_changes(repo_root: &str) -> Result<usize> {
+    let output = crate::command::run(
+        Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .current_dir(repo_root),
+    )?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count())
+}
+
+/// Check out `branch`. A remote branch (`origin/x`) gets a local branch
+/// tracking it.
+pub fn git_switch(repo_root: &str, branch: &str, remote: bool) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.arg("switch").current_dir(repo_root);
+    if remote {
+        cmd.args(["--track", branch]);
+    } else {
+        cmd.arg(branch);
+    }
+    crate::command::run(&mut cmd)?;
+    Ok(())
+}
+
+/// Stash the tracked changes, staged ones included.
+pub fn git_stash_push(repo_root: &str, message: &str) -> Result<()> {
+    crate::command::run(
+        Command::new("git")
+            .args(["stash", "push", "-m", message])
+            .current_dir(repo_root),
+    )?;
+    Ok(())
+}
+
+/// Put the last stash back.
+pub fn git_stash_pop(repo_root: &str) -> Result<()> {
+    crate::command::run(
+        Command::new("git")
+            .args(["stash", "pop", "--index"])
+            .current_dir(repo_root),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branches_list_local_first_and_hide_remotes_already_checked_out() {
+        let output = "*\x1frefs/heads/main\x1fmain\x1fahead 2, behind 1\x1fabc1234\x1f2 days ago\x1fFix it\x1f\n\
+                       \x20\x1frefs/heads/old\x1fold\x1fgone\x1fdef5678\x1f3 weeks ago\x1fOld\x1f\n\
+                       \x20\x1frefs/remotes/origin/HEAD\x1forigin\x1f\x1fabc1234\x1f2 days ago\x1fFix it\x1frefs/remotes/origin/main\n\
+                       \x20\x1frefs/remotes/origin/main\x1forigin/main\x1f\x1fabc1234\x1f2 days ago\x1fFix it\x1f\n\
+                       \x20\x1frefs/remotes/origin/feature/x\x1forigin/feature/x\x1f\x1f9999999\x1f1 hour ago\x1fWIP\x1f\n";
+        let branches = parse_branches(output);
+        let names: Vec<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["main", "old", "origin/feature/x"]);
+        assert!(branches[0].current);
+        assert_eq!((branches[0].ahead, branches[0].behind), (2, 1));
+        assert_eq!((branches[1].ahead, branches[1].behind), (0, 0));
+        assert!(branches[2].remote);
+        assert_eq!(branches[2].local_name(), "feature/x");
+        assert_eq!(branches[2].age, "1 hour ago");
+    }
+}
@@ -0,0 +1,294 @@
+//! Commit message checks (`[commit_lint]`).
+//!
+//! Each rule looks at the subject line, except the issue reference, which may
+//! sit anywhere in the message. Merge commits are never flagged — their
+//! message is written by git.
+
+use crate::config::CommitLintConfig;
+
+/// Types accepted by the Conventional Commits check.
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// Verbs the imperative-mood check knows the other forms of ("Added",
+/// "Adds", "Adding" → "Add"). Anything else passes.
+const COMMON_VERBS: &[&str] = &[
+    "add",
+    "allow",
+    "apply",
+    "avoid",
+    "bump",
+    "change",
+    "check",
+    "clean",
+    "clarify",
+    "convert",
+    "create",
+    "delete",
+    "deprecate",
+    "disable",
+    "document",
+    "drop",
+    "enable",
+    "ensure",
+    "expose",
+    "extract",
+    "fix",
+    "handle",
+    "hide",
+    "implement",
+    "improve",
+    "include",
+    "introduce",
+    "keep",
+    "load",
+    "make",
+    "merge",
+    "migrate",
+    "move",
+    "optimize",
+    "parse",
+    "prevent",
+    "refactor",
+    "reduce",
+    "remove",
+    "rename",
+    "reorder",
+    "replace",
+    "restore",
+    "revert",
+    "rework",
+    "rewrite",
+    "run",
+    "separate",
+    "set",
+    "show",
+    "simplify",
+    "skip",
+    "sort",
+    "split",
+    "stop",
+    "support",
+    "switch",
+    "test",
+    "tidy",
+    "tweak",
+    "update",
+    "upgrade",
+    "use",
+    "validate",
+    "wrap",
+];
+
+/// Which rule a message broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitLintRule {
+    SubjectLength,
+    Imperative,
+    Conventional,
+    IssueReference,
+}
+
+/// One broken rule, with a short explanation for the commit list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitLintIssue {
+    pub rule: CommitLintRule,
+    pub message: String,
+}
+
+/// Check a full commit message (subject, blank line, body) against the
+/// enabled rules.
+pub fn lint_commit_message(message: &str, config: &CommitLintConfig) -> Vec<CommitLintIssue> {
+    let subject = message.lines().next().unwrap_or("").trim();
+    if subject.starts_with("Merge ") {
+        return Vec::new();
+    }
+    let mut issues = Vec::new();
+    let len = subject.chars().count();
+    if config.max_subject > 0 && len > config.max_subject {
+        issues.push(CommitLintIssue {
+            rule: CommitLintRule::SubjectLength,
+            message: format!("subject is {} chars (max {})", len, config.max_subject),
+        });
+    }
+    let description = conventional_description(subject);
+    if config.conventional && description.is_none() {
+        issues.push(CommitLintIssue {
+            rule: CommitLintRule::Conventional,
+            message: "not `type(scope): subject`".into(),
+        });
+    }
+    if config.imperative {
+        let first = description
+            .unwrap_or(subject)
+            .split_whitespace()
+            .next()
+            .unwrap_or("");
+        if let Some(verb) = imperative_form(first) {
+            issues.push(CommitLintIssue {
+                rule: CommitLintRule::Imperative,
+                message: format!("“{}” — write “{}”", first, verb),
+            });
+        }
+    }
+    if config.require_issue && !has_issue_reference(message) {
+        issues.push(CommitLintIssue {
+            rule: CommitLintRule::IssueReference,
+            message: "no issue reference".into(),
+        });
+    }
+    issues
+}
+
+/// The text after `type(scope)!: ` when `subject` is a Conventional Commit.
+fn conventional_description(subject: &str) -> Option<&str> {
+    let (head, description) = subject.split_once(": ")?;
+    let head = head.strip_suffix('!').unwrap_or(head);
+    let kind = match head.split_once('(') {
+        Some((kind, scope)) => {
+            let scope = scope.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            kind
+        }
+        None => head,
+    };
+    (CONVENTIONAL_TYPES.contains(&kind) && !description.trim().is_empty())
+        .then_some(description.trim_start())
+}
+
+/// The imperative verb `word` is a past, third-person or -ing form of, if
+/// it is one of [`COMMON_VERBS`].
+fn imperative_form(word: &str) -> Option<String> {
+    let lower = word
+        .trim_end_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    if COMMON_VERBS.contains(&lower.as_str()) {
+        return None;
+    }
+    let mut stems: Vec<String> = Vec::new();
+    for suffix in ["ing", "ed", "es", "s", "d"] {
+        let Some(stem) = lower.strip_suffix(suffix) else {
+            continue;
+        };
+        stems.push(stem.to_string());
+        stems.push(format!("{}e", stem));
+        if suffix == "ed" || suffix == "es" {
+            if let Some(stem) = stem.strip_suffix('i') {
+                stems.push(format!("{}y", stem));
+            }
+        }
+        // dropped → drop, running → run
+        let mut chars = stem.chars().rev();
+        if let (Some(a), Some(b)) = (chars.next(), chars.next()) {
+            if a == b {
+                stems.push(stem[..stem.len() - a.len_utf8()].to_string());
+            }
+        }
+    }
+    let verb = stems
+        .into_iter()
+        .find(|stem| COMMON_VERBS.contains(&stem.as_str()))?;
+    let mut out: String = word.chars().take(1).flat_map(char::to_uppercase).collect();
+    out.push_str(&verb[verb.chars().next().map_or(0, char::len_utf8)..]);
+    Some(out)
+}
+
+/// `#123`, `GH-123`, `ABC-123`, or an issue / pull request URL.
+fn has_issue_reference(message: &str) -> bool {
+    message
+        .split(|c: char| c.is_whitespace() || "(),;:[]".contains(c))
+        .any(|token| {
+            if let Some(num) = token.rsplit_once('#').map(|(_, n)| n) {
+                if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) {
+                    return true;
+                }
+            }
+            if token.contains("/issues/") || token.contains("/pull/") {
+                return true;
+            }
+            match token.split_once('-') {
+                Some((key, num)) => {
+                    key.len() >= 2
+                        && key
+                            .chars()
+                            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                        && key.starts_with(|c: char| c.is_ascii_uppercase())
+                        && !num.is_empty()
+                        && num.chars().all(|c| c.is_ascii_digit())
+                }
+                None => false,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_rules() -> CommitLintConfig {
+        CommitLintConfig {
+            max_subject: 50,
+            imperative: true,
+            conventional: true,
+            require_issue: true,
+        }
+    }
+
+    fn rules(message: &str, config: &CommitLintConfig) -> Vec<CommitLintRule> {
+        lint_commit_message(message, config)
+            .into_iter()
+            .map(|i| i.rule)
+            .collect()
+    }
+
+    #[test]
+    fn clean_messages_pass_every_rule() {
+        let config = all_rules();
+        assert!(rules("fix(parser): handle empty input\n\nFixes #12", &config).is_empty());
+        assert!(rules("feat!: drop v1 API (ER-204)", &config).is_empty());
+        assert!(rules("Merge branch 'main' into feature", &config).is_empty());
+    }
+
+    #[test]
+    fn each_rule_reports_its_own_problem() {
+        let config = all_rules();
+        assert_eq!(
+            rules(
+                "Added a much longer subject line than the limit allows here",
+                &config
+            ),
+            [
+                CommitLintRule::SubjectLength,
+                CommitLintRule::Conventional,
+                CommitLintRule::Imperative,
+                CommitLintRule::IssueReference
+            ]
+        );
+        let issues = lint_commit_message("fix: simplified retries #3", &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "“simplified” — write “Simplify”");
+    }
+
+    #[test]
+    fn imperative_check_only_flags_known_verb_forms() {
+        assert_eq!(imperative_form("Fixes").as_deref(), Some("Fix"));
+        assert_eq!(imperative_form("dropped").as_deref(), Some("Drop"));
+        assert_eq!(imperative_form("Making").as_deref(), Some("Make"));
+        assert_eq!(imperative_form("Updated,").as_deref(), Some("Update"));
+        assert_eq!(imperative_form("Add"), None);
+        assert_eq!(imperative_form("Process"), None);
+        assert_eq!(imperative_form("README"), None);
+    }
+
+    #[test]
+    fn issue_references_come_in_several_shapes() {
+        assert!(has_issue_reference("Closes owner/repo#9"));
+        assert!(has_issue_reference("see https://github.com/o/r/issues/4"));
+        assert!(has_issue_reference("[PROJ-77] tidy"));
+        assert!(!has_issue_reference("bump to v1.2-3"));
+        assert!(!has_issue_reference("use a #hashtag"));
+    }
+}
@@ -0,0 +1,539 @@
+//! Size and complexity of the functions in a source file.
+//!
+//! A small per-language scanner rather than a full parser: comments and
+//! string literals are blanked out, then brace languages are walked token
+//! by token (a `{` whose header reads like a signature opens a function)
+//! and Python by indentation (`def`). Per function it counts lines, the
+//! deepest nesting of blocks inside it, and cyclomatic complexity as one
+//! plus its branch points (`if`, loops, `case`, `catch`, `&&`, `||`, and
+//! `match` arms past the first). Nested functions are measured on their
+//! own; their branches don't count toward the enclosing one.
+
+use std::path::Path;
+
+/// Metrics of one function, on the lines of the file it was read from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionMetrics {
+    pub name: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    /// Deepest block nesting inside the body; 0 for a flat body
+    pub nesting: usize,
+    pub complexity: usize,
+}
+
+impl FunctionMetrics {
+    pub fn length(&self) -> usize {
+        self.line_end + 1 - self.line_start
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Lang {
+    Rust,
+    Go,
+    /// C, C++, Java, C#, JS/TS, Kotlin, Swift, PHP, …
+    CLike,
+    Python,
+}
+
+fn lang(path: &str) -> Option<Lang> {
+    let ext = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "rs" => Lang::Rust,
+        "go" => Lang::Go,
+        "py" | "pyi" => Lang::Python,
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "java" | "cs" | "js" | "jsx" | "mjs"
+        | "cjs" | "ts" | "tsx" | "kt" | "kts" | "swift" | "php" | "scala" | "dart" => Lang::CLike,
+        _ => return None,
+    })
+}
+
+/// Whether `path` is in a language [`function_metrics`] reads.
+pub fn is_measured(path: &str) -> bool {
+    lang(path).is_some()
+}
+
+/// The functions of `source`, in order of where they start. Empty for a
+/// language that isn't scanned.
+pub fn function_metrics(path: &str, source: &str) -> Vec<FunctionMetrics> {
+    match lang(path) {
+        Some(Lang::Python) => python_functions(&blank_literals(source, Lang::Python)),
+        Some(lang) => brace_functions(&blank_literals(source, lang), lang),
+        None => Vec::new(),
+    }
+}
+
+/// `source` with comments and the inside of string and char literals
+/// turned to spaces, newlines kept, so tokens still sit on their lines.
+fn blank_literals(source: &str, lang: Lang) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let blank = |c: char| if c == '\n' { '\n' } else { ' ' };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let line_comment = match lang {
+            Lang::Python => c == '#',
+            _ => c == '/' && next == Some('/'),
+        };
+        if line_comment {
+            while i < chars.len() && chars[i] != '\n' {
+                out.push(' ');
+                i += 1;
+            }
+            continue;
+        }
+        if lang != Lang::Python && c == '/' && next == Some('*') {
+            out.push_str("  ");
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                out.push(blank(chars[i]));
+                i += 1;
+            }
+            out.push_str("  ");
+            i += 2;
+            continue;
+        }
+        // A Rust `'` is a lifetime unless a char literal closes it
+        let quote = match c {
+            '"' | '`' => true,
+            '\'' if lang == Lang::Rust => {
+                next == Some('\\') || (next.is_some() && chars.get(i + 2) == Some(&'\''))
+            }
+            '\'' => true,
+            _ => false,
+        };
+        if !quote {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        let triple = lang == Lang::Python && next == Some(c) && chars.get(i + 2) == Some(&c);
+        let width = if triple { 3 } else { 1 };
+        out.extend(std::iter::repeat_n(c, width));
+        i += width;
+        while i < chars.len() {
+            if chars[i] == '\\' {
+                out.push(' ');
+                if let Some(&escaped) = chars.get(i + 1) {
+                    out.push(blank(escaped));
+                }
+                i += 2;
+                continue;
+            }
+            let closes = chars[i] == c
+                && (!triple || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)));
+            if closes {
+                out.extend(std::iter::repeat_n(c, width));
+                i += width;
+                break;
+            }
+            // An unclosed one-line literal ends with its line
+            if chars[i] == '\n' && !triple && c != '`' && lang != Lang::Rust {
+                break;
+            }
+            out.push(blank(chars[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Words, two-character operators and single punctuation, with their
+/// 1-based lines.
+fn tokens(text: &str) -> Vec<(&str, usize)> {
+    let mut out = Vec::new();
+    for (ln, line) in text.lines().enumerate() {
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < line.len() {
+            let c = line[i..].chars().next().unwrap_or(' ');
+            let len = c.len_utf8();
+            if c.is_alphanumeric() || c == '_' || c == '$' {
+                let start = i;
+                while i < line.len() {
+                    let c = line[i..].chars().next().unwrap_or(' ');
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    i += c.len_utf8();
+                }
+                out.push((&line[start..i], ln + 1));
+                continue;
+            }
+            if !c.is_whitespace() {
+                let pair = bytes
+                    .get(i..i + 2)
+                    .and_then(|p| std::str::from_utf8(p).ok());
+                if let Some(op @ ("&&" | "||" | "=>" | "->" | "::")) = pair {
+                    out.push((op, ln + 1));
+                    i += 2;
+                    continue;
+                }
+                out.push((&line[i..i + len], ln + 1));
+            }
+            i += len;
+        }
+    }
+    out
+}
+
+fn is_word(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+}
+
+/// Words before `(` that make a block, not a function.
+const CONTROL: &[&str] = &[
+    "if",
+    "for",
+    "foreach",
+    "while",
+    "switch",
+    "catch",
+    "using",
+    "lock",
+    "synchronized",
+    "with",
+    "return",
+    "new",
+    "else",
+    "do",
+    "try",
+    "when",
+    "guard",
+    "sizeof",
+];
+
+/// Words that open a type or namespace body.
+const TYPE_WORDS: &[&str] = &[
+    "class",
+    "struct",
+    "enum",
+    "interface",
+    "namespace",
+    "impl",
+    "trait",
+    "mod",
+    "union",
+    "extension",
+    "object",
+];
+
+/// The function a `{` after `header` opens, as (name, line), if it opens one.
+fn function_header(header: &[(&str, usize)], lang: Lang) -> Option<(String, usize)> {
+    let words = |w: &[&str]| header.iter().position(|(t, _)| w.contains(t));
+    let word_after = |at: usize| header.get(at + 1).filter(|(t, _)| is_word(t));
+    match lang {
+        Lang::Rust => {
+            let at = words(&["fn"])?;
+            let (name, line) = word_after(at)?;
+            Some((name.to_string(), *line))
+        }
+        Lang::Go => {
+            let at = words(&["func"])?;
+            // `func (r *T) Name(…)`: the first word followed by `(`
+            let (name, line) = header[at + 1..]
+                .windows(2)
+                .find(|w| is_word(w[0].0) && w[1].0 == "(")
+                .map(|w| w[0])?;
+            Some((name.to_string(), line))
+        }
+        Lang::CLike => {
+            if header.last()?.0 == "=>" {
+                // `const name = (…) => {`, or a callback named after its call
+                let name = header
+                    .iter()
+                    .position(|(t, _)| *t == "=")
+                    .and_then(|eq| eq.checked_sub(1))
+                    .or_else(|| header.iter().position(|(t, _)| *t == "(")?.checked_sub(1))
+                    .map(|i| header[i])
+                    .filter(|(t, _)| is_word(t));
+                let (name, line) = name.unwrap_or(("<anonymous>", header.last()?.1));
+                return Some((name.to_string(), line));
+            }
+            if let Some(at) = words(&["function", "func", "fun"]) {
+                let (name, line) = match word_after(at) {
+                    Some(named) => *named,
+                    None => ("<anonymous>", header[at].1),
+                };
+                return Some((name.to_string(), line));
+            }
+            let paren = header.iter().position(|(t, _)| *t == "(")?;
+            // `x = call(…) {` is no signature; `f(a = 1) {` is one
+            if words(TYPE_WORDS).is_some() || header[..paren].iter().any(|(t, _)| *t == "=") {
+                return None;
+            }
+            let (name, line) = header[paren.checked_sub(1)?];
+            let before = paren.checked_sub(2).map(|i| header[i].0);
+            if !is_word(name) || CONTROL.contains(&name) || before == Some("new") {
+                return None;
+            }
+            // After the parameters: only qualifiers (`const`, `throws E`,
+            // `: Type`), never a call chain
+            let close = header.iter().rposition(|(t, _)| *t == ")")?;
+            let tail_ok = header[close + 1..].iter().all(|(t, _)| {
+                is_word(t)
+                    || matches!(
+                        *t,
+                        ":" | ","
+                            | "<"
+                            | ">"
+                            | "?"
+                            | "&"
+                            | "|"
+                            | "*"
+                            | "->"
+                            | "["
+                            | "]"
+                            | "."
+                            | "::"
+                    )
+            });
+            tail_ok.then(|| (name.to_string(), line))
+        }
+        Lang::Python => None,
+    }
+}
+
+/// Branch points a token adds.
+fn branches(token: &str, lang: Lang) -> usize {
+    match token {
+        "if" | "for" | "while" | "case" | "catch" | "&&" | "||" => 1,
+        "=>" if lang == Lang::Rust => 1,
+        "foreach" | "except" | "elif" | "and" | "or" => 1,
+        _ => 0,
+    }
+}
+
+fn brace_functions(text: &str, lang: Lang) -> Vec<FunctionMetrics> {
+    enum Frame {
+        /// Index into `found`, and how many blocks were open at its start
+        Function(usize, usize),
+        Block,
+    }
+    let tokens = tokens(text);
+    let mut found: Vec<FunctionMetrics> = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut blocks_open = 0usize;
+    let mut header_start = 0;
+    for (i, &(token, line)) in tokens.iter().enumerate() {
+        let current = stack.iter().rev().find_map(|f| match f {
+            Frame::Function(idx, depth) => Some((*idx, *depth)),
+            Frame::Block => None,
+        });
+        match token {
+            "{" => {
+                match function_header(&tokens[header_start..i], lang) {
+                    Some((name, start)) => {
+                        stack.push(Frame::Function(found.len(), blocks_open));
+                        found.push(FunctionMetrics {
+                            name,
+                            line_start: start,
+                            line_end: line,
+                            nesting: 0,
+                            complexity: 1,
+                        });
+                    }
+                    None => {
+                        blocks_open += 1;
+                        stack.push(Frame::Block);
+                        if let Some((idx, depth)) = current {
+                            let f = &mut found[idx];
+                            f.nesting = f.nesting.max(blocks_open - depth);
+                        }
+                    }
+                }
+                header_start = i + 1;
+            }
+            "}" => {
+                match stack.pop() {
+                    Some(Frame::Function(idx, _)) => found[idx].line_end = line,
+                    Some(Frame::Block) => blocks_open -= 1,
+                    None => {}
+                }
+                header_start = i + 1;
+            }
+            ";" => header_start = i + 1,
+            _ => {
+                // A Rust `||` after no operand is a closure's empty parameters
+                let closure = lang == Lang::Rust
+                    && token == "||"
+                    && i.checked_sub(1).is_none_or(|p| {
+                        let prev = tokens[p].0;
+                        !(is_word(prev) && prev != "move" || matches!(prev, ")" | "]" | "?"))
+                    });
+                if let (Some((idx, _)), false) = (current, closure) {
+                    found[idx].complexity += branches(token, lang);
+                    if token == "match" && lang == Lang::Rust {
+                        // n arms are n − 1 branches
+                        found[idx].complexity = found[idx].complexity.saturating_sub(1);
+                    }
+                }
+            }
+        }
+    }
+    found.sort_by_key(|f| f.line_start);
+    found
+}
+
+fn python_functions(text: &str) -> Vec<FunctionMetrics> {
+    struct Open {
+        idx: usize,
+        indent: usize,
+        /// Indents of the blocks open in the body
+        levels: Vec<usize>,
+    }
+    let mut found: Vec<FunctionMetrics> = Vec::new();
+    let mut open: Vec<Open> = Vec::new();
+    for (ln, line) in text.lines().enumerate() {
+        let code = line.trim_start();
+        if code.is_empty() {
+            continue;
+        }
+        let indent = line.len() - code.len();
+        while open.last().is_some_and(|o| indent <= o.indent) {
+            open.pop();
+        }
+        if let Some(o) = open.last_mut() {
+            while o.levels.last().is_some_and(|l| indent < *l) {
+                o.levels.pop();
+            }
+            if o.levels.last().is_none_or(|l| indent > *l) {
+                o.levels.push(indent);
+            }
+            let f = &mut found[o.idx];
+            f.line_end = ln + 1;
+            f.nesting = f.nesting.max(o.levels.len() - 1);
+            f.complexity += tokens(code)
+                .iter()
+                .map(|(t, _)| branches(t, Lang::Python))
+                .sum::<usize>();
+        }
+        let def = code.strip_prefix("async ").unwrap_or(code);
+        if let Some(rest) = def.strip_prefix("def ") {
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            open.push(Open {
+                idx: found.len(),
+                indent,
+                levels: Vec::new(),
+            });
+            found.push(FunctionMetrics {
+                name,
+                line_start: ln + 1,
+                line_end: ln + 1,
+                nesting: 0,
+                complexity: 1,
+            });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn by_name<'a>(found: &'a [FunctionMetrics], name: &str) -> &'a FunctionMetrics {
+        found.iter().find(|f| f.name == name).unwrap()
+    }
+
+    #[test]
+    fn rust_functions_are_measured() {
+        let source = r#"
+impl Parser {
+    /// Says "if { for" here
+    fn parse(&mut self, input: &str) -> Result<u32, Error> {
+        let lifetime: &'static str = "while { }";
+        let _lazy = input.chars().find(|_| true).unwrap_or_else(|| 'x');
+        for c in input.chars() {
+            if c == '{' && self.strict {
+                match c {
+                    'a' => return Ok(1),
+                    'b' | 'c' => return Ok(2),
+                    _ => {}
+                }
+            }
+        }
+        Ok(0)
+    }
+
+    fn empty() {}
+}
+"#;
+        let found = function_metrics("src/parser.rs", source);
+        assert_eq!(found.len(), 2);
+        let parse = by_name(&found, "parse");
+        assert_eq!(
+            (parse.line_start, parse.line_end, parse.length()),
+            (4, 17, 14)
+        );
+        // for → if → match → `_ => {}`
+        assert_eq!(parse.nesting, 4);
+        // 1 + for + if + && + 2 more match arms
+        assert_eq!(parse.complexity, 6);
+        let empty = by_name(&found, "empty");
+        assert_eq!((empty.nesting, empty.complexity), (0, 1));
+        assert!(function_metrics("README.md", source).is_empty());
+    }
+
+    #[test]
+    fn c_like_methods_arrows_and_go_receivers() {
+        let source = r#"
+class Cart {
+  total(items) {
+    if (items.length === 0) { return 0; }
+    return items.reduce((sum, item) => {
+      return sum + item.price;
+    }, 0);
+  }
+}
+const handler = async (req) => {
+  while (req.next() || req.retry) {}
+};
+"#;
+        let found = function_metrics("cart.ts", source);
+        let names: Vec<&str> = found.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["total", "reduce", "handler"]);
+        assert_eq!(by_name(&found, "total").complexity, 2);
+        assert_eq!(by_name(&found, "total").nesting, 1);
+        assert_eq!(by_name(&found, "handler").complexity, 3);
+
+        let go = "func (s *Server) Handle(w http.ResponseWriter) error {\n\tif s == nil {\n\t\treturn nil\n\t}\n\treturn nil\n}\n";
+        let found = function_metrics("server.go", go);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].name.as_str(), found[0].line_end), ("Handle", 6));
+    }
+
+    #[test]
+    fn python_functions_by_indentation() {
+        let source = "\
+def load(path):
+    # if this were code
+    with open(path) as f:
+        for line in f:
+            if line and not line.startswith('#'):
+                yield line
+
+    return None
+
+async def other():
+    pass
+";
+        let found = function_metrics("load.py", source);
+        assert_eq!(found.len(), 2);
+        let load = by_name(&found, "load");
+        assert_eq!((load.line_start, load.line_end), (1, 8));
+        assert_eq!(load.nesting, 3);
+        // 1 + for + if + and
+        assert_eq!(load.complexity, 4);
+        assert_eq!(by_name(&found, "other").line_start, 10);
+    }
+}
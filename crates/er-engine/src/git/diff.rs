@@ -87,6 +87,7 @@ pub struct DiffFileHeader {
 /// Extracts file paths, status, +/- counts, and byte offsets without
 /// allocating DiffLine structs. ~10x faster than full parse for large diffs.
 pub fn parse_diff_headers(raw: &str) -> Vec<DiffFileHeader> {
+    let _profile = crate::profile::scope("parse headers");
     let mut headers: Vec<DiffFileHeader> = Vec::new();
     let mut current_header: Option<DiffFileHeader> = None;
     let mut byte_pos: usize = 0;
@@ -251,12 +252,7 @@ pub fn lazy_files_with_compaction(
         .map(|h| {
             let mut file = header_to_stub(h);
             let total_lines = h.adds + h.dels;
-            let should_compact = config.enabled
-                && (config
-                    .patterns
-                    .iter()
-                    .any(|p| compact_files_match(p, &file.path))
-                    || total_lines > config.max_lines_before_compact);
+            let should_compact = config.should_compact(&file.path, total_lines);
 
             if !is_user_expanded(&file.path) && should_compact {
                 file.compacted = true;
@@ -287,6 +283,7 @@ pub fn header_to_stub(header: &DiffFileHeader) -> DiffFile {
 
 /// Parse unified diff output into structured data
 pub fn parse_diff(raw: &str) -> Vec<DiffFile> {
+    let _profile = crate::profile::scope("parse diff");
     let mut files: Vec<DiffFile> = Vec::new();
     let mut current_file: Option<DiffFile> = None;
     let mut current_hunk: Option<DiffHunk> = None;
@@ -575,6 +572,14 @@ pub struct CompactionConfig {
     pub enabled: bool,
     pub patterns: Vec<String>,
     pub max_lines_before_compact: usize,
+    /// Working-tree files over this size start compacted (0 = no limit)
+    pub max_file_bytes: u64,
+    /// Paths that never start compacted (`[large_files] always_expand`)
+    pub always_expand: Vec<String>,
+    /// Paths learned to never start compacted (see `compaction_profile`)
+    pub learned_expanded: Vec<String>,
+    /// Paths learned to always start compacted
+    pub learned_compacted: Vec<String>,
 }
 
 impl Default for CompactionConfig {
@@ -586,6 +591,10 @@ impl Default for CompactionConfig {
                 .map(|s| s.to_string())
                 .collect(),
             max_lines_before_compact: 2000,
+            max_file_bytes: 0,
+            always_expand: Vec::new(),
+            learned_expanded: Vec::new(),
+            learned_compacted: Vec::new(),
         }
     }
 }
@@ -622,16 +631,28 @@ fn glob_match(pattern: &str, path: &str) -> bool {
     }
 }
 
+impl CompactionConfig {
+    /// Whether a file at `path` with `total_lines` diff lines starts compacted.
+    pub fn should_compact(&self, path: &str, total_lines: usize) -> bool {
+        self.enabled
+            && !self.always_expand.iter().any(|p| p == path)
+            && !self.learned_expanded.iter().any(|p| p == path)
+            && (self.learned_compacted.iter().any(|p| p == path)
+                || self.patterns.iter().any(|p| glob_match(p, path))
+                || total_lines > self.max_lines_before_compact)
+    }
+}
+
 /// Apply compaction to files based on pattern matching and size thresholds.
 /// Compacted files have their hunks cleared to save memory.
 pub fn compact_files(files: &mut [DiffFile], config: &CompactionConfig) {
+    let _profile = crate::profile::scope("compact");
     if !config.enabled {
         return;
     }
     for file in files.iter_mut() {
         let total_lines: usize = file.hunks.iter().map(|h| h.lines.len()).sum();
-        let should_compact = config.patterns.iter().any(|p| glob_match(p, &file.path))
-            || total_lines > config.max_lines_before_compact;
+        let should_compact = config.should_compact(&file.path, total_lines);
 
         if should_compact {
             file.compacted = true;
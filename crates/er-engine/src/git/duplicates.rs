@@ -0,0 +1,401 @@
+//! Near-duplicate code among the blocks a diff adds, and between them and
+//! the rest of the repo, by winnowing (Schleimer et al., the MOSS scheme).
+//!
+//! A block's lines are split into tokens, whitespace dropped; every run of
+//! [`K`] tokens is hashed, and of each [`WINDOW`] consecutive hashes the
+//! smallest is kept as a fingerprint. Two copies of the same code share
+//! fingerprints however it is indented or wrapped, and a copy that was
+//! lightly edited still shares most of them. A block is a duplicate when
+//! [`MIN_OVERLAP`] of its fingerprints turn up in one other place.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::process::Command;
+
+use super::{DiffHunk, LineType};
+use crate::command::OutputLogged;
+
+/// Tokens per hashed run: shorter shared runs are coincidence.
+pub const K: usize = 15;
+
+/// Hashes per winnowing window.
+pub const WINDOW: usize = 8;
+
+/// Share of a block's fingerprints that must appear in the copy.
+pub const MIN_OVERLAP: f32 = 0.6;
+
+/// Repo files larger than this aren't read.
+const MAX_REPO_FILE_BYTES: u64 = 256 * 1024;
+
+/// Repo files read at most.
+const MAX_REPO_FILES: usize = 5000;
+
+/// A run of added lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddedBlock {
+    pub path: String,
+    pub hunk: usize,
+    /// `(new-side number, content)` of the non-blank lines
+    pub lines: Vec<(usize, String)>,
+}
+
+impl AddedBlock {
+    pub fn first_line(&self) -> usize {
+        self.lines.first().map_or(0, |l| l.0)
+    }
+
+    pub fn last_line(&self) -> usize {
+        self.lines.last().map_or(0, |l| l.0)
+    }
+}
+
+/// Where a block's copy is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyLocation {
+    pub path: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    /// The copy is itself added by the diff
+    pub added: bool,
+}
+
+/// A block and its closest copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duplicate {
+    /// Index into the blocks searched
+    pub block: usize,
+    pub copy: CopyLocation,
+    /// Share of the block's fingerprints in the copy
+    pub overlap: f32,
+    /// Other places sharing as much of it
+    pub more: usize,
+}
+
+/// Runs of at least `min_lines` non-blank added lines in `files`.
+pub fn added_blocks(files: &[(&str, &[DiffHunk])], min_lines: usize) -> Vec<AddedBlock> {
+    let mut blocks = Vec::new();
+    for (path, hunks) in files {
+        for (hi, hunk) in hunks.iter().enumerate() {
+            let mut run: Vec<(usize, String)> = Vec::new();
+            let mut flush = |run: &mut Vec<(usize, String)>| {
+                if run.len() >= min_lines.max(1) {
+                    blocks.push(AddedBlock {
+                        path: path.to_string(),
+                        hunk: hi,
+                        lines: std::mem::take(run),
+                    });
+                }
+                run.clear();
+            };
+            for line in &hunk.lines {
+                match (line.line_type, line.new_num) {
+                    (LineType::Add, Some(n)) => {
+                        if !line.content.trim().is_empty() {
+                            run.push((n, line.content.clone()));
+                        }
+                    }
+                    _ => flush(&mut run),
+                }
+            }
+            flush(&mut run);
+        }
+    }
+    blocks
+}
+
+/// Identifier/number runs and single punctuation characters.
+fn tokens<'a>(line: &'a str, out: &mut Vec<&'a str>) {
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        let word = c.is_alphanumeric() || c == '_';
+        match (word, start) {
+            (true, None) => start = Some(i),
+            (true, Some(_)) => {}
+            (false, Some(s)) => {
+                out.push(&line[s..i]);
+                start = None;
+            }
+            (false, None) => {}
+        }
+        if !word && !c.is_whitespace() {
+            out.push(&line[i..i + c.len_utf8()]);
+        }
+    }
+    if let Some(s) = start {
+        out.push(&line[s..]);
+    }
+}
+
+/// Winnowed `(hash, line)` fingerprints of `lines`, the line being where
+/// the hashed run starts.
+pub fn fingerprints<'a>(lines: impl IntoIterator<Item = (usize, &'a str)>) -> Vec<(u64, usize)> {
+    let mut toks: Vec<&str> = Vec::new();
+    let mut tok_lines: Vec<usize> = Vec::new();
+    for (number, text) in lines {
+        let before = toks.len();
+        tokens(text, &mut toks);
+        tok_lines.extend(std::iter::repeat_n(number, toks.len() - before));
+    }
+    if toks.len() < K {
+        return Vec::new();
+    }
+    let hashes: Vec<u64> = toks
+        .windows(K)
+        .map(|run| {
+            let mut h = DefaultHasher::new();
+            run.hash(&mut h);
+            h.finish()
+        })
+        .collect();
+    let mut picked: Vec<(u64, usize)> = Vec::new();
+    let mut last: Option<usize> = None;
+    for start in 0..hashes.len().saturating_sub(WINDOW - 1).max(1) {
+        let window = &hashes[start..(start + WINDOW).min(hashes.len())];
+        // Rightmost minimum, so a run of equal hashes is picked once
+        let (offset, _) = window
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by_key(|(_, h)| **h)
+            .expect("window is never empty");
+        let at = start + offset;
+        if last != Some(at) {
+            picked.push((hashes[at], tok_lines[at]));
+            last = Some(at);
+        }
+    }
+    picked
+}
+
+/// A place fingerprints come from: an added block, or a repo file.
+enum Source {
+    Block(usize),
+    File(String),
+}
+
+/// The repo's tracked files with one of `extensions`, for a working-tree
+/// scan.
+fn repo_files(repo_root: &str, extensions: &HashSet<String>) -> Vec<String> {
+    let Ok(output) = Command::new("git")
+        .args(["ls-files", "-z"])
+        .current_dir(repo_root)
+        .logged_output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| {
+            Path::new(p)
+                .extension()
+                .is_some_and(|e| extensions.contains(&*e.to_string_lossy()))
+        })
+        .take(MAX_REPO_FILES)
+        .map(String::from)
+        .collect()
+}
+
+/// The closest copy of each of `blocks`: another block, or code already in
+/// the working tree at `repo_root` (lines the diff adds don't count there).
+pub fn find_duplicates(blocks: &[AddedBlock], repo_root: Option<&str>) -> Vec<Duplicate> {
+    let block_prints: Vec<Vec<(u64, usize)>> = blocks
+        .iter()
+        .map(|b| fingerprints(b.lines.iter().map(|(n, l)| (*n, l.as_str()))))
+        .collect();
+    let wanted: HashSet<u64> = block_prints.iter().flatten().map(|(h, _)| *h).collect();
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sources: Vec<Source> = Vec::new();
+    // hash → (source, line) for the hashes some block has
+    let mut index: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    for (bi, prints) in block_prints.iter().enumerate() {
+        let source = sources.len();
+        sources.push(Source::Block(bi));
+        for &(h, line) in prints {
+            index.entry(h).or_default().push((source, line));
+        }
+    }
+    if let Some(root) = repo_root {
+        let mut added: HashMap<&str, HashSet<usize>> = HashMap::new();
+        for b in blocks {
+            added
+                .entry(b.path.as_str())
+                .or_default()
+                .extend(b.lines.iter().map(|l| l.0));
+        }
+        let extensions: HashSet<String> = blocks
+            .iter()
+            .filter_map(|b| Path::new(&b.path).extension())
+            .map(|e| e.to_string_lossy().to_string())
+            .collect();
+        for path in repo_files(root, &extensions) {
+            let full = Path::new(root).join(&path);
+            if std::fs::metadata(&full).map_or(true, |m| m.len() > MAX_REPO_FILE_BYTES) {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(&full) else {
+                continue;
+            };
+            let skip = added.get(path.as_str());
+            let lines = text
+                .lines()
+                .enumerate()
+                .map(|(i, l)| (i + 1, l))
+                .filter(|(n, l)| !l.trim().is_empty() && !skip.is_some_and(|s| s.contains(n)));
+            let prints: Vec<(u64, usize)> = fingerprints(lines)
+                .into_iter()
+                .filter(|(h, _)| wanted.contains(h))
+                .collect();
+            if prints.is_empty() {
+                continue;
+            }
+            let source = sources.len();
+            sources.push(Source::File(path));
+            for (h, line) in prints {
+                index.entry(h).or_default().push((source, line));
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    for (bi, prints) in block_prints.iter().enumerate() {
+        if prints.is_empty() {
+            continue;
+        }
+        let own: HashSet<u64> = prints.iter().map(|(h, _)| *h).collect();
+        // source → (shared hashes, first line, last line)
+        let mut shared: HashMap<usize, (HashSet<u64>, usize, usize)> = HashMap::new();
+        for h in &own {
+            for &(source, line) in index.get(h).into_iter().flatten() {
+                if matches!(sources[source], Source::Block(other) if other == bi) {
+                    continue;
+                }
+                let entry = shared
+                    .entry(source)
+                    .or_insert_with(|| (HashSet::new(), line, line));
+                entry.0.insert(*h);
+                entry.1 = entry.1.min(line);
+                entry.2 = entry.2.max(line);
+            }
+        }
+        let mut copies: Vec<(usize, f32, usize, usize)> = shared
+            .into_iter()
+            .map(|(source, (hashes, start, end))| {
+                (source, hashes.len() as f32 / own.len() as f32, start, end)
+            })
+            .filter(|c| c.1 >= MIN_OVERLAP)
+            .collect();
+        // Best overlap first; ties go to copies in the diff, then by place
+        copies.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then_with(|| a.0.cmp(&b.0))
+                .then_with(|| a.2.cmp(&b.2))
+        });
+        let Some(&(source, overlap, start, end)) = copies.first() else {
+            continue;
+        };
+        // A copy in the diff is the whole block; a repo one spans its matches
+        let copy = match &sources[source] {
+            Source::Block(other) => {
+                let other = &blocks[*other];
+                CopyLocation {
+                    path: other.path.clone(),
+                    line_start: other.first_line(),
+                    line_end: other.last_line(),
+                    added: true,
+                }
+            }
+            Source::File(path) => CopyLocation {
+                path: path.clone(),
+                line_start: start,
+                line_end: end,
+                added: false,
+            },
+        };
+        found.push(Duplicate {
+            block: bi,
+            copy,
+            overlap,
+            more: copies.len() - 1,
+        });
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    const BODY: &str = "fn total(items: &[Item]) -> u64 {\n\
+                        let mut sum = 0;\n\
+                        for item in items {\n\
+                        if item.active {\n\
+                        sum += item.price * item.quantity;\n\
+                        }\n\
+                        }\n\
+                        sum\n\
+                        }";
+
+    fn diff_adding(path: &str, body: &str, indent: &str) -> String {
+        let lines: Vec<String> = body.lines().map(|l| format!("+{}{}", indent, l)).collect();
+        format!(
+            "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n@@ -1,1 +1,{n} @@\n use x;\n{}\n",
+            lines.join("\n"),
+            p = path,
+            n = lines.len() + 1
+        )
+    }
+
+    #[test]
+    fn fingerprints_ignore_layout() {
+        let flat = fingerprints(BODY.lines().enumerate().map(|(i, l)| (i + 1, l)));
+        assert!(flat.len() >= 3, "{}", flat.len());
+        let indented: Vec<String> = BODY.lines().map(|l| format!("        {}", l)).collect();
+        let moved = fingerprints(
+            indented
+                .iter()
+                .enumerate()
+                .map(|(i, l)| (i + 40, l.as_str())),
+        );
+        let hashes = |p: &[(u64, usize)]| p.iter().map(|(h, _)| *h).collect::<Vec<_>>();
+        assert_eq!(hashes(&flat), hashes(&moved));
+        assert_eq!(moved[0].1, 40);
+        assert!(fingerprints([(1, "let x = 1;")]).is_empty());
+    }
+
+    #[test]
+    fn copies_between_added_blocks_link_both_ways() {
+        let raw = format!(
+            "{}{}{}",
+            diff_adding("src/a.rs", BODY, ""),
+            diff_adding("src/b.rs", &BODY.replace("quantity", "qty"), "    "),
+            diff_adding(
+                "src/c.rs",
+                "let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\nlet e = 5;\nlet f = 6;",
+                ""
+            )
+        );
+        let files = parse_diff(&raw);
+        let hunks: Vec<(&str, &[DiffHunk])> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.hunks.as_slice()))
+            .collect();
+        let blocks = added_blocks(&hunks, 6);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!((blocks[0].first_line(), blocks[0].last_line()), (2, 10));
+
+        let dups = find_duplicates(&blocks, None);
+        assert_eq!(dups.len(), 2);
+        assert_eq!(dups[0].block, 0);
+        assert_eq!(dups[0].copy.path, "src/b.rs");
+        assert!(dups[0].copy.added);
+        assert!(dups[0].overlap < 1.0, "one renamed field changes some runs");
+        assert_eq!(dups[1].copy.path, "src/a.rs");
+        assert_eq!(dups[1].copy.line_start, 2);
+    }
+}
@@ -0,0 +1,285 @@
+//! Non-UTF-8 text in diffs. `git diff` prints file content in whatever
+//! encoding the file uses, so sections that aren't valid UTF-8 are detected
+//! (Windows-1252/Latin-1, Shift_JIS or EUC-JP) and converted before parsing
+//! instead of being replaced with `�`. Files that declare a
+//! `working-tree-encoding` in `.gitattributes` are labelled with it.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Bytes of a working-tree file sniffed for its encoding.
+const SNIFF_BYTES: usize = 8192;
+
+/// Files checked for an encoding label per refresh.
+const MAX_LABELLED_FILES: usize = 500;
+
+/// A text encoding this module can tell apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// Also covers ISO-8859-1, which it's a superset of for printable text
+    Windows1252,
+    ShiftJis,
+    EucJp,
+}
+
+impl TextEncoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Windows1252 => "Windows-1252",
+            TextEncoding::ShiftJis => "Shift_JIS",
+            TextEncoding::EucJp => "EUC-JP",
+        }
+    }
+}
+
+/// Guess the encoding of `bytes`. Valid UTF-8 wins; otherwise high bytes
+/// that mostly come in pairs point to a double-byte Japanese encoding (EUC-JP
+/// when the pairs fit it at least as well), and isolated ones to
+/// Windows-1252.
+pub fn detect_encoding(bytes: &[u8]) -> TextEncoding {
+    if std::str::from_utf8(bytes).is_ok() {
+        return TextEncoding::Utf8;
+    }
+    let (mut high, mut paired, mut euc_pairs, mut sjis_pairs) = (0usize, 0usize, 0usize, 0usize);
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b < 0x80 {
+            i += 1;
+            continue;
+        }
+        high += 1;
+        match bytes.get(i + 1) {
+            Some(&next) if next >= 0x40 && (b >= 0x81 || next >= 0x80) && next != 0x7f => {
+                // Both bytes high, or a Shift_JIS-only lead byte with an ASCII trail
+                if next >= 0x80 {
+                    paired += 2;
+                    high += 1;
+                } else if b <= 0x9f {
+                    paired += 1;
+                }
+                if (0xa1..=0xfe).contains(&b) && (0xa1..=0xfe).contains(&next) {
+                    euc_pairs += 1;
+                }
+                if matches!(b, 0x81..=0x9f | 0xe0..=0xfc)
+                    && matches!(next, 0x40..=0x7e | 0x80..=0xfc)
+                {
+                    sjis_pairs += 1;
+                }
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+    if high == 0 || paired * 2 <= high {
+        return TextEncoding::Windows1252;
+    }
+    if euc_pairs >= sjis_pairs {
+        TextEncoding::EucJp
+    } else {
+        TextEncoding::ShiftJis
+    }
+}
+
+/// Windows-1252 code points for 0x80..=0x9F (unassigned bytes map to C1).
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decode `bytes` as `encoding`. The Japanese encodings go through `iconv`;
+/// without it (or on bad input) this falls back to lossy UTF-8.
+pub fn decode(bytes: &[u8], encoding: TextEncoding) -> String {
+    match encoding {
+        TextEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        TextEncoding::Windows1252 => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9f => CP1252_HIGH[(b - 0x80) as usize],
+                _ => b as char,
+            })
+            .collect(),
+        TextEncoding::ShiftJis | TextEncoding::EucJp => iconv_to_utf8(bytes, encoding.label())
+            .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned()),
+    }
+}
+
+fn iconv_to_utf8(bytes: &[u8], from: &str) -> Option<String> {
+    let mut child = Command::new("iconv")
+        .args(["-f", from, "-t", "UTF-8"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut stdin = child.stdin.take()?;
+    let input = bytes.to_vec();
+    // Feed from a thread so a large section can't fill the stdout pipe first
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+    let output = child.wait_with_output().ok()?;
+    writer.join().ok()?.ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8(output.stdout).ok())
+        .flatten()
+}
+
+/// `git diff` output as text, converting each file whose section isn't
+/// UTF-8 from its detected encoding.
+pub fn decode_diff_output(bytes: &[u8]) -> String {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(bytes.len());
+    for section in diff_sections(bytes) {
+        match std::str::from_utf8(section) {
+            Ok(text) => out.push_str(text),
+            Err(_) => out.push_str(&decode(section, detect_encoding(section))),
+        }
+    }
+    out
+}
+
+/// `bytes` split before every `diff --git ` line.
+fn diff_sections(bytes: &[u8]) -> Vec<&[u8]> {
+    const MARKER: &[u8] = b"diff --git ";
+    let mut starts = vec![0];
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'\n' && bytes[i + 1..].starts_with(MARKER) {
+            starts.push(i + 1);
+        }
+    }
+    starts.push(bytes.len());
+    starts.windows(2).map(|w| &bytes[w[0]..w[1]]).collect()
+}
+
+/// Encoding labels for files that aren't plain UTF-8: the
+/// `working-tree-encoding` attribute of `paths` when set, else a guess from
+/// the start of the working-tree file for those in `sniff`.
+pub fn file_encodings(repo_root: &str, paths: &[&str], sniff: &[&str]) -> HashMap<String, String> {
+    let mut labels = working_tree_encodings(repo_root, paths);
+    for path in sniff.iter().take(MAX_LABELLED_FILES) {
+        if labels.contains_key(*path) {
+            continue;
+        }
+        let Ok(file) = std::fs::File::open(std::path::Path::new(repo_root).join(path)) else {
+            continue;
+        };
+        let mut head = Vec::with_capacity(SNIFF_BYTES);
+        if std::io::Read::read_to_end(
+            &mut std::io::Read::take(file, SNIFF_BYTES as u64),
+            &mut head,
+        )
+        .is_err()
+            || head.contains(&0)
+        {
+            continue;
+        }
+        // A cut can split a UTF-8 sequence; only the complete part counts
+        let valid_prefix = match std::str::from_utf8(&head) {
+            Ok(_) => continue,
+            Err(e) if e.error_len().is_none() => continue,
+            Err(e) => e.valid_up_to(),
+        };
+        let encoding = detect_encoding(&head[valid_prefix..]);
+        if encoding != TextEncoding::Utf8 {
+            labels.insert(path.to_string(), encoding.label().to_string());
+        }
+    }
+    labels
+}
+
+/// `working-tree-encoding` from `.gitattributes` for `paths`.
+fn working_tree_encodings(repo_root: &str, paths: &[&str]) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    if paths.is_empty() {
+        return labels;
+    }
+    let Ok(mut child) = Command::new("git")
+        .args(["check-attr", "--stdin", "-z", "working-tree-encoding"])
+        .current_dir(repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return labels;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let input: Vec<u8> = paths
+            .iter()
+            .flat_map(|p| p.bytes().chain(std::iter::once(0)))
+            .collect();
+        std::thread::spawn(move || stdin.write_all(&input));
+    }
+    let Ok(output) = child.wait_with_output() else {
+        return labels;
+    };
+    labels.extend(parse_check_attr(&String::from_utf8_lossy(&output.stdout)));
+    labels
+}
+
+/// `path\0attr\0value\0` records, dropping unset/unspecified values.
+fn parse_check_attr(output: &str) -> Vec<(String, String)> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    fields
+        .chunks(3)
+        .filter_map(|record| match record {
+            [path, _, value] if !matches!(*value, "unspecified" | "unset" | "set" | "") => {
+                Some((path.to_string(), value.to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latin1_and_japanese_text_are_told_apart() {
+        assert_eq!(
+            detect_encoding("plain ascii".as_bytes()),
+            TextEncoding::Utf8
+        );
+        // "café naïve" in Latin-1
+        let latin1 = b"caf\xe9 na\xefve";
+        assert_eq!(detect_encoding(latin1), TextEncoding::Windows1252);
+        assert_eq!(decode(latin1, TextEncoding::Windows1252), "café naïve");
+        assert_eq!(decode(b"\x93ok\x94", TextEncoding::Windows1252), "“ok”");
+        // "日本語" in Shift_JIS and EUC-JP
+        assert_eq!(
+            detect_encoding(b"\x93\xfa\x96\x7b\x8c\xea"),
+            TextEncoding::ShiftJis
+        );
+        assert_eq!(
+            detect_encoding(b"\xc6\xfc\xcb\xdc\xb8\xec"),
+            TextEncoding::EucJp
+        );
+    }
+
+    #[test]
+    fn only_non_utf8_diff_sections_are_converted() {
+        let mut raw = b"diff --git a/a.txt b/a.txt\n@@ -1 +1 @@\n-\xc3\xa9\n+e\n".to_vec();
+        raw.extend_from_slice(b"diff --git a/b.txt b/b.txt\n@@ -1 +1 @@\n-caf\xe9\n+cafe\n");
+        let text = decode_diff_output(&raw);
+        assert!(text.contains("-é\n+e\n"));
+        assert!(text.contains("-café\n+cafe\n"));
+        assert!(!text.contains('\u{fffd}'));
+    }
+
+    #[test]
+    fn check_attr_records_keep_declared_encodings() {
+        let out =
+            "a.txt\0working-tree-encoding\0SHIFT-JIS\0b.txt\0working-tree-encoding\0unspecified\0";
+        assert_eq!(
+            parse_check_attr(out),
+            [("a.txt".to_string(), "SHIFT-JIS".to_string())]
+        );
+    }
+}
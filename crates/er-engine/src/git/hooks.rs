@@ -0,0 +1,110 @@
+//! The repo's pre-push hook, run ahead of the push action so a failure shows
+//! up in the app instead of as a failed push (the push then skips it with
+//! `--no-verify`).
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::command::OutputLogged;
+
+fn git_line(repo_root: &str, args: &[&str]) -> Option<String> {
+    let out = Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()?;
+    let line = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (out.status.success() && !line.is_empty()).then_some(line)
+}
+
+/// Path of an executable pre-push hook (honours `core.hooksPath`).
+pub fn pre_push_hook(repo_root: &str) -> Option<String> {
+    let path = git_line(repo_root, &["rev-parse", "--git-path", "hooks/pre-push"])?;
+    let path = std::path::Path::new(repo_root).join(path);
+    let meta = std::fs::metadata(&path).ok()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if meta.permissions().mode() & 0o111 == 0 {
+            return None;
+        }
+    }
+    meta.is_file().then(|| path.display().to_string())
+}
+
+/// Run the pre-push hook the way `git push` would for the current branch:
+/// `<remote> <url>` as arguments and the ref being pushed on stdin.
+/// `None` when the repo has no hook.
+pub fn run_pre_push_hook(repo_root: &str) -> Result<Option<Output>> {
+    let Some(hook) = pre_push_hook(repo_root) else {
+        return Ok(None);
+    };
+    let upstream = git_line(
+        repo_root,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+    );
+    let remote = upstream
+        .as_deref()
+        .and_then(|u| u.split_once('/'))
+        .map_or("origin", |(remote, _)| remote)
+        .to_string();
+    let url = git_line(repo_root, &["remote", "get-url", &remote]).unwrap_or_default();
+    let local_ref = git_line(repo_root, &["symbolic-ref", "HEAD"]).unwrap_or_default();
+    let local_sha = git_line(repo_root, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let remote_sha =
+        git_line(repo_root, &["rev-parse", "@{upstream}"]).unwrap_or_else(|| "0".repeat(40));
+    let stdin = format!("{} {} {} {}\n", local_ref, local_sha, local_ref, remote_sha);
+
+    let mut child = Command::new(&hook)
+        .args([&remote, &url])
+        .current_dir(repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", hook))?;
+    if let Some(mut pipe) = child.stdin.take() {
+        // A hook that ignores stdin closes it early; that's not an error
+        let _ = pipe.write_all(stdin.as_bytes());
+    }
+    Ok(Some(child.wait_with_output()?))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn hook_gets_the_pushed_ref_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+        assert!(run_pre_push_hook(root).unwrap().is_none());
+
+        let hook = dir.path().join(".git/hooks/pre-push");
+        std::fs::write(&hook, "#!/bin/sh\necho \"$1\"; cat; exit 1\n").unwrap();
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let out = run_pre_push_hook(root).unwrap().unwrap();
+        assert!(!out.status.success());
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.starts_with("origin\nrefs/heads/main "));
+        assert!(stdout.trim_end().ends_with(&"0".repeat(40)));
+    }
+}
@@ -0,0 +1,288 @@
+//! Image assets in a diff: which paths are images, their pixel size read
+//! from the file header (PNG, GIF, JPEG, WebP, BMP, SVG), and small
+//! previews of PNGs for a terminal to draw with half blocks.
+
+/// Extensions counted as image assets.
+pub const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "ico", "avif",
+];
+
+/// Decoded images over this many bytes get no preview.
+const MAX_DECODE_BYTES: usize = 48 << 20;
+
+/// Whether `path` is an image asset.
+pub fn is_image(path: &str) -> bool {
+    let Some((_, ext)) = path.rsplit_once('.') else {
+        return false;
+    };
+    IMAGE_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext))
+}
+
+fn be16(data: &[u8], at: usize) -> Option<u32> {
+    let b = data.get(at..at + 2)?;
+    Some(u32::from(b[0]) << 8 | u32::from(b[1]))
+}
+
+fn le16(data: &[u8], at: usize) -> Option<u32> {
+    let b = data.get(at..at + 2)?;
+    Some(u32::from(b[1]) << 8 | u32::from(b[0]))
+}
+
+fn le24(data: &[u8], at: usize) -> Option<u32> {
+    Some(le16(data, at)? | u32::from(*data.get(at + 2)?) << 16)
+}
+
+fn be32(data: &[u8], at: usize) -> Option<u32> {
+    Some(be16(data, at)? << 16 | be16(data, at + 2)?)
+}
+
+fn le32(data: &[u8], at: usize) -> Option<u32> {
+    Some(le16(data, at)? | le16(data, at + 2)? << 16)
+}
+
+/// The first `SOFn` marker's frame size.
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    while at + 9 < data.len() {
+        if data[at] != 0xFF {
+            return None;
+        }
+        let marker = data[at + 1];
+        // Fill bytes, then standalone markers without a length
+        if marker == 0xFF {
+            at += 1;
+            continue;
+        }
+        if matches!(marker, 0x01 | 0xD0..=0xD7) {
+            at += 2;
+            continue;
+        }
+        // SOF0..SOF15, except DHT (C4), JPG (C8) and DAC (CC)
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            return Some((be16(data, at + 7)?, be16(data, at + 5)?));
+        }
+        at += 2 + be16(data, at + 2)? as usize;
+    }
+    None
+}
+
+/// `width` / `height` attributes of the root `<svg>` element, else its
+/// `viewBox`. Units are dropped: `24px` and `24` are both 24.
+fn svg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let text = std::str::from_utf8(data.get(..data.len().min(4096))?).ok()?;
+    let start = text.find("<svg")?;
+    let tag = &text[start..start + text[start..].find('>')?];
+    let attr = |name: &str| -> Option<&str> {
+        let mut rest = tag;
+        loop {
+            let at = rest.find(name)?;
+            let before = rest[..at].chars().last();
+            rest = &rest[at + name.len()..];
+            if before.is_some_and(char::is_whitespace) {
+                if let Some(value) = rest.trim_start().strip_prefix('=') {
+                    let value = value.trim_start();
+                    let quote = value.chars().next()?;
+                    let value = &value[1..];
+                    return Some(&value[..value.find(quote)?]);
+                }
+            }
+        }
+    };
+    let number = |s: &str| -> Option<u32> {
+        let digits: String = s
+            .trim()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let n: f64 = digits.parse().ok()?;
+        (n > 0.0).then(|| n.round() as u32)
+    };
+    if let (Some(w), Some(h)) = (attr("width"), attr("height")) {
+        if !w.ends_with('%') && !h.ends_with('%') {
+            if let (Some(w), Some(h)) = (number(w), number(h)) {
+                return Some((w, h));
+            }
+        }
+    }
+    let view_box: Vec<&str> = attr("viewBox")?
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .collect();
+    Some((number(view_box.get(2)?)?, number(view_box.get(3)?)?))
+}
+
+/// Pixel `(width, height)` of an image, from its header.
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(data, 16)?, be32(data, 20)?));
+    }
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        return Some((le16(data, 6)?, le16(data, 8)?));
+    }
+    if data.starts_with(&[0xFF, 0xD8]) {
+        return jpeg_dimensions(data);
+    }
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        return match data.get(12..16)? {
+            b"VP8X" => Some((le24(data, 24)? + 1, le24(data, 27)? + 1)),
+            b"VP8 " => Some((le16(data, 26)? & 0x3FFF, le16(data, 28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = le32(data, 21)?;
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            _ => None,
+        };
+    }
+    if data.starts_with(b"BM") {
+        let height = le32(data, 22)? as i32;
+        return Some((le32(data, 18)?, height.unsigned_abs()));
+    }
+    svg_dimensions(data)
+}
+
+/// A downscaled image, row-major RGBA.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePreview {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl ImagePreview {
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        (x < self.width).then(|| self.pixels.get(y * self.width + x).copied())?
+    }
+}
+
+/// A PNG scaled to fit `max_w` × `max_h` pixels, aspect kept, each pixel
+/// the average of the ones it covers. `None` for other formats and PNGs
+/// too big to decode.
+pub fn image_preview(data: &[u8], max_w: usize, max_h: usize) -> Option<ImagePreview> {
+    if !data.starts_with(b"\x89PNG\r\n\x1a\n") || max_w == 0 || max_h == 0 {
+        return None;
+    }
+    let mut decoder = png::Decoder::new_with_limits(
+        std::io::Cursor::new(data),
+        png::Limits {
+            bytes: MAX_DECODE_BYTES,
+        },
+    );
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let size = reader.output_buffer_size()?;
+    if size > MAX_DECODE_BYTES {
+        return None;
+    }
+    let mut buf = vec![0; size];
+    let info = reader.next_frame(&mut buf).ok()?;
+    let channels = match info.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => return None,
+    };
+    let (w, h) = (info.width as usize, info.height as usize);
+    if w == 0 || h == 0 {
+        return None;
+    }
+    let rgba = |x: usize, y: usize| -> [u8; 4] {
+        let p = &buf[y * info.line_size + x * channels..][..channels];
+        match channels {
+            1 => [p[0], p[0], p[0], 255],
+            2 => [p[0], p[0], p[0], p[1]],
+            3 => [p[0], p[1], p[2], 255],
+            _ => [p[0], p[1], p[2], p[3]],
+        }
+    };
+    // Never scale up
+    let scale = (w as f64 / max_w as f64)
+        .max(h as f64 / max_h as f64)
+        .max(1.0);
+    let out_w = ((w as f64 / scale).round() as usize).clamp(1, max_w);
+    let out_h = ((h as f64 / scale).round() as usize).clamp(1, max_h);
+    let mut pixels = Vec::with_capacity(out_w * out_h);
+    for oy in 0..out_h {
+        let (y0, y1) = (
+            oy * h / out_h,
+            ((oy + 1) * h / out_h).max(oy * h / out_h + 1),
+        );
+        for ox in 0..out_w {
+            let (x0, x1) = (
+                ox * w / out_w,
+                ((ox + 1) * w / out_w).max(ox * w / out_w + 1),
+            );
+            let mut sum = [0u64; 4];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let p = rgba(x, y).map(u64::from);
+                    // Colors weighted by alpha so transparent pixels don't darken
+                    for c in 0..3 {
+                        sum[c] += p[c] * p[3];
+                    }
+                    sum[3] += p[3];
+                }
+            }
+            let n = ((y1 - y0) * (x1 - x0)) as u64;
+            let channel = |c: usize| sum[c].checked_div(sum[3]).unwrap_or(0) as u8;
+            let pixel = [channel(0), channel(1), channel(2), (sum[3] / n) as u8];
+            pixels.push(pixel);
+        }
+    }
+    Some(ImagePreview {
+        width: out_w,
+        height: out_h,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(rgba).unwrap();
+        writer.finish().unwrap();
+        out
+    }
+
+    #[test]
+    fn dimensions_come_from_each_formats_header() {
+        assert!(is_image("assets/Logo.PNG"));
+        assert!(!is_image("src/png.rs"));
+
+        let red = [255, 0, 0, 255];
+        let clear = [0, 0, 0, 0];
+        let data = png(
+            4,
+            2,
+            &[red, red, clear, clear, red, red, clear, clear].concat(),
+        );
+        assert_eq!(image_dimensions(&data), Some((4, 2)));
+        let preview = image_preview(&data, 2, 2).unwrap();
+        assert_eq!((preview.width, preview.height), (2, 1));
+        assert_eq!(preview.pixel(0, 0), Some(red));
+        assert_eq!(preview.pixel(1, 0), Some(clear));
+        assert_eq!(preview.pixel(2, 0), None);
+
+        let gif = b"GIF89a\x20\x00\x10\x00";
+        assert_eq!(image_dimensions(gif), Some((32, 16)));
+        // SOI, an APP0 segment, then SOF0 with height 480 and width 640
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01,
+            0xE0, 0x02, 0x80, 0x03,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((640, 480)));
+        let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 12" stroke-width="2">"#;
+        assert_eq!(image_dimensions(svg), Some((24, 12)));
+        let svg = br#"<svg width="48px" height="32" viewBox="0 0 24 16">"#;
+        assert_eq!(image_dimensions(svg), Some((48, 32)));
+        assert_eq!(image_dimensions(b"not an image"), None);
+        assert!(image_preview(gif, 8, 8).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+//! Files whose content shouldn't be loaded like ordinary source: Git LFS
+//! pointers (the diff is pointer text, so it's shown as the object's size
+//! and oid instead) and working-tree files over the `[large_files]` size
+//! limit, which start compacted.
+
+use super::{DiffFile, FileStatus, LineType};
+
+/// Longest diff that can still be a pointer change (three keys a side plus
+/// a few extensions).
+const MAX_POINTER_DIFF_LINES: usize = 16;
+
+/// The object a Git LFS pointer file stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// `sha256:…`
+    pub oid: String,
+    pub size: u64,
+}
+
+/// Old and new LFS object of a pointer diff; `None` on the side where the
+/// pointer is added or removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsChange {
+    pub old: Option<LfsPointer>,
+    pub new: Option<LfsPointer>,
+}
+
+/// Parse pointer text (`version https://git-lfs…`, `oid …`, `size …`).
+pub fn parse_lfs_pointer<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<LfsPointer> {
+    let (mut version, mut oid, mut size) = (false, None, None);
+    for line in lines {
+        let (key, value) = line.trim_end().split_once(' ')?;
+        match key {
+            "version" => version = value.starts_with("https://git-lfs.github.com/spec/"),
+            "oid" => oid = Some(value.to_string()),
+            "size" => size = value.parse().ok(),
+            // Extensions (`ext-0-name …`) are allowed; free text is not
+            _ if key.starts_with("ext-") => {}
+            _ => return None,
+        }
+    }
+    match (version, oid, size) {
+        (true, Some(oid), Some(size)) => Some(LfsPointer { oid, size }),
+        _ => None,
+    }
+}
+
+/// The LFS objects `file` switches between, when its diff is a pointer
+/// change. Needs parsed hunks; compacted or lazy stubs return `None`.
+pub fn lfs_change(file: &DiffFile) -> Option<LfsChange> {
+    // Pointers are a handful of lines; don't scan real diffs
+    if file.hunks.len() != 1 || file.hunks[0].lines.len() > MAX_POINTER_DIFF_LINES {
+        return None;
+    }
+    let side = |skip: LineType| {
+        let lines: Vec<&str> = file
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| l.line_type != skip && !matches!(l.line_type, LineType::Fold(_)))
+            .map(|l| l.content.as_str())
+            .collect();
+        (!lines.is_empty()).then(|| parse_lfs_pointer(lines))
+    };
+    // `None` = no lines on that side, `Some(None)` = lines that aren't a pointer
+    let (old, new) = (side(LineType::Add), side(LineType::Delete));
+    if matches!(old, Some(None)) || matches!(new, Some(None)) {
+        return None;
+    }
+    let (old, new) = (old.flatten(), new.flatten());
+    if old.is_none() && new.is_none() {
+        return None;
+    }
+    Some(LfsChange { old, new })
+}
+
+/// Files in `files` whose working-tree copy is over `max_bytes`, with their
+/// size. Deleted files and compacted ones are skipped.
+pub fn oversized_files(repo_root: &str, files: &[DiffFile], max_bytes: u64) -> Vec<(String, u64)> {
+    if max_bytes == 0 {
+        return Vec::new();
+    }
+    files
+        .iter()
+        .filter(|f| !f.compacted && f.status != FileStatus::Deleted)
+        .filter_map(|f| {
+            let size = std::fs::metadata(std::path::Path::new(repo_root).join(&f.path))
+                .ok()?
+                .len();
+            (size > max_bytes).then(|| (f.path.clone(), size))
+        })
+        .collect()
+}
+
+/// `12 KB`, `3.4 MB`, `1.2 GB`.
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1 << 30 {
+        format!("{:.1} GB", bytes as f64 / (1u64 << 30) as f64)
+    } else if bytes >= 1 << 20 {
+        format!("{:.1} MB", bytes as f64 / (1u64 << 20) as f64)
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn pointer_diffs_become_lfs_changes() {
+        let diff = "diff --git a/model.bin b/model.bin\n--- a/model.bin\n+++ b/model.bin\n\
+                    @@ -1,3 +1,3 @@\n version https://git-lfs.github.com/spec/v1\n\
+                    -oid sha256:aaa\n-size 1024\n+oid sha256:bbb\n+size 3145728\n";
+        let files = parse_diff(diff);
+        let change = lfs_change(&files[0]).unwrap();
+        assert_eq!(
+            change.old,
+            Some(LfsPointer {
+                oid: "sha256:aaa".into(),
+                size: 1024
+            })
+        );
+        assert_eq!(change.new.unwrap().size, 3 << 20);
+
+        let added =
+            "diff --git a/x.psd b/x.psd\nnew file mode 100644\n--- /dev/null\n+++ b/x.psd\n\
+                     @@ -0,0 +1,3 @@\n+version https://git-lfs.github.com/spec/v1\n\
+                     +oid sha256:ccc\n+size 5\n";
+        let change = lfs_change(&parse_diff(added)[0]).unwrap();
+        assert!(change.old.is_none() && change.new.is_some());
+
+        let text =
+            "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-size 1\n+size 2\n";
+        assert!(lfs_change(&parse_diff(text)[0]).is_none());
+    }
+
+    #[test]
+    fn only_files_over_the_limit_are_oversized() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("big.json"), vec![b'x'; 4096]).unwrap();
+        std::fs::write(dir.path().join("small.rs"), "fn a() {}\n").unwrap();
+        let file = |path: &str| DiffFile {
+            path: path.into(),
+            status: FileStatus::Modified,
+            hunks: Vec::new(),
+            adds: 1,
+            dels: 0,
+            compacted: false,
+            raw_hunk_count: 1,
+        };
+        let files = [file("big.json"), file("small.rs"), file("gone.txt")];
+        let root = dir.path().to_str().unwrap();
+        assert_eq!(
+            oversized_files(root, &files, 1024),
+            [("big.json".to_string(), 4096)]
+        );
+        assert!(oversized_files(root, &files, 0).is_empty());
+        assert_eq!(format_size(3 << 20), "3.0 MB");
+        assert_eq!(format_size(1500), "2 KB");
+    }
+}
@@ -0,0 +1,319 @@
+//! Database migrations in a diff: destructive statements on added lines
+//! and whether each migration can be rolled back.
+//!
+//! A migration is found by path (`migrations/` anywhere in it, or a
+//! compaction-style glob such as `*.sql`). Its down side is either in the
+//! file (`def down`, `-- +goose Down`, `exports.down`, …) or a sibling
+//! named by the common conventions: `x.up.sql` → `x.down.sql`, `up.sql` →
+//! `down.sql`, `x_up.sql` → `x_down.sql`, Flyway `V3__x.sql` → `U3__x.sql`.
+
+use std::sync::OnceLock;
+
+use super::{compact_files_match, DiffFile, LineType};
+
+/// Migration files, by path.
+pub const DEFAULT_MIGRATION_PATHS: &[&str] = &["migrations/", "db/migrate/", "*.sql"];
+
+/// Markers of a down migration inside the file itself.
+const IN_FILE_DOWN: &[&str] = &[
+    "-- +migrate down",
+    "-- +goose down",
+    "-- migrate:down",
+    "def down",
+    "def downgrade",
+    "def change",
+    "exports.down",
+    "async down(",
+    "public function down",
+    "public async down(",
+    "void down(",
+    "override fun down(",
+];
+
+/// A statement that loses data, or breaks code still running against the
+/// old schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Destructive {
+    pub hunk: usize,
+    /// New-side line
+    pub line: usize,
+    /// What it does, e.g. `DROP TABLE`
+    pub statement: &'static str,
+    /// Rows or columns are gone for good (as opposed to a lock or a
+    /// constraint existing data may fail)
+    pub data_loss: bool,
+    /// The line, trimmed
+    pub text: String,
+}
+
+/// How a migration is rolled back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownMigration {
+    /// The file has its own down section
+    InFile,
+    /// This sibling file
+    Sibling(String),
+    /// The file is itself a down migration
+    IsDown,
+    Missing,
+}
+
+/// One migration file of the diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationCheck {
+    pub path: String,
+    pub destructive: Vec<Destructive>,
+    pub down: DownMigration,
+}
+
+/// Whether `path` is a migration: a `dir/` pattern matches that directory
+/// anywhere in the path, anything else is a compaction glob.
+pub fn is_migration(path: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|p| match p.strip_suffix('/') {
+        Some(dir) if !dir.is_empty() => {
+            path.starts_with(p.as_str()) || path.contains(&format!("/{}/", dir))
+        }
+        _ => compact_files_match(p, path),
+    })
+}
+
+/// `(regex, statement, data loss)`, matched case-insensitively.
+fn rules() -> &'static [(regex::Regex, &'static str, bool)] {
+    static RULES: OnceLock<Vec<(regex::Regex, &'static str, bool)>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        [
+            (r"\bDROP\s+TABLE\b", "DROP TABLE", true),
+            (r"\bDROP\s+COLUMN\b", "DROP COLUMN", true),
+            (r"\bDROP\s+(?:SCHEMA|DATABASE)\b", "DROP SCHEMA", true),
+            (r"\bTRUNCATE\b", "TRUNCATE", true),
+            (r"\bDELETE\s+FROM\b", "DELETE without WHERE", true),
+            (r"\b(?:drop_table|dropTable)\b", "DROP TABLE", true),
+            (
+                r"\b(?:remove_column|drop_column|dropColumn)\b",
+                "DROP COLUMN",
+                true,
+            ),
+            (
+                r"\bALTER\s+(?:COLUMN\s+)?\S+\s+(?:SET\s+DATA\s+)?TYPE\b",
+                "column type change",
+                false,
+            ),
+            (r"\bMODIFY\s+(?:COLUMN\s+)?\S+", "column type change", false),
+            (
+                r"\bRENAME\s+(?:COLUMN|TO)\b|\brename_(?:column|table)\b",
+                "RENAME",
+                false,
+            ),
+            (
+                r"\bSET\s+NOT\s+NULL\b|\bchange_column_null\b.*\bfalse\b",
+                "NOT NULL",
+                false,
+            ),
+            (
+                r"\bADD\s+(?:COLUMN\s+)?\S+\s+[^,;]*\bNOT\s+NULL\b",
+                "NOT NULL column",
+                false,
+            ),
+            (r"\bDROP\s+(?:INDEX|CONSTRAINT|VIEW|TYPE)\b", "DROP", false),
+        ]
+        .into_iter()
+        .map(|(pattern, statement, loss)| {
+            let re = regex::RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .expect("migration rule");
+            (re, statement, loss)
+        })
+        .collect()
+    })
+}
+
+/// The first destructive statement on `text`, if any.
+fn destructive_statement(text: &str) -> Option<(&'static str, bool)> {
+    let code = text.trim();
+    if code.starts_with("--") || code.starts_with('#') || code.starts_with("//") {
+        return None;
+    }
+    let upper = code.to_ascii_uppercase();
+    rules()
+        .iter()
+        .find(|(re, statement, _)| {
+            re.is_match(code)
+                // A NOT NULL column with a default fills the existing rows
+                && !(statement.starts_with("NOT NULL") && upper.contains("DEFAULT"))
+                && !(statement.starts_with("DELETE") && upper.contains("WHERE"))
+        })
+        .map(|(_, statement, loss)| (*statement, *loss))
+}
+
+/// The down sibling `path` would have; none when it is one.
+fn sibling_down(path: &str) -> Result<Option<String>, ()> {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), path),
+    };
+    let lower = name.to_ascii_lowercase();
+    if lower.contains(".down.") || lower.starts_with("down.") || lower.contains("_down.") {
+        return Err(());
+    }
+    let flyway_undo = name.len() > 1
+        && name.starts_with('U')
+        && name[1..].starts_with(|c: char| c.is_ascii_digit())
+        && name.contains("__");
+    if flyway_undo {
+        return Err(());
+    }
+    let sibling = if let Some(at) = lower.find(".up.") {
+        Some(format!("{}.down.{}", &name[..at], &name[at + 4..]))
+    } else if let Some(rest) = lower.strip_prefix("up.") {
+        Some(format!("down.{}", rest))
+    } else if let Some(at) = lower.find("_up.") {
+        Some(format!("{}_down.{}", &name[..at], &name[at + 4..]))
+    } else if name.starts_with('V')
+        && name[1..].starts_with(|c: char| c.is_ascii_digit())
+        && name.contains("__")
+    {
+        Some(format!("U{}", &name[1..]))
+    } else {
+        None
+    };
+    Ok(sibling.map(|s| format!("{}{}", dir, s)))
+}
+
+/// How `path` is rolled back. `text` is what's known of its new side;
+/// `exists` says whether a sibling path is there.
+fn down_migration(path: &str, text: &str, exists: &dyn Fn(&str) -> bool) -> DownMigration {
+    let sibling = match sibling_down(path) {
+        Err(()) => return DownMigration::IsDown,
+        Ok(sibling) => sibling,
+    };
+    let lower = text.to_ascii_lowercase();
+    if IN_FILE_DOWN.iter().any(|marker| lower.contains(marker)) {
+        return DownMigration::InFile;
+    }
+    match sibling.filter(|s| exists(s)) {
+        Some(sibling) => DownMigration::Sibling(sibling),
+        None => DownMigration::Missing,
+    }
+}
+
+/// Check the migration files of `files`. `read` gives a file's full new
+/// side when it can be read, for down sections outside the diff; `exists`
+/// whether a path is there.
+pub fn check_migrations(
+    files: &[DiffFile],
+    patterns: &[String],
+    read: &dyn Fn(&str) -> Option<String>,
+    exists: &dyn Fn(&str) -> bool,
+) -> Vec<MigrationCheck> {
+    let in_diff = |p: &str| files.iter().any(|f| f.path == p) || exists(p);
+    files
+        .iter()
+        .filter(|f| f.status != super::FileStatus::Deleted && is_migration(&f.path, patterns))
+        .map(|file| {
+            let mut destructive = Vec::new();
+            let mut known = String::new();
+            for (hi, hunk) in file.hunks.iter().enumerate() {
+                for line in &hunk.lines {
+                    if line.line_type == LineType::Delete {
+                        continue;
+                    }
+                    known.push_str(&line.content);
+                    known.push('\n');
+                    if line.line_type != LineType::Add {
+                        continue;
+                    }
+                    let (Some(number), Some((statement, data_loss))) =
+                        (line.new_num, destructive_statement(&line.content))
+                    else {
+                        continue;
+                    };
+                    destructive.push(Destructive {
+                        hunk: hi,
+                        line: number,
+                        statement,
+                        data_loss,
+                        text: line.content.trim().to_string(),
+                    });
+                }
+            }
+            let mut down = down_migration(&file.path, &known, &in_diff);
+            if down == DownMigration::Missing {
+                if let Some(text) = read(&file.path) {
+                    down = down_migration(&file.path, &text, &in_diff);
+                }
+            }
+            MigrationCheck {
+                path: file.path.clone(),
+                destructive,
+                down,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_MIGRATION_PATHS
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn destructive_statements_are_flagged_on_added_lines() {
+        let diff = "diff --git a/db/migrations/004_users.up.sql b/db/migrations/004_users.up.sql\n\
+                    new file mode 100644\n--- /dev/null\n+++ b/db/migrations/004_users.up.sql\n\
+                    @@ -0,0 +1,6 @@\n+-- DROP TABLE users; was considered\n+DROP TABLE legacy_users;\n\
+                    +ALTER TABLE users ALTER COLUMN email SET NOT NULL;\n\
+                    +ALTER TABLE users ADD COLUMN plan text NOT NULL DEFAULT 'free';\n\
+                    +DELETE FROM sessions;\n+DELETE FROM tokens WHERE expired;\n";
+        let checks = check_migrations(&parse_diff(diff), &defaults(), &|_| None, &|_| false);
+        assert_eq!(checks.len(), 1);
+        let found: Vec<(usize, &str, bool)> = checks[0]
+            .destructive
+            .iter()
+            .map(|d| (d.line, d.statement, d.data_loss))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "DROP TABLE", true),
+                (3, "NOT NULL", false),
+                (5, "DELETE without WHERE", true),
+            ]
+        );
+        assert_eq!(checks[0].down, DownMigration::Missing);
+    }
+
+    #[test]
+    fn down_migrations_are_found_by_convention() {
+        let exists = |p: &str| p == "migrations/2_add.down.sql" || p == "sql/U7__drop.sql";
+        let check = |path: &str, text: &str| down_migration(path, text, &exists);
+        assert_eq!(
+            check("migrations/2_add.up.sql", ""),
+            DownMigration::Sibling("migrations/2_add.down.sql".into())
+        );
+        assert_eq!(
+            check("sql/V7__drop.sql", ""),
+            DownMigration::Sibling("sql/U7__drop.sql".into())
+        );
+        assert_eq!(check("migrations/3_x.down.sql", ""), DownMigration::IsDown);
+        assert_eq!(
+            check("db/migrate/20240101_add.rb", "  def down\n"),
+            DownMigration::InFile
+        );
+        assert_eq!(
+            check("migrations/9_gone/up.sql", ""),
+            DownMigration::Missing
+        );
+
+        assert!(is_migration("services/api/migrations/1.py", &defaults()));
+        assert!(is_migration("schema.sql", &defaults()));
+        assert!(!is_migration("src/migrate.rs", &defaults()));
+    }
+}
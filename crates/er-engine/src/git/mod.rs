@@ -1,7 +1,27 @@
+mod artifact_ignore;
+mod authors;
+mod branch_audit;
+mod branches;
+mod commit_lint;
+mod complexity;
 mod diff;
 mod diff_stats;
+mod duplicates;
+mod encoding;
 mod file_kind;
+mod hooks;
+mod images;
+mod large_files;
+mod migrations;
+mod net_zero;
+mod operational;
+mod ownership;
+mod packages;
+mod patch;
+mod recent_changes;
 mod status;
+mod test_pairing;
+mod todos;
 
 /// Default `--unified=N` context lines for every `git diff` invocation and
 /// for the in-process context fold. Per-file overrides via `+`/`-` build on
@@ -23,7 +43,18 @@ pub const CONTEXT_STEPS: &[usize] = &[DEFAULT_CONTEXT_LINES, 20, 40, 80, FULL_CO
 /// `DEFAULT_CONTEXT_LINES`. Ordered small → large.
 pub const SIZE_LADDER: &[(usize, usize)] = &[(60, FULL_CONTEXT), (180, 80), (500, 40), (1500, 20)];
 
+pub use artifact_ignore::{
+    append_ignore_patterns, ignore_file_for, is_artifact_path, unignored_artifact_patterns,
+    ARTIFACT_PATTERNS,
+};
+pub use authors::{blame_line_authors, blame_line_times, branch_author_stats, AuthorStat};
+pub use branch_audit::{audit_branch, AuditCheck, AuditKind, BranchAudit};
+pub use branches::{
+    git_branches, git_stash_pop, git_stash_push, git_switch, uncommitted_changes, BranchInfo,
+};
+pub use commit_lint::{lint_commit_message, CommitLintIssue, CommitLintRule};
 #[allow(unused_imports)]
+pub use complexity::{function_metrics, is_measured, FunctionMetrics};
 pub use diff::{
     compact_files, compact_files_match, expand_compacted_file, filter_raw_diff_by_paths,
     filter_raw_diff_exclude_globs, header_to_stub, lazy_files_with_compaction, parse_diff,
@@ -31,13 +62,46 @@ pub use diff::{
     DiffFile, DiffFileHeader, DiffHunk, DiffLine, LineType,
 };
 pub use diff_stats::{DiffKindStats, FileDiffStat, ProdDiffStats};
+pub use duplicates::{added_blocks, find_duplicates, AddedBlock, CopyLocation, Duplicate};
+pub use encoding::{decode_diff_output, detect_encoding, file_encodings, TextEncoding};
 pub use file_kind::{classify_path, FileKind};
+pub use hooks::{pre_push_hook, run_pre_push_hook};
+pub use images::{image_dimensions, image_preview, is_image, ImagePreview, IMAGE_EXTENSIONS};
+pub use large_files::{
+    format_size, lfs_change, oversized_files, parse_lfs_pointer, LfsChange, LfsPointer,
+};
+pub use migrations::{
+    check_migrations, is_migration, Destructive, DownMigration, MigrationCheck,
+    DEFAULT_MIGRATION_PATHS,
+};
+pub use net_zero::net_zero_commits;
+pub use operational::{
+    scan_operational, OpsChange, OpsChangeKind, OpsKind, OpsScanner, DEFAULT_CONFIG_PATHS,
+    DEFAULT_ENV_PATTERNS, DEFAULT_FLAG_PATTERNS,
+};
+pub use ownership::{file_ownership, FileOwner, FileOwnership};
+pub use packages::{
+    affected_packages, detect_packages, package_dependencies, package_for, AffectedPackage,
+    Package, PackageKind, PACKAGE_MANIFESTS,
+};
+pub use patch::{
+    apply_patch, apply_worktree_patch, file_patch, old_side, select_lines, stage_patch, PatchApply,
+};
+pub use recent_changes::{recent_commits, RecentCommit};
 pub use status::{
     detect_base_branch_in, diff_shortstat, diff_watched_file_snapshot, discover_watched_files,
-    get_current_branch_in, get_repo_root, get_repo_root_in, git_commit, git_diff_against_branch,
+    get_current_branch_in, get_repo_root, get_repo_root_in, git_ahead_behind, git_branch_names,
+    git_commit, git_commit_messages, git_config_value, git_diff_against_branch,
     git_diff_checkout_against_base, git_diff_commit, git_diff_conflicts, git_diff_raw,
-    git_diff_raw_file, git_diff_raw_range, git_log_branch, git_log_head, git_log_range, git_push,
-    git_stage_all, git_stage_file, git_unstage_file, gitignored_paths, is_merge_in_progress,
-    list_worktrees, read_watched_file_content, save_snapshot, unmerged_files, CommitInfo,
-    FileStatus, WatchedFile, Worktree,
+    git_diff_raw_file, git_diff_raw_range, git_diff_since, git_grep_fixed, git_log_branch,
+    git_log_head, git_log_paths, git_log_range, git_push, git_show_bytes, git_show_file,
+    git_snapshot_commit, git_stage_all, git_stage_file, git_tags, git_unstage_all,
+    git_unstage_file, gitignored_paths, has_staged_changes, is_merge_in_progress, list_worktrees,
+    read_watched_file_content, save_snapshot, unmerged_files, CommitInfo, FileStatus, Tag,
+    WatchedFile, Worktree,
+};
+pub use test_pairing::{
+    is_pairable_source, pair_tests, source_candidates, test_candidates, TestPairing,
+    DEFAULT_TEST_PATTERNS,
 };
+pub use todos::{scan_todos, TodoMarker, TodoScanner, DEFAULT_TODO_MARKERS};
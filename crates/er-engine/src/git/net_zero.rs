@@ -0,0 +1,169 @@
+//! Commits whose changes a later commit on the branch undoes.
+//!
+//! A commit is *net-zero* when every file it touches is back, at the branch
+//! head, to exactly the blob it had before the commit — code added and later
+//! removed, a file created and deleted again — or when it is the commit that
+//! puts its files back to how the branch found them (the revert). The branch
+//! diff never shows either, so History mode can badge or fold them.
+
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::command::OutputLogged;
+
+/// Object id git uses for "no file".
+const NULL_OID: &str = "0000000000000000000000000000000000000000";
+
+/// Full hashes of the net-zero commits in `base..head`. Merge commits are
+/// never net-zero.
+pub fn net_zero_commits(repo_root: &str, base: &str, head: &str) -> Result<HashSet<String>> {
+    let log = Command::new("git")
+        .args([
+            "log",
+            "--format=%x1e%H",
+            "--raw",
+            "--no-abbrev",
+            "--no-renames",
+            &format!("{}..{}", base, head),
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git log")?;
+    if !log.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&log.stderr).trim()
+        );
+    }
+    let fork = Command::new("git")
+        .args(["merge-base", base, head])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git merge-base")?;
+    let fork = String::from_utf8_lossy(&fork.stdout).trim().to_string();
+    let blobs = |rev: &str| -> Result<HashMap<String, String>> {
+        let tree = Command::new("git")
+            .args(["ls-tree", "-r", "--full-tree", rev])
+            .current_dir(repo_root)
+            .logged_output()
+            .context("Failed to run git ls-tree")?;
+        Ok(parse_ls_tree(&String::from_utf8_lossy(&tree.stdout)))
+    };
+    let base_blobs = if fork.is_empty() {
+        HashMap::new()
+    } else {
+        blobs(&fork)?
+    };
+    Ok(find_net_zero(
+        &String::from_utf8_lossy(&log.stdout),
+        &base_blobs,
+        &blobs(head)?,
+    ))
+}
+
+/// `path → blob` from `git ls-tree -r`.
+fn parse_ls_tree(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // <mode> <type> <object>\t<path>
+            let (meta, path) = line.split_once('\t')?;
+            let oid = meta.split_whitespace().nth(2)?;
+            Some((path.to_string(), oid.to_string()))
+        })
+        .collect()
+}
+
+/// Commits from `git log --format=%x1e%H --raw` where each touched file
+/// either ends up at its pre-commit blob, or is left by the commit exactly as
+/// it was at the fork point and stays that way.
+fn find_net_zero(
+    log: &str,
+    base_blobs: &HashMap<String, String>,
+    head_blobs: &HashMap<String, String>,
+) -> HashSet<String> {
+    let blob = |blobs: &'_ HashMap<String, String>, path: &str| -> String {
+        blobs
+            .get(path)
+            .map_or_else(|| NULL_OID.to_string(), String::clone)
+    };
+    let mut out = HashSet::new();
+    for record in log.split('\x1e') {
+        let mut lines = record.lines();
+        let Some(hash) = lines.next().map(str::trim).filter(|h| !h.is_empty()) else {
+            continue;
+        };
+        let mut touched = 0;
+        let mut undone = true;
+        for line in lines {
+            // :old_mode new_mode old_oid new_oid status\tpath
+            let Some((meta, path)) = line.strip_prefix(':').and_then(|l| l.split_once('\t')) else {
+                continue;
+            };
+            let mut oids = meta.split_whitespace().skip(2);
+            let (Some(before), Some(after)) = (oids.next(), oids.next()) else {
+                continue;
+            };
+            touched += 1;
+            let now = blob(head_blobs, path);
+            let undone_later = before == now;
+            let restores_fork = after == now && blob(base_blobs, path) == now;
+            if !undone_later && !restores_fork {
+                undone = false;
+                break;
+            }
+        }
+        // Merges have no --raw lines; an empty commit undoes nothing
+        if touched > 0 && undone {
+            out.insert(hash.to_string());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commits_whose_files_are_back_to_their_old_blobs_are_net_zero() {
+        let head: HashMap<String, String> = [
+            ("src/a.rs".to_string(), "a0".repeat(20)),
+            ("src/b.rs".to_string(), "b2".repeat(20)),
+        ]
+        .into();
+        let log = format!(
+            "\x1erevert\n\n:100644 100644 {a1} {a0} M\tsrc/a.rs\n\
+             \x1ekeep\n\n:100644 100644 {b1} {b2} M\tsrc/b.rs\n\
+             \x1eadd\n\n:100644 100644 {a0} {a1} M\tsrc/a.rs\n\
+             :000000 100644 {null} {t1} A\tscratch.txt\n\
+             \x1emixed\n\n:100644 100644 {a0} {a1} M\tsrc/a.rs\n\
+             :100644 100644 {b0} {b1} M\tsrc/b.rs\n\
+             \x1emerge\n",
+            a0 = "a0".repeat(20),
+            a1 = "a1".repeat(20),
+            b0 = "b0".repeat(20),
+            b1 = "b1".repeat(20),
+            b2 = "b2".repeat(20),
+            t1 = "71".repeat(20),
+            null = NULL_OID,
+        );
+        let base: HashMap<String, String> = [
+            ("src/a.rs".to_string(), "a0".repeat(20)),
+            ("src/b.rs".to_string(), "b0".repeat(20)),
+        ]
+        .into();
+        let net_zero = find_net_zero(&log, &base, &head);
+        assert!(net_zero.contains("add"));
+        assert!(net_zero.contains("revert"));
+        assert!(!net_zero.contains("keep"));
+        assert!(!net_zero.contains("mixed"));
+        assert!(!net_zero.contains("merge"));
+        assert_eq!(
+            parse_ls_tree("100644 blob abc\tsrc/x.rs\n").get("src/x.rs"),
+            Some(&"abc".to_string())
+        );
+    }
+}
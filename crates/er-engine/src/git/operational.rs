@@ -0,0 +1,340 @@
+//! Operational changes in a diff: edited config files, environment
+//! variables read or no longer read, and feature flags — the changes that
+//! need a deploy note or a settings change, not just a code review.
+//!
+//! Config files are recognised by path (compaction-style globs); each key
+//! whose line is added or removed counts once. Env vars and flags are
+//! found on changed lines of any file by regexes whose first capture group
+//! is the name. A name only on added lines is new, only on removed lines
+//! gone, on both changed.
+
+use std::collections::HashMap;
+
+use super::{compact_files_match, DiffFile, LineType};
+
+/// Config files, by path.
+pub const DEFAULT_CONFIG_PATHS: &[&str] = &[
+    ".env",
+    ".env.example",
+    ".env.sample",
+    "*.env",
+    "*.tfvars",
+    "*.schema.json",
+    "config/**",
+    "config.toml",
+    "config.yaml",
+    "config.yml",
+    "config.json",
+    "settings.py",
+    "application.yml",
+    "application.yaml",
+    "application.properties",
+    "appsettings.json",
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "values.yaml",
+];
+
+/// Environment variable reads, by language.
+pub const DEFAULT_ENV_PATTERNS: &[&str] = &[
+    // Rust
+    r#"env::var(?:_os)?\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+    r#"env!\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+    // JS / TS
+    r"process\.env\.([A-Za-z_][A-Za-z0-9_]*)",
+    r#"process\.env\[\s*['"]([A-Za-z_][A-Za-z0-9_]*)"#,
+    r"import\.meta\.env\.([A-Za-z_][A-Za-z0-9_]*)",
+    // Python
+    r#"os\.(?:environ(?:\.get)?\s*[\[(]|getenv\()\s*['"]([A-Za-z_][A-Za-z0-9_]*)"#,
+    // Go
+    r#"os\.(?:Getenv|LookupEnv)\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+    // Java / Kotlin
+    r#"System\.getenv\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+    // Ruby
+    r#"ENV(?:\.fetch\(|\[)\s*['"]([A-Za-z_][A-Za-z0-9_]*)"#,
+    // Shell, compose and CI interpolation
+    r"\$\{([A-Z_][A-Z0-9_]*)(?:[}:?-])",
+];
+
+/// Feature-flag checks and definitions.
+pub const DEFAULT_FLAG_PATTERNS: &[&str] = &[
+    r#"cfg\(\s*feature\s*=\s*"([^"]+)""#,
+    r#"(?i)(?:is_?enabled|is_?feature_?enabled|feature_?enabled|bool_?variation|variation)\(\s*['":]?([A-Za-z0-9_.\-]+)"#,
+    r#"(?i)feature_?flags?\s*[\[.(]\s*['":]?([A-Za-z0-9_.\-]+)"#,
+    r"Flipper\[\s*:([A-Za-z0-9_]+)",
+];
+
+/// Key at the start of a config line: `key = `, `key: `, `"key": `, `KEY=`.
+const CONFIG_KEY: &str = r#"^\s*(?:export\s+)?["']?([A-Za-z0-9_][A-Za-z0-9_.\-]*)["']?\s*[:=]"#;
+
+/// What kind of operational change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OpsKind {
+    Config,
+    EnvVar,
+    FeatureFlag,
+}
+
+impl OpsKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OpsKind::Config => "Config",
+            OpsKind::EnvVar => "Environment variables",
+            OpsKind::FeatureFlag => "Feature flags",
+        }
+    }
+}
+
+/// Whether the name is new, gone or edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpsChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl OpsChangeKind {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            OpsChangeKind::Added => "+",
+            OpsChangeKind::Removed => "-",
+            OpsChangeKind::Changed => "~",
+        }
+    }
+}
+
+/// A config key, env var or flag the diff touches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpsChange {
+    pub kind: OpsKind,
+    pub change: OpsChangeKind,
+    pub name: String,
+    /// The file of its first changed line
+    pub path: String,
+    /// New-side line of its first added line, old-side when only removed
+    pub line: Option<usize>,
+    /// That line, trimmed
+    pub text: String,
+}
+
+/// How `scan_operational` recognises changes.
+#[derive(Debug, Clone)]
+pub struct OpsScanner {
+    pub config_paths: Vec<String>,
+    pub env: Vec<regex::Regex>,
+    pub flags: Vec<regex::Regex>,
+}
+
+impl Default for OpsScanner {
+    fn default() -> Self {
+        OpsScanner::new(&[], &[], &[])
+    }
+}
+
+impl OpsScanner {
+    /// Empty lists take the defaults; patterns that don't compile are
+    /// dropped.
+    pub fn new(config_paths: &[String], env: &[String], flags: &[String]) -> Self {
+        let or_default = |given: &[String], default: &[&str]| -> Vec<String> {
+            if given.is_empty() {
+                default.iter().map(|p| p.to_string()).collect()
+            } else {
+                given.to_vec()
+            }
+        };
+        let compile = |patterns: Vec<String>| -> Vec<regex::Regex> {
+            patterns
+                .iter()
+                .filter_map(|p| match regex::Regex::new(p) {
+                    Ok(re) => Some(re),
+                    Err(e) => {
+                        crate::debug_log::warn("config", format!("[operational] {}: {}", p, e));
+                        None
+                    }
+                })
+                .collect()
+        };
+        OpsScanner {
+            config_paths: or_default(config_paths, DEFAULT_CONFIG_PATHS),
+            env: compile(or_default(env, DEFAULT_ENV_PATTERNS)),
+            flags: compile(or_default(flags, DEFAULT_FLAG_PATTERNS)),
+        }
+    }
+
+    fn is_config(&self, path: &str) -> bool {
+        self.config_paths
+            .iter()
+            .any(|p| compact_files_match(p, path))
+    }
+}
+
+/// Comment-only lines of config files don't count.
+fn is_comment(text: &str) -> bool {
+    ["#", "//", ";", "<!--"].iter().any(|c| text.starts_with(c))
+}
+
+/// The config keys, env vars and flags `files` add or remove, grouped by
+/// kind and in diff order within each.
+pub fn scan_operational(files: &[DiffFile], scanner: &OpsScanner) -> Vec<OpsChange> {
+    let key = regex::Regex::new(CONFIG_KEY).expect("config key pattern");
+    let mut found: Vec<OpsChange> = Vec::new();
+    let mut index: HashMap<(OpsKind, String), usize> = HashMap::new();
+    let mut note =
+        |kind: OpsKind, name: &str, path: &str, added: bool, line: Option<usize>, text: &str| {
+            let change = if added {
+                OpsChangeKind::Added
+            } else {
+                OpsChangeKind::Removed
+            };
+            // Config keys are per file; a key of the same name elsewhere is another setting
+            let id = match kind {
+                OpsKind::Config => format!("{}\0{}", path, name),
+                _ => name.to_string(),
+            };
+            match index.get(&(kind, id.clone())) {
+                Some(&at) => {
+                    let existing = &mut found[at];
+                    if existing.change != change {
+                        existing.change = OpsChangeKind::Changed;
+                    }
+                    // Point at the new side when there is one
+                    if added && existing.change == OpsChangeKind::Changed && existing.line.is_some()
+                    {
+                        if let Some(line) = line.filter(|_| existing.path == path) {
+                            existing.line = Some(line);
+                            existing.text = text.to_string();
+                        }
+                    }
+                }
+                None => {
+                    index.insert((kind, id), found.len());
+                    found.push(OpsChange {
+                        kind,
+                        change,
+                        name: name.to_string(),
+                        path: path.to_string(),
+                        line,
+                        text: text.to_string(),
+                    });
+                }
+            }
+        };
+    for file in files {
+        let config = scanner.is_config(&file.path);
+        for line in file.hunks.iter().flat_map(|h| &h.lines) {
+            let (added, number) = match line.line_type {
+                LineType::Add => (true, line.new_num),
+                LineType::Delete => (false, line.old_num),
+                _ => continue,
+            };
+            let text = line.content.trim();
+            if text.is_empty() {
+                continue;
+            }
+            if config && !is_comment(text) {
+                if let Some(name) = key.captures(&line.content).and_then(|c| c.get(1)) {
+                    note(
+                        OpsKind::Config,
+                        name.as_str(),
+                        &file.path,
+                        added,
+                        number,
+                        text,
+                    );
+                }
+            }
+            for (kind, patterns) in [
+                (OpsKind::EnvVar, &scanner.env),
+                (OpsKind::FeatureFlag, &scanner.flags),
+            ] {
+                for re in patterns {
+                    for caps in re.captures_iter(&line.content) {
+                        let name = caps
+                            .get(1)
+                            .or_else(|| caps.get(0))
+                            .map_or("", |m| m.as_str());
+                        if !name.is_empty() {
+                            note(kind, name, &file.path, added, number, text);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found.sort_by_key(|c| c.kind);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn config_keys_env_vars_and_flags_are_found() {
+        let diff = "diff --git a/config/app.yaml b/config/app.yaml\n--- a/config/app.yaml\n+++ b/config/app.yaml\n\
+                    @@ -1,3 +1,3 @@\n # database\n-pool_size: 5\n+pool_size: 20\n+  timeout_ms: 300\n\
+                    diff --git a/src/main.rs b/src/main.rs\n--- a/src/main.rs\n+++ b/src/main.rs\n\
+                    @@ -10,3 +10,4 @@\n fn main() {\n-    let url = std::env::var(\"DB_URL\").unwrap();\n\
+                    +    let url = std::env::var(\"DATABASE_URL\").unwrap();\n\
+                    +    if flags.is_enabled(\"new-checkout\") { checkout_v2() }\n\
+                    +    #[cfg(feature = \"metrics\")] init_metrics();\n }\n";
+        let found = scan_operational(&parse_diff(diff), &OpsScanner::default());
+        let summary: Vec<(OpsKind, OpsChangeKind, &str, Option<usize>)> = found
+            .iter()
+            .map(|c| (c.kind, c.change, c.name.as_str(), c.line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    OpsKind::Config,
+                    OpsChangeKind::Changed,
+                    "pool_size",
+                    Some(2)
+                ),
+                (OpsKind::Config, OpsChangeKind::Added, "timeout_ms", Some(3)),
+                (OpsKind::EnvVar, OpsChangeKind::Removed, "DB_URL", Some(11)),
+                (
+                    OpsKind::EnvVar,
+                    OpsChangeKind::Added,
+                    "DATABASE_URL",
+                    Some(11)
+                ),
+                (
+                    OpsKind::FeatureFlag,
+                    OpsChangeKind::Added,
+                    "new-checkout",
+                    Some(12)
+                ),
+                (
+                    OpsKind::FeatureFlag,
+                    OpsChangeKind::Added,
+                    "metrics",
+                    Some(13)
+                ),
+            ]
+        );
+        assert_eq!(found[0].text, "pool_size: 20");
+    }
+
+    #[test]
+    fn configured_patterns_replace_the_defaults() {
+        let diff = "diff --git a/deploy/flags.txt b/deploy/flags.txt\n--- a/deploy/flags.txt\n+++ b/deploy/flags.txt\n\
+                    @@ -0,0 +1,2 @@\n+retries = 3\n+Unleash.on(\"dark-mode\")\n";
+        let files = parse_diff(diff);
+        assert!(scan_operational(&files, &OpsScanner::default()).is_empty());
+
+        let scanner = OpsScanner::new(
+            &["deploy/**".into()],
+            &[],
+            &[r#"Unleash\.on\("([^"]+)""#.into(), "(unclosed".into()],
+        );
+        assert_eq!(scanner.flags.len(), 1);
+        let names: Vec<String> = scan_operational(&files, &scanner)
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["retries", "dark-mode"]);
+    }
+}
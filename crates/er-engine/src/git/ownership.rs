@@ -0,0 +1,153 @@
+//! Who has worked on a file — `git shortlog -sn -- path` plus when each
+//! author last touched it.
+
+use crate::command::OutputLogged;
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// One historical author of a file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FileOwner {
+    pub name: String,
+    pub commits: usize,
+    /// `YYYY-MM-DD` of their most recent commit to the file
+    pub last_touched: String,
+}
+
+/// A file's authors by commit count, plus who wrote the change under review.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FileOwnership {
+    /// Most commits first; ties go to whoever touched it last
+    pub owners: Vec<FileOwner>,
+    pub total_commits: usize,
+    /// Author of the commit at `rev` (the change being reviewed)
+    pub change_author: Option<String>,
+}
+
+impl FileOwnership {
+    /// `change_author`'s share of the file's commits, 0 when unknown.
+    pub fn change_author_commits(&self) -> usize {
+        self.change_author
+            .as_ref()
+            .and_then(|a| self.owners.iter().find(|o| &o.name == a))
+            .map_or(0, |o| o.commits)
+    }
+}
+
+/// Authors of `path` as of `rev`, following renames. `rev` is usually
+/// `HEAD`; for a branch viewed without checkout, the branch name.
+pub fn file_ownership(repo_root: &str, rev: &str, path: &str) -> Result<FileOwnership> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--follow",
+            "--no-merges",
+            "--format=%aN%x09%as",
+            rev,
+            "--",
+            path,
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut ownership = parse_file_ownership(&String::from_utf8_lossy(&output.stdout));
+    ownership.change_author = Command::new("git")
+        .args(["log", "-1", "--format=%aN", rev])
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|name| !name.is_empty());
+    Ok(ownership)
+}
+
+/// Parse `git log --format=%aN%x09%as` (newest first) into per-author counts.
+fn parse_file_ownership(output: &str) -> FileOwnership {
+    let mut owners: Vec<FileOwner> = Vec::new();
+    let mut total_commits = 0;
+    for line in output.lines() {
+        let Some((name, date)) = line.split_once('\t') else {
+            continue;
+        };
+        total_commits += 1;
+        match owners.iter_mut().find(|o| o.name == name) {
+            Some(owner) => owner.commits += 1,
+            None => owners.push(FileOwner {
+                name: name.to_string(),
+                commits: 1,
+                last_touched: date.to_string(),
+            }),
+        }
+    }
+    // Stable sort keeps the most recent author first among equals
+    owners.sort_by_key(|o| std::cmp::Reverse(o.commits));
+    FileOwnership {
+        owners,
+        total_commits,
+        change_author: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owners_rank_by_commits_and_keep_their_latest_date() {
+        let log = "bob\t2026-03-02\nalice\t2026-03-01\nbob\t2026-02-01\ncarol\t2025-12-24\nalice\t2025-11-30\nbob\t2025-01-05\n";
+        let ownership = parse_file_ownership(log);
+        assert_eq!(ownership.total_commits, 6);
+        let names: Vec<(&str, usize, &str)> = ownership
+            .owners
+            .iter()
+            .map(|o| (o.name.as_str(), o.commits, o.last_touched.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("bob", 3, "2026-03-02"),
+                ("alice", 2, "2026-03-01"),
+                ("carol", 1, "2025-12-24")
+            ]
+        );
+    }
+
+    #[test]
+    fn ownership_reads_a_real_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |author: &str, args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", author)
+                .env("GIT_AUTHOR_EMAIL", "a@t.com")
+                .env("GIT_COMMITTER_NAME", author)
+                .env("GIT_COMMITTER_EMAIL", "a@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git("t", &["init", "-b", "main"]);
+        for (author, body) in [
+            ("owner", "one\n"),
+            ("owner", "two\n"),
+            ("newcomer", "three\n"),
+        ] {
+            std::fs::write(root.join("a.txt"), body).unwrap();
+            git(author, &["add", "a.txt"]);
+            git(author, &["commit", "-m", "edit", "--no-gpg-sign"]);
+        }
+        let ownership = file_ownership(root.to_str().unwrap(), "HEAD", "a.txt").unwrap();
+        assert_eq!(ownership.owners[0].name, "owner");
+        assert_eq!(ownership.owners[0].commits, 2);
+        assert_eq!(ownership.change_author.as_deref(), Some("newcomer"));
+        assert_eq!(ownership.change_author_commits(), 1);
+    }
+}
@@ -0,0 +1,520 @@
+//! Monorepo workspace members: Cargo workspaces, pnpm / npm / yarn
+//! workspaces and Bazel packages, found from the manifests at the repo root.
+//! Files are attributed to the package with the deepest root containing them.
+//! The members' manifests also give the workspace's internal dependency
+//! graph, so a change can be traced to the packages that build on it.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::process::Command;
+
+use crate::command::OutputLogged;
+
+/// Bazel packages listed at most, so a huge BUILD tree stays cheap.
+const MAX_BAZEL_PACKAGES: usize = 2000;
+
+/// Manifests whose change can add or remove a package.
+pub const PACKAGE_MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pnpm-workspace.yaml",
+    "BUILD",
+    "BUILD.bazel",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Cargo,
+    /// pnpm, npm or yarn workspace member
+    Node,
+    Bazel,
+}
+
+/// A workspace member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// Crate / package.json name, or `//dir` for Bazel
+    pub name: String,
+    /// Repo-relative directory without a trailing slash; `""` is the root
+    pub root: String,
+    pub kind: PackageKind,
+}
+
+impl Package {
+    /// Whether `path` is inside this package's directory (nested
+    /// packages included).
+    pub fn contains(&self, path: &str) -> bool {
+        self.root.is_empty()
+            || path
+                .strip_prefix(self.root.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// The package owning `path`: the one with the deepest root containing it.
+pub fn package_for<'a>(packages: &'a [Package], path: &str) -> Option<&'a Package> {
+    packages
+        .iter()
+        .filter(|p| p.contains(path))
+        .max_by_key(|p| p.root.len())
+}
+
+/// Workspace members of the repo at `repo_root`, sorted by root. Empty for
+/// a repo that isn't a monorepo.
+pub fn detect_packages(repo_root: &str) -> Vec<Package> {
+    let root = Path::new(repo_root);
+    let mut packages = Vec::new();
+    packages.extend(cargo_packages(root));
+    packages.extend(node_packages(root));
+    packages.extend(bazel_packages(repo_root));
+
+    // One package per directory; the first manifest kind found wins
+    let mut seen = HashSet::new();
+    packages.retain(|p| seen.insert(p.root.clone()));
+    packages.sort_by(|a, b| a.root.cmp(&b.root));
+    // A lone root package isn't a monorepo
+    if packages.len() == 1 && packages[0].root.is_empty() {
+        packages.clear();
+    }
+    packages
+}
+
+/// A package the diff doesn't touch that depends, directly or through
+/// other members, on one it does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedPackage {
+    pub name: String,
+    /// Touched package it depends on
+    pub via: String,
+    /// Dependency hops to `via`; 1 is a direct dependency
+    pub depth: usize,
+}
+
+/// For each of `packages`, the names of the other members it depends on:
+/// path and workspace dependencies of a Cargo member (what `cargo metadata`
+/// reports for the workspace), package.json dependencies on other workspace
+/// members, and `//dir` labels in a Bazel BUILD file.
+pub fn package_dependencies(repo_root: &str, packages: &[Package]) -> HashMap<String, Vec<String>> {
+    let root = Path::new(repo_root);
+    let names: HashSet<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+    let mut graph = HashMap::new();
+    for package in packages {
+        let dir = root.join(&package.root);
+        let mut deps: Vec<String> = match package.kind {
+            PackageKind::Cargo => read_toml(&dir.join("Cargo.toml"))
+                .map(|m| cargo_dependency_names(&m))
+                .unwrap_or_default(),
+            PackageKind::Node => read_json(&dir.join("package.json"))
+                .map(|j| node_dependency_names(&j))
+                .unwrap_or_default(),
+            PackageKind::Bazel => {
+                let build = ["BUILD.bazel", "BUILD"]
+                    .iter()
+                    .find_map(|f| std::fs::read_to_string(dir.join(f)).ok())
+                    .unwrap_or_default();
+                packages
+                    .iter()
+                    .filter(|p| p.kind == PackageKind::Bazel && p.root != package.root)
+                    .filter(|p| {
+                        let label = format!("\"{}", p.name);
+                        build.contains(&format!("{}\"", label))
+                            || build.contains(&format!("{}:", label))
+                    })
+                    .map(|p| p.name.clone())
+                    .collect()
+            }
+        };
+        deps.retain(|d| *d != package.name && names.contains(d.as_str()));
+        deps.sort();
+        deps.dedup();
+        graph.insert(package.name.clone(), deps);
+    }
+    graph
+}
+
+/// Dependency names in every `*dependencies` table of a Cargo manifest,
+/// following `package = "…"` renames.
+fn cargo_dependency_names(manifest: &toml::Table) -> Vec<String> {
+    let targets = manifest
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(|t| t.as_table()));
+    let tables: Vec<&toml::Table> = std::iter::once(manifest)
+        .chain(targets)
+        .flat_map(|t| {
+            ["dependencies", "dev-dependencies", "build-dependencies"]
+                .iter()
+                .filter_map(|key| t.get(*key)?.as_table())
+        })
+        .collect();
+    tables
+        .into_iter()
+        .flat_map(|deps| deps.iter())
+        .map(|(key, value)| {
+            value
+                .get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(key)
+                .to_string()
+        })
+        .collect()
+}
+
+fn node_dependency_names(json: &serde_json::Value) -> Vec<String> {
+    [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ]
+    .iter()
+    .filter_map(|key| json.get(key)?.as_object())
+    .flat_map(|deps| deps.keys().cloned())
+    .collect()
+}
+
+/// Members that don't appear in `touched` but depend on one that does,
+/// nearest first, walking `graph` (from [`package_dependencies`]) backwards.
+pub fn affected_packages(
+    graph: &HashMap<String, Vec<String>>,
+    touched: &[&str],
+) -> Vec<AffectedPackage> {
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (name, deps) in graph {
+        for dep in deps {
+            dependents
+                .entry(dep.as_str())
+                .or_default()
+                .push(name.as_str());
+        }
+    }
+    let mut seen: HashSet<&str> = touched.iter().copied().collect();
+    let mut queue: VecDeque<(&str, &str, usize)> = touched.iter().map(|&t| (t, t, 0)).collect();
+    let mut affected = Vec::new();
+    while let Some((name, via, depth)) = queue.pop_front() {
+        let mut next = dependents.get(name).cloned().unwrap_or_default();
+        next.sort_unstable();
+        for dependent in next {
+            if seen.insert(dependent) {
+                affected.push(AffectedPackage {
+                    name: dependent.to_string(),
+                    via: via.to_string(),
+                    depth: depth + 1,
+                });
+                queue.push_back((dependent, via, depth + 1));
+            }
+        }
+    }
+    affected
+}
+
+/// Member directories matching `patterns` (relative globs, `!` excludes)
+/// that hold `manifest`.
+fn expand_members(root: &Path, patterns: &[String], manifest: &str) -> Vec<String> {
+    let excluded: Vec<glob::Pattern> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!'))
+        .filter_map(|p| glob::Pattern::new(p.trim_end_matches('/')).ok())
+        .collect();
+    let mut dirs = Vec::new();
+    for pattern in patterns.iter().filter(|p| !p.starts_with('!')) {
+        let pattern = pattern.trim_end_matches('/');
+        let full = root.join(pattern).display().to_string();
+        let Ok(paths) = glob::glob(&full) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            if !path.join(manifest).is_file() {
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            if !excluded.iter().any(|p| p.matches(&rel)) && !dirs.contains(&rel) {
+                dirs.push(rel);
+            }
+        }
+    }
+    dirs
+}
+
+fn dir_name(rel: &str) -> String {
+    rel.rsplit('/').next().unwrap_or(rel).to_string()
+}
+
+fn cargo_packages(root: &Path) -> Vec<Package> {
+    let Some(manifest) = read_toml(&root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Some(workspace) = manifest.get("workspace") else {
+        return Vec::new();
+    };
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut patterns = strings("members");
+    patterns.extend(strings("exclude").into_iter().map(|p| format!("!{}", p)));
+
+    let mut packages: Vec<Package> = expand_members(root, &patterns, "Cargo.toml")
+        .into_iter()
+        .map(|rel| {
+            let name = read_toml(&root.join(&rel).join("Cargo.toml"))
+                .and_then(|m| cargo_name(&m))
+                .unwrap_or_else(|| dir_name(&rel));
+            Package {
+                name,
+                root: rel,
+                kind: PackageKind::Cargo,
+            }
+        })
+        .collect();
+    if let Some(name) = cargo_name(&manifest) {
+        packages.push(Package {
+            name,
+            root: String::new(),
+            kind: PackageKind::Cargo,
+        });
+    }
+    packages
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
+
+fn cargo_name(manifest: &toml::Table) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn node_packages(root: &Path) -> Vec<Package> {
+    let mut patterns = std::fs::read_to_string(root.join("pnpm-workspace.yaml"))
+        .map(|yaml| pnpm_workspace_globs(&yaml))
+        .unwrap_or_default();
+    if patterns.is_empty() {
+        patterns = read_json(&root.join("package.json"))
+            .map(|json| npm_workspace_globs(&json))
+            .unwrap_or_default();
+    }
+    expand_members(root, &patterns, "package.json")
+        .into_iter()
+        .map(|rel| {
+            let name = read_json(&root.join(&rel).join("package.json"))
+                .and_then(|j| j.get("name")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| dir_name(&rel));
+            Package {
+                name,
+                root: rel,
+                kind: PackageKind::Node,
+            }
+        })
+        .collect()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// `workspaces` of a package.json: an array, or `{ "packages": [...] }`.
+fn npm_workspace_globs(json: &serde_json::Value) -> Vec<String> {
+    let Some(workspaces) = json.get("workspaces") else {
+        return Vec::new();
+    };
+    workspaces
+        .as_array()
+        .or_else(|| workspaces.get("packages")?.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The `packages:` list of a pnpm-workspace.yaml (block or `[a, b]` form).
+fn pnpm_workspace_globs(yaml: &str) -> Vec<String> {
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '\'' || c == '"').to_string();
+    let mut globs = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let line = line.split(" #").next().unwrap_or(line).trim_end();
+        if let Some(rest) = line.strip_prefix("packages:") {
+            let inline = rest.trim();
+            if let Some(list) = inline.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                globs.extend(list.split(',').map(unquote).filter(|g| !g.is_empty()));
+                return globs;
+            }
+            in_packages = true;
+            continue;
+        }
+        if !in_packages || line.trim().is_empty() {
+            continue;
+        }
+        match line.trim_start().strip_prefix("- ") {
+            Some(item) if line.starts_with(char::is_whitespace) || line.starts_with('-') => {
+                globs.push(unquote(item))
+            }
+            // The next top-level key ends the list
+            _ => break,
+        }
+    }
+    globs
+}
+
+/// Directories with a BUILD file, when the repo is a Bazel workspace.
+fn bazel_packages(repo_root: &str) -> Vec<Package> {
+    let root = Path::new(repo_root);
+    let is_workspace = ["WORKSPACE", "WORKSPACE.bazel", "MODULE.bazel"]
+        .iter()
+        .any(|f| root.join(f).is_file());
+    if !is_workspace {
+        return Vec::new();
+    }
+    let Ok(output) = Command::new("git")
+        .args([
+            "ls-files",
+            "--",
+            "BUILD",
+            "BUILD.bazel",
+            "**/BUILD",
+            "**/BUILD.bazel",
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+    else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|path| path.rsplit_once('/').map_or("", |(dir, _)| dir).to_string())
+        .collect();
+    dirs.dedup();
+    dirs.truncate(MAX_BAZEL_PACKAGES);
+    dirs.into_iter()
+        .map(|dir| Package {
+            name: format!("//{}", dir),
+            root: dir,
+            kind: PackageKind::Bazel,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn cargo_and_pnpm_members_are_found_and_own_their_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let d = dir.path();
+        write(
+            d,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write(
+            d,
+            "crates/api/Cargo.toml",
+            "[package]\nname = \"api-server\"\n",
+        );
+        write(d, "crates/old/Cargo.toml", "[package]\nname = \"old\"\n");
+        write(d, "crates/notes/README.md", "no manifest\n");
+        write(
+            d,
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'web/*'\n  - \"!web/legacy\"\nonlyBuiltDependencies: []\n",
+        );
+        write(d, "web/app/package.json", "{\"name\": \"@acme/app\"}");
+        write(d, "web/legacy/package.json", "{\"name\": \"legacy\"}");
+
+        let packages = detect_packages(d.to_str().unwrap());
+        let names: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.root.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [("api-server", "crates/api"), ("@acme/app", "web/app")]
+        );
+        assert_eq!(
+            package_for(&packages, "crates/api/src/main.rs").map(|p| p.kind),
+            Some(PackageKind::Cargo)
+        );
+        assert!(package_for(&packages, "crates/apix/lib.rs").is_none());
+        assert!(package_for(&packages, "README.md").is_none());
+    }
+
+    #[test]
+    fn dependents_of_touched_packages_are_affected() {
+        let dir = tempfile::tempdir().unwrap();
+        let d = dir.path();
+        write(d, "Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+        write(d, "crates/core/Cargo.toml", "[package]\nname = \"core\"\n");
+        write(
+            d,
+            "crates/api/Cargo.toml",
+            "[package]\nname = \"api\"\n[dependencies]\nmy-core = { package = \"core\", path = \"../core\" }\nserde = \"1\"\n",
+        );
+        write(
+            d,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"cli\"\n[target.'cfg(unix)'.dev-dependencies]\napi = { workspace = true }\n",
+        );
+        write(d, "crates/docs/Cargo.toml", "[package]\nname = \"docs\"\n");
+        let root = d.to_str().unwrap();
+        let graph = package_dependencies(root, &detect_packages(root));
+        assert_eq!(graph["api"], ["core"]);
+        assert_eq!(graph["cli"], ["api"]);
+
+        let affected = affected_packages(&graph, &["core"]);
+        let summary: Vec<(&str, &str, usize)> = affected
+            .iter()
+            .map(|a| (a.name.as_str(), a.via.as_str(), a.depth))
+            .collect();
+        assert_eq!(summary, [("api", "core", 1), ("cli", "core", 2)]);
+        assert!(affected_packages(&graph, &["core", "api", "cli"]).is_empty());
+    }
+
+    #[test]
+    fn workspace_globs_come_from_either_manifest_form() {
+        let json: serde_json::Value =
+            serde_json::from_str(r#"{"workspaces": {"packages": ["apps/*"]}}"#).unwrap();
+        assert_eq!(npm_workspace_globs(&json), ["apps/*"]);
+        assert_eq!(
+            pnpm_workspace_globs("packages: ['a/*', \"b\"]\n"),
+            ["a/*", "b"]
+        );
+        let nested = Package {
+            name: "inner".into(),
+            root: "a/b".into(),
+            kind: PackageKind::Node,
+        };
+        let outer = Package {
+            name: "outer".into(),
+            root: "a".into(),
+            kind: PackageKind::Node,
+        };
+        let packages = [outer, nested];
+        assert_eq!(package_for(&packages, "a/b/c.ts").unwrap().name, "inner");
+        assert_eq!(package_for(&packages, "a/c.ts").unwrap().name, "outer");
+    }
+}
@@ -0,0 +1,471 @@
+//! Rebuilding `git apply`-able patches from parsed hunks (copy hub: hunk as
+//! patch, reverse patch, save to a `.patch` file), applying one to another
+//! worktree, and staging one for the commit splitter or for the lines
+//! selected in the diff; and undoing a file's hunks on its new side to get
+//! the old one back without asking git for it.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use super::{DiffFile, DiffHunk, FileStatus, LineType};
+
+/// `@@ -a,b +c,d @@` for `hunk`, counted from its lines so a pruned or
+/// reversed hunk still applies. Keeps the function context after the `@@`.
+fn hunk_header(hunk: &DiffHunk, reverse: bool) -> String {
+    let (mut old_count, mut new_count) = (0, 0);
+    for line in &hunk.lines {
+        match line.line_type {
+            LineType::Context => {
+                old_count += 1;
+                new_count += 1;
+            }
+            LineType::Delete => old_count += 1,
+            LineType::Add => new_count += 1,
+            LineType::Fold(_) => {}
+        }
+    }
+    let (mut old, mut new) = ((hunk.old_start, old_count), (hunk.new_start, new_count));
+    if reverse {
+        std::mem::swap(&mut old, &mut new);
+    }
+    let context = hunk
+        .header
+        .strip_prefix("@@")
+        .and_then(|rest| rest.split_once("@@"))
+        .map_or("", |(_, ctx)| ctx);
+    format!("@@ -{},{} +{},{} @@{}", old.0, old.1, new.0, new.1, context)
+}
+
+/// `hunk` cut down to the selected lines at indices `range`, for staging,
+/// unstaging or discarding part of a hunk. `side` limits the selection to
+/// additions (`Add`) or deletions (`Delete`), as in the focused split pane;
+/// `None` takes both.
+///
+/// The patch applies to one side of the hunk: the old side going forward
+/// (staging), the new side in `reverse` (unstaging, discarding). Lines that
+/// side already has but that aren't selected become context; unselected
+/// lines it doesn't have are dropped. `None` when nothing was selected.
+pub fn select_lines(
+    hunk: &DiffHunk,
+    range: std::ops::RangeInclusive<usize>,
+    side: Option<LineType>,
+    reverse: bool,
+) -> Option<DiffHunk> {
+    let mut selected = 0;
+    let mut lines = Vec::with_capacity(hunk.lines.len());
+    for (i, line) in hunk.lines.iter().enumerate() {
+        let changed = matches!(line.line_type, LineType::Add | LineType::Delete);
+        if changed && range.contains(&i) && side.is_none_or(|s| s == line.line_type) {
+            selected += 1;
+            lines.push(line.clone());
+            continue;
+        }
+        // The side the patch applies to keeps its own unselected lines
+        let kept = match line.line_type {
+            LineType::Delete => !reverse,
+            LineType::Add => reverse,
+            LineType::Context | LineType::Fold(_) => {
+                lines.push(line.clone());
+                continue;
+            }
+        };
+        if kept {
+            let mut line = line.clone();
+            line.line_type = LineType::Context;
+            lines.push(line);
+        }
+    }
+    (selected > 0).then(|| DiffHunk {
+        lines,
+        ..hunk.clone()
+    })
+}
+
+/// A patch for `hunks` of `file`, with the headers `git apply` needs.
+/// `reverse` builds the patch that undoes them. `preimage` is the blob id of
+/// the old side, written as the `index` line that `git apply --3way` needs.
+///
+/// Fails on hunks with folded context: the hidden lines aren't in memory,
+/// so the patch would not apply.
+pub fn file_patch(
+    file: &DiffFile,
+    hunks: &[&DiffHunk],
+    reverse: bool,
+    preimage: Option<&str>,
+) -> Result<String> {
+    if hunks.iter().any(|h| {
+        h.lines
+            .iter()
+            .any(|l| matches!(l.line_type, LineType::Fold(_)))
+    }) {
+        anyhow::bail!("{} has folded context — expand it first", file.path);
+    }
+    let (mut old_path, mut new_path) = match &file.status {
+        FileStatus::Renamed(old) | FileStatus::Copied(old) => (old.as_str(), file.path.as_str()),
+        _ => (file.path.as_str(), file.path.as_str()),
+    };
+    let (mut added, mut deleted) = (
+        file.status == FileStatus::Added,
+        file.status == FileStatus::Deleted,
+    );
+    if reverse {
+        std::mem::swap(&mut old_path, &mut new_path);
+        std::mem::swap(&mut added, &mut deleted);
+    }
+
+    let mut out = format!("diff --git a/{} b/{}\n", old_path, new_path);
+    if let Some(blob) = preimage {
+        // Only the old side is read; the new id is left unknown
+        out.push_str(&format!("index {}..0000000\n", blob));
+    }
+    if added {
+        out.push_str("new file mode 100644\n");
+    } else if deleted {
+        out.push_str("deleted file mode 100644\n");
+    } else if old_path != new_path {
+        out.push_str(&format!(
+            "rename from {}\nrename to {}\n",
+            old_path, new_path
+        ));
+    }
+    if added {
+        out.push_str("--- /dev/null\n");
+    } else {
+        out.push_str(&format!("--- a/{}\n", old_path));
+    }
+    if deleted {
+        out.push_str("+++ /dev/null\n");
+    } else {
+        out.push_str(&format!("+++ b/{}\n", new_path));
+    }
+
+    for hunk in hunks {
+        out.push_str(&hunk_header(hunk, reverse));
+        out.push('\n');
+        for line in &hunk.lines {
+            let prefix = match (line.line_type, reverse) {
+                (LineType::Context, _) => ' ',
+                (LineType::Add, false) | (LineType::Delete, true) => '+',
+                (LineType::Delete, false) | (LineType::Add, true) => '-',
+                (LineType::Fold(_), _) => continue,
+            };
+            out.push(prefix);
+            out.push_str(&line.content);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Result of [`apply_patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchApply {
+    Clean,
+    /// Fell back to a three-way merge; these paths have conflict markers
+    Conflicts(Vec<String>),
+}
+
+fn run_apply(worktree: &str, patch: &str, flag: Option<&str>) -> Result<std::process::Output> {
+    let mut args = vec!["apply", "--whitespace=nowarn"];
+    args.extend(flag);
+    let mut child = Command::new("git")
+        .args(&args)
+        .arg("-")
+        .current_dir(worktree)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run git apply")?;
+    // git apply reads the whole patch before writing anything, so this can't
+    // deadlock on a full stdout pipe. Dropping stdin signals EOF.
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes())?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+/// `git apply` `patch` in `worktree`. When it doesn't apply cleanly, retries
+/// as a three-way merge (`--3way`, which also stages the result) and reports
+/// the conflicted paths; errors when that fails too.
+pub fn apply_patch(worktree: &str, patch: &str) -> Result<PatchApply> {
+    let plain = run_apply(worktree, patch, None)?;
+    if plain.status.success() {
+        return Ok(PatchApply::Clean);
+    }
+    let merged = run_apply(worktree, patch, Some("--3way"))?;
+    let report = format!(
+        "{}{}",
+        String::from_utf8_lossy(&merged.stdout),
+        String::from_utf8_lossy(&merged.stderr)
+    );
+    let conflicts: Vec<String> = report
+        .lines()
+        .filter_map(|l| l.strip_prefix("U "))
+        .map(str::to_string)
+        .collect();
+    if merged.status.success() {
+        return Ok(PatchApply::Clean);
+    }
+    if !conflicts.is_empty() {
+        return Ok(PatchApply::Conflicts(conflicts));
+    }
+    let stderr = String::from_utf8_lossy(&plain.stderr);
+    anyhow::bail!(
+        "{}",
+        stderr
+            .lines()
+            .map(|l| l.trim_start_matches("error: "))
+            .collect::<Vec<_>>()
+            .join("; ")
+    )
+}
+
+/// Add `patch` to the index only (`git apply --cached`); the working tree
+/// is left alone.
+pub fn stage_patch(repo_root: &str, patch: &str) -> Result<()> {
+    checked(run_apply(repo_root, patch, Some("--cached"))?)
+}
+
+/// Apply `patch` to the working tree only, with no three-way fallback:
+/// discarding lines must not touch the index or leave conflict markers.
+pub fn apply_worktree_patch(repo_root: &str, patch: &str) -> Result<()> {
+    checked(run_apply(repo_root, patch, None)?)
+}
+
+/// The old side of a file from its new side `new` and its diff `hunks`:
+/// added lines dropped, deleted ones put back.
+pub fn old_side(new: &str, hunks: &[DiffHunk]) -> String {
+    let new_lines: Vec<&str> = new.lines().collect();
+    let mut old: Vec<&str> = Vec::with_capacity(new_lines.len());
+    let mut pos = 0;
+    for hunk in hunks {
+        // A hunk with no new-side lines starts after `new_start`, not on it
+        let has_new = hunk
+            .lines
+            .iter()
+            .any(|l| !matches!(l.line_type, LineType::Delete));
+        let start = if has_new {
+            hunk.new_start.saturating_sub(1)
+        } else {
+            hunk.new_start
+        }
+        .min(new_lines.len());
+        if start > pos {
+            old.extend_from_slice(&new_lines[pos..start]);
+            pos = start;
+        }
+        for line in &hunk.lines {
+            match line.line_type {
+                LineType::Context => {
+                    old.push(&line.content);
+                    pos += 1;
+                }
+                LineType::Delete => old.push(&line.content),
+                LineType::Add => pos += 1,
+                LineType::Fold(hidden) => {
+                    let end = (pos + hidden).min(new_lines.len());
+                    old.extend_from_slice(&new_lines[pos.min(end)..end]);
+                    pos += hidden;
+                }
+            }
+        }
+    }
+    if pos < new_lines.len() {
+        old.extend_from_slice(&new_lines[pos..]);
+    }
+    let mut text = old.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+fn checked(output: std::process::Output) -> Result<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .map(|l| l.trim_start_matches("error: "))
+            .collect::<Vec<_>>()
+            .join("; ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    const DIFF: &str = "diff --git a/src/a.rs b/src/a.rs\n\
+                        --- a/src/a.rs\n\
+                        +++ b/src/a.rs\n\
+                        @@ -1,3 +1,3 @@ fn main() {\n \
+                        keep\n\
+                        -old\n\
+                        +new\n \
+                        tail\n\
+                        @@ -10,2 +10,3 @@\n \
+                        x\n\
+                        +y\n \
+                        z\n";
+
+    #[test]
+    fn hunk_patches_apply_forward_and_in_reverse() {
+        let files = parse_diff(DIFF);
+        let file = &files[0];
+        let patch = file_patch(file, &[&file.hunks[1]], false, None).unwrap();
+        assert_eq!(
+            patch,
+            "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+             @@ -10,2 +10,3 @@\n x\n+y\n z\n"
+        );
+
+        let reverse = file_patch(file, &[&file.hunks[0]], true, None).unwrap();
+        assert!(reverse.ends_with("@@ -1,3 +1,3 @@ fn main() {\n keep\n+old\n-new\n tail\n"));
+
+        let mut added = file.clone();
+        added.status = FileStatus::Added;
+        let reverse = file_patch(&added, &[&added.hunks[1]], true, None).unwrap();
+        assert!(reverse.contains("deleted file mode 100644\n--- a/src/a.rs\n+++ /dev/null\n"));
+        assert!(reverse.contains("@@ -10,3 +10,2 @@\n x\n-y\n z\n"));
+    }
+
+    #[test]
+    fn old_side_undoes_the_hunks() {
+        let files = parse_diff(DIFF);
+        let new: String = (1..=12)
+            .map(|n| match n {
+                1 => "keep\n".to_string(),
+                2 => "new\n".to_string(),
+                3 => "tail\n".to_string(),
+                10 => "x\n".to_string(),
+                11 => "y\n".to_string(),
+                12 => "z\n".to_string(),
+                n => format!("{}\n", n),
+            })
+            .collect();
+        let old = old_side(&new, &files[0].hunks);
+        assert_eq!(old, "keep\nold\ntail\n4\n5\n6\n7\n8\n9\nx\nz\n");
+
+        let deleted = parse_diff(
+            "diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -2,2 +1,0 @@\n-gone\n-too\n",
+        );
+        assert_eq!(
+            old_side("one\ntwo\n", &deleted[0].hunks),
+            "one\ngone\ntoo\ntwo\n"
+        );
+    }
+
+    #[test]
+    fn selected_lines_become_a_partial_hunk_for_either_side() {
+        let diff =
+            "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n+C\n d\n";
+        let files = parse_diff(diff);
+        let (file, hunk) = (&files[0], &files[0].hunks[0]);
+
+        // New pane, both additions selected: only they are staged, the
+        // deletions stay as context
+        let stage = select_lines(hunk, 1..=4, Some(LineType::Add), false).unwrap();
+        assert!(file_patch(file, &[&stage], false, None)
+            .unwrap()
+            .ends_with("@@ -1,4 +1,6 @@\n a\n b\n c\n+B\n+C\n d\n"));
+
+        // Old pane, discarding "c": restores it in the worktree, which never
+        // had the other deletion
+        let discard = select_lines(hunk, 2..=2, Some(LineType::Delete), true).unwrap();
+        assert!(file_patch(file, &[&discard], true, None)
+            .unwrap()
+            .ends_with("@@ -1,4 +1,5 @@\n a\n+c\n B\n C\n d\n"));
+
+        // Unified: the selection takes both kinds
+        let both = select_lines(hunk, 2..=3, None, false).unwrap();
+        assert!(file_patch(file, &[&both], false, None)
+            .unwrap()
+            .ends_with("@@ -1,4 +1,4 @@\n a\n b\n-c\n+B\n d\n"));
+
+        assert!(select_lines(hunk, 1..=2, Some(LineType::Add), false).is_none());
+        assert!(select_lines(hunk, 0..=0, None, false).is_none());
+    }
+
+    #[test]
+    fn selected_lines_stage_and_discard_in_a_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            let out = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "{:?}", out);
+            String::from_utf8(out.stdout).unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("f"), "a\nb\nc\nd\n").unwrap();
+        git(&["add", "f"]);
+        git(&["commit", "-qm", "init"]);
+        std::fs::write(dir.path().join("f"), "a\nB\nC\nd\n").unwrap();
+        let files = parse_diff(&git(&["diff"]));
+        let (file, hunk) = (&files[0], &files[0].hunks[0]);
+
+        let stage = select_lines(hunk, 3..=3, Some(LineType::Add), false).unwrap();
+        stage_patch(root, &file_patch(file, &[&stage], false, None).unwrap()).unwrap();
+        assert_eq!(git(&["show", ":f"]), "a\nb\nc\nB\nd\n");
+
+        let discard = select_lines(hunk, 1..=1, Some(LineType::Delete), true).unwrap();
+        apply_worktree_patch(root, &file_patch(file, &[&discard], true, None).unwrap()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("f")).unwrap(),
+            "a\nb\nB\nC\nd\n"
+        );
+        // The index is untouched by a discard
+        assert_eq!(git(&["show", ":f"]), "a\nb\nc\nB\nd\n");
+    }
+
+    #[test]
+    fn apply_falls_back_to_a_three_way_merge_and_reports_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            let out = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "{:?}", out);
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("f"), "a\nb\nc\n").unwrap();
+        git(&["add", "f"]);
+        git(&["commit", "-qm", "init"]);
+
+        let blob = crate::github::rev_parse_oid(root, "HEAD:f").unwrap();
+        let patch = format!(
+            "diff --git a/f b/f\nindex {}..0000000\n--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n",
+            blob
+        );
+        assert_eq!(apply_patch(root, &patch).unwrap(), PatchApply::Clean);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("f")).unwrap(),
+            "a\nB\nc\n"
+        );
+
+        std::fs::write(dir.path().join("f"), "a\nX\nc\n").unwrap();
+        git(&["commit", "-qam", "diverge"]);
+        assert_eq!(
+            apply_patch(root, &patch).unwrap(),
+            PatchApply::Conflicts(vec!["f".to_string()])
+        );
+
+        let missing = "diff --git a/g b/g\n--- a/g\n+++ b/g\n@@ -1 +1 @@\n-a\n+b\n";
+        assert!(apply_patch(root, missing).is_err());
+    }
+}
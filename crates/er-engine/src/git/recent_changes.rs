@@ -0,0 +1,103 @@
+//! Recent commits to a file on the base branch, for the Related panel —
+//! `git log -n N base -- path`, with the ones that landed after the branch
+//! under review forked flagged.
+
+use crate::command::OutputLogged;
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// A commit that touched the file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RecentCommit {
+    /// Abbreviated hash
+    pub hash: String,
+    pub author: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub subject: String,
+    /// On the base branch but not yet in `rev`: landed after the branch forked
+    pub since_fork: bool,
+}
+
+/// Up to `limit` commits touching `path` on `base`, newest first, following
+/// renames. Those `rev` doesn't contain are marked `since_fork`.
+pub fn recent_commits(
+    repo_root: &str,
+    base: &str,
+    rev: &str,
+    path: &str,
+    limit: usize,
+) -> Result<Vec<RecentCommit>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--follow",
+            &format!("-n{}", limit),
+            "--format=%h%x09%aN%x09%as%x09%s",
+            base,
+            "--",
+            path,
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut commits = parse_recent_commits(&String::from_utf8_lossy(&output.stdout));
+    let new_on_base = Command::new("git")
+        .args([
+            "log",
+            "--format=%h",
+            &format!("{}..{}", rev, base),
+            "--",
+            path,
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    for commit in &mut commits {
+        commit.since_fork = new_on_base.lines().any(|h| h.trim() == commit.hash);
+    }
+    Ok(commits)
+}
+
+/// Parse `git log --format=%h%x09%aN%x09%as%x09%s`.
+fn parse_recent_commits(output: &str) -> Vec<RecentCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            Some(RecentCommit {
+                hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next().unwrap_or_default().to_string(),
+                since_fork: false,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_lines_parse_and_short_ones_are_skipped() {
+        let commits = parse_recent_commits(
+            "a1b2c3d\tAda\t2026-10-01\tFix rounding in ledger (#41)\nbroken\n\
+             e4f5a6b\tLin\t2026-09-12\tRefactor: split\tledger\n",
+        );
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "Fix rounding in ledger (#41)");
+        assert_eq!(commits[1].subject, "Refactor: split\tledger");
+        assert!(!commits[0].since_fork);
+    }
+}
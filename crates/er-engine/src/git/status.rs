@@ -1,4 +1,6 @@
+use crate::command::OutputLogged;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 use std::time::SystemTime;
@@ -28,6 +30,16 @@ pub struct Worktree {
     pub branch: String,
 }
 
+/// A tag, newest first from [`git_tags`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// Tagger date for annotated tags, commit date otherwise (ISO 8601)
+    pub date: String,
+    /// Tag message or commit subject
+    pub subject: String,
+}
+
 /// File change status in git
 #[derive(Debug, Clone, PartialEq)]
 pub enum FileStatus {
@@ -59,7 +71,7 @@ impl FileStatus {
 pub fn get_repo_root() -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "--show-toplevel"])
-        .output()
+        .logged_output()
         .context("Failed to run git")?;
 
     if !output.status.success() {
@@ -74,7 +86,7 @@ pub fn get_repo_root_in(dir: &str) -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "--show-toplevel"])
         .current_dir(dir)
-        .output()
+        .logged_output()
         .context(format!("Failed to run git in '{}'", dir))?;
 
     if !output.status.success() {
@@ -89,7 +101,7 @@ pub fn get_repo_root_in(dir: &str) -> Result<String> {
 pub fn get_current_branch() -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
+        .logged_output()
         .context("Failed to get current branch")?;
 
     if !output.status.success() {
@@ -104,7 +116,7 @@ pub fn get_current_branch_in(repo_root: &str) -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get current branch")?;
 
     if !output.status.success() {
@@ -114,6 +126,17 @@ pub fn get_current_branch_in(repo_root: &str) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+/// `git config <key>` for a repo, `None` when unset or empty.
+pub fn git_config_value(repo_root: &str, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
 /// Auto-detect the base branch by checking upstream tracking, then falling
 /// back to common names (main, master, develop).
 #[allow(dead_code)]
@@ -146,7 +169,7 @@ fn detect_base_branch_impl(repo_root: Option<&str>) -> Result<String> {
                 cmd.env("GIT_CEILING_DIRECTORIES", parent);
             }
         }
-        let out = cmd.output().ok()?;
+        let out = cmd.logged_output().ok()?;
         if out.status.success() {
             Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
         } else {
@@ -208,6 +231,7 @@ pub fn git_diff_raw(
     repo_root: &str,
     head_ref: Option<&str>,
 ) -> Result<String> {
+    let _profile = crate::profile::scope("git diff");
     if base.starts_with('-') {
         anyhow::bail!("Invalid base branch: {}", base);
     }
@@ -233,13 +257,11 @@ pub fn git_diff_raw(
         _ => anyhow::bail!("Unknown diff mode: {}", mode),
     };
 
-    let output = Command::new("git")
-        .args(&args)
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to run git diff")?;
+    let mut cmd = Command::new("git");
+    cmd.args(&args).current_dir(repo_root);
+    let output = cmd.logged_output().context("Failed to run git diff")?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = super::decode_diff_output(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
     if std::env::var("ER_DEBUG").is_ok() {
@@ -256,7 +278,7 @@ pub fn git_diff_raw(
     }
 
     if !stderr.is_empty() && !output.status.success() {
-        anyhow::bail!("git diff failed: {}", stderr.trim());
+        return Err(crate::command::CommandFailure::from_output(&cmd, &output).into());
     }
 
     if mode == "unstaged" {
@@ -308,10 +330,10 @@ pub fn git_diff_raw_file(
     let output = Command::new("git")
         .args(&args)
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff for single file")?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = super::decode_diff_output(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
     if !stderr.is_empty() && !output.status.success() {
@@ -341,7 +363,7 @@ fn untracked_files(repo_root: &str) -> Result<Vec<String>> {
     let output = Command::new("git")
         .args(["ls-files", "--others", "--exclude-standard"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to list untracked files")?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -375,7 +397,7 @@ pub fn unmerged_files(repo_root: &str) -> Result<Vec<String>> {
     let output = Command::new("git")
         .args(["diff", "--name-only", "--diff-filter=U"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to list unmerged files")?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -392,7 +414,7 @@ pub fn is_merge_in_progress(repo_root: &str) -> bool {
     let output = Command::new("git")
         .args(["rev-parse", "--git-dir"])
         .current_dir(repo_root)
-        .output();
+        .logged_output();
     let git_dir = match output {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
         _ => return false,
@@ -431,7 +453,7 @@ pub fn git_diff_conflicts(repo_root: &str) -> Result<String> {
             "--no-ext-diff",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff --cached HEAD")?;
 
     let staged_stderr = String::from_utf8_lossy(&staged_output.stderr);
@@ -454,7 +476,7 @@ pub fn git_diff_conflicts(repo_root: &str) -> Result<String> {
                 file,
             ])
             .current_dir(repo_root)
-            .output()
+            .logged_output()
             .with_context(|| format!("Failed to run git diff HEAD for conflict file: {}", file))?;
 
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -474,7 +496,7 @@ pub fn list_worktrees(repo_root: &str) -> Result<Vec<Worktree>> {
     let output = Command::new("git")
         .args(["worktree", "list", "--porcelain"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to list worktrees")?;
 
     if !output.status.success() {
@@ -527,63 +549,54 @@ pub fn list_worktrees(repo_root: &str) -> Result<Vec<Worktree>> {
 
 /// Stage a single file
 pub fn git_stage_file(repo_root: &str, file_path: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["add", "--", file_path])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to stage file")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git add failed: {}", stderr.trim());
-    }
+    crate::command::run(
+        Command::new("git")
+            .args(["add", "--", file_path])
+            .current_dir(repo_root),
+    )?;
     Ok(())
 }
 
 /// Unstage a single file
 pub fn git_unstage_file(repo_root: &str, file_path: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["reset", "HEAD", "--", file_path])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to unstage file")?;
+    crate::command::run(
+        Command::new("git")
+            .args(["reset", "HEAD", "--", file_path])
+            .current_dir(repo_root),
+    )?;
+    Ok(())
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git reset failed: {}", stderr.trim());
-    }
+/// Unstage everything; the working tree is left alone
+pub fn git_unstage_all(repo_root: &str) -> Result<()> {
+    crate::command::run(
+        Command::new("git")
+            .args(["reset", "-q"])
+            .current_dir(repo_root),
+    )?;
     Ok(())
 }
 
 /// Stage all files
 #[allow(dead_code)]
 pub fn git_stage_all(repo_root: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["add", "-A"])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to stage all")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git add -A failed: {}", stderr.trim());
-    }
+    crate::command::run(
+        Command::new("git")
+            .args(["add", "-A"])
+            .current_dir(repo_root),
+    )?;
     Ok(())
 }
 
-/// Push current branch to remote, returning trimmed stderr output on success
-pub fn git_push(repo_root: &str) -> Result<String> {
-    let output = Command::new("git")
-        .args(["push"])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to run git push")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git push failed: {}", stderr.trim());
+/// Push current branch to remote, returning trimmed stderr output on success.
+/// `no_verify` skips the pre-push hook.
+pub fn git_push(repo_root: &str, no_verify: bool) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.arg("push").current_dir(repo_root);
+    if no_verify {
+        cmd.arg("--no-verify");
     }
-
+    let output = crate::command::run(&mut cmd)?;
     Ok(String::from_utf8_lossy(&output.stderr).trim().to_string())
 }
 
@@ -593,7 +606,7 @@ pub fn has_unpushed_commits(repo_root: &str) -> bool {
     let output = Command::new("git")
         .args(["rev-list", "--count", "@{upstream}..HEAD"])
         .current_dir(repo_root)
-        .output();
+        .logged_output();
     match output {
         Ok(out) if out.status.success() => {
             let count_str = String::from_utf8_lossy(&out.stdout);
@@ -603,17 +616,50 @@ pub fn has_unpushed_commits(repo_root: &str) -> bool {
     }
 }
 
+/// Commits only on `local` and only on `other`, as `(ahead, behind)`.
+pub fn git_ahead_behind(repo_root: &str, local: &str, other: &str) -> Result<(usize, usize)> {
+    let output = Command::new("git")
+        .args([
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{}...{}", local, other),
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git rev-list")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git rev-list failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let counts = String::from_utf8_lossy(&output.stdout);
+    let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+    Ok((counts.next().unwrap_or(0), counts.next().unwrap_or(0)))
+}
+
+/// Returns true if the index differs from HEAD (something is staged)
+pub fn has_staged_changes(repo_root: &str) -> bool {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(repo_root)
+        .logged_output();
+    matches!(output, Ok(out) if out.status.code() == Some(1))
+}
+
 /// Get raw diff output between two refs (e.g. "HEAD~1" and "HEAD")
 pub fn git_diff_raw_range(from: &str, to: &str, repo_root: &str) -> Result<String> {
+    let _profile = crate::profile::scope("git diff");
     let range = format!("{}..{}", from, to);
     let unified_arg = format!("--unified={}", super::DEFAULT_CONTEXT_LINES);
     let output = Command::new("git")
         .args(["diff", &range, &unified_arg, "--no-color", "--no-ext-diff"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff for range")?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = super::decode_diff_output(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
     if !stderr.is_empty() && !output.status.success() {
@@ -634,7 +680,7 @@ pub fn git_diff_checkout_against_base(root: &str, base: &str) -> Result<String>
     let merge_base_out = Command::new("git")
         .args(["merge-base", base, "HEAD"])
         .current_dir(root)
-        .output()
+        .logged_output()
         .context("failed to run git merge-base")?;
     if !merge_base_out.status.success() {
         let stderr = String::from_utf8_lossy(&merge_base_out.stderr);
@@ -654,16 +700,78 @@ pub fn git_diff_checkout_against_base(root: &str, base: &str) -> Result<String>
             "--no-ext-diff",
         ])
         .current_dir(root)
-        .output()
+        .logged_output()
         .context("failed to run git diff <merge-base>")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         anyhow::bail!("git diff failed: {}", stderr.trim());
     }
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = super::decode_diff_output(&output.stdout);
     append_untracked_synthetic_diffs(root, stdout)
 }
 
+/// Snapshot the reviewed side of a diff as a commit oid. With `head` the ref
+/// is resolved; without it the working tree of `root` is captured via
+/// `git stash create` (falls back to `HEAD` when the tree is clean). The oid
+/// is pinned under `pin_ref` so `git gc` keeps it. Read-only for the tree.
+pub fn git_snapshot_commit(root: &str, head: Option<&str>, pin_ref: &str) -> Result<String> {
+    let run = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .logged_output()
+            .with_context(|| format!("failed to run git {}", args[0]))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git {} failed: {}", args[0], stderr.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let oid = match head {
+        Some(head) if !head.starts_with('-') => run(&["rev-parse", "--verify", head])?,
+        Some(head) => anyhow::bail!("Invalid ref: {}", head),
+        None => {
+            let stash = run(&["stash", "create"])?;
+            if stash.is_empty() {
+                run(&["rev-parse", "--verify", "HEAD"])?
+            } else {
+                stash
+            }
+        }
+    };
+    run(&["update-ref", pin_ref, &oid])?;
+    Ok(oid)
+}
+
+/// Interdiff: raw diff from a snapshot commit to `to` (or the working tree
+/// when `None`), limited to `paths` when given.
+pub fn git_diff_since(
+    root: &str,
+    from: &str,
+    to: Option<&str>,
+    paths: &[String],
+) -> Result<String> {
+    let unified_arg = format!("--unified={}", super::DEFAULT_CONTEXT_LINES);
+    let mut args: Vec<&str> = vec!["diff", &unified_arg, "--no-color", "--no-ext-diff", from];
+    if let Some(to) = to {
+        args.push(to);
+    }
+    if !paths.is_empty() {
+        args.push("--");
+        args.extend(paths.iter().map(String::as_str));
+    }
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(root)
+        .logged_output()
+        .context("failed to run git diff <checkpoint>")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git diff failed: {}", stderr.trim());
+    }
+    Ok(super::decode_diff_output(&output.stdout))
+}
+
 /// Get raw diff output between a base branch and a target branch using the
 /// symmetric-difference range `base...branch` (everything on `branch` since
 /// it diverged from `base`). Read-only; never mutates the working tree.
@@ -672,27 +780,22 @@ pub fn git_diff_against_branch(root: &str, base: &str, branch: &str) -> Result<S
     let output = Command::new("git")
         .args(["diff", "--no-color", "--no-ext-diff", &spec])
         .current_dir(root)
-        .output()
+        .logged_output()
         .context("failed to run git diff <base>...<branch>")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         anyhow::bail!("git diff failed: {}", stderr.trim());
     }
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(super::decode_diff_output(&output.stdout))
 }
 
 /// Commit staged changes with the given message
 pub fn git_commit(repo_root: &str, message: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["commit", "-m", message])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to run git commit")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git commit failed: {}", stderr.trim());
-    }
+    crate::command::run(
+        Command::new("git")
+            .args(["commit", "-m", message])
+            .current_dir(repo_root),
+    )?;
     Ok(())
 }
 
@@ -722,7 +825,7 @@ pub fn git_log_branch(
             "--shortstat",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git log")?;
 
     if !output.status.success() {
@@ -730,7 +833,7 @@ pub fn git_log_branch(
         let output = Command::new("git")
             .args(["log", &limit_str, &skip_str, format_str, "--shortstat"])
             .current_dir(repo_root)
-            .output()
+            .logged_output()
             .context("Failed to run git log")?;
 
         return parse_git_log(&String::from_utf8_lossy(&output.stdout));
@@ -768,7 +871,7 @@ pub fn git_log_range(
             "--shortstat",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git log")?;
 
     if !output.status.success() {
@@ -776,7 +879,7 @@ pub fn git_log_range(
         let output = Command::new("git")
             .args(["log", to, &limit_str, &skip_str, format_str, "--shortstat"])
             .current_dir(repo_root)
-            .output()
+            .logged_output()
             .context("Failed to run git log")?;
         if !output.status.success() {
             return Ok(Vec::new());
@@ -794,12 +897,215 @@ pub fn git_log_head(repo_root: &str, limit: usize) -> Result<Vec<CommitInfo>> {
     let output = Command::new("git")
         .args(["log", &limit_str, format_str, "--shortstat"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git log")?;
 
     parse_git_log(&String::from_utf8_lossy(&output.stdout))
 }
 
+/// Full messages (subject and body) of `hashes`, by hash. Commits git
+/// doesn't have locally are left out.
+pub fn git_commit_messages(repo_root: &str, hashes: &[&str]) -> Result<HashMap<String, String>> {
+    if hashes.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let output = Command::new("git")
+        .args(["show", "-s", "--format=%H%x1f%B%x1e"])
+        .args(hashes)
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git show")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\x1e')
+        .filter_map(|record| record.trim_start().split_once('\x1f'))
+        .map(|(hash, message)| (hash.to_string(), message.trim_end().to_string()))
+        .collect())
+}
+
+/// Files each commit in `from..to` touched, by full hash, for the
+/// `path:` term of the History search. Falls back to logging `to` alone
+/// like [`git_log_range`].
+pub fn git_log_paths(
+    from: &str,
+    to: &str,
+    repo_root: &str,
+) -> Result<HashMap<String, Vec<String>>> {
+    let range = format!("{}..{}", from, to);
+    let log = |rev: &str| {
+        Command::new("git")
+            .args(["log", rev, "--format=%x1e%H", "--name-only"])
+            .current_dir(repo_root)
+            .logged_output()
+            .context("Failed to run git log")
+    };
+    let mut output = log(&range)?;
+    if !output.status.success() {
+        output = log(to)?;
+        if !output.status.success() {
+            return Ok(HashMap::new());
+        }
+    }
+    Ok(parse_log_paths(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git log --format=%x1e%H --name-only`.
+fn parse_log_paths(output: &str) -> HashMap<String, Vec<String>> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let hash = lines.next()?.trim();
+            if hash.is_empty() {
+                return None;
+            }
+            let paths = lines
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect();
+            Some((hash.to_string(), paths))
+        })
+        .collect()
+}
+
+/// The `limit` most recently created tags, newest first.
+pub fn git_tags(repo_root: &str, limit: usize) -> Result<Vec<Tag>> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-creatordate",
+            &format!("--count={}", limit),
+            "--format=%(refname:short)%1f%(creatordate:iso-strict)%1f%(subject)",
+            "refs/tags",
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git for-each-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git for-each-ref failed: {}", stderr.trim());
+    }
+    Ok(parse_tags(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Local and remote-tracking branch names, most recently committed first.
+/// Remotes' `HEAD` pointers are left out.
+pub fn git_branch_names(repo_root: &str) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-committerdate",
+            "--format=%(refname:short)%1f%(symref)",
+            "refs/heads",
+            "refs/remotes",
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git for-each-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git for-each-ref failed: {}", stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, symref) = line.split_once('\x1f')?;
+            (symref.is_empty() && !name.is_empty()).then(|| name.to_string())
+        })
+        .collect())
+}
+
+/// Parse `git for-each-ref --format=%(refname:short)%1f%(creatordate)%1f%(subject)`.
+fn parse_tags(output: &str) -> Vec<Tag> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\x1f');
+            let name = fields.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(Tag {
+                name: name.to_string(),
+                date: fields.next().unwrap_or("").to_string(),
+                subject: fields.next().unwrap_or("").trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The text of `path` at `rev`, `""` being the index. `None` when it
+/// isn't there or is binary.
+pub fn git_show_file(repo_root: &str, rev: &str, path: &str) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:{}", rev, path)])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git show")?;
+    if !output.status.success() || output.stdout.contains(&0) {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// The bytes of `path` at `rev` (`""` being the index), binary or not;
+/// `None` when it isn't there.
+pub fn git_show_bytes(repo_root: &str, rev: &str, path: &str) -> Result<Option<Vec<u8>>> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:{}", rev, path)])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git show")?;
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+/// Where `needle` occurs as a fixed string in the working tree, or in
+/// `rev` when given: `(path, line)`, at most `limit` of them. No match is
+/// an empty list, not an error.
+pub fn git_grep_fixed(
+    repo_root: &str,
+    rev: Option<&str>,
+    needle: &str,
+    limit: usize,
+) -> Result<Vec<(String, usize)>> {
+    let mut cmd = Command::new("git");
+    cmd.args(["grep", "-z", "-n", "-I", "--fixed-strings", "-e", needle]);
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+    let output = cmd
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run git grep")?;
+    // 1 is "no match"
+    if !output.status.success() && output.status.code() != Some(1) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git grep failed: {}", stderr.trim());
+    }
+    let mut found = parse_grep_z(&String::from_utf8_lossy(&output.stdout), rev);
+    found.truncate(limit);
+    Ok(found)
+}
+
+/// Parse `git grep -z -n`: `path\0line\0content` lines, paths prefixed
+/// with `rev:` when a revision was searched.
+fn parse_grep_z(output: &str, rev: Option<&str>) -> Vec<(String, usize)> {
+    let prefix = rev.map(|r| format!("{}:", r));
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\0');
+            let path = fields.next()?;
+            let number = fields.next()?.parse().ok()?;
+            let path = match &prefix {
+                Some(p) => path.strip_prefix(p.as_str()).unwrap_or(path),
+                None => path,
+            };
+            Some((path.to_string(), number))
+        })
+        .collect()
+}
+
 /// Parse the output of `git log --format=... --shortstat`
 ///
 /// The format string uses `\x1e` (ASCII record separator) as the field delimiter,
@@ -868,7 +1174,7 @@ pub fn diff_shortstat(repo_root: &str, staged: bool) -> (usize, usize) {
     let output = match std::process::Command::new("git")
         .args(&args)
         .current_dir(repo_root)
-        .output()
+        .logged_output()
     {
         Ok(o) if o.status.success() => o,
         _ => return (0, 0),
@@ -920,11 +1226,11 @@ pub fn git_diff_commit(hash: &str, repo_root: &str) -> Result<String> {
             "--no-ext-diff",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff for commit")?;
 
     if output.status.success() {
-        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+        return Ok(super::decode_diff_output(&output.stdout));
     }
 
     // Fallback: might be the initial commit (no parent) — use diff-tree --root
@@ -939,7 +1245,7 @@ pub fn git_diff_commit(hash: &str, repo_root: &str) -> Result<String> {
             hash,
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff-tree for root commit")?;
 
     if !output.status.success() {
@@ -947,7 +1253,7 @@ pub fn git_diff_commit(hash: &str, repo_root: &str) -> Result<String> {
         anyhow::bail!("git diff-tree failed for {hash}: {}", stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(super::decode_diff_output(&output.stdout))
 }
 
 // ── Watched Files ──
@@ -1144,7 +1450,7 @@ pub fn diff_watched_file_snapshot(
         .arg(&snapshot_path)
         .arg(&current_path)
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run git diff --no-index")?;
 
     let raw = String::from_utf8_lossy(&output.stdout).to_string();
@@ -1307,6 +1613,41 @@ mod tests {
         assert_eq!(commits[1].short_hash, "bbbb123");
     }
 
+    #[test]
+    fn parse_log_paths_groups_files_by_commit() {
+        let output = "\x1eaaaa\n\nsrc/a.rs\nsrc/b.rs\n\x1ebbbb\n\x1ecccc\n\nREADME.md\n";
+        let paths = parse_log_paths(output);
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths["aaaa"], vec!["src/a.rs", "src/b.rs"]);
+        assert!(paths["bbbb"].is_empty());
+        assert_eq!(paths["cccc"], vec!["README.md"]);
+    }
+
+    #[test]
+    fn parse_tags_keeps_order_and_subjects() {
+        let output = "v1.4.0\x1f2024-06-01T10:00:00+02:00\x1fRelease 1.4.0\n\
+                      v1.3.0\x1f2024-03-01T09:00:00Z\x1f\n\n";
+        let tags = parse_tags(output);
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "v1.4.0");
+        assert_eq!(tags[0].subject, "Release 1.4.0");
+        assert_eq!(tags[1].date, "2024-03-01T09:00:00Z");
+        assert!(tags[1].subject.is_empty());
+    }
+
+    #[test]
+    fn parse_grep_z_strips_the_revision() {
+        let output = "HEAD:src/a:b.rs\x0012\x00    let x = 1;\nHEAD:lib.rs\x003\x00let x = 1;\n";
+        assert_eq!(
+            parse_grep_z(output, Some("HEAD")),
+            vec![("src/a:b.rs".to_string(), 12), ("lib.rs".to_string(), 3)]
+        );
+        assert_eq!(
+            parse_grep_z("lib.rs\x007\x00x\n", None),
+            vec![("lib.rs".to_string(), 7)]
+        );
+    }
+
     // ── strip upstream remote logic (used in detect_base_branch) ──
 
     /// Helper to replicate the upstream branch name extraction logic from detect_base_branch_impl
@@ -1354,12 +1695,12 @@ mod tests {
         std::process::Command::new("git")
             .args(["init"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["add", "tracked.txt"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["commit", "-m", "init", "--no-gpg-sign"])
@@ -1368,7 +1709,7 @@ mod tests {
             .env("GIT_COMMITTER_NAME", "t")
             .env("GIT_COMMITTER_EMAIL", "t@t.com")
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
 
         let combined =
@@ -1377,6 +1718,43 @@ mod tests {
         assert!(combined.contains("+hello"));
     }
 
+    #[test]
+    fn snapshot_commit_captures_working_tree_and_diffs_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "t")
+                .env("GIT_AUTHOR_EMAIL", "t@t.com")
+                .env("GIT_COMMITTER_NAME", "t")
+                .env("GIT_COMMITTER_EMAIL", "t@t.com")
+                .current_dir(root)
+                .logged_output()
+                .unwrap()
+        };
+        git(&["init", "-b", "main"]);
+        std::fs::write(root.join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["commit", "-m", "init", "--no-gpg-sign"]);
+        let root_str = root.to_str().unwrap();
+
+        // Uncommitted edit is part of the snapshot
+        std::fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        let oid = git_snapshot_commit(root_str, None, "refs/er/checkpoints/test").unwrap();
+        assert!(git_diff_since(root_str, &oid, None, &[])
+            .unwrap()
+            .is_empty());
+
+        std::fs::write(root.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        let raw = git_diff_since(root_str, &oid, None, &["a.txt".to_string()]).unwrap();
+        assert!(raw.contains("+three"));
+        assert!(!raw.contains("+two"));
+
+        let pinned = git(&["rev-parse", "refs/er/checkpoints/test"]);
+        assert_eq!(String::from_utf8_lossy(&pinned.stdout).trim(), oid);
+    }
+
     #[test]
     fn git_diff_checkout_against_base_includes_untracked() {
         let dir = tempfile::tempdir().unwrap();
@@ -1385,12 +1763,12 @@ mod tests {
         std::process::Command::new("git")
             .args(["init", "-b", "main"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["add", "tracked.txt"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["commit", "-m", "init", "--no-gpg-sign"])
@@ -1399,17 +1777,17 @@ mod tests {
             .env("GIT_COMMITTER_NAME", "t")
             .env("GIT_COMMITTER_EMAIL", "t@t.com")
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["branch", "-M", "main"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::process::Command::new("git")
             .args(["checkout", "-b", "feature"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::fs::write(root.join("tracked.txt"), "v2\n").unwrap();
         std::fs::write(root.join("new.txt"), "new file\n").unwrap();
@@ -1439,7 +1817,7 @@ mod tests {
         let out = Command::new("git")
             .args(["check-ignore", "-q", path])
             .current_dir(repo_root)
-            .output();
+            .logged_output();
         matches!(out, Ok(o) if o.status.success())
     }
 
@@ -1450,7 +1828,7 @@ mod tests {
         std::process::Command::new("git")
             .args(["init"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::fs::write(root.join(".gitignore"), "ignored/\nsecret.txt\n").unwrap();
         std::fs::create_dir(root.join("ignored")).unwrap();
@@ -1493,7 +1871,7 @@ mod tests {
         std::process::Command::new("git")
             .args(["init"])
             .current_dir(root)
-            .output()
+            .logged_output()
             .unwrap();
         std::fs::write(root.join(".gitignore"), "*.txt\n").unwrap();
         std::fs::write(root.join("café.txt"), "x").unwrap();
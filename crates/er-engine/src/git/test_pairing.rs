@@ -0,0 +1,283 @@
+//! Pair changed source files with their tests.
+//!
+//! A convention is a path template (`[tests] patterns`) built from the
+//! source path: `{dir}` is its directory, `{stem}` the file name without
+//! extension, `{ext}` the extension, and `{pkg}` the directory above a `src/`
+//! component (empty when there is none), so `crates/x/src/foo.rs` gives
+//! `{pkg}/tests/{stem}.{ext}` → `crates/x/tests/foo.rs`. A source whose
+//! candidate tests are all absent from the diff is *untested*.
+
+use super::file_kind::{classify_path, FileKind};
+use std::collections::{HashMap, HashSet};
+
+/// Conventions used when `[tests] patterns` is empty.
+pub const DEFAULT_TEST_PATTERNS: &[&str] = &[
+    "{dir}/{stem}_test.{ext}",
+    "{dir}/{stem}.test.{ext}",
+    "{dir}/{stem}.spec.{ext}",
+    "{dir}/__tests__/{stem}.{ext}",
+    "{dir}/__tests__/{stem}.test.{ext}",
+    "{dir}/test_{stem}.{ext}",
+    "{pkg}/tests/{stem}.{ext}",
+    "{pkg}/tests/test_{stem}.{ext}",
+    "{pkg}/tests/{stem}_test.{ext}",
+];
+
+/// Extensions of files that are expected to have tests.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "go", "py", "rb", "js", "jsx", "mjs", "cjs", "ts", "tsx", "java", "kt", "swift", "c",
+    "cc", "cpp", "h", "hpp", "cs", "php", "scala", "ex", "exs", "dart", "vue", "svelte",
+];
+
+/// Which changed sources have changed tests, and which have none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestPairing {
+    /// Source → its tests that are also in the diff
+    pub tested: HashMap<String, Vec<String>>,
+    /// Sources with no test in the diff
+    pub untested: HashSet<String>,
+}
+
+impl TestPairing {
+    /// `Some(true)` when `path` is a source whose tests changed too,
+    /// `Some(false)` when they didn't, `None` for files without test pairing.
+    pub fn tests_changed(&self, path: &str) -> Option<bool> {
+        if self.tested.contains_key(path) {
+            Some(true)
+        } else if self.untested.contains(path) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Production code in a language that usually has tests.
+pub fn is_pairable_source(path: &str) -> bool {
+    if classify_path(path) != FileKind::Production {
+        return false;
+    }
+    let (_, _, ext) = split_path(path);
+    SOURCE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+}
+
+/// Where the tests for `source` would live under each convention.
+pub fn test_candidates(source: &str, patterns: &[String]) -> Vec<String> {
+    let (dir, stem, ext) = split_path(source);
+    if stem.is_empty() || ext.is_empty() {
+        return Vec::new();
+    }
+    let pkg = package_dir(dir);
+    let mut out: Vec<String> = Vec::new();
+    for pattern in patterns {
+        let candidate = normalize(
+            &pattern
+                .replace("{dir}", dir)
+                .replace("{pkg}", pkg)
+                .replace("{stem}", stem)
+                .replace("{ext}", ext),
+        );
+        if candidate != source && !out.contains(&candidate) {
+            out.push(candidate);
+        }
+    }
+    out
+}
+
+/// Sources `test` could be the test for — the conventions run backwards.
+/// `{pkg}` conventions can't recover the directory under `src/`, so they
+/// guess `src/` itself.
+pub fn source_candidates(test: &str, patterns: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let rooted = format!("/{}", test);
+    for pattern in patterns {
+        let Some(caps) = match_template(pattern, test).or_else(|| match_template(pattern, &rooted))
+        else {
+            continue;
+        };
+        let (Some(stem), Some(ext)) = (caps.get("stem"), caps.get("ext")) else {
+            continue;
+        };
+        let dirs: Vec<String> = match (caps.get("dir"), caps.get("pkg")) {
+            (Some(dir), _) => vec![dir.to_string()],
+            (None, Some(pkg)) => vec![format!("{}/src", pkg), pkg.to_string()],
+            (None, None) => vec![String::new()],
+        };
+        for dir in dirs {
+            let candidate = normalize(&format!("{}/{}.{}", dir, stem, ext));
+            if candidate != test && !out.contains(&candidate) {
+                out.push(candidate);
+            }
+        }
+    }
+    out
+}
+
+/// Pair every pairable source in `paths` with the tests among `paths`.
+pub fn pair_tests<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+    patterns: &[String],
+) -> TestPairing {
+    let paths: Vec<&str> = paths.into_iter().collect();
+    let changed: HashSet<&str> = paths.iter().copied().collect();
+    let mut pairing = TestPairing::default();
+    for path in paths {
+        if !is_pairable_source(path) {
+            continue;
+        }
+        let tests: Vec<String> = test_candidates(path, patterns)
+            .into_iter()
+            .filter(|t| changed.contains(t.as_str()))
+            .collect();
+        if tests.is_empty() {
+            pairing.untested.insert(path.to_string());
+        } else {
+            pairing.tested.insert(path.to_string(), tests);
+        }
+    }
+    pairing
+}
+
+/// `(dir, stem, ext)` of a repo-relative path; `dir` is empty at the root.
+fn split_path(path: &str) -> (&str, &str, &str) {
+    let (dir, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    (dir, stem, ext)
+}
+
+/// The part of `dir` above its first `src` component.
+fn package_dir(dir: &str) -> &str {
+    if dir == "src" || dir.starts_with("src/") {
+        return "";
+    }
+    match dir
+        .find("/src/")
+        .or_else(|| dir.strip_suffix("/src").map(|d| d.len()))
+    {
+        Some(end) => &dir[..end],
+        None => "",
+    }
+}
+
+/// Drop empty components left by empty placeholders.
+fn normalize(path: &str) -> String {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Match `path` against a convention, capturing its placeholders. `{dir}` and
+/// `{pkg}` may span components; `{stem}` and `{ext}` can't, and `{ext}` has
+/// no dots.
+fn match_template(template: &str, path: &str) -> Option<HashMap<&'static str, String>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let next = ["{dir}", "{pkg}", "{stem}", "{ext}"]
+            .iter()
+            .filter_map(|p| rest.find(p).map(|at| (at, *p)))
+            .min_by_key(|(at, _)| *at);
+        match next {
+            Some((0, placeholder)) => {
+                tokens.push(Token::Placeholder(&placeholder[1..placeholder.len() - 1]));
+                rest = &rest[placeholder.len()..];
+            }
+            Some((at, _)) => {
+                tokens.push(Token::Literal(&rest[..at]));
+                rest = &rest[at..];
+            }
+            None => {
+                tokens.push(Token::Literal(rest));
+                rest = "";
+            }
+        }
+    }
+    let mut caps = HashMap::new();
+    match_tokens(&tokens, path, &mut caps).then_some(caps)
+}
+
+enum Token<'a> {
+    Literal(&'a str),
+    Placeholder(&'static str),
+}
+
+fn match_tokens(tokens: &[Token], path: &str, caps: &mut HashMap<&'static str, String>) -> bool {
+    let Some((first, rest)) = tokens.split_first() else {
+        return path.is_empty();
+    };
+    match first {
+        Token::Literal(literal) => path
+            .strip_prefix(literal)
+            .is_some_and(|tail| match_tokens(rest, tail, caps)),
+        Token::Placeholder(name) => {
+            let spans_dirs = matches!(*name, "dir" | "pkg");
+            let min = if spans_dirs { 0 } else { 1 };
+            for end in (min..=path.len()).rev() {
+                if !path.is_char_boundary(end) {
+                    continue;
+                }
+                let value = &path[..end];
+                if !spans_dirs && value.contains('/') || *name == "ext" && value.contains('.') {
+                    continue;
+                }
+                if match_tokens(rest, &path[end..], caps) {
+                    caps.insert(name, value.to_string());
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_TEST_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn candidates_cover_sibling_and_package_conventions() {
+        let candidates = test_candidates("crates/x/src/foo.rs", &defaults());
+        assert!(candidates.contains(&"crates/x/src/foo_test.rs".to_string()));
+        assert!(candidates.contains(&"crates/x/tests/foo.rs".to_string()));
+        let candidates = test_candidates("main.go", &defaults());
+        assert!(candidates.contains(&"main_test.go".to_string()));
+        assert!(candidates.contains(&"tests/main.go".to_string()));
+    }
+
+    #[test]
+    fn tests_map_back_to_their_sources() {
+        let sources = source_candidates("web/__tests__/Button.test.tsx", &defaults());
+        assert!(sources.contains(&"web/Button.tsx".to_string()));
+        let sources = source_candidates("crates/x/tests/foo.rs", &defaults());
+        assert!(sources.contains(&"crates/x/src/foo.rs".to_string()));
+        let sources = source_candidates("pkg/server_test.go", &defaults());
+        assert_eq!(sources[0], "pkg/server.go");
+    }
+
+    #[test]
+    fn pairing_splits_sources_by_whether_their_tests_changed() {
+        let pairing = pair_tests(
+            [
+                "src/api.ts",
+                "src/api.test.ts",
+                "src/db.ts",
+                "README.md",
+                "Cargo.toml",
+            ],
+            &defaults(),
+        );
+        assert_eq!(pairing.tests_changed("src/api.ts"), Some(true));
+        assert_eq!(pairing.tests_changed("src/db.ts"), Some(false));
+        assert_eq!(pairing.tests_changed("src/api.test.ts"), None);
+        assert_eq!(pairing.tests_changed("README.md"), None);
+        assert_eq!(pairing.tests_changed("Cargo.toml"), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! TODO/FIXME/HACK markers the diff adds. Only added lines count — markers
+//! that were already there aren't this change's debt. With a ticket pattern
+//! (`[todos] ticket_pattern`), markers that don't reference a ticket are
+//! flagged so the review isn't ready until they do.
+
+use super::{DiffFile, LineType};
+
+/// How `scan_todos` recognises markers and tickets.
+#[derive(Debug, Clone)]
+pub struct TodoScanner {
+    /// Upper-case words, e.g. `TODO`
+    pub markers: Vec<String>,
+    /// A new marker must match this to count as tracked
+    pub ticket: Option<regex::Regex>,
+}
+
+impl Default for TodoScanner {
+    fn default() -> Self {
+        TodoScanner {
+            markers: DEFAULT_TODO_MARKERS.iter().map(|m| m.to_string()).collect(),
+            ticket: None,
+        }
+    }
+}
+
+pub const DEFAULT_TODO_MARKERS: &[&str] = &["TODO", "FIXME", "HACK"];
+
+/// A marker on an added line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoMarker {
+    pub path: String,
+    /// New-side line number
+    pub line: Option<usize>,
+    pub marker: String,
+    /// Text after the marker, trimmed of `:` and comment closers
+    pub text: String,
+    /// The ticket reference found on the line, when a pattern is set
+    pub ticket: Option<String>,
+}
+
+impl TodoMarker {
+    /// A ticket pattern is set and this marker doesn't match it.
+    pub fn needs_ticket(&self, scanner: &TodoScanner) -> bool {
+        scanner.ticket.is_some() && self.ticket.is_none()
+    }
+}
+
+/// Markers on added lines of `files`, in diff order.
+pub fn scan_todos(files: &[DiffFile], scanner: &TodoScanner) -> Vec<TodoMarker> {
+    let mut todos = Vec::new();
+    for file in files {
+        for line in file.hunks.iter().flat_map(|h| &h.lines) {
+            if line.line_type != LineType::Add {
+                continue;
+            }
+            let Some((marker, rest)) = find_marker(&line.content, &scanner.markers) else {
+                continue;
+            };
+            todos.push(TodoMarker {
+                path: file.path.clone(),
+                line: line.new_num,
+                marker: marker.to_string(),
+                text: clean_text(rest),
+                ticket: scanner
+                    .ticket
+                    .as_ref()
+                    .and_then(|re| re.find(&line.content))
+                    .map(|m| m.as_str().to_string()),
+            });
+        }
+    }
+    todos
+}
+
+/// The first marker standing as its own word (`TODO`, `TODO:`, `TODO(ab)`),
+/// and the text after it.
+fn find_marker<'a>(content: &'a str, markers: &'a [String]) -> Option<(&'a str, &'a str)> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    markers
+        .iter()
+        .filter_map(|marker| {
+            content.match_indices(marker.as_str()).find_map(|(at, _)| {
+                let before = content[..at].chars().next_back();
+                let rest = &content[at + marker.len()..];
+                let after = rest.chars().next();
+                (!before.is_some_and(is_word) && !after.is_some_and(is_word)).then_some((
+                    at,
+                    marker.as_str(),
+                    rest,
+                ))
+            })
+        })
+        .min_by_key(|(at, _, _)| *at)
+        .map(|(_, marker, rest)| (marker, rest))
+}
+
+fn clean_text(rest: &str) -> String {
+    let rest = rest.trim_start();
+    // `TODO(owner): text` keeps the owner
+    let rest = rest.strip_prefix(':').unwrap_or(rest);
+    rest.trim()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    #[test]
+    fn only_added_markers_are_listed_with_their_tickets() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                    @@ -1,2 +1,4 @@\n // TODO: old one\n-x\n+// FIXME(ops): retry ABC-12 */\n\
+                    +let todo_list = TODOS;\n+// HACK: until the API settles\n";
+        let files = parse_diff(diff);
+        let scanner = TodoScanner {
+            ticket: Some(regex::Regex::new(r"[A-Z]+-\d+").unwrap()),
+            ..TodoScanner::default()
+        };
+        let todos = scan_todos(&files, &scanner);
+        let found: Vec<(&str, Option<usize>, &str, Option<&str>)> = todos
+            .iter()
+            .map(|t| {
+                (
+                    t.marker.as_str(),
+                    t.line,
+                    t.text.as_str(),
+                    t.ticket.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("FIXME", Some(2), "(ops): retry ABC-12", Some("ABC-12")),
+                ("HACK", Some(4), "until the API settles", None),
+            ]
+        );
+        assert!(!todos[0].needs_ticket(&scanner));
+        assert!(todos[1].needs_ticket(&scanner));
+        assert!(!todos[1].needs_ticket(&TodoScanner::default()));
+    }
+}
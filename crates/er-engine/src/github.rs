@@ -1,18 +1,191 @@
 #![allow(clippy::too_many_arguments)]
 
+use crate::command::OutputLogged;
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::process::Command;
+use std::sync::Mutex;
 
 /// Parsed reference to a GitHub PR
 #[derive(Debug, Clone)]
 pub struct PrRef {
+    /// `github.com`, or a GitHub Enterprise Server host
+    pub host: String,
     pub owner: String,
     pub repo: String,
     pub number: u64,
 }
 
+// ── Host ──
+
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// GitHub Enterprise Server host every `gh` call and link goes to; `None`
+/// for github.com.
+static HOST: Mutex<Option<String>> = Mutex::new(None);
+
+/// Host `gh` talks to and links point at.
+pub fn host() -> String {
+    HOST.lock()
+        .ok()
+        .and_then(|h| h.clone())
+        .unwrap_or_else(|| DEFAULT_HOST.to_string())
+}
+
+/// Point `gh` and links at `host` (empty = github.com).
+pub fn set_host(host: &str) {
+    let host = host.trim().trim_end_matches('/');
+    let host = host
+        .strip_prefix("https://")
+        .or_else(|| host.strip_prefix("http://"))
+        .unwrap_or(host);
+    if let Ok(mut current) = HOST.lock() {
+        *current = (!host.is_empty() && host != DEFAULT_HOST).then(|| host.to_string());
+    }
+}
+
+/// `gh`, with `GH_HOST` set when the host isn't github.com. Inside a clone
+/// `gh` still follows the clone's own remote.
+pub fn gh_command() -> Command {
+    let mut cmd = Command::new("gh");
+    if let Ok(Some(host)) = HOST.lock().as_deref() {
+        cmd.env("GH_HOST", host);
+    }
+    cmd
+}
+
+/// `https://<host>/<path>`.
+pub fn web_url(path: &str) -> String {
+    format!("https://{}/{}", host(), path.trim_start_matches('/'))
+}
+
+/// Host of a git remote URL (`git@ghe.corp:o/r.git`, `https://ghe.corp/o/r`).
+pub fn remote_host(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.0,
+        None => url.split_once(':')?.0,
+    };
+    // Drop `user@` and `:port`
+    let host = rest.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// Hosts `gh auth login` has credentials for, from its `hosts.yml`.
+pub fn gh_known_hosts() -> Vec<String> {
+    let dir = std::env::var_os("GH_CONFIG_DIR")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config").join("gh")));
+    let Some(content) = dir.and_then(|d| std::fs::read_to_string(d.join("hosts.yml")).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|l| !l.starts_with([' ', '\t', '#']))
+        .filter_map(|l| l.trim_end().strip_suffix(':'))
+        .map(|h| h.trim_matches('"').to_ascii_lowercase())
+        .collect()
+}
+
+/// The host for a clone: `configured` (`[github] host`) when set, else the
+/// forge remote's host when `gh` is logged in to it, else github.com.
+pub fn resolve_host(repo_root: &str, configured: &str) -> String {
+    if !configured.trim().is_empty() {
+        return configured.trim().to_string();
+    }
+    let origin = remote_url(repo_root, &forge_remote(repo_root)).and_then(|url| remote_host(&url));
+    match origin {
+        Some(host) if host != DEFAULT_HOST && gh_known_hosts().contains(&host) => host,
+        _ => DEFAULT_HOST.to_string(),
+    }
+}
+
+// ── Remotes ──
+
+/// Global `[github] remote`; a repo's `.er-config.toml` overrides it.
+static REMOTE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Remote preferred in fork clones, where `origin` is the fork.
+const UPSTREAM_REMOTE: &str = "upstream";
+
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// Set the global `[github] remote` (empty = detect).
+pub fn set_remote(remote: &str) {
+    let remote = remote.trim();
+    if let Ok(mut current) = REMOTE.lock() {
+        *current = (!remote.is_empty()).then(|| remote.to_string());
+    }
+}
+
+/// `(name, fetch url)` for every remote of the clone.
+pub fn git_remotes(repo_root: &str) -> Vec<(String, String)> {
+    let Ok(out) = Command::new("git")
+        .args(["remote", "-v"])
+        .current_dir(repo_root)
+        .logged_output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter(|l| l.ends_with("(fetch)"))
+        .filter_map(|l| {
+            let mut parts = l.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// URL of `remote`, `None` when the clone doesn't have it.
+pub fn remote_url(repo_root: &str, remote: &str) -> Option<String> {
+    let out = Command::new("git")
+        .args(["remote", "get-url", remote])
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Which of `remotes` the PRs live on: `configured` when the clone has it,
+/// else `upstream`, else `origin`, else the only remote.
+pub fn pick_forge_remote(remotes: &[String], configured: Option<&str>) -> Option<String> {
+    let has = |name: &str| remotes.iter().any(|r| r == name);
+    configured
+        .filter(|c| has(c))
+        .or_else(|| {
+            [UPSTREAM_REMOTE, DEFAULT_REMOTE]
+                .into_iter()
+                .find(|r| has(r))
+        })
+        .map(str::to_string)
+        .or_else(|| (remotes.len() == 1).then(|| remotes[0].clone()))
+}
+
+/// The remote PR heads and bases are fetched from (`[github] remote`,
+/// detected when unset). Falls back to `origin`.
+pub fn forge_remote(repo_root: &str) -> String {
+    let configured = crate::config::load_repo_github_remote(repo_root)
+        .or_else(|| REMOTE.lock().ok().and_then(|r| r.clone()));
+    let names: Vec<String> = git_remotes(repo_root)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    pick_forge_remote(&names, configured.as_deref()).unwrap_or_else(|| DEFAULT_REMOTE.to_string())
+}
+
+/// `base` without its `<remote>/` prefix (`upstream/main` → `main`).
+pub fn strip_remote_prefix<'a>(repo_root: &str, base: &'a str) -> &'a str {
+    let remote = forge_remote(repo_root);
+    base.strip_prefix(&format!("{}/", remote))
+        .or_else(|| base.strip_prefix("origin/"))
+        .unwrap_or(base)
+}
+
 /// Fetched PR overview data for the PrOverview panel
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PrOverviewData {
@@ -26,6 +199,14 @@ pub struct PrOverviewData {
     pub head_branch: String,
     pub checks: Vec<CiCheck>,
     pub reviewers: Vec<ReviewerStatus>,
+    /// When the PR was opened (`createdAt`, ISO 8601)
+    #[serde(default)]
+    pub created_at: String,
+    /// Commit the PR head points at on GitHub (`headRefOid`)
+    #[serde(default)]
+    pub head_oid: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -115,10 +296,10 @@ pub fn gh_pr_commits(
     number: u64,
     limit: usize,
 ) -> Result<Vec<crate::git::CommitInfo>> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["pr", "view", &number.to_string(), "--json", "commits"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run gh pr view for commits")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -172,6 +353,9 @@ pub struct GitHubComment {
     /// GitHub marks a comment outdated when the lines it was left on have since changed.
     #[serde(default)]
     pub outdated: bool,
+    /// "line" or "file"; file comments have no line.
+    #[serde(default)]
+    pub subject_type: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -189,15 +373,31 @@ struct CreateCommentResponse {
 /// Supports: https://github.com/owner/repo/pull/42
 /// Also handles: trailing /files, /commits, /checks, etc.
 /// Also handles: github.com/owner/repo/pull/42 (no scheme)
+/// Also handles: https://ghe.example.com/owner/repo/pull/42 (Enterprise
+/// Server: the configured host or one `gh` is logged in to; the scheme is
+/// required for hosts other than github.com)
 pub fn parse_github_pr_url(url: &str) -> Option<PrRef> {
+    let mut hosts = gh_known_hosts();
+    hosts.push(host());
+    parse_pr_url_on(url, &hosts)
+}
+
+/// [`parse_github_pr_url`] with `hosts` accepted besides github.com.
+fn parse_pr_url_on(url: &str, hosts: &[String]) -> Option<PrRef> {
     // Strip scheme
-    let stripped = url
+    let (stripped, had_scheme) = match url
         .strip_prefix("https://")
         .or_else(|| url.strip_prefix("http://"))
-        .unwrap_or(url);
+    {
+        Some(rest) => (rest, true),
+        None => (url, false),
+    };
 
-    // Must start with github.com
-    let rest = stripped.strip_prefix("github.com/")?;
+    let (host, rest) = stripped.split_once('/')?;
+    let host = host.to_ascii_lowercase();
+    if host != DEFAULT_HOST && (!had_scheme || !hosts.contains(&host)) {
+        return None;
+    }
 
     // Split: owner/repo/pull/number[/...]
     let parts: Vec<&str> = rest.split('/').collect();
@@ -217,6 +417,7 @@ pub fn parse_github_pr_url(url: &str) -> Option<PrRef> {
     }
 
     Some(PrRef {
+        host,
         owner,
         repo,
         number,
@@ -236,9 +437,9 @@ fn gh_spawn_context(err: std::io::Error) -> anyhow::Error {
 
 /// Check if `gh` CLI is installed and authenticated
 pub fn ensure_gh_installed() -> Result<()> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["--version"])
-        .output()
+        .logged_output()
         .context("GitHub CLI (gh) is not installed. Install it: https://cli.github.com")?;
 
     if !output.status.success() {
@@ -246,9 +447,9 @@ pub fn ensure_gh_installed() -> Result<()> {
     }
 
     // Check auth
-    let auth = Command::new("gh")
+    let auth = gh_command()
         .args(["auth", "status"])
-        .output()
+        .logged_output()
         .context("Failed to check gh auth status")?;
 
     if !auth.status.success() {
@@ -260,7 +461,7 @@ pub fn ensure_gh_installed() -> Result<()> {
 
 /// Get the base branch for a PR using `gh pr view`
 pub fn gh_pr_base_branch(pr_number: u64, repo_root: &str) -> Result<String> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -271,7 +472,7 @@ pub fn gh_pr_base_branch(pr_number: u64, repo_root: &str) -> Result<String> {
             ".baseRefName",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get PR base branch")?;
 
     if !output.status.success() {
@@ -289,7 +490,7 @@ pub fn gh_pr_base_branch(pr_number: u64, repo_root: &str) -> Result<String> {
 
 /// Get the base and head branch names for a PR with a single `gh pr view` call.
 pub fn gh_pr_branch_names(pr_number: u64, repo_root: &str) -> Result<(String, String)> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -300,7 +501,7 @@ pub fn gh_pr_branch_names(pr_number: u64, repo_root: &str) -> Result<(String, St
             r#"[.baseRefName, .headRefName] | @tsv"#,
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get PR branch names")?;
 
     if !output.status.success() {
@@ -327,16 +528,20 @@ pub fn gh_pr_branch_names(pr_number: u64, repo_root: &str) -> Result<(String, St
 /// leaving the stale `refs/er/pr/<n>/head` and breaking re-entry into PR diff.
 /// The `+` only affects this `refs/er/` destination ref — never local branches
 /// (`refs/heads/`), remote-tracking refs, HEAD, or the working tree.
+///
+/// Fetched from the forge remote, which has `pull/<n>/head` even when the
+/// head branch lives on a fork.
 pub fn fetch_pr_head(number: u64, root: &str) -> Result<String> {
     let ref_name = format!("refs/er/pr/{}/head", number);
+    let remote = forge_remote(root);
     let output = std::process::Command::new("git")
         .args([
             "fetch",
-            "origin",
+            &remote,
             &format!("+pull/{}/head:{}", number, ref_name),
         ])
         .current_dir(root)
-        .output()
+        .logged_output()
         .context("failed to run git fetch for PR head")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -353,11 +558,53 @@ pub fn is_no_upstream_to_refresh(err: &anyhow::Error) -> bool {
         .any(|cause| cause.to_string().contains("has no upstream to refresh"))
 }
 
+/// Whether GitHub turned a call away for rate limiting — the primary limit,
+/// or the secondary "too many requests in a row" one that bulk comment
+/// pushes trip.
+pub fn is_rate_limited(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        let msg = cause.to_string().to_lowercase();
+        msg.contains("rate limit")
+            || msg.contains("abuse detection")
+            || msg.contains("http 429")
+            || msg.contains("too many requests")
+    })
+}
+
+/// Waits between retries of a rate-limited call.
+pub const RATE_LIMIT_BACKOFF: &[std::time::Duration] = &[
+    std::time::Duration::from_secs(2),
+    std::time::Duration::from_secs(8),
+];
+
+/// Run `call`, retrying after each of `waits` while GitHub rate-limits it.
+/// `on_wait` hears about every pause before it starts. The last error is
+/// returned once the waits run out.
+pub fn with_rate_limit_backoff<T>(
+    waits: &[std::time::Duration],
+    mut call: impl FnMut() -> Result<T>,
+    mut on_wait: impl FnMut(std::time::Duration),
+) -> Result<T> {
+    let mut waits = waits.iter();
+    loop {
+        match call() {
+            Err(e) if is_rate_limited(&e) => match waits.next() {
+                Some(&wait) => {
+                    on_wait(wait);
+                    std::thread::sleep(wait);
+                }
+                None => return Err(e),
+            },
+            result => return result,
+        }
+    }
+}
+
 pub fn ref_exists_locally(repo_root: &str, ref_name: &str) -> bool {
     let out = Command::new("git")
         .args(["rev-parse", "--verify", ref_name])
         .current_dir(repo_root)
-        .output();
+        .logged_output();
     out.map(|o| o.status.success()).unwrap_or(false)
 }
 
@@ -366,7 +613,7 @@ pub fn rev_parse_oid(repo_root: &str, ref_name: &str) -> Option<String> {
     let out = Command::new("git")
         .args(["rev-parse", "--verify", ref_name])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .ok()?;
     if !out.status.success() {
         return None;
@@ -379,26 +626,28 @@ pub fn rev_parse_oid(repo_root: &str, ref_name: &str) -> Option<String> {
     }
 }
 
-/// Force-fetch a base branch from origin, updating `origin/<base>` even if it already exists.
-/// Returns `origin/<base_branch>` on success.
+/// Force-fetch a base branch from the forge remote, updating `<remote>/<base>`
+/// even if it already exists. Returns `<remote>/<base_branch>` on success.
 pub fn fetch_base_branch_ref(repo_root: &str, base_branch: &str) -> Result<String> {
-    let remote_ref = format!("origin/{}", base_branch);
+    let remote = forge_remote(repo_root);
+    let remote_ref = format!("{}/{}", remote, base_branch);
     let refspec = format!(
-        "+refs/heads/{}:refs/remotes/origin/{}",
-        base_branch, base_branch
+        "+refs/heads/{}:refs/remotes/{}/{}",
+        base_branch, remote, base_branch
     );
 
     let fetch = std::process::Command::new("git")
-        .args(["fetch", "origin", &refspec])
+        .args(["fetch", &remote, &refspec])
         .current_dir(repo_root)
-        .output()
-        .context("Failed to fetch base branch from origin")?;
+        .logged_output()
+        .with_context(|| format!("Failed to fetch base branch from {}", remote))?;
 
     if !fetch.status.success() {
         let stderr = String::from_utf8_lossy(&fetch.stderr);
         anyhow::bail!(
-            "Failed to fetch base branch '{}' from origin: {}",
+            "Failed to fetch base branch '{}' from {}: {}",
             base_branch,
+            remote,
             stderr.trim()
         );
     }
@@ -406,7 +655,7 @@ pub fn fetch_base_branch_ref(repo_root: &str, base_branch: &str) -> Result<Strin
     let verify = std::process::Command::new("git")
         .args(["rev-parse", "--verify", &remote_ref])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to verify fetched base branch")?;
 
     if !verify.status.success() {
@@ -420,9 +669,62 @@ pub fn fetch_base_branch_ref(repo_root: &str, base_branch: &str) -> Result<Strin
     Ok(remote_ref)
 }
 
+/// Where a PR's head branch lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrHead {
+    pub branch: String,
+    /// Owner of the fork the head branch is on; `None` for same-repo PRs
+    pub fork_owner: Option<String>,
+}
+
+impl PrHead {
+    /// `branch`, or `owner:branch` for a fork (gh's notation), so a fork's
+    /// `main` isn't mistaken for the local `main`.
+    pub fn label(&self) -> String {
+        match &self.fork_owner {
+            Some(owner) => format!("{}:{}", owner, self.branch),
+            None => self.branch.clone(),
+        }
+    }
+}
+
+/// Head branch of a PR and the fork it lives on, via gh CLI.
+pub fn gh_pr_head(number: u64, root: &str) -> Result<PrHead> {
+    let output = gh_command()
+        .args([
+            "pr",
+            "view",
+            &number.to_string(),
+            "--json",
+            "headRefName,headRepositoryOwner,isCrossRepository",
+            "--jq",
+            r#"[.headRefName, .headRepositoryOwner.login // "", .isCrossRepository] | @tsv"#,
+        ])
+        .current_dir(root)
+        .logged_output()
+        .context("failed to run gh pr view")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("gh pr view failed: {}", stderr.trim());
+    }
+    Ok(parse_pr_head(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `headRefName<TAB>owner<TAB>isCrossRepository` from [`gh_pr_head`].
+fn parse_pr_head(tsv: &str) -> PrHead {
+    let mut parts = tsv.trim_end_matches('\n').split('\t');
+    let branch = parts.next().unwrap_or("").trim().to_string();
+    let owner = parts.next().unwrap_or("").trim().to_string();
+    let cross = parts.next().is_some_and(|c| c.trim() == "true");
+    PrHead {
+        branch,
+        fork_owner: (cross && !owner.is_empty()).then_some(owner),
+    }
+}
+
 /// Get the head branch name of a PR via gh CLI
 pub fn gh_pr_head_branch_name(number: u64, root: &str) -> Result<String> {
-    let output = std::process::Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -433,7 +735,7 @@ pub fn gh_pr_head_branch_name(number: u64, root: &str) -> Result<String> {
             ".headRefName",
         ])
         .current_dir(root)
-        .output()
+        .logged_output()
         .context("failed to run gh pr view")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -442,22 +744,21 @@ pub fn gh_pr_head_branch_name(number: u64, root: &str) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-/// Verify the local repo's remote matches the PR's owner/repo
+/// Verify one of the local repo's remotes matches the PR's owner/repo. In a
+/// fork clone that's usually `upstream`, not `origin`.
 pub fn verify_remote_matches(repo_root: &str, pr_ref: &PrRef) -> Result<()> {
-    let output = Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to get git remote URL")?;
-
-    if !output.status.success() {
+    let remotes = git_remotes(repo_root);
+    if remotes.is_empty() {
         // No remote — can't verify, let gh handle it
         return Ok(());
     }
 
-    let remote = String::from_utf8_lossy(&output.stdout).trim().to_string();
     let expected = format!("{}/{}", pr_ref.owner, pr_ref.repo);
-    if !remote_matches_repo(&remote, &pr_ref.owner, &pr_ref.repo) {
+    if !remotes
+        .iter()
+        .any(|(_, url)| remote_matches_repo(url, &pr_ref.owner, &pr_ref.repo))
+    {
+        let remote = remote_url(repo_root, &forge_remote(repo_root)).unwrap_or_default();
         anyhow::bail!(
             "PR is for {} but current repo remote is '{}'. Navigate to the correct repo first.",
             expected,
@@ -469,8 +770,8 @@ pub fn verify_remote_matches(repo_root: &str, pr_ref: &PrRef) -> Result<()> {
 }
 
 /// Ensure a remote ref is available locally by fetching if needed.
-/// Returns the ref name that actually resolves — may be `origin/<base>` if
-/// no local branch exists.
+/// Returns the ref name that actually resolves — may be `<remote>/<base>`
+/// (the forge remote) if no local branch exists.
 pub fn ensure_base_ref_available(repo_root: &str, base_branch: &str) -> Result<String> {
     let base_branch = base_branch.trim();
     let base_branch = if base_branch.is_empty() {
@@ -484,7 +785,7 @@ pub fn ensure_base_ref_available(repo_root: &str, base_branch: &str) -> Result<S
         let out = Command::new("git")
             .args(["rev-parse", "--verify", refname])
             .current_dir(repo_root)
-            .output()
+            .logged_output()
             .context("Failed to check ref")?;
         Ok(out.status.success())
     };
@@ -495,28 +796,30 @@ pub fn ensure_base_ref_available(repo_root: &str, base_branch: &str) -> Result<S
     }
 
     // Remote-tracking ref exists — use it (no fetch needed)
-    let remote_ref = format!("origin/{}", base_branch);
+    let remote = forge_remote(repo_root);
+    let remote_ref = format!("{}/{}", remote, base_branch);
     if rev_parse_ok(&remote_ref)? {
         return Ok(remote_ref);
     }
 
-    // Fetch from origin
+    // Fetch from the forge remote
     let fetch = Command::new("git")
-        .args(["fetch", "origin", base_branch.as_str()])
+        .args(["fetch", &remote, base_branch.as_str()])
         .current_dir(repo_root)
-        .output()
-        .context("Failed to fetch base branch from origin")?;
+        .logged_output()
+        .with_context(|| format!("Failed to fetch base branch from {}", remote))?;
 
     if !fetch.status.success() {
         let stderr = String::from_utf8_lossy(&fetch.stderr);
         anyhow::bail!(
-            "Base branch '{}' not found locally or on origin: {}",
+            "Base branch '{}' not found locally or on {}: {}",
             base_branch,
+            remote,
             stderr.trim()
         );
     }
 
-    // After fetch, origin/<base> should exist
+    // After fetch, <remote>/<base> should exist
     if rev_parse_ok(&remote_ref)? {
         return Ok(remote_ref);
     }
@@ -536,7 +839,7 @@ pub fn ensure_base_ref_available(repo_root: &str, base_branch: &str) -> Result<S
 /// Silently returns None if gh is unavailable, not authenticated, or no PR exists.
 pub fn gh_pr_for_current_branch(repo_root: &str) -> Option<(u64, String)> {
     // Use --jq to extract "number<tab>baseRefName" — robust against JSON formatting
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -546,7 +849,7 @@ pub fn gh_pr_for_current_branch(repo_root: &str) -> Option<(u64, String)> {
             r#"[.number, .baseRefName] | @tsv"#,
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .ok()?;
 
     if !output.status.success() {
@@ -570,13 +873,13 @@ pub fn is_github_pr_url(s: &str) -> bool {
     parse_github_pr_url(s).is_some()
 }
 
-/// Get PR info (owner, repo, number) for the current branch
+/// Get PR info (owner, repo, number) for the current branch. Owner/repo are
+/// the PR's base repo — for a fork PR, not where the head branch lives.
 pub fn get_pr_info(repo_root: &str) -> Result<(String, String, u64)> {
-    // Try `gh pr view --json number,headRepository,baseRefName`
-    let output = Command::new("gh")
-        .args(["pr", "view", "--json", "number,headRepository"])
+    let output = gh_command()
+        .args(["pr", "view", "--json", "number,url"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get PR info")?;
 
     if !output.status.success() {
@@ -589,57 +892,36 @@ pub fn get_pr_info(repo_root: &str) -> Result<(String, String, u64)> {
 
     let number = v["number"].as_u64().context("Missing PR number")?;
 
-    // headRepository can be absent (e.g. a deleted fork); the empty values are
-    // detected below and fall back to the git remote.
-    let owner = v["headRepository"]["owner"]["login"].as_str().unwrap_or("");
-    let repo_name = v["headRepository"]["name"].as_str().unwrap_or("");
-
-    // If headRepository is missing, try to get owner/repo from remote
-    if owner.is_empty() || repo_name.is_empty() {
-        let remote_output = Command::new("git")
-            .args(["remote", "get-url", "origin"])
-            .current_dir(repo_root)
-            .output()?;
-        let remote = String::from_utf8_lossy(&remote_output.stdout)
-            .trim()
-            .to_string();
-        // Parse owner/repo from remote URL
-        let (o, r) = parse_owner_repo_from_remote(&remote)?;
-        return Ok((o, r, number));
+    // The PR URL names the base repo; without it, fall back to the git remote
+    let url = v["url"].as_str().unwrap_or("");
+    if let Some(pr) = parse_pr_url_on(url, &[host()]) {
+        return Ok((pr.owner, pr.repo, number));
     }
-
-    Ok((owner.to_string(), repo_name.to_string(), number))
+    let remote = remote_url(repo_root, &forge_remote(repo_root)).unwrap_or_default();
+    let (owner, repo) = parse_owner_repo_from_remote(&remote)?;
+    Ok((owner, repo, number))
 }
 
 /// Parse owner/repo from a git remote URL
 fn parse_owner_repo_from_remote(remote: &str) -> Result<(String, String)> {
     // SSH: git@github.com:owner/repo.git
-    // HTTPS: https://github.com/owner/repo.git
-    let stripped = remote
-        .strip_prefix("https://github.com/")
-        .or_else(|| remote.strip_prefix("http://github.com/"))
-        .or_else(|| remote.strip_prefix("git@github.com:"))
-        .unwrap_or(remote);
-
-    let stripped = stripped.strip_suffix(".git").unwrap_or(stripped);
-    let parts: Vec<&str> = stripped.split('/').collect();
-    if parts.len() >= 2 {
-        Ok((parts[0].to_string(), parts[1].to_string()))
-    } else {
-        anyhow::bail!("Cannot parse owner/repo from remote: {}", remote);
-    }
+    // HTTPS: https://github.com/owner/repo.git (or an Enterprise host)
+    parse_remote_url(remote)
+        .ok_or_else(|| anyhow::anyhow!("Cannot parse owner/repo from remote: {}", remote))
 }
 
-/// Get GitHub owner/repo from the repository's `origin` remote.
+/// Get GitHub owner/repo from the repository's forge remote (`origin`, or
+/// `upstream` in a fork clone).
 pub fn get_repo_info(repo_root: &str) -> Result<(String, String)> {
+    let remote = forge_remote(repo_root);
     let remote_output = Command::new("git")
-        .args(["remote", "get-url", "origin"])
+        .args(["remote", "get-url", &remote])
         .current_dir(repo_root)
-        .output()
-        .context("Failed to get origin remote")?;
+        .logged_output()
+        .with_context(|| format!("Failed to get {} remote", remote))?;
     if !remote_output.status.success() {
         let stderr = String::from_utf8_lossy(&remote_output.stderr);
-        anyhow::bail!("Failed to get origin remote: {}", stderr.trim());
+        anyhow::bail!("Failed to get {} remote: {}", remote, stderr.trim());
     }
     let remote = String::from_utf8_lossy(&remote_output.stdout)
         .trim()
@@ -654,20 +936,15 @@ pub fn gh_pr_comments(
     pr: u64,
     repo_root: &str,
 ) -> Result<Vec<GitHubComment>> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{}/{}/pulls/{}/comments", owner, repo, pr),
-            "--paginate",
-        ])
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to fetch PR comments")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to fetch PR comments: {}", stderr.trim());
-    }
+    let output = crate::command::run(
+        gh_command()
+            .args([
+                "api",
+                &format!("repos/{}/{}/pulls/{}/comments", owner, repo, pr),
+                "--paginate",
+            ])
+            .current_dir(repo_root),
+    )?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     // gh api --paginate concatenates JSON arrays: [...][...]
@@ -744,18 +1021,21 @@ pub fn gh_pr_push_comment(
     repo_root: &str,
 ) -> Result<u64> {
     // Get the latest commit SHA for the PR (required for review comments)
-    let sha_output = Command::new("gh")
+    // `--repo`: in a fork clone gh's default repo may be the fork
+    let sha_output = gh_command()
         .args([
             "pr",
             "view",
             &pr.to_string(),
+            "--repo",
+            &format!("{}/{}", owner, repo),
             "--json",
             "headRefOid",
             "--jq",
             ".headRefOid",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get PR head SHA")?;
 
     if !sha_output.status.success() {
@@ -772,7 +1052,7 @@ pub fn gh_pr_push_comment(
 
     let end = line_end.unwrap_or(line_start);
     let field_args = review_comment_field_args(path, line_start, end, side, body, &commit_id);
-    let mut cmd = Command::new("gh");
+    let mut cmd = gh_command();
     cmd.args([
         "api",
         "-X",
@@ -786,15 +1066,7 @@ pub fn gh_pr_push_comment(
             cmd.arg("-f").arg(arg);
         }
     }
-    let output = cmd
-        .current_dir(repo_root)
-        .output()
-        .context("Failed to push comment to GitHub")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to push comment: {}", stderr.trim());
-    }
+    let output = crate::command::run(cmd.current_dir(repo_root))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let resp: CreateCommentResponse =
@@ -812,7 +1084,7 @@ pub fn gh_pr_reply_comment(
     body: &str,
     repo_root: &str,
 ) -> Result<u64> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -824,7 +1096,7 @@ pub fn gh_pr_reply_comment(
             &format!("in_reply_to={}", in_reply_to),
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to push reply to GitHub")?;
 
     if !output.status.success() {
@@ -847,7 +1119,7 @@ pub fn gh_pr_update_review_comment(
     body: &str,
     repo_root: &str,
 ) -> Result<()> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -857,7 +1129,7 @@ pub fn gh_pr_update_review_comment(
             &format!("body={}", body),
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to update comment on GitHub")?;
 
     if !output.status.success() {
@@ -875,7 +1147,7 @@ pub fn gh_pr_delete_comment(
     comment_id: u64,
     repo_root: &str,
 ) -> Result<()> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -883,7 +1155,7 @@ pub fn gh_pr_delete_comment(
             &format!("repos/{}/{}/pulls/comments/{}", owner, repo, comment_id),
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to delete comment from GitHub")?;
 
     if !output.status.success() {
@@ -899,10 +1171,10 @@ pub fn gh_pr_delete_comment(
 
 /// Update the PR body with the given content via `gh pr edit --body`
 pub fn gh_pr_edit_body(repo_root: &str, body: &str) -> Result<()> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["pr", "edit", "--body", body])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to update PR body")?;
 
     if !output.status.success() {
@@ -920,14 +1192,14 @@ pub fn gh_pr_approve(
     remote_repo: Option<&str>,
     pr_number: Option<u64>,
 ) -> Result<()> {
-    let mut cmd = Command::new("gh");
+    let mut cmd = gh_command();
     cmd.args(["pr", "review", "--approve"]);
     if let (Some(slug), Some(n)) = (remote_repo, pr_number) {
         cmd.args(["--repo", slug, &n.to_string()]);
     }
     cmd.current_dir(repo_root);
     let output = cmd
-        .output()
+        .logged_output()
         .context("Failed to run gh pr review --approve")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -966,12 +1238,12 @@ fn gh_pr_overview_impl(
     }
     args.extend_from_slice(&[
         "--json",
-        "number,title,body,state,author,url,baseRefName,headRefName,reviews",
+        "number,title,body,state,author,url,baseRefName,headRefName,headRefOid,reviews,createdAt,labels",
     ]);
-    let view_output = Command::new("gh")
+    let view_output = gh_command()
         .args(&args)
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .ok()?;
 
     if !view_output.status.success() {
@@ -989,6 +1261,9 @@ fn gh_pr_overview_impl(
     let url = v["url"].as_str().unwrap_or("").to_string();
     let base_branch = v["baseRefName"].as_str().unwrap_or("").to_string();
     let head_branch = v["headRefName"].as_str().unwrap_or("").to_string();
+    let created_at = v["createdAt"].as_str().unwrap_or("").to_string();
+    let head_oid = v["headRefOid"].as_str().unwrap_or("").to_string();
+    let labels = label_names(&v["labels"]);
 
     let reviewers: Vec<ReviewerStatus> = if let Some(reviews_arr) = v["reviews"].as_array() {
         deduplicate_reviewers(reviews_arr)
@@ -1016,15 +1291,30 @@ fn gh_pr_overview_impl(
         head_branch,
         checks,
         reviewers,
+        created_at,
+        head_oid,
+        labels,
     })
 }
 
+/// Names from a `labels` array of `{ "name": … }` objects.
+fn label_names(labels: &serde_json::Value) -> Vec<String> {
+    labels
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|l| l["name"].as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Fetch CI check runs for the current PR
 fn gh_pr_checks_data(repo_root: &str) -> Result<Vec<CiCheck>> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["pr", "checks", "--json", "name,state,bucket"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to run gh pr checks")?;
 
     if !output.status.success() {
@@ -1069,7 +1359,7 @@ const REMOTE_PR_MAX_LINE_CHANGES: u64 = 5_000_000;
 /// Quick size check via `gh pr view` so we do not clone multi‑GB repos for hopeless PRs.
 pub fn gh_pr_size_check_remote(owner: &str, repo: &str, number: u64) -> Result<()> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -1081,7 +1371,7 @@ pub fn gh_pr_size_check_remote(owner: &str, repo: &str, number: u64) -> Result<(
             "--jq",
             r#"{additions: .additions, deletions: .deletions, files: .changedFiles}"#,
         ])
-        .output()
+        .logged_output()
         .context("Failed to check PR size")?;
 
     if !output.status.success() {
@@ -1116,11 +1406,12 @@ pub fn gh_pr_size_check_remote(owner: &str, repo: &str, number: u64) -> Result<(
 /// Falls back to shallow clone + local git diff when the PR exceeds GitHub's
 /// API line limit (HTTP 406 / diff_too_large).
 pub fn gh_pr_diff_remote(owner: &str, repo: &str, number: u64) -> Result<String> {
+    let _profile = crate::profile::scope("gh pr diff");
     gh_pr_size_check_remote(owner, repo, number)?;
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["pr", "diff", &number.to_string(), "--repo", &repo_slug])
-        .output()
+        .logged_output()
         .map_err(gh_spawn_context)?;
 
     if !output.status.success() {
@@ -1131,7 +1422,7 @@ pub fn gh_pr_diff_remote(owner: &str, repo: &str, number: u64) -> Result<String>
         anyhow::bail!("Failed to get PR diff: {}", stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(crate::git::decode_diff_output(&output.stdout))
 }
 
 /// Get raw unified diff for a PR using the local repo context.
@@ -1142,10 +1433,11 @@ pub fn gh_pr_diff_remote(owner: &str, repo: &str, number: u64) -> Result<String>
 /// every edge case, and using `gh pr diff` keeps Easy Review aligned with the
 /// Files changed view.
 pub fn gh_pr_diff(pr_number: u64, repo_root: &str) -> Result<String> {
-    let output = Command::new("gh")
+    let _profile = crate::profile::scope("gh pr diff");
+    let output = gh_command()
         .args(["pr", "diff", &pr_number.to_string()])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .map_err(gh_spawn_context)?;
 
     if !output.status.success() {
@@ -1153,14 +1445,29 @@ pub fn gh_pr_diff(pr_number: u64, repo_root: &str) -> Result<String> {
         anyhow::bail!("Failed to get PR diff: {}", stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(crate::git::decode_diff_output(&output.stdout))
+}
+
+/// The PR's diff, parsed — what GitHub anchors review comments against.
+/// `repo_root` is the local clone; `None` asks GitHub by `owner/repo`.
+pub fn gh_pr_diff_files(
+    owner: &str,
+    repo: &str,
+    pr: u64,
+    repo_root: Option<&str>,
+) -> Result<Vec<crate::git::DiffFile>> {
+    let raw = match repo_root {
+        Some(root) => gh_pr_diff(pr, root)?,
+        None => gh_pr_diff_remote(owner, repo, pr)?,
+    };
+    Ok(crate::git::parse_diff(&raw))
 }
 
 /// Base and head commit SHAs for a PR (from `gh pr view`). Works for merged PRs and
 /// deleted head branches — unlike branch names, which may no longer exist on origin.
 pub fn gh_pr_commit_shas_remote(owner: &str, repo: &str, number: u64) -> Result<(String, String)> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -1172,7 +1479,7 @@ pub fn gh_pr_commit_shas_remote(owner: &str, repo: &str, number: u64) -> Result<
             "--jq",
             r#"[.baseRefOid, .headRefOid] | @tsv"#,
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR commit SHAs")?;
 
     if !output.status.success() {
@@ -1198,7 +1505,7 @@ pub fn gh_pr_commit_shas_remote(owner: &str, repo: &str, number: u64) -> Result<
 /// commits by SHA, and produce the diff locally. The temp dir is cleaned up automatically.
 fn gh_pr_diff_via_clone(owner: &str, repo: &str, number: u64) -> Result<String> {
     let (base_sha, head_sha) = gh_pr_commit_shas_remote(owner, repo, number)?;
-    let repo_url = format!("https://github.com/{}/{}.git", owner, repo);
+    let repo_url = web_url(&format!("{}/{}.git", owner, repo));
     let tmp_dir = std::env::temp_dir().join(format!("er-remote-{}-{}-{}", owner, repo, number));
     let tmp_path = tmp_dir
         .to_str()
@@ -1218,7 +1525,7 @@ fn gh_pr_diff_via_clone(owner: &str, repo: &str, number: u64) -> Result<String>
             &repo_url,
             &tmp_path,
         ])
-        .output()
+        .logged_output()
         .context("Failed to shallow clone for large PR diff")?;
 
     if !clone.status.success() {
@@ -1238,7 +1545,7 @@ fn gh_pr_diff_via_clone(owner: &str, repo: &str, number: u64) -> Result<String>
             &base_sha,
             &head_sha,
         ])
-        .output()
+        .logged_output()
         .context("Failed to fetch PR commits")?;
 
     if !fetch.status.success() {
@@ -1260,7 +1567,7 @@ fn gh_pr_diff_via_clone(owner: &str, repo: &str, number: u64) -> Result<String>
             "--no-color",
             "--no-ext-diff",
         ])
-        .output()
+        .logged_output()
         .context("Failed to generate diff from cloned repo")?;
 
     // Clean up
@@ -1278,7 +1585,7 @@ fn gh_pr_diff_via_clone(owner: &str, repo: &str, number: u64) -> Result<String>
 /// Returns (base_ref_name, head_ref_name).
 pub fn gh_pr_metadata_remote(owner: &str, repo: &str, number: u64) -> Result<(String, String)> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -1290,7 +1597,7 @@ pub fn gh_pr_metadata_remote(owner: &str, repo: &str, number: u64) -> Result<(St
             "--jq",
             r#"[.baseRefName, .headRefName] | @tsv"#,
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR metadata")?;
 
     if !output.status.success() {
@@ -1310,7 +1617,7 @@ pub fn gh_pr_metadata_remote(owner: &str, repo: &str, number: u64) -> Result<(St
 /// Fetch PR overview data for a remote repo (no local clone needed).
 pub fn gh_pr_overview_remote(owner: &str, repo: &str, number: u64) -> Option<PrOverviewData> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let view_output = Command::new("gh")
+    let view_output = gh_command()
         .args([
             "pr",
             "view",
@@ -1318,9 +1625,9 @@ pub fn gh_pr_overview_remote(owner: &str, repo: &str, number: u64) -> Option<PrO
             "--repo",
             &repo_slug,
             "--json",
-            "number,title,body,state,author,url,baseRefName,headRefName,reviews",
+            "number,title,body,state,author,url,baseRefName,headRefName,headRefOid,reviews,createdAt,labels",
         ])
-        .output()
+        .logged_output()
         .ok()?;
 
     if !view_output.status.success() {
@@ -1338,6 +1645,9 @@ pub fn gh_pr_overview_remote(owner: &str, repo: &str, number: u64) -> Option<PrO
     let url = v["url"].as_str().unwrap_or("").to_string();
     let base_branch = v["baseRefName"].as_str().unwrap_or("").to_string();
     let head_branch = v["headRefName"].as_str().unwrap_or("").to_string();
+    let created_at = v["createdAt"].as_str().unwrap_or("").to_string();
+    let head_oid = v["headRefOid"].as_str().unwrap_or("").to_string();
+    let labels = label_names(&v["labels"]);
 
     let reviewers: Vec<ReviewerStatus> = if let Some(reviews_arr) = v["reviews"].as_array() {
         deduplicate_reviewers(reviews_arr)
@@ -1359,24 +1669,19 @@ pub fn gh_pr_overview_remote(owner: &str, repo: &str, number: u64) -> Option<PrO
         head_branch,
         checks,
         reviewers,
+        created_at,
+        head_oid,
+        labels,
     })
 }
 
 /// Fetch PR comments for a remote repo (no local clone needed).
 pub fn gh_pr_comments_remote(owner: &str, repo: &str, pr: u64) -> Result<Vec<GitHubComment>> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{}/{}/pulls/{}/comments", owner, repo, pr),
-            "--paginate",
-        ])
-        .output()
-        .context("Failed to fetch PR comments")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to fetch PR comments: {}", stderr.trim());
-    }
+    let output = crate::command::run(gh_command().args([
+        "api",
+        &format!("repos/{}/{}/pulls/{}/comments", owner, repo, pr),
+        "--paginate",
+    ]))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let all_comments: Vec<GitHubComment> = if stdout.contains("][") {
@@ -1489,10 +1794,10 @@ pub fn gh_pr_review_threads(
         owner, repo, pr
     );
 
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["api", "graphql", "-f", &format!("query={}", query)])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to fetch review threads")?;
 
     if !output.status.success() {
@@ -1515,9 +1820,9 @@ pub fn gh_pr_review_threads_remote(
         owner, repo, pr
     );
 
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["api", "graphql", "-f", &format!("query={}", query)])
-        .output()
+        .logged_output()
         .context("Failed to fetch review threads")?;
 
     if !output.status.success() {
@@ -1553,6 +1858,157 @@ fn parse_review_threads_response(json: &str) -> Result<HashMap<u64, ReviewThread
     Ok(state_map)
 }
 
+/// The authenticated user's per-file "viewed" checkboxes on a PR.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrViewedFiles {
+    /// GraphQL node ID of the PR, needed to change the checkboxes.
+    pub pr_id: String,
+    /// Files ticked as viewed. `DISMISSED` (viewed, then changed) counts as not viewed.
+    pub viewed: HashSet<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewedFilesResponse {
+    data: ViewedFilesData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewedFilesData {
+    repository: ViewedFilesRepository,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewedFilesRepository {
+    pull_request: ViewedFilesPullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewedFilesPullRequest {
+    id: String,
+    files: ViewedFilesConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewedFilesConnection {
+    #[serde(default)]
+    page_info: Option<GraphQlPageInfo>,
+    nodes: Vec<ViewedFileNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewedFileNode {
+    path: String,
+    viewer_viewed_state: String,
+}
+
+/// One page of `pullRequest.files`: PR id, viewed paths, next cursor.
+fn parse_viewed_files_page(json: &str) -> Option<(String, Vec<String>, Option<String>)> {
+    let response: ViewedFilesResponse = serde_json::from_str(json).ok()?;
+    let pr = response.data.repository.pull_request;
+    let viewed = pr
+        .files
+        .nodes
+        .into_iter()
+        .filter(|f| f.viewer_viewed_state == "VIEWED")
+        .map(|f| f.path)
+        .collect();
+    let next = pr
+        .files
+        .page_info
+        .filter(|p| p.has_next_page)
+        .and_then(|p| p.end_cursor);
+    Some((pr.id, viewed, next))
+}
+
+/// Fetch which PR files the current `gh` user marked as viewed. `repo_root`
+/// is `None` for remote PRs (no local clone).
+pub fn gh_pr_viewed_files(
+    owner: &str,
+    repo: &str,
+    pr: u64,
+    repo_root: Option<&str>,
+) -> Result<PrViewedFiles> {
+    const MAX_PAGES: u32 = 10;
+    let mut out = PrViewedFiles::default();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let after_arg = match &cursor {
+            Some(c) => format!(r#", after: "{}""#, c.replace('"', "")),
+            None => String::new(),
+        };
+        let query = format!(
+            r#"query {{ repository(owner: "{}", name: "{}") {{ pullRequest(number: {}) {{ id files(first: 100{}) {{ pageInfo {{ hasNextPage endCursor }} nodes {{ path viewerViewedState }} }} }} }} }}"#,
+            owner, repo, pr, after_arg
+        );
+        let mut cmd = gh_command();
+        cmd.args(["api", "graphql", "-f", &format!("query={}", query)]);
+        if let Some(root) = repo_root {
+            cmd.current_dir(root);
+        }
+        let output = cmd
+            .logged_output()
+            .context("Failed to fetch viewed files")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("gh viewed files query failed: {}", stderr.trim());
+        }
+        let (pr_id, viewed, next) =
+            parse_viewed_files_page(&String::from_utf8_lossy(&output.stdout))
+                .context("Unexpected viewed files response")?;
+        out.pr_id = pr_id;
+        out.viewed.extend(viewed);
+        match next {
+            Some(c) if !c.is_empty() => cursor = Some(c),
+            _ => break,
+        }
+    }
+    Ok(out)
+}
+
+/// Tick or untick a file's "viewed" checkbox on a PR (`pr_id` from
+/// [`gh_pr_viewed_files`]).
+pub fn gh_pr_set_viewed(
+    pr_id: &str,
+    path: &str,
+    viewed: bool,
+    repo_root: Option<&str>,
+) -> Result<()> {
+    let mutation = if viewed {
+        "markFileAsViewed"
+    } else {
+        "unmarkFileAsViewed"
+    };
+    let query = format!(
+        r#"mutation($pr: ID!, $path: String!) {{ {}(input: {{ pullRequestId: $pr, path: $path }}) {{ clientMutationId }} }}"#,
+        mutation
+    );
+    let mut cmd = gh_command();
+    cmd.args([
+        "api",
+        "graphql",
+        "-f",
+        &format!("query={}", query),
+        "-f",
+        &format!("pr={}", pr_id),
+        "-f",
+        &format!("path={}", path),
+    ]);
+    if let Some(root) = repo_root {
+        cmd.current_dir(root);
+    }
+    let output = cmd
+        .logged_output()
+        .with_context(|| format!("Failed to run {}", mutation))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{} failed for {}: {}", mutation, path, stderr.trim());
+    }
+    Ok(())
+}
+
 /// Push a new review comment to a remote PR (no local clone needed).
 #[allow(clippy::too_many_arguments)]
 pub fn gh_pr_push_comment_remote(
@@ -1567,7 +2023,7 @@ pub fn gh_pr_push_comment_remote(
 ) -> Result<u64> {
     // Get the latest commit SHA for the PR
     let repo_slug = format!("{}/{}", owner, repo);
-    let sha_output = Command::new("gh")
+    let sha_output = gh_command()
         .args([
             "pr",
             "view",
@@ -1579,7 +2035,7 @@ pub fn gh_pr_push_comment_remote(
             "--jq",
             ".headRefOid",
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR head SHA")?;
 
     if !sha_output.status.success() {
@@ -1596,7 +2052,7 @@ pub fn gh_pr_push_comment_remote(
 
     let end = line_end.unwrap_or(line_start);
     let field_args = review_comment_field_args(path, line_start, end, side, body, &commit_id);
-    let mut cmd = Command::new("gh");
+    let mut cmd = gh_command();
     cmd.args([
         "api",
         "-X",
@@ -1610,12 +2066,7 @@ pub fn gh_pr_push_comment_remote(
             cmd.arg("-f").arg(arg);
         }
     }
-    let output = cmd.output().context("Failed to push comment to GitHub")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to push comment: {}", stderr.trim());
-    }
+    let output = crate::command::run(&mut cmd)?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let resp: CreateCommentResponse =
@@ -1646,20 +2097,23 @@ pub fn gh_pr_submit_review(
     repo_root: &str,
     event: &str,
     body: &str,
-) -> Result<()> {
+) -> Result<u64> {
     // Fetch the head commit SHA
-    let sha_output = Command::new("gh")
+    // `--repo`: in a fork clone gh's default repo may be the fork
+    let sha_output = gh_command()
         .args([
             "pr",
             "view",
             &pr.to_string(),
+            "--repo",
+            &format!("{}/{}", owner, repo),
             "--json",
             "headRefOid",
             "--jq",
             ".headRefOid",
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to get PR head SHA")?;
 
     if !sha_output.status.success() {
@@ -1723,6 +2177,7 @@ fn pr_review_payload_json(
     payload
 }
 
+/// Post a review and return its id.
 #[allow(clippy::too_many_arguments)]
 fn post_pr_review(
     owner: &str,
@@ -1733,13 +2188,13 @@ fn post_pr_review(
     body: &str,
     comments: &[ReviewBatchEntry],
     repo_root: Option<&str>,
-) -> Result<()> {
+) -> Result<u64> {
     let payload = pr_review_payload_json(commit_id, event, body, comments);
     let tmp_path = format!("/tmp/er_review_payload_{}.json", std::process::id());
     std::fs::write(&tmp_path, serde_json::to_string(&payload)?)
         .context("Failed to write review payload")?;
 
-    let mut cmd = Command::new("gh");
+    let mut cmd = gh_command();
     cmd.args([
         "api",
         "-X",
@@ -1751,7 +2206,7 @@ fn post_pr_review(
     if let Some(root) = repo_root {
         cmd.current_dir(root);
     }
-    let output = cmd.output().context("Failed to submit PR review")?;
+    let output = cmd.logged_output().context("Failed to submit PR review")?;
 
     let _ = std::fs::remove_file(&tmp_path);
 
@@ -1766,7 +2221,54 @@ fn post_pr_review(
         anyhow::bail!("Failed to submit review: {}", detail);
     }
 
-    Ok(())
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let resp: CreateCommentResponse =
+        serde_json::from_str(&stdout).context("Failed to parse review response")?;
+    Ok(resp.id)
+}
+
+/// A line comment GitHub created for a submitted review.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ReviewCommentId {
+    pub id: u64,
+    pub path: String,
+    pub body: String,
+}
+
+/// The comments review `review_id` created — reviews don't return them,
+/// and replies need their ids.
+pub fn gh_pr_review_comment_ids(
+    owner: &str,
+    repo: &str,
+    pr: u64,
+    review_id: u64,
+    repo_root: Option<&str>,
+) -> Result<Vec<ReviewCommentId>> {
+    let mut cmd = gh_command();
+    cmd.args([
+        "api",
+        "--paginate",
+        &format!(
+            "repos/{}/{}/pulls/{}/reviews/{}/comments?per_page=100",
+            owner, repo, pr, review_id
+        ),
+        "--jq",
+        ".[] | {id, path, body}",
+    ]);
+    if let Some(root) = repo_root {
+        cmd.current_dir(root);
+    }
+    let output = crate::command::run(&mut cmd).context("Failed to list the review's comments")?;
+    parse_review_comment_ids(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// One `{id, path, body}` object per line, as `--jq` prints them.
+fn parse_review_comment_ids(lines: &str) -> Result<Vec<ReviewCommentId>> {
+    lines
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).context("Failed to parse review comment"))
+        .collect()
 }
 
 /// Submit a batch PR review with multiple comments on a remote PR (no local repo required).
@@ -1777,9 +2279,9 @@ pub fn gh_pr_submit_review_remote(
     comments: &[ReviewBatchEntry],
     event: &str,
     body: &str,
-) -> Result<()> {
+) -> Result<u64> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let sha_output = Command::new("gh")
+    let sha_output = gh_command()
         .args([
             "pr",
             "view",
@@ -1791,7 +2293,7 @@ pub fn gh_pr_submit_review_remote(
             "--jq",
             ".headRefOid",
         ])
-        .output()
+        .logged_output()
         .context("Failed to get PR head SHA")?;
 
     if !sha_output.status.success() {
@@ -1809,6 +2311,65 @@ pub fn gh_pr_submit_review_remote(
     post_pr_review(owner, repo, pr, &commit_id, event, body, comments, None)
 }
 
+/// Post a file-level review comment (`subject_type=file`, no line). Without
+/// `repo_root` the PR is addressed by `owner/repo` alone.
+pub fn gh_pr_file_comment(
+    owner: &str,
+    repo: &str,
+    pr: u64,
+    path: &str,
+    body: &str,
+    repo_root: Option<&str>,
+) -> Result<u64> {
+    let repo_slug = format!("{}/{}", owner, repo);
+    let pr_arg = pr.to_string();
+    let mut sha_cmd = gh_command();
+    sha_cmd.args([
+        "pr",
+        "view",
+        &pr_arg,
+        "--json",
+        "headRefOid",
+        "--jq",
+        ".headRefOid",
+    ]);
+    sha_cmd.args(["--repo", &repo_slug]);
+    if let Some(root) = repo_root {
+        sha_cmd.current_dir(root);
+    }
+    let sha_output = crate::command::run(&mut sha_cmd).context("Failed to get PR head SHA")?;
+    let commit_id = String::from_utf8_lossy(&sha_output.stdout)
+        .trim()
+        .to_string();
+    if commit_id.is_empty() {
+        anyhow::bail!("Failed to get HEAD SHA: empty output");
+    }
+
+    let mut cmd = gh_command();
+    cmd.args([
+        "api",
+        "-X",
+        "POST",
+        &format!("repos/{}/pulls/{}/comments", repo_slug, pr),
+        "-f",
+        &format!("path={}", path),
+        "-f",
+        &format!("body={}", body),
+        "-f",
+        &format!("commit_id={}", commit_id),
+        "-f",
+        "subject_type=file",
+    ]);
+    if let Some(root) = repo_root {
+        cmd.current_dir(root);
+    }
+    let output = crate::command::run(&mut cmd)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let resp: CreateCommentResponse =
+        serde_json::from_str(&stdout).context("Failed to parse create comment response")?;
+    Ok(resp.id)
+}
+
 /// Post a general comment on a PR (not attached to any file/line).
 /// Uses the Issues API — PRs are issues in GitHub.
 pub fn gh_pr_general_comment(
@@ -1818,7 +2379,7 @@ pub fn gh_pr_general_comment(
     body: &str,
     repo_root: &str,
 ) -> Result<u64> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -1828,7 +2389,7 @@ pub fn gh_pr_general_comment(
             &format!("body={}", body),
         ])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .context("Failed to post general PR comment")?;
 
     if !output.status.success() {
@@ -1845,7 +2406,7 @@ pub fn gh_pr_general_comment(
 
 /// Post a general comment on a remote PR (no local repo required).
 pub fn gh_pr_general_comment_remote(owner: &str, repo: &str, pr: u64, body: &str) -> Result<u64> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -1854,7 +2415,7 @@ pub fn gh_pr_general_comment_remote(owner: &str, repo: &str, pr: u64, body: &str
             "-f",
             &format!("body={}", body),
         ])
-        .output()
+        .logged_output()
         .context("Failed to post general PR comment")?;
 
     if !output.status.success() {
@@ -1937,14 +2498,7 @@ pub fn parse_pr_overview(json: &str) -> Result<PrOverviewFull> {
             .map(|s| s.to_string()),
         head_ref_name: v["headRefName"].as_str().unwrap_or("").to_string(),
         base_ref_name: v["baseRefName"].as_str().unwrap_or("").to_string(),
-        labels: v["labels"]
-            .as_array()
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|l| l["name"].as_str().map(|s| s.to_string()))
-                    .collect()
-            })
-            .unwrap_or_default(),
+        labels: label_names(&v["labels"]),
     })
 }
 
@@ -2033,7 +2587,7 @@ pub fn gh_pr_commits_remote(
     limit: usize,
 ) -> Vec<crate::git::CommitInfo> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = match Command::new("gh")
+    let output = match gh_command()
         .args([
             "pr",
             "view",
@@ -2043,7 +2597,7 @@ pub fn gh_pr_commits_remote(
             "--json",
             "commits",
         ])
-        .output()
+        .logged_output()
     {
         Ok(o) => o,
         Err(_) => return Vec::new(),
@@ -2056,6 +2610,155 @@ pub fn gh_pr_commits_remote(
     parse_pr_commits_view_json(&output.stdout, limit).unwrap_or_default()
 }
 
+/// Number of the merged PR that brought in commit `sha`
+/// (`commits/{sha}/pulls`). `Ok(None)` when no merged PR contains it.
+pub fn gh_commit_pr(owner: &str, repo: &str, sha: &str) -> Result<Option<u64>> {
+    let output = gh_command()
+        .args([
+            "api",
+            &format!("repos/{}/{}/commits/{}/pulls", owner, repo, sha),
+        ])
+        .logged_output()
+        .context("Failed to run gh api")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("gh api commits/{}/pulls failed: {}", sha, stderr.trim());
+    }
+    parse_commit_pulls(&output.stdout)
+}
+
+/// The first merged PR in a `commits/{sha}/pulls` response.
+fn parse_commit_pulls(json: &[u8]) -> Result<Option<u64>> {
+    let pulls: Vec<serde_json::Value> =
+        serde_json::from_slice(json).context("Failed to parse commit pulls")?;
+    Ok(pulls
+        .iter()
+        .filter(|p| p.get("merged_at").is_some_and(|m| !m.is_null()))
+        .find_map(|p| p.get("number").and_then(|n| n.as_u64())))
+}
+
+/// A recent PR that touched a file, for the Related panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelatedPr {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    /// `open` or `merged`
+    pub state: String,
+    /// `YYYY-MM-DD` it was merged, or last updated while open
+    pub date: String,
+}
+
+/// Open and merged PRs among the repo's `limit` most recent that change
+/// `path` (`gh pr list --json files`), newest first.
+pub fn gh_prs_touching(repo_root: &str, path: &str, limit: usize) -> Result<Vec<RelatedPr>> {
+    let output = gh_command()
+        .args([
+            "pr",
+            "list",
+            "--state",
+            "all",
+            "--limit",
+            &limit.to_string(),
+        ])
+        .args([
+            "--json",
+            "number,title,state,author,mergedAt,updatedAt,files",
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run gh pr list")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("gh pr list failed: {}", stderr.trim());
+    }
+    parse_prs_touching(&output.stdout, path)
+}
+
+/// The open and merged PRs in a `gh pr list --json …,files` payload that
+/// change `path`.
+fn parse_prs_touching(json: &[u8], path: &str) -> Result<Vec<RelatedPr>> {
+    let prs: Vec<serde_json::Value> =
+        serde_json::from_slice(json).context("Failed to parse gh pr list")?;
+    let str_field = |v: &serde_json::Value, key: &str| {
+        v.get(key)
+            .and_then(|s| s.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(prs
+        .iter()
+        .filter(|pr| {
+            pr.get("files")
+                .and_then(|f| f.as_array())
+                .is_some_and(|files| {
+                    files
+                        .iter()
+                        .any(|f| f.get("path").and_then(|p| p.as_str()) == Some(path))
+                })
+        })
+        .filter_map(|pr| {
+            let state = str_field(pr, "state").to_ascii_lowercase();
+            let when = match state.as_str() {
+                "merged" => str_field(pr, "mergedAt"),
+                "open" => str_field(pr, "updatedAt"),
+                _ => return None,
+            };
+            Some(RelatedPr {
+                number: pr.get("number")?.as_u64()?,
+                title: str_field(pr, "title"),
+                author: pr
+                    .get("author")
+                    .map(|a| str_field(a, "login"))
+                    .unwrap_or_default(),
+                state,
+                date: when.chars().take(10).collect(),
+            })
+        })
+        .collect())
+}
+
+/// A forge release, for the PR overview panel slot of a tag range tab
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotes {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub url: String,
+    /// ISO 8601; `None` for drafts
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+/// The release published for `tag` (`gh release view`). `Ok(None)` when
+/// the tag has no release.
+pub fn gh_release_notes(repo_root: &str, tag: &str) -> Result<Option<ReleaseNotes>> {
+    let output = gh_command()
+        .args([
+            "release",
+            "view",
+            tag,
+            "--json",
+            "tagName,name,body,url,publishedAt",
+        ])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run gh release view")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("release not found") {
+            return Ok(None);
+        }
+        anyhow::bail!("gh release view {} failed: {}", tag, stderr.trim());
+    }
+    let notes = serde_json::from_slice(&output.stdout).context("Failed to parse release")?;
+    Ok(Some(notes))
+}
+
 /// Combined overview + conversation-comments + reviews for a remote PR, in
 /// ONE `gh pr view --json` subprocess. Collapses what used to be three
 /// separate `gh pr view` calls (`gh_pr_overview_remote_full` +
@@ -2076,7 +2779,7 @@ pub struct PrStatusBundle {
 /// No local clone required.
 pub fn gh_pr_status_remote(owner: &str, repo: &str, number: u64) -> Result<PrStatusBundle> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -2086,7 +2789,7 @@ pub fn gh_pr_status_remote(owner: &str, repo: &str, number: u64) -> Result<PrSta
             "--json",
             "number,title,body,state,isDraft,author,reviewDecision,mergeable,headRefName,baseRefName,labels,url,comments,reviews",
         ])
-        .output()
+        .logged_output()
         .context("Failed to run gh pr view (status bundle)")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2110,7 +2813,7 @@ pub fn gh_pr_status_remote(owner: &str, repo: &str, number: u64) -> Result<PrSta
 /// Fetch CI check runs for a remote PR.
 pub fn gh_pr_checks_remote(owner: &str, repo: &str, number: u64) -> Result<Vec<CheckRun>> {
     let repo_slug = format!("{}/{}", owner, repo);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "checks",
@@ -2120,7 +2823,7 @@ pub fn gh_pr_checks_remote(owner: &str, repo: &str, number: u64) -> Result<Vec<C
             "--json",
             "name,state,bucket,link",
         ])
-        .output()
+        .logged_output()
         .context("Failed to run gh pr checks")?;
     if !output.status.success() {
         // No checks configured is not an error — return empty.
@@ -2187,7 +2890,7 @@ pub fn parse_origin_remote(repo_root: &str) -> Option<(String, String)> {
     let out = Command::new("git")
         .args(["remote", "get-url", "origin"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
         .ok()?;
     if !out.status.success() {
         return None;
@@ -2196,6 +2899,41 @@ pub fn parse_origin_remote(repo_root: &str) -> Option<(String, String)> {
     parse_remote_url(&url)
 }
 
+/// Permalink to `path` at commit `sha`, optionally anchored to a line range:
+/// `https://github.com/owner/repo/blob/<sha>/<path>#L1-L3`.
+pub fn blob_permalink(
+    owner: &str,
+    repo: &str,
+    sha: &str,
+    path: &str,
+    lines: Option<(usize, usize)>,
+) -> String {
+    let path: String = path
+        .split('/')
+        .map(|seg| {
+            seg.bytes()
+                .map(|b| {
+                    if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+                        (b as char).to_string()
+                    } else {
+                        format!("%{:02X}", b)
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let anchor = match lines {
+        Some((start, end)) if end > start => format!("#L{}-L{}", start, end),
+        Some((start, _)) => format!("#L{}", start),
+        None => String::new(),
+    };
+    web_url(&format!(
+        "{}/{}/blob/{}/{}{}",
+        owner, repo, sha, path, anchor
+    ))
+}
+
 /// Derive the canonical owner/repo slug for a local clone, matching the slug
 /// produced by `er --remote` for the same repository.
 ///
@@ -2221,7 +2959,7 @@ pub fn gh_pr_reply_comment_remote(
     in_reply_to: u64,
     body: &str,
 ) -> Result<u64> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -2232,7 +2970,7 @@ pub fn gh_pr_reply_comment_remote(
             "-F",
             &format!("in_reply_to={}", in_reply_to),
         ])
-        .output()
+        .logged_output()
         .context("Failed to push reply to GitHub")?;
 
     if !output.status.success() {
@@ -2254,9 +2992,9 @@ pub fn gh_pr_reply_comment_remote(
 
 /// Login of the authenticated `gh` user, if available.
 pub fn gh_current_login() -> Option<String> {
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["api", "user", "--jq", ".login"])
-        .output()
+        .logged_output()
         .ok()?;
     if !output.status.success() {
         return None;
@@ -2269,6 +3007,28 @@ pub fn gh_current_login() -> Option<String> {
     }
 }
 
+/// Number and title of the repo's open PRs, newest first, for completing
+/// `er --pr`.
+pub fn gh_open_prs(repo_root: &str, limit: usize) -> Result<Vec<(u64, String)>> {
+    #[derive(Deserialize)]
+    struct Raw {
+        number: u64,
+        title: String,
+    }
+    let output = gh_command()
+        .args(["pr", "list", "--limit", &limit.to_string()])
+        .args(["--json", "number,title"])
+        .current_dir(repo_root)
+        .logged_output()
+        .context("Failed to run gh pr list")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("gh pr list failed: {}", stderr.trim());
+    }
+    let prs: Vec<Raw> = serde_json::from_slice(&output.stdout)?;
+    Ok(prs.into_iter().map(|pr| (pr.number, pr.title)).collect())
+}
+
 /// List PRs for a repo with fields needed by [`crate::review_queue`].
 ///
 /// `state` is passed to `gh pr list --state` (`open`, `closed`, `merged`, `all`).
@@ -2280,7 +3040,7 @@ pub fn gh_pr_list_queue(
 ) -> Result<Vec<crate::review_queue::QueuePr>> {
     let repo_slug = format!("{}/{}", owner, repo);
     let limit = limit.clamp(1, 100);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "pr",
             "list",
@@ -2293,7 +3053,7 @@ pub fn gh_pr_list_queue(
             "--json",
             "number,title,headRefName,baseRefName,state,isDraft,author,reviewRequests,reviewDecision,mergeable,mergeStateStatus,additions,deletions,changedFiles,updatedAt,labels,url,latestReviews",
         ])
-        .output()
+        .logged_output()
         .context("Failed to run gh pr list")?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2513,9 +3273,9 @@ pub fn gh_pr_thread_addressing_remote(
             r#"query {{ repository(owner: "{}", name: "{}") {{ pullRequest(number: {}) {{ reviewThreads(first: {}{}) {{ pageInfo {{ hasNextPage endCursor }} nodes {{ isResolved isOutdated comments(first: 1) {{ nodes {{ databaseId }} }} }} }} }} }} }}"#,
             owner, repo, pr, PAGE_SIZE, after_arg
         );
-        let output = Command::new("gh")
+        let output = gh_command()
             .args(["api", "graphql", "-f", &format!("query={}", query)])
-            .output()
+            .logged_output()
             .context("Failed to fetch review threads")?;
         if !output.status.success() {
             if page == 0 {
@@ -2567,6 +3327,74 @@ pub fn owner_repo_storage_slug(owner: &str, repo: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn review_comment_ids_parse_one_per_line() {
+        let out = "{\"id\":7,\"path\":\"src/a.rs\",\"body\":\"Nit\\nhere\"}\n\
+                   {\"id\":9,\"path\":\"src/b.rs\",\"body\":\"Why?\"}\n";
+        let ids = parse_review_comment_ids(out).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(
+            ids[0],
+            ReviewCommentId {
+                id: 7,
+                path: "src/a.rs".into(),
+                body: "Nit\nhere".into(),
+            }
+        );
+        assert_eq!(ids[1].id, 9);
+        assert!(parse_review_comment_ids("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn commit_pulls_take_the_merged_pr() {
+        let json = br#"[{"number": 7, "merged_at": null}, {"number": 12, "merged_at": "2024-06-01T10:00:00Z"}]"#;
+        assert_eq!(parse_commit_pulls(json).unwrap(), Some(12));
+        assert_eq!(
+            parse_commit_pulls(br#"[{"number": 7, "merged_at": null}]"#).unwrap(),
+            None
+        );
+        assert_eq!(parse_commit_pulls(b"[]").unwrap(), None);
+        assert!(parse_commit_pulls(b"{").is_err());
+    }
+
+    #[test]
+    fn prs_touching_a_file_keep_open_and_merged_ones() {
+        let json = br#"[
+            {"number": 9, "title": "Ledger rounding", "state": "MERGED", "author": {"login": "ada"},
+             "mergedAt": "2026-10-01T09:00:00Z", "updatedAt": "2026-10-02T09:00:00Z",
+             "files": [{"path": "payments/ledger.rs"}]},
+            {"number": 8, "title": "Abandoned", "state": "CLOSED", "author": {"login": "lin"},
+             "mergedAt": null, "updatedAt": "2026-09-01T09:00:00Z",
+             "files": [{"path": "payments/ledger.rs"}]},
+            {"number": 7, "title": "Docs", "state": "OPEN", "author": {"login": "lin"},
+             "mergedAt": null, "updatedAt": "2026-09-30T09:00:00Z",
+             "files": [{"path": "README.md"}]},
+            {"number": 6, "title": "Split ledger", "state": "OPEN", "author": {"login": "kim"},
+             "mergedAt": null, "updatedAt": "2026-09-29T09:00:00Z",
+             "files": [{"path": "payments/ledger.rs"}, {"path": "payments/mod.rs"}]}
+        ]"#;
+        let prs = parse_prs_touching(json, "payments/ledger.rs").unwrap();
+        let summary: Vec<(u64, &str, &str)> = prs
+            .iter()
+            .map(|p| (p.number, p.state.as_str(), p.date.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(9, "merged", "2026-10-01"), (6, "open", "2026-09-29")]
+        );
+        assert_eq!(prs[0].author, "ada");
+        assert!(parse_prs_touching(b"{", "a").is_err());
+    }
+
+    #[test]
+    fn release_notes_parse_from_release_view() {
+        let json = br#"{"tagName": "v1.4.0", "name": "1.4.0", "body": "- Faster diffs", "url": "https://github.com/o/r/releases/tag/v1.4.0", "publishedAt": null}"#;
+        let notes: ReleaseNotes = serde_json::from_slice(json).unwrap();
+        assert_eq!(notes.tag_name, "v1.4.0");
+        assert_eq!(notes.body, "- Faster diffs");
+        assert_eq!(notes.published_at, None);
+    }
+
     #[test]
     fn parse_standard_url() {
         let pr = parse_github_pr_url("https://github.com/owner/repo/pull/42").unwrap();
@@ -2575,6 +3403,58 @@ mod tests {
         assert_eq!(pr.number, 42);
     }
 
+    #[test]
+    fn rate_limited_calls_back_off_then_give_up() {
+        let limited = || anyhow::anyhow!("gh: API rate limit exceeded for user ID 1 (HTTP 403)");
+        assert!(is_rate_limited(&limited()));
+        assert!(is_rate_limited(&anyhow::anyhow!(
+            "You have exceeded a secondary rate limit"
+        )));
+        assert!(!is_rate_limited(&anyhow::anyhow!(
+            "HTTP 422: Validation Failed"
+        )));
+
+        let waits = [std::time::Duration::ZERO; 2];
+        let (mut calls, mut paused) = (0, 0);
+        let ok = with_rate_limit_backoff(
+            &waits,
+            || {
+                calls += 1;
+                if calls < 2 {
+                    Err(limited())
+                } else {
+                    Ok(calls)
+                }
+            },
+            |_| paused += 1,
+        );
+        assert_eq!((ok.unwrap(), paused), (2, 1));
+
+        let mut calls = 0;
+        let err = with_rate_limit_backoff(
+            &waits,
+            || -> Result<()> {
+                calls += 1;
+                Err(limited())
+            },
+            |_| {},
+        );
+        assert!(err.is_err());
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let other = with_rate_limit_backoff(
+            &waits,
+            || -> Result<()> {
+                calls += 1;
+                anyhow::bail!("not found")
+            },
+            |_| {},
+        );
+        assert!(other.is_err());
+        assert_eq!(calls, 1);
+    }
+
     #[test]
     fn parse_url_with_trailing_path() {
         let pr = parse_github_pr_url("https://github.com/owner/repo/pull/42/files").unwrap();
@@ -2601,6 +3481,74 @@ mod tests {
         assert_eq!(pr.number, 99);
     }
 
+    #[test]
+    fn parse_enterprise_pr_url() {
+        let hosts = vec!["ghe.corp.example".to_string()];
+        let pr = parse_pr_url_on("https://GHE.corp.example/o/r/pull/5/files", &hosts).unwrap();
+        assert_eq!(pr.host, "ghe.corp.example");
+        assert_eq!(
+            (pr.owner.as_str(), pr.repo.as_str(), pr.number),
+            ("o", "r", 5)
+        );
+        // Unknown hosts, and Enterprise hosts without a scheme, aren't PRs
+        assert!(parse_pr_url_on("https://other.example/o/r/pull/5", &hosts).is_none());
+        assert!(parse_pr_url_on("ghe.corp.example/o/r/pull/5", &hosts).is_none());
+        assert_eq!(
+            parse_pr_url_on("github.com/o/r/pull/5", &hosts)
+                .unwrap()
+                .host,
+            DEFAULT_HOST
+        );
+
+        assert_eq!(
+            remote_host("git@ghe.corp.example:o/r.git").as_deref(),
+            Some("ghe.corp.example")
+        );
+        assert_eq!(
+            remote_host("ssh://git@GHE.corp.example:2222/o/r.git").as_deref(),
+            Some("ghe.corp.example")
+        );
+        assert_eq!(
+            remote_host("https://github.com/o/r").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            parse_remote_url("ssh://git@ghe.corp.example:2222/o/r.git"),
+            Some(("o".to_string(), "r".to_string()))
+        );
+    }
+
+    #[test]
+    fn fork_pr_head_is_labelled_with_its_owner() {
+        let fork = parse_pr_head("main\talice\ttrue\n");
+        assert_eq!(fork.fork_owner.as_deref(), Some("alice"));
+        assert_eq!(fork.label(), "alice:main");
+        let same_repo = parse_pr_head("feat/x\tacme\tfalse\n");
+        assert_eq!(same_repo.fork_owner, None);
+        assert_eq!(same_repo.label(), "feat/x");
+    }
+
+    #[test]
+    fn forge_remote_prefers_configured_then_upstream() {
+        let names = |ns: &[&str]| ns.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let fork = names(&["origin", "upstream"]);
+        assert_eq!(pick_forge_remote(&fork, None).as_deref(), Some("upstream"));
+        assert_eq!(
+            pick_forge_remote(&fork, Some("origin")).as_deref(),
+            Some("origin")
+        );
+        // A configured remote the clone doesn't have is ignored
+        assert_eq!(
+            pick_forge_remote(&names(&["origin"]), Some("gh")).as_deref(),
+            Some("origin")
+        );
+        assert_eq!(
+            pick_forge_remote(&names(&["github"]), None).as_deref(),
+            Some("github")
+        );
+        assert_eq!(pick_forge_remote(&names(&["a", "b"]), None), None);
+    }
+
     #[test]
     fn parse_invalid_not_github() {
         assert!(parse_github_pr_url("https://gitlab.com/owner/repo/pull/42").is_none());
@@ -2773,6 +3721,9 @@ mod tests {
             head_branch: "fix/the-bug".to_string(),
             checks: vec![],
             reviewers: vec![],
+            created_at: String::new(),
+            head_oid: String::new(),
+            labels: Vec::new(),
         };
         assert_eq!(data.number, 42);
         assert_eq!(data.title, "Fix the bug");
@@ -3157,6 +4108,16 @@ mod tests {
         assert!(parse_remote_url("").is_none());
     }
 
+    #[test]
+    fn blob_permalink_encodes_path_and_range() {
+        assert_eq!(
+            blob_permalink("acme", "app", "abc123", "src/my file.rs", Some((7, 7))),
+            "https://github.com/acme/app/blob/abc123/src/my%20file.rs#L7"
+        );
+        assert!(blob_permalink("acme", "app", "abc123", "a.rs", Some((3, 9))).ends_with("#L3-L9"));
+        assert!(blob_permalink("acme", "app", "abc123", "a.rs", None).ends_with("/a.rs"));
+    }
+
     // ── canonical_owner_repo_slug (pure slug logic, no git subprocess) ──
 
     #[test]
@@ -3298,6 +4259,22 @@ mod tests {
         assert_eq!(summarize_checks_state(&queued), "pending");
     }
 
+    #[test]
+    fn viewed_files_page_keeps_only_viewed() {
+        let json = r#"{"data":{"repository":{"pullRequest":{"id":"PR_kw1","files":{
+            "pageInfo":{"hasNextPage":true,"endCursor":"Y3Vy"},
+            "nodes":[
+                {"path":"src/a.rs","viewerViewedState":"VIEWED"},
+                {"path":"src/b.rs","viewerViewedState":"DISMISSED"},
+                {"path":"src/c.rs","viewerViewedState":"UNVIEWED"}
+            ]}}}}}"#;
+        let (id, viewed, next) = parse_viewed_files_page(json).unwrap();
+        assert_eq!(id, "PR_kw1");
+        assert_eq!(viewed, vec!["src/a.rs"]);
+        assert_eq!(next.as_deref(), Some("Y3Vy"));
+        assert!(parse_viewed_files_page("{}").is_none());
+    }
+
     #[test]
     fn thread_addressing_summary_from_graphql() {
         let json = r#"{
@@ -42,6 +42,7 @@ pub struct Highlighter {
 
 impl Highlighter {
     pub fn new() -> Self {
+        let _profile = crate::profile::scope("highlight init");
         let mut theme_set = ThemeSet::load_defaults();
         let extra: ThemeSet = (&two_face::theme::extra()).into();
         theme_set.themes.extend(extra.themes);
@@ -71,6 +72,7 @@ impl Highlighter {
         filename: &str,
         theme_name: &str,
     ) -> Vec<HighlightSpan> {
+        let _profile = crate::profile::scope("highlight");
         let key = cache_key(line, filename, theme_name);
 
         if let Some(cached) = self.cache.get(&key) {
@@ -0,0 +1,137 @@
+//! The local reviewer's name.
+//!
+//! Comments written here are stored with author [`LOCAL_AUTHOR`] (`"You"`);
+//! edit and delete rights key off it, so it never changes on disk. What
+//! leaves the machine — handoff exports and the shared review ref — goes
+//! through [`Identity::attribute`] instead, which swaps in the configured
+//! name: `[identity] name` first, then `git config user.name`, then the
+//! email. Comments pushed to GitHub are attributed by GitHub to the `gh`
+//! account, so they need nothing here.
+
+use crate::config::IdentityConfig;
+
+/// Author stored on comments written in this checkout.
+pub const LOCAL_AUTHOR: &str = "You";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Display name; [`LOCAL_AUTHOR`] when nothing is configured
+    pub name: String,
+    pub email: Option<String>,
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+impl Identity {
+    /// `config` overrides git's `user.name` / `user.email` in `repo_root`.
+    pub fn resolve(config: &IdentityConfig, repo_root: &str) -> Self {
+        let git = |key: &str| crate::git::git_config_value(repo_root, key);
+        let email = non_empty(config.email.as_ref()).or_else(|| git("user.email"));
+        let name = non_empty(config.name.as_ref())
+            .or_else(|| git("user.name"))
+            .or_else(|| email.clone())
+            .unwrap_or_else(|| LOCAL_AUTHOR.to_string());
+        Identity { name, email }
+    }
+
+    /// Directory-safe id, as used under `refs/er/reviews/`.
+    pub fn slug(&self) -> String {
+        if self.name == LOCAL_AUTHOR {
+            return crate::shared_review::reviewer_slug("");
+        }
+        crate::shared_review::reviewer_slug(&self.name)
+    }
+
+    /// `author` as others should see it: [`LOCAL_AUTHOR`] becomes our name.
+    pub fn attribute<'a>(&'a self, author: &'a str) -> &'a str {
+        if author == LOCAL_AUTHOR {
+            &self.name
+        } else {
+            author
+        }
+    }
+
+    /// Rewrite every `"author": "You"` in a questions / github-comments JSON
+    /// document. Unparseable input is returned as is.
+    pub fn attribute_json(&self, contents: &str) -> String {
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(contents) else {
+            return contents.to_string();
+        };
+        attribute_value(&mut value, &self.name);
+        serde_json::to_string_pretty(&value).unwrap_or_else(|_| contents.to_string())
+    }
+}
+
+fn attribute_value(value: &mut serde_json::Value, name: &str) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if key == "author" && v.as_str() == Some(LOCAL_AUTHOR) {
+                    *v = serde_json::Value::String(name.to_string());
+                } else {
+                    attribute_value(v, name);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for v in items {
+                attribute_value(v, name);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_wins_over_git_and_email_backs_up_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["config", "user.name", "Git Name"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+
+        let from_git = Identity::resolve(&IdentityConfig::default(), &root);
+        assert_eq!(from_git.name, "Git Name");
+        assert_eq!(from_git.slug(), "git-name");
+
+        let configured = IdentityConfig {
+            name: Some("Ada Lovelace".into()),
+            email: Some("ada@example.com".into()),
+        };
+        let id = Identity::resolve(&configured, &root);
+        assert_eq!(id.name, "Ada Lovelace");
+        assert_eq!(id.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(id.attribute(LOCAL_AUTHOR), "Ada Lovelace");
+        assert_eq!(id.attribute("octocat"), "octocat");
+    }
+
+    #[test]
+    fn attribute_json_rewrites_only_local_authors() {
+        let id = Identity {
+            name: "ada".into(),
+            email: None,
+        };
+        let json =
+            r#"{"comments":[{"author":"You","replies":[{"author":"bob"},{"author":"You"}]}]}"#;
+        let out: serde_json::Value = serde_json::from_str(&id.attribute_json(json)).unwrap();
+        assert_eq!(out["comments"][0]["author"], "ada");
+        assert_eq!(out["comments"][0]["replies"][0]["author"], "bob");
+        assert_eq!(out["comments"][0]["replies"][1]["author"], "ada");
+        assert_eq!(id.attribute_json("not json"), "not json");
+    }
+}
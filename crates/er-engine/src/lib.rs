@@ -1,34 +1,63 @@
 pub mod agent_slots;
 pub mod ai;
+pub mod analysis;
 #[cfg(feature = "ui")]
 pub mod app;
 #[cfg(feature = "ui")]
 pub mod arena;
+pub mod artifact_gc;
+pub mod bug_report;
 pub mod cache;
+pub mod clipboard;
+pub mod command;
 pub mod config;
+pub mod config_overrides;
+pub mod crash;
 #[allow(unused_imports)]
 pub use config::{
     apply_config_field, config_hub_items_for_scope, desktop_settings_snapshot, ConfigFieldValue,
     ConfigHubFieldDto, DesktopSettingsSnapshot, SettingsScope,
 };
 pub mod agent_runtime;
+pub mod debug_log;
 pub mod dev_log;
+pub mod doctor;
 pub mod env_path;
+pub mod events;
+pub mod file_lock;
+#[cfg(feature = "test-support")]
+pub mod fixture_repo;
+pub mod focus_ipc;
 pub mod git;
 pub mod github;
 #[cfg(feature = "highlight")]
 pub mod highlight;
+pub mod identity;
 pub mod model_discovery;
+pub mod multiplexer;
+pub mod outbox;
 pub mod paths;
+pub mod profile;
 pub mod projects_pins;
+#[cfg(feature = "sqlite")]
+pub mod review_db;
 pub mod review_queue;
+pub mod review_session;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod self_update;
+pub mod session_bundle;
+pub mod shared_review;
 pub mod sidecar_specs;
 pub mod sidecar_summary;
 pub mod sidecar_upload;
+pub mod spell;
 pub mod storage;
 pub mod sync;
 pub mod uninstall;
+pub mod usage;
 #[cfg(feature = "watch")]
 pub mod watch;
+pub mod webhook;
 
 pub use paths::ErRoot;
@@ -0,0 +1,152 @@
+//! Terminal multiplexer detection and "open in split" command templates.
+//!
+//! When er runs inside tmux, WezTerm or kitty, files can be opened in an
+//! editor pane beside the TUI instead of a detached GUI editor. Templates come
+//! from `[split]` in the global config and support `{editor}`, `{file}`,
+//! `{line}` and `{repo}` placeholders. Placeholders are substituted after the
+//! template is split into args, so paths with spaces stay a single argument.
+
+use crate::config::{split_shell_args, SplitConfig};
+
+/// A terminal multiplexer er knows how to split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Wezterm,
+    Kitty,
+}
+
+impl Multiplexer {
+    pub fn label(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Wezterm => "WezTerm",
+            Multiplexer::Kitty => "kitty",
+        }
+    }
+
+    /// Detect from the process environment. tmux wins when nested inside a
+    /// GUI terminal because its panes are the ones the user sees.
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(|key| std::env::var(key).ok())
+    }
+
+    fn detect_from(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let set = |key: &str| get(key).is_some_and(|v| !v.is_empty());
+        if set("TMUX") {
+            Some(Multiplexer::Tmux)
+        } else if set("WEZTERM_PANE") {
+            Some(Multiplexer::Wezterm)
+        } else if set("KITTY_WINDOW_ID") {
+            Some(Multiplexer::Kitty)
+        } else {
+            None
+        }
+    }
+
+    /// Configured template for this multiplexer.
+    pub fn template(self, config: &SplitConfig) -> &str {
+        match self {
+            Multiplexer::Tmux => &config.tmux,
+            Multiplexer::Wezterm => &config.wezterm,
+            Multiplexer::Kitty => &config.kitty,
+        }
+    }
+}
+
+/// Expand a split template into argv. Returns `None` for an empty template.
+pub fn split_command_args(
+    template: &str,
+    editor: &str,
+    file: &str,
+    line: usize,
+    repo: &str,
+) -> Option<Vec<String>> {
+    let line = line.to_string();
+    let mut args = Vec::new();
+    for arg in split_shell_args(template) {
+        // `{editor}` may carry flags (e.g. "nvim -u NONE") — expand it into
+        // multiple args when it stands alone.
+        if arg == "{editor}" {
+            args.extend(split_shell_args(editor));
+            continue;
+        }
+        args.push(
+            arg.replace("{editor}", editor)
+                .replace("{file}", file)
+                .replace("{line}", &line)
+                .replace("{repo}", repo),
+        );
+    }
+    if args.is_empty() {
+        None
+    } else {
+        Some(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_prefers_tmux_over_gui_terminal() {
+        let env = |key: &str| match key {
+            "TMUX" => Some("/tmp/tmux-1000/default,1,0".to_string()),
+            "WEZTERM_PANE" => Some("3".to_string()),
+            _ => None,
+        };
+        assert_eq!(Multiplexer::detect_from(env), Some(Multiplexer::Tmux));
+    }
+
+    #[test]
+    fn detect_ignores_empty_vars() {
+        let env = |key: &str| match key {
+            "TMUX" => Some(String::new()),
+            "KITTY_WINDOW_ID" => Some("1".to_string()),
+            _ => None,
+        };
+        assert_eq!(Multiplexer::detect_from(env), Some(Multiplexer::Kitty));
+        assert_eq!(Multiplexer::detect_from(|_| None), None);
+    }
+
+    #[test]
+    fn split_args_keep_paths_with_spaces_whole() {
+        let args = split_command_args(
+            "tmux split-window -h -c {repo} {editor} +{line} {file}",
+            "nvim -u NONE",
+            "/tmp/my repo/src/main.rs",
+            42,
+            "/tmp/my repo",
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "tmux",
+                "split-window",
+                "-h",
+                "-c",
+                "/tmp/my repo",
+                "nvim",
+                "-u",
+                "NONE",
+                "+42",
+                "/tmp/my repo/src/main.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_template_yields_none() {
+        assert!(split_command_args("  ", "vi", "f", 1, "/r").is_none());
+    }
+
+    #[test]
+    fn default_templates_are_set_for_every_multiplexer() {
+        let config = SplitConfig::default();
+        for mux in [Multiplexer::Tmux, Multiplexer::Wezterm, Multiplexer::Kitty] {
+            assert!(mux.template(&config).contains("{file}"));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! GitHub pushes held back by a rate limit.
+//!
+//! When GitHub keeps refusing a push after [`crate::github::RATE_LIMIT_BACKOFF`],
+//! the comments still to go are recorded in `outbox.json` next to
+//! `github-comments.json` instead of being counted as failed, and the app
+//! pushes again once `retry_at` has passed. The comments themselves stay
+//! unsynced in `github-comments.json`; the outbox only says what was queued
+//! and when to try again.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+pub const OUTBOX_FILE: &str = "outbox.json";
+
+/// How long to leave GitHub alone after a rate limit.
+pub const RATE_LIMIT_COOLDOWN_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Outbox {
+    pub comment_ids: Vec<String>,
+    /// Unix seconds
+    pub retry_at: u64,
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Outbox {
+    /// Queue `comment_ids` for a retry after the cooldown.
+    pub fn queue(comment_ids: Vec<String>, now: u64) -> Self {
+        Outbox {
+            comment_ids,
+            retry_at: now + RATE_LIMIT_COOLDOWN_SECS,
+        }
+    }
+
+    pub fn path(dir: &str) -> PathBuf {
+        Path::new(dir).join(OUTBOX_FILE)
+    }
+
+    /// The queue in `dir`; `None` when there is nothing waiting.
+    pub fn load(dir: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::path(dir)).ok()?;
+        serde_json::from_str::<Outbox>(&content)
+            .ok()
+            .filter(|o| !o.comment_ids.is_empty())
+    }
+
+    pub fn save(&self, dir: &str) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let path = Self::path(dir);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    pub fn clear(dir: &str) {
+        let _ = std::fs::remove_file(Self::path(dir));
+    }
+
+    pub fn due(&self, now: u64) -> bool {
+        now >= self.retry_at
+    }
+
+    pub fn seconds_left(&self, now: u64) -> u64 {
+        self.retry_at.saturating_sub(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_round_trips_and_comes_due_after_the_cooldown() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_string_lossy().to_string();
+        assert!(Outbox::load(&dir).is_none());
+
+        let outbox = Outbox::queue(vec!["c-1".into(), "c-2".into()], 1_000);
+        outbox.save(&dir).unwrap();
+        let loaded = Outbox::load(&dir).unwrap();
+        assert_eq!(loaded, outbox);
+        assert!(!loaded.due(1_000));
+        assert_eq!(loaded.seconds_left(1_010), RATE_LIMIT_COOLDOWN_SECS - 10);
+        assert!(loaded.due(1_000 + RATE_LIMIT_COOLDOWN_SECS));
+
+        Outbox::clear(&dir);
+        assert!(Outbox::load(&dir).is_none());
+    }
+}
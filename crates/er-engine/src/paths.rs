@@ -41,6 +41,35 @@ impl ErRoot {
         }
     }
 
+    /// Files skipped from review, with the reason (see `skip_list`).
+    pub fn skipped_path(&self) -> String {
+        match self {
+            ErRoot::RepoLocal(repo_root) => format!("{repo_root}/.er/skipped"),
+            ErRoot::Managed { session_dir, .. } => format!("{session_dir}/skipped"),
+        }
+    }
+
+    /// Files assigned to a reviewer (see `multi_select`).
+    pub fn assigned_path(&self) -> String {
+        match self {
+            ErRoot::RepoLocal(repo_root) => format!("{repo_root}/.er/assigned"),
+            ErRoot::Managed { session_dir, .. } => format!("{session_dir}/assigned"),
+        }
+    }
+
+    /// Viewed files GitHub reported at the last sync (see `viewed_sync`).
+    pub fn github_viewed_path(&self) -> String {
+        match self {
+            ErRoot::RepoLocal(repo_root) => format!("{repo_root}/.er/github-viewed"),
+            ErRoot::Managed { session_dir, .. } => format!("{session_dir}/github-viewed"),
+        }
+    }
+
+    /// Directory for `.patch` files saved from the copy hub.
+    pub fn patches_dir(&self) -> String {
+        format!("{}/patches", self.er_dir())
+    }
+
     /// Directory for file snapshots (watched-file baseline copies).
     pub fn snapshots_dir(&self) -> String {
         format!("{}/snapshots", self.er_dir())
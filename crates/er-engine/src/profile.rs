@@ -0,0 +1,206 @@
+//! Hot-path timing for `er --profile` / `er --stats`.
+//!
+//! Off by default, where a [`scope`] costs one atomic load. Once [`enable`]d
+//! every scope adds its wall time to a per-phase total (git diff, parse,
+//! highlight, AI load, …) that [`report`] prints as a table. With the
+//! `tracing` feature each scope also opens a `tracing` span named after its
+//! phase, so any subscriber sees the same breakdown.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PHASES: Mutex<Vec<PhaseTotal>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTotal {
+    pub name: &'static str,
+    pub total: Duration,
+    pub calls: u64,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Times until dropped. Phases with the same name accumulate.
+pub struct Scope {
+    name: &'static str,
+    started: Option<Instant>,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+pub fn scope(name: &'static str) -> Scope {
+    Scope {
+        name,
+        started: enabled().then(Instant::now),
+        #[cfg(feature = "tracing")]
+        _span: tracing::info_span!("er", phase = name).entered(),
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some(started) = self.started {
+            record(self.name, started.elapsed());
+        }
+    }
+}
+
+/// Add `elapsed` to phase `name` (no-op while profiling is off).
+pub fn record(name: &'static str, elapsed: Duration) {
+    if !enabled() {
+        return;
+    }
+    let Ok(mut phases) = PHASES.lock() else {
+        return;
+    };
+    match phases.iter_mut().find(|p| p.name == name) {
+        Some(phase) => {
+            phase.total += elapsed;
+            phase.calls += 1;
+        }
+        None => phases.push(PhaseTotal {
+            name,
+            total: elapsed,
+            calls: 1,
+        }),
+    }
+}
+
+/// Phase totals so far, slowest first.
+pub fn snapshot() -> Vec<PhaseTotal> {
+    let mut phases = PHASES.lock().map(|p| p.clone()).unwrap_or_default();
+    phases.sort_by_key(|p| std::cmp::Reverse(p.total));
+    phases
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Table of `phases` against a wall-clock `total`. Phases nest (a refresh
+/// contains its git diff and parse), so shares can add up past 100%.
+pub fn report(phases: &[PhaseTotal], total: Duration) -> String {
+    let width = phases
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let mut out = format!(
+        "{:<width$}  {:>10}  {:>7}  {:>5}\n",
+        "phase", "ms", "calls", "%"
+    );
+    for p in phases {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            p.total.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        out.push_str(&format!(
+            "{:<width$}  {:>10.1}  {:>7}  {:>5.1}\n",
+            p.name,
+            ms(p.total),
+            p.calls,
+            share
+        ));
+    }
+    out.push_str(&format!("{:<width$}  {:>10.1}\n", "total", ms(total)));
+    out
+}
+
+/// Rolling frame times for the `--profile` overlay.
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    samples: VecDeque<Duration>,
+    frames: u64,
+}
+
+const FRAME_WINDOW: usize = 120;
+
+impl FrameStats {
+    pub fn push(&mut self, frame: Duration) {
+        if self.samples.len() == FRAME_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(frame);
+        self.frames += 1;
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn last(&self) -> Duration {
+        self.samples.back().copied().unwrap_or_default()
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+
+    /// One-line summary: `frame 2.1ms · avg 1.8 · max 9.4 (120)`.
+    pub fn summary(&self) -> String {
+        format!(
+            "frame {:.1}ms · avg {:.1} · max {:.1} ({})",
+            ms(self.last()),
+            ms(self.average()),
+            ms(self.max()),
+            self.samples.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_phases_with_share() {
+        let phases = vec![
+            PhaseTotal {
+                name: "git diff",
+                total: Duration::from_millis(30),
+                calls: 1,
+            },
+            PhaseTotal {
+                name: "parse",
+                total: Duration::from_millis(10),
+                calls: 2,
+            },
+        ];
+        let out = report(&phases, Duration::from_millis(100));
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[1].starts_with("git diff") && lines[1].ends_with("30.0"));
+        assert!(lines[2].contains("      2"));
+        assert!(lines[3].starts_with("total") && lines[3].ends_with("100.0"));
+    }
+
+    #[test]
+    fn frame_stats_keep_a_rolling_window() {
+        let mut stats = FrameStats::default();
+        for ms in 1..=200u64 {
+            stats.push(Duration::from_millis(ms));
+        }
+        assert_eq!(stats.frames(), 200);
+        assert_eq!(stats.last(), Duration::from_millis(200));
+        assert_eq!(stats.max(), Duration::from_millis(200));
+        // Window holds 81..=200
+        assert_eq!(stats.average(), Duration::from_micros(140_500));
+    }
+}
@@ -45,12 +45,13 @@ fn now_epoch_ms() -> u64 {
 /// Normalize a GitHub remote reference to a lowercase `owner/repo` slug.
 pub fn normalize_remote_slug(remote: &str) -> String {
     let trimmed = remote.trim();
-    let without_scheme = trimmed
-        .strip_prefix("https://github.com/")
-        .or_else(|| trimmed.strip_prefix("http://github.com/"))
-        .or_else(|| trimmed.strip_prefix("git@github.com:"))
-        .unwrap_or(trimmed);
-    without_scheme
+    // Any host, so Enterprise remotes pin the same as github.com ones
+    if trimmed.contains("://") || trimmed.contains('@') {
+        if let Some((owner, repo)) = crate::github::parse_remote_url(trimmed) {
+            return format!("{}/{}", owner, repo).to_ascii_lowercase();
+        }
+    }
+    trimmed
         .trim_end_matches(".git")
         .trim_matches('/')
         .to_ascii_lowercase()
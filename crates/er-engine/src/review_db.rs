@@ -0,0 +1,636 @@
+//! SQLite index of review data (`[storage] backend = "sqlite"`, built with
+//! the `sqlite` feature).
+//!
+//! The JSON sidecars stay the source of truth — agents and other er builds
+//! read and write them — and every comment sidecar er writes or loads, each
+//! AI review run with its findings, and the session records `er stats` reads
+//! are mirrored into `review.db` under [`crate::storage::artifact_root`].
+//! Questions like "every unresolved comment by alice" or "every high-risk
+//! finding" become one query instead of reading every bucket; `er comments`
+//! and the review dashboard ask it that way.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use crate::ai::comments::{ErGitHubComments, ErNotes, ErQuestions};
+use crate::ai::store::{self, IndexedComment, Sidecar};
+use crate::ai::{ErReview, RiskLevel};
+use crate::usage::UsageRecord;
+
+/// File name under the artifact root.
+const DB_FILE: &str = "review.db";
+
+const SCHEMA: &str = "
+PRAGMA journal_mode = WAL;
+PRAGMA synchronous = NORMAL;
+CREATE TABLE IF NOT EXISTS comments (
+    dir TEXT NOT NULL,
+    sidecar TEXT NOT NULL,
+    id TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER,
+    author TEXT NOT NULL,
+    text TEXT NOT NULL,
+    resolved INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    PRIMARY KEY (dir, sidecar, id)
+);
+CREATE INDEX IF NOT EXISTS comments_open ON comments (resolved, author);
+CREATE TABLE IF NOT EXISTS review_runs (
+    dir TEXT NOT NULL,
+    diff_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    files INTEGER NOT NULL,
+    findings INTEGER NOT NULL,
+    PRIMARY KEY (dir, diff_hash)
+);
+CREATE TABLE IF NOT EXISTS findings (
+    dir TEXT NOT NULL,
+    diff_hash TEXT NOT NULL,
+    file TEXT NOT NULL,
+    id TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    category TEXT NOT NULL,
+    title TEXT NOT NULL,
+    line INTEGER,
+    PRIMARY KEY (dir, file, id)
+);
+CREATE INDEX IF NOT EXISTS findings_severity ON findings (severity);
+CREATE TABLE IF NOT EXISTS sessions (
+    started INTEGER NOT NULL,
+    ended INTEGER NOT NULL,
+    repo TEXT NOT NULL,
+    branch TEXT NOT NULL,
+    pr INTEGER,
+    active_secs INTEGER NOT NULL,
+    files INTEGER NOT NULL,
+    adds INTEGER NOT NULL,
+    dels INTEGER NOT NULL,
+    reviewed INTEGER NOT NULL,
+    comments INTEGER NOT NULL,
+    PRIMARY KEY (started, repo, branch)
+);
+";
+
+/// Index writes are on (`[storage] backend = "sqlite"`).
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The process's connection, opened on first use.
+static DB: Mutex<Option<ReviewDb>> = Mutex::new(None);
+
+/// A comment in the index, with the sidecar directory it lives in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentRow {
+    pub dir: String,
+    /// `questions.json`, `notes.json` or `github-comments.json`
+    pub sidecar: String,
+    pub comment: IndexedComment,
+}
+
+/// One AI review of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewRun {
+    pub diff_hash: String,
+    pub created_at: String,
+    pub files: usize,
+    pub findings: usize,
+}
+
+/// An AI finding in the index, from the latest review of its dir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindingRow {
+    pub dir: String,
+    pub diff_hash: String,
+    pub file: String,
+    pub id: String,
+    /// `high`, `medium`, `low` or `info`
+    pub severity: String,
+    pub category: String,
+    pub title: String,
+    pub line: Option<usize>,
+}
+
+/// Filter for [`ReviewDb::findings`].
+#[derive(Debug, Clone, Default)]
+pub struct FindingQuery {
+    pub severity: Option<RiskLevel>,
+    /// Only sidecar dirs under this one
+    pub dir_prefix: Option<String>,
+}
+
+/// `high`, as review.json spells it.
+fn severity_name(severity: RiskLevel) -> String {
+    serde_json::to_value(severity)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Filter for [`ReviewDb::comments`].
+#[derive(Debug, Clone, Default)]
+pub struct CommentQuery {
+    pub unresolved: bool,
+    /// Exact author, case-insensitive
+    pub author: Option<String>,
+    /// Only sidecar dirs under this one
+    pub dir_prefix: Option<String>,
+}
+
+pub struct ReviewDb {
+    conn: Connection,
+}
+
+impl ReviewDb {
+    /// Where the index lives for the current storage location.
+    pub fn default_path() -> PathBuf {
+        crate::storage::artifact_root().join(DB_FILE)
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let conn =
+            Connection::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        conn.execute_batch(SCHEMA)
+            .with_context(|| format!("Failed to set up {}", path.display()))?;
+        Ok(ReviewDb { conn })
+    }
+
+    /// The index for the current storage location; a new one is filled from
+    /// the sidecars and usage records already on disk.
+    pub fn open_default() -> Result<Self> {
+        let path = Self::default_path();
+        let fresh = !path.exists();
+        let mut db = Self::open(&path)?;
+        if fresh {
+            db.reindex(&crate::storage::artifact_root())?;
+            db.index_sessions(&crate::usage::load_since(0))?;
+        }
+        Ok(db)
+    }
+
+    /// Replace the comments of `sidecar` in `dir`.
+    pub fn index_comments(
+        &mut self,
+        dir: &str,
+        sidecar: &str,
+        comments: &[IndexedComment],
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM comments WHERE dir = ?1 AND sidecar = ?2",
+            params![dir, sidecar],
+        )?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO comments
+                 (dir, sidecar, id, file, line, author, text, resolved, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for c in comments {
+                insert.execute(params![
+                    dir,
+                    sidecar,
+                    c.id,
+                    c.file,
+                    c.line.map(|l| l as i64),
+                    c.author,
+                    c.text,
+                    c.resolved,
+                    c.timestamp
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record the review run in `dir` and replace the dir's findings with
+    /// its.
+    pub fn index_review(&mut self, dir: &str, review: &ErReview) -> Result<()> {
+        let findings: usize = review.files.values().map(|f| f.findings.len()).sum();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO review_runs (dir, diff_hash, created_at, files, findings)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                dir,
+                review.diff_hash,
+                review.created_at,
+                review.files.len() as i64,
+                findings as i64
+            ],
+        )?;
+        tx.execute("DELETE FROM findings WHERE dir = ?1", params![dir])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO findings
+                 (dir, diff_hash, file, id, severity, category, title, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (file, file_review) in &review.files {
+                for f in &file_review.findings {
+                    insert.execute(params![
+                        dir,
+                        review.diff_hash,
+                        file,
+                        f.id,
+                        severity_name(f.severity),
+                        f.category,
+                        f.title,
+                        f.line_start.map(|l| l as i64)
+                    ])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Add usage sessions; one already indexed is replaced.
+    pub fn index_sessions(&mut self, records: &[UsageRecord]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO sessions
+                 (started, ended, repo, branch, pr, active_secs, files, adds, dels, reviewed, comments)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            )?;
+            for r in records {
+                insert.execute(params![
+                    r.started as i64,
+                    r.ended as i64,
+                    r.repo,
+                    r.branch,
+                    r.pr.map(|n| n as i64),
+                    r.active_secs as i64,
+                    r.files as i64,
+                    r.adds as i64,
+                    r.dels as i64,
+                    r.reviewed as i64,
+                    r.comments
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Index the sidecars in `dir`. Returns whether it had any.
+    pub fn index_dir(&mut self, dir: &str) -> Result<bool> {
+        let mut any = self.index_sidecar::<ErQuestions>(dir)?;
+        any |= self.index_sidecar::<ErNotes>(dir)?;
+        any |= self.index_sidecar::<ErGitHubComments>(dir)?;
+        let review = Path::new(dir).join("review.json");
+        if let Some(review) = std::fs::read_to_string(review)
+            .ok()
+            .and_then(|content| serde_json::from_str::<ErReview>(&content).ok())
+        {
+            self.index_review(dir, &review)?;
+            any = true;
+        }
+        Ok(any)
+    }
+
+    fn index_sidecar<T: Sidecar>(&mut self, dir: &str) -> Result<bool> {
+        // Unreadable files are left out, as the loader leaves them out
+        let Some(comments) = store::load::<T>(dir)
+            .ok()
+            .flatten()
+            .and_then(|value| value.indexed_comments())
+        else {
+            return Ok(false);
+        };
+        self.index_comments(dir, T::FILE, &comments)?;
+        Ok(true)
+    }
+
+    /// Index every sidecar dir under `root`. Returns how many had sidecars.
+    pub fn reindex(&mut self, root: &Path) -> Result<usize> {
+        let mut count = 0;
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    pending.push(entry.path());
+                }
+            }
+            if self.index_dir(&dir.to_string_lossy())? {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Comments matching `query`, newest first.
+    pub fn comments(&self, query: &CommentQuery) -> Result<Vec<CommentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dir, sidecar, id, file, line, author, text, resolved, timestamp
+             FROM comments
+             WHERE (?1 = 0 OR resolved = 0)
+               AND (?2 IS NULL OR lower(author) = lower(?2))
+               AND (?3 IS NULL OR substr(dir, 1, length(?3)) = ?3)
+             ORDER BY timestamp DESC, dir, id",
+        )?;
+        let rows = stmt.query_map(
+            params![query.unresolved, query.author, query.dir_prefix],
+            |row| {
+                Ok(CommentRow {
+                    dir: row.get(0)?,
+                    sidecar: row.get(1)?,
+                    comment: IndexedComment {
+                        id: row.get(2)?,
+                        file: row.get(3)?,
+                        line: row.get::<_, Option<i64>>(4)?.map(|l| l as usize),
+                        author: row.get(5)?,
+                        text: row.get(6)?,
+                        resolved: row.get(7)?,
+                        timestamp: row.get(8)?,
+                    },
+                })
+            },
+        )?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Findings matching `query`, by dir, file and line.
+    pub fn findings(&self, query: &FindingQuery) -> Result<Vec<FindingRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT dir, diff_hash, file, id, severity, category, title, line
+             FROM findings
+             WHERE (?1 IS NULL OR severity = ?1)
+               AND (?2 IS NULL OR substr(dir, 1, length(?2)) = ?2)
+             ORDER BY dir, file, line, id",
+        )?;
+        let severity = query.severity.map(severity_name);
+        let rows = stmt.query_map(params![severity, query.dir_prefix], |row| {
+            Ok(FindingRow {
+                dir: row.get(0)?,
+                diff_hash: row.get(1)?,
+                file: row.get(2)?,
+                id: row.get(3)?,
+                severity: row.get(4)?,
+                category: row.get(5)?,
+                title: row.get(6)?,
+                line: row.get::<_, Option<i64>>(7)?.map(|l| l as usize),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Sessions that ended at or after `since` (unix seconds), newest
+    /// first. Feature counts aren't indexed and come back empty.
+    pub fn sessions(&self, since: u64) -> Result<Vec<UsageRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT started, ended, repo, branch, pr, active_secs, files, adds, dels, reviewed, comments
+             FROM sessions WHERE ended >= ?1 ORDER BY ended DESC",
+        )?;
+        let rows = stmt.query_map(params![since as i64], |row| {
+            Ok(UsageRecord {
+                started: row.get::<_, i64>(0)? as u64,
+                ended: row.get::<_, i64>(1)? as u64,
+                repo: row.get(2)?,
+                branch: row.get(3)?,
+                pr: row.get::<_, Option<i64>>(4)?.map(|n| n as u64),
+                active_secs: row.get::<_, i64>(5)? as u64,
+                files: row.get::<_, i64>(6)? as usize,
+                adds: row.get::<_, i64>(7)? as usize,
+                dels: row.get::<_, i64>(8)? as usize,
+                reviewed: row.get::<_, i64>(9)? as usize,
+                comments: row.get(10)?,
+                features: Default::default(),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Unresolved comments across `dirs`.
+    pub fn unresolved_count(&self, dirs: &[&str]) -> Result<usize> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT count(*) FROM comments WHERE dir = ?1 AND resolved = 0")?;
+        let mut total = 0;
+        for dir in dirs {
+            let count: i64 = stmt.query_row(params![dir], |row| row.get(0))?;
+            total += count as usize;
+        }
+        Ok(total)
+    }
+
+    /// AI reviews recorded for `dir`, newest first.
+    pub fn review_runs(&self, dir: &str) -> Result<Vec<ReviewRun>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT diff_hash, created_at, files, findings FROM review_runs
+             WHERE dir = ?1 ORDER BY created_at DESC",
+        )?;
+        let rows = stmt.query_map(params![dir], |row| {
+            Ok(ReviewRun {
+                diff_hash: row.get(0)?,
+                created_at: row.get(1)?,
+                files: row.get::<_, i64>(2)? as usize,
+                findings: row.get::<_, i64>(3)? as usize,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+/// Turn index writes on or off for this process.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Run `f` on the process's index when it's enabled. Failures are logged,
+/// never surfaced: the JSON files are already written.
+pub fn with_db<R>(f: impl FnOnce(&mut ReviewDb) -> Result<R>) -> Option<R> {
+    if !enabled() {
+        return None;
+    }
+    let mut guard = DB.lock().ok()?;
+    if guard.is_none() {
+        match ReviewDb::open_default() {
+            Ok(db) => *guard = Some(db),
+            Err(e) => {
+                crate::debug_log::warn("review db", format!("{:#}", e));
+                set_enabled(false);
+                return None;
+            }
+        }
+    }
+    let db = guard.as_mut()?;
+    f(db)
+        .map_err(|e| crate::debug_log::warn("review db", format!("{:#}", e)))
+        .ok()
+}
+
+/// Mirror `value`, just written to or read from `dir`, into the index.
+pub fn record<T: Sidecar>(dir: &str, value: &T) {
+    let Some(comments) = value.indexed_comments() else {
+        return;
+    };
+    with_db(|db| db.index_comments(dir, T::FILE, &comments));
+}
+
+/// Note the AI review in `dir`, with its findings.
+pub fn record_review(dir: &str, review: &ErReview) {
+    with_db(|db| db.index_review(dir, review));
+}
+
+/// Note usage sessions just appended to the usage file.
+pub fn record_sessions(records: &[UsageRecord]) {
+    with_db(|db| db.index_sessions(records));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::comments::ReviewQuestion;
+
+    fn question(id: &str, author: &str, resolved: bool) -> ReviewQuestion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "timestamp": format!("2026-01-0{}T00:00:00Z", id.len()),
+            "file": "src/lib.rs",
+            "hunk_index": 0,
+            "line_start": 3,
+            "text": format!("{} asks", author),
+            "resolved": resolved,
+            "author": author,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_unresolved_comments_by_author_across_buckets() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let branch = tmp.path().join("repos/r/branches/main/view-buckets/branch");
+        let pr = tmp.path().join("repos/r/prs/pr-7");
+        let branch = branch.to_string_lossy().into_owned();
+        let pr = pr.to_string_lossy().into_owned();
+        let mut questions = ErQuestions::empty("h");
+        questions.questions = vec![
+            question("q1", "alice", false),
+            question("q22", "bob", false),
+        ];
+        store::save(&branch, &questions).unwrap();
+        let mut notes = ErNotes::empty("h");
+        notes.notes = vec![
+            question("n1", "Alice", false),
+            question("n2", "alice", true),
+        ];
+        store::save(&pr, &notes).unwrap();
+
+        let mut db = ReviewDb::open(&tmp.path().join(DB_FILE)).unwrap();
+        assert_eq!(db.reindex(tmp.path()).unwrap(), 2);
+        let by_alice = db
+            .comments(&CommentQuery {
+                unresolved: true,
+                author: Some("alice".into()),
+                dir_prefix: None,
+            })
+            .unwrap();
+        let ids: Vec<&str> = by_alice.iter().map(|r| r.comment.id.as_str()).collect();
+        assert_eq!(ids, ["q1", "n1"]);
+        assert_eq!(db.unresolved_count(&[&branch, &pr]).unwrap(), 3);
+
+        // Re-indexing a sidecar replaces its rows
+        questions.questions.truncate(1);
+        db.index_comments(
+            &branch,
+            ErQuestions::FILE,
+            &questions.indexed_comments().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(db.unresolved_count(&[&branch]).unwrap(), 1);
+    }
+
+    #[test]
+    fn indexes_findings_and_sessions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("repos/r/prs/pr-7");
+        let dir = dir.to_string_lossy().into_owned();
+        let review: ErReview = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "diff_hash": "h1",
+            "created_at": "2026-01-01T00:00:00Z",
+            "files": {
+                "src/lib.rs": {
+                    "risk": "high",
+                    "findings": [
+                        {"id": "f1", "severity": "high", "title": "Unchecked index", "line_start": 9},
+                        {"id": "f2", "severity": "low", "title": "Naming"}
+                    ]
+                }
+            }
+        }))
+        .unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            Path::new(&dir).join("review.json"),
+            serde_json::to_string(&review).unwrap(),
+        )
+        .unwrap();
+
+        let mut db = ReviewDb::open(&tmp.path().join(DB_FILE)).unwrap();
+        assert_eq!(db.reindex(tmp.path()).unwrap(), 1);
+        let high = db
+            .findings(&FindingQuery {
+                severity: Some(RiskLevel::High),
+                dir_prefix: None,
+            })
+            .unwrap();
+        assert_eq!(high.len(), 1);
+        assert_eq!(
+            (high[0].id.as_str(), high[0].line, high[0].severity.as_str()),
+            ("f1", Some(9), "high")
+        );
+        assert_eq!(db.review_runs(&dir).unwrap()[0].findings, 2);
+
+        // A newer review replaces the dir's findings
+        let mut newer = review.clone();
+        newer.diff_hash = "h2".into();
+        newer
+            .files
+            .get_mut("src/lib.rs")
+            .unwrap()
+            .findings
+            .truncate(1);
+        db.index_review(&dir, &newer).unwrap();
+        let all = db.findings(&FindingQuery::default()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].diff_hash, "h2");
+
+        let session = |started: u64, ended: u64| UsageRecord {
+            started,
+            ended,
+            repo: "r".into(),
+            branch: "main".into(),
+            active_secs: 60,
+            files: 3,
+            reviewed: 2,
+            comments: 1,
+            ..Default::default()
+        };
+        db.index_sessions(&[session(100, 200), session(300, 400)])
+            .unwrap();
+        // Indexing the same session again doesn't duplicate it
+        db.index_sessions(&[session(300, 400)]).unwrap();
+        let recent = db.sessions(150).unwrap();
+        let ended: Vec<u64> = recent.iter().map(|r| r.ended).collect();
+        assert_eq!(ended, [400, 200]);
+        assert_eq!(recent[0], session(300, 400));
+        assert!(db.sessions(500).unwrap().is_empty());
+    }
+}
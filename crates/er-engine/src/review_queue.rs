@@ -475,6 +475,73 @@ pub fn filter_failing_ci(prs: &[QueuePr], limit: usize) -> Vec<RankedPr> {
     ranked
 }
 
+/// Where a PR stands against the review SLA (`[github] review_sla_hours`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaState {
+    /// More than a quarter of the SLA left
+    OnTrack,
+    /// Within the last quarter
+    DueSoon,
+    Overdue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlaStatus {
+    /// Seconds since the PR was opened
+    pub elapsed_secs: u64,
+    /// Seconds until the SLA; negative once it has passed
+    pub remaining_secs: i64,
+    pub state: SlaState,
+}
+
+impl SlaStatus {
+    /// `open 20h · 4h left` / `open 1d6h · 6h over`
+    pub fn label(&self) -> String {
+        let left = format_age(self.remaining_secs.unsigned_abs());
+        if self.remaining_secs < 0 {
+            format!("open {} · {} over", format_age(self.elapsed_secs), left)
+        } else {
+            format!("open {} · {} left", format_age(self.elapsed_secs), left)
+        }
+    }
+}
+
+/// SLA standing of a PR opened at `created_at` (GitHub `createdAt`), or
+/// `None` when the SLA is off or the timestamp unreadable.
+pub fn sla_status(created_at: &str, sla_hours: u64, now_epoch_secs: i64) -> Option<SlaStatus> {
+    if sla_hours == 0 {
+        return None;
+    }
+    let created = parse_github_updated_at(created_at)?;
+    let elapsed = now_epoch_secs.saturating_sub(created).max(0);
+    let sla = (sla_hours * 3600) as i64;
+    let remaining = sla - elapsed;
+    let state = if remaining < 0 {
+        SlaState::Overdue
+    } else if remaining * 4 <= sla {
+        SlaState::DueSoon
+    } else {
+        SlaState::OnTrack
+    };
+    Some(SlaStatus {
+        elapsed_secs: elapsed as u64,
+        remaining_secs: remaining,
+        state,
+    })
+}
+
+/// Compact duration: `45m`, `20h`, `3d4h`.
+pub fn format_age(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, h) => format!("{}h", h),
+        (d, 0) => format!("{}d", d),
+        (d, h) => format!("{}d{}h", d, h),
+    }
+}
+
 /// Summary of review-thread addressing for "already fixed" detection.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ThreadAddressingSummary {
@@ -539,7 +606,7 @@ pub fn open_in_easy_review(owner: &str, repo: &str, number: u64) -> OpenInEasyRe
         owner: owner.to_string(),
         repo: repo.to_string(),
         number,
-        github_url: format!("https://github.com/{owner}/{repo}/pull/{number}"),
+        github_url: crate::github::web_url(&format!("{owner}/{repo}/pull/{number}")),
         desktop_hint: format!(
             "In Easy Review Desktop: open project for {owner}/{repo}, then open PR #{number} from the sidebar (or paste the GitHub URL)."
         ),
@@ -673,4 +740,23 @@ mod tests {
         p3.merge_state_status = Some("BLOCKED".into());
         assert!(is_blocked(&p3));
     }
+
+    #[test]
+    fn sla_turns_due_soon_in_the_last_quarter_then_overdue() {
+        let opened = parse_github_updated_at("2026-07-01T00:00:00Z").unwrap();
+        let at = |hours: i64| sla_status("2026-07-01T00:00:00Z", 24, opened + hours * 3600);
+
+        let fresh = at(2).unwrap();
+        assert_eq!(fresh.state, SlaState::OnTrack);
+        assert_eq!(fresh.label(), "open 2h · 22h left");
+        assert_eq!(at(18).unwrap().state, SlaState::DueSoon);
+        let late = at(30).unwrap();
+        assert_eq!(late.state, SlaState::Overdue);
+        assert_eq!(late.label(), "open 1d6h · 6h over");
+
+        assert!(sla_status("2026-07-01T00:00:00Z", 0, opened).is_none());
+        assert!(sla_status("", 24, opened).is_none());
+        assert_eq!(format_age(45 * 60), "45m");
+        assert_eq!(format_age(2 * 86_400), "2d");
+    }
 }
@@ -0,0 +1,492 @@
+//! Headless access to a local branch review — the same sidecars the TUI shows.
+//!
+//! Used by the MCP server so agents can read findings and comments, add
+//! comments, mark files reviewed and fetch hunks for the live review. Reads and
+//! writes go to the branch view bucket (and the PR bucket for comments when a
+//! PR number is given), exactly where a running `er` polls for changes, so
+//! edits appear in the TUI within a second.
+
+use crate::ai::{self, ErGitHubComments, Finding, GitHubReviewComment};
+use crate::file_lock;
+use crate::git;
+use crate::paths::ErRoot;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static COMMENT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// A local branch review resolved from a repo path.
+#[derive(Debug, Clone)]
+pub struct ReviewSession {
+    pub repo_root: String,
+    pub branch: String,
+    pub base_branch: String,
+    pub pr_number: Option<u64>,
+    er_root: ErRoot,
+    comments_dir: String,
+}
+
+/// A finding with the file it belongs to.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionFinding {
+    pub file: String,
+    #[serde(flatten)]
+    pub finding: Finding,
+}
+
+/// One diff hunk, as text plus its ranges.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionHunk {
+    pub index: usize,
+    pub header: String,
+    pub old_start: usize,
+    pub old_count: usize,
+    pub new_start: usize,
+    pub new_count: usize,
+    pub text: String,
+}
+
+impl ReviewSession {
+    /// Resolve the review for the branch checked out at `repo_path`.
+    /// `base` overrides base-branch detection; `pr` routes comments to the PR
+    /// bucket (matching a local PR tab in the TUI).
+    pub fn open(repo_path: &str, base: Option<&str>, pr: Option<u64>) -> Result<Self> {
+        let repo_root = git::get_repo_root_in(repo_path)
+            .with_context(|| format!("Not a git repository: {}", repo_path))?;
+        let branch = git::get_current_branch_in(&repo_root)?;
+        let base_branch = match base {
+            Some(b) if !b.trim().is_empty() => b.trim().to_string(),
+            _ => git::detect_base_branch_in(&repo_root)?,
+        };
+
+        let er_root = if crate::storage::use_repo_local_storage() {
+            ErRoot::RepoLocal(repo_root.clone())
+        } else {
+            crate::storage::resolve_managed_root_for_view_bucket(
+                &crate::storage::slug_repo(&repo_root),
+                &crate::storage::slug_branch(&branch),
+                "branch",
+            )
+        };
+        let comments_dir = match pr {
+            Some(n) if !crate::storage::use_repo_local_storage() => {
+                crate::github::canonical_owner_repo_slug(&repo_root)
+                    .map(|slug| {
+                        crate::storage::pr_bucket_dir(&slug, n)
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                    .unwrap_or_else(|| er_root.er_dir())
+            }
+            _ => er_root.er_dir(),
+        };
+
+        Ok(Self {
+            repo_root,
+            branch,
+            base_branch,
+            pr_number: pr,
+            er_root,
+            comments_dir,
+        })
+    }
+
+    pub fn er_dir(&self) -> String {
+        self.er_root.er_dir()
+    }
+
+    fn raw_diff(&self) -> Result<String> {
+        git::git_diff_raw("branch", &self.base_branch, &self.repo_root, None)
+    }
+
+    fn comments_path(&self) -> String {
+        format!("{}/github-comments.json", self.comments_dir)
+    }
+
+    /// AI findings from `review.json` (plus merged expert sidecars), optionally
+    /// limited to one file. Findings on files whose diff changed since the
+    /// review (stale, as the TUI shows them) are left out. Ordered by file,
+    /// then hunk/line.
+    pub fn findings(&self, file: Option<&str>) -> Result<Vec<SessionFinding>> {
+        let raw = self.raw_diff()?;
+        let state = ai::load_ai_state(
+            &self.er_dir(),
+            &ai::compute_diff_hash(&raw),
+            Some(&self.branch),
+        );
+        let current = ai::compute_per_file_hashes(&raw);
+        let mut out: Vec<SessionFinding> = state
+            .review
+            .map(|r| {
+                let file_hashes = r.file_hashes;
+                let stale = move |path: &str| {
+                    !file_hashes.is_empty() && file_hashes.get(path) != current.get(path)
+                };
+                r.files
+                    .into_iter()
+                    .filter(|(path, _)| file.is_none_or(|f| f == path))
+                    .filter(|(path, _)| !stale(path))
+                    .flat_map(|(path, fr)| {
+                        fr.findings.into_iter().map(move |finding| SessionFinding {
+                            file: path.clone(),
+                            finding,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        out.sort_by(|a, b| {
+            (&a.file, a.finding.hunk_index, a.finding.line_start).cmp(&(
+                &b.file,
+                b.finding.hunk_index,
+                b.finding.line_start,
+            ))
+        });
+        Ok(out)
+    }
+
+    /// Review comments (local and synced from GitHub), optionally for one
+    /// file. A comments file that can't be read is an error.
+    pub fn comments(&self, file: Option<&str>) -> Result<Vec<GitHubReviewComment>> {
+        Ok(ai::store::load::<ErGitHubComments>(&self.comments_dir)?
+            .map(|gc| {
+                gc.comments
+                    .into_iter()
+                    .filter(|c| file.is_none_or(|f| f == c.file))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Add a local comment on new-side `line` (..=`line_end`) of `file`, or a
+    /// general comment when `file` is empty. Anchored like comments typed in
+    /// the TUI so relocation keeps working as the diff moves.
+    pub fn add_comment(
+        &self,
+        file: &str,
+        line: Option<usize>,
+        line_end: Option<usize>,
+        body: &str,
+        author: &str,
+    ) -> Result<GitHubReviewComment> {
+        if body.trim().is_empty() {
+            bail!("Comment body is empty");
+        }
+        let raw = self.raw_diff()?;
+        let diff_hash = ai::compute_diff_hash(&raw);
+
+        let mut hunk_index = None;
+        let mut anchor = Anchor::default();
+        if !file.is_empty() {
+            let files = git::parse_diff(&raw);
+            let Some(df) = files.iter().find(|f| f.path == file) else {
+                bail!("File not in diff: {}", file);
+            };
+            if let Some(ln) = line {
+                let found = df.hunks.iter().enumerate().find_map(|(hi, h)| {
+                    h.lines
+                        .iter()
+                        .position(|l| l.new_num == Some(ln))
+                        .map(|li| (hi, li))
+                });
+                let Some((hi, li)) = found else {
+                    bail!("Line {} of {} is outside the diff", ln, file);
+                };
+                hunk_index = Some(hi);
+                anchor = Anchor::capture(&df.hunks[hi], li);
+            }
+        }
+
+        let seq = COMMENT_SEQ.fetch_add(1, Ordering::Relaxed);
+        let id = format!(
+            "c-{}-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0),
+            seq
+        );
+        let line_end = match (line, line_end) {
+            (Some(s), Some(e)) if e > s => Some(e),
+            (Some(s), _) => Some(s),
+            _ => None,
+        };
+        let comment = GitHubReviewComment {
+            id,
+            timestamp: crate::sync::chrono_now(),
+            file: file.to_string(),
+            hunk_index,
+            line_start: line,
+            line_end,
+            line_content: anchor.line_content,
+            comment: body.to_string(),
+            in_reply_to: None,
+            resolved: false,
+            source: "local".to_string(),
+            github_id: None,
+            author: author.to_string(),
+            synced: false,
+            outdated: false,
+            stale: false,
+            context_before: anchor.context_before,
+            context_after: anchor.context_after,
+            old_line_start: anchor.old_line_start,
+            hunk_header: anchor.hunk_header,
+            anchor_status: "original".to_string(),
+            relocated_at_hash: diff_hash.clone(),
+            anchor_confidence: None,
+            finding_ref: None,
+            side: "RIGHT".to_string(),
+            edit_base: None,
+            remote_body: None,
+        };
+        let add = |gc: &mut ErGitHubComments| {
+            gc.diff_hash = diff_hash.clone();
+            gc.comments.push(comment.clone());
+        };
+        // An existing file that doesn't parse (or is from a newer er) is an
+        // error rather than replaced with just this comment
+        let added = ai::store::modify::<ErGitHubComments, _, _>(&self.comments_dir, |gc| {
+            add(gc);
+            Ok(())
+        })?;
+        if added.is_none() {
+            let updated = ai::store::update(&self.comments_dir, &diff_hash, add)?;
+            if updated == crate::file_lock::Updated::StartedFresh {
+                bail!("{} is not valid JSON", self.comments_path());
+            }
+        }
+        Ok(comment)
+    }
+
+    fn read_reviewed(&self) -> HashMap<String, String> {
+        std::fs::read_to_string(self.er_root.reviewed_path())
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(|l| match l.split_once('\t') {
+                        Some((p, h)) => (p.to_string(), h.to_string()),
+                        None => (l.to_string(), String::new()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Files marked reviewed, sorted.
+    pub fn reviewed_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self.read_reviewed().into_keys().collect();
+        files.sort();
+        files
+    }
+
+    /// Mark or unmark `file` as reviewed. Stores the file's current diff hash
+    /// so the TUI auto-unmarks it when the diff changes.
+    pub fn set_reviewed(&self, file: &str, reviewed: bool) -> Result<()> {
+        let hash = if reviewed {
+            let hashes = ai::compute_per_file_hashes(&self.raw_diff()?);
+            let Some(hash) = hashes.get(file) else {
+                bail!("File not in diff: {}", file);
+            };
+            Some(hash.clone())
+        } else {
+            None
+        };
+
+        // Re-read under the lock so a concurrent writer's marks survive
+        let path = self.er_root.reviewed_path();
+        let _lock = file_lock::lock(&path)?;
+        let mut entries = self.read_reviewed();
+        match hash {
+            Some(hash) => entries.insert(file.to_string(), hash),
+            None => entries.remove(file),
+        };
+        if entries.is_empty() {
+            let _ = std::fs::remove_file(&path);
+            return Ok(());
+        }
+        let mut sorted: Vec<(String, String)> = entries.into_iter().collect();
+        sorted.sort();
+        let content = sorted
+            .iter()
+            .map(|(p, h)| format!("{}\t{}", p, h))
+            .collect::<Vec<_>>()
+            .join("\n");
+        file_lock::write_atomic(&path, &format!("{}\n", content))
+    }
+
+    /// Hunks for `file` in the branch diff.
+    pub fn hunks(&self, file: &str) -> Result<Vec<SessionHunk>> {
+        let raw = self.raw_diff()?;
+        let files = git::parse_diff(&raw);
+        let Some(df) = files.into_iter().find(|f| f.path == file) else {
+            bail!("File not in diff: {}", file);
+        };
+        Ok(df
+            .hunks
+            .iter()
+            .enumerate()
+            .map(|(index, h)| SessionHunk {
+                index,
+                header: h.header.clone(),
+                old_start: h.old_start,
+                old_count: h.old_count,
+                new_start: h.new_start,
+                new_count: h.new_count,
+                text: h.to_text(),
+            })
+            .collect())
+    }
+
+    /// Paths in the branch diff.
+    pub fn files(&self) -> Result<Vec<String>> {
+        Ok(git::parse_diff(&self.raw_diff()?)
+            .into_iter()
+            .map(|f| f.path)
+            .collect())
+    }
+}
+
+/// Comment anchor captured from the diff (mirrors the TUI's `LineAnchor`).
+#[derive(Default)]
+struct Anchor {
+    line_content: String,
+    context_before: Vec<String>,
+    context_after: Vec<String>,
+    old_line_start: Option<usize>,
+    hunk_header: String,
+}
+
+impl Anchor {
+    fn capture(hunk: &git::DiffHunk, idx: usize) -> Self {
+        let line = &hunk.lines[idx];
+        let before_start = idx.saturating_sub(3);
+        let after_end = (idx + 4).min(hunk.lines.len());
+        Self {
+            line_content: line.content.clone(),
+            context_before: hunk.lines[before_start..idx]
+                .iter()
+                .map(|l| l.content.clone())
+                .collect(),
+            context_after: hunk.lines[idx + 1..after_end]
+                .iter()
+                .map(|l| l.content.clone())
+                .collect(),
+            old_line_start: line.old_num,
+            hunk_header: hunk.header.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let ok = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@t")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@t")
+            .output()
+            .unwrap()
+            .status
+            .success();
+        assert!(ok, "git {:?}", args);
+    }
+
+    fn fixture() -> (tempfile::TempDir, tempfile::TempDir) {
+        let storage = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let p = repo.path();
+        git(p, &["init", "-q", "-b", "main"]);
+        std::fs::write(p.join("a.rs"), "one\ntwo\nthree\n").unwrap();
+        git(p, &["add", "."]);
+        git(p, &["commit", "-qm", "init"]);
+        git(p, &["checkout", "-qb", "feat"]);
+        std::fs::write(p.join("a.rs"), "one\ntwo changed\nthree\nfour\n").unwrap();
+        git(p, &["commit", "-qam", "change"]);
+        (storage, repo)
+    }
+
+    #[test]
+    fn comments_and_reviewed_round_trip() {
+        let _guard = crate::storage::STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (storage, repo) = fixture();
+        std::env::set_var("ER_STORAGE_ROOT", storage.path());
+
+        let session =
+            ReviewSession::open(&repo.path().to_string_lossy(), Some("main"), None).unwrap();
+        assert_eq!(session.branch, "feat");
+        assert_eq!(session.files().unwrap(), vec!["a.rs".to_string()]);
+
+        let hunks = session.hunks("a.rs").unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert!(hunks[0].text.contains("+two changed"));
+
+        let c = session
+            .add_comment("a.rs", Some(2), None, "Why the change?", "agent")
+            .unwrap();
+        assert_eq!(c.line_content, "two changed");
+        assert_eq!(c.hunk_index, Some(0));
+        assert_eq!(session.comments(Some("a.rs")).unwrap().len(), 1);
+        // Without a line it's a file-level comment
+        let file_level = session
+            .add_comment("a.rs", None, None, "Split this file", "agent")
+            .unwrap();
+        assert!(file_level.is_file_level());
+        assert_eq!(session.comments(None).unwrap().len(), 2);
+        assert!(session
+            .add_comment("a.rs", Some(99), None, "x", "agent")
+            .is_err());
+
+        session.set_reviewed("a.rs", true).unwrap();
+        assert_eq!(session.reviewed_files(), vec!["a.rs".to_string()]);
+        session.set_reviewed("a.rs", false).unwrap();
+        assert!(session.reviewed_files().is_empty());
+        assert!(session.set_reviewed("nope.rs", true).is_err());
+
+        assert!(session.findings(None).unwrap().is_empty());
+
+        // A finding on a file changed since the review is stale and skipped
+        let hash = ai::compute_per_file_hashes(&session.raw_diff().unwrap())["a.rs"].clone();
+        let review = |file_hash: &str| {
+            serde_json::json!({
+                "version": 1,
+                "diff_hash": "old",
+                "files": {"a.rs": {"risk": "low", "findings": [
+                    {"id": "f1", "severity": "low", "title": "Check this"}
+                ]}},
+                "file_hashes": {"a.rs": file_hash}
+            })
+            .to_string()
+        };
+        std::fs::create_dir_all(session.er_dir()).unwrap();
+        let review_path = format!("{}/review.json", session.er_dir());
+        std::fs::write(&review_path, review(&hash)).unwrap();
+        assert_eq!(session.findings(None).unwrap().len(), 1);
+        std::fs::write(&review_path, review("changed")).unwrap();
+        assert!(session.findings(None).unwrap().is_empty());
+
+        // A comments file that doesn't parse is reported, not overwritten
+        std::fs::write(session.comments_path(), "{ half written").unwrap();
+        assert!(session.comments(None).is_err());
+        assert!(session
+            .add_comment("a.rs", Some(2), None, "Lost?", "agent")
+            .is_err());
+        assert_eq!(
+            std::fs::read_to_string(session.comments_path()).unwrap(),
+            "{ half written"
+        );
+        std::env::remove_var("ER_STORAGE_ROOT");
+    }
+}
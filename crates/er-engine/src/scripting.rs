@@ -0,0 +1,464 @@
+//! Lua plugins (built with the `scripting` feature).
+//!
+//! Every `*.lua` file in `~/.config/er/scripts/` is run once at startup and
+//! can register three kinds of extension through the `er` table:
+//!
+//! ```lua
+//! er.action("Mark docs reviewed", function(state)
+//!   local docs = {}
+//!   for _, f in ipairs(state.files) do
+//!     if f.path:match("%.md$") then table.insert(docs, f.path) end
+//!   end
+//!   return { reviewed = docs, notify = #docs .. " docs marked reviewed" }
+//! end)
+//! er.filter("big", function(file) return file.adds + file.dels > 300 end)
+//! er.panel("Touched dirs", function(state) return { "src/", "docs/" } end)
+//! ```
+//!
+//! Actions and panels are listed in the Verify hub; filters are used in a
+//! filter expression as `script:big`. Scripts see a read-only snapshot of the
+//! review ([`ScriptState`], versioned by [`API_VERSION`]) and change things
+//! only through what an action returns ([`ActionResult`]), so the app state
+//! they can touch stays a small, stable surface.
+//!
+//! Scripts run on the UI thread, so every call gets [`INSTRUCTION_BUDGET`]
+//! Lua instructions; one that runs past it (a loop that never ends) fails
+//! with an error. A filter that does is turned off until the scripts reload,
+//! rather than stalling every file-tree render.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use mlua::{Function, HookTriggers, Lua, LuaSerdeExt, RegistryKey, Value, VmState};
+use serde::{Deserialize, Serialize};
+
+/// Version of the state and result shapes scripts see, as `er.api_version`.
+/// Bumped only for changes that would break existing scripts.
+pub const API_VERSION: u32 = 1;
+
+/// Lua instructions one script call (loading a script, an action, a panel
+/// or one file through a filter) may run.
+pub const INSTRUCTION_BUDGET: u32 = 10_000_000;
+
+/// Instructions between budget checks.
+const HOOK_EVERY: u32 = 10_000;
+
+/// A diff file as scripts see it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScriptFile {
+    pub path: String,
+    /// `added`, `modified`, `deleted`, `renamed`, `copied` or `unmerged`
+    pub status: String,
+    pub adds: usize,
+    pub dels: usize,
+    /// Always false for filters, which only see the diff
+    pub reviewed: bool,
+}
+
+impl ScriptFile {
+    pub fn from_diff(file: &crate::git::DiffFile, reviewed: bool) -> Self {
+        use crate::git::FileStatus;
+        let status = match file.status {
+            FileStatus::Added => "added",
+            FileStatus::Modified => "modified",
+            FileStatus::Deleted => "deleted",
+            FileStatus::Renamed(_) => "renamed",
+            FileStatus::Copied(_) => "copied",
+            FileStatus::Unmerged => "unmerged",
+        };
+        ScriptFile {
+            path: file.path.clone(),
+            status: status.to_string(),
+            adds: file.adds,
+            dels: file.dels,
+            reviewed,
+        }
+    }
+}
+
+/// The review as scripts see it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScriptState {
+    pub api_version: u32,
+    pub repo_root: String,
+    pub branch: String,
+    pub base: String,
+    pub pr: Option<u64>,
+    /// Diff mode, e.g. `branch`, `unstaged`
+    pub mode: String,
+    pub files: Vec<ScriptFile>,
+    /// Path of the selected file
+    pub selected: Option<String>,
+    /// New-side line under the cursor
+    pub line: Option<usize>,
+    pub filter: String,
+}
+
+/// What an action asks the app to do; every field is optional. An action
+/// may also return just a string, shown as [`ActionResult::notify`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ActionResult {
+    /// Status message
+    pub notify: Option<String>,
+    /// Filter expression to apply (empty clears the filter)
+    pub filter: Option<String>,
+    /// File to select, optionally `path:line`
+    pub select: Option<String>,
+    /// Files to mark reviewed
+    pub reviewed: Vec<String>,
+    /// Panel to open afterwards
+    pub panel: Option<String>,
+}
+
+/// Names registered by the loaded scripts.
+#[derive(Default)]
+struct Registry {
+    actions: Vec<(String, RegistryKey)>,
+    filters: Vec<(String, RegistryKey)>,
+    panels: Vec<(String, RegistryKey)>,
+    /// Filters that ran past the budget, off until the scripts reload
+    stalled_filters: HashSet<String>,
+}
+
+/// A Lua runtime with the scripts loaded into it.
+pub struct ScriptHost {
+    lua: Lua,
+    registry: Arc<Mutex<Registry>>,
+    /// Budget checks left for the running call
+    ticks: Arc<AtomicU32>,
+    /// Scripts that failed to load, with the error
+    pub load_errors: Vec<String>,
+}
+
+/// The process's host, loaded from the scripts dir on first use.
+static HOST: Mutex<Option<ScriptHost>> = Mutex::new(None);
+
+impl ScriptHost {
+    /// A runtime with the `er` API and no scripts.
+    pub fn new() -> Result<Self> {
+        let lua = Lua::new();
+        let registry = Arc::new(Mutex::new(Registry::default()));
+        let er = lua.create_table()?;
+        er.set("api_version", API_VERSION)?;
+        for kind in ["action", "filter", "panel"] {
+            let registry = registry.clone();
+            let register = lua.create_function(move |lua, (name, f): (String, Function)| {
+                let key = lua.create_registry_value(f)?;
+                let mut registry = registry
+                    .lock()
+                    .map_err(|_| mlua::Error::runtime("script registry poisoned"))?;
+                let list = match kind {
+                    "action" => &mut registry.actions,
+                    "filter" => &mut registry.filters,
+                    _ => &mut registry.panels,
+                };
+                // Re-registering a name replaces it
+                list.retain(|(n, _)| *n != name);
+                list.push((name, key));
+                Ok(())
+            })?;
+            er.set(kind, register)?;
+        }
+        lua.globals().set("er", er)?;
+        let ticks = Arc::new(AtomicU32::new(0));
+        let left = ticks.clone();
+        lua.set_global_hook(
+            HookTriggers::new().every_nth_instruction(HOOK_EVERY),
+            move |_, _| {
+                let before = left.fetch_sub(1, Ordering::Relaxed);
+                if before == 0 {
+                    left.store(0, Ordering::Relaxed);
+                    return Err(mlua::Error::runtime(format!(
+                        "ran past {} instructions",
+                        INSTRUCTION_BUDGET
+                    )));
+                }
+                Ok(VmState::Continue)
+            },
+        )?;
+        Ok(ScriptHost {
+            lua,
+            registry,
+            ticks,
+            load_errors: Vec::new(),
+        })
+    }
+
+    /// Give the next call a fresh budget.
+    fn start_budget(&self) {
+        self.ticks
+            .store(INSTRUCTION_BUDGET / HOOK_EVERY, Ordering::Relaxed);
+    }
+
+    /// Whether the last call ran out of budget.
+    fn over_budget(&self) -> bool {
+        self.ticks.load(Ordering::Relaxed) == 0
+    }
+
+    /// `~/.config/er/scripts`.
+    pub fn scripts_dir() -> Option<PathBuf> {
+        crate::config::global_config_dir().map(|dir| dir.join("scripts"))
+    }
+
+    /// Run every `*.lua` in `dir`, in name order. A script that fails is
+    /// recorded in [`ScriptHost::load_errors`] and the rest still load.
+    pub fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut scripts: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "lua"))
+            .collect();
+        scripts.sort();
+        for path in scripts {
+            let loaded = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))
+                .and_then(|source| self.load_source(&path.to_string_lossy(), &source));
+            if let Err(e) = loaded {
+                crate::debug_log::warn("scripts", format!("{:#}", e));
+                self.load_errors.push(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// Run one script's source, registering what it defines.
+    pub fn load_source(&self, name: &str, source: &str) -> Result<()> {
+        self.start_budget();
+        self.lua
+            .load(source)
+            .set_name(name)
+            .exec()
+            .map_err(|e| anyhow::anyhow!("{}: {}", name, e))
+    }
+
+    pub fn actions(&self) -> Vec<String> {
+        self.names(|r| &r.actions)
+    }
+
+    pub fn filters(&self) -> Vec<String> {
+        self.names(|r| &r.filters)
+    }
+
+    pub fn panels(&self) -> Vec<String> {
+        self.names(|r| &r.panels)
+    }
+
+    fn names(&self, list: impl Fn(&Registry) -> &Vec<(String, RegistryKey)>) -> Vec<String> {
+        self.registry
+            .lock()
+            .map(|r| list(&r).iter().map(|(n, _)| n.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn function(
+        &self,
+        name: &str,
+        list: impl Fn(&Registry) -> &Vec<(String, RegistryKey)>,
+    ) -> Result<Function> {
+        let registry = self
+            .registry
+            .lock()
+            .map_err(|_| anyhow::anyhow!("script registry poisoned"))?;
+        let (_, key) = list(&registry)
+            .iter()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| anyhow::anyhow!("no script registered `{}`", name))?;
+        Ok(self.lua.registry_value(key)?)
+    }
+
+    /// Run action `name` against `state`.
+    pub fn run_action(&self, name: &str, state: &ScriptState) -> Result<ActionResult> {
+        let f = self.function(name, |r| &r.actions)?;
+        self.start_budget();
+        let result: Value = f
+            .call(self.lua.to_value(state)?)
+            .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+        match result {
+            Value::Nil => Ok(ActionResult::default()),
+            Value::String(s) => Ok(ActionResult {
+                notify: Some(s.to_str()?.to_string()),
+                ..ActionResult::default()
+            }),
+            other => self
+                .lua
+                .from_value(other)
+                .map_err(|e| anyhow::anyhow!("{} returned {}", name, e)),
+        }
+    }
+
+    /// Lines of panel `name` for `state`.
+    pub fn panel_lines(&self, name: &str, state: &ScriptState) -> Result<Vec<String>> {
+        let f = self.function(name, |r| &r.panels)?;
+        self.start_budget();
+        let result: Value = f
+            .call(self.lua.to_value(state)?)
+            .map_err(|e| anyhow::anyhow!("{}: {}", name, e))?;
+        match result {
+            Value::Nil => Ok(Vec::new()),
+            Value::String(s) => Ok(s.to_str()?.lines().map(str::to_string).collect()),
+            other => self
+                .lua
+                .from_value(other)
+                .map_err(|e| anyhow::anyhow!("{} returned {}", name, e)),
+        }
+    }
+
+    /// Whether filter `name` keeps `file`. A filter that ran past the
+    /// budget once keeps nothing, without running, until the scripts reload.
+    pub fn filter(&self, name: &str, file: &ScriptFile) -> Result<bool> {
+        if self.filter_stalled(name) {
+            return Ok(false);
+        }
+        let f = self.function(name, |r| &r.filters)?;
+        self.start_budget();
+        let keep: Value = match f.call(self.lua.to_value(file)?) {
+            Ok(keep) => keep,
+            Err(e) => {
+                if self.over_budget() {
+                    if let Ok(mut registry) = self.registry.lock() {
+                        registry.stalled_filters.insert(name.to_string());
+                    }
+                    anyhow::bail!("{}: {} (filter turned off until scripts reload)", name, e);
+                }
+                anyhow::bail!("{}: {}", name, e);
+            }
+        };
+        Ok(!matches!(keep, Value::Nil | Value::Boolean(false)))
+    }
+
+    fn filter_stalled(&self, name: &str) -> bool {
+        self.registry
+            .lock()
+            .is_ok_and(|r| r.stalled_filters.contains(name))
+    }
+}
+
+/// Run `f` on the process's host, loading the scripts dir the first time.
+/// `None` when the runtime couldn't start.
+pub fn with_host<R>(f: impl FnOnce(&ScriptHost) -> R) -> Option<R> {
+    let mut guard = HOST.lock().ok()?;
+    if guard.is_none() {
+        let mut host = match ScriptHost::new() {
+            Ok(host) => host,
+            Err(e) => {
+                crate::debug_log::warn("scripts", format!("{:#}", e));
+                return None;
+            }
+        };
+        if let Some(dir) = ScriptHost::scripts_dir() {
+            host.load_dir(&dir);
+        }
+        *guard = Some(host);
+    }
+    guard.as_ref().map(f)
+}
+
+/// Drop the loaded scripts; the next use loads them again.
+pub fn reload() {
+    if let Ok(mut guard) = HOST.lock() {
+        *guard = None;
+    }
+}
+
+/// Whether filter `name` keeps `file`; an unknown or failing filter keeps
+/// nothing, like an unresolved `package:`.
+pub fn filter_matches(name: &str, file: &ScriptFile) -> bool {
+    with_host(|host| host.filter(name, file))
+        .and_then(|result| {
+            result
+                .map_err(|e| crate::debug_log::warn("scripts", format!("{:#}", e)))
+                .ok()
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> ScriptState {
+        ScriptState {
+            api_version: API_VERSION,
+            files: vec![
+                ScriptFile {
+                    path: "README.md".into(),
+                    status: "modified".into(),
+                    adds: 3,
+                    ..ScriptFile::default()
+                },
+                ScriptFile {
+                    path: "src/lib.rs".into(),
+                    status: "modified".into(),
+                    adds: 400,
+                    ..ScriptFile::default()
+                },
+            ],
+            ..ScriptState::default()
+        }
+    }
+
+    #[test]
+    fn scripts_register_actions_filters_and_panels() {
+        let host = ScriptHost::new().unwrap();
+        host.load_source(
+            "test.lua",
+            r#"
+            er.action("docs", function(state)
+              local docs = {}
+              for _, f in ipairs(state.files) do
+                if f.path:match("%.md$") then table.insert(docs, f.path) end
+              end
+              return { reviewed = docs, notify = #docs .. " docs" }
+            end)
+            er.action("hello", function() return "hi v" .. er.api_version end)
+            er.filter("big", function(file) return file.adds > 300 end)
+            er.panel("paths", function(state)
+              local out = {}
+              for _, f in ipairs(state.files) do table.insert(out, f.path) end
+              return out
+            end)
+            "#,
+        )
+        .unwrap();
+        assert_eq!(host.actions(), ["docs", "hello"]);
+
+        let result = host.run_action("docs", &state()).unwrap();
+        assert_eq!(result.reviewed, ["README.md"]);
+        assert_eq!(result.notify.as_deref(), Some("1 docs"));
+        let hello = host.run_action("hello", &state()).unwrap();
+        assert_eq!(hello.notify.as_deref(), Some("hi v1"));
+
+        let files = state().files;
+        assert!(!host.filter("big", &files[0]).unwrap());
+        assert!(host.filter("big", &files[1]).unwrap());
+        assert_eq!(
+            host.panel_lines("paths", &state()).unwrap(),
+            ["README.md", "src/lib.rs"]
+        );
+
+        assert!(host.run_action("missing", &state()).is_err());
+        assert!(host.load_source("bad.lua", "er.action(").is_err());
+
+        // A loop that never ends fails instead of hanging er
+        host.load_source(
+            "spin.lua",
+            r#"
+            er.filter("spin", function(file) while true do end end)
+            er.action("spin", function() while true do end end)
+            "#,
+        )
+        .unwrap();
+        let err = host.filter("spin", &files[0]).unwrap_err().to_string();
+        assert!(err.contains("turned off"), "{}", err);
+        assert!(!host.filter("spin", &files[1]).unwrap());
+        assert!(host.run_action("spin", &state()).is_err());
+        assert!(host.load_source("spin2.lua", "while true do end").is_err());
+        assert!(host.filter("big", &files[1]).unwrap());
+    }
+}
@@ -0,0 +1,299 @@
+//! `er update`: find the latest release on GitHub, download the binary for
+//! this platform and swap it in for the running one. Also the release notes
+//! between two versions (`er --changelog`) and the opt-in background check
+//! behind the status-bar hint (`[updates] check`).
+//!
+//! Like `webhook`, this shells out to `curl` (and `tar`) — the engine carries
+//! no HTTP client. Release assets follow `install.sh`: `er-<target>.tar.gz`
+//! holding a single `er`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::command::OutputLogged;
+
+/// Where releases are published.
+pub const REPO: &str = "VilfredSikker/easy-review";
+
+/// The running version.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A published (non-draft, non-prerelease) release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    /// `v0.5.0`
+    pub tag: String,
+    /// Markdown release notes
+    pub notes: String,
+    /// RFC 3339
+    pub published_at: String,
+}
+
+impl Release {
+    /// The tag without its `v`.
+    pub fn version(&self) -> &str {
+        self.tag.trim_start_matches('v')
+    }
+}
+
+/// The release target built for this platform, `None` where no binary is
+/// published.
+pub fn release_target() -> Option<&'static str> {
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("aarch64-apple-darwin")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("x86_64-apple-darwin")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("x86_64-unknown-linux-gnu")
+    } else {
+        None
+    }
+}
+
+/// Download URL of the `er` tarball for `target` in release `tag`.
+pub fn asset_url(tag: &str, target: &str) -> String {
+    format!(
+        "https://github.com/{}/releases/download/{}/er-{}.tar.gz",
+        REPO, tag, target
+    )
+}
+
+/// `major.minor.patch`, ignoring a leading `v` and any pre-release suffix.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v');
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+/// Whether `candidate` is a later version than `current`.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(c), Some(cur)) => c > cur,
+        _ => false,
+    }
+}
+
+/// Parse the GitHub releases API listing, newest first, skipping drafts and
+/// prereleases.
+pub fn parse_releases(json: &str) -> Result<Vec<Release>> {
+    #[derive(Deserialize)]
+    struct Raw {
+        tag_name: String,
+        #[serde(default)]
+        body: Option<String>,
+        #[serde(default)]
+        published_at: Option<String>,
+        #[serde(default)]
+        draft: bool,
+        #[serde(default)]
+        prerelease: bool,
+    }
+    let raw: Vec<Raw> = serde_json::from_str(json).context("Unexpected releases response")?;
+    let mut releases: Vec<Release> = raw
+        .into_iter()
+        .filter(|r| !r.draft && !r.prerelease && parse_version(&r.tag_name).is_some())
+        .map(|r| Release {
+            tag: r.tag_name,
+            notes: r.body.unwrap_or_default(),
+            published_at: r.published_at.unwrap_or_default(),
+        })
+        .collect();
+    releases.sort_by_key(|r| std::cmp::Reverse(parse_version(&r.tag)));
+    Ok(releases)
+}
+
+fn curl(url: &str) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", "30"])
+        .args(["-H", "Accept: application/vnd.github+json", url])
+        .logged_output()
+        .context("Failed to run curl")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("curl {} failed: {}", url, stderr.trim());
+    }
+    Ok(output.stdout)
+}
+
+/// The published releases, newest first.
+pub fn fetch_releases() -> Result<Vec<Release>> {
+    let url = format!("https://api.github.com/repos/{}/releases?per_page=30", REPO);
+    parse_releases(&String::from_utf8_lossy(&curl(&url)?))
+}
+
+/// Releases after `current`, newest first.
+pub fn newer_than<'a>(releases: &'a [Release], current: &str) -> Vec<&'a Release> {
+    releases
+        .iter()
+        .filter(|r| is_newer(r.version(), current))
+        .collect()
+}
+
+/// The notes of every release after `current`, newest first, as markdown.
+pub fn changelog(releases: &[Release], current: &str) -> String {
+    let mut out = String::new();
+    for release in newer_than(releases, current) {
+        let date = release.published_at.get(..10).unwrap_or_default();
+        out.push_str(&format!("## {} ({})\n\n", release.tag, date));
+        out.push_str(release.notes.trim());
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Download `release` for this platform and atomically replace `exe` with
+/// it. The new binary is unpacked next to `exe` (same filesystem, so the
+/// final rename is atomic) and must report the release's version before it
+/// replaces anything.
+pub fn install(release: &Release, exe: &Path) -> Result<()> {
+    let target = release_target().context(
+        "No prebuilt er for this platform — build from source: cargo install --path crates/er-tui",
+    )?;
+    let dir = exe
+        .parent()
+        .with_context(|| format!("{} has no parent directory", exe.display()))?;
+    let staging = dir.join(format!(".er-update-{}", std::process::id()));
+    std::fs::create_dir_all(&staging).with_context(|| {
+        format!(
+            "Can't write to {} — re-run er update with sudo, or reinstall with install.sh",
+            dir.display()
+        )
+    })?;
+    let result = unpack_and_swap(release, target, &staging, exe);
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+fn unpack_and_swap(release: &Release, target: &str, staging: &Path, exe: &Path) -> Result<()> {
+    let tarball = staging.join("er.tar.gz");
+    let url = asset_url(&release.tag, target);
+    let status = Command::new("curl")
+        .args(["-fsSL", "--max-time", "300", "-o"])
+        .arg(&tarball)
+        .arg(&url)
+        .status()
+        .context("Failed to run curl")?;
+    if !status.success() {
+        anyhow::bail!("Download of {} failed", url);
+    }
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(&tarball)
+        .arg("-C")
+        .arg(staging)
+        .status()
+        .context("Failed to run tar")?;
+    if !status.success() {
+        anyhow::bail!("Could not unpack {}", url);
+    }
+    let new = staging.join("er");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new, std::fs::Permissions::from_mode(0o755))?;
+    }
+    let output = Command::new(&new)
+        .arg("--version")
+        .output()
+        .context("The downloaded er doesn't run")?;
+    let reported = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !reported.contains(release.version()) {
+        anyhow::bail!(
+            "The downloaded er reports {:?}, expected {}",
+            reported.trim(),
+            release.version()
+        );
+    }
+    std::fs::rename(&new, exe).with_context(|| format!("Could not replace {}", exe.display()))?;
+    Ok(())
+}
+
+/// The last background check, so it runs at most once per interval.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckCache {
+    /// Unix seconds
+    checked_at: u64,
+    /// Latest release version then
+    latest: Option<String>,
+}
+
+fn cache_path() -> PathBuf {
+    crate::storage::storage_root().join("update-check.json")
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// The latest release version when it's newer than this one. Asks GitHub at
+/// most once per `interval_hours`, answering from the last check otherwise.
+/// Blocking; run it off the UI thread.
+pub fn check_for_update(interval_hours: u64) -> Option<String> {
+    let path = cache_path();
+    let cached: Option<CheckCache> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok());
+    let fresh = cached
+        .as_ref()
+        .is_some_and(|c| now_secs().saturating_sub(c.checked_at) < interval_hours * 3600);
+    let latest = if fresh {
+        cached.and_then(|c| c.latest)
+    } else {
+        let latest = fetch_releases()
+            .ok()?
+            .first()
+            .map(|r| r.version().to_string());
+        let cache = CheckCache {
+            checked_at: now_secs(),
+            latest: latest.clone(),
+        };
+        if let Ok(json) = serde_json::to_string(&cache) {
+            let _ = std::fs::write(&path, json);
+        }
+        latest
+    };
+    latest.filter(|v| is_newer(v, VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases_parse_sort_and_compare_by_version() {
+        let json = r#"[
+            {"tag_name": "v0.4.6", "body": "Uninstall", "published_at": "2026-03-01T00:00:00Z"},
+            {"tag_name": "v0.5.0-rc.1", "prerelease": true},
+            {"tag_name": "v0.10.0", "body": "Big one\n", "published_at": "2026-05-02T10:00:00Z"},
+            {"tag_name": "v0.5.0", "body": "Smaller", "published_at": "2026-04-01T00:00:00Z"},
+            {"tag_name": "nightly", "draft": true}
+        ]"#;
+        let releases = parse_releases(json).unwrap();
+        let tags: Vec<&str> = releases.iter().map(|r| r.tag.as_str()).collect();
+        assert_eq!(tags, ["v0.10.0", "v0.5.0", "v0.4.6"]);
+
+        assert!(is_newer("0.10.0", "0.4.6"));
+        assert!(!is_newer("0.4.6", "0.4.6"));
+        assert!(!is_newer("v0.4.6", "0.5.0-dev"));
+        assert_eq!(parse_version("v1.2"), Some((1, 2, 0)));
+
+        assert_eq!(newer_than(&releases, "0.4.6").len(), 2);
+        assert_eq!(
+            changelog(&releases, "0.5.0"),
+            "## v0.10.0 (2026-05-02)\n\nBig one\n\n"
+        );
+        assert!(changelog(&releases, "0.10.0").is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+//! Session handoff bundles: one JSON file with a reviewer's reviewed set,
+//! questions, unsent comments, checked checklist items and filter, so a
+//! second reviewer can pick up where the first stopped — "I reviewed half,
+//! you take the rest" — without a forge round-trip.
+//!
+//! Importing merges instead of replacing. Imported questions and comments
+//! get reviewer-namespaced ids (so importing twice adds nothing) and carry
+//! the exporter's name instead of "You"; anything the importer wrote
+//! themselves comes back under its original id and is skipped.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ai::{
+    shared_question_id, ErChecklist, ErGitHubComments, ErQuestions, SHARED_QUESTION_PREFIX,
+};
+use crate::ai::{GitHubReviewComment, ReviewQuestion};
+
+/// File name suffix of a bundle.
+pub const BUNDLE_SUFFIX: &str = ".er-session.json";
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Author name of the local reviewer's own items.
+const LOCAL_AUTHOR: &str = "You";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub version: u32,
+    /// Display name of the reviewer who exported it
+    pub reviewer: String,
+    /// Directory-safe id the imported ids are namespaced with
+    pub reviewer_id: String,
+    #[serde(default)]
+    pub exported_at: String,
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default)]
+    pub base_branch: String,
+    #[serde(default)]
+    pub filter: String,
+    /// Reviewed file → the diff hash it was reviewed at
+    #[serde(default)]
+    pub reviewed: BTreeMap<String, String>,
+    #[serde(default)]
+    pub questions: Vec<ReviewQuestion>,
+    /// Comments not yet posted to the forge
+    #[serde(default)]
+    pub comments: Vec<GitHubReviewComment>,
+    /// Ids of checked checklist items
+    #[serde(default)]
+    pub checked: Vec<String>,
+}
+
+/// What an import added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub reviewed: usize,
+    pub questions: usize,
+    pub comments: usize,
+    pub checked: usize,
+    pub filter_applied: bool,
+}
+
+impl ImportSummary {
+    /// `4 reviewed files · 2 questions · 1 comment`
+    pub fn label(&self) -> String {
+        let plural =
+            |n: usize, one: &str| format!("{} {}{}", n, one, if n == 1 { "" } else { "s" });
+        let mut parts = vec![plural(self.reviewed, "reviewed file")];
+        if self.questions > 0 {
+            parts.push(plural(self.questions, "question"));
+        }
+        if self.comments > 0 {
+            parts.push(plural(self.comments, "comment"));
+        }
+        if self.checked > 0 {
+            parts.push(plural(self.checked, "checklist item"));
+        }
+        if self.filter_applied {
+            parts.push("their filter".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+impl SessionBundle {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        reviewer: &str,
+        reviewer_id: &str,
+        branch: &str,
+        base_branch: &str,
+        filter: &str,
+        reviewed: &HashMap<String, String>,
+        questions: Option<&ErQuestions>,
+        comments: Option<&ErGitHubComments>,
+        checklist: Option<&ErChecklist>,
+    ) -> Self {
+        let with_author = |author: &str| {
+            if author.is_empty() || author == LOCAL_AUTHOR {
+                reviewer.to_string()
+            } else {
+                author.to_string()
+            }
+        };
+        SessionBundle {
+            version: BUNDLE_VERSION,
+            reviewer: reviewer.to_string(),
+            reviewer_id: reviewer_id.to_string(),
+            exported_at: crate::sync::chrono_now(),
+            branch: branch.to_string(),
+            base_branch: base_branch.to_string(),
+            filter: filter.to_string(),
+            reviewed: reviewed
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            questions: questions
+                .map(|q| q.questions.clone())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|mut q| {
+                    q.author = with_author(&q.author);
+                    q
+                })
+                .collect(),
+            comments: comments
+                .map(|c| c.comments.clone())
+                .unwrap_or_default()
+                .into_iter()
+                // Posted comments are already on the forge for everyone
+                .filter(|c| !c.synced && c.github_id.is_none())
+                .map(|mut c| {
+                    c.author = with_author(&c.author);
+                    c
+                })
+                .collect(),
+            checked: checklist
+                .map(|c| {
+                    c.items
+                        .iter()
+                        .filter(|i| i.checked)
+                        .map(|i| i.id.clone())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let bundle: SessionBundle = serde_json::from_str(content)?;
+        if bundle.version > BUNDLE_VERSION {
+            anyhow::bail!(
+                "bundle version {} is newer than this er understands",
+                bundle.version
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Id an item of this bundle gets locally. Items namespaced to
+    /// `local_id` were the importer's own and get their original id back.
+    fn local_id(&self, id: &str, local_id: &str) -> String {
+        match id.strip_prefix(&shared_question_id(local_id, "")) {
+            Some(original) => original.to_string(),
+            // Already namespaced, e.g. pulled from a shared review
+            None if id.starts_with(SHARED_QUESTION_PREFIX) => id.to_string(),
+            None => shared_question_id(&self.reviewer_id, id),
+        }
+    }
+
+    /// Add the bundle's reviewed files that aren't reviewed here yet.
+    pub fn merge_reviewed(&self, reviewed: &mut HashMap<String, String>) -> usize {
+        let mut added = 0;
+        for (path, hash) in &self.reviewed {
+            if !reviewed.contains_key(path) {
+                reviewed.insert(path.clone(), hash.clone());
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Add the bundle's questions and replies missing from `local`.
+    pub fn merge_questions(&self, local: &mut ErQuestions, local_id: &str) -> usize {
+        let mut added = 0;
+        for q in &self.questions {
+            let id = self.local_id(&q.id, local_id);
+            if local.questions.iter().any(|existing| existing.id == id) {
+                continue;
+            }
+            let mut q = q.clone();
+            q.id = id;
+            q.in_reply_to = q.in_reply_to.map(|r| self.local_id(&r, local_id));
+            local.questions.push(q);
+            added += 1;
+        }
+        added
+    }
+
+    /// Add the bundle's unsent comments missing from `local`.
+    pub fn merge_comments(&self, local: &mut ErGitHubComments, local_id: &str) -> usize {
+        let mut added = 0;
+        for c in &self.comments {
+            let id = self.local_id(&c.id, local_id);
+            if local.comments.iter().any(|existing| existing.id == id) {
+                continue;
+            }
+            let mut c = c.clone();
+            c.id = id;
+            c.in_reply_to = c.in_reply_to.map(|r| self.local_id(&r, local_id));
+            local.comments.push(c);
+            added += 1;
+        }
+        added
+    }
+
+    /// Check the checklist items the exporter checked.
+    pub fn merge_checklist(&self, checklist: &mut ErChecklist) -> usize {
+        let mut checked = 0;
+        for item in &mut checklist.items {
+            if !item.checked && self.checked.contains(&item.id) {
+                item.checked = true;
+                checked += 1;
+            }
+        }
+        checked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(id: &str, author: &str) -> ReviewQuestion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "file": "src/a.rs",
+            "hunk_index": 0,
+            "line_start": 3,
+            "text": "why?",
+            "author": author,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn importing_twice_adds_nothing_and_keeps_attribution() {
+        let questions = ErQuestions {
+            version: 1,
+            diff_hash: "h".into(),
+            questions: vec![question("q-1", "You"), question("q-2", "bob")],
+        };
+        let reviewed: HashMap<String, String> = [("src/a.rs".to_string(), "x".to_string())].into();
+        let bundle = SessionBundle::new(
+            "Ada",
+            "ada",
+            "feature",
+            "main",
+            "*.rs",
+            &reviewed,
+            Some(&questions),
+            None,
+            None,
+        );
+        let bundle = SessionBundle::parse(&serde_json::to_string(&bundle).unwrap()).unwrap();
+
+        let mut mine = ErQuestions {
+            version: 1,
+            diff_hash: "h".into(),
+            questions: Vec::new(),
+        };
+        assert_eq!(bundle.merge_questions(&mut mine, "bob"), 2);
+        assert_eq!(mine.questions[0].id, shared_question_id("ada", "q-1"));
+        assert_eq!(mine.questions[0].author, "Ada");
+        assert_eq!(mine.questions[1].author, "bob");
+        assert_eq!(bundle.merge_questions(&mut mine, "bob"), 0);
+
+        let mut my_reviewed = HashMap::new();
+        assert_eq!(bundle.merge_reviewed(&mut my_reviewed), 1);
+        assert_eq!(bundle.merge_reviewed(&mut my_reviewed), 0);
+    }
+
+    #[test]
+    fn own_items_coming_back_keep_their_ids() {
+        let bundle = SessionBundle {
+            reviewer_id: "bob".into(),
+            questions: vec![question(&shared_question_id("ada", "q-1"), "Ada")],
+            ..SessionBundle::new("Bob", "bob", "", "", "", &HashMap::new(), None, None, None)
+        };
+        let mut mine = ErQuestions {
+            version: 1,
+            diff_hash: "h".into(),
+            questions: vec![question("q-1", "You")],
+        };
+        assert_eq!(bundle.merge_questions(&mut mine, "ada"), 0);
+        assert_eq!(mine.questions.len(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Team-shared review state kept in a git ref.
+//!
+//! `refs/er/reviews/<branch>` holds one directory per reviewer
+//! (`reviewers/<id>/questions.json`, `github-comments.json`,
+//! `checklist.json`, `reviewed`). Publishing rewrites only your own
+//! directory on top of the remote's latest commit and pushes it; pulling
+//! fetches the ref and reads everyone's directories. It is plain git
+//! objects built through a throwaway index, so the working tree, index and
+//! branches are never touched and any remote works — GitHub or not.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+
+pub const REF_PREFIX: &str = "refs/er/reviews/";
+
+/// Review artifacts shared per reviewer.
+pub const SHARED_FILES: &[&str] = &[
+    "questions.json",
+    "github-comments.json",
+    "checklist.json",
+    "reviewed",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedReviewer {
+    pub id: String,
+    /// (file name, contents)
+    pub files: Vec<(String, String)>,
+}
+
+impl SharedReviewer {
+    pub fn file(&self, name: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c.as_str())
+    }
+}
+
+pub fn ref_name(branch: &str) -> String {
+    format!("{}{}", REF_PREFIX, branch)
+}
+
+/// Directory-safe reviewer id: `Ada Lovelace` → `ada-lovelace`.
+pub fn reviewer_slug(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "reviewer".to_string()
+    } else {
+        slug
+    }
+}
+
+fn git(repo_root: &str, index: Option<&Path>, args: &[&str]) -> Result<String> {
+    git_raw(repo_root, index, args).map(|out| out.trim().to_string())
+}
+
+fn git_raw(repo_root: &str, index: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args).current_dir(repo_root);
+    if let Some(index) = index {
+        cmd.env("GIT_INDEX_FILE", index);
+    }
+    let output = crate::command::run(&mut cmd)?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Fetch `refname` from `remote` into the same local ref. False when the
+/// remote doesn't have it (yet).
+fn fetch_ref(repo_root: &str, remote: &str, refname: &str) -> bool {
+    let spec = format!("+{}:{}", refname, refname);
+    git(repo_root, None, &["fetch", "--quiet", remote, &spec]).is_ok()
+}
+
+fn local_commit(repo_root: &str, refname: &str) -> Option<String> {
+    let rev = format!("{}^{{commit}}", refname);
+    git(repo_root, None, &["rev-parse", "--verify", "--quiet", &rev]).ok()
+}
+
+/// Write `files` (name, path on disk; missing paths are skipped) as
+/// `reviewers/<reviewer>/` and push. Returns false when nothing changed.
+pub fn publish(
+    repo_root: &str,
+    remote: &str,
+    branch: &str,
+    reviewer: &str,
+    files: &[(&str, PathBuf)],
+) -> Result<bool> {
+    let refname = ref_name(branch);
+    let dir = format!("reviewers/{}", reviewer);
+    let index = std::env::temp_dir().join(format!("er-shared-index-{}", std::process::id()));
+    let mut attempt = 0;
+    loop {
+        fetch_ref(repo_root, remote, &refname);
+        let parent = local_commit(repo_root, &refname);
+        let _ = std::fs::remove_file(&index);
+        let built = (|| -> Result<Option<String>> {
+            match &parent {
+                Some(p) => git(repo_root, Some(&index), &["read-tree", p])?,
+                None => git(repo_root, Some(&index), &["read-tree", "--empty"])?,
+            };
+            git(
+                repo_root,
+                Some(&index),
+                &["rm", "--cached", "-r", "-q", "--ignore-unmatch", "--", &dir],
+            )?;
+            for (name, path) in files {
+                if !path.exists() {
+                    continue;
+                }
+                let blob = git(
+                    repo_root,
+                    None,
+                    &["hash-object", "-w", "--", &path.to_string_lossy()],
+                )?;
+                let info = format!("100644,{},{}/{}", blob, dir, name);
+                git(
+                    repo_root,
+                    Some(&index),
+                    &["update-index", "--add", "--cacheinfo", &info],
+                )?;
+            }
+            let tree = git(repo_root, Some(&index), &["write-tree"])?;
+            if let Some(p) = &parent {
+                let parent_tree = format!("{}^{{tree}}", p);
+                if git(repo_root, None, &["rev-parse", &parent_tree])? == tree {
+                    return Ok(None);
+                }
+            }
+            Ok(Some(tree))
+        })();
+        let _ = std::fs::remove_file(&index);
+        let Some(tree) = built? else {
+            return Ok(false);
+        };
+
+        let message = format!("er: review state from {}", reviewer);
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", message.as_str()];
+        if let Some(p) = &parent {
+            args.extend(["-p", p.as_str()]);
+        }
+        let commit = git(repo_root, None, &args)?;
+        git(repo_root, None, &["update-ref", &refname, &commit])?;
+        let spec = format!("{}:{}", refname, refname);
+        match git(repo_root, None, &["push", "--quiet", remote, &spec]) {
+            Ok(_) => return Ok(true),
+            // Someone else published in between: rebuild on top of theirs
+            Err(_) if attempt == 0 => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Fetch the shared ref and read every reviewer's files. Empty when nobody
+/// has published for `branch`.
+pub fn pull(repo_root: &str, remote: &str, branch: &str) -> Result<Vec<SharedReviewer>> {
+    let refname = ref_name(branch);
+    fetch_ref(repo_root, remote, &refname);
+    let Some(commit) = local_commit(repo_root, &refname) else {
+        return Ok(Vec::new());
+    };
+    let listing = git(
+        repo_root,
+        None,
+        &["ls-tree", "-r", "--name-only", &commit, "--", "reviewers/"],
+    )?;
+    let mut reviewers: Vec<SharedReviewer> = Vec::new();
+    for path in listing.lines() {
+        let mut parts = path.splitn(3, '/');
+        let (Some("reviewers"), Some(id), Some(name)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let contents = git_raw(repo_root, None, &["show", &format!("{}:{}", commit, path)])?;
+        match reviewers.iter_mut().find(|r| r.id == id) {
+            Some(r) => r.files.push((name.to_string(), contents)),
+            None => reviewers.push(SharedReviewer {
+                id: id.to_string(),
+                files: vec![(name.to_string(), contents)],
+            }),
+        }
+    }
+    Ok(reviewers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn slug_is_directory_safe() {
+        assert_eq!(reviewer_slug("Ada Lovelace"), "ada-lovelace");
+        assert_eq!(reviewer_slug(" bob@corp.io "), "bob-corp.io");
+        assert_eq!(reviewer_slug("///"), "reviewer");
+    }
+
+    #[test]
+    fn two_reviewers_publish_and_see_each_other() {
+        let tmp = tempfile::tempdir().unwrap();
+        let remote = tmp.path().join("remote.git");
+        std::fs::create_dir(&remote).unwrap();
+        sh(&remote, &["init", "--bare", "-q"]);
+        let clone = |name: &str| {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            sh(&dir, &["init", "-q"]);
+            sh(&dir, &["config", "user.name", name]);
+            sh(&dir, &["config", "user.email", "t@example.com"]);
+            sh(
+                &dir,
+                &["remote", "add", "origin", &remote.to_string_lossy()],
+            );
+            dir
+        };
+        let (alice, bob) = (clone("alice"), clone("bob"));
+        let write = |dir: &Path, name: &str, body: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, body).unwrap();
+            path
+        };
+
+        let q = write(&alice, "q.json", "{\"a\":1}");
+        let files = [("questions.json", q), ("reviewed", alice.join("missing"))];
+        let alice_root = alice.to_string_lossy().to_string();
+        assert!(publish(&alice_root, "origin", "feat", "alice", &files).unwrap());
+        assert!(!publish(&alice_root, "origin", "feat", "alice", &files).unwrap());
+
+        let r = write(&bob, "r", "src/lib.rs\t\n");
+        let bob_root = bob.to_string_lossy().to_string();
+        publish(&bob_root, "origin", "feat", "bob", &[("reviewed", r)]).unwrap();
+
+        let seen = pull(&alice_root, "origin", "feat").unwrap();
+        let ids: Vec<&str> = seen.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["alice", "bob"]);
+        assert_eq!(seen[0].file("questions.json"), Some("{\"a\":1}"));
+        assert_eq!(seen[1].file("reviewed"), Some("src/lib.rs\t\n"));
+        assert!(pull(&alice_root, "origin", "other").unwrap().is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 use crate::ai::{load_tour_sidecar, load_triage_review, ErReview, ErTour, RiskLevel, TriageReview};
 use crate::github::owner_repo_storage_slug;
 use crate::sidecar_upload::SidecarKind;
-use crate::storage::{pr_bucket_dir, storage_root};
+use crate::storage::{artifact_root, pr_bucket_dir};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -199,7 +199,7 @@ pub fn list_repo_pr_artifacts(
     limit: usize,
 ) -> Vec<ListedPrArtifacts> {
     let slug = owner_repo_storage_slug(owner, repo);
-    let prs_dir = storage_root().join("repos").join(&slug).join("prs");
+    let prs_dir = artifact_root().join("repos").join(&slug).join("prs");
     list_repo_pr_artifacts_in_dir(owner, repo, &prs_dir, kinds_filter, limit)
 }
 
@@ -0,0 +1,277 @@
+//! Spell checking for prose: added doc comments, added lines of markdown and
+//! text files, and the reviewer's own comments before they go to GitHub.
+//!
+//! The checking itself is done by an external checker that reads text on
+//! stdin and prints one misspelled word per line — `hunspell -l` or
+//! `aspell list`, or any `[spell] command` that behaves the same. Tokens that
+//! look like code (camelCase, snake_case, digits, inline `code`, URLs) are
+//! never sent, and `[spell] words`, from the global config and the repo's
+//! `.er-config.toml`, are always accepted.
+
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::config::SpellConfig;
+use crate::git::{DiffFile, LineType};
+
+/// Checkers tried in order when `[spell] command` is empty. `{lang}` is
+/// replaced with `[spell] language`.
+const DEFAULT_CHECKERS: &[&str] = &["hunspell -l -d {lang}", "aspell list --lang={lang}"];
+
+/// File extensions whose added lines are prose throughout.
+const PROSE_EXTENSIONS: &[&str] = &["md", "markdown", "mdx", "rst", "txt", "adoc"];
+
+/// Doc comment leaders whose text is prose.
+const DOC_COMMENT_PREFIXES: &[&str] = &["///", "//!", "/**", "/*!"];
+
+/// Words shorter than this are never flagged.
+const MIN_WORD_LEN: usize = 3;
+
+/// A word the checker didn't know, on an added line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Typo {
+    pub path: String,
+    /// New-side line number
+    pub line: Option<usize>,
+    pub word: String,
+}
+
+/// A resolved external checker plus the words it should let through.
+#[derive(Debug, Clone)]
+pub struct SpellChecker {
+    argv: Vec<String>,
+    accepted: HashSet<String>,
+}
+
+impl SpellChecker {
+    /// The configured checker, or the first default one installed. `None`
+    /// when spell checking is off or no checker is available.
+    pub fn detect(config: &SpellConfig, repo_words: &[String]) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let candidates: Vec<String> = if config.command.trim().is_empty() {
+            DEFAULT_CHECKERS.iter().map(|c| c.to_string()).collect()
+        } else {
+            vec![config.command.clone()]
+        };
+        let accepted: HashSet<String> = config
+            .words
+            .iter()
+            .chain(repo_words)
+            .map(|w| w.to_lowercase())
+            .collect();
+        candidates.iter().find_map(|command| {
+            let argv =
+                crate::config::split_shell_args(&command.replace("{lang}", &config.language));
+            let checker = SpellChecker {
+                argv,
+                accepted: HashSet::new(),
+            };
+            checker.run("").map(|_| SpellChecker {
+                accepted: accepted.clone(),
+                ..checker
+            })
+        })
+    }
+
+    /// Program the checker runs, for status messages.
+    pub fn name(&self) -> &str {
+        self.argv.first().map_or("", String::as_str)
+    }
+
+    /// Misspelled words of `text`, deduplicated, in first-seen order.
+    pub fn check(&self, text: &str) -> Vec<String> {
+        let words: Vec<&str> = prose_words(text)
+            .into_iter()
+            .filter(|w| !self.accepted.contains(&w.to_lowercase()))
+            .collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+        let unknown: HashSet<String> = self
+            .run(&words.join("\n"))
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let mut seen = BTreeSet::new();
+        words
+            .into_iter()
+            .filter(|w| unknown.contains(*w) && seen.insert(w.to_string()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn run(&self, input: &str) -> Option<String> {
+        let (program, args) = self.argv.split_first()?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut stdin = child.stdin.take()?;
+        let input = input.as_bytes().to_vec();
+        let writer = std::thread::spawn(move || stdin.write_all(&input));
+        let output = child.wait_with_output().ok()?;
+        writer.join().ok()?.ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Typos on the added prose lines of `files`, in diff order.
+    pub fn diff_typos(&self, files: &[DiffFile]) -> Vec<Typo> {
+        // One checker run for the whole diff, then map words back to lines
+        let lines: Vec<(&str, Option<usize>, &str)> = files
+            .iter()
+            .flat_map(|file| {
+                let whole = is_prose_file(&file.path);
+                file.hunks
+                    .iter()
+                    .flat_map(|h| &h.lines)
+                    .filter(|l| l.line_type == LineType::Add)
+                    .filter_map(move |l| {
+                        let text = if whole {
+                            Some(l.content.as_str())
+                        } else {
+                            doc_comment_text(&l.content)
+                        };
+                        text.map(|t| (file.path.as_str(), l.new_num, t))
+                    })
+            })
+            .collect();
+        let all: Vec<&str> = lines.iter().map(|(_, _, text)| *text).collect();
+        let unknown: HashSet<String> = self.check(&all.join("\n")).into_iter().collect();
+        if unknown.is_empty() {
+            return Vec::new();
+        }
+        lines
+            .iter()
+            .flat_map(|(path, line, text)| {
+                prose_words(text)
+                    .into_iter()
+                    .filter(|w| unknown.contains(*w))
+                    .map(|w| Typo {
+                        path: path.to_string(),
+                        line: *line,
+                        word: w.to_string(),
+                    })
+            })
+            .collect()
+    }
+}
+
+pub fn is_prose_file(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| PROSE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// The text of a doc comment line, `None` for anything else.
+pub fn doc_comment_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    DOC_COMMENT_PREFIXES
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .map(|rest| rest.trim_end_matches("*/"))
+}
+
+/// Words worth checking: inline code and URLs are dropped, as are tokens
+/// that look like identifiers.
+pub fn prose_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for (i, segment) in text.split('`').enumerate() {
+        // Odd segments are inside `code`
+        if i % 2 == 1 {
+            continue;
+        }
+        for token in segment.split_whitespace() {
+            if token.contains("://") || token.starts_with("www.") || token.contains('@') {
+                continue;
+            }
+            let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+            if looks_like_code(token) {
+                continue;
+            }
+            words.extend(
+                token
+                    .split(|c: char| !c.is_alphabetic() && c != '\'')
+                    .map(|w| w.trim_matches('\''))
+                    .filter(|w| w.chars().count() >= MIN_WORD_LEN),
+            );
+        }
+    }
+    words
+}
+
+fn looks_like_code(token: &str) -> bool {
+    let mut chars = token.chars();
+    let rest_has_upper = chars.next().is_some() && chars.any(char::is_uppercase);
+    rest_has_upper
+        || token.contains(['_', '.', '/', '\\', ':', '=', '(', '<', '{', '['])
+        || token.chars().any(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prose_words_skip_code_and_identifiers() {
+        assert_eq!(
+            prose_words("Retuns the `parse_diff` result, see https://x.io or HashMap and v2 don't"),
+            ["Retuns", "the", "result", "see", "and", "don't"]
+        );
+        assert_eq!(
+            doc_comment_text("    /// Loads teh file"),
+            Some(" Loads teh file")
+        );
+        assert_eq!(doc_comment_text("// plain comment"), None);
+        assert!(is_prose_file("docs/README.md"));
+        assert!(!is_prose_file("src/lib.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn checker_flags_unknown_words_on_added_prose_lines() {
+        // Stands in for `hunspell -l`: every line matching a "misspelling"
+        let config = SpellConfig {
+            command: "sh -c 'grep -x -e teh -e recieve -e Kubernetes; true'".into(),
+            words: vec!["kubernetes".into()],
+            ..SpellConfig::default()
+        };
+        let checker = SpellChecker::detect(&config, &[]).unwrap();
+        assert_eq!(
+            checker.check("teh cat will recieve teh Kubernetes"),
+            ["teh", "recieve"]
+        );
+
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                    @@ -1 +1,3 @@\n /// teh old\n+/// Will recieve\n+let teh = 1;\n\
+                    diff --git a/NOTES.md b/NOTES.md\n--- a/NOTES.md\n+++ b/NOTES.md\n\
+                    @@ -0,0 +1 @@\n+See teh list\n";
+        let typos = checker.diff_typos(&crate::git::parse_diff(diff));
+        let found: Vec<(&str, Option<usize>, &str)> = typos
+            .iter()
+            .map(|t| (t.path.as_str(), t.line, t.word.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("src/a.rs", Some(2), "recieve"),
+                ("NOTES.md", Some(1), "teh")
+            ]
+        );
+
+        let off = SpellConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(SpellChecker::detect(&off, &[]).is_none());
+    }
+}
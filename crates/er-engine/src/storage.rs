@@ -2,9 +2,17 @@
 //!
 //! Flat branch-level layout under
 //! `<app_data>/easy-review/repos/<repo_slug>/branches/<branch_slug>/`.
+//!
+//! `[storage] location` moves the artifacts: `git` keeps the same layout
+//! under the clone's `.git/er/`, `repo` uses the working tree's `.er/` like
+//! `ER_REPO_LOCAL=1`. Either way, a bucket that is still empty in the new
+//! place is filled from the old one the first time it's opened.
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
+use crate::command::OutputLogged;
 use crate::ErRoot;
 
 const MARKER_FILES: &[&str] = &[
@@ -18,6 +26,93 @@ const MARKER_FILES: &[&str] = &[
     "session.json",
 ];
 
+/// Where review artifacts live, from `[storage] location`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageLocation {
+    /// The user data dir ([`storage_root`])
+    #[default]
+    Data,
+    /// `.git/er/` inside the clone (the common dir, shared by worktrees)
+    Git,
+    /// `.er/` in the working tree
+    Repo,
+}
+
+impl StorageLocation {
+    /// Parse a `[storage] location` value; unknown values are `Data`.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "git" => StorageLocation::Git,
+            "repo" => StorageLocation::Repo,
+            _ => StorageLocation::Data,
+        }
+    }
+}
+
+/// Root of the artifact layout when `[storage] location = "git"`.
+static GIT_ARTIFACT_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// `[storage] location = "repo"` is in effect.
+static REPO_LOCATION: AtomicBool = AtomicBool::new(false);
+
+/// Apply `[storage] location` for the repo being reviewed. `git` falls back
+/// to the data dir outside a clone.
+pub fn set_location(location: StorageLocation, repo_root: &str) {
+    let git_root = match location {
+        StorageLocation::Git => git_common_dir(repo_root).map(|dir| dir.join("er")),
+        _ => None,
+    };
+    REPO_LOCATION.store(location == StorageLocation::Repo, Ordering::Relaxed);
+    if let Ok(mut root) = GIT_ARTIFACT_ROOT.lock() {
+        *root = git_root;
+    }
+}
+
+/// Apply `[storage] location` for `repo_root`: the repo's `.er-config.toml`
+/// wins over the global config.
+pub fn configure_for_repo(repo_root: &str, storage: &crate::config::StorageConfig) {
+    let location = crate::config::load_repo_storage_location(repo_root)
+        .unwrap_or_else(|| storage.location.clone());
+    set_location(StorageLocation::parse(&location), repo_root);
+}
+
+/// The configured location, as applied by [`set_location`]. `ER_REPO_LOCAL=1`
+/// is separate: see [`use_repo_local_storage`].
+pub fn location() -> StorageLocation {
+    if REPO_LOCATION.load(Ordering::Relaxed) {
+        StorageLocation::Repo
+    } else if git_artifact_root().is_some() {
+        StorageLocation::Git
+    } else {
+        StorageLocation::Data
+    }
+}
+
+fn git_artifact_root() -> Option<PathBuf> {
+    GIT_ARTIFACT_ROOT.lock().ok().and_then(|root| root.clone())
+}
+
+fn git_common_dir(repo_root: &str) -> Option<PathBuf> {
+    let out = std::process::Command::new("git")
+        .args(["rev-parse", "--git-common-dir"])
+        .current_dir(repo_root)
+        .logged_output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let dir = PathBuf::from(String::from_utf8_lossy(&out.stdout).trim());
+    Some(if dir.is_absolute() {
+        dir
+    } else {
+        Path::new(repo_root).join(dir)
+    })
+}
+
+/// Root of the `repos/…` artifact layout: `.git/er/` with
+/// `[storage] location = "git"`, else [`storage_root`].
+pub fn artifact_root() -> PathBuf {
+    git_artifact_root().unwrap_or_else(storage_root)
+}
+
 /// Root of all managed review storage.
 ///
 /// Overridden by `ER_STORAGE_ROOT` when set — used by tests to write under a
@@ -59,7 +154,7 @@ pub fn slug_repo(repo_root: &str) -> String {
     if let Ok(out) = std::process::Command::new("git")
         .args(["remote", "get-url", "origin"])
         .current_dir(repo_root)
-        .output()
+        .logged_output()
     {
         if out.status.success() {
             let url = String::from_utf8_lossy(&out.stdout).trim().to_string();
@@ -87,9 +182,15 @@ pub fn slug_branch(branch: &str) -> String {
     slugify(&branch.replace('/', "-"))
 }
 
+/// Directory for everything stored about a repo under the managed storage
+/// root; its branches and PRs are subdirectories.
+pub fn repo_dir(repo_slug: &str) -> PathBuf {
+    artifact_root().join("repos").join(repo_slug)
+}
+
 /// Directory for a specific branch under the managed storage root.
 pub fn branch_dir(repo_slug: &str, branch_slug: &str) -> PathBuf {
-    storage_root()
+    artifact_root()
         .join("repos")
         .join(repo_slug)
         .join("branches")
@@ -99,7 +200,7 @@ pub fn branch_dir(repo_slug: &str, branch_slug: &str) -> PathBuf {
 /// Resolve managed storage from already-slugged components.
 pub fn resolve_managed_root_from_slugs(repo_slug: &str, branch_slug: &str) -> ErRoot {
     let branch_path = branch_dir(repo_slug, branch_slug);
-    if std::fs::create_dir_all(&branch_path).is_err() {
+    if create_bucket_dir(&branch_path).is_err() {
         return ErRoot::RepoLocal(String::new());
     }
     let path_str = branch_path.to_string_lossy().into_owned();
@@ -114,7 +215,7 @@ pub fn resolve_managed_root_from_slugs(repo_slug: &str, branch_slug: &str) -> Er
 /// Layout: `<storage_root>/repos/<repo_slug>/branches/<branch_slug>/view-buckets/<bucket>`
 /// Bucket is one of `"branch"`, `"unstaged"`, `"staged"`, or `"history"`.
 pub fn view_bucket_dir(repo_slug: &str, branch_slug: &str, bucket: &str) -> PathBuf {
-    storage_root()
+    artifact_root()
         .join("repos")
         .join(repo_slug)
         .join("branches")
@@ -128,7 +229,7 @@ pub fn view_bucket_dir(repo_slug: &str, branch_slug: &str, bucket: &str) -> Path
 /// Layout: `<storage_root>/repos/<owner_repo_slug>/prs/pr-<N>`
 /// `owner_repo_slug` is the slugified `owner-repo` string (e.g. `"myorg-myrepo"`).
 pub fn pr_bucket_dir(owner_repo_slug: &str, pr_number: u64) -> PathBuf {
-    storage_root()
+    artifact_root()
         .join("repos")
         .join(owner_repo_slug)
         .join("prs")
@@ -145,7 +246,7 @@ pub fn resolve_managed_root_for_view_bucket(
     bucket: &str,
 ) -> ErRoot {
     let dir = view_bucket_dir(repo_slug, branch_slug, bucket);
-    if std::fs::create_dir_all(&dir).is_err() {
+    if create_bucket_dir(&dir).is_err() {
         return ErRoot::RepoLocal(String::new());
     }
     let path_str = dir.to_string_lossy().into_owned();
@@ -161,7 +262,7 @@ pub fn resolve_managed_root_for_view_bucket(
 /// on failure.
 pub fn resolve_managed_root_for_pr_bucket(owner_repo_slug: &str, pr_number: u64) -> ErRoot {
     let dir = pr_bucket_dir(owner_repo_slug, pr_number);
-    if std::fs::create_dir_all(&dir).is_err() {
+    if create_bucket_dir(&dir).is_err() {
         return ErRoot::RepoLocal(String::new());
     }
     let path_str = dir.to_string_lossy().into_owned();
@@ -171,9 +272,36 @@ pub fn resolve_managed_root_for_pr_bucket(owner_repo_slug: &str, pr_number: u64)
     }
 }
 
-/// True when `ER_REPO_LOCAL=1` — use repo `.er/` instead of managed storage.
+/// True when `ER_REPO_LOCAL=1` or `[storage] location = "repo"` — use repo
+/// `.er/` instead of managed storage.
 pub fn use_repo_local_storage() -> bool {
-    std::env::var("ER_REPO_LOCAL").as_deref() == Ok("1")
+    std::env::var("ER_REPO_LOCAL").as_deref() == Ok("1") || REPO_LOCATION.load(Ordering::Relaxed)
+}
+
+/// Create a bucket dir, filling it from the same bucket in the data dir
+/// when artifacts moved to `.git/er/` and it's still empty.
+fn create_bucket_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    if let Some(relative) =
+        git_artifact_root().and_then(|root| dir.strip_prefix(&root).ok().map(Path::to_path_buf))
+    {
+        migrate_dir_if_empty(dir, &storage_root().join(relative))?;
+    }
+    Ok(())
+}
+
+/// Fill `<repo_root>/.er/` from the data-dir bucket of `branch_slug` when
+/// `[storage] location = "repo"` is new for this repo.
+pub fn migrate_into_repo_local(repo_root: &str, branch_slug: &str) -> std::io::Result<bool> {
+    let repo_er = Path::new(repo_root).join(".er");
+    let managed = storage_root()
+        .join("repos")
+        .join(slug_repo(repo_root))
+        .join("branches")
+        .join(branch_slug)
+        .join("view-buckets")
+        .join("branch");
+    migrate_dir_if_empty(&repo_er, &managed)
 }
 
 /// Whether the managed directory has any review artifacts yet.
@@ -321,6 +449,40 @@ mod tests {
         assert!(agent_dir.contains("feature-branch"));
     }
 
+    #[test]
+    fn git_location_moves_buckets_into_git_dir() {
+        let _guard = STORAGE_TEST_ENV_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&repo)
+            .status()
+            .unwrap();
+        let data = tmp.path().join("data");
+        std::env::set_var("ER_STORAGE_ROOT", &data);
+        let old = view_bucket_dir("r", "main", "branch");
+        std::fs::create_dir_all(&old).unwrap();
+        std::fs::write(old.join("review.json"), "{}").unwrap();
+
+        set_location(StorageLocation::parse("git"), &repo.to_string_lossy());
+        let root = resolve_managed_root_for_view_bucket("r", "main", "branch");
+        set_location(StorageLocation::Data, "");
+        std::env::remove_var("ER_STORAGE_ROOT");
+
+        let crate::ErRoot::Managed { agent_dir, .. } = root else {
+            panic!("expected Managed root");
+        };
+        let agent_dir = PathBuf::from(agent_dir);
+        assert!(agent_dir.starts_with(repo.join(".git").join("er")));
+        assert!(agent_dir.join("review.json").exists());
+        assert_eq!(StorageLocation::parse("Repo"), StorageLocation::Repo);
+        assert_eq!(StorageLocation::parse(""), StorageLocation::Data);
+    }
+
     #[test]
     fn migrate_skips_when_managed_has_files() {
         let tmp = TempDir::new().unwrap();
@@ -12,7 +12,7 @@
 
 use anyhow::Result;
 
-use crate::ai;
+use crate::ai::{self, store::Sidecar};
 use crate::git;
 use crate::github;
 use crate::github::PrOverviewData;
@@ -35,7 +35,7 @@ pub fn chrono_now() -> String {
 ///
 /// Pure calendar math — no external crate and no system clock — so the leap-year
 /// and month-walking logic is unit-testable with known inputs.
-fn format_iso8601(secs: u64) -> String {
+pub(crate) fn format_iso8601(secs: u64) -> String {
     let days = secs / 86400;
     let remaining = secs % 86400;
     let hours = remaining / 3600;
@@ -109,7 +109,8 @@ pub struct CommentSyncContext {
     pub pr_number: u64,
     pub is_remote: bool,
     pub repo_root: String,
-    pub comments_path: String,
+    /// Where `github-comments.json` lives (the PR bucket for PR tabs)
+    pub comments_dir: String,
     /// SHA-256 of the branch diff (stored in comments JSON)
     pub diff_hash: String,
     /// Current diff hash (for anchor status on new comments)
@@ -118,6 +119,8 @@ pub struct CommentSyncContext {
     pub files: Vec<git::DiffFile>,
     /// pr_number from the tab field (may differ from pr_number in local mode)
     pub pr_number_for_overview: Option<u64>,
+    /// Also fetch the PR's "viewed" files (`[features] github_viewed_sync`)
+    pub sync_viewed: bool,
 }
 
 /// Pre-processed results ready to apply to App state.
@@ -126,8 +129,12 @@ pub struct CommentSyncResult {
     pub pr_data: Option<PrOverviewData>,
     pub github_count: usize,
     pub local_count: usize,
+    /// Comments edited both here and on GitHub (see [`merge_github_comments`])
+    pub conflicts: usize,
     pub is_remote: bool,
     pub comments_path: String,
+    /// The PR's "viewed" files, when asked for and the query worked
+    pub viewed: Option<github::PrViewedFiles>,
     /// Tab identity for safe application without race conditions.
     /// (repo_root, pr_number, is_remote)
     pub tab_key: (String, Option<u64>, bool),
@@ -137,6 +144,80 @@ pub(crate) fn merged_outdated_state(thread_state: ReviewThreadState, rest_outdat
     thread_state.outdated || rest_outdated
 }
 
+/// Merge freshly fetched GitHub comments with the local file's. Unpushed
+/// local comments are kept as they are. A pushed comment that was edited
+/// here since the last sync (`edit_base` set) is merged three ways against
+/// GitHub's current body, matched by GitHub id: GitHub unchanged keeps our
+/// edit pending, both bodies equal settles it, and both sides changed keeps
+/// ours with GitHub's in `remote_body` for the reviewer to resolve. Returns
+/// the merged comments and how many are in conflict.
+pub fn merge_github_comments(
+    local: Vec<ai::GitHubReviewComment>,
+    fetched: Vec<ai::GitHubReviewComment>,
+) -> (Vec<ai::GitHubReviewComment>, usize) {
+    let edited: Vec<(u64, String, String)> = local
+        .iter()
+        .filter_map(|c| Some((c.github_id?, c.edit_base.clone()?, c.comment.clone())))
+        .collect();
+    let mut merged: Vec<_> = local
+        .into_iter()
+        .filter(|c| c.source == "local" && !c.synced)
+        .collect();
+    let mut conflicts = 0;
+    for mut remote in fetched {
+        let Some((_, base, ours)) = edited.iter().find(|(id, ..)| remote.github_id == Some(*id))
+        else {
+            merged.push(remote);
+            continue;
+        };
+        if remote.comment != *ours {
+            if remote.comment != *base {
+                remote.remote_body = Some(std::mem::take(&mut remote.comment));
+                conflicts += 1;
+            }
+            remote.comment = ours.clone();
+            remote.edit_base = Some(base.clone());
+        }
+        merged.push(remote);
+    }
+    (merged, conflicts)
+}
+
+/// `, 2 in conflict` for sync summaries; empty when there are none.
+pub fn conflict_note(conflicts: usize) -> String {
+    if conflicts == 0 {
+        String::new()
+    } else {
+        format!(", {} in conflict", conflicts)
+    }
+}
+
+/// Send local edits of comments already on GitHub (see
+/// [`ai::GitHubReviewComment::edit_body`]); conflicted ones wait for the
+/// reviewer. Returns how many went up and the failures.
+pub fn push_comment_edits(
+    comments: &mut [ai::GitHubReviewComment],
+    owner: &str,
+    repo: &str,
+    repo_root: &str,
+) -> (u32, Vec<anyhow::Error>) {
+    let mut pushed = 0;
+    let mut errors = Vec::new();
+    for c in comments.iter_mut().filter(|c| c.has_unpushed_edit()) {
+        let Some(github_id) = c.github_id else {
+            continue;
+        };
+        match github::gh_pr_update_review_comment(owner, repo, github_id, &c.comment, repo_root) {
+            Ok(()) => {
+                c.edit_base = None;
+                pushed += 1;
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    (pushed, errors)
+}
+
 /// Perform the network I/O and data processing for a comment sync.
 /// Does NOT hold the App mutex — all data comes from `CommentSyncContext`.
 /// Writes the comments JSON file to disk before returning.
@@ -155,20 +236,10 @@ pub fn fetch_comment_sync_data(ctx: &CommentSyncContext) -> Result<CommentSyncRe
             .unwrap_or_default()
     };
 
-    let mut gc: ai::ErGitHubComments = match std::fs::read_to_string(&ctx.comments_path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| ai::ErGitHubComments {
-            version: 1,
-            diff_hash: ctx.diff_hash.clone(),
-            github: None,
-            comments: Vec::new(),
-        }),
-        Err(_) => ai::ErGitHubComments {
-            version: 1,
-            diff_hash: ctx.diff_hash.clone(),
-            github: None,
-            comments: Vec::new(),
-        },
-    };
+    // Locked from here to the write so local comments added meanwhile survive
+    let _lock = ai::store::lock::<ai::ErGitHubComments>(&ctx.comments_dir)?;
+    let mut gc = ai::store::load(&ctx.comments_dir)?
+        .unwrap_or_else(|| ai::ErGitHubComments::empty(&ctx.diff_hash));
 
     gc.github = Some(ai::GitHubSyncState {
         pr_number: Some(ctx.pr_number),
@@ -177,11 +248,11 @@ pub fn fetch_comment_sync_data(ctx: &CommentSyncContext) -> Result<CommentSyncRe
         last_synced: chrono_now(),
     });
 
-    let local_unpushed: Vec<_> = gc
-        .comments
-        .into_iter()
+    let local = std::mem::take(&mut gc.comments);
+    let local_count = local
+        .iter()
         .filter(|c| c.source == "local" && !c.synced)
-        .collect();
+        .count();
 
     let mut github_entries = Vec::new();
     for gh in &gh_comments {
@@ -191,7 +262,11 @@ pub fn fetch_comment_sync_data(ctx: &CommentSyncContext) -> Result<CommentSyncRe
             (Some(start), Some(end)) if end > start => Some(end),
             _ => None,
         };
-        let resolved_line: Option<usize> = if let (Some(diff_hunk), Some(f)) = (
+        // File-level comments stay unanchored — shown above the first hunk
+        let file_level = gh.subject_type.as_deref() == Some("file");
+        let resolved_line: Option<usize> = if file_level {
+            None
+        } else if let (Some(diff_hunk), Some(f)) = (
             &gh.diff_hunk,
             ctx.files.iter().find(|f| f.path == file_path),
         ) {
@@ -242,24 +317,20 @@ pub fn fetch_comment_sync_data(ctx: &CommentSyncContext) -> Result<CommentSyncRe
             hunk_header: anchor_hunk_header,
             anchor_status: "original".to_string(),
             relocated_at_hash: ctx.anchor_hash.clone(),
+            anchor_confidence: None,
             finding_ref: None,
             side: gh.side.clone().unwrap_or_else(|| "RIGHT".to_string()),
+            edit_base: None,
+            remote_body: None,
         });
     }
 
     let github_count = github_entries.len();
-    let local_count = local_unpushed.len();
-    gc.comments = local_unpushed;
-    gc.comments.extend(github_entries);
+    let (comments, conflicts) = merge_github_comments(local, github_entries);
+    gc.comments = comments;
 
     // Write to disk (atomic rename, outside app lock)
-    if let Some(dir) = std::path::Path::new(&ctx.comments_path).parent() {
-        std::fs::create_dir_all(dir)?;
-    }
-    let json = serde_json::to_string_pretty(&gc)?;
-    let tmp_path = format!("{}.tmp", ctx.comments_path);
-    std::fs::write(&tmp_path, &json)?;
-    std::fs::rename(&tmp_path, &ctx.comments_path)?;
+    ai::store::write(&ctx.comments_dir, &gc)?;
 
     // Refresh PR overview (still outside app lock).
     // Remote PRs already skip the CI-checks subprocess inside
@@ -282,13 +353,22 @@ pub fn fetch_comment_sync_data(ctx: &CommentSyncContext) -> Result<CommentSyncRe
         github::gh_pr_overview_no_checks(&ctx.repo_root, ctx.pr_number_for_overview)
     };
 
+    let viewed = if ctx.sync_viewed {
+        let cwd = (!ctx.is_remote).then_some(ctx.repo_root.as_str());
+        github::gh_pr_viewed_files(&ctx.owner, &ctx.repo_name, ctx.pr_number, cwd).ok()
+    } else {
+        None
+    };
+
     Ok(CommentSyncResult {
         gc,
         pr_data,
+        viewed,
         github_count,
         local_count,
+        conflicts,
         is_remote: ctx.is_remote,
-        comments_path: ctx.comments_path.clone(),
+        comments_path: ai::store::path::<ai::ErGitHubComments>(&ctx.comments_dir),
         tab_key: (
             ctx.repo_root.clone(),
             ctx.pr_number_for_overview,
@@ -605,6 +685,47 @@ mod tests {
         assert!(!merged_outdated_state(state, false));
     }
 
+    #[test]
+    fn merge_keeps_local_edits_and_flags_both_sides_changed() {
+        let comment = |id: &str, github_id: u64, body: &str| {
+            let mut c: ai::GitHubReviewComment = serde_json::from_value(serde_json::json!({
+                "id": id, "file": "a.rs", "hunk_index": 0, "line_start": 1,
+                "line_end": null, "comment": "original", "github_id": github_id,
+                "source": "github", "synced": true,
+            }))
+            .unwrap();
+            c.edit_body(body);
+            c
+        };
+        let local = vec![
+            comment("gh-1", 1, "ours, only here"),
+            comment("gh-2", 2, "ours"),
+            comment("gh-3", 3, "same on both"),
+            comment("gh-4", 4, "original"),
+        ];
+        let mut fetched = vec![
+            comment("gh-1", 1, "original"),
+            comment("gh-2", 2, "theirs"),
+            comment("gh-3", 3, "same on both"),
+            comment("gh-4", 4, "theirs, only there"),
+        ];
+        for c in &mut fetched {
+            c.edit_base = None;
+        }
+
+        let (merged, conflicts) = merge_github_comments(local, fetched);
+        assert_eq!(conflicts, 1);
+        let by_id = |id: &str| merged.iter().find(|c| c.id == id).unwrap();
+        assert_eq!(by_id("gh-1").comment, "ours, only here");
+        assert!(by_id("gh-1").has_unpushed_edit());
+        assert_eq!(by_id("gh-2").comment, "ours");
+        assert_eq!(by_id("gh-2").remote_body.as_deref(), Some("theirs"));
+        assert_eq!(by_id("gh-2").edit_base.as_deref(), Some("original"));
+        assert!(!by_id("gh-3").has_unpushed_edit());
+        assert_eq!(by_id("gh-4").comment, "theirs, only there");
+        assert!(by_id("gh-4").edit_base.is_none());
+    }
+
     // ── format_iso8601 ────────────────────────────────────────────────────────
 
     #[test]
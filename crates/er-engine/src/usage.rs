@@ -0,0 +1,354 @@
+//! Local usage stats behind `er stats`: one record per reviewed tab per
+//! session — active review time, the diff's size, files marked, comments
+//! and which features were opened — appended to `usage.jsonl` under the
+//! storage root when er exits.
+//!
+//! Nothing here is sent anywhere. Turn recording off with
+//! `[usage] record = false`; delete the file to forget it.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// One tab's share of a session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Unix seconds the tab was first used
+    pub started: u64,
+    /// Unix seconds the session ended
+    pub ended: u64,
+    pub repo: String,
+    pub branch: String,
+    #[serde(default)]
+    pub pr: Option<u64>,
+    /// Time spent pressing keys in this tab, idle gaps capped
+    pub active_secs: u64,
+    pub files: usize,
+    pub adds: usize,
+    pub dels: usize,
+    /// Files marked reviewed by the end
+    pub reviewed: usize,
+    /// Comments added this session
+    #[serde(default)]
+    pub comments: u32,
+    /// Feature (`hub.git`, `mode.history`, `input.search`, …) → times opened
+    #[serde(default)]
+    pub features: BTreeMap<String, u32>,
+}
+
+impl UsageRecord {
+    pub fn lines(&self) -> usize {
+        self.adds + self.dels
+    }
+}
+
+pub fn usage_path() -> PathBuf {
+    crate::storage::storage_root().join("usage.jsonl")
+}
+
+/// Append `records` to the usage file.
+pub fn append(records: &[UsageRecord]) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let path = usage_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    file.write_all(out.as_bytes())?;
+    #[cfg(feature = "sqlite")]
+    crate::review_db::record_sessions(records);
+    Ok(())
+}
+
+/// Records ended at or after `since` (unix seconds). Lines that don't
+/// parse are skipped.
+pub fn load_since(since: u64) -> Vec<UsageRecord> {
+    std::fs::read_to_string(usage_path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<UsageRecord>(line).ok())
+        .filter(|r| r.ended >= since)
+        .collect()
+}
+
+/// `30d`, `2w`, `12h` or a bare number of days, in seconds.
+pub fn parse_window(window: &str) -> Option<u64> {
+    let window = window.trim();
+    let (number, unit) = match window.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, _)) => window.split_at(i),
+        None => (window, "d"),
+    };
+    let n: u64 = number.parse().ok()?;
+    let unit_secs = match unit {
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return None,
+    };
+    Some(n * unit_secs)
+}
+
+/// `1h 5m`, `12m`, `<1m`.
+pub fn format_duration(secs: u64) -> String {
+    let minutes = secs / 60;
+    match minutes {
+        0 => "<1m".to_string(),
+        m if m < 60 => format!("{}m", m),
+        m => format!("{}h {}m", m / 60, m % 60),
+    }
+}
+
+fn median(mut values: Vec<u64>) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+/// One branch or PR, across the sessions spent on it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReviewTotals {
+    pub repo: String,
+    pub branch: String,
+    pub pr: Option<u64>,
+    pub sessions: usize,
+    pub active_secs: u64,
+    /// Changed lines the last time it was open
+    pub lines: usize,
+    pub files: usize,
+}
+
+/// A repo's share of the window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RepoTotals {
+    pub repo: String,
+    pub reviews: usize,
+    pub active_secs: u64,
+    pub lines: usize,
+}
+
+/// What `er stats` prints, and `--json` emits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub sessions: usize,
+    pub reviews: Vec<ReviewTotals>,
+    pub repos: Vec<RepoTotals>,
+    pub active_secs: u64,
+    pub median_review_secs: u64,
+    pub median_review_lines: u64,
+    pub files_reviewed: usize,
+    pub comments: u32,
+    /// Most used first
+    pub features: Vec<(String, u32)>,
+}
+
+fn repo_name(repo: &str) -> String {
+    std::path::Path::new(repo)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo.to_string())
+}
+
+pub fn summarize(records: &[UsageRecord]) -> Summary {
+    let mut reviews: BTreeMap<(String, String, Option<u64>), (u64, ReviewTotals)> = BTreeMap::new();
+    let mut features: BTreeMap<String, u32> = BTreeMap::new();
+    let mut summary = Summary {
+        sessions: records.len(),
+        ..Default::default()
+    };
+    for r in records {
+        summary.active_secs += r.active_secs;
+        summary.files_reviewed += r.reviewed;
+        summary.comments += r.comments;
+        for (name, count) in &r.features {
+            *features.entry(name.clone()).or_default() += count;
+        }
+        let key = (r.repo.clone(), r.branch.clone(), r.pr);
+        let (last_ended, totals) = reviews.entry(key).or_insert_with(|| {
+            (
+                0,
+                ReviewTotals {
+                    repo: repo_name(&r.repo),
+                    branch: r.branch.clone(),
+                    pr: r.pr,
+                    ..Default::default()
+                },
+            )
+        });
+        totals.sessions += 1;
+        totals.active_secs += r.active_secs;
+        if r.ended >= *last_ended {
+            *last_ended = r.ended;
+            totals.lines = r.lines();
+            totals.files = r.files;
+        }
+    }
+    summary.reviews = reviews.into_values().map(|(_, t)| t).collect();
+    summary
+        .reviews
+        .sort_by_key(|r| std::cmp::Reverse(r.active_secs));
+    summary.median_review_secs = median(summary.reviews.iter().map(|r| r.active_secs).collect());
+    summary.median_review_lines = median(summary.reviews.iter().map(|r| r.lines as u64).collect());
+
+    let mut repos: BTreeMap<String, RepoTotals> = BTreeMap::new();
+    for review in &summary.reviews {
+        let totals = repos
+            .entry(review.repo.clone())
+            .or_insert_with(|| RepoTotals {
+                repo: review.repo.clone(),
+                ..Default::default()
+            });
+        totals.reviews += 1;
+        totals.active_secs += review.active_secs;
+        totals.lines += review.lines;
+    }
+    summary.repos = repos.into_values().collect();
+    summary
+        .repos
+        .sort_by_key(|r| std::cmp::Reverse(r.active_secs));
+
+    summary.features = features.into_iter().collect();
+    summary
+        .features
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    summary
+}
+
+/// How many most-used features `er stats` lists.
+const TOP_FEATURES: usize = 10;
+
+/// The plain-text report for `window` (as typed, e.g. `30d`).
+pub fn format_summary(summary: &Summary, window: &str) -> String {
+    if summary.sessions == 0 {
+        return format!("No reviews recorded in the last {}\n", window);
+    }
+    let mut out = format!(
+        "Last {} — {} reviews over {} sessions in {} repos\n\n",
+        window,
+        summary.reviews.len(),
+        summary.sessions,
+        summary.repos.len()
+    );
+    out.push_str(&format!(
+        "Review time     {} active (median {} per review)\n",
+        format_duration(summary.active_secs),
+        format_duration(summary.median_review_secs)
+    ));
+    if let Some(largest) = summary.reviews.iter().max_by_key(|r| r.lines) {
+        let which = match largest.pr {
+            Some(pr) => format!("{} #{}", largest.repo, pr),
+            None => format!("{} {}", largest.repo, largest.branch),
+        };
+        out.push_str(&format!(
+            "Review size     median {} lines, largest {} lines ({})\n",
+            summary.median_review_lines, largest.lines, which
+        ));
+    }
+    out.push_str(&format!("Files reviewed  {}\n", summary.files_reviewed));
+    out.push_str(&format!("Comments        {}\n", summary.comments));
+
+    let width = summary
+        .repos
+        .iter()
+        .map(|r| r.repo.len())
+        .max()
+        .unwrap_or_default();
+    out.push_str("\nBy repo\n");
+    for repo in &summary.repos {
+        out.push_str(&format!(
+            "  {:width$}  {:>3} reviews  {:>7}  {:>6} lines\n",
+            repo.repo,
+            repo.reviews,
+            format_duration(repo.active_secs),
+            repo.lines
+        ));
+    }
+
+    if !summary.features.is_empty() {
+        let width = summary
+            .features
+            .iter()
+            .take(TOP_FEATURES)
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or_default();
+        out.push_str("\nMost used\n");
+        for (name, count) in summary.features.iter().take(TOP_FEATURES) {
+            out.push_str(&format!("  {:width$}  {}\n", name, count));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(repo: &str, pr: Option<u64>, ended: u64, active: u64, lines: usize) -> UsageRecord {
+        UsageRecord {
+            started: ended - active,
+            ended,
+            repo: repo.to_string(),
+            branch: "feature".to_string(),
+            pr,
+            active_secs: active,
+            files: 3,
+            adds: lines,
+            dels: 0,
+            reviewed: 2,
+            comments: 1,
+            features: BTreeMap::from([("hub.git".to_string(), 2)]),
+        }
+    }
+
+    #[test]
+    fn windows_parse_and_durations_format() {
+        assert_eq!(parse_window("30d"), Some(30 * 86_400));
+        assert_eq!(parse_window("2w"), Some(14 * 86_400));
+        assert_eq!(parse_window("12h"), Some(12 * 3600));
+        assert_eq!(parse_window("7"), Some(7 * 86_400));
+        assert_eq!(parse_window("3y"), None);
+        assert_eq!(format_duration(30), "<1m");
+        assert_eq!(format_duration(65 * 60), "1h 5m");
+    }
+
+    #[test]
+    fn sessions_on_the_same_pr_add_up_to_one_review() {
+        let records = vec![
+            record("/src/api", Some(7), 1_000, 600, 100),
+            record("/src/api", Some(7), 5_000, 300, 120),
+            record("/src/web", None, 3_000, 60, 40),
+        ];
+        let summary = summarize(&records);
+        assert_eq!(summary.sessions, 3);
+        assert_eq!(summary.reviews.len(), 2);
+        assert_eq!(summary.reviews[0].active_secs, 900);
+        assert_eq!(
+            summary.reviews[0].lines, 120,
+            "size from the latest session"
+        );
+        assert_eq!(summary.repos[0].repo, "api");
+        assert_eq!(summary.features, vec![("hub.git".to_string(), 6)]);
+        assert_eq!(summary.comments, 3);
+
+        let text = format_summary(&summary, "30d");
+        assert!(text.contains("2 reviews over 3 sessions in 2 repos"));
+        assert!(text.contains("largest 120 lines (api #7)"));
+        assert!(format_summary(&Summary::default(), "7d").starts_with("No reviews"));
+    }
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use notify::{RecommendedWatcher, RecursiveMode};
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
-use std::path::Path;
-use std::sync::mpsc;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 
 /// Events emitted by the file watcher
@@ -10,17 +10,24 @@ use std::time::Duration;
 pub enum WatchEvent {
     /// One or more files changed — time to refresh diffs
     FilesChanged(Vec<String>),
+    /// Files under a directory given to [`FileWatcher::watch_ai_dirs`]
+    /// changed — time to reload AI data
+    AiFilesChanged(Vec<String>),
 }
 
 /// A debounced file watcher that monitors a git working tree
 pub struct FileWatcher {
-    _watcher: notify_debouncer_mini::Debouncer<RecommendedWatcher>,
+    debouncer: notify_debouncer_mini::Debouncer<RecommendedWatcher>,
+    /// Where AI sidecars live, shared with the event callback
+    ai_dirs: Arc<Mutex<Vec<PathBuf>>>,
 }
 
 impl FileWatcher {
     /// Start watching a directory. Changed file events are sent to the provided sender.
     /// Events are debounced by `debounce_ms` milliseconds.
     pub fn new(root: &Path, debounce_ms: u64, tx: mpsc::Sender<WatchEvent>) -> Result<Self> {
+        let ai_dirs: Arc<Mutex<Vec<PathBuf>>> = Arc::default();
+        let callback_ai_dirs = Arc::clone(&ai_dirs);
         let mut debouncer = new_debouncer(
             Duration::from_millis(debounce_ms),
             move |result: std::result::Result<
@@ -30,15 +37,31 @@ impl FileWatcher {
                 // Watcher errors are discarded — if the OS watch limit is hit
                 // (e.g. inotify ENOSPC), live updates silently stop.
                 if let Ok(events) = result {
-                    let paths: Vec<String> = events
+                    let events: Vec<_> = events
                         .iter()
                         .filter(|e| e.kind == DebouncedEventKind::Any)
+                        .collect();
+                    let (ai_events, events): (Vec<_>, Vec<_>) = match callback_ai_dirs.lock() {
+                        Ok(dirs) => events
+                            .into_iter()
+                            .partition(|e| dirs.iter().any(|d| e.path.starts_with(d))),
+                        Err(_) => (Vec::new(), events),
+                    };
+                    if !ai_events.is_empty() {
+                        let paths = ai_events
+                            .iter()
+                            .map(|e| e.path.to_string_lossy().to_string())
+                            .collect();
+                        let _ = tx.send(WatchEvent::AiFilesChanged(paths));
+                    }
+                    let paths: Vec<String> = events
+                        .iter()
                         .filter_map(|e| {
                             let p = e.path.to_string_lossy().to_string();
                             // Skip .er/ directory — written by er itself (session saves,
                             // reviewed markers, comments, snapshots). Watching these causes
                             // spurious "N files changed" refresh loops. AI sidecar files
-                            // are polled separately via mtime checks.
+                            // come through `watch_ai_dirs` instead.
                             if p.contains("/.er/") {
                                 return None;
                             }
@@ -67,8 +90,38 @@ impl FileWatcher {
 
         debouncer.watcher().watch(root, RecursiveMode::Recursive)?;
 
-        Ok(FileWatcher {
-            _watcher: debouncer,
-        })
+        Ok(FileWatcher { debouncer, ai_dirs })
+    }
+
+    /// Also watch `dirs` (the active tab's AI sidecars), reporting their
+    /// changes as [`WatchEvent::AiFilesChanged`]. Replaces the previous set;
+    /// a no-op when it's the same. Fails when a directory doesn't exist yet,
+    /// leaving none watched.
+    pub fn watch_ai_dirs(&mut self, dirs: &[String]) -> Result<()> {
+        let dirs: Vec<PathBuf> = dirs.iter().map(PathBuf::from).collect();
+        let mut current = self
+            .ai_dirs
+            .lock()
+            .map_err(|_| anyhow::anyhow!("watcher state poisoned"))?;
+        if *current == dirs {
+            return Ok(());
+        }
+        for old in current.drain(..) {
+            let _ = self.debouncer.watcher().unwatch(&old);
+        }
+        for (i, dir) in dirs.iter().enumerate() {
+            if let Err(e) = self
+                .debouncer
+                .watcher()
+                .watch(dir, RecursiveMode::Recursive)
+            {
+                for watched in &dirs[..i] {
+                    let _ = self.debouncer.watcher().unwatch(watched);
+                }
+                return Err(e.into());
+            }
+        }
+        *current = dirs;
+        Ok(())
     }
 }
@@ -0,0 +1,222 @@
+//! Outbound webhook notifications for review milestones.
+//!
+//! Configured via `[notifications]` in the global config. Delivery shells out
+//! to `curl` on a background thread (the engine carries no HTTP client), so a
+//! slow or unreachable endpoint never blocks the UI. Failures are dropped —
+//! notifications are best-effort.
+
+use crate::config::NotificationsConfig;
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Review milestones that can be posted to the webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// Every file in the diff has been marked reviewed.
+    ReviewCompleted,
+    /// The PR was approved from er.
+    Approved,
+    /// An AI review added a new high-severity finding.
+    HighFinding,
+    /// Local comments were pushed to GitHub.
+    CommentsPushed,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::ReviewCompleted,
+        WebhookEvent::Approved,
+        WebhookEvent::HighFinding,
+        WebhookEvent::CommentsPushed,
+    ];
+
+    /// Config / payload identifier.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::ReviewCompleted => "review_completed",
+            WebhookEvent::Approved => "approved",
+            WebhookEvent::HighFinding => "high_finding",
+            WebhookEvent::CommentsPushed => "comments_pushed",
+        }
+    }
+
+    fn emoji(self) -> &'static str {
+        match self {
+            WebhookEvent::ReviewCompleted => ":white_check_mark:",
+            WebhookEvent::Approved => ":rocket:",
+            WebhookEvent::HighFinding => ":rotating_light:",
+            WebhookEvent::CommentsPushed => ":speech_balloon:",
+        }
+    }
+}
+
+/// Where the milestone happened — included in the payload so a shared channel
+/// can tell reviews apart.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookContext {
+    /// `owner/repo` when known, else the repo directory name.
+    pub repo: String,
+    pub branch: String,
+    pub pr_number: Option<u64>,
+}
+
+/// Build the JSON body. `text` is what Slack renders; the other keys are for
+/// generic receivers.
+pub fn build_payload(
+    event: WebhookEvent,
+    ctx: &WebhookContext,
+    message: &str,
+) -> serde_json::Value {
+    let target = match ctx.pr_number {
+        Some(n) => format!("{} #{}", ctx.repo, n),
+        None => format!("{} ({})", ctx.repo, ctx.branch),
+    };
+    json!({
+        "text": format!("{} [{}] {}", event.emoji(), target, message),
+        "event": event.as_str(),
+        "repo": ctx.repo,
+        "branch": ctx.branch,
+        "pr": ctx.pr_number,
+        "message": message,
+    })
+}
+
+/// Post `event` if the config enables it. Returns `true` when a delivery was
+/// started (not whether it succeeded).
+pub fn send(
+    config: &NotificationsConfig,
+    event: WebhookEvent,
+    ctx: &WebhookContext,
+    message: &str,
+) -> bool {
+    if !config.wants(event.as_str()) {
+        return false;
+    }
+    let Some(url) = config.webhook_url.clone() else {
+        return false;
+    };
+    let body = build_payload(event, ctx, message).to_string();
+    std::thread::spawn(move || {
+        let _ = post_json(&url, &body);
+    });
+    true
+}
+
+/// curl config for the request. The URL is a credential (Slack, Discord),
+/// so it goes to curl on stdin rather than argv, where `ps` would show it.
+fn curl_config(url: &str, body: &str) -> String {
+    format!(
+        "url = {}\ndata-binary = {}\n",
+        quote_config(url),
+        quote_config(body)
+    )
+}
+
+/// Double-quoted curl config value; `\\`, `"` and line breaks escaped.
+fn quote_config(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn post_json(url: &str, body: &str) -> std::io::Result<()> {
+    let mut child = Command::new("curl")
+        .args([
+            "-sS",
+            "-X",
+            "POST",
+            "--max-time",
+            "10",
+            "-H",
+            "Content-Type: application/json",
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(curl_config(url, body).as_bytes())?;
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> WebhookContext {
+        WebhookContext {
+            repo: "acme/widgets".into(),
+            branch: "feature/x".into(),
+            pr_number: None,
+        }
+    }
+
+    #[test]
+    fn payload_has_slack_text_and_structured_fields() {
+        let p = build_payload(WebhookEvent::Approved, &ctx(), "PR approved");
+        assert_eq!(p["event"], "approved");
+        assert_eq!(p["repo"], "acme/widgets");
+        let text = p["text"].as_str().unwrap();
+        assert!(text.contains("acme/widgets (feature/x)"));
+        assert!(text.ends_with("PR approved"));
+    }
+
+    #[test]
+    fn payload_prefers_pr_number_in_text() {
+        let mut c = ctx();
+        c.pr_number = Some(42);
+        let p = build_payload(WebhookEvent::CommentsPushed, &c, "Pushed 3 comments");
+        assert!(p["text"].as_str().unwrap().contains("acme/widgets #42"));
+        assert_eq!(p["pr"], 42);
+    }
+
+    #[test]
+    fn send_is_noop_without_url_or_event() {
+        let mut config = NotificationsConfig::default();
+        assert!(!send(&config, WebhookEvent::Approved, &ctx(), "x"));
+
+        config.webhook_url = Some("   ".into());
+        assert!(!send(&config, WebhookEvent::Approved, &ctx(), "x"));
+
+        config.webhook_url = Some("http://127.0.0.1:9/hook".into());
+        config.events = vec!["review_completed".into()];
+        assert!(!config.wants("approved"));
+        assert!(config.wants("review_completed"));
+    }
+
+    #[test]
+    fn curl_config_quotes_url_and_body() {
+        let config = curl_config(
+            "https://hooks.example/T0/B0?x=1",
+            r#"{"text":"a \"b\"\nc"}"#,
+        );
+        let lines: Vec<&str> = config.lines().collect();
+        assert_eq!(lines[0], r#"url = "https://hooks.example/T0/B0?x=1""#);
+        assert_eq!(
+            lines[1],
+            r#"data-binary = "{\"text\":\"a \\\"b\\\"\\nc\"}""#
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn default_config_enables_all_events() {
+        let config = NotificationsConfig::default();
+        assert_eq!(config.events.len(), WebhookEvent::ALL.len());
+    }
+}
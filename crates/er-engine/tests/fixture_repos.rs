@@ -0,0 +1,125 @@
+//! Integration tests over real git repos from `er_engine::fixture_repo`
+//! (`cargo test -p er-engine --features test-support`).
+
+use std::sync::Mutex;
+
+use er_engine::ai::{relocate_comment, CommentAnchor, RelocationResult};
+use er_engine::app::App;
+use er_engine::fixture_repo::{self, FixtureRepo};
+use er_engine::git::{self, FileStatus};
+
+/// `App` writes review data under `ER_STORAGE_ROOT`, which is process-wide.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn open_app(repo: &FixtureRepo) -> (App, tempfile::TempDir) {
+    let storage = tempfile::tempdir().unwrap();
+    std::env::set_var("ER_STORAGE_ROOT", storage.path());
+    std::env::remove_var("ER_REPO_LOCAL");
+    (App::new_with_args(&[repo.root()]).expect("app"), storage)
+}
+
+#[test]
+fn refresh_diff_sees_every_kind_of_change() {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = fixture_repo::feature_branch().unwrap();
+    let (mut app, _storage) = open_app(&repo);
+
+    let status = |app: &App, path: &str| {
+        app.tab()
+            .files
+            .iter()
+            .find(|f| f.path == path)
+            .map(|f| f.status.clone())
+    };
+    assert_eq!(status(&app, "src/lib.rs"), Some(FileStatus::Modified));
+    assert_eq!(status(&app, "src/new.rs"), Some(FileStatus::Added));
+    assert_eq!(status(&app, "README.md"), Some(FileStatus::Deleted));
+    assert_eq!(
+        status(&app, "src/new_name.rs"),
+        Some(FileStatus::Renamed("src/old_name.rs".into()))
+    );
+
+    repo.write_generated("data/big.txt", 5_000).unwrap();
+    repo.commit("Add generated data").unwrap();
+    app.tab_mut().refresh_diff().unwrap();
+    let big = app
+        .tab()
+        .files
+        .iter()
+        .find(|f| f.path == "data/big.txt")
+        .expect("new file in the refreshed diff");
+    assert_eq!(big.adds, 5_000);
+}
+
+#[test]
+fn conflicted_merge_lists_the_unmerged_file() {
+    let repo = fixture_repo::conflicted().unwrap();
+    assert_eq!(
+        git::unmerged_files(&repo.root()).unwrap(),
+        vec!["conflict.txt"]
+    );
+    let diff = git::git_diff_conflicts(&repo.root()).unwrap();
+    assert!(diff.contains("<<<<<<<"), "{diff}");
+    assert!(diff.contains("feature two") && diff.contains("main two"));
+}
+
+#[test]
+fn history_lists_branch_commits_newest_first() {
+    let repo = fixture_repo::history(5).unwrap();
+    let commits = git::git_log_branch("main", &repo.root(), 50, 0).unwrap();
+    let subjects: Vec<&str> = commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(
+        subjects,
+        ["Commit 5", "Commit 4", "Commit 3", "Commit 2", "Commit 1"]
+    );
+}
+
+#[test]
+fn comment_follows_its_line_when_code_is_inserted_above() {
+    let repo = FixtureRepo::new().unwrap();
+    repo.write("src/lib.rs", "fn base() {}\n").unwrap();
+    repo.commit("base").unwrap();
+    repo.branch("feature").unwrap();
+    let body = "fn a() {}\nfn target() {\n    work();\n}\nfn b() {}\n";
+    repo.write("src/lib.rs", &format!("fn base() {{}}\n{}", body))
+        .unwrap();
+    repo.commit("add functions").unwrap();
+
+    let diff = |repo: &FixtureRepo| {
+        let raw = git::git_diff_raw("branch", "main", &repo.root(), None).unwrap();
+        git::parse_diff(&raw).remove(0)
+    };
+    let before = diff(&repo);
+    let line = before.hunks[0]
+        .lines
+        .iter()
+        .find(|l| l.content == "    work();")
+        .and_then(|l| l.new_num)
+        .unwrap();
+    let anchor = CommentAnchor {
+        file: "src/lib.rs".into(),
+        hunk_index: Some(0),
+        line_start: Some(line),
+        line_content: "    work();".into(),
+        context_before: vec!["fn target() {".into()],
+        context_after: vec!["}".into()],
+        old_line_start: None,
+        hunk_header: before.hunks[0].header.clone(),
+    };
+
+    repo.write(
+        "src/lib.rs",
+        &format!(
+            "fn base() {{}}\nfn inserted() {{}}\nfn also() {{}}\n{}",
+            body
+        ),
+    )
+    .unwrap();
+    repo.commit("insert above").unwrap();
+    match relocate_comment(&anchor, &diff(&repo)) {
+        RelocationResult::Relocated { new_line_start, .. } => {
+            assert_eq!(new_line_start, line + 2)
+        }
+        _ => panic!("expected the comment to move down two lines"),
+    }
+}
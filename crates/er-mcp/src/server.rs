@@ -13,6 +13,7 @@ use er_engine::review_queue::{
     open_in_easy_review, rank_low_hanging, rank_priority, score_pr, QueuePr, RankedPr,
     ReviewStatus,
 };
+use er_engine::review_session::ReviewSession;
 use er_engine::sidecar_specs::{artifact_specs, artifact_specs_for_dir};
 use er_engine::sidecar_summary::{list_repo_pr_artifacts, present_kinds, summarize_pr_sidecars};
 use er_engine::sidecar_upload::{
@@ -220,6 +221,75 @@ pub struct ScanLimitArgs {
     pub scan_limit: Option<u32>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SessionArgs {
+    /// Local checkout to review. Defaults to the project root (project_id or active project), else the server cwd.
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Base branch override (default: auto-detected, like `er`).
+    #[serde(default)]
+    pub base: Option<String>,
+    /// PR number when the TUI tab is a PR review (comments go to the PR bucket).
+    #[serde(default)]
+    pub pr: Option<u64>,
+    /// Limit to one repo-relative file path.
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SessionHunksArgs {
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Repo-relative file path.
+    pub file: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SessionAddCommentArgs {
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub pr: Option<u64>,
+    /// Repo-relative file path.
+    pub file: String,
+    /// New-side line number. Omit for a file-level comment.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// Last line of a multi-line range (inclusive).
+    #[serde(default)]
+    pub line_end: Option<usize>,
+    pub body: String,
+    /// Author shown in er (default "agent").
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SessionMarkReviewedArgs {
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Repo-relative file path.
+    pub file: String,
+    /// false to unmark (default true).
+    #[serde(default)]
+    pub reviewed: Option<bool>,
+}
+
 fn clamp_limit(limit: Option<u32>, default: u32) -> usize {
     limit.unwrap_or(default).clamp(1, 50) as usize
 }
@@ -237,13 +307,67 @@ fn text_json(value: &impl Serialize) -> Result<CallToolResult, McpError> {
     Ok(CallToolResult::success(vec![ContentBlock::text(body)]))
 }
 
+/// Local checkout for session tools: explicit path, else the Easy Review
+/// project root, else the server cwd.
+fn resolve_repo_path(
+    repo_path: Option<String>,
+    project_id: Option<&str>,
+) -> Result<String, McpError> {
+    if let Some(path) = repo_path.filter(|p| !p.trim().is_empty()) {
+        return Ok(path);
+    }
+    let file = projects::load_projects();
+    let project = match project_id.or(file.active_id.as_deref()) {
+        Some(id) => file.projects.iter().find(|p| p.id == id),
+        None => None,
+    };
+    if let Some(project) = project {
+        return Ok(project.root_path.clone());
+    }
+    if let Some(id) = project_id {
+        return Err(tool_err(format!("project not found: {id}")));
+    }
+    std::env::current_dir()
+        .map(|p| p.to_string_lossy().into_owned())
+        .map_err(|e| tool_err(e.to_string()))
+}
+
+/// Open the session on a blocking thread and run `f` against it.
+async fn with_session<T, F>(
+    repo_path: String,
+    base: Option<String>,
+    pr: Option<u64>,
+    f: F,
+) -> Result<T, McpError>
+where
+    T: Send + 'static,
+    F: FnOnce(ReviewSession) -> anyhow::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let session = ReviewSession::open(&repo_path, base.as_deref(), pr)?;
+        f(session)
+    })
+    .await
+    .map_err(|e| McpError::internal_error(e.to_string(), None))?
+    .map_err(|e| tool_err(e.to_string()))
+}
+
+fn session_json(session: &ReviewSession) -> serde_json::Value {
+    json!({
+        "repo_root": session.repo_root,
+        "branch": session.branch,
+        "base": session.base_branch,
+        "pr": session.pr_number,
+    })
+}
+
 fn tool_err(msg: impl Into<String>) -> McpError {
     McpError::invalid_params(msg.into(), None)
 }
 
 /// Best-effort PR title via `gh pr view --json title`.
 fn fetch_pr_title(owner: &str, repo: &str, number: u64) -> Option<String> {
-    let output = std::process::Command::new("gh")
+    let output = er_engine::github::gh_command()
         .args([
             "pr",
             "view",
@@ -1133,6 +1257,136 @@ impl ErMcp {
         }))
     }
 
+    #[tool(
+        description = "Live local review: changed files with reviewed state for the branch checked out at repo_path (same data the er TUI shows)."
+    )]
+    async fn session_files(
+        &self,
+        Parameters(args): Parameters<SessionArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let value = with_session(repo_path, args.base, args.pr, |session| {
+            let reviewed: std::collections::HashSet<String> =
+                session.reviewed_files().into_iter().collect();
+            let files: Vec<_> = session
+                .files()?
+                .into_iter()
+                .map(|path| {
+                    let done = reviewed.contains(&path);
+                    json!({ "path": path, "reviewed": done })
+                })
+                .collect();
+            let mut out = session_json(&session);
+            out["count"] = json!(files.len());
+            out["reviewed_count"] = json!(files.iter().filter(|f| f["reviewed"] == true).count());
+            out["files"] = json!(files);
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
+    #[tool(
+        description = "Live local review: AI findings from review.json for the current branch diff (optional file filter). Stale findings are skipped."
+    )]
+    async fn session_findings(
+        &self,
+        Parameters(args): Parameters<SessionArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let file = args.file;
+        let value = with_session(repo_path, args.base, args.pr, move |session| {
+            let findings = session.findings(file.as_deref())?;
+            let mut out = session_json(&session);
+            out["count"] = json!(findings.len());
+            out["findings"] = json!(findings);
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
+    #[tool(
+        description = "Live local review: review comments (local and synced GitHub) for the current branch or PR (optional file filter)."
+    )]
+    async fn session_comments(
+        &self,
+        Parameters(args): Parameters<SessionArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let file = args.file;
+        let value = with_session(repo_path, args.base, args.pr, move |session| {
+            let comments = session.comments(file.as_deref())?;
+            let mut out = session_json(&session);
+            out["count"] = json!(comments.len());
+            out["comments"] = json!(comments);
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
+    #[tool(
+        description = "Live local review: add a local comment on a file (or a new-side line / line range). Appears in a running er within a second; push from er to publish."
+    )]
+    async fn session_add_comment(
+        &self,
+        Parameters(args): Parameters<SessionAddCommentArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let author = args.author.unwrap_or_else(|| "agent".to_string());
+        let value = with_session(repo_path, args.base, args.pr, move |session| {
+            let comment =
+                session.add_comment(&args.file, args.line, args.line_end, &args.body, &author)?;
+            let mut out = session_json(&session);
+            out["comment"] = json!(comment);
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
+    #[tool(
+        description = "Live local review: mark a file reviewed (or reviewed=false to unmark). Stored with the file's diff hash, so later edits reset it like in er."
+    )]
+    async fn session_mark_reviewed(
+        &self,
+        Parameters(args): Parameters<SessionMarkReviewedArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let reviewed = args.reviewed.unwrap_or(true);
+        let value = with_session(repo_path, args.base, None, move |session| {
+            session.set_reviewed(&args.file, reviewed)?;
+            let mut out = session_json(&session);
+            out["file"] = json!(args.file);
+            out["reviewed"] = json!(reviewed);
+            out["reviewed_count"] = json!(session.reviewed_files().len());
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
+    #[tool(
+        description = "Live local review: diff hunks for one file (header, ranges and unified text) against the branch base."
+    )]
+    async fn session_hunks(
+        &self,
+        Parameters(args): Parameters<SessionHunksArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let repo_path = resolve_repo_path(args.repo_path, args.project_id.as_deref())?;
+        let value = with_session(repo_path, args.base, None, move |session| {
+            let hunks = session.hunks(&args.file)?;
+            let mut out = session_json(&session);
+            out["file"] = json!(args.file);
+            out["count"] = json!(hunks.len());
+            out["hunks"] = json!(hunks);
+            Ok(out)
+        })
+        .await?;
+        text_json(&value)
+    }
+
     #[tool(description = "Catalog of Easy Review MCP tools (shipped + future ideas).")]
     async fn tool_ideas(&self) -> Result<CallToolResult, McpError> {
         text_json(&json!({
@@ -1205,6 +1459,12 @@ const SHIPPED_TOOLS: &[&str] = &[
     "unpin_pr",
     "list_pinned_prs",
     "list_artifacts",
+    "session_files",
+    "session_findings",
+    "session_comments",
+    "session_add_comment",
+    "session_mark_reviewed",
+    "session_hunks",
     "tool_ideas",
 ];
 
@@ -1227,6 +1487,8 @@ impl ServerHandler for ErMcp {
              Sizing: pr_diff_stats, diff_hotspots, compare_prod_size. \
              AI sidecars: get_artifact_specs (schemas+prompts) → prepare_review → upload_artifacts → summarize_triage. \
              Find reviewed work: pin_pr (Desktop Saved) / list_pinned_prs / list_artifacts (scan managed storage). \
+             Live local review (branch checked out at repo_path): session_files, session_findings, session_comments, session_hunks; \
+             write back with session_add_comment / session_mark_reviewed — a running er picks changes up live. \
              Open: open_in_easy_review.",
             )
     }
@@ -0,0 +1,71 @@
+//! `er completions <shell>` and `er man`. The completion script calls back
+//! into `er` (`COMPLETE=<shell> er -- …`) so values are computed as you
+//! type: open PR numbers for `--pr`, branches for `--target`, filter
+//! presets for `--filter`.
+
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use clap_complete::env::Shells;
+use clap_complete::CompletionCandidate;
+use er_engine::app::filter::FILTER_PRESETS;
+use er_engine::{git, github};
+
+/// The environment variable the completion script sets when it calls back.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells `er completions` writes a script for.
+pub const SHELLS: [&str; 5] = ["bash", "elvish", "fish", "powershell", "zsh"];
+
+/// How many open PRs `--pr` offers.
+const PR_LIMIT: usize = 30;
+
+/// Open PRs of the repo in the current directory, titles as help.
+pub fn pr_numbers() -> Vec<CompletionCandidate> {
+    let Ok(repo_root) = git::get_repo_root() else {
+        return Vec::new();
+    };
+    github::gh_open_prs(&repo_root, PR_LIMIT)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(number, title)| {
+            CompletionCandidate::new(number.to_string()).help(Some(title.into()))
+        })
+        .collect()
+}
+
+/// Local and remote branches of the repo in the current directory.
+pub fn branches() -> Vec<CompletionCandidate> {
+    let Ok(repo_root) = git::get_repo_root() else {
+        return Vec::new();
+    };
+    git::git_branch_names(&repo_root)
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Filter presets by name, their expressions as help.
+pub fn filters() -> Vec<CompletionCandidate> {
+    FILTER_PRESETS
+        .iter()
+        .map(|p| CompletionCandidate::new(p.name).help(Some(p.expr.into())))
+        .collect()
+}
+
+/// The script that registers completions for `er` in `shell`.
+pub fn write_registration(shell: &str, out: &mut dyn Write) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(shell)
+        .with_context(|| format!("No completions for {}", shell))?;
+    completer.write_registration(COMPLETE_VAR, "er", "er", "er", out)?;
+    Ok(())
+}
+
+/// The man page for `cmd`, as roff.
+pub fn write_man(cmd: clap::Command, out: &mut dyn Write) -> Result<()> {
+    clap_mangen::Man::new(cmd).render(out)?;
+    Ok(())
+}
@@ -1,16 +1,44 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use er_engine::ai::store::{self, Sidecar};
 use er_engine::app;
 use er_engine::app::{
     cleanup_question_answers, cleanup_questions_and_notes, cleanup_reviews, AiActionKind, App,
     ConfirmAction, DiffMode, HubAction, InputMode,
 };
+use er_engine::webhook::WebhookEvent;
 use er_engine::{git, github};
 
 pub mod normal;
 
 pub use normal::handle_normal_input;
 
+/// Route a key: the overlay takes priority, then the input mode's handler.
+pub fn handle_key(
+    app: &mut App,
+    key: KeyEvent,
+    watch_tx: &std::sync::mpsc::Sender<er_engine::watch::WatchEvent>,
+    watcher: &mut Option<er_engine::watch::FileWatcher>,
+) -> Result<()> {
+    if app.overlay.is_some() {
+        return handle_overlay_input(app, key);
+    }
+    match &app.input_mode {
+        InputMode::Search => handle_search_input(app, key),
+        InputMode::Find => handle_find_input(app, key),
+        InputMode::Comment => handle_comment_input(app, key)?,
+        InputMode::Confirm(_) => handle_confirm_input(app, key)?,
+        InputMode::Filter => handle_filter_input(app, key),
+        InputMode::Commit => handle_commit_input(app, key)?,
+        InputMode::RemoteUrl => handle_remote_url_input(app, key)?,
+        InputMode::SkipReason => handle_skip_reason_input(app, key)?,
+        InputMode::Assignee => handle_assignee_input(app, key)?,
+        InputMode::QuickDiffRef => handle_quick_diff_input(app, key),
+        InputMode::Normal => handle_normal_input(app, key, watch_tx, watcher)?,
+    }
+    Ok(())
+}
+
 /// Byte index of the char boundary immediately before `pos` (0 if at start).
 fn prev_char_boundary(s: &str, pos: usize) -> usize {
     s[..pos].char_indices().last().map(|(i, _)| i).unwrap_or(0)
@@ -104,6 +132,113 @@ pub fn handle_overlay_input(app: &mut App, key: KeyEvent) -> Result<()> {
         return Ok(());
     }
 
+    if matches!(app.overlay, Some(app::OverlayData::CommandError { .. }))
+        && key.code == KeyCode::Char('y')
+    {
+        app.copy_command_error();
+        return Ok(());
+    }
+
+    // Notifications pane — extra keys on top of the list navigation below
+    if matches!(app.overlay, Some(app::OverlayData::NotificationLog { .. })) {
+        let handled = match key.code {
+            KeyCode::Char('x') => {
+                app.clear_notification_log();
+                true
+            }
+            KeyCode::Char('y') => {
+                app.copy_selected_notification();
+                true
+            }
+            KeyCode::Char('L') => {
+                app.overlay_close();
+                true
+            }
+            _ => false,
+        };
+        if handled {
+            return Ok(());
+        }
+    }
+
+    // Lost comments hub — delete / file-level on the selected comment
+    if matches!(
+        app.overlay,
+        Some(app::OverlayData::ModalHub {
+            kind: app::HubKind::LostComments,
+            ..
+        })
+    ) {
+        match key.code {
+            KeyCode::Char('d') => {
+                app.request_delete_lost_comment();
+                return Ok(());
+            }
+            KeyCode::Char('f') => return app.lost_comment_to_file_level(),
+            _ => {}
+        }
+    }
+
+    // Compaction hub — each change on its own key
+    if let KeyCode::Char(c) = key.code {
+        if let Some(policy) = app.compaction_hub_key(c) {
+            app.overlay = None;
+            return app.set_compaction_policy(policy);
+        }
+    }
+
+    // Huge Diff hub — Space leaves the selected directory out
+    if key.code == KeyCode::Char(' ')
+        && matches!(
+            app.overlay,
+            Some(app::OverlayData::ModalHub {
+                kind: app::HubKind::SizeGuard,
+                ..
+            })
+        )
+    {
+        app.toggle_selected_guard_dir();
+        return Ok(());
+    }
+
+    // Marks hub — delete the selected mark
+    if key.code == KeyCode::Char('d')
+        && matches!(
+            app.overlay,
+            Some(app::OverlayData::ModalHub {
+                kind: app::HubKind::Marks,
+                ..
+            })
+        )
+    {
+        app.delete_selected_mark();
+        return Ok(());
+    }
+
+    // Folder browser — type a PR URL or a patch path instead
+    if key.code == KeyCode::Char('o')
+        && matches!(app.overlay, Some(app::OverlayData::DirectoryBrowser { .. }))
+    {
+        app.overlay = None;
+        app.remote_url_input.clear();
+        app.input_mode = InputMode::RemoteUrl;
+        return Ok(());
+    }
+
+    // Review dashboard — clear the selected follow-up
+    if key.code == KeyCode::Char('d')
+        && matches!(
+            app.overlay,
+            Some(app::OverlayData::ModalHub {
+                kind: app::HubKind::Reviews,
+                ..
+            })
+        )
+    {
+        app.clear_selected_follow_up();
+        return Ok(());
+    }
+
     match key.code {
         KeyCode::Char('j') | KeyCode::Down => app.overlay_next(),
         KeyCode::Char('k') | KeyCode::Up => app.overlay_prev(),
@@ -122,27 +257,75 @@ pub fn handle_overlay_input(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Ask before pushing, naming the branch.
+pub(super) fn confirm_push(app: &mut App) {
+    let branch = app.tab().current_branch.clone();
+    let mut prompt = ConfirmAction::Push.prompt();
+    if !branch.is_empty() {
+        prompt = prompt.detail(format!("{} → upstream", branch));
+    }
+    if let Some(sync) = app.tab().pr_head_sync().filter(|s| s.unpushed() > 0) {
+        prompt = prompt.detail(format!(
+            "{} commit{} not on the PR yet",
+            sync.unpushed(),
+            if sync.unpushed() == 1 { "" } else { "s" }
+        ));
+    }
+    app.ask_confirm(prompt);
+}
+
 pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()> {
     match action {
         HubAction::Noop => {}
         HubAction::PushToRemote => {
-            if app.tab().mode == DiffMode::Staged {
-                app.input_mode = InputMode::Confirm(ConfirmAction::Push);
+            if app.tab().mode == DiffMode::Staged || app.tab().has_unpushed_pr_commits() {
+                confirm_push(app);
             }
         }
         HubAction::PullGitHubComments => {
             sync_github_comments(app)?;
         }
         HubAction::PushCommentsToGitHub => {
-            app.input_mode = InputMode::Confirm(ConfirmAction::PushComments);
+            let local = app
+                .tab()
+                .ai
+                .github_comments
+                .as_ref()
+                .map_or(0, |gc| gc.comments.iter().filter(|c| !c.synced).count());
+            let mut prompt = ConfirmAction::PushComments
+                .prompt()
+                .detail(format!("{} local comment(s) to GitHub", local));
+            let notes = app.tab().ai.review_body().1.len();
+            if notes > 0 {
+                prompt = prompt.detail(format!(
+                    "+ {} review note(s) in the review body (review only)",
+                    notes
+                ));
+            }
+            if let Some(typos) = app.pending_comment_typos() {
+                prompt = prompt.detail(typos);
+            }
+            app.ask_confirm(prompt);
+        }
+        HubAction::PublishSharedReview => {
+            app.publish_shared_review();
+        }
+        HubAction::PullSharedReview => {
+            app.pull_shared_review();
         }
         HubAction::CommentOnPR => {
             app.start_general_comment();
         }
-        HubAction::RefreshDiff => {
-            app.tab_mut().refresh_diff()?;
-            app.notify("Refreshed");
+        HubAction::CommentOnFile => {
+            app.start_file_comment(er_engine::ai::CommentType::Note);
+        }
+        HubAction::ReviewNote => {
+            app.start_review_comment(er_engine::ai::CommentType::Note);
         }
+        HubAction::RefreshDiff => match app.tab_mut().refresh_diff() {
+            Ok(()) => app.notify("Refreshed"),
+            Err(e) => app.report_command_error("Refresh failed", &e),
+        },
         HubAction::StageFile => {
             app.toggle_stage_file()?;
         }
@@ -152,6 +335,31 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
         HubAction::CopyContext => {
             app.copy_context()?;
         }
+        HubAction::ExplainHunk => {
+            app.explain_current_hunk();
+        }
+        HubAction::ExportHandoff => {
+            app.export_handoff()?;
+        }
+        HubAction::ShowOnboarding => {
+            app.open_onboarding();
+        }
+        HubAction::SetReviewCheckpoint => {
+            app.set_review_checkpoint();
+        }
+        HubAction::ToggleCheckpointView => {
+            app.toggle_checkpoint_view();
+        }
+        HubAction::TogglePrHeadView => {
+            app.toggle_pr_head_view();
+        }
+        HubAction::StartQuickDiff => {
+            if app.tab().in_quick_diff() {
+                app.close_quick_diff();
+            } else {
+                app.start_quick_diff();
+            }
+        }
         HubAction::CopyReviewJson => {
             app.copy_review_json()?;
         }
@@ -196,11 +404,11 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
         }
         HubAction::CleanupQuestions => {
             let count = app.tab().ai.local_draft_count();
-            app.input_mode = InputMode::Confirm(ConfirmAction::CleanupQuestions { count });
+            app.confirm(ConfirmAction::CleanupQuestions { count });
         }
         HubAction::CleanupReviews => {
             let count = app.tab().ai.review.as_ref().map_or(0, |r| r.files.len());
-            app.input_mode = InputMode::Confirm(ConfirmAction::CleanupReviews { count });
+            app.confirm(ConfirmAction::CleanupReviews { count });
         }
         HubAction::RunCommand(name) => {
             if let Some(cmd) = app.config.resolve_command(&name) {
@@ -209,6 +417,26 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
                 app.notify(&format!("{}: not configured", name));
             }
         }
+        HubAction::RunCustomCommand(name) => {
+            app.run_custom_command(&name)?;
+        }
+        HubAction::RunScriptAction(name) => {
+            app.run_script_action(&name)?;
+        }
+        HubAction::OpenLostComments => app.open_lost_comments_hub(),
+        HubAction::ReanchorComment(id) => app.start_reanchor(&id),
+        HubAction::JumpToMark(name) => app.jump_to_mark(name),
+        HubAction::SetCompactionPolicy(policy) => app.set_compaction_policy(policy)?,
+        HubAction::ToggleGuardDir(dir) => app.toggle_guard_dir(&dir),
+        HubAction::LoadGuardedDiff => app.load_guarded_diff(true)?,
+        HubAction::OpenReleaseRange { from, to } => app.open_release_range(&from, &to),
+        HubAction::ToggleCodeAge => app.toggle_code_age(),
+        HubAction::OpenBranchPicker => app.open_branch_picker(),
+        HubAction::SwitchBranch { name, remote } => app.request_switch_branch(&name, remote),
+        HubAction::BugReport { snapshot, gh } => app.start_bug_report(snapshot, gh),
+        HubAction::OpenScriptPanel(name) => {
+            app.open_script_panel(&name);
+        }
         HubAction::ConfigureAiSelection => {
             app.open_ai_provider_picker(None);
         }
@@ -275,7 +503,7 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
             let has_review = app.tab().ai.review.is_some();
             if has_review {
                 // Ask to clear previous review first
-                app.input_mode = InputMode::Confirm(ConfirmAction::RunAgentReview {
+                app.confirm(ConfirmAction::RunAgentReview {
                     clear_previous: true,
                 });
             } else {
@@ -328,7 +556,7 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
             }
         }
         HubAction::ApprovePR => {
-            app.input_mode = InputMode::Confirm(ConfirmAction::ApprovePR);
+            app.request_approve_pr();
         }
         HubAction::PromptQuestions => {
             let has_answers = app.tab().ai.questions.as_ref().is_some_and(|q| {
@@ -338,7 +566,7 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
             });
             if has_answers {
                 // Ask to clear previous answers first
-                app.input_mode = InputMode::Confirm(ConfirmAction::RunAgentQuestions {
+                app.confirm(ConfirmAction::RunAgentQuestions {
                     clear_previous: true,
                 });
             } else {
@@ -358,6 +586,15 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
             app.remote_url_input.clear();
             app.input_mode = InputMode::RemoteUrl;
         }
+        HubAction::OpenInSplit => {
+            app.open_in_split()?;
+        }
+        HubAction::OpenLineOnForge => {
+            app.open_line_on_forge();
+        }
+        HubAction::OpenCommitOnForge => {
+            app.open_commit_on_forge();
+        }
         HubAction::OpenPrInBrowser => {
             let repo_root = app.tab().repo_root.clone();
             if let Some(pr_number) = app.tab().pr_number {
@@ -371,7 +608,7 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
                     args.push("-R".to_string());
                     args.push(slug.clone());
                 }
-                if let Ok(mut child) = std::process::Command::new("gh")
+                if let Ok(mut child) = github::gh_command()
                     .args(&args)
                     .current_dir(&repo_root)
                     .stdout(std::process::Stdio::null())
@@ -397,6 +634,127 @@ pub(super) fn dispatch_hub_action(app: &mut App, action: HubAction) -> Result<()
         HubAction::CopyLine => {
             app.copy_line()?;
         }
+        HubAction::CopyHunkPatch { reverse } => {
+            app.copy_hunk_patch(reverse)?;
+        }
+        HubAction::CopyViewDiff => {
+            app.copy_view_diff()?;
+        }
+        HubAction::SaveViewPatch => {
+            app.save_view_patch()?;
+        }
+        HubAction::CopyPermalink => {
+            app.copy_permalink()?;
+        }
+        HubAction::CopyDiffCommand => {
+            app.copy_diff_command()?;
+        }
+        HubAction::ResumeDraft(idx) => {
+            app.resume_saved_draft(idx);
+        }
+        HubAction::DiscardDrafts => {
+            let count = app.tab().comment_drafts.len();
+            app.confirm(ConfirmAction::DiscardDrafts { count });
+        }
+        HubAction::ApplyChecklistTemplate(name) => {
+            app.apply_checklist_template(&name)?;
+        }
+        HubAction::SaveChecklistTemplate => {
+            app.save_checklist_template()?;
+        }
+        HubAction::OpenCommentConflicts => {
+            app.open_comment_conflicts_hub();
+        }
+        HubAction::ResolveCommentConflict { id, keep_mine } => {
+            app.resolve_comment_conflict(&id, keep_mine)?;
+        }
+        HubAction::OpenReviewsDashboard => {
+            app.open_reviews_dashboard();
+        }
+        HubAction::Snooze(secs) => {
+            app.snooze(secs);
+        }
+        HubAction::ClearFollowUp(id) => {
+            app.clear_follow_up(&id);
+        }
+        HubAction::OpenFollowUp(id) => {
+            app.open_follow_up(&id);
+        }
+        HubAction::ReviewSelection => {
+            app.review_selection()?;
+        }
+        HubAction::StageSelection => {
+            app.stage_selection()?;
+        }
+        HubAction::FilterSelection { include } => {
+            app.filter_selection(include);
+        }
+        HubAction::CompactSelection => {
+            app.compact_selection()?;
+        }
+        HubAction::AssignSelection => {
+            app.start_assign_selection();
+        }
+        HubAction::ClearSelection => {
+            app.clear_file_selection();
+        }
+        HubAction::OpenBranchAudit => {
+            app.open_branch_audit();
+        }
+        HubAction::OpenBranchAuthors => {
+            app.open_branch_authors();
+        }
+        HubAction::ApplyHunkToWorktree => {
+            app.start_apply_hunk()?;
+        }
+        HubAction::FilterByAuthor(name) => {
+            app.filter_by_author(&name);
+        }
+        HubAction::OpenPackages => {
+            app.open_packages();
+        }
+        HubAction::FilterByPackage(name) => {
+            app.filter_by_package(&name);
+        }
+        HubAction::ExportSession => {
+            app.export_session();
+        }
+        HubAction::OpenImportSession => {
+            app.open_import_session();
+        }
+        HubAction::ImportSession(path) => {
+            app.import_session(&path);
+        }
+        HubAction::OpenTodos => {
+            app.open_todos();
+        }
+        HubAction::OpenOperational => {
+            app.open_operational();
+        }
+        HubAction::OpenAssets => {
+            app.open_assets();
+        }
+        HubAction::OpenSpelling => {
+            app.open_spelling();
+        }
+        HubAction::FocusFileLine { path, line } => {
+            app.focus_file_line(&path, line);
+        }
+        HubAction::OpenCommitPlan => {
+            app.open_commit_plan();
+        }
+        HubAction::AssignToCommit { bucket, path, hunk } => {
+            app.assign_to_commit(bucket, path, hunk);
+        }
+        HubAction::RunCommitPlan => {
+            app.run_commit_plan()?;
+        }
+        HubAction::ClearCommitPlan => {
+            app.clear_commit_plan();
+        }
+        HubAction::SwitchTab(idx) => {
+            app.switch_to_tab(idx);
+        }
         HubAction::SelectVerifyPackage { package_id } => {
             app.open_package_commands_hub(package_id);
         }
@@ -462,7 +820,9 @@ pub fn handle_search_input(app: &mut App, key: KeyEvent) {
                 tab.search_query_lower.clear();
             } else {
                 // Search confirmed — snap selection to a visible file
-                app.tab_mut().snap_to_visible();
+                let tab = app.tab_mut();
+                tab.record_jump();
+                tab.snap_to_visible();
             }
         }
         KeyCode::Char(c) => {
@@ -479,6 +839,31 @@ pub fn handle_search_input(app: &mut App, key: KeyEvent) {
     }
 }
 
+pub fn handle_find_input(app: &mut App, key: KeyEvent) {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Enter => app.confirm_find(),
+        KeyCode::Esc => app.cancel_find(),
+        KeyCode::Char(c) => {
+            if let Some(find) = app.tab_mut().find.as_mut() {
+                match c {
+                    'r' if alt => find.toggle_regex(),
+                    'c' if alt => find.toggle_case(),
+                    _ => find.push(c),
+                }
+            }
+            app.find_query_changed();
+        }
+        KeyCode::Backspace => {
+            if let Some(find) = app.tab_mut().find.as_mut() {
+                find.pop();
+            }
+            app.find_query_changed();
+        }
+        _ => {}
+    }
+}
+
 pub fn handle_filter_input(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Enter => {
@@ -515,7 +900,7 @@ pub fn handle_remote_url_input(app: &mut App, key: KeyEvent) -> Result<()> {
             if url.trim().is_empty() {
                 return Ok(());
             }
-            if let Err(e) = app.open_remote_url(url.trim()) {
+            if let Err(e) = app.open_location(&url) {
                 app.notify(&format!("Failed: {}", e));
             }
             app.remote_url_input.clear();
@@ -535,6 +920,44 @@ pub fn handle_remote_url_input(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+pub fn handle_skip_reason_input(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => app.submit_skip_reason()?,
+        KeyCode::Esc => app.cancel_skip_reason(),
+        KeyCode::Char(c) => app.skip_reason_input.push(c),
+        KeyCode::Backspace => {
+            app.skip_reason_input.pop();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn handle_assignee_input(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => app.submit_assignee()?,
+        KeyCode::Esc => app.cancel_assignee(),
+        KeyCode::Char(c) => app.assignee_input.push(c),
+        KeyCode::Backspace => {
+            app.assignee_input.pop();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn handle_quick_diff_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Enter => app.submit_quick_diff(),
+        KeyCode::Esc => app.cancel_quick_diff(),
+        KeyCode::Char(c) => app.quick_diff_input.push(c),
+        KeyCode::Backspace => {
+            app.quick_diff_input.pop();
+        }
+        _ => {}
+    }
+}
+
 pub fn handle_comment_input(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
         KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
@@ -574,23 +997,23 @@ pub fn handle_comment_input(app: &mut App, key: KeyEvent) -> Result<()> {
 
 pub fn handle_confirm_input(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
+        KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let InputMode::Confirm(ConfirmAction::Quit { .. }) = app.input_mode {
+                app.request_quit();
+            }
+        }
         KeyCode::Char('y') => {
             let action = app.input_mode.clone();
             if let InputMode::Confirm(ConfirmAction::DeleteComment { comment_id }) = action {
                 app.confirm_delete_comment(&comment_id)?;
+            } else if let InputMode::Confirm(ConfirmAction::Quit { .. }) = action {
+                app.should_quit = true;
             } else if let InputMode::Confirm(ConfirmAction::Push) = action {
                 app.input_mode = InputMode::Normal;
-                let repo_root = app.tab().repo_root.clone();
-                match git::git_push(&repo_root) {
-                    Ok(_) => {
-                        app.tab_mut().committed_unpushed = false;
-                        let _ = app.tab_mut().refresh_diff();
-                        app.notify("Pushed!");
-                    }
-                    Err(e) => {
-                        app.notify(&format!("Push failed: {}", e));
-                    }
-                }
+                app.start_push()?;
+            } else if let InputMode::Confirm(ConfirmAction::PushDespiteChecks { .. }) = action {
+                app.input_mode = InputMode::Normal;
+                app.push_now(true);
             } else if let InputMode::Confirm(ConfirmAction::CleanupQuestions { .. }) = action {
                 app.input_mode = InputMode::Normal;
                 let er_dir = app.tab().er_dir();
@@ -619,6 +1042,13 @@ pub fn handle_confirm_input(app: &mut App, key: KeyEvent) -> Result<()> {
                         app.notify(&format!("Delete failed: {}", e));
                     }
                 }
+            } else if let InputMode::Confirm(ConfirmAction::DiscardDrafts { .. }) = action {
+                app.input_mode = InputMode::Normal;
+                app.discard_drafts();
+            } else if let InputMode::Confirm(ConfirmAction::DeleteChecklistItem { index }) = action
+            {
+                app.input_mode = InputMode::Normal;
+                app.delete_checklist_item(index)?;
             } else if let InputMode::Confirm(ConfirmAction::CleanupReviews { .. }) = action {
                 app.input_mode = InputMode::Normal;
                 let er_dir = app.tab().er_dir();
@@ -645,13 +1075,44 @@ pub fn handle_confirm_input(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.spawn_agent_prompt("questions", &prompt)?;
                 }
                 app.clear_ai_selection_override();
+            } else if let InputMode::Confirm(ConfirmAction::MoveComment { ref comment_id }) = action
+            {
+                app.accept_comment_move(comment_id)?;
+            } else if let InputMode::Confirm(ConfirmAction::DiscardLines {
+                ref path,
+                ref patch,
+                ..
+            }) = action
+            {
+                app.discard_lines(path, patch)?;
+            } else if let InputMode::Confirm(ConfirmAction::CleanReviewData { ref targets }) =
+                action
+            {
+                app.input_mode = InputMode::Normal;
+                app.clean_review_data(targets);
+            } else if let InputMode::Confirm(ConfirmAction::SwitchBranch {
+                ref name, remote, ..
+            }) = action
+            {
+                app.input_mode = InputMode::Normal;
+                app.switch_branch(name, remote, true);
+            } else if let InputMode::Confirm(ConfirmAction::HideNoise { ref expr, .. }) = action {
+                app.input_mode = InputMode::Normal;
+                app.tab_mut().apply_filter_expr(expr);
+                app.notify(&format!("Filter: {} — f to edit", expr));
             } else if let InputMode::Confirm(ConfirmAction::ApprovePR) = action {
                 app.input_mode = InputMode::Normal;
+                if app.blocked_by_read_only("approving") {
+                    return Ok(());
+                }
                 let repo_root = app.tab().repo_root.clone();
                 let remote = app.tab().remote_repo.clone();
                 let pr = app.tab().pr_number;
                 match er_engine::github::gh_pr_approve(&repo_root, remote.as_deref(), pr) {
-                    Ok(()) => app.notify("PR approved"),
+                    Ok(()) => {
+                        app.notify("PR approved");
+                        app.send_webhook(WebhookEvent::Approved, "PR approved");
+                    }
                     Err(e) => app.notify(&format!("Approve failed: {}", e)),
                 }
             }
@@ -659,13 +1120,13 @@ pub fn handle_confirm_input(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('r') => {
             if let InputMode::Confirm(ConfirmAction::PushComments) = &app.input_mode {
                 app.input_mode = InputMode::Normal;
-                push_comments_as_review(app)?;
+                app.push_comments_as_review()?;
             }
         }
         KeyCode::Char('i') => {
             if let InputMode::Confirm(ConfirmAction::PushComments) = &app.input_mode {
                 app.input_mode = InputMode::Normal;
-                push_all_comments_to_github(app)?;
+                app.push_all_comments_to_github()?;
             }
         }
         KeyCode::Char('n') => {
@@ -775,14 +1236,14 @@ pub(super) fn build_agent_expert_prompt(app: &mut App, expert_id: &str) -> Optio
     let base = tab.base_branch.clone();
     let output_dir = tab.er_dir();
     match mode {
-        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => Some(
+        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => Some(app.with_review_hints(
             er_engine::ai::prompts::build_expert_review_prompt_local_managed(
                 &base,
                 mode.git_mode(),
                 &output_dir,
                 expert_id,
             ),
-        ),
+        )),
         _ => {
             app.notify("Expert review not available in this mode");
             None
@@ -807,28 +1268,28 @@ pub(super) fn build_agent_review_prompt(app: &mut App) -> Option<String> {
             return None;
         }
         let output_dir = app.tab().er_dir();
-        return Some(er_engine::ai::prompts::build_review_prompt_remote(
-            parts[0],
-            parts[1],
-            pr_number,
-            &output_dir,
-        ));
+        return Some(
+            app.with_review_hints(er_engine::ai::prompts::build_review_prompt_remote(
+                parts[0],
+                parts[1],
+                pr_number,
+                &output_dir,
+            )),
+        );
     }
     let mode = tab.mode;
     let base = tab.base_branch.clone();
     let output_dir = tab.er_dir();
     match mode {
-        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => {
-            Some(er_engine::ai::prompts::build_review_prompt_local_managed(
+        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => Some(app.with_review_hints(
+            er_engine::ai::prompts::build_review_prompt_local_managed(
                 &base,
                 mode.git_mode(),
                 &output_dir,
-            ))
-        }
-        DiffMode::PrDiff => Some(er_engine::ai::prompts::build_review_prompt_local_managed(
-            &base,
-            "branch",
-            &output_dir,
+            ),
+        )),
+        DiffMode::PrDiff => Some(app.with_review_hints(
+            er_engine::ai::prompts::build_review_prompt_local_managed(&base, "branch", &output_dir),
         )),
         _ => {
             app.notify("AI review not available in this mode");
@@ -885,24 +1346,26 @@ pub(super) fn build_agent_questions_prompt(app: &mut App) -> Option<String> {
             return None;
         }
         let output_dir = app.tab().er_dir();
-        return Some(er_engine::ai::prompts::build_questions_prompt_remote(
-            parts[0],
-            parts[1],
-            pr_number,
-            &output_dir,
-        ));
+        return Some(
+            app.with_review_hints(er_engine::ai::prompts::build_questions_prompt_remote(
+                parts[0],
+                parts[1],
+                pr_number,
+                &output_dir,
+            )),
+        );
     }
     let mode = tab.mode;
     let base = tab.base_branch.clone();
     let output_dir = tab.er_dir();
     match mode {
-        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => Some(
+        DiffMode::Branch | DiffMode::Unstaged | DiffMode::Staged => Some(app.with_review_hints(
             er_engine::ai::prompts::build_questions_prompt_local_managed(
                 &base,
                 mode.git_mode(),
                 &output_dir,
             ),
-        ),
+        )),
         _ => {
             app.notify("AI questions not available in this mode");
             None
@@ -1111,7 +1574,7 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
         match github::gh_pr_comments_remote(&owner, &repo_name, pr_number) {
             Ok(c) => c,
             Err(e) => {
-                app.notify(&format!("GitHub sync error: {}", e));
+                app.report_command_error("GitHub sync failed", &e);
                 return Ok(());
             }
         }
@@ -1119,7 +1582,7 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
         match github::gh_pr_comments(&owner, &repo_name, pr_number, &repo_root) {
             Ok(c) => c,
             Err(e) => {
-                app.notify(&format!("GitHub sync error: {}", e));
+                app.report_command_error("GitHub sync failed", &e);
                 return Ok(());
             }
         }
@@ -1134,25 +1597,10 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
 
     // Load existing github-comments.json (PR-scoped: shared PR bucket for PR tabs)
     let comments_dir = app.tab().github_comments_dir();
-    let _ = std::fs::create_dir_all(&comments_dir);
-    let comments_path = app.tab().github_comments_path();
+    let _lock = store::lock::<er_engine::ai::ErGitHubComments>(&comments_dir)?;
     let diff_hash = tab.branch_diff_hash.clone();
-    let mut gc: er_engine::ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-        Ok(content) => {
-            serde_json::from_str(&content).unwrap_or_else(|_| er_engine::ai::ErGitHubComments {
-                version: 1,
-                diff_hash: diff_hash.clone(),
-                github: None,
-                comments: Vec::new(),
-            })
-        }
-        Err(_) => er_engine::ai::ErGitHubComments {
-            version: 1,
-            diff_hash: diff_hash.clone(),
-            github: None,
-            comments: Vec::new(),
-        },
-    };
+    let mut gc = store::load(&comments_dir)?
+        .unwrap_or_else(|| er_engine::ai::ErGitHubComments::empty(&diff_hash));
 
     gc.github = Some(er_engine::ai::GitHubSyncState {
         pr_number: Some(pr_number),
@@ -1161,12 +1609,14 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
         last_synced: chrono_now(),
     });
 
-    // Keep only truly local unpushed comments
-    let local_unpushed: Vec<_> = gc
-        .comments
-        .into_iter()
+    // Unpushed local comments and edits GitHub hasn't seen survive the merge
+
+    let local = std::mem::take(&mut gc.comments);
+
+    let local_count = local
+        .iter()
         .filter(|c| c.source == "local" && !c.synced)
-        .collect();
+        .count();
 
     // Build fresh GitHub entries from API response
     let tab_files = &app.tab().files;
@@ -1312,23 +1762,19 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
             hunk_header: anchor_hunk_header,
             anchor_status: "original".to_string(),
             relocated_at_hash: diff_hash_for_anchor.clone(),
+            anchor_confidence: None,
             finding_ref: None,
             side: gh.side.clone().unwrap_or_else(|| "RIGHT".to_string()),
+            edit_base: None,
+            remote_body: None,
         });
     }
 
     let github_count = github_entries.len();
-    let local_count = local_unpushed.len();
-    gc.comments = local_unpushed;
-    gc.comments.extend(github_entries);
+    let (comments, conflicts) = er_engine::sync::merge_github_comments(local, github_entries);
+    gc.comments = comments;
 
-    if let Some(dir) = std::path::Path::new(&comments_path).parent() {
-        std::fs::create_dir_all(dir)?;
-    }
-    let json = serde_json::to_string_pretty(&gc)?;
-    let tmp_path = format!("{}.tmp", comments_path);
-    std::fs::write(&tmp_path, &json)?;
-    std::fs::rename(&tmp_path, &comments_path)?;
+    store::write(&comments_dir, &gc)?;
 
     if is_remote {
         app.tab_mut().reload_remote_comments();
@@ -1350,434 +1796,18 @@ pub(super) fn sync_github_comments(app: &mut App) -> Result<()> {
         app.tab_mut().pr_data = Some(pr_data);
     }
 
+    let viewed = app
+        .sync_viewed_files(&owner, &repo_name, pr_number)
+        .map(|s| format!(", {}", s))
+        .unwrap_or_default();
     app.notify(&format!(
-        "GitHub sync: {} from GitHub, {} local kept, PR status refreshed",
-        github_count, local_count
+        "GitHub sync: {} from GitHub, {} local kept{}{}, PR status refreshed",
+        github_count,
+        local_count,
+        er_engine::sync::conflict_note(conflicts),
+        viewed
     ));
-    Ok(())
-}
-
-/// Push all unpushed local comments to GitHub
-fn push_all_comments_to_github(app: &mut App) -> Result<()> {
-    let tab = app.tab();
-    let repo_root = tab.repo_root.clone();
-    let explicit_pr_number = tab.pr_number;
-    let is_remote = tab.is_remote();
-    let remote_repo = tab.remote_repo.clone();
-
-    let (owner, repo_name, pr_number) = if is_remote {
-        if let (Some(ref slug), Some(n)) = (&remote_repo, explicit_pr_number) {
-            let parts: Vec<&str> = slug.split('/').collect();
-            if parts.len() == 2 {
-                (parts[0].to_string(), parts[1].to_string(), n)
-            } else {
-                app.notify("Invalid remote repo slug");
-                return Ok(());
-            }
-        } else {
-            app.notify("No PR info for remote mode");
-            return Ok(());
-        }
-    } else {
-        let pr_info = match github::get_pr_info(&repo_root) {
-            Ok(info) => info,
-            Err(_) => {
-                app.notify("No PR found for current branch");
-                return Ok(());
-            }
-        };
-        // If we're in no-checkout PR review mode, override the PR number
-        if let Some(n) = explicit_pr_number {
-            (pr_info.0, pr_info.1, n)
-        } else {
-            pr_info
-        }
-    };
-
-    let comments_path = app.tab().github_comments_path();
-    let mut gc: er_engine::ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-        Ok(content) => match serde_json::from_str(&content) {
-            Ok(gc) => gc,
-            Err(_) => return Ok(()),
-        },
-        Err(_) => return Ok(()),
-    };
-
-    let mut pushed = 0u32;
-    let mut failed = 0u32;
-
-    // Push parents first
-    let comment_ids: Vec<String> = gc
-        .comments
-        .iter()
-        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_none())
-        .map(|c| c.id.clone())
-        .collect();
-
-    for cid in &comment_ids {
-        let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-        if let Some(comment) = comment {
-            // General comments (empty file) route to the issues API
-            if comment.file.is_empty() {
-                match if is_remote {
-                    github::gh_pr_general_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.comment,
-                    )
-                } else {
-                    github::gh_pr_general_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        &comment.comment,
-                        &repo_root,
-                    )
-                } {
-                    Ok(github_id) => {
-                        if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                            c.github_id = Some(github_id);
-                            c.synced = true;
-                        }
-                        pushed += 1;
-                    }
-                    Err(_) => {
-                        failed += 1;
-                    }
-                }
-                continue;
-            }
-
-            let path = &comment.file;
-            // Hunk-level comments have no line_start; the line-level push API requires a
-            // line, so they get anchored to line 1 on GitHub.
-            let line = comment.line_start.unwrap_or(1);
-            // Push failures are only counted — individual error messages are not surfaced.
-            let start = comment.line_start.unwrap_or(line);
-            let end = comment.line_end.unwrap_or(start);
-            let side = comment.side.as_str();
-            match if is_remote {
-                github::gh_pr_push_comment_remote(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    path,
-                    start,
-                    Some(end),
-                    &comment.comment,
-                    side,
-                )
-            } else {
-                github::gh_pr_push_comment(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    path,
-                    start,
-                    Some(end),
-                    &comment.comment,
-                    side,
-                    &repo_root,
-                )
-            } {
-                Ok(github_id) => {
-                    if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                        c.github_id = Some(github_id);
-                        c.synced = true;
-                    }
-                    pushed += 1;
-                }
-                Err(_) => {
-                    failed += 1;
-                }
-            }
-        }
-    }
-
-    // Then push replies
-    let reply_ids: Vec<String> = gc
-        .comments
-        .iter()
-        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_some())
-        .map(|c| c.id.clone())
-        .collect();
-
-    for cid in &reply_ids {
-        let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-        if let Some(comment) = comment {
-            let parent_gh_id = comment
-                .in_reply_to
-                .as_ref()
-                .and_then(|rt| gc.comments.iter().find(|c| c.id == *rt))
-                .and_then(|c| c.github_id);
-
-            if let Some(parent_gh_id) = parent_gh_id {
-                match if is_remote {
-                    github::gh_pr_reply_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        parent_gh_id,
-                        &comment.comment,
-                    )
-                } else {
-                    github::gh_pr_reply_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        parent_gh_id,
-                        &comment.comment,
-                        &repo_root,
-                    )
-                } {
-                    Ok(github_id) => {
-                        if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                            c.github_id = Some(github_id);
-                            c.synced = true;
-                        }
-                        pushed += 1;
-                    }
-                    Err(_) => {
-                        failed += 1;
-                    }
-                }
-            } else {
-                failed += 1;
-            }
-        }
-    }
-
-    let json = serde_json::to_string_pretty(&gc)?;
-    let tmp_path = format!("{}.tmp", comments_path);
-    std::fs::write(&tmp_path, &json)?;
-    std::fs::rename(&tmp_path, &comments_path)?;
-    if is_remote {
-        app.tab_mut().reload_remote_comments();
-    } else {
-        app.tab_mut().reload_ai_state();
-    }
-
-    if failed > 0 {
-        app.notify(&format!("Pushed {} comments ({} failed)", pushed, failed));
-    } else {
-        app.notify(&format!("Pushed {} comments", pushed));
-    }
-    Ok(())
-}
-
-fn push_comments_as_review(app: &mut App) -> Result<()> {
-    let tab = app.tab();
-    let repo_root = tab.repo_root.clone();
-    let explicit_pr_number = tab.pr_number;
-    let is_remote = tab.is_remote();
-    let remote_repo = tab.remote_repo.clone();
-
-    let (owner, repo_name, pr_number) = if is_remote {
-        if let (Some(ref slug), Some(n)) = (&remote_repo, explicit_pr_number) {
-            let parts: Vec<&str> = slug.split('/').collect();
-            if parts.len() == 2 {
-                (parts[0].to_string(), parts[1].to_string(), n)
-            } else {
-                app.notify("Invalid remote repo slug");
-                return Ok(());
-            }
-        } else {
-            app.notify("No PR info for remote mode");
-            return Ok(());
-        }
-    } else {
-        let pr_info = match github::get_pr_info(&repo_root) {
-            Ok(info) => info,
-            Err(_) => {
-                app.notify("No PR found for current branch");
-                return Ok(());
-            }
-        };
-        if let Some(n) = explicit_pr_number {
-            (pr_info.0, pr_info.1, n)
-        } else {
-            pr_info
-        }
-    };
-
-    let comments_path = app.tab().github_comments_path();
-    let mut gc: er_engine::ai::ErGitHubComments = match std::fs::read_to_string(&comments_path) {
-        Ok(content) => match serde_json::from_str(&content) {
-            Ok(gc) => gc,
-            Err(_) => return Ok(()),
-        },
-        Err(_) => return Ok(()),
-    };
-
-    let mut pushed = 0u32;
-    let mut failed = 0u32;
-
-    // Collect unsynced parent line comments (non-empty file) for the review batch
-    let line_comment_ids: Vec<String> = gc
-        .comments
-        .iter()
-        .filter(|c| {
-            c.source == "local" && !c.synced && c.in_reply_to.is_none() && !c.file.is_empty()
-        })
-        .map(|c| c.id.clone())
-        .collect();
-
-    // Collect unsynced general comments (empty file) for individual posting
-    let general_comment_ids: Vec<String> = gc
-        .comments
-        .iter()
-        .filter(|c| {
-            c.source == "local" && !c.synced && c.in_reply_to.is_none() && c.file.is_empty()
-        })
-        .map(|c| c.id.clone())
-        .collect();
-
-    // Submit line comments as a single review batch
-    if !line_comment_ids.is_empty() {
-        let batch: Vec<github::ReviewBatchEntry> = line_comment_ids
-            .iter()
-            .filter_map(|cid| gc.comments.iter().find(|c| c.id == *cid))
-            .map(|c| {
-                let start = c.line_start.unwrap_or(1);
-                let end = c.line_end.unwrap_or(start);
-                github::ReviewBatchEntry {
-                    file: c.file.clone(),
-                    line: end,
-                    start_line: if end > start { Some(start) } else { None },
-                    body: c.comment.clone(),
-                    side: c.side.clone(),
-                }
-            })
-            .collect();
-
-        let result = if is_remote {
-            github::gh_pr_submit_review_remote(&owner, &repo_name, pr_number, &batch, "COMMENT", "")
-        } else {
-            github::gh_pr_submit_review(
-                &owner, &repo_name, pr_number, &batch, &repo_root, "COMMENT", "",
-            )
-        };
-
-        match result {
-            Ok(()) => {
-                for cid in &line_comment_ids {
-                    if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                        c.synced = true;
-                        // Review API doesn't return individual comment IDs
-                    }
-                }
-                pushed += line_comment_ids.len() as u32;
-            }
-            Err(e) => {
-                app.notify(&format!("Review submit failed: {}", e));
-                failed += line_comment_ids.len() as u32;
-            }
-        }
-    }
-
-    // Post general comments individually (review API doesn't support them)
-    for cid in &general_comment_ids {
-        let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-        if let Some(comment) = comment {
-            match if is_remote {
-                github::gh_pr_general_comment_remote(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    &comment.comment,
-                )
-            } else {
-                github::gh_pr_general_comment(
-                    &owner,
-                    &repo_name,
-                    pr_number,
-                    &comment.comment,
-                    &repo_root,
-                )
-            } {
-                Ok(github_id) => {
-                    if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                        c.github_id = Some(github_id);
-                        c.synced = true;
-                    }
-                    pushed += 1;
-                }
-                Err(_) => {
-                    failed += 1;
-                }
-            }
-        }
-    }
-
-    // Push replies individually (review API doesn't support threading)
-    let reply_ids: Vec<String> = gc
-        .comments
-        .iter()
-        .filter(|c| c.source == "local" && !c.synced && c.in_reply_to.is_some())
-        .map(|c| c.id.clone())
-        .collect();
-
-    for cid in &reply_ids {
-        let comment = gc.comments.iter().find(|c| c.id == *cid).cloned();
-        if let Some(comment) = comment {
-            let parent_gh_id = comment
-                .in_reply_to
-                .as_ref()
-                .and_then(|rt| gc.comments.iter().find(|c| c.id == *rt))
-                .and_then(|c| c.github_id);
-
-            if let Some(parent_gh_id) = parent_gh_id {
-                match if is_remote {
-                    github::gh_pr_reply_comment_remote(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        parent_gh_id,
-                        &comment.comment,
-                    )
-                } else {
-                    github::gh_pr_reply_comment(
-                        &owner,
-                        &repo_name,
-                        pr_number,
-                        parent_gh_id,
-                        &comment.comment,
-                        &repo_root,
-                    )
-                } {
-                    Ok(github_id) => {
-                        if let Some(c) = gc.comments.iter_mut().find(|c| c.id == *cid) {
-                            c.github_id = Some(github_id);
-                            c.synced = true;
-                        }
-                        pushed += 1;
-                    }
-                    Err(_) => {
-                        failed += 1;
-                    }
-                }
-            } else {
-                failed += 1;
-            }
-        }
-    }
-
-    let json = serde_json::to_string_pretty(&gc)?;
-    let tmp_path = format!("{}.tmp", comments_path);
-    std::fs::write(&tmp_path, &json)?;
-    std::fs::rename(&tmp_path, &comments_path)?;
-    if is_remote {
-        app.tab_mut().reload_remote_comments();
-    } else {
-        app.tab_mut().reload_ai_state();
-    }
-
-    if failed > 0 {
-        app.notify(&format!("Review: pushed {} ({} failed)", pushed, failed));
-    } else {
-        app.notify(&format!("Review: pushed {}", pushed));
-    }
+    app.surface_comment_conflicts(conflicts);
     Ok(())
 }
 
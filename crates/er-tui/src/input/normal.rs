@@ -1,7 +1,7 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use er_engine::ai::{PanelContent, ReviewFocus};
-use er_engine::app::{App, ConfirmAction, DiffMode, InputMode, SplitSide};
+use er_engine::app::{App, ConfirmAction, DiffMode, InputMode, Motion, SplitSide};
 use er_engine::watch::{FileWatcher, WatchEvent};
 use std::path::Path;
 use std::sync::mpsc;
@@ -14,12 +14,44 @@ pub fn handle_normal_input(
     watch_tx: &mpsc::Sender<WatchEvent>,
     watcher: &mut Option<FileWatcher>,
 ) -> Result<()> {
+    // Checklist editing keys shadow the global e / x / J / K
+    if handle_checklist_key(app, key)? {
+        return Ok(());
+    }
+
+    // Binding a lost comment: Enter takes the cursor line, Esc gives up
+    if app.tab().reanchoring.is_some() {
+        match key.code {
+            KeyCode::Enter => return app.finish_reanchor(),
+            KeyCode::Esc => {
+                app.cancel_reanchor();
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
+    // The key after `m` or `'`
+    if let Some(prefix) = app.key_prefix.take() {
+        handle_prefixed_key(app, prefix, key);
+        return Ok(());
+    }
+
+    // Count prefix: Alt+digit starts one, digits after it extend it
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        if key.modifiers.contains(KeyModifiers::ALT) || app.pending_count.is_some() {
+            app.push_count_digit(c);
+            return Ok(());
+        }
+    }
+    let count = app.pending_count.take().unwrap_or(1);
+
     // ── Global keys: work in all view modes including AiReview ──
 
     match key.code {
-        // Quit (Ctrl+q)
+        // Quit (Ctrl+q) — asks first if work would be left behind
         KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.should_quit = true;
+            app.request_quit();
             return Ok(());
         }
 
@@ -48,24 +80,118 @@ pub fn handle_normal_input(
             }
             return Ok(());
         }
-        // Toggle mtime sort (works in any mode)
-        KeyCode::Char('m') => {
-            let tab = app.tab_mut();
-            tab.sort_by_mtime = !tab.sort_by_mtime;
-            let _ = tab.refresh_diff();
-            let label = if app.tab().sort_by_mtime {
-                "Sort: recent first"
-            } else {
-                "Sort: default"
-            };
-            app.notify(label);
+        // Marks: m + letter sets one (mm cycles the file sort), ' + letter
+        // jumps to it, ` lists them
+        KeyCode::Char(c @ ('m' | '\'')) if !key.modifiers.contains(KeyModifiers::ALT) => {
+            app.key_prefix = Some(c);
+            return Ok(());
+        }
+        KeyCode::Char('`') => {
+            app.open_marks_hub();
+            return Ok(());
+        }
+        // Where did the deleted block under the cursor go? (Alt+m)
+        KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.find_moved_code();
+            return Ok(());
+        }
+        // Notebook / table view of the selected data file (Alt+v)
+        KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.toggle_data_view();
+            return Ok(());
+        }
+        // Why the selected file is compacted, and changing that (Alt+c)
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.open_compaction_hub();
+            return Ok(());
+        }
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.open_snooze_hub();
+            return Ok(());
+        }
+        // Tint added lines by the age of the code around them (Alt+g)
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.toggle_code_age();
+            return Ok(());
+        }
+        // Multi-selection: Alt+s picks the file, Alt+j / Alt+k extend,
+        // Alt+a acts on the selection
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.toggle_file_selection();
+            return Ok(());
+        }
+        KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.extend_file_selection(Motion::PrevFile);
+            return Ok(());
+        }
+        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.extend_file_selection(Motion::NextFile);
+            return Ok(());
+        }
+        KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.open_selection_hub();
+            return Ok(());
+        }
+        KeyCode::Char('t') if key.modifiers == KeyModifiers::NONE => {
+            app.open_releases_hub();
+            return Ok(());
+        }
+        // Jump list: back to where the last jump came from (Ctrl+o, Alt+←)
+        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if !app.tab_mut().jump_back() {
+                app.notify("Jump list: nothing further back");
+            }
+            return Ok(());
+        }
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
+            if !app.tab_mut().jump_back() {
+                app.notify("Jump list: nothing further back");
+            }
+            return Ok(());
+        }
+        // Forward again (Ctrl+i where the terminal tells it from Tab, Alt+→)
+        KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if !app.tab_mut().jump_forward() {
+                app.notify("Jump list: nothing further forward");
+            }
+            return Ok(());
+        }
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::ALT) => {
+            if !app.tab_mut().jump_forward() {
+                app.notify("Jump list: nothing further forward");
+            }
+            return Ok(());
+        }
+
+        // Guided review order from [review_order] (Ctrl+g)
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            match app.tab_mut().toggle_guided_order() {
+                Some(true) => app.notify("Guided order: on"),
+                Some(false) => app.notify("Guided order: off"),
+                None => app.notify("No [review_order] phases in .er-config.toml"),
+            }
             return Ok(());
         }
 
         // Reload/refresh diff
         KeyCode::Char('R') => {
-            app.tab_mut().refresh_diff()?;
-            app.notify("Refreshed");
+            match app.tab_mut().refresh_diff() {
+                Ok(()) => app.notify("Refreshed"),
+                Err(e) => app.report_command_error("Refresh failed", &e),
+            }
+            return Ok(());
+        }
+
+        // Soft-wrap long lines instead of scrolling horizontally (Ctrl+w)
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let display = &mut app.config.display;
+            display.wrap_lines = !display.wrap_lines;
+            let on = display.wrap_lines;
+            app.notify(if on {
+                "Wrap: on (h-scroll paused)"
+            } else {
+                "Wrap: off"
+            });
             return Ok(());
         }
 
@@ -96,6 +222,12 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
+        // Open in an editor pane beside er (tmux / WezTerm / kitty)
+        KeyCode::Char('E') => {
+            app.open_in_split()?;
+            return Ok(());
+        }
+
         // Open in editor (or edit focused comment if own top-level)
         KeyCode::Char('e') => {
             if let Some(id) = app.tab().focused_comment_id.clone() {
@@ -106,6 +238,9 @@ pub fn handle_normal_input(
                     }
                 }
             }
+            if app.blocked_by_read_only("editing files") {
+                return Ok(());
+            }
             if app.tab().is_remote() {
                 app.notify("Editor not available in remote mode");
             } else {
@@ -116,20 +251,20 @@ pub fn handle_normal_input(
 
         // Unified hint jumping across files (Shift+J / Shift+K)
         KeyCode::Char('J') => {
-            app.prev_hint();
+            app.run_motion(Motion::PrevHint, count);
             return Ok(());
         }
         KeyCode::Char('K') => {
-            app.next_hint();
+            app.run_motion(Motion::NextHint, count);
             return Ok(());
         }
         // AI finding jumping across files (Ctrl+j / Ctrl+k)
         KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.next_finding();
+            app.run_motion(Motion::NextFinding, count);
             return Ok(());
         }
         KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.prev_finding();
+            app.run_motion(Motion::PrevFinding, count);
             return Ok(());
         }
         // Delete watched file in Hidden mode
@@ -139,7 +274,7 @@ pub fn handle_normal_input(
             if let Some(idx) = app.tab().selected_watched {
                 if let Some(wf) = app.tab().watched_files.get(idx) {
                     let path = wf.path.clone();
-                    app.input_mode = InputMode::Confirm(ConfirmAction::DeleteWatchedFile { path });
+                    app.confirm(ConfirmAction::DeleteWatchedFile { path });
                 }
             }
             return Ok(());
@@ -151,14 +286,35 @@ pub fn handle_normal_input(
             if let Some(ref id) = app.tab().focused_comment_id.clone() {
                 if let Some(comment) = app.tab().ai.find_comment(id) {
                     if comment.can_delete() {
-                        app.input_mode = InputMode::Confirm(ConfirmAction::DeleteComment {
+                        let first_line: String = comment
+                            .text()
+                            .lines()
+                            .next()
+                            .unwrap_or("")
+                            .chars()
+                            .take(60)
+                            .collect();
+                        let detail = format!("{}: {}", comment.author(), first_line);
+                        let prompt = ConfirmAction::DeleteComment {
                             comment_id: id.clone(),
-                        });
+                        }
+                        .prompt()
+                        .detail(detail);
+                        app.ask_confirm(prompt);
                     }
                 }
             }
             return Ok(());
         }
+        // Discard the selected lines (split view: the focused pane's)
+        KeyCode::Char('x')
+            if key.modifiers == KeyModifiers::NONE
+                && app.tab().selection_anchor.is_some()
+                && !app.tab().is_remote() =>
+        {
+            app.request_discard_lines();
+            return Ok(());
+        }
         // Reply to focused comment/question or finding
         KeyCode::Char('r') => {
             if let Some(id) = app.tab().focused_comment_id.clone() {
@@ -172,13 +328,20 @@ pub fn handle_normal_input(
             }
             return Ok(());
         }
+        // Expand / collapse the focused finding inline
+        KeyCode::Char('i') if key.modifiers == KeyModifiers::NONE => {
+            if app.tab_mut().toggle_finding_details().is_none() {
+                app.notify("No focused finding — J/K or Ctrl+j/k to focus one");
+            }
+            return Ok(());
+        }
         // Cleanup AI sidecar files
         KeyCode::Char('z') if key.modifiers == KeyModifiers::NONE => {
             if app.tab().is_remote() {
                 return Ok(());
             }
             let count = app.tab().ai.local_draft_count();
-            app.input_mode = InputMode::Confirm(ConfirmAction::CleanupQuestions { count });
+            app.confirm(ConfirmAction::CleanupQuestions { count });
             return Ok(());
         }
         KeyCode::Char('Z') => {
@@ -186,7 +349,7 @@ pub fn handle_normal_input(
                 return Ok(());
             }
             let count = app.tab().ai.review.as_ref().map_or(0, |r| r.files.len());
-            app.input_mode = InputMode::Confirm(ConfirmAction::CleanupReviews { count });
+            app.confirm(ConfirmAction::CleanupReviews { count });
             return Ok(());
         }
         KeyCode::Char('x') => {
@@ -228,27 +391,48 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
+        // File tree drawer on narrow terminals, show/hide elsewhere (\)
+        KeyCode::Char('\\') => {
+            app.file_tree_toggled = !app.file_tree_toggled;
+            return Ok(());
+        }
+
+        // Zoom the focused pane to the full frame, like tmux's prefix-z (M)
+        KeyCode::Char('M') => {
+            app.zoomed = !app.zoomed;
+            app.notify(if app.zoomed {
+                "Zoomed — M to restore"
+            } else {
+                "Layout restored"
+            });
+            return Ok(());
+        }
+
         // Resize file tree panel (</>)
         KeyCode::Char('<') => {
             let w = app.last_terminal_width;
-            app.tab_mut().resize_file_tree(-2, w);
+            let layout = app.config.layout.clone();
+            app.tab_mut().resize_file_tree(-2, w, &layout);
             return Ok(());
         }
         KeyCode::Char('>') => {
             let w = app.last_terminal_width;
-            app.tab_mut().resize_file_tree(2, w);
+            let layout = app.config.layout.clone();
+            app.tab_mut().resize_file_tree(2, w, &layout);
             return Ok(());
         }
 
         // Resize side panel ({/})
         KeyCode::Char('{') => {
             let w = app.last_terminal_width;
-            app.tab_mut().resize_panel(-4, w);
+            let layout = app.config.layout.clone();
+            app.tab_mut().resize_panel(-4, w, &layout);
             return Ok(());
         }
         KeyCode::Char('}') => {
             let w = app.last_terminal_width;
-            app.tab_mut().resize_panel(4, w);
+            let layout = app.config.layout.clone();
+            app.tab_mut().resize_panel(4, w, &layout);
             return Ok(());
         }
 
@@ -283,6 +467,13 @@ pub fn handle_normal_input(
                 app.jump_to_focused_finding();
                 return Ok(());
             }
+            KeyCode::Char('a') if app.tab().panel == Some(PanelContent::PrOverview) => {
+                app.start_review_comment(er_engine::ai::CommentType::Note);
+                return Ok(());
+            }
+            KeyCode::Char('a') if app.tab().panel == Some(PanelContent::Assets) => {
+                return app.mark_assets_reviewed();
+            }
             KeyCode::Char('k') | KeyCode::Down => {
                 app.tab_mut().panel_scroll_down(1);
                 app.tab_mut().panel_scroll = app.tab().panel_scroll.min(4096);
@@ -305,6 +496,15 @@ pub fn handle_normal_input(
     let mode = app.tab().mode;
 
     match key.code {
+        // Find in the current file (Ctrl+f)
+        KeyCode::Char('f')
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && !matches!(mode, DiffMode::History | DiffMode::Tour) =>
+        {
+            app.start_find();
+            return Ok(());
+        }
+
         // Search
         KeyCode::Char('/') => {
             app.input_mode = InputMode::Search;
@@ -346,21 +546,28 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
-        // Push current branch to remote (Staged mode only)
+        // Push current branch to remote (Staged mode, or commits the PR lacks)
         KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            if app.tab().mode == DiffMode::Staged && !app.tab().is_remote() {
-                app.input_mode = InputMode::Confirm(ConfirmAction::Push);
+            let tab = app.tab();
+            if (tab.mode == DiffMode::Staged || tab.has_unpushed_pr_commits()) && !tab.is_remote() {
+                super::confirm_push(app);
             }
             return Ok(());
         }
 
+        // Pin the panel and open a second one beside it, or unpin (Alt+p)
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.tab_mut().toggle_pin_panel();
+            return Ok(());
+        }
+
         // Toggle context panel (p) — cycles through panel states
         KeyCode::Char('p') => {
             app.tab_mut().toggle_panel();
             return Ok(());
         }
 
-        // Tab: resume comment draft, or toggle split/panel focus
+        // Tab: resume comment draft, or toggle split focus / cycle panel focus
         KeyCode::Tab => {
             if app.has_comment_draft() {
                 app.resume_comment();
@@ -375,10 +582,7 @@ pub fn handle_normal_input(
                     SplitSide::New => SplitSide::Old,
                 };
             } else {
-                let tab = app.tab_mut();
-                if tab.panel.is_some() {
-                    tab.panel_focus = !tab.panel_focus;
-                }
+                app.tab_mut().cycle_panel_focus();
             }
             return Ok(());
         }
@@ -393,6 +597,12 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
+        // Commit splitting: assign the current hunk/file to a planned commit (B)
+        KeyCode::Char('B') => {
+            app.open_commit_plan();
+            return Ok(());
+        }
+
         // Toggle comment layer visibility (C)
         KeyCode::Char('C') => {
             app.tab_mut().toggle_layer_comments();
@@ -465,11 +675,19 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
-        // Expand/compact toggle for compacted files (no-op in History — commit files aren't compacted)
+        // History: review the PR that merged the commit
+        KeyCode::Enter if mode == DiffMode::History => {
+            app.open_commit_pr();
+            return Ok(());
+        }
+
+        // Expand/compact toggle for compacted files, else fold / unfold the hunk
         KeyCode::Enter => {
             let is_compacted = app.tab().selected_diff_file().is_some_and(|f| f.compacted);
             if is_compacted {
                 app.tab_mut().toggle_compacted()?;
+            } else if mode != DiffMode::Tour {
+                app.toggle_hunk_fold();
             }
             return Ok(());
         }
@@ -492,12 +710,23 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
-        // Clear search first, then filter (History gains filter-clear, which is correct)
+        // Close the files drawer, then clear find, search, the quick diff, then filter (History gains filter-clear, which is correct)
         KeyCode::Esc => {
-            if !app.tab().search_query.is_empty() {
+            let narrow =
+                er_engine::app::layout::layout_mode(app.last_terminal_width, &app.config.layout)
+                    == er_engine::app::layout::LayoutMode::Narrow;
+            if narrow && app.file_tree_toggled {
+                app.file_tree_toggled = false;
+            } else if app.clear_file_selection() {
+                app.notify("Selection cleared");
+            } else if app.tab().find.is_some() {
+                app.clear_find();
+            } else if !app.tab().search_query.is_empty() {
                 let tab = app.tab_mut();
                 tab.search_query.clear();
                 tab.search_query_lower.clear();
+            } else if app.tab().in_quick_diff() {
+                app.close_quick_diff();
             } else if !app.tab().filter_expr.is_empty() {
                 app.tab_mut().clear_filter();
                 app.notify("Filter cleared");
@@ -505,7 +734,7 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
-        // Stage/unstage file (or update snapshot for watched files) — not meaningful in History or remote mode
+        // Stage/unstage file or selected lines (or update snapshot for watched files) — not meaningful in History or remote mode
         KeyCode::Char('s')
             if !matches!(mode, DiffMode::History | DiffMode::Tour)
                 && !app.tab().is_remote()
@@ -521,6 +750,10 @@ pub fn handle_normal_input(
                 } else {
                     app.notify("Snapshot mode not enabled (diff_mode = \"content\")");
                 }
+            } else if app.tab().selection_anchor.is_some() {
+                app.toggle_stage_lines()?;
+            } else if app.tab().has_selection() {
+                app.stage_selection()?;
             } else {
                 app.toggle_stage_file()?;
             }
@@ -536,7 +769,11 @@ pub fn handle_normal_input(
         // Toggle reviewed — review tracking is per-branch, not meaningful in History.
         // Tour handles `space` in its own handler (operates on the tour file list).
         KeyCode::Char(' ') if !matches!(mode, DiffMode::History | DiffMode::Tour) => {
-            app.toggle_reviewed()?;
+            if app.tab().has_selection() {
+                app.review_selection()?;
+            } else {
+                app.toggle_reviewed()?;
+            }
             return Ok(());
         }
 
@@ -549,6 +786,51 @@ pub fn handle_normal_input(
             return Ok(());
         }
 
+        // Skip the file from review (asks for a reason), or unskip it
+        KeyCode::Char('S') if !matches!(mode, DiffMode::History | DiffMode::Tour) => {
+            app.start_skip_file()?;
+            return Ok(());
+        }
+
+        // Focus mode: Space moves on to the next unreviewed file
+        KeyCode::Char('V') if !matches!(mode, DiffMode::History | DiffMode::Tour) => {
+            app.toggle_focus_mode();
+            return Ok(());
+        }
+
+        // Ask the AI provider what the current hunk does
+        KeyCode::Char('H') if !matches!(mode, DiffMode::History | DiffMode::Tour) => {
+            app.explain_current_hunk();
+            return Ok(());
+        }
+
+        // Jump between a source file and its test
+        KeyCode::Char('T') if !matches!(mode, DiffMode::History | DiffMode::Tour) => {
+            app.jump_to_test_pair();
+            return Ok(());
+        }
+
+        // Changes since the review checkpoint (interdiff)
+        KeyCode::Char('I') => {
+            app.toggle_checkpoint_view();
+            return Ok(());
+        }
+
+        KeyCode::Char('L') => {
+            app.open_notification_log();
+            return Ok(());
+        }
+
+        KeyCode::Char('D') => {
+            app.open_drafts_hub();
+            return Ok(());
+        }
+
+        KeyCode::Char('Y') if !matches!(mode, DiffMode::History) => {
+            app.export_handoff()?;
+            return Ok(());
+        }
+
         // Copy hub — offers full file, path, hunk, or line copy options
         KeyCode::Char('y')
             if !matches!(mode, DiffMode::History | DiffMode::Tour)
@@ -575,16 +857,8 @@ pub fn handle_normal_input(
 
     match key.code {
         // File navigation
-        KeyCode::Char('j') => {
-            app.tab_mut().prev_file();
-            let threshold = app.config.display.auto_context_threshold;
-            app.tab_mut().maybe_auto_expand_context(threshold);
-        }
-        KeyCode::Char('k') => {
-            app.tab_mut().next_file();
-            let threshold = app.config.display.auto_context_threshold;
-            app.tab_mut().maybe_auto_expand_context(threshold);
-        }
+        KeyCode::Char('j') => app.run_motion(Motion::PrevFile, count),
+        KeyCode::Char('k') => app.run_motion(Motion::NextFile, count),
 
         // Line/comment navigation (arrow keys: comments when focused, else lines)
         // Shift+arrow extends selection, plain arrow clears it
@@ -623,16 +897,21 @@ pub fn handle_normal_input(
                 }
             }
         }
-        KeyCode::Down => {
-            app.tab_mut().next_line();
-        }
-        KeyCode::Up => {
-            app.tab_mut().prev_line();
-        }
+        KeyCode::Down => app.run_motion(Motion::NextLine, count),
+        KeyCode::Up => app.run_motion(Motion::PrevLine, count),
 
+        // Between find matches while a find is active, else hunks
+        KeyCode::Char(c @ ('n' | 'N')) if app.tab().active_find().is_some() => {
+            for _ in 0..count {
+                app.find_next(c == 'n');
+            }
+        }
         // Hunk navigation
-        KeyCode::Char('n') => app.tab_mut().next_hunk(),
-        KeyCode::Char('N') => app.tab_mut().prev_hunk(),
+        KeyCode::Char('n') => app.run_motion(Motion::NextHunk, count),
+        KeyCode::Char('N') => app.run_motion(Motion::PrevHunk, count),
+
+        // The last motion again, with its count
+        KeyCode::Char('.') => app.repeat_motion(),
 
         // Horizontal scroll (for long lines)
         KeyCode::Char('l') | KeyCode::Right => {
@@ -703,6 +982,47 @@ pub fn handle_normal_input(
     Ok(())
 }
 
+/// Edit keys for the focused checklist column. True when the key was used.
+/// `key` after prefix `m` or `'`. Anything but a mark letter (or `m` after
+/// `m`) cancels the prefix.
+fn handle_prefixed_key(app: &mut App, prefix: char, key: KeyEvent) {
+    match (prefix, key.code) {
+        // Cycle the file tree sort (works in any mode)
+        ('m', KeyCode::Char('m')) => {
+            let sort = app.tab_mut().cycle_file_sort();
+            app.notify(&format!("Sort: {}", sort.label()));
+        }
+        ('m', KeyCode::Char(c)) if er_engine::app::is_mark_name(c) => app.set_mark(c),
+        ('\'', KeyCode::Char(c)) if er_engine::app::is_mark_name(c) => app.jump_to_mark(c),
+        (_, KeyCode::Esc) => {}
+        _ => app.notify("Marks are named a–z"),
+    }
+}
+
+fn handle_checklist_key(app: &mut App, key: KeyEvent) -> Result<bool> {
+    let tab = app.tab();
+    if !tab.panel_focus
+        || tab.panel != Some(PanelContent::AiSummary)
+        || tab.review_focus != ReviewFocus::Checklist
+        || key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return Ok(false);
+    }
+    match key.code {
+        KeyCode::Char('a') => app.start_checklist_add(),
+        KeyCode::Char('e') => app.start_checklist_edit(),
+        KeyCode::Char('x') => app.request_delete_checklist_item(),
+        KeyCode::Char('K') => app.move_checklist_item(true)?,
+        KeyCode::Char('J') => app.move_checklist_item(false)?,
+        KeyCode::Char('f') => app.toggle_checklist_file()?,
+        KeyCode::Char('t') => app.open_checklist_hub(),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
 pub fn handle_ai_review_input(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
         // Navigation within focused column
@@ -713,24 +1033,41 @@ pub fn handle_ai_review_input(app: &mut App, key: KeyEvent) -> Result<()> {
             app.tab_mut().review_prev();
         }
 
-        // Switch focus between left/right columns
+        // Cycle focus through files → categories → checklist
         KeyCode::Tab
         | KeyCode::Char('l')
         | KeyCode::Right
         | KeyCode::BackTab
         | KeyCode::Char('h')
         | KeyCode::Left => {
-            app.tab_mut().review_toggle_focus();
-            let (files_offset, checklist_offset) = app.tab().ai_summary_section_offsets();
+            let back = matches!(
+                key.code,
+                KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Left
+            );
+            app.tab_mut().review_cycle_focus(back);
+            let (files_offset, categories_offset, checklist_offset) =
+                app.tab().ai_summary_section_offsets();
             app.tab_mut().panel_scroll = match app.tab().review_focus {
                 ReviewFocus::Files => files_offset,
+                ReviewFocus::Categories => categories_offset,
                 ReviewFocus::Checklist => checklist_offset,
             };
         }
 
-        // Toggle checklist item
+        // Toggle checklist item / fold category
         KeyCode::Char(' ') => {
-            app.review_toggle_checklist()?;
+            if app.tab().review_focus == ReviewFocus::Categories {
+                app.tab_mut().review_toggle_category();
+            } else {
+                app.review_toggle_checklist()?;
+            }
+        }
+
+        // Cycle file list order
+        KeyCode::Char('s') => {
+            app.tab_mut().review_cycle_sort();
+            let label = app.tab().review_sort.label();
+            app.notify(&format!("Files sorted by {}", label));
         }
 
         // Jump to file
@@ -788,6 +1125,21 @@ pub fn handle_history_input(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('n') => app.tab_mut().history_next_file(),
         KeyCode::Char('N') => app.tab_mut().history_prev_file(),
 
+        // Fold commits a later commit undoes
+        KeyCode::Char('O') => {
+            let folded = app.tab_mut().toggle_net_zero_fold();
+            let fold_on = app.tab().history.as_ref().is_some_and(|h| h.fold_net_zero);
+            if fold_on {
+                app.notify(&format!(
+                    "Folded {} commit{} undone later",
+                    folded,
+                    if folded == 1 { "" } else { "s" }
+                ));
+            } else {
+                app.notify("Showing all commits");
+            }
+        }
+
         // Line navigation (arrows)
         KeyCode::Down => app.tab_mut().history_next_line(),
         KeyCode::Up => app.tab_mut().history_prev_line(),
@@ -1,8 +1,11 @@
+mod completions;
 mod input;
+mod replay;
 mod ui;
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
 use crossterm::{
     cursor::Show,
     event::{self, Event},
@@ -10,11 +13,8 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use er_engine::app::{self, App, InputMode};
-use er_engine::{github, uninstall, watch};
-use input::{
-    handle_comment_input, handle_commit_input, handle_confirm_input, handle_filter_input,
-    handle_normal_input, handle_overlay_input, handle_remote_url_input, handle_search_input,
-};
+use er_engine::{artifact_gc, debug_log, git, github, profile, uninstall, watch};
+use input::handle_key;
 use ratatui::prelude::*;
 use std::io::{self, Write};
 use std::sync::mpsc;
@@ -32,11 +32,12 @@ struct Cli {
     paths: Vec<String>,
 
     /// Open a specific PR by number (from the current repo)
-    #[arg(long)]
+    #[arg(long, add = ArgValueCandidates::new(completions::pr_numbers))]
     pr: Option<u64>,
 
-    /// Pre-apply a file filter expression (e.g. '+*.rs,-*.lock')
-    #[arg(long)]
+    /// Pre-apply a file filter expression (e.g. '+*.rs,-*.lock') or a
+    /// preset by name (frontend, backend, config, docs, review)
+    #[arg(long, add = ArgValueCandidates::new(completions::filters))]
     filter: Option<String>,
 
     /// Review a PR from any directory without a local clone
@@ -44,8 +45,57 @@ struct Cli {
     remote: bool,
 
     /// Override the base branch to diff against (useful for stacked branches)
-    #[arg(long)]
+    #[arg(long, visible_alias = "base", add = ArgValueCandidates::new(completions::branches))]
     target: Option<String>,
+
+    /// Merge a session bundle exported by another reviewer (see Git hub →
+    /// Export session) before starting
+    #[arg(long, value_name = "FILE")]
+    import_session: Option<String>,
+
+    /// Move the cursor of the running er instance for this repo to FILE[:LINE]
+    /// (for editor plugins), then exit
+    #[arg(long, value_name = "FILE[:LINE]")]
+    focus: Option<String>,
+
+    /// Show the first-run onboarding guide again
+    #[arg(long)]
+    onboarding: bool,
+
+    /// Time startup phases (git diff, parse, highlight, AI load), show a
+    /// frame-time overlay, and print the breakdown on exit
+    #[arg(long)]
+    profile: bool,
+
+    /// Load the diff, print its size and the startup breakdown, then exit
+    /// without starting the UI
+    #[arg(long, conflicts_with = "profile")]
+    stats: bool,
+
+    /// Review without changing anything: staging, committing, pushing,
+    /// switching branches, editing files, approving and writing comments,
+    /// viewed ticks or the shared review to GitHub are disabled
+    #[arg(long)]
+    read_only: bool,
+
+    /// Save the keys pressed this session to FILE, as a script for --replay
+    #[arg(long, value_name = "FILE")]
+    record: Option<String>,
+
+    /// Feed the keys in FILE (see --record) to er on an in-memory terminal,
+    /// then print the final state and requested frames as JSON and exit
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record", "remote", "stats"])]
+    replay: Option<String>,
+
+    /// Override a config key for this run, on top of the config files and
+    /// `ER_SECTION__KEY` environment variables (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    set: Vec<String>,
+
+    /// Print the release notes between this version and the latest release,
+    /// then exit
+    #[arg(long)]
+    changelog: bool,
 }
 
 #[derive(Subcommand)]
@@ -68,19 +118,308 @@ enum Commands {
         #[arg(long)]
         keep_apps: bool,
     },
+    /// Remove this repo's stale review data: buckets of deleted branches and
+    /// closed PRs, empty sidecars and leftover temp and lock files
+    Clean {
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+        /// Print what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List review comments across every review, from the SQLite review
+    /// index (`[storage] backend = "sqlite"`)
+    Comments {
+        /// Only comments that aren't resolved
+        #[arg(long)]
+        unresolved: bool,
+        /// Only comments by this author (GitHub login, or "You")
+        #[arg(long)]
+        author: Option<String>,
+        /// Re-read every review's sidecars into the index first
+        #[arg(long)]
+        reindex: bool,
+    },
+    /// Print the completion script for a shell: `source <(er completions bash)`
+    Completions {
+        #[arg(value_parser = completions::SHELLS)]
+        shell: String,
+    },
+    /// Print the man page as roff: `er man > er.1`
+    Man,
+    /// Summarize local usage stats: review time, review sizes, files and
+    /// comments, features used. Nothing leaves the machine
+    Stats {
+        /// How far back: 30d, 2w, 12h
+        #[arg(long, default_value = "30d")]
+        last: String,
+        /// Print the summary as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check git, gh, the clipboard, file watching, the terminal and the
+    /// config files, and print how to fix what's wrong
+    Doctor,
+    /// Download the latest release for this platform and replace this
+    /// binary with it
+    Update {
+        /// Only say whether a newer release is out
+        #[arg(long)]
+        check: bool,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective configuration — config files, environment and
+    /// --set merged — with where each value comes from
+    Show,
+}
+
+fn run_config_show() -> Result<()> {
+    let repo_root = git::get_repo_root().ok();
+    let values = er_engine::config_overrides::effective(repo_root.as_deref());
+    let width = values.iter().map(|v| v.key.len()).max().unwrap_or_default();
+    for v in &values {
+        println!("{:width$} = {}  # {}", v.key, v.value, v.source);
+    }
+    Ok(())
+}
+
+fn run_stats(last: &str, json: bool) -> Result<()> {
+    use er_engine::usage;
+
+    let window = usage::parse_window(last)
+        .with_context(|| format!("--last {:?}: expected e.g. 30d, 2w or 12h", last))?;
+    let since = er_engine::outbox::unix_now().saturating_sub(window);
+    let summary = usage::summarize(&usage::load_since(since));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        print!("{}", usage::format_summary(&summary, last));
+    }
+    Ok(())
+}
+
+fn run_doctor() -> Result<()> {
+    use er_engine::doctor::{self, Status};
+
+    let repo_root = git::get_repo_root().ok();
+    let checks = doctor::run(repo_root.as_deref());
+    let width = checks
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or_default();
+    for check in &checks {
+        println!(
+            "{} {:width$}  {}",
+            check.status.symbol(),
+            check.name,
+            check.detail
+        );
+        if let Some(ref fix) = check.fix {
+            println!("  {:width$}  → {}", "", fix);
+        }
+    }
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    let warned = checks.iter().filter(|c| c.status == Status::Warn).count();
+    println!();
+    if failed > 0 {
+        anyhow::bail!("{} problems, {} warnings", failed, warned);
+    }
+    println!("No problems, {} warnings", warned);
+    Ok(())
+}
+
+fn run_update(check: bool) -> Result<()> {
+    use er_engine::self_update::{self, VERSION};
+
+    let releases = self_update::fetch_releases()?;
+    let newer = self_update::newer_than(&releases, VERSION);
+    let Some(latest) = newer.first() else {
+        println!("er {} is up to date", VERSION);
+        return Ok(());
+    };
+    if check {
+        println!(
+            "er {} is out (you have {}) — run er update",
+            latest.version(),
+            VERSION
+        );
+        return Ok(());
+    }
+    let exe = std::env::current_exe()?.canonicalize()?;
+    println!("Updating {} to er {}…", exe.display(), latest.version());
+    self_update::install(latest, &exe)?;
+    println!("Updated er {} → {}", VERSION, latest.version());
+    for release in &newer {
+        let date = release.published_at.get(..10).unwrap_or_default();
+        println!("  {} ({})", release.tag, date);
+    }
+    println!(
+        "Release notes: https://github.com/{}/releases",
+        self_update::REPO
+    );
+    Ok(())
+}
+
+fn run_changelog() -> Result<()> {
+    use er_engine::self_update::{self, VERSION};
+
+    let releases = self_update::fetch_releases()?;
+    let changelog = self_update::changelog(&releases, VERSION);
+    if changelog.is_empty() {
+        println!("er {} is the latest release", VERSION);
+    } else {
+        print!("{}", changelog);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+fn run_comments(unresolved: bool, author: Option<String>, reindex: bool) -> Result<()> {
+    use er_engine::review_db::{CommentQuery, ReviewDb};
+
+    // Same artifact root as the reviews opened from here
+    if let Ok(repo_root) = git::get_repo_root() {
+        let global = er_engine::config::load_global_config();
+        er_engine::storage::configure_for_repo(&repo_root, &global.storage);
+    }
+    let mut db = ReviewDb::open_default()?;
+    if reindex {
+        let dirs = db.reindex(&er_engine::storage::artifact_root())?;
+        eprintln!("Indexed {} review directories", dirs);
+    }
+    let rows = db.comments(&CommentQuery {
+        unresolved,
+        author,
+        dir_prefix: None,
+    })?;
+    let root = er_engine::storage::artifact_root();
+    for row in &rows {
+        let review = std::path::Path::new(&row.dir)
+            .strip_prefix(&root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| row.dir.clone());
+        let c = &row.comment;
+        let line = c.line.map(|l| format!(":{}", l)).unwrap_or_default();
+        let first_line = c.text.lines().next().unwrap_or_default();
+        println!("{review}\t{}{line}\t{}\t{first_line}", c.file, c.author);
+    }
+    eprintln!("{} comments", rows.len());
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn run_comments(_unresolved: bool, _author: Option<String>, _reindex: bool) -> Result<()> {
+    anyhow::bail!(
+        "er comments needs er built with the sqlite feature (cargo install --features sqlite)"
+    )
 }
 
-/// Restore the terminal before the default panic handler runs, so a panic
-/// inside the event loop doesn't leave the shell in raw mode with no cursor.
+/// Restore the terminal first, so a panic inside the event loop doesn't leave
+/// the shell in raw mode with no cursor, then write a crash report (see
+/// `er_engine::crash`) and say where it went. Falls back to the default
+/// handler's output if the report can't be written.
 fn install_panic_hook() {
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         let _ = disable_raw_mode();
         let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
-        default_hook(info);
+
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let location = info.location().map(|l| l.to_string());
+        debug_log::error(
+            "panic",
+            format!("{} at {}", message, location.as_deref().unwrap_or("?")),
+        );
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let report = er_engine::crash::report(
+            &message,
+            location.as_deref(),
+            &backtrace,
+            &debug_log::recent(),
+        );
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let dir = std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir());
+        match er_engine::crash::write_report(&dir, secs, &report) {
+            Ok(path) => {
+                eprintln!("\ner crashed, sorry about that: {}", message);
+                eprintln!("A crash report was written to {}", path.display());
+                eprintln!(
+                    "Please attach it to an issue at {}",
+                    er_engine::crash::ISSUES_URL
+                );
+            }
+            Err(_) => default_hook(info),
+        }
     }));
 }
 
+fn run_clean(yes: bool, dry_run: bool) -> Result<()> {
+    let repo_root = git::get_repo_root().context("er clean runs inside a git repository")?;
+    let global = er_engine::config::load_global_config();
+    er_engine::storage::configure_for_repo(&repo_root, &global.storage);
+    let targets = artifact_gc::plan(&repo_root, &[]);
+
+    println!("Easy Review clean — {}", repo_root);
+    println!();
+    if targets.is_empty() {
+        println!("Nothing to clean up.");
+        return Ok(());
+    }
+    for t in &targets {
+        println!("  • {}", t.description());
+    }
+    println!();
+    for line in artifact_gc::summarize(&targets) {
+        println!("{line}");
+    }
+    println!();
+
+    if dry_run {
+        println!("Dry run — no changes made.");
+        return Ok(());
+    }
+
+    if !yes {
+        print!("Remove these? [y/N] ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        if !line.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let report = artifact_gc::execute(&targets);
+    for line in report.summary_lines() {
+        println!("{line}");
+    }
+    if !report.is_success() {
+        anyhow::bail!("Clean completed with errors");
+    }
+    println!();
+    println!("Done. Freed {}.", er_engine::git::format_size(report.bytes));
+    Ok(())
+}
+
 fn run_uninstall(yes: bool, dry_run: bool, opts: uninstall::UninstallOptions) -> Result<()> {
     let plan = uninstall::plan(&opts);
     let existing = uninstall::existing_targets(&opts);
@@ -144,26 +483,69 @@ fn run_uninstall(yes: bool, dry_run: bool, opts: uninstall::UninstallOptions) ->
 }
 
 fn main() -> Result<()> {
+    let started = Instant::now();
     er_engine::env_path::init_cli_path();
     install_panic_hook();
+    // The completion script calling back: print the candidates and exit
+    clap_complete::CompleteEnv::with_factory(Cli::command)
+        .var(completions::COMPLETE_VAR)
+        .complete();
     let cli = Cli::parse();
+    if cli.profile || cli.stats {
+        profile::enable();
+    }
+    let profile_start = cli.profile.then_some(started);
+    #[cfg(feature = "tracing")]
+    init_tracing();
+    let _ = debug_log::init_from_env();
+    er_engine::config_overrides::install(&cli.set).map_err(anyhow::Error::msg)?;
+
+    match cli.command {
+        Some(Commands::Uninstall {
+            yes,
+            dry_run,
+            keep_data,
+            keep_config,
+            keep_apps,
+        }) => {
+            let opts = uninstall::UninstallOptions {
+                remove_config: !keep_config,
+                remove_data: !keep_data,
+                remove_cache: true,
+                remove_binaries: !keep_apps,
+                remove_desktop_app: !keep_apps,
+            };
+            return run_uninstall(yes, dry_run, opts);
+        }
+        Some(Commands::Clean { yes, dry_run }) => return run_clean(yes, dry_run),
+        Some(Commands::Comments {
+            unresolved,
+            author,
+            reindex,
+        }) => return run_comments(unresolved, author, reindex),
+        Some(Commands::Stats { last, json }) => return run_stats(&last, json),
+        Some(Commands::Doctor) => return run_doctor(),
+        Some(Commands::Update { check }) => return run_update(check),
+        Some(Commands::Config {
+            command: ConfigCommands::Show,
+        }) => return run_config_show(),
+        Some(Commands::Completions { shell }) => {
+            return completions::write_registration(&shell, &mut io::stdout());
+        }
+        Some(Commands::Man) => return completions::write_man(Cli::command(), &mut io::stdout()),
+        None => {}
+    }
+
+    if cli.changelog {
+        return run_changelog();
+    }
 
-    if let Some(Commands::Uninstall {
-        yes,
-        dry_run,
-        keep_data,
-        keep_config,
-        keep_apps,
-    }) = cli.command
-    {
-        let opts = uninstall::UninstallOptions {
-            remove_config: !keep_config,
-            remove_data: !keep_data,
-            remove_cache: true,
-            remove_binaries: !keep_apps,
-            remove_desktop_app: !keep_apps,
+    if let Some(ref target) = cli.focus {
+        let repo_root = match cli.paths.first() {
+            Some(dir) => git::get_repo_root_in(dir)?,
+            None => git::get_repo_root()?,
         };
-        return run_uninstall(yes, dry_run, opts);
+        return er_engine::focus_ipc::send_focus(&repo_root, target);
     }
 
     // Reject conflicting --pr and PR URL arguments
@@ -209,6 +591,7 @@ fn main() -> Result<()> {
         let tab = app::TabState::new_remote(&pr_ref)?;
         let pr_data = github::gh_pr_overview_remote(&pr_ref.owner, &pr_ref.repo, pr_ref.number);
         let mut app = App::new_remote(tab, pr_data);
+        app.read_only = cli.read_only;
         app.tab_mut().reload_remote_comments();
 
         // Open additional remote PR tabs
@@ -220,26 +603,45 @@ fn main() -> Result<()> {
 
         // Apply --filter flag if provided
         if let Some(ref filter_expr) = cli.filter {
-            app.tab_mut().apply_filter_expr(filter_expr);
+            let expr = app::filter::preset_expr(filter_expr).unwrap_or(filter_expr);
+            app.tab_mut().apply_filter_expr(expr);
         }
 
         let mut highlighter = ui::highlight::Highlighter::new();
+        if cli.stats {
+            print_stats(&app, started);
+            return Ok(());
+        }
 
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
-
-        let result = run_app(&mut terminal, &mut app, &mut highlighter, None, None);
+        let mut recording = start_recording(&cli, &terminal);
+
+        let result = run_app(
+            &mut terminal,
+            &mut app,
+            &mut highlighter,
+            None,
+            None,
+            profile_start,
+            recording.as_mut(),
+        );
 
         disable_raw_mode()?;
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
+        save_recording(&cli, recording);
 
         if let Err(err) = result {
+            debug_log::error("er", format!("exited with error: {:#}", err));
             eprintln!("Error: {:?}", err);
         }
+        if cli.profile {
+            print_profile(started);
+        }
 
         // Print resume hint for remote sessions
         print_resume_hint(&app);
@@ -248,10 +650,24 @@ fn main() -> Result<()> {
     }
 
     // Init app state (detects repo, branch, base branch, runs initial diff)
-    let mut app = App::new_with_args(&cli.paths)?;
+    // Huge diffs ask which directories to leave out before parsing
+    app::enable_size_guard();
+    // Outside a repository: start on the folder browser rather than erroring
+    let no_repo = cli.paths.is_empty()
+        && cli.pr.is_none()
+        && cli.target.is_none()
+        && cli.replay.is_none()
+        && git::get_repo_root().is_err();
+    let mut app = if no_repo {
+        let cwd = std::env::current_dir()?.to_string_lossy().to_string();
+        App::new_without_repo(cwd)
+    } else {
+        App::new_with_args(&cli.paths)?
+    };
+    app.read_only = cli.read_only;
 
     // Initialize theme from config
-    ui::themes::set_theme_by_name(&app.config.display.theme);
+    ui::themes::set_theme_by_name(app.config.accessibility.theme(&app.config.display.theme));
 
     // Apply --target flag: override base branch for all local tabs
     if let Some(ref target) = cli.target {
@@ -265,61 +681,81 @@ fn main() -> Result<()> {
     if let Some(pr_number) = cli.pr {
         github::ensure_gh_installed()?;
         let repo_root = app.tab().repo_root.clone();
-        let head_ref = github::fetch_pr_head(pr_number, &repo_root)?;
-        let base = github::gh_pr_base_branch(pr_number, &repo_root)?;
-        let base = github::ensure_base_ref_available(&repo_root, &base)?;
-        let head_branch = github::gh_pr_head_branch_name(pr_number, &repo_root)
-            .unwrap_or_else(|_| format!("pr/{}", pr_number));
-        let tab = app.tab_mut();
-        tab.base_branch = base;
-        tab.pr_head_ref = Some(head_ref);
-        tab.pr_number = Some(pr_number);
-        tab.current_branch = head_branch;
-        tab.refresh_diff()?;
+        *app.tab_mut() = app::TabState::new_pr_head_review(repo_root, pr_number)?;
     }
 
     // Apply --filter flag if provided
     if let Some(ref filter_expr) = cli.filter {
-        app.tab_mut().apply_filter_expr(filter_expr);
+        let expr = app::filter::preset_expr(filter_expr).unwrap_or(filter_expr);
+        app.tab_mut().apply_filter_expr(expr);
     }
 
+    // Replays start from the diff alone: no saved session, no onboarding
+    if let Some(ref path) = cli.replay {
+        let script = replay::load(std::path::Path::new(path))?;
+        let state = replay::run(&mut app, &script)?;
+        println!("{}", serde_json::to_string_pretty(&state)?);
+        return Ok(());
+    }
+
+    // Per-repo view preferences first, so a matching session wins
+    app.restore_view_prefs();
+
     // Restore previous session if diff hash matches
     for tab in &mut app.tabs {
         tab.restore_session();
     }
+    app.notify_restored_drafts();
+
+    if let Some(ref path) = cli.import_session {
+        app.import_session(path);
+    }
+
+    if cli.onboarding {
+        app.open_onboarding();
+    } else {
+        app.maybe_show_onboarding();
+    }
+
+    if cli.stats {
+        let _highlighter = ui::highlight::Highlighter::new();
+        print_stats(&app, started);
+        return Ok(());
+    }
 
     // Hint + PR data: check for PR in background (avoids blocking startup on network)
-    let (hint_rx, pr_data_rx) =
-        if cli.pr.is_none() && !cli.paths.iter().any(|p| github::is_github_pr_url(p)) {
-            let repo_root = app.tab().repo_root.clone();
-            let current_base = app.tab().base_branch.clone();
-            let (hint_tx, hint_rx) = mpsc::channel::<String>();
-            let (pr_tx, pr_rx) = mpsc::channel::<github::PrOverviewData>();
-            std::thread::spawn(move || {
-                if let Some((pr_num, pr_base)) = github::gh_pr_for_current_branch(&repo_root) {
-                    if pr_base != current_base {
-                        let _ = hint_tx.send(format!(
-                            "PR #{} targets {} — run: er --pr {}",
-                            pr_num, pr_base, pr_num
-                        ));
-                    }
-                    // Fetch PR overview data regardless of base mismatch
-                    if let Some(data) = github::gh_pr_overview(&repo_root, Some(pr_num)) {
-                        let _ = pr_tx.send(data);
-                    }
+    let (hint_rx, pr_data_rx) = if app.tab().is_repoless() {
+        (None, None)
+    } else if cli.pr.is_none() && !cli.paths.iter().any(|p| github::is_github_pr_url(p)) {
+        let repo_root = app.tab().repo_root.clone();
+        let current_base = app.tab().base_branch.clone();
+        let (hint_tx, hint_rx) = mpsc::channel::<String>();
+        let (pr_tx, pr_rx) = mpsc::channel::<github::PrOverviewData>();
+        std::thread::spawn(move || {
+            if let Some((pr_num, pr_base)) = github::gh_pr_for_current_branch(&repo_root) {
+                if pr_base != current_base {
+                    let _ = hint_tx.send(format!(
+                        "PR #{} targets {} — run: er --pr {}",
+                        pr_num, pr_base, pr_num
+                    ));
+                }
+                // Fetch PR overview data regardless of base mismatch
+                if let Some(data) = github::gh_pr_overview(&repo_root, Some(pr_num)) {
+                    let _ = pr_tx.send(data);
                 }
-            });
-            (Some(hint_rx), Some(pr_rx))
-        } else {
-            // For --pr flag or PR URL, fetch PR data synchronously (already in the right state)
-            let repo_root = app.tab().repo_root.clone();
-            let pr_number_for_data = app.tab().pr_number;
-            let pr_data = github::gh_pr_overview(&repo_root, pr_number_for_data);
-            if let Some(data) = pr_data {
-                app.tab_mut().pr_data = Some(data);
             }
-            (None, None)
-        };
+        });
+        (Some(hint_rx), Some(pr_rx))
+    } else {
+        // For --pr flag or PR URL, fetch PR data synchronously (already in the right state)
+        let repo_root = app.tab().repo_root.clone();
+        let pr_number_for_data = app.tab().pr_number;
+        let pr_data = github::gh_pr_overview(&repo_root, pr_number_for_data);
+        if let Some(data) = pr_data {
+            app.tab_mut().pr_data = Some(data);
+        }
+        (None, None)
+    };
 
     // Load syntax highlighting (once, reused for all files)
     let mut highlighter = ui::highlight::Highlighter::new();
@@ -330,6 +766,7 @@ fn main() -> Result<()> {
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    let mut recording = start_recording(&cli, &terminal);
 
     // Run event loop
     let result = run_app(
@@ -338,22 +775,87 @@ fn main() -> Result<()> {
         &mut highlighter,
         hint_rx,
         pr_data_rx,
+        profile_start,
+        recording.as_mut(),
     );
 
     // Cleanup (the panic hook covers the panic path)
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
+    save_recording(&cli, recording);
 
     if let Err(err) = result {
+        debug_log::error("er", format!("exited with error: {:#}", err));
         eprintln!("Error: {:?}", err);
     }
+    if cli.profile {
+        print_profile(started);
+    }
 
     print_resume_hint(&app);
 
     Ok(())
 }
 
+/// With the `tracing` feature, `ER_TRACE=<file>` logs every profiled span
+/// (with its duration on close) to that file.
+#[cfg(feature = "tracing")]
+fn init_tracing() {
+    use tracing_subscriber::fmt::format::FmtSpan;
+    let Some(path) = std::env::var_os("ER_TRACE") else {
+        return;
+    };
+    match std::fs::File::create(&path) {
+        Ok(file) => {
+            let _ = tracing_subscriber::fmt()
+                .with_writer(std::sync::Mutex::new(file))
+                .with_ansi(false)
+                .with_span_events(FmtSpan::CLOSE)
+                .try_init();
+        }
+        Err(e) => eprintln!("Warning: cannot write ER_TRACE file: {}", e),
+    }
+}
+
+/// `er --stats`: diff size plus where startup time went.
+fn print_stats(app: &App, started: Instant) {
+    for tab in &app.tabs {
+        let files = tab.active_diff_files();
+        let hunks: usize = files
+            .iter()
+            .map(|f| f.raw_hunk_count.max(f.hunks.len()))
+            .sum();
+        let adds: usize = files.iter().map(|f| f.adds).sum();
+        let dels: usize = files.iter().map(|f| f.dels).sum();
+        println!(
+            "{} ({} vs {}): {} files, {} hunks, +{} -{}{}",
+            tab.tab_name(),
+            tab.mode.git_mode(),
+            tab.base_branch,
+            files.len(),
+            hunks,
+            adds,
+            dels,
+            if tab.lazy_mode { ", lazily parsed" } else { "" }
+        );
+    }
+    println!();
+    print!(
+        "{}",
+        profile::report(&profile::snapshot(), started.elapsed())
+    );
+}
+
+/// `er --profile`: phase totals for the whole session, printed on exit.
+fn print_profile(started: Instant) {
+    eprintln!();
+    eprint!(
+        "{}",
+        profile::report(&profile::snapshot(), started.elapsed())
+    );
+}
+
 /// Print a dim `er <args>` hint so the user can quickly reopen the same session.
 fn print_resume_hint(app: &App) {
     let has_remote = app.tabs.iter().any(|t| t.remote_repo.is_some());
@@ -364,7 +866,9 @@ fn print_resume_hint(app: &App) {
             .iter()
             .map(|t| {
                 if let (Some(slug), Some(n)) = (&t.remote_repo, t.pr_number) {
-                    format!("https://github.com/{}/pull/{}", slug, n)
+                    github::web_url(&format!("{}/pull/{}", slug, n))
+                } else if let Some(app::NoRepo::Patch(path)) = &t.no_repo {
+                    path.clone()
                 } else {
                     t.repo_root.clone()
                 }
@@ -380,12 +884,64 @@ fn print_resume_hint(app: &App) {
     }
 }
 
+/// Poll interval while anything is happening (keys, toasts, pending refresh).
+const ACTIVE_POLL: Duration = Duration::from_millis(50);
+/// Longest poll interval once the UI has been idle for a while.
+const IDLE_POLL_MAX: Duration = Duration::from_millis(400);
+/// Redraw at least this often so relative timestamps and clocks stay current.
+const REDRAW_HEARTBEAT: Duration = Duration::from_secs(1);
+/// How often `.er-*` files are checked for AI data changes when their
+/// directory can't be watched, and how often the watched directory follows
+/// the active tab.
+const AI_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// A burst of `.er-*` writes (an agent saving several sidecars) reloads once,
+/// this long after the last one.
+const AI_RELOAD_COALESCE: Duration = Duration::from_millis(300);
+/// Session autosave debounce after the last key.
+const SESSION_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// Upper bound on how stale the saved comment draft can get while typing.
+const DRAFT_SAVE_DELAY: Duration = Duration::from_millis(500);
+/// How often watched (git-ignored) files are rescanned.
+const WATCHED_RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Next poll timeout: back to [`ACTIVE_POLL`] on activity, otherwise double
+/// up to [`IDLE_POLL_MAX`].
+/// `--record`: an empty recording sized like the terminal.
+fn start_recording<B: Backend>(cli: &Cli, terminal: &Terminal<B>) -> Option<replay::Recording> {
+    cli.record.as_ref()?;
+    let size = terminal.size().ok()?;
+    Some(replay::Recording::new(size.width, size.height))
+}
+
+fn save_recording(cli: &Cli, recording: Option<replay::Recording>) {
+    let (Some(path), Some(recording)) = (cli.record.as_ref(), recording) else {
+        return;
+    };
+    match recording.save(std::path::Path::new(path)) {
+        Ok(()) => eprintln!(
+            "Recorded keys to {} (replay with: er --replay {})",
+            path, path
+        ),
+        Err(e) => eprintln!("Error: {:#}", e),
+    }
+}
+
+fn next_poll(current: Duration, active: bool) -> Duration {
+    if active {
+        ACTIVE_POLL
+    } else {
+        (current * 2).min(IDLE_POLL_MAX)
+    }
+}
+
 fn run_app<B: Backend<Error: Send + Sync + 'static>>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     hl: &mut ui::highlight::Highlighter,
     hint_rx: Option<mpsc::Receiver<String>>,
     pr_data_rx: Option<mpsc::Receiver<github::PrOverviewData>>,
+    profile_start: Option<Instant>,
+    mut recording: Option<&mut replay::Recording>,
 ) -> Result<()> {
     // Channel for file watch events
     let (watch_tx, watch_rx) = mpsc::channel::<WatchEvent>();
@@ -401,6 +957,15 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
     let mut refresh_deadline = Instant::now();
     let mut pending_file_count = 0usize;
 
+    // Editor round-trip: `er --focus file:line` from plugins (unix only).
+    // Binding fails silently when another er already serves this repo.
+    #[cfg(unix)]
+    let focus_listener = if app.tab().is_remote() || app.tab().is_repoless() {
+        None
+    } else {
+        er_engine::focus_ipc::FocusListener::bind(&app.tab().repo_root).ok()
+    };
+
     // Session auto-save: debounced at ~2 seconds
     let mut session_dirty = false;
     let mut session_save_deadline = Instant::now();
@@ -409,7 +974,7 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
     let root_str = app.tab().repo_root.clone();
     let root = std::path::Path::new(&root_str);
     // If FileWatcher::new fails, watch mode is silently disabled (app.watching stays false).
-    let mut _watcher: Option<FileWatcher> = if app.tab().is_remote() {
+    let mut _watcher: Option<FileWatcher> = if app.tab().is_remote() || app.tab().is_repoless() {
         None
     } else {
         match FileWatcher::new(root, 500, watch_tx.clone()) {
@@ -421,57 +986,142 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
         }
     };
 
+    // Screen-reader output: one plain line per focus change / notification
+    let mut announcer = match er_engine::app::accessibility::Announcer::open(
+        &app.config.accessibility.screen_reader,
+    ) {
+        Ok(announcer) => announcer,
+        Err(e) => {
+            app.notify_error(&format!("{:#}", e));
+            None
+        }
+    };
+
+    // `--profile`: rolling frame times for the overlay
+    let mut frame_stats = profile_start.map(|_| profile::FrameStats::default());
+
+    // Redraw only when something changed; poll less often while idle
+    let mut dirty = true;
+    let mut last_draw = Instant::now();
+    let mut last_size = None;
+    let mut poll = ACTIVE_POLL;
+    let mut last_ai_poll = Instant::now();
+    let mut ai_reload_deadline: Option<Instant> = None;
+    let mut last_watched_rescan = Instant::now();
+    app.start_update_check();
+    app.start_usage();
+
     loop {
         // Update terminal width for resize calculations
         if let Ok(size) = terminal.size() {
             app.last_terminal_width = size.width;
+            if last_size != Some(size) {
+                last_size = Some(size);
+                dirty = true;
+            }
         }
 
         // Draw
-        terminal.draw(|f| ui::draw(f, app, hl))?;
+        if dirty || last_draw.elapsed() >= REDRAW_HEARTBEAT {
+            let frame_start = Instant::now();
+            terminal.draw(|f| {
+                ui::draw(f, app, hl);
+                if let Some(ref stats) = frame_stats {
+                    ui::draw_frame_stats(f, stats);
+                }
+            })?;
+            if let Some(ref mut stats) = frame_stats {
+                let elapsed = frame_start.elapsed();
+                stats.push(elapsed);
+                profile::record("frame", elapsed);
+                if stats.frames() == 1 {
+                    if let Some(started) = profile_start {
+                        profile::record("startup (to first frame)", started.elapsed());
+                    }
+                }
+            }
+            if let Some(ref mut announcer) = announcer {
+                announcer.observe(app);
+            }
+            dirty = false;
+            last_draw = Instant::now();
+        }
 
         // Poll for events with a timeout (lets us process watch events too)
-        if event::poll(Duration::from_millis(50))? {
+        let mut changed = false;
+        if event::poll(poll)? {
+            // Any event (key, resize, focus, paste) is worth a redraw
+            changed = true;
             if let Event::Key(key) = event::read()? {
-                // Route keys: overlay takes priority, then search, then normal
-                if app.overlay.is_some() {
-                    handle_overlay_input(app, key)?;
-                } else {
-                    match &app.input_mode {
-                        InputMode::Search => handle_search_input(app, key),
-                        InputMode::Comment => handle_comment_input(app, key)?,
-                        InputMode::Confirm(_) => handle_confirm_input(app, key)?,
-                        InputMode::Filter => handle_filter_input(app, key),
-                        InputMode::Commit => handle_commit_input(app, key)?,
-                        InputMode::RemoteUrl => handle_remote_url_input(app, key)?,
-                        InputMode::Normal => {
-                            handle_normal_input(app, key, &watch_tx, &mut _watcher)?
-                        }
-                    }
+                if let Some(ref mut recording) = recording {
+                    recording.push(key);
                 }
+                handle_key(app, key, &watch_tx, &mut _watcher)?;
+                app.note_usage();
             }
 
-            // Mark session dirty after any key input
+            // Mark session dirty after any key input. While a comment is being
+            // typed the deadline isn't pushed back, so a long burst of typing
+            // still reaches disk within DRAFT_SAVE_DELAY
+            let typing = app.input_mode == InputMode::Comment;
+            let deadline = Instant::now()
+                + if typing {
+                    DRAFT_SAVE_DELAY
+                } else {
+                    SESSION_SAVE_DELAY
+                };
+            session_save_deadline = if typing && session_dirty {
+                session_save_deadline.min(deadline)
+            } else {
+                deadline
+            };
             session_dirty = true;
-            session_save_deadline = Instant::now() + Duration::from_secs(2);
         }
 
         // Check for file watch events (non-blocking) — debounced
         // Drain all pending events each tick to avoid accumulation under rapid changes.
-        while let Ok(WatchEvent::FilesChanged(paths)) = watch_rx.try_recv() {
+        while let Ok(event) = watch_rx.try_recv() {
+            let paths = match event {
+                WatchEvent::FilesChanged(paths) => paths,
+                WatchEvent::AiFilesChanged(_) => {
+                    ai_reload_deadline = Some(Instant::now() + AI_RELOAD_COALESCE);
+                    continue;
+                }
+            };
+            if debug_log::enabled(debug_log::Level::Debug) {
+                let shown: Vec<&str> = paths.iter().take(5).map(String::as_str).collect();
+                debug_log::debug(
+                    "watch",
+                    format!("{} path(s) changed: {}", paths.len(), shown.join(", ")),
+                );
+            }
             pending_file_count += paths.len();
             pending_refresh = true;
             refresh_deadline = Instant::now() + Duration::from_millis(200);
+            changed = true;
         }
 
         // Execute debounced refresh when deadline passes
         if pending_refresh && Instant::now() >= refresh_deadline {
             pending_refresh = false;
+            changed = true;
             let count = pending_file_count;
             pending_file_count = 0;
-            let _ = app.tab_mut().refresh_diff_quick();
+            let before = app.tab().added_lines_snapshot();
+            debug_log::debug("watch", format!("refresh after {} change(s)", count));
+            if let Err(e) = app.tab_mut().refresh_diff_quick() {
+                debug_log::error("watch", format!("refresh failed: {:#}", e));
+            }
+            let addressed = app.tab_mut().mark_addressed_since(&before);
             let unmark_count = std::mem::replace(&mut app.tab_mut().pending_unmark_count, 0);
-            if unmark_count > 0 {
+            if addressed > 0 {
+                app.notify(&format!(
+                    "{} finding{}/comment{} addressed — needs re-check",
+                    addressed,
+                    if addressed == 1 { "" } else { "s" },
+                    if addressed == 1 { "" } else { "s" },
+                ));
+            } else if unmark_count > 0 {
                 app.notify(&format!(
                     "{} reviewed file{} auto-unmarked (diff changed)",
                     unmark_count,
@@ -486,21 +1136,84 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
             }
         }
 
-        // Check for .er-* file changes (throttled: every 10 ticks ≈ 1s)
+        // .er-* file changes: from the watcher, coalesced, or polled once a
+        // second when the active tab's AI dirs can't be watched
         app.ai_poll_counter = app.ai_poll_counter.wrapping_add(1);
-        if app.ai_poll_counter.is_multiple_of(10) && app.tab_mut().check_ai_files_changed() {
-            app.notify("✓ AI data refreshed");
+        if last_ai_poll.elapsed() >= AI_POLL_INTERVAL {
+            last_ai_poll = Instant::now();
+            let dirs = app.tab().ai_watch_dirs();
+            let watched = !app.tab().is_remote()
+                && _watcher
+                    .as_mut()
+                    .is_some_and(|w| w.watch_ai_dirs(&dirs).is_ok());
+            if !watched {
+                ai_reload_deadline.get_or_insert(last_ai_poll);
+            }
+        }
+        if ai_reload_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            ai_reload_deadline = None;
+            if let Some(delta) = app.tab_mut().check_ai_files_delta() {
+                if let Some(message) = delta.describe() {
+                    app.notify(&format!("✓ {}", message));
+                }
+                app.send_new_high_findings_webhook(&delta.high_findings_added);
+                changed = true;
+            }
+        }
+
+        // Cursor positions pushed by editors via `er --focus`
+        #[cfg(unix)]
+        if let Some(ref listener) = focus_listener {
+            for req in listener.poll() {
+                app.apply_focus_request(listener.repo_root(), &req);
+                changed = true;
+            }
         }
 
         // Poll background commands for completion
-        app.check_commands();
+        changed |= app.check_commands();
+
+        // Background comment pulls (`[github] comment_sync_minutes`)
+        changed |= app.poll_comment_autosync();
+
+        // Authors of the file in the File Detail panel
+        changed |= app.poll_file_ownership();
+        changed |= app.poll_related_changes();
+        changed |= app.poll_code_age();
+        changed |= app.poll_commit_search();
+        changed |= app.poll_commit_prs();
+        changed |= app.poll_release_notes();
+        changed |= app.poll_duplicates();
+        changed |= app.poll_complexity();
+        changed |= app.poll_contracts();
+        changed |= app.poll_infrastructure();
+        changed |= app.poll_data_views();
+        changed |= app.poll_assets();
+        changed |= app.poll_compaction_learning();
+        changed |= app.poll_size_guard();
+        changed |= app.poll_config_reload();
+        changed |= app.poll_update_check();
+        changed |= app.poll_bug_report();
+        changed |= app.poll_viewed_pushes();
+        changed |= app.poll_comment_push();
+        changed |= app.poll_followups();
+        changed |= app.poll_hunk_explanation();
+        changed |= app.poll_pr_head_sync();
+        changed |= app.poll_pre_push();
+        changed |= app.poll_review_template();
+        changed |= app.poll_noise_suggestion();
+        changed |= app.poll_artifact_ignore();
+        changed |= app.poll_comment_moves();
+        changed |= app.poll_lock_contention();
 
         // Drain agent log entries from background threads
-        app.drain_agent_log();
+        changed |= app.drain_agent_log();
 
-        // Rescan watched files (every 50 ticks ≈ 5s)
-        if !app.tab().is_remote() && app.ai_poll_counter.is_multiple_of(50) {
+        // Rescan watched files (every 5s)
+        if !app.tab().is_remote() && last_watched_rescan.elapsed() >= WATCHED_RESCAN_INTERVAL {
+            last_watched_rescan = Instant::now();
             app.tab_mut().refresh_watched_files();
+            changed = true;
         }
 
         // Check for PR base hint from background thread (fires once)
@@ -508,6 +1221,7 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
             if let Ok(msg) = rx.try_recv() {
                 app.notify(&msg);
                 hint_rx = None;
+                changed = true;
             }
         }
 
@@ -516,6 +1230,7 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
             if let Ok(data) = rx.try_recv() {
                 app.tab_mut().pr_data = Some(data);
                 pr_data_rx = None;
+                changed = true;
             }
         }
 
@@ -542,6 +1257,7 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
         // Apply discovered models from background threads
         while let Ok((provider_id, result)) = discovery_rx.try_recv() {
             app.model_discovery_inflight.remove(&provider_id);
+            changed = true;
             match result {
                 Ok(models) => {
                     app.apply_discovered_models(&provider_id, &models);
@@ -567,16 +1283,27 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
         if !app.tab().is_remote() && session_dirty && Instant::now() >= session_save_deadline {
             session_dirty = false;
             app.tab().save_session();
+            app.save_view_prefs();
         }
 
         // Tick — used for auto-clearing notifications
-        app.tick();
+        changed |= app.tick();
+
+        // Toasts count down in ticks and debounced refreshes wait on a
+        // deadline, so both keep the loop at full speed
+        dirty |= changed;
+        poll = next_poll(
+            poll,
+            changed || pending_refresh || app.watch_message.is_some(),
+        );
 
         if app.should_quit {
             // Save session on quit
             if !app.tab().is_remote() {
                 app.tab().save_session();
+                app.save_view_prefs();
             }
+            app.finish_usage();
             return Ok(());
         }
     }
@@ -585,6 +1312,7 @@ fn run_app<B: Backend<Error: Send + Sync + 'static>>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{handle_confirm_input, handle_normal_input};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use er_engine::ai::PanelContent;
     use er_engine::app::{ConfirmAction, InputMode};
@@ -628,6 +1356,42 @@ mod tests {
         handle_normal_input(app, key, &tx, &mut watcher).unwrap();
     }
 
+    // ── Completions and man page ──
+
+    #[test]
+    fn completion_scripts_and_man_page_cover_the_cli() {
+        Cli::command().debug_assert();
+        for shell in completions::SHELLS {
+            let mut script = Vec::new();
+            completions::write_registration(shell, &mut script).unwrap();
+            assert!(String::from_utf8(script).unwrap().contains("COMPLETE"));
+        }
+        let mut man = Vec::new();
+        completions::write_man(Cli::command(), &mut man).unwrap();
+        let man = String::from_utf8(man).unwrap();
+        assert!(man.contains(".TH er 1") && man.contains("completions"));
+
+        let filters: Vec<_> = completions::filters()
+            .iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert!(filters.contains(&"review".to_string()));
+        assert_eq!(app::filter::preset_expr("review"), Some("-risk:info"));
+    }
+
+    // ── Idle poll backoff ──
+
+    #[test]
+    fn poll_backs_off_when_idle_and_snaps_back_on_activity() {
+        let mut poll = ACTIVE_POLL;
+        for _ in 0..10 {
+            poll = next_poll(poll, false);
+        }
+        assert_eq!(poll, IDLE_POLL_MAX);
+        assert_eq!(next_poll(poll, true), ACTIVE_POLL);
+        assert_eq!(next_poll(ACTIVE_POLL, false), ACTIVE_POLL * 2);
+    }
+
     // ── Ctrl+q vs bare q ──
 
     #[test]
@@ -642,6 +1406,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ctrl_q_with_paused_draft_asks_then_quits_on_second_press() {
+        let mut app = make_app(vec![]);
+        app.tab_mut().comment_textarea = tui_textarea::TextArea::new(vec!["wip".into()]);
+        send_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(!app.should_quit);
+        assert!(matches!(app.input_mode, InputMode::Confirm(_)));
+        let key = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        handle_confirm_input(&mut app, key).unwrap();
+        assert!(app.should_quit);
+    }
+
     #[test]
     fn bare_q_starts_comment_mode_when_file_selected() {
         let mut app = make_app(vec![make_file_with_hunk()]);
@@ -734,6 +1510,7 @@ mod tests {
                 hunk_header: String::new(),
                 anchor_status: "original".to_string(),
                 relocated_at_hash: String::new(),
+                anchor_confidence: None,
                 in_reply_to: None,
                 author: "You".to_string(),
                 promoted_to: None,
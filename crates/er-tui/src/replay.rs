@@ -0,0 +1,406 @@
+//! `er --record` / `er --replay`: key sequences saved as JSON and fed back
+//! through the same key routing as the live loop, against a real (fixture)
+//! repo and an in-memory terminal. A replay prints the final app state and
+//! any frames the script asked for, so keybinding flows can be tested end to
+//! end — and a recording attached to a bug report reproduces it.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "width": 120,
+//!   "height": 40,
+//!   "steps": [
+//!     { "key": "j" },
+//!     { "key": "ctrl+d" },
+//!     { "snapshot": "after-scroll" },
+//!     { "key": "space" },
+//!     { "wait_ms": 500 },
+//!     { "key": "ctrl+q" }
+//!   ]
+//! }
+//! ```
+//!
+//! Keys are written as the character (`j`, `J`, `?`) or a name (`enter`,
+//! `esc`, `tab`, `backtab`, `backspace`, `delete`, `space`, `up`, `down`,
+//! `left`, `right`, `home`, `end`, `pageup`, `pagedown`, `insert`, `f1`…),
+//! optionally prefixed with `ctrl+`, `alt+` and `shift+`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use er_engine::app::App;
+use er_engine::watch::WatchEvent;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use serde::{Deserialize, Serialize};
+
+use crate::input::handle_key;
+use crate::ui;
+
+/// Newest script format this build reads.
+pub const VERSION: u32 = 1;
+
+/// A recorded session: the terminal size and what happened, in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Script {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default = "default_width")]
+    pub width: u16,
+    #[serde(default = "default_height")]
+    pub height: u16,
+    pub steps: Vec<Step>,
+}
+
+fn default_version() -> u32 {
+    VERSION
+}
+
+fn default_width() -> u16 {
+    120
+}
+
+fn default_height() -> u16 {
+    40
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Step {
+    /// Press a key (see the module docs for the notation)
+    Key { key: String },
+    /// Keep the frame drawn at this point under `name` in the output
+    Snapshot { snapshot: String },
+    /// Let background commands run for up to this long
+    Wait { wait_ms: u64 },
+}
+
+/// What a replay prints: where the app ended up.
+#[derive(Debug, Serialize)]
+pub struct FinalState {
+    /// Steps run before the script ended or the app quit
+    pub steps_run: usize,
+    pub quit: bool,
+    pub mode: String,
+    pub input_mode: String,
+    /// The open overlay's variant, if any
+    pub overlay: Option<String>,
+    pub file: Option<String>,
+    pub hunk: usize,
+    pub line: Option<usize>,
+    pub reviewed: Vec<String>,
+    pub filter: String,
+    /// The status-bar message showing at the end
+    pub message: Option<String>,
+    /// The last frame
+    pub screen: String,
+    /// Frames kept by `snapshot` steps
+    pub snapshots: BTreeMap<String, String>,
+}
+
+/// Keys seen during a live session, for `--record`.
+#[derive(Debug)]
+pub struct Recording {
+    width: u16,
+    height: u16,
+    steps: Vec<Step>,
+}
+
+impl Recording {
+    pub fn new(width: u16, height: u16) -> Self {
+        Recording {
+            width,
+            height,
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Press {
+            self.steps.push(Step::Key {
+                key: format_key(&key),
+            });
+        }
+    }
+
+    pub fn into_script(self) -> Script {
+        Script {
+            version: VERSION,
+            width: self.width,
+            height: self.height,
+            steps: self.steps,
+        }
+    }
+
+    pub fn save(self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.into_script())?;
+        std::fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Read a script, rejecting unknown keys and newer formats up front.
+pub fn load(path: &Path) -> Result<Script> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let script: Script = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    if script.version > VERSION {
+        anyhow::bail!(
+            "{} is version {} (this er replays up to {}) — update er",
+            path.display(),
+            script.version,
+            VERSION
+        );
+    }
+    for (i, step) in script.steps.iter().enumerate() {
+        if let Step::Key { key } = step {
+            parse_key(key).with_context(|| format!("step {}", i + 1))?;
+        }
+    }
+    Ok(script)
+}
+
+/// Run `script` against `app` on an in-memory terminal. Stops early when a
+/// key quits the app.
+pub fn run(app: &mut App, script: &Script) -> Result<FinalState> {
+    let mut terminal = Terminal::new(TestBackend::new(script.width, script.height))?;
+    let mut hl = ui::highlight::Highlighter::new();
+    app.last_terminal_width = script.width;
+    // No watcher: a replay only changes what its keys change
+    let (watch_tx, _watch_rx) = mpsc::channel::<WatchEvent>();
+    let mut watcher = None;
+    let mut snapshots = BTreeMap::new();
+    let mut steps_run = 0;
+
+    for step in &script.steps {
+        if app.should_quit {
+            break;
+        }
+        steps_run += 1;
+        match step {
+            Step::Key { key } => {
+                handle_key(app, parse_key(key)?, &watch_tx, &mut watcher)?;
+                app.check_commands();
+            }
+            Step::Snapshot { snapshot } => {
+                let frame = draw(&mut terminal, app, &mut hl)?;
+                snapshots.insert(snapshot.clone(), frame);
+            }
+            Step::Wait { wait_ms } => {
+                let deadline = Instant::now() + Duration::from_millis(*wait_ms);
+                while Instant::now() < deadline {
+                    app.check_commands();
+                    if app.tabs.iter().all(|tab| tab.command_rx.is_empty()) {
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+            }
+        }
+    }
+
+    let screen = draw(&mut terminal, app, &mut hl)?;
+    let tab = app.tab();
+    let mut reviewed: Vec<String> = tab.reviewed.keys().cloned().collect();
+    reviewed.sort();
+    Ok(FinalState {
+        steps_run,
+        quit: app.should_quit,
+        mode: format!("{:?}", tab.mode),
+        input_mode: format!("{:?}", app.input_mode),
+        overlay: app.overlay.as_ref().map(|overlay| {
+            let debug = format!("{:?}", overlay);
+            debug
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        }),
+        file: tab.selected_diff_file().map(|f| f.path.clone()),
+        hunk: tab.current_hunk,
+        line: tab.current_line,
+        reviewed,
+        filter: tab.filter_expr.clone(),
+        message: app.watch_message.clone(),
+        screen,
+        snapshots,
+    })
+}
+
+fn draw(
+    terminal: &mut Terminal<TestBackend>,
+    app: &App,
+    hl: &mut ui::highlight::Highlighter,
+) -> Result<String> {
+    terminal.draw(|f| ui::draw(f, app, hl))?;
+    Ok(ui::screen_text(terminal.backend().buffer()))
+}
+
+/// `ctrl+d`, `J`, `enter` … → the key event a terminal would send.
+pub fn parse_key(notation: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = notation;
+    loop {
+        if let Some(r) = rest.strip_prefix("ctrl+") {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("alt+") {
+            modifiers |= KeyModifiers::ALT;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("shift+") {
+            modifiers |= KeyModifiers::SHIFT;
+            rest = r;
+        } else {
+            break;
+        }
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            if c.is_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            KeyCode::Char(c)
+        }
+        _ => match rest {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => match rest.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n) if (1..=24).contains(&n) => KeyCode::F(n),
+                _ => anyhow::bail!("unknown key `{}`", notation),
+            },
+        },
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// The notation [`parse_key`] reads back as `key`.
+pub fn format_key(key: &KeyEvent) -> String {
+    let mut out = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        out.push_str("ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        out.push_str("alt+");
+    }
+    let name = match key.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => {
+            // An uppercase letter already says shift
+            if key.modifiers.contains(KeyModifiers::SHIFT) && !c.is_uppercase() {
+                out.push_str("shift+");
+            }
+            c.to_string()
+        }
+        KeyCode::F(n) => format!("f{}", n),
+        code => {
+            if key.modifiers.contains(KeyModifiers::SHIFT) {
+                out.push_str("shift+");
+            }
+            match code {
+                KeyCode::Enter => "enter",
+                KeyCode::Esc => "esc",
+                KeyCode::Tab => "tab",
+                KeyCode::BackTab => "backtab",
+                KeyCode::Backspace => "backspace",
+                KeyCode::Delete => "delete",
+                KeyCode::Insert => "insert",
+                KeyCode::Up => "up",
+                KeyCode::Down => "down",
+                KeyCode::Left => "left",
+                KeyCode::Right => "right",
+                KeyCode::Home => "home",
+                KeyCode::End => "end",
+                KeyCode::PageUp => "pageup",
+                KeyCode::PageDown => "pagedown",
+                _ => "unknown",
+            }
+            .to_string()
+        }
+    };
+    out.push_str(&name);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use er_engine::app::fixtures;
+
+    #[test]
+    fn key_notation_round_trips() {
+        for notation in [
+            "j",
+            "J",
+            "?",
+            "+",
+            "space",
+            "enter",
+            "ctrl+d",
+            "alt+x",
+            "shift+tab",
+            "backtab",
+            "f5",
+            "ctrl+alt+up",
+        ] {
+            let key = parse_key(notation).unwrap();
+            assert_eq!(format_key(&key), notation);
+        }
+        assert_eq!(
+            parse_key("G").unwrap().modifiers,
+            KeyModifiers::SHIFT,
+            "uppercase letters arrive with shift, like from a terminal"
+        );
+        assert!(parse_key("hyper+q").is_err());
+        assert!(parse_key("f99").is_err());
+    }
+
+    #[test]
+    fn replay_drives_the_app_and_keeps_snapshots() {
+        let mut app = fixtures::app(fixtures::small_diff());
+        let script: Script = serde_json::from_str(
+            r#"{"width": 80, "height": 20, "steps": [
+                {"key": "j"}, {"snapshot": "second"}, {"key": "ctrl+q"}, {"key": "j"}
+            ]}"#,
+        )
+        .unwrap();
+        let state = run(&mut app, &script).unwrap();
+        assert_eq!(state.steps_run, 3, "keys after quitting are not run");
+        assert!(state.quit);
+        assert_ne!(state.file.as_deref(), Some("src/lib.rs"), "j moved on");
+        assert!(state.snapshots["second"].contains("FILES (3)"));
+
+        let mut recording = Recording::new(80, 20);
+        recording.push(parse_key("j").unwrap());
+        recording.push(KeyEvent {
+            kind: KeyEventKind::Release,
+            ..parse_key("k").unwrap()
+        });
+        let script = recording.into_script();
+        assert_eq!(
+            script.steps,
+            vec![Step::Key {
+                key: "j".to_string()
+            }]
+        );
+    }
+}
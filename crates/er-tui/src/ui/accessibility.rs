@@ -0,0 +1,104 @@
+//! Post-render passes for `[accessibility]`: ASCII-only glyphs and no-color.
+//!
+//! Both run over the finished frame buffer instead of threading flags through
+//! every renderer, so new widgets get them for free.
+
+use super::themes::Theme;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+/// ASCII stand-in for a UI glyph; `None` leaves the cell alone.
+fn ascii_for(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let c = chars.next()?;
+    if c.is_ascii() || chars.next().is_some() {
+        return None;
+    }
+    Some(match c {
+        '─' | '━' | '═' | '—' | '–' | '┄' | '┈' => "-",
+        '│' | '┃' | '║' | '┆' | '┊' | '▏' | '▕' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' | '╔' | '╗'
+        | '╚' | '╝' => "+",
+        '▶' | '▸' | '►' | '→' | '⇒' | '↳' | '»' => ">",
+        '◀' | '◂' | '←' | '«' => "<",
+        '▲' | '↑' => "^",
+        '▼' | '↓' => "v",
+        '↔' => "~",
+        '✓' | '✔' => "v",
+        '✗' | '✘' | '×' => "x",
+        '⚠' | '❗' => "!",
+        '❓' => "?",
+        '●' | '◉' | '•' | '◆' | '▪' | '★' | '◐' | '⊕' => "*",
+        '○' | '◯' | '☆' | '⊘' => "o",
+        '·' | '…' => ".",
+        '≈' => "~",
+        '≥' => ">",
+        '␣' => "_",
+        '↻' | '⟳' => "@",
+        '█' | '▌' | '▐' | '░' | '▒' | '▓' => "#",
+        '💬' | '📝' => "\"",
+        '🎉' | '🎊' | '🎈' => "*",
+        _ => return None,
+    })
+}
+
+pub fn ascii_glyphs(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        if let Some(ascii) = ascii_for(cell.symbol()) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+/// Strip every color. Cursor/selection rows keep their emphasis as reverse
+/// video and dim text stays dim, so focus is still visible.
+pub fn strip_colors(buf: &mut Buffer, theme: &Theme) {
+    let highlighted = [
+        theme.line_cursor_bg,
+        theme.selected_bg,
+        theme.comment_focus_bg,
+        theme.finding_focus_bg,
+    ];
+    let dim = [theme.text_dim, theme.text_muted];
+    for cell in buf.content.iter_mut() {
+        if highlighted.contains(&cell.bg) {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        if dim.contains(&cell.fg) {
+            cell.modifier.insert(Modifier::DIM);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.underline_color = Color::Reset;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::themes;
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn ascii_replaces_known_glyphs_only() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buf.set_string(0, 0, "─▶✓é", Style::default());
+        ascii_glyphs(&mut buf);
+        let row: String = buf.content.iter().map(|c| c.symbol()).collect();
+        assert_eq!(row, "->vé  ");
+    }
+
+    #[test]
+    fn no_color_keeps_cursor_visible() {
+        let theme = themes::graphite();
+        let cursor = theme.line_cursor_bg;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf.set_string(0, 0, "ab", Style::default().bg(cursor).fg(Color::Red));
+        strip_colors(&mut buf, &theme);
+        let cell = &buf.content[0];
+        assert_eq!(cell.bg, Color::Reset);
+        assert_eq!(cell.fg, Color::Reset);
+        assert!(cell.modifier.contains(Modifier::REVERSED));
+    }
+}
@@ -7,16 +7,20 @@ use ratatui::{
 
 use super::highlight::Highlighter;
 use super::styles;
-use super::utils::word_wrap;
+use super::utils::{display_width, soft_wrap, truncate_to_width, word_wrap};
 use er_engine::ai::{CommentRef, CommentType, Finding, RiskLevel};
-use er_engine::app::{App, DiffMode, SplitSide, TabState};
+use er_engine::app::{App, CompactionReason, DiffMode, SplitSide, TabState};
 use er_engine::config::ErConfig;
 use er_engine::git::{DiffHunk, DiffLine, LineType};
+use std::ops::Range;
 
 /// Expand tab characters to spaces based on configured tab width.
-/// Uses column-aware expansion (tabs align to tab stops, not fixed width).
+/// Uses column-aware expansion (tabs align to tab stops, not fixed width),
+/// counting wide characters as two columns. Bidi overrides/isolates and
+/// other control characters are shown as `�` so they can neither reorder
+/// the line on screen (Trojan Source) nor reach the terminal.
 fn expand_tabs(line: &str, tab_width: u8) -> String {
-    if !line.contains('\t') {
+    if !line.contains('\t') && !line.chars().any(is_unsafe_char) {
         return line.to_string();
     }
     let tw = (tab_width as usize).max(1);
@@ -29,25 +33,38 @@ fn expand_tabs(line: &str, tab_width: u8) -> String {
                 result.push(' ');
             }
             col += spaces;
+        } else if is_unsafe_char(ch) {
+            result.push('\u{FFFD}');
+            col += 1;
         } else {
             result.push(ch);
-            col += 1;
+            col += unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
         }
     }
     result
 }
 
+/// Characters that must not be written to the terminal verbatim.
+fn is_unsafe_char(ch: char) -> bool {
+    (ch.is_control() && ch != '\t')
+        || matches!(ch, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{200E}' | '\u{200F}')
+}
+
 /// Threshold (total diff lines) above which viewport-based rendering is used
 const VIRTUALIZE_THRESHOLD: usize = 200;
 
 /// Threshold above which a "large file" warning is shown in the title
 const LARGE_FILE_WARNING_LINES: usize = 2000;
 
-/// Blank unified gutter matching `format!("{} {} \u{2502}", "    ", "    ")`
-const BLANK_UNIFIED_GUTTER: &str = "          \u{2502}";
+/// Blank line numbers matching `format!("{} {}", "    ", "    ")` in the unified gutter
+const BLANK_UNIFIED_NUMS: &str = "         ";
 
-/// Blank split gutter matching `format!("{} \u{2502}", "    ")`
-const BLANK_SPLIT_GUTTER: &str = "     \u{2502}";
+/// Blank line number matching `format!("{}", "    ")` in the split gutter
+const BLANK_SPLIT_NUM: &str = "    ";
+
+/// Finding marker cells between the line numbers and the separator
+const UNIFIED_MARKER_CELLS: usize = 2;
+const SPLIT_MARKER_CELLS: usize = 1;
 
 /// A paired row in split diff view. Both panes advance `logical_line` by the same
 /// `row_height` so they stay vertically aligned regardless of wrap asymmetry.
@@ -121,6 +138,18 @@ fn comment_layer_visible(tab: &TabState, comment: &CommentRef<'_>) -> bool {
     visible && !(tab.layers.hide_resolved && comment.is_resolved())
 }
 
+/// Gutter of an added line, in the code age layer's tint when it has one.
+fn add_gutter_style(tab: &TabState, path: &str, line: &DiffLine) -> ratatui::style::Style {
+    match line.new_num.and_then(|n| tab.code_age(path, n)) {
+        Some(age) => ratatui::style::Style::default()
+            .fg(styles::BG())
+            .bg(styles::code_age_color(age)),
+        None => ratatui::style::Style::default()
+            .fg(styles::DIM())
+            .bg(styles::ADD_BG()),
+    }
+}
+
 fn hunk_new_line_count(hunk: &DiffHunk) -> usize {
     hunk.lines
         .iter()
@@ -233,7 +262,7 @@ fn hunk_findings_for_mode<'a>(
 }
 
 /// Number of terminal rows this cell will occupy given wrapping settings.
-/// Uses the same pipeline as rendering: `expand_tabs` then `word_wrap`.
+/// Uses the same pipeline as rendering: `expand_tabs` then `soft_wrap`.
 fn cell_wrap_height(
     cell: Option<&SplitCell<'_>>,
     wrap: bool,
@@ -248,7 +277,7 @@ fn cell_wrap_height(
             }
             if wrap && !c.line.content.is_empty() {
                 let expanded = expand_tabs(&c.line.content, tab_width);
-                word_wrap(&expanded, wrap_width.max(1)).len().max(1)
+                soft_wrap(&expanded, wrap_width.max(1)).len().max(1)
             } else {
                 1
             }
@@ -259,6 +288,111 @@ fn cell_wrap_height(
 /// Pad `lines` with empty BG-styled rows so the Paragraph fills the entire visible area.
 /// Without this, Ratatui's double-buffer reuses the previous frame's cell content for rows
 /// below the Paragraph's last text line, causing stale content to bleed through.
+/// The `@@` row of a hunk, with the ▶ marker on the current one and the
+/// size of a folded one.
+fn hunk_header_line(hunk: &DiffHunk, is_current: bool, folded: bool) -> Line<'_> {
+    let marker = if is_current { "\u{25b6}" } else { " " };
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", marker),
+            if is_current {
+                ratatui::style::Style::default()
+                    .fg(styles::CYAN())
+                    .bg(styles::HUNK_BG())
+            } else {
+                ratatui::style::Style::default()
+                    .fg(styles::DIM())
+                    .bg(styles::HUNK_BG())
+            },
+        ),
+        Span::styled(&hunk.header, styles::hunk_header_style()),
+    ];
+    if folded {
+        spans.push(Span::styled(
+            format!(
+                "  \u{25b8} {} lines folded (Enter to unfold)",
+                hunk.lines.len()
+            ),
+            ratatui::style::Style::default()
+                .fg(styles::MUTED())
+                .bg(styles::HUNK_BG()),
+        ));
+    }
+    Line::from(spans).style(styles::hunk_header_style())
+}
+
+/// The row after a hunk: how many unchanged lines sit before the next one,
+/// or blank.
+fn hunk_gap_line(file: &er_engine::git::DiffFile, hunk_idx: usize) -> Line<'static> {
+    let hunk = &file.hunks[hunk_idx];
+    let gap = match file.hunks.get(hunk_idx + 1) {
+        Some(next) => next
+            .old_start
+            .saturating_sub(hunk.old_start + hunk.old_count),
+        None => 0,
+    };
+    if gap > 0 {
+        Line::from(Span::styled(
+            format!("  ··· {} lines hidden (+/- to expand) ···", gap),
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        ))
+    } else {
+        Line::from("")
+    }
+}
+
+/// The hunk to pin under the sticky file row when the diff is scrolled to
+/// `scroll`: the one being read on the second row, once its own header has
+/// scrolled under the sticky rows. `hunk_rows` holds each hunk's header row
+/// and index, in order.
+fn sticky_hunk(hunk_rows: &[(usize, usize)], scroll: usize) -> Option<usize> {
+    let below_file_row = scroll + 1;
+    let &(row, idx) = hunk_rows
+        .iter()
+        .rev()
+        .find(|(row, _)| *row <= below_file_row)?;
+    (row < below_file_row).then_some(idx)
+}
+
+/// Restyle the parts of `spans` in `ranges` (byte offsets into their
+/// joined text, in order) as find matches.
+pub(super) fn mark_find_matches(
+    spans: Vec<Span<'static>>,
+    ranges: &[Range<usize>],
+) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+    let mut out = Vec::with_capacity(spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in spans {
+        let text = span.content.as_ref();
+        let end = offset + text.len();
+        let mut cut = 0;
+        for r in ranges.iter().filter(|r| r.start < end && r.end > offset) {
+            let (from, to) = (r.start.max(offset) - offset, r.end.min(end) - offset);
+            let (Some(before), Some(hit)) = (text.get(cut..from), text.get(from..to)) else {
+                continue;
+            };
+            if !before.is_empty() {
+                out.push(Span::styled(before.to_string(), span.style));
+            }
+            out.push(Span::styled(
+                hit.to_string(),
+                span.style.patch(styles::find_match()),
+            ));
+            cut = to;
+        }
+        if cut == 0 {
+            out.push(span);
+        } else if cut < text.len() {
+            out.push(Span::styled(text[cut..].to_string(), span.style));
+        }
+        offset = end;
+    }
+    out
+}
+
 fn pad_lines_to_fill(lines: &mut Vec<Line<'_>>, scroll_y: u16, visible_height: u16) {
     let needed = scroll_y as usize + visible_height as usize;
     while lines.len() < needed {
@@ -290,6 +424,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
 
     let file = match tab.selected_diff_file() {
         Some(f) => f,
+        None if tab.is_repoless() => {
+            render_no_repo(f, area);
+            return;
+        }
         None => {
             render_empty(f, area);
             return;
@@ -298,7 +436,18 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
 
     // Handle compacted files — show summary instead of full diff
     if file.compacted {
-        render_compacted(f, area, file);
+        render_compacted(f, area, file, tab.compaction_reason(&file.path));
+        return;
+    }
+
+    // LFS pointer diffs — the object's size and oid, not the pointer text
+    if let Some(change) = er_engine::git::lfs_change(file) {
+        render_lfs_change(f, area, file, &change);
+        return;
+    }
+
+    if let Some(view) = tab.data_view(&file.path) {
+        render_data_view(f, area, app, file, view);
         return;
     }
 
@@ -311,11 +460,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
     let total_diff_lines: usize = file.hunks.iter().map(|h| h.lines.len()).sum();
     let use_viewport = total_diff_lines > VIRTUALIZE_THRESHOLD;
 
-    let context_suffix = match tab.context_overrides.get(&file.path).copied() {
+    let mut context_suffix = match tab.context_overrides.get(&file.path).copied() {
         Some(99999) => " [full context]".to_string(),
         Some(n) if n != 3 => format!(" [context: {}]", n),
         _ => String::new(),
     };
+    if let Some(encoding) = tab.file_encodings.get(&file.path) {
+        context_suffix.push_str(&format!(" [{}]", encoding));
+    }
     let title = if total_diff_lines > LARGE_FILE_WARNING_LINES {
         format!(
             " {} \u{26a0} +{} lines{} ",
@@ -438,7 +590,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             }
             let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
             let pre_len = lines.len();
-            render_comment_lines(&mut lines, comment, area.width, false, is_focused);
+            render_comment_lines(
+                &mut lines,
+                comment,
+                area.width,
+                false,
+                is_focused,
+                tab.is_addressed(comment.id()),
+            );
             let comment_line_count = lines.len() - pre_len;
             if logical_line < render_start || logical_line >= render_end {
                 lines.truncate(pre_len);
@@ -460,16 +619,19 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
         }
     }
 
-    // Unified gutter: "{old_num} {new_num} │" = 4+1+4+1+1=11 chars, plus prefix char = 12 total
-    let unified_gutter_width: u16 = 12;
+    // Unified gutter: "{old_num} {new_num}{markers}│" = 4+1+4+2+1=12 chars, plus prefix char = 13 total
+    let unified_gutter_width: u16 = 13;
     let wrap_lines = app.config.display.wrap_lines;
+    let find = tab.active_find();
+    let find_ranges = |text: &str| find.map(|f| f.ranges(text)).unwrap_or_default();
     // Content width for wrapping: area width minus right padding (1) minus gutter
     let unified_wrap_width = (area
         .width
         .saturating_sub(1)
         .saturating_sub(unified_gutter_width)) as usize;
 
-    // Render hunks
+    // Render hunks, noting the row of each header for the sticky one
+    let mut hunk_rows: Vec<(usize, usize)> = Vec::with_capacity(total_hunks);
     for (hunk_idx, hunk) in file.hunks.iter().enumerate() {
         let is_current = hunk_idx == tab.active_current_hunk();
         let hunk_anchors = comments_for_hunk_resolved(tab, &file.path, hunk_idx, hunk);
@@ -480,29 +642,22 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
         }
 
         // Hunk header
+        hunk_rows.push((logical_line, hunk_idx));
+        let folded = tab.is_hunk_folded(&file.path, hunk);
         if logical_line >= render_start && logical_line < render_end {
-            let marker = if is_current { "\u{25b6}" } else { " " };
-            lines.push(
-                Line::from(vec![
-                    Span::styled(
-                        format!(" {} ", marker),
-                        if is_current {
-                            ratatui::style::Style::default()
-                                .fg(styles::CYAN())
-                                .bg(styles::HUNK_BG())
-                        } else {
-                            ratatui::style::Style::default()
-                                .fg(styles::DIM())
-                                .bg(styles::HUNK_BG())
-                        },
-                    ),
-                    Span::styled(&hunk.header, styles::hunk_header_style()),
-                ])
-                .style(styles::hunk_header_style()),
-            );
+            lines.push(hunk_header_line(hunk, is_current, folded));
         }
         logical_line += 1;
 
+        // A folded hunk is just its header
+        if folded {
+            if logical_line >= render_start && logical_line < render_end {
+                lines.push(hunk_gap_line(file, hunk_idx));
+            }
+            logical_line += 1;
+            continue;
+        }
+
         // ── Hunk-level comments right after the @@ header ──
         {
             for comment in hunk_level_comments(&hunk_anchors) {
@@ -511,7 +666,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                 }
                 let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                 let pre_len = lines.len();
-                render_comment_lines(&mut lines, comment, area.width, false, is_focused);
+                render_comment_lines(
+                    &mut lines,
+                    comment,
+                    area.width,
+                    false,
+                    is_focused,
+                    tab.is_addressed(comment.id()),
+                );
                 let comment_line_count = lines.len() - pre_len;
                 if logical_line < render_start || logical_line >= render_end {
                     lines.truncate(pre_len);
@@ -577,11 +739,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                 ratatui::style::Style::default()
                     .fg(styles::BRIGHT())
                     .bg(styles::LINE_CURSOR_BG())
+            } else if diff_line
+                .new_num
+                .is_some_and(|n| tab.is_line_flashed(&file.path, n))
+            {
+                styles::line_flash()
             } else {
                 match diff_line.line_type {
-                    LineType::Add => ratatui::style::Style::default()
-                        .fg(styles::DIM())
-                        .bg(styles::ADD_BG()),
+                    LineType::Add => add_gutter_style(tab, &file.path, diff_line),
                     LineType::Delete => ratatui::style::Style::default()
                         .fg(styles::DIM())
                         .bg(styles::DEL_BG()),
@@ -590,27 +755,37 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                 }
             };
 
+            let markers = match diff_line.new_num {
+                Some(n) if in_overlay => tab.ai.line_markers(&file.path, n),
+                _ => Vec::new(),
+            };
+            let gutter = |nums: String| {
+                let mut spans = vec![Span::styled(nums, gutter_style)];
+                spans.extend(finding_marker_spans(
+                    &markers,
+                    UNIFIED_MARKER_CELLS,
+                    gutter_style,
+                ));
+                spans.push(Span::styled("\u{2502}", gutter_style));
+                spans
+            };
+
             if wrap_lines && !diff_line.content.is_empty() {
                 // Wrap the content and emit multiple logical lines.
                 // Segments are owned Strings; highlight them and convert to Span<'static>
                 // so they can safely outlive the local `segments` Vec.
                 let content = expand_tabs(&diff_line.content, app.config.display.tab_width);
-                let segments = word_wrap(&content, unified_wrap_width.max(1));
+                let segments = soft_wrap(&content, unified_wrap_width.max(1));
                 for (seg_idx, segment) in segments.iter().enumerate() {
                     if logical_line >= render_start && logical_line < render_end {
                         let mut spans: Vec<Span<'static>> = if seg_idx == 0 {
-                            vec![
-                                Span::styled(
-                                    format!("{} {} \u{2502}", old_num, new_num),
-                                    gutter_style,
-                                ),
-                                Span::styled(prefix, base_style),
-                            ]
+                            let mut spans = gutter(format!("{} {}", old_num, new_num));
+                            spans.push(Span::styled(prefix, base_style));
+                            spans
                         } else {
-                            vec![
-                                Span::styled(BLANK_UNIFIED_GUTTER, gutter_style),
-                                Span::styled(" ", base_style),
-                            ]
+                            let mut spans = gutter(BLANK_UNIFIED_NUMS.to_string());
+                            spans.push(Span::styled(" ", base_style));
+                            spans
                         };
                         // highlight_line borrows `segment`, so we eagerly clone span text to 'static
                         let highlighted: Vec<Span<'static>> = hl
@@ -618,7 +793,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                             .into_iter()
                             .map(|s| Span::styled(s.content.into_owned(), s.style))
                             .collect();
-                        spans.extend(highlighted);
+                        spans.extend(mark_find_matches(highlighted, &find_ranges(segment)));
                         spans.push(Span::styled(" ".repeat(area.width as usize), base_style));
                         lines.push(Line::from(spans).style(base_style));
                     }
@@ -627,10 +802,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             } else {
                 if logical_line >= render_start && logical_line < render_end {
                     // Build the line: gutter + prefix + syntax-highlighted content
-                    let mut spans = vec![
-                        Span::styled(format!("{} {} \u{2502}", old_num, new_num), gutter_style),
-                        Span::styled(prefix, base_style),
-                    ];
+                    let mut spans = gutter(format!("{} {}", old_num, new_num));
+                    spans.push(Span::styled(prefix, base_style));
 
                     // Syntax highlight the code content
                     if diff_line.content.is_empty() {
@@ -642,7 +815,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                             .into_iter()
                             .map(|s| Span::styled(s.content.into_owned(), s.style))
                             .collect();
-                        spans.extend(highlighted);
+                        spans.extend(mark_find_matches(highlighted, &find_ranges(&content)));
                     }
 
                     spans.push(Span::styled(" ".repeat(area.width as usize), base_style));
@@ -662,7 +835,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                     }
                     let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                     let pre_len = lines.len();
-                    render_comment_lines(&mut lines, comment, area.width, true, is_focused);
+                    render_comment_lines(
+                        &mut lines,
+                        comment,
+                        area.width,
+                        true,
+                        is_focused,
+                        tab.is_addressed(comment.id()),
+                    );
                     let comment_line_count = lines.len() - pre_len;
                     if logical_line < render_start || logical_line >= render_end {
                         lines.truncate(pre_len);
@@ -699,7 +879,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
                         let is_focused = tab.focused_finding_id.as_deref() == Some(&finding.id);
                         let pre_len = lines.len();
                         render_finding_banner(
-                            &mut lines, finding, area.width, file_stale, is_focused,
+                            &mut lines,
+                            finding,
+                            area.width,
+                            file_stale,
+                            is_focused,
+                            tab.is_addressed(&finding.id),
+                            tab.expanded_findings.contains(&finding.id),
+                            tab.ai.is_suppressed(&file.path, finding, &file.hunks),
                         );
                         let finding_line_count = lines.len() - pre_len;
                         if logical_line < render_start || logical_line >= render_end {
@@ -745,7 +932,16 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             for finding in &findings {
                 let is_focused = tab.focused_finding_id.as_deref() == Some(&finding.id);
                 let pre_len = lines.len();
-                render_finding_banner(&mut lines, finding, area.width, file_stale, is_focused);
+                render_finding_banner(
+                    &mut lines,
+                    finding,
+                    area.width,
+                    file_stale,
+                    is_focused,
+                    tab.is_addressed(&finding.id),
+                    tab.expanded_findings.contains(&finding.id),
+                    tab.ai.is_suppressed(&file.path, finding, &file.hunks),
+                );
                 let finding_line_count = lines.len() - pre_len;
                 if logical_line < render_start || logical_line >= render_end {
                     lines.truncate(pre_len);
@@ -775,21 +971,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
 
         // Gap indicator or blank line between hunks
         if logical_line >= render_start && logical_line < render_end {
-            let gap = if hunk_idx + 1 < file.hunks.len() {
-                let next = &file.hunks[hunk_idx + 1];
-                next.old_start
-                    .saturating_sub(hunk.old_start + hunk.old_count)
-            } else {
-                0
-            };
-            if gap > 0 {
-                lines.push(Line::from(Span::styled(
-                    format!("  ··· {} lines hidden (+/- to expand) ···", gap),
-                    ratatui::style::Style::default().fg(styles::MUTED()),
-                )));
-            } else {
-                lines.push(Line::from(""));
-            }
+            lines.push(hunk_gap_line(file, hunk_idx));
         }
         logical_line += 1;
     }
@@ -851,7 +1033,14 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             for comment in &orphaned {
                 let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                 let pre_len = lines.len();
-                render_comment_lines(&mut lines, comment, area.width, false, is_focused);
+                render_comment_lines(
+                    &mut lines,
+                    comment,
+                    area.width,
+                    false,
+                    is_focused,
+                    tab.is_addressed(comment.id()),
+                );
                 let comment_line_count = lines.len() - pre_len;
                 if logical_line < render_start || logical_line >= render_end {
                     lines.truncate(pre_len);
@@ -931,7 +1120,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             ),
         ];
         // Pad to fill the full width so the background covers the row
-        let sticky_len: usize = sticky_spans.iter().map(|s| s.content.chars().count()).sum();
+        let sticky_len: usize = sticky_spans.iter().map(|s| display_width(&s.content)).sum();
         let sticky_remaining = (area.width as usize).saturating_sub(sticky_len);
         sticky_spans.push(Span::styled(
             " ".repeat(sticky_remaining),
@@ -944,6 +1133,25 @@ pub fn render(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter) {
             height: 1,
         };
         f.render_widget(Paragraph::new(Line::from(sticky_spans)), sticky_area);
+
+        // And the header of the hunk being read, under it
+        if let Some(idx) = sticky_hunk(&hunk_rows, scroll).filter(|_| area.height > 2) {
+            let is_current = idx == tab.active_current_hunk();
+            let row = Rect {
+                y: area.y + 1,
+                ..sticky_area
+            };
+            f.render_widget(Clear, row);
+            f.render_widget(
+                Paragraph::new(hunk_header_line(
+                    &file.hunks[idx],
+                    is_current,
+                    tab.is_hunk_folded(&file.path, &file.hunks[idx]),
+                ))
+                .style(styles::hunk_header_style()),
+                row,
+            );
+        }
     }
 
     // Render hunk indicator overlay in top-right corner
@@ -988,7 +1196,10 @@ pub fn render_split(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
             return;
         }
     };
-    if file.compacted {
+    if file.compacted
+        || er_engine::git::lfs_change(file).is_some()
+        || tab.data_view(&file.path).is_some()
+    {
         render(f, area, app, hl);
         return;
     }
@@ -1116,7 +1327,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
             if side == SplitSide::New {
                 let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                 let pre_len = lines.len();
-                render_comment_lines(&mut lines, comment, inner.width, false, is_focused);
+                render_comment_lines(
+                    &mut lines,
+                    comment,
+                    inner.width,
+                    false,
+                    is_focused,
+                    tab.is_addressed(comment.id()),
+                );
                 let n = lines.len() - pre_len;
                 if logical_line < render_start || logical_line >= render_end {
                     lines.truncate(pre_len);
@@ -1124,7 +1342,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                 logical_line += n;
             } else {
                 let mut tmp: Vec<Line> = Vec::new();
-                render_comment_lines(&mut tmp, comment, inner.width, false, false);
+                render_comment_lines(
+                    &mut tmp,
+                    comment,
+                    inner.width,
+                    false,
+                    false,
+                    tab.is_addressed(comment.id()),
+                );
                 let n = tmp.len();
                 for k in 0..n {
                     if logical_line + k >= render_start && logical_line + k < render_end {
@@ -1168,10 +1393,13 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
     // Split gutter: "{num} │" = 4+1+1=6 chars, plus prefix char = 7 total
     let split_gutter_width: u16 = 7;
     let wrap_lines = app.config.display.wrap_lines;
+    let find = tab.active_find();
+    let find_ranges = |text: &str| find.map(|f| f.ranges(text)).unwrap_or_default();
     // Content width for wrapping: inner width minus gutter
     let split_wrap_width = (inner.width.saturating_sub(split_gutter_width)) as usize;
 
-    // Render hunks
+    // Render hunks, noting the row of each header for the sticky one
+    let mut hunk_rows: Vec<(usize, usize)> = Vec::with_capacity(file.hunks.len());
     for (hunk_idx, hunk) in file.hunks.iter().enumerate() {
         let is_current = hunk_idx == tab.active_current_hunk();
         let hunk_anchors = comments_for_hunk_resolved(tab, &file.path, hunk_idx, hunk);
@@ -1182,29 +1410,22 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
         }
 
         // Hunk header — shown on both sides
+        hunk_rows.push((logical_line, hunk_idx));
+        let folded = tab.is_hunk_folded(&file.path, hunk);
         if logical_line >= render_start && logical_line < render_end {
-            let marker = if is_current { "\u{25b6}" } else { " " };
-            lines.push(
-                Line::from(vec![
-                    Span::styled(
-                        format!(" {} ", marker),
-                        if is_current {
-                            ratatui::style::Style::default()
-                                .fg(styles::CYAN())
-                                .bg(styles::HUNK_BG())
-                        } else {
-                            ratatui::style::Style::default()
-                                .fg(styles::DIM())
-                                .bg(styles::HUNK_BG())
-                        },
-                    ),
-                    Span::styled(&hunk.header, styles::hunk_header_style()),
-                ])
-                .style(styles::hunk_header_style()),
-            );
+            lines.push(hunk_header_line(hunk, is_current, folded));
         }
         logical_line += 1;
 
+        // A folded hunk is just its header
+        if folded {
+            if logical_line >= render_start && logical_line < render_end {
+                lines.push(hunk_gap_line(file, hunk_idx));
+            }
+            logical_line += 1;
+            continue;
+        }
+
         // Hunk-level comments — New side renders, Old side pads with blanks.
         {
             for comment in hunk_level_comments(&hunk_anchors) {
@@ -1215,7 +1436,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                 if side == SplitSide::New {
                     let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                     let pre_len = lines.len();
-                    render_comment_lines(&mut lines, comment, inner.width, false, is_focused);
+                    render_comment_lines(
+                        &mut lines,
+                        comment,
+                        inner.width,
+                        false,
+                        is_focused,
+                        tab.is_addressed(comment.id()),
+                    );
                     let n = lines.len() - pre_len;
                     if logical_line < render_start || logical_line >= render_end {
                         lines.truncate(pre_len);
@@ -1223,7 +1451,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                     logical_line += n;
                 } else {
                     let mut tmp: Vec<Line> = Vec::new();
-                    render_comment_lines(&mut tmp, comment, inner.width, false, false);
+                    render_comment_lines(
+                        &mut tmp,
+                        comment,
+                        inner.width,
+                        false,
+                        false,
+                        tab.is_addressed(comment.id()),
+                    );
                     let n = tmp.len();
                     for k in 0..n {
                         if logical_line + k >= render_start && logical_line + k < render_end {
@@ -1373,11 +1608,15 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                     ratatui::style::Style::default()
                         .fg(styles::BRIGHT())
                         .bg(styles::LINE_CURSOR_BG())
+                } else if side == SplitSide::New
+                    && diff_line
+                        .new_num
+                        .is_some_and(|n| tab.is_line_flashed(&file.path, n))
+                {
+                    styles::line_flash()
                 } else {
                     match diff_line.line_type {
-                        LineType::Add => ratatui::style::Style::default()
-                            .fg(styles::DIM())
-                            .bg(styles::ADD_BG()),
+                        LineType::Add => add_gutter_style(tab, &file.path, diff_line),
                         LineType::Delete => ratatui::style::Style::default()
                             .fg(styles::DIM())
                             .bg(styles::DEL_BG()),
@@ -1386,38 +1625,51 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                     }
                 };
 
+                let markers = match diff_line.new_num {
+                    Some(n) if side == SplitSide::New && tab.layers.show_ai_findings => {
+                        tab.ai.line_markers(&file.path, n)
+                    }
+                    _ => Vec::new(),
+                };
+                let gutter = |num: String| {
+                    let mut spans = vec![Span::styled(num, gutter_style)];
+                    spans.extend(finding_marker_spans(
+                        &markers,
+                        SPLIT_MARKER_CELLS,
+                        gutter_style,
+                    ));
+                    spans.push(Span::styled("\u{2502}", gutter_style));
+                    spans
+                };
+
                 if wrap_lines && !diff_line.content.is_empty() {
                     let content = expand_tabs(&diff_line.content, app.config.display.tab_width);
-                    let segments = word_wrap(&content, split_wrap_width.max(1));
+                    let segments = soft_wrap(&content, split_wrap_width.max(1));
                     for (seg_idx, segment) in segments.iter().enumerate() {
                         if logical_line + seg_idx >= render_start
                             && logical_line + seg_idx < render_end
                         {
                             let mut spans: Vec<Span<'static>> = if seg_idx == 0 {
-                                vec![
-                                    Span::styled(format!("{} \u{2502}", num_str), gutter_style),
-                                    Span::styled(prefix, base_style),
-                                ]
+                                let mut spans = gutter(num_str.clone());
+                                spans.push(Span::styled(prefix, base_style));
+                                spans
                             } else {
-                                vec![
-                                    Span::styled(BLANK_SPLIT_GUTTER, gutter_style),
-                                    Span::styled(" ", base_style),
-                                ]
+                                let mut spans = gutter(BLANK_SPLIT_NUM.to_string());
+                                spans.push(Span::styled(" ", base_style));
+                                spans
                             };
                             let highlighted: Vec<Span<'static>> = hl
                                 .highlight_line(segment, &file.path, base_style)
                                 .into_iter()
                                 .map(|s| Span::styled(s.content.into_owned(), s.style))
                                 .collect();
-                            spans.extend(highlighted);
+                            spans.extend(mark_find_matches(highlighted, &find_ranges(segment)));
                             lines.push(Line::from(spans).style(base_style));
                         }
                     }
                 } else if logical_line >= render_start && logical_line < render_end {
-                    let mut spans = vec![
-                        Span::styled(format!("{} \u{2502}", num_str), gutter_style),
-                        Span::styled(prefix, base_style),
-                    ];
+                    let mut spans = gutter(num_str.clone());
+                    spans.push(Span::styled(prefix, base_style));
                     if diff_line.content.is_empty() {
                         spans.push(Span::styled("", base_style));
                     } else {
@@ -1427,7 +1679,7 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                             .into_iter()
                             .map(|s| Span::styled(s.content.into_owned(), s.style))
                             .collect();
-                        spans.extend(highlighted);
+                        spans.extend(mark_find_matches(highlighted, &find_ranges(&content)));
                     }
                     lines.push(Line::from(spans).style(base_style));
                 }
@@ -1476,7 +1728,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                     if side == SplitSide::New {
                         let is_focused = tab.focused_comment_id.as_deref() == Some(comment.id());
                         let pre_len = lines.len();
-                        render_comment_lines(&mut lines, comment, inner.width, true, is_focused);
+                        render_comment_lines(
+                            &mut lines,
+                            comment,
+                            inner.width,
+                            true,
+                            is_focused,
+                            tab.is_addressed(comment.id()),
+                        );
                         let n = lines.len() - pre_len;
                         if logical_line < render_start || logical_line >= render_end {
                             lines.truncate(pre_len);
@@ -1484,7 +1743,14 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                         logical_line += n;
                     } else {
                         let mut tmp: Vec<Line> = Vec::new();
-                        render_comment_lines(&mut tmp, comment, inner.width, true, false);
+                        render_comment_lines(
+                            &mut tmp,
+                            comment,
+                            inner.width,
+                            true,
+                            false,
+                            tab.is_addressed(comment.id()),
+                        );
                         let n = tmp.len();
                         for k in 0..n {
                             if logical_line + k >= render_start && logical_line + k < render_end {
@@ -1550,6 +1816,9 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                                 inner.width,
                                 file_stale,
                                 is_focused,
+                                tab.is_addressed(&finding.id),
+                                tab.expanded_findings.contains(&finding.id),
+                                tab.ai.is_suppressed(&file.path, finding, &file.hunks),
                             );
                             let n = lines.len() - pre_len;
                             if logical_line < render_start || logical_line >= render_end {
@@ -1564,6 +1833,9 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                                 inner.width,
                                 file_stale,
                                 false,
+                                tab.is_addressed(&finding.id),
+                                tab.expanded_findings.contains(&finding.id),
+                                tab.ai.is_suppressed(&file.path, finding, &file.hunks),
                             );
                             let n = tmp.len();
                             for k in 0..n {
@@ -1600,7 +1872,16 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                 if side == SplitSide::New {
                     let is_focused = tab.focused_finding_id.as_deref() == Some(&finding.id);
                     let pre_len = lines.len();
-                    render_finding_banner(&mut lines, finding, inner.width, file_stale, is_focused);
+                    render_finding_banner(
+                        &mut lines,
+                        finding,
+                        inner.width,
+                        file_stale,
+                        is_focused,
+                        tab.is_addressed(&finding.id),
+                        tab.expanded_findings.contains(&finding.id),
+                        tab.ai.is_suppressed(&file.path, finding, &file.hunks),
+                    );
                     let n = lines.len() - pre_len;
                     if logical_line < render_start || logical_line >= render_end {
                         lines.truncate(pre_len);
@@ -1608,7 +1889,16 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
                     logical_line += n;
                 } else {
                     let mut tmp: Vec<Line> = Vec::new();
-                    render_finding_banner(&mut tmp, finding, inner.width, file_stale, false);
+                    render_finding_banner(
+                        &mut tmp,
+                        finding,
+                        inner.width,
+                        file_stale,
+                        false,
+                        tab.is_addressed(&finding.id),
+                        tab.expanded_findings.contains(&finding.id),
+                        tab.ai.is_suppressed(&file.path, finding, &file.hunks),
+                    );
                     let n = tmp.len();
                     for k in 0..n {
                         if logical_line + k >= render_start && logical_line + k < render_end {
@@ -1625,21 +1915,7 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
 
         // Gap indicator or blank line between hunks
         if logical_line >= render_start && logical_line < render_end {
-            let gap = if hunk_idx + 1 < file.hunks.len() {
-                let next = &file.hunks[hunk_idx + 1];
-                next.old_start
-                    .saturating_sub(hunk.old_start + hunk.old_count)
-            } else {
-                0
-            };
-            if gap > 0 {
-                lines.push(Line::from(Span::styled(
-                    format!("  ··· {} lines hidden (+/- to expand) ···", gap),
-                    ratatui::style::Style::default().fg(styles::MUTED()),
-                )));
-            } else {
-                lines.push(Line::from(""));
-            }
+            lines.push(hunk_gap_line(file, hunk_idx));
         }
         logical_line += 1;
     }
@@ -1700,7 +1976,7 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
             ),
         ];
         // Pad to fill the full inner width
-        let sticky_len: usize = sticky_spans.iter().map(|s| s.content.chars().count()).sum();
+        let sticky_len: usize = sticky_spans.iter().map(|s| display_width(&s.content)).sum();
         let sticky_remaining = (inner.width as usize).saturating_sub(sticky_len);
         sticky_spans.push(Span::styled(
             " ".repeat(sticky_remaining),
@@ -1714,6 +1990,28 @@ fn render_split_side(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter,
         };
         f.render_widget(Paragraph::new(Line::from(sticky_spans)), sticky_area);
     }
+    // The hunk header is on both sides; pin it on both, under the file row
+    if scroll > 0 && inner.height > 2 {
+        if let Some(idx) = sticky_hunk(&hunk_rows, scroll) {
+            let is_current = idx == tab.active_current_hunk();
+            let row = Rect {
+                x: inner.x,
+                y: inner.y + 1,
+                width: inner.width,
+                height: 1,
+            };
+            f.render_widget(Clear, row);
+            f.render_widget(
+                Paragraph::new(hunk_header_line(
+                    &file.hunks[idx],
+                    is_current,
+                    tab.is_hunk_folded(&file.path, &file.hunks[idx]),
+                ))
+                .style(styles::hunk_header_style()),
+                row,
+            );
+        }
+    }
 }
 
 /// Render multi-file commit diff (History mode)
@@ -1853,7 +2151,7 @@ fn render_history_diff(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighte
         ];
 
         // Pad the rest of the file header line
-        let header_len: usize = header_spans.iter().map(|s| s.content.chars().count()).sum();
+        let header_len: usize = header_spans.iter().map(|s| display_width(&s.content)).sum();
         let remaining = (area.width as usize).saturating_sub(header_len);
         header_spans.push(Span::styled(
             " ".repeat(remaining),
@@ -1939,9 +2237,7 @@ fn render_history_diff(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighte
                         .bg(styles::LINE_CURSOR_BG())
                 } else {
                     match diff_line.line_type {
-                        LineType::Add => ratatui::style::Style::default()
-                            .fg(styles::DIM())
-                            .bg(styles::ADD_BG()),
+                        LineType::Add => add_gutter_style(tab, &file.path, diff_line),
                         LineType::Delete => ratatui::style::Style::default()
                             .fg(styles::DIM())
                             .bg(styles::DEL_BG()),
@@ -2054,7 +2350,7 @@ fn render_history_diff(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighte
             ];
 
             // Pad the sticky header to fill the full width
-            let sticky_len: usize = sticky_spans.iter().map(|s| s.content.chars().count()).sum();
+            let sticky_len: usize = sticky_spans.iter().map(|s| display_width(&s.content)).sum();
             let sticky_remaining = (area.width as usize).saturating_sub(sticky_len);
             sticky_spans.push(Span::styled(
                 " ".repeat(sticky_remaining),
@@ -2232,7 +2528,7 @@ fn render_tour_diff(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter)
                     .bg(file_header_bg),
             ));
         }
-        let header_len: usize = header_spans.iter().map(|s| s.content.chars().count()).sum();
+        let header_len: usize = header_spans.iter().map(|s| display_width(&s.content)).sum();
         let remaining = (area.width as usize).saturating_sub(header_len);
         header_spans.push(Span::styled(
             " ".repeat(remaining),
@@ -2307,9 +2603,7 @@ fn render_tour_diff(f: &mut Frame, area: Rect, app: &App, hl: &mut Highlighter)
                         .bg(styles::LINE_CURSOR_BG())
                 } else {
                     match diff_line.line_type {
-                        LineType::Add => ratatui::style::Style::default()
-                            .fg(styles::DIM())
-                            .bg(styles::ADD_BG()),
+                        LineType::Add => add_gutter_style(tab, &file.path, diff_line),
                         LineType::Delete => ratatui::style::Style::default()
                             .fg(styles::DIM())
                             .bg(styles::DEL_BG()),
@@ -2454,8 +2748,13 @@ fn render_history_empty(f: &mut Frame, area: Rect, message: &str) {
     f.render_widget(text, area);
 }
 
-/// Render a compacted file summary
-fn render_compacted(f: &mut Frame, area: Rect, file: &er_engine::git::DiffFile) {
+/// Render a compacted file summary, with why it's compacted
+fn render_compacted(
+    f: &mut Frame,
+    area: Rect,
+    file: &er_engine::git::DiffFile,
+    reason: Option<CompactionReason>,
+) {
     let block = Block::default()
         .title(Span::styled(
             format!(" {} ", file.path),
@@ -2488,27 +2787,300 @@ fn render_compacted(f: &mut Frame, area: Rect, file: &er_engine::git::DiffFile)
             ),
         ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "  (compacted \u{2014} press Enter to expand)",
-            ratatui::style::Style::default().fg(styles::MUTED()),
-        )),
+    ];
+    let enter = if matches!(reason, Some(CompactionReason::TooBig(_))) {
+        "  press Enter to load anyway"
+    } else {
+        "  (compacted \u{2014} press Enter to expand)"
+    };
+    compacted_lines.push(Line::from(Span::styled(
+        enter,
+        ratatui::style::Style::default().fg(styles::MUTED()),
+    )));
+    if let Some(reason) = reason {
+        compacted_lines.extend([
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    "  Why: ",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled(
+                    reason.label(),
+                    ratatui::style::Style::default().fg(styles::TEXT()),
+                ),
+            ]),
+            Line::from(Span::styled(
+                "  Alt+c: always expand it, stop compacting the pattern or raise the limit",
+                ratatui::style::Style::default().fg(styles::DIM()),
+            )),
+        ]);
+    }
+    pad_lines_to_fill(&mut compacted_lines, 0, area.height);
+    let text = Paragraph::new(compacted_lines).block(block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(text, area);
+}
+
+/// Render a Git LFS pointer change as object metadata
+fn render_lfs_change(
+    f: &mut Frame,
+    area: Rect,
+    file: &er_engine::git::DiffFile,
+    change: &er_engine::git::LfsChange,
+) {
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} ", file.path),
+            ratatui::style::Style::default().fg(styles::BRIGHT()),
+        ))
+        .borders(Borders::NONE)
+        .style(ratatui::style::Style::default().bg(styles::BG()))
+        .padding(Padding::new(0, 1, 0, 0));
+
+    let side = |pointer: &Option<er_engine::git::LfsPointer>,
+                f: fn(&er_engine::git::LfsPointer) -> String| {
+        pointer.as_ref().map_or_else(|| "\u{2014}".to_string(), f)
+    };
+    let size = |p: &er_engine::git::LfsPointer| er_engine::git::format_size(p.size);
+    let oid = |p: &er_engine::git::LfsPointer| {
+        let hash = p.oid.split_once(':').map_or(p.oid.as_str(), |(_, h)| h);
+        hash.chars().take(12).collect()
+    };
+    let row = |label: &str, old: String, new: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<6}", label),
+                ratatui::style::Style::default().fg(styles::DIM()),
+            ),
+            Span::styled(old, ratatui::style::Style::default().fg(styles::RED())),
+            Span::styled(
+                "  \u{2192}  ",
+                ratatui::style::Style::default().fg(styles::MUTED()),
+            ),
+            Span::styled(new, ratatui::style::Style::default().fg(styles::GREEN())),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "  \u{1f4e6} ",
+                ratatui::style::Style::default().fg(styles::MUTED()),
+            ),
+            Span::styled(
+                "Git LFS object",
+                ratatui::style::Style::default().fg(styles::TEXT()),
+            ),
+        ]),
+        Line::from(""),
+        row("size", side(&change.old, size), side(&change.new, size)),
+        row("oid", side(&change.old, oid), side(&change.new, oid)),
         Line::from(""),
         Line::from(Span::styled(
-            "  Lock files, generated code, and large diffs are",
-            ratatui::style::Style::default().fg(styles::DIM()),
-        )),
-        Line::from(Span::styled(
-            "  compacted automatically to save memory.",
+            "  The content lives in LFS storage and isn't diffed.",
             ratatui::style::Style::default().fg(styles::DIM()),
         )),
     ];
-    pad_lines_to_fill(&mut compacted_lines, 0, area.height);
-    let text = Paragraph::new(compacted_lines).block(block);
+    pad_lines_to_fill(&mut lines, 0, area.height);
+    let text = Paragraph::new(lines).block(block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(text, area);
+}
+
+/// A notebook cell by cell, or CSV / TSV hunks as columns (Alt+v).
+fn render_data_view(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    file: &er_engine::git::DiffFile,
+    view: &er_engine::app::DataView,
+) {
+    let label = match view {
+        er_engine::app::DataView::Notebook(_) => "notebook",
+        er_engine::app::DataView::Table(_) => "table",
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} [{} \u{00b7} Alt+v line diff] ", file.path, label),
+            ratatui::style::Style::default().fg(styles::BRIGHT()),
+        ))
+        .borders(Borders::NONE)
+        .style(ratatui::style::Style::default().bg(styles::BG()))
+        .padding(Padding::new(0, 1, 0, 0));
+
+    let tab_width = app.config.display.tab_width;
+    let max_w = area.width.saturating_sub(1) as usize;
+    let mut lines = vec![Line::from("")];
+    match view {
+        er_engine::app::DataView::Notebook(Err(e)) => lines.push(Line::from(Span::styled(
+            format!("  couldn't read the notebook: {}", e),
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        ))),
+        er_engine::app::DataView::Notebook(Ok(cells)) => {
+            notebook_lines(&mut lines, cells, tab_width, max_w)
+        }
+        er_engine::app::DataView::Table(table) => table_lines(&mut lines, table, tab_width),
+    }
+    let scroll = (app.tab().active_diff_scroll() as usize).min(lines.len().saturating_sub(1));
+    pad_lines_to_fill(&mut lines, scroll as u16, area.height);
+    let text = Paragraph::new(lines)
+        .block(block)
+        .scroll((scroll as u16, app.tab().h_scroll));
 
     f.render_widget(Clear, area);
     f.render_widget(text, area);
 }
 
+fn notebook_lines(
+    lines: &mut Vec<Line<'_>>,
+    cells: &[er_engine::analysis::notebook::CellDiff],
+    tab_width: u8,
+    max_w: usize,
+) {
+    use er_engine::analysis::notebook::CellChange;
+
+    if cells.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  no cells",
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        )));
+    }
+    for cell in cells {
+        let color = match cell.change {
+            CellChange::Added => styles::GREEN(),
+            CellChange::Removed => styles::RED(),
+            CellChange::Modified => styles::YELLOW(),
+            CellChange::Unchanged => styles::DIM(),
+        };
+        let mut title = format!(
+            "  {} [{}] {} ",
+            cell.change.symbol(),
+            cell.number,
+            cell.kind.label()
+        );
+        if cell.outputs_changed {
+            title.push_str("\u{00b7} outputs changed ");
+        }
+        let rule = max_w.saturating_sub(display_width(&title));
+        lines.push(Line::from(vec![
+            Span::styled(
+                title,
+                ratatui::style::Style::default()
+                    .fg(color)
+                    .add_modifier(ratatui::style::Modifier::BOLD),
+            ),
+            Span::styled(
+                "\u{2500}".repeat(rule.min(300)),
+                ratatui::style::Style::default().fg(styles::BORDER()),
+            ),
+        ]));
+        for (line_type, text) in &cell.lines {
+            let (prefix, style) = match line_type {
+                LineType::Add => ("+", styles::add_style()),
+                LineType::Delete => ("-", styles::del_style()),
+                _ => (" ", ratatui::style::Style::default().fg(styles::TEXT())),
+            };
+            lines.push(Line::from(Span::styled(
+                format!("  {} {}", prefix, expand_tabs(text, tab_width)),
+                style,
+            )));
+        }
+        let output_color = if cell.outputs_changed {
+            styles::YELLOW()
+        } else {
+            styles::MUTED()
+        };
+        for output in &cell.outputs {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "    \u{21b3} {}",
+                    truncate_to_width(output, max_w.saturating_sub(6))
+                ),
+                ratatui::style::Style::default().fg(output_color),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+}
+
+fn table_lines(
+    lines: &mut Vec<Line<'_>>,
+    table: &er_engine::analysis::tabular::Table,
+    tab_width: u8,
+) {
+    let cell = |text: &str, width: usize| {
+        let text = truncate_to_width(&expand_tabs(text, tab_width), width);
+        let pad = width.saturating_sub(display_width(&text));
+        format!("{}{} ", text, " ".repeat(pad))
+    };
+    if let Some(header) = &table.header {
+        let mut spans = vec![Span::raw("         ")];
+        spans.extend(header.iter().zip(&table.widths).map(|(h, &w)| {
+            Span::styled(
+                cell(h, w),
+                ratatui::style::Style::default()
+                    .fg(styles::BRIGHT())
+                    .add_modifier(ratatui::style::Modifier::BOLD),
+            )
+        }));
+        lines.push(Line::from(spans));
+    }
+    for (i, row) in table.rows.iter().enumerate() {
+        if i > 0 && table.hunk_starts.contains(&i) {
+            lines.push(Line::from(Span::styled(
+                "  \u{22ef}",
+                ratatui::style::Style::default().fg(styles::DIM()),
+            )));
+        }
+        let (prefix, color, changed_bg) = match row.line_type {
+            LineType::Add => ("+", styles::ADD_TEXT(), Some(styles::ADD_BG())),
+            LineType::Delete => ("-", styles::DEL_TEXT(), Some(styles::DEL_BG())),
+            _ => (" ", styles::TEXT(), None),
+        };
+        let num = row
+            .new_num
+            .or(row.old_num)
+            .map_or(String::new(), |n| n.to_string());
+        let mut spans = vec![Span::styled(
+            format!("{:>5} {} ", num, prefix),
+            ratatui::style::Style::default().fg(styles::DIM()),
+        )];
+        for (c, text) in row.cells.iter().enumerate() {
+            let width = table.widths.get(c).copied().unwrap_or(0);
+            let mut style = ratatui::style::Style::default().fg(color);
+            if let (true, Some(bg)) = (row.changed.get(c) == Some(&true), changed_bg) {
+                style = style.bg(bg).add_modifier(ratatui::style::Modifier::BOLD);
+            }
+            spans.push(Span::styled(cell(text, width), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    if table.rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  no rows changed",
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        )));
+    }
+}
+
+/// The "moved" badge of a relocated comment: a plain arrow when the match
+/// is sure, the percentage in the stale color when the reviewer should check.
+fn relocated_badge(comment: &CommentRef) -> (String, ratatui::style::Color) {
+    match comment.anchor_confidence() {
+        Some(c) if c < er_engine::ai::LOW_CONFIDENCE => (
+            format!("  \u{21aa} moved? {:.0}%", c * 100.0),
+            styles::STALE(),
+        ),
+        _ => (
+            "  \u{21aa} moved".to_string(),
+            styles::RELOCATED_INDICATOR(),
+        ),
+    }
+}
 /// Render a single comment (line-level or hunk-level) into the lines buffer
 fn render_comment_lines(
     lines: &mut Vec<Line<'_>>,
@@ -2516,6 +3088,7 @@ fn render_comment_lines(
     width: u16,
     inline: bool,
     focused: bool,
+    addressed: bool,
 ) {
     let ctype = comment.comment_type();
     let is_question = ctype == CommentType::Question;
@@ -2549,6 +3122,7 @@ fn render_comment_lines(
         "💬"
     };
     let author = comment.author();
+    let author_fg = comment.teammate().map_or(accent, styles::reviewer_color);
 
     if inline {
         // ── GitHub-style inline block ──
@@ -2591,7 +3165,7 @@ fn render_comment_lines(
             Span::styled(
                 author.to_string(),
                 ratatui::style::Style::default()
-                    .fg(accent)
+                    .fg(author_fg)
                     .bg(bg)
                     .add_modifier(ratatui::style::Modifier::BOLD),
             ),
@@ -2605,11 +3179,10 @@ fn render_comment_lines(
             ));
         }
         if anchor == "relocated" {
+            let (badge, color) = relocated_badge(comment);
             author_spans.push(Span::styled(
-                "  \u{21aa} moved",
-                ratatui::style::Style::default()
-                    .fg(styles::RELOCATED_INDICATOR())
-                    .bg(bg),
+                badge,
+                ratatui::style::Style::default().fg(color).bg(bg),
             ));
         } else if is_lost {
             author_spans.push(Span::styled(
@@ -2625,6 +3198,12 @@ fn render_comment_lines(
                 ratatui::style::Style::default().fg(styles::STALE()).bg(bg),
             ));
         }
+        if addressed {
+            author_spans.push(Span::styled(
+                "  \u{21bb} needs re-check",
+                ratatui::style::Style::default().fg(styles::YELLOW()).bg(bg),
+            ));
+        }
         if comment.is_resolved() {
             author_spans.push(Span::styled(
                 "  \u{2713} resolved",
@@ -2644,6 +3223,17 @@ fn render_comment_lines(
                 ));
             }
         }
+        if comment.in_sync_conflict() {
+            author_spans.push(Span::styled(
+                "  \u{26a0} conflict",
+                ratatui::style::Style::default().fg(styles::RED()).bg(bg),
+            ));
+        } else if comment.has_unpushed_edit() {
+            author_spans.push(Span::styled(
+                "  \u{270e} edited",
+                ratatui::style::Style::default().fg(styles::YELLOW()).bg(bg),
+            ));
+        }
         if focused {
             author_spans.push(Span::styled(
                 "  \u{25c6}",
@@ -2658,7 +3248,7 @@ fn render_comment_lines(
         // Text lines: "  │   text..."
         let bar_style = ratatui::style::Style::default().fg(accent).bg(bg);
         let indent_str = "  \u{2502}  ";
-        let max_len = (width as usize).saturating_sub(indent_str.len() + 2);
+        let max_len = (width as usize).saturating_sub(display_width(indent_str) + 2);
         let text = comment.text();
         let text_fg = if is_stale || is_lost {
             styles::DIM()
@@ -2711,7 +3301,7 @@ fn render_comment_lines(
         Span::styled(
             author.to_string(),
             ratatui::style::Style::default()
-                .fg(accent)
+                .fg(author_fg)
                 .bg(bg)
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ),
@@ -2729,11 +3319,10 @@ fn render_comment_lines(
 
     // Relocated/lost anchor indicators
     if anchor == "relocated" {
+        let (badge, color) = relocated_badge(comment);
         header_spans.push(Span::styled(
-            "  \u{21aa} moved",
-            ratatui::style::Style::default()
-                .fg(styles::RELOCATED_INDICATOR())
-                .bg(bg),
+            badge,
+            ratatui::style::Style::default().fg(color).bg(bg),
         ));
     } else if is_lost {
         header_spans.push(Span::styled(
@@ -2753,6 +3342,12 @@ fn render_comment_lines(
     }
 
     // Resolved indicator
+    if addressed {
+        header_spans.push(Span::styled(
+            "  \u{21bb} needs re-check",
+            ratatui::style::Style::default().fg(styles::YELLOW()).bg(bg),
+        ));
+    }
     if comment.is_resolved() {
         header_spans.push(Span::styled(
             "  \u{2713} resolved",
@@ -2772,6 +3367,17 @@ fn render_comment_lines(
             ratatui::style::Style::default().fg(styles::DIM()).bg(bg),
         ));
     }
+    if comment.in_sync_conflict() {
+        header_spans.push(Span::styled(
+            "  \u{26a0} conflict",
+            ratatui::style::Style::default().fg(styles::RED()).bg(bg),
+        ));
+    } else if comment.has_unpushed_edit() {
+        header_spans.push(Span::styled(
+            "  \u{270e} edited",
+            ratatui::style::Style::default().fg(styles::YELLOW()).bg(bg),
+        ));
+    }
 
     // Focus indicator
     if focused {
@@ -2829,6 +3435,7 @@ fn render_reply_lines(
     };
     let icon = if is_question { "❓" } else { "💬" };
     let author = reply.author();
+    let author_fg = reply.teammate().map_or(accent, styles::reviewer_color);
 
     let prefix = if inline {
         format!("       ↳ {} ", icon)
@@ -2843,7 +3450,7 @@ fn render_reply_lines(
         Span::styled(
             author.to_string(),
             ratatui::style::Style::default()
-                .fg(accent)
+                .fg(author_fg)
                 .bg(bg)
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ),
@@ -2895,13 +3502,50 @@ fn render_reply_lines(
     }
 }
 
+/// Gutter cells for the findings covering a line: one severity-coloured bar
+/// per finding, dimmed once resolved or dismissed. When more findings overlap
+/// than there are cells, the last cell shows `+`.
+fn finding_marker_spans(
+    markers: &[er_engine::ai::LineMarker],
+    cells: usize,
+    gutter_style: ratatui::style::Style,
+) -> Vec<Span<'static>> {
+    (0..cells)
+        .map(|i| {
+            let Some(marker) = markers.get(i) else {
+                return Span::styled(" ", gutter_style);
+            };
+            let color = if !marker.active {
+                styles::DIM()
+            } else {
+                match marker.severity {
+                    RiskLevel::High => styles::RED(),
+                    RiskLevel::Medium => styles::ORANGE(),
+                    RiskLevel::Low => styles::YELLOW(),
+                    RiskLevel::Info => styles::BLUE(),
+                }
+            };
+            let glyph = if i + 1 == cells && markers.len() > cells {
+                "+"
+            } else {
+                "\u{2503}"
+            };
+            Span::styled(glyph, gutter_style.fg(color))
+        })
+        .collect()
+}
+
 /// Render an AI finding banner (title + description + suggestion)
+#[allow(clippy::too_many_arguments)]
 fn render_finding_banner(
     lines: &mut Vec<Line<'_>>,
     finding: &Finding,
     width: u16,
     file_stale: bool,
     focused: bool,
+    addressed: bool,
+    expanded: bool,
+    suppressed: bool,
 ) {
     let bg = if focused {
         styles::FINDING_FOCUS_BG()
@@ -2909,6 +3553,35 @@ fn render_finding_banner(
         styles::FINDING_BG()
     };
 
+    // Suppressed by an `er:ignore` note: just the dimmed title until expanded
+    if suppressed {
+        let dim = ratatui::style::Style::default().fg(styles::MUTED()).bg(bg);
+        let mut spans = vec![Span::styled(
+            format!(
+                "  {} [{}] {} · suppressed by note",
+                finding.severity.symbol(),
+                finding.category,
+                finding.title
+            ),
+            dim,
+        )];
+        if focused {
+            spans.push(Span::styled(
+                if expanded {
+                    "  i collapse"
+                } else {
+                    "  i details"
+                },
+                ratatui::style::Style::default().fg(styles::DIM()).bg(bg),
+            ));
+        }
+        lines.push(Line::from(spans).style(ratatui::style::Style::default().bg(bg)));
+        if expanded {
+            render_finding_details(lines, finding, width, bg);
+        }
+        return;
+    }
+
     let severity_style = if file_stale {
         styles::stale_style()
     } else {
@@ -2933,6 +3606,12 @@ fn render_finding_banner(
             ratatui::style::Style::default().fg(styles::ORANGE()).bg(bg),
         ),
     ];
+    if addressed {
+        title_spans.push(Span::styled(
+            "  \u{21bb} addressed — needs re-check",
+            ratatui::style::Style::default().fg(styles::YELLOW()).bg(bg),
+        ));
+    }
     if focused {
         title_spans.push(Span::styled(
             "  ◆ focused",
@@ -2941,23 +3620,26 @@ fn render_finding_banner(
                 .bg(bg)
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ));
+        title_spans.push(Span::styled(
+            if expanded {
+                "  i collapse"
+            } else {
+                "  i details"
+            },
+            ratatui::style::Style::default().fg(styles::DIM()).bg(bg),
+        ));
     }
 
     lines.push(Line::from(title_spans).style(ratatui::style::Style::default().bg(bg)));
 
+    if expanded {
+        render_finding_details(lines, finding, width, bg);
+        return;
+    }
+
     if !finding.description.is_empty() {
         let desc = finding.description.lines().next().unwrap_or("");
-        let max_len = width.saturating_sub(6) as usize;
-        let truncated = if desc.chars().count() > max_len {
-            format!(
-                "{}\u{2026}",
-                desc.chars()
-                    .take(max_len.saturating_sub(1))
-                    .collect::<String>()
-            )
-        } else {
-            desc.to_string()
-        };
+        let truncated = truncate_to_width(desc, width.saturating_sub(6) as usize);
         lines.push(
             Line::from(vec![Span::styled(
                 format!("    {}", truncated),
@@ -2969,17 +3651,7 @@ fn render_finding_banner(
 
     if !finding.suggestion.is_empty() {
         let sug = finding.suggestion.lines().next().unwrap_or("");
-        let max_len = width.saturating_sub(8) as usize;
-        let truncated = if sug.chars().count() > max_len {
-            format!(
-                "{}\u{2026}",
-                sug.chars()
-                    .take(max_len.saturating_sub(1))
-                    .collect::<String>()
-            )
-        } else {
-            sug.to_string()
-        };
+        let truncated = truncate_to_width(sug, width.saturating_sub(8) as usize);
         lines.push(
             Line::from(vec![Span::styled(
                 format!("    \u{2192} {}", truncated),
@@ -2990,6 +3662,63 @@ fn render_finding_banner(
     }
 }
 
+/// The whole finding beneath its title, wrapped to `width`: description,
+/// suggestion and the AI's replies.
+fn render_finding_details(
+    lines: &mut Vec<Line<'_>>,
+    finding: &Finding,
+    width: u16,
+    bg: ratatui::style::Color,
+) {
+    let line_style = ratatui::style::Style::default().bg(bg);
+    let mut push_wrapped = |text: &str, first: &str, rest: &str, fg: ratatui::style::Color| {
+        let max_w = (width as usize)
+            .saturating_sub(display_width(first) + 2)
+            .max(1);
+        let mut lead = first;
+        for paragraph in text.lines() {
+            let wrapped = if paragraph.trim().is_empty() {
+                vec![String::new()]
+            } else {
+                word_wrap(paragraph, max_w)
+            };
+            for segment in wrapped {
+                lines.push(
+                    Line::from(vec![Span::styled(
+                        format!("{}{}", lead, segment),
+                        ratatui::style::Style::default().fg(fg).bg(bg),
+                    )])
+                    .style(line_style),
+                );
+                lead = rest;
+            }
+        }
+    };
+
+    if !finding.description.is_empty() {
+        push_wrapped(&finding.description, "    ", "    ", styles::TEXT());
+    }
+    if !finding.suggestion.is_empty() {
+        push_wrapped(
+            &finding.suggestion,
+            "    \u{2192} ",
+            "      ",
+            styles::GREEN(),
+        );
+    }
+    for response in &finding.responses {
+        if response.text.is_empty() {
+            continue;
+        }
+        push_wrapped(
+            &response.text,
+            "    \u{21b3} AI: ",
+            "          ",
+            styles::MUTED(),
+        );
+    }
+}
+
 /// Render an empty state when no file is selected
 fn render_empty(f: &mut Frame, area: Rect) {
     let block = Block::default()
@@ -3016,6 +3745,35 @@ fn render_empty(f: &mut Frame, area: Rect) {
     f.render_widget(text, area);
 }
 
+/// The start tab of a launch outside a repository
+fn render_no_repo(f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::NONE)
+        .style(ratatui::style::Style::default().bg(styles::BG()));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Not in a git repository",
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  o", styles::key_hint_style()),
+            Span::styled(
+                "  browse folders, or open a PR URL or patch file",
+                ratatui::style::Style::default().fg(styles::DIM()),
+            ),
+        ]),
+    ];
+    pad_lines_to_fill(&mut lines, 0, area.height);
+    let text = Paragraph::new(lines).block(block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(text, area);
+}
+
 /// Render a watched file's content in the diff view area
 fn render_watched(f: &mut Frame, area: Rect, app: &App, path: &str, size: u64) {
     let tab = app.tab();
@@ -3376,6 +4134,43 @@ mod build_split_rows_tests {
         }
     }
 
+    #[test]
+    fn sticky_hunk_pins_the_hunk_whose_header_scrolled_off() {
+        // Headers at rows 2 and 10
+        let rows = [(2, 0), (10, 1)];
+        // Header still below the sticky file row
+        assert_eq!(sticky_hunk(&rows, 0), None);
+        assert_eq!(sticky_hunk(&rows, 1), None);
+        // Scrolled under it
+        assert_eq!(sticky_hunk(&rows, 2), Some(0));
+        assert_eq!(sticky_hunk(&rows, 8), Some(0));
+        // The next header is right under the file row: no overlay
+        assert_eq!(sticky_hunk(&rows, 9), None);
+        assert_eq!(sticky_hunk(&rows, 10), Some(1));
+        assert_eq!(sticky_hunk(&[], 5), None);
+    }
+
+    #[test]
+    fn find_matches_split_across_highlight_spans() {
+        let plain = ratatui::style::Style::default();
+        let spans = vec![
+            Span::styled("let total", plain),
+            Span::styled(" = total;", plain),
+        ];
+        let marked = mark_find_matches(spans, &[4..9, 12..17]);
+        let text: Vec<&str> = marked.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, ["let ", "total", " = ", "total", ";"]);
+        assert_eq!(marked[1].style, plain.patch(styles::find_match()));
+        assert_eq!(marked[2].style, plain);
+
+        // A match across two spans marks both parts
+        let spans = vec![Span::styled("ab", plain), Span::styled("cd", plain)];
+        let marked = mark_find_matches(spans, &[Range { start: 1, end: 3 }]);
+        let text: Vec<&str> = marked.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, ["a", "b", "c", "d"]);
+        assert_eq!(marked[2].style, plain.patch(styles::find_match()));
+    }
+
     #[test]
     fn build_split_rows_context_both_sides() {
         let hunk = make_hunk(vec![make_line(LineType::Context, "ctx", Some(1), Some(1))]);
@@ -3493,6 +4288,19 @@ mod build_split_rows_tests {
 mod tests {
     use super::*;
 
+    #[test]
+    fn expand_tabs_aligns_after_wide_characters() {
+        // "日" is two columns, so the tab only needs two more to reach col 4
+        assert_eq!(expand_tabs("日\tx", 4), "日  x");
+        assert_eq!(display_width(&expand_tabs("ab\tx", 4)), 5);
+    }
+
+    #[test]
+    fn expand_tabs_neutralizes_bidi_and_control_chars() {
+        let shown = expand_tabs("if x \u{202E}{ \x1b[31m", 4);
+        assert_eq!(shown, "if x \u{FFFD}{ \u{FFFD}[31m");
+    }
+
     #[test]
     fn format_size_bytes_range() {
         assert_eq!(format_size(0), "0 B");
@@ -3512,4 +4320,86 @@ mod tests {
         assert_eq!(format_size(1048576), "1.0 MB");
         assert_eq!(format_size(2 * 1024 * 1024), "2.0 MB");
     }
+
+    #[test]
+    fn finding_markers_fill_cells_and_flag_overflow() {
+        use er_engine::ai::LineMarker;
+        let marker = |severity, active| LineMarker { severity, active };
+        let text = |spans: Vec<Span>| {
+            spans
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect::<String>()
+        };
+        let base = ratatui::style::Style::default();
+
+        assert_eq!(text(finding_marker_spans(&[], 2, base)), "  ");
+        let one = [marker(RiskLevel::High, true)];
+        assert_eq!(text(finding_marker_spans(&one, 2, base)), "\u{2503} ");
+        let three = [
+            marker(RiskLevel::High, true),
+            marker(RiskLevel::Low, true),
+            marker(RiskLevel::Info, false),
+        ];
+        let spans = finding_marker_spans(&three, 2, base);
+        assert_eq!(spans[0].style.fg, Some(styles::RED()));
+        assert_eq!(text(spans), "\u{2503}+");
+        let dimmed = finding_marker_spans(&[marker(RiskLevel::High, false)], 1, base);
+        assert_eq!(dimmed[0].style.fg, Some(styles::DIM()));
+    }
+
+    #[test]
+    fn expanded_finding_wraps_full_text_and_replies() {
+        let finding: Finding = serde_json::from_value(serde_json::json!({
+            "id": "f1",
+            "severity": "high",
+            "title": "Unchecked index",
+            "description": "first paragraph that is long enough to wrap\nsecond",
+            "suggestion": "use get()",
+            "responses": [{"id": "r1", "text": "still present"}]
+        }))
+        .unwrap();
+        let text = |lines: &[Line]| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.to_string()).collect())
+                .collect()
+        };
+
+        let mut collapsed = Vec::new();
+        render_finding_banner(
+            &mut collapsed,
+            &finding,
+            30,
+            false,
+            true,
+            false,
+            false,
+            false,
+        );
+        assert_eq!(collapsed.len(), 3);
+
+        let mut suppressed = Vec::new();
+        render_finding_banner(
+            &mut suppressed,
+            &finding,
+            30,
+            false,
+            true,
+            false,
+            false,
+            true,
+        );
+        assert_eq!(suppressed.len(), 1);
+        assert!(text(&suppressed)[0].contains("suppressed by note"));
+
+        let mut expanded = Vec::new();
+        render_finding_banner(&mut expanded, &finding, 30, false, true, false, true, false);
+        let body = text(&expanded[1..]);
+        assert!(body.len() > 4, "{:?}", body);
+        assert!(body.iter().all(|l| display_width(l) <= 30), "{:?}", body);
+        assert!(body.iter().any(|l| l.trim() == "second"));
+        assert!(body.iter().any(|l| l.contains("\u{2192} use get()")));
+        assert!(body.last().unwrap().contains("AI: still present"));
+    }
 }
@@ -4,13 +4,18 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Padding},
     Frame,
 };
+use std::ops::Range;
 use std::time::SystemTime;
 
+use super::diff_view::mark_find_matches;
 use super::styles;
-use super::utils::{horizontal_rule, word_wrap};
+use super::utils::{horizontal_rule, truncate_to_width, word_wrap};
 use er_engine::ai::{Finding, RiskLevel};
-use er_engine::app::{App, DiffMode};
-use er_engine::git::FileStatus;
+use er_engine::app::{
+    author_initials, commit_age, date_group, App, CommitField, DateGroup, DiffMode, FileSort,
+    TabState,
+};
+use er_engine::git::{CommitInfo, DiffFile, FileStatus};
 
 /// Format a SystemTime as a relative time string (e.g. "2m ago", "1h ago")
 fn format_relative_time(mtime: SystemTime) -> String {
@@ -127,6 +132,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     } else {
         format!(" FILES ({}) ", count_label)
     };
+    let title = if tab.file_sort == FileSort::Diff {
+        title
+    } else {
+        format!("{}· by {} ", title, tab.file_sort.label())
+    };
 
     // Virtualized rendering: find which position the selected file is in the visible list,
     // then only render items in the viewport window
@@ -151,6 +161,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     let viewport_end = (file_scroll + viewport_height).min(visible.len());
     let viewport_slice = &visible[file_scroll..viewport_end];
 
+    let pairing = tab.test_pairing();
+
     let mut items: Vec<ListItem> = viewport_slice
         .iter()
         .map(|(idx, file)| {
@@ -192,7 +204,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
 
             // Relative time when sorting by mtime — read from the cache populated on refresh,
             // not from the filesystem directly (avoids per-frame syscalls).
-            let time_str = if tab.sort_by_mtime {
+            let time_str = if tab.file_sort == FileSort::Recent {
                 let mtime = tab
                     .mtime_cache
                     .get(&file.path)
@@ -221,9 +233,49 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             } else {
                 String::new()
             };
-            let comment_width: usize = q_indicator.chars().count()
+            // GitHub comments that arrived since the file was last selected (★N)
+            let new_comment_count = tab.new_comment_files.get(&file.path).copied().unwrap_or(0);
+            let new_indicator = if new_comment_count > 0 {
+                format!("\u{2605}{} ", new_comment_count)
+            } else {
+                String::new()
+            };
+            // Items an agent touched since the last sign-off (↻N)
+            let addressed_count = tab.addressed_count_for_file(&file.path);
+            let addressed_indicator = if addressed_count > 0 {
+                format!("\u{21bb}{} ", addressed_count)
+            } else {
+                String::new()
+            };
+            // Stale: the AI review predates this file's diff, or comments whose
+            // anchor lines changed (⚠ / ⚠N)
+            let stale_comment_count = tab.ai.file_stale_comment_count(&file.path);
+            let stale_indicator = match (file_stale, stale_comment_count) {
+                (_, n) if n > 0 => format!("\u{26a0}{} ", n),
+                (true, _) => "\u{26a0} ".to_string(),
+                _ => String::new(),
+            };
+            // Did this source's tests change too? (T✓ / T✗)
+            let tests_changed = pairing.tests_changed(&file.path);
+            let test_indicator = match tests_changed {
+                Some(true) => "T\u{2713} ",
+                Some(false) => "T\u{2717} ",
+                None => "",
+            };
+            // Assigned to a reviewer (@name)
+            let assignee_indicator = tab
+                .assigned
+                .get(&file.path)
+                .map(|reviewer| format!("@{} ", reviewer))
+                .unwrap_or_default();
+            let comment_width: usize = test_indicator.chars().count()
+                + assignee_indicator.chars().count()
+                + q_indicator.chars().count()
                 + n_indicator.chars().count()
-                + gh_indicator.chars().count();
+                + gh_indicator.chars().count()
+                + new_indicator.chars().count()
+                + addressed_indicator.chars().count()
+                + stale_indicator.chars().count();
 
             // Adjust path width to account for finding dots, comment indicators, and time column
             let path = shorten_path(
@@ -236,11 +288,12 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             let stats = format!("+{} -{}", file.adds, file.dels);
 
             let is_reviewed = tab.reviewed.contains_key(&file.path);
+            let is_skipped = tab.is_skipped(&file.path);
             let is_compacted = file.compacted;
 
             let line_style = if is_selected {
                 styles::selected_style()
-            } else if is_compacted || is_reviewed {
+            } else if is_compacted || is_reviewed || is_skipped {
                 ratatui::style::Style::default()
                     .fg(styles::DIM())
                     .bg(styles::SURFACE())
@@ -248,8 +301,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 styles::surface_style()
             };
 
-            // Dim the symbol if reviewed (unless selected)
-            let effective_symbol_style = if is_reviewed && !is_selected {
+            // Dim the symbol if reviewed or skipped (unless selected)
+            let effective_symbol_style = if (is_reviewed || is_skipped) && !is_selected {
                 ratatui::style::Style::default().fg(styles::DIM())
             } else {
                 symbol_style
@@ -259,15 +312,24 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 .saturating_sub(14 + finding_width + comment_width + time_width)
                 .max(1);
 
-            let mut spans = vec![Span::styled(
-                format!(" {} ", symbol),
-                effective_symbol_style,
-            )];
+            // Multi-selected files get a bar in place of the leading space
+            let in_selection = tab.multi_selected.contains(&file.path);
+            let mut spans = vec![
+                Span::styled(
+                    if in_selection { "\u{258c}" } else { " " },
+                    ratatui::style::Style::default().fg(styles::PURPLE()),
+                ),
+                Span::styled(format!("{} ", symbol), effective_symbol_style),
+            ];
 
             spans.push(Span::styled(
                 format!("{:<width$}", path, width = path_width),
                 if is_selected {
                     styles::selected_style()
+                } else if is_skipped {
+                    ratatui::style::Style::default()
+                        .fg(styles::DIM())
+                        .add_modifier(ratatui::style::Modifier::CROSSED_OUT)
                 } else if is_reviewed {
                     ratatui::style::Style::default().fg(styles::DIM())
                 } else {
@@ -296,6 +358,39 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     ratatui::style::Style::default().fg(styles::CYAN()),
                 ));
             }
+            if new_comment_count > 0 {
+                spans.push(Span::styled(
+                    new_indicator,
+                    ratatui::style::Style::default()
+                        .fg(styles::CYAN())
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ));
+            }
+            if addressed_count > 0 {
+                spans.push(Span::styled(
+                    addressed_indicator,
+                    ratatui::style::Style::default().fg(styles::YELLOW()),
+                ));
+            }
+            if !stale_indicator.is_empty() {
+                spans.push(Span::styled(stale_indicator, styles::stale_style()));
+            }
+            if !assignee_indicator.is_empty() {
+                spans.push(Span::styled(
+                    assignee_indicator,
+                    ratatui::style::Style::default().fg(styles::PURPLE()),
+                ));
+            }
+            if let Some(changed) = tests_changed {
+                spans.push(Span::styled(
+                    test_indicator,
+                    ratatui::style::Style::default().fg(if changed {
+                        styles::GREEN()
+                    } else {
+                        styles::MUTED()
+                    }),
+                ));
+            }
             // Show relative time when sorting by mtime
             if let Some(ref ts) = time_str {
                 spans.push(Span::styled(
@@ -305,7 +400,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
             }
             if area.width > 24 {
                 spans.push(Span::styled(
-                    format!("{:>8} ", stats),
+                    format!("{:>8} ", if is_skipped { "skipped" } else { &stats }),
                     ratatui::style::Style::default().fg(styles::DIM()),
                 ));
             }
@@ -314,6 +409,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         })
         .collect();
 
+    if tab.groups_tree() {
+        items = group_into_sections(items, viewport_slice, tab, area.width, viewport_height);
+    }
+
     // ── Watched files section ──
     if !visible_watched.is_empty() {
         // Separator
@@ -396,6 +495,109 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(list, area);
 }
 
+/// `⊕ ` for merges, `↺ ` for commits a later commit undoes.
+/// Insert a `── name reviewed/total ──` header before each section's files
+/// (package or guided-order phase), dropping rows off the top if the headers
+/// pushed the selection out of view.
+fn group_into_sections<'a>(
+    rows: Vec<ListItem<'a>>,
+    files: &[(usize, &DiffFile)],
+    tab: &TabState,
+    width: u16,
+    viewport_height: usize,
+) -> Vec<ListItem<'a>> {
+    let progress = tab.tree_group_progress();
+    let sep_width = width.saturating_sub(2) as usize;
+    let mut items = Vec::with_capacity(rows.len() + 4);
+    let mut current: Option<Option<&str>> = None;
+    let mut selected_row = None;
+    for (row, (idx, file)) in rows.into_iter().zip(files) {
+        let group = tab.tree_group(&file.path);
+        if current != Some(group) {
+            current = Some(group);
+            let entry = group.and_then(|name| progress.iter().find(|p| p.name == name));
+            let label = match entry {
+                Some(p) => format!(" {} {}/{} ", p.name, p.reviewed, p.total),
+                None => " other ".to_string(),
+            };
+            let done = entry.is_some_and(|p| p.total > 0 && p.reviewed == p.total);
+            let dashes = sep_width.saturating_sub(label.chars().count() + 2);
+            items.push(
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {}", horizontal_rule(2)),
+                        ratatui::style::Style::default().fg(styles::BORDER()),
+                    ),
+                    Span::styled(
+                        label,
+                        ratatui::style::Style::default()
+                            .fg(if done {
+                                styles::GREEN()
+                            } else {
+                                styles::PURPLE()
+                            })
+                            .add_modifier(ratatui::style::Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        horizontal_rule(dashes),
+                        ratatui::style::Style::default().fg(styles::BORDER()),
+                    ),
+                ]))
+                .style(styles::surface_style()),
+            );
+        }
+        if *idx == tab.selected_file {
+            selected_row = Some(items.len());
+        }
+        items.push(row);
+    }
+    if let Some(row) = selected_row {
+        if row >= viewport_height {
+            items.drain(..=row - viewport_height);
+        }
+    }
+    items
+}
+
+fn commit_prefix(tab: &TabState, commit: &CommitInfo) -> &'static str {
+    if commit.is_merge {
+        "⊕ "
+    } else if tab
+        .history
+        .as_ref()
+        .is_some_and(|h| h.net_zero.contains(&commit.hash))
+    {
+        "↺ "
+    } else {
+        ""
+    }
+}
+
+/// Split `ranges` (byte offsets into `text`) over the `segments` word_wrap
+/// cut it into, as offsets into each segment.
+fn wrapped_segment_ranges(
+    text: &str,
+    segments: &[String],
+    ranges: &[Range<usize>],
+) -> Vec<Vec<Range<usize>>> {
+    let mut cursor = 0;
+    segments
+        .iter()
+        .map(|segment| {
+            let start = text[cursor..]
+                .find(segment.as_str())
+                .map_or(cursor, |at| cursor + at);
+            let end = start + segment.len();
+            cursor = end.min(text.len());
+            ranges
+                .iter()
+                .filter(|r| r.start < end && r.end > start)
+                .map(|r| r.start.max(start) - start..r.end.min(end) - start)
+                .collect()
+        })
+        .collect()
+}
+
 /// Render the commit list panel (left side, History mode)
 fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
     let tab = app.tab();
@@ -403,7 +605,27 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
     let total = tab.history.as_ref().map(|h| h.commits.len()).unwrap_or(0);
     let selected_commit = tab.history.as_ref().map(|h| h.selected_commit).unwrap_or(0);
 
-    let title = format!(" COMMITS ({}) ", total);
+    let lint_count = app.history_lint_count();
+    let folded = tab
+        .history
+        .as_ref()
+        .map_or(0, |h| h.commits.iter().filter(|c| h.is_folded(c)).count());
+    let mut title = format!(" COMMITS ({}", total);
+    if folded > 0 {
+        title.push_str(&format!(" · {} folded", folded));
+    }
+    if lint_count > 0 {
+        title.push_str(&format!(" · ⚠ {}", lint_count));
+    }
+    let query = tab.commit_query().filter(|q| !q.is_empty());
+    if query.is_some() {
+        let n = visible.len();
+        title.push_str(&format!(" · {} match{}", n, if n == 1 { "" } else { "es" }));
+        if tab.history.as_ref().is_some_and(|h| h.search.loading()) {
+            title.push_str(" · searching…");
+        }
+    }
+    title.push_str(") ");
 
     // " ● " = 3 chars for the indicator prefix; leave 1 char margin on the right
     let indicator_width: usize = 3;
@@ -411,14 +633,34 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
         .saturating_sub(indicator_width + 1)
         .max(1);
 
-    // Calculate the visual height of each commit item (subject lines + author + separator)
-    let item_heights: Vec<usize> = visible
+    // Date header over the first commit of each day / week / month
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let mut last_group = None;
+    let headers: Vec<Option<DateGroup>> = visible
         .iter()
         .map(|(_, commit)| {
-            let merge_prefix = if commit.is_merge { "⊕ " } else { "" };
-            let full_subject = format!("{}{}", merge_prefix, commit.subject);
+            let group = date_group(&commit.date, now);
+            if group.is_some() && group != last_group {
+                last_group = group.clone();
+                group
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Calculate the visual height of each commit item (header + subject
+    // lines + author + separator)
+    let item_heights: Vec<usize> = visible
+        .iter()
+        .zip(&headers)
+        .map(|((_, commit), header)| {
+            let full_subject = format!("{}{}", commit_prefix(tab, commit), commit.subject);
             let subject_lines = word_wrap(&full_subject, subject_width).len().max(1);
-            subject_lines + 2 // author line + separator
+            // author line + one line per lint issue + separator
+            header.is_some() as usize + subject_lines + 2 + app.commit_lint_issues(commit).len()
         })
         .collect();
 
@@ -455,7 +697,8 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
 
     let items: Vec<ListItem> = visible_from_scroll
         .iter()
-        .flat_map(|(idx, commit)| {
+        .zip(&headers[scroll_start..])
+        .flat_map(|((idx, commit), header)| {
             let is_selected = *idx == selected_commit;
 
             let line_style = if is_selected {
@@ -465,10 +708,19 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
             };
 
             let indicator = if is_selected { "●" } else { "○" };
-            let merge_prefix = if commit.is_merge { "⊕ " } else { "" };
-            let full_subject = format!("{}{}", merge_prefix, commit.subject);
+            let prefix = commit_prefix(tab, commit);
+            let full_subject = format!("{}{}", prefix, commit.subject);
 
             let wrapped_lines = word_wrap(&full_subject, subject_width);
+            let subject_ranges: Vec<Range<usize>> = query
+                .as_ref()
+                .map(|q| q.highlights(CommitField::Subject, &commit.subject))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|r| r.start + prefix.len()..r.end + prefix.len())
+                .collect();
+            let segment_ranges =
+                wrapped_segment_ranges(&full_subject, &wrapped_lines, &subject_ranges);
 
             let indicator_style = if is_selected {
                 ratatui::style::Style::default().fg(styles::PURPLE())
@@ -483,35 +735,73 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
             let continuation_indent = " ".repeat(indicator_width);
 
             // First wrapped line: indicator + subject text
-            let first_line = Line::from(vec![
-                Span::styled(format!(" {} ", indicator), indicator_style),
-                Span::styled(
+            let mut first_line = vec![Span::styled(format!(" {} ", indicator), indicator_style)];
+            first_line.extend(mark_find_matches(
+                vec![Span::styled(
                     wrapped_lines.first().cloned().unwrap_or_default(),
                     subject_style,
-                ),
-            ]);
+                )],
+                segment_ranges.first().map_or(&[], |r| r.as_slice()),
+            ));
+            let first_line = Line::from(first_line);
 
             // Additional wrapped lines (indented to align with subject)
             let continuation_lines: Vec<ListItem> = wrapped_lines
                 .iter()
+                .zip(segment_ranges.iter())
                 .skip(1)
-                .map(|segment| {
-                    let line = Line::from(vec![
-                        Span::styled(
-                            continuation_indent.clone(),
-                            ratatui::style::Style::default(),
-                        ),
-                        Span::styled(segment.clone(), subject_style),
-                    ]);
+                .map(|(segment, ranges)| {
+                    let mut spans = vec![Span::styled(
+                        continuation_indent.clone(),
+                        ratatui::style::Style::default(),
+                    )];
+                    spans.extend(mark_find_matches(
+                        vec![Span::styled(segment.clone(), subject_style)],
+                        ranges,
+                    ));
+                    let line = Line::from(spans);
                     ListItem::new(line).style(line_style)
                 })
                 .collect();
 
             // Author line: indented, dimmed
-            let author_line = Line::from(vec![Span::styled(
-                format!("   {}", commit.author),
-                ratatui::style::Style::default().fg(styles::DIM()),
-            )]);
+            let author_ranges = query
+                .as_ref()
+                .map(|q| q.highlights(CommitField::Author, &commit.author))
+                .unwrap_or_default();
+            // Author line: initials badge, merging PR, name, age
+            let mut author_spans = vec![
+                Span::raw(" "),
+                Span::styled(
+                    format!(" {} ", author_initials(&commit.author)),
+                    ratatui::style::Style::default()
+                        .fg(styles::BG())
+                        .bg(styles::reviewer_color(&commit.author))
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ),
+                Span::raw(" "),
+            ];
+            // The PR that merged it, before the name so a narrow list keeps it
+            if let Some(pr) = tab.history.as_ref().and_then(|h| h.commit_pr(commit)) {
+                author_spans.push(Span::styled(
+                    format!("#{} ", pr),
+                    ratatui::style::Style::default().fg(styles::BLUE()),
+                ));
+            }
+            author_spans.extend(mark_find_matches(
+                vec![Span::styled(
+                    commit.author.clone(),
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                )],
+                &author_ranges,
+            ));
+            if let Some(age) = commit_age(&commit.date, now) {
+                author_spans.push(Span::styled(
+                    format!(" · {}", age),
+                    ratatui::style::Style::default().fg(styles::MUTED()),
+                ));
+            }
+            let author_line = Line::from(author_spans);
 
             // Separator line
             let separator = Line::from(Span::styled(
@@ -519,9 +809,31 @@ fn render_commit_list(f: &mut Frame, area: Rect, app: &App) {
                 ratatui::style::Style::default().fg(styles::BORDER()),
             ));
 
-            let mut result = vec![ListItem::new(first_line).style(line_style)];
+            let mut result = Vec::new();
+            if let Some(group) = header {
+                result.push(
+                    ListItem::new(Line::from(Span::styled(
+                        format!(" {}", group.label().to_uppercase()),
+                        ratatui::style::Style::default()
+                            .fg(styles::PURPLE())
+                            .add_modifier(ratatui::style::Modifier::BOLD),
+                    )))
+                    .style(styles::surface_style()),
+                );
+            }
+            result.push(ListItem::new(first_line).style(line_style));
             result.extend(continuation_lines);
             result.push(ListItem::new(author_line).style(line_style));
+            for issue in app.commit_lint_issues(commit) {
+                let text = format!("   ⚠ {}", issue.message);
+                result.push(
+                    ListItem::new(Line::from(Span::styled(
+                        truncate_to_width(&text, area.width.saturating_sub(1) as usize),
+                        ratatui::style::Style::default().fg(styles::YELLOW()),
+                    )))
+                    .style(line_style),
+                );
+            }
             result.push(ListItem::new(separator).style(styles::surface_style()));
             result
         })
@@ -721,7 +1033,17 @@ pub(crate) fn shorten_path(path: &str, max_width: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::shorten_path;
+    use super::{shorten_path, word_wrap, wrapped_segment_ranges, Range};
+
+    #[test]
+    fn highlights_follow_the_wrapped_subject() {
+        let text = "Fix login redirect loop";
+        let segments = word_wrap(text, 10);
+        assert_eq!(segments, ["Fix login", "redirect", "loop"]);
+        // "login redirect"
+        let ranges = wrapped_segment_ranges(text, &segments, &[Range { start: 4, end: 18 }]);
+        assert_eq!(ranges, vec![vec![4..9], vec![0..8], vec![]]);
+    }
 
     #[test]
     fn path_shorter_than_max_width_returned_as_is() {
@@ -1,22 +1,68 @@
+mod accessibility;
 mod diff_view;
 mod file_tree;
 pub mod highlight;
 mod overlay;
 pub mod panel;
 mod settings;
+#[cfg(test)]
+mod snapshots;
 mod status_bar;
 mod styles;
 pub mod themes;
 mod utils;
 
+use er_engine::ai::PanelContent;
+use er_engine::app::layout::LayoutMode;
 use er_engine::app::{App, OverlayData};
 use highlight::Highlighter;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::widgets::Clear;
 use ratatui::Frame;
 
+/// A drawn frame as plain text, one line per row with trailing blanks
+/// trimmed (snapshot tests, `--replay`).
+pub fn screen_text(buffer: &ratatui::buffer::Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        for x in area.left()..area.right() {
+            row.push_str(buffer[(x, y)].symbol());
+        }
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// `--profile` frame-time overlay, drawn over the finished frame.
+pub fn draw_frame_stats(f: &mut Frame, stats: &er_engine::profile::FrameStats) {
+    status_bar::render_frame_stats(f, f.area(), stats);
+}
+
+/// `panels` side by side in `area`, splitting it evenly along `direction`.
+fn render_panels(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    panels: &[PanelContent],
+    direction: Direction,
+) {
+    let share = 100 / panels.len().max(1) as u16;
+    let areas = Layout::default()
+        .direction(direction)
+        .constraints(panels.iter().map(|_| Constraint::Percentage(share)))
+        .split(area);
+    for (&content, &area) in panels.iter().zip(areas.iter()) {
+        panel::render_panel(f, area, app, content);
+    }
+}
+
 /// Render the entire UI
 pub fn draw(f: &mut Frame, app: &App, hl: &mut Highlighter) {
-    themes::set_theme_by_name(&app.config.display.theme);
+    let a11y = &app.config.accessibility;
+    themes::set_theme_by_name(a11y.theme(&app.config.display.theme));
     let top_height = status_bar::top_bar_height(app, f.area().width);
 
     let bottom_height = status_bar::bottom_bar_height(app, f.area().width);
@@ -43,34 +89,72 @@ pub fn draw(f: &mut Frame, app: &App, hl: &mut Highlighter) {
     // Top bar
     status_bar::render_top_bar(f, outer[0], app);
 
-    // Main content — layout depends on mode and panel state
-    let tab = app.tab();
+    // Main content — columns depend on terminal width and panel state
+    let layout = er_engine::app::layout::main_layout(app, outer[1].width);
+    let mut render_diff = |f: &mut Frame, area: Rect| {
+        if layout.right.is_empty() && app.split_diff_active(&app.config) {
+            diff_view::render_split(f, area, app, hl, &app.config);
+        } else {
+            diff_view::render(f, area, app, hl);
+        }
+    };
+    // The diff column, with any bottom-docked panels under the diff
+    let mut render_diff_column = |f: &mut Frame, area: Rect| {
+        if layout.bottom.is_empty() {
+            render_diff(f, area);
+            return;
+        }
+        let percent = app.config.layout.bottom_percent.clamp(10, 90) as u16;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(100 - percent),
+                Constraint::Percentage(percent),
+            ])
+            .split(area);
+        render_diff(f, rows[0]);
+        render_panels(f, rows[1], app, &layout.bottom, Direction::Horizontal);
+    };
 
-    if tab.panel.is_some() && outer[1].width >= (tab.file_tree_width + tab.panel_width + 20) {
-        // 3-col layout: file_tree + diff + panel
+    if !layout.diff {
+        // Zoomed panel
+        render_panels(f, outer[1], app, &layout.right, Direction::Vertical);
+    } else if layout.mode == LayoutMode::Narrow {
+        // Single pane: panels stacked under the diff, file tree as a drawer
+        render_diff_column(f, outer[1]);
+        if layout.tree > 0 {
+            let drawer = Rect {
+                width: layout.tree,
+                ..outer[1]
+            };
+            f.render_widget(Clear, drawer);
+            file_tree::render(f, drawer, app);
+        }
+    } else if !layout.right.is_empty() {
+        // 3-col layout: file_tree + diff + panel column
         let main_area = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Length(tab.file_tree_width),
+                Constraint::Length(layout.tree),
                 Constraint::Min(20),
-                Constraint::Length(tab.panel_width),
+                Constraint::Length(layout.panel_width),
             ])
             .split(outer[1]);
-        file_tree::render(f, main_area[0], app);
-        diff_view::render(f, main_area[1], app, hl);
-        panel::render(f, main_area[2], app);
+        if layout.tree > 0 {
+            file_tree::render(f, main_area[0], app);
+        }
+        render_diff_column(f, main_area[1]);
+        render_panels(f, main_area[2], app, &layout.right, Direction::Vertical);
     } else {
         // 2-col layout: file_tree + diff
         let main_area = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(tab.file_tree_width), Constraint::Min(1)])
+            .constraints([Constraint::Length(layout.tree), Constraint::Min(1)])
             .split(outer[1]);
-        file_tree::render(f, main_area[0], app);
-        if app.split_diff_active(&app.config) {
-            diff_view::render_split(f, main_area[1], app, hl, &app.config);
-        } else {
-            diff_view::render(f, main_area[1], app, hl);
+        if layout.tree > 0 {
+            file_tree::render(f, main_area[0], app);
         }
+        render_diff_column(f, main_area[1]);
     }
 
     // Bottom status bar
@@ -78,7 +162,7 @@ pub fn draw(f: &mut Frame, app: &App, hl: &mut Highlighter) {
 
     // Watch notification overlay
     if let Some(ref msg) = app.watch_message {
-        status_bar::render_watch_notification(f, f.area(), msg);
+        status_bar::render_watch_notification(f, f.area(), msg, app.watch_message_level);
     }
 
     // Popup overlay (worktree picker, directory browser, config hub)
@@ -98,4 +182,15 @@ pub fn draw(f: &mut Frame, app: &App, hl: &mut Highlighter) {
             }
         }
     }
+
+    if let Some(prompt) = app.current_confirm() {
+        overlay::render_confirm(f, f.area(), &prompt);
+    }
+
+    if a11y.ascii {
+        accessibility::ascii_glyphs(f.buffer_mut());
+    }
+    if a11y.no_color_active() {
+        accessibility::strip_colors(f.buffer_mut(), &themes::current());
+    }
 }
@@ -1,12 +1,16 @@
 use ratatui::{
     layout::Rect,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
 use super::styles;
-use er_engine::app::{DirEntry, HubItem, HubKind, OverlayData, Worktree};
+use er_engine::app::{
+    ConfirmPrompt, DirEntry, HubItem, HubKind, NotificationEntry, OverlayData, Worktree,
+    ONBOARDING_SECTIONS,
+};
+use er_engine::command::CommandFailure;
 
 /// Render the active overlay on top of the main UI
 /// Note: ConfigHub overlay is rendered separately in ui/mod.rs since it needs App access.
@@ -15,8 +19,13 @@ pub fn render_overlay(f: &mut Frame, area: Rect, overlay: &OverlayData) {
         OverlayData::WorktreePicker {
             worktrees,
             selected,
+            apply,
         } => {
-            render_worktree_picker(f, area, worktrees, *selected);
+            let title = match apply {
+                Some(_) => " APPLY HUNK TO (Enter=apply, Esc=close) ",
+                None => " WORKTREES (Enter=select, Esc=close) ",
+            };
+            render_worktree_picker(f, area, worktrees, *selected, title);
         }
         OverlayData::DirectoryBrowser {
             current_path,
@@ -43,10 +52,29 @@ pub fn render_overlay(f: &mut Frame, area: Rect, overlay: &OverlayData) {
         } => {
             render_modal_hub(f, area, *kind, title.as_deref(), items, *selected);
         }
+        OverlayData::NotificationLog { entries, selected } => {
+            render_notification_log(f, area, entries, *selected);
+        }
+        OverlayData::Onboarding { scroll } => {
+            render_onboarding(f, area, *scroll);
+        }
+        OverlayData::CommandError {
+            title,
+            failure,
+            scroll,
+        } => {
+            render_command_error(f, area, title, failure, *scroll);
+        }
     }
 }
 
-fn render_worktree_picker(f: &mut Frame, area: Rect, worktrees: &[Worktree], selected: usize) {
+fn render_worktree_picker(
+    f: &mut Frame,
+    area: Rect,
+    worktrees: &[Worktree],
+    selected: usize,
+    title: &str,
+) {
     let popup_height = (worktrees.len() as u16 + 2).min(area.height.saturating_sub(6));
     let popup_width = 70u16.min(area.width.saturating_sub(6));
     let popup = centered_rect(popup_width, popup_height, area);
@@ -86,7 +114,7 @@ fn render_worktree_picker(f: &mut Frame, area: Rect, worktrees: &[Worktree], sel
 
     let block = Block::default()
         .title(Span::styled(
-            " WORKTREES (Enter=select, Esc=close) ",
+            title,
             ratatui::style::Style::default().fg(styles::CYAN()),
         ))
         .borders(Borders::ALL)
@@ -189,7 +217,7 @@ fn render_directory_browser(
         .collect();
 
     // Shorten path for title if too long, using char counts to avoid UTF-8 byte-boundary panics.
-    let max_title_width = popup_width.saturating_sub(20) as usize;
+    let max_title_width = popup_width.saturating_sub(32) as usize;
     let title_path = {
         let char_count = current_path.chars().count();
         if char_count > max_title_width {
@@ -209,7 +237,10 @@ fn render_directory_browser(
 
     let block = Block::default()
         .title(Span::styled(
-            format!(" {} (Enter=open, Bksp=up, Esc=close) ", title_path),
+            format!(
+                " {} (Enter=open, Bksp=up, o=URL/patch, Esc=close) ",
+                title_path
+            ),
             ratatui::style::Style::default().fg(styles::CYAN()),
         ))
         .borders(Borders::ALL)
@@ -328,6 +359,285 @@ fn render_filter_history(
     f.render_widget(list, popup);
 }
 
+/// Notifications pane: one row per message (newest first) above the full,
+/// wrapped text of the selected one.
+fn render_notification_log(
+    f: &mut Frame,
+    area: Rect,
+    entries: &[NotificationEntry],
+    selected: usize,
+) {
+    let popup_width = 90u16.min(area.width.saturating_sub(6));
+    let popup_height = area.height.saturating_sub(6).clamp(6, 30);
+    let popup = centered_rect(popup_width, popup_height, area);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(
+                " NOTIFICATIONS ({}) Enter/y=copy x=clear Esc=close ",
+                entries.len()
+            ),
+            ratatui::style::Style::default().fg(styles::CYAN()),
+        ))
+        .borders(Borders::ALL)
+        .border_style(ratatui::style::Style::default().fg(styles::CYAN()))
+        .style(ratatui::style::Style::default().bg(styles::PANEL()));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // Bottom third shows the selected message in full (top border included)
+    let detail_height = (inner.height / 3).max(4).min(inner.height);
+    let list_area = Rect {
+        height: inner.height - detail_height,
+        ..inner
+    };
+    let detail_area = Rect {
+        y: list_area.y + list_area.height,
+        height: detail_height,
+        ..inner
+    };
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            let is_sel = idx == selected;
+            let first_line = entry.message.lines().next().unwrap_or("");
+            let line = Line::from(vec![
+                Span::styled(
+                    if is_sel { "▶ " } else { "  " },
+                    ratatui::style::Style::default().fg(styles::CYAN()),
+                ),
+                Span::styled(
+                    format!("{} ", entry.time()),
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled(
+                    format!("{:<6}", entry.level.label()),
+                    ratatui::style::Style::default()
+                        .fg(super::status_bar::level_color(entry.level)),
+                ),
+                Span::styled(
+                    first_line,
+                    if is_sel {
+                        ratatui::style::Style::default().fg(styles::BRIGHT())
+                    } else {
+                        ratatui::style::Style::default().fg(styles::TEXT())
+                    },
+                ),
+            ]);
+            let style = if is_sel {
+                styles::selected_style()
+            } else {
+                ratatui::style::Style::default().bg(styles::PANEL())
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+    let mut state = ratatui::widgets::ListState::default().with_selected(Some(selected));
+    f.render_stateful_widget(List::new(items), list_area, &mut state);
+
+    if let Some(entry) = entries.get(selected) {
+        let detail = Paragraph::new(entry.message.as_str())
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(ratatui::style::Style::default().fg(styles::MUTED()))
+                    .title(Span::styled(
+                        format!(" {} ", entry.timestamp),
+                        ratatui::style::Style::default().fg(styles::DIM()),
+                    )),
+            )
+            .style(ratatui::style::Style::default().fg(styles::TEXT()))
+            .wrap(Wrap { trim: false });
+        f.render_widget(detail, detail_area);
+    }
+}
+
+/// First-run guide: one section per topic, keys in a fixed-width column.
+fn render_onboarding(f: &mut Frame, area: Rect, scroll: u16) {
+    let popup_width = 76u16.min(area.width.saturating_sub(6));
+    let popup_height = area.height.saturating_sub(4).min(32);
+    let popup = centered_rect(popup_width, popup_height, area);
+    f.render_widget(Clear, popup);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Welcome to easy-review — review diffs file by file, with AI findings inline.",
+            ratatui::style::Style::default().fg(styles::BRIGHT()),
+        )),
+        Line::raw(""),
+    ];
+    for (section, entries) in ONBOARDING_SECTIONS {
+        lines.push(Line::from(Span::styled(
+            format!("── {} ──", section),
+            ratatui::style::Style::default()
+                .fg(styles::CYAN())
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        )));
+        for (key, text) in entries.iter() {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<16}", key),
+                    ratatui::style::Style::default().fg(styles::YELLOW()),
+                ),
+                Span::styled(*text, ratatui::style::Style::default().fg(styles::TEXT())),
+            ]));
+        }
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "Run `er --onboarding` or pick \"Getting started\" in the help hub (?) to see this again.",
+        ratatui::style::Style::default().fg(styles::DIM()),
+    )));
+
+    let block = Block::default()
+        .title(Span::styled(
+            " GETTING STARTED (Enter/Esc=dismiss, j/k=scroll) ",
+            ratatui::style::Style::default().fg(styles::CYAN()),
+        ))
+        .borders(Borders::ALL)
+        .border_style(ratatui::style::Style::default().fg(styles::CYAN()))
+        .style(ratatui::style::Style::default().bg(styles::PANEL()));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, popup);
+}
+
+/// Error detail overlay: the failed invocation, its exit code and full stderr.
+fn render_command_error(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    failure: &CommandFailure,
+    scroll: u16,
+) {
+    let popup_width = 100u16.min(area.width.saturating_sub(6));
+    let popup_height = area.height.saturating_sub(6).clamp(8, 30);
+    let popup = centered_rect(popup_width, popup_height, area);
+    f.render_widget(Clear, popup);
+
+    let label = ratatui::style::Style::default().fg(styles::DIM());
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("$ ", label),
+            Span::styled(
+                failure.command_line(),
+                ratatui::style::Style::default().fg(styles::BRIGHT()),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("exit ", label),
+            Span::styled(
+                failure.exit_label(),
+                ratatui::style::Style::default().fg(styles::RED()),
+            ),
+            Span::styled(
+                failure
+                    .cwd
+                    .as_deref()
+                    .map(|cwd| format!("  in {}", cwd))
+                    .unwrap_or_default(),
+                label,
+            ),
+        ]),
+    ];
+    for (name, text) in [("stderr", &failure.stderr), ("stdout", &failure.stdout)] {
+        if text.trim().is_empty() {
+            continue;
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(format!("{}:", name), label)));
+        lines.extend(text.trim_end().lines().map(|l| {
+            Line::from(Span::styled(
+                l.to_string(),
+                ratatui::style::Style::default().fg(styles::TEXT()),
+            ))
+        }));
+    }
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} — y/Enter=copy Esc=close ", title),
+            ratatui::style::Style::default().fg(styles::RED()),
+        ))
+        .borders(Borders::ALL)
+        .border_style(ratatui::style::Style::default().fg(styles::RED()))
+        .style(ratatui::style::Style::default().bg(styles::PANEL()));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, popup);
+}
+
+/// y/n popup for a pending confirm; red with ⚠ when the action is destructive.
+pub fn render_confirm(f: &mut Frame, area: Rect, prompt: &ConfirmPrompt) {
+    let accent = if prompt.danger {
+        styles::RED()
+    } else {
+        styles::YELLOW()
+    };
+    let popup_width = 64u16.min(area.width.saturating_sub(4));
+    let popup_height = (prompt.details.len() as u16 + 6).min(area.height.saturating_sub(2));
+    let popup = centered_rect(popup_width, popup_height, area);
+    f.render_widget(Clear, popup);
+
+    let mut lines = vec![Line::from(Span::styled(
+        prompt.title.clone(),
+        ratatui::style::Style::default()
+            .fg(styles::BRIGHT())
+            .add_modifier(ratatui::style::Modifier::BOLD),
+    ))];
+    lines.extend(prompt.details.iter().map(|d| {
+        Line::from(Span::styled(
+            format!("  {}", d),
+            ratatui::style::Style::default().fg(styles::TEXT()),
+        ))
+    }));
+    lines.push(Line::raw(""));
+    let mut keys = Vec::new();
+    for (key, label) in &prompt.keys {
+        keys.push(Span::styled(
+            format!(" {} ", key),
+            ratatui::style::Style::default()
+                .fg(styles::BG())
+                .bg(if *key == 'y' { accent } else { styles::DIM() })
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+        keys.push(Span::styled(
+            format!(" {}   ", label),
+            ratatui::style::Style::default().fg(styles::TEXT()),
+        ));
+    }
+    keys.push(Span::styled(
+        "Esc cancel",
+        ratatui::style::Style::default().fg(styles::DIM()),
+    ));
+    lines.push(Line::from(keys));
+
+    let title = if prompt.danger {
+        " ⚠ Confirm "
+    } else {
+        " Confirm "
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            ratatui::style::Style::default().fg(accent),
+        ))
+        .borders(Borders::ALL)
+        .border_style(ratatui::style::Style::default().fg(accent))
+        .style(ratatui::style::Style::default().bg(styles::PANEL()));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, popup);
+}
+
 // Use the shared centered_rect from utils (deduplicated from overlay + settings)
 use super::utils::centered_rect;
 
@@ -361,6 +671,27 @@ fn render_modal_hub(
         HubKind::Help => styles::CYAN(),
         HubKind::Open => styles::BLUE(),
         HubKind::Copy => styles::CYAN(),
+        HubKind::Drafts => styles::YELLOW(),
+        HubKind::Checklist => styles::CYAN(),
+        HubKind::CommentConflicts => styles::RED(),
+        HubKind::Reviews => styles::YELLOW(),
+        HubKind::Audit => styles::GREEN(),
+        HubKind::Authors => styles::BLUE(),
+        HubKind::Packages => styles::PURPLE(),
+        HubKind::Sessions => styles::GREEN(),
+        HubKind::Todos => styles::YELLOW(),
+        HubKind::Spelling => styles::ORANGE(),
+        HubKind::CommitPlan => styles::GREEN(),
+        HubKind::Script => styles::CYAN(),
+        HubKind::LostComments => styles::RED(),
+        HubKind::Marks => styles::CYAN(),
+        HubKind::Releases => styles::PURPLE(),
+        HubKind::Branches => styles::GREEN(),
+        HubKind::Compaction => styles::YELLOW(),
+        HubKind::SizeGuard => styles::RED(),
+        HubKind::Snooze => styles::CYAN(),
+        HubKind::Selection => styles::PURPLE(),
+        HubKind::BugReport => styles::YELLOW(),
     };
 
     let list_items: Vec<ListItem> = items
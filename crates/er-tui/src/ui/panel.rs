@@ -1,6 +1,6 @@
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
     Frame,
@@ -8,7 +8,7 @@ use ratatui::{
 
 use super::file_tree::shorten_path;
 use super::styles;
-use super::utils::{horizontal_rule, word_wrap};
+use super::utils::{horizontal_rule, truncate_to_width, word_wrap};
 use er_engine::ai::{CommentRef, CommentType, Confidence, PanelContent, ReviewFocus, RiskLevel};
 use er_engine::app::App;
 
@@ -35,23 +35,13 @@ fn review_state_style(state: &str) -> (&'static str, ratatui::style::Color) {
 }
 
 /// Render the context panel (right side, when tab.panel is Some)
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
-    let tab = app.tab();
-    let panel_content = match &tab.panel {
-        Some(c) => *c,
-        None => return,
-    };
-
-    render_panel(f, area, app, panel_content);
-}
-
-fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
+pub fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
     let tab = app.tab();
     let mut lines: Vec<Line> = Vec::new();
 
     // Title bar: [File] [AI] [PR] — AI and PR only shown when available
     let has_ai = tab.ai.has_data();
-    let has_pr = tab.pr_data.is_some();
+    let has_pr = tab.pr_data.is_some() || tab.release_notes().is_some();
 
     let file_style = if content == PanelContent::FileDetail {
         Style::default()
@@ -89,7 +79,13 @@ fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
             Style::default().fg(styles::DIM())
         };
         tab_spans.push(Span::styled(" [", Style::default().fg(styles::MUTED())));
-        tab_spans.push(Span::styled("PR", pr_style));
+        // A tag range tab shows its release notes in the PR slot
+        let label = if tab.pr_data.is_none() {
+            "Release"
+        } else {
+            "PR"
+        };
+        tab_spans.push(Span::styled(label, pr_style));
         tab_spans.push(Span::styled("]", Style::default().fg(styles::MUTED())));
     }
 
@@ -106,6 +102,59 @@ fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
         tab_spans.push(Span::styled("]", Style::default().fg(styles::MUTED())));
     }
 
+    if !tab.operational.is_empty() {
+        let ops_style = if content == PanelContent::Operational {
+            Style::default()
+                .fg(styles::PURPLE())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(styles::YELLOW())
+        };
+        tab_spans.push(Span::styled(" [", Style::default().fg(styles::MUTED())));
+        tab_spans.push(Span::styled(
+            format!("Ops {}", tab.operational.len()),
+            ops_style,
+        ));
+        tab_spans.push(Span::styled("]", Style::default().fg(styles::MUTED())));
+    }
+
+    if !tab.assets.assets.is_empty() {
+        let assets_style = if content == PanelContent::Assets {
+            Style::default()
+                .fg(styles::PURPLE())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(styles::DIM())
+        };
+        tab_spans.push(Span::styled(" [", Style::default().fg(styles::MUTED())));
+        tab_spans.push(Span::styled(
+            format!("Assets {}", tab.assets.assets.len()),
+            assets_style,
+        ));
+        tab_spans.push(Span::styled("]", Style::default().fg(styles::MUTED())));
+    }
+
+    if !tab.is_remote() && tab.selected_diff_file().is_some() {
+        let open_prs = tab.selected_related_changes().map_or(0, |r| r.open_prs());
+        let related_style = if content == PanelContent::RelatedChanges {
+            Style::default()
+                .fg(styles::PURPLE())
+                .add_modifier(Modifier::BOLD)
+        } else if open_prs > 0 {
+            Style::default().fg(styles::YELLOW())
+        } else {
+            Style::default().fg(styles::DIM())
+        };
+        let label = if open_prs > 0 {
+            format!("Related {}", open_prs)
+        } else {
+            "Related".to_string()
+        };
+        tab_spans.push(Span::styled(" [", Style::default().fg(styles::MUTED())));
+        tab_spans.push(Span::styled(label, related_style));
+        tab_spans.push(Span::styled("]", Style::default().fg(styles::MUTED())));
+    }
+
     let log_style = if content == PanelContent::AgentLog {
         Style::default()
             .fg(styles::PURPLE())
@@ -125,14 +174,23 @@ fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
 
     // Content area
     match content {
-        PanelContent::FileDetail => render_file_detail(&mut lines, area, tab),
+        PanelContent::FileDetail => {
+            let hints = tab
+                .selected_diff_file()
+                .map(|f| app.review_hints_for(&f.path))
+                .unwrap_or_default();
+            render_file_detail(&mut lines, area, tab, &hints, &app.config.complexity)
+        }
         PanelContent::AiSummary => render_ai_summary(&mut lines, area, tab),
         PanelContent::PrOverview => render_pr_overview(&mut lines, area, tab),
         PanelContent::SymbolRefs => render_symbol_refs(&mut lines, area, tab),
+        PanelContent::Operational => render_operational(&mut lines, area, tab),
+        PanelContent::Assets => render_assets(&mut lines, area, tab),
+        PanelContent::RelatedChanges => render_related_changes(&mut lines, area, tab),
         PanelContent::AgentLog => render_agent_log(&mut lines, area, tab),
     }
 
-    let border_style = if tab.panel_focus {
+    let border_style = if tab.panel_has_focus(content) {
         Style::default().fg(styles::PURPLE())
     } else {
         Style::default().fg(styles::BORDER())
@@ -146,17 +204,358 @@ fn render_panel(f: &mut Frame, area: Rect, app: &App, content: PanelContent) {
 
     let paragraph = Paragraph::new(lines)
         .block(block)
-        .scroll((tab.panel_scroll, 0));
+        .scroll((tab.scroll_of_panel(content), 0));
 
     f.render_widget(paragraph, area);
 }
 
 // ── FileDetail ──
 
+/// Owners to list before folding the rest into "+N more"
+const OWNERS_SHOWN: usize = 5;
+
+/// Changed functions listed in the File Detail panel, most complex first.
+const FUNCTIONS_SHOWN: usize = 8;
+
+/// Length, nesting and complexity of the file's changed functions, the
+/// ones over a `[complexity]` budget in yellow.
+fn render_function_metrics(
+    lines: &mut Vec<Line<'_>>,
+    functions: &[er_engine::git::FunctionMetrics],
+    budget: &er_engine::config::ComplexityConfig,
+    max_w: usize,
+) {
+    if functions.is_empty() {
+        return;
+    }
+    let over_count = functions
+        .iter()
+        .filter(|f| budget.over(f).contains(&true))
+        .count();
+    let header = if over_count > 0 {
+        format!(
+            " ─── Changed functions · {} · {} over budget ───",
+            functions.len(),
+            over_count
+        )
+    } else {
+        format!(" ─── Changed functions · {} ───", functions.len())
+    };
+    lines.push(Line::from(vec![Span::styled(
+        header,
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let mut sorted: Vec<&er_engine::git::FunctionMetrics> = functions.iter().collect();
+    sorted.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| b.length().cmp(&a.length()))
+    });
+    let name_w = max_w.saturating_sub(24).clamp(8, 32);
+    for f in sorted.iter().take(FUNCTIONS_SHOWN) {
+        let over = budget.over(f);
+        let metric = |text: String, over: bool| {
+            Span::styled(
+                text,
+                Style::default().fg(if over {
+                    styles::YELLOW()
+                } else {
+                    styles::DIM()
+                }),
+            )
+        };
+        let name: String = if f.name.chars().count() > name_w {
+            let head: String = f.name.chars().take(name_w - 1).collect();
+            format!("{}…", head)
+        } else {
+            f.name.clone()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<name_w$}", name),
+                Style::default().fg(styles::TEXT()),
+            ),
+            metric(format!(" {:>4}L", f.length()), over[0]),
+            metric(format!(" d{:<2}", f.nesting), over[1]),
+            metric(format!(" cc{:<3}", f.complexity), over[2]),
+        ]));
+    }
+    if sorted.len() > FUNCTIONS_SHOWN {
+        lines.push(Line::from(vec![Span::styled(
+            format!(" +{} more", sorted.len() - FUNCTIONS_SHOWN),
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    lines.push(Line::from(""));
+}
+
+/// The selected migration's down side and destructive statements.
+fn render_migration_check(
+    lines: &mut Vec<Line<'_>>,
+    check: &er_engine::git::MigrationCheck,
+    max_w: usize,
+) {
+    use er_engine::git::DownMigration;
+
+    lines.push(Line::from(vec![Span::styled(
+        " ─── Migration ───",
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let (icon, color, down) = match &check.down {
+        DownMigration::InFile => (
+            "✓",
+            styles::GREEN(),
+            "down section in this file".to_string(),
+        ),
+        DownMigration::Sibling(path) => (
+            "✓",
+            styles::GREEN(),
+            format!("down: {}", shorten_path(path, max_w.saturating_sub(10))),
+        ),
+        DownMigration::IsDown => ("↺", styles::DIM(), "this is a down migration".to_string()),
+        DownMigration::Missing => ("✗", styles::RED_TEXT(), "no down migration".to_string()),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+        Span::styled(down, Style::default().fg(styles::TEXT())),
+    ]));
+    for d in &check.destructive {
+        let color = if d.data_loss {
+            styles::RED_TEXT()
+        } else {
+            styles::YELLOW()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" ⚠ {:>4} ", d.line),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(d.statement, Style::default().fg(color)),
+        ]));
+    }
+    lines.push(Line::from(""));
+}
+
+/// The semantic diff of a changed `.proto` or OpenAPI file, breaking
+/// changes first.
+fn render_contract_changes(
+    lines: &mut Vec<Line<'_>>,
+    contract: &er_engine::app::ContractDiff,
+    max_w: usize,
+) {
+    let breaking = contract.breaking();
+    let mut title = format!(" ─── Contract ({})", contract.kind.label());
+    if breaking > 0 {
+        title.push_str(&format!(" · {} breaking", breaking));
+    }
+    title.push_str(&format!(" · {} changes ───", contract.changes.len()));
+    lines.push(Line::from(vec![Span::styled(
+        title,
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    if let Some(error) = &contract.error {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                " couldn't compare: {}",
+                truncate_to_width(error, max_w.saturating_sub(18))
+            ),
+            Style::default().fg(styles::MUTED()),
+        )]));
+    } else if contract.changes.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " no contract changes",
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    for change in &contract.changes {
+        let color = if change.breaking {
+            styles::RED_TEXT()
+        } else {
+            styles::TEXT()
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" {} ", change.kind.symbol()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(change.subject.clone(), Style::default().fg(color)),
+        ];
+        if !change.detail.is_empty() {
+            let room = max_w.saturating_sub(change.subject.chars().count() + 5);
+            spans.push(Span::styled(
+                format!("  {}", truncate_to_width(&change.detail, room)),
+                Style::default().fg(styles::MUTED()),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+}
+
+/// Resources a Terraform or Kubernetes file creates, modifies and
+/// destroys, then its dangerous fields.
+fn render_infra_changes(
+    lines: &mut Vec<Line<'_>>,
+    infra: &er_engine::app::InfraDiff,
+    max_w: usize,
+) {
+    use er_engine::analysis::infra::Action;
+
+    let summary = &infra.summary;
+    let count = |action: Action| {
+        summary
+            .changes
+            .iter()
+            .filter(|c| c.action == action)
+            .count()
+    };
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            " ─── {} · +{} ~{} -{} ───",
+            infra.kind.label(),
+            count(Action::Create),
+            count(Action::Update),
+            count(Action::Destroy)
+        ),
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    if let Some(error) = &infra.error {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                " couldn't compare: {}",
+                truncate_to_width(error, max_w.saturating_sub(18))
+            ),
+            Style::default().fg(styles::MUTED()),
+        )]));
+    } else if summary.changes.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " no resource changes",
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    for change in &summary.changes {
+        let color = match change.action {
+            Action::Create => styles::GREEN(),
+            Action::Destroy => styles::RED_TEXT(),
+            Action::Update => styles::YELLOW(),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" {} ", change.action.symbol()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(change.address.clone(), Style::default().fg(styles::TEXT())),
+        ];
+        if !change.fields.is_empty() {
+            let room = max_w.saturating_sub(change.address.chars().count() + 5);
+            spans.push(Span::styled(
+                format!("  {}", truncate_to_width(&change.fields.join(", "), room)),
+                Style::default().fg(styles::MUTED()),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    for danger in &summary.dangers {
+        let color = if danger.security {
+            styles::RED_TEXT()
+        } else {
+            styles::YELLOW()
+        };
+        let text = format!("{} · {}", danger.field, danger.reason);
+        lines.push(Line::from(vec![
+            Span::styled(
+                " ⚠ ",
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                truncate_to_width(&text, max_w.saturating_sub(3)),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+}
+
+/// Top historical authors of the file, and how well the change's author
+/// knows it.
+fn render_file_ownership(lines: &mut Vec<Line<'_>>, ownership: &er_engine::git::FileOwnership) {
+    if ownership.owners.is_empty() {
+        return;
+    }
+    lines.push(Line::from(vec![Span::styled(
+        format!(" ─── Ownership · {} commits ───", ownership.total_commits),
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let name_w = ownership
+        .owners
+        .iter()
+        .take(OWNERS_SHOWN)
+        .map(|o| o.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for owner in ownership.owners.iter().take(OWNERS_SHOWN) {
+        let share = owner.commits * 100 / ownership.total_commits.max(1);
+        let is_author = ownership.change_author.as_deref() == Some(owner.name.as_str());
+        let name_style = if is_author {
+            Style::default()
+                .fg(styles::GREEN())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(styles::TEXT())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<name_w$}", owner.name), name_style),
+            Span::styled(
+                format!(" {:>3} {:>3}%", owner.commits, share),
+                Style::default().fg(styles::DIM()),
+            ),
+            Span::styled(
+                format!("  {}", owner.last_touched),
+                Style::default().fg(styles::MUTED()),
+            ),
+        ]));
+    }
+    if ownership.owners.len() > OWNERS_SHOWN {
+        lines.push(Line::from(vec![Span::styled(
+            format!(" +{} more", ownership.owners.len() - OWNERS_SHOWN),
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    if let Some(author) = &ownership.change_author {
+        let (note, color) = match ownership.change_author_commits() {
+            0 => (
+                format!(" {} hasn't touched this file before", author),
+                styles::YELLOW(),
+            ),
+            n => (
+                format!(" {}: {} of {} commits", author, n, ownership.total_commits),
+                styles::DIM(),
+            ),
+        };
+        lines.push(Line::from(vec![Span::styled(
+            note,
+            Style::default().fg(color),
+        )]));
+    }
+    lines.push(Line::from(""));
+}
+
 fn render_file_detail<'a>(
     lines: &mut Vec<Line<'a>>,
     area: Rect,
     tab: &'a er_engine::app::TabState,
+    hints: &[String],
+    budget: &er_engine::config::ComplexityConfig,
 ) {
     let ai_stale = tab.ai.is_stale;
 
@@ -186,6 +585,77 @@ fn render_file_detail<'a>(
     }
     lines.push(Line::from(""));
 
+    // Checklist from [review_hints] for files like this one
+    if !hints.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " ─── Review hints ───",
+            Style::default()
+                .fg(styles::YELLOW())
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for hint in hints {
+            for (i, wrapped) in word_wrap(hint, max_w.saturating_sub(2))
+                .into_iter()
+                .enumerate()
+            {
+                let bullet = if i == 0 { "•" } else { " " };
+                lines.push(Line::from(vec![Span::styled(
+                    format!(" {} {}", bullet, wrapped),
+                    Style::default().fg(styles::TEXT()),
+                )]));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    // What the current hunk does, when asked with H
+    let explanation = tab.current_hunk_explanation();
+    if explanation.is_some() || tab.explaining_current_hunk() {
+        lines.push(Line::from(vec![Span::styled(
+            format!(" ─── Hunk {} explained ───", tab.current_hunk + 1),
+            Style::default()
+                .fg(styles::PURPLE())
+                .add_modifier(Modifier::BOLD),
+        )]));
+        match explanation {
+            Some(text) => {
+                for paragraph in text.lines() {
+                    for wrapped in word_wrap(paragraph, max_w) {
+                        lines.push(Line::from(vec![Span::styled(
+                            format!(" {}", wrapped),
+                            Style::default().fg(styles::TEXT()),
+                        )]));
+                    }
+                }
+            }
+            None => lines.push(Line::from(vec![Span::styled(
+                " explaining…",
+                Style::default().fg(styles::MUTED()),
+            )])),
+        }
+        lines.push(Line::from(""));
+    }
+
+    if let Some(contract) = tab.contract_changes(path) {
+        render_contract_changes(lines, contract, max_w);
+    }
+
+    if let Some(infra) = tab.infra_changes(path) {
+        render_infra_changes(lines, infra, max_w);
+    }
+
+    if let Some(ownership) = tab.file_ownership.get(path) {
+        render_file_ownership(lines, ownership);
+    }
+
+    if let Some(check) = tab.migration_check(path) {
+        render_migration_check(lines, check, max_w);
+    }
+
+    if let Some(functions) = tab.changed_function_metrics(path) {
+        render_function_metrics(lines, functions, budget, max_w);
+    }
+
     // AI file risk (assessment metadata — distinct from line-anchored findings below)
     if tab.layers.show_ai_findings {
         if let Some(fr) = tab.ai.file_review(path) {
@@ -242,6 +712,12 @@ fn render_file_detail<'a>(
             if !fr.findings.is_empty() {
                 let file_stale = tab.ai.is_file_stale(path);
                 let max_w = area.width.saturating_sub(5) as usize;
+                let hunks = tab
+                    .files
+                    .iter()
+                    .find(|f| f.path == path)
+                    .map(|f| f.hunks.as_slice())
+                    .unwrap_or_default();
 
                 let mut sorted_findings: Vec<&er_engine::ai::Finding> =
                     fr.findings.iter().collect();
@@ -293,6 +769,7 @@ fn render_file_detail<'a>(
                         styles::SURFACE()
                     };
                     let prefix = if is_focused { "▸" } else { " " };
+                    let suppressed = tab.ai.is_suppressed(path, finding, hunks);
 
                     let sev_style = if finding.resolved {
                         // Resolved findings render dimmed + strikethrough regardless of severity.
@@ -300,6 +777,8 @@ fn render_file_detail<'a>(
                             .fg(styles::MUTED())
                             .add_modifier(Modifier::CROSSED_OUT)
                             .bg(bg)
+                    } else if suppressed {
+                        Style::default().fg(styles::MUTED()).bg(bg)
                     } else if file_stale {
                         styles::stale_style().bg(bg)
                     } else {
@@ -315,6 +794,8 @@ fn render_file_detail<'a>(
                     // Resolved findings always show green ✓, overriding the confidence badge.
                     let (conf_glyph, conf_style) = if finding.resolved {
                         ("✓ ", Style::default().fg(styles::GREEN()).bg(bg))
+                    } else if suppressed {
+                        ("⊘ ", Style::default().fg(styles::MUTED()).bg(bg))
                     } else {
                         match finding.confidence {
                             Confidence::Confirmed => {
@@ -357,6 +838,13 @@ fn render_file_detail<'a>(
                         }
                     }
 
+                    if tab.is_addressed(&finding.id) {
+                        lines.push(Line::from(vec![Span::styled(
+                            "   \u{21bb} addressed — needs re-check",
+                            Style::default().fg(styles::YELLOW()).bg(bg),
+                        )]));
+                    }
+
                     // Location line for inline findings (hunk/line-anchored)
                     if finding.hunk_index.is_some() || finding.line_start.is_some() {
                         // Validate that the referenced hunk/line actually exists in the parsed diff.
@@ -398,6 +886,13 @@ fn render_file_detail<'a>(
                         lines.push(Line::from(vec![Span::styled(loc, loc_style)]));
                     }
 
+                    if suppressed && !finding.resolved {
+                        lines.push(Line::from(vec![Span::styled(
+                            "   suppressed by an er:ignore note",
+                            Style::default().fg(styles::MUTED()).bg(bg),
+                        )]));
+                    }
+
                     if finding.resolved && !finding.resolved_note.is_empty() {
                         let note_style = Style::default().fg(styles::GREEN()).bg(bg);
                         for wrapped in word_wrap(&finding.resolved_note, max_w) {
@@ -502,7 +997,9 @@ fn render_file_detail<'a>(
             Span::styled(format!(" {} ", bullet), Style::default().fg(accent)),
             Span::styled(
                 author.to_string(),
-                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(comment.teammate().map_or(accent, styles::reviewer_color))
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::styled(format!("  {}", target), Style::default().fg(styles::DIM())),
         ];
@@ -557,7 +1054,9 @@ fn render_file_detail<'a>(
                 Span::styled(
                     reply_author.to_string(),
                     Style::default()
-                        .fg(styles::CYAN())
+                        .fg(reply
+                            .teammate()
+                            .map_or(styles::CYAN(), styles::reviewer_color))
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -667,25 +1166,21 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
     } else {
         Style::default().fg(styles::BORDER())
     };
-    lines.push(Line::from(vec![Span::styled(
+    let mut files_header = vec![Span::styled(
         " ─── File Risk Overview ───",
         files_header_style,
-    )]));
+    )];
+    if tab.review_sort != er_engine::ai::ReviewSort::Risk {
+        files_header.push(Span::styled(
+            format!("  sorted by {}", tab.review_sort.label()),
+            Style::default().fg(styles::MUTED()),
+        ));
+    }
+    lines.push(Line::from(files_header));
     lines.push(Line::from(""));
 
-    if let Some(ref review) = tab.ai.review {
-        let mut file_entries: Vec<_> = review.files.iter().collect();
-        file_entries.sort_by(|a, b| {
-            let risk_ord = |r: &RiskLevel| match r {
-                RiskLevel::High => 0,
-                RiskLevel::Medium => 1,
-                RiskLevel::Low => 2,
-                RiskLevel::Info => 3,
-            };
-            risk_ord(&a.1.risk)
-                .cmp(&risk_ord(&b.1.risk))
-                .then_with(|| a.0.cmp(b.0))
-        });
+    if tab.ai.review.is_some() {
+        let file_entries = tab.ai.review_file_entries(tab.review_sort);
 
         let cursor = tab.review_cursor;
         for (idx, (path, fr)) in file_entries.iter().enumerate() {
@@ -726,20 +1221,23 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
 
             let prefix = if is_selected { "▸" } else { " " };
 
-            let max_path_w = area.width.saturating_sub(5) as usize;
+            let max_path_w = area.width.saturating_sub(5 + SEVERITY_BAR_WIDTH as u16 + 1) as usize;
             let display_path = shorten_path(path, max_path_w);
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("{prefix} "), Style::default()),
                 Span::styled(
-                    format!("{risk_label} "),
+                    format!("{risk_label:<4} "),
                     if is_selected {
                         risk_style.bg(bg)
                     } else {
                         risk_style
                     },
                 ),
-                Span::styled(display_path, path_style),
-            ]));
+            ];
+            spans.extend(severity_bar(fr.severity_counts()));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(display_path, path_style));
+            lines.push(Line::from(spans));
         }
 
         let total = tab.ai.total_findings();
@@ -759,6 +1257,10 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
 
     lines.push(Line::from(""));
 
+    if tab.ai.review.is_some() {
+        render_finding_categories(lines, area, tab);
+    }
+
     // Checklist
     let is_checklist_focused = tab.review_focus == ReviewFocus::Checklist;
     let checklist_header_style = if is_checklist_focused {
@@ -770,10 +1272,17 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
             .fg(styles::CYAN())
             .add_modifier(Modifier::BOLD)
     };
-    lines.push(Line::from(vec![Span::styled(
+    let mut checklist_header = vec![Span::styled(
         " ─── Review Checklist ───",
         checklist_header_style,
-    )]));
+    )];
+    if is_checklist_focused {
+        checklist_header.push(Span::styled(
+            "  a add · e edit · x del · J/K move · f file · t templates",
+            Style::default().fg(styles::MUTED()),
+        ));
+    }
+    lines.push(Line::from(checklist_header));
     lines.push(Line::from(""));
 
     if let Some(ref checklist) = tab.ai.checklist {
@@ -833,7 +1342,7 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
         }
     } else {
         lines.push(Line::from(vec![Span::styled(
-            " No .er/checklist.json found",
+            " No checklist yet — a to add an item, t for templates",
             Style::default().fg(styles::MUTED()),
         )]));
     }
@@ -841,13 +1350,228 @@ fn render_ai_summary<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engi
 
 // ── PrOverview ──
 
-fn render_pr_overview<'a>(
+/// Cells in a file row's severity bar
+const SEVERITY_BAR_WIDTH: usize = 6;
+
+/// One block per active finding, most severe first, capped at
+/// [`SEVERITY_BAR_WIDTH`] (the last cell becomes `+` when there are more).
+fn severity_bar(counts: er_engine::ai::SeverityCounts) -> Vec<Span<'static>> {
+    let levels = [
+        (counts.high, styles::risk_high()),
+        (counts.medium, styles::risk_medium()),
+        (counts.low, styles::risk_low()),
+        (counts.info, Style::default().fg(styles::BLUE())),
+    ];
+    let overflow = counts.total() > SEVERITY_BAR_WIDTH;
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for (n, style) in levels {
+        let take = n.min(SEVERITY_BAR_WIDTH - used);
+        if take == 0 {
+            continue;
+        }
+        used += take;
+        let cells = if overflow && used == SEVERITY_BAR_WIDTH {
+            format!("{}+", "■".repeat(take - 1))
+        } else {
+            "■".repeat(take)
+        };
+        spans.push(Span::styled(cells, style));
+    }
+    spans.push(Span::raw(" ".repeat(SEVERITY_BAR_WIDTH - used)));
+    spans
+}
+
+/// Active findings grouped by category, with foldable headers.
+fn render_finding_categories<'a>(
     lines: &mut Vec<Line<'a>>,
     area: Rect,
     tab: &'a er_engine::app::TabState,
 ) {
-    lines.push(Line::from(vec![Span::styled(
-        " PR Overview",
+    use er_engine::ai::CategoryRow;
+
+    let is_focused = tab.review_focus == ReviewFocus::Categories;
+    let header_style = if is_focused {
+        Style::default()
+            .fg(styles::BRIGHT())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(styles::BORDER())
+    };
+    let mut header = vec![Span::styled(" ─── Findings by Category ───", header_style)];
+    if is_focused {
+        header.push(Span::styled(
+            "  Enter/␣ fold",
+            Style::default().fg(styles::MUTED()),
+        ));
+    }
+    lines.push(Line::from(header));
+    lines.push(Line::from(""));
+
+    let rows = tab.review_category_rows();
+    if rows.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " No open findings",
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    for (idx, row) in rows.iter().enumerate() {
+        let is_selected = is_focused && idx == tab.review_cursor;
+        let prefix = if is_selected { "▸" } else { " " };
+        let bg = if is_selected {
+            styles::LINE_CURSOR_BG()
+        } else {
+            styles::BG()
+        };
+        match row {
+            CategoryRow::Header {
+                category,
+                counts,
+                collapsed,
+            } => {
+                let fold = if *collapsed { "▶" } else { "▼" };
+                let mut spans = vec![
+                    Span::styled(format!("{prefix} "), Style::default()),
+                    Span::styled(
+                        format!("{fold} {category:<12}"),
+                        Style::default()
+                            .fg(styles::BRIGHT())
+                            .bg(bg)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:>3} ", counts.total()),
+                        Style::default().fg(styles::MUTED()),
+                    ),
+                ];
+                spans.extend(severity_bar(*counts));
+                lines.push(Line::from(spans));
+            }
+            CategoryRow::Finding { path, finding_id } => {
+                let Some(finding) = tab
+                    .ai
+                    .file_review(path)
+                    .and_then(|fr| fr.findings.iter().find(|f| &f.id == finding_id))
+                else {
+                    continue;
+                };
+                let (label, style) = match finding.severity {
+                    RiskLevel::High => ("HIGH", styles::risk_high()),
+                    RiskLevel::Medium => ("MED", styles::risk_medium()),
+                    RiskLevel::Low => ("LOW", styles::risk_low()),
+                    RiskLevel::Info => ("INFO", Style::default().fg(styles::BLUE())),
+                };
+                let location = match finding.line_start {
+                    Some(line) => format!("{}:{}", path, line),
+                    None => path.clone(),
+                };
+                let text_w = area.width.saturating_sub(11) as usize;
+                let location = shorten_path(&location, text_w / 2);
+                let title_w = text_w.saturating_sub(location.chars().count() + 2);
+                let title: String = finding.title.chars().take(title_w).collect();
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{prefix}   "), Style::default()),
+                    Span::styled(format!("{label:<4} "), style.bg(bg)),
+                    Span::styled(location, Style::default().fg(styles::MUTED()).bg(bg)),
+                    Span::styled(
+                        format!("  {}", title),
+                        Style::default().fg(styles::TEXT()).bg(bg),
+                    ),
+                ]));
+            }
+        }
+    }
+    lines.push(Line::from(""));
+}
+
+/// A tag range's release: name, tag and date, link, and the notes.
+fn render_release_notes<'a>(
+    lines: &mut Vec<Line<'a>>,
+    area: Rect,
+    notes: &'a er_engine::github::ReleaseNotes,
+) {
+    lines.push(Line::from(vec![Span::styled(
+        " Release",
+        Style::default()
+            .fg(styles::PURPLE())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.push(Line::from(""));
+
+    let max_w = area.width.saturating_sub(3) as usize;
+    let published = notes
+        .published_at
+        .as_deref()
+        .map(|d| d.get(..10).unwrap_or(d))
+        .unwrap_or("draft");
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" {} ", notes.tag_name),
+            Style::default()
+                .fg(styles::CYAN())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(published.to_string(), Style::default().fg(styles::DIM())),
+    ]));
+    if !notes.url.is_empty() {
+        for wrapped in word_wrap(&notes.url, max_w) {
+            lines.push(Line::from(vec![Span::styled(
+                format!(" {}", wrapped),
+                Style::default()
+                    .fg(styles::DIM())
+                    .add_modifier(Modifier::UNDERLINED),
+            )]));
+        }
+    }
+    lines.push(Line::from(""));
+    if !notes.name.is_empty() {
+        for wrapped in word_wrap(&notes.name, max_w) {
+            lines.push(Line::from(vec![Span::styled(
+                format!(" {}", wrapped),
+                Style::default()
+                    .fg(styles::BRIGHT())
+                    .add_modifier(Modifier::BOLD),
+            )]));
+        }
+        lines.push(Line::from(""));
+    }
+    if notes.body.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " No release notes",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        return;
+    }
+    lines.push(Line::from(vec![Span::styled(
+        " ─── Release notes ───",
+        Style::default().fg(styles::BORDER()),
+    )]));
+    lines.push(Line::from(""));
+    for line in notes.body.lines() {
+        if line.is_empty() {
+            lines.push(Line::from(""));
+        } else {
+            for wrapped in word_wrap(line, max_w) {
+                lines.push(Line::from(vec![Span::styled(
+                    format!(" {}", wrapped),
+                    Style::default().fg(styles::TEXT()),
+                )]));
+            }
+        }
+    }
+}
+
+fn render_pr_overview<'a>(
+    lines: &mut Vec<Line<'a>>,
+    area: Rect,
+    tab: &'a er_engine::app::TabState,
+) {
+    if let (None, Some(notes)) = (&tab.pr_data, tab.release_notes()) {
+        render_release_notes(lines, area, notes);
+        return;
+    }
+    lines.push(Line::from(vec![Span::styled(
+        " PR Overview",
         Style::default()
             .fg(styles::PURPLE())
             .add_modifier(Modifier::BOLD),
@@ -864,6 +1588,8 @@ fn render_pr_overview<'a>(
             " Open a branch with an active PR",
             Style::default().fg(styles::MUTED()),
         )]));
+        lines.push(Line::from(""));
+        render_review_notes(lines, area, tab);
         return;
     };
 
@@ -897,7 +1623,7 @@ fn render_pr_overview<'a>(
         // Short display that fits narrow panels: "owner/repo#N"
         let display = pr
             .url
-            .strip_prefix("https://github.com/")
+            .strip_prefix(&er_engine::github::web_url(""))
             .unwrap_or(&pr.url)
             .replace("/pull/", "#");
         let url_style = Style::default()
@@ -1045,6 +1771,8 @@ fn render_pr_overview<'a>(
         lines.push(Line::from(""));
     }
 
+    render_review_notes(lines, area, tab);
+
     // General PR comments (those with empty file field)
     let general_comments: Vec<_> = tab
         .ai
@@ -1090,6 +1818,406 @@ fn render_pr_overview<'a>(
     }
 }
 
+/// Review-wide notes and questions, sent in the next review's body
+fn render_review_notes<'a>(
+    lines: &mut Vec<Line<'a>>,
+    area: Rect,
+    tab: &'a er_engine::app::TabState,
+) {
+    let max_w = area.width.saturating_sub(3) as usize;
+    lines.push(Line::from(vec![
+        Span::styled(
+            " ─── Review notes ───",
+            Style::default().fg(styles::BORDER()),
+        ),
+        Span::styled("  (a to add)", Style::default().fg(styles::MUTED())),
+    ]));
+    lines.push(Line::from(""));
+    let notes = tab.ai.review_notes();
+    if notes.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " Overall impressions and questions for the author",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        lines.push(Line::from(""));
+        return;
+    }
+    for note in &notes {
+        let (label, color) = match note.comment_type() {
+            CommentType::Question => ("question", styles::YELLOW()),
+            _ => ("note", styles::CYAN()),
+        };
+        let sent = matches!(note, CommentRef::Question(q) | CommentRef::Note(q) if q.promoted_to.is_some());
+        let mut header = vec![Span::styled(
+            format!(" {}", label),
+            Style::default().fg(color),
+        )];
+        if sent {
+            header.push(Span::styled("  sent", Style::default().fg(styles::MUTED())));
+        } else if note.is_resolved() {
+            header.push(Span::styled(
+                "  resolved",
+                Style::default().fg(styles::MUTED()),
+            ));
+        }
+        lines.push(Line::from(header));
+        for wrapped in word_wrap(note.text(), max_w.saturating_sub(2)) {
+            lines.push(Line::from(vec![Span::styled(
+                format!("   {}", wrapped),
+                Style::default().fg(styles::TEXT()),
+            )]));
+        }
+        lines.push(Line::from(""));
+    }
+}
+
+// ── Operational ──
+
+fn render_operational<'a>(
+    lines: &mut Vec<Line<'a>>,
+    area: Rect,
+    tab: &'a er_engine::app::TabState,
+) {
+    use er_engine::git::OpsChangeKind;
+
+    if tab.operational.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " No config, env var or feature-flag changes",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        return;
+    }
+    let (added, removed, changed) = tab.operational_counts();
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            " {} added · {} removed · {} changed",
+            added, removed, changed
+        ),
+        Style::default().fg(styles::DIM()),
+    )]));
+    lines.push(Line::from(""));
+
+    let max_w = area.width.saturating_sub(4) as usize;
+    let mut kind = None;
+    for change in &tab.operational {
+        if kind != Some(change.kind) {
+            if kind.is_some() {
+                lines.push(Line::from(""));
+            }
+            kind = Some(change.kind);
+            let count = tab
+                .operational
+                .iter()
+                .filter(|c| c.kind == change.kind)
+                .count();
+            lines.push(Line::from(vec![Span::styled(
+                format!(" {} ({})", change.kind.label(), count),
+                Style::default()
+                    .fg(styles::CYAN())
+                    .add_modifier(Modifier::BOLD),
+            )]));
+        }
+        let color = match change.change {
+            OpsChangeKind::Added => styles::GREEN(),
+            OpsChangeKind::Removed => styles::RED_TEXT(),
+            OpsChangeKind::Changed => styles::YELLOW(),
+        };
+        let location = match change.line {
+            Some(line) => format!("  {}:{}", shorten_path(&change.path, max_w / 2), line),
+            None => format!("  {}", shorten_path(&change.path, max_w / 2)),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", change.change.symbol()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&*change.name, Style::default().fg(styles::TEXT())),
+            Span::styled(location, Style::default().fg(styles::BLUE())),
+        ]));
+        let text = if change.text.chars().count() > max_w {
+            format!(
+                "{}…",
+                change
+                    .text
+                    .chars()
+                    .take(max_w.saturating_sub(1))
+                    .collect::<String>()
+            )
+        } else {
+            change.text.clone()
+        };
+        lines.push(Line::from(vec![Span::styled(
+            format!("   {}", text),
+            Style::default().fg(styles::DIM()),
+        )]));
+    }
+}
+
+// ── Assets ──
+
+/// `1.2 KB → 3.4 KB (+2.2 KB)`, or the one side there is.
+fn asset_sizes(asset: &er_engine::app::AssetChange) -> String {
+    use er_engine::git::format_size;
+
+    match (&asset.old, &asset.new) {
+        (Some(old), Some(new)) => {
+            let delta = asset.size_delta();
+            let sign = if delta < 0 { "−" } else { "+" };
+            format!(
+                "{} → {} ({}{})",
+                format_size(old.bytes),
+                format_size(new.bytes),
+                sign,
+                format_size(delta.unsigned_abs())
+            )
+        }
+        (Some(side), None) | (None, Some(side)) => format_size(side.bytes),
+        (None, None) => "size unknown".to_string(),
+    }
+}
+
+/// `64×64 → 128×128`, or the one side there is.
+fn asset_dimensions(asset: &er_engine::app::AssetChange) -> Option<String> {
+    let dims = |side: &Option<er_engine::app::AssetSide>| {
+        side.as_ref()
+            .and_then(|s| s.dimensions)
+            .map(|(w, h)| format!("{}×{}", w, h))
+    };
+    match (dims(&asset.old), dims(&asset.new)) {
+        (Some(old), Some(new)) if old != new => Some(format!("{} → {}", old, new)),
+        (_, Some(side)) | (Some(side), None) => Some(side),
+        (None, None) => None,
+    }
+}
+
+/// A preview as half blocks: each cell the pixel above in the foreground
+/// and the one below in the background, transparent ones left as the panel.
+fn preview_lines(preview: &er_engine::git::ImagePreview) -> Vec<Line<'static>> {
+    let color = |pixel: Option<[u8; 4]>| match pixel {
+        Some([r, g, b, a]) if a >= 128 => Color::Rgb(r, g, b),
+        _ => styles::SURFACE(),
+    };
+    (0..preview.height.div_ceil(2))
+        .map(|row| {
+            let mut spans = vec![Span::raw("   ")];
+            spans.extend((0..preview.width).map(|x| {
+                Span::styled(
+                    "▀",
+                    Style::default()
+                        .fg(color(preview.pixel(x, row * 2)))
+                        .bg(color(preview.pixel(x, row * 2 + 1))),
+                )
+            }));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn render_assets<'a>(lines: &mut Vec<Line<'a>>, area: Rect, tab: &'a er_engine::app::TabState) {
+    use er_engine::git::FileStatus;
+
+    if tab.assets.assets.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " No image assets in this diff",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        return;
+    }
+    let (total, reviewed) = tab.asset_counts();
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" {} images · {} reviewed", total, reviewed),
+            Style::default().fg(styles::DIM()),
+        ),
+        Span::styled("  a", styles::key_hint_style()),
+        Span::styled(" mark all reviewed", Style::default().fg(styles::DIM())),
+    ]));
+    lines.push(Line::from(""));
+
+    let max_w = area.width.saturating_sub(6) as usize;
+    for asset in &tab.assets.assets {
+        let (symbol, color) = match asset.status {
+            FileStatus::Added => ("+", styles::GREEN()),
+            FileStatus::Deleted => ("-", styles::RED_TEXT()),
+            _ => ("~", styles::YELLOW()),
+        };
+        let done = !tab.needs_review(&asset.path);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", symbol),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                shorten_path(&asset.path, max_w),
+                Style::default().fg(if done { styles::DIM() } else { styles::TEXT() }),
+            ),
+            Span::styled(
+                if done { " ✓" } else { "" },
+                Style::default().fg(styles::GREEN()),
+            ),
+        ]));
+        let mut detail = vec![Span::styled(
+            format!("   {}", asset_sizes(asset)),
+            Style::default().fg(styles::DIM()),
+        )];
+        if let Some(dims) = asset_dimensions(asset) {
+            let style = if asset.resized() {
+                Style::default().fg(styles::YELLOW())
+            } else {
+                Style::default().fg(styles::DIM())
+            };
+            detail.push(Span::styled(format!("  {}", dims), style));
+        }
+        lines.push(Line::from(detail));
+        if let Some(preview) = &asset.preview {
+            lines.extend(preview_lines(preview));
+        }
+        lines.push(Line::from(""));
+    }
+}
+
+// ── RelatedChanges ──
+
+/// Open and merged PRs that changed the selected file, then its recent
+/// commits on the base branch, the ones newer than the fork marked.
+fn render_related_changes<'a>(
+    lines: &mut Vec<Line<'a>>,
+    area: Rect,
+    tab: &'a er_engine::app::TabState,
+) {
+    let max_w = area.width.saturating_sub(4) as usize;
+    let Some(file) = tab.selected_diff_file() else {
+        lines.push(Line::from(vec![Span::styled(
+            " No file selected",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        return;
+    };
+    lines.push(Line::from(vec![Span::styled(
+        format!(" {}", shorten_path(&file.path, max_w)),
+        Style::default()
+            .fg(styles::BRIGHT())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.push(Line::from(""));
+    let Some(related) = tab.selected_related_changes() else {
+        lines.push(Line::from(vec![Span::styled(
+            " Looking up recent PRs and commits…",
+            Style::default().fg(styles::MUTED()),
+        )]));
+        return;
+    };
+
+    let open = related.open_prs();
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            " ─── PRs · {} open · {} merged ───",
+            open,
+            related.prs.len() - open
+        ),
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    if let Some(error) = &related.forge_error {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                " couldn't search PRs: {}",
+                truncate_to_width(error, max_w.saturating_sub(22))
+            ),
+            Style::default().fg(styles::MUTED()),
+        )]));
+    } else if related.prs.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " no other recent PRs changed this file",
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    for pr in &related.prs {
+        let in_flight = pr.state == "open";
+        let (icon, color) = if in_flight {
+            ("●", styles::YELLOW())
+        } else {
+            ("✓", styles::PURPLE())
+        };
+        let prefix = format!(" {} #{} ", icon, pr.number);
+        lines.push(Line::from(vec![
+            Span::styled(
+                prefix.clone(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                truncate_to_width(&pr.title, max_w.saturating_sub(prefix.chars().count())),
+                Style::default().fg(styles::TEXT()),
+            ),
+        ]));
+        let detail = if in_flight {
+            format!("   in flight · {} · updated {}", pr.author, pr.date)
+        } else {
+            format!("   merged {} · {}", pr.date, pr.author)
+        };
+        lines.push(Line::from(vec![Span::styled(
+            detail,
+            Style::default().fg(styles::DIM()),
+        )]));
+    }
+    lines.push(Line::from(""));
+
+    let since_fork = related.commits_since_fork();
+    let mut title = format!(
+        " ─── {} · {} commits",
+        tab.base_branch,
+        related.commits.len()
+    );
+    if since_fork > 0 {
+        title.push_str(&format!(" · {} since fork", since_fork));
+    }
+    title.push_str(" ───");
+    lines.push(Line::from(vec![Span::styled(
+        title,
+        Style::default()
+            .fg(styles::CYAN())
+            .add_modifier(Modifier::BOLD),
+    )]));
+    if related.commits.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " no commits to this file on the base branch",
+            Style::default().fg(styles::MUTED()),
+        )]));
+    }
+    for commit in &related.commits {
+        let (hash_color, subject_color) = if commit.since_fork {
+            (styles::YELLOW(), styles::TEXT())
+        } else {
+            (styles::DIM(), styles::DIM())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", commit.hash),
+                Style::default().fg(hash_color),
+            ),
+            Span::styled(
+                truncate_to_width(
+                    &commit.subject,
+                    max_w.saturating_sub(commit.hash.chars().count() + 2),
+                ),
+                Style::default().fg(subject_color),
+            ),
+        ]));
+        let mut detail = vec![Span::styled(
+            format!("   {} · {}", commit.date, commit.author),
+            Style::default().fg(styles::MUTED()),
+        )];
+        if commit.since_fork {
+            detail.push(Span::styled(
+                " · new since this branch forked",
+                Style::default().fg(styles::YELLOW()),
+            ));
+        }
+        lines.push(Line::from(detail));
+    }
+}
+
 // ── SymbolRefs ──
 
 fn render_symbol_refs<'a>(
@@ -1133,7 +2261,8 @@ fn render_symbol_refs<'a>(
         )]));
 
         for entry in &state.in_diff {
-            let is_selected = entry_idx == state.cursor && tab.panel_focus;
+            let is_selected =
+                entry_idx == state.cursor && tab.panel_has_focus(PanelContent::SymbolRefs);
             let loc = format!(" {}:{}", entry.file, entry.line_num);
             let content = entry.line_content.trim();
             let truncated = if content.chars().count() > max_w {
@@ -1183,7 +2312,8 @@ fn render_symbol_refs<'a>(
         )]));
 
         for entry in &state.external {
-            let is_selected = entry_idx == state.cursor && tab.panel_focus;
+            let is_selected =
+                entry_idx == state.cursor && tab.panel_has_focus(PanelContent::SymbolRefs);
             let loc = format!(" {}:{}", entry.file, entry.line_num);
             let content = entry.line_content.trim();
             let truncated = if content.chars().count() > max_w {
@@ -0,0 +1,132 @@
+//! Snapshot tests: the whole UI drawn on a `TestBackend` for fixture app
+//! states ([`er_engine::app::fixtures`]), compared as text with the files in
+//! `crates/er-tui/snapshots/`. Run with `ER_UPDATE_SNAPSHOTS=1` to write
+//! them after an intended rendering change, then review the diff.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use er_engine::ai::PanelContent;
+use er_engine::app::{fixtures, App, HubKind};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use super::highlight::Highlighter;
+
+/// Draw `app` on a `width`×`height` terminal and return the screen as
+/// text, one line per row with trailing blanks trimmed.
+pub(crate) fn render(app: &App, width: u16, height: u16) -> String {
+    // Loading the syntax sets is most of a test's time; share one
+    static HIGHLIGHTER: OnceLock<Mutex<Highlighter>> = OnceLock::new();
+    let mut hl = HIGHLIGHTER
+        .get_or_init(|| Mutex::new(Highlighter::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend");
+    terminal
+        .draw(|f| super::draw(f, app, &mut hl))
+        .expect("draw");
+    super::screen_text(terminal.backend().buffer())
+}
+
+/// Compare `actual` with snapshot `name`, or write it under
+/// `ER_UPDATE_SNAPSHOTS=1`. A missing snapshot fails, so new ones get
+/// reviewed before they are committed.
+pub(crate) fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+    if std::env::var_os("ER_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().expect("snapshot dir")).expect("create snapshots");
+        std::fs::write(&path, actual).expect("write snapshot");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot {} — run with ER_UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    if expected != actual {
+        let first = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "snapshot {} differs from line {}:\n--- expected\n{}\n--- actual\n{}\n\
+             (run with ER_UPDATE_SNAPSHOTS=1 if the change is intended)",
+            name,
+            first + 1,
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn small_diff() {
+    let app = fixtures::app(fixtures::small_diff());
+    assert_snapshot("small_diff", &render(&app, 100, 30));
+}
+
+#[test]
+fn big_diff() {
+    let app = fixtures::app(fixtures::big_diff(40, 30));
+    assert_snapshot("big_diff", &render(&app, 120, 40));
+}
+
+#[test]
+fn sticky_file_and_hunk_headers() {
+    let mut app = fixtures::app(fixtures::big_diff(40, 30));
+    app.tab_mut().diff_scroll = 12;
+    assert_snapshot("sticky_headers", &render(&app, 120, 24));
+}
+
+#[test]
+fn file_detail_panel() {
+    let mut app = fixtures::app(fixtures::small_diff());
+    fixtures::open_panel(&mut app, PanelContent::FileDetail);
+    assert_snapshot("file_detail_panel", &render(&app, 140, 30));
+}
+
+#[test]
+fn pinned_panels_docked_right_and_bottom() {
+    let mut app = fixtures::app(fixtures::small_diff());
+    fixtures::open_panel(&mut app, PanelContent::FileDetail);
+    app.tab_mut().toggle_pin_panel();
+    app.tab_mut().panel = Some(PanelContent::AgentLog);
+    app.config
+        .layout
+        .panel_docks
+        .insert("log".into(), "bottom".into());
+    assert_snapshot("pinned_panels", &render(&app, 140, 30));
+}
+
+#[test]
+fn hub_overlay() {
+    let mut app = fixtures::app(fixtures::small_diff());
+    fixtures::open_hub(&mut app, HubKind::Verify, &["Run tests", "Run linter"]);
+    assert_snapshot("hub_overlay", &render(&app, 100, 30));
+}
+
+#[test]
+fn history_mode() {
+    let mut app = fixtures::app(fixtures::small_diff());
+    let commits = vec![
+        fixtures::commit(0, "Add the parser"),
+        fixtures::commit(1, "Fix the parser"),
+    ];
+    fixtures::history(
+        &mut app,
+        commits,
+        vec![fixtures::modified_file("src/parse.rs", 2)],
+    );
+    assert_snapshot("history_mode", &render(&app, 100, 30));
+}
+
+#[test]
+fn narrow_terminal() {
+    let app = fixtures::app(fixtures::small_diff());
+    assert_snapshot("narrow_terminal", &render(&app, 50, 20));
+}
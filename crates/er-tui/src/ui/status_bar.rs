@@ -7,7 +7,12 @@ use ratatui::{
 
 use super::styles;
 use er_engine::ai::PanelContent;
-use er_engine::app::{App, ConfirmAction, DiffMode, InputMode};
+use er_engine::app::hints::CONTEXT_HINT_LIMIT;
+use er_engine::app::{App, DiffMode, FileSort, InputMode, NotificationLevel};
+use er_engine::review_queue::SlaState;
+
+/// Width of the reviewed progress bar, in cells
+const PROGRESS_CELLS: usize = 8;
 
 /// Compute the display width of a list of spans
 fn spans_width(spans: &[Span]) -> usize {
@@ -122,6 +127,23 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ));
     }
+    if let Some(differs) = tab.pr_head_sync().and_then(|s| s.label()) {
+        info_spans.push(Span::styled(
+            format!(" {}", differs),
+            ratatui::style::Style::default().fg(styles::YELLOW()),
+        ));
+    }
+    if let Some(sla) = app.tab_sla(app.active_tab) {
+        let color = match sla.state {
+            SlaState::OnTrack => styles::GREEN(),
+            SlaState::DueSoon => styles::YELLOW(),
+            SlaState::Overdue => styles::RED(),
+        };
+        info_spans.push(Span::styled(
+            format!(" {}", sla.label()),
+            ratatui::style::Style::default().fg(color),
+        ));
+    }
     if tab.is_remote() {
         info_spans.push(Span::styled(
             " [remote]",
@@ -137,6 +159,30 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
             ratatui::style::Style::default().fg(styles::DIM()),
         ));
     }
+    // Monorepo packages the diff touches
+    let touched = tab.package_progress();
+    if !touched.is_empty() {
+        const SHOWN: usize = 3;
+        let mut names: Vec<&str> = touched
+            .iter()
+            .take(SHOWN)
+            .map(|p| p.name.as_str())
+            .collect();
+        let more = touched.len().saturating_sub(SHOWN);
+        let more_label = format!("+{}", more);
+        if more > 0 {
+            names.push(&more_label);
+        }
+        info_spans.push(Span::styled(
+            format!(
+                " · {} pkg{}: {}",
+                touched.len(),
+                if touched.len() == 1 { "" } else { "s" },
+                names.join(", ")
+            ),
+            ratatui::style::Style::default().fg(styles::PURPLE()),
+        ));
+    }
     if tab.mode == DiffMode::Conflicts && tab.merge_active {
         info_spans.push(Span::styled(
             " [merge in progress]",
@@ -183,10 +229,47 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
         modes.push(Span::styled(label, mode_style(vmode, tab.mode)));
         modes.push(Span::raw(" "));
     }
-    if tab.sort_by_mtime {
+    if tab.in_checkpoint_view() {
+        modes.push(Span::raw(" "));
+        modes.push(Span::styled(
+            if let Some(r) = tab.quick_diff_ref() {
+                format!(" VS {} ", r)
+            } else if tab.in_pr_head_view() {
+                " I VS PR HEAD ".to_string()
+            } else {
+                " I SINCE CHECKPOINT ".to_string()
+            },
+            ratatui::style::Style::default()
+                .fg(styles::BG())
+                .bg(styles::CYAN())
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+    }
+    if tab.focus_mode {
+        modes.push(Span::raw(" "));
+        modes.push(Span::styled(
+            " V FOCUS ",
+            ratatui::style::Style::default()
+                .fg(styles::BG())
+                .bg(styles::GREEN())
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+    }
+    let selected = tab.selection_paths().len();
+    if selected > 0 {
+        modes.push(Span::raw(" "));
+        modes.push(Span::styled(
+            format!(" {} SELECTED ", selected),
+            ratatui::style::Style::default()
+                .fg(styles::BG())
+                .bg(styles::PURPLE())
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+    }
+    if tab.file_sort != FileSort::Diff {
         modes.push(Span::raw(" "));
         modes.push(Span::styled(
-            " m RECENT ",
+            format!(" m {} ", tab.file_sort.label().to_uppercase()),
             ratatui::style::Style::default()
                 .fg(styles::BG())
                 .bg(styles::YELLOW())
@@ -247,6 +330,24 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
         }
         right.push(Span::raw("  "));
     }
+    // Code age legend, young to old
+    if tab.layers.show_code_age {
+        right.push(Span::styled(
+            "AGE ",
+            ratatui::style::Style::default()
+                .fg(styles::MUTED())
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+        for age in er_engine::app::CodeAge::ALL {
+            right.push(Span::styled(
+                format!(" {} ", age.label()),
+                ratatui::style::Style::default()
+                    .fg(styles::BG())
+                    .bg(styles::code_age_color(age)),
+            ));
+        }
+        right.push(Span::raw("  "));
+    }
     if app.config.ai_hub.has_presets() {
         right.push(Span::styled(
             format!(" {} ", app.active_ai_selection_label()),
@@ -261,8 +362,12 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
         let panel_label = match panel {
             PanelContent::FileDetail => " File Detail ",
             PanelContent::AiSummary => " AI Summary ",
+            PanelContent::PrOverview if tab.pr_data.is_none() => " Release ",
             PanelContent::PrOverview => " PR Overview ",
             PanelContent::SymbolRefs => " Symbol Refs ",
+            PanelContent::Operational => " Ops Changes ",
+            PanelContent::Assets => " Assets ",
+            PanelContent::RelatedChanges => " Related ",
             PanelContent::AgentLog => " Agent Log ",
         };
         let panel_style = if tab.panel_focus {
@@ -330,11 +435,59 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
             ratatui::style::Style::default().fg(styles::MUTED()),
         ));
     }
+    let untracked_todos = tab.untracked_todos();
+    if untracked_todos > 0 {
+        right.push(Span::styled(
+            format!(
+                "{} TODO{} w/o ticket",
+                untracked_todos,
+                if untracked_todos == 1 { "" } else { "s" }
+            ),
+            ratatui::style::Style::default().fg(styles::RED()),
+        ));
+        right.push(Span::styled(
+            " · ",
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        ));
+    }
     let (reviewed, total) = tab.reviewed_count();
     if total > 0 {
+        // Green once nothing is left: every file reviewed, every TODO ticketed
+        let accent = if tab.review_ready() {
+            styles::GREEN()
+        } else {
+            styles::BLUE()
+        };
+        right.push(Span::styled(
+            format!("{}/{} reviewed ", reviewed, total),
+            ratatui::style::Style::default().fg(accent),
+        ));
+        let progress = tab.review_progress();
+        let filled = (progress.fraction() * PROGRESS_CELLS as f64).round() as usize;
+        right.push(Span::styled(
+            "█".repeat(filled),
+            ratatui::style::Style::default().fg(accent),
+        ));
         right.push(Span::styled(
-            format!("{}/{} reviewed", reviewed, total),
-            ratatui::style::Style::default().fg(styles::BLUE()),
+            "░".repeat(PROGRESS_CELLS - filled),
+            ratatui::style::Style::default().fg(styles::MUTED()),
+        ));
+        if let Some(eta) = progress.eta_label() {
+            right.push(Span::styled(
+                format!(" {}", eta),
+                ratatui::style::Style::default().fg(styles::MUTED()),
+            ));
+        }
+    }
+    if app.read_only {
+        if !right.is_empty() {
+            right.push(Span::raw("  "));
+        }
+        right.push(Span::styled(
+            "READ-ONLY",
+            ratatui::style::Style::default()
+                .fg(styles::ORANGE())
+                .add_modifier(ratatui::style::Modifier::BOLD),
         ));
     }
     if app.watching {
@@ -348,6 +501,15 @@ pub fn render_top_bar(f: &mut Frame, area: Rect, app: &App) {
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ));
     }
+    if let Some(ref version) = app.update_check.available {
+        if !right.is_empty() {
+            right.push(Span::raw("  "));
+        }
+        right.push(Span::styled(
+            format!("\u{2191} er {}", version),
+            ratatui::style::Style::default().fg(styles::CYAN()),
+        ));
+    }
 
     // Memory budget indicator (debug mode)
     if std::env::var("ER_DEBUG").is_ok() {
@@ -404,6 +566,20 @@ impl Hint {
     }
 }
 
+/// Append the highest-weighted registry actions for the current state,
+/// skipping keys the caller already shows.
+fn push_context_hints(app: &App, hints: &mut Vec<Hint>) {
+    for action in app.contextual_hints(CONTEXT_HINT_LIMIT) {
+        push_unique(hints, action.key, &format!(" {} ", action.label));
+    }
+}
+
+fn push_unique(hints: &mut Vec<Hint>, key: &str, label: &str) {
+    if !hints.iter().any(|h| h.key == key) {
+        hints.push(Hint::new(key, label));
+    }
+}
+
 /// Build the hint list for when the AI Summary panel is focused
 fn build_ai_panel_hints(app: &App) -> Vec<Hint> {
     let tab = app.tab();
@@ -413,10 +589,17 @@ fn build_ai_panel_hints(app: &App) -> Vec<Hint> {
         Hint::new("␣", " toggle "),
         Hint::new("Enter", " jump "),
     ];
+    if tab.review_focus == er_engine::ai::ReviewFocus::Files {
+        hints.push(Hint::new(
+            "s",
+            &format!(" sort: {} ", tab.review_sort.label()),
+        ));
+    }
 
     // Show which column is focused
     let focus_label = match tab.review_focus {
         er_engine::ai::ReviewFocus::Files => " [Files] ",
+        er_engine::ai::ReviewFocus::Categories => " [Categories] ",
         er_engine::ai::ReviewFocus::Checklist => " [Checklist] ",
     };
     hints.push(Hint {
@@ -455,23 +638,13 @@ fn build_history_hints(app: &App) -> Vec<Hint> {
         hints.push(Hint::new("↑↓", " lines "));
         hints.push(Hint::new("/", " search "));
     }
-
-    // Context-sensitive: focused comment actions
-    if h.comments {
-        if let Some(ref fid) = tab.focused_comment_id {
-            if let Some(comment) = tab.ai.find_comment(fid) {
-                if comment.can_reply() {
-                    hints.push(Hint::new("r", " reply "));
-                }
-                if comment.can_delete() {
-                    hints.push(Hint::new("x", " delete "));
-                }
-            }
-        } else if tab.focused_finding_id.is_some() {
-            hints.push(Hint::new("r", " reply "));
-        }
+    if let Some(pr) = tab.selected_commit_pr() {
+        hints.push(Hint::new("⏎", &format!(" open #{} ", pr)));
     }
 
+    // Context-sensitive actions from the registry (focused comment)
+    push_context_hints(app, &mut hints);
+
     // Hub triggers
     hints.push(Hint::new("g", " git "));
     hints.push(Hint::new("a", " ai "));
@@ -480,7 +653,7 @@ fn build_history_hints(app: &App) -> Vec<Hint> {
 
     // Verbose hints for history mode
     if h.verbose {
-        hints.push(Hint::new("e", " edit "));
+        push_unique(&mut hints, "e", " edit ");
         hints.push(Hint::new("p", " panel "));
         hints.push(Hint::new("f", " filter "));
     }
@@ -521,10 +694,38 @@ fn build_history_hints(app: &App) -> Vec<Hint> {
 }
 
 /// Build the normal-mode hint list
+/// What the key after a count or `m` / `'` does.
+fn pending_key_hints(app: &App) -> Option<Vec<Hint>> {
+    let hints = match (app.pending_count, app.key_prefix) {
+        (_, Some('m')) => vec![
+            Hint::new("a–z", " set mark "),
+            Hint::new("m", " cycle sort "),
+            Hint::new("Esc", " cancel "),
+        ],
+        (_, Some(_)) => vec![
+            Hint::new("a–z", " jump to mark "),
+            Hint::new("Esc", " cancel "),
+        ],
+        (Some(count), None) => vec![
+            Hint::new(&count.to_string(), " × "),
+            Hint::new("n/N", " hunks "),
+            Hint::new("↓/↑", " lines "),
+            Hint::new("j/k", " files "),
+            Hint::new("J/K", " comments "),
+        ],
+        (None, None) => return None,
+    };
+    Some(hints)
+}
+
 fn build_hints(app: &App) -> Vec<Hint> {
     let tab = app.tab();
     let h = &app.config.hints;
 
+    if let Some(hints) = pending_key_hints(app) {
+        return hints;
+    }
+
     // Delegate to AI panel hints when focus is on the AI Summary panel
     if tab.panel_focus && tab.panel == Some(PanelContent::AiSummary) {
         return build_ai_panel_hints(app);
@@ -545,10 +746,26 @@ fn build_hints(app: &App) -> Vec<Hint> {
 
     let mut hints: Vec<Hint> = Vec::new();
 
+    // An active find: where the cursor is among its matches
+    if let Some((n, total)) = tab.find_position() {
+        let flags = tab.find.as_ref().map(|f| f.flags()).unwrap_or_default();
+        let label = if flags.is_empty() {
+            format!(" {}/{} matches ", n, total)
+        } else {
+            format!(" {}/{} matches ({}) ", n, total, flags)
+        };
+        hints.push(Hint::new("n/N", &label));
+    }
+
     if app.has_comment_draft() {
         hints.insert(0, Hint::new("Tab", " resume draft "));
     }
 
+    if tab.has_selection() {
+        hints.push(Hint::new("Alt+a", " act on selection "));
+        hints.push(Hint::new("Esc", " clear selection "));
+    }
+
     if tab.panel.is_some() {
         // Context: panel open — show panel controls
         if h.navigation {
@@ -562,21 +779,8 @@ fn build_hints(app: &App) -> Vec<Hint> {
         }
         hints.push(Hint::new("p", " close panel "));
 
-        // Context-sensitive: focused comment actions (panel open)
-        if h.comments {
-            if let Some(ref fid) = tab.focused_comment_id {
-                if let Some(comment) = tab.ai.find_comment(fid) {
-                    if comment.can_reply() {
-                        hints.push(Hint::new("r", " reply "));
-                    }
-                    if comment.can_delete() {
-                        hints.push(Hint::new("x", " delete "));
-                    }
-                }
-            } else if tab.focused_finding_id.is_some() {
-                hints.push(Hint::new("r", " reply "));
-            }
-        }
+        // Context-sensitive actions from the registry (focused comment, mode)
+        push_context_hints(app, &mut hints);
 
         // Hub triggers — always shown
         hints.push(Hint::new("g", " git "));
@@ -589,12 +793,14 @@ fn build_hints(app: &App) -> Vec<Hint> {
         // Verbose hints for panel-open mode
         if h.verbose {
             if tab.mode != DiffMode::Staged {
-                hints.push(Hint::new("q", " question "));
-                hints.push(Hint::new("c", " comment "));
+                push_unique(&mut hints, "q", " question ");
+                push_unique(&mut hints, "c", " comment ");
             }
             hints.push(Hint::new("f", " filter "));
             hints.push(Hint::new("!", " unreviewed "));
             hints.push(Hint::new("</>", " tree w "));
+            hints.push(Hint::new("\\", " tree "));
+            hints.push(Hint::new("M", " zoom "));
             hints.push(Hint::new("{/}", " panel w "));
         }
     } else {
@@ -603,33 +809,11 @@ fn build_hints(app: &App) -> Vec<Hint> {
             hints.push(Hint::new("j/k", " nav "));
             hints.push(Hint::new("n/N", " hunks "));
             hints.push(Hint::new("+/-", " context "));
-            hints.push(Hint::new("␣", " review "));
             hints.push(Hint::new("/", " search "));
         }
 
-        // Staging: the most common workflow keys stay visible
-        if h.staging && (tab.mode == DiffMode::Unstaged || tab.mode == DiffMode::Staged) {
-            hints.push(Hint::new("s", " stage "));
-        }
-        if h.staging && tab.mode == DiffMode::Staged {
-            hints.push(Hint::new("c", " commit "));
-        }
-
-        // Context-sensitive: focused comment actions
-        if h.comments {
-            if let Some(ref fid) = tab.focused_comment_id {
-                if let Some(comment) = tab.ai.find_comment(fid) {
-                    if comment.can_reply() {
-                        hints.push(Hint::new("r", " reply "));
-                    }
-                    if comment.can_delete() {
-                        hints.push(Hint::new("x", " delete "));
-                    }
-                }
-            } else if tab.focused_finding_id.is_some() {
-                hints.push(Hint::new("r", " reply "));
-            }
-        }
+        // Context-sensitive actions from the registry (review, staging, comments)
+        push_context_hints(app, &mut hints);
 
         // Hub triggers — always shown
         hints.push(Hint::new("g", " git "));
@@ -642,27 +826,18 @@ fn build_hints(app: &App) -> Vec<Hint> {
         // Verbose hints — additional keybinds beyond hub triggers
         if h.verbose {
             if tab.mode != DiffMode::Staged {
-                hints.push(Hint::new("q", " question "));
-                hints.push(Hint::new("c", " comment "));
+                push_unique(&mut hints, "q", " question ");
+                push_unique(&mut hints, "c", " comment ");
             }
-            hints.push(Hint::new("e", " edit "));
+            push_unique(&mut hints, "e", " edit ");
             hints.push(Hint::new("p", " panel "));
             hints.push(Hint::new("f", " filter "));
             hints.push(Hint::new("!", " unreviewed "));
-            hints.push(Hint::new("U", " next unreviewed "));
-            hints.push(Hint::new("m", " recent "));
+            push_unique(&mut hints, "U", " next unreviewed ");
+            hints.push(Hint::new("mm", " sort "));
             if tab.ai.has_data() {
                 hints.push(Hint::new("A", " AI toggle "));
             }
-            // Show comment navigation hints when current file has comments
-            if let Some(file) = tab.files.get(tab.selected_file) {
-                let has_comments = tab.ai.file_question_count(&file.path) > 0
-                    || tab.ai.file_note_count(&file.path) > 0
-                    || tab.ai.file_github_comment_count(&file.path) > 0;
-                if has_comments {
-                    hints.push(Hint::new("J/K", " comments "));
-                }
-            }
             if tab.ai.has_questions() || tab.ai.has_notes() {
                 hints.push(Hint::new("z", " clear questions & notes "));
             }
@@ -754,10 +929,14 @@ pub fn bottom_bar_height(app: &App, width: u16) -> u16 {
     match &app.input_mode {
         InputMode::Comment => 5,
         InputMode::Search
+        | InputMode::Find
         | InputMode::Confirm(_)
         | InputMode::Filter
         | InputMode::Commit
-        | InputMode::RemoteUrl => 1,
+        | InputMode::RemoteUrl
+        | InputMode::SkipReason
+        | InputMode::Assignee
+        | InputMode::QuickDiffRef => 1,
         InputMode::Normal => {
             let hints = build_hints(app);
             let lines = pack_hint_lines(&hints, width as usize);
@@ -772,42 +951,27 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
     let panel_bg = ratatui::style::Style::default().bg(styles::PANEL());
 
     match &app.input_mode {
-        InputMode::Confirm(action) => {
-            let prompt = match action {
-                ConfirmAction::DeleteComment { .. } => "Delete comment? (y/n)".to_string(),
-                ConfirmAction::DeleteWatchedFile { ref path } => {
-                    format!("Delete {}? (y/n)", path)
-                }
-                ConfirmAction::Push => "Push branch to remote? (y/n)".to_string(),
-                ConfirmAction::CleanupQuestions { count } => {
-                    format!("Clear {} item(s) (questions & notes)? (y/n)", count)
-                }
-                ConfirmAction::CleanupReviews { count } => {
-                    format!("Clear {} review file(s)? (y/n)", count)
-                }
-                ConfirmAction::RunAgentReview { .. } => {
-                    "Clear previous review before running? (y=clear k=keep Esc=cancel)".to_string()
-                }
-                ConfirmAction::RunAgentQuestions { .. } => {
-                    "Clear previous AI answers before running? (y=clear k=keep Esc=cancel)"
-                        .to_string()
-                }
-                ConfirmAction::ApprovePR => "Approve this PR on GitHub? (y/n)".to_string(),
-                ConfirmAction::PushComments => {
-                    "Push as: (r) Review  (i) Individual  (Esc) Cancel".to_string()
-                }
+        InputMode::Confirm(_) => {
+            let confirm = app.current_confirm();
+            let (prompt, danger) = confirm
+                .map(|c| (format!("{} ({})", c.title, c.key_hint()), c.danger))
+                .unwrap_or_default();
+            let accent = if danger {
+                styles::RED()
+            } else {
+                styles::YELLOW()
             };
             let spans = vec![
                 Span::styled(
                     " ⚠ ",
                     ratatui::style::Style::default()
                         .fg(styles::BG())
-                        .bg(styles::YELLOW())
+                        .bg(accent)
                         .add_modifier(ratatui::style::Modifier::BOLD),
                 ),
                 Span::styled(
                     format!(" {} ", prompt),
-                    ratatui::style::Style::default().fg(styles::YELLOW()),
+                    ratatui::style::Style::default().fg(accent),
                 ),
             ];
             let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
@@ -820,7 +984,9 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
             let yellow_kind = is_question || is_note;
             let is_reply = tab.comment_reply_to.is_some();
             let is_finding_reply = tab.comment_finding_ref.is_some();
-            let (label, accent) = if is_reply {
+            let (label, accent) = if tab.checklist_edit.is_some() {
+                ("checklist", styles::GREEN())
+            } else if is_reply {
                 (
                     "reply",
                     if yellow_kind {
@@ -843,7 +1009,20 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
                 .rsplit('/')
                 .next()
                 .unwrap_or(&tab.comment_file);
-            let target_label = if let Some(ln) = tab.comment_line_num {
+            let target_label = if let Some(edit) = tab.checklist_edit {
+                match edit {
+                    er_engine::app::ChecklistEdit::Add { .. } => "new item".to_string(),
+                    er_engine::app::ChecklistEdit::Edit { index } => format!("item {}", index + 1),
+                }
+            } else if tab.comment_file.is_empty() {
+                if tab.comment_file_level {
+                    "review body".to_string()
+                } else {
+                    "PR".to_string()
+                }
+            } else if tab.comment_file_level {
+                format!("{} (whole file)", file_short)
+            } else if let Some(ln) = tab.comment_line_num {
                 format!("{}:L{}", file_short, ln)
             } else {
                 format!("{}:h{}", file_short, tab.comment_hunk + 1)
@@ -924,7 +1103,7 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
         InputMode::RemoteUrl => {
             let spans = vec![
                 Span::styled(
-                    " remote ",
+                    " open ",
                     ratatui::style::Style::default()
                         .fg(styles::BG())
                         .bg(styles::CYAN())
@@ -950,8 +1129,100 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
             let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
             f.render_widget(bar, area);
         }
-        InputMode::Search => {
+        InputMode::SkipReason => {
+            let spans = vec![
+                Span::styled(
+                    " skip ",
+                    ratatui::style::Style::default()
+                        .fg(styles::BG())
+                        .bg(styles::MUTED())
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {}", app.skip_reason_input),
+                    ratatui::style::Style::default().fg(styles::TEXT()),
+                ),
+                Span::styled("█", ratatui::style::Style::default().fg(styles::MUTED())),
+                Span::styled("  ", ratatui::style::Style::default()),
+                Span::styled("Enter", styles::key_hint_style()),
+                Span::styled(
+                    " skip with this reason  ",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled("Esc", styles::key_hint_style()),
+                Span::styled(
+                    " cancel",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+            ];
+            let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
+            f.render_widget(bar, area);
+        }
+        InputMode::Assignee => {
+            let spans = vec![
+                Span::styled(
+                    " assign ",
+                    ratatui::style::Style::default()
+                        .fg(styles::BG())
+                        .bg(styles::PURPLE())
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" @{}", app.assignee_input),
+                    ratatui::style::Style::default().fg(styles::TEXT()),
+                ),
+                Span::styled("█", ratatui::style::Style::default().fg(styles::MUTED())),
+                Span::styled("  ", ratatui::style::Style::default()),
+                Span::styled("Enter", styles::key_hint_style()),
+                Span::styled(
+                    format!(" assign {} file(s)  ", app.tab().selection_paths().len()),
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled("Esc", styles::key_hint_style()),
+                Span::styled(
+                    " cancel",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+            ];
+            let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
+            f.render_widget(bar, area);
+        }
+        InputMode::QuickDiffRef => {
+            let path = app
+                .tab()
+                .selected_diff_file()
+                .map(|f| f.path.clone())
+                .unwrap_or_default();
             let spans = vec![
+                Span::styled(
+                    " diff vs ",
+                    ratatui::style::Style::default()
+                        .fg(styles::BG())
+                        .bg(styles::CYAN())
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {}", app.quick_diff_input),
+                    ratatui::style::Style::default().fg(styles::TEXT()),
+                ),
+                Span::styled("█", ratatui::style::Style::default().fg(styles::MUTED())),
+                Span::styled("  ", ratatui::style::Style::default()),
+                Span::styled("Enter", styles::key_hint_style()),
+                Span::styled(
+                    format!(" diff {} against this tag, branch or commit  ", path),
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled("Esc", styles::key_hint_style()),
+                Span::styled(
+                    " cancel",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+            ];
+            let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
+            f.render_widget(bar, area);
+        }
+        InputMode::Search => {
+            let mut spans = vec![
                 Span::styled(" /", styles::key_hint_style()),
                 Span::styled(
                     format!(" {}", tab.search_query),
@@ -959,6 +1230,20 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
                 ),
                 Span::styled("█", ratatui::style::Style::default().fg(styles::BLUE())),
                 Span::styled("  ", ratatui::style::Style::default()),
+            ];
+            if tab.mode == DiffMode::History {
+                match tab.commit_query().and_then(|q| q.error) {
+                    Some(error) => spans.push(Span::styled(
+                        format!("⚠ {}  ", error),
+                        ratatui::style::Style::default().fg(styles::YELLOW()),
+                    )),
+                    None => spans.push(Span::styled(
+                        "author: path: before: after: /re/  ",
+                        ratatui::style::Style::default().fg(styles::DIM()),
+                    )),
+                }
+            }
+            spans.extend([
                 Span::styled("Enter", styles::key_hint_style()),
                 Span::styled(
                     " confirm  ",
@@ -969,14 +1254,69 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
                     " cancel",
                     ratatui::style::Style::default().fg(styles::DIM()),
                 ),
+            ]);
+            let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
+            f.render_widget(bar, area);
+        }
+        InputMode::Find => {
+            let find = tab.find.as_ref();
+            let query = find.map(|f| f.query.as_str()).unwrap_or("");
+            let mut spans = vec![
+                Span::styled(" find", styles::key_hint_style()),
+                Span::styled(
+                    format!(" {}", query),
+                    ratatui::style::Style::default().fg(styles::TEXT()),
+                ),
+                Span::styled("█", ratatui::style::Style::default().fg(styles::BLUE())),
+                Span::styled("  ", ratatui::style::Style::default()),
             ];
+            if let Some(error) = find.and_then(|f| f.error.as_ref()) {
+                spans.push(Span::styled(
+                    format!("⚠ {}  ", error),
+                    ratatui::style::Style::default().fg(styles::YELLOW()),
+                ));
+            } else if let Some((n, total)) = tab.find_position() {
+                spans.push(Span::styled(
+                    format!("{}/{}  ", n, total),
+                    ratatui::style::Style::default().fg(if total == 0 {
+                        styles::RED()
+                    } else {
+                        styles::TEXT()
+                    }),
+                ));
+            }
+            let flag = |on: bool| {
+                ratatui::style::Style::default().fg(if on { styles::BLUE() } else { styles::DIM() })
+            };
+            spans.extend([
+                Span::styled("Alt+r", styles::key_hint_style()),
+                Span::styled(" regex ", flag(find.is_some_and(|f| f.regex))),
+                Span::styled("Alt+c", styles::key_hint_style()),
+                Span::styled(" case  ", flag(find.is_some_and(|f| f.case_sensitive))),
+                Span::styled("Enter", styles::key_hint_style()),
+                Span::styled(
+                    " keep  ",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+                Span::styled("Esc", styles::key_hint_style()),
+                Span::styled(
+                    " cancel",
+                    ratatui::style::Style::default().fg(styles::DIM()),
+                ),
+            ]);
             let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
             f.render_widget(bar, area);
         }
         InputMode::Commit => {
-            let spans = vec![
+            // Naming a new bucket of the commit plan rather than committing
+            let planning = tab.split_pending.is_some();
+            let mut spans = vec![
                 Span::styled(
-                    " commit ",
+                    if planning {
+                        " plan commit "
+                    } else {
+                        " commit "
+                    },
                     ratatui::style::Style::default()
                         .fg(styles::BG())
                         .bg(styles::GREEN())
@@ -990,7 +1330,11 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
                 Span::styled("  ", ratatui::style::Style::default()),
                 Span::styled("Enter", styles::key_hint_style()),
                 Span::styled(
-                    " commit  ",
+                    if planning {
+                        " add to plan  "
+                    } else {
+                        " commit  "
+                    },
                     ratatui::style::Style::default().fg(styles::DIM()),
                 ),
                 Span::styled("Esc", styles::key_hint_style()),
@@ -999,6 +1343,13 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
                     ratatui::style::Style::default().fg(styles::DIM()),
                 ),
             ];
+            // What [commit_lint] thinks of the message so far
+            if let Some(issue) = app.commit_input_lint().first() {
+                spans.push(Span::styled(
+                    format!("  ⚠ {}", issue.message),
+                    ratatui::style::Style::default().fg(styles::YELLOW()),
+                ));
+            }
             let bar = Paragraph::new(Line::from(spans)).style(panel_bg);
             f.render_widget(bar, area);
         }
@@ -1028,8 +1379,43 @@ pub fn render_bottom_bar(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
+/// Accent color for a notification level (toast dot, log badge).
+pub(super) fn level_color(level: NotificationLevel) -> ratatui::style::Color {
+    match level {
+        NotificationLevel::Info => styles::CYAN(),
+        NotificationLevel::Success => styles::GREEN(),
+        NotificationLevel::Warning => styles::YELLOW(),
+        NotificationLevel::Error => styles::RED(),
+    }
+}
+
+/// `--profile` frame-time readout, bottom-right above the status bar.
+pub fn render_frame_stats(f: &mut Frame, area: Rect, stats: &er_engine::profile::FrameStats) {
+    let text = format!(" {} ", stats.summary());
+    let width = (text.chars().count() as u16).min(area.width);
+    let stats_area = Rect {
+        x: area.x + area.width.saturating_sub(width),
+        y: area.y + area.height.saturating_sub(2),
+        width,
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(text).style(
+            ratatui::style::Style::default()
+                .bg(styles::PANEL())
+                .fg(styles::MUTED()),
+        ),
+        stats_area,
+    );
+}
+
 /// Render watch notification overlay
-pub fn render_watch_notification(f: &mut Frame, area: Rect, message: &str) {
+pub fn render_watch_notification(
+    f: &mut Frame,
+    area: Rect,
+    message: &str,
+    level: NotificationLevel,
+) {
     let char_count = message.chars().count().min(u16::MAX as usize - 4);
     let notif_width = char_count as u16 + 4;
     let notif_x = area.x + area.width.saturating_sub(notif_width + 2);
@@ -1043,7 +1429,10 @@ pub fn render_watch_notification(f: &mut Frame, area: Rect, message: &str) {
     };
 
     let notif = Paragraph::new(Line::from(vec![
-        Span::styled(" ● ", ratatui::style::Style::default().fg(styles::GREEN())),
+        Span::styled(
+            " ● ",
+            ratatui::style::Style::default().fg(level_color(level)),
+        ),
         Span::styled(message, ratatui::style::Style::default().fg(styles::TEXT())),
         Span::raw(" "),
     ]))
@@ -72,6 +72,16 @@ pub fn ORANGE() -> Color {
     super::themes::current().orange
 }
 
+/// Stable per-reviewer colour for teammates' comments. Yellow and cyan are
+/// left out — they already mean "question" and "GitHub comment".
+pub fn reviewer_color(name: &str) -> Color {
+    let palette = [PURPLE(), GREEN(), BLUE(), ORANGE()];
+    let hash = name.bytes().fold(0x811c_9dc5u32, |h, b| {
+        (h ^ b as u32).wrapping_mul(0x0100_0193)
+    });
+    palette[hash as usize % palette.len()]
+}
+
 // ── Diff colors ──
 #[allow(non_snake_case)]
 pub fn ADD_BG() -> Color {
@@ -150,6 +160,18 @@ pub fn LOST_INDICATOR() -> Color {
     super::themes::current().lost_indicator
 }
 
+/// Tint for the code age layer, young to old.
+pub fn code_age_color(age: er_engine::app::CodeAge) -> Color {
+    use er_engine::app::CodeAge;
+    match age {
+        CodeAge::New => GREEN(),
+        CodeAge::Month => CYAN(),
+        CodeAge::Year => BLUE(),
+        CodeAge::FiveYears => YELLOW(),
+        CodeAge::Older => ORANGE(),
+    }
+}
+
 // ── Composed styles ──
 
 pub fn default_style() -> Style {
@@ -224,6 +246,16 @@ pub fn risk_low() -> Style {
     Style::default().fg(YELLOW())
 }
 
+/// Gutter of lines flashed after jumping to a finding
+pub fn line_flash() -> Style {
+    Style::default().fg(BG()).bg(YELLOW())
+}
+
+/// Text matching the find-in-file query
+pub fn find_match() -> Style {
+    Style::default().fg(BG()).bg(ORANGE())
+}
+
 /// Line cursor styles — brighter bg to show selected line
 pub fn line_cursor() -> Style {
     Style::default().fg(TEXT()).bg(LINE_CURSOR_BG())
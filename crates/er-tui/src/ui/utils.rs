@@ -4,9 +4,40 @@ pub(crate) fn horizontal_rule(width: usize) -> String {
     "\u{2500}".repeat(width.min(300))
 }
 
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Terminal columns `text` occupies (wide CJK/emoji count 2, combining marks 0).
+pub(crate) fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cut `text` to at most `max_width` columns, ending in `…` when shortened.
+/// Never splits a grapheme, so combining marks stay on their base character.
+pub(crate) fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut out = String::new();
+    let mut used = 0;
+    for g in text.graphemes(true) {
+        let w = display_width(g);
+        if used + w > budget {
+            break;
+        }
+        out.push_str(g);
+        used += w;
+    }
+    if max_width > 0 {
+        out.push('\u{2026}');
+    }
+    out
+}
+
 /// Simple word-wrap helper.
-/// Uses `chars().count()` for the width check so multi-byte UTF-8 strings
-/// are measured in characters, not bytes.
+/// Measures display columns (so CJK and emoji count double) and breaks
+/// over-long words between graphemes.
 /// Preserves leading whitespace on the first segment of each line.
 // A max_width of 0 disables wrapping: the input comes back as a single unsplit line.
 pub(crate) fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
@@ -22,38 +53,52 @@ pub(crate) fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
         // Capture leading whitespace to preserve indentation
         let indent_len = line.len() - line.trim_start().len();
         let indent = &line[..indent_len];
-        let indent_chars = indent.chars().count();
+        let indent_width = display_width(indent);
 
         let mut current = String::new();
+        let mut current_width = 0;
         let mut is_first_segment = true;
         for word in line.split_whitespace() {
-            let word_len = word.chars().count();
-            if word_len > max_width {
+            let word_width = display_width(word);
+            if word_width > max_width {
                 // Flush current line before breaking the long word
                 if !current.is_empty() {
-                    result.push(current);
-                    current = String::new();
+                    result.push(std::mem::take(&mut current));
+                }
+                // Break the word into max_width-column chunks
+                let mut chunk = String::new();
+                let mut chunk_width = 0;
+                for g in word.graphemes(true) {
+                    let w = display_width(g);
+                    if chunk_width + w > max_width && !chunk.is_empty() {
+                        result.push(std::mem::take(&mut chunk));
+                        chunk_width = 0;
+                    }
+                    chunk.push_str(g);
+                    chunk_width += w;
                 }
-                // Break the word into max_width chunks
-                let mut chars = word.chars().peekable();
-                while chars.peek().is_some() {
-                    let chunk: String = chars.by_ref().take(max_width).collect();
+                if !chunk.is_empty() {
                     result.push(chunk);
                 }
+                current_width = 0;
                 is_first_segment = false;
             } else if current.is_empty() {
-                if is_first_segment && indent_chars > 0 {
+                if is_first_segment && indent_width > 0 {
                     // Prepend original indentation on the first segment
                     current = format!("{}{}", indent, word);
+                    current_width = indent_width + word_width;
                 } else {
                     current = word.to_string();
+                    current_width = word_width;
                 }
-            } else if current.chars().count() + 1 + word_len <= max_width {
+            } else if current_width + 1 + word_width <= max_width {
                 current.push(' ');
                 current.push_str(word);
+                current_width += 1 + word_width;
             } else {
-                result.push(current);
+                result.push(std::mem::take(&mut current));
                 current = word.to_string();
+                current_width = word_width;
                 is_first_segment = false;
             }
         }
@@ -67,6 +112,63 @@ pub(crate) fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
     result
 }
 
+/// Soft-wrap one diff line to `max_width` columns without touching its
+/// content: runs of spaces survive, breaks prefer the last space, and
+/// continuation rows get a hanging indent (the line's own indentation plus
+/// two), so wrapped code still reads as one statement. Widths are display
+/// columns and breaks fall between graphemes.
+pub(crate) fn soft_wrap(text: &str, max_width: usize) -> Vec<String> {
+    if max_width == 0 || display_width(text) <= max_width {
+        return vec![text.to_string()];
+    }
+    let graphemes: Vec<(&str, usize)> = text
+        .graphemes(true)
+        .map(|g| (g, display_width(g)))
+        .collect();
+    let indent: usize = graphemes
+        .iter()
+        .take_while(|(g, _)| g.chars().all(char::is_whitespace))
+        .map(|(_, w)| *w)
+        .sum();
+    let hang = (indent + 2).min(max_width / 2);
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < graphemes.len() {
+        let (lead, width) = if rows.is_empty() {
+            (0, max_width)
+        } else {
+            (hang, max_width - hang)
+        };
+        // Widest run that fits; always take one grapheme so a wide
+        // character in a 1-column budget still makes progress
+        let mut end = start;
+        let mut used = 0;
+        while end < graphemes.len() && (end == start || used + graphemes[end].1 <= width) {
+            used += graphemes[end].1;
+            end += 1;
+        }
+        if end < graphemes.len() {
+            // Break after the last space, unless that leaves the row mostly empty
+            if let Some(space) = graphemes[start..end].iter().rposition(|(g, _)| *g == " ") {
+                let kept: usize = graphemes[start..=start + space]
+                    .iter()
+                    .map(|(_, w)| w)
+                    .sum();
+                if kept > width / 2 {
+                    end = start + space + 1;
+                }
+            }
+        }
+        let mut row = " ".repeat(lead);
+        for (g, _) in &graphemes[start..end] {
+            row.push_str(g);
+        }
+        rows.push(row);
+        start = end;
+    }
+    rows
+}
+
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
 /// Calculate a centered rectangle within an area.
@@ -126,6 +228,46 @@ mod tests {
         assert_eq!(result, vec![""]);
     }
 
+    #[test]
+    fn soft_wrap_keeps_spacing_and_hangs_continuations() {
+        assert_eq!(soft_wrap("let  x = 1;", 40), vec!["let  x = 1;"]);
+        let rows = soft_wrap("    call(alpha, beta, gamma)", 16);
+        assert_eq!(
+            rows,
+            vec!["    call(alpha, ", "      beta, ", "      gamma)"]
+        );
+        let joined: String = rows
+            .iter()
+            .enumerate()
+            .map(|(i, r)| if i == 0 { r.as_str() } else { &r[6..] })
+            .collect();
+        assert_eq!(joined, "    call(alpha, beta, gamma)");
+    }
+
+    #[test]
+    fn wide_characters_count_two_columns() {
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(word_wrap("日本語 テキスト", 8), vec!["日本語", "テキスト"]);
+        assert_eq!(
+            soft_wrap("日本語テキスト", 6),
+            vec!["日本語", "  テキ", "  スト"]
+        );
+    }
+
+    #[test]
+    fn truncate_respects_width_and_graphemes() {
+        assert_eq!(truncate_to_width("日本語テキスト", 7), "日本語\u{2026}");
+        assert_eq!(truncate_to_width("cafe\u{301}s", 5), "cafe\u{301}s");
+        assert_eq!(truncate_to_width("cafe\u{301}s!", 5), "cafe\u{301}\u{2026}");
+    }
+
+    #[test]
+    fn soft_wrap_hard_breaks_long_tokens() {
+        let rows = soft_wrap("abcdefghijkl", 5);
+        assert_eq!(rows, vec!["abcde", "  fgh", "  ijk", "  l"]);
+    }
+
     // ── centered_rect ──
 
     #[test]